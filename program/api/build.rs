@@ -0,0 +1,15 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Compiled with protox so building needs no `protoc` install
+    #[cfg(feature = "grpc")]
+    {
+        let proto = "proto/commerce/v1/payments.proto";
+        println!("cargo:rerun-if-changed={proto}");
+
+        let descriptors = protox::compile([proto], ["proto"])?;
+        tonic_build::configure()
+            .build_client(false)
+            .compile_fds(descriptors)?;
+    }
+
+    Ok(())
+}
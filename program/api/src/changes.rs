@@ -0,0 +1,64 @@
+//! Payment changes between successive reads of a merchant's payments, shared by the
+//! subscriptions that poll the source: gRPC `SubscribePayments` and webhook deliveries.
+
+use std::collections::HashMap;
+
+use solana_pubkey::Pubkey;
+
+use crate::records::{PaymentRecord, PaymentStatus};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Found by the first read.
+    Existing,
+    Created,
+    StatusChanged,
+}
+
+impl ChangeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangeKind::Existing => "existing",
+            ChangeKind::Created => "created",
+            ChangeKind::StatusChanged => "status_changed",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaymentChange<'a> {
+    pub kind: ChangeKind,
+    pub payment: &'a PaymentRecord,
+    /// Status before a `StatusChanged`.
+    pub previous_status: Option<PaymentStatus>,
+}
+
+/// Diffs `payments` against the statuses seen by earlier reads, recording the new ones.
+///
+/// Changes are returned oldest first. On the first read (`initial`) every payment is
+/// `Existing`; afterwards unseen payments are `Created`.
+pub fn payment_changes<'a>(
+    seen: &mut HashMap<Pubkey, PaymentStatus>,
+    payments: &'a [PaymentRecord],
+    initial: bool,
+) -> Vec<PaymentChange<'a>> {
+    payments
+        .iter()
+        .rev()
+        .filter_map(|payment| {
+            let (kind, previous_status) = match seen.insert(payment.address, payment.status) {
+                None if initial => (ChangeKind::Existing, None),
+                None => (ChangeKind::Created, None),
+                Some(previous) if previous != payment.status => {
+                    (ChangeKind::StatusChanged, Some(previous))
+                }
+                Some(_) => return None,
+            };
+            Some(PaymentChange {
+                kind,
+                payment,
+                previous_status,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,494 @@
+//! gRPC service over the same `PaymentSource` as the REST routes, for merchants with existing
+//! gRPC infrastructure. Built with the `grpc` feature from `proto/commerce/v1/payments.proto`.
+//!
+//! `SubscribePayments` polls the source and diffs each read against the previous one, so
+//! events arrive up to one poll interval after the transaction that caused them.
+
+use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
+
+use solana_pubkey::Pubkey;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+use tracing::Instrument;
+
+use crate::{
+    changes::{payment_changes, ChangeKind},
+    metrics::Metrics,
+    records::{PaymentRecord, PaymentStatus},
+    source::{PaymentSource, SourceError},
+};
+
+pub mod proto {
+    tonic::include_proto!("commerce.v1");
+}
+
+use proto::{
+    payments_server::{Payments, PaymentsServer},
+    GetPaymentRequest, PaymentEvent, PaymentEventKind, PaymentSnapshot, SubscribePaymentsRequest,
+};
+
+/// Events buffered per subscriber before polling waits for the client to catch up.
+const SUBSCRIBER_BUFFER: usize = 64;
+
+pub struct PaymentsService<S> {
+    source: Arc<S>,
+    metrics: Arc<Metrics>,
+    poll_interval: Duration,
+}
+
+impl<S: PaymentSource> PaymentsService<S> {
+    pub fn new(source: Arc<S>, metrics: Arc<Metrics>, poll_interval: Duration) -> Self {
+        Self {
+            source,
+            metrics,
+            poll_interval,
+        }
+    }
+
+    pub fn into_server(self) -> PaymentsServer<Self> {
+        PaymentsServer::new(self)
+    }
+}
+
+impl From<PaymentStatus> for proto::PaymentStatus {
+    fn from(status: PaymentStatus) -> Self {
+        match status {
+            PaymentStatus::Paid => Self::Paid,
+            PaymentStatus::Cleared => Self::Cleared,
+            PaymentStatus::Refunded => Self::Refunded,
+            PaymentStatus::RefundApproved => Self::RefundApproved,
+            PaymentStatus::Authorized => Self::Authorized,
+            PaymentStatus::Closed => Self::Closed,
+        }
+    }
+}
+
+impl From<ChangeKind> for PaymentEventKind {
+    fn from(kind: ChangeKind) -> Self {
+        match kind {
+            ChangeKind::Existing => Self::Existing,
+            ChangeKind::Created => Self::Created,
+            ChangeKind::StatusChanged => Self::StatusChanged,
+        }
+    }
+}
+
+impl From<&PaymentRecord> for PaymentSnapshot {
+    fn from(record: &PaymentRecord) -> Self {
+        Self {
+            address: record.address.to_string(),
+            merchant: record.merchant.to_string(),
+            operator: record.operator.to_string(),
+            merchant_operator_config: record.merchant_operator_config.to_string(),
+            buyer: record.buyer.to_string(),
+            mint: record.mint.to_string(),
+            order_id: record.order_id,
+            amount: record.amount,
+            status: proto::PaymentStatus::from(record.status).into(),
+            created_at: record.created_at,
+            signature: record.signature.clone(),
+        }
+    }
+}
+
+fn source_status(error: SourceError) -> Status {
+    Status::unavailable(error.to_string())
+}
+
+fn parse_pubkey(name: &str, value: &str) -> Result<Pubkey, Status> {
+    Pubkey::from_str(value)
+        .map_err(|_| Status::invalid_argument(format!("invalid {name}: {value}")))
+}
+
+fn parse_status(value: i32) -> Result<PaymentStatus, Status> {
+    match proto::PaymentStatus::try_from(value) {
+        Ok(proto::PaymentStatus::Paid) => Ok(PaymentStatus::Paid),
+        Ok(proto::PaymentStatus::Cleared) => Ok(PaymentStatus::Cleared),
+        Ok(proto::PaymentStatus::Refunded) => Ok(PaymentStatus::Refunded),
+        Ok(proto::PaymentStatus::RefundApproved) => Ok(PaymentStatus::RefundApproved),
+        Ok(proto::PaymentStatus::Authorized) => Ok(PaymentStatus::Authorized),
+        Ok(proto::PaymentStatus::Closed) => Ok(PaymentStatus::Closed),
+        Ok(proto::PaymentStatus::Unspecified) | Err(_) => Err(Status::invalid_argument(format!(
+            "invalid payment status: {value}"
+        ))),
+    }
+}
+
+/// Diffs `payments` against the statuses seen by earlier polls, recording the new ones.
+///
+/// Payments are reported oldest first. On the first poll (`initial`) every payment is
+/// `Existing`; afterwards unseen payments are `Created`. Payments outside `statuses` are
+/// tracked but not reported, unless `statuses` is empty. Every change is counted in
+/// `metrics`, reported or not.
+fn payment_events(
+    metrics: &Metrics,
+    seen: &mut HashMap<Pubkey, PaymentStatus>,
+    payments: &[PaymentRecord],
+    initial: bool,
+    statuses: &[PaymentStatus],
+) -> Vec<PaymentEvent> {
+    payment_changes(seen, payments, initial)
+        .into_iter()
+        .inspect(|change| {
+            metrics.record_payment_change("grpc", change.kind);
+            tracing::debug!(
+                payment = %change.payment.address,
+                order_id = change.payment.order_id,
+                kind = change.kind.name(),
+                "payment change"
+            );
+        })
+        .filter(|change| statuses.is_empty() || statuses.contains(&change.payment.status))
+        .map(|change| PaymentEvent {
+            kind: PaymentEventKind::from(change.kind).into(),
+            payment: Some(change.payment.into()),
+            previous_status: change
+                .previous_status
+                .map_or(proto::PaymentStatus::Unspecified, Into::into)
+                .into(),
+        })
+        .collect()
+}
+
+#[tonic::async_trait]
+impl<S: PaymentSource> Payments for PaymentsService<S> {
+    type SubscribePaymentsStream = ReceiverStream<Result<PaymentEvent, Status>>;
+
+    async fn subscribe_payments(
+        &self,
+        request: Request<SubscribePaymentsRequest>,
+    ) -> Result<Response<Self::SubscribePaymentsStream>, Status> {
+        let request = request.into_inner();
+        let merchant = parse_pubkey("merchant", &request.merchant)?;
+        let statuses = request
+            .statuses
+            .iter()
+            .map(|status| parse_status(*status))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (sender, receiver) = mpsc::channel(SUBSCRIBER_BUFFER);
+        let source = Arc::clone(&self.source);
+        let metrics = Arc::clone(&self.metrics);
+        let mut ticker = tokio::time::interval(self.poll_interval);
+
+        tokio::spawn(
+            async move {
+                let mut seen = HashMap::new();
+                let mut initial = true;
+                loop {
+                    tokio::select! {
+                        _ = ticker.tick() => {}
+                        _ = sender.closed() => return,
+                    }
+
+                    let payments = match source.payments(&merchant).await {
+                        Ok(payments) => payments,
+                        Err(error) => {
+                            tracing::warn!(%error, "subscription ended: payments unavailable");
+                            let _ = sender.send(Err(source_status(error))).await;
+                            return;
+                        }
+                    };
+                    for event in payment_events(&metrics, &mut seen, &payments, initial, &statuses)
+                    {
+                        if sender.send(Ok(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                    initial = false;
+                }
+            }
+            .instrument(tracing::info_span!("subscribe_payments", %merchant)),
+        );
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+
+    async fn get_payment(
+        &self,
+        request: Request<GetPaymentRequest>,
+    ) -> Result<Response<PaymentSnapshot>, Status> {
+        let request = request.into_inner();
+        let merchant = parse_pubkey("merchant", &request.merchant)?;
+        let config = request
+            .merchant_operator_config
+            .as_deref()
+            .map(|config| parse_pubkey("merchant_operator_config", config))
+            .transpose()?;
+
+        // Newest first, so a reused order id resolves to the latest payment
+        let payments = self
+            .source
+            .payments(&merchant)
+            .await
+            .map_err(source_status)?;
+        payments
+            .iter()
+            .find(|payment| {
+                payment.order_id == request.order_id
+                    && config.is_none_or(|config| payment.merchant_operator_config == config)
+            })
+            .map(|payment| Response::new(payment.into()))
+            .ok_or_else(|| {
+                Status::not_found(format!("no payment with order id {}", request.order_id))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::records::MerchantStatsRecord;
+    use commerce_program_client::events::PaymentEventRecord;
+    use std::sync::Mutex;
+    use tokio_stream::StreamExt;
+
+    #[derive(Default)]
+    struct MemorySource {
+        payments: Mutex<Vec<PaymentRecord>>,
+    }
+
+    impl PaymentSource for MemorySource {
+        async fn payments(&self, merchant: &Pubkey) -> Result<Vec<PaymentRecord>, SourceError> {
+            let payments = self.payments.lock().unwrap();
+            if payments.is_empty() {
+                return Err(SourceError::Rpc("connection refused".to_string()));
+            }
+            Ok(payments
+                .iter()
+                .filter(|payment| payment.merchant == *merchant)
+                .cloned()
+                .collect())
+        }
+
+        async fn merchant_stats(
+            &self,
+            _merchant: &Pubkey,
+        ) -> Result<Vec<MerchantStatsRecord>, SourceError> {
+            Ok(vec![])
+        }
+
+        async fn payment_events(
+            &self,
+            _payment: &Pubkey,
+        ) -> Result<Vec<PaymentEventRecord>, SourceError> {
+            Ok(vec![])
+        }
+    }
+
+    fn payment(merchant: Pubkey, order_id: u32, status: PaymentStatus) -> PaymentRecord {
+        PaymentRecord {
+            address: Pubkey::new_unique(),
+            merchant,
+            operator: Pubkey::new_unique(),
+            merchant_operator_config: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            order_id,
+            amount: 1_000,
+            status,
+            created_at: Some(1_700_000_000),
+            signature: "sig".to_string(),
+        }
+    }
+
+    fn service(payments: Vec<PaymentRecord>) -> PaymentsService<MemorySource> {
+        let source = MemorySource {
+            payments: Mutex::new(payments),
+        };
+        PaymentsService::new(Arc::new(source), Arc::default(), Duration::from_millis(10))
+    }
+
+    fn kinds(events: &[PaymentEvent]) -> Vec<(PaymentEventKind, u32)> {
+        events
+            .iter()
+            .map(|event| (event.kind(), event.payment.as_ref().unwrap().order_id))
+            .collect()
+    }
+
+    #[test]
+    fn test_payment_events() {
+        let merchant = Pubkey::new_unique();
+        let mut payments = vec![
+            payment(merchant, 2, PaymentStatus::Cleared),
+            payment(merchant, 1, PaymentStatus::Paid),
+        ];
+        let mut seen = HashMap::new();
+        let metrics = Metrics::default();
+
+        let events = payment_events(&metrics, &mut seen, &payments, true, &[]);
+        assert_eq!(
+            kinds(&events),
+            vec![
+                (PaymentEventKind::Existing, 1),
+                (PaymentEventKind::Existing, 2)
+            ]
+        );
+        assert_eq!(
+            events[1].payment.as_ref().unwrap().merchant,
+            merchant.to_string()
+        );
+
+        // Unchanged payments are not reported again
+        assert!(payment_events(&metrics, &mut seen, &payments, false, &[]).is_empty());
+
+        payments[1].status = PaymentStatus::Refunded;
+        payments.insert(0, payment(merchant, 3, PaymentStatus::Paid));
+        let events = payment_events(&metrics, &mut seen, &payments, false, &[]);
+        assert_eq!(
+            kinds(&events),
+            vec![
+                (PaymentEventKind::StatusChanged, 1),
+                (PaymentEventKind::Created, 3)
+            ]
+        );
+        assert_eq!(events[0].previous_status(), proto::PaymentStatus::Paid);
+        assert_eq!(
+            events[0].payment.as_ref().unwrap().status(),
+            proto::PaymentStatus::Refunded
+        );
+        assert_eq!(
+            events[1].previous_status(),
+            proto::PaymentStatus::Unspecified
+        );
+
+        let text = metrics.render();
+        assert!(text.contains(
+            "commerce_api_payment_changes_total{consumer=\"grpc\",kind=\"existing\"} 2\n"
+        ));
+        assert!(text.contains(
+            "commerce_api_payment_changes_total{consumer=\"grpc\",kind=\"created\"} 1\n"
+        ));
+    }
+
+    #[test]
+    fn test_payment_events_status_filter() {
+        let merchant = Pubkey::new_unique();
+        let mut payments = vec![
+            payment(merchant, 2, PaymentStatus::Paid),
+            payment(merchant, 1, PaymentStatus::Cleared),
+        ];
+        let mut seen = HashMap::new();
+        let metrics = Metrics::default();
+
+        let events = payment_events(
+            &metrics,
+            &mut seen,
+            &payments,
+            true,
+            &[PaymentStatus::Cleared],
+        );
+        assert_eq!(kinds(&events), vec![(PaymentEventKind::Existing, 1)]);
+
+        // Filtered payments are still tracked, so clearing reports a status change
+        payments[0].status = PaymentStatus::Cleared;
+        let events = payment_events(
+            &metrics,
+            &mut seen,
+            &payments,
+            false,
+            &[PaymentStatus::Cleared],
+        );
+        assert_eq!(kinds(&events), vec![(PaymentEventKind::StatusChanged, 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_payments() {
+        let merchant = Pubkey::new_unique();
+        let service = service(vec![payment(merchant, 1, PaymentStatus::Paid)]);
+
+        let mut stream = service
+            .subscribe_payments(Request::new(SubscribePaymentsRequest {
+                merchant: merchant.to_string(),
+                statuses: vec![],
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let event = stream.next().await.unwrap().unwrap();
+        assert_eq!(event.kind(), PaymentEventKind::Existing);
+
+        service
+            .source
+            .payments
+            .lock()
+            .unwrap()
+            .insert(0, payment(merchant, 2, PaymentStatus::Paid));
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.kind(), PaymentEventKind::Created);
+        assert_eq!(event.payment.unwrap().order_id, 2);
+
+        // Source errors end the stream
+        service.source.payments.lock().unwrap().clear();
+        let status = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_payments_invalid_request() {
+        let service = service(vec![]);
+
+        let status = service
+            .subscribe_payments(Request::new(SubscribePaymentsRequest {
+                merchant: "not-a-pubkey".to_string(),
+                statuses: vec![],
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(status.message(), "invalid merchant: not-a-pubkey");
+
+        let status = service
+            .subscribe_payments(Request::new(SubscribePaymentsRequest {
+                merchant: Pubkey::new_unique().to_string(),
+                statuses: vec![proto::PaymentStatus::Unspecified.into()],
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_get_payment() {
+        let merchant = Pubkey::new_unique();
+        let newer = payment(merchant, 1, PaymentStatus::Cleared);
+        let older = payment(merchant, 1, PaymentStatus::Paid);
+        let older_config = older.merchant_operator_config;
+        let service = service(vec![
+            newer,
+            older,
+            payment(merchant, 2, PaymentStatus::Paid),
+        ]);
+
+        let get = |order_id, merchant_operator_config: Option<Pubkey>| {
+            service.get_payment(Request::new(GetPaymentRequest {
+                merchant: merchant.to_string(),
+                order_id,
+                merchant_operator_config: merchant_operator_config.map(|c| c.to_string()),
+            }))
+        };
+
+        let snapshot = get(1, None).await.unwrap().into_inner();
+        assert_eq!(snapshot.status(), proto::PaymentStatus::Cleared);
+        assert_eq!(snapshot.created_at, Some(1_700_000_000));
+
+        let snapshot = get(1, Some(older_config)).await.unwrap().into_inner();
+        assert_eq!(snapshot.status(), proto::PaymentStatus::Paid);
+        assert_eq!(snapshot.merchant_operator_config, older_config.to_string());
+
+        let status = get(3, None).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        assert_eq!(status.message(), "no payment with order id 3");
+    }
+}
@@ -0,0 +1,177 @@
+//! `commerce-kit-api`: a small REST/JSON API over on-chain Commerce Program state.
+//!
+//! - `GET /payments?merchant=<merchant PDA>&status=<paid|cleared|refunded|closed>`
+//! - `GET /payments/<payment PDA>/events`: the payment's events with their slot and block time
+//! - `GET /merchants/<merchant PDA>/stats`
+//! - `GET /errors` and `GET /errors/<code>`: program error codes with their names and messages
+//! - `POST /payment-links/<token>`: the `MakePayment` transaction of a signed payment link, see
+//!   [`payment_links`]
+//! - `GET /metrics`: Prometheus metrics, see [`metrics`]
+//!
+//! Configuration comes from the environment:
+//! - `COMMERCE_API_RPC_URL` (default `http://127.0.0.1:8899`)
+//! - `COMMERCE_API_BIND` (default `127.0.0.1:8080`)
+//! - `COMMERCE_API_SIGNATURES_PER_CONFIG` (default `100`): recent transactions scanned per
+//!   merchant operator config when listing payments, and per payment when listing its events
+//! - `COMMERCE_API_LOG_FORMAT` (default `text`): `json` for one JSON object per log line;
+//!   `RUST_LOG` filters them (default `info`)
+//! - `COMMERCE_API_PAYMENT_LINK_SIGNERS`: comma-separated operator authorities whose payment
+//!   links are paid out; `/payment-links` is not served when unset
+//!
+//! With the `grpc` feature, the `commerce.v1.Payments` service (`SubscribePayments`,
+//! `GetPayment`) is served as well:
+//! - `COMMERCE_API_GRPC_BIND` (default `127.0.0.1:50051`)
+//! - `COMMERCE_API_GRPC_POLL_SECONDS` (default `5`): how often subscriptions re-read payments
+//!
+//! With the `webhooks` feature, payment changes are POSTed to the endpoints listed in
+//! `COMMERCE_API_WEBHOOKS`, if set:
+//! - `COMMERCE_API_WEBHOOKS`: JSON file of endpoints (`id`, `url`, `merchant`, `secret`, and
+//!   `verify_commitment` to deliver only to endpoints matching the on-chain webhook commitment)
+//! - `COMMERCE_API_WEBHOOK_OUTBOX` (default `commerce-webhooks-outbox.json`): durable queue of
+//!   deliveries
+//! - `COMMERCE_API_WEBHOOK_POLL_SECONDS` (default `5`): how often payments are re-read
+
+#[cfg(any(feature = "grpc", feature = "webhooks"))]
+mod changes;
+// tonic handlers return `tonic::Status`, which is larger than clippy allows for errors
+#[cfg(feature = "grpc")]
+#[allow(clippy::result_large_err)]
+mod grpc;
+mod metrics;
+#[cfg(feature = "webhooks")]
+mod outbox;
+mod payment_links;
+mod records;
+mod routes;
+mod source;
+#[cfg(feature = "webhooks")]
+mod webhooks;
+
+use std::{env, error::Error, sync::Arc};
+
+use metrics::Metrics;
+use solana_pubkey::Pubkey;
+use source::RpcSource;
+use tokio::task::JoinSet;
+use tracing_subscriber::EnvFilter;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+const DEFAULT_BIND: &str = "127.0.0.1:8080";
+const DEFAULT_SIGNATURES_PER_CONFIG: usize = 100;
+#[cfg(feature = "grpc")]
+const DEFAULT_GRPC_BIND: &str = "127.0.0.1:50051";
+#[cfg(feature = "grpc")]
+const DEFAULT_GRPC_POLL_SECONDS: u64 = 5;
+#[cfg(feature = "webhooks")]
+const DEFAULT_WEBHOOK_OUTBOX: &str = "commerce-webhooks-outbox.json";
+#[cfg(feature = "webhooks")]
+const DEFAULT_WEBHOOK_POLL_SECONDS: u64 = 5;
+
+/// Logs to stdout as text, or as JSON lines when `format` is `json`.
+fn init_tracing(format: Option<&str>) -> Result<(), BoxError> {
+    let logs = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()));
+    match format {
+        None | Some("text") => logs.init(),
+        Some("json") => logs.json().init(),
+        Some(format) => return Err(format!("unknown log format: {format}").into()),
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), BoxError> {
+    init_tracing(env::var("COMMERCE_API_LOG_FORMAT").ok().as_deref())?;
+    let rpc_url = env::var("COMMERCE_API_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+    let bind = env::var("COMMERCE_API_BIND").unwrap_or_else(|_| DEFAULT_BIND.to_string());
+    let signatures_per_config = match env::var("COMMERCE_API_SIGNATURES_PER_CONFIG") {
+        Ok(value) => value.parse()?,
+        Err(_) => DEFAULT_SIGNATURES_PER_CONFIG,
+    };
+    let payment_link_signers = match env::var("COMMERCE_API_PAYMENT_LINK_SIGNERS") {
+        Ok(value) => value
+            .split(',')
+            .map(|signer| signer.trim().parse())
+            .collect::<Result<Vec<Pubkey>, _>>()?,
+        Err(_) => Vec::new(),
+    };
+
+    let metrics = Arc::new(Metrics::default());
+    let source = Arc::new(RpcSource::new(
+        rpc_url.clone(),
+        signatures_per_config,
+        Arc::clone(&metrics),
+    ));
+    let listener = tokio::net::TcpListener::bind(&bind).await?;
+    tracing::info!(%bind, %rpc_url, "commerce-kit-api listening");
+
+    // Each service runs until it fails, which stops the process
+    let mut services: JoinSet<Result<(), BoxError>> = JoinSet::new();
+    let rest = axum::serve(
+        listener,
+        routes::router(
+            Arc::clone(&source),
+            payment_link_signers,
+            Arc::clone(&metrics),
+        ),
+    );
+    services.spawn(async { rest.await.map_err(BoxError::from) });
+
+    #[cfg(feature = "grpc")]
+    {
+        let grpc_bind = env::var("COMMERCE_API_GRPC_BIND")
+            .unwrap_or_else(|_| DEFAULT_GRPC_BIND.to_string())
+            .parse()?;
+        let poll_seconds = match env::var("COMMERCE_API_GRPC_POLL_SECONDS") {
+            Ok(value) => value.parse()?,
+            Err(_) => DEFAULT_GRPC_POLL_SECONDS,
+        };
+        let service = grpc::PaymentsService::new(
+            Arc::clone(&source),
+            Arc::clone(&metrics),
+            std::time::Duration::from_secs(poll_seconds),
+        );
+        tracing::info!(%grpc_bind, "commerce-kit-api gRPC listening");
+
+        let grpc = tonic::transport::Server::builder()
+            .add_service(service.into_server())
+            .serve(grpc_bind);
+        services.spawn(async { grpc.await.map_err(BoxError::from) });
+    }
+
+    #[cfg(feature = "webhooks")]
+    if let Ok(endpoints_path) = env::var("COMMERCE_API_WEBHOOKS") {
+        let endpoints = webhooks::load_endpoints(endpoints_path.as_ref())?;
+        let outbox_path = env::var("COMMERCE_API_WEBHOOK_OUTBOX")
+            .unwrap_or_else(|_| DEFAULT_WEBHOOK_OUTBOX.to_string());
+        let poll_seconds = match env::var("COMMERCE_API_WEBHOOK_POLL_SECONDS") {
+            Ok(value) => value.parse()?,
+            Err(_) => DEFAULT_WEBHOOK_POLL_SECONDS,
+        };
+        let outbox = outbox::Outbox::open(&outbox_path)?;
+        tracing::info!(
+            endpoints = endpoints.len(),
+            %outbox_path,
+            pending = outbox.pending().len(),
+            dead = outbox.dead().len(),
+            "commerce-kit-api delivering webhooks"
+        );
+
+        let webhooks = webhooks::Webhooks::new(
+            Arc::clone(&source),
+            webhooks::HttpSender::new()?,
+            endpoints,
+            outbox,
+            Arc::clone(&metrics),
+            std::time::Duration::from_secs(poll_seconds),
+        );
+        services.spawn(async { webhooks.run().await.map_err(BoxError::from) });
+    }
+
+    while let Some(result) = services.join_next().await {
+        result??;
+    }
+    Ok(())
+}
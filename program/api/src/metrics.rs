@@ -0,0 +1,162 @@
+//! Prometheus metrics, served as text at `GET /metrics` next to the REST routes.
+//!
+//! - `commerce_api_requests_total{route, status}`: REST requests by matched route and status
+//! - `commerce_api_rpc_errors_total{method}`: failed RPC calls by RPC method
+//! - `commerce_api_payment_changes_total{consumer, kind}`: payment changes found by the
+//!   `grpc` subscriptions and `webhooks` polls, `existing`, `created` or `status_changed`
+//! - `commerce_api_webhook_deliveries_total{endpoint, result}`: webhook attempts, `ok` or
+//!   `failed`
+//! - `commerce_api_webhook_delivery_seconds{endpoint}`: how long endpoints took to answer
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+    routing::get,
+    Router,
+};
+use prometheus::{IntCounterVec, Opts, Registry, TextEncoder};
+
+#[cfg(any(feature = "grpc", feature = "webhooks"))]
+use crate::changes::ChangeKind;
+
+pub struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    rpc_errors: IntCounterVec,
+    #[cfg(any(feature = "grpc", feature = "webhooks"))]
+    payment_changes: IntCounterVec,
+    #[cfg(feature = "webhooks")]
+    webhook_deliveries: IntCounterVec,
+    #[cfg(feature = "webhooks")]
+    webhook_delivery_seconds: prometheus::HistogramVec,
+}
+
+fn counter(registry: &Registry, name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+    let counter = IntCounterVec::new(Opts::new(name, help), labels).expect("valid metric");
+    registry
+        .register(Box::new(counter.clone()))
+        .expect("metric registered once");
+    counter
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let registry = Registry::new();
+        Self {
+            requests: counter(
+                &registry,
+                "commerce_api_requests_total",
+                "REST requests by route and status.",
+                &["route", "status"],
+            ),
+            rpc_errors: counter(
+                &registry,
+                "commerce_api_rpc_errors_total",
+                "Failed RPC calls by method.",
+                &["method"],
+            ),
+            #[cfg(any(feature = "grpc", feature = "webhooks"))]
+            payment_changes: counter(
+                &registry,
+                "commerce_api_payment_changes_total",
+                "Payment changes found by subscriptions.",
+                &["consumer", "kind"],
+            ),
+            #[cfg(feature = "webhooks")]
+            webhook_deliveries: counter(
+                &registry,
+                "commerce_api_webhook_deliveries_total",
+                "Webhook delivery attempts.",
+                &["endpoint", "result"],
+            ),
+            #[cfg(feature = "webhooks")]
+            webhook_delivery_seconds: {
+                let histogram = prometheus::HistogramVec::new(
+                    prometheus::HistogramOpts::new(
+                        "commerce_api_webhook_delivery_seconds",
+                        "Time webhook endpoints took to answer.",
+                    ),
+                    &["endpoint"],
+                )
+                .expect("valid metric");
+                registry
+                    .register(Box::new(histogram.clone()))
+                    .expect("metric registered once");
+                histogram
+            },
+            registry,
+        }
+    }
+}
+
+impl Metrics {
+    pub fn record_request(&self, route: &str, status: u16) {
+        self.requests
+            .with_label_values(&[route, &status.to_string()])
+            .inc();
+    }
+
+    pub fn record_rpc_error(&self, method: &str) {
+        self.rpc_errors.with_label_values(&[method]).inc();
+    }
+
+    #[cfg(any(feature = "grpc", feature = "webhooks"))]
+    pub fn record_payment_change(&self, consumer: &str, kind: ChangeKind) {
+        self.payment_changes
+            .with_label_values(&[consumer, kind.name()])
+            .inc();
+    }
+
+    #[cfg(feature = "webhooks")]
+    pub fn record_webhook_delivery(&self, endpoint: &str, ok: bool, elapsed: std::time::Duration) {
+        let result = if ok { "ok" } else { "failed" };
+        self.webhook_deliveries
+            .with_label_values(&[endpoint, result])
+            .inc();
+        self.webhook_delivery_seconds
+            .with_label_values(&[endpoint])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .expect("metrics encode as text")
+    }
+}
+
+/// Adds `GET /metrics` to `router` and counts every request it serves.
+pub fn instrument(router: Router, metrics: Arc<Metrics>) -> Router {
+    let endpoint = Router::new()
+        .route("/metrics", get(render))
+        .with_state(Arc::clone(&metrics));
+    router
+        .merge(endpoint)
+        .layer(axum::middleware::from_fn_with_state(
+            metrics,
+            count_requests,
+        ))
+}
+
+async fn render(State(metrics): State<Arc<Metrics>>) -> String {
+    metrics.render()
+}
+
+/// Counts requests by their route template, so path parameters do not become labels.
+async fn count_requests(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |path| path.as_str().to_string());
+    let response = next.run(request).await;
+    metrics.record_request(&route, response.status().as_u16());
+    response
+}
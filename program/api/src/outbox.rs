@@ -0,0 +1,251 @@
+//! Durable outbox of webhook deliveries.
+//!
+//! The outbox is a JSON file rewritten through a temporary file and a rename on every change,
+//! so a crash leaves either the old or the new contents. Deliveries are queued together with
+//! the payment statuses they report, so a restart neither loses a queued delivery nor
+//! re-queues one that was already queued: delivery is at least once, and receivers dedupe
+//! on the delivery id.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::records::PaymentStatus;
+
+/// Delay before the first retry, doubled on each further retry.
+pub const RETRY_BASE_DELAY: Duration = Duration::from_secs(10);
+/// Longest delay between two retries.
+pub const RETRY_MAX_DELAY: Duration = Duration::from_secs(60 * 60);
+/// Attempts after which a delivery is moved to the dead letters.
+pub const MAX_ATTEMPTS: u32 = 16;
+
+#[derive(Debug, Error)]
+pub enum OutboxError {
+    #[error("failed to access outbox {0}: {1}")]
+    Io(PathBuf, io::Error),
+    #[error("failed to parse outbox {0}: {1}")]
+    Parse(PathBuf, serde_json::Error),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delivery {
+    /// Idempotency key of the event, the same on every attempt.
+    pub id: String,
+    pub endpoint: String,
+    /// The signed JSON body.
+    pub body: String,
+    pub attempts: u32,
+    /// Unix timestamp of the next attempt.
+    pub next_attempt_at: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OutboxState {
+    pending: Vec<Delivery>,
+    /// Deliveries that failed `MAX_ATTEMPTS` times, kept for inspection.
+    dead: Vec<Delivery>,
+    /// Payment statuses already queued, per endpoint id and payment address.
+    seen: HashMap<String, HashMap<String, PaymentStatus>>,
+}
+
+#[derive(Debug)]
+pub struct Outbox {
+    path: PathBuf,
+    state: OutboxState,
+}
+
+/// Delay before retrying a delivery that has failed `attempts` times.
+pub fn retry_delay(attempts: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+    RETRY_BASE_DELAY.saturating_mul(factor).min(RETRY_MAX_DELAY)
+}
+
+impl Outbox {
+    /// Opens the outbox at `path`, starting empty if the file does not exist.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, OutboxError> {
+        let path = path.into();
+        let state = match fs::read(&path) {
+            Ok(data) => serde_json::from_slice(&data)
+                .map_err(|error| OutboxError::Parse(path.clone(), error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => OutboxState::default(),
+            Err(error) => return Err(OutboxError::Io(path, error)),
+        };
+        Ok(Self { path, state })
+    }
+
+    /// Payment statuses already queued for `endpoint`, or `None` before its first poll.
+    pub fn seen(&self, endpoint: &str) -> Option<&HashMap<String, PaymentStatus>> {
+        self.state.seen.get(endpoint)
+    }
+
+    /// Records the statuses `endpoint` has now seen and queues their deliveries, in one write.
+    pub fn enqueue(
+        &mut self,
+        endpoint: &str,
+        seen: HashMap<String, PaymentStatus>,
+        deliveries: Vec<Delivery>,
+    ) -> Result<(), OutboxError> {
+        self.state.seen.insert(endpoint.to_string(), seen);
+        self.state.pending.extend(deliveries);
+        self.persist()
+    }
+
+    pub fn pending(&self) -> &[Delivery] {
+        &self.state.pending
+    }
+
+    pub fn dead(&self) -> &[Delivery] {
+        &self.state.dead
+    }
+
+    /// Pending deliveries whose next attempt is due at `now`, oldest first.
+    pub fn due(&self, now: u64) -> Vec<Delivery> {
+        self.state
+            .pending
+            .iter()
+            .filter(|delivery| delivery.next_attempt_at <= now)
+            .cloned()
+            .collect()
+    }
+
+    /// Removes a delivery the endpoint accepted.
+    pub fn delivered(&mut self, endpoint: &str, id: &str) -> Result<(), OutboxError> {
+        self.state
+            .pending
+            .retain(|delivery| delivery.endpoint != endpoint || delivery.id != id);
+        self.persist()
+    }
+
+    /// Schedules a failed delivery's retry with exponential backoff, or moves it to the dead
+    /// letters after `MAX_ATTEMPTS`.
+    pub fn failed(
+        &mut self,
+        endpoint: &str,
+        id: &str,
+        error: String,
+        now: u64,
+    ) -> Result<(), OutboxError> {
+        let Some(index) = self
+            .state
+            .pending
+            .iter()
+            .position(|delivery| delivery.endpoint == endpoint && delivery.id == id)
+        else {
+            return Ok(());
+        };
+
+        let delivery = &mut self.state.pending[index];
+        delivery.attempts += 1;
+        delivery.last_error = Some(error);
+        if delivery.attempts >= MAX_ATTEMPTS {
+            let delivery = self.state.pending.remove(index);
+            self.state.dead.push(delivery);
+        } else {
+            delivery.next_attempt_at = now + retry_delay(delivery.attempts).as_secs();
+        }
+        self.persist()
+    }
+
+    fn persist(&self) -> Result<(), OutboxError> {
+        let io_error = |error| OutboxError::Io(self.path.clone(), error);
+        let data = serde_json::to_vec(&self.state).map_err(|error| io_error(error.into()))?;
+
+        let temp = temp_path(&self.path);
+        let mut file = File::create(&temp).map_err(io_error)?;
+        file.write_all(&data).map_err(io_error)?;
+        file.sync_all().map_err(io_error)?;
+        fs::rename(&temp, &self.path).map_err(io_error)
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery(id: &str) -> Delivery {
+        Delivery {
+            id: id.to_string(),
+            endpoint: "shop".to_string(),
+            body: "{}".to_string(),
+            attempts: 0,
+            next_attempt_at: 100,
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), Duration::from_secs(10));
+        assert_eq!(retry_delay(2), Duration::from_secs(20));
+        assert_eq!(retry_delay(5), Duration::from_secs(160));
+        assert_eq!(retry_delay(10), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(u32::MAX), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_outbox_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbox.json");
+
+        let mut outbox = Outbox::open(&path).unwrap();
+        assert!(outbox.seen("shop").is_none());
+        let seen = HashMap::from([("payment".to_string(), PaymentStatus::Paid)]);
+        outbox
+            .enqueue("shop", seen.clone(), vec![delivery("a"), delivery("b")])
+            .unwrap();
+        outbox.delivered("shop", "a").unwrap();
+        outbox.failed("shop", "b", "503".to_string(), 100).unwrap();
+
+        let outbox = Outbox::open(&path).unwrap();
+        assert_eq!(outbox.seen("shop"), Some(&seen));
+        assert_eq!(outbox.pending().len(), 1);
+        assert_eq!(outbox.pending()[0].attempts, 1);
+        assert_eq!(outbox.pending()[0].last_error.as_deref(), Some("503"));
+        assert!(outbox.due(109).is_empty());
+        assert_eq!(outbox.due(110).len(), 1);
+    }
+
+    #[test]
+    fn test_failed_deliveries_are_dead_lettered() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut outbox = Outbox::open(dir.path().join("outbox.json")).unwrap();
+        outbox
+            .enqueue("shop", HashMap::new(), vec![delivery("a")])
+            .unwrap();
+
+        for attempt in 1..MAX_ATTEMPTS {
+            outbox
+                .failed("shop", "a", "timeout".to_string(), 0)
+                .unwrap();
+            assert_eq!(outbox.pending()[0].attempts, attempt);
+        }
+        outbox
+            .failed("shop", "a", "timeout".to_string(), 0)
+            .unwrap();
+        assert!(outbox.pending().is_empty());
+        assert_eq!(outbox.dead()[0].attempts, MAX_ATTEMPTS);
+    }
+
+    #[test]
+    fn test_open_rejects_corrupt_outbox() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbox.json");
+        fs::write(&path, b"not json").unwrap();
+
+        assert!(matches!(Outbox::open(&path), Err(OutboxError::Parse(..))));
+    }
+}
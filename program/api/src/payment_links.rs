@@ -0,0 +1,325 @@
+//! Pay-by-link checkouts.
+//!
+//! `POST /payment-links/<token>` takes the body of a Solana Pay transaction request,
+//! `{"account": "<buyer>"}`, and answers with the `MakePayment` transaction of the link for
+//! that buyer, base64-encoded, with its Payment PDA and the link's expiry.
+//!
+//! The token is a [`commerce_program_client::payment_link`] token. The transaction is only
+//! built when the token verifies against the operator authorities the API trusts, has not
+//! expired, and its signer is the operator authority of the link's config, so a trusted
+//! operator cannot offer payments on another operator's configs. The API holds no keys: the
+//! transaction is returned unsigned, to be signed by the operator authority, which pays its
+//! fees, and by the buyer.
+
+use std::{
+    str::FromStr,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    extract::{rejection::JsonRejection, Path, State},
+    routing::post,
+    Json, Router,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use commerce_program_client::payment_link::{PaymentLinkError, SignedPaymentLink};
+use serde::Deserialize;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_transaction::versioned::VersionedTransaction;
+
+use crate::{records::PaymentLinkTransactionResponse, routes::ApiError, source::CheckoutSource};
+
+struct PaymentLinks<S> {
+    source: Arc<S>,
+    /// Operator authorities whose links are paid out
+    trusted_signers: Vec<Pubkey>,
+}
+
+pub fn router<S: CheckoutSource>(source: Arc<S>, trusted_signers: Vec<Pubkey>) -> Router {
+    Router::new()
+        .route(
+            "/payment-links/{token}",
+            post(payment_link_transaction::<S>),
+        )
+        .with_state(Arc::new(PaymentLinks {
+            source,
+            trusted_signers,
+        }))
+}
+
+impl From<PaymentLinkError> for ApiError {
+    fn from(error: PaymentLinkError) -> Self {
+        match error {
+            PaymentLinkError::InvalidEncoding
+            | PaymentLinkError::InvalidLength(_)
+            | PaymentLinkError::UnsupportedVersion(_) => Self::BadRequest(error.to_string()),
+            PaymentLinkError::UntrustedSigner(_) | PaymentLinkError::InvalidSignature => {
+                Self::Forbidden(error.to_string())
+            }
+            PaymentLinkError::Expired(_) => Self::Gone(error.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransactionRequest {
+    account: String,
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+/// `POST /payment-links/<token>` with `{"account": "<buyer>"}`
+async fn payment_link_transaction<S: CheckoutSource>(
+    State(links): State<Arc<PaymentLinks<S>>>,
+    Path(token): Path<String>,
+    request: Result<Json<TransactionRequest>, JsonRejection>,
+) -> Result<Json<PaymentLinkTransactionResponse>, ApiError> {
+    let Json(request) = request.map_err(|e| ApiError::BadRequest(e.body_text()))?;
+    let buyer = Pubkey::from_str(&request.account)
+        .map_err(|_| ApiError::BadRequest(format!("invalid account: {}", request.account)))?;
+
+    let signed = SignedPaymentLink::from_token(&token)?;
+    let link = signed.verify(&links.trusted_signers, now())?;
+
+    let accounts = links
+        .source
+        .checkout_accounts(&link.merchant_operator_config)
+        .await?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "unknown merchant operator config: {}",
+                link.merchant_operator_config
+            ))
+        })?;
+    if accounts.operator_authority != signed.signer {
+        return Err(ApiError::Forbidden(format!(
+            "payment link signer {} is not the config's operator authority",
+            signed.signer
+        )));
+    }
+
+    let request = link.checkout_request();
+    let message = request
+        .checkout_builder(&accounts, buyer)
+        .message(&[], links.source.latest_blockhash().await?)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let transaction = VersionedTransaction {
+        signatures: vec![
+            Signature::default();
+            usize::from(message.header().num_required_signatures)
+        ],
+        message,
+    };
+    let transaction = bincode::serialize(&transaction).expect("versioned transactions serialize");
+
+    Ok(Json(PaymentLinkTransactionResponse {
+        transaction: STANDARD.encode(transaction),
+        payment: request.payment(&buyer),
+        expires_at: link.expires_at,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SourceError;
+    use axum::{
+        body::Body,
+        http::{header::CONTENT_TYPE, Request, StatusCode},
+    };
+    use commerce_program_client::{payment_link::PaymentLink, solana_pay::CheckoutAccounts};
+    use serde_json::Value;
+    use solana_hash::Hash;
+    use solana_keypair::Keypair;
+    use solana_signer::Signer;
+    use tower::ServiceExt;
+
+    struct MemorySource {
+        config: Pubkey,
+        accounts: CheckoutAccounts,
+    }
+
+    impl CheckoutSource for MemorySource {
+        async fn checkout_accounts(
+            &self,
+            merchant_operator_config: &Pubkey,
+        ) -> Result<Option<CheckoutAccounts>, SourceError> {
+            Ok((*merchant_operator_config == self.config).then(|| self.accounts.clone()))
+        }
+
+        async fn latest_blockhash(&self) -> Result<Hash, SourceError> {
+            Ok(Hash::new_from_array([7; 32]))
+        }
+    }
+
+    struct Setup {
+        operator_authority: Keypair,
+        router: Router,
+        link: PaymentLink,
+    }
+
+    fn fixture() -> Setup {
+        let operator_authority = Keypair::new();
+        let config = Pubkey::new_unique();
+        let source = MemorySource {
+            config,
+            accounts: CheckoutAccounts {
+                payer: operator_authority.pubkey(),
+                operator_authority: operator_authority.pubkey(),
+                operator: Pubkey::new_unique(),
+                merchant: Pubkey::new_unique(),
+                settlement_wallet: Pubkey::new_unique(),
+            },
+        };
+        let link = PaymentLink {
+            merchant_operator_config: config,
+            mint: Pubkey::new_unique(),
+            amount: 1_500_000,
+            order_id: 42,
+            idempotency_key: [0xa5; 16],
+            expires_at: now() + 3_600,
+        };
+        Setup {
+            router: router(Arc::new(source), vec![operator_authority.pubkey()]),
+            operator_authority,
+            link,
+        }
+    }
+
+    async fn post(router: Router, token: &str, account: &str) -> (StatusCode, Value) {
+        let body = serde_json::json!({ "account": account }).to_string();
+        let response = router
+            .oneshot(
+                Request::post(format!("/payment-links/{token}"))
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_payment_link_transaction() {
+        let setup = fixture();
+        let buyer = Pubkey::new_unique();
+        let token = setup
+            .link
+            .clone()
+            .sign(&setup.operator_authority)
+            .unwrap()
+            .to_token();
+
+        let (status, body) = post(setup.router, &token, &buyer.to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["payment"],
+            setup.link.checkout_request().payment(&buyer).to_string()
+        );
+        assert_eq!(body["expires_at"], setup.link.expires_at);
+
+        let transaction: VersionedTransaction = bincode::deserialize(
+            &STANDARD
+                .decode(body["transaction"].as_str().unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        let signers = &transaction.message.static_account_keys()[..transaction.signatures.len()];
+        assert!(signers.contains(&setup.operator_authority.pubkey()));
+        assert!(signers.contains(&buyer));
+        assert_eq!(
+            transaction.message.recent_blockhash(),
+            &Hash::new_from_array([7; 32])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payment_link_rejections() {
+        let buyer = Pubkey::new_unique().to_string();
+
+        // Bad token
+        let setup = fixture();
+        let (status, _) = post(setup.router, "zz", &buyer).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // Signed by an operator the API does not trust
+        let setup = fixture();
+        let token = setup.link.sign(&Keypair::new()).unwrap().to_token();
+        let (status, _) = post(setup.router, &token, &buyer).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // Expired
+        let setup = fixture();
+        let link = PaymentLink {
+            expires_at: now() - 1,
+            ..setup.link
+        };
+        let token = link.sign(&setup.operator_authority).unwrap().to_token();
+        let (status, _) = post(setup.router, &token, &buyer).await;
+        assert_eq!(status, StatusCode::GONE);
+
+        // Unknown config
+        let setup = fixture();
+        let link = PaymentLink {
+            merchant_operator_config: Pubkey::new_unique(),
+            ..setup.link
+        };
+        let token = link.sign(&setup.operator_authority).unwrap().to_token();
+        let (status, _) = post(setup.router, &token, &buyer).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Invalid buyer
+        let setup = fixture();
+        let token = setup
+            .link
+            .sign(&setup.operator_authority)
+            .unwrap()
+            .to_token();
+        let (status, _) = post(setup.router, &token, "buyer").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_payment_link_signer_must_operate_config() {
+        let setup = fixture();
+        let other_operator = Keypair::new();
+        let source = MemorySource {
+            config: setup.link.merchant_operator_config,
+            accounts: CheckoutAccounts {
+                payer: other_operator.pubkey(),
+                operator_authority: other_operator.pubkey(),
+                operator: Pubkey::new_unique(),
+                merchant: Pubkey::new_unique(),
+                settlement_wallet: Pubkey::new_unique(),
+            },
+        };
+        // Both operators are trusted, but only the config's may offer payments on it
+        let router = router(
+            Arc::new(source),
+            vec![setup.operator_authority.pubkey(), other_operator.pubkey()],
+        );
+        let token = setup
+            .link
+            .sign(&setup.operator_authority)
+            .unwrap()
+            .to_token();
+
+        let (status, body) = post(router, &token, &Pubkey::new_unique().to_string()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .contains("not the config's operator authority"));
+    }
+}
@@ -0,0 +1,121 @@
+//! JSON shapes returned by the API.
+
+use commerce_program_client::{accounts::MerchantStats, events::PaymentEventRecord, types::Status};
+use serde::{Deserialize, Serialize, Serializer};
+use solana_pubkey::Pubkey;
+
+fn display<S: Serializer>(key: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(key)
+}
+
+/// Payment status as seen by the API. `Closed` payments no longer have an account, so their
+/// amount and order id come from the `MakePayment` instruction that created them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+    Paid,
+    Cleared,
+    Refunded,
+    RefundApproved,
+    Authorized,
+    Closed,
+}
+
+impl From<Status> for PaymentStatus {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Paid => Self::Paid,
+            Status::Cleared => Self::Cleared,
+            Status::Refunded => Self::Refunded,
+            Status::RefundApproved => Self::RefundApproved,
+            Status::Authorized => Self::Authorized,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PaymentRecord {
+    #[serde(serialize_with = "display")]
+    pub address: Pubkey,
+    #[serde(serialize_with = "display")]
+    pub merchant: Pubkey,
+    #[serde(serialize_with = "display")]
+    pub operator: Pubkey,
+    #[serde(serialize_with = "display")]
+    pub merchant_operator_config: Pubkey,
+    #[serde(serialize_with = "display")]
+    pub buyer: Pubkey,
+    #[serde(serialize_with = "display")]
+    pub mint: Pubkey,
+    pub order_id: u32,
+    pub amount: u64,
+    pub status: PaymentStatus,
+    /// Unix timestamp from the payment account; `None` once the payment is closed.
+    pub created_at: Option<i64>,
+    /// Signature of the `MakePayment` transaction.
+    pub signature: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MerchantStatsRecord {
+    #[serde(serialize_with = "display")]
+    pub address: Pubkey,
+    #[serde(serialize_with = "display")]
+    pub mint: Pubkey,
+    pub payment_count: u64,
+    pub total_volume: u64,
+    pub total_cleared: u64,
+    pub total_fees_paid: u64,
+    pub refund_count: u64,
+    pub total_refunded: u64,
+    pub closed_count: u64,
+}
+
+impl MerchantStatsRecord {
+    pub fn new(address: Pubkey, stats: &MerchantStats) -> Self {
+        Self {
+            address,
+            mint: stats.mint,
+            payment_count: stats.payment_count,
+            total_volume: stats.total_volume,
+            total_cleared: stats.total_cleared,
+            total_fees_paid: stats.total_fees_paid,
+            refund_count: stats.refund_count,
+            total_refunded: stats.total_refunded,
+            closed_count: stats.closed_count,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaymentsResponse {
+    pub payments: Vec<PaymentRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaymentEventsResponse {
+    #[serde(serialize_with = "display")]
+    pub payment: Pubkey,
+    /// Events emitted on the payment, oldest first, with the slot and block time of the
+    /// transaction that emitted each.
+    pub events: Vec<PaymentEventRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MerchantStatsResponse {
+    #[serde(serialize_with = "display")]
+    pub merchant: Pubkey,
+    /// One entry per mint with an initialized `MerchantStats` account.
+    pub stats: Vec<MerchantStatsRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaymentLinkTransactionResponse {
+    /// Base64 `MakePayment` transaction, unsigned
+    pub transaction: String,
+    /// Payment PDA the transaction creates
+    #[serde(serialize_with = "display")]
+    pub payment: Pubkey,
+    /// Unix timestamp the link expires at
+    pub expires_at: i64,
+}
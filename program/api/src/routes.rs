@@ -0,0 +1,437 @@
+use std::{str::FromStr, sync::Arc};
+
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use commerce_program_client::{
+    error_registry::{error_registry, ErrorInfo},
+    CommerceProgramError,
+};
+use serde::Deserialize;
+use serde_json::json;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    metrics::{self, Metrics},
+    payment_links,
+    records::{MerchantStatsResponse, PaymentEventsResponse, PaymentStatus, PaymentsResponse},
+    source::{CheckoutSource, PaymentSource, SourceError},
+};
+
+/// The API's routes. `POST /payment-links/<token>` is only served when
+/// `payment_link_signers` is not empty.
+pub fn router<S: PaymentSource + CheckoutSource>(
+    source: Arc<S>,
+    payment_link_signers: Vec<Pubkey>,
+    metrics: Arc<Metrics>,
+) -> Router {
+    let mut routes = Router::new()
+        .route("/payments", get(list_payments::<S>))
+        .route("/payments/{payment}/events", get(payment_events::<S>))
+        .route("/merchants/{merchant}/stats", get(merchant_stats::<S>))
+        .route("/errors", get(list_errors))
+        .route("/errors/{code}", get(program_error))
+        .with_state(Arc::clone(&source));
+    if !payment_link_signers.is_empty() {
+        routes = routes.merge(payment_links::router(source, payment_link_signers));
+    }
+    metrics::instrument(routes, metrics)
+}
+
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    Forbidden(String),
+    NotFound(String),
+    Gone(String),
+    Source(SourceError),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            Self::Forbidden(message) => (StatusCode::FORBIDDEN, message),
+            Self::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            Self::Gone(message) => (StatusCode::GONE, message),
+            Self::Source(error) => (StatusCode::BAD_GATEWAY, error.to_string()),
+        };
+        (status, Json(json!({ "error": message }))).into_response()
+    }
+}
+
+impl From<SourceError> for ApiError {
+    fn from(error: SourceError) -> Self {
+        Self::Source(error)
+    }
+}
+
+fn parse_pubkey(name: &str, value: &str) -> Result<Pubkey, ApiError> {
+    Pubkey::from_str(value).map_err(|_| ApiError::BadRequest(format!("invalid {name}: {value}")))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PaymentsQuery {
+    merchant: String,
+    status: Option<PaymentStatus>,
+}
+
+/// `GET /payments?merchant=<merchant PDA>&status=<paid|cleared|refunded|closed>`
+async fn list_payments<S: PaymentSource>(
+    State(source): State<Arc<S>>,
+    query: Result<Query<PaymentsQuery>, QueryRejection>,
+) -> Result<Json<PaymentsResponse>, ApiError> {
+    let Query(query) = query.map_err(|e| ApiError::BadRequest(e.body_text()))?;
+    let merchant = parse_pubkey("merchant", &query.merchant)?;
+
+    let mut payments = source.payments(&merchant).await?;
+    if let Some(status) = query.status {
+        payments.retain(|payment| payment.status == status);
+    }
+
+    Ok(Json(PaymentsResponse { payments }))
+}
+
+/// `GET /payments/<payment PDA>/events`
+async fn payment_events<S: PaymentSource>(
+    State(source): State<Arc<S>>,
+    Path(payment): Path<String>,
+) -> Result<Json<PaymentEventsResponse>, ApiError> {
+    let payment = parse_pubkey("payment", &payment)?;
+    let events = source.payment_events(&payment).await?;
+
+    Ok(Json(PaymentEventsResponse { payment, events }))
+}
+
+/// `GET /merchants/<merchant PDA>/stats`
+async fn merchant_stats<S: PaymentSource>(
+    State(source): State<Arc<S>>,
+    Path(merchant): Path<String>,
+) -> Result<Json<MerchantStatsResponse>, ApiError> {
+    let merchant = parse_pubkey("merchant", &merchant)?;
+    let stats = source.merchant_stats(&merchant).await?;
+
+    Ok(Json(MerchantStatsResponse { merchant, stats }))
+}
+
+/// `GET /errors`
+async fn list_errors() -> Json<Vec<ErrorInfo>> {
+    Json(error_registry())
+}
+
+/// `GET /errors/<code>`, the `N` of a failed transaction's `Custom(N)`
+async fn program_error(Path(code): Path<String>) -> Result<Json<ErrorInfo>, ApiError> {
+    let code: u32 = code
+        .parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid code: {code}")))?;
+    let error = CommerceProgramError::from_code(code)
+        .ok_or_else(|| ApiError::NotFound(format!("unknown program error: {code}")))?;
+
+    Ok(Json(error.info()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::records::{MerchantStatsRecord, PaymentRecord};
+    use axum::{body::Body, http::Request};
+    use commerce_program_client::{
+        events::{PaymentEventKind, PaymentEventRecord},
+        solana_pay::CheckoutAccounts,
+    };
+    use serde_json::Value;
+    use solana_hash::Hash;
+    use tower::ServiceExt;
+
+    struct MemorySource {
+        payments: Vec<PaymentRecord>,
+        stats: Vec<MerchantStatsRecord>,
+        events: Vec<PaymentEventRecord>,
+    }
+
+    impl PaymentSource for MemorySource {
+        async fn payments(&self, merchant: &Pubkey) -> Result<Vec<PaymentRecord>, SourceError> {
+            Ok(self
+                .payments
+                .iter()
+                .filter(|payment| payment.merchant == *merchant)
+                .cloned()
+                .collect())
+        }
+
+        async fn merchant_stats(
+            &self,
+            _merchant: &Pubkey,
+        ) -> Result<Vec<MerchantStatsRecord>, SourceError> {
+            if self.stats.is_empty() {
+                return Err(SourceError::Rpc("connection refused".to_string()));
+            }
+            Ok(self.stats.clone())
+        }
+
+        async fn payment_events(
+            &self,
+            payment: &Pubkey,
+        ) -> Result<Vec<PaymentEventRecord>, SourceError> {
+            Ok(self
+                .events
+                .iter()
+                .filter(|event| event.payment == *payment)
+                .cloned()
+                .collect())
+        }
+    }
+
+    impl CheckoutSource for MemorySource {
+        async fn checkout_accounts(
+            &self,
+            _merchant_operator_config: &Pubkey,
+        ) -> Result<Option<CheckoutAccounts>, SourceError> {
+            Ok(None)
+        }
+
+        async fn latest_blockhash(&self) -> Result<Hash, SourceError> {
+            Ok(Hash::default())
+        }
+    }
+
+    fn payment(merchant: Pubkey, order_id: u32, status: PaymentStatus) -> PaymentRecord {
+        PaymentRecord {
+            address: Pubkey::new_unique(),
+            merchant,
+            operator: Pubkey::new_unique(),
+            merchant_operator_config: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            order_id,
+            amount: 1_000,
+            status,
+            created_at: Some(1_700_000_000),
+            signature: "sig".to_string(),
+        }
+    }
+
+    async fn get(router: Router, uri: &str) -> (StatusCode, Value) {
+        let response = router
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_list_payments_filters_by_status() {
+        let merchant = Pubkey::new_unique();
+        let source = MemorySource {
+            payments: vec![
+                payment(merchant, 1, PaymentStatus::Paid),
+                payment(merchant, 2, PaymentStatus::Cleared),
+                payment(Pubkey::new_unique(), 3, PaymentStatus::Paid),
+            ],
+            stats: vec![],
+            events: vec![],
+        };
+        let router = router(Arc::new(source), Vec::new(), Arc::default());
+
+        let (status, body) = get(router.clone(), &format!("/payments?merchant={merchant}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["payments"].as_array().unwrap().len(), 2);
+
+        let (status, body) = get(
+            router,
+            &format!("/payments?merchant={merchant}&status=cleared"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let payments = body["payments"].as_array().unwrap();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0]["order_id"], 2);
+        assert_eq!(payments[0]["status"], "cleared");
+        assert_eq!(payments[0]["merchant"], merchant.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_list_payments_bad_request() {
+        let source = MemorySource {
+            payments: vec![],
+            stats: vec![],
+            events: vec![],
+        };
+        let router = router(Arc::new(source), Vec::new(), Arc::default());
+
+        let (status, body) = get(router.clone(), "/payments?merchant=not-a-pubkey").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid merchant: not-a-pubkey");
+
+        let (status, _) = get(
+            router.clone(),
+            &format!("/payments?merchant={}&status=pending", Pubkey::new_unique()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = get(router, "/payments").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_payment_events() {
+        let payment = Pubkey::new_unique();
+        let event = |slot, kind| PaymentEventRecord {
+            signature: "sig".to_string(),
+            slot,
+            block_time: Some(1_700_000_000),
+            kind,
+            payment,
+            merchant_operator_config: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            merchant: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            order_id: 1,
+            amount: 1_000,
+            operator_fee: 0,
+        };
+        let source = MemorySource {
+            payments: vec![],
+            stats: vec![],
+            events: vec![
+                event(10, PaymentEventKind::Created),
+                event(12, PaymentEventKind::Cleared),
+            ],
+        };
+        let router = router(Arc::new(source), Vec::new(), Arc::default());
+
+        let (status, body) = get(router.clone(), &format!("/payments/{payment}/events")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["payment"], payment.to_string());
+        let events = body["events"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["kind"], "created");
+        assert_eq!(events[0]["slot"], 10);
+        assert_eq!(events[0]["block_time"], 1_700_000_000);
+        assert_eq!(events[0]["payment"], payment.to_string());
+        assert_eq!(events[1]["kind"], "cleared");
+
+        let (status, body) = get(router, "/payments/not-a-pubkey/events").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid payment: not-a-pubkey");
+    }
+
+    #[tokio::test]
+    async fn test_merchant_stats() {
+        let merchant = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let source = MemorySource {
+            payments: vec![],
+            stats: vec![MerchantStatsRecord {
+                address: Pubkey::new_unique(),
+                mint,
+                payment_count: 3,
+                total_volume: 3_000,
+                total_cleared: 2_000,
+                total_fees_paid: 20,
+                refund_count: 1,
+                total_refunded: 1_000,
+                closed_count: 0,
+            }],
+            events: vec![],
+        };
+
+        let (status, body) = get(
+            router(Arc::new(source), Vec::new(), Arc::default()),
+            &format!("/merchants/{merchant}/stats"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["merchant"], merchant.to_string());
+        assert_eq!(body["stats"][0]["mint"], mint.to_string());
+        assert_eq!(body["stats"][0]["total_volume"], 3_000);
+    }
+
+    #[tokio::test]
+    async fn test_merchant_stats_source_error() {
+        let source = MemorySource {
+            payments: vec![],
+            stats: vec![],
+            events: vec![],
+        };
+
+        let (status, body) = get(
+            router(Arc::new(source), Vec::new(), Arc::default()),
+            &format!("/merchants/{}/stats", Pubkey::new_unique()),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(body["error"], "rpc error: connection refused");
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let source = MemorySource {
+            payments: vec![],
+            stats: vec![],
+            events: vec![],
+        };
+        let router = router(Arc::new(source), Vec::new(), Arc::default());
+
+        let (status, body) = get(router.clone(), "/errors").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), error_registry().len());
+        assert_eq!(body[0]["name"], "InvalidMint");
+
+        let (status, body) = get(router.clone(), "/errors/78").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["code"], 78);
+        assert_eq!(body["name"], "RiskStateRequired");
+        assert_eq!(
+            body["message"],
+            CommerceProgramError::RiskStateRequired.message()
+        );
+
+        let (status, body) = get(router.clone(), "/errors/100000").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "unknown program error: 100000");
+
+        let (status, _) = get(router, "/errors/abc").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_requests_by_route() {
+        let source = MemorySource {
+            payments: vec![],
+            stats: vec![],
+            events: vec![],
+        };
+        let router = router(Arc::new(source), Vec::new(), Arc::default());
+
+        get(router.clone(), "/errors/78").await;
+        get(router.clone(), "/errors/abc").await;
+        get(router.clone(), "/errors/79").await;
+
+        let response = router
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text
+            .contains("commerce_api_requests_total{route=\"/errors/{code}\",status=\"200\"} 2\n"));
+        assert!(text
+            .contains("commerce_api_requests_total{route=\"/errors/{code}\",status=\"400\"} 1\n"));
+    }
+}
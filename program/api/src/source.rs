@@ -0,0 +1,621 @@
+//! Where the API reads on-chain state from.
+//!
+//! `RpcSource` needs nothing but an RPC endpoint. Merchant configs and stats are found with
+//! `getProgramAccounts` filters. Payment accounts do not store their merchant, so payments
+//! are discovered from the `MakePayment` transactions that touched each of the merchant's
+//! configs, then their current status is read from the payment accounts. A payment's events
+//! are decoded from the transactions that touched its account. Checkouts built from payment
+//! links read the config's merchant and operator accounts and a recent blockhash. Webhook
+//! commitments are read from each config's WebhookCommitment PDA.
+
+use std::{future::Future, str::FromStr, sync::Arc};
+
+#[cfg(feature = "webhooks")]
+use commerce_program_client::{accounts::WebhookCommitment, pdas::find_webhook_commitment_pda};
+use commerce_program_client::{
+    accounts::{Merchant, MerchantOperatorConfig, MerchantStats, Operator, Payment},
+    discriminator::{parse_instruction, ParsedInstruction},
+    events::{payment_event_records, PaymentEventRecord},
+    solana_pay::CheckoutAccounts,
+    COMMERCE_PROGRAM_ID,
+};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{
+    client_error::ClientError, nonblocking::rpc_client::RpcClient,
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_hash::Hash;
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_pubkey::Pubkey;
+use solana_rpc_client_api::{
+    config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig},
+    filter::{Memcmp, RpcFilterType},
+};
+use solana_signature::Signature;
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+    UiTransactionEncoding,
+};
+use thiserror::Error;
+
+use crate::{
+    metrics::Metrics,
+    records::{MerchantStatsRecord, PaymentRecord, PaymentStatus},
+};
+
+const MERCHANT_OPERATOR_CONFIG_DISCRIMINATOR: u8 = 2;
+const MERCHANT_STATS_DISCRIMINATOR: u8 = 5;
+#[cfg(feature = "webhooks")]
+const WEBHOOK_COMMITMENT_DISCRIMINATOR: u8 = 18;
+
+/// Offset of `merchant` in `MerchantOperatorConfig` (discriminator, version, bump).
+const CONFIG_MERCHANT_OFFSET: usize = 6;
+/// Offset of `merchant` in `MerchantStats` (discriminator).
+const STATS_MERCHANT_OFFSET: usize = 1;
+
+/// `getMultipleAccounts` limit.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum SourceError {
+    #[error("rpc error: {0}")]
+    Rpc(String),
+    #[error("failed to decode account {0}: {1}")]
+    Decode(Pubkey, std::io::Error),
+}
+
+impl From<ClientError> for SourceError {
+    fn from(error: ClientError) -> Self {
+        Self::Rpc(error.to_string())
+    }
+}
+
+/// Read access to payments and merchant stats.
+pub trait PaymentSource: Send + Sync + 'static {
+    /// Payments made to `merchant`, newest first.
+    fn payments(
+        &self,
+        merchant: &Pubkey,
+    ) -> impl Future<Output = Result<Vec<PaymentRecord>, SourceError>> + Send;
+
+    /// Every `MerchantStats` account of `merchant`.
+    fn merchant_stats(
+        &self,
+        merchant: &Pubkey,
+    ) -> impl Future<Output = Result<Vec<MerchantStatsRecord>, SourceError>> + Send;
+
+    /// Events emitted on `payment`, oldest first.
+    fn payment_events(
+        &self,
+        payment: &Pubkey,
+    ) -> impl Future<Output = Result<Vec<PaymentEventRecord>, SourceError>> + Send;
+}
+
+/// Read access to what a checkout transaction needs.
+pub trait CheckoutSource: Send + Sync + 'static {
+    /// Operator-side accounts of checkouts on `merchant_operator_config`, paid by its
+    /// operator authority, or `None` if it is not a config.
+    fn checkout_accounts(
+        &self,
+        merchant_operator_config: &Pubkey,
+    ) -> impl Future<Output = Result<Option<CheckoutAccounts>, SourceError>> + Send;
+
+    fn latest_blockhash(&self) -> impl Future<Output = Result<Hash, SourceError>> + Send;
+}
+
+/// Read access to the webhook commitments merchants set on their configs.
+#[cfg(feature = "webhooks")]
+pub trait WebhookCommitmentSource: Send + Sync + 'static {
+    /// Commitment set on `merchant_operator_config`, or `None` if it has none.
+    fn webhook_commitment(
+        &self,
+        merchant_operator_config: &Pubkey,
+    ) -> impl Future<Output = Result<Option<[u8; 32]>, SourceError>> + Send;
+}
+
+pub struct RpcSource {
+    rpc: RpcClient,
+    /// Number of recent transactions inspected per merchant operator config or payment.
+    signatures_per_config: usize,
+    metrics: Arc<Metrics>,
+}
+
+impl RpcSource {
+    pub fn new(rpc_url: String, signatures_per_config: usize, metrics: Arc<Metrics>) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed()),
+            signatures_per_config,
+            metrics,
+        }
+    }
+
+    /// Converts a failed `method` call, counting it in the metrics.
+    fn rpc_error(&self, method: &'static str) -> impl FnOnce(ClientError) -> SourceError + '_ {
+        move |error| {
+            self.metrics.record_rpc_error(method);
+            error.into()
+        }
+    }
+
+    async fn program_accounts(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>, SourceError> {
+        let accounts = self
+            .rpc
+            .get_program_accounts_with_config(
+                &COMMERCE_PROGRAM_ID,
+                RpcProgramAccountsConfig {
+                    filters: Some(filters),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        ..RpcAccountInfoConfig::default()
+                    },
+                    ..RpcProgramAccountsConfig::default()
+                },
+            )
+            .await
+            .map_err(self.rpc_error("getProgramAccounts"))?;
+
+        Ok(accounts
+            .into_iter()
+            .map(|(address, account)| (address, account.data))
+            .collect())
+    }
+
+    async fn merchant_configs(&self, merchant: &Pubkey) -> Result<Vec<Pubkey>, SourceError> {
+        let accounts = self
+            .program_accounts(vec![
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    0,
+                    vec![MERCHANT_OPERATOR_CONFIG_DISCRIMINATOR],
+                )),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    CONFIG_MERCHANT_OFFSET,
+                    merchant.to_bytes().to_vec(),
+                )),
+            ])
+            .await?;
+
+        accounts
+            .into_iter()
+            .map(|(address, data)| {
+                MerchantOperatorConfig::from_bytes(&data)
+                    .map(|_| address)
+                    .map_err(|e| SourceError::Decode(address, e))
+            })
+            .collect()
+    }
+
+    /// Recent successful transactions that touched `address`, newest first.
+    async fn transactions(
+        &self,
+        address: &Pubkey,
+    ) -> Result<Vec<(String, EncodedConfirmedTransactionWithStatusMeta)>, SourceError> {
+        let signatures = self
+            .rpc
+            .get_signatures_for_address_with_config(
+                address,
+                GetConfirmedSignaturesForAddress2Config {
+                    limit: Some(self.signatures_per_config),
+                    ..GetConfirmedSignaturesForAddress2Config::default()
+                },
+            )
+            .await
+            .map_err(self.rpc_error("getSignaturesForAddress"))?;
+
+        let mut transactions = Vec::new();
+        for status in signatures.into_iter().filter(|status| status.err.is_none()) {
+            let signature = Signature::from_str(&status.signature)
+                .map_err(|e| SourceError::Rpc(e.to_string()))?;
+            let transaction = self
+                .rpc
+                .get_transaction_with_config(
+                    &signature,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Base64),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )
+                .await
+                .map_err(self.rpc_error("getTransaction"))?;
+            transactions.push((status.signature, transaction));
+        }
+
+        Ok(transactions)
+    }
+
+    async fn made_payments(&self, config: &Pubkey) -> Result<Vec<MadePayment>, SourceError> {
+        Ok(self
+            .transactions(config)
+            .await?
+            .iter()
+            .flat_map(|(signature, transaction)| {
+                made_payments_in_transaction(transaction, signature)
+            })
+            .collect())
+    }
+
+    async fn payment_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<Payment>>, SourceError> {
+        let mut payments = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let accounts = self
+                .rpc
+                .get_multiple_accounts(chunk)
+                .await
+                .map_err(self.rpc_error("getMultipleAccounts"))?;
+            for (address, account) in chunk.iter().zip(accounts) {
+                payments.push(
+                    account
+                        .map(|account| Payment::from_bytes(&account.data))
+                        .transpose()
+                        .map_err(|e| SourceError::Decode(*address, e))?,
+                );
+            }
+        }
+        Ok(payments)
+    }
+}
+
+impl PaymentSource for RpcSource {
+    async fn payments(&self, merchant: &Pubkey) -> Result<Vec<PaymentRecord>, SourceError> {
+        let mut made = Vec::new();
+        for config in self.merchant_configs(merchant).await? {
+            made.extend(self.made_payments(&config).await?);
+        }
+
+        let addresses: Vec<Pubkey> = made.iter().map(|payment| payment.address).collect();
+        let accounts = self.payment_accounts(&addresses).await?;
+
+        let mut records: Vec<PaymentRecord> = made
+            .into_iter()
+            .zip(accounts)
+            .map(|(made, account)| made.into_record(account.as_ref()))
+            .collect();
+        records.sort_by_key(|record| std::cmp::Reverse(record.created_at));
+        Ok(records)
+    }
+
+    async fn merchant_stats(
+        &self,
+        merchant: &Pubkey,
+    ) -> Result<Vec<MerchantStatsRecord>, SourceError> {
+        let accounts = self
+            .program_accounts(vec![
+                RpcFilterType::DataSize(MerchantStats::LEN as u64),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![MERCHANT_STATS_DISCRIMINATOR])),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    STATS_MERCHANT_OFFSET,
+                    merchant.to_bytes().to_vec(),
+                )),
+            ])
+            .await?;
+
+        accounts
+            .into_iter()
+            .map(|(address, data)| {
+                MerchantStats::from_bytes(&data)
+                    .map(|stats| MerchantStatsRecord::new(address, &stats))
+                    .map_err(|e| SourceError::Decode(address, e))
+            })
+            .collect()
+    }
+
+    async fn payment_events(
+        &self,
+        payment: &Pubkey,
+    ) -> Result<Vec<PaymentEventRecord>, SourceError> {
+        Ok(self
+            .transactions(payment)
+            .await?
+            .iter()
+            .rev()
+            .flat_map(|(signature, transaction)| payment_event_records(transaction, signature))
+            .filter(|event| event.payment == *payment)
+            .collect())
+    }
+}
+
+impl CheckoutSource for RpcSource {
+    async fn checkout_accounts(
+        &self,
+        merchant_operator_config: &Pubkey,
+    ) -> Result<Option<CheckoutAccounts>, SourceError> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(merchant_operator_config, CommitmentConfig::confirmed())
+            .await
+            .map_err(self.rpc_error("getAccountInfo"))?
+            .value;
+        let Some(account) = account.filter(|account| {
+            account.owner == COMMERCE_PROGRAM_ID
+                && account.data.first() == Some(&MERCHANT_OPERATOR_CONFIG_DISCRIMINATOR)
+        }) else {
+            return Ok(None);
+        };
+        let config = MerchantOperatorConfig::from_bytes(&account.data)
+            .map_err(|e| SourceError::Decode(*merchant_operator_config, e))?;
+
+        let accounts = self
+            .rpc
+            .get_multiple_accounts(&[config.operator, config.merchant])
+            .await
+            .map_err(self.rpc_error("getMultipleAccounts"))?;
+        let [Some(operator), Some(merchant)] = <[_; 2]>::try_from(accounts).map_err(|_| {
+            SourceError::Rpc("getMultipleAccounts did not return 2 accounts".into())
+        })?
+        else {
+            return Ok(None);
+        };
+        let operator = Operator::from_bytes(&operator.data)
+            .map_err(|e| SourceError::Decode(config.operator, e))?;
+        let merchant = Merchant::from_bytes(&merchant.data)
+            .map_err(|e| SourceError::Decode(config.merchant, e))?;
+
+        Ok(Some(CheckoutAccounts {
+            payer: operator.owner,
+            operator_authority: operator.owner,
+            operator: config.operator,
+            merchant: config.merchant,
+            settlement_wallet: merchant.settlement_wallet,
+        }))
+    }
+
+    async fn latest_blockhash(&self) -> Result<Hash, SourceError> {
+        self.rpc
+            .get_latest_blockhash()
+            .await
+            .map_err(self.rpc_error("getLatestBlockhash"))
+    }
+}
+
+#[cfg(feature = "webhooks")]
+impl WebhookCommitmentSource for RpcSource {
+    async fn webhook_commitment(
+        &self,
+        merchant_operator_config: &Pubkey,
+    ) -> Result<Option<[u8; 32]>, SourceError> {
+        let (address, _) = find_webhook_commitment_pda(merchant_operator_config);
+        let account = self
+            .rpc
+            .get_account_with_commitment(&address, CommitmentConfig::confirmed())
+            .await
+            .map_err(self.rpc_error("getAccountInfo"))?
+            .value;
+        let Some(account) = account.filter(|account| {
+            account.owner == COMMERCE_PROGRAM_ID
+                && account.data.first() == Some(&WEBHOOK_COMMITMENT_DISCRIMINATOR)
+        }) else {
+            return Ok(None);
+        };
+        let webhook_commitment = WebhookCommitment::from_bytes(&account.data)
+            .map_err(|e| SourceError::Decode(address, e))?;
+        Ok(Some(webhook_commitment.commitment))
+    }
+}
+
+/// A payment as created by a `MakePayment` instruction.
+#[derive(Clone, Debug, PartialEq)]
+struct MadePayment {
+    address: Pubkey,
+    buyer: Pubkey,
+    operator: Pubkey,
+    merchant: Pubkey,
+    merchant_operator_config: Pubkey,
+    mint: Pubkey,
+    order_id: u32,
+    amount: u64,
+    signature: String,
+}
+
+impl MadePayment {
+    fn into_record(self, account: Option<&Payment>) -> PaymentRecord {
+        PaymentRecord {
+            address: self.address,
+            merchant: self.merchant,
+            operator: self.operator,
+            merchant_operator_config: self.merchant_operator_config,
+            buyer: self.buyer,
+            mint: self.mint,
+            order_id: self.order_id,
+            amount: self.amount,
+            status: account.map_or(PaymentStatus::Closed, |payment| payment.status.into()),
+            created_at: account.map(|payment| payment.created_at),
+            signature: self.signature,
+        }
+    }
+}
+
+fn made_payments_in_transaction(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    signature: &str,
+) -> Vec<MadePayment> {
+    let Some(decoded) = transaction.transaction.transaction.decode() else {
+        return Vec::new();
+    };
+
+    // v0 transactions may load accounts from lookup tables; they follow the static keys
+    let mut account_keys = decoded.message.static_account_keys().to_vec();
+    if let Some(meta) = &transaction.transaction.meta {
+        if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+            account_keys.extend(
+                loaded
+                    .writable
+                    .iter()
+                    .chain(&loaded.readonly)
+                    .filter_map(|key| Pubkey::from_str(key).ok()),
+            );
+        }
+    }
+
+    made_payments_in_instructions(&account_keys, decoded.message.instructions(), signature)
+}
+
+/// Decodes top-level `MakePayment` instructions. Payments made through CPI are not found.
+fn made_payments_in_instructions(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    signature: &str,
+) -> Vec<MadePayment> {
+    instructions
+        .iter()
+        .filter(|ix| account_keys.get(ix.program_id_index as usize) == Some(&COMMERCE_PROGRAM_ID))
+        .filter_map(|ix| {
+            let Ok(ParsedInstruction::MakePayment(args)) = parse_instruction(&ix.data) else {
+                return None;
+            };
+            let key = |index: usize| {
+                ix.accounts
+                    .get(index)
+                    .and_then(|i| account_keys.get(*i as usize))
+                    .copied()
+            };
+
+            Some(MadePayment {
+                address: key(1)?,
+                buyer: key(3)?,
+                operator: key(4)?,
+                merchant: key(5)?,
+                merchant_operator_config: key(6)?,
+                mint: key(7)?,
+                order_id: args.order_id,
+                amount: args.amount,
+                signature: signature.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commerce_program_client::instructions::MakePaymentBuilder;
+
+    fn compile(
+        account_keys: &[Pubkey],
+        ix: &solana_instruction::Instruction,
+    ) -> CompiledInstruction {
+        let index = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+        CompiledInstruction {
+            program_id_index: index(&ix.program_id),
+            accounts: ix.accounts.iter().map(|meta| index(&meta.pubkey)).collect(),
+            data: ix.data.clone(),
+        }
+    }
+
+    #[test]
+    fn test_made_payments_in_instructions() {
+        let keys: Vec<Pubkey> = (0..18).map(|_| Pubkey::new_unique()).collect();
+        let ix = MakePaymentBuilder::new()
+            .payer(keys[0])
+            .payment(keys[1])
+            .operator_authority(keys[2])
+            .buyer(keys[3])
+            .operator(keys[4])
+            .merchant(keys[5])
+            .merchant_operator_config(keys[6])
+            .mint(keys[7])
+            .buyer_ata(keys[8])
+            .escrow_authority(keys[17])
+            .merchant_escrow_ata(keys[9])
+            .merchant_settlement_ata(keys[10])
+            .settlement_wallet(keys[11])
+            .token_program(keys[12])
+            .associated_token_program(keys[13])
+            .system_program(keys[14])
+            .event_authority(keys[15])
+            .order_id(42)
+            .amount(1_000)
+            .idempotency_key([0; 16])
+            .instruction();
+
+        let mut account_keys = keys.clone();
+        account_keys.push(COMMERCE_PROGRAM_ID);
+        let compiled = compile(&account_keys, &ix);
+
+        let made = made_payments_in_instructions(&account_keys, &[compiled], "sig");
+
+        assert_eq!(
+            made,
+            vec![MadePayment {
+                address: keys[1],
+                buyer: keys[3],
+                operator: keys[4],
+                merchant: keys[5],
+                merchant_operator_config: keys[6],
+                mint: keys[7],
+                order_id: 42,
+                amount: 1_000,
+                signature: "sig".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_made_payments_ignores_other_programs_and_instructions() {
+        let other_program = Pubkey::new_unique();
+        let account_keys = vec![other_program, COMMERCE_PROGRAM_ID];
+
+        let instructions = [
+            // MakePayment discriminator sent to another program
+            CompiledInstruction {
+                program_id_index: 0,
+                accounts: vec![],
+                data: vec![3],
+            },
+            // ClearPayment sent to the commerce program
+            CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![],
+                data: vec![4],
+            },
+        ];
+
+        assert!(made_payments_in_instructions(&account_keys, &instructions, "sig").is_empty());
+    }
+
+    #[test]
+    fn test_into_record_status() {
+        let made = MadePayment {
+            address: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            merchant: Pubkey::new_unique(),
+            merchant_operator_config: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            order_id: 1,
+            amount: 500,
+            signature: "sig".to_string(),
+        };
+        let payment = Payment {
+            discriminator: 3,
+            order_id: 1,
+            amount: 500,
+            created_at: 1_700_000_000,
+            status: commerce_program_client::types::Status::Cleared,
+            bump: 255,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: commerce_program_client::types::FeeOverride {
+                fee: 0,
+                fee_type: commerce_program_client::types::FeeType::Bps,
+            },
+            references: [Pubkey::default(); 2],
+            rent_payer: Pubkey::default(),
+            has_order: false,
+        };
+
+        let record = made.clone().into_record(Some(&payment));
+        assert_eq!(record.status, PaymentStatus::Cleared);
+        assert_eq!(record.created_at, Some(1_700_000_000));
+
+        let record = made.into_record(None);
+        assert_eq!(record.status, PaymentStatus::Closed);
+        assert_eq!(record.created_at, None);
+    }
+}
@@ -0,0 +1,771 @@
+//! Signed payment webhooks, for merchants who would rather receive payment changes than poll
+//! for them. Built with the `webhooks` feature.
+//!
+//! Each endpoint in the `COMMERCE_API_WEBHOOKS` file names a merchant, a URL and its own
+//! signing secret. Every poll diffs the merchant's payments against what the endpoint was
+//! last sent and queues a `payment.created` or `payment.status_changed` event per change in
+//! the durable [`Outbox`]. Queued events are POSTed with:
+//! - `Idempotency-Key`: the event id, `<payment address>:<status>`, the same on every retry
+//! - `Commerce-Signature`: `t=<unix timestamp>,v1=<hex HMAC-SHA256 of "<t>.<body>">` keyed by
+//!   the endpoint's secret, as Stripe signs its webhooks
+//!
+//! A delivery succeeds on a 2xx response; anything else is retried with exponential backoff
+//! until `MAX_ATTEMPTS`, then dead-lettered in the outbox.
+//!
+//! An endpoint with `verify_commitment` is only sent a payment's events while the payment's
+//! config has a webhook commitment on-chain equal to
+//! [`commerce_program_client::webhook_commitment::webhook_commitment`] of the endpoint's id
+//! and URL, so an endpoint changed in the file without the merchant setting a new commitment
+//! receives nothing. Its deliveries stay queued until the commitments match.
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fs,
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use commerce_program_client::webhook_commitment::webhook_commitment;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Deserializer, Serialize};
+use sha2::Sha256;
+use solana_pubkey::Pubkey;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::{
+    changes::{payment_changes, ChangeKind, PaymentChange},
+    metrics::Metrics,
+    outbox::{Delivery, Outbox, OutboxError},
+    records::{PaymentRecord, PaymentStatus},
+    source::{PaymentSource, WebhookCommitmentSource},
+};
+
+pub const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+pub const SIGNATURE_HEADER: &str = "Commerce-Signature";
+
+/// How long an endpoint has to respond before the attempt counts as failed.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum WebhookConfigError {
+    #[error("failed to read webhook endpoints {0}: {1}")]
+    Io(PathBuf, io::Error),
+    #[error("failed to parse webhook endpoints {0}: {1}")]
+    Parse(PathBuf, serde_json::Error),
+    #[error("duplicate webhook endpoint id: {0}")]
+    DuplicateId(String),
+    #[error("webhook endpoint {0} has no secret")]
+    MissingSecret(String),
+}
+
+fn pubkey_from_str<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    Pubkey::from_str(&value).map_err(serde::de::Error::custom)
+}
+
+/// An endpoint receiving one merchant's payment webhooks.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct WebhookEndpoint {
+    /// Names the endpoint in the outbox; changing it resends every change since.
+    pub id: String,
+    pub url: String,
+    #[serde(deserialize_with = "pubkey_from_str")]
+    pub merchant: Pubkey,
+    pub secret: String,
+    /// Deliver only while the endpoint matches the webhook commitment of the payment's
+    /// config.
+    #[serde(default)]
+    pub verify_commitment: bool,
+}
+
+impl WebhookEndpoint {
+    /// What the merchant commits to on-chain for this endpoint.
+    pub fn commitment(&self) -> [u8; 32] {
+        webhook_commitment(&self.id, &self.url)
+    }
+}
+
+/// Reads the JSON array of endpoints at `path`.
+pub fn load_endpoints(path: &Path) -> Result<Vec<WebhookEndpoint>, WebhookConfigError> {
+    let data = fs::read(path).map_err(|error| WebhookConfigError::Io(path.into(), error))?;
+    let endpoints: Vec<WebhookEndpoint> = serde_json::from_slice(&data)
+        .map_err(|error| WebhookConfigError::Parse(path.into(), error))?;
+
+    for (index, endpoint) in endpoints.iter().enumerate() {
+        if endpoint.secret.is_empty() {
+            return Err(WebhookConfigError::MissingSecret(endpoint.id.clone()));
+        }
+        if endpoints[..index]
+            .iter()
+            .any(|other| other.id == endpoint.id)
+        {
+            return Err(WebhookConfigError::DuplicateId(endpoint.id.clone()));
+        }
+    }
+    Ok(endpoints)
+}
+
+/// Body of a webhook delivery.
+#[derive(Debug, Serialize)]
+struct WebhookEvent<'a> {
+    id: &'a str,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    /// Unix timestamp of the poll that found the change.
+    created_at: u64,
+    previous_status: Option<PaymentStatus>,
+    payment: &'a PaymentRecord,
+}
+
+fn status_name(status: PaymentStatus) -> &'static str {
+    match status {
+        PaymentStatus::Paid => "paid",
+        PaymentStatus::Cleared => "cleared",
+        PaymentStatus::Refunded => "refunded",
+        PaymentStatus::RefundApproved => "refund_approved",
+        PaymentStatus::Authorized => "authorized",
+        PaymentStatus::Closed => "closed",
+    }
+}
+
+fn delivery(endpoint: &WebhookEndpoint, change: &PaymentChange, now: u64) -> Delivery {
+    let id = format!(
+        "{}:{}",
+        change.payment.address,
+        status_name(change.payment.status)
+    );
+    let event = WebhookEvent {
+        id: &id,
+        kind: match change.kind {
+            ChangeKind::StatusChanged => "payment.status_changed",
+            ChangeKind::Existing | ChangeKind::Created => "payment.created",
+        },
+        created_at: now,
+        previous_status: change.previous_status,
+        payment: change.payment,
+    };
+
+    Delivery {
+        body: serde_json::to_string(&event).expect("webhook events serialize"),
+        id,
+        endpoint: endpoint.id.clone(),
+        attempts: 0,
+        next_attempt_at: now,
+        last_error: None,
+    }
+}
+
+fn hmac_sha256_hex(secret: &str, message: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any size");
+    mac.update(message);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// `Commerce-Signature` header value for `body` sent at `timestamp`. Receivers recompute the
+/// HMAC over `"<t>.<body>"` and reject stale timestamps, so a captured delivery cannot be
+/// replayed later.
+pub fn signature_header(secret: &str, timestamp: u64, body: &str) -> String {
+    let signature = hmac_sha256_hex(secret, format!("{timestamp}.{body}").as_bytes());
+    format!("t={timestamp},v1={signature}")
+}
+
+pub struct WebhookRequest<'a> {
+    pub url: &'a str,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: &'a str,
+}
+
+/// Sends webhook requests.
+pub trait WebhookSender: Send + Sync + 'static {
+    /// Posts `request`, succeeding only on a 2xx response.
+    fn send(&self, request: WebhookRequest<'_>) -> impl Future<Output = Result<(), String>> + Send;
+}
+
+pub struct HttpSender {
+    client: reqwest::Client,
+}
+
+impl HttpSender {
+    pub fn new() -> Result<Self, reqwest::Error> {
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(DELIVERY_TIMEOUT)
+                .build()?,
+        })
+    }
+}
+
+impl WebhookSender for HttpSender {
+    async fn send(&self, request: WebhookRequest<'_>) -> Result<(), String> {
+        let mut builder = self.client.post(request.url).body(request.body.to_string());
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+        builder
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+}
+
+/// Span of one delivery attempt, keyed by the payment and order id the event is about.
+fn delivery_span(delivery: &Delivery) -> tracing::Span {
+    let payment = delivery
+        .id
+        .split_once(':')
+        .map_or(delivery.id.as_str(), |(payment, _)| payment);
+    let order_id = serde_json::from_str::<serde_json::Value>(&delivery.body)
+        .ok()
+        .and_then(|event| event["payment"]["order_id"].as_u64());
+    tracing::info_span!(
+        "webhook_delivery",
+        endpoint = %delivery.endpoint,
+        payment,
+        order_id,
+        event = %delivery.id,
+    )
+}
+
+/// Config of the payment a delivery is about.
+fn delivery_config(delivery: &Delivery) -> Option<Pubkey> {
+    let event = serde_json::from_str::<serde_json::Value>(&delivery.body).ok()?;
+    Pubkey::from_str(event["payment"]["merchant_operator_config"].as_str()?).ok()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+pub struct Webhooks<S, T> {
+    source: Arc<S>,
+    sender: T,
+    endpoints: Vec<WebhookEndpoint>,
+    outbox: Outbox,
+    metrics: Arc<Metrics>,
+    poll_interval: Duration,
+}
+
+impl<S: PaymentSource + WebhookCommitmentSource, T: WebhookSender> Webhooks<S, T> {
+    pub fn new(
+        source: Arc<S>,
+        sender: T,
+        endpoints: Vec<WebhookEndpoint>,
+        outbox: Outbox,
+        metrics: Arc<Metrics>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            source,
+            sender,
+            endpoints,
+            outbox,
+            metrics,
+            poll_interval,
+        }
+    }
+
+    /// Polls and delivers until the outbox can no longer be written.
+    pub async fn run(mut self) -> Result<(), OutboxError> {
+        let mut ticker = tokio::time::interval(self.poll_interval);
+        loop {
+            ticker.tick().await;
+            let now = unix_now();
+            self.poll(now).await?;
+            self.deliver(now).await?;
+        }
+    }
+
+    /// Queues an event for every payment created or changed since each endpoint's last poll.
+    ///
+    /// An endpoint's first poll only records the payments that already exist. Read errors
+    /// skip the endpoint until the next poll.
+    async fn poll(&mut self, now: u64) -> Result<(), OutboxError> {
+        let mut payments_by_merchant: HashMap<Pubkey, Vec<PaymentRecord>> = HashMap::new();
+
+        for endpoint in &self.endpoints {
+            let payments = match payments_by_merchant.entry(endpoint.merchant) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => match self.source.payments(&endpoint.merchant).await {
+                    Ok(payments) => entry.insert(payments),
+                    Err(error) => {
+                        tracing::warn!(endpoint = %endpoint.id, %error, "payments unavailable");
+                        continue;
+                    }
+                },
+            };
+
+            let previous = self.outbox.seen(&endpoint.id);
+            let mut seen: HashMap<Pubkey, PaymentStatus> = previous
+                .into_iter()
+                .flatten()
+                .filter_map(|(address, status)| Some((Pubkey::from_str(address).ok()?, *status)))
+                .collect();
+            let deliveries: Vec<Delivery> =
+                payment_changes(&mut seen, payments, previous.is_none())
+                    .iter()
+                    .inspect(|change| {
+                        self.metrics.record_payment_change("webhooks", change.kind);
+                        tracing::debug!(
+                            endpoint = %endpoint.id,
+                            payment = %change.payment.address,
+                            order_id = change.payment.order_id,
+                            kind = change.kind.name(),
+                            "payment change"
+                        );
+                    })
+                    .filter(|change| change.kind != ChangeKind::Existing)
+                    .map(|change| delivery(endpoint, change, now))
+                    .collect();
+
+            // Payments that dropped out of the source's window are forgotten; should one come
+            // back, its event id is unchanged and receivers dedupe it
+            seen.retain(|address, _| payments.iter().any(|payment| payment.address == *address));
+            let seen: HashMap<String, PaymentStatus> = seen
+                .into_iter()
+                .map(|(address, status)| (address.to_string(), status))
+                .collect();
+
+            if deliveries.is_empty() && previous == Some(&seen) {
+                continue;
+            }
+            self.outbox.enqueue(&endpoint.id, seen, deliveries)?;
+        }
+        Ok(())
+    }
+
+    /// Whether `endpoint` may be sent `delivery`: always, unless it verifies commitments and
+    /// the payment's config is not committed to it. Commitments are read once per config
+    /// into `commitments`.
+    async fn committed(
+        &self,
+        endpoint: &WebhookEndpoint,
+        delivery: &Delivery,
+        commitments: &mut HashMap<Pubkey, Option<[u8; 32]>>,
+    ) -> bool {
+        if !endpoint.verify_commitment {
+            return true;
+        }
+        let Some(config) = delivery_config(delivery) else {
+            tracing::warn!(endpoint = %endpoint.id, event = %delivery.id, "event has no config");
+            return false;
+        };
+        let commitment = match commitments.entry(config) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => match self.source.webhook_commitment(&config).await {
+                Ok(commitment) => *entry.insert(commitment),
+                Err(error) => {
+                    tracing::warn!(endpoint = %endpoint.id, %error, "webhook commitment unavailable");
+                    return false;
+                }
+            },
+        };
+        if commitment != Some(endpoint.commitment()) {
+            tracing::warn!(
+                endpoint = %endpoint.id,
+                merchant_operator_config = %config,
+                "endpoint does not match the config's webhook commitment"
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Attempts every due delivery once. Deliveries to endpoints no longer configured, or not
+    /// matching their config's webhook commitment, stay queued.
+    async fn deliver(&mut self, now: u64) -> Result<(), OutboxError> {
+        let mut commitments = HashMap::new();
+        for delivery in self.outbox.due(now) {
+            let Some(endpoint) = self
+                .endpoints
+                .iter()
+                .find(|endpoint| endpoint.id == delivery.endpoint)
+            else {
+                continue;
+            };
+            if !self.committed(endpoint, &delivery, &mut commitments).await {
+                continue;
+            }
+
+            let request = WebhookRequest {
+                url: &endpoint.url,
+                headers: vec![
+                    ("Content-Type", "application/json".to_string()),
+                    (IDEMPOTENCY_HEADER, delivery.id.clone()),
+                    (
+                        SIGNATURE_HEADER,
+                        signature_header(&endpoint.secret, now, &delivery.body),
+                    ),
+                ],
+                body: &delivery.body,
+            };
+            let span = delivery_span(&delivery);
+            let started = Instant::now();
+            let result = self.sender.send(request).instrument(span.clone()).await;
+            self.metrics.record_webhook_delivery(
+                &delivery.endpoint,
+                result.is_ok(),
+                started.elapsed(),
+            );
+
+            let _entered = span.enter();
+            match result {
+                Ok(()) => {
+                    tracing::info!("webhook delivered");
+                    self.outbox.delivered(&delivery.endpoint, &delivery.id)?
+                }
+                Err(error) => {
+                    tracing::warn!(%error, attempts = delivery.attempts + 1, "webhook failed");
+                    self.outbox
+                        .failed(&delivery.endpoint, &delivery.id, error, now)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{records::MerchantStatsRecord, source::SourceError};
+    use commerce_program_client::events::PaymentEventRecord;
+    use serde_json::Value;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemorySource {
+        payments: Mutex<Vec<PaymentRecord>>,
+        commitments: Mutex<HashMap<Pubkey, [u8; 32]>>,
+    }
+
+    impl WebhookCommitmentSource for MemorySource {
+        async fn webhook_commitment(
+            &self,
+            merchant_operator_config: &Pubkey,
+        ) -> Result<Option<[u8; 32]>, SourceError> {
+            Ok(self
+                .commitments
+                .lock()
+                .unwrap()
+                .get(merchant_operator_config)
+                .copied())
+        }
+    }
+
+    impl PaymentSource for MemorySource {
+        async fn payments(&self, merchant: &Pubkey) -> Result<Vec<PaymentRecord>, SourceError> {
+            Ok(self
+                .payments
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|payment| payment.merchant == *merchant)
+                .cloned()
+                .collect())
+        }
+
+        async fn merchant_stats(
+            &self,
+            _merchant: &Pubkey,
+        ) -> Result<Vec<MerchantStatsRecord>, SourceError> {
+            Ok(vec![])
+        }
+
+        async fn payment_events(
+            &self,
+            _payment: &Pubkey,
+        ) -> Result<Vec<PaymentEventRecord>, SourceError> {
+            Ok(vec![])
+        }
+    }
+
+    /// URL, headers and body of a sent request.
+    type SentRequest = (String, HashMap<&'static str, String>, String);
+
+    /// Records every request, failing the first `failures` of them.
+    #[derive(Default)]
+    struct RecordingSender {
+        requests: Mutex<Vec<SentRequest>>,
+        failures: Mutex<usize>,
+    }
+
+    impl WebhookSender for RecordingSender {
+        async fn send(&self, request: WebhookRequest<'_>) -> Result<(), String> {
+            self.requests.lock().unwrap().push((
+                request.url.to_string(),
+                request.headers.into_iter().collect(),
+                request.body.to_string(),
+            ));
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err("503 Service Unavailable".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    fn payment(merchant: Pubkey, order_id: u32, status: PaymentStatus) -> PaymentRecord {
+        PaymentRecord {
+            address: Pubkey::new_unique(),
+            merchant,
+            operator: Pubkey::new_unique(),
+            merchant_operator_config: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            order_id,
+            amount: 1_000,
+            status,
+            created_at: Some(1_700_000_000),
+            signature: "sig".to_string(),
+        }
+    }
+
+    fn endpoint(merchant: Pubkey) -> WebhookEndpoint {
+        WebhookEndpoint {
+            id: "shop".to_string(),
+            url: "https://shop.example/webhooks".to_string(),
+            merchant,
+            secret: "whsec_test".to_string(),
+            verify_commitment: false,
+        }
+    }
+
+    #[test]
+    fn test_signature_header() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            hmac_sha256_hex("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            signature_header("whsec_test", 1_700_000_000, "{}"),
+            format!(
+                "t=1700000000,v1={}",
+                hmac_sha256_hex("whsec_test", b"1700000000.{}")
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_poll_and_deliver_with_retries() {
+        let dir = tempfile::tempdir().unwrap();
+        let merchant = Pubkey::new_unique();
+        let source = Arc::new(MemorySource::default());
+        source
+            .payments
+            .lock()
+            .unwrap()
+            .push(payment(merchant, 1, PaymentStatus::Paid));
+        let sender = RecordingSender {
+            failures: Mutex::new(1),
+            ..RecordingSender::default()
+        };
+        let outbox = Outbox::open(dir.path().join("outbox.json")).unwrap();
+        let metrics = Arc::new(Metrics::default());
+        let mut webhooks = Webhooks::new(
+            Arc::clone(&source),
+            sender,
+            vec![endpoint(merchant)],
+            outbox,
+            Arc::clone(&metrics),
+            Duration::from_secs(5),
+        );
+
+        // Existing payments are recorded without being sent
+        webhooks.poll(100).await.unwrap();
+        assert!(webhooks.outbox.pending().is_empty());
+
+        let created = payment(merchant, 2, PaymentStatus::Paid);
+        source.payments.lock().unwrap().insert(0, created.clone());
+        source.payments.lock().unwrap()[1].status = PaymentStatus::Cleared;
+        webhooks.poll(105).await.unwrap();
+        assert_eq!(webhooks.outbox.pending().len(), 2);
+
+        // The first attempt fails and is retried after the backoff
+        webhooks.deliver(105).await.unwrap();
+        assert_eq!(webhooks.outbox.pending().len(), 1);
+        webhooks.deliver(114).await.unwrap();
+        assert_eq!(webhooks.sender.requests.lock().unwrap().len(), 2);
+        webhooks.deliver(115).await.unwrap();
+        assert!(webhooks.outbox.pending().is_empty());
+
+        let requests = webhooks.sender.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        let (url, headers, body) = &requests[0];
+        let event: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(url, "https://shop.example/webhooks");
+        assert_eq!(event["type"], "payment.status_changed");
+        assert_eq!(event["previous_status"], "paid");
+        assert_eq!(event["payment"]["status"], "cleared");
+        assert_eq!(event["payment"]["order_id"], 1);
+
+        // The retry carries the same idempotency key and a fresh signature
+        let (_, retry_headers, retry_body) = &requests[2];
+        assert_eq!(retry_body, body);
+        assert_eq!(
+            retry_headers[IDEMPOTENCY_HEADER],
+            headers[IDEMPOTENCY_HEADER]
+        );
+        assert_eq!(
+            retry_headers[SIGNATURE_HEADER],
+            signature_header("whsec_test", 115, body)
+        );
+
+        let event: Value = serde_json::from_str(&requests[1].2).unwrap();
+        assert_eq!(event["type"], "payment.created");
+        assert_eq!(event["id"], format!("{}:paid", created.address));
+        assert_eq!(event["previous_status"], Value::Null);
+
+        let text = metrics.render();
+        assert!(text.contains(
+            "commerce_api_webhook_deliveries_total{endpoint=\"shop\",result=\"ok\"} 2\n"
+        ));
+        assert!(text.contains(
+            "commerce_api_webhook_deliveries_total{endpoint=\"shop\",result=\"failed\"} 1\n"
+        ));
+        assert!(text.contains("commerce_api_webhook_delivery_seconds_count{endpoint=\"shop\"} 3\n"));
+    }
+
+    #[tokio::test]
+    async fn test_poll_resumes_from_outbox() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outbox.json");
+        let merchant = Pubkey::new_unique();
+        let source = Arc::new(MemorySource::default());
+        source
+            .payments
+            .lock()
+            .unwrap()
+            .push(payment(merchant, 1, PaymentStatus::Paid));
+
+        let webhooks = |outbox| {
+            Webhooks::new(
+                Arc::clone(&source),
+                RecordingSender::default(),
+                vec![endpoint(merchant)],
+                outbox,
+                Arc::default(),
+                Duration::from_secs(5),
+            )
+        };
+        webhooks(Outbox::open(&path).unwrap())
+            .poll(100)
+            .await
+            .unwrap();
+
+        // A change made while the service was down is sent after a restart
+        source.payments.lock().unwrap()[0].status = PaymentStatus::Refunded;
+        let mut restarted = webhooks(Outbox::open(&path).unwrap());
+        restarted.poll(200).await.unwrap();
+        assert_eq!(restarted.outbox.pending().len(), 1);
+
+        // And is still queued after another restart
+        let restarted = webhooks(Outbox::open(&path).unwrap());
+        assert_eq!(restarted.outbox.pending().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_verifies_commitment() {
+        let dir = tempfile::tempdir().unwrap();
+        let merchant = Pubkey::new_unique();
+        let source = Arc::new(MemorySource::default());
+        let endpoint = WebhookEndpoint {
+            verify_commitment: true,
+            ..endpoint(merchant)
+        };
+        let mut webhooks = Webhooks::new(
+            Arc::clone(&source),
+            RecordingSender::default(),
+            vec![endpoint.clone()],
+            Outbox::open(dir.path().join("outbox.json")).unwrap(),
+            Arc::default(),
+            Duration::from_secs(5),
+        );
+        webhooks.poll(100).await.unwrap();
+        let paid = payment(merchant, 1, PaymentStatus::Paid);
+        let config = paid.merchant_operator_config;
+        source.payments.lock().unwrap().push(paid);
+        webhooks.poll(105).await.unwrap();
+
+        // No commitment on the config
+        webhooks.deliver(105).await.unwrap();
+        assert!(webhooks.sender.requests.lock().unwrap().is_empty());
+
+        // Committed to another URL
+        let moved = WebhookEndpoint {
+            url: "https://attacker.example/webhooks".to_string(),
+            ..endpoint.clone()
+        };
+        source
+            .commitments
+            .lock()
+            .unwrap()
+            .insert(config, moved.commitment());
+        webhooks.deliver(105).await.unwrap();
+        assert!(webhooks.sender.requests.lock().unwrap().is_empty());
+        assert_eq!(webhooks.outbox.pending().len(), 1);
+
+        source
+            .commitments
+            .lock()
+            .unwrap()
+            .insert(config, endpoint.commitment());
+        webhooks.deliver(105).await.unwrap();
+        assert_eq!(webhooks.sender.requests.lock().unwrap().len(), 1);
+        assert!(webhooks.outbox.pending().is_empty());
+    }
+
+    #[test]
+    fn test_load_endpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("webhooks.json");
+        let merchant = Pubkey::new_unique();
+
+        fs::write(
+            &path,
+            format!(
+                r#"[{{"id": "shop", "url": "https://shop.example/webhooks", "merchant": "{merchant}", "secret": "whsec_test"}}]"#
+            ),
+        )
+        .unwrap();
+        assert_eq!(load_endpoints(&path).unwrap(), vec![endpoint(merchant)]);
+
+        fs::write(
+            &path,
+            format!(
+                r#"[{{"id": "shop", "url": "a", "merchant": "{merchant}", "secret": "a"}},
+                    {{"id": "shop", "url": "b", "merchant": "{merchant}", "secret": "b"}}]"#
+            ),
+        )
+        .unwrap();
+        assert!(matches!(
+            load_endpoints(&path),
+            Err(WebhookConfigError::DuplicateId(id)) if id == "shop"
+        ));
+
+        fs::write(
+            &path,
+            format!(r#"[{{"id": "shop", "url": "a", "merchant": "{merchant}", "secret": ""}}]"#),
+        )
+        .unwrap();
+        assert!(matches!(
+            load_endpoints(&path),
+            Err(WebhookConfigError::MissingSecret(_))
+        ));
+    }
+}
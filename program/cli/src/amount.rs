@@ -0,0 +1,62 @@
+//! Amount conversion for `commerce-cli amount`.
+//!
+//! Converts between display amounts such as `10.50` and the raw amounts the program stores,
+//! using the decimals of the mint the amount is in, so an amount meant for a 6-decimal mint
+//! is never encoded for a 9-decimal one.
+
+use commerce_program_client::amount::{format_amount, parse_amount, AmountError};
+use serde_json::{json, Value};
+use solana_pubkey::Pubkey;
+
+/// `amount` of `mint` in both raw and display units. `amount` is raw when `raw` is set and
+/// a display amount otherwise.
+pub fn amount_json(
+    mint: &Pubkey,
+    decimals: u8,
+    amount: &str,
+    raw: bool,
+) -> Result<Value, AmountError> {
+    let raw_amount = if raw {
+        amount
+            .parse()
+            .map_err(|_| AmountError::Invalid(amount.to_string()))?
+    } else {
+        parse_amount(amount, decimals)?
+    };
+
+    Ok(json!({
+        "mint": mint.to_string(),
+        "decimals": decimals,
+        "raw": raw_amount,
+        "display": format_amount(raw_amount, decimals),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_json() {
+        let mint = Pubkey::new_unique();
+
+        let json = amount_json(&mint, 6, "10.50", false).unwrap();
+        assert_eq!(json["mint"], mint.to_string());
+        assert_eq!(json["decimals"], 6);
+        assert_eq!(json["raw"], 10_500_000);
+        assert_eq!(json["display"], "10.5");
+
+        let json = amount_json(&mint, 9, "10500000", true).unwrap();
+        assert_eq!(json["raw"], 10_500_000);
+        assert_eq!(json["display"], "0.0105");
+
+        assert_eq!(
+            amount_json(&mint, 6, "10.5", true),
+            Err(AmountError::Invalid("10.5".to_string()))
+        );
+        assert!(matches!(
+            amount_json(&mint, 6, "0.0000001", false),
+            Err(AmountError::TooManyDecimals { .. })
+        ));
+    }
+}
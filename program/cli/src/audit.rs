@@ -0,0 +1,87 @@
+//! Escrow audits for `commerce-cli audit`.
+//!
+//! The payments audited are those named by the `PaymentCreated` events of the config's
+//! transactions, with the mint each was made in; see
+//! [`commerce_program_client::escrow_audit`] for what each mint's escrow should hold.
+
+use std::collections::HashMap;
+
+use commerce_program_client::escrow_audit::EscrowAudit;
+use serde_json::{json, Number, Value};
+use solana_pubkey::Pubkey;
+
+/// The audit as JSON, with raw amounts and each mint's `decimals`.
+pub fn audit_json(audit: &EscrowAudit, decimals: &HashMap<Pubkey, u8>) -> Value {
+    let mints: Vec<Value> = audit
+        .mints
+        .iter()
+        .map(|mint| {
+            json!({
+                "mint": mint.mint.to_string(),
+                "decimals": decimals.get(&mint.mint),
+                "balance": mint.balance,
+                "open_payments": mint.open_payments.len(),
+                "open_amount": mint.open_amount,
+                "batch_pending": mint.batch_pending,
+                "expected": Number::from_u128(mint.expected()),
+                "discrepancy": Number::from_i128(mint.discrepancy()),
+                "unbacked_payments": mint
+                    .unbacked_payments
+                    .iter()
+                    .map(Pubkey::to_string)
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    json!({
+        "merchant_operator_config": audit.merchant_operator_config.to_string(),
+        "balanced": audit.discrepancies().next().is_none(),
+        "mints": mints,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commerce_program_client::escrow_audit::MintEscrowAudit;
+
+    #[test]
+    fn test_audit_json() {
+        let config = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let unbacked = Pubkey::new_unique();
+        let audit = EscrowAudit {
+            merchant_operator_config: config,
+            mints: vec![MintEscrowAudit {
+                mint,
+                balance: 1_000,
+                batch_pending: 200,
+                open_payments: vec![Pubkey::new_unique(), unbacked],
+                open_amount: 1_500,
+                unbacked_payments: vec![unbacked],
+            }],
+        };
+
+        let json = audit_json(&audit, &HashMap::from([(mint, 6)]));
+
+        assert_eq!(
+            json,
+            json!({
+                "merchant_operator_config": config.to_string(),
+                "balanced": false,
+                "mints": [{
+                    "mint": mint.to_string(),
+                    "decimals": 6,
+                    "balance": 1_000,
+                    "open_payments": 2,
+                    "open_amount": 1_500,
+                    "batch_pending": 200,
+                    "expected": 1_700,
+                    "discrepancy": -700,
+                    "unbacked_payments": [unbacked.to_string()],
+                }],
+            })
+        );
+    }
+}
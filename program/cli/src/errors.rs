@@ -0,0 +1,72 @@
+//! Error lookup for `commerce-cli error`.
+//!
+//! Resolves what a failed transaction reports, a decimal code, a hex code as in
+//! `custom program error: 0x4e`, or an error name, to the program error it stands for.
+
+use commerce_program_client::{
+    error_registry::{error_registry, ErrorInfo},
+    CommerceProgramError,
+};
+use serde_json::{json, Value};
+
+/// The program error `query` refers to, by code (decimal or `0x` hex) or name.
+pub fn lookup_error(query: &str) -> Option<ErrorInfo> {
+    let query = query.trim();
+    let code = match query
+        .strip_prefix("0x")
+        .or_else(|| query.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => query.parse().ok(),
+    };
+
+    match code {
+        Some(code) => CommerceProgramError::from_code(code).map(|error| error.info()),
+        None => error_registry()
+            .into_iter()
+            .find(|info| info.name.eq_ignore_ascii_case(query)),
+    }
+}
+
+pub fn error_json(info: &ErrorInfo) -> Value {
+    json!({
+        "code": info.code,
+        "hex": format!("{:#x}", info.code),
+        "name": info.name,
+        "message": info.message,
+    })
+}
+
+/// Every program error in code order.
+pub fn registry_json() -> Value {
+    Value::Array(error_registry().iter().map(error_json).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_error() {
+        let expected = Some(CommerceProgramError::RiskStateRequired.info());
+        assert_eq!(lookup_error("78"), expected);
+        assert_eq!(lookup_error("0x4e"), expected);
+        assert_eq!(lookup_error("0x4E"), expected);
+        assert_eq!(lookup_error("RiskStateRequired"), expected);
+        assert_eq!(lookup_error("riskstaterequired"), expected);
+
+        assert_eq!(lookup_error("100000"), None);
+        assert_eq!(lookup_error("0xzz"), None);
+        assert_eq!(lookup_error("NotAnError"), None);
+    }
+
+    #[test]
+    fn test_registry_json() {
+        let registry = registry_json();
+        let first = &registry[0];
+        assert_eq!(first["code"], 0);
+        assert_eq!(first["hex"], "0x0");
+        assert_eq!(first["name"], "InvalidMint");
+        assert_eq!(first["message"], "Incorrect mint provided");
+    }
+}
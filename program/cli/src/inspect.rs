@@ -0,0 +1,1076 @@
+//! Account decoding for `commerce-cli inspect`.
+//!
+//! [`CommerceAccount::decode`] picks the account type from its discriminator byte and decodes
+//! it, including the policies and accepted currencies stored after a config's fixed fields.
+//! The JSON form lists the stored fields with pubkeys in base58 and byte arrays in hex; the
+//! `derived_*` helpers add what follows from them, such as escrow ATAs and the time left on a
+//! payment's refund and close windows.
+
+use commerce_program_client::{
+    accounts::{
+        BuyerIndex, ConfigRegistryPage, ConversionRate, Merchant, MerchantOperatorConfig,
+        MerchantProfile, MerchantStats, Operator, OperatorDelegate, OperatorFreezeList, Order,
+        Payment, PaymentAnnotation, PaymentNote, PaymentTree, Quote, RefundApproval, RiskState,
+        SettlementBatch, WebhookCommitment,
+    },
+    amount::{format_amount, AmountError},
+    buyer_index::recent_order_ids,
+    checkout::{get_associated_token_address, TOKEN_PROGRAM_ID},
+    compression::PAYMENT_TREE_DEPTH,
+    pdas::{find_escrow_authority_pda, find_merchant_profile_pda},
+    preview::decode_merchant_operator_config,
+    types::{FeeTier, PolicyData, Status},
+};
+use serde_json::{json, Value};
+use solana_pubkey::Pubkey;
+use thiserror::Error;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Error)]
+pub enum InspectError {
+    #[error("rpc error: {0}")]
+    Rpc(String),
+    #[error("account {0} not found")]
+    NotFound(Pubkey),
+    #[error("account {0} is owned by {1}, not the Commerce Program")]
+    NotCommerceAccount(Pubkey, Pubkey),
+    #[error("account has no data")]
+    Empty,
+    #[error("unknown account discriminator {0}")]
+    UnknownDiscriminator(u8),
+    #[error("failed to decode account: {0}")]
+    Decode(#[from] std::io::Error),
+    #[error(transparent)]
+    Amount(#[from] AmountError),
+}
+
+impl From<solana_client::client_error::ClientError> for InspectError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(error.to_string())
+    }
+}
+
+/// A decoded Commerce Program account.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommerceAccount {
+    Merchant(Merchant),
+    Operator(Operator),
+    MerchantOperatorConfig {
+        config: MerchantOperatorConfig,
+        policies: Vec<PolicyData>,
+        accepted_currencies: Vec<Pubkey>,
+    },
+    Payment(Payment),
+    PaymentTree(Box<PaymentTree>),
+    MerchantStats(MerchantStats),
+    OperatorDelegate(OperatorDelegate),
+    SettlementBatch(SettlementBatch),
+    Order(Order),
+    MerchantProfile(MerchantProfile),
+    RefundApproval(RefundApproval),
+    RiskState(RiskState),
+    BuyerIndex(BuyerIndex),
+    Quote(Quote),
+    ConfigRegistryPage(Box<ConfigRegistryPage>),
+    OperatorFreezeList(Box<OperatorFreezeList>),
+    ConversionRate(ConversionRate),
+    PaymentNote(PaymentNote),
+    WebhookCommitment(WebhookCommitment),
+    PaymentAnnotation(PaymentAnnotation),
+}
+
+impl CommerceAccount {
+    /// Decodes `data` as the account type named by its first byte.
+    pub fn decode(data: &[u8]) -> Result<Self, InspectError> {
+        let discriminator = *data.first().ok_or(InspectError::Empty)?;
+        Ok(match discriminator {
+            0 => Self::Merchant(Merchant::from_bytes(data)?),
+            1 => Self::Operator(Operator::from_bytes(data)?),
+            2 => {
+                let (config, policies, accepted_currencies) =
+                    decode_merchant_operator_config(data)?;
+                Self::MerchantOperatorConfig {
+                    config,
+                    policies,
+                    accepted_currencies,
+                }
+            }
+            3 => Self::Payment(Payment::from_bytes(data)?),
+            4 => Self::PaymentTree(Box::new(PaymentTree::from_bytes(data)?)),
+            5 => Self::MerchantStats(MerchantStats::from_bytes(data)?),
+            6 => Self::OperatorDelegate(OperatorDelegate::from_bytes(data)?),
+            7 => Self::SettlementBatch(SettlementBatch::from_bytes(data)?),
+            8 => Self::Order(Order::from_bytes(data)?),
+            9 => Self::MerchantProfile(MerchantProfile::from_bytes(data)?),
+            10 => Self::RefundApproval(RefundApproval::from_bytes(data)?),
+            11 => Self::RiskState(RiskState::from_bytes(data)?),
+            12 => Self::BuyerIndex(BuyerIndex::from_bytes(data)?),
+            13 => Self::Quote(Quote::from_bytes(data)?),
+            14 => Self::ConfigRegistryPage(Box::new(ConfigRegistryPage::from_bytes(data)?)),
+            15 => Self::OperatorFreezeList(Box::new(OperatorFreezeList::from_bytes(data)?)),
+            16 => Self::ConversionRate(ConversionRate::from_bytes(data)?),
+            17 => Self::PaymentNote(PaymentNote::from_bytes(data)?),
+            18 => Self::WebhookCommitment(WebhookCommitment::from_bytes(data)?),
+            19 => Self::PaymentAnnotation(PaymentAnnotation::from_bytes(data)?),
+            _ => return Err(InspectError::UnknownDiscriminator(discriminator)),
+        })
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Merchant(_) => "Merchant",
+            Self::Operator(_) => "Operator",
+            Self::MerchantOperatorConfig { .. } => "MerchantOperatorConfig",
+            Self::Payment(_) => "Payment",
+            Self::PaymentTree(_) => "PaymentTree",
+            Self::MerchantStats(_) => "MerchantStats",
+            Self::OperatorDelegate(_) => "OperatorDelegate",
+            Self::SettlementBatch(_) => "SettlementBatch",
+            Self::Order(_) => "Order",
+            Self::MerchantProfile(_) => "MerchantProfile",
+            Self::RefundApproval(_) => "RefundApproval",
+            Self::RiskState(_) => "RiskState",
+            Self::BuyerIndex(_) => "BuyerIndex",
+            Self::Quote(_) => "Quote",
+            Self::ConfigRegistryPage(_) => "ConfigRegistryPage",
+            Self::OperatorFreezeList(_) => "OperatorFreezeList",
+            Self::ConversionRate(_) => "ConversionRate",
+            Self::PaymentNote(_) => "PaymentNote",
+            Self::WebhookCommitment(_) => "WebhookCommitment",
+            Self::PaymentAnnotation(_) => "PaymentAnnotation",
+        }
+    }
+
+    /// Stored fields, without the discriminator.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Merchant(merchant) => {
+                let unlocks_at = (merchant.pending_settlement_wallet != Pubkey::default())
+                    .then_some(merchant.pending_settlement_wallet_unlocks_at);
+                json!({
+                    "owner": merchant.owner.to_string(),
+                    "bump": merchant.bump,
+                    "settlement_wallet": merchant.settlement_wallet.to_string(),
+                    "pending_authority": pending(&merchant.pending_authority),
+                    "settlement_wallet_timelock_seconds": merchant.settlement_wallet_timelock_seconds,
+                    "pending_settlement_wallet": pending(&merchant.pending_settlement_wallet),
+                    "pending_settlement_wallet_unlocks_at": unlocks_at,
+                    "original_owner": merchant.original_owner.to_string(),
+                })
+            }
+            Self::Operator(operator) => json!({
+                "owner": operator.owner.to_string(),
+                "bump": operator.bump,
+                "pending_authority": pending(&operator.pending_authority),
+                "last_active_at": operator.last_active_at,
+                "original_owner": operator.original_owner.to_string(),
+            }),
+            Self::MerchantOperatorConfig {
+                config,
+                policies,
+                accepted_currencies,
+            } => json!({
+                "version": config.version,
+                "bump": config.bump,
+                "merchant": config.merchant.to_string(),
+                "operator": config.operator.to_string(),
+                "operator_fee": config.operator_fee,
+                "fee_type": format!("{:?}", config.fee_type),
+                "current_order_id": config.current_order_id,
+                "days_to_close": config.days_to_close,
+                "config_hash": hex(&config.config_hash),
+                "decommissioned_at": config.decommissioned_at,
+                "payment_accounts": config.payment_accounts,
+                "policies": policies.iter().map(policy_json).collect::<Vec<_>>(),
+                "accepted_currencies": accepted_currencies
+                    .iter()
+                    .map(Pubkey::to_string)
+                    .collect::<Vec<_>>(),
+            }),
+            Self::Payment(payment) => json!({
+                "order_id": payment.order_id,
+                "amount": payment.amount,
+                "created_at": payment.created_at,
+                "status": format!("{:?}", payment.status),
+                "bump": payment.bump,
+                "idempotency_key": hex(&payment.idempotency_key),
+                "fee_override": payment.has_fee_override.then(|| json!({
+                    "fee": payment.fee_override.fee,
+                    "fee_type": format!("{:?}", payment.fee_override.fee_type),
+                })),
+                "references": payment
+                    .references
+                    .iter()
+                    .filter(|reference| **reference != Pubkey::default())
+                    .map(Pubkey::to_string)
+                    .collect::<Vec<_>>(),
+                "rent_payer": (payment.rent_payer != Pubkey::default())
+                    .then(|| payment.rent_payer.to_string()),
+                "has_order": payment.has_order,
+            }),
+            Self::PaymentTree(tree) => json!({
+                "merchant_operator_config": tree.merchant_operator_config.to_string(),
+                "bump": tree.bump,
+                "num_leaves": tree.num_leaves,
+                "root": hex(&tree.root),
+            }),
+            Self::MerchantStats(stats) => json!({
+                "merchant": stats.merchant.to_string(),
+                "mint": stats.mint.to_string(),
+                "bump": stats.bump,
+                "payment_count": stats.payment_count,
+                "total_volume": stats.total_volume,
+                "total_cleared": stats.total_cleared,
+                "total_fees_paid": stats.total_fees_paid,
+                "refund_count": stats.refund_count,
+                "total_refunded": stats.total_refunded,
+                "closed_count": stats.closed_count,
+            }),
+            Self::OperatorDelegate(delegate) => json!({
+                "operator": delegate.operator.to_string(),
+                "delegate": delegate.delegate.to_string(),
+                "bump": delegate.bump,
+                "can_make_payment": delegate.can_make_payment,
+                "can_clear": delegate.can_clear,
+                "can_refund": delegate.can_refund,
+                "can_close": delegate.can_close,
+                "max_refund_amount": delegate.max_refund_amount,
+                "max_daily_refund_amount": delegate.max_daily_refund_amount,
+                "refund_day": delegate.refund_day,
+                "daily_refund_amount": delegate.daily_refund_amount,
+            }),
+            Self::SettlementBatch(batch) => json!({
+                "merchant_operator_config": batch.merchant_operator_config.to_string(),
+                "mint": batch.mint.to_string(),
+                "bump": batch.bump,
+                "pending_amount": batch.pending_amount,
+                "pending_count": batch.pending_count,
+                "last_settled_at": batch.last_settled_at,
+            }),
+            Self::Order(order) => json!({
+                "merchant_operator_config": order.merchant_operator_config.to_string(),
+                "buyer": order.buyer.to_string(),
+                "order_id": order.order_id,
+                "bump": order.bump,
+                "expected_payments": order.expected_payments,
+                "payment_count": order.payment_count,
+                "cleared_count": order.cleared_count,
+                "refunded_count": order.refunded_count,
+                "status": format!("{:?}", order.status),
+                "created_at": order.created_at,
+            }),
+            Self::MerchantProfile(profile) => json!({
+                "merchant": profile.merchant.to_string(),
+                "bump": profile.bump,
+                "name": profile.name,
+                "uri": profile.uri,
+                "logo_hash": hex(&profile.logo_hash),
+                "contact_hash": hex(&profile.contact_hash),
+            }),
+            Self::RefundApproval(approval) => json!({
+                "payment": approval.payment.to_string(),
+                "bump": approval.bump,
+                "approver": approval.approver.to_string(),
+                "amount": approval.amount,
+                "approved_at": approval.approved_at,
+                "rent_payer": approval.rent_payer.to_string(),
+            }),
+            Self::RiskState(risk_state) => json!({
+                "merchant_operator_config": risk_state.merchant_operator_config.to_string(),
+                "buyer": pending(&risk_state.buyer),
+                "bump": risk_state.bump,
+                "day": risk_state.day,
+                "daily_volume": risk_state.daily_volume,
+                "daily_payment_count": risk_state.daily_payment_count,
+            }),
+            Self::BuyerIndex(buyer_index) => json!({
+                "merchant_operator_config": buyer_index.merchant_operator_config.to_string(),
+                "buyer": buyer_index.buyer.to_string(),
+                "bump": buyer_index.bump,
+                "payment_count": buyer_index.payment_count,
+                "recent_order_ids": recent_order_ids(buyer_index),
+            }),
+            Self::Quote(quote) => json!({
+                "merchant_operator_config": quote.merchant_operator_config.to_string(),
+                "mint": quote.mint.to_string(),
+                "order_id": quote.order_id,
+                "bump": quote.bump,
+                "amount": quote.amount,
+                "expires_at": quote.expires_at,
+                "created_at": quote.created_at,
+                "quoter": quote.quoter.to_string(),
+            }),
+            Self::ConfigRegistryPage(page) => json!({
+                "operator": page.operator.to_string(),
+                "page": page.page,
+                "bump": page.bump,
+                "count": page.count,
+                "entries": page
+                    .listed()
+                    .iter()
+                    .map(|entry| json!({
+                        "merchant": entry.merchant.to_string(),
+                        "version": entry.version,
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+            Self::OperatorFreezeList(freeze_list) => json!({
+                "operator": freeze_list.operator.to_string(),
+                "bump": freeze_list.bump,
+                "count": freeze_list.count,
+                "addresses": freeze_list
+                    .frozen()
+                    .iter()
+                    .map(Pubkey::to_string)
+                    .collect::<Vec<_>>(),
+            }),
+            Self::ConversionRate(conversion_rate) => json!({
+                "merchant_operator_config": conversion_rate.merchant_operator_config.to_string(),
+                "from_mint": conversion_rate.from_mint.to_string(),
+                "to_mint": conversion_rate.to_mint.to_string(),
+                "bump": conversion_rate.bump,
+                "rate_numerator": conversion_rate.rate_numerator,
+                "rate_denominator": conversion_rate.rate_denominator,
+                "posted_at": conversion_rate.posted_at,
+                "attester": conversion_rate.attester.to_string(),
+            }),
+            Self::PaymentNote(payment_note) => json!({
+                "payment": payment_note.payment.to_string(),
+                "merchant": payment_note.merchant.to_string(),
+                "bump": payment_note.bump,
+                "rent_payer": payment_note.rent_payer.to_string(),
+                "ciphertext": hex(&payment_note.ciphertext),
+            }),
+            Self::WebhookCommitment(webhook_commitment) => json!({
+                "merchant_operator_config": webhook_commitment.merchant_operator_config.to_string(),
+                "bump": webhook_commitment.bump,
+                "commitment": hex(&webhook_commitment.commitment),
+                "updated_at": webhook_commitment.updated_at,
+            }),
+            Self::PaymentAnnotation(payment_annotation) => json!({
+                "payment": payment_annotation.payment.to_string(),
+                "merchant_operator_config": payment_annotation.merchant_operator_config.to_string(),
+                "bump": payment_annotation.bump,
+                "flag": format!("{:?}", payment_annotation.flag),
+                "note_hash": hex(&payment_annotation.note_hash),
+                "annotated_by": payment_annotation.annotated_by.to_string(),
+                "updated_at": payment_annotation.updated_at,
+            }),
+        }
+    }
+}
+
+/// The accounts a payment was made with, read from its `MakePayment` instruction since the
+/// payment account does not store them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaymentOrigin {
+    pub buyer: Pubkey,
+    pub operator: Pubkey,
+    pub merchant: Pubkey,
+    pub merchant_operator_config: Pubkey,
+    pub mint: Pubkey,
+    pub signature: String,
+}
+
+/// Escrow ATA of the config at `config` for each of `mints`, with the escrow authority PDA
+/// that owns it and the legacy escrow ATA of its `merchant` that MigrateEscrow moves from.
+pub fn derived_escrow_atas(config: &Pubkey, merchant: &Pubkey, mints: &[Pubkey]) -> Value {
+    mints
+        .iter()
+        .map(|mint| {
+            let escrow_authority = find_escrow_authority_pda(config, mint).0;
+            json!({
+                "mint": mint.to_string(),
+                "escrow_authority": escrow_authority.to_string(),
+                "address": get_associated_token_address(&escrow_authority, mint, &TOKEN_PROGRAM_ID)
+                    .to_string(),
+                "legacy_address": get_associated_token_address(merchant, mint, &TOKEN_PROGRAM_ID)
+                    .to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The MerchantProfile PDA of the merchant at `merchant`, which may not exist.
+pub fn derived_merchant(merchant: &Pubkey) -> Value {
+    json!({
+        "profile": find_merchant_profile_pda(merchant).0.to_string(),
+    })
+}
+
+/// The leaves left in a payment tree.
+pub fn derived_payment_tree(tree: &PaymentTree) -> Value {
+    let capacity = 1u64 << PAYMENT_TREE_DEPTH;
+    json!({
+        "capacity": capacity,
+        "remaining_leaves": capacity.saturating_sub(tree.num_leaves as u64),
+    })
+}
+
+/// Accounts and windows of a payment made through `origin` under `config`, at unix
+/// timestamp `now`, with its amount in display units of a mint with `decimals`.
+pub fn derived_payment(
+    payment: &Payment,
+    origin: &PaymentOrigin,
+    config: &MerchantOperatorConfig,
+    policies: &[PolicyData],
+    decimals: u8,
+    now: i64,
+) -> Value {
+    let mut derived = json!({
+        "merchant": origin.merchant.to_string(),
+        "operator": origin.operator.to_string(),
+        "merchant_operator_config": origin.merchant_operator_config.to_string(),
+        "buyer": origin.buyer.to_string(),
+        "mint": origin.mint.to_string(),
+        "amount_ui": format_amount(payment.amount, decimals),
+        "escrow_ata": get_associated_token_address(
+            &find_escrow_authority_pda(&origin.merchant_operator_config, &origin.mint).0,
+            &origin.mint,
+            &TOKEN_PROGRAM_ID,
+        )
+        .to_string(),
+        "make_payment_signature": origin.signature,
+    });
+
+    // Only paid payments can be refunded; 0 means no time restriction
+    let refund_policy = policies.iter().find_map(|policy| match policy {
+        PolicyData::Refund(refund) => Some(refund),
+        _ => None,
+    });
+    if let Some(refund) = refund_policy.filter(|refund| refund.max_time_after_purchase > 0) {
+        if payment.status == Status::Paid {
+            let ends_at = payment
+                .created_at
+                .saturating_add(refund.max_time_after_purchase as i64);
+            derived["refund_window"] = window_json("ends_at", ends_at, now);
+        }
+    }
+
+    // Paid payments can be charged back, and not cleared, until the window ends
+    let chargeback_policy = policies.iter().find_map(|policy| match policy {
+        PolicyData::Chargeback(chargeback) => Some(chargeback),
+        _ => None,
+    });
+    if let Some(chargeback) = chargeback_policy {
+        if payment.status == Status::Paid {
+            let ends_at = payment
+                .created_at
+                .saturating_add(chargeback.window_seconds.min(i64::MAX as u64) as i64);
+            derived["chargeback_window"] = window_json("ends_at", ends_at, now);
+            derived["chargeback_window"]["arbiter"] = json!(chargeback.arbiter.to_string());
+        }
+    }
+
+    // Paid payments must be cleared or refunded, approved refunds claimed, and authorized
+    // payments captured, before they can be closed
+    let closes_at = payment
+        .created_at
+        .saturating_add(config.days_to_close as i64 * SECONDS_PER_DAY);
+    derived["close_window"] = window_json("opens_at", closes_at, now);
+    let resolved = !matches!(
+        payment.status,
+        Status::Paid | Status::RefundApproved | Status::Authorized
+    );
+    derived["close_window"]["closable"] = json!(resolved && now >= closes_at);
+
+    derived
+}
+
+fn window_json(label: &str, at: i64, now: i64) -> Value {
+    let remaining = at.saturating_sub(now).max(0);
+    json!({
+        label: at,
+        "seconds_remaining": remaining,
+        "time_remaining": format_duration(remaining),
+    })
+}
+
+/// `1d 02h 03m 04s` style duration.
+pub fn format_duration(seconds: i64) -> String {
+    let (days, rest) = (seconds / SECONDS_PER_DAY, seconds % SECONDS_PER_DAY);
+    let (hours, minutes, seconds) = (rest / 3600, rest % 3600 / 60, rest % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}h {minutes:02}m {seconds:02}s")
+    } else {
+        format!("{hours:02}h {minutes:02}m {seconds:02}s")
+    }
+}
+
+fn policy_json(policy: &PolicyData) -> Value {
+    match policy {
+        PolicyData::Refund(refund) => json!({
+            "type": "Refund",
+            "max_amount": refund.max_amount,
+            "max_time_after_purchase": refund.max_time_after_purchase,
+        }),
+        PolicyData::Settlement(settlement) => json!({
+            "type": "Settlement",
+            "min_settlement_amount": settlement.min_settlement_amount,
+            "settlement_frequency_hours": settlement.settlement_frequency_hours,
+            "auto_settle": settlement.auto_settle,
+        }),
+        PolicyData::FeeTier(fee_tier) => json!({
+            "type": "FeeTier",
+            "tiers": fee_tier
+                .tiers
+                .iter()
+                .take(fee_tier.num_tiers as usize)
+                .map(fee_tier_json)
+                .collect::<Vec<_>>(),
+        }),
+        PolicyData::Memo(memo) => json!({
+            "type": "Memo",
+            "memo_on_clear": memo.memo_on_clear,
+            "memo_on_refund": memo.memo_on_refund,
+        }),
+        PolicyData::Conversion(conversion) => json!({
+            "type": "Conversion",
+            "settlement_mint": conversion.settlement_mint.to_string(),
+            "swap_adapter": format!("{:?}", conversion.swap_adapter),
+            "max_slippage_bps": conversion.max_slippage_bps,
+        }),
+        PolicyData::Chargeback(chargeback) => json!({
+            "type": "Chargeback",
+            "window_seconds": chargeback.window_seconds,
+            "arbiter": chargeback.arbiter.to_string(),
+        }),
+        PolicyData::FeeOverride(fee_override) => json!({
+            "type": "FeeOverride",
+            "max_fee": fee_override.max_fee,
+            "max_fee_type": format!("{:?}", fee_override.max_fee_type),
+        }),
+        PolicyData::Denylist(denylist) => json!({
+            "type": "Denylist",
+            "root": hex(&denylist.root),
+            "depth": denylist.depth,
+        }),
+        PolicyData::SplitSettlement(split_settlement) => json!({
+            "type": "SplitSettlement",
+            "destinations_hash": hex(&split_settlement.destinations_hash),
+            "shares_bps": split_settlement
+                .shares_bps
+                .iter()
+                .take(split_settlement.num_destinations as usize)
+                .collect::<Vec<_>>(),
+        }),
+        PolicyData::Risk(risk) => json!({
+            "type": "Risk",
+            "max_payment_amount": risk.max_payment_amount,
+            "max_daily_volume": risk.max_daily_volume,
+            "max_payments_per_buyer_per_day": risk.max_payments_per_buyer_per_day,
+        }),
+        PolicyData::BuyerIndex(buyer_index) => json!({
+            "type": "BuyerIndex",
+            "required": buyer_index.required,
+        }),
+        PolicyData::Callback(callback) => json!({
+            "type": "Callback",
+            "program": callback.program.to_string(),
+            "on_clear": callback.on_clear,
+            "on_refund": callback.on_refund,
+        }),
+        PolicyData::PlatformFee(platform_fee) => json!({
+            "type": "PlatformFee",
+            "platform": platform_fee.platform.to_string(),
+            "fee": platform_fee.fee,
+            "fee_type": format!("{:?}", platform_fee.fee_type),
+        }),
+        PolicyData::Compliance(compliance) => json!({
+            "type": "Compliance",
+            "program": compliance.program.to_string(),
+        }),
+        PolicyData::SettlementDiscount(settlement_discount) => json!({
+            "type": "SettlementDiscount",
+            "discount_bps_per_day": settlement_discount.discount_bps_per_day,
+            "max_discount_bps": settlement_discount.max_discount_bps,
+        }),
+        PolicyData::RefundConversion(refund_conversion) => json!({
+            "type": "RefundConversion",
+            "max_rate_age_seconds": refund_conversion.max_rate_age_seconds,
+        }),
+        PolicyData::OperatorFailover(operator_failover) => json!({
+            "type": "OperatorFailover",
+            "inactivity_threshold_seconds": operator_failover.inactivity_threshold_seconds,
+        }),
+        PolicyData::RentRebate(rent_rebate) => json!({
+            "type": "RentRebate",
+            "buyer_share_bps": rent_rebate.buyer_share_bps,
+        }),
+        PolicyData::DirectSettlement(direct_settlement) => json!({
+            "type": "DirectSettlement",
+            "max_amount": direct_settlement.max_amount,
+        }),
+    }
+}
+
+fn fee_tier_json(tier: &FeeTier) -> Value {
+    json!({
+        "threshold": tier.threshold,
+        "fee": tier.fee,
+        "fee_type": format!("{:?}", tier.fee_type),
+    })
+}
+
+/// The pending key of a two-step change, or null when none is in progress.
+fn pending(key: &Pubkey) -> Value {
+    if *key == Pubkey::default() {
+        Value::Null
+    } else {
+        Value::String(key.to_string())
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+    use commerce_program_client::types::{
+        AnnotationFlag, ChargebackPolicy, ConfigRegistryEntry, FeeOverride, FeeType, MemoPolicy,
+        RefundPolicy,
+    };
+
+    fn config(
+        days_to_close: u16,
+        num_policies: u32,
+        num_currencies: u32,
+    ) -> MerchantOperatorConfig {
+        MerchantOperatorConfig {
+            discriminator: 2,
+            version: 1,
+            bump: 255,
+            merchant: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 7,
+            days_to_close,
+            num_policies,
+            num_accepted_currencies: num_currencies,
+            config_hash: [0xab; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        }
+    }
+
+    fn payment(status: Status) -> Payment {
+        Payment {
+            discriminator: 3,
+            order_id: 1,
+            amount: 1_000,
+            created_at: 1_000_000,
+            status,
+            bump: 254,
+            idempotency_key: [0xab; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride {
+                fee: 0,
+                fee_type: FeeType::Bps,
+            },
+            references: [Pubkey::default(); 2],
+            rent_payer: Pubkey::default(),
+            has_order: false,
+        }
+    }
+
+    fn origin() -> PaymentOrigin {
+        PaymentOrigin {
+            buyer: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            merchant: Pubkey::new_unique(),
+            merchant_operator_config: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            signature: "sig".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_decode_by_discriminator() {
+        let reference = Pubkey::new_unique();
+        let mut payment = payment(Status::Cleared);
+        payment.references[0] = reference;
+        let data = borsh::to_vec(&payment).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(account.type_name(), "Payment");
+
+        let json = account.to_json();
+        assert_eq!(json["status"], "Cleared");
+        assert_eq!(json["idempotency_key"], "ab".repeat(16));
+        assert_eq!(json["references"], json!([reference.to_string()]));
+        assert_eq!(json["rent_payer"], Value::Null);
+
+        assert!(matches!(
+            CommerceAccount::decode(&[42]),
+            Err(InspectError::UnknownDiscriminator(42))
+        ));
+        assert!(matches!(
+            CommerceAccount::decode(&[]),
+            Err(InspectError::Empty)
+        ));
+        assert!(matches!(
+            CommerceAccount::decode(&data[..10]),
+            Err(InspectError::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_config_with_policies_and_currencies() {
+        let config = config(30, 2, 1);
+        let mint = Pubkey::new_unique();
+
+        let mut data = borsh::to_vec(&config).unwrap();
+        for policy in [
+            PolicyData::Refund(RefundPolicy {
+                max_amount: 500,
+                max_time_after_purchase: 3600,
+            }),
+            PolicyData::Memo(MemoPolicy {
+                memo_on_clear: true,
+                memo_on_refund: false,
+            }),
+        ] {
+            let mut slot = vec![0u8; commerce_program_client::preview::POLICY_DATA_SIZE];
+            policy.serialize(&mut slot.as_mut_slice()).unwrap();
+            data.extend_from_slice(&slot);
+        }
+        data.extend_from_slice(mint.as_ref());
+
+        let json = CommerceAccount::decode(&data).unwrap().to_json();
+        assert_eq!(json["policies"][0]["type"], "Refund");
+        assert_eq!(json["policies"][0]["max_time_after_purchase"], 3600);
+        assert_eq!(json["policies"][1]["memo_on_clear"], true);
+        assert_eq!(json["accepted_currencies"][0], mint.to_string());
+    }
+
+    #[test]
+    fn test_derived_escrow_atas() {
+        let config = Pubkey::new_unique();
+        let merchant = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let escrow_authority = find_escrow_authority_pda(&config, &mint).0;
+        let atas = derived_escrow_atas(&config, &merchant, &[mint]);
+        assert_eq!(atas[0]["escrow_authority"], escrow_authority.to_string());
+        assert_eq!(
+            atas[0]["address"],
+            get_associated_token_address(&escrow_authority, &mint, &TOKEN_PROGRAM_ID).to_string()
+        );
+        assert_eq!(
+            atas[0]["legacy_address"],
+            get_associated_token_address(&merchant, &mint, &TOKEN_PROGRAM_ID).to_string()
+        );
+    }
+
+    #[test]
+    fn test_decode_merchant_profile() {
+        let profile = MerchantProfile {
+            discriminator: 9,
+            merchant: Pubkey::new_unique(),
+            bump: 253,
+            logo_hash: [0xcd; 32],
+            contact_hash: [0xef; 32],
+            name: "Coffee Shop".to_string(),
+            uri: "https://coffee.example".to_string(),
+        };
+
+        // Allocated with room to spare, zero-padded after `uri`
+        let mut data = borsh::to_vec(&profile).unwrap();
+        data.resize(data.len() + 64, 0);
+
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(account, CommerceAccount::MerchantProfile(profile));
+        let json = account.to_json();
+        assert_eq!(json["name"], "Coffee Shop");
+        assert_eq!(json["logo_hash"], "cd".repeat(32));
+    }
+
+    #[test]
+    fn test_decode_refund_approval() {
+        let approval = RefundApproval {
+            discriminator: 10,
+            payment: Pubkey::new_unique(),
+            bump: 252,
+            approver: Pubkey::new_unique(),
+            amount: 1_000_000,
+            approved_at: 1_700_000_000,
+            rent_payer: Pubkey::new_unique(),
+        };
+
+        let rent_payer = approval.rent_payer;
+
+        let data = borsh::to_vec(&approval).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(account, CommerceAccount::RefundApproval(approval));
+        let json = account.to_json();
+        assert_eq!(json["amount"], 1_000_000);
+        assert_eq!(json["rent_payer"], rent_payer.to_string());
+    }
+
+    #[test]
+    fn test_decode_risk_state() {
+        let risk_state = RiskState {
+            discriminator: 11,
+            merchant_operator_config: Pubkey::new_unique(),
+            buyer: Pubkey::default(),
+            bump: 251,
+            day: 20_000,
+            daily_volume: 5_000_000,
+            daily_payment_count: 3,
+        };
+
+        let data = borsh::to_vec(&risk_state).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(account, CommerceAccount::RiskState(risk_state));
+        let json = account.to_json();
+        assert_eq!(json["buyer"], Value::Null);
+        assert_eq!(json["daily_volume"], 5_000_000);
+    }
+
+    #[test]
+    fn test_decode_buyer_index() {
+        let buyer_index = BuyerIndex {
+            discriminator: 12,
+            merchant_operator_config: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            bump: 250,
+            payment_count: 2,
+            recent_order_ids: [9, 4, 0, 0, 0, 0, 0, 0],
+        };
+
+        let data = borsh::to_vec(&buyer_index).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(account, CommerceAccount::BuyerIndex(buyer_index));
+        let json = account.to_json();
+        assert_eq!(json["payment_count"], 2);
+        assert_eq!(json["recent_order_ids"], json!([9, 4]));
+    }
+
+    #[test]
+    fn test_decode_quote() {
+        let quote = Quote {
+            discriminator: 13,
+            merchant_operator_config: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            order_id: 7,
+            bump: 249,
+            amount: 2_500_000,
+            expires_at: 1_700_000_600,
+            created_at: 1_700_000_000,
+            quoter: Pubkey::new_unique(),
+        };
+
+        let data = borsh::to_vec(&quote).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(account, CommerceAccount::Quote(quote.clone()));
+        let json = account.to_json();
+        assert_eq!(json["amount"], 2_500_000);
+        assert_eq!(json["mint"], quote.mint.to_string());
+    }
+
+    #[test]
+    fn test_decode_config_registry_page() {
+        let merchant = Pubkey::new_unique();
+        let mut entries = core::array::from_fn(|_| ConfigRegistryEntry {
+            merchant: Pubkey::default(),
+            version: 0,
+        });
+        entries[0] = ConfigRegistryEntry {
+            merchant,
+            version: 2,
+        };
+        let page = ConfigRegistryPage {
+            discriminator: 14,
+            operator: Pubkey::new_unique(),
+            page: 0,
+            bump: 250,
+            count: 1,
+            entries,
+        };
+
+        let data = borsh::to_vec(&page).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(account, CommerceAccount::ConfigRegistryPage(Box::new(page)));
+        let json = account.to_json();
+        assert_eq!(
+            json["entries"],
+            json!([{ "merchant": merchant.to_string(), "version": 2 }])
+        );
+    }
+
+    #[test]
+    fn test_decode_operator_freeze_list() {
+        let frozen = Pubkey::new_unique();
+        let mut addresses = [Pubkey::default(); 64];
+        addresses[0] = frozen;
+        let freeze_list = OperatorFreezeList {
+            discriminator: 15,
+            operator: Pubkey::new_unique(),
+            bump: 250,
+            count: 1,
+            addresses,
+        };
+
+        let data = borsh::to_vec(&freeze_list).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(
+            account,
+            CommerceAccount::OperatorFreezeList(Box::new(freeze_list))
+        );
+        let json = account.to_json();
+        assert_eq!(json["addresses"], json!([frozen.to_string()]));
+    }
+
+    #[test]
+    fn test_decode_conversion_rate() {
+        let conversion_rate = ConversionRate {
+            discriminator: 16,
+            merchant_operator_config: Pubkey::new_unique(),
+            from_mint: Pubkey::new_unique(),
+            to_mint: Pubkey::new_unique(),
+            bump: 248,
+            rate_numerator: 98,
+            rate_denominator: 100,
+            posted_at: 1_700_000_000,
+            attester: Pubkey::new_unique(),
+        };
+
+        let data = borsh::to_vec(&conversion_rate).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(
+            account,
+            CommerceAccount::ConversionRate(conversion_rate.clone())
+        );
+        let json = account.to_json();
+        assert_eq!(json["rate_numerator"], 98);
+        assert_eq!(json["to_mint"], conversion_rate.to_mint.to_string());
+    }
+
+    #[test]
+    fn test_decode_payment_note() {
+        let payment_note = PaymentNote {
+            discriminator: 17,
+            payment: Pubkey::new_unique(),
+            merchant: Pubkey::new_unique(),
+            bump: 250,
+            rent_payer: Pubkey::new_unique(),
+            ciphertext: [0xab; 128],
+        };
+
+        let data = borsh::to_vec(&payment_note).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(account, CommerceAccount::PaymentNote(payment_note.clone()));
+        let json = account.to_json();
+        assert_eq!(json["payment"], payment_note.payment.to_string());
+        assert_eq!(json["ciphertext"], "ab".repeat(128));
+    }
+
+    #[test]
+    fn test_decode_webhook_commitment() {
+        let webhook_commitment = WebhookCommitment {
+            discriminator: 18,
+            merchant_operator_config: Pubkey::new_unique(),
+            bump: 249,
+            commitment: [0xcd; 32],
+            updated_at: 1_700_000_000,
+        };
+
+        let data = borsh::to_vec(&webhook_commitment).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(
+            account,
+            CommerceAccount::WebhookCommitment(webhook_commitment.clone())
+        );
+        let json = account.to_json();
+        assert_eq!(json["commitment"], "cd".repeat(32));
+        assert_eq!(json["updated_at"], 1_700_000_000);
+    }
+
+    #[test]
+    fn test_decode_payment_annotation() {
+        let payment_annotation = PaymentAnnotation {
+            discriminator: 19,
+            payment: Pubkey::new_unique(),
+            merchant_operator_config: Pubkey::new_unique(),
+            bump: 252,
+            flag: AnnotationFlag::ManualReview,
+            note_hash: [0xcd; 32],
+            annotated_by: Pubkey::new_unique(),
+            updated_at: 1_700_000_000,
+        };
+
+        let data = borsh::to_vec(&payment_annotation).unwrap();
+        let account = CommerceAccount::decode(&data).unwrap();
+        assert_eq!(
+            account,
+            CommerceAccount::PaymentAnnotation(payment_annotation.clone())
+        );
+        let json = account.to_json();
+        assert_eq!(json["flag"], "ManualReview");
+        assert_eq!(json["note_hash"], "cd".repeat(32));
+    }
+
+    #[test]
+    fn test_derived_merchant() {
+        let merchant = Pubkey::new_unique();
+        assert_eq!(
+            derived_merchant(&merchant)["profile"],
+            find_merchant_profile_pda(&merchant).0.to_string()
+        );
+    }
+
+    #[test]
+    fn test_derived_payment_windows() {
+        let config = config(1, 1, 0);
+        let arbiter = Pubkey::new_unique();
+        let policies = [
+            PolicyData::Refund(RefundPolicy {
+                max_amount: 0,
+                max_time_after_purchase: 7200,
+            }),
+            PolicyData::Chargeback(ChargebackPolicy {
+                window_seconds: 5400,
+                arbiter,
+            }),
+        ];
+        let now = 1_000_000 + 3600;
+
+        let paid = derived_payment(
+            &payment(Status::Paid),
+            &origin(),
+            &config,
+            &policies,
+            6,
+            now,
+        );
+        assert_eq!(paid["amount_ui"], "0.001");
+        assert_eq!(paid["refund_window"]["seconds_remaining"], 3600);
+        assert_eq!(paid["refund_window"]["time_remaining"], "01h 00m 00s");
+        assert_eq!(paid["chargeback_window"]["seconds_remaining"], 1800);
+        assert_eq!(paid["chargeback_window"]["arbiter"], arbiter.to_string());
+        assert_eq!(paid["close_window"]["seconds_remaining"], 86_400 - 3600);
+        assert_eq!(paid["close_window"]["closable"], false);
+
+        let cleared = derived_payment(
+            &payment(Status::Cleared),
+            &origin(),
+            &config,
+            &policies,
+            6,
+            1_000_000 + SECONDS_PER_DAY,
+        );
+        assert!(cleared.get("refund_window").is_none());
+        assert!(cleared.get("chargeback_window").is_none());
+        assert_eq!(cleared["close_window"]["seconds_remaining"], 0);
+        assert_eq!(cleared["close_window"]["closable"], true);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "00h 00m 00s");
+        assert_eq!(format_duration(93_784), "1d 02h 03m 04s");
+    }
+}
@@ -0,0 +1,19 @@
+//! Command line tools for the Commerce Program.
+//!
+//! [`rpc::Inspector`] fetches any Commerce Program account, detects its type from the
+//! discriminator byte and decodes it, adding derived info such as escrow ATAs and the time
+//! left on a payment's refund and close windows. The result is JSON, which
+//! [`table::render_table`] can flatten for terminal output. [`errors::lookup_error`] explains
+//! the `custom program error` codes of failed transactions. [`amount::amount_json`] converts
+//! amounts between display and raw units with the mint's decimals. [`report::Report`] totals a
+//! config's payment events over a time range per mint, for reconciliation exports.
+//! [`audit::audit_json`] reports where a config's escrow balances disagree with its open
+//! payments.
+
+pub mod amount;
+pub mod audit;
+pub mod errors;
+pub mod inspect;
+pub mod report;
+pub mod rpc;
+pub mod table;
@@ -0,0 +1,263 @@
+//! `commerce-cli`: command line tools for the Commerce Program.
+//!
+//! ```text
+//! commerce-cli inspect <PUBKEY> [--url <RPC_URL>] [--output json|table]
+//! commerce-cli error [<CODE|NAME>]
+//! commerce-cli amount <AMOUNT> --mint <MINT> [--raw] [--url <RPC_URL>]
+//! commerce-cli report <CONFIG> --from <YYYY-MM-DD> [--to <YYYY-MM-DD>] [--url <RPC_URL>] [--output json|csv]
+//! commerce-cli audit <CONFIG> [--url <RPC_URL>]
+//! ```
+//!
+//! `inspect` decodes any Commerce Program account along with derived info such as escrow
+//! ATAs and the time left on a payment's refund and close windows. Defaults to
+//! `http://127.0.0.1:8899` and pretty JSON.
+//!
+//! `error` explains a program error by code (e.g. `78` or `0x4e`) or name, or lists every
+//! error without an argument.
+//!
+//! `amount` converts a display amount (e.g. `10.50`), or a raw amount with `--raw`, into
+//! both forms using the decimals fetched from the mint.
+//!
+//! `report` totals a merchant operator config's payments, fees, refunds and chargebacks per
+//! mint over the UTC days from `--from` through `--to` (up to now without it), and lists
+//! each payment cross-referenced with its account. CSV has the per-mint totals in display
+//! units; JSON has raw amounts and the payments too.
+//!
+//! `audit` checks that each of a merchant operator config's escrow ATAs holds the payments
+//! still in escrow plus its settlement batch's pending amount, and lists the payments a
+//! shortfall leaves unbacked. It prints JSON and fails if any escrow is short, for monitoring.
+
+use std::{env, error::Error, str::FromStr};
+
+use commerce_cli::{
+    amount::amount_json,
+    audit::audit_json,
+    errors::{error_json, lookup_error, registry_json},
+    report::{parse_date, parse_end_date},
+    rpc::Inspector,
+    table::render_table,
+};
+use solana_pubkey::Pubkey;
+
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+
+enum Output {
+    Json,
+    Table,
+}
+
+struct InspectArgs {
+    address: Pubkey,
+    url: String,
+    output: Output,
+}
+
+fn parse_inspect_args(
+    mut iter: impl Iterator<Item = String>,
+) -> Result<InspectArgs, Box<dyn Error>> {
+    let mut address = None;
+    let mut url = DEFAULT_RPC_URL.to_string();
+    let mut output = Output::Json;
+
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--url" | "-u" => url = value()?,
+            "--output" | "-o" => {
+                output = match value()?.as_str() {
+                    "json" => Output::Json,
+                    "table" => Output::Table,
+                    other => return Err(format!("unknown output format {other}").into()),
+                }
+            }
+            _ if address.is_none() && !arg.starts_with('-') => {
+                address = Some(Pubkey::from_str(&arg).map_err(|e| format!("{arg}: {e}"))?)
+            }
+            _ => return Err(format!("unknown argument {arg}").into()),
+        }
+    }
+
+    Ok(InspectArgs {
+        address: address.ok_or("missing account pubkey")?,
+        url,
+        output,
+    })
+}
+
+struct AmountArgs {
+    amount: String,
+    mint: Pubkey,
+    raw: bool,
+    url: String,
+}
+
+fn parse_amount_args(mut iter: impl Iterator<Item = String>) -> Result<AmountArgs, Box<dyn Error>> {
+    let mut amount = None;
+    let mut mint = None;
+    let mut raw = false;
+    let mut url = DEFAULT_RPC_URL.to_string();
+
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--url" | "-u" => url = value()?,
+            "--mint" | "-m" => {
+                let value = value()?;
+                mint = Some(Pubkey::from_str(&value).map_err(|e| format!("{value}: {e}"))?)
+            }
+            "--raw" => raw = true,
+            _ if amount.is_none() && !arg.starts_with('-') => amount = Some(arg),
+            _ => return Err(format!("unknown argument {arg}").into()),
+        }
+    }
+
+    Ok(AmountArgs {
+        amount: amount.ok_or("missing amount")?,
+        mint: mint.ok_or("missing --mint")?,
+        raw,
+        url,
+    })
+}
+
+enum ReportOutput {
+    Json,
+    Csv,
+}
+
+struct ReportArgs {
+    config: Pubkey,
+    from: i64,
+    to: Option<i64>,
+    url: String,
+    output: ReportOutput,
+}
+
+fn parse_report_args(mut iter: impl Iterator<Item = String>) -> Result<ReportArgs, Box<dyn Error>> {
+    let mut config = None;
+    let mut from = None;
+    let mut to = None;
+    let mut url = DEFAULT_RPC_URL.to_string();
+    let mut output = ReportOutput::Json;
+
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--url" | "-u" => url = value()?,
+            "--from" => {
+                let value = value()?;
+                from = Some(parse_date(&value).ok_or(format!("invalid date {value}"))?)
+            }
+            "--to" => {
+                let value = value()?;
+                to = Some(parse_end_date(&value).ok_or(format!("invalid date {value}"))?)
+            }
+            "--output" | "-o" => {
+                output = match value()?.as_str() {
+                    "json" => ReportOutput::Json,
+                    "csv" => ReportOutput::Csv,
+                    other => return Err(format!("unknown output format {other}").into()),
+                }
+            }
+            _ if config.is_none() && !arg.starts_with('-') => {
+                config = Some(Pubkey::from_str(&arg).map_err(|e| format!("{arg}: {e}"))?)
+            }
+            _ => return Err(format!("unknown argument {arg}").into()),
+        }
+    }
+
+    Ok(ReportArgs {
+        config: config.ok_or("missing merchant operator config pubkey")?,
+        from: from.ok_or("missing --from")?,
+        to,
+        url,
+        output,
+    })
+}
+
+struct AuditArgs {
+    config: Pubkey,
+    url: String,
+}
+
+fn parse_audit_args(mut iter: impl Iterator<Item = String>) -> Result<AuditArgs, Box<dyn Error>> {
+    let mut config = None;
+    let mut url = DEFAULT_RPC_URL.to_string();
+
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {arg}"));
+        match arg.as_str() {
+            "--url" | "-u" => url = value()?,
+            _ if config.is_none() && !arg.starts_with('-') => {
+                config = Some(Pubkey::from_str(&arg).map_err(|e| format!("{arg}: {e}"))?)
+            }
+            _ => return Err(format!("unknown argument {arg}").into()),
+        }
+    }
+
+    Ok(AuditArgs {
+        config: config.ok_or("missing merchant operator config pubkey")?,
+        url,
+    })
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut iter = env::args().skip(1);
+    match iter.next().as_deref() {
+        Some("inspect") => {
+            let args = parse_inspect_args(iter)?;
+            let inspection = Inspector::new(args.url).inspect(&args.address)?;
+            match args.output {
+                Output::Json => println!("{}", serde_json::to_string_pretty(&inspection)?),
+                Output::Table => print!("{}", render_table(&inspection)),
+            }
+        }
+        Some("error") => {
+            let json = match iter.next() {
+                Some(query) => error_json(
+                    &lookup_error(&query).ok_or(format!("unknown program error {query}"))?,
+                ),
+                None => registry_json(),
+            };
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+        Some("amount") => {
+            let args = parse_amount_args(iter)?;
+            let decimals = Inspector::new(args.url).mint_decimals(&args.mint)?;
+            let json = amount_json(&args.mint, decimals, &args.amount, args.raw)?;
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+        Some("report") => {
+            let args = parse_report_args(iter)?;
+            let (report, decimals) =
+                Inspector::new(args.url).report(&args.config, args.from, args.to)?;
+            match args.output {
+                ReportOutput::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&report.to_json(&decimals))?
+                ),
+                ReportOutput::Csv => print!("{}", report.to_csv(&decimals)),
+            }
+        }
+        Some("audit") => {
+            let args = parse_audit_args(iter)?;
+            let (audit, decimals) = Inspector::new(args.url).audit(&args.config)?;
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&audit_json(&audit, &decimals))?
+            );
+            let short = audit.shortfalls().count();
+            if short > 0 {
+                return Err(format!("escrow short for {short} mint(s)").into());
+            }
+        }
+        Some(command) => return Err(format!("unknown command {command}").into()),
+        None => {
+            return Err(
+                "usage: commerce-cli inspect <PUBKEY> [--url <RPC_URL>] [--output json|table]\n       commerce-cli error [<CODE|NAME>]\n       commerce-cli amount <AMOUNT> --mint <MINT> [--raw] [--url <RPC_URL>]\n       commerce-cli report <CONFIG> --from <YYYY-MM-DD> [--to <YYYY-MM-DD>] [--url <RPC_URL>] [--output json|csv]\n       commerce-cli audit <CONFIG> [--url <RPC_URL>]"
+                    .into(),
+            )
+        }
+    }
+
+    Ok(())
+}
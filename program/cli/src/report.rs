@@ -0,0 +1,474 @@
+//! Settlement reports for `commerce-cli report`.
+//!
+//! A report covers one merchant operator config over a time range and is built from the
+//! events its instructions emitted in that range: `PaymentCreated` counts towards gross,
+//! `PaymentCleared` and `PlatformFeeCollected` towards operator and platform fees, and
+//! `PaymentRefunded` and `PaymentChargebacked` towards refunds and chargebacks. Net is gross less the other three, so it is negative when
+//! more was refunded in the range, for payments made before it, than was paid. The part of an
+//! authorization that `PaymentCaptured` releases back to the buyer counts as a refund. Events are
+//! read as `PaymentEventRecord`s, which attribute each to the payment and mint of the
+//! instruction that emitted it. Instructions invoked through CPI and compressed payments are
+//! not covered.
+//!
+//! Each payment is then cross-referenced with its account: the report lists its current
+//! status, `Closed` once the account is gone, and flags a stored amount that differs from
+//! its `PaymentCaptured` amount, or its `PaymentCreated` amount if it was not captured. Amounts
+//! still in escrow are those of the payments created in the range that are currently `Paid`,
+//! `RefundApproved` or `Authorized`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use commerce_program_client::{
+    accounts::Payment,
+    amount::format_amount,
+    events::{PaymentEventKind, PaymentEventRecord},
+    types::Status,
+};
+use serde_json::{json, Number, Value};
+use solana_pubkey::Pubkey;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Totals of one mint's payment events over the report's range.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MintTotals {
+    pub payments: u64,
+    pub gross: u64,
+    pub fees: u64,
+    pub refunds: u64,
+    pub chargebacks: u64,
+    pub in_escrow: u64,
+}
+
+impl MintTotals {
+    /// Gross less fees, refunds and chargebacks.
+    pub fn net(&self) -> i128 {
+        self.gross as i128 - self.fees as i128 - self.refunds as i128 - self.chargebacks as i128
+    }
+}
+
+/// One payment's events over the report's range, cross-referenced with its account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PaymentLine {
+    pub payment: Pubkey,
+    pub mint: Pubkey,
+    pub order_id: u32,
+    /// `PaymentCreated` amount, if the payment was made in the range.
+    pub created: Option<u64>,
+    /// `PaymentCaptured` amount, if the payment was captured in the range.
+    pub captured: Option<u64>,
+    pub fee: u64,
+    pub refunded: u64,
+    pub chargebacked: u64,
+    /// Current status, `None` once the account is closed.
+    pub status: Option<Status>,
+    /// Whether the account's amount differs from the `PaymentCaptured` amount, or the
+    /// `PaymentCreated` amount if not captured.
+    pub amount_mismatch: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub merchant_operator_config: Pubkey,
+    pub from: i64,
+    pub to: Option<i64>,
+    pub mints: BTreeMap<Pubkey, MintTotals>,
+    pub payments: Vec<PaymentLine>,
+}
+
+impl Report {
+    /// Builds the report for `events` in `[from, to)`, with the current `accounts` of their
+    /// payments, `None` for closed ones. Events outside the range are left out.
+    pub fn new(
+        merchant_operator_config: Pubkey,
+        from: i64,
+        to: Option<i64>,
+        events: &[PaymentEventRecord],
+        accounts: &HashMap<Pubkey, Option<Payment>>,
+    ) -> Self {
+        let mut lines: BTreeMap<Pubkey, PaymentLine> = BTreeMap::new();
+        let in_range = |event: &&PaymentEventRecord| {
+            event
+                .block_time
+                .is_some_and(|time| time >= from && to.is_none_or(|to| time < to))
+        };
+        // Approvals and closes move no funds; the payment's status comes from its account
+        let counted = |event: &&PaymentEventRecord| {
+            !matches!(
+                event.kind,
+                PaymentEventKind::RefundApproved | PaymentEventKind::Closed
+            )
+        };
+        for event in events.iter().filter(in_range).filter(counted) {
+            let line = lines.entry(event.payment).or_insert_with(|| PaymentLine {
+                payment: event.payment,
+                mint: event.mint,
+                order_id: event.order_id,
+                ..PaymentLine::default()
+            });
+            match event.kind {
+                PaymentEventKind::Created => line.created = Some(event.amount),
+                PaymentEventKind::Captured => line.captured = Some(event.amount),
+                PaymentEventKind::Cleared => line.fee = line.fee.saturating_add(event.operator_fee),
+                PaymentEventKind::Refunded => {
+                    line.refunded = line.refunded.saturating_add(event.amount)
+                }
+                PaymentEventKind::Chargebacked => {
+                    line.chargebacked = line.chargebacked.saturating_add(event.amount)
+                }
+                PaymentEventKind::PlatformFeeCollected => {
+                    line.fee = line.fee.saturating_add(event.amount)
+                }
+                PaymentEventKind::RefundApproved | PaymentEventKind::Closed => {}
+            }
+        }
+
+        let mut mints: BTreeMap<Pubkey, MintTotals> = BTreeMap::new();
+        let mut payments = Vec::with_capacity(lines.len());
+        for mut line in lines.into_values() {
+            let account = accounts.get(&line.payment).and_then(Option::as_ref);
+            line.status = account.map(|payment| payment.status);
+            line.amount_mismatch = matches!(
+                (account, line.captured.or(line.created)),
+                (Some(payment), Some(expected)) if payment.amount != expected
+            );
+            // The rest of a captured authorization went back to the buyer
+            if let (Some(created), Some(captured)) = (line.created, line.captured) {
+                line.refunded = line
+                    .refunded
+                    .saturating_add(created.saturating_sub(captured));
+            }
+
+            let totals = mints.entry(line.mint).or_default();
+            if let Some(created) = line.created {
+                totals.payments += 1;
+                totals.gross = totals.gross.saturating_add(created);
+                if let (
+                    Some(Status::Paid | Status::RefundApproved | Status::Authorized),
+                    Some(payment),
+                ) = (line.status, account)
+                {
+                    totals.in_escrow = totals.in_escrow.saturating_add(payment.amount);
+                }
+            }
+            totals.fees = totals.fees.saturating_add(line.fee);
+            totals.refunds = totals.refunds.saturating_add(line.refunded);
+            totals.chargebacks = totals.chargebacks.saturating_add(line.chargebacked);
+            payments.push(line);
+        }
+
+        Self {
+            merchant_operator_config,
+            from,
+            to,
+            mints,
+            payments,
+        }
+    }
+
+    /// The report as JSON, with raw amounts and each mint's `decimals`.
+    pub fn to_json(&self, decimals: &HashMap<Pubkey, u8>) -> Value {
+        let mints: Vec<Value> = self
+            .mints
+            .iter()
+            .map(|(mint, totals)| {
+                json!({
+                    "mint": mint.to_string(),
+                    "decimals": decimals.get(mint),
+                    "payments": totals.payments,
+                    "gross": totals.gross,
+                    "fees": totals.fees,
+                    "refunds": totals.refunds,
+                    "chargebacks": totals.chargebacks,
+                    "net": Number::from_i128(totals.net()),
+                    "in_escrow": totals.in_escrow,
+                })
+            })
+            .collect();
+        let payments: Vec<Value> = self
+            .payments
+            .iter()
+            .map(|line| {
+                json!({
+                    "payment": line.payment.to_string(),
+                    "mint": line.mint.to_string(),
+                    "order_id": line.order_id,
+                    "created": line.created,
+                    "captured": line.captured,
+                    "fee": line.fee,
+                    "refunded": line.refunded,
+                    "chargebacked": line.chargebacked,
+                    "status": line
+                        .status
+                        .map_or("Closed".to_string(), |status| format!("{status:?}")),
+                    "amount_mismatch": line.amount_mismatch,
+                })
+            })
+            .collect();
+
+        json!({
+            "merchant_operator_config": self.merchant_operator_config.to_string(),
+            "from": self.from,
+            "to": self.to,
+            "mints": mints,
+            "payments": payments,
+        })
+    }
+
+    /// The per-mint totals as CSV, with amounts in display units. Mints missing from
+    /// `decimals` are listed in raw units.
+    pub fn to_csv(&self, decimals: &HashMap<Pubkey, u8>) -> String {
+        let mut csv = String::from("mint,payments,gross,fees,refunds,chargebacks,net,in_escrow\n");
+        for (mint, totals) in &self.mints {
+            let decimals = decimals.get(mint).copied().unwrap_or(0);
+            let amount = |amount: u64| format_amount(amount, decimals);
+            let net = totals.net();
+            let net = match u64::try_from(net.unsigned_abs()) {
+                Ok(abs) if net < 0 => format!("-{}", amount(abs)),
+                Ok(abs) => amount(abs),
+                Err(_) => net.to_string(),
+            };
+            csv.push_str(&format!(
+                "{mint},{},{},{},{},{},{net},{}\n",
+                totals.payments,
+                amount(totals.gross),
+                amount(totals.fees),
+                amount(totals.refunds),
+                amount(totals.chargebacks),
+                amount(totals.in_escrow),
+            ));
+        }
+        csv
+    }
+}
+
+/// Parses a `YYYY-MM-DD` UTC date into the unix timestamp of its start.
+pub fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return None;
+    }
+
+    // Days since 1970-01-01 in the proleptic Gregorian calendar, counting years from March
+    // so the leap day ends them
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some((era * 146_097 + day_of_era - 719_468) * SECONDS_PER_DAY)
+}
+
+/// Parses an inclusive `YYYY-MM-DD` end date into the unix timestamp the range stops before.
+pub fn parse_end_date(date: &str) -> Option<i64> {
+    Some(parse_date(date)? + SECONDS_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commerce_program_client::types::{FeeOverride, FeeType};
+
+    const MINT_A: Pubkey = Pubkey::new_from_array([1; 32]);
+    const MINT_B: Pubkey = Pubkey::new_from_array([2; 32]);
+
+    fn event(
+        payment: u8,
+        mint: Pubkey,
+        block_time: i64,
+        amount: u64,
+        kind: PaymentEventKind,
+    ) -> PaymentEventRecord {
+        PaymentEventRecord {
+            signature: "sig".to_string(),
+            slot: block_time as u64,
+            block_time: Some(block_time),
+            kind,
+            payment: Pubkey::new_from_array([payment; 32]),
+            merchant_operator_config: Pubkey::default(),
+            mint,
+            buyer: Pubkey::default(),
+            merchant: Pubkey::default(),
+            operator: Pubkey::default(),
+            order_id: payment as u32,
+            amount,
+            operator_fee: 0,
+        }
+    }
+
+    fn cleared(
+        payment: u8,
+        mint: Pubkey,
+        block_time: i64,
+        amount: u64,
+        operator_fee: u64,
+    ) -> PaymentEventRecord {
+        PaymentEventRecord {
+            operator_fee,
+            ..event(payment, mint, block_time, amount, PaymentEventKind::Cleared)
+        }
+    }
+
+    fn payment(amount: u64, status: Status) -> Payment {
+        Payment {
+            discriminator: 3,
+            order_id: 1,
+            amount,
+            created_at: 0,
+            status,
+            bump: 255,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride {
+                fee: 0,
+                fee_type: FeeType::Bps,
+            },
+            references: [Pubkey::default(); 2],
+            rent_payer: Pubkey::default(),
+            has_order: false,
+        }
+    }
+
+    #[test]
+    fn test_report_totals() {
+        let events = [
+            event(1, MINT_A, 100, 1_000, PaymentEventKind::Created),
+            cleared(1, MINT_A, 150, 1_000, 30),
+            event(1, MINT_A, 150, 20, PaymentEventKind::PlatformFeeCollected),
+            event(2, MINT_A, 120, 500, PaymentEventKind::Created),
+            event(2, MINT_A, 125, 500, PaymentEventKind::RefundApproved),
+            // Closed in the range with no other event in it
+            event(6, MINT_A, 140, 0, PaymentEventKind::Closed),
+            // Made before the range, refunded in it
+            event(3, MINT_A, 50, 2_000, PaymentEventKind::Created),
+            event(3, MINT_A, 130, 2_000, PaymentEventKind::Refunded),
+            event(4, MINT_B, 140, 700, PaymentEventKind::Created),
+            event(4, MINT_B, 160, 700, PaymentEventKind::Chargebacked),
+            // After the range
+            event(5, MINT_B, 200, 900, PaymentEventKind::Created),
+        ];
+        let accounts = HashMap::from([
+            (
+                Pubkey::new_from_array([1; 32]),
+                Some(payment(1_000, Status::Cleared)),
+            ),
+            (
+                Pubkey::new_from_array([2; 32]),
+                Some(payment(500, Status::Paid)),
+            ),
+            (Pubkey::new_from_array([3; 32]), None),
+            (
+                Pubkey::new_from_array([4; 32]),
+                Some(payment(600, Status::Refunded)),
+            ),
+        ]);
+
+        let report = Report::new(Pubkey::default(), 100, Some(200), &events, &accounts);
+
+        let totals_a = &report.mints[&MINT_A];
+        assert_eq!(
+            *totals_a,
+            MintTotals {
+                payments: 2,
+                gross: 1_500,
+                fees: 50,
+                refunds: 2_000,
+                chargebacks: 0,
+                in_escrow: 500,
+            }
+        );
+        assert_eq!(totals_a.net(), -550);
+        assert_eq!(report.mints[&MINT_B].net(), 0);
+
+        assert_eq!(report.payments.len(), 4);
+        assert_eq!(report.payments[2].created, None);
+        assert_eq!(report.payments[2].status, None);
+        assert!(!report.payments[0].amount_mismatch);
+        assert!(report.payments[3].amount_mismatch);
+
+        let json = report.to_json(&HashMap::from([(MINT_A, 2)]));
+        assert_eq!(json["mints"][0]["net"], -550);
+        assert_eq!(json["mints"][0]["decimals"], 2);
+        assert_eq!(json["mints"][1]["decimals"], Value::Null);
+        assert_eq!(json["payments"][2]["status"], "Closed");
+        assert_eq!(json["payments"][3]["status"], "Refunded");
+    }
+
+    #[test]
+    fn test_report_captured_authorization() {
+        let events = [
+            event(1, MINT_A, 100, 1_000, PaymentEventKind::Created),
+            event(1, MINT_A, 110, 600, PaymentEventKind::Captured),
+            event(2, MINT_A, 120, 500, PaymentEventKind::Created),
+        ];
+        let accounts = HashMap::from([
+            (
+                Pubkey::new_from_array([1; 32]),
+                Some(payment(600, Status::Paid)),
+            ),
+            (
+                Pubkey::new_from_array([2; 32]),
+                Some(payment(500, Status::Authorized)),
+            ),
+        ]);
+
+        let report = Report::new(Pubkey::default(), 0, None, &events, &accounts);
+
+        // The released 400 is refunded and both holds are still in escrow
+        let totals = &report.mints[&MINT_A];
+        assert_eq!(totals.gross, 1_500);
+        assert_eq!(totals.refunds, 400);
+        assert_eq!(totals.in_escrow, 1_100);
+        assert_eq!(totals.net(), 1_100);
+
+        assert_eq!(report.payments[0].captured, Some(600));
+        assert_eq!(report.payments[0].refunded, 400);
+        assert!(!report.payments[0].amount_mismatch);
+    }
+
+    #[test]
+    fn test_report_csv() {
+        let events = [
+            event(1, MINT_A, 100, 1_050, PaymentEventKind::Created),
+            cleared(1, MINT_A, 110, 1_050, 5),
+            event(2, MINT_B, 100, 300, PaymentEventKind::Refunded),
+        ];
+        let report = Report::new(Pubkey::default(), 0, None, &events, &HashMap::new());
+
+        assert_eq!(
+            report.to_csv(&HashMap::from([(MINT_A, 2), (MINT_B, 2)])),
+            format!(
+                "mint,payments,gross,fees,refunds,chargebacks,net,in_escrow\n\
+                 {MINT_A},1,10.5,0.05,0,0,10.45,0\n\
+                 {MINT_B},0,0,0,3,0,-3,0\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2000-03-01"), Some(951_868_800));
+        assert_eq!(parse_date("2024-02-29"), Some(1_709_164_800));
+        assert_eq!(parse_end_date("2024-02-29"), Some(1_709_251_200));
+        assert_eq!(parse_date("1969-12-31"), Some(-SECONDS_PER_DAY));
+
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("2024-04-31"), None);
+        assert_eq!(parse_date("2024-04"), None);
+        assert_eq!(parse_date("yesterday"), None);
+    }
+}
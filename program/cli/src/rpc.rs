@@ -0,0 +1,430 @@
+//! Fetches an account to inspect along with the context its derived info needs.
+//!
+//! Payment accounts do not store their merchant, config or mint, so they are read from the
+//! `MakePayment` instruction found in the payment's oldest transactions, then the config is
+//! fetched for its close window and refund policy. Windows are measured against the
+//! cluster's latest block time, which is what the program checks.
+//!
+//! Reports page through a config's transactions newest first until the start of their range,
+//! decode the events each emitted, then fetch the payment accounts and mints they name.
+//! Escrow audits page through all of them for the payments created on the config.
+
+use std::{collections::HashMap, str::FromStr};
+
+use commerce_program_client::{
+    accounts::Payment,
+    amount::{mint_decimals, AmountError},
+    checkout::{get_associated_token_address, TOKEN_PROGRAM_ID},
+    discriminator::{parse_instruction, ParsedInstruction},
+    escrow_audit::{fetch_audit_escrow, EscrowAudit},
+    events::{payment_event_records, PaymentEventKind, PaymentEventRecord},
+    preview::decode_merchant_operator_config,
+    COMMERCE_PROGRAM_ID,
+};
+use serde_json::{json, Value};
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_commitment_config::CommitmentConfig;
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_pubkey::Pubkey;
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_signature::Signature;
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+    UiTransactionEncoding,
+};
+
+use crate::{
+    inspect::{
+        derived_escrow_atas, derived_merchant, derived_payment, derived_payment_tree,
+        CommerceAccount, InspectError, PaymentOrigin,
+    },
+    report::Report,
+};
+
+/// `getMultipleAccounts` limit.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+pub struct Inspector {
+    rpc: RpcClient,
+}
+
+impl Inspector {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed()),
+        }
+    }
+
+    /// Decodes the account at `address` with its derived info.
+    pub fn inspect(&self, address: &Pubkey) -> Result<Value, InspectError> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(address, self.rpc.commitment())?
+            .value
+            .ok_or(InspectError::NotFound(*address))?;
+        if account.owner != COMMERCE_PROGRAM_ID {
+            return Err(InspectError::NotCommerceAccount(*address, account.owner));
+        }
+
+        let decoded = CommerceAccount::decode(&account.data)?;
+        let derived = match &decoded {
+            CommerceAccount::MerchantOperatorConfig {
+                config,
+                accepted_currencies,
+                ..
+            } => json!({
+                "escrow_atas": derived_escrow_atas(address, &config.merchant, accepted_currencies),
+            }),
+            CommerceAccount::Payment(payment) => self.derive_payment(address, payment)?,
+            CommerceAccount::PaymentTree(tree) => derived_payment_tree(tree),
+            CommerceAccount::MerchantStats(stats) => json!({
+                "legacy_escrow_ata": get_associated_token_address(&stats.merchant, &stats.mint, &TOKEN_PROGRAM_ID)
+                    .to_string(),
+            }),
+            CommerceAccount::Merchant(_) => derived_merchant(address),
+            CommerceAccount::Operator(_)
+            | CommerceAccount::OperatorDelegate(_)
+            | CommerceAccount::SettlementBatch(_)
+            | CommerceAccount::Order(_)
+            | CommerceAccount::MerchantProfile(_)
+            | CommerceAccount::RefundApproval(_)
+            | CommerceAccount::RiskState(_)
+            | CommerceAccount::BuyerIndex(_)
+            | CommerceAccount::Quote(_)
+            | CommerceAccount::ConfigRegistryPage(_)
+            | CommerceAccount::OperatorFreezeList(_)
+            | CommerceAccount::ConversionRate(_)
+            | CommerceAccount::PaymentNote(_)
+            | CommerceAccount::WebhookCommitment(_)
+            | CommerceAccount::PaymentAnnotation(_) => Value::Null,
+        };
+
+        let mut inspection = json!({
+            "address": address.to_string(),
+            "type": decoded.type_name(),
+            "lamports": account.lamports,
+            "data": decoded.to_json(),
+        });
+        if !derived.is_null() {
+            inspection["derived"] = derived;
+        }
+        Ok(inspection)
+    }
+
+    fn derive_payment(&self, address: &Pubkey, payment: &Payment) -> Result<Value, InspectError> {
+        let Some(origin) = self.payment_origin(address)? else {
+            return Ok(json!({
+                "unavailable": "no top-level MakePayment instruction found for this payment",
+            }));
+        };
+
+        let config_account = self
+            .rpc
+            .get_account_with_commitment(&origin.merchant_operator_config, self.rpc.commitment())?
+            .value
+            .ok_or(InspectError::NotFound(origin.merchant_operator_config))?;
+        let (config, policies, _) = decode_merchant_operator_config(&config_account.data)?;
+
+        let decimals = self.mint_decimals(&origin.mint)?;
+        let now = self.rpc.get_block_time(self.rpc.get_slot()?)?;
+
+        Ok(derived_payment(
+            payment, &origin, &config, &policies, decimals, now,
+        ))
+    }
+
+    /// Decimals of the token mint at `mint`.
+    pub fn mint_decimals(&self, mint: &Pubkey) -> Result<u8, InspectError> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(mint, self.rpc.commitment())?
+            .value
+            .ok_or(InspectError::NotFound(*mint))?;
+        Ok(mint_decimals(&account.data).ok_or(AmountError::InvalidMint(*mint))?)
+    }
+
+    /// Finds the `MakePayment` that created `payment`, searching its transactions oldest first.
+    fn payment_origin(&self, payment: &Pubkey) -> Result<Option<PaymentOrigin>, InspectError> {
+        let signatures = self.rpc.get_signatures_for_address_with_config(
+            payment,
+            GetConfirmedSignaturesForAddress2Config {
+                commitment: Some(self.rpc.commitment()),
+                ..GetConfirmedSignaturesForAddress2Config::default()
+            },
+        )?;
+
+        for status in signatures
+            .into_iter()
+            .rev()
+            .filter(|status| status.err.is_none())
+        {
+            let transaction = self.transaction(&status.signature)?;
+            if let Some(origin) = payment_origin_in_transaction(&transaction, payment) {
+                return Ok(Some(PaymentOrigin {
+                    signature: status.signature,
+                    ..origin
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Settlement report of `config` over `[from, to)`, with the decimals of its mints.
+    pub fn report(
+        &self,
+        config: &Pubkey,
+        from: i64,
+        to: Option<i64>,
+    ) -> Result<(Report, HashMap<Pubkey, u8>), InspectError> {
+        let events = self.payment_events(config, from, to)?;
+
+        let mut payments: Vec<Pubkey> = events.iter().map(|event| event.payment).collect();
+        payments.sort_unstable();
+        payments.dedup();
+        let mut accounts = HashMap::with_capacity(payments.len());
+        for chunk in payments.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let fetched = self
+                .rpc
+                .get_multiple_accounts_with_commitment(chunk, self.rpc.commitment())?
+                .value;
+            for (payment, account) in chunk.iter().zip(fetched) {
+                let account = account
+                    .map(|account| Payment::from_bytes(&account.data))
+                    .transpose()?;
+                accounts.insert(*payment, account);
+            }
+        }
+
+        let report = Report::new(*config, from, to, &events, &accounts);
+        let decimals = report
+            .mints
+            .keys()
+            .map(|mint| Ok((*mint, self.mint_decimals(mint)?)))
+            .collect::<Result<_, InspectError>>()?;
+        Ok((report, decimals))
+    }
+
+    /// Audit of `config`'s escrow against the payments ever created on it, with the decimals
+    /// of its mints.
+    pub fn audit(
+        &self,
+        config: &Pubkey,
+    ) -> Result<(EscrowAudit, HashMap<Pubkey, u8>), InspectError> {
+        let mut payments: Vec<(Pubkey, Pubkey)> = self
+            .payment_events(config, i64::MIN, None)?
+            .into_iter()
+            .filter(|event| event.kind == PaymentEventKind::Created)
+            .map(|event| (event.payment, event.mint))
+            .collect();
+        payments.sort_unstable();
+        payments.dedup();
+
+        let audit = fetch_audit_escrow(&self.rpc, config, &payments)
+            .map_err(|e| InspectError::Rpc(e.to_string()))?;
+        let decimals = audit
+            .mints
+            .iter()
+            .map(|mint| Ok((mint.mint, self.mint_decimals(&mint.mint)?)))
+            .collect::<Result<_, InspectError>>()?;
+        Ok((audit, decimals))
+    }
+
+    /// Payment events emitted on `config` over `[from, to)`, newest first.
+    fn payment_events(
+        &self,
+        config: &Pubkey,
+        from: i64,
+        to: Option<i64>,
+    ) -> Result<Vec<PaymentEventRecord>, InspectError> {
+        let mut events = Vec::new();
+        let mut before = None;
+        loop {
+            let signatures = self.rpc.get_signatures_for_address_with_config(
+                config,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    commitment: Some(self.rpc.commitment()),
+                    ..GetConfirmedSignaturesForAddress2Config::default()
+                },
+            )?;
+            let Some(last) = signatures.last() else {
+                return Ok(events);
+            };
+            before = Some(parse_signature(&last.signature)?);
+
+            for status in &signatures {
+                let Some(block_time) = status.block_time else {
+                    continue;
+                };
+                if block_time < from {
+                    return Ok(events);
+                }
+                if status.err.is_some() || to.is_some_and(|to| block_time >= to) {
+                    continue;
+                }
+
+                let transaction = self.transaction(&status.signature)?;
+                events.extend(
+                    payment_event_records(&transaction, &status.signature)
+                        .into_iter()
+                        .filter(|event| event.merchant_operator_config == *config),
+                );
+            }
+        }
+    }
+
+    fn transaction(
+        &self,
+        signature: &str,
+    ) -> Result<EncodedConfirmedTransactionWithStatusMeta, InspectError> {
+        Ok(self.rpc.get_transaction_with_config(
+            &parse_signature(signature)?,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(self.rpc.commitment()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?)
+    }
+}
+
+fn parse_signature(signature: &str) -> Result<Signature, InspectError> {
+    Signature::from_str(signature).map_err(|e| InspectError::Rpc(e.to_string()))
+}
+
+/// Account keys of a transaction. v0 transactions may load accounts from lookup tables;
+/// they follow the static keys.
+fn account_keys(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    static_account_keys: &[Pubkey],
+) -> Vec<Pubkey> {
+    let mut account_keys = static_account_keys.to_vec();
+    if let Some(meta) = &transaction.transaction.meta {
+        if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+            account_keys.extend(
+                loaded
+                    .writable
+                    .iter()
+                    .chain(&loaded.readonly)
+                    .filter_map(|key| Pubkey::from_str(key).ok()),
+            );
+        }
+    }
+    account_keys
+}
+
+fn payment_origin_in_transaction(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    payment: &Pubkey,
+) -> Option<PaymentOrigin> {
+    let decoded = transaction.transaction.transaction.decode()?;
+    let account_keys = account_keys(transaction, decoded.message.static_account_keys());
+
+    payment_origin_in_instructions(&account_keys, decoded.message.instructions(), payment)
+}
+
+/// Decodes the top-level `MakePayment` instruction creating `payment`, if any. The returned
+/// origin has no signature.
+fn payment_origin_in_instructions(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    payment: &Pubkey,
+) -> Option<PaymentOrigin> {
+    instructions
+        .iter()
+        .filter(|ix| account_keys.get(ix.program_id_index as usize) == Some(&COMMERCE_PROGRAM_ID))
+        .filter(|ix| {
+            matches!(
+                parse_instruction(&ix.data),
+                Ok(ParsedInstruction::MakePayment(_))
+            )
+        })
+        .find_map(|ix| {
+            let key = |index: usize| {
+                ix.accounts
+                    .get(index)
+                    .and_then(|i| account_keys.get(*i as usize))
+                    .copied()
+            };
+            if key(1)? != *payment {
+                return None;
+            }
+
+            Some(PaymentOrigin {
+                buyer: key(3)?,
+                operator: key(4)?,
+                merchant: key(5)?,
+                merchant_operator_config: key(6)?,
+                mint: key(7)?,
+                signature: String::new(),
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commerce_program_client::instructions::MakePaymentBuilder;
+
+    fn compile(
+        account_keys: &[Pubkey],
+        ix: &solana_instruction::Instruction,
+    ) -> CompiledInstruction {
+        let index = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+        CompiledInstruction {
+            program_id_index: index(&ix.program_id),
+            accounts: ix.accounts.iter().map(|meta| index(&meta.pubkey)).collect(),
+            data: ix.data.clone(),
+        }
+    }
+
+    #[test]
+    fn test_payment_origin_in_instructions() {
+        let keys: Vec<Pubkey> = (0..18).map(|_| Pubkey::new_unique()).collect();
+        let ix = MakePaymentBuilder::new()
+            .payer(keys[0])
+            .payment(keys[1])
+            .operator_authority(keys[2])
+            .buyer(keys[3])
+            .operator(keys[4])
+            .merchant(keys[5])
+            .merchant_operator_config(keys[6])
+            .mint(keys[7])
+            .buyer_ata(keys[8])
+            .escrow_authority(keys[17])
+            .merchant_escrow_ata(keys[9])
+            .merchant_settlement_ata(keys[10])
+            .settlement_wallet(keys[11])
+            .token_program(keys[12])
+            .associated_token_program(keys[13])
+            .system_program(keys[14])
+            .event_authority(keys[15])
+            .order_id(42)
+            .amount(1_000)
+            .idempotency_key([0; 16])
+            .instruction();
+
+        let mut account_keys = keys.clone();
+        account_keys.push(COMMERCE_PROGRAM_ID);
+        let compiled = [compile(&account_keys, &ix)];
+
+        assert_eq!(
+            payment_origin_in_instructions(&account_keys, &compiled, &keys[1]),
+            Some(PaymentOrigin {
+                buyer: keys[3],
+                operator: keys[4],
+                merchant: keys[5],
+                merchant_operator_config: keys[6],
+                mint: keys[7],
+                signature: String::new(),
+            })
+        );
+
+        // A different payment
+        assert_eq!(
+            payment_origin_in_instructions(&account_keys, &compiled, &keys[16]),
+            None
+        );
+    }
+}
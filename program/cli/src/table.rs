@@ -0,0 +1,70 @@
+//! Two-column table rendering of an inspection.
+//!
+//! Nested objects and arrays are flattened into dotted and indexed paths, e.g.
+//! `data.policies[0].max_amount`, so every leaf value gets its own row. Keys are listed in
+//! alphabetical order.
+
+use serde_json::Value;
+
+/// Renders `value` as `path  value` rows with the paths padded to a common width.
+pub fn render_table(value: &Value) -> String {
+    let mut rows = Vec::new();
+    flatten(String::new(), value, &mut rows);
+
+    let width = rows.iter().map(|(path, _)| path.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(path, value)| format!("{path:<width$}  {value}\n"))
+        .collect()
+}
+
+fn flatten(path: String, value: &Value, rows: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                flatten(path, value, rows);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, item) in items.iter().enumerate() {
+                flatten(format!("{path}[{index}]"), item, rows);
+            }
+        }
+        Value::Object(_) => rows.push((path, "{}".to_string())),
+        Value::Array(_) => rows.push((path, "[]".to_string())),
+        Value::Null => rows.push((path, "-".to_string())),
+        Value::String(string) => rows.push((path, string.clone())),
+        _ => rows.push((path, value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_table_flattens_paths() {
+        let value = json!({
+            "type": "MerchantOperatorConfig",
+            "data": {
+                "policies": [{ "type": "Memo", "memo_on_clear": true }],
+                "accepted_currencies": [],
+                "pending_authority": null,
+            },
+        });
+
+        assert_eq!(
+            render_table(&value),
+            "data.accepted_currencies        []\n\
+             data.pending_authority          -\n\
+             data.policies[0].memo_on_clear  true\n\
+             data.policies[0].type           Memo\n\
+             type                            MerchantOperatorConfig\n"
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! TTL cache of the accounts every checkout reads but that rarely change.
+//!
+//! Each `MakePayment` needs its config, the config's Merchant (for the settlement wallet) and
+//! its Operator, which only change when the merchant or operator updates them. An
+//! [`AccountCache`] keeps their data for a TTL, so a high-volume operator fetches them once per
+//! TTL rather than once per checkout. The `fetch_*` methods refetch only missing or expired
+//! addresses, in `getMultipleAccounts` requests of at most [`MAX_MULTIPLE_ACCOUNTS`], and
+//! `CheckoutTransactionBuilder::fetch_accounts` fills a checkout's merchant, operator and
+//! settlement wallet through the cache.
+//!
+//! Cached data may be up to one TTL old. After changing an account, e.g. the merchant's
+//! settlement wallet, [`AccountCache::invalidate`] it so the next checkout refetches it.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use solana_pubkey::Pubkey;
+
+#[cfg(feature = "fetch")]
+use crate::generated::{
+    accounts::{Merchant, MerchantOperatorConfig, Operator},
+    types::PolicyData,
+};
+
+/// TTL of [`AccountCache::default`].
+pub const DEFAULT_ACCOUNT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Most addresses a `getMultipleAccounts` request takes.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Account data by address, each kept for the cache's TTL after it was fetched.
+#[derive(Clone, Debug)]
+pub struct AccountCache {
+    ttl: Duration,
+    entries: HashMap<Pubkey, CachedAccount>,
+}
+
+#[derive(Clone, Debug)]
+struct CachedAccount {
+    data: Vec<u8>,
+    fetched_at: Instant,
+}
+
+impl Default for AccountCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_ACCOUNT_CACHE_TTL)
+    }
+}
+
+impl AccountCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Caches `data` as the current data of `address`, e.g. after creating the account.
+    pub fn insert(&mut self, address: Pubkey, data: Vec<u8>) -> &mut Self {
+        self.insert_at(address, data, Instant::now())
+    }
+
+    /// Drops `address`, so it is refetched on next use.
+    pub fn invalidate(&mut self, address: &Pubkey) -> &mut Self {
+        self.entries.remove(address);
+        self
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Data of `address`, unless missing or older than the TTL.
+    pub fn get(&self, address: &Pubkey) -> Option<&[u8]> {
+        self.get_at(address, Instant::now())
+    }
+
+    fn insert_at(&mut self, address: Pubkey, data: Vec<u8>, now: Instant) -> &mut Self {
+        self.entries.insert(
+            address,
+            CachedAccount {
+                data,
+                fetched_at: now,
+            },
+        );
+        self
+    }
+
+    fn get_at(&self, address: &Pubkey, now: Instant) -> Option<&[u8]> {
+        self.entries
+            .get(address)
+            .filter(|entry| now.saturating_duration_since(entry.fetched_at) < self.ttl)
+            .map(|entry| entry.data.as_slice())
+    }
+
+    /// Each of `addresses` that is missing or expired, once.
+    fn stale(&self, addresses: &[Pubkey], now: Instant) -> Vec<Pubkey> {
+        let mut stale: Vec<Pubkey> = addresses
+            .iter()
+            .filter(|address| self.get_at(address, now).is_none())
+            .copied()
+            .collect();
+        stale.sort();
+        stale.dedup();
+        stale
+    }
+
+    /// Fetches each of `addresses` that is missing or expired. Accounts that do not exist are
+    /// left out of the cache.
+    #[cfg(feature = "fetch")]
+    pub fn fetch(
+        &mut self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        addresses: &[Pubkey],
+    ) -> Result<(), std::io::Error> {
+        let now = Instant::now();
+        let stale = self.stale(addresses, now);
+        if stale.is_empty() {
+            return Ok(());
+        }
+
+        for (address, account) in stale.iter().zip(get_multiple_accounts(rpc, &stale)?) {
+            match account {
+                Some(account) => self.insert_at(*address, account.data, now),
+                None => self.invalidate(address),
+            };
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "fetch")]
+    pub fn fetch_merchant(
+        &mut self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        address: &Pubkey,
+    ) -> Result<Merchant, std::io::Error> {
+        Merchant::from_bytes(self.fetch_data(rpc, address)?)
+    }
+
+    #[cfg(feature = "fetch")]
+    pub fn fetch_operator(
+        &mut self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        address: &Pubkey,
+    ) -> Result<Operator, std::io::Error> {
+        Operator::from_bytes(self.fetch_data(rpc, address)?)
+    }
+
+    /// The config along with its policies and accepted currencies.
+    #[cfg(feature = "fetch")]
+    pub fn fetch_merchant_operator_config(
+        &mut self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        address: &Pubkey,
+    ) -> Result<(MerchantOperatorConfig, Vec<PolicyData>, Vec<Pubkey>), std::io::Error> {
+        crate::preview::decode_merchant_operator_config(self.fetch_data(rpc, address)?)
+    }
+
+    #[cfg(feature = "fetch")]
+    fn fetch_data(
+        &mut self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        address: &Pubkey,
+    ) -> Result<&[u8], std::io::Error> {
+        self.fetch(rpc, &[*address])?;
+        self.get(address).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Account not found: {address}"),
+            )
+        })
+    }
+}
+
+/// `getMultipleAccounts` for any number of `addresses`, split into requests of at most
+/// [`MAX_MULTIPLE_ACCOUNTS`].
+#[cfg(feature = "fetch")]
+pub fn get_multiple_accounts(
+    rpc: &solana_client::rpc_client::RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<Option<solana_account::Account>>, std::io::Error> {
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        accounts.extend(
+            rpc.get_multiple_accounts(chunk)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?,
+        );
+    }
+    Ok(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_expires_after_ttl() {
+        let mut cache = AccountCache::new(Duration::from_secs(30));
+        let address = Pubkey::new_unique();
+        let start = Instant::now();
+
+        cache.insert_at(address, vec![1, 2, 3], start);
+
+        assert_eq!(cache.get_at(&address, start), Some(&[1, 2, 3][..]));
+        assert_eq!(
+            cache.get_at(&address, start + Duration::from_secs(29)),
+            Some(&[1, 2, 3][..])
+        );
+        assert_eq!(
+            cache.get_at(&address, start + Duration::from_secs(30)),
+            None
+        );
+        assert_eq!(cache.get_at(&Pubkey::new_unique(), start), None);
+    }
+
+    #[test]
+    fn test_invalidate_and_clear() {
+        let mut cache = AccountCache::default();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        cache.insert(first, vec![1]).insert(second, vec![2]);
+
+        cache.invalidate(&first);
+        assert_eq!(cache.get(&first), None);
+        assert_eq!(cache.get(&second), Some(&[2][..]));
+
+        cache.clear();
+        assert_eq!(cache.get(&second), None);
+    }
+
+    #[test]
+    fn test_stale_skips_fresh_and_dedups() {
+        let mut cache = AccountCache::new(Duration::from_secs(30));
+        let (fresh, expired, missing) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let start = Instant::now();
+        cache.insert_at(fresh, vec![1], start + Duration::from_secs(20));
+        cache.insert_at(expired, vec![2], start);
+
+        let now = start + Duration::from_secs(40);
+        let mut expected = vec![expired, missing];
+        expected.sort();
+        assert_eq!(
+            cache.stale(&[missing, fresh, expired, missing], now),
+            expected
+        );
+        assert!(cache.stale(&[fresh], now).is_empty());
+    }
+}
@@ -0,0 +1,284 @@
+//! Token amounts in display units and raw units.
+//!
+//! The program only deals in raw amounts: `10_500_000` is 10.5 USDC (6 decimals) but 0.0105
+//! of a 9-decimal token. Converting with the wrong mint's decimals is off by orders of
+//! magnitude, so every conversion here takes the mint's decimals explicitly. Read them from
+//! the mint account with [`mint_decimals`], or keep them per mint in [`MintDecimals`], which
+//! refuses to convert amounts of mints it does not know.
+//!
+//! Conversions are exact: a display amount with more decimal places than the mint has, or a
+//! raw amount that does not fit in a `u64`, is an error rather than rounded.
+
+use std::collections::HashMap;
+
+use solana_pubkey::Pubkey;
+use thiserror::Error;
+
+/// Size of an SPL Token mint, and of the base of a Token-2022 mint.
+pub const MINT_LEN: usize = 82;
+
+// Offsets of `decimals` and `is_initialized` in the mint layout
+const MINT_DECIMALS_OFFSET: usize = 44;
+const MINT_IS_INITIALIZED_OFFSET: usize = 45;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("invalid amount {0:?}")]
+    Invalid(String),
+    #[error("amount {amount} has more than {decimals} decimal places")]
+    TooManyDecimals { amount: String, decimals: u8 },
+    #[error("amount {0} is too large")]
+    Overflow(String),
+    #[error("decimals of mint {0} are unknown")]
+    UnknownMint(Pubkey),
+    #[error("account {0} is not a token mint")]
+    InvalidMint(Pubkey),
+    #[error("rpc error: {0}")]
+    Rpc(String),
+}
+
+/// Converts a display amount such as `"10.50"` into raw units of a mint with `decimals`.
+///
+/// # Errors
+/// Returns `AmountError::TooManyDecimals` if `amount` is more precise than the mint, and
+/// `AmountError::Overflow` if the raw amount does not fit in a `u64`.
+pub fn parse_amount(amount: &str, decimals: u8) -> Result<u64, AmountError> {
+    let invalid = || AmountError::Invalid(amount.to_string());
+
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(AmountError::TooManyDecimals {
+            amount: amount.to_string(),
+            decimals,
+        });
+    }
+
+    let mut raw = String::with_capacity(whole.len() + decimals as usize);
+    raw.push_str(whole);
+    raw.push_str(fraction);
+    raw.extend(std::iter::repeat('0').take(decimals as usize - fraction.len()));
+
+    let raw = raw.trim_start_matches('0');
+    if raw.is_empty() {
+        return Ok(0);
+    }
+    raw.parse()
+        .map_err(|_| AmountError::Overflow(amount.to_string()))
+}
+
+/// Formats a raw amount of a mint with `decimals` for display, without trailing zeros,
+/// e.g. `10.5` for `10_500_000` with 6 decimals.
+pub fn format_amount(amount: u64, decimals: u8) -> String {
+    let digits = format!("{amount:0>width$}", width = decimals as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+/// Decimals of an SPL Token or Token-2022 mint account, or `None` if `data` is not an
+/// initialized mint.
+pub fn mint_decimals(data: &[u8]) -> Option<u8> {
+    if data.len() < MINT_LEN || data[MINT_IS_INITIALIZED_OFFSET] != 1 {
+        return None;
+    }
+    Some(data[MINT_DECIMALS_OFFSET])
+}
+
+/// Decimals of the mints an integration handles, to convert their amounts.
+#[derive(Clone, Debug, Default)]
+pub struct MintDecimals {
+    decimals: HashMap<Pubkey, u8>,
+}
+
+impl MintDecimals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, mint: Pubkey, decimals: u8) -> &mut Self {
+        self.decimals.insert(mint, decimals);
+        self
+    }
+
+    pub fn get(&self, mint: &Pubkey) -> Option<u8> {
+        self.decimals.get(mint).copied()
+    }
+
+    /// Converts a display amount of `mint` into raw units.
+    pub fn parse(&self, mint: &Pubkey, amount: &str) -> Result<u64, AmountError> {
+        parse_amount(amount, self.known(mint)?)
+    }
+
+    /// Formats a raw amount of `mint` for display.
+    pub fn format(&self, mint: &Pubkey, amount: u64) -> Result<String, AmountError> {
+        Ok(format_amount(amount, self.known(mint)?))
+    }
+
+    fn known(&self, mint: &Pubkey) -> Result<u8, AmountError> {
+        self.get(mint).ok_or(AmountError::UnknownMint(*mint))
+    }
+
+    /// Decimals of `mint`, fetched and cached unless already known.
+    #[cfg(feature = "fetch")]
+    pub fn fetch(
+        &mut self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        mint: &Pubkey,
+    ) -> Result<u8, AmountError> {
+        Ok(self.fetch_all(rpc, &[*mint])?[0])
+    }
+
+    /// Decimals of each of `mints`, fetching the unknown ones in a single request.
+    #[cfg(feature = "fetch")]
+    pub fn fetch_all(
+        &mut self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        mints: &[Pubkey],
+    ) -> Result<Vec<u8>, AmountError> {
+        let mut unknown: Vec<Pubkey> = mints
+            .iter()
+            .filter(|mint| self.get(mint).is_none())
+            .copied()
+            .collect();
+        unknown.sort();
+        unknown.dedup();
+
+        if !unknown.is_empty() {
+            let accounts = rpc
+                .get_multiple_accounts(&unknown)
+                .map_err(|e| AmountError::Rpc(e.to_string()))?;
+            for (mint, account) in unknown.into_iter().zip(accounts) {
+                let decimals = account
+                    .and_then(|account| mint_decimals(&account.data))
+                    .ok_or(AmountError::InvalidMint(mint))?;
+                self.insert(mint, decimals);
+            }
+        }
+
+        mints.iter().map(|mint| self.known(mint)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("10.50", 6), Ok(10_500_000));
+        assert_eq!(parse_amount("10.50", 9), Ok(10_500_000_000));
+        assert_eq!(parse_amount("10", 0), Ok(10));
+        assert_eq!(parse_amount(".5", 2), Ok(50));
+        assert_eq!(parse_amount("7.", 2), Ok(700));
+        assert_eq!(parse_amount("0.000001", 6), Ok(1));
+        assert_eq!(parse_amount("000.0", 6), Ok(0));
+        assert_eq!(parse_amount("1.10", 1), Ok(11));
+        assert_eq!(parse_amount("18446744073709.551615", 6), Ok(u64::MAX));
+        assert_eq!(parse_amount("0.00000000000000000000001", 23), Ok(1));
+    }
+
+    #[test]
+    fn test_parse_amount_errors() {
+        for amount in [
+            "",
+            ".",
+            "-1",
+            "1,5",
+            "1.2.3",
+            " 1",
+            "1e6",
+            "+1",
+            "10.50 USDC",
+        ] {
+            assert_eq!(
+                parse_amount(amount, 6),
+                Err(AmountError::Invalid(amount.to_string())),
+                "{amount:?}"
+            );
+        }
+        assert_eq!(
+            parse_amount("0.0000001", 6),
+            Err(AmountError::TooManyDecimals {
+                amount: "0.0000001".to_string(),
+                decimals: 6,
+            })
+        );
+        assert_eq!(
+            parse_amount("18446744073709.551616", 6),
+            Err(AmountError::Overflow("18446744073709.551616".to_string()))
+        );
+        assert_eq!(
+            parse_amount("1", 20),
+            Err(AmountError::Overflow("1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(10_500_000, 6), "10.5");
+        assert_eq!(format_amount(10_500_000, 9), "0.0105");
+        assert_eq!(format_amount(1, 6), "0.000001");
+        assert_eq!(format_amount(0, 6), "0");
+        assert_eq!(format_amount(42, 0), "42");
+        assert_eq!(format_amount(u64::MAX, 6), "18446744073709.551615");
+        assert_eq!(format_amount(1, 23), "0.00000000000000000000001");
+
+        for (amount, decimals) in [(10_500_000, 6), (1, 9), (u64::MAX, 19), (0, 0)] {
+            assert_eq!(
+                parse_amount(&format_amount(amount, decimals), decimals),
+                Ok(amount)
+            );
+        }
+    }
+
+    #[test]
+    fn test_mint_decimals() {
+        let mut data = [0u8; MINT_LEN];
+        assert_eq!(mint_decimals(&data), None);
+
+        data[MINT_DECIMALS_OFFSET] = 9;
+        data[MINT_IS_INITIALIZED_OFFSET] = 1;
+        assert_eq!(mint_decimals(&data), Some(9));
+        assert_eq!(mint_decimals(&data[..MINT_LEN - 1]), None);
+
+        // Token-2022 extensions follow the base mint
+        let mut extended = data.to_vec();
+        extended.extend_from_slice(&[0; 83]);
+        assert_eq!(mint_decimals(&extended), Some(9));
+    }
+
+    #[test]
+    fn test_mint_decimals_cache() {
+        let usdc = Pubkey::new_unique();
+        let nine_decimals = Pubkey::new_unique();
+        let mut decimals = MintDecimals::new();
+        decimals.insert(usdc, 6).insert(nine_decimals, 9);
+
+        assert_eq!(decimals.parse(&usdc, "10.50"), Ok(10_500_000));
+        assert_eq!(decimals.parse(&nine_decimals, "10.50"), Ok(10_500_000_000));
+        assert_eq!(
+            decimals.format(&nine_decimals, 10_500_000),
+            Ok("0.0105".to_string())
+        );
+
+        let unknown = Pubkey::new_unique();
+        assert_eq!(
+            decimals.parse(&unknown, "1"),
+            Err(AmountError::UnknownMint(unknown))
+        );
+    }
+}
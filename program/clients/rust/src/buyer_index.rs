@@ -0,0 +1,115 @@
+//! Off-chain helpers for buyer payment history.
+//!
+//! Under a `BuyerIndexPolicy`, `MakePayment` counts each buyer's payments and keeps their
+//! latest order ids in the buyer's BuyerIndex PDA, created with `InitializeBuyerIndex`.
+//! [`buyer_index_account`] is the account to pass to `MakePayment`, and
+//! [`recent_payment_pdas`] turns an index into the Payment PDAs to fetch, so wallets can show
+//! recent purchases without scanning every Payment account.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    generated::accounts::BuyerIndex,
+    pdas::{find_buyer_index_pda, find_payment_pda},
+};
+
+/// Writable BuyerIndex PDA to pass to `MakePayment` after any MerchantStats, before any
+/// RiskState accounts, the optional Order and references.
+pub fn buyer_index_account(merchant_operator_config: &Pubkey, buyer: &Pubkey) -> AccountMeta {
+    AccountMeta::new(
+        find_buyer_index_pda(merchant_operator_config, buyer).0,
+        false,
+    )
+}
+
+/// Order ids of the buyer's latest payments, most recent first.
+pub fn recent_order_ids(buyer_index: &BuyerIndex) -> &[u32] {
+    let len = (buyer_index.payment_count as usize).min(buyer_index.recent_order_ids.len());
+    &buyer_index.recent_order_ids[..len]
+}
+
+/// Payment PDAs the index's recent order ids may have been paid at, most recent first. The
+/// index does not record mints, so each order id is paired with every mint in `mints`
+/// (typically the config's accepted currencies); the PDAs that don't exist are the mints
+/// the order was not paid in, or payments since closed.
+pub fn recent_payment_pdas(buyer_index: &BuyerIndex, mints: &[Pubkey]) -> Vec<Pubkey> {
+    recent_order_ids(buyer_index)
+        .iter()
+        .flat_map(|order_id| {
+            mints.iter().map(move |mint| {
+                find_payment_pda(
+                    &buyer_index.merchant_operator_config,
+                    &buyer_index.buyer,
+                    mint,
+                    *order_id,
+                )
+                .0
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buyer_index(payment_count: u32, recent_order_ids: [u32; 8]) -> BuyerIndex {
+        BuyerIndex {
+            discriminator: 12,
+            merchant_operator_config: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            bump: 255,
+            payment_count,
+            recent_order_ids,
+        }
+    }
+
+    #[test]
+    fn test_recent_order_ids() {
+        assert!(recent_order_ids(&buyer_index(0, [0; 8])).is_empty());
+        assert_eq!(
+            recent_order_ids(&buyer_index(2, [5, 4, 0, 0, 0, 0, 0, 0])),
+            &[5, 4]
+        );
+        assert_eq!(
+            recent_order_ids(&buyer_index(20, [20, 19, 18, 17, 16, 15, 14, 13])).len(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_recent_payment_pdas() {
+        let index = buyer_index(2, [5, 4, 0, 0, 0, 0, 0, 0]);
+        let mints = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let payment = |mint: &Pubkey, order_id: u32| {
+            find_payment_pda(
+                &index.merchant_operator_config,
+                &index.buyer,
+                mint,
+                order_id,
+            )
+            .0
+        };
+
+        assert_eq!(
+            recent_payment_pdas(&index, &mints),
+            vec![
+                payment(&mints[0], 5),
+                payment(&mints[1], 5),
+                payment(&mints[0], 4),
+                payment(&mints[1], 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_buyer_index_account() {
+        let config = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+
+        let meta = buyer_index_account(&config, &buyer);
+        assert_eq!(meta.pubkey, find_buyer_index_pda(&config, &buyer).0);
+        assert!(meta.is_writable && !meta.is_signer);
+    }
+}
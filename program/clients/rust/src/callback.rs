@@ -0,0 +1,112 @@
+//! Merchant program callbacks.
+//!
+//! When a config carries a `CallbackPolicy`, `ClearPayment` and/or `RefundPayment` invoke the
+//! policy's program once the payment's funds have moved and its new status is stored, so a
+//! failing callback rolls back the settlement. The callback program, followed by the
+//! accounts forwarded to it, must then be passed after the other remaining accounts and
+//! before the memo program, if any; without it the instruction fails with
+//! `CallbackProgramRequired`.
+//!
+//! The callback instruction receives the payment PDA (read-only) followed by the forwarded
+//! accounts, with data `PAYMENT_CALLBACK_DISCRIMINATOR || status (u8) || amount (u64 LE)`.
+//! The discriminator is the Anchor one of `commerce_payment_callback`, so Anchor programs can
+//! handle it as a regular instruction. Forwarded accounts keep the writable and signer flags
+//! they have in the outer instruction.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::types::Status;
+
+/// Anchor-style discriminator of the callback instruction:
+/// Sha256(global:commerce_payment_callback)[..8]
+pub const PAYMENT_CALLBACK_DISCRIMINATOR: [u8; 8] = [128, 216, 104, 223, 189, 183, 58, 192];
+
+/// Length of the callback instruction data.
+pub const PAYMENT_CALLBACK_DATA_LEN: usize = 8 + 1 + 8;
+
+/// The payment transition a callback program is told about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaymentCallback {
+    /// The payment's new status, `Cleared` or `Refunded`
+    pub status: Status,
+    /// The amount cleared or refunded
+    pub amount: u64,
+}
+
+impl PaymentCallback {
+    /// Encodes the callback instruction data.
+    pub fn to_bytes(&self) -> [u8; PAYMENT_CALLBACK_DATA_LEN] {
+        let mut data = [0u8; PAYMENT_CALLBACK_DATA_LEN];
+        data[..8].copy_from_slice(&PAYMENT_CALLBACK_DISCRIMINATOR);
+        data[8] = self.status as u8;
+        data[9..].copy_from_slice(&self.amount.to_le_bytes());
+        data
+    }
+
+    /// Decodes callback instruction data, or `None` if `data` is not a payment callback.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() != PAYMENT_CALLBACK_DATA_LEN || data[..8] != PAYMENT_CALLBACK_DISCRIMINATOR {
+            return None;
+        }
+
+        Some(Self {
+            status: num_traits::FromPrimitive::from_u8(data[8])?,
+            amount: u64::from_le_bytes(data[9..].try_into().ok()?),
+        })
+    }
+}
+
+/// Remaining accounts to append to `ClearPayment` or `RefundPayment` under a callback policy,
+/// before the memo program: the callback program followed by `accounts`.
+pub fn callback_remaining_accounts(program: &Pubkey, accounts: &[AccountMeta]) -> Vec<AccountMeta> {
+    let mut remaining_accounts = Vec::with_capacity(accounts.len() + 1);
+    remaining_accounts.push(AccountMeta::new_readonly(*program, false));
+    remaining_accounts.extend_from_slice(accounts);
+    remaining_accounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_callback_discriminator() {
+        let hash = solana_sha256_hasher::hash(b"global:commerce_payment_callback");
+        assert_eq!(PAYMENT_CALLBACK_DISCRIMINATOR, hash.to_bytes()[..8]);
+    }
+
+    #[test]
+    fn test_payment_callback_roundtrip() {
+        let callback = PaymentCallback {
+            status: Status::Refunded,
+            amount: 1_000_000,
+        };
+
+        let data = callback.to_bytes();
+        assert_eq!(data[8], 2);
+        assert_eq!(PaymentCallback::from_bytes(&data), Some(callback));
+
+        assert_eq!(PaymentCallback::from_bytes(&data[..16]), None);
+
+        let mut unknown_status = data;
+        unknown_status[8] = 9;
+        assert_eq!(PaymentCallback::from_bytes(&unknown_status), None);
+
+        let mut other_instruction = data;
+        other_instruction[0] ^= 1;
+        assert_eq!(PaymentCallback::from_bytes(&other_instruction), None);
+    }
+
+    #[test]
+    fn test_callback_remaining_accounts() {
+        let program = Pubkey::new_unique();
+        let loyalty = AccountMeta::new(Pubkey::new_unique(), false);
+
+        let accounts = callback_remaining_accounts(&program, &[loyalty.clone()]);
+        assert_eq!(
+            accounts,
+            vec![AccountMeta::new_readonly(program, false), loyalty]
+        );
+    }
+}
@@ -0,0 +1,764 @@
+//! Checkout transaction composition.
+//!
+//! [`CheckoutTransactionBuilder`] assembles the instructions a checkout needs — compute
+//! budget, idempotent ATA creation, `MakePayment` and an optional memo — into a single v0
+//! transaction compiled against the caller's address lookup tables. Required signers
+//! (payer, operator authority, buyer) are matched to the message's signer order, so callers
+//! can pass them in any order and may pass the same keypair for several roles.
+
+use solana_hash::Hash;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_message::{v0, AddressLookupTableAccount, CompileError, VersionedMessage};
+use solana_pubkey::{pubkey, Pubkey};
+use solana_signer::{Signer, SignerError};
+use solana_transaction::versioned::VersionedTransaction;
+use thiserror::Error;
+
+pub use crate::pdas::find_payment_pda;
+use crate::{
+    compliance::compliance_remaining_accounts,
+    generated::{
+        instructions::MakePaymentBuilder,
+        types::{DenylistProof, EncryptedNote, PolicyData},
+    },
+    pdas::{find_escrow_authority_pda, find_payment_note_pda, find_quote_pda},
+    priority_fee::{set_compute_unit_limit, set_compute_unit_price, PriorityFee},
+    COMMERCE_PROGRAM_ID,
+};
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+pub const SYSTEM_PROGRAM_ID: Pubkey = pubkey!("11111111111111111111111111111111");
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pubkey!("ComputeBudget111111111111111111111111111111");
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+#[derive(Debug, Error)]
+pub enum CheckoutError {
+    #[error("failed to compile checkout message: {0}")]
+    Compile(#[from] CompileError),
+    #[error("missing signer {0}")]
+    MissingSigner(Pubkey),
+    #[error("failed to sign checkout transaction: {0}")]
+    Signer(#[from] SignerError),
+}
+
+/// Associated token account address for `owner` and `mint`.
+pub fn get_associated_token_address(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// `CreateIdempotent` instruction of the associated token account program.
+pub fn create_associated_token_account_idempotent(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(
+                get_associated_token_address(owner, mint, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        data: vec![1],
+    }
+}
+
+/// Builds a checkout transaction around `MakePayment`.
+///
+/// ### Instructions
+///   0. `[optional]` ComputeBudget `SetComputeUnitLimit`
+///   1. `[optional]` ComputeBudget `SetComputeUnitPrice`
+///   2. `[optional]` ATA `CreateIdempotent` for each owner added with `create_ata`
+///   3. `MakePayment`
+///   4. `[optional]` Memo
+///
+/// The compute unit limit and price come from a [`PriorityFee`] set with `priority_fee` or
+/// `fetch_priority_fee`, unless `compute_unit_limit` or `compute_unit_price` override them.
+///
+/// `MakePayment` creates the config's escrow or settlement ATA itself; `create_ata` is for
+/// other token accounts the integration needs, such as the operator's settlement ATA.
+///
+/// Solana Pay reference keys added with `reference` (at most two) are attached read-only to
+/// `MakePayment`, which stores them on the payment, so it can be found with
+/// [`crate::solana_pay::find_payment_by_reference`].
+///
+/// With `quote`, the checkout's Quote PDA, created by `CreateQuote`, is passed ahead of the
+/// references so `MakePayment` checks the amount against it and rejects an expired quote.
+///
+/// A `note`, encrypted off-chain to the merchant's key, is stored in the payment's
+/// PaymentNote PDA, which leads `MakePayment`'s remaining accounts.
+///
+/// For a Token-2022 mint with a transfer hook, pass the hook accounts resolved by
+/// [`crate::transfer_hook`] with `transfer_hook_accounts` and the Token-2022 program with
+/// `token_program`.
+///
+/// Under a compliance policy, pass the policy's program and the accounts it reads with
+/// `compliance`; they trail the references (see [`crate::compliance`]).
+///
+/// Under a direct settlement policy, `MakePayment` pays the fees at payment time to the
+/// operator owner's ATA and, with a platform fee policy, the platform's; pass both owners
+/// with `direct_settlement` so their ATAs follow the transfer hook accounts.
+#[derive(Clone, Debug, Default)]
+pub struct CheckoutTransactionBuilder {
+    payer: Option<Pubkey>,
+    operator_authority: Option<Pubkey>,
+    buyer: Option<Pubkey>,
+    operator: Option<Pubkey>,
+    merchant: Option<Pubkey>,
+    merchant_operator_config: Option<Pubkey>,
+    settlement_wallet: Option<Pubkey>,
+    mint: Option<Pubkey>,
+    token_program: Option<Pubkey>,
+    order_id: Option<u32>,
+    amount: Option<u64>,
+    idempotency_key: Option<[u8; 16]>,
+    denylist_proof: Option<DenylistProof>,
+    note: Option<EncryptedNote>,
+    create_ata_owners: Vec<Pubkey>,
+    memo: Option<String>,
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+    priority_fee: Option<PriorityFee>,
+    quote: bool,
+    references: Vec<Pubkey>,
+    transfer_hook_accounts: Vec<AccountMeta>,
+    direct_settlement: Option<(Pubkey, Option<Pubkey>)>,
+    compliance_accounts: Vec<AccountMeta>,
+}
+
+impl CheckoutTransactionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn payer(&mut self, payer: Pubkey) -> &mut Self {
+        self.payer = Some(payer);
+        self
+    }
+    pub fn operator_authority(&mut self, operator_authority: Pubkey) -> &mut Self {
+        self.operator_authority = Some(operator_authority);
+        self
+    }
+    pub fn buyer(&mut self, buyer: Pubkey) -> &mut Self {
+        self.buyer = Some(buyer);
+        self
+    }
+    pub fn operator(&mut self, operator: Pubkey) -> &mut Self {
+        self.operator = Some(operator);
+        self
+    }
+    pub fn merchant(&mut self, merchant: Pubkey) -> &mut Self {
+        self.merchant = Some(merchant);
+        self
+    }
+    pub fn merchant_operator_config(&mut self, merchant_operator_config: Pubkey) -> &mut Self {
+        self.merchant_operator_config = Some(merchant_operator_config);
+        self
+    }
+    pub fn settlement_wallet(&mut self, settlement_wallet: Pubkey) -> &mut Self {
+        self.settlement_wallet = Some(settlement_wallet);
+        self
+    }
+    pub fn mint(&mut self, mint: Pubkey) -> &mut Self {
+        self.mint = Some(mint);
+        self
+    }
+    /// `[optional account, default to 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA']`
+    pub fn token_program(&mut self, token_program: Pubkey) -> &mut Self {
+        self.token_program = Some(token_program);
+        self
+    }
+    pub fn order_id(&mut self, order_id: u32) -> &mut Self {
+        self.order_id = Some(order_id);
+        self
+    }
+    pub fn amount(&mut self, amount: u64) -> &mut Self {
+        self.amount = Some(amount);
+        self
+    }
+    pub fn idempotency_key(&mut self, idempotency_key: [u8; 16]) -> &mut Self {
+        self.idempotency_key = Some(idempotency_key);
+        self
+    }
+    /// Required when the config has a `DenylistPolicy`; see [`crate::denylist`].
+    pub fn denylist_proof(&mut self, denylist_proof: DenylistProof) -> &mut Self {
+        self.denylist_proof = Some(denylist_proof);
+        self
+    }
+    /// Note for the merchant, stored on-chain in the payment's PaymentNote PDA.
+    pub fn note(&mut self, note: EncryptedNote) -> &mut Self {
+        self.note = Some(note);
+        self
+    }
+    /// Creates `owner`'s ATA for the checkout mint if it does not exist yet.
+    pub fn create_ata(&mut self, owner: Pubkey) -> &mut Self {
+        self.create_ata_owners.push(owner);
+        self
+    }
+    pub fn memo(&mut self, memo: impl Into<String>) -> &mut Self {
+        self.memo = Some(memo.into());
+        self
+    }
+    pub fn compute_unit_limit(&mut self, compute_unit_limit: u32) -> &mut Self {
+        self.compute_unit_limit = Some(compute_unit_limit);
+        self
+    }
+    /// Priority fee in micro-lamports per compute unit.
+    pub fn compute_unit_price(&mut self, compute_unit_price: u64) -> &mut Self {
+        self.compute_unit_price = Some(compute_unit_price);
+        self
+    }
+
+    /// Compute unit limit and price estimated with [`crate::priority_fee`].
+    pub fn priority_fee(&mut self, priority_fee: PriorityFee) -> &mut Self {
+        self.priority_fee = Some(priority_fee);
+        self
+    }
+
+    /// Estimates the checkout's priority fee with `estimator` from the recent fees paid to
+    /// write its accounts, and its compute unit limit from the profile. Call it once the rest
+    /// of the checkout is set, since both depend on its instructions.
+    #[cfg(feature = "fetch")]
+    pub fn fetch_priority_fee(
+        &mut self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        estimator: &crate::priority_fee::PriorityFeeEstimator,
+    ) -> Result<&mut Self, solana_client::client_error::ClientError> {
+        let priority_fee = estimator.fetch_estimate(rpc, &self.instructions())?;
+        Ok(self.priority_fee(priority_fee))
+    }
+    /// Validates the payment against the Quote PDA for the checkout's config, mint and
+    /// order id.
+    pub fn quote(&mut self) -> &mut Self {
+        self.quote = true;
+        self
+    }
+    /// Solana Pay reference key to find the transaction by.
+    pub fn reference(&mut self, reference: Pubkey) -> &mut Self {
+        self.references.push(reference);
+        self
+    }
+    /// Transfer hook accounts of a Token-2022 mint with a hook, which lead `MakePayment`'s
+    /// remaining accounts.
+    pub fn transfer_hook_accounts(&mut self, accounts: Vec<AccountMeta>) -> &mut Self {
+        self.transfer_hook_accounts = accounts;
+        self
+    }
+    /// Operator owner and platform, under a platform fee policy, whose ATAs receive the
+    /// fees of a config with a direct settlement policy.
+    pub fn direct_settlement(
+        &mut self,
+        operator_owner: Pubkey,
+        platform: Option<Pubkey>,
+    ) -> &mut Self {
+        self.direct_settlement = Some((operator_owner, platform));
+        self
+    }
+    /// Compliance program of the config's compliance policy and the accounts forwarded to
+    /// it, which trail `MakePayment`'s remaining accounts.
+    pub fn compliance(&mut self, program: Pubkey, accounts: &[AccountMeta]) -> &mut Self {
+        self.compliance_accounts = compliance_remaining_accounts(&program, accounts);
+        self
+    }
+
+    /// Sets the operator, merchant and settlement wallet of the checkout's config, and the fee
+    /// ATA owners of a direct settlement policy, reading the config, its Merchant and its
+    /// Operator through `cache`, so a warm cache makes no request.
+    #[cfg(feature = "fetch")]
+    pub fn fetch_accounts(
+        &mut self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        cache: &mut crate::account_cache::AccountCache,
+    ) -> Result<&mut Self, std::io::Error> {
+        let merchant_operator_config = self
+            .merchant_operator_config
+            .expect("merchant_operator_config is not set");
+        let (config, policies, _) =
+            cache.fetch_merchant_operator_config(rpc, &merchant_operator_config)?;
+
+        // Fetch both in one request when neither is cached
+        cache.fetch(rpc, &[config.merchant, config.operator])?;
+        let merchant = cache.fetch_merchant(rpc, &config.merchant)?;
+        let operator = cache.fetch_operator(rpc, &config.operator)?;
+
+        if policies
+            .iter()
+            .any(|policy| matches!(policy, PolicyData::DirectSettlement(_)))
+        {
+            let platform = policies.iter().find_map(|policy| match policy {
+                PolicyData::PlatformFee(platform_fee) => Some(platform_fee.platform),
+                _ => None,
+            });
+            self.direct_settlement(operator.owner, platform);
+        }
+
+        Ok(self
+            .operator(config.operator)
+            .merchant(config.merchant)
+            .settlement_wallet(merchant.settlement_wallet))
+    }
+
+    /// Payment PDA the checkout creates.
+    pub fn payment(&self) -> Pubkey {
+        find_payment_pda(
+            &self
+                .merchant_operator_config
+                .expect("merchant_operator_config is not set"),
+            &self.buyer.expect("buyer is not set"),
+            &self.mint.expect("mint is not set"),
+            self.order_id.expect("order_id is not set"),
+        )
+        .0
+    }
+
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let payer = self.payer.expect("payer is not set");
+        let buyer = self.buyer.expect("buyer is not set");
+        let merchant = self.merchant.expect("merchant is not set");
+        let merchant_operator_config = self
+            .merchant_operator_config
+            .expect("merchant_operator_config is not set");
+        let settlement_wallet = self
+            .settlement_wallet
+            .expect("settlement_wallet is not set");
+        let mint = self.mint.expect("mint is not set");
+        let order_id = self.order_id.expect("order_id is not set");
+        let token_program = self.token_program.unwrap_or(TOKEN_PROGRAM_ID);
+
+        let mut instructions = Vec::new();
+
+        let compute_unit_limit = self
+            .compute_unit_limit
+            .or(self.priority_fee.map(|fee| fee.compute_unit_limit));
+        if let Some(compute_unit_limit) = compute_unit_limit {
+            instructions.push(set_compute_unit_limit(compute_unit_limit));
+        }
+
+        let compute_unit_price = self
+            .compute_unit_price
+            .or(self.priority_fee.map(|fee| fee.compute_unit_price));
+        if let Some(compute_unit_price) = compute_unit_price {
+            instructions.push(set_compute_unit_price(compute_unit_price));
+        }
+
+        for owner in &self.create_ata_owners {
+            instructions.push(create_associated_token_account_idempotent(
+                &payer,
+                owner,
+                &mint,
+                &token_program,
+            ));
+        }
+
+        let (payment, _) = find_payment_pda(&merchant_operator_config, &buyer, &mint, order_id);
+        let (escrow_authority, _) = find_escrow_authority_pda(&merchant_operator_config, &mint);
+        let mut make_payment = MakePaymentBuilder::new();
+        if let Some(denylist_proof) = &self.denylist_proof {
+            make_payment.denylist_proof(denylist_proof.clone());
+        }
+        if let Some(note) = &self.note {
+            let (payment_note, _) = find_payment_note_pda(&payment);
+            make_payment
+                .note(note.clone())
+                .add_remaining_account(AccountMeta::new(payment_note, false));
+        }
+        make_payment.add_remaining_accounts(&self.transfer_hook_accounts);
+        if let Some((operator_owner, platform)) = &self.direct_settlement {
+            for owner in core::iter::once(operator_owner).chain(platform) {
+                make_payment.add_remaining_account(AccountMeta::new(
+                    get_associated_token_address(owner, &mint, &token_program),
+                    false,
+                ));
+            }
+        }
+        if self.quote {
+            let (quote, _) = find_quote_pda(&merchant_operator_config, &mint, order_id);
+            make_payment.add_remaining_account(AccountMeta::new_readonly(quote, false));
+        }
+        for reference in &self.references {
+            make_payment.add_remaining_account(AccountMeta::new_readonly(*reference, false));
+        }
+        make_payment.add_remaining_accounts(&self.compliance_accounts);
+        instructions.push(
+            make_payment
+                .payer(payer)
+                .payment(payment)
+                .operator_authority(
+                    self.operator_authority
+                        .expect("operator_authority is not set"),
+                )
+                .buyer(buyer)
+                .operator(self.operator.expect("operator is not set"))
+                .merchant(merchant)
+                .merchant_operator_config(merchant_operator_config)
+                .mint(mint)
+                .buyer_ata(get_associated_token_address(&buyer, &mint, &token_program))
+                .escrow_authority(escrow_authority)
+                .merchant_escrow_ata(get_associated_token_address(
+                    &escrow_authority,
+                    &mint,
+                    &token_program,
+                ))
+                .merchant_settlement_ata(get_associated_token_address(
+                    &settlement_wallet,
+                    &mint,
+                    &token_program,
+                ))
+                .settlement_wallet(settlement_wallet)
+                .token_program(token_program)
+                .order_id(order_id)
+                .amount(self.amount.expect("amount is not set"))
+                .idempotency_key(self.idempotency_key.expect("idempotency_key is not set"))
+                .instruction(),
+        );
+
+        if let Some(memo) = &self.memo {
+            instructions.push(Instruction::new_with_bytes(
+                MEMO_PROGRAM_ID,
+                memo.as_bytes(),
+                vec![],
+            ));
+        }
+
+        instructions
+    }
+
+    /// Compiles the checkout into a v0 message using `lookup_tables`.
+    pub fn message(
+        &self,
+        lookup_tables: &[AddressLookupTableAccount],
+        recent_blockhash: Hash,
+    ) -> Result<VersionedMessage, CheckoutError> {
+        let message = v0::Message::try_compile(
+            &self.payer.expect("payer is not set"),
+            &self.instructions(),
+            lookup_tables,
+            recent_blockhash,
+        )?;
+        Ok(VersionedMessage::V0(message))
+    }
+
+    /// Compiles and signs the checkout.
+    ///
+    /// `signers` may be in any order and may contain duplicates or extra keypairs; each
+    /// required signer of the message must be present.
+    pub fn transaction(
+        &self,
+        lookup_tables: &[AddressLookupTableAccount],
+        recent_blockhash: Hash,
+        signers: &[&dyn Signer],
+    ) -> Result<VersionedTransaction, CheckoutError> {
+        let message = self.message(lookup_tables, recent_blockhash)?;
+        let ordered = order_signers(&message, signers)?;
+        Ok(VersionedTransaction::try_new(message, &ordered)?)
+    }
+}
+
+/// Required signers of `message`, in the order their signatures appear.
+pub fn required_signers(message: &VersionedMessage) -> &[Pubkey] {
+    let num_required_signatures = message.header().num_required_signatures as usize;
+    &message.static_account_keys()[..num_required_signatures]
+}
+
+fn order_signers<'a>(
+    message: &VersionedMessage,
+    signers: &[&'a dyn Signer],
+) -> Result<Vec<&'a dyn Signer>, CheckoutError> {
+    required_signers(message)
+        .iter()
+        .map(|key| {
+            signers
+                .iter()
+                .find(|signer| signer.try_pubkey().ok() == Some(*key))
+                .copied()
+                .ok_or(CheckoutError::MissingSigner(*key))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_signer::null_signer::NullSigner;
+
+    fn key(n: u8) -> Pubkey {
+        Pubkey::new_from_array([n; 32])
+    }
+
+    fn builder(
+        payer: Pubkey,
+        operator_authority: Pubkey,
+        buyer: Pubkey,
+    ) -> CheckoutTransactionBuilder {
+        let mut builder = CheckoutTransactionBuilder::new();
+        builder
+            .payer(payer)
+            .operator_authority(operator_authority)
+            .buyer(buyer)
+            .operator(key(4))
+            .merchant(key(5))
+            .merchant_operator_config(key(6))
+            .settlement_wallet(key(7))
+            .mint(key(8))
+            .order_id(42)
+            .amount(1_000_000)
+            .idempotency_key([9; 16]);
+        builder
+    }
+
+    #[test]
+    fn test_instructions_order() {
+        let mut builder = builder(key(1), key(2), key(3));
+        builder
+            .compute_unit_limit(200_000)
+            .compute_unit_price(1_000)
+            .create_ata(key(2))
+            .memo("order 42");
+
+        let instructions = builder.instructions();
+        let program_ids: Vec<Pubkey> = instructions.iter().map(|ix| ix.program_id).collect();
+        assert_eq!(
+            program_ids,
+            vec![
+                COMPUTE_BUDGET_PROGRAM_ID,
+                COMPUTE_BUDGET_PROGRAM_ID,
+                ASSOCIATED_TOKEN_PROGRAM_ID,
+                COMMERCE_PROGRAM_ID,
+                MEMO_PROGRAM_ID,
+            ]
+        );
+        assert_eq!(instructions[0].data, [2, 0x40, 0x0d, 0x03, 0x00]);
+        assert_eq!(instructions[1].data[0], 3);
+        assert_eq!(instructions[3].accounts[1].pubkey, builder.payment());
+        assert_eq!(instructions[4].data, b"order 42");
+    }
+
+    #[test]
+    fn test_priority_fee_is_overridden_by_explicit_budget() {
+        let priority_fee = PriorityFee {
+            compute_unit_limit: 50_300,
+            compute_unit_price: 2_000,
+        };
+        let mut builder = builder(key(1), key(2), key(3));
+        builder.priority_fee(priority_fee);
+
+        let instructions = builder.instructions();
+        assert_eq!(instructions[..2], priority_fee.instructions()[..]);
+        assert_eq!(instructions[2].program_id, COMMERCE_PROGRAM_ID);
+
+        builder.compute_unit_price(5_000);
+        let instructions = builder.instructions();
+        assert_eq!(instructions[0], set_compute_unit_limit(50_300));
+        assert_eq!(instructions[1], set_compute_unit_price(5_000));
+    }
+
+    #[test]
+    fn test_minimal_checkout_is_make_payment_only() {
+        let instructions = builder(key(1), key(2), key(3)).instructions();
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].program_id, COMMERCE_PROGRAM_ID);
+    }
+
+    #[test]
+    fn test_references_ride_on_make_payment() {
+        let mut builder = builder(key(1), key(2), key(3));
+        builder.reference(key(10)).reference(key(11));
+
+        let instructions = builder.instructions();
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].program_id, COMMERCE_PROGRAM_ID);
+        assert_eq!(
+            instructions[0].accounts[18..],
+            [
+                AccountMeta::new_readonly(key(10), false),
+                AccountMeta::new_readonly(key(11), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quote_precedes_references() {
+        let mut builder = builder(key(1), key(2), key(3));
+        builder.reference(key(10)).quote();
+
+        let instructions = builder.instructions();
+        assert_eq!(
+            instructions[0].accounts[18..],
+            [
+                AccountMeta::new_readonly(find_quote_pda(&key(6), &key(8), 42).0, false),
+                AccountMeta::new_readonly(key(10), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compliance_accounts_follow_references() {
+        let registry = AccountMeta::new_readonly(key(13), false);
+        let mut builder = builder(key(1), key(2), key(3));
+        builder
+            .compliance(key(12), &[registry.clone()])
+            .reference(key(10));
+
+        let instructions = builder.instructions();
+        assert_eq!(
+            instructions[0].accounts[18..],
+            [
+                AccountMeta::new_readonly(key(10), false),
+                AccountMeta::new_readonly(key(12), false),
+                registry,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transfer_hook_accounts_lead_remaining_accounts() {
+        let hook_accounts = vec![
+            AccountMeta::new_readonly(key(12), false),
+            AccountMeta::new(key(13), false),
+            AccountMeta::new_readonly(key(14), false),
+        ];
+        let mut builder = builder(key(1), key(2), key(3));
+        builder
+            .token_program(crate::transfer_hook::TOKEN_2022_PROGRAM_ID)
+            .reference(key(10))
+            .transfer_hook_accounts(hook_accounts.clone());
+
+        let instructions = builder.instructions();
+        assert_eq!(instructions[0].accounts[18..21], hook_accounts[..]);
+        assert_eq!(
+            instructions[0].accounts[21],
+            AccountMeta::new_readonly(key(10), false)
+        );
+        assert_eq!(
+            instructions[0].accounts[8].pubkey,
+            get_associated_token_address(
+                &key(3),
+                &key(8),
+                &crate::transfer_hook::TOKEN_2022_PROGRAM_ID
+            )
+        );
+    }
+
+    #[test]
+    fn test_payment_note_leads_remaining_accounts() {
+        let hook_program = AccountMeta::new_readonly(key(12), false);
+        let mut builder = builder(key(1), key(2), key(3));
+        builder
+            .transfer_hook_accounts(vec![hook_program.clone()])
+            .note(EncryptedNote {
+                ciphertext: [7; 128],
+            });
+
+        let instructions = builder.instructions();
+        let (payment_note, _) = find_payment_note_pda(&builder.payment());
+        assert_eq!(
+            instructions[0].accounts[18],
+            AccountMeta::new(payment_note, false)
+        );
+        assert_eq!(instructions[0].accounts[19], hook_program);
+    }
+
+    #[test]
+    fn test_direct_settlement_atas_follow_transfer_hook_accounts() {
+        let hook_program = AccountMeta::new_readonly(key(12), false);
+        let mut builder = builder(key(1), key(2), key(3));
+        builder
+            .transfer_hook_accounts(vec![hook_program.clone()])
+            .direct_settlement(key(13), Some(key(14)))
+            .reference(key(10));
+
+        let instructions = builder.instructions();
+        assert_eq!(
+            instructions[0].accounts[18..],
+            [
+                hook_program,
+                AccountMeta::new(
+                    get_associated_token_address(&key(13), &key(8), &TOKEN_PROGRAM_ID),
+                    false
+                ),
+                AccountMeta::new(
+                    get_associated_token_address(&key(14), &key(8), &TOKEN_PROGRAM_ID),
+                    false
+                ),
+                AccountMeta::new_readonly(key(10), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transaction_orders_signers() {
+        let payer = NullSigner::new(&key(1));
+        let operator_authority = NullSigner::new(&key(2));
+        let buyer = NullSigner::new(&key(3));
+
+        let transaction = builder(key(1), key(2), key(3))
+            .transaction(&[], Hash::default(), &[&buyer, &operator_authority, &payer])
+            .unwrap();
+
+        let signers = required_signers(&transaction.message);
+        assert_eq!(signers[0], key(1));
+        assert_eq!(signers.len(), 3);
+        assert_eq!(transaction.signatures.len(), 3);
+    }
+
+    #[test]
+    fn test_transaction_payer_as_operator_authority() {
+        let payer = NullSigner::new(&key(1));
+        let buyer = NullSigner::new(&key(3));
+
+        let transaction = builder(key(1), key(1), key(3))
+            .transaction(&[], Hash::default(), &[&buyer, &payer, &payer])
+            .unwrap();
+
+        assert_eq!(transaction.signatures.len(), 2);
+    }
+
+    #[test]
+    fn test_transaction_missing_signer() {
+        let payer = NullSigner::new(&key(1));
+        let operator_authority = NullSigner::new(&key(2));
+
+        let result = builder(key(1), key(2), key(3)).transaction(
+            &[],
+            Hash::default(),
+            &[&payer, &operator_authority],
+        );
+
+        assert!(matches!(result, Err(CheckoutError::MissingSigner(missing)) if missing == key(3)));
+    }
+
+    #[test]
+    fn test_message_uses_lookup_table() {
+        let lookup_table = AddressLookupTableAccount {
+            key: key(20),
+            addresses: vec![key(4), key(6), key(8)],
+        };
+
+        let message = builder(key(1), key(2), key(3))
+            .message(&[lookup_table], Hash::default())
+            .unwrap();
+
+        let VersionedMessage::V0(message) = message else {
+            panic!("Expected a v0 message");
+        };
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert!(!message.account_keys.contains(&key(4)));
+    }
+}
@@ -0,0 +1,394 @@
+//! Business-level entry point to the SDK for backend services.
+//!
+//! [`CommerceClient`] holds a merchant's config with an operator and turns checkouts,
+//! captures, refunds and order listings into instructions and domain structs, deriving every
+//! PDA and ATA through [`DerivedAccounts`]:
+//!
+//! ```ignore
+//! let client = CommerceClient::fetch(&rpc, merchant_authority, operator_authority)?;
+//! let checkout = client.create_checkout(buyer, mint, 7, 1_000_000, idempotency_key);
+//! send(checkout.instruction)?;
+//! for order in client.list_orders(&rpc)? {
+//!     println!("{} {:?}", order.order_id, order.status);
+//! }
+//! ```
+//!
+//! Instructions are signed by the operator authority, which also pays; `create_checkout` is
+//! signed by the buyer as well. With [`CommerceClient::track_orders`], the Order PDA of the
+//! buyer and order id is passed to every instruction so the program keeps it up to date. The
+//! Order itself is created beforehand with `CreateOrderBuilder`.
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    checkout::TOKEN_PROGRAM_ID,
+    derived_accounts::{DerivedAccounts, DEFAULT_CONFIG_VERSION},
+    generated::{
+        accounts::Order,
+        instructions::{CapturePaymentBuilder, MakePaymentBuilder, RefundPaymentBuilder},
+        types::OrderStatus,
+    },
+    idempotency::IdempotencyKey,
+    pdas::find_order_pda,
+};
+
+/// First byte of every Order account.
+const ORDER_DISCRIMINATOR: u8 = 8;
+
+/// Offset of `merchant_operator_config` in an Order account.
+const ORDER_MERCHANT_OPERATOR_CONFIG_OFFSET: usize = 1;
+
+/// A merchant's config with an operator, from the point of view of the operator's backend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommerceClient {
+    pub merchant_authority: Pubkey,
+    pub operator_authority: Pubkey,
+    /// Merchant's settlement wallet, as stored on its Merchant account
+    pub settlement_wallet: Pubkey,
+    /// Version of the merchant's config with the operator
+    pub config_version: u32,
+    /// Token program owning the mints paid in
+    pub token_program: Pubkey,
+    /// Whether payments are recorded on their Order accounts
+    pub track_orders: bool,
+}
+
+/// Payment instruction of a checkout.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkout {
+    /// Payment PDA the checkout creates
+    pub payment: Pubkey,
+    /// Order PDA the payment is recorded on, when orders are tracked
+    pub order: Option<Pubkey>,
+    pub instruction: Instruction,
+}
+
+/// An order of the client's config.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderSummary {
+    /// Order PDA
+    pub order: Pubkey,
+    pub buyer: Pubkey,
+    pub order_id: u32,
+    pub status: OrderStatus,
+    pub expected_payments: u8,
+    pub payment_count: u8,
+    pub cleared_count: u8,
+    pub refunded_count: u8,
+    pub created_at: i64,
+}
+
+impl OrderSummary {
+    pub fn new(order: Pubkey, account: &Order) -> Self {
+        Self {
+            order,
+            buyer: account.buyer,
+            order_id: account.order_id,
+            status: account.status,
+            expected_payments: account.expected_payments,
+            payment_count: account.payment_count,
+            cleared_count: account.cleared_count,
+            refunded_count: account.refunded_count,
+            created_at: account.created_at,
+        }
+    }
+}
+
+impl CommerceClient {
+    /// Client for config version [`DEFAULT_CONFIG_VERSION`], paid in SPL Token mints and
+    /// without order tracking.
+    pub fn new(
+        merchant_authority: Pubkey,
+        operator_authority: Pubkey,
+        settlement_wallet: Pubkey,
+    ) -> Self {
+        Self {
+            merchant_authority,
+            operator_authority,
+            settlement_wallet,
+            config_version: DEFAULT_CONFIG_VERSION,
+            token_program: TOKEN_PROGRAM_ID,
+            track_orders: false,
+        }
+    }
+
+    /// Client reading the settlement wallet from the merchant's Merchant account.
+    #[cfg(feature = "fetch")]
+    pub fn fetch(
+        rpc: &solana_client::rpc_client::RpcClient,
+        merchant_authority: Pubkey,
+        operator_authority: Pubkey,
+    ) -> Result<Self, std::io::Error> {
+        let merchant = crate::generated::accounts::fetch_merchant(
+            rpc,
+            &crate::pdas::find_merchant_pda(&merchant_authority).0,
+        )?;
+        Ok(Self::new(
+            merchant_authority,
+            operator_authority,
+            merchant.data.settlement_wallet,
+        ))
+    }
+
+    pub fn config_version(mut self, config_version: u32) -> Self {
+        self.config_version = config_version;
+        self
+    }
+
+    pub fn token_program(mut self, token_program: Pubkey) -> Self {
+        self.token_program = token_program;
+        self
+    }
+
+    pub fn track_orders(mut self, track_orders: bool) -> Self {
+        self.track_orders = track_orders;
+        self
+    }
+
+    /// Merchant-operator config PDA the client works with.
+    pub fn merchant_operator_config(&self) -> Pubkey {
+        self.accounts(Pubkey::default(), Pubkey::default(), 0)
+            .merchant_operator_config()
+    }
+
+    /// Accounts of order `order_id` paid by `buyer` in `mint`.
+    pub fn accounts(&self, buyer: Pubkey, mint: Pubkey, order_id: u32) -> DerivedAccounts {
+        DerivedAccounts::new(
+            self.merchant_authority,
+            self.operator_authority,
+            buyer,
+            mint,
+            order_id,
+        )
+        .config_version(self.config_version)
+        .token_program(self.token_program)
+        .settlement_wallet(self.settlement_wallet)
+    }
+
+    /// Payment of `amount` by `buyer` for order `order_id`.
+    pub fn create_checkout(
+        &self,
+        buyer: Pubkey,
+        mint: Pubkey,
+        order_id: u32,
+        amount: u64,
+        idempotency_key: IdempotencyKey,
+    ) -> Checkout {
+        self.checkout(buyer, mint, order_id, amount, idempotency_key, false)
+    }
+
+    /// Hold of `amount` on `buyer`'s funds for order `order_id`, settled later by
+    /// [`CommerceClient::capture`].
+    pub fn authorize_checkout(
+        &self,
+        buyer: Pubkey,
+        mint: Pubkey,
+        order_id: u32,
+        amount: u64,
+        idempotency_key: IdempotencyKey,
+    ) -> Checkout {
+        self.checkout(buyer, mint, order_id, amount, idempotency_key, true)
+    }
+
+    /// Captures `amount` of an authorized checkout and releases the rest to the buyer.
+    pub fn capture(&self, buyer: Pubkey, mint: Pubkey, order_id: u32, amount: u64) -> Instruction {
+        let accounts = self.accounts(buyer, mint, order_id);
+        let mut builder = CapturePaymentBuilder::new();
+        builder.with_derived_accounts(&accounts).amount(amount);
+        if let Some(order) = self.order(&accounts) {
+            builder.add_remaining_account(AccountMeta::new(order, false));
+        }
+        builder.instruction()
+    }
+
+    /// Refunds the payment of order `order_id` in `mint` to the buyer while it is in escrow.
+    pub fn refund_order(&self, buyer: Pubkey, mint: Pubkey, order_id: u32) -> Instruction {
+        let accounts = self.accounts(buyer, mint, order_id);
+        let mut builder = RefundPaymentBuilder::new();
+        builder.with_derived_accounts(&accounts);
+        if let Some(order) = self.order(&accounts) {
+            builder.add_remaining_account(AccountMeta::new(order, false));
+        }
+        builder.instruction()
+    }
+
+    /// `getProgramAccounts` filters matching the Order accounts of the client's config.
+    #[cfg(feature = "fetch")]
+    pub fn order_filters(&self) -> Vec<solana_client::rpc_filter::RpcFilterType> {
+        use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+        vec![
+            RpcFilterType::DataSize(Order::LEN as u64),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![ORDER_DISCRIMINATOR])),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                ORDER_MERCHANT_OPERATOR_CONFIG_OFFSET,
+                self.merchant_operator_config().to_bytes().to_vec(),
+            )),
+        ]
+    }
+
+    /// Orders of the client's config, oldest first.
+    #[cfg(feature = "fetch")]
+    pub fn list_orders(
+        &self,
+        rpc: &solana_client::rpc_client::RpcClient,
+    ) -> Result<Vec<OrderSummary>, std::io::Error> {
+        use solana_client::rpc_config::RpcProgramAccountsConfig;
+
+        let config = RpcProgramAccountsConfig {
+            filters: Some(self.order_filters()),
+            ..RpcProgramAccountsConfig::default()
+        };
+        let accounts = rpc
+            .get_program_accounts_with_config(&crate::COMMERCE_PROGRAM_ID, config)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+
+        let mut orders = accounts
+            .iter()
+            .map(|(address, account)| {
+                Ok(OrderSummary::new(
+                    *address,
+                    &Order::from_bytes(&account.data)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, std::io::Error>>()?;
+        orders.sort_by_key(|order| (order.created_at, order.order_id));
+        Ok(orders)
+    }
+
+    fn checkout(
+        &self,
+        buyer: Pubkey,
+        mint: Pubkey,
+        order_id: u32,
+        amount: u64,
+        idempotency_key: IdempotencyKey,
+        authorize: bool,
+    ) -> Checkout {
+        let accounts = self.accounts(buyer, mint, order_id);
+        let order = self.order(&accounts);
+        let mut builder = MakePaymentBuilder::new();
+        builder
+            .with_derived_accounts(&accounts)
+            .amount(amount)
+            .idempotency_key(idempotency_key)
+            .authorize(authorize);
+        if let Some(order) = order {
+            builder.add_remaining_account(AccountMeta::new(order, false));
+        }
+        Checkout {
+            payment: accounts.payment().0,
+            order,
+            instruction: builder.instruction(),
+        }
+    }
+
+    fn order(&self, accounts: &DerivedAccounts) -> Option<Pubkey> {
+        self.track_orders.then(|| {
+            find_order_pda(
+                &accounts.merchant_operator_config(),
+                &accounts.buyer,
+                accounts.order_id,
+            )
+            .0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(n: u8) -> Pubkey {
+        Pubkey::new_from_array([n; 32])
+    }
+
+    fn client() -> CommerceClient {
+        CommerceClient::new(key(1), key(2), key(5))
+    }
+
+    #[test]
+    fn test_create_checkout_matches_derived_accounts() {
+        let accounts =
+            DerivedAccounts::new(key(1), key(2), key(3), key(4), 42).settlement_wallet(key(5));
+        let instruction = MakePaymentBuilder::new()
+            .with_derived_accounts(&accounts)
+            .amount(1_000_000)
+            .idempotency_key([9; 16])
+            .instruction();
+
+        let checkout = client().create_checkout(key(3), key(4), 42, 1_000_000, [9; 16]);
+        assert_eq!(checkout.instruction, instruction);
+        assert_eq!(checkout.payment, accounts.payment().0);
+        assert_eq!(checkout.order, None);
+    }
+
+    #[test]
+    fn test_track_orders_appends_order() {
+        let client = client().track_orders(true);
+        let order = find_order_pda(&client.merchant_operator_config(), &key(3), 42).0;
+
+        let checkout = client.authorize_checkout(key(3), key(4), 42, 1_000_000, [9; 16]);
+        assert_eq!(checkout.order, Some(order));
+        for instruction in [
+            checkout.instruction,
+            client.capture(key(3), key(4), 42, 500_000),
+            client.refund_order(key(3), key(4), 42),
+        ] {
+            assert_eq!(
+                instruction.accounts.last(),
+                Some(&AccountMeta::new(order, false))
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_version() {
+        let client = client().config_version(3);
+        assert_eq!(
+            client.merchant_operator_config(),
+            client
+                .accounts(key(3), key(4), 42)
+                .config_version(3)
+                .merchant_operator_config()
+        );
+        assert_ne!(
+            client.merchant_operator_config(),
+            CommerceClient::new(key(1), key(2), key(5)).merchant_operator_config()
+        );
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn test_order_filters_match_order() {
+        use solana_client::rpc_filter::RpcFilterType;
+
+        let client = client();
+        let mut order = Order {
+            discriminator: ORDER_DISCRIMINATOR,
+            merchant_operator_config: client.merchant_operator_config(),
+            buyer: key(3),
+            order_id: 42,
+            bump: 255,
+            expected_payments: 1,
+            payment_count: 0,
+            cleared_count: 0,
+            refunded_count: 0,
+            status: OrderStatus::Pending,
+            created_at: 0,
+        };
+        let matches = |order: &Order| {
+            let data = borsh::to_vec(order).unwrap();
+            client.order_filters().iter().all(|filter| match filter {
+                RpcFilterType::DataSize(size) => data.len() as u64 == *size,
+                RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&data),
+                _ => unreachable!(),
+            })
+        };
+
+        assert!(matches(&order));
+        order.merchant_operator_config = key(6);
+        assert!(!matches(&order));
+    }
+}
@@ -0,0 +1,103 @@
+//! External compliance checks on payments.
+//!
+//! When a config carries a `CompliancePolicy`, `MakePayment` and `MakePaymentCompressed`
+//! invoke the policy's program before any funds move, and the payment fails unless the call
+//! succeeds. This lets a config gate payments on an on-chain identity or KYC registry. The
+//! compliance program, followed by the accounts forwarded to it, must then be passed as the
+//! last remaining accounts, after any references; without it the payment fails with
+//! `ComplianceProgramRequired`.
+//!
+//! The check instruction receives the buyer and the mint (both read-only, the buyer without
+//! its signature) followed by the forwarded accounts, with data
+//! `COMPLIANCE_CHECK_DISCRIMINATOR || amount (u64 LE)`. The discriminator is the Anchor one of
+//! `commerce_compliance_check`, so Anchor programs can handle it as a regular instruction.
+//! Forwarded accounts keep the writable and signer flags they have in the outer instruction.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+/// Anchor-style discriminator of the compliance check instruction:
+/// Sha256(global:commerce_compliance_check)[..8]
+pub const COMPLIANCE_CHECK_DISCRIMINATOR: [u8; 8] = [192, 147, 185, 97, 5, 110, 185, 172];
+
+/// Length of the compliance check instruction data.
+pub const COMPLIANCE_CHECK_DATA_LEN: usize = 8 + 8;
+
+/// The payment a compliance program is asked to approve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComplianceCheck {
+    /// The payment amount, in the mint's base units
+    pub amount: u64,
+}
+
+impl ComplianceCheck {
+    /// Encodes the compliance check instruction data.
+    pub fn to_bytes(&self) -> [u8; COMPLIANCE_CHECK_DATA_LEN] {
+        let mut data = [0u8; COMPLIANCE_CHECK_DATA_LEN];
+        data[..8].copy_from_slice(&COMPLIANCE_CHECK_DISCRIMINATOR);
+        data[8..].copy_from_slice(&self.amount.to_le_bytes());
+        data
+    }
+
+    /// Decodes compliance check instruction data, or `None` if `data` is not a compliance
+    /// check.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() != COMPLIANCE_CHECK_DATA_LEN || data[..8] != COMPLIANCE_CHECK_DISCRIMINATOR {
+            return None;
+        }
+
+        Some(Self {
+            amount: u64::from_le_bytes(data[8..].try_into().ok()?),
+        })
+    }
+}
+
+/// Remaining accounts to append to `MakePayment` or `MakePaymentCompressed` under a
+/// compliance policy, after any references: the compliance program followed by `accounts`.
+pub fn compliance_remaining_accounts(
+    program: &Pubkey,
+    accounts: &[AccountMeta],
+) -> Vec<AccountMeta> {
+    let mut remaining_accounts = Vec::with_capacity(accounts.len() + 1);
+    remaining_accounts.push(AccountMeta::new_readonly(*program, false));
+    remaining_accounts.extend_from_slice(accounts);
+    remaining_accounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compliance_check_discriminator() {
+        let hash = solana_sha256_hasher::hash(b"global:commerce_compliance_check");
+        assert_eq!(COMPLIANCE_CHECK_DISCRIMINATOR, hash.to_bytes()[..8]);
+    }
+
+    #[test]
+    fn test_compliance_check_roundtrip() {
+        let check = ComplianceCheck { amount: 1_000_000 };
+
+        let data = check.to_bytes();
+        assert_eq!(data[8..], 1_000_000u64.to_le_bytes());
+        assert_eq!(ComplianceCheck::from_bytes(&data), Some(check));
+
+        assert_eq!(ComplianceCheck::from_bytes(&data[..15]), None);
+
+        let mut other_instruction = data;
+        other_instruction[0] ^= 1;
+        assert_eq!(ComplianceCheck::from_bytes(&other_instruction), None);
+    }
+
+    #[test]
+    fn test_compliance_remaining_accounts() {
+        let program = Pubkey::new_unique();
+        let registry = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+
+        let accounts = compliance_remaining_accounts(&program, &[registry.clone()]);
+        assert_eq!(
+            accounts,
+            vec![AccountMeta::new_readonly(program, false), registry]
+        );
+    }
+}
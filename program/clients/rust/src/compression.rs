@@ -0,0 +1,158 @@
+//! Off-chain helpers for compressed payments.
+//!
+//! Compressed payments are stored as leaves of the `PaymentTree` account, which only keeps
+//! the root on-chain. Clients mirror the leaves (from `CompressedPaymentEvent`s) in a
+//! [`PaymentMerkleTree`] to produce the proofs `ClearPaymentCompressed` requires.
+
+use solana_pubkey::Pubkey;
+use solana_sha256_hasher::hashv;
+
+use crate::generated::types::Status;
+
+/// Depth of the on-chain payment tree.
+pub const PAYMENT_TREE_DEPTH: usize = 20;
+
+/// Leaf hash of a compressed payment, matching the program's encoding.
+pub fn payment_leaf_hash(
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    order_id: u32,
+    amount: u64,
+    created_at: i64,
+    status: Status,
+) -> [u8; 32] {
+    hashv(&[
+        buyer.as_ref(),
+        mint.as_ref(),
+        &order_id.to_le_bytes(),
+        &amount.to_le_bytes(),
+        &created_at.to_le_bytes(),
+        &[status as u8],
+    ])
+    .to_bytes()
+}
+
+/// Root of an empty subtree at each level, from an empty leaf up to the empty tree root.
+pub fn zero_hashes() -> [[u8; 32]; PAYMENT_TREE_DEPTH + 1] {
+    let mut zeros = [[0u8; 32]; PAYMENT_TREE_DEPTH + 1];
+    for level in 0..PAYMENT_TREE_DEPTH {
+        zeros[level + 1] = hashv(&[&zeros[level], &zeros[level]]).to_bytes();
+    }
+    zeros
+}
+
+/// Full off-chain copy of a payment tree's leaves.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PaymentMerkleTree {
+    leaves: Vec<[u8; 32]>,
+}
+
+impl PaymentMerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.leaves
+    }
+
+    /// Appends a leaf and returns its index.
+    pub fn append(&mut self, leaf: [u8; 32]) -> u32 {
+        self.leaves.push(leaf);
+        (self.leaves.len() - 1) as u32
+    }
+
+    /// Replaces the leaf at `leaf_index`, returning the previous leaf.
+    pub fn replace(&mut self, leaf_index: u32, leaf: [u8; 32]) -> Option<[u8; 32]> {
+        let slot = self.leaves.get_mut(leaf_index as usize)?;
+        Some(std::mem::replace(slot, leaf))
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels()[PAYMENT_TREE_DEPTH][0]
+    }
+
+    /// Sibling path from the leaf at `leaf_index` up to the root.
+    pub fn proof(&self, leaf_index: u32) -> Option<Vec<[u8; 32]>> {
+        if leaf_index as usize >= self.leaves.len() {
+            return None;
+        }
+
+        let zeros = zero_hashes();
+        let levels = self.levels();
+
+        Some(
+            (0..PAYMENT_TREE_DEPTH)
+                .map(|level| {
+                    let sibling = ((leaf_index as usize) >> level) ^ 1;
+                    levels[level].get(sibling).copied().unwrap_or(zeros[level])
+                })
+                .collect(),
+        )
+    }
+
+    fn levels(&self) -> Vec<Vec<[u8; 32]>> {
+        let zeros = zero_hashes();
+        let mut levels = vec![self.leaves.clone()];
+
+        for level in 0..PAYMENT_TREE_DEPTH {
+            let next: Vec<[u8; 32]> = if levels[level].is_empty() {
+                vec![zeros[level + 1]]
+            } else {
+                levels[level]
+                    .chunks(2)
+                    .map(|pair| {
+                        let right = pair.get(1).copied().unwrap_or(zeros[level]);
+                        hashv(&[&pair[0], &right]).to_bytes()
+                    })
+                    .collect()
+            };
+            levels.push(next);
+        }
+
+        levels
+    }
+}
+
+/// Recomputes the root implied by `leaf` at `leaf_index` and its sibling path.
+pub fn compute_root(leaf_index: u32, leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    proof
+        .iter()
+        .enumerate()
+        .fold(leaf, |node, (level, sibling)| {
+            if (leaf_index >> level) & 1 == 0 {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_tree_root() {
+        assert_eq!(
+            PaymentMerkleTree::new().root(),
+            zero_hashes()[PAYMENT_TREE_DEPTH]
+        );
+    }
+
+    #[test]
+    fn test_proofs_match_root() {
+        let mut tree = PaymentMerkleTree::new();
+        for i in 0..5u8 {
+            tree.append(hashv(&[&[i]]).to_bytes());
+        }
+        tree.replace(2, [9u8; 32]);
+
+        for (index, leaf) in tree.leaves().iter().enumerate() {
+            let proof = tree.proof(index as u32).unwrap();
+            assert_eq!(proof.len(), PAYMENT_TREE_DEPTH);
+            assert_eq!(compute_root(index as u32, *leaf, &proof), tree.root());
+        }
+        assert!(tree.proof(5).is_none());
+    }
+}
@@ -0,0 +1,168 @@
+//! Hashes of a config's terms, for off-chain agreements.
+//!
+//! Every `MerchantOperatorConfig` stores `config_hash`, the SHA-256 of its terms, computed
+//! when the config is created and emitted in `MerchantOperatorConfigInitialized`, so a
+//! merchant and an operator can sign an off-chain contract over the hash and anyone can
+//! check it against the account. `AddPolicy` and `AddAcceptedCurrency` change the terms
+//! and recompute the hash; the config's other terms cannot be changed.
+//!
+//! The terms are hashed in a fixed order, integers little-endian: `version`, `merchant`,
+//! `operator`, `operator_fee`, `fee_type` and `days_to_close`, then the number of policies
+//! and each policy in its stored 101-byte slot, then the number of accepted currencies and
+//! each currency. `bump`, `current_order_id` and `config_hash` itself are left out.
+
+use borsh::BorshSerialize;
+use solana_pubkey::Pubkey;
+use solana_sha256_hasher::hashv;
+
+use crate::{
+    generated::{accounts::MerchantOperatorConfig, types::PolicyData},
+    preview::{decode_merchant_operator_config, POLICY_DATA_SIZE},
+};
+
+/// Hash of the terms of `config` with `policies` and `currencies`, matching the program's.
+pub fn config_hash(
+    config: &MerchantOperatorConfig,
+    policies: &[PolicyData],
+    currencies: &[Pubkey],
+) -> [u8; 32] {
+    let mut terms = Vec::new();
+    terms.extend_from_slice(&config.version.to_le_bytes());
+    terms.extend_from_slice(config.merchant.as_ref());
+    terms.extend_from_slice(config.operator.as_ref());
+    terms.extend_from_slice(&config.operator_fee.to_le_bytes());
+    terms.push(config.fee_type as u8);
+    terms.extend_from_slice(&config.days_to_close.to_le_bytes());
+
+    terms.extend_from_slice(&(policies.len() as u32).to_le_bytes());
+    for policy in policies {
+        let start = terms.len();
+        policy
+            .serialize(&mut terms)
+            .expect("writing to a Vec cannot fail");
+        terms.resize(start + POLICY_DATA_SIZE, 0);
+    }
+
+    terms.extend_from_slice(&(currencies.len() as u32).to_le_bytes());
+    for currency in currencies {
+        terms.extend_from_slice(currency.as_ref());
+    }
+
+    hashv(&[&terms]).to_bytes()
+}
+
+/// Decodes a `MerchantOperatorConfig` account and checks its stored `config_hash` against
+/// its terms, returning the hash.
+///
+/// # Errors
+/// Returns `InvalidData` if the account does not decode or its hash does not match.
+pub fn verify_config_hash(data: &[u8]) -> Result<[u8; 32], std::io::Error> {
+    let (config, policies, currencies) = decode_merchant_operator_config(data)?;
+    let hash = config_hash(&config, &policies, &currencies);
+    if hash != config.config_hash {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "config_hash does not match the config's terms",
+        ));
+    }
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FeeType, RefundPolicy};
+
+    fn config() -> (MerchantOperatorConfig, Vec<PolicyData>, Vec<Pubkey>) {
+        let policies = vec![PolicyData::Refund(RefundPolicy {
+            max_amount: 1_000,
+            max_time_after_purchase: 3_600,
+        })];
+        let currencies = vec![
+            Pubkey::new_from_array([8; 32]),
+            Pubkey::new_from_array([9; 32]),
+        ];
+        let config = MerchantOperatorConfig {
+            discriminator: 2,
+            version: 1,
+            bump: 255,
+            merchant: Pubkey::new_from_array([1; 32]),
+            operator: Pubkey::new_from_array([2; 32]),
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 0,
+            days_to_close: 7,
+            num_policies: policies.len() as u32,
+            num_accepted_currencies: currencies.len() as u32,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        (config, policies, currencies)
+    }
+
+    fn account_data(
+        config: &MerchantOperatorConfig,
+        policies: &[PolicyData],
+        currencies: &[Pubkey],
+    ) -> Vec<u8> {
+        let mut data = borsh::to_vec(config).unwrap();
+        for policy in policies {
+            let mut slot = borsh::to_vec(policy).unwrap();
+            slot.resize(POLICY_DATA_SIZE, 0);
+            data.extend_from_slice(&slot);
+        }
+        for currency in currencies {
+            data.extend_from_slice(currency.as_ref());
+        }
+        data
+    }
+
+    #[test]
+    fn test_config_hash_encoding() {
+        let (config, policies, currencies) = config();
+
+        let mut terms = Vec::new();
+        terms.extend_from_slice(&1u32.to_le_bytes());
+        terms.extend_from_slice(&[1; 32]);
+        terms.extend_from_slice(&[2; 32]);
+        terms.extend_from_slice(&100u64.to_le_bytes());
+        terms.push(0); // FeeType::Bps
+        terms.extend_from_slice(&7u16.to_le_bytes());
+        terms.extend_from_slice(&1u32.to_le_bytes());
+        let mut refund = vec![0u8]; // PolicyType::Refund
+        refund.extend_from_slice(&1_000u64.to_le_bytes());
+        refund.extend_from_slice(&3_600u64.to_le_bytes());
+        refund.resize(POLICY_DATA_SIZE, 0);
+        terms.extend_from_slice(&refund);
+        terms.extend_from_slice(&2u32.to_le_bytes());
+        terms.extend_from_slice(&[8; 32]);
+        terms.extend_from_slice(&[9; 32]);
+
+        assert_eq!(
+            config_hash(&config, &policies, &currencies),
+            hashv(&[&terms]).to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_verify_config_hash() {
+        let (mut config, policies, currencies) = config();
+        config.config_hash = config_hash(&config, &policies, &currencies);
+
+        // Payments moving current_order_id do not change the terms
+        config.current_order_id = 42;
+        let data = account_data(&config, &policies, &currencies);
+        assert_eq!(verify_config_hash(&data).unwrap(), config.config_hash);
+
+        let mut tampered = config.clone();
+        tampered.operator_fee = 101;
+        let data = account_data(&tampered, &policies, &currencies);
+        assert!(verify_config_hash(&data).is_err());
+
+        let mut fewer_currencies = config.clone();
+        fewer_currencies.num_accepted_currencies = 1;
+        let data = account_data(&fewer_currencies, &policies, &currencies[..1]);
+        assert!(verify_config_hash(&data).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Listing an operator's configs from its ConfigRegistryPage accounts.
+//!
+//! `InitializeMerchantOperatorConfig` and `InitializeMerchantWithConfig` append the new config
+//! to the operator's registry page passed after the mint accounts. Pages hold
+//! [`CONFIG_REGISTRY_PAGE_CAPACITY`] configs and the next page can only be created once the
+//! previous one is full, so a client reads pages 0, 1, ... until one is missing.
+
+use solana_pubkey::Pubkey;
+
+use crate::{
+    generated::{accounts::ConfigRegistryPage, types::ConfigRegistryEntry},
+    pdas::{find_config_registry_page_pda, find_merchant_operator_config_pda},
+};
+
+/// Configs listed per ConfigRegistryPage.
+pub const CONFIG_REGISTRY_PAGE_CAPACITY: usize = 32;
+
+impl ConfigRegistryPage {
+    /// Configs listed on the page, oldest first.
+    pub fn listed(&self) -> &[ConfigRegistryEntry] {
+        let len = (self.count as usize).min(CONFIG_REGISTRY_PAGE_CAPACITY);
+        &self.entries[..len]
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.count as usize >= CONFIG_REGISTRY_PAGE_CAPACITY
+    }
+}
+
+/// Page a new config should be registered on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegistrySlot {
+    pub page: u32,
+    pub address: Pubkey,
+    /// Whether the page must be created with `InitializeConfigRegistryPage` first.
+    pub needs_initialize: bool,
+}
+
+/// Page the next config of `operator` goes on, given its existing `pages` in order.
+pub fn next_registry_slot(operator: &Pubkey, pages: &[ConfigRegistryPage]) -> RegistrySlot {
+    let (page, needs_initialize) = match pages.last() {
+        Some(last) if last.is_full() => (last.page + 1, true),
+        Some(last) => (last.page, false),
+        None => (0, true),
+    };
+    RegistrySlot {
+        page,
+        address: find_config_registry_page_pda(operator, page).0,
+        needs_initialize,
+    }
+}
+
+/// Addresses and versions of `merchant`'s configs listed on `pages`, oldest first.
+pub fn merchant_configs(pages: &[ConfigRegistryPage], merchant: &Pubkey) -> Vec<(Pubkey, u32)> {
+    pages
+        .iter()
+        .flat_map(|page| {
+            page.listed()
+                .iter()
+                .filter(|entry| entry.merchant == *merchant)
+                .map(|entry| {
+                    let (config, _) = find_merchant_operator_config_pda(
+                        &entry.merchant,
+                        &page.operator,
+                        entry.version,
+                    );
+                    (config, entry.version)
+                })
+        })
+        .collect()
+}
+
+/// Fetches `operator`'s registry pages in order, stopping at the first missing page.
+#[cfg(feature = "fetch")]
+pub fn fetch_config_registry(
+    rpc: &solana_client::rpc_client::RpcClient,
+    operator: &Pubkey,
+) -> Result<Vec<ConfigRegistryPage>, std::io::Error> {
+    use crate::{generated::accounts::fetch_maybe_config_registry_page, shared::MaybeAccount};
+
+    let mut pages = Vec::new();
+    for page in 0.. {
+        let (address, _) = find_config_registry_page_pda(operator, page);
+        match fetch_maybe_config_registry_page(rpc, &address)? {
+            MaybeAccount::Exists(account) => pages.push(account.data),
+            MaybeAccount::NotFound(_) => break,
+        }
+    }
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(operator: Pubkey, index: u32, entries: &[(Pubkey, u32)]) -> ConfigRegistryPage {
+        let mut listed = core::array::from_fn(|_| ConfigRegistryEntry {
+            merchant: Pubkey::default(),
+            version: 0,
+        });
+        for (slot, (merchant, version)) in listed.iter_mut().zip(entries) {
+            *slot = ConfigRegistryEntry {
+                merchant: *merchant,
+                version: *version,
+            };
+        }
+        ConfigRegistryPage {
+            discriminator: 14,
+            operator,
+            page: index,
+            bump: 255,
+            count: entries.len() as u32,
+            entries: listed,
+        }
+    }
+
+    #[test]
+    fn test_next_registry_slot() {
+        let operator = Pubkey::new_unique();
+        let merchant = Pubkey::new_unique();
+
+        let slot = next_registry_slot(&operator, &[]);
+        assert_eq!(slot.page, 0);
+        assert!(slot.needs_initialize);
+        assert_eq!(slot.address, find_config_registry_page_pda(&operator, 0).0);
+
+        let partial = page(operator, 0, &[(merchant, 1)]);
+        let slot = next_registry_slot(&operator, &[partial]);
+        assert_eq!(slot.page, 0);
+        assert!(!slot.needs_initialize);
+
+        let full = page(operator, 0, &[(merchant, 1); CONFIG_REGISTRY_PAGE_CAPACITY]);
+        let slot = next_registry_slot(&operator, &[full]);
+        assert_eq!(slot.page, 1);
+        assert!(slot.needs_initialize);
+        assert_eq!(slot.address, find_config_registry_page_pda(&operator, 1).0);
+    }
+
+    #[test]
+    fn test_merchant_configs() {
+        let operator = Pubkey::new_unique();
+        let merchant = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        let pages = [
+            page(operator, 0, &[(merchant, 1), (other, 1)]),
+            page(operator, 1, &[(merchant, 2)]),
+        ];
+        assert_eq!(
+            merchant_configs(&pages, &merchant),
+            vec![
+                (
+                    find_merchant_operator_config_pda(&merchant, &operator, 1).0,
+                    1
+                ),
+                (
+                    find_merchant_operator_config_pda(&merchant, &operator, 2).0,
+                    2
+                ),
+            ]
+        );
+        assert!(merchant_configs(&pages, &Pubkey::new_unique()).is_empty());
+    }
+}
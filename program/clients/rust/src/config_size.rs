@@ -0,0 +1,88 @@
+//! Sizes and limits of `MerchantOperatorConfig` accounts.
+//!
+//! A config's account holds its fixed fields, then one [`POLICY_DATA_SIZE`] slot per policy,
+//! then one 32-byte key per accepted currency, then each currency's 4-byte open-payment
+//! count. A config holds at most [`MAX_POLICIES`]
+//! policies and [`MAX_ACCEPTED_CURRENCIES`] currencies, whether set by
+//! `InitializeMerchantOperatorConfig` or added later with `AddPolicy` and
+//! `AddAcceptedCurrency`, which grow the account and charge their payer the extra rent.
+
+use crate::{
+    generated::{accounts::MerchantOperatorConfig, errors::CommerceProgramError},
+    preview::POLICY_DATA_SIZE,
+};
+
+/// Most policies a config can hold.
+pub const MAX_POLICIES: usize = 16;
+
+/// Most accepted currencies a config can hold.
+pub const MAX_ACCEPTED_CURRENCIES: usize = 32;
+
+/// Size of a config account with `num_policies` policies and `num_accepted_currencies`
+/// accepted currencies.
+pub fn merchant_operator_config_size(num_policies: usize, num_accepted_currencies: usize) -> usize {
+    MerchantOperatorConfig::LEN
+        + num_policies * POLICY_DATA_SIZE
+        + num_accepted_currencies * (32 + 4)
+}
+
+/// Checks a config's policy and accepted currency counts against the program's limits,
+/// before sending an instruction the program would reject.
+pub fn check_config_limits(
+    num_policies: usize,
+    num_accepted_currencies: usize,
+) -> Result<(), CommerceProgramError> {
+    if num_policies > MAX_POLICIES {
+        return Err(CommerceProgramError::TooManyPolicies);
+    }
+    if num_accepted_currencies > MAX_ACCEPTED_CURRENCIES {
+        return Err(CommerceProgramError::TooManyAcceptedCurrencies);
+    }
+    Ok(())
+}
+
+/// Rent-exempt balance of a config account with `num_policies` policies and
+/// `num_accepted_currencies` accepted currencies.
+#[cfg(feature = "fetch")]
+pub fn fetch_merchant_operator_config_rent(
+    rpc: &solana_client::rpc_client::RpcClient,
+    num_policies: usize,
+    num_accepted_currencies: usize,
+) -> Result<u64, std::io::Error> {
+    rpc.get_minimum_balance_for_rent_exemption(merchant_operator_config_size(
+        num_policies,
+        num_accepted_currencies,
+    ))
+    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merchant_operator_config_size() {
+        assert_eq!(merchant_operator_config_size(0, 1), 137 + 36);
+        assert_eq!(merchant_operator_config_size(2, 3), 137 + 2 * 101 + 3 * 36);
+        assert_eq!(
+            merchant_operator_config_size(MAX_POLICIES, MAX_ACCEPTED_CURRENCIES),
+            2_905
+        );
+    }
+
+    #[test]
+    fn test_check_config_limits() {
+        assert_eq!(
+            check_config_limits(MAX_POLICIES, MAX_ACCEPTED_CURRENCIES),
+            Ok(())
+        );
+        assert_eq!(
+            check_config_limits(MAX_POLICIES + 1, 1),
+            Err(CommerceProgramError::TooManyPolicies)
+        );
+        assert_eq!(
+            check_config_limits(0, MAX_ACCEPTED_CURRENCIES + 1),
+            Err(CommerceProgramError::TooManyAcceptedCurrencies)
+        );
+    }
+}
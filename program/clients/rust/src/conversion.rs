@@ -0,0 +1,96 @@
+//! Cross-currency settlement.
+//!
+//! When a config carries a `ConversionPolicy`, `ClearPaymentWithConversion` pays the
+//! operator fee in the payment mint and swaps the merchant amount into the policy's
+//! settlement mint through its swap adapter, straight into the settlement wallet's ATA.
+//!
+//! Build the swap off-chain with the config's escrow authority PDA (`find_escrow_authority_pda`)
+//! as the token owner (for Jupiter v6, an exact-input `route` or `sharedAccountsRoute` from the
+//! swap-instructions API) spending exactly the merchant amount, then append its accounts last
+//! with `swap_remaining_accounts` and pass their count as `num_swap_accounts`. The program
+//! checks the escrow spent exactly the merchant amount and the settlement ATA received at least
+//! `min_amount_out`.
+//!
+//! The quote may not be below the merchant amount at the rate posted with `PostConversionRate`
+//! from the payment mint into the settlement mint, whose PDA is passed as `conversion_rate`
+//! (`find_conversion_rate_pda`).
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+
+use crate::types::{ConversionPolicy, SwapAdapter};
+
+pub const JUPITER_V6_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zvZ3tGvvH7uCvy2gSfr");
+
+const MAX_BPS: u128 = 10_000;
+
+impl SwapAdapter {
+    /// Swap program to pass as `swap_program`.
+    pub fn program_id(&self) -> Pubkey {
+        match self {
+            SwapAdapter::JupiterV6 => JUPITER_V6_PROGRAM_ID,
+        }
+    }
+}
+
+/// Smallest swap output the program accepts for a quote of `quoted_amount_out`.
+pub fn min_amount_out(policy: &ConversionPolicy, quoted_amount_out: u64) -> u64 {
+    let max_slippage_bps = (policy.max_slippage_bps as u128).min(MAX_BPS);
+    (quoted_amount_out as u128 * (MAX_BPS - max_slippage_bps) / MAX_BPS) as u64
+}
+
+/// Remaining accounts forwarding `swap_instruction` to the swap program. Signer flags are
+/// cleared since the program signs for the escrow authority PDA itself.
+pub fn swap_remaining_accounts(swap_instruction: &Instruction) -> Vec<AccountMeta> {
+    swap_instruction
+        .accounts
+        .iter()
+        .map(|meta| AccountMeta {
+            is_signer: false,
+            ..meta.clone()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_slippage_bps: u16) -> ConversionPolicy {
+        ConversionPolicy {
+            settlement_mint: Pubkey::new_unique(),
+            swap_adapter: SwapAdapter::JupiterV6,
+            max_slippage_bps,
+        }
+    }
+
+    #[test]
+    fn test_min_amount_out() {
+        assert_eq!(min_amount_out(&policy(50), 1_000_000), 995_000);
+        assert_eq!(min_amount_out(&policy(0), 1_000_000), 1_000_000);
+        assert_eq!(min_amount_out(&policy(10_000), 1_000_000), 0);
+    }
+
+    #[test]
+    fn test_swap_remaining_accounts_clears_signers() {
+        let merchant = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let swap_instruction = Instruction {
+            program_id: SwapAdapter::JupiterV6.program_id(),
+            accounts: vec![
+                AccountMeta::new_readonly(merchant, true),
+                AccountMeta::new(source, false),
+            ],
+            data: vec![],
+        };
+
+        let accounts = swap_remaining_accounts(&swap_instruction);
+        assert_eq!(
+            accounts,
+            vec![
+                AccountMeta::new_readonly(merchant, false),
+                AccountMeta::new(source, false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,1111 @@
+//! Decoding of whole instructions back into their builder inputs.
+//!
+//! [`decode_instruction`] splits an instruction's accounts into the generated accounts struct
+//! (e.g. [`MakePayment`]) and the remaining accounts, and parses its data with
+//! [`parse_instruction`]. Each accounts struct also gets a `decode` for a known instruction,
+//! e.g. `MakePayment::decode`. [`DecodedInstruction::instruction`] re-encodes through the
+//! generated builders, so decoding an instruction built by them and re-encoding it returns
+//! the same instruction. Instruction data with a version header decodes too and re-encodes
+//! without it, as the builders build it.
+//!
+//! Account flags are not checked, so instructions whose accounts were promoted to signer or
+//! writable still decode; re-encoding restores the flags the builders set. Decoding never
+//! panics, whatever the input, so it can be run on untrusted transactions.
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+use thiserror::Error;
+
+use crate::{
+    discriminator::{
+        parse_instruction, InstructionDiscriminator, ParseInstructionError, ParsedInstruction,
+    },
+    generated::instructions::*,
+    COMMERCE_PROGRAM_ID,
+};
+
+#[derive(Debug, Error)]
+pub enum DecodeInstructionError {
+    #[error("instruction is for program {0}, not the commerce program")]
+    WrongProgram(Pubkey),
+    #[error(transparent)]
+    InvalidData(#[from] ParseInstructionError),
+    #[error("{} expects {expected} accounts, got {found}", instruction.name())]
+    NotEnoughAccounts {
+        instruction: InstructionDiscriminator,
+        expected: usize,
+        found: usize,
+    },
+    #[error("expected {}, got {}", expected.name(), found.name())]
+    UnexpectedInstruction {
+        expected: InstructionDiscriminator,
+        found: InstructionDiscriminator,
+    },
+}
+
+/// An instruction's accounts, arguments and remaining accounts.
+///
+/// Instructions without arguments have `()` as `args`; `EmitEvent` has the raw event bytes.
+#[derive(Debug)]
+pub struct Decoded<A, T> {
+    pub accounts: A,
+    pub args: T,
+    pub remaining_accounts: Vec<AccountMeta>,
+}
+
+impl<A: DecodeAccounts, T> Decoded<A, T> {
+    fn new(instruction: &Instruction, args: T) -> Result<Self, DecodeInstructionError> {
+        if instruction.accounts.len() < A::LEN {
+            return Err(DecodeInstructionError::NotEnoughAccounts {
+                instruction: A::DISCRIMINATOR,
+                expected: A::LEN,
+                found: instruction.accounts.len(),
+            });
+        }
+
+        let (accounts, remaining_accounts) = instruction.accounts.split_at(A::LEN);
+        Ok(Self {
+            accounts: A::from_accounts(accounts),
+            args,
+            remaining_accounts: remaining_accounts.to_vec(),
+        })
+    }
+}
+
+/// A decoded commerce program instruction.
+#[derive(Debug)]
+pub enum DecodedInstruction {
+    InitializeMerchant(Decoded<InitializeMerchant, InitializeMerchantInstructionArgs>),
+    CreateOperator(Decoded<CreateOperator, ()>),
+    InitializeMerchantOperatorConfig(
+        Decoded<InitializeMerchantOperatorConfig, InitializeMerchantOperatorConfigInstructionArgs>,
+    ),
+    MakePayment(Decoded<MakePayment, MakePaymentInstructionArgs>),
+    ClearPayment(Decoded<ClearPayment, ()>),
+    RefundPayment(Decoded<RefundPayment, RefundPaymentInstructionArgs>),
+    UpdateMerchantSettlementWallet(Decoded<UpdateMerchantSettlementWallet, ()>),
+    ProposeMerchantAuthority(Decoded<ProposeMerchantAuthority, ()>),
+    ProposeOperatorAuthority(Decoded<ProposeOperatorAuthority, ()>),
+    ClosePayment(Decoded<ClosePayment, ()>),
+    InitializePaymentTree(Decoded<InitializePaymentTree, InitializePaymentTreeInstructionArgs>),
+    MakePaymentCompressed(Decoded<MakePaymentCompressed, MakePaymentCompressedInstructionArgs>),
+    ClearPaymentCompressed(Decoded<ClearPaymentCompressed, ClearPaymentCompressedInstructionArgs>),
+    AcceptMerchantAuthority(Decoded<AcceptMerchantAuthority, ()>),
+    AcceptOperatorAuthority(Decoded<AcceptOperatorAuthority, ()>),
+    InitializeMerchantStats(
+        Decoded<InitializeMerchantStats, InitializeMerchantStatsInstructionArgs>,
+    ),
+    RefundSettledPayment(Decoded<RefundSettledPayment, RefundSettledPaymentInstructionArgs>),
+    InitializeOperatorDelegate(
+        Decoded<InitializeOperatorDelegate, InitializeOperatorDelegateInstructionArgs>,
+    ),
+    UpdateOperatorDelegate(Decoded<UpdateOperatorDelegate, UpdateOperatorDelegateInstructionArgs>),
+    RevokeOperatorDelegate(Decoded<RevokeOperatorDelegate, ()>),
+    ClearPaymentWithConversion(
+        Decoded<ClearPaymentWithConversion, ClearPaymentWithConversionInstructionArgs>,
+    ),
+    ChargebackPayment(Decoded<ChargebackPayment, ()>),
+    SweepClosedPayments(Decoded<SweepClosedPayments, SweepClosedPaymentsInstructionArgs>),
+    ProposeSettlementWallet(Decoded<ProposeSettlementWallet, ()>),
+    CommitSettlementWallet(Decoded<CommitSettlementWallet, ()>),
+    InitializeSettlementBatch(
+        Decoded<InitializeSettlementBatch, InitializeSettlementBatchInstructionArgs>,
+    ),
+    ExecuteSettlementBatch(Decoded<ExecuteSettlementBatch, ()>),
+    CreateOrder(Decoded<CreateOrder, CreateOrderInstructionArgs>),
+    InitializeMerchantProfile(
+        Decoded<InitializeMerchantProfile, InitializeMerchantProfileInstructionArgs>,
+    ),
+    UpdateMerchantProfile(Decoded<UpdateMerchantProfile, UpdateMerchantProfileInstructionArgs>),
+    ApproveRefund(Decoded<ApproveRefund, ()>),
+    ClaimRefund(Decoded<ClaimRefund, ()>),
+    InitializeRiskState(Decoded<InitializeRiskState, InitializeRiskStateInstructionArgs>),
+    InitializeBuyerIndex(Decoded<InitializeBuyerIndex, InitializeBuyerIndexInstructionArgs>),
+    InitializeMerchantWithConfig(
+        Decoded<InitializeMerchantWithConfig, InitializeMerchantWithConfigInstructionArgs>,
+    ),
+    MigrateEscrow(Decoded<MigrateEscrow, MigrateEscrowInstructionArgs>),
+    CreateQuote(Decoded<CreateQuote, CreateQuoteInstructionArgs>),
+    InitializeConfigRegistryPage(
+        Decoded<InitializeConfigRegistryPage, InitializeConfigRegistryPageInstructionArgs>,
+    ),
+    CapturePayment(Decoded<CapturePayment, CapturePaymentInstructionArgs>),
+    InitializeOperatorFreezeList(
+        Decoded<InitializeOperatorFreezeList, InitializeOperatorFreezeListInstructionArgs>,
+    ),
+    AddToFreezeList(Decoded<AddToFreezeList, AddToFreezeListInstructionArgs>),
+    RemoveFromFreezeList(Decoded<RemoveFromFreezeList, RemoveFromFreezeListInstructionArgs>),
+    PostConversionRate(Decoded<PostConversionRate, PostConversionRateInstructionArgs>),
+    RefundPaymentWithConversion(Decoded<RefundPaymentWithConversion, ()>),
+    AddPolicy(Decoded<AddPolicy, AddPolicyInstructionArgs>),
+    AddAcceptedCurrency(Decoded<AddAcceptedCurrency, ()>),
+    ClosePaymentNote(Decoded<ClosePaymentNote, ()>),
+    Heartbeat(Decoded<Heartbeat, ()>),
+    CloseEscrowAta(Decoded<CloseEscrowAta, ()>),
+    MigratePayment(Decoded<MigratePayment, ()>),
+    GetConfigSummary(Decoded<GetConfigSummary, ()>),
+    SetWebhookCommitment(Decoded<SetWebhookCommitment, SetWebhookCommitmentInstructionArgs>),
+    AnnotatePayment(Decoded<AnnotatePayment, AnnotatePaymentInstructionArgs>),
+    DecommissionConfig(Decoded<DecommissionConfig, ()>),
+    CloseMerchantOperatorConfig(Decoded<CloseMerchantOperatorConfig, ()>),
+    UpgradeAccount(Decoded<UpgradeAccount, ()>),
+    SetSettlementWalletTimelock(
+        Decoded<SetSettlementWalletTimelock, SetSettlementWalletTimelockInstructionArgs>,
+    ),
+    EmitEvent(Decoded<EmitEvent, Vec<u8>>),
+}
+
+impl DecodedInstruction {
+    pub fn discriminator(&self) -> InstructionDiscriminator {
+        match self {
+            Self::InitializeMerchant(_) => InstructionDiscriminator::InitializeMerchant,
+            Self::CreateOperator(_) => InstructionDiscriminator::CreateOperator,
+            Self::InitializeMerchantOperatorConfig(_) => {
+                InstructionDiscriminator::InitializeMerchantOperatorConfig
+            }
+            Self::MakePayment(_) => InstructionDiscriminator::MakePayment,
+            Self::ClearPayment(_) => InstructionDiscriminator::ClearPayment,
+            Self::RefundPayment(_) => InstructionDiscriminator::RefundPayment,
+            Self::UpdateMerchantSettlementWallet(_) => {
+                InstructionDiscriminator::UpdateMerchantSettlementWallet
+            }
+            Self::ProposeMerchantAuthority(_) => InstructionDiscriminator::ProposeMerchantAuthority,
+            Self::ProposeOperatorAuthority(_) => InstructionDiscriminator::ProposeOperatorAuthority,
+            Self::ClosePayment(_) => InstructionDiscriminator::ClosePayment,
+            Self::InitializePaymentTree(_) => InstructionDiscriminator::InitializePaymentTree,
+            Self::MakePaymentCompressed(_) => InstructionDiscriminator::MakePaymentCompressed,
+            Self::ClearPaymentCompressed(_) => InstructionDiscriminator::ClearPaymentCompressed,
+            Self::AcceptMerchantAuthority(_) => InstructionDiscriminator::AcceptMerchantAuthority,
+            Self::AcceptOperatorAuthority(_) => InstructionDiscriminator::AcceptOperatorAuthority,
+            Self::InitializeMerchantStats(_) => InstructionDiscriminator::InitializeMerchantStats,
+            Self::RefundSettledPayment(_) => InstructionDiscriminator::RefundSettledPayment,
+            Self::InitializeOperatorDelegate(_) => {
+                InstructionDiscriminator::InitializeOperatorDelegate
+            }
+            Self::UpdateOperatorDelegate(_) => InstructionDiscriminator::UpdateOperatorDelegate,
+            Self::RevokeOperatorDelegate(_) => InstructionDiscriminator::RevokeOperatorDelegate,
+            Self::ClearPaymentWithConversion(_) => {
+                InstructionDiscriminator::ClearPaymentWithConversion
+            }
+            Self::ChargebackPayment(_) => InstructionDiscriminator::ChargebackPayment,
+            Self::SweepClosedPayments(_) => InstructionDiscriminator::SweepClosedPayments,
+            Self::ProposeSettlementWallet(_) => InstructionDiscriminator::ProposeSettlementWallet,
+            Self::CommitSettlementWallet(_) => InstructionDiscriminator::CommitSettlementWallet,
+            Self::InitializeSettlementBatch(_) => {
+                InstructionDiscriminator::InitializeSettlementBatch
+            }
+            Self::ExecuteSettlementBatch(_) => InstructionDiscriminator::ExecuteSettlementBatch,
+            Self::CreateOrder(_) => InstructionDiscriminator::CreateOrder,
+            Self::InitializeMerchantProfile(_) => {
+                InstructionDiscriminator::InitializeMerchantProfile
+            }
+            Self::UpdateMerchantProfile(_) => InstructionDiscriminator::UpdateMerchantProfile,
+            Self::ApproveRefund(_) => InstructionDiscriminator::ApproveRefund,
+            Self::ClaimRefund(_) => InstructionDiscriminator::ClaimRefund,
+            Self::InitializeRiskState(_) => InstructionDiscriminator::InitializeRiskState,
+            Self::InitializeBuyerIndex(_) => InstructionDiscriminator::InitializeBuyerIndex,
+            Self::InitializeMerchantWithConfig(_) => {
+                InstructionDiscriminator::InitializeMerchantWithConfig
+            }
+            Self::MigrateEscrow(_) => InstructionDiscriminator::MigrateEscrow,
+            Self::CreateQuote(_) => InstructionDiscriminator::CreateQuote,
+            Self::InitializeConfigRegistryPage(_) => {
+                InstructionDiscriminator::InitializeConfigRegistryPage
+            }
+            Self::CapturePayment(_) => InstructionDiscriminator::CapturePayment,
+            Self::InitializeOperatorFreezeList(_) => {
+                InstructionDiscriminator::InitializeOperatorFreezeList
+            }
+            Self::AddToFreezeList(_) => InstructionDiscriminator::AddToFreezeList,
+            Self::RemoveFromFreezeList(_) => InstructionDiscriminator::RemoveFromFreezeList,
+            Self::PostConversionRate(_) => InstructionDiscriminator::PostConversionRate,
+            Self::RefundPaymentWithConversion(_) => {
+                InstructionDiscriminator::RefundPaymentWithConversion
+            }
+            Self::AddPolicy(_) => InstructionDiscriminator::AddPolicy,
+            Self::AddAcceptedCurrency(_) => InstructionDiscriminator::AddAcceptedCurrency,
+            Self::ClosePaymentNote(_) => InstructionDiscriminator::ClosePaymentNote,
+            Self::Heartbeat(_) => InstructionDiscriminator::Heartbeat,
+            Self::CloseEscrowAta(_) => InstructionDiscriminator::CloseEscrowAta,
+            Self::MigratePayment(_) => InstructionDiscriminator::MigratePayment,
+            Self::GetConfigSummary(_) => InstructionDiscriminator::GetConfigSummary,
+            Self::SetWebhookCommitment(_) => InstructionDiscriminator::SetWebhookCommitment,
+            Self::AnnotatePayment(_) => InstructionDiscriminator::AnnotatePayment,
+            Self::DecommissionConfig(_) => InstructionDiscriminator::DecommissionConfig,
+            Self::CloseMerchantOperatorConfig(_) => {
+                InstructionDiscriminator::CloseMerchantOperatorConfig
+            }
+            Self::UpgradeAccount(_) => InstructionDiscriminator::UpgradeAccount,
+            Self::SetSettlementWalletTimelock(_) => {
+                InstructionDiscriminator::SetSettlementWalletTimelock
+            }
+            Self::EmitEvent(_) => InstructionDiscriminator::EmitEvent,
+        }
+    }
+
+    /// Re-encodes the instruction with the generated builders.
+    pub fn instruction(&self) -> Instruction {
+        match self {
+            Self::InitializeMerchant(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::CreateOperator(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::InitializeMerchantOperatorConfig(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::MakePayment(decoded) => decoded.accounts.instruction_with_remaining_accounts(
+                decoded.args.clone(),
+                &decoded.remaining_accounts,
+            ),
+            Self::ClearPayment(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::RefundPayment(decoded) => decoded.accounts.instruction_with_remaining_accounts(
+                decoded.args.clone(),
+                &decoded.remaining_accounts,
+            ),
+            Self::UpdateMerchantSettlementWallet(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::ProposeMerchantAuthority(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::ProposeOperatorAuthority(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::ClosePayment(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::InitializePaymentTree(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::MakePaymentCompressed(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::ClearPaymentCompressed(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::AcceptMerchantAuthority(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::AcceptOperatorAuthority(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::InitializeMerchantStats(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::RefundSettledPayment(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::InitializeOperatorDelegate(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::UpdateOperatorDelegate(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::RevokeOperatorDelegate(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::ClearPaymentWithConversion(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::ChargebackPayment(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::SweepClosedPayments(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::ProposeSettlementWallet(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::CommitSettlementWallet(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::InitializeSettlementBatch(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::ExecuteSettlementBatch(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::CreateOrder(decoded) => decoded.accounts.instruction_with_remaining_accounts(
+                decoded.args.clone(),
+                &decoded.remaining_accounts,
+            ),
+            Self::InitializeMerchantProfile(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::UpdateMerchantProfile(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::ApproveRefund(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::ClaimRefund(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::InitializeRiskState(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::InitializeBuyerIndex(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::InitializeMerchantWithConfig(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::MigrateEscrow(decoded) => decoded.accounts.instruction_with_remaining_accounts(
+                decoded.args.clone(),
+                &decoded.remaining_accounts,
+            ),
+            Self::CreateQuote(decoded) => decoded.accounts.instruction_with_remaining_accounts(
+                decoded.args.clone(),
+                &decoded.remaining_accounts,
+            ),
+            Self::InitializeConfigRegistryPage(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::CapturePayment(decoded) => decoded.accounts.instruction_with_remaining_accounts(
+                decoded.args.clone(),
+                &decoded.remaining_accounts,
+            ),
+            Self::InitializeOperatorFreezeList(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::AddToFreezeList(decoded) => decoded.accounts.instruction_with_remaining_accounts(
+                decoded.args.clone(),
+                &decoded.remaining_accounts,
+            ),
+            Self::RemoveFromFreezeList(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::PostConversionRate(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::RefundPaymentWithConversion(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::AddPolicy(decoded) => decoded.accounts.instruction_with_remaining_accounts(
+                decoded.args.clone(),
+                &decoded.remaining_accounts,
+            ),
+            Self::AddAcceptedCurrency(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::ClosePaymentNote(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::Heartbeat(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::CloseEscrowAta(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::MigratePayment(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::GetConfigSummary(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::SetWebhookCommitment(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::AnnotatePayment(decoded) => decoded.accounts.instruction_with_remaining_accounts(
+                decoded.args.clone(),
+                &decoded.remaining_accounts,
+            ),
+            Self::DecommissionConfig(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::CloseMerchantOperatorConfig(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::UpgradeAccount(decoded) => decoded
+                .accounts
+                .instruction_with_remaining_accounts(&decoded.remaining_accounts),
+            Self::SetSettlementWalletTimelock(decoded) => {
+                decoded.accounts.instruction_with_remaining_accounts(
+                    decoded.args.clone(),
+                    &decoded.remaining_accounts,
+                )
+            }
+            Self::EmitEvent(decoded) => {
+                let mut instruction = decoded
+                    .accounts
+                    .instruction_with_remaining_accounts(&decoded.remaining_accounts);
+                instruction.data.extend_from_slice(&decoded.args);
+                instruction
+            }
+        }
+    }
+}
+
+/// Decodes a commerce program instruction into its accounts, arguments and remaining
+/// accounts.
+///
+/// Like the program, trailing bytes after the arguments are ignored.
+pub fn decode_instruction(
+    instruction: &Instruction,
+) -> Result<DecodedInstruction, DecodeInstructionError> {
+    if instruction.program_id != COMMERCE_PROGRAM_ID {
+        return Err(DecodeInstructionError::WrongProgram(instruction.program_id));
+    }
+
+    let decoded = match parse_instruction(&instruction.data)? {
+        ParsedInstruction::InitializeMerchant(args) => {
+            DecodedInstruction::InitializeMerchant(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::CreateOperator => {
+            DecodedInstruction::CreateOperator(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::InitializeMerchantOperatorConfig(args) => {
+            DecodedInstruction::InitializeMerchantOperatorConfig(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::MakePayment(args) => {
+            DecodedInstruction::MakePayment(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::ClearPayment => {
+            DecodedInstruction::ClearPayment(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::RefundPayment(args) => {
+            DecodedInstruction::RefundPayment(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::UpdateMerchantSettlementWallet => {
+            DecodedInstruction::UpdateMerchantSettlementWallet(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::ProposeMerchantAuthority => {
+            DecodedInstruction::ProposeMerchantAuthority(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::ProposeOperatorAuthority => {
+            DecodedInstruction::ProposeOperatorAuthority(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::ClosePayment => {
+            DecodedInstruction::ClosePayment(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::InitializePaymentTree(args) => {
+            DecodedInstruction::InitializePaymentTree(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::MakePaymentCompressed(args) => {
+            DecodedInstruction::MakePaymentCompressed(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::ClearPaymentCompressed(args) => {
+            DecodedInstruction::ClearPaymentCompressed(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::AcceptMerchantAuthority => {
+            DecodedInstruction::AcceptMerchantAuthority(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::AcceptOperatorAuthority => {
+            DecodedInstruction::AcceptOperatorAuthority(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::InitializeMerchantStats(args) => {
+            DecodedInstruction::InitializeMerchantStats(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::RefundSettledPayment(args) => {
+            DecodedInstruction::RefundSettledPayment(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::InitializeOperatorDelegate(args) => {
+            DecodedInstruction::InitializeOperatorDelegate(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::UpdateOperatorDelegate(args) => {
+            DecodedInstruction::UpdateOperatorDelegate(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::RevokeOperatorDelegate => {
+            DecodedInstruction::RevokeOperatorDelegate(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::ClearPaymentWithConversion(args) => {
+            DecodedInstruction::ClearPaymentWithConversion(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::ChargebackPayment => {
+            DecodedInstruction::ChargebackPayment(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::SweepClosedPayments(args) => {
+            DecodedInstruction::SweepClosedPayments(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::ProposeSettlementWallet => {
+            DecodedInstruction::ProposeSettlementWallet(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::CommitSettlementWallet => {
+            DecodedInstruction::CommitSettlementWallet(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::InitializeSettlementBatch(args) => {
+            DecodedInstruction::InitializeSettlementBatch(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::ExecuteSettlementBatch => {
+            DecodedInstruction::ExecuteSettlementBatch(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::CreateOrder(args) => {
+            DecodedInstruction::CreateOrder(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::InitializeMerchantProfile(args) => {
+            DecodedInstruction::InitializeMerchantProfile(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::UpdateMerchantProfile(args) => {
+            DecodedInstruction::UpdateMerchantProfile(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::ApproveRefund => {
+            DecodedInstruction::ApproveRefund(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::ClaimRefund => {
+            DecodedInstruction::ClaimRefund(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::InitializeRiskState(args) => {
+            DecodedInstruction::InitializeRiskState(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::InitializeBuyerIndex(args) => {
+            DecodedInstruction::InitializeBuyerIndex(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::InitializeMerchantWithConfig(args) => {
+            DecodedInstruction::InitializeMerchantWithConfig(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::MigrateEscrow(args) => {
+            DecodedInstruction::MigrateEscrow(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::CreateQuote(args) => {
+            DecodedInstruction::CreateQuote(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::InitializeConfigRegistryPage(args) => {
+            DecodedInstruction::InitializeConfigRegistryPage(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::CapturePayment(args) => {
+            DecodedInstruction::CapturePayment(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::InitializeOperatorFreezeList(args) => {
+            DecodedInstruction::InitializeOperatorFreezeList(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::AddToFreezeList(args) => {
+            DecodedInstruction::AddToFreezeList(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::RemoveFromFreezeList(args) => {
+            DecodedInstruction::RemoveFromFreezeList(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::PostConversionRate(args) => {
+            DecodedInstruction::PostConversionRate(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::RefundPaymentWithConversion => {
+            DecodedInstruction::RefundPaymentWithConversion(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::AddPolicy(args) => {
+            DecodedInstruction::AddPolicy(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::AddAcceptedCurrency => {
+            DecodedInstruction::AddAcceptedCurrency(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::ClosePaymentNote => {
+            DecodedInstruction::ClosePaymentNote(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::Heartbeat => {
+            DecodedInstruction::Heartbeat(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::CloseEscrowAta => {
+            DecodedInstruction::CloseEscrowAta(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::MigratePayment => {
+            DecodedInstruction::MigratePayment(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::GetConfigSummary => {
+            DecodedInstruction::GetConfigSummary(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::SetWebhookCommitment(args) => {
+            DecodedInstruction::SetWebhookCommitment(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::AnnotatePayment(args) => {
+            DecodedInstruction::AnnotatePayment(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::DecommissionConfig => {
+            DecodedInstruction::DecommissionConfig(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::CloseMerchantOperatorConfig => {
+            DecodedInstruction::CloseMerchantOperatorConfig(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::UpgradeAccount => {
+            DecodedInstruction::UpgradeAccount(Decoded::new(instruction, ())?)
+        }
+        ParsedInstruction::SetSettlementWalletTimelock(args) => {
+            DecodedInstruction::SetSettlementWalletTimelock(Decoded::new(instruction, args)?)
+        }
+        ParsedInstruction::EmitEvent(args) => {
+            DecodedInstruction::EmitEvent(Decoded::new(instruction, args)?)
+        }
+    };
+
+    Ok(decoded)
+}
+
+/// Generated accounts structs, filled from an instruction's leading accounts in order.
+trait DecodeAccounts {
+    const DISCRIMINATOR: InstructionDiscriminator;
+    const LEN: usize;
+
+    /// `accounts` holds exactly `LEN` accounts.
+    fn from_accounts(accounts: &[AccountMeta]) -> Self;
+}
+
+macro_rules! decode_accounts {
+    ($name:ident($args:ty) { $($account:ident),+ $(,)? }) => {
+        impl DecodeAccounts for $name {
+            const DISCRIMINATOR: InstructionDiscriminator = InstructionDiscriminator::$name;
+            const LEN: usize = [$(stringify!($account)),+].len();
+
+            fn from_accounts(accounts: &[AccountMeta]) -> Self {
+                let mut keys = accounts.iter().map(|account| account.pubkey);
+                Self {
+                    $($account: keys.next().unwrap(),)+
+                }
+            }
+        }
+
+        impl $name {
+            #[doc = concat!("Decodes a `", stringify!($name), "` instruction.")]
+            pub fn decode(
+                instruction: &Instruction,
+            ) -> Result<Decoded<Self, $args>, DecodeInstructionError> {
+                match decode_instruction(instruction)? {
+                    DecodedInstruction::$name(decoded) => Ok(decoded),
+                    other => Err(DecodeInstructionError::UnexpectedInstruction {
+                        expected: InstructionDiscriminator::$name,
+                        found: other.discriminator(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+decode_accounts!(InitializeMerchant(InitializeMerchantInstructionArgs) {
+    payer, authority, merchant, settlement_wallet, system_program, event_authority,
+    commerce_program,
+});
+decode_accounts!(CreateOperator(()) {
+    payer, operator, authority, system_program,
+});
+decode_accounts!(InitializeMerchantOperatorConfig(InitializeMerchantOperatorConfigInstructionArgs) {
+    payer, authority, merchant, operator, config, system_program, event_authority,
+    commerce_program,
+});
+decode_accounts!(MakePayment(MakePaymentInstructionArgs) {
+    payer, payment, operator_authority, buyer, operator, merchant, merchant_operator_config, mint,
+    buyer_ata, escrow_authority, merchant_escrow_ata, merchant_settlement_ata, settlement_wallet,
+    token_program, associated_token_program, system_program, event_authority, commerce_program,
+});
+decode_accounts!(ClearPayment(()) {
+    payer, payment, operator_authority, buyer, merchant, operator, merchant_operator_config, mint,
+    escrow_authority, merchant_escrow_ata, merchant_settlement_ata, operator_settlement_ata,
+    token_program, associated_token_program, system_program, event_authority, commerce_program,
+});
+decode_accounts!(RefundPayment(RefundPaymentInstructionArgs) {
+    payer, payment, operator_authority, buyer, merchant, operator, merchant_operator_config, mint,
+    escrow_authority, merchant_escrow_ata, buyer_ata, token_program, system_program,
+    event_authority, commerce_program,
+});
+decode_accounts!(UpdateMerchantSettlementWallet(()) {
+    payer, authority, merchant, new_settlement_wallet, event_authority, commerce_program,
+});
+decode_accounts!(ProposeMerchantAuthority(()) {
+    payer, authority, merchant, new_authority,
+});
+decode_accounts!(ProposeOperatorAuthority(()) {
+    payer, authority, operator, new_operator_authority,
+});
+decode_accounts!(ClosePayment(()) {
+    payer, payment, operator_authority, operator, merchant, buyer, merchant_operator_config,
+    mint, system_program, event_authority, commerce_program,
+});
+decode_accounts!(InitializePaymentTree(InitializePaymentTreeInstructionArgs) {
+    payer, operator_authority, operator, merchant_operator_config, payment_tree, system_program,
+});
+decode_accounts!(MakePaymentCompressed(MakePaymentCompressedInstructionArgs) {
+    payment_tree, operator_authority, buyer, operator, merchant, merchant_operator_config, mint,
+    buyer_ata, escrow_authority, merchant_escrow_ata, merchant_settlement_ata, token_program,
+    event_authority, commerce_program,
+});
+decode_accounts!(ClearPaymentCompressed(ClearPaymentCompressedInstructionArgs) {
+    payer, payment_tree, operator_authority, buyer, merchant, operator, merchant_operator_config,
+    mint, escrow_authority, merchant_escrow_ata, merchant_settlement_ata, operator_settlement_ata,
+    token_program, associated_token_program, system_program, event_authority, commerce_program,
+});
+decode_accounts!(AcceptMerchantAuthority(()) {
+    payer, new_authority, merchant, event_authority, commerce_program,
+});
+decode_accounts!(AcceptOperatorAuthority(()) {
+    payer, new_operator_authority, operator, event_authority, commerce_program,
+});
+decode_accounts!(InitializeMerchantStats(InitializeMerchantStatsInstructionArgs) {
+    payer, authority, merchant, mint, merchant_stats, system_program,
+});
+decode_accounts!(RefundSettledPayment(RefundSettledPaymentInstructionArgs) {
+    payer, payment, operator_authority, buyer, merchant, operator, merchant_operator_config,
+    mint, merchant_settlement_ata, buyer_ata, refund_delegate, token_program, system_program,
+    event_authority, commerce_program,
+});
+decode_accounts!(InitializeOperatorDelegate(InitializeOperatorDelegateInstructionArgs) {
+    payer, authority, operator, operator_delegate, delegate, system_program,
+});
+decode_accounts!(UpdateOperatorDelegate(UpdateOperatorDelegateInstructionArgs) {
+    authority, operator, operator_delegate,
+});
+decode_accounts!(RevokeOperatorDelegate(()) {
+    authority, operator, operator_delegate,
+});
+decode_accounts!(ClearPaymentWithConversion(ClearPaymentWithConversionInstructionArgs) {
+    payer, payment, operator_authority, buyer, merchant, operator, merchant_operator_config, mint,
+    escrow_authority, merchant_escrow_ata, settlement_mint, conversion_rate,
+    merchant_settlement_ata, operator_settlement_ata, token_program, associated_token_program,
+    system_program, swap_program, event_authority, commerce_program,
+});
+decode_accounts!(ChargebackPayment(()) {
+    payer, payment, arbiter, buyer, merchant, operator, merchant_operator_config, mint,
+    escrow_authority, merchant_escrow_ata, buyer_ata, token_program, system_program,
+    event_authority, commerce_program,
+});
+decode_accounts!(SweepClosedPayments(SweepClosedPaymentsInstructionArgs) {
+    payer, operator_authority, operator, merchant, merchant_operator_config, rent_collector,
+    system_program, event_authority, commerce_program,
+});
+decode_accounts!(ProposeSettlementWallet(()) {
+    payer, authority, merchant, new_settlement_wallet, event_authority, commerce_program,
+});
+decode_accounts!(CommitSettlementWallet(()) {
+    payer, authority, merchant, event_authority, commerce_program,
+});
+decode_accounts!(InitializeSettlementBatch(InitializeSettlementBatchInstructionArgs) {
+    payer, authority, merchant, merchant_operator_config, mint, settlement_batch,
+    system_program,
+});
+decode_accounts!(ExecuteSettlementBatch(()) {
+    settlement_batch, merchant, merchant_operator_config, mint, escrow_authority,
+    merchant_escrow_ata, merchant_settlement_ata, token_program, event_authority, commerce_program,
+});
+decode_accounts!(CreateOrder(CreateOrderInstructionArgs) {
+    payer, operator_authority, buyer, operator, merchant, merchant_operator_config, order,
+    system_program, event_authority, commerce_program,
+});
+decode_accounts!(InitializeMerchantProfile(InitializeMerchantProfileInstructionArgs) {
+    payer, authority, merchant, merchant_profile, system_program,
+});
+decode_accounts!(UpdateMerchantProfile(UpdateMerchantProfileInstructionArgs) {
+    authority, merchant, merchant_profile,
+});
+decode_accounts!(ApproveRefund(()) {
+    payer, payment, authority, buyer, merchant, operator, merchant_operator_config, mint,
+    refund_approval, system_program, event_authority, commerce_program,
+});
+decode_accounts!(ClaimRefund(()) {
+    payer, payment, buyer, merchant, operator, merchant_operator_config, mint, refund_approval,
+    rent_payer, escrow_authority, merchant_escrow_ata, destination, token_program,
+    event_authority, commerce_program,
+});
+decode_accounts!(InitializeRiskState(InitializeRiskStateInstructionArgs) {
+    payer, merchant_operator_config, risk_state, system_program,
+});
+decode_accounts!(InitializeBuyerIndex(InitializeBuyerIndexInstructionArgs) {
+    payer, merchant_operator_config, buyer, buyer_index, system_program,
+});
+decode_accounts!(InitializeMerchantWithConfig(InitializeMerchantWithConfigInstructionArgs) {
+    payer, authority, operator_authority, merchant, settlement_wallet, operator, config,
+    system_program, event_authority, commerce_program,
+});
+decode_accounts!(MigrateEscrow(MigrateEscrowInstructionArgs) {
+    payer, merchant_authority, operator_authority, merchant, operator, merchant_operator_config,
+    mint, legacy_escrow_ata, escrow_authority, merchant_escrow_ata, token_program,
+    associated_token_program, system_program,
+});
+decode_accounts!(CreateQuote(CreateQuoteInstructionArgs) {
+    payer, operator_authority, operator, merchant_operator_config, mint, quote, system_program,
+});
+decode_accounts!(InitializeConfigRegistryPage(InitializeConfigRegistryPageInstructionArgs) {
+    payer, operator, config_registry_page, system_program,
+});
+decode_accounts!(CapturePayment(CapturePaymentInstructionArgs) {
+    payer, payment, operator_authority, buyer, merchant, operator, merchant_operator_config, mint,
+    escrow_authority, merchant_escrow_ata, buyer_ata, token_program, system_program,
+    event_authority, commerce_program,
+});
+decode_accounts!(InitializeOperatorFreezeList(InitializeOperatorFreezeListInstructionArgs) {
+    payer, authority, operator, operator_freeze_list, system_program,
+});
+decode_accounts!(AddToFreezeList(AddToFreezeListInstructionArgs) {
+    authority, operator, operator_freeze_list,
+});
+decode_accounts!(RemoveFromFreezeList(RemoveFromFreezeListInstructionArgs) {
+    authority, operator, operator_freeze_list,
+});
+decode_accounts!(PostConversionRate(PostConversionRateInstructionArgs) {
+    payer, operator_authority, operator, merchant_operator_config, from_mint, to_mint,
+    conversion_rate, system_program,
+});
+decode_accounts!(RefundPaymentWithConversion(()) {
+    payer, payment, operator_authority, buyer, merchant, operator, merchant_operator_config, mint,
+    escrow_authority, merchant_escrow_ata, operator_settlement_ata, refund_mint, conversion_rate,
+    operator_refund_ata, buyer_refund_ata, token_program, associated_token_program,
+    system_program, event_authority, commerce_program,
+});
+decode_accounts!(AddPolicy(AddPolicyInstructionArgs) {
+    payer, authority, merchant, merchant_operator_config, system_program,
+});
+decode_accounts!(AddAcceptedCurrency(()) {
+    payer, authority, merchant, merchant_operator_config, mint, system_program,
+});
+decode_accounts!(ClosePaymentNote(()) {
+    authority, merchant, payment_note, rent_payer,
+});
+decode_accounts!(Heartbeat(()) {
+    operator_authority, operator,
+});
+decode_accounts!(CloseEscrowAta(()) {
+    merchant_authority, operator_authority, merchant, operator, merchant_operator_config, mint,
+    escrow_authority, merchant_escrow_ata, merchant_settlement_ata, token_program,
+});
+decode_accounts!(MigratePayment(()) {
+    payer, merchant_authority, operator_authority, merchant, operator, buyer, mint,
+    old_merchant_operator_config, new_merchant_operator_config, old_payment, new_payment,
+    old_escrow_authority, old_merchant_escrow_ata, new_escrow_authority, new_merchant_escrow_ata,
+    token_program, associated_token_program, system_program, event_authority, commerce_program,
+});
+decode_accounts!(GetConfigSummary(()) {
+    merchant_operator_config, mint, escrow_authority, merchant_escrow_ata, token_program,
+});
+decode_accounts!(SetWebhookCommitment(SetWebhookCommitmentInstructionArgs) {
+    payer, authority, merchant, merchant_operator_config, webhook_commitment, system_program,
+    event_authority, commerce_program,
+});
+decode_accounts!(AnnotatePayment(AnnotatePaymentInstructionArgs) {
+    payer, payment, operator_authority, buyer, operator, merchant_operator_config, mint,
+    payment_annotation, system_program, event_authority, commerce_program,
+});
+decode_accounts!(DecommissionConfig(()) {
+    authority, merchant, operator, merchant_operator_config, event_authority, commerce_program,
+});
+decode_accounts!(CloseMerchantOperatorConfig(()) {
+    merchant_authority, operator_authority, merchant, operator, merchant_operator_config,
+    event_authority, commerce_program,
+});
+decode_accounts!(UpgradeAccount(()) {
+    payer, account, system_program,
+});
+decode_accounts!(SetSettlementWalletTimelock(SetSettlementWalletTimelockInstructionArgs) {
+    payer, authority, merchant, event_authority, commerce_program,
+});
+decode_accounts!(EmitEvent(Vec<u8>) {
+    event_authority,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn clear_payment() -> Instruction {
+        let p = Pubkey::new_unique;
+        ClearPaymentBuilder::new()
+            .payer(p())
+            .payment(p())
+            .operator_authority(p())
+            .buyer(p())
+            .merchant(p())
+            .operator(p())
+            .merchant_operator_config(p())
+            .mint(p())
+            .escrow_authority(p())
+            .merchant_escrow_ata(p())
+            .merchant_settlement_ata(p())
+            .operator_settlement_ata(p())
+            .add_remaining_account(AccountMeta::new(p(), false))
+            .instruction()
+    }
+
+    #[test]
+    fn test_decode_clear_payment() {
+        let instruction = clear_payment();
+
+        let decoded = ClearPayment::decode(&instruction).unwrap();
+        assert_eq!(decoded.accounts.payer, instruction.accounts[0].pubkey);
+        assert_eq!(
+            decoded.accounts.commerce_program,
+            instruction.accounts[16].pubkey
+        );
+        assert_eq!(decoded.remaining_accounts, instruction.accounts[17..]);
+
+        let decoded = decode_instruction(&instruction).unwrap();
+        assert_eq!(
+            decoded.discriminator(),
+            InstructionDiscriminator::ClearPayment
+        );
+        assert_eq!(decoded.instruction(), instruction);
+    }
+
+    #[test]
+    fn test_decode_versioned_instruction() {
+        let instruction = clear_payment();
+        let versioned = Instruction {
+            data: crate::discriminator::with_version_header(&instruction.data),
+            ..instruction.clone()
+        };
+
+        let decoded = decode_instruction(&versioned).unwrap();
+        assert_eq!(
+            decoded.discriminator(),
+            InstructionDiscriminator::ClearPayment
+        );
+        assert_eq!(decoded.instruction(), instruction);
+    }
+
+    #[test]
+    fn test_decode_emit_event_keeps_event_bytes() {
+        let mut instruction = EmitEventBuilder::new()
+            .event_authority(Pubkey::new_unique())
+            .instruction();
+        instruction.data.extend_from_slice(&[1, 2, 3]);
+
+        let decoded = EmitEvent::decode(&instruction).unwrap();
+        assert_eq!(decoded.args, vec![1, 2, 3]);
+        assert_eq!(
+            decode_instruction(&instruction).unwrap().instruction(),
+            instruction
+        );
+    }
+
+    #[test]
+    fn test_decode_instruction_errors() {
+        let mut instruction = clear_payment();
+
+        assert!(matches!(
+            RefundPayment::decode(&instruction),
+            Err(DecodeInstructionError::UnexpectedInstruction {
+                expected: InstructionDiscriminator::RefundPayment,
+                found: InstructionDiscriminator::ClearPayment,
+            })
+        ));
+
+        instruction.accounts.truncate(3);
+        assert!(matches!(
+            decode_instruction(&instruction),
+            Err(DecodeInstructionError::NotEnoughAccounts {
+                instruction: InstructionDiscriminator::ClearPayment,
+                expected: 17,
+                found: 3,
+            })
+        ));
+
+        instruction.data.clear();
+        assert!(matches!(
+            decode_instruction(&instruction),
+            Err(DecodeInstructionError::InvalidData(
+                ParseInstructionError::Empty
+            ))
+        ));
+
+        instruction.program_id = Pubkey::new_unique();
+        assert!(matches!(
+            decode_instruction(&instruction),
+            Err(DecodeInstructionError::WrongProgram(_))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn test_decode_arbitrary_instruction(
+            discriminator in prop::sample::select(InstructionDiscriminator::ALL.to_vec()),
+            args in prop::collection::vec(any::<u8>(), 0..256),
+            accounts in prop::collection::vec(
+                (any::<[u8; 32]>(), any::<bool>(), any::<bool>()),
+                0..24,
+            ),
+        ) {
+            let mut data = vec![u8::from(discriminator)];
+            data.extend(args);
+            let instruction = Instruction {
+                program_id: COMMERCE_PROGRAM_ID,
+                accounts: accounts
+                    .into_iter()
+                    .map(|(key, is_signer, is_writable)| AccountMeta {
+                        pubkey: Pubkey::new_from_array(key),
+                        is_signer,
+                        is_writable,
+                    })
+                    .collect(),
+                data,
+            };
+
+            // Whatever decodes re-encodes to an instruction that decodes to itself
+            if let Ok(decoded) = decode_instruction(&instruction) {
+                prop_assert_eq!(decoded.discriminator(), discriminator);
+
+                let encoded = decoded.instruction();
+                prop_assert_eq!(
+                    &encoded.accounts.iter().map(|account| account.pubkey).collect::<Vec<_>>(),
+                    &instruction.accounts.iter().map(|account| account.pubkey).collect::<Vec<_>>()
+                );
+                prop_assert_eq!(
+                    decode_instruction(&encoded).unwrap().instruction(),
+                    encoded
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,559 @@
+//! Offboarding a merchant's config with an operator.
+//!
+//! A config is closed in four steps, since the payments already made under it settle first:
+//!
+//! 1. `DecommissionConfig`, signed by the merchant or the operator owner, stops new payments.
+//!    Existing payments still clear and refund as usual.
+//! 2. `CloseEscrowAta` closes each accepted currency's escrow ATA once the config counts no
+//!    open payment in it, sweeping any balance left to the merchant's settlement wallet,
+//!    co-signed by both owners.
+//! 3. `ClosePayment` closes each Payment account made under the config once its close window
+//!    has passed, as it needs the config to. The config counts the accounts left.
+//! 4. `CloseMerchantOperatorConfig` closes the config once it counts no open payment in any
+//!    currency and no Payment account, and returns its rent to the merchant authority. The
+//!    program does not check the escrow ATAs, but their rent can only be reclaimed before the
+//!    config goes.
+//!
+//! [`Decommission::next_step`] works out the step a config is at from its account, its
+//! open-payment counts, its escrow ATAs and the Payment accounts the caller knows of, and
+//! builds the instructions for it; call it again after they land until it returns
+//! [`DecommissionStep::Closed`]:
+//!
+//! ```ignore
+//! let decommission = Decommission::new(merchant_authority, operator_authority);
+//! loop {
+//!     match decommission.fetch_step(&rpc, &payment_accounts)? {
+//!         DecommissionStep::Closed => break,
+//!         DecommissionStep::AwaitingSettlement { close_escrows, .. } => {
+//!             send(close_escrows)?;
+//!             sleep(poll_interval);
+//!         }
+//!         step => send(step.instructions())?,
+//!     }
+//! }
+//! ```
+//!
+//! Funds still in a legacy escrow owned by the merchant PDA are not seen; move them with
+//! `MigrateEscrow` first. Sidecar PDAs of the config, such as its PaymentTree, SettlementBatch
+//! or WebhookCommitment, are left as they are.
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    checkout::{get_associated_token_address, TOKEN_PROGRAM_ID},
+    derived_accounts::DEFAULT_CONFIG_VERSION,
+    generated::{
+        accounts::MerchantOperatorConfig,
+        instructions::{
+            CloseEscrowAtaBuilder, CloseMerchantOperatorConfigBuilder, ClosePaymentBuilder,
+            DecommissionConfigBuilder,
+        },
+    },
+    pdas::{
+        find_escrow_authority_pda, find_merchant_operator_config_pda, find_merchant_pda,
+        find_operator_pda,
+    },
+};
+
+/// A config's escrow ATA for one of its accepted currencies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EscrowAta {
+    pub mint: Pubkey,
+    /// Token program owning the mint
+    pub token_program: Pubkey,
+    /// The escrow ATA, owned by the escrow authority PDA of the config and mint
+    pub address: Pubkey,
+    /// Balance of the escrow ATA, `None` once it is closed
+    pub balance: Option<u64>,
+    /// Payments holding funds in the escrow, as counted by the config
+    pub open_payments: u32,
+}
+
+impl EscrowAta {
+    pub fn new(
+        merchant_operator_config: &Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+        balance: Option<u64>,
+        open_payments: u32,
+    ) -> Self {
+        let (escrow_authority, _) = find_escrow_authority_pda(merchant_operator_config, &mint);
+        Self {
+            mint,
+            token_program,
+            address: get_associated_token_address(&escrow_authority, &mint, &token_program),
+            balance,
+            open_payments,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.balance.is_none()
+    }
+}
+
+/// A Payment account made under the config, with the buyer and mint its PDA is derived from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaymentAccount {
+    pub payment: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    /// Rent treasury recorded as the payment's `rent_payer`, `None` if the fee payer paid
+    pub rent_payer: Option<Pubkey>,
+}
+
+/// Where a config is in its decommissioning, with the instructions that move it on.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecommissionStep {
+    /// The config still takes payments; the instruction is its `DecommissionConfig`.
+    Decommission(Instruction),
+    /// Escrows in `mints` still hold the funds of open payments, which must clear or refund
+    /// first. `close_escrows` closes the escrows with none left in the meantime.
+    AwaitingSettlement {
+        mints: Vec<Pubkey>,
+        close_escrows: Vec<Instruction>,
+    },
+    /// No payment is open; one `CloseEscrowAta` per escrow ATA still open.
+    CloseEscrows(Vec<Instruction>),
+    /// The config still counts `payment_accounts` Payment accounts, which must close before
+    /// it; `close_payments` is one `ClosePayment` per payment passed to `next_step`.
+    ClosePayments {
+        payment_accounts: u32,
+        close_payments: Vec<Instruction>,
+    },
+    /// Every escrow is closed; the instruction is the config's `CloseMerchantOperatorConfig`.
+    CloseConfig(Instruction),
+    /// The config account no longer exists.
+    Closed,
+}
+
+impl DecommissionStep {
+    /// Instructions to send for this step, none once closed.
+    pub fn instructions(&self) -> Vec<Instruction> {
+        match self {
+            Self::Decommission(instruction) | Self::CloseConfig(instruction) => {
+                vec![instruction.clone()]
+            }
+            Self::AwaitingSettlement { close_escrows, .. } => close_escrows.clone(),
+            Self::ClosePayments { close_payments, .. } => close_payments.clone(),
+            Self::CloseEscrows(instructions) => instructions.clone(),
+            Self::Closed => Vec::new(),
+        }
+    }
+}
+
+/// Drives the decommissioning of a merchant's config with an operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Decommission {
+    pub merchant_authority: Pubkey,
+    pub operator_authority: Pubkey,
+    /// Version of the merchant's config with the operator
+    pub config_version: u32,
+    /// Signer of `DecommissionConfig`, the merchant or the operator authority
+    pub decommission_authority: Pubkey,
+    /// The merchant's settlement wallet, whose ATA `CloseEscrowAta` sweeps escrow balances to
+    pub settlement_wallet: Pubkey,
+}
+
+impl Decommission {
+    /// Decommissioning of config version [`DEFAULT_CONFIG_VERSION`], stopped by the operator,
+    /// for a merchant settling to its authority until [`Self::settlement_wallet`] says
+    /// otherwise.
+    pub fn new(merchant_authority: Pubkey, operator_authority: Pubkey) -> Self {
+        Self {
+            merchant_authority,
+            operator_authority,
+            config_version: DEFAULT_CONFIG_VERSION,
+            decommission_authority: operator_authority,
+            settlement_wallet: merchant_authority,
+        }
+    }
+
+    pub fn config_version(mut self, config_version: u32) -> Self {
+        self.config_version = config_version;
+        self
+    }
+
+    /// Sets the merchant's settlement wallet, as stored on its Merchant account.
+    pub fn settlement_wallet(mut self, settlement_wallet: Pubkey) -> Self {
+        self.settlement_wallet = settlement_wallet;
+        self
+    }
+
+    /// Has the merchant rather than the operator sign `DecommissionConfig`.
+    pub fn decommissioned_by_merchant(mut self) -> Self {
+        self.decommission_authority = self.merchant_authority;
+        self
+    }
+
+    pub fn merchant(&self) -> Pubkey {
+        find_merchant_pda(&self.merchant_authority).0
+    }
+
+    pub fn operator(&self) -> Pubkey {
+        find_operator_pda(&self.operator_authority).0
+    }
+
+    pub fn merchant_operator_config(&self) -> Pubkey {
+        find_merchant_operator_config_pda(&self.merchant(), &self.operator(), self.config_version).0
+    }
+
+    /// `DecommissionConfig` stopping new payments under the config.
+    pub fn decommission_config(&self) -> Instruction {
+        DecommissionConfigBuilder::new()
+            .authority(self.decommission_authority)
+            .merchant(self.merchant())
+            .operator(self.operator())
+            .merchant_operator_config(self.merchant_operator_config())
+            .instruction()
+    }
+
+    /// `CloseEscrowAta` closing `escrow`, its rent returned to the merchant authority and any
+    /// balance swept to the settlement wallet's ATA.
+    pub fn close_escrow_ata(&self, escrow: &EscrowAta) -> Instruction {
+        let merchant_operator_config = self.merchant_operator_config();
+        CloseEscrowAtaBuilder::new()
+            .merchant_authority(self.merchant_authority)
+            .operator_authority(self.operator_authority)
+            .merchant(self.merchant())
+            .operator(self.operator())
+            .merchant_operator_config(merchant_operator_config)
+            .mint(escrow.mint)
+            .escrow_authority(find_escrow_authority_pda(&merchant_operator_config, &escrow.mint).0)
+            .merchant_escrow_ata(escrow.address)
+            .merchant_settlement_ata(get_associated_token_address(
+                &self.settlement_wallet,
+                &escrow.mint,
+                &escrow.token_program,
+            ))
+            .token_program(escrow.token_program)
+            .instruction()
+    }
+
+    /// `ClosePayment` closing `payment`, signed by the operator authority, which is refunded
+    /// its rent unless a rent treasury paid it.
+    pub fn close_payment(&self, payment: &PaymentAccount) -> Instruction {
+        let mut builder = ClosePaymentBuilder::new();
+        builder
+            .payer(self.operator_authority)
+            .payment(payment.payment)
+            .operator_authority(self.operator_authority)
+            .operator(self.operator())
+            .merchant(self.merchant())
+            .buyer(payment.buyer)
+            .merchant_operator_config(self.merchant_operator_config())
+            .mint(payment.mint);
+        if let Some(rent_payer) = payment.rent_payer {
+            builder.add_remaining_account(AccountMeta::new(rent_payer, false));
+        }
+        builder.instruction()
+    }
+
+    /// `CloseMerchantOperatorConfig`, its rent returned to the merchant authority.
+    pub fn close_merchant_operator_config(&self) -> Instruction {
+        CloseMerchantOperatorConfigBuilder::new()
+            .merchant_authority(self.merchant_authority)
+            .operator_authority(self.operator_authority)
+            .merchant(self.merchant())
+            .operator(self.operator())
+            .merchant_operator_config(self.merchant_operator_config())
+            .instruction()
+    }
+
+    /// The step the config is at, given its account, `None` once closed, the escrow of each
+    /// of its accepted currencies in order, and the Payment accounts still open under it that
+    /// the caller knows of.
+    pub fn next_step(
+        &self,
+        config: Option<&MerchantOperatorConfig>,
+        escrows: &[EscrowAta],
+        payments: &[PaymentAccount],
+    ) -> DecommissionStep {
+        let Some(config) = config else {
+            return DecommissionStep::Closed;
+        };
+        if config.decommissioned_at == 0 {
+            return DecommissionStep::Decommission(self.decommission_config());
+        }
+
+        let mints: Vec<Pubkey> = escrows
+            .iter()
+            .filter(|escrow| escrow.open_payments > 0)
+            .map(|escrow| escrow.mint)
+            .collect();
+        let close_escrows: Vec<Instruction> = escrows
+            .iter()
+            .filter(|escrow| !escrow.is_closed() && escrow.open_payments == 0)
+            .map(|escrow| self.close_escrow_ata(escrow))
+            .collect();
+
+        if !mints.is_empty() {
+            DecommissionStep::AwaitingSettlement {
+                mints,
+                close_escrows,
+            }
+        } else if !close_escrows.is_empty() {
+            DecommissionStep::CloseEscrows(close_escrows)
+        } else if config.payment_accounts > 0 {
+            DecommissionStep::ClosePayments {
+                payment_accounts: config.payment_accounts,
+                close_payments: payments
+                    .iter()
+                    .map(|payment| self.close_payment(payment))
+                    .collect(),
+            }
+        } else {
+            DecommissionStep::CloseConfig(self.close_merchant_operator_config())
+        }
+    }
+
+    /// Fetches the config, its accepted mints and their escrow ATAs, and the merchant's
+    /// settlement wallet, and returns the step the config is at. The Payment accounts under
+    /// the config are not discoverable from it and are passed as for `next_step`.
+    #[cfg(feature = "fetch")]
+    pub fn fetch_step(
+        &self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        payments: &[PaymentAccount],
+    ) -> Result<DecommissionStep, std::io::Error> {
+        use crate::{
+            escrow_audit::token_account_amount,
+            generated::accounts::Merchant,
+            preview::{decode_merchant_operator_config, decode_open_payments},
+        };
+
+        let to_io = |e: solana_client::client_error::ClientError| {
+            std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+        };
+        let merchant_operator_config = self.merchant_operator_config();
+
+        let mut accounts = rpc
+            .get_multiple_accounts(&[merchant_operator_config, self.merchant()])
+            .map_err(to_io)?
+            .into_iter();
+        let Some(account) = accounts.next().flatten() else {
+            return Ok(DecommissionStep::Closed);
+        };
+        let (config, _, currencies) = decode_merchant_operator_config(&account.data)?;
+        let open_payments = decode_open_payments(&account.data)?;
+        let decommission = match accounts.next().flatten() {
+            Some(merchant) => {
+                self.settlement_wallet(Merchant::from_bytes(&merchant.data)?.settlement_wallet)
+            }
+            None => *self,
+        };
+
+        // The mints' owners are their token programs, which the escrow ATAs are derived with
+        let token_programs = rpc
+            .get_multiple_accounts(&currencies)
+            .map_err(to_io)?
+            .into_iter()
+            .map(|mint| mint.map_or(TOKEN_PROGRAM_ID, |mint| mint.owner));
+        let mut escrows: Vec<EscrowAta> = currencies
+            .iter()
+            .zip(token_programs)
+            .zip(open_payments)
+            .map(|((mint, token_program), open_payments)| {
+                EscrowAta::new(
+                    &merchant_operator_config,
+                    *mint,
+                    token_program,
+                    None,
+                    open_payments,
+                )
+            })
+            .collect();
+
+        let addresses: Vec<Pubkey> = escrows.iter().map(|escrow| escrow.address).collect();
+        let balances = rpc.get_multiple_accounts(&addresses).map_err(to_io)?;
+        for (escrow, account) in escrows.iter_mut().zip(balances) {
+            escrow.balance = account
+                .filter(|account| !account.data.is_empty())
+                .map(|account| token_account_amount(&account.data).unwrap_or_default());
+        }
+
+        Ok(decommission.next_step(Some(&config), &escrows, payments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        discriminator::{parse_instruction, ParsedInstruction},
+        generated::types::FeeType,
+    };
+
+    fn key(n: u8) -> Pubkey {
+        Pubkey::new_from_array([n; 32])
+    }
+
+    fn decommission() -> Decommission {
+        Decommission::new(key(1), key(2))
+    }
+
+    fn config(decommissioned_at: i64) -> MerchantOperatorConfig {
+        MerchantOperatorConfig {
+            discriminator: 2,
+            version: 1,
+            bump: 255,
+            merchant: decommission().merchant(),
+            operator: decommission().operator(),
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 7,
+            days_to_close: 30,
+            num_policies: 0,
+            num_accepted_currencies: 2,
+            config_hash: [0; 32],
+            decommissioned_at,
+            payment_accounts: 0,
+        }
+    }
+
+    fn escrows(balances: [Option<u64>; 2], open_payments: [u32; 2]) -> Vec<EscrowAta> {
+        let merchant_operator_config = decommission().merchant_operator_config();
+        vec![
+            EscrowAta::new(
+                &merchant_operator_config,
+                key(3),
+                TOKEN_PROGRAM_ID,
+                balances[0],
+                open_payments[0],
+            ),
+            EscrowAta::new(
+                &merchant_operator_config,
+                key(4),
+                TOKEN_PROGRAM_ID,
+                balances[1],
+                open_payments[1],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_active_config_is_decommissioned_first() {
+        let step =
+            decommission().next_step(Some(&config(0)), &escrows([Some(0), None], [0, 0]), &[]);
+        let DecommissionStep::Decommission(instruction) = step else {
+            panic!("expected Decommission, got {step:?}");
+        };
+        assert!(matches!(
+            parse_instruction(&instruction.data).unwrap(),
+            ParsedInstruction::DecommissionConfig
+        ));
+        assert_eq!(instruction.accounts[0].pubkey, key(2));
+        assert!(instruction.accounts[0].is_signer);
+
+        let instruction = decommission()
+            .decommissioned_by_merchant()
+            .decommission_config();
+        assert_eq!(instruction.accounts[0].pubkey, key(1));
+    }
+
+    #[test]
+    fn test_escrow_with_open_payments_awaits_settlement() {
+        // Dust left in an escrow with no open payment does not hold up its close
+        let escrows = escrows([Some(500), Some(3)], [1, 0]);
+        let step = decommission().next_step(Some(&config(1_700_000_000)), &escrows, &[]);
+        assert_eq!(
+            step,
+            DecommissionStep::AwaitingSettlement {
+                mints: vec![key(3)],
+                close_escrows: vec![decommission().close_escrow_ata(&escrows[1])],
+            }
+        );
+    }
+
+    #[test]
+    fn test_empty_escrows_are_closed_then_config() {
+        let escrows = escrows([Some(0), None], [0, 0]);
+        let step = decommission().next_step(Some(&config(1_700_000_000)), &escrows, &[]);
+        assert_eq!(
+            step,
+            DecommissionStep::CloseEscrows(vec![decommission().close_escrow_ata(&escrows[0])])
+        );
+
+        let escrows = self::escrows([None, None], [0, 0]);
+        let step = decommission().next_step(Some(&config(1_700_000_000)), &escrows, &[]);
+        let DecommissionStep::CloseConfig(instruction) = step else {
+            panic!("expected CloseConfig, got {step:?}");
+        };
+        assert!(matches!(
+            parse_instruction(&instruction.data).unwrap(),
+            ParsedInstruction::CloseMerchantOperatorConfig
+        ));
+        assert_eq!(instruction.accounts.len(), 7);
+
+        assert_eq!(
+            decommission().next_step(None, &escrows, &[]),
+            DecommissionStep::Closed
+        );
+        assert!(DecommissionStep::Closed.instructions().is_empty());
+    }
+
+    #[test]
+    fn test_payment_accounts_are_closed_before_config() {
+        let payment = PaymentAccount {
+            payment: key(6),
+            buyer: key(7),
+            mint: key(3),
+            rent_payer: None,
+        };
+        let funded = PaymentAccount {
+            rent_payer: Some(key(8)),
+            ..payment
+        };
+        let config = MerchantOperatorConfig {
+            payment_accounts: 2,
+            ..config(1_700_000_000)
+        };
+        let step = decommission().next_step(
+            Some(&config),
+            &escrows([None, None], [0, 0]),
+            &[payment, funded],
+        );
+        let DecommissionStep::ClosePayments {
+            payment_accounts,
+            close_payments,
+        } = step
+        else {
+            panic!("expected ClosePayments, got {step:?}");
+        };
+        assert_eq!(payment_accounts, 2);
+        assert_eq!(close_payments.len(), 2);
+        assert!(matches!(
+            parse_instruction(&close_payments[0].data).unwrap(),
+            ParsedInstruction::ClosePayment
+        ));
+        assert_eq!(close_payments[0].accounts[1].pubkey, key(6));
+        assert!(close_payments[0].accounts[6].is_writable);
+        assert_eq!(close_payments[0].accounts.len(), 11);
+        assert_eq!(
+            close_payments[1].accounts[11],
+            AccountMeta::new(key(8), false)
+        );
+    }
+
+    #[test]
+    fn test_close_escrow_ata_uses_escrow_token_program() {
+        let token_2022 = key(9);
+        let merchant_operator_config = decommission().merchant_operator_config();
+        let escrow = EscrowAta::new(&merchant_operator_config, key(3), token_2022, Some(0), 0);
+
+        let instruction = decommission()
+            .settlement_wallet(key(5))
+            .close_escrow_ata(&escrow);
+        assert_eq!(
+            instruction.accounts[6].pubkey,
+            find_escrow_authority_pda(&merchant_operator_config, &key(3)).0
+        );
+        assert_eq!(instruction.accounts[7].pubkey, escrow.address);
+        assert_eq!(
+            instruction.accounts[8].pubkey,
+            get_associated_token_address(&key(5), &key(3), &token_2022)
+        );
+        assert_eq!(instruction.accounts[9].pubkey, token_2022);
+    }
+}
@@ -0,0 +1,218 @@
+//! Off-chain helpers for buyer denylists.
+//!
+//! A `DenylistPolicy` only stores a Merkle root. Its leaves are the gaps between
+//! consecutive banned buyers in sorted order, bounded by the all-zero and all-`0xff` keys,
+//! so a buyer proves they are not banned by opening the gap that strictly contains them.
+//! [`DenylistTree`] keeps the banned set and produces both the policy and the
+//! `DenylistProof` that `MakePayment` and `MakePaymentCompressed` require.
+//!
+//! Policies cannot be updated in place, so a changed denylist is rolled out with a new
+//! config version carrying the new root.
+
+use std::collections::BTreeSet;
+
+use solana_pubkey::Pubkey;
+use solana_sha256_hasher::hashv;
+
+use crate::generated::types::{DenylistPolicy, DenylistProof};
+
+/// Deepest denylist tree the program accepts.
+pub const MAX_DENYLIST_DEPTH: usize = 20;
+
+/// Lower bound of the first gap.
+pub const DENYLIST_MIN_KEY: Pubkey = Pubkey::new_from_array([0; 32]);
+
+/// Upper bound of the last gap.
+pub const DENYLIST_MAX_KEY: Pubkey = Pubkey::new_from_array([0xff; 32]);
+
+/// Leaf hash of the gap between `lower` and `upper`, matching the program's encoding.
+pub fn denylist_leaf_hash(lower: &Pubkey, upper: &Pubkey) -> [u8; 32] {
+    hashv(&[lower.as_ref(), upper.as_ref()]).to_bytes()
+}
+
+/// Banned buyers of a denylist.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DenylistTree {
+    banned: BTreeSet<Pubkey>,
+}
+
+impl DenylistTree {
+    pub fn new(banned: impl IntoIterator<Item = Pubkey>) -> Self {
+        Self {
+            banned: banned.into_iter().collect(),
+        }
+    }
+
+    /// Bans `buyer`, returning whether it was newly added.
+    pub fn insert(&mut self, buyer: Pubkey) -> bool {
+        self.banned.insert(buyer)
+    }
+
+    /// Lifts the ban on `buyer`, returning whether it was banned.
+    pub fn remove(&mut self, buyer: &Pubkey) -> bool {
+        self.banned.remove(buyer)
+    }
+
+    pub fn contains(&self, buyer: &Pubkey) -> bool {
+        self.banned.contains(buyer)
+    }
+
+    pub fn banned(&self) -> impl Iterator<Item = &Pubkey> {
+        self.banned.iter()
+    }
+
+    /// Depth of the smallest tree that fits every gap.
+    pub fn depth(&self) -> u8 {
+        let gaps = self.banned.len() + 1;
+        gaps.next_power_of_two().trailing_zeros() as u8
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels().last().expect("tree has a root level")[0]
+    }
+
+    /// Policy committing to the current denylist.
+    ///
+    /// Panics if the denylist needs a deeper tree than [`MAX_DENYLIST_DEPTH`].
+    pub fn policy(&self) -> DenylistPolicy {
+        assert!(
+            self.depth() as usize <= MAX_DENYLIST_DEPTH,
+            "denylist is too large"
+        );
+        DenylistPolicy {
+            root: self.root(),
+            depth: self.depth(),
+        }
+    }
+
+    /// Proof that `buyer` is not banned, or `None` if it is banned or a gap bound.
+    pub fn proof(&self, buyer: &Pubkey) -> Option<DenylistProof> {
+        if self.contains(buyer) || *buyer == DENYLIST_MIN_KEY || *buyer == DENYLIST_MAX_KEY {
+            return None;
+        }
+
+        let leaf_index = self.banned.range(..*buyer).count();
+        let (lower, upper) = self.gaps().nth(leaf_index)?;
+        let levels = self.levels();
+
+        Some(DenylistProof {
+            lower,
+            upper,
+            leaf_index: leaf_index as u32,
+            proof: levels[..levels.len() - 1]
+                .iter()
+                .enumerate()
+                .map(|(level, nodes)| nodes[(leaf_index >> level) ^ 1])
+                .collect(),
+        })
+    }
+
+    fn gaps(&self) -> impl Iterator<Item = (Pubkey, Pubkey)> + '_ {
+        let bounds = std::iter::once(&DENYLIST_MIN_KEY)
+            .chain(self.banned.iter())
+            .chain(std::iter::once(&DENYLIST_MAX_KEY));
+        bounds
+            .clone()
+            .zip(bounds.skip(1))
+            .map(|(lower, upper)| (*lower, *upper))
+    }
+
+    /// Every level from the leaves, padded with zeroed leaves, up to the root.
+    fn levels(&self) -> Vec<Vec<[u8; 32]>> {
+        let mut leaves: Vec<[u8; 32]> = self
+            .gaps()
+            .map(|(lower, upper)| denylist_leaf_hash(&lower, &upper))
+            .collect();
+        leaves.resize(1 << self.depth(), [0; 32]);
+
+        let mut levels = vec![leaves];
+        while levels.last().expect("tree has a leaf level").len() > 1 {
+            let next = levels
+                .last()
+                .expect("tree has a leaf level")
+                .chunks(2)
+                .map(|pair| hashv(&[&pair[0], &pair[1]]).to_bytes())
+                .collect();
+            levels.push(next);
+        }
+
+        levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::compute_root;
+
+    fn verify(policy: &DenylistPolicy, buyer: &Pubkey, proof: &DenylistProof) -> bool {
+        proof.lower < *buyer
+            && *buyer < proof.upper
+            && proof.proof.len() == policy.depth as usize
+            && compute_root(
+                proof.leaf_index,
+                denylist_leaf_hash(&proof.lower, &proof.upper),
+                &proof.proof,
+            ) == policy.root
+    }
+
+    #[test]
+    fn test_empty_denylist_allows_everyone() {
+        let tree = DenylistTree::default();
+        let policy = tree.policy();
+        assert_eq!(policy.depth, 0);
+        assert_eq!(
+            policy.root,
+            denylist_leaf_hash(&DENYLIST_MIN_KEY, &DENYLIST_MAX_KEY)
+        );
+
+        let buyer = Pubkey::new_unique();
+        let proof = tree.proof(&buyer).unwrap();
+        assert!(proof.proof.is_empty());
+        assert!(verify(&policy, &buyer, &proof));
+    }
+
+    #[test]
+    fn test_proofs_verify_for_allowed_buyers() {
+        let banned: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let tree = DenylistTree::new(banned.iter().copied());
+        let policy = tree.policy();
+        assert_eq!(policy.depth, 3);
+
+        for _ in 0..10 {
+            let buyer = Pubkey::new_unique();
+            let proof = tree.proof(&buyer).unwrap();
+            assert!(verify(&policy, &buyer, &proof));
+        }
+        for buyer in &banned {
+            assert!(tree.proof(buyer).is_none());
+        }
+    }
+
+    #[test]
+    fn test_proof_does_not_cover_banned_neighbour() {
+        let banned = Pubkey::new_unique();
+        let tree = DenylistTree::new([banned]);
+        let policy = tree.policy();
+
+        let allowed = Pubkey::new_unique();
+        let proof = tree.proof(&allowed).unwrap();
+        assert!(!verify(&policy, &banned, &proof));
+    }
+
+    #[test]
+    fn test_root_changes_with_denylist() {
+        let buyer = Pubkey::new_unique();
+        let mut tree = DenylistTree::default();
+        let empty_root = tree.root();
+
+        assert!(tree.insert(buyer));
+        assert!(!tree.insert(buyer));
+        assert_ne!(tree.root(), empty_root);
+        assert!(tree.proof(&buyer).is_none());
+
+        assert!(tree.remove(&buyer));
+        assert_eq!(tree.root(), empty_root);
+        assert!(tree.proof(&buyer).is_some());
+    }
+}
@@ -0,0 +1,439 @@
+//! Instruction builders with their accounts derived from a few keys.
+//!
+//! A payment's PDAs, ATAs and bumps all follow from the merchant and operator authorities,
+//! the buyer, the mint and the order id. [`DerivedAccounts`] derives them, and the
+//! `with_derived_accounts` methods it adds to the payment instruction builders set every
+//! account and bump they can, leaving only the arguments to set:
+//!
+//! ```ignore
+//! let accounts = DerivedAccounts::new(merchant_authority, operator_authority, buyer, mint, 7)
+//!     .settlement_wallet(settlement_wallet);
+//! let instruction = MakePaymentBuilder::new()
+//!     .with_derived_accounts(&accounts)
+//!     .amount(1_000_000)
+//!     .idempotency_key(idempotency_key)
+//!     .instruction();
+//! ```
+//!
+//! Accounts that cannot be derived, or that differ from the defaults, are set with the
+//! builder's own setters after `with_derived_accounts`. The payer defaults to the operator
+//! authority (the buyer for `ClaimRefund`). `merchant_settlement_ata`, and `settlement_wallet`
+//! for `MakePayment`, are only set when [`DerivedAccounts::settlement_wallet`] is given, since
+//! the wallet is stored on the Merchant account. Programs keep the builders' defaults, except
+//! the token program, which follows [`DerivedAccounts::token_program`].
+
+use solana_pubkey::Pubkey;
+
+use crate::{
+    checkout::{get_associated_token_address, TOKEN_PROGRAM_ID},
+    generated::instructions::{
+        ApproveRefundBuilder, CapturePaymentBuilder, ClaimRefundBuilder, ClearPaymentBuilder,
+        ClosePaymentBuilder, CreateOrderBuilder, CreateQuoteBuilder, MakePaymentBuilder,
+        MakePaymentCompressedBuilder, RefundPaymentBuilder, RefundSettledPaymentBuilder,
+    },
+    pdas::{
+        find_escrow_authority_pda, find_merchant_operator_config_pda, find_merchant_pda,
+        find_operator_pda, find_order_pda, find_payment_pda, find_payment_tree_pda, find_quote_pda,
+        find_refund_approval_pda, find_refund_delegate_pda,
+    },
+};
+
+/// Config version `DerivedAccounts` uses unless told otherwise.
+pub const DEFAULT_CONFIG_VERSION: u32 = 1;
+
+/// Keys a payment's accounts are derived from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerivedAccounts {
+    pub merchant_authority: Pubkey,
+    pub operator_authority: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub order_id: u32,
+    /// Version of the merchant's config with the operator
+    pub config_version: u32,
+    /// Token program owning `mint`
+    pub token_program: Pubkey,
+    /// Merchant's settlement wallet, as stored on its Merchant account
+    pub settlement_wallet: Option<Pubkey>,
+}
+
+impl DerivedAccounts {
+    /// Accounts of order `order_id` paid by `buyer` in `mint`, under config version
+    /// [`DEFAULT_CONFIG_VERSION`] of an SPL Token mint.
+    pub fn new(
+        merchant_authority: Pubkey,
+        operator_authority: Pubkey,
+        buyer: Pubkey,
+        mint: Pubkey,
+        order_id: u32,
+    ) -> Self {
+        Self {
+            merchant_authority,
+            operator_authority,
+            buyer,
+            mint,
+            order_id,
+            config_version: DEFAULT_CONFIG_VERSION,
+            token_program: TOKEN_PROGRAM_ID,
+            settlement_wallet: None,
+        }
+    }
+
+    pub fn config_version(mut self, config_version: u32) -> Self {
+        self.config_version = config_version;
+        self
+    }
+
+    pub fn token_program(mut self, token_program: Pubkey) -> Self {
+        self.token_program = token_program;
+        self
+    }
+
+    pub fn settlement_wallet(mut self, settlement_wallet: Pubkey) -> Self {
+        self.settlement_wallet = Some(settlement_wallet);
+        self
+    }
+
+    pub fn merchant(&self) -> Pubkey {
+        find_merchant_pda(&self.merchant_authority).0
+    }
+
+    pub fn operator(&self) -> Pubkey {
+        find_operator_pda(&self.operator_authority).0
+    }
+
+    pub fn merchant_operator_config(&self) -> Pubkey {
+        find_merchant_operator_config_pda(&self.merchant(), &self.operator(), self.config_version).0
+    }
+
+    /// Payment PDA and bump.
+    pub fn payment(&self) -> (Pubkey, u8) {
+        find_payment_pda(
+            &self.merchant_operator_config(),
+            &self.buyer,
+            &self.mint,
+            self.order_id,
+        )
+    }
+
+    pub fn escrow_authority(&self) -> Pubkey {
+        find_escrow_authority_pda(&self.merchant_operator_config(), &self.mint).0
+    }
+
+    pub fn merchant_escrow_ata(&self) -> Pubkey {
+        self.ata(&self.escrow_authority())
+    }
+
+    pub fn buyer_ata(&self) -> Pubkey {
+        self.ata(&self.buyer)
+    }
+
+    pub fn operator_settlement_ata(&self) -> Pubkey {
+        self.ata(&self.operator_authority)
+    }
+
+    /// Settlement ATA of the merchant's settlement wallet, if it was given.
+    pub fn merchant_settlement_ata(&self) -> Option<Pubkey> {
+        self.settlement_wallet.map(|wallet| self.ata(&wallet))
+    }
+
+    fn ata(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.mint, &self.token_program)
+    }
+}
+
+impl MakePaymentBuilder {
+    /// Sets every account and `order_id` from `accounts`.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        let (payment, _) = accounts.payment();
+        self.payer(accounts.operator_authority)
+            .payment(payment)
+            .operator_authority(accounts.operator_authority)
+            .buyer(accounts.buyer)
+            .operator(accounts.operator())
+            .merchant(accounts.merchant())
+            .merchant_operator_config(accounts.merchant_operator_config())
+            .mint(accounts.mint)
+            .buyer_ata(accounts.buyer_ata())
+            .escrow_authority(accounts.escrow_authority())
+            .merchant_escrow_ata(accounts.merchant_escrow_ata())
+            .token_program(accounts.token_program)
+            .order_id(accounts.order_id);
+        if let Some(settlement_wallet) = accounts.settlement_wallet {
+            self.settlement_wallet(settlement_wallet)
+                .merchant_settlement_ata(accounts.ata(&settlement_wallet));
+        }
+        self
+    }
+}
+
+impl MakePaymentCompressedBuilder {
+    /// Sets every account and `order_id` from `accounts`.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        let merchant_operator_config = accounts.merchant_operator_config();
+        self.payment_tree(find_payment_tree_pda(&merchant_operator_config).0)
+            .operator_authority(accounts.operator_authority)
+            .buyer(accounts.buyer)
+            .operator(accounts.operator())
+            .merchant(accounts.merchant())
+            .merchant_operator_config(merchant_operator_config)
+            .mint(accounts.mint)
+            .buyer_ata(accounts.buyer_ata())
+            .escrow_authority(accounts.escrow_authority())
+            .merchant_escrow_ata(accounts.merchant_escrow_ata())
+            .token_program(accounts.token_program)
+            .order_id(accounts.order_id);
+        if let Some(merchant_settlement_ata) = accounts.merchant_settlement_ata() {
+            self.merchant_settlement_ata(merchant_settlement_ata);
+        }
+        self
+    }
+}
+
+impl ClearPaymentBuilder {
+    /// Sets every account from `accounts`.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        self.payer(accounts.operator_authority)
+            .payment(accounts.payment().0)
+            .operator_authority(accounts.operator_authority)
+            .buyer(accounts.buyer)
+            .merchant(accounts.merchant())
+            .operator(accounts.operator())
+            .merchant_operator_config(accounts.merchant_operator_config())
+            .mint(accounts.mint)
+            .escrow_authority(accounts.escrow_authority())
+            .merchant_escrow_ata(accounts.merchant_escrow_ata())
+            .operator_settlement_ata(accounts.operator_settlement_ata())
+            .token_program(accounts.token_program);
+        if let Some(merchant_settlement_ata) = accounts.merchant_settlement_ata() {
+            self.merchant_settlement_ata(merchant_settlement_ata);
+        }
+        self
+    }
+}
+
+impl RefundPaymentBuilder {
+    /// Sets every account from `accounts`.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        self.payer(accounts.operator_authority)
+            .payment(accounts.payment().0)
+            .operator_authority(accounts.operator_authority)
+            .buyer(accounts.buyer)
+            .merchant(accounts.merchant())
+            .operator(accounts.operator())
+            .merchant_operator_config(accounts.merchant_operator_config())
+            .mint(accounts.mint)
+            .escrow_authority(accounts.escrow_authority())
+            .merchant_escrow_ata(accounts.merchant_escrow_ata())
+            .buyer_ata(accounts.buyer_ata())
+            .token_program(accounts.token_program)
+    }
+}
+
+impl CapturePaymentBuilder {
+    /// Sets every account from `accounts`.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        self.payer(accounts.operator_authority)
+            .payment(accounts.payment().0)
+            .operator_authority(accounts.operator_authority)
+            .buyer(accounts.buyer)
+            .merchant(accounts.merchant())
+            .operator(accounts.operator())
+            .merchant_operator_config(accounts.merchant_operator_config())
+            .mint(accounts.mint)
+            .escrow_authority(accounts.escrow_authority())
+            .merchant_escrow_ata(accounts.merchant_escrow_ata())
+            .buyer_ata(accounts.buyer_ata())
+            .token_program(accounts.token_program)
+    }
+}
+
+impl RefundSettledPaymentBuilder {
+    /// Sets every account and `refund_delegate_bump` from `accounts`.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        let merchant = accounts.merchant();
+        let (refund_delegate, refund_delegate_bump) = find_refund_delegate_pda(&merchant);
+        self.payer(accounts.operator_authority)
+            .payment(accounts.payment().0)
+            .operator_authority(accounts.operator_authority)
+            .buyer(accounts.buyer)
+            .merchant(merchant)
+            .operator(accounts.operator())
+            .merchant_operator_config(accounts.merchant_operator_config())
+            .mint(accounts.mint)
+            .buyer_ata(accounts.buyer_ata())
+            .refund_delegate(refund_delegate)
+            .refund_delegate_bump(refund_delegate_bump)
+            .token_program(accounts.token_program);
+        if let Some(merchant_settlement_ata) = accounts.merchant_settlement_ata() {
+            self.merchant_settlement_ata(merchant_settlement_ata);
+        }
+        self
+    }
+}
+
+impl ApproveRefundBuilder {
+    /// Sets every account from `accounts`, with the operator authority approving.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        let (payment, _) = accounts.payment();
+        let (refund_approval, _) = find_refund_approval_pda(&payment);
+        self.payer(accounts.operator_authority)
+            .payment(payment)
+            .authority(accounts.operator_authority)
+            .buyer(accounts.buyer)
+            .merchant(accounts.merchant())
+            .operator(accounts.operator())
+            .merchant_operator_config(accounts.merchant_operator_config())
+            .mint(accounts.mint)
+            .refund_approval(refund_approval)
+    }
+}
+
+impl ClaimRefundBuilder {
+    /// Sets every account from `accounts`, paying the refund to the buyer's ATA and the
+    /// approval's rent back to the operator authority, who paid it in `ApproveRefund`.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        let (payment, _) = accounts.payment();
+        self.payer(accounts.buyer)
+            .payment(payment)
+            .buyer(accounts.buyer)
+            .merchant(accounts.merchant())
+            .operator(accounts.operator())
+            .merchant_operator_config(accounts.merchant_operator_config())
+            .mint(accounts.mint)
+            .refund_approval(find_refund_approval_pda(&payment).0)
+            .rent_payer(accounts.operator_authority)
+            .escrow_authority(accounts.escrow_authority())
+            .merchant_escrow_ata(accounts.merchant_escrow_ata())
+            .destination(accounts.buyer_ata())
+            .token_program(accounts.token_program)
+    }
+}
+
+impl ClosePaymentBuilder {
+    /// Sets every account from `accounts`.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        self.payer(accounts.operator_authority)
+            .payment(accounts.payment().0)
+            .operator_authority(accounts.operator_authority)
+            .operator(accounts.operator())
+            .merchant(accounts.merchant())
+            .buyer(accounts.buyer)
+            .merchant_operator_config(accounts.merchant_operator_config())
+            .mint(accounts.mint)
+    }
+}
+
+impl CreateOrderBuilder {
+    /// Sets every account, `order_id` and `bump` from `accounts`. The mint is unused.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        let merchant_operator_config = accounts.merchant_operator_config();
+        let (order, bump) = find_order_pda(
+            &merchant_operator_config,
+            &accounts.buyer,
+            accounts.order_id,
+        );
+        self.payer(accounts.operator_authority)
+            .operator_authority(accounts.operator_authority)
+            .buyer(accounts.buyer)
+            .operator(accounts.operator())
+            .merchant(accounts.merchant())
+            .merchant_operator_config(merchant_operator_config)
+            .order(order)
+            .order_id(accounts.order_id)
+            .bump(bump)
+    }
+}
+
+impl CreateQuoteBuilder {
+    /// Sets every account, `order_id` and `bump` from `accounts`. The buyer is unused.
+    pub fn with_derived_accounts(&mut self, accounts: &DerivedAccounts) -> &mut Self {
+        let merchant_operator_config = accounts.merchant_operator_config();
+        let (quote, bump) =
+            find_quote_pda(&merchant_operator_config, &accounts.mint, accounts.order_id);
+        self.payer(accounts.operator_authority)
+            .operator_authority(accounts.operator_authority)
+            .operator(accounts.operator())
+            .merchant_operator_config(merchant_operator_config)
+            .mint(accounts.mint)
+            .quote(quote)
+            .order_id(accounts.order_id)
+            .bump(bump)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkout::CheckoutTransactionBuilder;
+    use solana_instruction::AccountMeta;
+
+    fn key(n: u8) -> Pubkey {
+        Pubkey::new_from_array([n; 32])
+    }
+
+    fn accounts() -> DerivedAccounts {
+        DerivedAccounts::new(key(1), key(2), key(3), key(4), 42).settlement_wallet(key(5))
+    }
+
+    #[test]
+    fn test_make_payment_matches_checkout() {
+        let accounts = accounts();
+        let instruction = MakePaymentBuilder::new()
+            .with_derived_accounts(&accounts)
+            .amount(1_000_000)
+            .idempotency_key([9; 16])
+            .instruction();
+
+        let mut checkout = CheckoutTransactionBuilder::new();
+        checkout
+            .payer(key(2))
+            .operator_authority(key(2))
+            .buyer(key(3))
+            .operator(accounts.operator())
+            .merchant(accounts.merchant())
+            .merchant_operator_config(accounts.merchant_operator_config())
+            .settlement_wallet(key(5))
+            .mint(key(4))
+            .order_id(42)
+            .amount(1_000_000)
+            .idempotency_key([9; 16]);
+
+        assert_eq!(instruction, checkout.instructions()[0]);
+    }
+
+    #[test]
+    fn test_derived_config_version() {
+        let accounts = accounts().config_version(3);
+        assert_eq!(
+            accounts.merchant_operator_config(),
+            find_merchant_operator_config_pda(
+                &find_merchant_pda(&key(1)).0,
+                &find_operator_pda(&key(2)).0,
+                3
+            )
+            .0
+        );
+    }
+
+    #[test]
+    fn test_setters_override_derived_accounts() {
+        let instruction = ClearPaymentBuilder::new()
+            .with_derived_accounts(&accounts())
+            .payer(key(9))
+            .instruction();
+        assert_eq!(instruction.accounts[0], AccountMeta::new(key(9), true));
+    }
+
+    #[test]
+    fn test_claim_refund_pays_buyer() {
+        let accounts = accounts();
+        let instruction = ClaimRefundBuilder::new()
+            .with_derived_accounts(&accounts)
+            .instruction();
+        assert_eq!(instruction.accounts[0].pubkey, key(3));
+        assert!(instruction
+            .accounts
+            .contains(&AccountMeta::new(accounts.buyer_ata(), false)));
+    }
+}
@@ -0,0 +1,205 @@
+//! Serializable instruction descriptors.
+//!
+//! [`InstructionDescriptor`] is a plain-data copy of an [`Instruction`] for callers that hand
+//! instructions to another runtime instead of signing them here, e.g. a browser wallet or an
+//! edge function built for `wasm32-unknown-unknown`. With the `serde` feature it serializes
+//! to `{ programId, accounts: [{ pubkey, isSigner, isWritable }], data }` with base58 pubkeys
+//! and `data` as a byte array.
+//!
+//! Every instruction builder implements [`BuildDescriptor`], so `.descriptor()` can be
+//! called wherever `.instruction()` would be.
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::generated::instructions::*;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AccountMetaDescriptor {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct InstructionDescriptor {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMetaDescriptor>,
+    pub data: Vec<u8>,
+}
+
+impl From<AccountMeta> for AccountMetaDescriptor {
+    fn from(meta: AccountMeta) -> Self {
+        Self {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        }
+    }
+}
+
+impl From<AccountMetaDescriptor> for AccountMeta {
+    fn from(descriptor: AccountMetaDescriptor) -> Self {
+        Self {
+            pubkey: descriptor.pubkey,
+            is_signer: descriptor.is_signer,
+            is_writable: descriptor.is_writable,
+        }
+    }
+}
+
+impl From<Instruction> for InstructionDescriptor {
+    fn from(instruction: Instruction) -> Self {
+        Self {
+            program_id: instruction.program_id,
+            accounts: instruction.accounts.into_iter().map(Into::into).collect(),
+            data: instruction.data,
+        }
+    }
+}
+
+impl From<InstructionDescriptor> for Instruction {
+    fn from(descriptor: InstructionDescriptor) -> Self {
+        Self {
+            program_id: descriptor.program_id,
+            accounts: descriptor.accounts.into_iter().map(Into::into).collect(),
+            data: descriptor.data,
+        }
+    }
+}
+
+/// Builds an [`InstructionDescriptor`] instead of an [`Instruction`].
+pub trait BuildDescriptor {
+    fn descriptor(&self) -> InstructionDescriptor;
+}
+
+macro_rules! impl_build_descriptor {
+    ($($builder:ty),* $(,)?) => {
+        $(
+            impl BuildDescriptor for $builder {
+                fn descriptor(&self) -> InstructionDescriptor {
+                    self.instruction().into()
+                }
+            }
+        )*
+    };
+}
+
+impl_build_descriptor!(
+    AcceptMerchantAuthorityBuilder,
+    AcceptOperatorAuthorityBuilder,
+    AddAcceptedCurrencyBuilder,
+    AddPolicyBuilder,
+    AddToFreezeListBuilder,
+    AnnotatePaymentBuilder,
+    ApproveRefundBuilder,
+    CapturePaymentBuilder,
+    ChargebackPaymentBuilder,
+    ClaimRefundBuilder,
+    ClearPaymentBuilder,
+    ClearPaymentCompressedBuilder,
+    ClearPaymentWithConversionBuilder,
+    CloseEscrowAtaBuilder,
+    CloseMerchantOperatorConfigBuilder,
+    ClosePaymentBuilder,
+    ClosePaymentNoteBuilder,
+    CommitSettlementWalletBuilder,
+    CreateOperatorBuilder,
+    CreateOrderBuilder,
+    CreateQuoteBuilder,
+    DecommissionConfigBuilder,
+    EmitEventBuilder,
+    ExecuteSettlementBatchBuilder,
+    GetConfigSummaryBuilder,
+    HeartbeatBuilder,
+    InitializeBuyerIndexBuilder,
+    InitializeConfigRegistryPageBuilder,
+    InitializeMerchantBuilder,
+    InitializeMerchantOperatorConfigBuilder,
+    InitializeMerchantProfileBuilder,
+    InitializeMerchantStatsBuilder,
+    InitializeMerchantWithConfigBuilder,
+    InitializeOperatorDelegateBuilder,
+    InitializeOperatorFreezeListBuilder,
+    InitializePaymentTreeBuilder,
+    InitializeRiskStateBuilder,
+    InitializeSettlementBatchBuilder,
+    MakePaymentBuilder,
+    MakePaymentCompressedBuilder,
+    MigrateEscrowBuilder,
+    MigratePaymentBuilder,
+    PostConversionRateBuilder,
+    ProposeMerchantAuthorityBuilder,
+    ProposeOperatorAuthorityBuilder,
+    ProposeSettlementWalletBuilder,
+    RefundPaymentBuilder,
+    RefundPaymentWithConversionBuilder,
+    RefundSettledPaymentBuilder,
+    RemoveFromFreezeListBuilder,
+    RevokeOperatorDelegateBuilder,
+    SetSettlementWalletTimelockBuilder,
+    SetWebhookCommitmentBuilder,
+    SweepClosedPaymentsBuilder,
+    UpdateMerchantProfileBuilder,
+    UpdateMerchantSettlementWalletBuilder,
+    UpdateOperatorDelegateBuilder,
+    UpgradeAccountBuilder,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::COMMERCE_PROGRAM_ID;
+
+    fn commit_settlement_wallet() -> CommitSettlementWalletBuilder {
+        let mut builder = CommitSettlementWalletBuilder::new();
+        builder
+            .payer(Pubkey::new_from_array([1; 32]))
+            .authority(Pubkey::new_from_array([2; 32]))
+            .merchant(Pubkey::new_from_array([3; 32]));
+        builder
+    }
+
+    #[test]
+    fn test_descriptor_round_trips_instruction() {
+        let builder = commit_settlement_wallet();
+        let descriptor = builder.descriptor();
+
+        assert_eq!(descriptor.program_id, COMMERCE_PROGRAM_ID);
+        assert_eq!(descriptor.accounts.len(), 5);
+        assert!(descriptor.accounts[0].is_signer && descriptor.accounts[0].is_writable);
+        assert!(!descriptor.accounts[2].is_signer && descriptor.accounts[2].is_writable);
+        assert_eq!(Instruction::from(descriptor), builder.instruction());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_descriptor_json_shape() {
+        let descriptor = commit_settlement_wallet().descriptor();
+        let json = serde_json::to_value(&descriptor).unwrap();
+
+        assert_eq!(json["programId"], COMMERCE_PROGRAM_ID.to_string());
+        assert_eq!(
+            json["accounts"][1]["pubkey"],
+            Pubkey::new_from_array([2; 32]).to_string()
+        );
+        assert_eq!(json["accounts"][1]["isSigner"], true);
+        assert_eq!(json["accounts"][1]["isWritable"], false);
+        assert_eq!(json["data"], serde_json::json!(descriptor.data));
+
+        let parsed: InstructionDescriptor = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, descriptor);
+    }
+}
@@ -0,0 +1,811 @@
+//! Instruction discriminators and decoding of raw instruction data.
+//!
+//! Every instruction's data starts with a one-byte discriminator followed by its
+//! borsh-encoded arguments. `parse_instruction` reverses the generated builders, which is
+//! useful for inspecting transactions.
+//!
+//! The discriminator may carry [`INSTRUCTION_VERSION_FLAG`], in which case a version byte
+//! follows it and the arguments come after. The builders leave the version out, which the
+//! program reads as [`INSTRUCTION_VERSION`]; [`with_version_header`] adds it.
+
+use alloc::vec::Vec;
+use borsh::BorshDeserialize;
+use thiserror::Error;
+
+use crate::generated::instructions::*;
+
+/// Set on the discriminator of instruction data whose next byte is its version.
+pub const INSTRUCTION_VERSION_FLAG: u8 = 0x80;
+
+/// Current layout version of the instructions' arguments, assumed for data without a version.
+pub const INSTRUCTION_VERSION: u8 = 2;
+
+/// First byte of every instruction's data.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InstructionDiscriminator {
+    InitializeMerchant = INITIALIZE_MERCHANT_DISCRIMINATOR,
+    CreateOperator = CREATE_OPERATOR_DISCRIMINATOR,
+    InitializeMerchantOperatorConfig = INITIALIZE_MERCHANT_OPERATOR_CONFIG_DISCRIMINATOR,
+    MakePayment = MAKE_PAYMENT_DISCRIMINATOR,
+    ClearPayment = CLEAR_PAYMENT_DISCRIMINATOR,
+    RefundPayment = REFUND_PAYMENT_DISCRIMINATOR,
+    UpdateMerchantSettlementWallet = UPDATE_MERCHANT_SETTLEMENT_WALLET_DISCRIMINATOR,
+    ProposeMerchantAuthority = PROPOSE_MERCHANT_AUTHORITY_DISCRIMINATOR,
+    ProposeOperatorAuthority = PROPOSE_OPERATOR_AUTHORITY_DISCRIMINATOR,
+    ClosePayment = CLOSE_PAYMENT_DISCRIMINATOR,
+    InitializePaymentTree = INITIALIZE_PAYMENT_TREE_DISCRIMINATOR,
+    MakePaymentCompressed = MAKE_PAYMENT_COMPRESSED_DISCRIMINATOR,
+    ClearPaymentCompressed = CLEAR_PAYMENT_COMPRESSED_DISCRIMINATOR,
+    AcceptMerchantAuthority = ACCEPT_MERCHANT_AUTHORITY_DISCRIMINATOR,
+    AcceptOperatorAuthority = ACCEPT_OPERATOR_AUTHORITY_DISCRIMINATOR,
+    InitializeMerchantStats = INITIALIZE_MERCHANT_STATS_DISCRIMINATOR,
+    RefundSettledPayment = REFUND_SETTLED_PAYMENT_DISCRIMINATOR,
+    InitializeOperatorDelegate = INITIALIZE_OPERATOR_DELEGATE_DISCRIMINATOR,
+    UpdateOperatorDelegate = UPDATE_OPERATOR_DELEGATE_DISCRIMINATOR,
+    RevokeOperatorDelegate = REVOKE_OPERATOR_DELEGATE_DISCRIMINATOR,
+    ClearPaymentWithConversion = CLEAR_PAYMENT_WITH_CONVERSION_DISCRIMINATOR,
+    ChargebackPayment = CHARGEBACK_PAYMENT_DISCRIMINATOR,
+    SweepClosedPayments = SWEEP_CLOSED_PAYMENTS_DISCRIMINATOR,
+    ProposeSettlementWallet = PROPOSE_SETTLEMENT_WALLET_DISCRIMINATOR,
+    CommitSettlementWallet = COMMIT_SETTLEMENT_WALLET_DISCRIMINATOR,
+    InitializeSettlementBatch = INITIALIZE_SETTLEMENT_BATCH_DISCRIMINATOR,
+    ExecuteSettlementBatch = EXECUTE_SETTLEMENT_BATCH_DISCRIMINATOR,
+    CreateOrder = CREATE_ORDER_DISCRIMINATOR,
+    InitializeMerchantProfile = INITIALIZE_MERCHANT_PROFILE_DISCRIMINATOR,
+    UpdateMerchantProfile = UPDATE_MERCHANT_PROFILE_DISCRIMINATOR,
+    ApproveRefund = APPROVE_REFUND_DISCRIMINATOR,
+    ClaimRefund = CLAIM_REFUND_DISCRIMINATOR,
+    InitializeRiskState = INITIALIZE_RISK_STATE_DISCRIMINATOR,
+    InitializeBuyerIndex = INITIALIZE_BUYER_INDEX_DISCRIMINATOR,
+    InitializeMerchantWithConfig = INITIALIZE_MERCHANT_WITH_CONFIG_DISCRIMINATOR,
+    MigrateEscrow = MIGRATE_ESCROW_DISCRIMINATOR,
+    CreateQuote = CREATE_QUOTE_DISCRIMINATOR,
+    InitializeConfigRegistryPage = INITIALIZE_CONFIG_REGISTRY_PAGE_DISCRIMINATOR,
+    CapturePayment = CAPTURE_PAYMENT_DISCRIMINATOR,
+    InitializeOperatorFreezeList = INITIALIZE_OPERATOR_FREEZE_LIST_DISCRIMINATOR,
+    AddToFreezeList = ADD_TO_FREEZE_LIST_DISCRIMINATOR,
+    RemoveFromFreezeList = REMOVE_FROM_FREEZE_LIST_DISCRIMINATOR,
+    PostConversionRate = POST_CONVERSION_RATE_DISCRIMINATOR,
+    RefundPaymentWithConversion = REFUND_PAYMENT_WITH_CONVERSION_DISCRIMINATOR,
+    AddPolicy = ADD_POLICY_DISCRIMINATOR,
+    AddAcceptedCurrency = ADD_ACCEPTED_CURRENCY_DISCRIMINATOR,
+    ClosePaymentNote = CLOSE_PAYMENT_NOTE_DISCRIMINATOR,
+    Heartbeat = HEARTBEAT_DISCRIMINATOR,
+    CloseEscrowAta = CLOSE_ESCROW_ATA_DISCRIMINATOR,
+    MigratePayment = MIGRATE_PAYMENT_DISCRIMINATOR,
+    GetConfigSummary = GET_CONFIG_SUMMARY_DISCRIMINATOR,
+    SetWebhookCommitment = SET_WEBHOOK_COMMITMENT_DISCRIMINATOR,
+    AnnotatePayment = ANNOTATE_PAYMENT_DISCRIMINATOR,
+    DecommissionConfig = DECOMMISSION_CONFIG_DISCRIMINATOR,
+    CloseMerchantOperatorConfig = CLOSE_MERCHANT_OPERATOR_CONFIG_DISCRIMINATOR,
+    UpgradeAccount = UPGRADE_ACCOUNT_DISCRIMINATOR,
+    SetSettlementWalletTimelock = SET_SETTLEMENT_WALLET_TIMELOCK_DISCRIMINATOR,
+    EmitEvent = EMIT_EVENT_DISCRIMINATOR,
+}
+
+impl InstructionDiscriminator {
+    pub const ALL: [Self; 58] = [
+        Self::InitializeMerchant,
+        Self::CreateOperator,
+        Self::InitializeMerchantOperatorConfig,
+        Self::MakePayment,
+        Self::ClearPayment,
+        Self::RefundPayment,
+        Self::UpdateMerchantSettlementWallet,
+        Self::ProposeMerchantAuthority,
+        Self::ProposeOperatorAuthority,
+        Self::ClosePayment,
+        Self::InitializePaymentTree,
+        Self::MakePaymentCompressed,
+        Self::ClearPaymentCompressed,
+        Self::AcceptMerchantAuthority,
+        Self::AcceptOperatorAuthority,
+        Self::InitializeMerchantStats,
+        Self::RefundSettledPayment,
+        Self::InitializeOperatorDelegate,
+        Self::UpdateOperatorDelegate,
+        Self::RevokeOperatorDelegate,
+        Self::ClearPaymentWithConversion,
+        Self::ChargebackPayment,
+        Self::SweepClosedPayments,
+        Self::ProposeSettlementWallet,
+        Self::CommitSettlementWallet,
+        Self::InitializeSettlementBatch,
+        Self::ExecuteSettlementBatch,
+        Self::CreateOrder,
+        Self::InitializeMerchantProfile,
+        Self::UpdateMerchantProfile,
+        Self::ApproveRefund,
+        Self::ClaimRefund,
+        Self::InitializeRiskState,
+        Self::InitializeBuyerIndex,
+        Self::InitializeMerchantWithConfig,
+        Self::MigrateEscrow,
+        Self::CreateQuote,
+        Self::InitializeConfigRegistryPage,
+        Self::CapturePayment,
+        Self::InitializeOperatorFreezeList,
+        Self::AddToFreezeList,
+        Self::RemoveFromFreezeList,
+        Self::PostConversionRate,
+        Self::RefundPaymentWithConversion,
+        Self::AddPolicy,
+        Self::AddAcceptedCurrency,
+        Self::ClosePaymentNote,
+        Self::Heartbeat,
+        Self::CloseEscrowAta,
+        Self::MigratePayment,
+        Self::GetConfigSummary,
+        Self::SetWebhookCommitment,
+        Self::AnnotatePayment,
+        Self::DecommissionConfig,
+        Self::CloseMerchantOperatorConfig,
+        Self::UpgradeAccount,
+        Self::SetSettlementWalletTimelock,
+        Self::EmitEvent,
+    ];
+
+    /// Oldest version the program reads for the instruction. Version 2 dropped the bump from
+    /// `CreateOperator`, `MakePayment` and `MigratePayment`; every other layout is unchanged
+    /// since version 1.
+    pub const fn min_version(self) -> u8 {
+        match self {
+            Self::CreateOperator | Self::MakePayment | Self::MigratePayment => 2,
+            _ => 1,
+        }
+    }
+
+    /// Instruction name, e.g. `"MakePayment"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::InitializeMerchant => "InitializeMerchant",
+            Self::CreateOperator => "CreateOperator",
+            Self::InitializeMerchantOperatorConfig => "InitializeMerchantOperatorConfig",
+            Self::MakePayment => "MakePayment",
+            Self::ClearPayment => "ClearPayment",
+            Self::RefundPayment => "RefundPayment",
+            Self::UpdateMerchantSettlementWallet => "UpdateMerchantSettlementWallet",
+            Self::ProposeMerchantAuthority => "ProposeMerchantAuthority",
+            Self::ProposeOperatorAuthority => "ProposeOperatorAuthority",
+            Self::ClosePayment => "ClosePayment",
+            Self::InitializePaymentTree => "InitializePaymentTree",
+            Self::MakePaymentCompressed => "MakePaymentCompressed",
+            Self::ClearPaymentCompressed => "ClearPaymentCompressed",
+            Self::AcceptMerchantAuthority => "AcceptMerchantAuthority",
+            Self::AcceptOperatorAuthority => "AcceptOperatorAuthority",
+            Self::InitializeMerchantStats => "InitializeMerchantStats",
+            Self::RefundSettledPayment => "RefundSettledPayment",
+            Self::InitializeOperatorDelegate => "InitializeOperatorDelegate",
+            Self::UpdateOperatorDelegate => "UpdateOperatorDelegate",
+            Self::RevokeOperatorDelegate => "RevokeOperatorDelegate",
+            Self::ClearPaymentWithConversion => "ClearPaymentWithConversion",
+            Self::ChargebackPayment => "ChargebackPayment",
+            Self::SweepClosedPayments => "SweepClosedPayments",
+            Self::ProposeSettlementWallet => "ProposeSettlementWallet",
+            Self::CommitSettlementWallet => "CommitSettlementWallet",
+            Self::InitializeSettlementBatch => "InitializeSettlementBatch",
+            Self::ExecuteSettlementBatch => "ExecuteSettlementBatch",
+            Self::CreateOrder => "CreateOrder",
+            Self::InitializeMerchantProfile => "InitializeMerchantProfile",
+            Self::UpdateMerchantProfile => "UpdateMerchantProfile",
+            Self::ApproveRefund => "ApproveRefund",
+            Self::ClaimRefund => "ClaimRefund",
+            Self::InitializeRiskState => "InitializeRiskState",
+            Self::InitializeBuyerIndex => "InitializeBuyerIndex",
+            Self::InitializeMerchantWithConfig => "InitializeMerchantWithConfig",
+            Self::MigrateEscrow => "MigrateEscrow",
+            Self::CreateQuote => "CreateQuote",
+            Self::InitializeConfigRegistryPage => "InitializeConfigRegistryPage",
+            Self::CapturePayment => "CapturePayment",
+            Self::InitializeOperatorFreezeList => "InitializeOperatorFreezeList",
+            Self::AddToFreezeList => "AddToFreezeList",
+            Self::RemoveFromFreezeList => "RemoveFromFreezeList",
+            Self::PostConversionRate => "PostConversionRate",
+            Self::RefundPaymentWithConversion => "RefundPaymentWithConversion",
+            Self::AddPolicy => "AddPolicy",
+            Self::AddAcceptedCurrency => "AddAcceptedCurrency",
+            Self::ClosePaymentNote => "ClosePaymentNote",
+            Self::Heartbeat => "Heartbeat",
+            Self::CloseEscrowAta => "CloseEscrowAta",
+            Self::MigratePayment => "MigratePayment",
+            Self::GetConfigSummary => "GetConfigSummary",
+            Self::SetWebhookCommitment => "SetWebhookCommitment",
+            Self::AnnotatePayment => "AnnotatePayment",
+            Self::DecommissionConfig => "DecommissionConfig",
+            Self::CloseMerchantOperatorConfig => "CloseMerchantOperatorConfig",
+            Self::UpgradeAccount => "UpgradeAccount",
+            Self::SetSettlementWalletTimelock => "SetSettlementWalletTimelock",
+            Self::EmitEvent => "EmitEvent",
+        }
+    }
+}
+
+impl From<InstructionDiscriminator> for u8 {
+    fn from(discriminator: InstructionDiscriminator) -> Self {
+        discriminator as u8
+    }
+}
+
+impl TryFrom<u8> for InstructionDiscriminator {
+    type Error = ParseInstructionError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|discriminator| *discriminator as u8 == value)
+            .ok_or(ParseInstructionError::UnknownDiscriminator(value))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ParseInstructionError {
+    #[error("instruction data is empty")]
+    Empty,
+    #[error("unknown instruction discriminator {0}")]
+    UnknownDiscriminator(u8),
+    #[error("instruction data ends before its version")]
+    MissingVersion,
+    #[error("{} version {version} is not supported", discriminator.name())]
+    UnsupportedVersion {
+        discriminator: InstructionDiscriminator,
+        version: u8,
+    },
+    #[error("invalid instruction arguments: {0}")]
+    InvalidArgs(#[from] borsh::io::Error),
+}
+
+/// Decoded instruction data.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParsedInstruction {
+    InitializeMerchant(InitializeMerchantInstructionArgs),
+    CreateOperator,
+    InitializeMerchantOperatorConfig(InitializeMerchantOperatorConfigInstructionArgs),
+    MakePayment(MakePaymentInstructionArgs),
+    ClearPayment,
+    RefundPayment(RefundPaymentInstructionArgs),
+    UpdateMerchantSettlementWallet,
+    ProposeMerchantAuthority,
+    ProposeOperatorAuthority,
+    ClosePayment,
+    InitializePaymentTree(InitializePaymentTreeInstructionArgs),
+    MakePaymentCompressed(MakePaymentCompressedInstructionArgs),
+    ClearPaymentCompressed(ClearPaymentCompressedInstructionArgs),
+    AcceptMerchantAuthority,
+    AcceptOperatorAuthority,
+    InitializeMerchantStats(InitializeMerchantStatsInstructionArgs),
+    RefundSettledPayment(RefundSettledPaymentInstructionArgs),
+    InitializeOperatorDelegate(InitializeOperatorDelegateInstructionArgs),
+    UpdateOperatorDelegate(UpdateOperatorDelegateInstructionArgs),
+    RevokeOperatorDelegate,
+    ClearPaymentWithConversion(ClearPaymentWithConversionInstructionArgs),
+    ChargebackPayment,
+    SweepClosedPayments(SweepClosedPaymentsInstructionArgs),
+    ProposeSettlementWallet,
+    CommitSettlementWallet,
+    InitializeSettlementBatch(InitializeSettlementBatchInstructionArgs),
+    ExecuteSettlementBatch,
+    CreateOrder(CreateOrderInstructionArgs),
+    InitializeMerchantProfile(InitializeMerchantProfileInstructionArgs),
+    UpdateMerchantProfile(UpdateMerchantProfileInstructionArgs),
+    ApproveRefund,
+    ClaimRefund,
+    InitializeRiskState(InitializeRiskStateInstructionArgs),
+    InitializeBuyerIndex(InitializeBuyerIndexInstructionArgs),
+    InitializeMerchantWithConfig(InitializeMerchantWithConfigInstructionArgs),
+    MigrateEscrow(MigrateEscrowInstructionArgs),
+    CreateQuote(CreateQuoteInstructionArgs),
+    InitializeConfigRegistryPage(InitializeConfigRegistryPageInstructionArgs),
+    CapturePayment(CapturePaymentInstructionArgs),
+    InitializeOperatorFreezeList(InitializeOperatorFreezeListInstructionArgs),
+    AddToFreezeList(AddToFreezeListInstructionArgs),
+    RemoveFromFreezeList(RemoveFromFreezeListInstructionArgs),
+    PostConversionRate(PostConversionRateInstructionArgs),
+    RefundPaymentWithConversion,
+    AddPolicy(AddPolicyInstructionArgs),
+    AddAcceptedCurrency,
+    ClosePaymentNote,
+    Heartbeat,
+    CloseEscrowAta,
+    MigratePayment,
+    GetConfigSummary,
+    SetWebhookCommitment(SetWebhookCommitmentInstructionArgs),
+    AnnotatePayment(AnnotatePaymentInstructionArgs),
+    DecommissionConfig,
+    CloseMerchantOperatorConfig,
+    UpgradeAccount,
+    SetSettlementWalletTimelock(SetSettlementWalletTimelockInstructionArgs),
+    /// Raw event bytes following the discriminator.
+    EmitEvent(Vec<u8>),
+}
+
+impl ParsedInstruction {
+    pub fn discriminator(&self) -> InstructionDiscriminator {
+        match self {
+            Self::InitializeMerchant(_) => InstructionDiscriminator::InitializeMerchant,
+            Self::CreateOperator => InstructionDiscriminator::CreateOperator,
+            Self::InitializeMerchantOperatorConfig(_) => {
+                InstructionDiscriminator::InitializeMerchantOperatorConfig
+            }
+            Self::MakePayment(_) => InstructionDiscriminator::MakePayment,
+            Self::ClearPayment => InstructionDiscriminator::ClearPayment,
+            Self::RefundPayment(_) => InstructionDiscriminator::RefundPayment,
+            Self::UpdateMerchantSettlementWallet => {
+                InstructionDiscriminator::UpdateMerchantSettlementWallet
+            }
+            Self::ProposeMerchantAuthority => InstructionDiscriminator::ProposeMerchantAuthority,
+            Self::ProposeOperatorAuthority => InstructionDiscriminator::ProposeOperatorAuthority,
+            Self::ClosePayment => InstructionDiscriminator::ClosePayment,
+            Self::InitializePaymentTree(_) => InstructionDiscriminator::InitializePaymentTree,
+            Self::MakePaymentCompressed(_) => InstructionDiscriminator::MakePaymentCompressed,
+            Self::ClearPaymentCompressed(_) => InstructionDiscriminator::ClearPaymentCompressed,
+            Self::AcceptMerchantAuthority => InstructionDiscriminator::AcceptMerchantAuthority,
+            Self::AcceptOperatorAuthority => InstructionDiscriminator::AcceptOperatorAuthority,
+            Self::InitializeMerchantStats(_) => InstructionDiscriminator::InitializeMerchantStats,
+            Self::RefundSettledPayment(_) => InstructionDiscriminator::RefundSettledPayment,
+            Self::InitializeOperatorDelegate(_) => {
+                InstructionDiscriminator::InitializeOperatorDelegate
+            }
+            Self::UpdateOperatorDelegate(_) => InstructionDiscriminator::UpdateOperatorDelegate,
+            Self::RevokeOperatorDelegate => InstructionDiscriminator::RevokeOperatorDelegate,
+            Self::ClearPaymentWithConversion(_) => {
+                InstructionDiscriminator::ClearPaymentWithConversion
+            }
+            Self::ChargebackPayment => InstructionDiscriminator::ChargebackPayment,
+            Self::SweepClosedPayments(_) => InstructionDiscriminator::SweepClosedPayments,
+            Self::ProposeSettlementWallet => InstructionDiscriminator::ProposeSettlementWallet,
+            Self::CommitSettlementWallet => InstructionDiscriminator::CommitSettlementWallet,
+            Self::InitializeSettlementBatch(_) => {
+                InstructionDiscriminator::InitializeSettlementBatch
+            }
+            Self::ExecuteSettlementBatch => InstructionDiscriminator::ExecuteSettlementBatch,
+            Self::CreateOrder(_) => InstructionDiscriminator::CreateOrder,
+            Self::InitializeMerchantProfile(_) => {
+                InstructionDiscriminator::InitializeMerchantProfile
+            }
+            Self::UpdateMerchantProfile(_) => InstructionDiscriminator::UpdateMerchantProfile,
+            Self::ApproveRefund => InstructionDiscriminator::ApproveRefund,
+            Self::ClaimRefund => InstructionDiscriminator::ClaimRefund,
+            Self::InitializeRiskState(_) => InstructionDiscriminator::InitializeRiskState,
+            Self::InitializeBuyerIndex(_) => InstructionDiscriminator::InitializeBuyerIndex,
+            Self::InitializeMerchantWithConfig(_) => {
+                InstructionDiscriminator::InitializeMerchantWithConfig
+            }
+            Self::MigrateEscrow(_) => InstructionDiscriminator::MigrateEscrow,
+            Self::CreateQuote(_) => InstructionDiscriminator::CreateQuote,
+            Self::InitializeConfigRegistryPage(_) => {
+                InstructionDiscriminator::InitializeConfigRegistryPage
+            }
+            Self::CapturePayment(_) => InstructionDiscriminator::CapturePayment,
+            Self::InitializeOperatorFreezeList(_) => {
+                InstructionDiscriminator::InitializeOperatorFreezeList
+            }
+            Self::AddToFreezeList(_) => InstructionDiscriminator::AddToFreezeList,
+            Self::RemoveFromFreezeList(_) => InstructionDiscriminator::RemoveFromFreezeList,
+            Self::PostConversionRate(_) => InstructionDiscriminator::PostConversionRate,
+            Self::RefundPaymentWithConversion => {
+                InstructionDiscriminator::RefundPaymentWithConversion
+            }
+            Self::AddPolicy(_) => InstructionDiscriminator::AddPolicy,
+            Self::AddAcceptedCurrency => InstructionDiscriminator::AddAcceptedCurrency,
+            Self::ClosePaymentNote => InstructionDiscriminator::ClosePaymentNote,
+            Self::Heartbeat => InstructionDiscriminator::Heartbeat,
+            Self::CloseEscrowAta => InstructionDiscriminator::CloseEscrowAta,
+            Self::MigratePayment => InstructionDiscriminator::MigratePayment,
+            Self::GetConfigSummary => InstructionDiscriminator::GetConfigSummary,
+            Self::SetWebhookCommitment(_) => InstructionDiscriminator::SetWebhookCommitment,
+            Self::AnnotatePayment(_) => InstructionDiscriminator::AnnotatePayment,
+            Self::DecommissionConfig => InstructionDiscriminator::DecommissionConfig,
+            Self::CloseMerchantOperatorConfig => {
+                InstructionDiscriminator::CloseMerchantOperatorConfig
+            }
+            Self::UpgradeAccount => InstructionDiscriminator::UpgradeAccount,
+            Self::SetSettlementWalletTimelock(_) => {
+                InstructionDiscriminator::SetSettlementWalletTimelock
+            }
+            Self::EmitEvent(_) => InstructionDiscriminator::EmitEvent,
+        }
+    }
+}
+
+/// Splits instruction data into its discriminator and arguments, checking its version if it
+/// has one.
+fn split_instruction_data(
+    data: &[u8],
+) -> Result<(InstructionDiscriminator, &[u8]), ParseInstructionError> {
+    let (first, args) = data.split_first().ok_or(ParseInstructionError::Empty)?;
+    if *first == u8::from(InstructionDiscriminator::EmitEvent)
+        || first & INSTRUCTION_VERSION_FLAG == 0
+    {
+        return Ok((InstructionDiscriminator::try_from(*first)?, args));
+    }
+
+    let discriminator = InstructionDiscriminator::try_from(first & !INSTRUCTION_VERSION_FLAG)
+        .map_err(|_| ParseInstructionError::UnknownDiscriminator(*first))?;
+    let (version, args) = args
+        .split_first()
+        .ok_or(ParseInstructionError::MissingVersion)?;
+    if !(discriminator.min_version()..=INSTRUCTION_VERSION).contains(version) {
+        return Err(ParseInstructionError::UnsupportedVersion {
+            discriminator,
+            version: *version,
+        });
+    }
+    Ok((discriminator, args))
+}
+
+/// Adds the version to instruction data built without one, e.g. by the generated builders.
+/// Data that already has a version, `EmitEvent` data and empty data are returned unchanged.
+pub fn with_version_header(data: &[u8]) -> Vec<u8> {
+    match data.split_first() {
+        Some((first, args))
+            if *first != u8::from(InstructionDiscriminator::EmitEvent)
+                && first & INSTRUCTION_VERSION_FLAG == 0 =>
+        {
+            let mut versioned = Vec::with_capacity(data.len() + 1);
+            versioned.push(first | INSTRUCTION_VERSION_FLAG);
+            versioned.push(INSTRUCTION_VERSION);
+            versioned.extend_from_slice(args);
+            versioned
+        }
+        _ => data.to_vec(),
+    }
+}
+
+/// Decodes commerce program instruction data into its discriminator and arguments, with or
+/// without a version.
+///
+/// Like the program, trailing bytes after the arguments are ignored.
+pub fn parse_instruction(data: &[u8]) -> Result<ParsedInstruction, ParseInstructionError> {
+    let (discriminator, mut args) = split_instruction_data(data)?;
+
+    let parsed = match discriminator {
+        InstructionDiscriminator::InitializeMerchant => ParsedInstruction::InitializeMerchant(
+            InitializeMerchantInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::CreateOperator => ParsedInstruction::CreateOperator,
+        InstructionDiscriminator::InitializeMerchantOperatorConfig => {
+            ParsedInstruction::InitializeMerchantOperatorConfig(
+                InitializeMerchantOperatorConfigInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::MakePayment => {
+            ParsedInstruction::MakePayment(MakePaymentInstructionArgs::deserialize(&mut args)?)
+        }
+        InstructionDiscriminator::ClearPayment => ParsedInstruction::ClearPayment,
+        // RefundPayment data from clients predating `close_escrow` carries no arguments
+        InstructionDiscriminator::RefundPayment if args.is_empty() => {
+            ParsedInstruction::RefundPayment(RefundPaymentInstructionArgs {
+                close_escrow: false,
+            })
+        }
+        InstructionDiscriminator::RefundPayment => {
+            ParsedInstruction::RefundPayment(RefundPaymentInstructionArgs::deserialize(&mut args)?)
+        }
+        InstructionDiscriminator::UpdateMerchantSettlementWallet => {
+            ParsedInstruction::UpdateMerchantSettlementWallet
+        }
+        InstructionDiscriminator::ProposeMerchantAuthority => {
+            ParsedInstruction::ProposeMerchantAuthority
+        }
+        InstructionDiscriminator::ProposeOperatorAuthority => {
+            ParsedInstruction::ProposeOperatorAuthority
+        }
+        InstructionDiscriminator::ClosePayment => ParsedInstruction::ClosePayment,
+        InstructionDiscriminator::InitializePaymentTree => {
+            ParsedInstruction::InitializePaymentTree(
+                InitializePaymentTreeInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::MakePaymentCompressed => {
+            ParsedInstruction::MakePaymentCompressed(
+                MakePaymentCompressedInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::ClearPaymentCompressed => {
+            ParsedInstruction::ClearPaymentCompressed(
+                ClearPaymentCompressedInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::AcceptMerchantAuthority => {
+            ParsedInstruction::AcceptMerchantAuthority
+        }
+        InstructionDiscriminator::AcceptOperatorAuthority => {
+            ParsedInstruction::AcceptOperatorAuthority
+        }
+        InstructionDiscriminator::InitializeMerchantStats => {
+            ParsedInstruction::InitializeMerchantStats(
+                InitializeMerchantStatsInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::RefundSettledPayment => ParsedInstruction::RefundSettledPayment(
+            RefundSettledPaymentInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::InitializeOperatorDelegate => {
+            ParsedInstruction::InitializeOperatorDelegate(
+                InitializeOperatorDelegateInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::UpdateOperatorDelegate => {
+            ParsedInstruction::UpdateOperatorDelegate(
+                UpdateOperatorDelegateInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::RevokeOperatorDelegate => {
+            ParsedInstruction::RevokeOperatorDelegate
+        }
+        InstructionDiscriminator::ClearPaymentWithConversion => {
+            ParsedInstruction::ClearPaymentWithConversion(
+                ClearPaymentWithConversionInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::ChargebackPayment => ParsedInstruction::ChargebackPayment,
+        InstructionDiscriminator::SweepClosedPayments => ParsedInstruction::SweepClosedPayments(
+            SweepClosedPaymentsInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::ProposeSettlementWallet => {
+            ParsedInstruction::ProposeSettlementWallet
+        }
+        InstructionDiscriminator::CommitSettlementWallet => {
+            ParsedInstruction::CommitSettlementWallet
+        }
+        InstructionDiscriminator::InitializeSettlementBatch => {
+            ParsedInstruction::InitializeSettlementBatch(
+                InitializeSettlementBatchInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::ExecuteSettlementBatch => {
+            ParsedInstruction::ExecuteSettlementBatch
+        }
+        InstructionDiscriminator::CreateOrder => {
+            ParsedInstruction::CreateOrder(CreateOrderInstructionArgs::deserialize(&mut args)?)
+        }
+        InstructionDiscriminator::InitializeMerchantProfile => {
+            ParsedInstruction::InitializeMerchantProfile(
+                InitializeMerchantProfileInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::UpdateMerchantProfile => {
+            ParsedInstruction::UpdateMerchantProfile(
+                UpdateMerchantProfileInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::ApproveRefund => ParsedInstruction::ApproveRefund,
+        InstructionDiscriminator::ClaimRefund => ParsedInstruction::ClaimRefund,
+        InstructionDiscriminator::InitializeRiskState => ParsedInstruction::InitializeRiskState(
+            InitializeRiskStateInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::InitializeBuyerIndex => ParsedInstruction::InitializeBuyerIndex(
+            InitializeBuyerIndexInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::InitializeMerchantWithConfig => {
+            ParsedInstruction::InitializeMerchantWithConfig(
+                InitializeMerchantWithConfigInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::MigrateEscrow => {
+            ParsedInstruction::MigrateEscrow(MigrateEscrowInstructionArgs::deserialize(&mut args)?)
+        }
+        InstructionDiscriminator::CreateQuote => {
+            ParsedInstruction::CreateQuote(CreateQuoteInstructionArgs::deserialize(&mut args)?)
+        }
+        InstructionDiscriminator::InitializeConfigRegistryPage => {
+            ParsedInstruction::InitializeConfigRegistryPage(
+                InitializeConfigRegistryPageInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::CapturePayment => ParsedInstruction::CapturePayment(
+            CapturePaymentInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::InitializeOperatorFreezeList => {
+            ParsedInstruction::InitializeOperatorFreezeList(
+                InitializeOperatorFreezeListInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::AddToFreezeList => ParsedInstruction::AddToFreezeList(
+            AddToFreezeListInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::RemoveFromFreezeList => ParsedInstruction::RemoveFromFreezeList(
+            RemoveFromFreezeListInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::PostConversionRate => ParsedInstruction::PostConversionRate(
+            PostConversionRateInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::RefundPaymentWithConversion => {
+            ParsedInstruction::RefundPaymentWithConversion
+        }
+        InstructionDiscriminator::AddPolicy => {
+            ParsedInstruction::AddPolicy(AddPolicyInstructionArgs::deserialize(&mut args)?)
+        }
+        InstructionDiscriminator::AddAcceptedCurrency => ParsedInstruction::AddAcceptedCurrency,
+        InstructionDiscriminator::ClosePaymentNote => ParsedInstruction::ClosePaymentNote,
+        InstructionDiscriminator::Heartbeat => ParsedInstruction::Heartbeat,
+        InstructionDiscriminator::CloseEscrowAta => ParsedInstruction::CloseEscrowAta,
+        InstructionDiscriminator::MigratePayment => ParsedInstruction::MigratePayment,
+        InstructionDiscriminator::GetConfigSummary => ParsedInstruction::GetConfigSummary,
+        InstructionDiscriminator::SetWebhookCommitment => ParsedInstruction::SetWebhookCommitment(
+            SetWebhookCommitmentInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::AnnotatePayment => ParsedInstruction::AnnotatePayment(
+            AnnotatePaymentInstructionArgs::deserialize(&mut args)?,
+        ),
+        InstructionDiscriminator::DecommissionConfig => ParsedInstruction::DecommissionConfig,
+        InstructionDiscriminator::CloseMerchantOperatorConfig => {
+            ParsedInstruction::CloseMerchantOperatorConfig
+        }
+        InstructionDiscriminator::UpgradeAccount => ParsedInstruction::UpgradeAccount,
+        InstructionDiscriminator::SetSettlementWalletTimelock => {
+            ParsedInstruction::SetSettlementWalletTimelock(
+                SetSettlementWalletTimelockInstructionArgs::deserialize(&mut args)?,
+            )
+        }
+        InstructionDiscriminator::EmitEvent => ParsedInstruction::EmitEvent(args.to_vec()),
+    };
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_pubkey::Pubkey;
+
+    #[test]
+    fn test_instruction_discriminator_roundtrip() {
+        for discriminator in InstructionDiscriminator::ALL {
+            assert_eq!(
+                InstructionDiscriminator::try_from(u8::from(discriminator)).unwrap(),
+                discriminator
+            );
+        }
+        assert_eq!(u8::from(InstructionDiscriminator::ClearPayment), 4);
+        assert_eq!(
+            InstructionDiscriminator::ClearPayment.name(),
+            "ClearPayment"
+        );
+        assert!(matches!(
+            InstructionDiscriminator::try_from(57),
+            Err(ParseInstructionError::UnknownDiscriminator(57))
+        ));
+    }
+
+    #[test]
+    fn test_parse_make_payment() {
+        let p = Pubkey::new_unique;
+        let instruction = MakePaymentBuilder::new()
+            .payer(p())
+            .payment(p())
+            .operator_authority(p())
+            .buyer(p())
+            .operator(p())
+            .merchant(p())
+            .merchant_operator_config(p())
+            .mint(p())
+            .buyer_ata(p())
+            .escrow_authority(p())
+            .merchant_escrow_ata(p())
+            .merchant_settlement_ata(p())
+            .settlement_wallet(p())
+            .order_id(7)
+            .amount(1_000_000)
+            .idempotency_key([9; 16])
+            .instruction();
+
+        let parsed = parse_instruction(&instruction.data).unwrap();
+        assert_eq!(
+            parsed.discriminator(),
+            InstructionDiscriminator::MakePayment
+        );
+        assert_eq!(
+            parsed,
+            ParsedInstruction::MakePayment(MakePaymentInstructionArgs {
+                order_id: 7,
+                amount: 1_000_000,
+                idempotency_key: [9; 16],
+                fee_override: None,
+                authorize: false,
+                denylist_proof: None,
+                note: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_refund_payment() {
+        let refund_payment = u8::from(InstructionDiscriminator::RefundPayment);
+        for (data, close_escrow) in [
+            (vec![refund_payment], false),
+            (vec![refund_payment, 0], false),
+            (vec![refund_payment, 1], true),
+        ] {
+            assert_eq!(
+                parse_instruction(&data).unwrap(),
+                ParsedInstruction::RefundPayment(RefundPaymentInstructionArgs { close_escrow })
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_instruction_without_args() {
+        assert_eq!(
+            parse_instruction(&[InstructionDiscriminator::ClosePayment.into()]).unwrap(),
+            ParsedInstruction::ClosePayment
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_errors() {
+        assert!(matches!(
+            parse_instruction(&[]),
+            Err(ParseInstructionError::Empty)
+        ));
+        assert!(matches!(
+            parse_instruction(&[200]),
+            Err(ParseInstructionError::UnknownDiscriminator(200))
+        ));
+        assert!(matches!(
+            parse_instruction(&[InstructionDiscriminator::MakePayment.into(), 1, 2]),
+            Err(ParseInstructionError::InvalidArgs(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_versioned_instruction() {
+        let refund_payment = u8::from(InstructionDiscriminator::RefundPayment);
+        let data = with_version_header(&[refund_payment, 1]);
+        assert_eq!(
+            data,
+            [
+                refund_payment | INSTRUCTION_VERSION_FLAG,
+                INSTRUCTION_VERSION,
+                1
+            ]
+        );
+        assert_eq!(
+            parse_instruction(&data).unwrap(),
+            ParsedInstruction::RefundPayment(RefundPaymentInstructionArgs { close_escrow: true })
+        );
+        assert_eq!(with_version_header(&data), data);
+        assert_eq!(
+            parse_instruction(&[refund_payment | INSTRUCTION_VERSION_FLAG, 1, 0]).unwrap(),
+            ParsedInstruction::RefundPayment(RefundPaymentInstructionArgs {
+                close_escrow: false
+            })
+        );
+
+        let emit_event = [u8::from(InstructionDiscriminator::EmitEvent), 1, 2];
+        assert_eq!(with_version_header(&emit_event), emit_event);
+        assert_eq!(
+            parse_instruction(&emit_event).unwrap(),
+            ParsedInstruction::EmitEvent(vec![1, 2])
+        );
+    }
+
+    #[test]
+    fn test_parse_versioned_instruction_errors() {
+        let close_payment = u8::from(InstructionDiscriminator::ClosePayment);
+        assert!(matches!(
+            parse_instruction(&[close_payment | INSTRUCTION_VERSION_FLAG]),
+            Err(ParseInstructionError::MissingVersion)
+        ));
+        assert!(matches!(
+            parse_instruction(&[close_payment | INSTRUCTION_VERSION_FLAG, 3]),
+            Err(ParseInstructionError::UnsupportedVersion {
+                discriminator: InstructionDiscriminator::ClosePayment,
+                version: 3,
+            })
+        ));
+        let create_operator = u8::from(InstructionDiscriminator::CreateOperator);
+        assert!(matches!(
+            parse_instruction(&[create_operator | INSTRUCTION_VERSION_FLAG, 1, 255]),
+            Err(ParseInstructionError::UnsupportedVersion {
+                discriminator: InstructionDiscriminator::CreateOperator,
+                version: 1,
+            })
+        ));
+        assert!(matches!(
+            parse_instruction(&[99 | INSTRUCTION_VERSION_FLAG, INSTRUCTION_VERSION]),
+            Err(ParseInstructionError::UnknownDiscriminator(227))
+        ));
+    }
+}
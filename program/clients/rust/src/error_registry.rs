@@ -0,0 +1,113 @@
+//! Codes, names and messages of the program's errors.
+//!
+//! Failed transactions only report `custom program error: 0x..`.
+//! [`CommerceProgramError::from_code`] turns the code back into the error, and
+//! [`error_registry`] lists every error for support tools. The same list is checked in as
+//! `idl/commerce_program_errors.json` for consumers outside Rust.
+
+use num_traits::FromPrimitive;
+use solana_program_error::ProgramError;
+
+use crate::generated::errors::CommerceProgramError;
+
+/// One entry of the error registry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorInfo {
+    pub code: u32,
+    pub name: String,
+    pub message: String,
+}
+
+impl CommerceProgramError {
+    /// Code carried by `ProgramError::Custom` when this error is returned.
+    pub fn code(&self) -> u32 {
+        self.clone() as u32
+    }
+
+    /// Variant name, e.g. `InvalidMint`.
+    pub fn name(&self) -> String {
+        format!("{self:?}")
+    }
+
+    /// Human-readable description, the same as the `Display` text.
+    pub fn message(&self) -> String {
+        self.to_string()
+    }
+
+    /// The error returned as `ProgramError::Custom(code)`, if `code` is one of ours.
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::from_u32(code)
+    }
+
+    /// The error behind a `ProgramError`, if it is one of ours.
+    pub fn from_program_error(error: &ProgramError) -> Option<Self> {
+        match error {
+            ProgramError::Custom(code) => Self::from_code(*code),
+            _ => None,
+        }
+    }
+
+    pub fn info(&self) -> ErrorInfo {
+        ErrorInfo {
+            code: self.code(),
+            name: self.name(),
+            message: self.message(),
+        }
+    }
+}
+
+/// Every program error in code order.
+pub fn error_registry() -> Vec<ErrorInfo> {
+    (0..)
+        .map_while(CommerceProgramError::from_code)
+        .map(|error| error.info())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(
+            CommerceProgramError::from_code(0),
+            Some(CommerceProgramError::InvalidMint)
+        );
+        assert_eq!(CommerceProgramError::from_code(u32::MAX), None);
+
+        let error = CommerceProgramError::RiskStateRequired;
+        assert_eq!(CommerceProgramError::from_code(error.code()), Some(error));
+    }
+
+    #[test]
+    fn test_from_program_error() {
+        let error = CommerceProgramError::InvalidRefundDestination;
+        assert_eq!(
+            CommerceProgramError::from_program_error(&error.clone().into()),
+            Some(error)
+        );
+        assert_eq!(
+            CommerceProgramError::from_program_error(&ProgramError::InvalidArgument),
+            None
+        );
+    }
+
+    #[test]
+    fn test_error_registry() {
+        let registry = error_registry();
+        assert!(registry
+            .iter()
+            .enumerate()
+            .all(|(index, info)| info.code == index as u32));
+        assert_eq!(
+            registry[0],
+            ErrorInfo {
+                code: 0,
+                name: "InvalidMint".to_string(),
+                message: "Incorrect mint provided".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,407 @@
+//! Escrow accounting invariant.
+//!
+//! A config's escrow ATA for a mint, owned by the escrow authority PDA of (config, mint), must
+//! hold at least the amounts of the config's payments in that mint still in escrow, `Paid`,
+//! `RefundApproved` or `Authorized`, plus the merchant amounts cleared into its settlement
+//! batch and not yet paid out. [`audit_escrow`] compares the two per mint. A shortfall means
+//! funds left escrow without their payments; it lists the open payments the balance no longer
+//! covers, oldest first. A surplus is not necessarily an error: anyone can transfer into an
+//! escrow ATA, and compressed payments and funds still in a legacy escrow are not counted.
+//!
+//! Payment accounts do not store their config or mint, so callers pass each payment with the
+//! mint its PDA was derived from, e.g. from `PaymentCreated` events.
+
+use std::collections::BTreeMap;
+
+use solana_pubkey::Pubkey;
+
+use crate::generated::{accounts::Payment, types::Status};
+
+/// Offset of `amount` in an SPL Token or Token-2022 token account.
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Whether a payment with `status` still has its amount in escrow.
+pub fn holds_escrow(status: Status) -> bool {
+    matches!(
+        status,
+        Status::Paid | Status::RefundApproved | Status::Authorized
+    )
+}
+
+/// Amount held by an SPL Token or Token-2022 token account, or `None` if `data` is too short.
+pub fn token_account_amount(data: &[u8]) -> Option<u64> {
+    let amount = data.get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)?;
+    Some(u64::from_le_bytes(amount.try_into().ok()?))
+}
+
+/// A payment of the audited config, with the mint its PDA was derived from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditedPayment {
+    pub payment: Pubkey,
+    pub mint: Pubkey,
+    pub account: Payment,
+}
+
+/// Escrow state of one mint of the audited config.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EscrowBalance {
+    pub mint: Pubkey,
+    /// Balance of the config's escrow ATA, 0 if it does not exist.
+    pub balance: u64,
+    /// `pending_amount` of the config's settlement batch, 0 without one.
+    pub batch_pending: u64,
+}
+
+/// Audit of one mint's escrow.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MintEscrowAudit {
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub batch_pending: u64,
+    /// Payments in escrow, oldest first.
+    pub open_payments: Vec<Pubkey>,
+    /// Sum of the open payments' amounts.
+    pub open_amount: u64,
+    /// Open payments not covered by the balance when it is allocated oldest first.
+    pub unbacked_payments: Vec<Pubkey>,
+}
+
+impl MintEscrowAudit {
+    /// What the escrow ATA should hold: the open payments plus the batch's pending amount.
+    pub fn expected(&self) -> u128 {
+        u128::from(self.open_amount) + u128::from(self.batch_pending)
+    }
+
+    /// Balance less what it should hold, negative on a shortfall.
+    pub fn discrepancy(&self) -> i128 {
+        i128::from(self.balance) - self.expected() as i128
+    }
+
+    pub fn is_short(&self) -> bool {
+        self.discrepancy() < 0
+    }
+}
+
+/// Audit of a config's escrow, per mint.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EscrowAudit {
+    pub merchant_operator_config: Pubkey,
+    /// Sorted by mint.
+    pub mints: Vec<MintEscrowAudit>,
+}
+
+impl EscrowAudit {
+    /// Mints whose balance differs from what their escrow should hold.
+    pub fn discrepancies(&self) -> impl Iterator<Item = &MintEscrowAudit> {
+        self.mints.iter().filter(|mint| mint.discrepancy() != 0)
+    }
+
+    /// Mints whose escrow holds less than its open payments and pending batch.
+    pub fn shortfalls(&self) -> impl Iterator<Item = &MintEscrowAudit> {
+        self.mints.iter().filter(|mint| mint.is_short())
+    }
+}
+
+/// Audits `merchant_operator_config`'s escrow for every mint in `payments` or `escrows`.
+/// A mint with payments but no entry in `escrows` has an empty escrow.
+pub fn audit_escrow(
+    merchant_operator_config: &Pubkey,
+    payments: &[AuditedPayment],
+    escrows: &[EscrowBalance],
+) -> EscrowAudit {
+    let mut mints: BTreeMap<Pubkey, (EscrowBalance, Vec<&AuditedPayment>)> = escrows
+        .iter()
+        .map(|escrow| (escrow.mint, (*escrow, Vec::new())))
+        .collect();
+    for payment in payments
+        .iter()
+        .filter(|payment| holds_escrow(payment.account.status))
+    {
+        mints
+            .entry(payment.mint)
+            .or_insert_with(|| {
+                (
+                    EscrowBalance {
+                        mint: payment.mint,
+                        ..EscrowBalance::default()
+                    },
+                    Vec::new(),
+                )
+            })
+            .1
+            .push(payment);
+    }
+
+    let mints = mints
+        .into_values()
+        .map(|(escrow, mut open)| {
+            open.sort_by_key(|payment| (payment.account.created_at, payment.account.order_id));
+            open.dedup_by_key(|payment| payment.payment);
+
+            let mut remaining = escrow.balance;
+            let mut unbacked_payments = Vec::new();
+            for payment in &open {
+                match remaining.checked_sub(payment.account.amount) {
+                    Some(left) => remaining = left,
+                    None => {
+                        remaining = 0;
+                        unbacked_payments.push(payment.payment);
+                    }
+                }
+            }
+
+            MintEscrowAudit {
+                mint: escrow.mint,
+                balance: escrow.balance,
+                batch_pending: escrow.batch_pending,
+                open_amount: open.iter().fold(0u64, |sum, payment| {
+                    sum.saturating_add(payment.account.amount)
+                }),
+                open_payments: open.iter().map(|payment| payment.payment).collect(),
+                unbacked_payments,
+            }
+        })
+        .collect();
+
+    EscrowAudit {
+        merchant_operator_config: *merchant_operator_config,
+        mints,
+    }
+}
+
+/// Fetches `payments`, each with the mint its PDA was derived from, along with the escrow ATA
+/// and settlement batch of every mint the config accepts or a payment uses, and audits them.
+/// Closed payments are skipped.
+#[cfg(feature = "fetch")]
+pub fn fetch_audit_escrow(
+    rpc: &solana_client::rpc_client::RpcClient,
+    merchant_operator_config: &Pubkey,
+    payments: &[(Pubkey, Pubkey)],
+) -> Result<EscrowAudit, std::io::Error> {
+    use crate::{
+        account_cache::get_multiple_accounts,
+        checkout::get_associated_token_address,
+        generated::accounts::SettlementBatch,
+        pdas::{find_escrow_authority_pda, find_settlement_batch_pda},
+        preview::decode_merchant_operator_config,
+    };
+
+    let to_io = |e: solana_client::client_error::ClientError| {
+        std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+    };
+
+    let config_account = rpc.get_account(merchant_operator_config).map_err(to_io)?;
+    let (_, _, mut mints) = decode_merchant_operator_config(&config_account.data)?;
+    mints.extend(payments.iter().map(|(_, mint)| *mint));
+    mints.sort_unstable();
+    mints.dedup();
+
+    let keys: Vec<Pubkey> = payments.iter().map(|(payment, _)| *payment).collect();
+    let mut audited = Vec::with_capacity(payments.len());
+    for ((payment, mint), account) in payments.iter().zip(get_multiple_accounts(rpc, &keys)?) {
+        if let Some(account) = account {
+            audited.push(AuditedPayment {
+                payment: *payment,
+                mint: *mint,
+                account: Payment::from_bytes(&account.data)?,
+            });
+        }
+    }
+
+    // The escrow ATA is derived with the token program that owns the mint
+    let mint_accounts = get_multiple_accounts(rpc, &mints)?;
+    let mut escrow_keys = Vec::with_capacity(2 * mints.len());
+    for (mint, account) in mints.iter().zip(&mint_accounts) {
+        let token_program = account
+            .as_ref()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, mint.to_string()))?
+            .owner;
+        let escrow_authority = find_escrow_authority_pda(merchant_operator_config, mint).0;
+        escrow_keys.push(get_associated_token_address(
+            &escrow_authority,
+            mint,
+            &token_program,
+        ));
+        escrow_keys.push(find_settlement_batch_pda(merchant_operator_config, mint).0);
+    }
+
+    let escrow_accounts = get_multiple_accounts(rpc, &escrow_keys)?;
+    let escrows = mints
+        .iter()
+        .zip(escrow_accounts.chunks(2))
+        .map(|(mint, accounts)| {
+            let balance = accounts[0]
+                .as_ref()
+                .and_then(|account| token_account_amount(&account.data))
+                .unwrap_or(0);
+            let batch_pending = accounts[1]
+                .as_ref()
+                .map(|account| SettlementBatch::from_bytes(&account.data))
+                .transpose()?
+                .map_or(0, |batch| batch.pending_amount);
+            Ok(EscrowBalance {
+                mint: *mint,
+                balance,
+                batch_pending,
+            })
+        })
+        .collect::<Result<Vec<_>, std::io::Error>>()?;
+
+    Ok(audit_escrow(merchant_operator_config, &audited, &escrows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated::types::{FeeOverride, FeeType};
+
+    const CREATED_AT: i64 = 1_700_000_000;
+
+    fn payment(mint: Pubkey, order_id: u32, amount: u64, status: Status) -> AuditedPayment {
+        AuditedPayment {
+            payment: Pubkey::new_unique(),
+            mint,
+            account: Payment {
+                discriminator: 3,
+                order_id,
+                amount,
+                created_at: CREATED_AT + i64::from(order_id),
+                status,
+                bump: 255,
+                idempotency_key: [0; 16],
+                has_fee_override: false,
+                fee_override: FeeOverride {
+                    fee: 0,
+                    fee_type: FeeType::Bps,
+                },
+                references: [Pubkey::default(); 2],
+                rent_payer: Pubkey::default(),
+                has_order: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_audit_escrow_balanced() {
+        let config = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let payments = [
+            payment(mint, 1, 1_000, Status::Paid),
+            payment(mint, 2, 500, Status::RefundApproved),
+            payment(mint, 3, 250, Status::Authorized),
+            // No longer in escrow
+            payment(mint, 4, 800, Status::Cleared),
+            payment(mint, 5, 700, Status::Refunded),
+        ];
+        let escrows = [EscrowBalance {
+            mint,
+            balance: 1_750 + 300,
+            batch_pending: 300,
+        }];
+
+        let audit = audit_escrow(&config, &payments, &escrows);
+
+        assert_eq!(audit.merchant_operator_config, config);
+        assert_eq!(
+            audit.mints,
+            vec![MintEscrowAudit {
+                mint,
+                balance: 2_050,
+                batch_pending: 300,
+                open_payments: vec![
+                    payments[0].payment,
+                    payments[1].payment,
+                    payments[2].payment
+                ],
+                open_amount: 1_750,
+                unbacked_payments: vec![],
+            }]
+        );
+        assert_eq!(audit.mints[0].expected(), 2_050);
+        assert_eq!(audit.discrepancies().count(), 0);
+    }
+
+    #[test]
+    fn test_audit_escrow_shortfall() {
+        let config = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        // Passed newest first
+        let payments = [
+            payment(mint, 3, 300, Status::Paid),
+            payment(mint, 2, 200, Status::Paid),
+            payment(mint, 1, 100, Status::Paid),
+        ];
+        let escrows = [EscrowBalance {
+            mint,
+            balance: 350,
+            batch_pending: 0,
+        }];
+
+        let audit = audit_escrow(&config, &payments, &escrows);
+
+        let short = audit.shortfalls().collect::<Vec<_>>();
+        assert_eq!(short.len(), 1);
+        assert_eq!(short[0].discrepancy(), -250);
+        assert_eq!(
+            short[0].open_payments,
+            vec![
+                payments[2].payment,
+                payments[1].payment,
+                payments[0].payment
+            ]
+        );
+        // 100 and 200 are covered, leaving 50 for the newest
+        assert_eq!(short[0].unbacked_payments, vec![payments[0].payment]);
+    }
+
+    #[test]
+    fn test_audit_escrow_per_mint() {
+        let config = Pubkey::new_unique();
+        let usdc = Pubkey::new_unique();
+        let usdt = Pubkey::new_unique();
+        let idle = Pubkey::new_unique();
+        let payments = [
+            payment(usdc, 1, 1_000, Status::Paid),
+            payment(usdt, 2, 2_000, Status::Paid),
+        ];
+        // No USDT escrow ATA, and a stray deposit into an escrow without payments
+        let escrows = [
+            EscrowBalance {
+                mint: usdc,
+                balance: 1_000,
+                batch_pending: 0,
+            },
+            EscrowBalance {
+                mint: idle,
+                balance: 5,
+                batch_pending: 0,
+            },
+        ];
+
+        let audit = audit_escrow(&config, &payments, &escrows);
+
+        assert_eq!(audit.mints.len(), 3);
+        let discrepancies: Vec<(Pubkey, i128)> = audit
+            .discrepancies()
+            .map(|mint| (mint.mint, mint.discrepancy()))
+            .collect();
+        let mut expected = vec![(usdt, -2_000), (idle, 5)];
+        expected.sort();
+        assert_eq!(discrepancies, expected);
+        assert_eq!(
+            audit
+                .shortfalls()
+                .flat_map(|mint| mint.unbacked_payments.clone())
+                .collect::<Vec<_>>(),
+            vec![payments[1].payment]
+        );
+    }
+
+    #[test]
+    fn test_token_account_amount() {
+        let mut data = vec![0u8; 165];
+        data[64..72].copy_from_slice(&1_234u64.to_le_bytes());
+        assert_eq!(token_account_amount(&data), Some(1_234));
+        assert_eq!(token_account_amount(&data[..71]), None);
+    }
+}
@@ -0,0 +1,428 @@
+//! Live payment events for backends that do not run the indexer.
+//!
+//! With the `fetch` feature, [`subscribe_payment_events`] subscribes to the logs of
+//! transactions that mention the commerce program, or one config, over the RPC websocket.
+//! Logs do not carry the `EmitEvent` self-CPI data, so each successful transaction is fetched
+//! with `getTransaction` and decoded with [`crate::events::payment_event_records`]. The
+//! subscription reconnects with backoff when the websocket drops and backfills the
+//! transactions it missed with `getSignaturesForAddress`; [`SlotDedupe`] drops the events the
+//! backfill and the new subscription both deliver.
+//!
+//! ```ignore
+//! let rpc = Arc::new(RpcClient::new(rpc_url));
+//! let filter = PaymentEventFilter::for_config(config).kinds([PaymentEventKind::Created]);
+//! let mut events = subscribe_payment_events(ws_url, rpc, filter, SubscribeConfig::default());
+//! while let Some(event) = events.next().await {
+//!     println!("order {} paid {}", event.order_id, event.amount);
+//! }
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+
+use solana_pubkey::Pubkey;
+
+use crate::events::{PaymentEventKind, PaymentEventRecord};
+
+/// Which payment events a subscription yields. Unset fields match every event.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PaymentEventFilter {
+    pub merchant_operator_config: Option<Pubkey>,
+    pub merchant: Option<Pubkey>,
+    pub operator: Option<Pubkey>,
+    pub buyer: Option<Pubkey>,
+    pub mint: Option<Pubkey>,
+    /// Event kinds to yield, every kind if empty.
+    pub kinds: Vec<PaymentEventKind>,
+}
+
+impl PaymentEventFilter {
+    /// Events of one merchant operator config, which is also the address subscribed to.
+    pub fn for_config(merchant_operator_config: Pubkey) -> Self {
+        Self {
+            merchant_operator_config: Some(merchant_operator_config),
+            ..Self::default()
+        }
+    }
+
+    pub fn kinds(mut self, kinds: impl IntoIterator<Item = PaymentEventKind>) -> Self {
+        self.kinds = kinds.into_iter().collect();
+        self
+    }
+
+    pub fn matches(&self, record: &PaymentEventRecord) -> bool {
+        let field = |filter: Option<Pubkey>, value: Pubkey| filter.is_none_or(|key| key == value);
+
+        field(
+            self.merchant_operator_config,
+            record.merchant_operator_config,
+        ) && field(self.merchant, record.merchant)
+            && field(self.operator, record.operator)
+            && field(self.buyer, record.buyer)
+            && field(self.mint, record.mint)
+            && (self.kinds.is_empty() || self.kinds.contains(&record.kind))
+    }
+
+    /// The address whose transactions are subscribed to: the config if set, since every
+    /// instruction that emits payment events takes it, and the program otherwise.
+    pub fn mentioned_address(&self) -> Pubkey {
+        self.merchant_operator_config
+            .unwrap_or(crate::COMMERCE_PROGRAM_ID)
+    }
+}
+
+/// Transactions already yielded, kept for the last `window` slots.
+///
+/// A transaction is identified by its slot and signature. Slots older than `window` slots
+/// before the newest one are forgotten and count as seen, so a backfill that reaches past
+/// the window does not yield them again.
+#[derive(Clone, Debug)]
+pub struct SlotDedupe {
+    window: u64,
+    seen: BTreeMap<u64, HashSet<String>>,
+}
+
+impl SlotDedupe {
+    pub fn new(window: u64) -> Self {
+        Self {
+            window,
+            seen: BTreeMap::new(),
+        }
+    }
+
+    /// Records the transaction, returning `false` if it was already seen or is older than
+    /// the window.
+    pub fn insert(&mut self, slot: u64, signature: &str) -> bool {
+        if slot < self.floor() {
+            return false;
+        }
+        if !self
+            .seen
+            .entry(slot)
+            .or_default()
+            .insert(signature.to_string())
+        {
+            return false;
+        }
+
+        let floor = self.floor();
+        self.seen = self.seen.split_off(&floor);
+        true
+    }
+
+    /// The oldest slot still remembered.
+    fn floor(&self) -> u64 {
+        self.seen
+            .last_key_value()
+            .map_or(0, |(newest, _)| newest.saturating_sub(self.window))
+    }
+}
+
+/// Settings of [`subscribe_payment_events`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubscribeConfig {
+    /// Commitment of the subscription and of the fetched transactions. `getTransaction`
+    /// does not serve processed transactions, so processed is fetched as confirmed.
+    pub commitment: solana_commitment_config::CommitmentConfig,
+    /// Wait before the first reconnect, doubled after every failed one.
+    pub reconnect_delay: std::time::Duration,
+    pub max_reconnect_delay: std::time::Duration,
+    /// Times a notified transaction is fetched before it is skipped, as nodes may not
+    /// serve it yet.
+    pub fetch_attempts: u32,
+    pub fetch_interval: std::time::Duration,
+    /// Slots a yielded transaction is remembered for, see [`SlotDedupe`].
+    pub dedupe_window: u64,
+    /// Signatures backfilled at most after a reconnect.
+    pub backfill_limit: usize,
+    /// Events buffered before the subscription waits for the stream to be read.
+    pub buffer: usize,
+}
+
+impl Default for SubscribeConfig {
+    fn default() -> Self {
+        Self {
+            commitment: solana_commitment_config::CommitmentConfig::confirmed(),
+            reconnect_delay: std::time::Duration::from_millis(500),
+            max_reconnect_delay: std::time::Duration::from_secs(30),
+            fetch_attempts: 5,
+            fetch_interval: std::time::Duration::from_millis(400),
+            dedupe_window: 1_000,
+            backfill_limit: 1_000,
+            buffer: 256,
+        }
+    }
+}
+
+/// Payment events matching `filter`, as their transactions reach `config.commitment`.
+///
+/// The subscription runs on a spawned task, so it must be called within a Tokio runtime,
+/// and stops when the stream is dropped. Websocket and RPC failures are retried and never
+/// end the stream; events of transactions that could not be fetched are skipped.
+#[cfg(feature = "fetch")]
+pub fn subscribe_payment_events(
+    ws_url: impl Into<String>,
+    rpc: std::sync::Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    filter: PaymentEventFilter,
+    config: SubscribeConfig,
+) -> impl tokio_stream::Stream<Item = PaymentEventRecord> {
+    let (sender, receiver) = tokio::sync::mpsc::channel(config.buffer.max(1));
+    tokio::spawn(run_subscription(ws_url.into(), rpc, filter, config, sender));
+    tokio_stream::wrappers::ReceiverStream::new(receiver)
+}
+
+#[cfg(feature = "fetch")]
+async fn run_subscription(
+    ws_url: String,
+    rpc: std::sync::Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    filter: PaymentEventFilter,
+    config: SubscribeConfig,
+    sender: tokio::sync::mpsc::Sender<PaymentEventRecord>,
+) {
+    use solana_client::{
+        nonblocking::pubsub_client::PubsubClient,
+        rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+    };
+    use tokio::time::sleep;
+    use tokio_stream::StreamExt;
+
+    let mut subscription = Subscription {
+        rpc,
+        filter,
+        dedupe: SlotDedupe::new(config.dedupe_window),
+        config,
+        sender,
+        last_signature: None,
+    };
+    let address = subscription.filter.mentioned_address();
+    let mut delay = subscription.config.reconnect_delay;
+
+    while !subscription.sender.is_closed() {
+        if let Ok(pubsub) = PubsubClient::new(&ws_url).await {
+            if let Ok((mut notifications, unsubscribe)) = pubsub
+                .logs_subscribe(
+                    RpcTransactionLogsFilter::Mentions(vec![address.to_string()]),
+                    RpcTransactionLogsConfig {
+                        commitment: Some(subscription.config.commitment),
+                    },
+                )
+                .await
+            {
+                delay = subscription.config.reconnect_delay;
+                // Subscribed first, so nothing lands between the backfill and the stream
+                if !subscription.backfill(&address).await {
+                    return;
+                }
+                while let Some(notification) = notifications.next().await {
+                    if notification.value.err.is_some() {
+                        continue;
+                    }
+                    if !subscription
+                        .deliver(notification.context.slot, &notification.value.signature)
+                        .await
+                    {
+                        unsubscribe().await;
+                        return;
+                    }
+                }
+                unsubscribe().await;
+            }
+            let _ = pubsub.shutdown().await;
+        }
+
+        sleep(delay).await;
+        delay = (delay * 2).min(subscription.config.max_reconnect_delay);
+    }
+}
+
+#[cfg(feature = "fetch")]
+struct Subscription {
+    rpc: std::sync::Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    filter: PaymentEventFilter,
+    config: SubscribeConfig,
+    sender: tokio::sync::mpsc::Sender<PaymentEventRecord>,
+    dedupe: SlotDedupe,
+    /// Newest transaction delivered, where a backfill after a reconnect starts.
+    last_signature: Option<solana_signature::Signature>,
+}
+
+#[cfg(feature = "fetch")]
+impl Subscription {
+    /// Delivers the successful transactions since the last delivered one, oldest first.
+    /// Returns `false` once the stream is dropped.
+    async fn backfill(&mut self, address: &Pubkey) -> bool {
+        use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+
+        let Some(until) = self.last_signature else {
+            return true;
+        };
+        let Ok(signatures) = self
+            .rpc
+            .get_signatures_for_address_with_config(
+                address,
+                GetConfirmedSignaturesForAddress2Config {
+                    until: Some(until),
+                    limit: Some(self.config.backfill_limit),
+                    commitment: Some(self.fetch_commitment()),
+                    ..GetConfirmedSignaturesForAddress2Config::default()
+                },
+            )
+            .await
+        else {
+            return true;
+        };
+
+        for status in signatures
+            .into_iter()
+            .rev()
+            .filter(|status| status.err.is_none())
+        {
+            if !self.deliver(status.slot, &status.signature).await {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Fetches the transaction and sends its matching events. Returns `false` once the
+    /// stream is dropped.
+    async fn deliver(&mut self, slot: u64, signature: &str) -> bool {
+        use std::str::FromStr;
+
+        let Ok(parsed) = solana_signature::Signature::from_str(signature) else {
+            return true;
+        };
+        if !self.dedupe.insert(slot, signature) {
+            return true;
+        }
+        self.last_signature = Some(parsed);
+
+        let Some(transaction) = self.fetch_transaction(&parsed).await else {
+            return true;
+        };
+        for record in crate::events::payment_event_records(&transaction, signature) {
+            if self.filter.matches(&record) && self.sender.send(record).await.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    async fn fetch_transaction(
+        &self,
+        signature: &solana_signature::Signature,
+    ) -> Option<solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta>
+    {
+        use solana_client::rpc_config::RpcTransactionConfig;
+        use solana_transaction_status_client_types::UiTransactionEncoding;
+
+        for attempt in 0..self.config.fetch_attempts {
+            if attempt > 0 {
+                tokio::time::sleep(self.config.fetch_interval).await;
+            }
+            if let Ok(transaction) = self
+                .rpc
+                .get_transaction_with_config(
+                    signature,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Base64),
+                        commitment: Some(self.fetch_commitment()),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )
+                .await
+            {
+                return Some(transaction);
+            }
+        }
+        None
+    }
+
+    fn fetch_commitment(&self) -> solana_commitment_config::CommitmentConfig {
+        if self.config.commitment.is_at_least_confirmed() {
+            self.config.commitment
+        } else {
+            solana_commitment_config::CommitmentConfig::confirmed()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(kind: PaymentEventKind, config: Pubkey, buyer: Pubkey) -> PaymentEventRecord {
+        PaymentEventRecord {
+            signature: "sig".to_string(),
+            slot: 1,
+            block_time: None,
+            kind,
+            payment: Pubkey::new_unique(),
+            merchant_operator_config: config,
+            mint: Pubkey::new_unique(),
+            buyer,
+            merchant: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            order_id: 1,
+            amount: 100,
+            operator_fee: 0,
+        }
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let config = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+        let created = record(PaymentEventKind::Created, config, buyer);
+
+        assert!(PaymentEventFilter::default().matches(&created));
+        assert!(PaymentEventFilter::for_config(config).matches(&created));
+        assert!(!PaymentEventFilter::for_config(Pubkey::new_unique()).matches(&created));
+
+        let filter = PaymentEventFilter::for_config(config).kinds([PaymentEventKind::Cleared]);
+        assert!(!filter.matches(&created));
+        assert!(filter.matches(&record(PaymentEventKind::Cleared, config, buyer)));
+
+        let filter = PaymentEventFilter {
+            buyer: Some(buyer),
+            ..PaymentEventFilter::default()
+        };
+        assert!(filter.matches(&created));
+        assert!(!filter.matches(&record(
+            PaymentEventKind::Created,
+            config,
+            Pubkey::new_unique()
+        )));
+    }
+
+    #[test]
+    fn test_filter_mentioned_address() {
+        let config = Pubkey::new_unique();
+        assert_eq!(
+            PaymentEventFilter::default().mentioned_address(),
+            crate::COMMERCE_PROGRAM_ID
+        );
+        assert_eq!(
+            PaymentEventFilter::for_config(config).mentioned_address(),
+            config
+        );
+    }
+
+    #[test]
+    fn test_slot_dedupe() {
+        let mut dedupe = SlotDedupe::new(10);
+
+        assert!(dedupe.insert(100, "a"));
+        assert!(!dedupe.insert(100, "a"));
+        assert!(dedupe.insert(100, "b"));
+        // Slots may arrive out of order within the window
+        assert!(dedupe.insert(95, "c"));
+        assert!(!dedupe.insert(95, "c"));
+
+        // Moving past the window forgets old slots and rejects anything older
+        assert!(dedupe.insert(120, "d"));
+        assert!(!dedupe.insert(100, "e"));
+        assert!(!dedupe.insert(109, "f"));
+        assert!(dedupe.insert(110, "g"));
+        assert_eq!(dedupe.seen.keys().copied().collect::<Vec<_>>(), [110, 120]);
+    }
+}
@@ -0,0 +1,638 @@
+//! Payment events paired with the slot and block time of the transaction that emitted them.
+//!
+//! The program emits events through its `EmitEvent` self-CPI and they carry no time, payment
+//! account or mint. With the `fetch` feature, `payment_event_records` decodes the payment
+//! events of a transaction's top-level instructions into [`PaymentEventRecord`]s, one shape
+//! for every event type, with the transaction's slot and block time and the payment, config
+//! and mint of the instruction that emitted each event. The CLI report and the API read
+//! events through it. Instructions invoked through CPI and compressed payments are not
+//! covered.
+
+use borsh::BorshDeserialize;
+use solana_pubkey::Pubkey;
+
+use crate::generated::types::{
+    PaymentCapturedEvent, PaymentChargebackedEvent, PaymentClearedEvent, PaymentClosedEvent,
+    PaymentCreatedEvent, PaymentRefundedEvent, PlatformFeeCollectedEvent, RefundApprovedEvent,
+};
+
+/// Prefix of every event emitted through the program's `EmitEvent` self-CPI.
+pub const EVENT_IX_TAG_LE: [u8; 8] = 0x1d9acb512ea545e4u64.to_le_bytes();
+
+const PAYMENT_CREATED: u8 = 0;
+const PAYMENT_CLEARED: u8 = 1;
+const PAYMENT_REFUNDED: u8 = 2;
+const PAYMENT_CHARGEBACKED: u8 = 4;
+const PAYMENT_CLOSED: u8 = 5;
+const REFUND_APPROVED: u8 = 9;
+const PLATFORM_FEE_COLLECTED: u8 = 12;
+const PAYMENT_CAPTURED: u8 = 13;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PaymentEventKind {
+    Created,
+    Cleared,
+    Refunded,
+    Chargebacked,
+    RefundApproved,
+    Closed,
+    /// A platform fee policy's fee, whose amount is the fee.
+    PlatformFeeCollected,
+    /// An authorized payment captured, whose amount is the amount captured.
+    Captured,
+}
+
+/// A payment event with the transaction that emitted it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaymentEventRecord {
+    pub signature: String,
+    pub slot: u64,
+    /// Unix timestamp of the block, `None` if the node did not record it.
+    pub block_time: Option<i64>,
+    pub kind: PaymentEventKind,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub payment: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub merchant_operator_config: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub mint: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub buyer: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub merchant: Pubkey,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub operator: Pubkey,
+    pub order_id: u32,
+    /// Amount of the payment, refund, chargeback or capture, the fee for
+    /// `PlatformFeeCollected`, and 0 for `Closed`.
+    pub amount: u64,
+    /// Operator fee taken by `Cleared`, 0 for other kinds.
+    pub operator_fee: u64,
+}
+
+/// The fields of a payment event, before it is attributed to an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaymentEventData {
+    pub kind: PaymentEventKind,
+    pub buyer: Pubkey,
+    pub merchant: Pubkey,
+    pub operator: Pubkey,
+    pub order_id: u32,
+    pub amount: u64,
+    pub operator_fee: u64,
+}
+
+/// Decodes `EmitEvent` instruction data into a payment event. Other events and data that
+/// is not an event give `None`.
+pub fn decode_payment_event(data: &[u8]) -> Option<PaymentEventData> {
+    let mut event = data.strip_prefix(&EVENT_IX_TAG_LE)?;
+    let data = |kind, buyer, merchant, operator, order_id, amount| PaymentEventData {
+        kind,
+        buyer,
+        merchant,
+        operator,
+        order_id,
+        amount,
+        operator_fee: 0,
+    };
+    Some(match *event.first()? {
+        PAYMENT_CREATED => {
+            let e = PaymentCreatedEvent::deserialize(&mut event).ok()?;
+            let kind = PaymentEventKind::Created;
+            data(kind, e.buyer, e.merchant, e.operator, e.order_id, e.amount)
+        }
+        PAYMENT_CLEARED => {
+            let e = PaymentClearedEvent::deserialize(&mut event).ok()?;
+            let kind = PaymentEventKind::Cleared;
+            PaymentEventData {
+                operator_fee: e.operator_fee,
+                ..data(kind, e.buyer, e.merchant, e.operator, e.order_id, e.amount)
+            }
+        }
+        PAYMENT_REFUNDED => {
+            let e = PaymentRefundedEvent::deserialize(&mut event).ok()?;
+            let kind = PaymentEventKind::Refunded;
+            data(kind, e.buyer, e.merchant, e.operator, e.order_id, e.amount)
+        }
+        PAYMENT_CHARGEBACKED => {
+            let e = PaymentChargebackedEvent::deserialize(&mut event).ok()?;
+            let kind = PaymentEventKind::Chargebacked;
+            data(kind, e.buyer, e.merchant, e.operator, e.order_id, e.amount)
+        }
+        PAYMENT_CLOSED => {
+            let e = PaymentClosedEvent::deserialize(&mut event).ok()?;
+            let kind = PaymentEventKind::Closed;
+            data(kind, e.buyer, e.merchant, e.operator, e.order_id, 0)
+        }
+        REFUND_APPROVED => {
+            let e = RefundApprovedEvent::deserialize(&mut event).ok()?;
+            let kind = PaymentEventKind::RefundApproved;
+            data(kind, e.buyer, e.merchant, e.operator, e.order_id, e.amount)
+        }
+        PLATFORM_FEE_COLLECTED => {
+            let e = PlatformFeeCollectedEvent::deserialize(&mut event).ok()?;
+            let kind = PaymentEventKind::PlatformFeeCollected;
+            data(kind, e.buyer, e.merchant, e.operator, e.order_id, e.amount)
+        }
+        PAYMENT_CAPTURED => {
+            let e = PaymentCapturedEvent::deserialize(&mut event).ok()?;
+            let kind = PaymentEventKind::Captured;
+            data(kind, e.buyer, e.merchant, e.operator, e.order_id, e.amount)
+        }
+        _ => return None,
+    })
+}
+
+/// Decodes the payment events of a transaction fetched with `getTransaction`, with its
+/// slot and block time. The transaction must be fetched with a binary encoding.
+#[cfg(feature = "fetch")]
+pub fn payment_event_records(
+    transaction: &solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta,
+    signature: &str,
+) -> Vec<PaymentEventRecord> {
+    use solana_transaction_status_client_types::{
+        option_serializer::OptionSerializer, InnerInstruction, InnerInstructions, UiInstruction,
+    };
+
+    let Some(decoded) = transaction.transaction.transaction.decode() else {
+        return Vec::new();
+    };
+    let Some(meta) = &transaction.transaction.meta else {
+        return Vec::new();
+    };
+
+    // v0 transactions may load accounts from lookup tables; they follow the static keys
+    let mut account_keys = decoded.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        account_keys.extend(
+            loaded
+                .writable
+                .iter()
+                .chain(&loaded.readonly)
+                .filter_map(|key| key.parse::<Pubkey>().ok()),
+        );
+    }
+
+    // Inner instruction data is base58 in every encoding
+    let inner_instructions: Vec<InnerInstructions> = match &meta.inner_instructions {
+        OptionSerializer::Some(inner) => inner
+            .iter()
+            .map(|inner| InnerInstructions {
+                index: inner.index,
+                instructions: inner
+                    .instructions
+                    .iter()
+                    .filter_map(|ix| match ix {
+                        UiInstruction::Compiled(ix) => Some(InnerInstruction {
+                            instruction:
+                                solana_message::compiled_instruction::CompiledInstruction {
+                                    program_id_index: ix.program_id_index,
+                                    accounts: ix.accounts.clone(),
+                                    data: bs58::decode(&ix.data).into_vec().ok()?,
+                                },
+                            stack_height: ix.stack_height,
+                        }),
+                        UiInstruction::Parsed(_) => None,
+                    })
+                    .collect(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    payment_event_records_in_instructions(
+        &account_keys,
+        decoded.message.instructions(),
+        &inner_instructions,
+        signature,
+        transaction.slot,
+        transaction.block_time,
+    )
+}
+
+/// Decodes the payment events emitted by the top-level `instructions` of a transaction.
+#[cfg(feature = "fetch")]
+pub fn payment_event_records_in_instructions(
+    account_keys: &[Pubkey],
+    instructions: &[solana_message::compiled_instruction::CompiledInstruction],
+    inner_instructions: &[solana_transaction_status_client_types::InnerInstructions],
+    signature: &str,
+    slot: u64,
+    block_time: Option<i64>,
+) -> Vec<PaymentEventRecord> {
+    let mut records = Vec::new();
+    for (index, ix) in instructions.iter().enumerate() {
+        let Some((payment, merchant_operator_config, mint)) = decompile(account_keys, ix)
+            .and_then(|ix| crate::decode::decode_instruction(&ix).ok())
+            .and_then(|decoded| payment_accounts(&decoded))
+        else {
+            continue;
+        };
+
+        let emitted = inner_instructions
+            .iter()
+            .filter(|inner| inner.index as usize == index)
+            .flat_map(|inner| &inner.instructions)
+            .filter(|inner| {
+                account_keys.get(inner.instruction.program_id_index as usize)
+                    == Some(&crate::COMMERCE_PROGRAM_ID)
+            });
+        for inner in emitted {
+            if let Some(event) = decode_payment_event(&inner.instruction.data) {
+                records.push(PaymentEventRecord {
+                    signature: signature.to_string(),
+                    slot,
+                    block_time,
+                    kind: event.kind,
+                    payment,
+                    merchant_operator_config,
+                    mint,
+                    buyer: event.buyer,
+                    merchant: event.merchant,
+                    operator: event.operator,
+                    order_id: event.order_id,
+                    amount: event.amount,
+                    operator_fee: event.operator_fee,
+                });
+            }
+        }
+    }
+    records
+}
+
+#[cfg(feature = "fetch")]
+fn decompile(
+    account_keys: &[Pubkey],
+    ix: &solana_message::compiled_instruction::CompiledInstruction,
+) -> Option<solana_instruction::Instruction> {
+    Some(solana_instruction::Instruction {
+        program_id: *account_keys.get(ix.program_id_index as usize)?,
+        accounts: ix
+            .accounts
+            .iter()
+            .map(|index| {
+                account_keys
+                    .get(*index as usize)
+                    .map(|key| solana_instruction::AccountMeta::new_readonly(*key, false))
+            })
+            .collect::<Option<_>>()?,
+        data: ix.data.clone(),
+    })
+}
+
+/// The payment, config and mint of an instruction that emits payment events.
+#[cfg(feature = "fetch")]
+fn payment_accounts(
+    decoded: &crate::decode::DecodedInstruction,
+) -> Option<(Pubkey, Pubkey, Pubkey)> {
+    use crate::decode::DecodedInstruction;
+
+    Some(match decoded {
+        DecodedInstruction::MakePayment(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::ClearPayment(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::ClearPaymentWithConversion(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::RefundPayment(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::RefundPaymentWithConversion(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::CapturePayment(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::RefundSettledPayment(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::ApproveRefund(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::ClaimRefund(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::ChargebackPayment(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        DecodedInstruction::ClosePayment(ix) => (
+            ix.accounts.payment,
+            ix.accounts.merchant_operator_config,
+            ix.accounts.mint,
+        ),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated::types::{PaymentLifecycleStatus, PaymentStatusChangedEvent};
+
+    fn event_data(event: &impl borsh::BorshSerialize) -> Vec<u8> {
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend_from_slice(&borsh::to_vec(event).unwrap());
+        data
+    }
+
+    #[test]
+    fn test_decode_payment_event() {
+        let (buyer, merchant, operator) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let cleared = PaymentClearedEvent {
+            discriminator: PAYMENT_CLEARED,
+            buyer,
+            merchant,
+            operator,
+            amount: 1_000,
+            operator_fee: 25,
+            order_id: 42,
+            idempotency_key: [0; 16],
+        };
+        assert_eq!(
+            decode_payment_event(&event_data(&cleared)),
+            Some(PaymentEventData {
+                kind: PaymentEventKind::Cleared,
+                buyer,
+                merchant,
+                operator,
+                order_id: 42,
+                amount: 1_000,
+                operator_fee: 25,
+            })
+        );
+
+        let closed = PaymentClosedEvent {
+            discriminator: PAYMENT_CLOSED,
+            buyer,
+            merchant,
+            operator,
+            order_id: 42,
+            rent_refunded_to: Pubkey::new_unique(),
+        };
+        let decoded = decode_payment_event(&event_data(&closed)).unwrap();
+        assert_eq!(decoded.kind, PaymentEventKind::Closed);
+        assert_eq!(decoded.amount, 0);
+
+        let captured = PaymentCapturedEvent {
+            discriminator: PAYMENT_CAPTURED,
+            buyer,
+            merchant,
+            operator,
+            amount: 750,
+            released_amount: 250,
+            order_id: 42,
+            idempotency_key: [0; 16],
+        };
+        let decoded = decode_payment_event(&event_data(&captured)).unwrap();
+        assert_eq!(decoded.kind, PaymentEventKind::Captured);
+        assert_eq!(decoded.amount, 750);
+
+        // The generic status change duplicates the specific events
+        let status_changed = PaymentStatusChangedEvent {
+            discriminator: 10,
+            buyer,
+            merchant,
+            operator,
+            order_id: 42,
+            from: PaymentLifecycleStatus::Paid,
+            to: PaymentLifecycleStatus::Cleared,
+            actor: operator,
+        };
+        assert_eq!(decode_payment_event(&event_data(&status_changed)), None);
+
+        // Data without the event tag
+        assert_eq!(
+            decode_payment_event(&borsh::to_vec(&cleared).unwrap()),
+            None
+        );
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn test_payment_event_records_in_instructions() {
+        use crate::{
+            instructions::{ClearPaymentBuilder, MakePaymentBuilder},
+            COMMERCE_PROGRAM_ID,
+        };
+        use solana_instruction::Instruction;
+        use solana_message::compiled_instruction::CompiledInstruction;
+        use solana_transaction_status_client_types::{InnerInstruction, InnerInstructions};
+
+        let keys: Vec<Pubkey> = (0..17).map(|_| Pubkey::new_unique()).collect();
+        let (payment, config, mint) = (keys[1], keys[6], keys[7]);
+        let make_payment = MakePaymentBuilder::new()
+            .payer(keys[0])
+            .payment(payment)
+            .operator_authority(keys[2])
+            .buyer(keys[3])
+            .operator(keys[4])
+            .merchant(keys[5])
+            .merchant_operator_config(config)
+            .mint(mint)
+            .buyer_ata(keys[8])
+            .escrow_authority(keys[16])
+            .merchant_escrow_ata(keys[9])
+            .merchant_settlement_ata(keys[10])
+            .settlement_wallet(keys[11])
+            .token_program(keys[12])
+            .order_id(42)
+            .amount(1_000)
+            .idempotency_key([0; 16])
+            .instruction();
+        let clear_payment = ClearPaymentBuilder::new()
+            .payer(keys[0])
+            .payment(payment)
+            .operator_authority(keys[2])
+            .buyer(keys[3])
+            .merchant(keys[5])
+            .operator(keys[4])
+            .merchant_operator_config(config)
+            .mint(mint)
+            .escrow_authority(keys[16])
+            .merchant_escrow_ata(keys[9])
+            .merchant_settlement_ata(keys[10])
+            .operator_settlement_ata(keys[13])
+            .token_program(keys[12])
+            .instruction();
+
+        let mut account_keys = keys.clone();
+        for ix in [&make_payment, &clear_payment] {
+            for key in ix
+                .accounts
+                .iter()
+                .map(|meta| &meta.pubkey)
+                .chain([&ix.program_id])
+            {
+                if !account_keys.contains(key) {
+                    account_keys.push(*key);
+                }
+            }
+        }
+        let index = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+        let compile = |ix: &Instruction| CompiledInstruction {
+            program_id_index: index(&ix.program_id),
+            accounts: ix.accounts.iter().map(|meta| index(&meta.pubkey)).collect(),
+            data: ix.data.clone(),
+        };
+        let emit = |data: Vec<u8>| InnerInstruction {
+            instruction: CompiledInstruction {
+                program_id_index: index(&COMMERCE_PROGRAM_ID),
+                accounts: vec![],
+                data,
+            },
+            stack_height: Some(2),
+        };
+
+        let created = PaymentCreatedEvent {
+            discriminator: PAYMENT_CREATED,
+            buyer: keys[3],
+            merchant: keys[5],
+            operator: keys[4],
+            amount: 1_000,
+            order_id: 42,
+            idempotency_key: [0; 16],
+        };
+        let cleared = PaymentClearedEvent {
+            discriminator: PAYMENT_CLEARED,
+            buyer: keys[3],
+            merchant: keys[5],
+            operator: keys[4],
+            amount: 1_000,
+            operator_fee: 25,
+            order_id: 42,
+            idempotency_key: [0; 16],
+        };
+        let platform_fee = PlatformFeeCollectedEvent {
+            discriminator: PLATFORM_FEE_COLLECTED,
+            buyer: keys[3],
+            merchant: keys[5],
+            operator: keys[4],
+            platform: keys[14],
+            order_id: 42,
+            amount: 10,
+        };
+        let instructions = [compile(&make_payment), compile(&clear_payment)];
+        let inner_instructions = [
+            InnerInstructions {
+                index: 0,
+                instructions: vec![emit(event_data(&created))],
+            },
+            InnerInstructions {
+                index: 1,
+                // A token transfer, then the events
+                instructions: vec![
+                    InnerInstruction {
+                        instruction: CompiledInstruction {
+                            program_id_index: index(&keys[12]),
+                            accounts: vec![],
+                            data: vec![3],
+                        },
+                        stack_height: Some(2),
+                    },
+                    emit(event_data(&cleared)),
+                    emit(event_data(&platform_fee)),
+                ],
+            },
+        ];
+
+        let records = payment_event_records_in_instructions(
+            &account_keys,
+            &instructions,
+            &inner_instructions,
+            "sig",
+            7,
+            Some(100),
+        );
+        let expected = |kind, amount, operator_fee| PaymentEventRecord {
+            signature: "sig".to_string(),
+            slot: 7,
+            block_time: Some(100),
+            kind,
+            payment,
+            merchant_operator_config: config,
+            mint,
+            buyer: keys[3],
+            merchant: keys[5],
+            operator: keys[4],
+            order_id: 42,
+            amount,
+            operator_fee,
+        };
+        assert_eq!(
+            records,
+            vec![
+                expected(PaymentEventKind::Created, 1_000, 0),
+                expected(PaymentEventKind::Cleared, 1_000, 25),
+                expected(PaymentEventKind::PlatformFeeCollected, 10, 0),
+            ]
+        );
+
+        // Events of instructions that are not the program's are ignored
+        let inner_of_transfer = [InnerInstructions {
+            index: 0,
+            instructions: vec![emit(event_data(&created))],
+        }];
+        let transfer = CompiledInstruction {
+            program_id_index: index(&keys[12]),
+            accounts: vec![],
+            data: vec![3],
+        };
+        assert!(payment_event_records_in_instructions(
+            &account_keys,
+            &[transfer],
+            &inner_of_transfer,
+            "sig",
+            7,
+            None,
+        )
+        .is_empty());
+    }
+}
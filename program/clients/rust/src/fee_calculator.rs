@@ -0,0 +1,169 @@
+//! Client-side simulation of how `ClearPayment` splits a payment.
+//!
+//! [`FeeCalculator`] takes a config's fee and policies and shows, for any amount, what the
+//! operator, the platform, the merchant and each split settlement destination would receive,
+//! so integrators can compare fee types and policies before setting up a config. Its tests
+//! run the same cases as the program's `calculate_fees` tests, from `commerce-fee-cases`.
+//!
+//! Settlement discounts and fee overrides depend on the payment being cleared, so they are
+//! left to [`preview_clear_payment`](crate::preview::preview_clear_payment).
+
+use crate::{
+    generated::{
+        accounts::MerchantOperatorConfig,
+        types::{FeeType, PlatformFeePolicy, PolicyData, SplitSettlementPolicy},
+    },
+    preview::{calculate_fees, platform_fee_amount},
+    split_settlement::split_settlement_amounts,
+};
+
+/// What each party receives when a payment clears.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeBreakdown {
+    /// Amount sent to the operator's settlement ATA.
+    pub operator_fee: u64,
+    /// Amount sent to the platform's settlement ATA under a platform fee policy.
+    pub platform_fee: u64,
+    /// Amount left for the merchant after both fees.
+    pub merchant_amount: u64,
+    /// `merchant_amount` split between the split settlement destinations, in the policy's
+    /// order; empty without a split settlement policy.
+    pub split_amounts: Vec<u64>,
+}
+
+/// A config's fee and the policies that change how its payments are split.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeCalculator {
+    operator_fee: u64,
+    fee_type: FeeType,
+    policies: Vec<PolicyData>,
+}
+
+impl FeeCalculator {
+    /// Calculator for a config charging `operator_fee` of `fee_type` under `policies`. Like the
+    /// program, only the first fee tier, platform fee and split settlement policy apply.
+    pub fn new(operator_fee: u64, fee_type: FeeType, policies: &[PolicyData]) -> Self {
+        Self {
+            operator_fee,
+            fee_type,
+            policies: policies.to_vec(),
+        }
+    }
+
+    /// Calculator for a fetched config and its policies, as returned by
+    /// [`decode_merchant_operator_config`](crate::preview::decode_merchant_operator_config).
+    pub fn from_config(config: &MerchantOperatorConfig, policies: &[PolicyData]) -> Self {
+        Self::new(config.operator_fee, config.fee_type, policies)
+    }
+
+    /// Splits a payment of `amount` the way `ClearPayment` does.
+    ///
+    /// Returns `None` when the operator fee would exceed `amount`, which the program rejects.
+    pub fn calculate(&self, amount: u64) -> Option<FeeBreakdown> {
+        let (operator_fee, merchant_amount) =
+            calculate_fees(amount, self.operator_fee, self.fee_type, &self.policies)?;
+
+        let platform_fee = self.platform_fee().map_or(0, |platform_fee| {
+            platform_fee_amount(platform_fee, amount, merchant_amount)
+        });
+        let merchant_amount = merchant_amount - platform_fee;
+
+        let split_amounts = self
+            .split_settlement()
+            .map_or_else(Vec::new, |split_settlement| {
+                split_settlement_amounts(split_settlement, merchant_amount)
+            });
+
+        Some(FeeBreakdown {
+            operator_fee,
+            platform_fee,
+            merchant_amount,
+            split_amounts,
+        })
+    }
+
+    fn platform_fee(&self) -> Option<&PlatformFeePolicy> {
+        self.policies.iter().find_map(|policy| match policy {
+            PolicyData::PlatformFee(platform_fee) => Some(platform_fee),
+            _ => None,
+        })
+    }
+
+    fn split_settlement(&self) -> Option<&SplitSettlementPolicy> {
+        self.policies.iter().find_map(|policy| match policy {
+            PolicyData::SplitSettlement(split_settlement) => Some(split_settlement),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated::types::{FeeTier, FeeTierPolicy};
+    use commerce_fee_cases::{Fee, FeeTierCase, FEE_CASES};
+    use solana_pubkey::Pubkey;
+
+    fn fee_of(fee: Fee) -> (u64, FeeType) {
+        match fee {
+            Fee::Bps(fee) => (fee, FeeType::Bps),
+            Fee::Fixed(fee) => (fee, FeeType::Fixed),
+        }
+    }
+
+    #[test]
+    fn test_fee_calculator_shared_cases() {
+        for case in FEE_CASES {
+            let mut policies = Vec::new();
+            if !case.fee_tiers.is_empty() {
+                let tiers = core::array::from_fn(|i| {
+                    let tier_case = case.fee_tiers.get(i).copied().unwrap_or(FeeTierCase {
+                        threshold: 0,
+                        fee: Fee::Bps(0),
+                    });
+                    let (fee, fee_type) = fee_of(tier_case.fee);
+                    FeeTier {
+                        threshold: tier_case.threshold,
+                        fee,
+                        fee_type,
+                    }
+                });
+                policies.push(PolicyData::FeeTier(FeeTierPolicy {
+                    num_tiers: case.fee_tiers.len() as u8,
+                    tiers,
+                }));
+            }
+            if let Some(platform_fee) = case.platform_fee {
+                let (fee, fee_type) = fee_of(platform_fee);
+                policies.push(PolicyData::PlatformFee(PlatformFeePolicy {
+                    platform: Pubkey::new_from_array([1; 32]),
+                    fee,
+                    fee_type,
+                }));
+            }
+            if !case.split_shares_bps.is_empty() {
+                let mut shares_bps = [0; 5];
+                shares_bps[..case.split_shares_bps.len()].copy_from_slice(case.split_shares_bps);
+                policies.push(PolicyData::SplitSettlement(SplitSettlementPolicy {
+                    destinations_hash: [1; 32],
+                    num_destinations: case.split_shares_bps.len() as u8,
+                    shares_bps,
+                }));
+            }
+
+            let (operator_fee, fee_type) = fee_of(case.operator_fee);
+            let expected = case.expected.map(|expected| FeeBreakdown {
+                operator_fee: expected.operator_fee,
+                platform_fee: expected.platform_fee,
+                merchant_amount: expected.merchant_amount,
+                split_amounts: expected.split_amounts.to_vec(),
+            });
+            assert_eq!(
+                FeeCalculator::new(operator_fee, fee_type, &policies).calculate(case.amount),
+                expected,
+                "{}",
+                case.name
+            );
+        }
+    }
+}
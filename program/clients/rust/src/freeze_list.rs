@@ -0,0 +1,70 @@
+//! Off-chain helpers for an operator's freeze list.
+//!
+//! An operator freezes buyer addresses, e.g. for a sanctions takedown, on its
+//! OperatorFreezeList PDA, created with `InitializeOperatorFreezeList` and maintained with
+//! `AddToFreezeList` and `RemoveFromFreezeList`. Once an operator has one, its
+//! `has_freeze_list` is set and every payment and refund under it must pass the list,
+//! [`operator_freeze_list_account`], or fails with `FreezeListRequired`.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{generated::accounts::OperatorFreezeList, pdas::find_operator_freeze_list_pda};
+
+/// Addresses an OperatorFreezeList holds.
+pub const OPERATOR_FREEZE_LIST_CAPACITY: usize = 64;
+
+impl OperatorFreezeList {
+    /// Frozen addresses, in no particular order.
+    pub fn frozen(&self) -> &[Pubkey] {
+        let len = (self.count as usize).min(OPERATOR_FREEZE_LIST_CAPACITY);
+        &self.addresses[..len]
+    }
+
+    pub fn is_frozen(&self, address: &Pubkey) -> bool {
+        self.frozen().contains(address)
+    }
+}
+
+/// Read-only OperatorFreezeList PDA to pass to `MakePayment` before the BuyerIndex, to
+/// `MakePaymentCompressed` before the RiskState PDAs, to `RefundPayment`, `ClaimRefund` or
+/// `CapturePayment` before the Order, to `ChargebackPayment` before the memo program, or last
+/// to `RefundSettledPayment`.
+pub fn operator_freeze_list_account(operator: &Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(find_operator_freeze_list_pda(operator).0, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_frozen() {
+        let frozen = Pubkey::new_unique();
+        let removed = Pubkey::new_unique();
+        let mut addresses = [Pubkey::default(); OPERATOR_FREEZE_LIST_CAPACITY];
+        addresses[0] = frozen;
+        // Slots past `count` are ignored
+        addresses[1] = removed;
+        let freeze_list = OperatorFreezeList {
+            discriminator: 15,
+            operator: Pubkey::new_unique(),
+            bump: 255,
+            count: 1,
+            addresses,
+        };
+
+        assert_eq!(freeze_list.frozen(), &[frozen]);
+        assert!(freeze_list.is_frozen(&frozen));
+        assert!(!freeze_list.is_frozen(&removed));
+    }
+
+    #[test]
+    fn test_operator_freeze_list_account() {
+        let operator = Pubkey::new_unique();
+        let meta = operator_freeze_list_account(&operator);
+        assert_eq!(meta.pubkey, find_operator_freeze_list_pda(&operator).0);
+        assert!(!meta.is_writable);
+        assert!(!meta.is_signer);
+    }
+}
@@ -5,6 +5,7 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::vec::Vec;
 use solana_pubkey::Pubkey;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
@@ -19,25 +20,33 @@ pub owner: Pubkey,
 pub bump: u8,
 #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::DisplayFromStr>"))]
 pub settlement_wallet: Pubkey,
+#[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::DisplayFromStr>"))]
+pub pending_authority: Pubkey,
+pub settlement_wallet_timelock_seconds: u64,
+#[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::DisplayFromStr>"))]
+pub pending_settlement_wallet: Pubkey,
+pub pending_settlement_wallet_unlocks_at: i64,
+#[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::DisplayFromStr>"))]
+pub original_owner: Pubkey,
 }
 
 
 
 
 impl Merchant {
-      pub const LEN: usize = 66;
+      pub const LEN: usize = 178;
   
   
   
   #[inline(always)]
-  pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {
+  pub fn from_bytes(data: &[u8]) -> Result<Self, borsh::io::Error> {
     let mut data = data;
     Self::deserialize(&mut data)
   }
 }
 
 impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for Merchant {
-  type Error = std::io::Error;
+  type Error = borsh::io::Error;
 
   fn try_from(account_info: &solana_account_info::AccountInfo<'a>) -> Result<Self, Self::Error> {
       let mut data: &[u8] = &(*account_info.data).borrow();
@@ -49,7 +58,7 @@ impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for Merchant {
 pub fn fetch_merchant(
   rpc: &solana_client::rpc_client::RpcClient,
   address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::DecodedAccount<Merchant>, std::io::Error> {
+) -> Result<crate::shared::DecodedAccount<Merchant>, borsh::io::Error> {
   let accounts = fetch_all_merchant(rpc, &[*address])?;
   Ok(accounts[0].clone())
 }
@@ -58,14 +67,14 @@ pub fn fetch_merchant(
 pub fn fetch_all_merchant(
   rpc: &solana_client::rpc_client::RpcClient,
   addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::DecodedAccount<Merchant>>, std::io::Error> {
+) -> Result<Vec<crate::shared::DecodedAccount<Merchant>>, borsh::io::Error> {
     let accounts = rpc.get_multiple_accounts(addresses)
-      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+      .map_err(|e| borsh::io::Error::new(borsh::io::ErrorKind::Other, e.to_string()))?;
     let mut decoded_accounts: Vec<crate::shared::DecodedAccount<Merchant>> = Vec::new();
     for i in 0..addresses.len() {
       let address = addresses[i];
       let account = accounts[i].as_ref()
-        .ok_or(std::io::Error::new(std::io::ErrorKind::Other, format!("Account not found: {}", address)))?;
+        .ok_or(borsh::io::Error::new(borsh::io::ErrorKind::Other, format!("Account not found: {}", address)))?;
       let data = Merchant::from_bytes(&account.data)?;
       decoded_accounts.push(crate::shared::DecodedAccount { address, account: account.clone(), data });
     }
@@ -76,7 +85,7 @@ pub fn fetch_all_merchant(
 pub fn fetch_maybe_merchant(
   rpc: &solana_client::rpc_client::RpcClient,
   address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::MaybeAccount<Merchant>, std::io::Error> {
+) -> Result<crate::shared::MaybeAccount<Merchant>, borsh::io::Error> {
     let accounts = fetch_all_maybe_merchant(rpc, &[*address])?;
     Ok(accounts[0].clone())
 }
@@ -85,9 +94,9 @@ pub fn fetch_maybe_merchant(
 pub fn fetch_all_maybe_merchant(
   rpc: &solana_client::rpc_client::RpcClient,
   addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::MaybeAccount<Merchant>>, std::io::Error> {
+) -> Result<Vec<crate::shared::MaybeAccount<Merchant>>, borsh::io::Error> {
     let accounts = rpc.get_multiple_accounts(addresses)
-      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+      .map_err(|e| borsh::io::Error::new(borsh::io::ErrorKind::Other, e.to_string()))?;
     let mut decoded_accounts: Vec<crate::shared::MaybeAccount<Merchant>> = Vec::new();
     for i in 0..addresses.len() {
       let address = addresses[i];
@@ -5,6 +5,7 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::vec::Vec;
 use solana_pubkey::Pubkey;
 use crate::generated::types::FeeType;
 use borsh::BorshSerialize;
@@ -27,25 +28,28 @@ pub current_order_id: u32,
 pub days_to_close: u16,
 pub num_policies: u32,
 pub num_accepted_currencies: u32,
+pub config_hash: [u8; 32],
+pub decommissioned_at: i64,
+pub payment_accounts: u32,
 }
 
 
 
 
 impl MerchantOperatorConfig {
-      pub const LEN: usize = 93;
+      pub const LEN: usize = 137;
   
   
   
   #[inline(always)]
-  pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {
+  pub fn from_bytes(data: &[u8]) -> Result<Self, borsh::io::Error> {
     let mut data = data;
     Self::deserialize(&mut data)
   }
 }
 
 impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for MerchantOperatorConfig {
-  type Error = std::io::Error;
+  type Error = borsh::io::Error;
 
   fn try_from(account_info: &solana_account_info::AccountInfo<'a>) -> Result<Self, Self::Error> {
       let mut data: &[u8] = &(*account_info.data).borrow();
@@ -57,7 +61,7 @@ impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for MerchantOperatorConf
 pub fn fetch_merchant_operator_config(
   rpc: &solana_client::rpc_client::RpcClient,
   address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::DecodedAccount<MerchantOperatorConfig>, std::io::Error> {
+) -> Result<crate::shared::DecodedAccount<MerchantOperatorConfig>, borsh::io::Error> {
   let accounts = fetch_all_merchant_operator_config(rpc, &[*address])?;
   Ok(accounts[0].clone())
 }
@@ -66,14 +70,14 @@ pub fn fetch_merchant_operator_config(
 pub fn fetch_all_merchant_operator_config(
   rpc: &solana_client::rpc_client::RpcClient,
   addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::DecodedAccount<MerchantOperatorConfig>>, std::io::Error> {
+) -> Result<Vec<crate::shared::DecodedAccount<MerchantOperatorConfig>>, borsh::io::Error> {
     let accounts = rpc.get_multiple_accounts(addresses)
-      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+      .map_err(|e| borsh::io::Error::new(borsh::io::ErrorKind::Other, e.to_string()))?;
     let mut decoded_accounts: Vec<crate::shared::DecodedAccount<MerchantOperatorConfig>> = Vec::new();
     for i in 0..addresses.len() {
       let address = addresses[i];
       let account = accounts[i].as_ref()
-        .ok_or(std::io::Error::new(std::io::ErrorKind::Other, format!("Account not found: {}", address)))?;
+        .ok_or(borsh::io::Error::new(borsh::io::ErrorKind::Other, format!("Account not found: {}", address)))?;
       let data = MerchantOperatorConfig::from_bytes(&account.data)?;
       decoded_accounts.push(crate::shared::DecodedAccount { address, account: account.clone(), data });
     }
@@ -84,7 +88,7 @@ pub fn fetch_all_merchant_operator_config(
 pub fn fetch_maybe_merchant_operator_config(
   rpc: &solana_client::rpc_client::RpcClient,
   address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::MaybeAccount<MerchantOperatorConfig>, std::io::Error> {
+) -> Result<crate::shared::MaybeAccount<MerchantOperatorConfig>, borsh::io::Error> {
     let accounts = fetch_all_maybe_merchant_operator_config(rpc, &[*address])?;
     Ok(accounts[0].clone())
 }
@@ -93,9 +97,9 @@ pub fn fetch_maybe_merchant_operator_config(
 pub fn fetch_all_maybe_merchant_operator_config(
   rpc: &solana_client::rpc_client::RpcClient,
   addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::MaybeAccount<MerchantOperatorConfig>>, std::io::Error> {
+) -> Result<Vec<crate::shared::MaybeAccount<MerchantOperatorConfig>>, borsh::io::Error> {
     let accounts = rpc.get_multiple_accounts(addresses)
-      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+      .map_err(|e| borsh::io::Error::new(borsh::io::ErrorKind::Other, e.to_string()))?;
     let mut decoded_accounts: Vec<crate::shared::MaybeAccount<MerchantOperatorConfig>> = Vec::new();
     for i in 0..addresses.len() {
       let address = addresses[i];
@@ -5,13 +5,45 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+  pub(crate) mod r#buyer_index;
+  pub(crate) mod r#config_registry_page;
+  pub(crate) mod r#conversion_rate;
   pub(crate) mod r#merchant;
   pub(crate) mod r#merchant_operator_config;
+  pub(crate) mod r#merchant_profile;
+  pub(crate) mod r#merchant_stats;
   pub(crate) mod r#operator;
+  pub(crate) mod r#operator_delegate;
+  pub(crate) mod r#operator_freeze_list;
+  pub(crate) mod r#order;
   pub(crate) mod r#payment;
+  pub(crate) mod r#payment_annotation;
+  pub(crate) mod r#payment_note;
+  pub(crate) mod r#payment_tree;
+  pub(crate) mod r#quote;
+  pub(crate) mod r#refund_approval;
+  pub(crate) mod r#risk_state;
+  pub(crate) mod r#settlement_batch;
+  pub(crate) mod r#webhook_commitment;
 
+  pub use self::r#buyer_index::*;
+  pub use self::r#config_registry_page::*;
+  pub use self::r#conversion_rate::*;
   pub use self::r#merchant::*;
   pub use self::r#merchant_operator_config::*;
+  pub use self::r#merchant_profile::*;
+  pub use self::r#merchant_stats::*;
   pub use self::r#operator::*;
+  pub use self::r#operator_delegate::*;
+  pub use self::r#operator_freeze_list::*;
+  pub use self::r#order::*;
   pub use self::r#payment::*;
+  pub use self::r#payment_annotation::*;
+  pub use self::r#payment_note::*;
+  pub use self::r#payment_tree::*;
+  pub use self::r#quote::*;
+  pub use self::r#refund_approval::*;
+  pub use self::r#risk_state::*;
+  pub use self::r#settlement_batch::*;
+  pub use self::r#webhook_commitment::*;
 
@@ -5,6 +5,7 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::vec::Vec;
 use solana_pubkey::Pubkey;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
@@ -17,25 +18,31 @@ pub discriminator: u8,
 #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::DisplayFromStr>"))]
 pub owner: Pubkey,
 pub bump: u8,
+#[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::DisplayFromStr>"))]
+pub pending_authority: Pubkey,
+pub last_active_at: i64,
+pub has_freeze_list: bool,
+#[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::DisplayFromStr>"))]
+pub original_owner: Pubkey,
 }
 
 
 
 
 impl Operator {
-      pub const LEN: usize = 34;
+      pub const LEN: usize = 107;
   
   
   
   #[inline(always)]
-  pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {
+  pub fn from_bytes(data: &[u8]) -> Result<Self, borsh::io::Error> {
     let mut data = data;
     Self::deserialize(&mut data)
   }
 }
 
 impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for Operator {
-  type Error = std::io::Error;
+  type Error = borsh::io::Error;
 
   fn try_from(account_info: &solana_account_info::AccountInfo<'a>) -> Result<Self, Self::Error> {
       let mut data: &[u8] = &(*account_info.data).borrow();
@@ -47,7 +54,7 @@ impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for Operator {
 pub fn fetch_operator(
   rpc: &solana_client::rpc_client::RpcClient,
   address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::DecodedAccount<Operator>, std::io::Error> {
+) -> Result<crate::shared::DecodedAccount<Operator>, borsh::io::Error> {
   let accounts = fetch_all_operator(rpc, &[*address])?;
   Ok(accounts[0].clone())
 }
@@ -56,14 +63,14 @@ pub fn fetch_operator(
 pub fn fetch_all_operator(
   rpc: &solana_client::rpc_client::RpcClient,
   addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::DecodedAccount<Operator>>, std::io::Error> {
+) -> Result<Vec<crate::shared::DecodedAccount<Operator>>, borsh::io::Error> {
     let accounts = rpc.get_multiple_accounts(addresses)
-      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+      .map_err(|e| borsh::io::Error::new(borsh::io::ErrorKind::Other, e.to_string()))?;
     let mut decoded_accounts: Vec<crate::shared::DecodedAccount<Operator>> = Vec::new();
     for i in 0..addresses.len() {
       let address = addresses[i];
       let account = accounts[i].as_ref()
-        .ok_or(std::io::Error::new(std::io::ErrorKind::Other, format!("Account not found: {}", address)))?;
+        .ok_or(borsh::io::Error::new(borsh::io::ErrorKind::Other, format!("Account not found: {}", address)))?;
       let data = Operator::from_bytes(&account.data)?;
       decoded_accounts.push(crate::shared::DecodedAccount { address, account: account.clone(), data });
     }
@@ -74,7 +81,7 @@ pub fn fetch_all_operator(
 pub fn fetch_maybe_operator(
   rpc: &solana_client::rpc_client::RpcClient,
   address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::MaybeAccount<Operator>, std::io::Error> {
+) -> Result<crate::shared::MaybeAccount<Operator>, borsh::io::Error> {
     let accounts = fetch_all_maybe_operator(rpc, &[*address])?;
     Ok(accounts[0].clone())
 }
@@ -83,9 +90,9 @@ pub fn fetch_maybe_operator(
 pub fn fetch_all_maybe_operator(
   rpc: &solana_client::rpc_client::RpcClient,
   addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::MaybeAccount<Operator>>, std::io::Error> {
+) -> Result<Vec<crate::shared::MaybeAccount<Operator>>, borsh::io::Error> {
     let accounts = rpc.get_multiple_accounts(addresses)
-      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+      .map_err(|e| borsh::io::Error::new(borsh::io::ErrorKind::Other, e.to_string()))?;
     let mut decoded_accounts: Vec<crate::shared::MaybeAccount<Operator>> = Vec::new();
     for i in 0..addresses.len() {
       let address = addresses[i];
@@ -5,7 +5,10 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::vec::Vec;
 use crate::generated::types::Status;
+use crate::generated::types::FeeOverride;
+use solana_pubkey::Pubkey;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
@@ -19,25 +22,32 @@ pub amount: u64,
 pub created_at: i64,
 pub status: Status,
 pub bump: u8,
+pub idempotency_key: [u8; 16],
+pub has_fee_override: bool,
+pub fee_override: FeeOverride,
+pub references: [Pubkey; 2],
+#[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::DisplayFromStr>"))]
+pub rent_payer: Pubkey,
+pub has_order: bool,
 }
 
 
 
 
 impl Payment {
-      pub const LEN: usize = 23;
+      pub const LEN: usize = 146;
   
   
   
   #[inline(always)]
-  pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {
+  pub fn from_bytes(data: &[u8]) -> Result<Self, borsh::io::Error> {
     let mut data = data;
     Self::deserialize(&mut data)
   }
 }
 
 impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for Payment {
-  type Error = std::io::Error;
+  type Error = borsh::io::Error;
 
   fn try_from(account_info: &solana_account_info::AccountInfo<'a>) -> Result<Self, Self::Error> {
       let mut data: &[u8] = &(*account_info.data).borrow();
@@ -49,7 +59,7 @@ impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for Payment {
 pub fn fetch_payment(
   rpc: &solana_client::rpc_client::RpcClient,
   address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::DecodedAccount<Payment>, std::io::Error> {
+) -> Result<crate::shared::DecodedAccount<Payment>, borsh::io::Error> {
   let accounts = fetch_all_payment(rpc, &[*address])?;
   Ok(accounts[0].clone())
 }
@@ -58,14 +68,14 @@ pub fn fetch_payment(
 pub fn fetch_all_payment(
   rpc: &solana_client::rpc_client::RpcClient,
   addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::DecodedAccount<Payment>>, std::io::Error> {
+) -> Result<Vec<crate::shared::DecodedAccount<Payment>>, borsh::io::Error> {
     let accounts = rpc.get_multiple_accounts(addresses)
-      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+      .map_err(|e| borsh::io::Error::new(borsh::io::ErrorKind::Other, e.to_string()))?;
     let mut decoded_accounts: Vec<crate::shared::DecodedAccount<Payment>> = Vec::new();
     for i in 0..addresses.len() {
       let address = addresses[i];
       let account = accounts[i].as_ref()
-        .ok_or(std::io::Error::new(std::io::ErrorKind::Other, format!("Account not found: {}", address)))?;
+        .ok_or(borsh::io::Error::new(borsh::io::ErrorKind::Other, format!("Account not found: {}", address)))?;
       let data = Payment::from_bytes(&account.data)?;
       decoded_accounts.push(crate::shared::DecodedAccount { address, account: account.clone(), data });
     }
@@ -76,7 +86,7 @@ pub fn fetch_all_payment(
 pub fn fetch_maybe_payment(
   rpc: &solana_client::rpc_client::RpcClient,
   address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::MaybeAccount<Payment>, std::io::Error> {
+) -> Result<crate::shared::MaybeAccount<Payment>, borsh::io::Error> {
     let accounts = fetch_all_maybe_payment(rpc, &[*address])?;
     Ok(accounts[0].clone())
 }
@@ -85,9 +95,9 @@ pub fn fetch_maybe_payment(
 pub fn fetch_all_maybe_payment(
   rpc: &solana_client::rpc_client::RpcClient,
   addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::MaybeAccount<Payment>>, std::io::Error> {
+) -> Result<Vec<crate::shared::MaybeAccount<Payment>>, borsh::io::Error> {
     let accounts = rpc.get_multiple_accounts(addresses)
-      .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+      .map_err(|e| borsh::io::Error::new(borsh::io::ErrorKind::Other, e.to_string()))?;
     let mut decoded_accounts: Vec<crate::shared::MaybeAccount<Payment>> = Vec::new();
     for i in 0..addresses.len() {
       let address = addresses[i];
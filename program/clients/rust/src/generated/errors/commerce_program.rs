@@ -67,6 +67,384 @@ pub enum CommerceProgramError {
     /// 18 - Duplicate mint in accepted currencies
     #[error("Duplicate mint in accepted currencies")]
     DuplicateMint = 0x12,
+    /// 19 - PaymentTree PDA is invalid
+    #[error("PaymentTree PDA is invalid")]
+    PaymentTreeInvalidPda = 0x13,
+    /// 20 - PaymentTree does not match config
+    #[error("PaymentTree does not match config")]
+    PaymentTreeMismatch = 0x14,
+    /// 21 - PaymentTree has no remaining leaves
+    #[error("PaymentTree has no remaining leaves")]
+    PaymentTreeFull = 0x15,
+    /// 22 - Merkle proof does not match the PaymentTree root
+    #[error("Merkle proof does not match the PaymentTree root")]
+    InvalidMerkleProof = 0x16,
+    /// 23 - Merchant has no pending authority matching the signer
+    #[error("Merchant has no pending authority matching the signer")]
+    MerchantPendingAuthorityMismatch = 0x17,
+    /// 24 - Operator has no pending authority matching the signer
+    #[error("Operator has no pending authority matching the signer")]
+    OperatorPendingAuthorityMismatch = 0x18,
+    /// 25 - Settlement wallet does not match merchant settlement wallet
+    #[error("Settlement wallet does not match merchant settlement wallet")]
+    SettlementWalletMismatch = 0x19,
+    /// 26 - MerchantStats PDA is invalid
+    #[error("MerchantStats PDA is invalid")]
+    MerchantStatsInvalidPda = 0x1A,
+    /// 27 - MerchantStats does not match merchant or mint
+    #[error("MerchantStats does not match merchant or mint")]
+    MerchantStatsMismatch = 0x1B,
+    /// 28 - Payment already exists
+    #[error("Payment already exists")]
+    PaymentAlreadyExists = 0x1C,
+    /// 29 - Fee tier policy is invalid
+    #[error("Fee tier policy is invalid")]
+    InvalidFeeTierPolicy = 0x1D,
+    /// 30 - Payment was not auto-settled
+    #[error("Payment was not auto-settled")]
+    PaymentNotAutoSettled = 0x1E,
+    /// 31 - Refunding a settled payment requires a refund policy with a refund window
+    #[error("Refunding a settled payment requires a refund policy with a refund window")]
+    RefundWindowRequired = 0x1F,
+    /// 32 - Memo policy requires the memo program account
+    #[error("Memo policy requires the memo program account")]
+    MemoProgramRequired = 0x20,
+    /// 33 - OperatorDelegate PDA is invalid
+    #[error("OperatorDelegate PDA is invalid")]
+    OperatorDelegateInvalidPda = 0x21,
+    /// 34 - OperatorDelegate does not match operator or signer
+    #[error("OperatorDelegate does not match operator or signer")]
+    OperatorDelegateMismatch = 0x22,
+    /// 35 - OperatorDelegate lacks the permission for this instruction
+    #[error("OperatorDelegate lacks the permission for this instruction")]
+    OperatorDelegatePermissionDenied = 0x23,
+    /// 36 - Conversion policy is invalid
+    #[error("Conversion policy is invalid")]
+    InvalidConversionPolicy = 0x24,
+    /// 37 - Config has no conversion policy
+    #[error("Config has no conversion policy")]
+    ConversionPolicyRequired = 0x25,
+    /// 38 - Swap program or instruction is not supported by the conversion policy
+    #[error("Swap program or instruction is not supported by the conversion policy")]
+    InvalidSwap = 0x26,
+    /// 39 - Swap did not spend exactly the merchant amount
+    #[error("Swap did not spend exactly the merchant amount")]
+    SwapInputMismatch = 0x27,
+    /// 40 - Swap output is below the slippage bound
+    #[error("Swap output is below the slippage bound")]
+    SlippageExceeded = 0x28,
+    /// 41 - Chargeback policy is invalid
+    #[error("Chargeback policy is invalid")]
+    InvalidChargebackPolicy = 0x29,
+    /// 42 - Config has no chargeback policy
+    #[error("Config has no chargeback policy")]
+    ChargebackPolicyRequired = 0x2A,
+    /// 43 - Payment is still within its chargeback window
+    #[error("Payment is still within its chargeback window")]
+    ChargebackWindowActive = 0x2B,
+    /// 44 - Payment chargeback window has expired
+    #[error("Payment chargeback window has expired")]
+    ChargebackWindowExpired = 0x2C,
+    /// 45 - Signer is not the chargeback arbiter
+    #[error("Signer is not the chargeback arbiter")]
+    ChargebackArbiterMismatch = 0x2D,
+    /// 46 - Settlement wallet timelock exceeds the maximum
+    #[error("Settlement wallet timelock exceeds the maximum")]
+    InvalidSettlementWalletTimelock = 0x2E,
+    /// 47 - Settlement wallet is timelocked and must be changed via ProposeSettlementWallet
+    #[error("Settlement wallet is timelocked and must be changed via ProposeSettlementWallet")]
+    SettlementWalletTimelocked = 0x2F,
+    /// 48 - No settlement wallet has been proposed
+    #[error("No settlement wallet has been proposed")]
+    SettlementWalletNotProposed = 0x30,
+    /// 49 - Proposed settlement wallet is still timelocked
+    #[error("Proposed settlement wallet is still timelocked")]
+    SettlementWalletTimelockActive = 0x31,
+    /// 50 - Fee override policy is invalid
+    #[error("Fee override policy is invalid")]
+    InvalidFeeOverridePolicy = 0x32,
+    /// 51 - Config has no fee override policy
+    #[error("Config has no fee override policy")]
+    FeeOverridePolicyRequired = 0x33,
+    /// 52 - Fee override exceeds the fee override policy maximum
+    #[error("Fee override exceeds the fee override policy maximum")]
+    FeeOverrideExceedsMax = 0x34,
+    /// 53 - Denylist policy is invalid
+    #[error("Denylist policy is invalid")]
+    InvalidDenylistPolicy = 0x35,
+    /// 54 - Config has a denylist policy but no denylist proof was supplied
+    #[error("Config has a denylist policy but no denylist proof was supplied")]
+    DenylistProofRequired = 0x36,
+    /// 55 - Denylist proof does not show the buyer is allowed
+    #[error("Denylist proof does not show the buyer is allowed")]
+    InvalidDenylistProof = 0x37,
+    /// 56 - SettlementBatch PDA is invalid
+    #[error("SettlementBatch PDA is invalid")]
+    SettlementBatchInvalidPda = 0x38,
+    /// 57 - SettlementBatch does not match config or mint
+    #[error("SettlementBatch does not match config or mint")]
+    SettlementBatchMismatch = 0x39,
+    /// 58 - SettlementBatch has no pending amount
+    #[error("SettlementBatch has no pending amount")]
+    SettlementBatchEmpty = 0x3A,
+    /// 59 - Order PDA is invalid
+    #[error("Order PDA is invalid")]
+    OrderInvalidPda = 0x3B,
+    /// 60 - Payment does not belong to the Order
+    #[error("Payment does not belong to the Order")]
+    OrderMismatch = 0x3C,
+    /// 61 - Order already has all its expected payments
+    #[error("Order already has all its expected payments")]
+    OrderFull = 0x3D,
+    /// 62 - Order expected payment count is invalid
+    #[error("Order expected payment count is invalid")]
+    InvalidOrderPaymentCount = 0x3E,
+    /// 63 - MerchantProfile PDA is invalid
+    #[error("MerchantProfile PDA is invalid")]
+    MerchantProfileInvalidPda = 0x3F,
+    /// 64 - MerchantProfile name or URI is too long
+    #[error("MerchantProfile name or URI is too long")]
+    MerchantProfileFieldTooLong = 0x40,
+    /// 65 - More reference accounts than a Payment can store
+    #[error("More reference accounts than a Payment can store")]
+    TooManyPaymentReferences = 0x41,
+    /// 66 - Split settlement policy is invalid
+    #[error("Split settlement policy is invalid")]
+    InvalidSplitSettlementPolicy = 0x42,
+    /// 67 - Split settlement destinations do not match the policy
+    #[error("Split settlement destinations do not match the policy")]
+    InvalidSplitSettlementDestinations = 0x43,
+    /// 68 - Split settlement is not supported on this settlement path
+    #[error("Split settlement is not supported on this settlement path")]
+    SplitSettlementUnsupported = 0x44,
+    /// 69 - RefundApproval PDA is invalid
+    #[error("RefundApproval PDA is invalid")]
+    RefundApprovalInvalidPda = 0x45,
+    /// 70 - Refund approval belongs to a different payment
+    #[error("Refund approval belongs to a different payment")]
+    RefundApprovalMismatch = 0x46,
+    /// 71 - Refund destination is not a token account the buyer owns for the payment mint
+    #[error("Refund destination is not a token account the buyer owns for the payment mint")]
+    InvalidRefundDestination = 0x47,
+    /// 72 - Risk policy is invalid
+    #[error("Risk policy is invalid")]
+    InvalidRiskPolicy = 0x48,
+    /// 73 - Payment amount exceeds the risk policy's maximum payment amount
+    #[error("Payment amount exceeds the risk policy's maximum payment amount")]
+    PaymentAmountExceedsRiskLimit = 0x49,
+    /// 74 - Payment would exceed the risk policy's maximum daily volume
+    #[error("Payment would exceed the risk policy's maximum daily volume")]
+    DailyVolumeLimitExceeded = 0x4A,
+    /// 75 - Buyer has reached the risk policy's maximum payments per day
+    #[error("Buyer has reached the risk policy's maximum payments per day")]
+    BuyerDailyPaymentLimitExceeded = 0x4B,
+    /// 76 - RiskState PDA is invalid
+    #[error("RiskState PDA is invalid")]
+    RiskStateInvalidPda = 0x4C,
+    /// 77 - RiskState belongs to a different config or buyer
+    #[error("RiskState belongs to a different config or buyer")]
+    RiskStateMismatch = 0x4D,
+    /// 78 - RiskState account required by the risk policy is missing
+    #[error("RiskState account required by the risk policy is missing")]
+    RiskStateRequired = 0x4E,
+    /// 79 - BuyerIndex PDA is invalid
+    #[error("BuyerIndex PDA is invalid")]
+    BuyerIndexInvalidPda = 0x4F,
+    /// 80 - BuyerIndex belongs to a different config or buyer
+    #[error("BuyerIndex belongs to a different config or buyer")]
+    BuyerIndexMismatch = 0x50,
+    /// 81 - BuyerIndex account required by the buyer index policy is missing
+    #[error("BuyerIndex account required by the buyer index policy is missing")]
+    BuyerIndexRequired = 0x51,
+    /// 82 - Rent refund account does not match the payment's rent payer
+    #[error("Rent refund account does not match the payment's rent payer")]
+    RentPayerMismatch = 0x52,
+    /// 83 - Callback policy is invalid
+    #[error("Callback policy is invalid")]
+    InvalidCallbackPolicy = 0x53,
+    /// 84 - Callback program required by the callback policy is missing
+    #[error("Callback program required by the callback policy is missing")]
+    CallbackProgramRequired = 0x54,
+    /// 85 - Callback program does not match the callback policy
+    #[error("Callback program does not match the callback policy")]
+    CallbackProgramMismatch = 0x55,
+    /// 86 - Refund exceeds the delegate's refund limit
+    #[error("Refund exceeds the delegate's refund limit")]
+    DelegateRefundLimitExceeded = 0x56,
+    /// 87 - Refund exceeds the delegate's daily refund limit
+    #[error("Refund exceeds the delegate's daily refund limit")]
+    DelegateDailyRefundLimitExceeded = 0x57,
+    /// 88 - Transfer hook program or its ExtraAccountMetaList PDA not passed
+    #[error("Transfer hook program or its ExtraAccountMetaList PDA not passed")]
+    TransferHookAccountsMissing = 0x58,
+    /// 89 - Mint has a Token-2022 extension the program does not support
+    #[error("Mint has a Token-2022 extension the program does not support")]
+    UnsupportedMintExtension = 0x59,
+    /// 90 - Platform fee policy is invalid
+    #[error("Platform fee policy is invalid")]
+    InvalidPlatformFeePolicy = 0x5A,
+    /// 91 - Platform fee is not supported on this settlement path
+    #[error("Platform fee is not supported on this settlement path")]
+    PlatformFeeUnsupported = 0x5B,
+    /// 92 - Escrow authority PDA is invalid
+    #[error("Escrow authority PDA is invalid")]
+    EscrowAuthorityInvalidPda = 0x5C,
+    /// 93 - Quote PDA is invalid
+    #[error("Quote PDA is invalid")]
+    QuoteInvalidPda = 0x5D,
+    /// 94 - Payment does not match the quote
+    #[error("Payment does not match the quote")]
+    QuoteMismatch = 0x5E,
+    /// 95 - Quote has expired
+    #[error("Quote has expired")]
+    QuoteExpired = 0x5F,
+    /// 96 - Config registry page PDA is invalid
+    #[error("Config registry page PDA is invalid")]
+    ConfigRegistryInvalidPda = 0x60,
+    /// 97 - Config registry page does not belong to the operator
+    #[error("Config registry page does not belong to the operator")]
+    ConfigRegistryMismatch = 0x61,
+    /// 98 - Config registry page is full
+    #[error("Config registry page is full")]
+    ConfigRegistryPageFull = 0x62,
+    /// 99 - Previous config registry page is not full
+    #[error("Previous config registry page is not full")]
+    ConfigRegistryPreviousPageNotFull = 0x63,
+    /// 100 - Compliance policy is invalid
+    #[error("Compliance policy is invalid")]
+    InvalidCompliancePolicy = 0x64,
+    /// 101 - Compliance program required by the compliance policy is missing
+    #[error("Compliance program required by the compliance policy is missing")]
+    ComplianceProgramRequired = 0x65,
+    /// 102 - Compliance program does not match the compliance policy
+    #[error("Compliance program does not match the compliance policy")]
+    ComplianceProgramMismatch = 0x66,
+    /// 103 - Capture amount exceeds the authorized amount
+    #[error("Capture amount exceeds the authorized amount")]
+    CaptureAmountExceedsAuthorization = 0x67,
+    /// 104 - Authorized payments must be held in escrow
+    #[error("Authorized payments must be held in escrow")]
+    AuthorizationRequiresEscrow = 0x68,
+    /// 105 - Operator freeze list PDA is invalid
+    #[error("Operator freeze list PDA is invalid")]
+    OperatorFreezeListInvalidPda = 0x69,
+    /// 106 - Operator freeze list does not belong to the operator
+    #[error("Operator freeze list does not belong to the operator")]
+    OperatorFreezeListMismatch = 0x6a,
+    /// 107 - Operator freeze list is full
+    #[error("Operator freeze list is full")]
+    OperatorFreezeListFull = 0x6b,
+    /// 108 - Address is already on the freeze list
+    #[error("Address is already on the freeze list")]
+    AddressAlreadyFrozen = 0x6c,
+    /// 109 - Address is not on the freeze list
+    #[error("Address is not on the freeze list")]
+    AddressNotFrozen = 0x6d,
+    /// 110 - Buyer is on the operator's freeze list
+    #[error("Buyer is on the operator's freeze list")]
+    BuyerFrozen = 0x6e,
+    /// 111 - Settlement discount policy is invalid
+    #[error("Settlement discount policy is invalid")]
+    InvalidSettlementDiscountPolicy = 0x6f,
+    /// 112 - Refund conversion policy is invalid
+    #[error("Refund conversion policy is invalid")]
+    InvalidRefundConversionPolicy = 0x70,
+    /// 113 - Refunding in another mint requires a refund conversion policy
+    #[error("Refunding in another mint requires a refund conversion policy")]
+    RefundConversionPolicyRequired = 0x71,
+    /// 114 - Conversion rate PDA is invalid
+    #[error("Conversion rate PDA is invalid")]
+    ConversionRateInvalidPda = 0x72,
+    /// 115 - Conversion rate does not match the config and mints
+    #[error("Conversion rate does not match the config and mints")]
+    ConversionRateMismatch = 0x73,
+    /// 116 - Conversion rate is older than the refund conversion policy allows
+    #[error("Conversion rate is older than the refund conversion policy allows")]
+    ConversionRateStale = 0x74,
+    /// 117 - Conversion rate or converted amount is zero
+    #[error("Conversion rate or converted amount is zero")]
+    InvalidConversionRate = 0x75,
+    /// 118 - More policies than a config can hold
+    #[error("More policies than a config can hold")]
+    TooManyPolicies = 0x76,
+    /// 119 - More accepted currencies than a config can hold
+    #[error("More accepted currencies than a config can hold")]
+    TooManyAcceptedCurrencies = 0x77,
+    /// 120 - Config already has a policy of this type
+    #[error("Config already has a policy of this type")]
+    PolicyAlreadyExists = 0x78,
+    /// 121 - Payment note PDA is invalid
+    #[error("Payment note PDA is invalid")]
+    PaymentNoteInvalidPda = 0x79,
+    /// 122 - Operator failover policy is invalid
+    #[error("Operator failover policy is invalid")]
+    InvalidOperatorFailoverPolicy = 0x7A,
+    /// 123 - Merchant can only clear payments with an operator failover policy
+    #[error("Merchant can only clear payments with an operator failover policy")]
+    OperatorFailoverPolicyRequired = 0x7B,
+    /// 124 - Operator has been active within the failover threshold
+    #[error("Operator has been active within the failover threshold")]
+    OperatorStillActive = 0x7C,
+    /// 125 - Token account is not owned by the expected wallet
+    #[error("Token account is not owned by the expected wallet")]
+    AtaOwnerMismatch = 0x7D,
+    /// 126 - Token account holds a different mint
+    #[error("Token account holds a different mint")]
+    AtaMintMismatch = 0x7E,
+    /// 127 - Associated token account is not initialized
+    #[error("Associated token account is not initialized")]
+    AtaNotInitialized = 0x7F,
+    /// 128 - Escrow still holds open payments
+    #[error("Escrow still holds open payments")]
+    EscrowNotEmpty = 0x80,
+    /// 129 - Payment already belongs to the migration's target config
+    #[error("Payment already belongs to the migration's target config")]
+    MigrationTargetUnchanged = 0x81,
+    /// 130 - Instruction version is not supported
+    #[error("Instruction version is not supported")]
+    UnsupportedInstructionVersion = 0x82,
+    /// 131 - Rent rebate policy is invalid
+    #[error("Rent rebate policy is invalid")]
+    InvalidRentRebatePolicy = 0x83,
+    /// 132 - Webhook commitment PDA is invalid
+    #[error("Webhook commitment PDA is invalid")]
+    WebhookCommitmentInvalidPda = 0x84,
+    /// 133 - Direct settlement is not supported with this instruction or policy
+    #[error("Direct settlement is not supported with this instruction or policy")]
+    DirectSettlementUnsupported = 0x85,
+    /// 134 - Payment annotation PDA is invalid
+    #[error("Payment annotation PDA is invalid")]
+    PaymentAnnotationInvalidPda = 0x86,
+    /// 135 - Merchant operator config is decommissioned
+    #[error("Merchant operator config is decommissioned")]
+    ConfigDecommissioned = 0x87,
+    /// 136 - Merchant operator config is not decommissioned
+    #[error("Merchant operator config is not decommissioned")]
+    ConfigNotDecommissioned = 0x88,
+    /// 137 - Config still has open payments
+    #[error("Config still has open payments")]
+    ConfigHasOpenPayments = 0x89,
+    /// 138 - Account is not in a legacy layout
+    #[error("Account is not in a legacy layout")]
+    AccountNotUpgradable = 0x8A,
+    /// 139 - Operator has a freeze list but it was not supplied
+    #[error("Operator has a freeze list but it was not supplied")]
+    FreezeListRequired = 0x8B,
+    /// 140 - Config still has payment accounts
+    #[error("Config still has payment accounts")]
+    ConfigHasPaymentAccounts = 0x8C,
+    /// 141 - Quoted amount is below the posted conversion rate
+    #[error("Quoted amount is below the posted conversion rate")]
+    QuoteBelowConversionRate = 0x8D,
+    /// 142 - Payment belongs to an Order, which must be passed
+    #[error("Payment belongs to an Order, which must be passed")]
+    OrderRequired = 0x8E,
+    /// 143 - Payment belongs to an Order and cannot move to another config
+    #[error("Payment belongs to an Order and cannot move to another config")]
+    PaymentInOrder = 0x8F,
+    /// 144 - Settlement wallet timelock can only be raised
+    #[error("Settlement wallet timelock can only be raised")]
+    SettlementWalletTimelockLowered = 0x90,
 }
 
 impl From<CommerceProgramError> for solana_program_error::ProgramError {
@@ -5,6 +5,8 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
@@ -43,7 +45,12 @@ pub struct ClearPayment {
           
               
           pub mint: solana_pubkey::Pubkey,
-                /// Merchant Escrow ATA (Merchant PDA is owner)
+                /// Escrow authority PDA
+
+    
+              
+          pub escrow_authority: solana_pubkey::Pubkey,
+                /// Merchant Escrow ATA (Escrow authority PDA is owner)
 
     
               
@@ -86,7 +93,7 @@ impl ClearPayment {
   #[allow(clippy::arithmetic_side_effects)]
   #[allow(clippy::vec_init_then_push)]
   pub fn instruction_with_remaining_accounts(&self, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
-    let mut accounts = Vec::with_capacity(16+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(17+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             self.payer,
             true
@@ -111,13 +118,17 @@ impl ClearPayment {
             self.operator,
             false
           ));
-                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+                                          accounts.push(solana_instruction::AccountMeta::new(
             self.merchant_operator_config,
             false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             self.mint,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.escrow_authority,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new(
             self.merchant_escrow_ata,
@@ -194,16 +205,17 @@ impl Default for ClearPaymentInstructionData {
           ///   3. `[]` buyer
           ///   4. `[]` merchant
           ///   5. `[]` operator
-          ///   6. `[]` merchant_operator_config
+                ///   6. `[writable]` merchant_operator_config
           ///   7. `[]` mint
-                ///   8. `[writable]` merchant_escrow_ata
-                ///   9. `[writable]` merchant_settlement_ata
-                ///   10. `[writable]` operator_settlement_ata
-                ///   11. `[optional]` token_program (default to `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`)
-                ///   12. `[optional]` associated_token_program (default to `ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL`)
-                ///   13. `[optional]` system_program (default to `11111111111111111111111111111111`)
-                ///   14. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
-                ///   15. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
+          ///   8. `[]` escrow_authority
+                ///   9. `[writable]` merchant_escrow_ata
+                ///   10. `[writable]` merchant_settlement_ata
+                ///   11. `[writable]` operator_settlement_ata
+                ///   12. `[optional]` token_program (default to `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`)
+                ///   13. `[optional]` associated_token_program (default to `ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL`)
+                ///   14. `[optional]` system_program (default to `11111111111111111111111111111111`)
+                ///   15. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
+                ///   16. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
 #[derive(Clone, Debug, Default)]
 pub struct ClearPaymentBuilder {
             payer: Option<solana_pubkey::Pubkey>,
@@ -214,6 +226,7 @@ pub struct ClearPaymentBuilder {
                 operator: Option<solana_pubkey::Pubkey>,
                 merchant_operator_config: Option<solana_pubkey::Pubkey>,
                 mint: Option<solana_pubkey::Pubkey>,
+                escrow_authority: Option<solana_pubkey::Pubkey>,
                 merchant_escrow_ata: Option<solana_pubkey::Pubkey>,
                 merchant_settlement_ata: Option<solana_pubkey::Pubkey>,
                 operator_settlement_ata: Option<solana_pubkey::Pubkey>,
@@ -272,7 +285,13 @@ impl ClearPaymentBuilder {
                         self.mint = Some(mint);
                     self
     }
-            /// Merchant Escrow ATA (Merchant PDA is owner)
+            /// Escrow authority PDA
+#[inline(always)]
+    pub fn escrow_authority(&mut self, escrow_authority: solana_pubkey::Pubkey) -> &mut Self {
+                        self.escrow_authority = Some(escrow_authority);
+                    self
+    }
+            /// Merchant Escrow ATA (Escrow authority PDA is owner)
 #[inline(always)]
     pub fn merchant_escrow_ata(&mut self, merchant_escrow_ata: solana_pubkey::Pubkey) -> &mut Self {
                         self.merchant_escrow_ata = Some(merchant_escrow_ata);
@@ -345,6 +364,7 @@ impl ClearPaymentBuilder {
                                         operator: self.operator.expect("operator is not set"),
                                         merchant_operator_config: self.merchant_operator_config.expect("merchant_operator_config is not set"),
                                         mint: self.mint.expect("mint is not set"),
+                                        escrow_authority: self.escrow_authority.expect("escrow_authority is not set"),
                                         merchant_escrow_ata: self.merchant_escrow_ata.expect("merchant_escrow_ata is not set"),
                                         merchant_settlement_ata: self.merchant_settlement_ata.expect("merchant_settlement_ata is not set"),
                                         operator_settlement_ata: self.operator_settlement_ata.expect("operator_settlement_ata is not set"),
@@ -391,7 +411,12 @@ impl ClearPaymentBuilder {
                 
                     
               pub mint: &'b solana_account_info::AccountInfo<'a>,
-                        /// Merchant Escrow ATA (Merchant PDA is owner)
+                        /// Escrow authority PDA
+
+      
+                    
+              pub escrow_authority: &'b solana_account_info::AccountInfo<'a>,
+                        /// Merchant Escrow ATA (Escrow authority PDA is owner)
 
       
                     
@@ -461,7 +486,12 @@ pub struct ClearPaymentCpi<'a, 'b> {
           
               
           pub mint: &'b solana_account_info::AccountInfo<'a>,
-                /// Merchant Escrow ATA (Merchant PDA is owner)
+                /// Escrow authority PDA
+
+    
+              
+          pub escrow_authority: &'b solana_account_info::AccountInfo<'a>,
+                /// Merchant Escrow ATA (Escrow authority PDA is owner)
 
     
               
@@ -512,6 +542,7 @@ impl<'a, 'b> ClearPaymentCpi<'a, 'b> {
               operator: accounts.operator,
               merchant_operator_config: accounts.merchant_operator_config,
               mint: accounts.mint,
+              escrow_authority: accounts.escrow_authority,
               merchant_escrow_ata: accounts.merchant_escrow_ata,
               merchant_settlement_ata: accounts.merchant_settlement_ata,
               operator_settlement_ata: accounts.operator_settlement_ata,
@@ -542,7 +573,7 @@ impl<'a, 'b> ClearPaymentCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program_error::ProgramResult {
-    let mut accounts = Vec::with_capacity(16+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(17+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             *self.payer.key,
             true
@@ -567,13 +598,17 @@ impl<'a, 'b> ClearPaymentCpi<'a, 'b> {
             *self.operator.key,
             false
           ));
-                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+                                          accounts.push(solana_instruction::AccountMeta::new(
             *self.merchant_operator_config.key,
             false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             *self.mint.key,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.escrow_authority.key,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new(
             *self.merchant_escrow_ata.key,
@@ -621,7 +656,7 @@ impl<'a, 'b> ClearPaymentCpi<'a, 'b> {
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(17 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(18 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
                   account_infos.push(self.payer.clone());
                         account_infos.push(self.payment.clone());
@@ -631,6 +666,7 @@ impl<'a, 'b> ClearPaymentCpi<'a, 'b> {
                         account_infos.push(self.operator.clone());
                         account_infos.push(self.merchant_operator_config.clone());
                         account_infos.push(self.mint.clone());
+                        account_infos.push(self.escrow_authority.clone());
                         account_infos.push(self.merchant_escrow_ata.clone());
                         account_infos.push(self.merchant_settlement_ata.clone());
                         account_infos.push(self.operator_settlement_ata.clone());
@@ -659,16 +695,17 @@ impl<'a, 'b> ClearPaymentCpi<'a, 'b> {
           ///   3. `[]` buyer
           ///   4. `[]` merchant
           ///   5. `[]` operator
-          ///   6. `[]` merchant_operator_config
+                ///   6. `[writable]` merchant_operator_config
           ///   7. `[]` mint
-                ///   8. `[writable]` merchant_escrow_ata
-                ///   9. `[writable]` merchant_settlement_ata
-                ///   10. `[writable]` operator_settlement_ata
-          ///   11. `[]` token_program
-          ///   12. `[]` associated_token_program
-          ///   13. `[]` system_program
-          ///   14. `[]` event_authority
-          ///   15. `[]` commerce_program
+          ///   8. `[]` escrow_authority
+                ///   9. `[writable]` merchant_escrow_ata
+                ///   10. `[writable]` merchant_settlement_ata
+                ///   11. `[writable]` operator_settlement_ata
+          ///   12. `[]` token_program
+          ///   13. `[]` associated_token_program
+          ///   14. `[]` system_program
+          ///   15. `[]` event_authority
+          ///   16. `[]` commerce_program
 #[derive(Clone, Debug)]
 pub struct ClearPaymentCpiBuilder<'a, 'b> {
   instruction: Box<ClearPaymentCpiBuilderInstruction<'a, 'b>>,
@@ -686,6 +723,7 @@ impl<'a, 'b> ClearPaymentCpiBuilder<'a, 'b> {
               operator: None,
               merchant_operator_config: None,
               mint: None,
+              escrow_authority: None,
               merchant_escrow_ata: None,
               merchant_settlement_ata: None,
               operator_settlement_ata: None,
@@ -741,7 +779,13 @@ impl<'a, 'b> ClearPaymentCpiBuilder<'a, 'b> {
                         self.instruction.mint = Some(mint);
                     self
     }
-      /// Merchant Escrow ATA (Merchant PDA is owner)
+      /// Escrow authority PDA
+#[inline(always)]
+    pub fn escrow_authority(&mut self, escrow_authority: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.escrow_authority = Some(escrow_authority);
+                    self
+    }
+      /// Merchant Escrow ATA (Escrow authority PDA is owner)
 #[inline(always)]
     pub fn merchant_escrow_ata(&mut self, merchant_escrow_ata: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.merchant_escrow_ata = Some(merchant_escrow_ata);
@@ -827,6 +871,8 @@ impl<'a, 'b> ClearPaymentCpiBuilder<'a, 'b> {
                   
           mint: self.instruction.mint.expect("mint is not set"),
                   
+          escrow_authority: self.instruction.escrow_authority.expect("escrow_authority is not set"),
+                  
           merchant_escrow_ata: self.instruction.merchant_escrow_ata.expect("merchant_escrow_ata is not set"),
                   
           merchant_settlement_ata: self.instruction.merchant_settlement_ata.expect("merchant_settlement_ata is not set"),
@@ -858,6 +904,7 @@ struct ClearPaymentCpiBuilderInstruction<'a, 'b> {
                 operator: Option<&'b solana_account_info::AccountInfo<'a>>,
                 merchant_operator_config: Option<&'b solana_account_info::AccountInfo<'a>>,
                 mint: Option<&'b solana_account_info::AccountInfo<'a>>,
+                escrow_authority: Option<&'b solana_account_info::AccountInfo<'a>>,
                 merchant_escrow_ata: Option<&'b solana_account_info::AccountInfo<'a>>,
                 merchant_settlement_ata: Option<&'b solana_account_info::AccountInfo<'a>>,
                 operator_settlement_ata: Option<&'b solana_account_info::AccountInfo<'a>>,
@@ -5,6 +5,8 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
@@ -34,12 +36,12 @@ pub struct ClosePayment {
     
               
           pub merchant: solana_pubkey::Pubkey,
-                /// Buyer account
+                /// Buyer account, credited its rent rebate under a RentRebatePolicy
 
     
               
           pub buyer: solana_pubkey::Pubkey,
-                /// Merchant Operator Config PDA
+                /// Merchant Operator Config PDA, which stops counting the payment account
 
     
               
@@ -52,6 +54,16 @@ pub struct ClosePayment {
           
               
           pub system_program: solana_pubkey::Pubkey,
+                /// Event authority PDA
+
+    
+              
+          pub event_authority: solana_pubkey::Pubkey,
+                /// Commerce Program ID
+
+    
+              
+          pub commerce_program: solana_pubkey::Pubkey,
       }
 
 impl ClosePayment {
@@ -61,7 +73,7 @@ impl ClosePayment {
   #[allow(clippy::arithmetic_side_effects)]
   #[allow(clippy::vec_init_then_push)]
   pub fn instruction_with_remaining_accounts(&self, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
-    let mut accounts = Vec::with_capacity(9+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(11+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             self.payer,
             true
@@ -82,11 +94,11 @@ impl ClosePayment {
             self.merchant,
             false
           ));
-                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+                                          accounts.push(solana_instruction::AccountMeta::new(
             self.buyer,
             false
           ));
-                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+                                          accounts.push(solana_instruction::AccountMeta::new(
             self.merchant_operator_config,
             false
           ));
@@ -97,6 +109,14 @@ impl ClosePayment {
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             self.system_program,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.event_authority,
+            false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.commerce_program,
+            false
           ));
                       accounts.extend_from_slice(remaining_accounts);
     let data = borsh::to_vec(&ClosePaymentInstructionData::new()).unwrap();
@@ -140,10 +160,12 @@ impl Default for ClosePaymentInstructionData {
                 ///   2. `[signer]` operator_authority
           ///   3. `[]` operator
           ///   4. `[]` merchant
-          ///   5. `[]` buyer
-          ///   6. `[]` merchant_operator_config
+                ///   5. `[writable]` buyer
+                ///   6. `[writable]` merchant_operator_config
           ///   7. `[]` mint
                 ///   8. `[optional]` system_program (default to `11111111111111111111111111111111`)
+                ///   9. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
+                ///   10. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
 #[derive(Clone, Debug, Default)]
 pub struct ClosePaymentBuilder {
             payer: Option<solana_pubkey::Pubkey>,
@@ -155,6 +177,8 @@ pub struct ClosePaymentBuilder {
                 merchant_operator_config: Option<solana_pubkey::Pubkey>,
                 mint: Option<solana_pubkey::Pubkey>,
                 system_program: Option<solana_pubkey::Pubkey>,
+                event_authority: Option<solana_pubkey::Pubkey>,
+                commerce_program: Option<solana_pubkey::Pubkey>,
                 __remaining_accounts: Vec<solana_instruction::AccountMeta>,
 }
 
@@ -190,13 +214,13 @@ impl ClosePaymentBuilder {
                         self.merchant = Some(merchant);
                     self
     }
-            /// Buyer account
+            /// Buyer account, credited its rent rebate under a RentRebatePolicy
 #[inline(always)]
     pub fn buyer(&mut self, buyer: solana_pubkey::Pubkey) -> &mut Self {
                         self.buyer = Some(buyer);
                     self
     }
-            /// Merchant Operator Config PDA
+            /// Merchant Operator Config PDA, which stops counting the payment account
 #[inline(always)]
     pub fn merchant_operator_config(&mut self, merchant_operator_config: solana_pubkey::Pubkey) -> &mut Self {
                         self.merchant_operator_config = Some(merchant_operator_config);
@@ -213,6 +237,20 @@ impl ClosePaymentBuilder {
     pub fn system_program(&mut self, system_program: solana_pubkey::Pubkey) -> &mut Self {
                         self.system_program = Some(system_program);
                     self
+    }
+            /// `[optional account, default to '3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1']`
+/// Event authority PDA
+#[inline(always)]
+    pub fn event_authority(&mut self, event_authority: solana_pubkey::Pubkey) -> &mut Self {
+                        self.event_authority = Some(event_authority);
+                    self
+    }
+            /// `[optional account, default to 'commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT']`
+/// Commerce Program ID
+#[inline(always)]
+    pub fn commerce_program(&mut self, commerce_program: solana_pubkey::Pubkey) -> &mut Self {
+                        self.commerce_program = Some(commerce_program);
+                    self
     }
             /// Add an additional account to the instruction.
   #[inline(always)]
@@ -238,6 +276,8 @@ impl ClosePaymentBuilder {
                                         merchant_operator_config: self.merchant_operator_config.expect("merchant_operator_config is not set"),
                                         mint: self.mint.expect("mint is not set"),
                                         system_program: self.system_program.unwrap_or(solana_pubkey::pubkey!("11111111111111111111111111111111")),
+                                        event_authority: self.event_authority.unwrap_or(solana_pubkey::pubkey!("3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1")),
+                                        commerce_program: self.commerce_program.unwrap_or(solana_pubkey::pubkey!("commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT")),
                       };
     
     accounts.instruction_with_remaining_accounts(&self.__remaining_accounts)
@@ -267,12 +307,12 @@ impl ClosePaymentBuilder {
       
                     
               pub merchant: &'b solana_account_info::AccountInfo<'a>,
-                        /// Buyer account
+                        /// Buyer account, credited its rent rebate under a RentRebatePolicy
 
       
                     
               pub buyer: &'b solana_account_info::AccountInfo<'a>,
-                        /// Merchant Operator Config PDA
+                        /// Merchant Operator Config PDA, which stops counting the payment account
 
       
                     
@@ -285,6 +325,16 @@ impl ClosePaymentBuilder {
                 
                     
               pub system_program: &'b solana_account_info::AccountInfo<'a>,
+                        /// Event authority PDA
+
+      
+                    
+              pub event_authority: &'b solana_account_info::AccountInfo<'a>,
+                        /// Commerce Program ID
+
+      
+                    
+              pub commerce_program: &'b solana_account_info::AccountInfo<'a>,
             }
 
 /// `close_payment` CPI instruction.
@@ -312,12 +362,12 @@ pub struct ClosePaymentCpi<'a, 'b> {
     
               
           pub merchant: &'b solana_account_info::AccountInfo<'a>,
-                /// Buyer account
+                /// Buyer account, credited its rent rebate under a RentRebatePolicy
 
     
               
           pub buyer: &'b solana_account_info::AccountInfo<'a>,
-                /// Merchant Operator Config PDA
+                /// Merchant Operator Config PDA, which stops counting the payment account
 
     
               
@@ -330,6 +380,16 @@ pub struct ClosePaymentCpi<'a, 'b> {
           
               
           pub system_program: &'b solana_account_info::AccountInfo<'a>,
+                /// Event authority PDA
+
+    
+              
+          pub event_authority: &'b solana_account_info::AccountInfo<'a>,
+                /// Commerce Program ID
+
+    
+              
+          pub commerce_program: &'b solana_account_info::AccountInfo<'a>,
         }
 
 impl<'a, 'b> ClosePaymentCpi<'a, 'b> {
@@ -348,6 +408,8 @@ impl<'a, 'b> ClosePaymentCpi<'a, 'b> {
               merchant_operator_config: accounts.merchant_operator_config,
               mint: accounts.mint,
               system_program: accounts.system_program,
+              event_authority: accounts.event_authority,
+              commerce_program: accounts.commerce_program,
                 }
   }
   #[inline(always)]
@@ -370,7 +432,7 @@ impl<'a, 'b> ClosePaymentCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program_error::ProgramResult {
-    let mut accounts = Vec::with_capacity(9+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(11+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             *self.payer.key,
             true
@@ -391,11 +453,11 @@ impl<'a, 'b> ClosePaymentCpi<'a, 'b> {
             *self.merchant.key,
             false
           ));
-                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+                                          accounts.push(solana_instruction::AccountMeta::new(
             *self.buyer.key,
             false
           ));
-                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+                                          accounts.push(solana_instruction::AccountMeta::new(
             *self.merchant_operator_config.key,
             false
           ));
@@ -406,6 +468,14 @@ impl<'a, 'b> ClosePaymentCpi<'a, 'b> {
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             *self.system_program.key,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.event_authority.key,
+            false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.commerce_program.key,
+            false
           ));
                       remaining_accounts.iter().for_each(|remaining_account| {
       accounts.push(solana_instruction::AccountMeta {
@@ -421,7 +491,7 @@ impl<'a, 'b> ClosePaymentCpi<'a, 'b> {
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(10 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(12 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
                   account_infos.push(self.payer.clone());
                         account_infos.push(self.payment.clone());
@@ -432,6 +502,8 @@ impl<'a, 'b> ClosePaymentCpi<'a, 'b> {
                         account_infos.push(self.merchant_operator_config.clone());
                         account_infos.push(self.mint.clone());
                         account_infos.push(self.system_program.clone());
+                        account_infos.push(self.event_authority.clone());
+                        account_infos.push(self.commerce_program.clone());
               remaining_accounts.iter().for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
 
     if signers_seeds.is_empty() {
@@ -451,10 +523,12 @@ impl<'a, 'b> ClosePaymentCpi<'a, 'b> {
                 ///   2. `[signer]` operator_authority
           ///   3. `[]` operator
           ///   4. `[]` merchant
-          ///   5. `[]` buyer
-          ///   6. `[]` merchant_operator_config
+                ///   5. `[writable]` buyer
+                ///   6. `[writable]` merchant_operator_config
           ///   7. `[]` mint
           ///   8. `[]` system_program
+          ///   9. `[]` event_authority
+          ///   10. `[]` commerce_program
 #[derive(Clone, Debug)]
 pub struct ClosePaymentCpiBuilder<'a, 'b> {
   instruction: Box<ClosePaymentCpiBuilderInstruction<'a, 'b>>,
@@ -473,6 +547,8 @@ impl<'a, 'b> ClosePaymentCpiBuilder<'a, 'b> {
               merchant_operator_config: None,
               mint: None,
               system_program: None,
+              event_authority: None,
+              commerce_program: None,
                                 __remaining_accounts: Vec::new(),
     });
     Self { instruction }
@@ -505,13 +581,13 @@ impl<'a, 'b> ClosePaymentCpiBuilder<'a, 'b> {
                         self.instruction.merchant = Some(merchant);
                     self
     }
-      /// Buyer account
+      /// Buyer account, credited its rent rebate under a RentRebatePolicy
 #[inline(always)]
     pub fn buyer(&mut self, buyer: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.buyer = Some(buyer);
                     self
     }
-      /// Merchant Operator Config PDA
+      /// Merchant Operator Config PDA, which stops counting the payment account
 #[inline(always)]
     pub fn merchant_operator_config(&mut self, merchant_operator_config: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.merchant_operator_config = Some(merchant_operator_config);
@@ -527,6 +603,18 @@ impl<'a, 'b> ClosePaymentCpiBuilder<'a, 'b> {
     pub fn system_program(&mut self, system_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.system_program = Some(system_program);
                     self
+    }
+      /// Event authority PDA
+#[inline(always)]
+    pub fn event_authority(&mut self, event_authority: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.event_authority = Some(event_authority);
+                    self
+    }
+      /// Commerce Program ID
+#[inline(always)]
+    pub fn commerce_program(&mut self, commerce_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.commerce_program = Some(commerce_program);
+                    self
     }
             /// Add an additional account to the instruction.
   #[inline(always)]
@@ -570,6 +658,10 @@ impl<'a, 'b> ClosePaymentCpiBuilder<'a, 'b> {
           mint: self.instruction.mint.expect("mint is not set"),
                   
           system_program: self.instruction.system_program.expect("system_program is not set"),
+                  
+          event_authority: self.instruction.event_authority.expect("event_authority is not set"),
+                  
+          commerce_program: self.instruction.commerce_program.expect("commerce_program is not set"),
                     };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
   }
@@ -587,6 +679,8 @@ struct ClosePaymentCpiBuilderInstruction<'a, 'b> {
                 merchant_operator_config: Option<&'b solana_account_info::AccountInfo<'a>>,
                 mint: Option<&'b solana_account_info::AccountInfo<'a>>,
                 system_program: Option<&'b solana_account_info::AccountInfo<'a>>,
+                event_authority: Option<&'b solana_account_info::AccountInfo<'a>>,
+                commerce_program: Option<&'b solana_account_info::AccountInfo<'a>>,
                 /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_account_info::AccountInfo<'a>, bool, bool)>,
 }
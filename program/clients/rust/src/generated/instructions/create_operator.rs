@@ -5,6 +5,8 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
@@ -28,12 +30,12 @@ pub struct CreateOperator {
       }
 
 impl CreateOperator {
-  pub fn instruction(&self, args: CreateOperatorInstructionArgs) -> solana_instruction::Instruction {
-    self.instruction_with_remaining_accounts(args, &[])
+  pub fn instruction(&self) -> solana_instruction::Instruction {
+    self.instruction_with_remaining_accounts(&[])
   }
   #[allow(clippy::arithmetic_side_effects)]
   #[allow(clippy::vec_init_then_push)]
-  pub fn instruction_with_remaining_accounts(&self, args: CreateOperatorInstructionArgs, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
+  pub fn instruction_with_remaining_accounts(&self, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
     let mut accounts = Vec::with_capacity(4+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             self.payer,
@@ -52,9 +54,7 @@ impl CreateOperator {
             false
           ));
                       accounts.extend_from_slice(remaining_accounts);
-    let mut data = borsh::to_vec(&CreateOperatorInstructionData::new()).unwrap();
-          let mut args = borsh::to_vec(&args).unwrap();
-      data.append(&mut args);
+    let data = borsh::to_vec(&CreateOperatorInstructionData::new()).unwrap();
     
     solana_instruction::Instruction {
       program_id: crate::COMMERCE_PROGRAM_ID,
@@ -84,13 +84,6 @@ impl Default for CreateOperatorInstructionData {
   }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
- pub struct CreateOperatorInstructionArgs {
-                  pub bump: u8,
-      }
-
-
 /// Instruction builder for `CreateOperator`.
 ///
 /// ### Accounts:
@@ -105,8 +98,7 @@ pub struct CreateOperatorBuilder {
                 operator: Option<solana_pubkey::Pubkey>,
                 authority: Option<solana_pubkey::Pubkey>,
                 system_program: Option<solana_pubkey::Pubkey>,
-                        bump: Option<u8>,
-        __remaining_accounts: Vec<solana_instruction::AccountMeta>,
+                __remaining_accounts: Vec<solana_instruction::AccountMeta>,
 }
 
 impl CreateOperatorBuilder {
@@ -134,12 +126,7 @@ impl CreateOperatorBuilder {
                         self.system_program = Some(system_program);
                     self
     }
-                    #[inline(always)]
-      pub fn bump(&mut self, bump: u8) -> &mut Self {
-        self.bump = Some(bump);
-        self
-      }
-        /// Add an additional account to the instruction.
+            /// Add an additional account to the instruction.
   #[inline(always)]
   pub fn add_remaining_account(&mut self, account: solana_instruction::AccountMeta) -> &mut Self {
     self.__remaining_accounts.push(account);
@@ -159,11 +146,8 @@ impl CreateOperatorBuilder {
                                         authority: self.authority.expect("authority is not set"),
                                         system_program: self.system_program.unwrap_or(solana_pubkey::pubkey!("11111111111111111111111111111111")),
                       };
-          let args = CreateOperatorInstructionArgs {
-                                                              bump: self.bump.clone().expect("bump is not set"),
-                                    };
     
-    accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
+    accounts.instruction_with_remaining_accounts(&self.__remaining_accounts)
   }
 }
 
@@ -199,24 +183,20 @@ pub struct CreateOperatorCpi<'a, 'b> {
           
               
           pub system_program: &'b solana_account_info::AccountInfo<'a>,
-            /// The arguments for the instruction.
-    pub __args: CreateOperatorInstructionArgs,
-  }
+        }
 
 impl<'a, 'b> CreateOperatorCpi<'a, 'b> {
   pub fn new(
     program: &'b solana_account_info::AccountInfo<'a>,
           accounts: CreateOperatorCpiAccounts<'a, 'b>,
-              args: CreateOperatorInstructionArgs,
-      ) -> Self {
+          ) -> Self {
     Self {
       __program: program,
               payer: accounts.payer,
               operator: accounts.operator,
               authority: accounts.authority,
               system_program: accounts.system_program,
-                    __args: args,
-          }
+                }
   }
   #[inline(always)]
   pub fn invoke(&self) -> solana_program_error::ProgramResult {
@@ -262,9 +242,7 @@ impl<'a, 'b> CreateOperatorCpi<'a, 'b> {
           is_writable: remaining_account.2,
       })
     });
-    let mut data = borsh::to_vec(&CreateOperatorInstructionData::new()).unwrap();
-          let mut args = borsh::to_vec(&self.__args).unwrap();
-      data.append(&mut args);
+    let data = borsh::to_vec(&CreateOperatorInstructionData::new()).unwrap();
     
     let instruction = solana_instruction::Instruction {
       program_id: crate::COMMERCE_PROGRAM_ID,
@@ -308,8 +286,7 @@ impl<'a, 'b> CreateOperatorCpiBuilder<'a, 'b> {
               operator: None,
               authority: None,
               system_program: None,
-                                            bump: None,
-                    __remaining_accounts: Vec::new(),
+                                __remaining_accounts: Vec::new(),
     });
     Self { instruction }
   }
@@ -333,12 +310,7 @@ impl<'a, 'b> CreateOperatorCpiBuilder<'a, 'b> {
                         self.instruction.system_program = Some(system_program);
                     self
     }
-                    #[inline(always)]
-      pub fn bump(&mut self, bump: u8) -> &mut Self {
-        self.instruction.bump = Some(bump);
-        self
-      }
-        /// Add an additional account to the instruction.
+            /// Add an additional account to the instruction.
   #[inline(always)]
   pub fn add_remaining_account(&mut self, account: &'b solana_account_info::AccountInfo<'a>, is_writable: bool, is_signer: bool) -> &mut Self {
     self.instruction.__remaining_accounts.push((account, is_writable, is_signer));
@@ -360,9 +332,6 @@ impl<'a, 'b> CreateOperatorCpiBuilder<'a, 'b> {
   #[allow(clippy::clone_on_copy)]
   #[allow(clippy::vec_init_then_push)]
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program_error::ProgramResult {
-          let args = CreateOperatorInstructionArgs {
-                                                              bump: self.instruction.bump.clone().expect("bump is not set"),
-                                    };
         let instruction = CreateOperatorCpi {
         __program: self.instruction.__program,
                   
@@ -373,8 +342,7 @@ impl<'a, 'b> CreateOperatorCpiBuilder<'a, 'b> {
           authority: self.instruction.authority.expect("authority is not set"),
                   
           system_program: self.instruction.system_program.expect("system_program is not set"),
-                          __args: args,
-            };
+                    };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
   }
 }
@@ -386,8 +354,7 @@ struct CreateOperatorCpiBuilderInstruction<'a, 'b> {
                 operator: Option<&'b solana_account_info::AccountInfo<'a>>,
                 authority: Option<&'b solana_account_info::AccountInfo<'a>>,
                 system_program: Option<&'b solana_account_info::AccountInfo<'a>>,
-                        bump: Option<u8>,
-        /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
+                /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_account_info::AccountInfo<'a>, bool, bool)>,
 }
 
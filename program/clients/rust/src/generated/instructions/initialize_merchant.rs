@@ -5,6 +5,8 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
@@ -28,6 +30,16 @@ pub struct InitializeMerchant {
           
               
           pub system_program: solana_pubkey::Pubkey,
+                /// Event authority PDA
+
+    
+              
+          pub event_authority: solana_pubkey::Pubkey,
+                /// Commerce Program ID
+
+    
+              
+          pub commerce_program: solana_pubkey::Pubkey,
       }
 
 impl InitializeMerchant {
@@ -37,7 +49,7 @@ impl InitializeMerchant {
   #[allow(clippy::arithmetic_side_effects)]
   #[allow(clippy::vec_init_then_push)]
   pub fn instruction_with_remaining_accounts(&self, args: InitializeMerchantInstructionArgs, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
-    let mut accounts = Vec::with_capacity(5+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(7+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             self.payer,
             true
@@ -57,6 +69,14 @@ impl InitializeMerchant {
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             self.system_program,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.event_authority,
+            false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.commerce_program,
+            false
           ));
                       accounts.extend_from_slice(remaining_accounts);
     let mut data = borsh::to_vec(&InitializeMerchantInstructionData::new()).unwrap();
@@ -75,13 +95,13 @@ impl InitializeMerchant {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
  pub struct InitializeMerchantInstructionData {
             discriminator: u8,
-            }
+                  }
 
 impl InitializeMerchantInstructionData {
   pub fn new() -> Self {
     Self {
                         discriminator: 0,
-                                }
+                                              }
   }
 }
 
@@ -95,6 +115,7 @@ impl Default for InitializeMerchantInstructionData {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
  pub struct InitializeMerchantInstructionArgs {
                   pub bump: u8,
+                pub settlement_wallet_timelock_seconds: u64,
       }
 
 
@@ -107,6 +128,8 @@ impl Default for InitializeMerchantInstructionData {
                 ///   2. `[writable]` merchant
           ///   3. `[]` settlement_wallet
                 ///   4. `[optional]` system_program (default to `11111111111111111111111111111111`)
+                ///   5. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
+                ///   6. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
 #[derive(Clone, Debug, Default)]
 pub struct InitializeMerchantBuilder {
             payer: Option<solana_pubkey::Pubkey>,
@@ -114,7 +137,10 @@ pub struct InitializeMerchantBuilder {
                 merchant: Option<solana_pubkey::Pubkey>,
                 settlement_wallet: Option<solana_pubkey::Pubkey>,
                 system_program: Option<solana_pubkey::Pubkey>,
+                event_authority: Option<solana_pubkey::Pubkey>,
+                commerce_program: Option<solana_pubkey::Pubkey>,
                         bump: Option<u8>,
+                settlement_wallet_timelock_seconds: Option<u64>,
         __remaining_accounts: Vec<solana_instruction::AccountMeta>,
 }
 
@@ -147,11 +173,30 @@ impl InitializeMerchantBuilder {
     pub fn system_program(&mut self, system_program: solana_pubkey::Pubkey) -> &mut Self {
                         self.system_program = Some(system_program);
                     self
+    }
+            /// `[optional account, default to '3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1']`
+/// Event authority PDA
+#[inline(always)]
+    pub fn event_authority(&mut self, event_authority: solana_pubkey::Pubkey) -> &mut Self {
+                        self.event_authority = Some(event_authority);
+                    self
+    }
+            /// `[optional account, default to 'commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT']`
+/// Commerce Program ID
+#[inline(always)]
+    pub fn commerce_program(&mut self, commerce_program: solana_pubkey::Pubkey) -> &mut Self {
+                        self.commerce_program = Some(commerce_program);
+                    self
     }
                     #[inline(always)]
       pub fn bump(&mut self, bump: u8) -> &mut Self {
         self.bump = Some(bump);
         self
+      }
+                #[inline(always)]
+      pub fn settlement_wallet_timelock_seconds(&mut self, settlement_wallet_timelock_seconds: u64) -> &mut Self {
+        self.settlement_wallet_timelock_seconds = Some(settlement_wallet_timelock_seconds);
+        self
       }
         /// Add an additional account to the instruction.
   #[inline(always)]
@@ -173,9 +218,12 @@ impl InitializeMerchantBuilder {
                                         merchant: self.merchant.expect("merchant is not set"),
                                         settlement_wallet: self.settlement_wallet.expect("settlement_wallet is not set"),
                                         system_program: self.system_program.unwrap_or(solana_pubkey::pubkey!("11111111111111111111111111111111")),
+                                        event_authority: self.event_authority.unwrap_or(solana_pubkey::pubkey!("3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1")),
+                                        commerce_program: self.commerce_program.unwrap_or(solana_pubkey::pubkey!("commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT")),
                       };
           let args = InitializeMerchantInstructionArgs {
                                                               bump: self.bump.clone().expect("bump is not set"),
+                                                                  settlement_wallet_timelock_seconds: self.settlement_wallet_timelock_seconds.clone().expect("settlement_wallet_timelock_seconds is not set"),
                                     };
     
     accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -199,6 +247,16 @@ impl InitializeMerchantBuilder {
                 
                     
               pub system_program: &'b solana_account_info::AccountInfo<'a>,
+                        /// Event authority PDA
+
+      
+                    
+              pub event_authority: &'b solana_account_info::AccountInfo<'a>,
+                        /// Commerce Program ID
+
+      
+                    
+              pub commerce_program: &'b solana_account_info::AccountInfo<'a>,
             }
 
 /// `initialize_merchant` CPI instruction.
@@ -220,6 +278,16 @@ pub struct InitializeMerchantCpi<'a, 'b> {
           
               
           pub system_program: &'b solana_account_info::AccountInfo<'a>,
+                /// Event authority PDA
+
+    
+              
+          pub event_authority: &'b solana_account_info::AccountInfo<'a>,
+                /// Commerce Program ID
+
+    
+              
+          pub commerce_program: &'b solana_account_info::AccountInfo<'a>,
             /// The arguments for the instruction.
     pub __args: InitializeMerchantInstructionArgs,
   }
@@ -237,6 +305,8 @@ impl<'a, 'b> InitializeMerchantCpi<'a, 'b> {
               merchant: accounts.merchant,
               settlement_wallet: accounts.settlement_wallet,
               system_program: accounts.system_program,
+              event_authority: accounts.event_authority,
+              commerce_program: accounts.commerce_program,
                     __args: args,
           }
   }
@@ -260,7 +330,7 @@ impl<'a, 'b> InitializeMerchantCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program_error::ProgramResult {
-    let mut accounts = Vec::with_capacity(5+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(7+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             *self.payer.key,
             true
@@ -280,6 +350,14 @@ impl<'a, 'b> InitializeMerchantCpi<'a, 'b> {
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             *self.system_program.key,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.event_authority.key,
+            false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.commerce_program.key,
+            false
           ));
                       remaining_accounts.iter().for_each(|remaining_account| {
       accounts.push(solana_instruction::AccountMeta {
@@ -297,13 +375,15 @@ impl<'a, 'b> InitializeMerchantCpi<'a, 'b> {
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(6 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(8 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
                   account_infos.push(self.payer.clone());
                         account_infos.push(self.authority.clone());
                         account_infos.push(self.merchant.clone());
                         account_infos.push(self.settlement_wallet.clone());
                         account_infos.push(self.system_program.clone());
+                        account_infos.push(self.event_authority.clone());
+                        account_infos.push(self.commerce_program.clone());
               remaining_accounts.iter().for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
 
     if signers_seeds.is_empty() {
@@ -323,6 +403,8 @@ impl<'a, 'b> InitializeMerchantCpi<'a, 'b> {
                 ///   2. `[writable]` merchant
           ///   3. `[]` settlement_wallet
           ///   4. `[]` system_program
+          ///   5. `[]` event_authority
+          ///   6. `[]` commerce_program
 #[derive(Clone, Debug)]
 pub struct InitializeMerchantCpiBuilder<'a, 'b> {
   instruction: Box<InitializeMerchantCpiBuilderInstruction<'a, 'b>>,
@@ -337,7 +419,10 @@ impl<'a, 'b> InitializeMerchantCpiBuilder<'a, 'b> {
               merchant: None,
               settlement_wallet: None,
               system_program: None,
+              event_authority: None,
+              commerce_program: None,
                                             bump: None,
+                                settlement_wallet_timelock_seconds: None,
                     __remaining_accounts: Vec::new(),
     });
     Self { instruction }
@@ -366,11 +451,28 @@ impl<'a, 'b> InitializeMerchantCpiBuilder<'a, 'b> {
     pub fn system_program(&mut self, system_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.system_program = Some(system_program);
                     self
+    }
+      /// Event authority PDA
+#[inline(always)]
+    pub fn event_authority(&mut self, event_authority: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.event_authority = Some(event_authority);
+                    self
+    }
+      /// Commerce Program ID
+#[inline(always)]
+    pub fn commerce_program(&mut self, commerce_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.commerce_program = Some(commerce_program);
+                    self
     }
                     #[inline(always)]
       pub fn bump(&mut self, bump: u8) -> &mut Self {
         self.instruction.bump = Some(bump);
         self
+      }
+                #[inline(always)]
+      pub fn settlement_wallet_timelock_seconds(&mut self, settlement_wallet_timelock_seconds: u64) -> &mut Self {
+        self.instruction.settlement_wallet_timelock_seconds = Some(settlement_wallet_timelock_seconds);
+        self
       }
         /// Add an additional account to the instruction.
   #[inline(always)]
@@ -396,6 +498,7 @@ impl<'a, 'b> InitializeMerchantCpiBuilder<'a, 'b> {
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program_error::ProgramResult {
           let args = InitializeMerchantInstructionArgs {
                                                               bump: self.instruction.bump.clone().expect("bump is not set"),
+                                                                  settlement_wallet_timelock_seconds: self.instruction.settlement_wallet_timelock_seconds.clone().expect("settlement_wallet_timelock_seconds is not set"),
                                     };
         let instruction = InitializeMerchantCpi {
         __program: self.instruction.__program,
@@ -409,6 +512,10 @@ impl<'a, 'b> InitializeMerchantCpiBuilder<'a, 'b> {
           settlement_wallet: self.instruction.settlement_wallet.expect("settlement_wallet is not set"),
                   
           system_program: self.instruction.system_program.expect("system_program is not set"),
+                  
+          event_authority: self.instruction.event_authority.expect("event_authority is not set"),
+                  
+          commerce_program: self.instruction.commerce_program.expect("commerce_program is not set"),
                           __args: args,
             };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
@@ -423,7 +530,10 @@ struct InitializeMerchantCpiBuilderInstruction<'a, 'b> {
                 merchant: Option<&'b solana_account_info::AccountInfo<'a>>,
                 settlement_wallet: Option<&'b solana_account_info::AccountInfo<'a>>,
                 system_program: Option<&'b solana_account_info::AccountInfo<'a>>,
+                event_authority: Option<&'b solana_account_info::AccountInfo<'a>>,
+                commerce_program: Option<&'b solana_account_info::AccountInfo<'a>>,
                         bump: Option<u8>,
+                settlement_wallet_timelock_seconds: Option<u64>,
         /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_account_info::AccountInfo<'a>, bool, bool)>,
 }
@@ -5,6 +5,8 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use crate::generated::types::FeeType;
 use crate::generated::types::PolicyData;
 use solana_pubkey::Pubkey;
@@ -42,6 +44,16 @@ pub struct InitializeMerchantOperatorConfig {
           
               
           pub system_program: solana_pubkey::Pubkey,
+                /// Event authority PDA
+
+    
+              
+          pub event_authority: solana_pubkey::Pubkey,
+                /// Commerce Program ID
+
+    
+              
+          pub commerce_program: solana_pubkey::Pubkey,
       }
 
 impl InitializeMerchantOperatorConfig {
@@ -51,7 +63,7 @@ impl InitializeMerchantOperatorConfig {
   #[allow(clippy::arithmetic_side_effects)]
   #[allow(clippy::vec_init_then_push)]
   pub fn instruction_with_remaining_accounts(&self, args: InitializeMerchantOperatorConfigInstructionArgs, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
-    let mut accounts = Vec::with_capacity(6+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(8+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             self.payer,
             true
@@ -75,6 +87,14 @@ impl InitializeMerchantOperatorConfig {
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             self.system_program,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.event_authority,
+            false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.commerce_program,
+            false
           ));
                       accounts.extend_from_slice(remaining_accounts);
     let mut data = borsh::to_vec(&InitializeMerchantOperatorConfigInstructionData::new()).unwrap();
@@ -132,6 +152,8 @@ impl Default for InitializeMerchantOperatorConfigInstructionData {
           ///   3. `[]` operator
                 ///   4. `[writable]` config
                 ///   5. `[optional]` system_program (default to `11111111111111111111111111111111`)
+                ///   6. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
+                ///   7. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
 #[derive(Clone, Debug, Default)]
 pub struct InitializeMerchantOperatorConfigBuilder {
             payer: Option<solana_pubkey::Pubkey>,
@@ -140,6 +162,8 @@ pub struct InitializeMerchantOperatorConfigBuilder {
                 operator: Option<solana_pubkey::Pubkey>,
                 config: Option<solana_pubkey::Pubkey>,
                 system_program: Option<solana_pubkey::Pubkey>,
+                event_authority: Option<solana_pubkey::Pubkey>,
+                commerce_program: Option<solana_pubkey::Pubkey>,
                         version: Option<u32>,
                 bump: Option<u8>,
                 operator_fee: Option<u64>,
@@ -188,6 +212,20 @@ impl InitializeMerchantOperatorConfigBuilder {
     pub fn system_program(&mut self, system_program: solana_pubkey::Pubkey) -> &mut Self {
                         self.system_program = Some(system_program);
                     self
+    }
+            /// `[optional account, default to '3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1']`
+/// Event authority PDA
+#[inline(always)]
+    pub fn event_authority(&mut self, event_authority: solana_pubkey::Pubkey) -> &mut Self {
+                        self.event_authority = Some(event_authority);
+                    self
+    }
+            /// `[optional account, default to 'commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT']`
+/// Commerce Program ID
+#[inline(always)]
+    pub fn commerce_program(&mut self, commerce_program: solana_pubkey::Pubkey) -> &mut Self {
+                        self.commerce_program = Some(commerce_program);
+                    self
     }
                     #[inline(always)]
       pub fn version(&mut self, version: u32) -> &mut Self {
@@ -245,6 +283,8 @@ impl InitializeMerchantOperatorConfigBuilder {
                                         operator: self.operator.expect("operator is not set"),
                                         config: self.config.expect("config is not set"),
                                         system_program: self.system_program.unwrap_or(solana_pubkey::pubkey!("11111111111111111111111111111111")),
+                                        event_authority: self.event_authority.unwrap_or(solana_pubkey::pubkey!("3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1")),
+                                        commerce_program: self.commerce_program.unwrap_or(solana_pubkey::pubkey!("commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT")),
                       };
           let args = InitializeMerchantOperatorConfigInstructionArgs {
                                                               version: self.version.clone().expect("version is not set"),
@@ -288,6 +328,16 @@ impl InitializeMerchantOperatorConfigBuilder {
                 
                     
               pub system_program: &'b solana_account_info::AccountInfo<'a>,
+                        /// Event authority PDA
+
+      
+                    
+              pub event_authority: &'b solana_account_info::AccountInfo<'a>,
+                        /// Commerce Program ID
+
+      
+                    
+              pub commerce_program: &'b solana_account_info::AccountInfo<'a>,
             }
 
 /// `initialize_merchant_operator_config` CPI instruction.
@@ -320,6 +370,16 @@ pub struct InitializeMerchantOperatorConfigCpi<'a, 'b> {
           
               
           pub system_program: &'b solana_account_info::AccountInfo<'a>,
+                /// Event authority PDA
+
+    
+              
+          pub event_authority: &'b solana_account_info::AccountInfo<'a>,
+                /// Commerce Program ID
+
+    
+              
+          pub commerce_program: &'b solana_account_info::AccountInfo<'a>,
             /// The arguments for the instruction.
     pub __args: InitializeMerchantOperatorConfigInstructionArgs,
   }
@@ -338,6 +398,8 @@ impl<'a, 'b> InitializeMerchantOperatorConfigCpi<'a, 'b> {
               operator: accounts.operator,
               config: accounts.config,
               system_program: accounts.system_program,
+              event_authority: accounts.event_authority,
+              commerce_program: accounts.commerce_program,
                     __args: args,
           }
   }
@@ -361,7 +423,7 @@ impl<'a, 'b> InitializeMerchantOperatorConfigCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program_error::ProgramResult {
-    let mut accounts = Vec::with_capacity(6+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(8+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             *self.payer.key,
             true
@@ -385,6 +447,14 @@ impl<'a, 'b> InitializeMerchantOperatorConfigCpi<'a, 'b> {
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             *self.system_program.key,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.event_authority.key,
+            false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.commerce_program.key,
+            false
           ));
                       remaining_accounts.iter().for_each(|remaining_account| {
       accounts.push(solana_instruction::AccountMeta {
@@ -402,7 +472,7 @@ impl<'a, 'b> InitializeMerchantOperatorConfigCpi<'a, 'b> {
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(7 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(9 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
                   account_infos.push(self.payer.clone());
                         account_infos.push(self.authority.clone());
@@ -410,6 +480,8 @@ impl<'a, 'b> InitializeMerchantOperatorConfigCpi<'a, 'b> {
                         account_infos.push(self.operator.clone());
                         account_infos.push(self.config.clone());
                         account_infos.push(self.system_program.clone());
+                        account_infos.push(self.event_authority.clone());
+                        account_infos.push(self.commerce_program.clone());
               remaining_accounts.iter().for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
 
     if signers_seeds.is_empty() {
@@ -430,6 +502,8 @@ impl<'a, 'b> InitializeMerchantOperatorConfigCpi<'a, 'b> {
           ///   3. `[]` operator
                 ///   4. `[writable]` config
           ///   5. `[]` system_program
+          ///   6. `[]` event_authority
+          ///   7. `[]` commerce_program
 #[derive(Clone, Debug)]
 pub struct InitializeMerchantOperatorConfigCpiBuilder<'a, 'b> {
   instruction: Box<InitializeMerchantOperatorConfigCpiBuilderInstruction<'a, 'b>>,
@@ -445,6 +519,8 @@ impl<'a, 'b> InitializeMerchantOperatorConfigCpiBuilder<'a, 'b> {
               operator: None,
               config: None,
               system_program: None,
+              event_authority: None,
+              commerce_program: None,
                                             version: None,
                                 bump: None,
                                 operator_fee: None,
@@ -489,6 +565,18 @@ impl<'a, 'b> InitializeMerchantOperatorConfigCpiBuilder<'a, 'b> {
     pub fn system_program(&mut self, system_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.system_program = Some(system_program);
                     self
+    }
+      /// Event authority PDA
+#[inline(always)]
+    pub fn event_authority(&mut self, event_authority: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.event_authority = Some(event_authority);
+                    self
+    }
+      /// Commerce Program ID
+#[inline(always)]
+    pub fn commerce_program(&mut self, commerce_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.commerce_program = Some(commerce_program);
+                    self
     }
                     #[inline(always)]
       pub fn version(&mut self, version: u32) -> &mut Self {
@@ -570,6 +658,10 @@ impl<'a, 'b> InitializeMerchantOperatorConfigCpiBuilder<'a, 'b> {
           config: self.instruction.config.expect("config is not set"),
                   
           system_program: self.instruction.system_program.expect("system_program is not set"),
+                  
+          event_authority: self.instruction.event_authority.expect("event_authority is not set"),
+                  
+          commerce_program: self.instruction.commerce_program.expect("commerce_program is not set"),
                           __args: args,
             };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
@@ -585,6 +677,8 @@ struct InitializeMerchantOperatorConfigCpiBuilderInstruction<'a, 'b> {
                 operator: Option<&'b solana_account_info::AccountInfo<'a>>,
                 config: Option<&'b solana_account_info::AccountInfo<'a>>,
                 system_program: Option<&'b solana_account_info::AccountInfo<'a>>,
+                event_authority: Option<&'b solana_account_info::AccountInfo<'a>>,
+                commerce_program: Option<&'b solana_account_info::AccountInfo<'a>>,
                         version: Option<u32>,
                 bump: Option<u8>,
                 operator_fee: Option<u64>,
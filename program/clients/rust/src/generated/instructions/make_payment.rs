@@ -5,6 +5,11 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use crate::generated::types::FeeOverride;
+use crate::generated::types::DenylistProof;
+use crate::generated::types::EncryptedNote;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
@@ -42,17 +47,30 @@ pub struct MakePayment {
           
               
           pub buyer_ata: solana_pubkey::Pubkey,
+                /// Escrow authority PDA
+
+    
+              
+          pub escrow_authority: solana_pubkey::Pubkey,
           
               
           pub merchant_escrow_ata: solana_pubkey::Pubkey,
           
               
           pub merchant_settlement_ata: solana_pubkey::Pubkey,
+                /// Merchant settlement wallet, owner of merchant_settlement_ata
+
+    
+              
+          pub settlement_wallet: solana_pubkey::Pubkey,
           
               
           pub token_program: solana_pubkey::Pubkey,
           
               
+          pub associated_token_program: solana_pubkey::Pubkey,
+          
+              
           pub system_program: solana_pubkey::Pubkey,
                 /// Event authority PDA
 
@@ -73,7 +91,7 @@ impl MakePayment {
   #[allow(clippy::arithmetic_side_effects)]
   #[allow(clippy::vec_init_then_push)]
   pub fn instruction_with_remaining_accounts(&self, args: MakePaymentInstructionArgs, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
-    let mut accounts = Vec::with_capacity(15+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(18+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             self.payer,
             true
@@ -109,6 +127,10 @@ impl MakePayment {
                                           accounts.push(solana_instruction::AccountMeta::new(
             self.buyer_ata,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.escrow_authority,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new(
             self.merchant_escrow_ata,
@@ -117,10 +139,18 @@ impl MakePayment {
                                           accounts.push(solana_instruction::AccountMeta::new(
             self.merchant_settlement_ata,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.settlement_wallet,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             self.token_program,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.associated_token_program,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             self.system_program,
@@ -151,13 +181,13 @@ impl MakePayment {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
  pub struct MakePaymentInstructionData {
             discriminator: u8,
-                        }
+                                          }
 
 impl MakePaymentInstructionData {
   pub fn new() -> Self {
     Self {
                         discriminator: 3,
-                                                            }
+                                                                                                      }
   }
 }
 
@@ -172,7 +202,11 @@ impl Default for MakePaymentInstructionData {
  pub struct MakePaymentInstructionArgs {
                   pub order_id: u32,
                 pub amount: u64,
-                pub bump: u8,
+                pub idempotency_key: [u8; 16],
+                pub fee_override: Option<FeeOverride>,
+                pub authorize: bool,
+                pub denylist_proof: Option<DenylistProof>,
+                pub note: Option<EncryptedNote>,
       }
 
 
@@ -189,12 +223,15 @@ impl Default for MakePaymentInstructionData {
                 ///   6. `[writable]` merchant_operator_config
           ///   7. `[]` mint
                 ///   8. `[writable]` buyer_ata
-                ///   9. `[writable]` merchant_escrow_ata
-                ///   10. `[writable]` merchant_settlement_ata
-                ///   11. `[optional]` token_program (default to `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`)
-                ///   12. `[optional]` system_program (default to `11111111111111111111111111111111`)
-                ///   13. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
-                ///   14. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
+          ///   9. `[]` escrow_authority
+                ///   10. `[writable]` merchant_escrow_ata
+                ///   11. `[writable]` merchant_settlement_ata
+          ///   12. `[]` settlement_wallet
+                ///   13. `[optional]` token_program (default to `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`)
+                ///   14. `[optional]` associated_token_program (default to `ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL`)
+                ///   15. `[optional]` system_program (default to `11111111111111111111111111111111`)
+                ///   16. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
+                ///   17. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
 #[derive(Clone, Debug, Default)]
 pub struct MakePaymentBuilder {
             payer: Option<solana_pubkey::Pubkey>,
@@ -206,15 +243,22 @@ pub struct MakePaymentBuilder {
                 merchant_operator_config: Option<solana_pubkey::Pubkey>,
                 mint: Option<solana_pubkey::Pubkey>,
                 buyer_ata: Option<solana_pubkey::Pubkey>,
+                escrow_authority: Option<solana_pubkey::Pubkey>,
                 merchant_escrow_ata: Option<solana_pubkey::Pubkey>,
                 merchant_settlement_ata: Option<solana_pubkey::Pubkey>,
+                settlement_wallet: Option<solana_pubkey::Pubkey>,
                 token_program: Option<solana_pubkey::Pubkey>,
+                associated_token_program: Option<solana_pubkey::Pubkey>,
                 system_program: Option<solana_pubkey::Pubkey>,
                 event_authority: Option<solana_pubkey::Pubkey>,
                 commerce_program: Option<solana_pubkey::Pubkey>,
                         order_id: Option<u32>,
                 amount: Option<u64>,
-                bump: Option<u8>,
+                idempotency_key: Option<[u8; 16]>,
+                fee_override: Option<FeeOverride>,
+                authorize: Option<bool>,
+                denylist_proof: Option<DenylistProof>,
+                note: Option<EncryptedNote>,
         __remaining_accounts: Vec<solana_instruction::AccountMeta>,
 }
 
@@ -267,6 +311,12 @@ impl MakePaymentBuilder {
     pub fn buyer_ata(&mut self, buyer_ata: solana_pubkey::Pubkey) -> &mut Self {
                         self.buyer_ata = Some(buyer_ata);
                     self
+    }
+            /// Escrow authority PDA
+#[inline(always)]
+    pub fn escrow_authority(&mut self, escrow_authority: solana_pubkey::Pubkey) -> &mut Self {
+                        self.escrow_authority = Some(escrow_authority);
+                    self
     }
             #[inline(always)]
     pub fn merchant_escrow_ata(&mut self, merchant_escrow_ata: solana_pubkey::Pubkey) -> &mut Self {
@@ -277,12 +327,24 @@ impl MakePaymentBuilder {
     pub fn merchant_settlement_ata(&mut self, merchant_settlement_ata: solana_pubkey::Pubkey) -> &mut Self {
                         self.merchant_settlement_ata = Some(merchant_settlement_ata);
                     self
+    }
+            /// Merchant settlement wallet, owner of merchant_settlement_ata
+#[inline(always)]
+    pub fn settlement_wallet(&mut self, settlement_wallet: solana_pubkey::Pubkey) -> &mut Self {
+                        self.settlement_wallet = Some(settlement_wallet);
+                    self
     }
             /// `[optional account, default to 'TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA']`
 #[inline(always)]
     pub fn token_program(&mut self, token_program: solana_pubkey::Pubkey) -> &mut Self {
                         self.token_program = Some(token_program);
                     self
+    }
+            /// `[optional account, default to 'ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL']`
+#[inline(always)]
+    pub fn associated_token_program(&mut self, associated_token_program: solana_pubkey::Pubkey) -> &mut Self {
+                        self.associated_token_program = Some(associated_token_program);
+                    self
     }
             /// `[optional account, default to '11111111111111111111111111111111']`
 #[inline(always)]
@@ -315,8 +377,32 @@ impl MakePaymentBuilder {
         self
       }
                 #[inline(always)]
-      pub fn bump(&mut self, bump: u8) -> &mut Self {
-        self.bump = Some(bump);
+      pub fn idempotency_key(&mut self, idempotency_key: [u8; 16]) -> &mut Self {
+        self.idempotency_key = Some(idempotency_key);
+        self
+      }
+                /// `[optional argument]`
+#[inline(always)]
+      pub fn fee_override(&mut self, fee_override: FeeOverride) -> &mut Self {
+        self.fee_override = Some(fee_override);
+        self
+      }
+                /// `[optional argument, defaults to 'false']`
+#[inline(always)]
+      pub fn authorize(&mut self, authorize: bool) -> &mut Self {
+        self.authorize = Some(authorize);
+        self
+      }
+                /// `[optional argument]`
+#[inline(always)]
+      pub fn denylist_proof(&mut self, denylist_proof: DenylistProof) -> &mut Self {
+        self.denylist_proof = Some(denylist_proof);
+        self
+      }
+                /// `[optional argument]`
+#[inline(always)]
+      pub fn note(&mut self, note: EncryptedNote) -> &mut Self {
+        self.note = Some(note);
         self
       }
         /// Add an additional account to the instruction.
@@ -343,9 +429,12 @@ impl MakePaymentBuilder {
                                         merchant_operator_config: self.merchant_operator_config.expect("merchant_operator_config is not set"),
                                         mint: self.mint.expect("mint is not set"),
                                         buyer_ata: self.buyer_ata.expect("buyer_ata is not set"),
+                                        escrow_authority: self.escrow_authority.expect("escrow_authority is not set"),
                                         merchant_escrow_ata: self.merchant_escrow_ata.expect("merchant_escrow_ata is not set"),
                                         merchant_settlement_ata: self.merchant_settlement_ata.expect("merchant_settlement_ata is not set"),
+                                        settlement_wallet: self.settlement_wallet.expect("settlement_wallet is not set"),
                                         token_program: self.token_program.unwrap_or(solana_pubkey::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")),
+                                        associated_token_program: self.associated_token_program.unwrap_or(solana_pubkey::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")),
                                         system_program: self.system_program.unwrap_or(solana_pubkey::pubkey!("11111111111111111111111111111111")),
                                         event_authority: self.event_authority.unwrap_or(solana_pubkey::pubkey!("3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1")),
                                         commerce_program: self.commerce_program.unwrap_or(solana_pubkey::pubkey!("commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT")),
@@ -353,7 +442,11 @@ impl MakePaymentBuilder {
           let args = MakePaymentInstructionArgs {
                                                               order_id: self.order_id.clone().expect("order_id is not set"),
                                                                   amount: self.amount.clone().expect("amount is not set"),
-                                                                  bump: self.bump.clone().expect("bump is not set"),
+                                                                  idempotency_key: self.idempotency_key.clone().expect("idempotency_key is not set"),
+                                                                  fee_override: self.fee_override.clone(),
+                                                                  authorize: self.authorize.clone().unwrap_or(false),
+                                                                  denylist_proof: self.denylist_proof.clone(),
+                                                                  note: self.note.clone(),
                                     };
     
     accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -391,17 +484,30 @@ impl MakePaymentBuilder {
                 
                     
               pub buyer_ata: &'b solana_account_info::AccountInfo<'a>,
+                        /// Escrow authority PDA
+
+      
+                    
+              pub escrow_authority: &'b solana_account_info::AccountInfo<'a>,
                 
                     
               pub merchant_escrow_ata: &'b solana_account_info::AccountInfo<'a>,
                 
                     
               pub merchant_settlement_ata: &'b solana_account_info::AccountInfo<'a>,
+                        /// Merchant settlement wallet, owner of merchant_settlement_ata
+
+      
+                    
+              pub settlement_wallet: &'b solana_account_info::AccountInfo<'a>,
                 
                     
               pub token_program: &'b solana_account_info::AccountInfo<'a>,
                 
                     
+              pub associated_token_program: &'b solana_account_info::AccountInfo<'a>,
+                
+                    
               pub system_program: &'b solana_account_info::AccountInfo<'a>,
                         /// Event authority PDA
 
@@ -448,17 +554,30 @@ pub struct MakePaymentCpi<'a, 'b> {
           
               
           pub buyer_ata: &'b solana_account_info::AccountInfo<'a>,
+                /// Escrow authority PDA
+
+    
+              
+          pub escrow_authority: &'b solana_account_info::AccountInfo<'a>,
           
               
           pub merchant_escrow_ata: &'b solana_account_info::AccountInfo<'a>,
           
               
           pub merchant_settlement_ata: &'b solana_account_info::AccountInfo<'a>,
+                /// Merchant settlement wallet, owner of merchant_settlement_ata
+
+    
+              
+          pub settlement_wallet: &'b solana_account_info::AccountInfo<'a>,
           
               
           pub token_program: &'b solana_account_info::AccountInfo<'a>,
           
               
+          pub associated_token_program: &'b solana_account_info::AccountInfo<'a>,
+          
+              
           pub system_program: &'b solana_account_info::AccountInfo<'a>,
                 /// Event authority PDA
 
@@ -491,9 +610,12 @@ impl<'a, 'b> MakePaymentCpi<'a, 'b> {
               merchant_operator_config: accounts.merchant_operator_config,
               mint: accounts.mint,
               buyer_ata: accounts.buyer_ata,
+              escrow_authority: accounts.escrow_authority,
               merchant_escrow_ata: accounts.merchant_escrow_ata,
               merchant_settlement_ata: accounts.merchant_settlement_ata,
+              settlement_wallet: accounts.settlement_wallet,
               token_program: accounts.token_program,
+              associated_token_program: accounts.associated_token_program,
               system_program: accounts.system_program,
               event_authority: accounts.event_authority,
               commerce_program: accounts.commerce_program,
@@ -520,7 +642,7 @@ impl<'a, 'b> MakePaymentCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program_error::ProgramResult {
-    let mut accounts = Vec::with_capacity(15+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(18+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             *self.payer.key,
             true
@@ -556,6 +678,10 @@ impl<'a, 'b> MakePaymentCpi<'a, 'b> {
                                           accounts.push(solana_instruction::AccountMeta::new(
             *self.buyer_ata.key,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.escrow_authority.key,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new(
             *self.merchant_escrow_ata.key,
@@ -564,10 +690,18 @@ impl<'a, 'b> MakePaymentCpi<'a, 'b> {
                                           accounts.push(solana_instruction::AccountMeta::new(
             *self.merchant_settlement_ata.key,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.settlement_wallet.key,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             *self.token_program.key,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.associated_token_program.key,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             *self.system_program.key,
@@ -597,7 +731,7 @@ impl<'a, 'b> MakePaymentCpi<'a, 'b> {
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(16 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(19 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
                   account_infos.push(self.payer.clone());
                         account_infos.push(self.payment.clone());
@@ -608,9 +742,12 @@ impl<'a, 'b> MakePaymentCpi<'a, 'b> {
                         account_infos.push(self.merchant_operator_config.clone());
                         account_infos.push(self.mint.clone());
                         account_infos.push(self.buyer_ata.clone());
+                        account_infos.push(self.escrow_authority.clone());
                         account_infos.push(self.merchant_escrow_ata.clone());
                         account_infos.push(self.merchant_settlement_ata.clone());
+                        account_infos.push(self.settlement_wallet.clone());
                         account_infos.push(self.token_program.clone());
+                        account_infos.push(self.associated_token_program.clone());
                         account_infos.push(self.system_program.clone());
                         account_infos.push(self.event_authority.clone());
                         account_infos.push(self.commerce_program.clone());
@@ -637,12 +774,15 @@ impl<'a, 'b> MakePaymentCpi<'a, 'b> {
                 ///   6. `[writable]` merchant_operator_config
           ///   7. `[]` mint
                 ///   8. `[writable]` buyer_ata
-                ///   9. `[writable]` merchant_escrow_ata
-                ///   10. `[writable]` merchant_settlement_ata
-          ///   11. `[]` token_program
-          ///   12. `[]` system_program
-          ///   13. `[]` event_authority
-          ///   14. `[]` commerce_program
+          ///   9. `[]` escrow_authority
+                ///   10. `[writable]` merchant_escrow_ata
+                ///   11. `[writable]` merchant_settlement_ata
+          ///   12. `[]` settlement_wallet
+          ///   13. `[]` token_program
+          ///   14. `[]` associated_token_program
+          ///   15. `[]` system_program
+          ///   16. `[]` event_authority
+          ///   17. `[]` commerce_program
 #[derive(Clone, Debug)]
 pub struct MakePaymentCpiBuilder<'a, 'b> {
   instruction: Box<MakePaymentCpiBuilderInstruction<'a, 'b>>,
@@ -661,15 +801,22 @@ impl<'a, 'b> MakePaymentCpiBuilder<'a, 'b> {
               merchant_operator_config: None,
               mint: None,
               buyer_ata: None,
+              escrow_authority: None,
               merchant_escrow_ata: None,
               merchant_settlement_ata: None,
+              settlement_wallet: None,
               token_program: None,
+              associated_token_program: None,
               system_program: None,
               event_authority: None,
               commerce_program: None,
                                             order_id: None,
                                 amount: None,
-                                bump: None,
+                                idempotency_key: None,
+                                fee_override: None,
+                                authorize: None,
+                                denylist_proof: None,
+                                note: None,
                     __remaining_accounts: Vec::new(),
     });
     Self { instruction }
@@ -719,6 +866,12 @@ impl<'a, 'b> MakePaymentCpiBuilder<'a, 'b> {
     pub fn buyer_ata(&mut self, buyer_ata: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.buyer_ata = Some(buyer_ata);
                     self
+    }
+      /// Escrow authority PDA
+#[inline(always)]
+    pub fn escrow_authority(&mut self, escrow_authority: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.escrow_authority = Some(escrow_authority);
+                    self
     }
       #[inline(always)]
     pub fn merchant_escrow_ata(&mut self, merchant_escrow_ata: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
@@ -729,6 +882,12 @@ impl<'a, 'b> MakePaymentCpiBuilder<'a, 'b> {
     pub fn merchant_settlement_ata(&mut self, merchant_settlement_ata: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.merchant_settlement_ata = Some(merchant_settlement_ata);
                     self
+    }
+      /// Merchant settlement wallet, owner of merchant_settlement_ata
+#[inline(always)]
+    pub fn settlement_wallet(&mut self, settlement_wallet: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.settlement_wallet = Some(settlement_wallet);
+                    self
     }
       #[inline(always)]
     pub fn token_program(&mut self, token_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
@@ -736,6 +895,11 @@ impl<'a, 'b> MakePaymentCpiBuilder<'a, 'b> {
                     self
     }
       #[inline(always)]
+    pub fn associated_token_program(&mut self, associated_token_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.associated_token_program = Some(associated_token_program);
+                    self
+    }
+      #[inline(always)]
     pub fn system_program(&mut self, system_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.system_program = Some(system_program);
                     self
@@ -763,8 +927,32 @@ impl<'a, 'b> MakePaymentCpiBuilder<'a, 'b> {
         self
       }
                 #[inline(always)]
-      pub fn bump(&mut self, bump: u8) -> &mut Self {
-        self.instruction.bump = Some(bump);
+      pub fn idempotency_key(&mut self, idempotency_key: [u8; 16]) -> &mut Self {
+        self.instruction.idempotency_key = Some(idempotency_key);
+        self
+      }
+                /// `[optional argument]`
+#[inline(always)]
+      pub fn fee_override(&mut self, fee_override: FeeOverride) -> &mut Self {
+        self.instruction.fee_override = Some(fee_override);
+        self
+      }
+                /// `[optional argument, defaults to 'false']`
+#[inline(always)]
+      pub fn authorize(&mut self, authorize: bool) -> &mut Self {
+        self.instruction.authorize = Some(authorize);
+        self
+      }
+                /// `[optional argument]`
+#[inline(always)]
+      pub fn denylist_proof(&mut self, denylist_proof: DenylistProof) -> &mut Self {
+        self.instruction.denylist_proof = Some(denylist_proof);
+        self
+      }
+                /// `[optional argument]`
+#[inline(always)]
+      pub fn note(&mut self, note: EncryptedNote) -> &mut Self {
+        self.instruction.note = Some(note);
         self
       }
         /// Add an additional account to the instruction.
@@ -792,7 +980,11 @@ impl<'a, 'b> MakePaymentCpiBuilder<'a, 'b> {
           let args = MakePaymentInstructionArgs {
                                                               order_id: self.instruction.order_id.clone().expect("order_id is not set"),
                                                                   amount: self.instruction.amount.clone().expect("amount is not set"),
-                                                                  bump: self.instruction.bump.clone().expect("bump is not set"),
+                                                                  idempotency_key: self.instruction.idempotency_key.clone().expect("idempotency_key is not set"),
+                                                                  fee_override: self.instruction.fee_override.clone(),
+                                                                  authorize: self.instruction.authorize.clone().unwrap_or(false),
+                                                                  denylist_proof: self.instruction.denylist_proof.clone(),
+                                                                  note: self.instruction.note.clone(),
                                     };
         let instruction = MakePaymentCpi {
         __program: self.instruction.__program,
@@ -815,12 +1007,18 @@ impl<'a, 'b> MakePaymentCpiBuilder<'a, 'b> {
                   
           buyer_ata: self.instruction.buyer_ata.expect("buyer_ata is not set"),
                   
+          escrow_authority: self.instruction.escrow_authority.expect("escrow_authority is not set"),
+                  
           merchant_escrow_ata: self.instruction.merchant_escrow_ata.expect("merchant_escrow_ata is not set"),
                   
           merchant_settlement_ata: self.instruction.merchant_settlement_ata.expect("merchant_settlement_ata is not set"),
                   
+          settlement_wallet: self.instruction.settlement_wallet.expect("settlement_wallet is not set"),
+                  
           token_program: self.instruction.token_program.expect("token_program is not set"),
                   
+          associated_token_program: self.instruction.associated_token_program.expect("associated_token_program is not set"),
+                  
           system_program: self.instruction.system_program.expect("system_program is not set"),
                   
           event_authority: self.instruction.event_authority.expect("event_authority is not set"),
@@ -844,15 +1042,22 @@ struct MakePaymentCpiBuilderInstruction<'a, 'b> {
                 merchant_operator_config: Option<&'b solana_account_info::AccountInfo<'a>>,
                 mint: Option<&'b solana_account_info::AccountInfo<'a>>,
                 buyer_ata: Option<&'b solana_account_info::AccountInfo<'a>>,
+                escrow_authority: Option<&'b solana_account_info::AccountInfo<'a>>,
                 merchant_escrow_ata: Option<&'b solana_account_info::AccountInfo<'a>>,
                 merchant_settlement_ata: Option<&'b solana_account_info::AccountInfo<'a>>,
+                settlement_wallet: Option<&'b solana_account_info::AccountInfo<'a>>,
                 token_program: Option<&'b solana_account_info::AccountInfo<'a>>,
+                associated_token_program: Option<&'b solana_account_info::AccountInfo<'a>>,
                 system_program: Option<&'b solana_account_info::AccountInfo<'a>>,
                 event_authority: Option<&'b solana_account_info::AccountInfo<'a>>,
                 commerce_program: Option<&'b solana_account_info::AccountInfo<'a>>,
                         order_id: Option<u32>,
                 amount: Option<u64>,
-                bump: Option<u8>,
+                idempotency_key: Option<[u8; 16]>,
+                fee_override: Option<FeeOverride>,
+                authorize: Option<bool>,
+                denylist_proof: Option<DenylistProof>,
+                note: Option<EncryptedNote>,
         /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_account_info::AccountInfo<'a>, bool, bool)>,
 }
@@ -5,27 +5,121 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+  pub(crate) mod r#accept_merchant_authority;
+  pub(crate) mod r#accept_operator_authority;
+  pub(crate) mod r#add_accepted_currency;
+  pub(crate) mod r#add_policy;
+  pub(crate) mod r#add_to_freeze_list;
+  pub(crate) mod r#annotate_payment;
+  pub(crate) mod r#approve_refund;
+  pub(crate) mod r#capture_payment;
+  pub(crate) mod r#chargeback_payment;
+  pub(crate) mod r#claim_refund;
   pub(crate) mod r#clear_payment;
+  pub(crate) mod r#clear_payment_compressed;
+  pub(crate) mod r#clear_payment_with_conversion;
+  pub(crate) mod r#close_escrow_ata;
+  pub(crate) mod r#close_merchant_operator_config;
   pub(crate) mod r#close_payment;
+  pub(crate) mod r#close_payment_note;
+  pub(crate) mod r#commit_settlement_wallet;
   pub(crate) mod r#create_operator;
+  pub(crate) mod r#create_order;
+  pub(crate) mod r#create_quote;
+  pub(crate) mod r#decommission_config;
   pub(crate) mod r#emit_event;
+  pub(crate) mod r#execute_settlement_batch;
+  pub(crate) mod r#get_config_summary;
+  pub(crate) mod r#heartbeat;
+  pub(crate) mod r#initialize_buyer_index;
+  pub(crate) mod r#initialize_config_registry_page;
   pub(crate) mod r#initialize_merchant;
   pub(crate) mod r#initialize_merchant_operator_config;
+  pub(crate) mod r#initialize_merchant_profile;
+  pub(crate) mod r#initialize_merchant_stats;
+  pub(crate) mod r#initialize_merchant_with_config;
+  pub(crate) mod r#initialize_operator_delegate;
+  pub(crate) mod r#initialize_operator_freeze_list;
+  pub(crate) mod r#initialize_payment_tree;
+  pub(crate) mod r#initialize_risk_state;
+  pub(crate) mod r#initialize_settlement_batch;
   pub(crate) mod r#make_payment;
+  pub(crate) mod r#make_payment_compressed;
+  pub(crate) mod r#migrate_escrow;
+  pub(crate) mod r#migrate_payment;
+  pub(crate) mod r#post_conversion_rate;
+  pub(crate) mod r#propose_merchant_authority;
+  pub(crate) mod r#propose_operator_authority;
+  pub(crate) mod r#propose_settlement_wallet;
   pub(crate) mod r#refund_payment;
-  pub(crate) mod r#update_merchant_authority;
+  pub(crate) mod r#refund_payment_with_conversion;
+  pub(crate) mod r#refund_settled_payment;
+  pub(crate) mod r#remove_from_freeze_list;
+  pub(crate) mod r#revoke_operator_delegate;
+  pub(crate) mod r#set_settlement_wallet_timelock;
+  pub(crate) mod r#set_webhook_commitment;
+  pub(crate) mod r#sweep_closed_payments;
+  pub(crate) mod r#update_merchant_profile;
   pub(crate) mod r#update_merchant_settlement_wallet;
-  pub(crate) mod r#update_operator_authority;
+  pub(crate) mod r#update_operator_delegate;
+  pub(crate) mod r#upgrade_account;
 
+  pub use self::r#accept_merchant_authority::*;
+  pub use self::r#accept_operator_authority::*;
+  pub use self::r#add_accepted_currency::*;
+  pub use self::r#add_policy::*;
+  pub use self::r#add_to_freeze_list::*;
+  pub use self::r#annotate_payment::*;
+  pub use self::r#approve_refund::*;
+  pub use self::r#capture_payment::*;
+  pub use self::r#chargeback_payment::*;
+  pub use self::r#claim_refund::*;
   pub use self::r#clear_payment::*;
+  pub use self::r#clear_payment_compressed::*;
+  pub use self::r#clear_payment_with_conversion::*;
+  pub use self::r#close_escrow_ata::*;
+  pub use self::r#close_merchant_operator_config::*;
   pub use self::r#close_payment::*;
+  pub use self::r#close_payment_note::*;
+  pub use self::r#commit_settlement_wallet::*;
   pub use self::r#create_operator::*;
+  pub use self::r#create_order::*;
+  pub use self::r#create_quote::*;
+  pub use self::r#decommission_config::*;
   pub use self::r#emit_event::*;
+  pub use self::r#execute_settlement_batch::*;
+  pub use self::r#get_config_summary::*;
+  pub use self::r#heartbeat::*;
+  pub use self::r#initialize_buyer_index::*;
+  pub use self::r#initialize_config_registry_page::*;
   pub use self::r#initialize_merchant::*;
   pub use self::r#initialize_merchant_operator_config::*;
+  pub use self::r#initialize_merchant_profile::*;
+  pub use self::r#initialize_merchant_stats::*;
+  pub use self::r#initialize_merchant_with_config::*;
+  pub use self::r#initialize_operator_delegate::*;
+  pub use self::r#initialize_operator_freeze_list::*;
+  pub use self::r#initialize_payment_tree::*;
+  pub use self::r#initialize_risk_state::*;
+  pub use self::r#initialize_settlement_batch::*;
   pub use self::r#make_payment::*;
+  pub use self::r#make_payment_compressed::*;
+  pub use self::r#migrate_escrow::*;
+  pub use self::r#migrate_payment::*;
+  pub use self::r#post_conversion_rate::*;
+  pub use self::r#propose_merchant_authority::*;
+  pub use self::r#propose_operator_authority::*;
+  pub use self::r#propose_settlement_wallet::*;
   pub use self::r#refund_payment::*;
-  pub use self::r#update_merchant_authority::*;
+  pub use self::r#refund_payment_with_conversion::*;
+  pub use self::r#refund_settled_payment::*;
+  pub use self::r#remove_from_freeze_list::*;
+  pub use self::r#revoke_operator_delegate::*;
+  pub use self::r#set_settlement_wallet_timelock::*;
+  pub use self::r#set_webhook_commitment::*;
+  pub use self::r#sweep_closed_payments::*;
+  pub use self::r#update_merchant_profile::*;
   pub use self::r#update_merchant_settlement_wallet::*;
-  pub use self::r#update_operator_authority::*;
+  pub use self::r#update_operator_delegate::*;
+  pub use self::r#upgrade_account::*;
 
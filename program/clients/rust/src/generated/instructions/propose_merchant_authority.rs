@@ -5,14 +5,16 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
-pub const UPDATE_MERCHANT_AUTHORITY_DISCRIMINATOR: u8 = 7;
+pub const PROPOSE_MERCHANT_AUTHORITY_DISCRIMINATOR: u8 = 7;
 
 /// Accounts.
 #[derive(Debug)]
-pub struct UpdateMerchantAuthority {
+pub struct ProposeMerchantAuthority {
       
               
           pub payer: solana_pubkey::Pubkey,
@@ -24,12 +26,14 @@ pub struct UpdateMerchantAuthority {
     
               
           pub merchant: solana_pubkey::Pubkey,
-          
+                /// Must sign AcceptMerchantAuthority
+
+    
               
           pub new_authority: solana_pubkey::Pubkey,
       }
 
-impl UpdateMerchantAuthority {
+impl ProposeMerchantAuthority {
   pub fn instruction(&self) -> solana_instruction::Instruction {
     self.instruction_with_remaining_accounts(&[])
   }
@@ -54,7 +58,7 @@ impl UpdateMerchantAuthority {
             false
           ));
                       accounts.extend_from_slice(remaining_accounts);
-    let data = borsh::to_vec(&UpdateMerchantAuthorityInstructionData::new()).unwrap();
+    let data = borsh::to_vec(&ProposeMerchantAuthorityInstructionData::new()).unwrap();
     
     solana_instruction::Instruction {
       program_id: crate::COMMERCE_PROGRAM_ID,
@@ -66,11 +70,11 @@ impl UpdateMerchantAuthority {
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
- pub struct UpdateMerchantAuthorityInstructionData {
+ pub struct ProposeMerchantAuthorityInstructionData {
             discriminator: u8,
       }
 
-impl UpdateMerchantAuthorityInstructionData {
+impl ProposeMerchantAuthorityInstructionData {
   pub fn new() -> Self {
     Self {
                         discriminator: 7,
@@ -78,7 +82,7 @@ impl UpdateMerchantAuthorityInstructionData {
   }
 }
 
-impl Default for UpdateMerchantAuthorityInstructionData {
+impl Default for ProposeMerchantAuthorityInstructionData {
   fn default() -> Self {
     Self::new()
   }
@@ -86,7 +90,7 @@ impl Default for UpdateMerchantAuthorityInstructionData {
 
 
 
-/// Instruction builder for `UpdateMerchantAuthority`.
+/// Instruction builder for `ProposeMerchantAuthority`.
 ///
 /// ### Accounts:
 ///
@@ -95,7 +99,7 @@ impl Default for UpdateMerchantAuthorityInstructionData {
                 ///   2. `[writable]` merchant
           ///   3. `[]` new_authority
 #[derive(Clone, Debug, Default)]
-pub struct UpdateMerchantAuthorityBuilder {
+pub struct ProposeMerchantAuthorityBuilder {
             payer: Option<solana_pubkey::Pubkey>,
                 authority: Option<solana_pubkey::Pubkey>,
                 merchant: Option<solana_pubkey::Pubkey>,
@@ -103,7 +107,7 @@ pub struct UpdateMerchantAuthorityBuilder {
                 __remaining_accounts: Vec<solana_instruction::AccountMeta>,
 }
 
-impl UpdateMerchantAuthorityBuilder {
+impl ProposeMerchantAuthorityBuilder {
   pub fn new() -> Self {
     Self::default()
   }
@@ -123,7 +127,8 @@ impl UpdateMerchantAuthorityBuilder {
                         self.merchant = Some(merchant);
                     self
     }
-            #[inline(always)]
+            /// Must sign AcceptMerchantAuthority
+#[inline(always)]
     pub fn new_authority(&mut self, new_authority: solana_pubkey::Pubkey) -> &mut Self {
                         self.new_authority = Some(new_authority);
                     self
@@ -142,7 +147,7 @@ impl UpdateMerchantAuthorityBuilder {
   }
   #[allow(clippy::clone_on_copy)]
   pub fn instruction(&self) -> solana_instruction::Instruction {
-    let accounts = UpdateMerchantAuthority {
+    let accounts = ProposeMerchantAuthority {
                               payer: self.payer.expect("payer is not set"),
                                         authority: self.authority.expect("authority is not set"),
                                         merchant: self.merchant.expect("merchant is not set"),
@@ -153,8 +158,8 @@ impl UpdateMerchantAuthorityBuilder {
   }
 }
 
-  /// `update_merchant_authority` CPI accounts.
-  pub struct UpdateMerchantAuthorityCpiAccounts<'a, 'b> {
+  /// `propose_merchant_authority` CPI accounts.
+  pub struct ProposeMerchantAuthorityCpiAccounts<'a, 'b> {
           
                     
               pub payer: &'b solana_account_info::AccountInfo<'a>,
@@ -166,13 +171,15 @@ impl UpdateMerchantAuthorityBuilder {
       
                     
               pub merchant: &'b solana_account_info::AccountInfo<'a>,
-                
+                        /// Must sign AcceptMerchantAuthority
+
+      
                     
               pub new_authority: &'b solana_account_info::AccountInfo<'a>,
             }
 
-/// `update_merchant_authority` CPI instruction.
-pub struct UpdateMerchantAuthorityCpi<'a, 'b> {
+/// `propose_merchant_authority` CPI instruction.
+pub struct ProposeMerchantAuthorityCpi<'a, 'b> {
   /// The program to invoke.
   pub __program: &'b solana_account_info::AccountInfo<'a>,
       
@@ -186,15 +193,17 @@ pub struct UpdateMerchantAuthorityCpi<'a, 'b> {
     
               
           pub merchant: &'b solana_account_info::AccountInfo<'a>,
-          
+                /// Must sign AcceptMerchantAuthority
+
+    
               
           pub new_authority: &'b solana_account_info::AccountInfo<'a>,
         }
 
-impl<'a, 'b> UpdateMerchantAuthorityCpi<'a, 'b> {
+impl<'a, 'b> ProposeMerchantAuthorityCpi<'a, 'b> {
   pub fn new(
     program: &'b solana_account_info::AccountInfo<'a>,
-          accounts: UpdateMerchantAuthorityCpiAccounts<'a, 'b>,
+          accounts: ProposeMerchantAuthorityCpiAccounts<'a, 'b>,
           ) -> Self {
     Self {
       __program: program,
@@ -248,7 +257,7 @@ impl<'a, 'b> UpdateMerchantAuthorityCpi<'a, 'b> {
           is_writable: remaining_account.2,
       })
     });
-    let data = borsh::to_vec(&UpdateMerchantAuthorityInstructionData::new()).unwrap();
+    let data = borsh::to_vec(&ProposeMerchantAuthorityInstructionData::new()).unwrap();
     
     let instruction = solana_instruction::Instruction {
       program_id: crate::COMMERCE_PROGRAM_ID,
@@ -271,7 +280,7 @@ impl<'a, 'b> UpdateMerchantAuthorityCpi<'a, 'b> {
   }
 }
 
-/// Instruction builder for `UpdateMerchantAuthority` via CPI.
+/// Instruction builder for `ProposeMerchantAuthority` via CPI.
 ///
 /// ### Accounts:
 ///
@@ -280,13 +289,13 @@ impl<'a, 'b> UpdateMerchantAuthorityCpi<'a, 'b> {
                 ///   2. `[writable]` merchant
           ///   3. `[]` new_authority
 #[derive(Clone, Debug)]
-pub struct UpdateMerchantAuthorityCpiBuilder<'a, 'b> {
-  instruction: Box<UpdateMerchantAuthorityCpiBuilderInstruction<'a, 'b>>,
+pub struct ProposeMerchantAuthorityCpiBuilder<'a, 'b> {
+  instruction: Box<ProposeMerchantAuthorityCpiBuilderInstruction<'a, 'b>>,
 }
 
-impl<'a, 'b> UpdateMerchantAuthorityCpiBuilder<'a, 'b> {
+impl<'a, 'b> ProposeMerchantAuthorityCpiBuilder<'a, 'b> {
   pub fn new(program: &'b solana_account_info::AccountInfo<'a>) -> Self {
-    let instruction = Box::new(UpdateMerchantAuthorityCpiBuilderInstruction {
+    let instruction = Box::new(ProposeMerchantAuthorityCpiBuilderInstruction {
       __program: program,
               payer: None,
               authority: None,
@@ -312,7 +321,8 @@ impl<'a, 'b> UpdateMerchantAuthorityCpiBuilder<'a, 'b> {
                         self.instruction.merchant = Some(merchant);
                     self
     }
-      #[inline(always)]
+      /// Must sign AcceptMerchantAuthority
+#[inline(always)]
     pub fn new_authority(&mut self, new_authority: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.new_authority = Some(new_authority);
                     self
@@ -339,7 +349,7 @@ impl<'a, 'b> UpdateMerchantAuthorityCpiBuilder<'a, 'b> {
   #[allow(clippy::clone_on_copy)]
   #[allow(clippy::vec_init_then_push)]
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program_error::ProgramResult {
-        let instruction = UpdateMerchantAuthorityCpi {
+        let instruction = ProposeMerchantAuthorityCpi {
         __program: self.instruction.__program,
                   
           payer: self.instruction.payer.expect("payer is not set"),
@@ -355,7 +365,7 @@ impl<'a, 'b> UpdateMerchantAuthorityCpiBuilder<'a, 'b> {
 }
 
 #[derive(Clone, Debug)]
-struct UpdateMerchantAuthorityCpiBuilderInstruction<'a, 'b> {
+struct ProposeMerchantAuthorityCpiBuilderInstruction<'a, 'b> {
   __program: &'b solana_account_info::AccountInfo<'a>,
             payer: Option<&'b solana_account_info::AccountInfo<'a>>,
                 authority: Option<&'b solana_account_info::AccountInfo<'a>>,
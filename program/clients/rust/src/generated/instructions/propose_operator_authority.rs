@@ -5,14 +5,16 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
-pub const UPDATE_OPERATOR_AUTHORITY_DISCRIMINATOR: u8 = 8;
+pub const PROPOSE_OPERATOR_AUTHORITY_DISCRIMINATOR: u8 = 8;
 
 /// Accounts.
 #[derive(Debug)]
-pub struct UpdateOperatorAuthority {
+pub struct ProposeOperatorAuthority {
       
               
           pub payer: solana_pubkey::Pubkey,
@@ -24,12 +26,14 @@ pub struct UpdateOperatorAuthority {
     
               
           pub operator: solana_pubkey::Pubkey,
-          
+                /// Must sign AcceptOperatorAuthority
+
+    
               
           pub new_operator_authority: solana_pubkey::Pubkey,
       }
 
-impl UpdateOperatorAuthority {
+impl ProposeOperatorAuthority {
   pub fn instruction(&self) -> solana_instruction::Instruction {
     self.instruction_with_remaining_accounts(&[])
   }
@@ -54,7 +58,7 @@ impl UpdateOperatorAuthority {
             false
           ));
                       accounts.extend_from_slice(remaining_accounts);
-    let data = borsh::to_vec(&UpdateOperatorAuthorityInstructionData::new()).unwrap();
+    let data = borsh::to_vec(&ProposeOperatorAuthorityInstructionData::new()).unwrap();
     
     solana_instruction::Instruction {
       program_id: crate::COMMERCE_PROGRAM_ID,
@@ -66,11 +70,11 @@ impl UpdateOperatorAuthority {
 
 #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
- pub struct UpdateOperatorAuthorityInstructionData {
+ pub struct ProposeOperatorAuthorityInstructionData {
             discriminator: u8,
       }
 
-impl UpdateOperatorAuthorityInstructionData {
+impl ProposeOperatorAuthorityInstructionData {
   pub fn new() -> Self {
     Self {
                         discriminator: 8,
@@ -78,7 +82,7 @@ impl UpdateOperatorAuthorityInstructionData {
   }
 }
 
-impl Default for UpdateOperatorAuthorityInstructionData {
+impl Default for ProposeOperatorAuthorityInstructionData {
   fn default() -> Self {
     Self::new()
   }
@@ -86,7 +90,7 @@ impl Default for UpdateOperatorAuthorityInstructionData {
 
 
 
-/// Instruction builder for `UpdateOperatorAuthority`.
+/// Instruction builder for `ProposeOperatorAuthority`.
 ///
 /// ### Accounts:
 ///
@@ -95,7 +99,7 @@ impl Default for UpdateOperatorAuthorityInstructionData {
                 ///   2. `[writable]` operator
           ///   3. `[]` new_operator_authority
 #[derive(Clone, Debug, Default)]
-pub struct UpdateOperatorAuthorityBuilder {
+pub struct ProposeOperatorAuthorityBuilder {
             payer: Option<solana_pubkey::Pubkey>,
                 authority: Option<solana_pubkey::Pubkey>,
                 operator: Option<solana_pubkey::Pubkey>,
@@ -103,7 +107,7 @@ pub struct UpdateOperatorAuthorityBuilder {
                 __remaining_accounts: Vec<solana_instruction::AccountMeta>,
 }
 
-impl UpdateOperatorAuthorityBuilder {
+impl ProposeOperatorAuthorityBuilder {
   pub fn new() -> Self {
     Self::default()
   }
@@ -123,7 +127,8 @@ impl UpdateOperatorAuthorityBuilder {
                         self.operator = Some(operator);
                     self
     }
-            #[inline(always)]
+            /// Must sign AcceptOperatorAuthority
+#[inline(always)]
     pub fn new_operator_authority(&mut self, new_operator_authority: solana_pubkey::Pubkey) -> &mut Self {
                         self.new_operator_authority = Some(new_operator_authority);
                     self
@@ -142,7 +147,7 @@ impl UpdateOperatorAuthorityBuilder {
   }
   #[allow(clippy::clone_on_copy)]
   pub fn instruction(&self) -> solana_instruction::Instruction {
-    let accounts = UpdateOperatorAuthority {
+    let accounts = ProposeOperatorAuthority {
                               payer: self.payer.expect("payer is not set"),
                                         authority: self.authority.expect("authority is not set"),
                                         operator: self.operator.expect("operator is not set"),
@@ -153,8 +158,8 @@ impl UpdateOperatorAuthorityBuilder {
   }
 }
 
-  /// `update_operator_authority` CPI accounts.
-  pub struct UpdateOperatorAuthorityCpiAccounts<'a, 'b> {
+  /// `propose_operator_authority` CPI accounts.
+  pub struct ProposeOperatorAuthorityCpiAccounts<'a, 'b> {
           
                     
               pub payer: &'b solana_account_info::AccountInfo<'a>,
@@ -166,13 +171,15 @@ impl UpdateOperatorAuthorityBuilder {
       
                     
               pub operator: &'b solana_account_info::AccountInfo<'a>,
-                
+                        /// Must sign AcceptOperatorAuthority
+
+      
                     
               pub new_operator_authority: &'b solana_account_info::AccountInfo<'a>,
             }
 
-/// `update_operator_authority` CPI instruction.
-pub struct UpdateOperatorAuthorityCpi<'a, 'b> {
+/// `propose_operator_authority` CPI instruction.
+pub struct ProposeOperatorAuthorityCpi<'a, 'b> {
   /// The program to invoke.
   pub __program: &'b solana_account_info::AccountInfo<'a>,
       
@@ -186,15 +193,17 @@ pub struct UpdateOperatorAuthorityCpi<'a, 'b> {
     
               
           pub operator: &'b solana_account_info::AccountInfo<'a>,
-          
+                /// Must sign AcceptOperatorAuthority
+
+    
               
           pub new_operator_authority: &'b solana_account_info::AccountInfo<'a>,
         }
 
-impl<'a, 'b> UpdateOperatorAuthorityCpi<'a, 'b> {
+impl<'a, 'b> ProposeOperatorAuthorityCpi<'a, 'b> {
   pub fn new(
     program: &'b solana_account_info::AccountInfo<'a>,
-          accounts: UpdateOperatorAuthorityCpiAccounts<'a, 'b>,
+          accounts: ProposeOperatorAuthorityCpiAccounts<'a, 'b>,
           ) -> Self {
     Self {
       __program: program,
@@ -248,7 +257,7 @@ impl<'a, 'b> UpdateOperatorAuthorityCpi<'a, 'b> {
           is_writable: remaining_account.2,
       })
     });
-    let data = borsh::to_vec(&UpdateOperatorAuthorityInstructionData::new()).unwrap();
+    let data = borsh::to_vec(&ProposeOperatorAuthorityInstructionData::new()).unwrap();
     
     let instruction = solana_instruction::Instruction {
       program_id: crate::COMMERCE_PROGRAM_ID,
@@ -271,7 +280,7 @@ impl<'a, 'b> UpdateOperatorAuthorityCpi<'a, 'b> {
   }
 }
 
-/// Instruction builder for `UpdateOperatorAuthority` via CPI.
+/// Instruction builder for `ProposeOperatorAuthority` via CPI.
 ///
 /// ### Accounts:
 ///
@@ -280,13 +289,13 @@ impl<'a, 'b> UpdateOperatorAuthorityCpi<'a, 'b> {
                 ///   2. `[writable]` operator
           ///   3. `[]` new_operator_authority
 #[derive(Clone, Debug)]
-pub struct UpdateOperatorAuthorityCpiBuilder<'a, 'b> {
-  instruction: Box<UpdateOperatorAuthorityCpiBuilderInstruction<'a, 'b>>,
+pub struct ProposeOperatorAuthorityCpiBuilder<'a, 'b> {
+  instruction: Box<ProposeOperatorAuthorityCpiBuilderInstruction<'a, 'b>>,
 }
 
-impl<'a, 'b> UpdateOperatorAuthorityCpiBuilder<'a, 'b> {
+impl<'a, 'b> ProposeOperatorAuthorityCpiBuilder<'a, 'b> {
   pub fn new(program: &'b solana_account_info::AccountInfo<'a>) -> Self {
-    let instruction = Box::new(UpdateOperatorAuthorityCpiBuilderInstruction {
+    let instruction = Box::new(ProposeOperatorAuthorityCpiBuilderInstruction {
       __program: program,
               payer: None,
               authority: None,
@@ -312,7 +321,8 @@ impl<'a, 'b> UpdateOperatorAuthorityCpiBuilder<'a, 'b> {
                         self.instruction.operator = Some(operator);
                     self
     }
-      #[inline(always)]
+      /// Must sign AcceptOperatorAuthority
+#[inline(always)]
     pub fn new_operator_authority(&mut self, new_operator_authority: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.new_operator_authority = Some(new_operator_authority);
                     self
@@ -339,7 +349,7 @@ impl<'a, 'b> UpdateOperatorAuthorityCpiBuilder<'a, 'b> {
   #[allow(clippy::clone_on_copy)]
   #[allow(clippy::vec_init_then_push)]
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program_error::ProgramResult {
-        let instruction = UpdateOperatorAuthorityCpi {
+        let instruction = ProposeOperatorAuthorityCpi {
         __program: self.instruction.__program,
                   
           payer: self.instruction.payer.expect("payer is not set"),
@@ -355,7 +365,7 @@ impl<'a, 'b> UpdateOperatorAuthorityCpiBuilder<'a, 'b> {
 }
 
 #[derive(Clone, Debug)]
-struct UpdateOperatorAuthorityCpiBuilderInstruction<'a, 'b> {
+struct ProposeOperatorAuthorityCpiBuilderInstruction<'a, 'b> {
   __program: &'b solana_account_info::AccountInfo<'a>,
             payer: Option<&'b solana_account_info::AccountInfo<'a>>,
                 authority: Option<&'b solana_account_info::AccountInfo<'a>>,
@@ -5,6 +5,8 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
@@ -47,7 +49,12 @@ pub struct RefundPayment {
           
               
           pub mint: solana_pubkey::Pubkey,
-                /// Merchant Escrow ATA (Merchant PDA is owner)
+                /// Escrow authority PDA
+
+    
+              
+          pub escrow_authority: solana_pubkey::Pubkey,
+                /// Merchant Escrow ATA (Escrow authority PDA is owner)
 
     
               
@@ -74,13 +81,13 @@ pub struct RefundPayment {
       }
 
 impl RefundPayment {
-  pub fn instruction(&self) -> solana_instruction::Instruction {
-    self.instruction_with_remaining_accounts(&[])
+  pub fn instruction(&self, args: RefundPaymentInstructionArgs) -> solana_instruction::Instruction {
+    self.instruction_with_remaining_accounts(args, &[])
   }
   #[allow(clippy::arithmetic_side_effects)]
   #[allow(clippy::vec_init_then_push)]
-  pub fn instruction_with_remaining_accounts(&self, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
-    let mut accounts = Vec::with_capacity(14+ remaining_accounts.len());
+  pub fn instruction_with_remaining_accounts(&self, args: RefundPaymentInstructionArgs, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
+    let mut accounts = Vec::with_capacity(15+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             self.payer,
             true
@@ -105,13 +112,17 @@ impl RefundPayment {
             self.operator,
             false
           ));
-                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+                                          accounts.push(solana_instruction::AccountMeta::new(
             self.merchant_operator_config,
             false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             self.mint,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.escrow_authority,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new(
             self.merchant_escrow_ata,
@@ -138,7 +149,9 @@ impl RefundPayment {
             false
           ));
                       accounts.extend_from_slice(remaining_accounts);
-    let data = borsh::to_vec(&RefundPaymentInstructionData::new()).unwrap();
+    let mut data = borsh::to_vec(&RefundPaymentInstructionData::new()).unwrap();
+          let mut args = borsh::to_vec(&args).unwrap();
+      data.append(&mut args);
     
     solana_instruction::Instruction {
       program_id: crate::COMMERCE_PROGRAM_ID,
@@ -168,6 +181,11 @@ impl Default for RefundPaymentInstructionData {
   }
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+ pub struct RefundPaymentInstructionArgs {
+                  pub close_escrow: bool,
+      }
 
 
 /// Instruction builder for `RefundPayment`.
@@ -180,14 +198,15 @@ impl Default for RefundPaymentInstructionData {
           ///   3. `[]` buyer
           ///   4. `[]` merchant
           ///   5. `[]` operator
-          ///   6. `[]` merchant_operator_config
+                ///   6. `[writable]` merchant_operator_config
           ///   7. `[]` mint
-                ///   8. `[writable]` merchant_escrow_ata
-                ///   9. `[writable]` buyer_ata
-                ///   10. `[optional]` token_program (default to `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`)
-                ///   11. `[optional]` system_program (default to `11111111111111111111111111111111`)
-                ///   12. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
-                ///   13. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
+          ///   8. `[]` escrow_authority
+                ///   9. `[writable]` merchant_escrow_ata
+                ///   10. `[writable]` buyer_ata
+                ///   11. `[optional]` token_program (default to `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`)
+                ///   12. `[optional]` system_program (default to `11111111111111111111111111111111`)
+                ///   13. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
+                ///   14. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
 #[derive(Clone, Debug, Default)]
 pub struct RefundPaymentBuilder {
             payer: Option<solana_pubkey::Pubkey>,
@@ -198,13 +217,15 @@ pub struct RefundPaymentBuilder {
                 operator: Option<solana_pubkey::Pubkey>,
                 merchant_operator_config: Option<solana_pubkey::Pubkey>,
                 mint: Option<solana_pubkey::Pubkey>,
+                escrow_authority: Option<solana_pubkey::Pubkey>,
                 merchant_escrow_ata: Option<solana_pubkey::Pubkey>,
                 buyer_ata: Option<solana_pubkey::Pubkey>,
                 token_program: Option<solana_pubkey::Pubkey>,
                 system_program: Option<solana_pubkey::Pubkey>,
                 event_authority: Option<solana_pubkey::Pubkey>,
                 commerce_program: Option<solana_pubkey::Pubkey>,
-                __remaining_accounts: Vec<solana_instruction::AccountMeta>,
+                        close_escrow: Option<bool>,
+        __remaining_accounts: Vec<solana_instruction::AccountMeta>,
 }
 
 impl RefundPaymentBuilder {
@@ -256,7 +277,13 @@ impl RefundPaymentBuilder {
                         self.mint = Some(mint);
                     self
     }
-            /// Merchant Escrow ATA (Merchant PDA is owner)
+            /// Escrow authority PDA
+#[inline(always)]
+    pub fn escrow_authority(&mut self, escrow_authority: solana_pubkey::Pubkey) -> &mut Self {
+                        self.escrow_authority = Some(escrow_authority);
+                    self
+    }
+            /// Merchant Escrow ATA (Escrow authority PDA is owner)
 #[inline(always)]
     pub fn merchant_escrow_ata(&mut self, merchant_escrow_ata: solana_pubkey::Pubkey) -> &mut Self {
                         self.merchant_escrow_ata = Some(merchant_escrow_ata);
@@ -293,7 +320,13 @@ impl RefundPaymentBuilder {
                         self.commerce_program = Some(commerce_program);
                     self
     }
-            /// Add an additional account to the instruction.
+                /// `[optional argument, defaults to 'false']`
+#[inline(always)]
+      pub fn close_escrow(&mut self, close_escrow: bool) -> &mut Self {
+        self.close_escrow = Some(close_escrow);
+        self
+      }
+        /// Add an additional account to the instruction.
   #[inline(always)]
   pub fn add_remaining_account(&mut self, account: solana_instruction::AccountMeta) -> &mut Self {
     self.__remaining_accounts.push(account);
@@ -316,6 +349,7 @@ impl RefundPaymentBuilder {
                                         operator: self.operator.expect("operator is not set"),
                                         merchant_operator_config: self.merchant_operator_config.expect("merchant_operator_config is not set"),
                                         mint: self.mint.expect("mint is not set"),
+                                        escrow_authority: self.escrow_authority.expect("escrow_authority is not set"),
                                         merchant_escrow_ata: self.merchant_escrow_ata.expect("merchant_escrow_ata is not set"),
                                         buyer_ata: self.buyer_ata.expect("buyer_ata is not set"),
                                         token_program: self.token_program.unwrap_or(solana_pubkey::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")),
@@ -323,8 +357,11 @@ impl RefundPaymentBuilder {
                                         event_authority: self.event_authority.unwrap_or(solana_pubkey::pubkey!("3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1")),
                                         commerce_program: self.commerce_program.unwrap_or(solana_pubkey::pubkey!("commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT")),
                       };
+          let args = RefundPaymentInstructionArgs {
+                                                              close_escrow: self.close_escrow.clone().unwrap_or(false),
+                                    };
     
-    accounts.instruction_with_remaining_accounts(&self.__remaining_accounts)
+    accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
   }
 }
 
@@ -364,7 +401,12 @@ impl RefundPaymentBuilder {
                 
                     
               pub mint: &'b solana_account_info::AccountInfo<'a>,
-                        /// Merchant Escrow ATA (Merchant PDA is owner)
+                        /// Escrow authority PDA
+
+      
+                    
+              pub escrow_authority: &'b solana_account_info::AccountInfo<'a>,
+                        /// Merchant Escrow ATA (Escrow authority PDA is owner)
 
       
                     
@@ -428,7 +470,12 @@ pub struct RefundPaymentCpi<'a, 'b> {
           
               
           pub mint: &'b solana_account_info::AccountInfo<'a>,
-                /// Merchant Escrow ATA (Merchant PDA is owner)
+                /// Escrow authority PDA
+
+    
+              
+          pub escrow_authority: &'b solana_account_info::AccountInfo<'a>,
+                /// Merchant Escrow ATA (Escrow authority PDA is owner)
 
     
               
@@ -452,13 +499,16 @@ pub struct RefundPaymentCpi<'a, 'b> {
     
               
           pub commerce_program: &'b solana_account_info::AccountInfo<'a>,
-        }
+            /// The arguments for the instruction.
+    pub __args: RefundPaymentInstructionArgs,
+  }
 
 impl<'a, 'b> RefundPaymentCpi<'a, 'b> {
   pub fn new(
     program: &'b solana_account_info::AccountInfo<'a>,
           accounts: RefundPaymentCpiAccounts<'a, 'b>,
-          ) -> Self {
+              args: RefundPaymentInstructionArgs,
+      ) -> Self {
     Self {
       __program: program,
               payer: accounts.payer,
@@ -469,13 +519,15 @@ impl<'a, 'b> RefundPaymentCpi<'a, 'b> {
               operator: accounts.operator,
               merchant_operator_config: accounts.merchant_operator_config,
               mint: accounts.mint,
+              escrow_authority: accounts.escrow_authority,
               merchant_escrow_ata: accounts.merchant_escrow_ata,
               buyer_ata: accounts.buyer_ata,
               token_program: accounts.token_program,
               system_program: accounts.system_program,
               event_authority: accounts.event_authority,
               commerce_program: accounts.commerce_program,
-                }
+                    __args: args,
+          }
   }
   #[inline(always)]
   pub fn invoke(&self) -> solana_program_error::ProgramResult {
@@ -497,7 +549,7 @@ impl<'a, 'b> RefundPaymentCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program_error::ProgramResult {
-    let mut accounts = Vec::with_capacity(14+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(15+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             *self.payer.key,
             true
@@ -522,13 +574,17 @@ impl<'a, 'b> RefundPaymentCpi<'a, 'b> {
             *self.operator.key,
             false
           ));
-                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+                                          accounts.push(solana_instruction::AccountMeta::new(
             *self.merchant_operator_config.key,
             false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             *self.mint.key,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.escrow_authority.key,
+            false
           ));
                                           accounts.push(solana_instruction::AccountMeta::new(
             *self.merchant_escrow_ata.key,
@@ -561,14 +617,16 @@ impl<'a, 'b> RefundPaymentCpi<'a, 'b> {
           is_writable: remaining_account.2,
       })
     });
-    let data = borsh::to_vec(&RefundPaymentInstructionData::new()).unwrap();
+    let mut data = borsh::to_vec(&RefundPaymentInstructionData::new()).unwrap();
+          let mut args = borsh::to_vec(&self.__args).unwrap();
+      data.append(&mut args);
     
     let instruction = solana_instruction::Instruction {
       program_id: crate::COMMERCE_PROGRAM_ID,
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(15 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(16 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
                   account_infos.push(self.payer.clone());
                         account_infos.push(self.payment.clone());
@@ -578,6 +636,7 @@ impl<'a, 'b> RefundPaymentCpi<'a, 'b> {
                         account_infos.push(self.operator.clone());
                         account_infos.push(self.merchant_operator_config.clone());
                         account_infos.push(self.mint.clone());
+                        account_infos.push(self.escrow_authority.clone());
                         account_infos.push(self.merchant_escrow_ata.clone());
                         account_infos.push(self.buyer_ata.clone());
                         account_infos.push(self.token_program.clone());
@@ -604,14 +663,15 @@ impl<'a, 'b> RefundPaymentCpi<'a, 'b> {
           ///   3. `[]` buyer
           ///   4. `[]` merchant
           ///   5. `[]` operator
-          ///   6. `[]` merchant_operator_config
+                ///   6. `[writable]` merchant_operator_config
           ///   7. `[]` mint
-                ///   8. `[writable]` merchant_escrow_ata
-                ///   9. `[writable]` buyer_ata
-          ///   10. `[]` token_program
-          ///   11. `[]` system_program
-          ///   12. `[]` event_authority
-          ///   13. `[]` commerce_program
+          ///   8. `[]` escrow_authority
+                ///   9. `[writable]` merchant_escrow_ata
+                ///   10. `[writable]` buyer_ata
+          ///   11. `[]` token_program
+          ///   12. `[]` system_program
+          ///   13. `[]` event_authority
+          ///   14. `[]` commerce_program
 #[derive(Clone, Debug)]
 pub struct RefundPaymentCpiBuilder<'a, 'b> {
   instruction: Box<RefundPaymentCpiBuilderInstruction<'a, 'b>>,
@@ -629,13 +689,15 @@ impl<'a, 'b> RefundPaymentCpiBuilder<'a, 'b> {
               operator: None,
               merchant_operator_config: None,
               mint: None,
+              escrow_authority: None,
               merchant_escrow_ata: None,
               buyer_ata: None,
               token_program: None,
               system_program: None,
               event_authority: None,
               commerce_program: None,
-                                __remaining_accounts: Vec::new(),
+                                            close_escrow: None,
+                    __remaining_accounts: Vec::new(),
     });
     Self { instruction }
   }
@@ -684,7 +746,13 @@ impl<'a, 'b> RefundPaymentCpiBuilder<'a, 'b> {
                         self.instruction.mint = Some(mint);
                     self
     }
-      /// Merchant Escrow ATA (Merchant PDA is owner)
+      /// Escrow authority PDA
+#[inline(always)]
+    pub fn escrow_authority(&mut self, escrow_authority: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.escrow_authority = Some(escrow_authority);
+                    self
+    }
+      /// Merchant Escrow ATA (Escrow authority PDA is owner)
 #[inline(always)]
     pub fn merchant_escrow_ata(&mut self, merchant_escrow_ata: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.merchant_escrow_ata = Some(merchant_escrow_ata);
@@ -717,7 +785,13 @@ impl<'a, 'b> RefundPaymentCpiBuilder<'a, 'b> {
                         self.instruction.commerce_program = Some(commerce_program);
                     self
     }
-            /// Add an additional account to the instruction.
+                /// `[optional argument, defaults to 'false']`
+#[inline(always)]
+      pub fn close_escrow(&mut self, close_escrow: bool) -> &mut Self {
+        self.instruction.close_escrow = Some(close_escrow);
+        self
+      }
+        /// Add an additional account to the instruction.
   #[inline(always)]
   pub fn add_remaining_account(&mut self, account: &'b solana_account_info::AccountInfo<'a>, is_writable: bool, is_signer: bool) -> &mut Self {
     self.instruction.__remaining_accounts.push((account, is_writable, is_signer));
@@ -739,6 +813,9 @@ impl<'a, 'b> RefundPaymentCpiBuilder<'a, 'b> {
   #[allow(clippy::clone_on_copy)]
   #[allow(clippy::vec_init_then_push)]
   pub fn invoke_signed(&self, signers_seeds: &[&[&[u8]]]) -> solana_program_error::ProgramResult {
+          let args = RefundPaymentInstructionArgs {
+                                                              close_escrow: self.instruction.close_escrow.clone().unwrap_or(false),
+                                    };
         let instruction = RefundPaymentCpi {
         __program: self.instruction.__program,
                   
@@ -758,6 +835,8 @@ impl<'a, 'b> RefundPaymentCpiBuilder<'a, 'b> {
                   
           mint: self.instruction.mint.expect("mint is not set"),
                   
+          escrow_authority: self.instruction.escrow_authority.expect("escrow_authority is not set"),
+                  
           merchant_escrow_ata: self.instruction.merchant_escrow_ata.expect("merchant_escrow_ata is not set"),
                   
           buyer_ata: self.instruction.buyer_ata.expect("buyer_ata is not set"),
@@ -769,7 +848,8 @@ impl<'a, 'b> RefundPaymentCpiBuilder<'a, 'b> {
           event_authority: self.instruction.event_authority.expect("event_authority is not set"),
                   
           commerce_program: self.instruction.commerce_program.expect("commerce_program is not set"),
-                    };
+                          __args: args,
+            };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
   }
 }
@@ -785,13 +865,15 @@ struct RefundPaymentCpiBuilderInstruction<'a, 'b> {
                 operator: Option<&'b solana_account_info::AccountInfo<'a>>,
                 merchant_operator_config: Option<&'b solana_account_info::AccountInfo<'a>>,
                 mint: Option<&'b solana_account_info::AccountInfo<'a>>,
+                escrow_authority: Option<&'b solana_account_info::AccountInfo<'a>>,
                 merchant_escrow_ata: Option<&'b solana_account_info::AccountInfo<'a>>,
                 buyer_ata: Option<&'b solana_account_info::AccountInfo<'a>>,
                 token_program: Option<&'b solana_account_info::AccountInfo<'a>>,
                 system_program: Option<&'b solana_account_info::AccountInfo<'a>>,
                 event_authority: Option<&'b solana_account_info::AccountInfo<'a>>,
                 commerce_program: Option<&'b solana_account_info::AccountInfo<'a>>,
-                /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
+                        close_escrow: Option<bool>,
+        /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_account_info::AccountInfo<'a>, bool, bool)>,
 }
 
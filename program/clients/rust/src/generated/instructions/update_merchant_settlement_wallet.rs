@@ -5,6 +5,8 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
@@ -27,6 +29,16 @@ pub struct UpdateMerchantSettlementWallet {
           
               
           pub new_settlement_wallet: solana_pubkey::Pubkey,
+                /// Event authority PDA
+
+    
+              
+          pub event_authority: solana_pubkey::Pubkey,
+                /// Commerce Program ID
+
+    
+              
+          pub commerce_program: solana_pubkey::Pubkey,
       }
 
 impl UpdateMerchantSettlementWallet {
@@ -36,7 +48,7 @@ impl UpdateMerchantSettlementWallet {
   #[allow(clippy::arithmetic_side_effects)]
   #[allow(clippy::vec_init_then_push)]
   pub fn instruction_with_remaining_accounts(&self, remaining_accounts: &[solana_instruction::AccountMeta]) -> solana_instruction::Instruction {
-    let mut accounts = Vec::with_capacity(4+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(6+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             self.payer,
             true
@@ -52,6 +64,14 @@ impl UpdateMerchantSettlementWallet {
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             self.new_settlement_wallet,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.event_authority,
+            false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            self.commerce_program,
+            false
           ));
                       accounts.extend_from_slice(remaining_accounts);
     let data = borsh::to_vec(&UpdateMerchantSettlementWalletInstructionData::new()).unwrap();
@@ -94,12 +114,16 @@ impl Default for UpdateMerchantSettlementWalletInstructionData {
                       ///   1. `[writable, signer]` authority
                 ///   2. `[writable]` merchant
           ///   3. `[]` new_settlement_wallet
+                ///   4. `[optional]` event_authority (default to `3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1`)
+                ///   5. `[optional]` commerce_program (default to `commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT`)
 #[derive(Clone, Debug, Default)]
 pub struct UpdateMerchantSettlementWalletBuilder {
             payer: Option<solana_pubkey::Pubkey>,
                 authority: Option<solana_pubkey::Pubkey>,
                 merchant: Option<solana_pubkey::Pubkey>,
                 new_settlement_wallet: Option<solana_pubkey::Pubkey>,
+                event_authority: Option<solana_pubkey::Pubkey>,
+                commerce_program: Option<solana_pubkey::Pubkey>,
                 __remaining_accounts: Vec<solana_instruction::AccountMeta>,
 }
 
@@ -127,6 +151,20 @@ impl UpdateMerchantSettlementWalletBuilder {
     pub fn new_settlement_wallet(&mut self, new_settlement_wallet: solana_pubkey::Pubkey) -> &mut Self {
                         self.new_settlement_wallet = Some(new_settlement_wallet);
                     self
+    }
+            /// `[optional account, default to '3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1']`
+/// Event authority PDA
+#[inline(always)]
+    pub fn event_authority(&mut self, event_authority: solana_pubkey::Pubkey) -> &mut Self {
+                        self.event_authority = Some(event_authority);
+                    self
+    }
+            /// `[optional account, default to 'commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT']`
+/// Commerce Program ID
+#[inline(always)]
+    pub fn commerce_program(&mut self, commerce_program: solana_pubkey::Pubkey) -> &mut Self {
+                        self.commerce_program = Some(commerce_program);
+                    self
     }
             /// Add an additional account to the instruction.
   #[inline(always)]
@@ -147,6 +185,8 @@ impl UpdateMerchantSettlementWalletBuilder {
                                         authority: self.authority.expect("authority is not set"),
                                         merchant: self.merchant.expect("merchant is not set"),
                                         new_settlement_wallet: self.new_settlement_wallet.expect("new_settlement_wallet is not set"),
+                                        event_authority: self.event_authority.unwrap_or(solana_pubkey::pubkey!("3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1")),
+                                        commerce_program: self.commerce_program.unwrap_or(solana_pubkey::pubkey!("commkU28d52cwo2Ma3Marxz4Qr9REtfJtuUfqnDnbhT")),
                       };
     
     accounts.instruction_with_remaining_accounts(&self.__remaining_accounts)
@@ -169,6 +209,16 @@ impl UpdateMerchantSettlementWalletBuilder {
                 
                     
               pub new_settlement_wallet: &'b solana_account_info::AccountInfo<'a>,
+                        /// Event authority PDA
+
+      
+                    
+              pub event_authority: &'b solana_account_info::AccountInfo<'a>,
+                        /// Commerce Program ID
+
+      
+                    
+              pub commerce_program: &'b solana_account_info::AccountInfo<'a>,
             }
 
 /// `update_merchant_settlement_wallet` CPI instruction.
@@ -189,6 +239,16 @@ pub struct UpdateMerchantSettlementWalletCpi<'a, 'b> {
           
               
           pub new_settlement_wallet: &'b solana_account_info::AccountInfo<'a>,
+                /// Event authority PDA
+
+    
+              
+          pub event_authority: &'b solana_account_info::AccountInfo<'a>,
+                /// Commerce Program ID
+
+    
+              
+          pub commerce_program: &'b solana_account_info::AccountInfo<'a>,
         }
 
 impl<'a, 'b> UpdateMerchantSettlementWalletCpi<'a, 'b> {
@@ -202,6 +262,8 @@ impl<'a, 'b> UpdateMerchantSettlementWalletCpi<'a, 'b> {
               authority: accounts.authority,
               merchant: accounts.merchant,
               new_settlement_wallet: accounts.new_settlement_wallet,
+              event_authority: accounts.event_authority,
+              commerce_program: accounts.commerce_program,
                 }
   }
   #[inline(always)]
@@ -224,7 +286,7 @@ impl<'a, 'b> UpdateMerchantSettlementWalletCpi<'a, 'b> {
     signers_seeds: &[&[&[u8]]],
     remaining_accounts: &[(&'b solana_account_info::AccountInfo<'a>, bool, bool)]
   ) -> solana_program_error::ProgramResult {
-    let mut accounts = Vec::with_capacity(4+ remaining_accounts.len());
+    let mut accounts = Vec::with_capacity(6+ remaining_accounts.len());
                             accounts.push(solana_instruction::AccountMeta::new(
             *self.payer.key,
             true
@@ -240,6 +302,14 @@ impl<'a, 'b> UpdateMerchantSettlementWalletCpi<'a, 'b> {
                                           accounts.push(solana_instruction::AccountMeta::new_readonly(
             *self.new_settlement_wallet.key,
             false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.event_authority.key,
+            false
+          ));
+                                          accounts.push(solana_instruction::AccountMeta::new_readonly(
+            *self.commerce_program.key,
+            false
           ));
                       remaining_accounts.iter().for_each(|remaining_account| {
       accounts.push(solana_instruction::AccountMeta {
@@ -255,12 +325,14 @@ impl<'a, 'b> UpdateMerchantSettlementWalletCpi<'a, 'b> {
       accounts,
       data,
     };
-    let mut account_infos = Vec::with_capacity(5 + remaining_accounts.len());
+    let mut account_infos = Vec::with_capacity(7 + remaining_accounts.len());
     account_infos.push(self.__program.clone());
                   account_infos.push(self.payer.clone());
                         account_infos.push(self.authority.clone());
                         account_infos.push(self.merchant.clone());
                         account_infos.push(self.new_settlement_wallet.clone());
+                        account_infos.push(self.event_authority.clone());
+                        account_infos.push(self.commerce_program.clone());
               remaining_accounts.iter().for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
 
     if signers_seeds.is_empty() {
@@ -279,6 +351,8 @@ impl<'a, 'b> UpdateMerchantSettlementWalletCpi<'a, 'b> {
                       ///   1. `[writable, signer]` authority
                 ///   2. `[writable]` merchant
           ///   3. `[]` new_settlement_wallet
+          ///   4. `[]` event_authority
+          ///   5. `[]` commerce_program
 #[derive(Clone, Debug)]
 pub struct UpdateMerchantSettlementWalletCpiBuilder<'a, 'b> {
   instruction: Box<UpdateMerchantSettlementWalletCpiBuilderInstruction<'a, 'b>>,
@@ -292,6 +366,8 @@ impl<'a, 'b> UpdateMerchantSettlementWalletCpiBuilder<'a, 'b> {
               authority: None,
               merchant: None,
               new_settlement_wallet: None,
+              event_authority: None,
+              commerce_program: None,
                                 __remaining_accounts: Vec::new(),
     });
     Self { instruction }
@@ -316,6 +392,18 @@ impl<'a, 'b> UpdateMerchantSettlementWalletCpiBuilder<'a, 'b> {
     pub fn new_settlement_wallet(&mut self, new_settlement_wallet: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
                         self.instruction.new_settlement_wallet = Some(new_settlement_wallet);
                     self
+    }
+      /// Event authority PDA
+#[inline(always)]
+    pub fn event_authority(&mut self, event_authority: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.event_authority = Some(event_authority);
+                    self
+    }
+      /// Commerce Program ID
+#[inline(always)]
+    pub fn commerce_program(&mut self, commerce_program: &'b solana_account_info::AccountInfo<'a>) -> &mut Self {
+                        self.instruction.commerce_program = Some(commerce_program);
+                    self
     }
             /// Add an additional account to the instruction.
   #[inline(always)]
@@ -349,6 +437,10 @@ impl<'a, 'b> UpdateMerchantSettlementWalletCpiBuilder<'a, 'b> {
           merchant: self.instruction.merchant.expect("merchant is not set"),
                   
           new_settlement_wallet: self.instruction.new_settlement_wallet.expect("new_settlement_wallet is not set"),
+                  
+          event_authority: self.instruction.event_authority.expect("event_authority is not set"),
+                  
+          commerce_program: self.instruction.commerce_program.expect("commerce_program is not set"),
                     };
     instruction.invoke_signed_with_remaining_accounts(signers_seeds, &self.instruction.__remaining_accounts)
   }
@@ -361,6 +453,8 @@ struct UpdateMerchantSettlementWalletCpiBuilderInstruction<'a, 'b> {
                 authority: Option<&'b solana_account_info::AccountInfo<'a>>,
                 merchant: Option<&'b solana_account_info::AccountInfo<'a>>,
                 new_settlement_wallet: Option<&'b solana_account_info::AccountInfo<'a>>,
+                event_authority: Option<&'b solana_account_info::AccountInfo<'a>>,
+                commerce_program: Option<&'b solana_account_info::AccountInfo<'a>>,
                 /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
   __remaining_accounts: Vec<(&'b solana_account_info::AccountInfo<'a>, bool, bool)>,
 }
@@ -5,23 +5,121 @@
 //! <https://github.com/codama-idl/codama>
 //!
 
+  pub(crate) mod r#annotation_flag;
+  pub(crate) mod r#buyer_index_policy;
+  pub(crate) mod r#callback_policy;
+  pub(crate) mod r#chargeback_policy;
+  pub(crate) mod r#compliance_policy;
+  pub(crate) mod r#compressed_payment_event;
+  pub(crate) mod r#config_decommissioned_event;
+  pub(crate) mod r#config_registry_entry;
+  pub(crate) mod r#config_summary;
+  pub(crate) mod r#conversion_policy;
+  pub(crate) mod r#denylist_policy;
+  pub(crate) mod r#denylist_proof;
+  pub(crate) mod r#direct_settlement_policy;
+  pub(crate) mod r#encrypted_note;
+  pub(crate) mod r#fee_override;
+  pub(crate) mod r#fee_override_policy;
+  pub(crate) mod r#fee_tier;
+  pub(crate) mod r#fee_tier_policy;
   pub(crate) mod r#fee_type;
+  pub(crate) mod r#memo_policy;
+  pub(crate) mod r#merchant_authority_updated_event;
+  pub(crate) mod r#merchant_initialized_event;
+  pub(crate) mod r#merchant_operator_config_closed_event;
+  pub(crate) mod r#merchant_operator_config_initialized_event;
+  pub(crate) mod r#merchant_settlement_wallet_updated_event;
+  pub(crate) mod r#operator_authority_updated_event;
+  pub(crate) mod r#operator_failover_policy;
+  pub(crate) mod r#order_created_event;
+  pub(crate) mod r#order_status;
+  pub(crate) mod r#order_status_changed_event;
+  pub(crate) mod r#payment_annotated_event;
+  pub(crate) mod r#payment_captured_event;
+  pub(crate) mod r#payment_chargebacked_event;
   pub(crate) mod r#payment_cleared_event;
+  pub(crate) mod r#payment_closed_event;
   pub(crate) mod r#payment_created_event;
+  pub(crate) mod r#payment_lifecycle_status;
+  pub(crate) mod r#payment_migrated_event;
   pub(crate) mod r#payment_refunded_event;
+  pub(crate) mod r#payment_status_changed_event;
+  pub(crate) mod r#platform_fee_collected_event;
+  pub(crate) mod r#platform_fee_policy;
   pub(crate) mod r#policy_data;
   pub(crate) mod r#policy_type;
+  pub(crate) mod r#refund_approved_event;
+  pub(crate) mod r#refund_conversion_policy;
   pub(crate) mod r#refund_policy;
+  pub(crate) mod r#rent_rebate_policy;
+  pub(crate) mod r#risk_policy;
+  pub(crate) mod r#settlement_batch_executed_event;
+  pub(crate) mod r#settlement_discount_policy;
   pub(crate) mod r#settlement_policy;
+  pub(crate) mod r#settlement_wallet_proposed_event;
+  pub(crate) mod r#settlement_wallet_timelock_raised_event;
+  pub(crate) mod r#split_settlement_policy;
   pub(crate) mod r#status;
+  pub(crate) mod r#swap_adapter;
+  pub(crate) mod r#webhook_commitment_updated_event;
 
+  pub use self::r#annotation_flag::*;
+  pub use self::r#buyer_index_policy::*;
+  pub use self::r#callback_policy::*;
+  pub use self::r#chargeback_policy::*;
+  pub use self::r#compliance_policy::*;
+  pub use self::r#compressed_payment_event::*;
+  pub use self::r#config_decommissioned_event::*;
+  pub use self::r#config_registry_entry::*;
+  pub use self::r#config_summary::*;
+  pub use self::r#conversion_policy::*;
+  pub use self::r#denylist_policy::*;
+  pub use self::r#denylist_proof::*;
+  pub use self::r#direct_settlement_policy::*;
+  pub use self::r#encrypted_note::*;
+  pub use self::r#fee_override::*;
+  pub use self::r#fee_override_policy::*;
+  pub use self::r#fee_tier::*;
+  pub use self::r#fee_tier_policy::*;
   pub use self::r#fee_type::*;
+  pub use self::r#memo_policy::*;
+  pub use self::r#merchant_authority_updated_event::*;
+  pub use self::r#merchant_initialized_event::*;
+  pub use self::r#merchant_operator_config_closed_event::*;
+  pub use self::r#merchant_operator_config_initialized_event::*;
+  pub use self::r#merchant_settlement_wallet_updated_event::*;
+  pub use self::r#operator_authority_updated_event::*;
+  pub use self::r#operator_failover_policy::*;
+  pub use self::r#order_created_event::*;
+  pub use self::r#order_status::*;
+  pub use self::r#order_status_changed_event::*;
+  pub use self::r#payment_annotated_event::*;
+  pub use self::r#payment_captured_event::*;
+  pub use self::r#payment_chargebacked_event::*;
   pub use self::r#payment_cleared_event::*;
+  pub use self::r#payment_closed_event::*;
   pub use self::r#payment_created_event::*;
+  pub use self::r#payment_lifecycle_status::*;
+  pub use self::r#payment_migrated_event::*;
   pub use self::r#payment_refunded_event::*;
+  pub use self::r#payment_status_changed_event::*;
+  pub use self::r#platform_fee_collected_event::*;
+  pub use self::r#platform_fee_policy::*;
   pub use self::r#policy_data::*;
   pub use self::r#policy_type::*;
+  pub use self::r#refund_approved_event::*;
+  pub use self::r#refund_conversion_policy::*;
   pub use self::r#refund_policy::*;
+  pub use self::r#rent_rebate_policy::*;
+  pub use self::r#risk_policy::*;
+  pub use self::r#settlement_batch_executed_event::*;
+  pub use self::r#settlement_discount_policy::*;
   pub use self::r#settlement_policy::*;
+  pub use self::r#settlement_wallet_proposed_event::*;
+  pub use self::r#settlement_wallet_timelock_raised_event::*;
+  pub use self::r#split_settlement_policy::*;
   pub use self::r#status::*;
+  pub use self::r#swap_adapter::*;
+  pub use self::r#webhook_commitment_updated_event::*;
 
@@ -22,6 +22,7 @@ pub operator: Pubkey,
 pub amount: u64,
 pub operator_fee: u64,
 pub order_id: u32,
+pub idempotency_key: [u8; 16],
 }
 
 
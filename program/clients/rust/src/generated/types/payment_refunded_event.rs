@@ -21,6 +21,7 @@ pub merchant: Pubkey,
 pub operator: Pubkey,
 pub amount: u64,
 pub order_id: u32,
+pub idempotency_key: [u8; 16],
 }
 
 
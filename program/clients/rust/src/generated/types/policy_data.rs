@@ -7,6 +7,23 @@
 
 use crate::generated::types::RefundPolicy;
 use crate::generated::types::SettlementPolicy;
+use crate::generated::types::FeeTierPolicy;
+use crate::generated::types::MemoPolicy;
+use crate::generated::types::ConversionPolicy;
+use crate::generated::types::ChargebackPolicy;
+use crate::generated::types::FeeOverridePolicy;
+use crate::generated::types::DenylistPolicy;
+use crate::generated::types::SplitSettlementPolicy;
+use crate::generated::types::RiskPolicy;
+use crate::generated::types::BuyerIndexPolicy;
+use crate::generated::types::CallbackPolicy;
+use crate::generated::types::PlatformFeePolicy;
+use crate::generated::types::CompliancePolicy;
+use crate::generated::types::SettlementDiscountPolicy;
+use crate::generated::types::RefundConversionPolicy;
+use crate::generated::types::OperatorFailoverPolicy;
+use crate::generated::types::RentRebatePolicy;
+use crate::generated::types::DirectSettlementPolicy;
 use borsh::BorshSerialize;
 use borsh::BorshDeserialize;
 
@@ -15,6 +32,23 @@ use borsh::BorshDeserialize;
 pub enum PolicyData {
 Refund(RefundPolicy),
 Settlement(SettlementPolicy),
+FeeTier(FeeTierPolicy),
+Memo(MemoPolicy),
+Conversion(ConversionPolicy),
+Chargeback(ChargebackPolicy),
+FeeOverride(FeeOverridePolicy),
+Denylist(DenylistPolicy),
+SplitSettlement(SplitSettlementPolicy),
+Risk(RiskPolicy),
+BuyerIndex(BuyerIndexPolicy),
+Callback(CallbackPolicy),
+PlatformFee(PlatformFeePolicy),
+Compliance(CompliancePolicy),
+SettlementDiscount(SettlementDiscountPolicy),
+RefundConversion(RefundConversionPolicy),
+OperatorFailover(OperatorFailoverPolicy),
+RentRebate(RentRebatePolicy),
+DirectSettlement(DirectSettlementPolicy),
 }
 
 
@@ -14,6 +14,23 @@ use num_derive::FromPrimitive;
 pub enum PolicyType {
 Refund,
 Settlement,
+FeeTier,
+Memo,
+Conversion,
+Chargeback,
+FeeOverride,
+Denylist,
+SplitSettlement,
+Risk,
+BuyerIndex,
+Callback,
+PlatformFee,
+Compliance,
+SettlementDiscount,
+RefundConversion,
+OperatorFailover,
+RentRebate,
+DirectSettlement,
 }
 
 
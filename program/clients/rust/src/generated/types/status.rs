@@ -15,6 +15,8 @@ pub enum Status {
 Paid,
 Cleared,
 Refunded,
+RefundApproved,
+Authorized,
 }
 
 
@@ -0,0 +1,139 @@
+//! Idempotent payment creation.
+//!
+//! `MakePayment` stores an operator-supplied idempotency key on the `Payment` account. A
+//! retried checkout reuses the same order id, so it targets the same payment PDA and fails
+//! with `PaymentAlreadyExists` once the first attempt has landed. The helpers here turn that
+//! failure into the existing payment when its key matches the retry's key.
+
+use thiserror::Error;
+
+use crate::generated::accounts::Payment;
+
+/// Operator-supplied key identifying a checkout attempt.
+pub type IdempotencyKey = [u8; 16];
+
+/// Result of an idempotent `MakePayment`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MakePaymentOutcome<T> {
+    /// The transaction landed and created the payment.
+    Created(T),
+    /// The payment already existed with the same idempotency key.
+    Existing(Payment),
+}
+
+#[derive(Debug, Error)]
+pub enum IdempotentPaymentError<E> {
+    /// The payment exists but was created by a different checkout attempt.
+    #[error("payment already exists with a different idempotency key")]
+    KeyMismatch { existing: Box<Payment> },
+    /// Sending failed and no existing payment was found.
+    #[error(transparent)]
+    Send(E),
+}
+
+/// Resolves the result of sending a `MakePayment` transaction.
+///
+/// On failure, `fetch_existing` is called to load the payment account. If it exists with
+/// `idempotency_key`, the earlier attempt is returned instead of the error.
+pub fn resolve_make_payment<T, E>(
+    result: Result<T, E>,
+    idempotency_key: &IdempotencyKey,
+    fetch_existing: impl FnOnce() -> Option<Payment>,
+) -> Result<MakePaymentOutcome<T>, IdempotentPaymentError<E>> {
+    let error = match result {
+        Ok(value) => return Ok(MakePaymentOutcome::Created(value)),
+        Err(error) => error,
+    };
+
+    match fetch_existing() {
+        Some(payment) if payment.idempotency_key == *idempotency_key => {
+            Ok(MakePaymentOutcome::Existing(payment))
+        }
+        Some(payment) => Err(IdempotentPaymentError::KeyMismatch {
+            existing: Box::new(payment),
+        }),
+        None => Err(IdempotentPaymentError::Send(error)),
+    }
+}
+
+/// Sends a `MakePayment` transaction through `send` and, if it fails, fetches the payment
+/// account at `payment` to check whether an earlier attempt already created it.
+#[cfg(feature = "fetch")]
+pub fn make_payment_idempotent<T>(
+    rpc: &solana_client::rpc_client::RpcClient,
+    payment: &solana_pubkey::Pubkey,
+    idempotency_key: &IdempotencyKey,
+    send: impl FnOnce() -> Result<T, solana_client::client_error::ClientError>,
+) -> Result<MakePaymentOutcome<T>, IdempotentPaymentError<solana_client::client_error::ClientError>>
+{
+    use crate::{generated::accounts::fetch_maybe_payment, shared::MaybeAccount};
+
+    resolve_make_payment(send(), idempotency_key, || {
+        match fetch_maybe_payment(rpc, payment) {
+            Ok(MaybeAccount::Exists(account)) => Some(account.data),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated::types::{FeeOverride, FeeType, Status};
+
+    fn payment(idempotency_key: IdempotencyKey) -> Payment {
+        Payment {
+            discriminator: 3,
+            order_id: 1,
+            amount: 1_000,
+            created_at: 1_700_000_000,
+            status: Status::Paid,
+            bump: 255,
+            idempotency_key,
+            has_fee_override: false,
+            fee_override: FeeOverride {
+                fee: 0,
+                fee_type: FeeType::Bps,
+            },
+            references: [solana_pubkey::Pubkey::default(); 2],
+            rent_payer: solana_pubkey::Pubkey::default(),
+            has_order: false,
+        }
+    }
+
+    #[test]
+    fn test_resolve_make_payment_created() {
+        let outcome =
+            resolve_make_payment::<_, ()>(Ok("signature"), &[1; 16], || unreachable!()).unwrap();
+        assert_eq!(outcome, MakePaymentOutcome::Created("signature"));
+    }
+
+    #[test]
+    fn test_resolve_make_payment_existing_with_same_key() {
+        let outcome = resolve_make_payment::<(), _>(Err("already exists"), &[1; 16], || {
+            Some(payment([1; 16]))
+        })
+        .unwrap();
+        assert_eq!(outcome, MakePaymentOutcome::Existing(payment([1; 16])));
+    }
+
+    #[test]
+    fn test_resolve_make_payment_existing_with_other_key() {
+        let result = resolve_make_payment::<(), _>(Err("already exists"), &[1; 16], || {
+            Some(payment([2; 16]))
+        });
+        assert!(matches!(
+            result,
+            Err(IdempotentPaymentError::KeyMismatch { existing }) if existing.idempotency_key == [2; 16]
+        ));
+    }
+
+    #[test]
+    fn test_resolve_make_payment_not_found_returns_error() {
+        let result = resolve_make_payment::<(), _>(Err("blockhash expired"), &[1; 16], || None);
+        assert!(matches!(
+            result,
+            Err(IdempotentPaymentError::Send("blockhash expired"))
+        ));
+    }
+}
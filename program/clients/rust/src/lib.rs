@@ -1,10 +1,103 @@
+//! Rust client for the Commerce Program.
+//!
+//! The generated accounts, types, instruction data and CPI builders, with `discriminator` and
+//! `pdas`, are `no_std` and only depend on crates an on-chain program already has, so other
+//! programs can CPI into the commerce program with `default-features = false`. Everything
+//! that builds, sends or decodes transactions is behind the default `sdk` feature.
+
+#![cfg_attr(not(feature = "sdk"), no_std)]
 // Suppress warnings for generated code
 #![allow(warnings)]
 
-// Re-export generated code
+extern crate alloc;
+
+// Re-export generated code, left as codama renders it
+#[rustfmt::skip]
 pub mod generated;
 pub use generated::*;
 
+pub mod discriminator;
+pub mod pdas;
+
+#[cfg(feature = "sdk")]
+pub mod account_cache;
+#[cfg(feature = "sdk")]
+pub mod amount;
+#[cfg(feature = "sdk")]
+pub mod buyer_index;
+#[cfg(feature = "sdk")]
+pub mod callback;
+#[cfg(feature = "sdk")]
+pub mod checkout;
+#[cfg(feature = "sdk")]
+pub mod commerce_client;
+#[cfg(feature = "sdk")]
+pub mod compliance;
+#[cfg(feature = "sdk")]
+pub mod compression;
+#[cfg(feature = "sdk")]
+pub mod config_hash;
+#[cfg(feature = "sdk")]
+pub mod config_registry;
+#[cfg(feature = "sdk")]
+pub mod config_size;
+#[cfg(feature = "sdk")]
+pub mod conversion;
+#[cfg(feature = "sdk")]
+pub mod decode;
+#[cfg(feature = "sdk")]
+pub mod decommission;
+#[cfg(feature = "sdk")]
+pub mod denylist;
+#[cfg(feature = "sdk")]
+pub mod derived_accounts;
+#[cfg(feature = "sdk")]
+pub mod descriptor;
+#[cfg(feature = "sdk")]
+pub mod error_registry;
+#[cfg(feature = "sdk")]
+pub mod escrow_audit;
+#[cfg(feature = "sdk")]
+pub mod event_subscription;
+#[cfg(feature = "sdk")]
+pub mod events;
+#[cfg(feature = "sdk")]
+pub mod fee_calculator;
+#[cfg(feature = "sdk")]
+pub mod freeze_list;
+#[cfg(feature = "sdk")]
+pub mod idempotency;
+#[cfg(feature = "sdk")]
+pub mod memo;
+#[cfg(feature = "sdk")]
+pub mod operator_delegate;
+#[cfg(feature = "sdk")]
+pub mod payment_annotation;
+#[cfg(feature = "sdk")]
+pub mod payment_link;
+#[cfg(feature = "sdk")]
+pub mod preview;
+#[cfg(feature = "sdk")]
+pub mod priority_fee;
+#[cfg(feature = "sdk")]
+pub mod refund_delegate;
+#[cfg(feature = "sdk")]
+pub mod risk;
+#[cfg(feature = "sdk")]
+pub mod send;
+#[cfg(feature = "sdk")]
+pub mod signer;
+#[cfg(feature = "sdk")]
+pub mod solana_pay;
+#[cfg(feature = "sdk")]
+pub mod split_settlement;
+#[cfg(feature = "sdk")]
+pub mod sweep;
+#[cfg(feature = "sdk")]
+pub mod transfer_hook;
+#[cfg(feature = "sdk")]
+pub mod webhook_commitment;
+
 // Re-export commonly used items
 pub use generated::accounts::*;
 pub use generated::errors::*;
@@ -0,0 +1,44 @@
+//! Settlement transfer memos.
+//!
+//! When a config carries a `MemoPolicy`, `ClearPayment` and/or `RefundPayment` log an SPL
+//! Memo next to their token transfers, so settlement wallets that reconcile deposits by
+//! memo (e.g. exchange deposit addresses) can match them to orders. The memo program must
+//! then be passed as the last remaining account, after the optional MerchantStats PDA;
+//! without it the instruction fails with `MemoProgramRequired`.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::checkout::MEMO_PROGRAM_ID;
+
+/// Memo text the program logs for a payment's transfers.
+pub fn transfer_memo(order_id: u32, payment: &Pubkey) -> String {
+    format!("order:{order_id} payment:{payment}")
+}
+
+/// Remaining account to append to `ClearPayment` or `RefundPayment` under a memo policy.
+pub fn memo_program_account() -> AccountMeta {
+    AccountMeta::new_readonly(MEMO_PROGRAM_ID, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_memo_format() {
+        let payment = Pubkey::new_from_array([0; 32]);
+        assert_eq!(
+            transfer_memo(42, &payment),
+            "order:42 payment:11111111111111111111111111111111"
+        );
+    }
+
+    #[test]
+    fn test_memo_program_account_is_readonly() {
+        let meta = memo_program_account();
+        assert_eq!(meta.pubkey, MEMO_PROGRAM_ID);
+        assert!(!meta.is_writable);
+        assert!(!meta.is_signer);
+    }
+}
@@ -0,0 +1,61 @@
+//! Scoped operator signing keys.
+//!
+//! The operator owner authorizes a delegate key with `InitializeOperatorDelegate`, choosing
+//! which of MakePayment, ClearPayment, RefundPayment and ClosePayment (plus their
+//! compressed and settled-refund variants) it may sign. Permissions are replaced with
+//! `UpdateOperatorDelegate` and the delegate is removed with `RevokeOperatorDelegate`.
+//!
+//! When a delegate signs as `operator_authority`, its OperatorDelegate PDA must be the
+//! first remaining account, ahead of the optional MerchantStats PDA.
+//!
+//! A delegate can also be given refund limits: `max_refund_amount` caps each refund it
+//! signs and `max_daily_refund_amount` the total it refunds per UTC day (0 for no limit).
+//! Under a daily limit the refunds are counted on the OperatorDelegate PDA, so pass it with
+//! [`operator_delegate_refund_account`] when the delegate refunds.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+pub use crate::pdas::find_operator_delegate_pda;
+
+/// Remaining account to prepend when `delegate` signs for `operator`.
+pub fn operator_delegate_account(operator: &Pubkey, delegate: &Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(find_operator_delegate_pda(operator, delegate).0, false)
+}
+
+/// Remaining account to prepend when `delegate` refunds for `operator` under a daily refund
+/// limit, writable so the refund is counted.
+pub fn operator_delegate_refund_account(operator: &Pubkey, delegate: &Pubkey) -> AccountMeta {
+    AccountMeta::new(find_operator_delegate_pda(operator, delegate).0, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operator_delegate_pda_is_per_delegate() {
+        let operator = Pubkey::new_unique();
+        let (first, _) = find_operator_delegate_pda(&operator, &Pubkey::new_unique());
+        let (second, _) = find_operator_delegate_pda(&operator, &Pubkey::new_unique());
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_operator_delegate_account_is_readonly() {
+        let operator = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let meta = operator_delegate_account(&operator, &delegate);
+
+        assert_eq!(
+            meta.pubkey,
+            find_operator_delegate_pda(&operator, &delegate).0
+        );
+        assert!(!meta.is_writable);
+        assert!(!meta.is_signer);
+
+        let refund_meta = operator_delegate_refund_account(&operator, &delegate);
+        assert_eq!(refund_meta.pubkey, meta.pubkey);
+        assert!(refund_meta.is_writable);
+    }
+}
@@ -0,0 +1,141 @@
+//! Operator review flags on payments.
+//!
+//! An operator's risk systems or reviewers mark a payment with `AnnotatePayment`, writing an
+//! [`AnnotationFlag`] and the hash of an off-chain note to the payment's PaymentAnnotation PDA.
+//! Each call replaces the last, so the PDA holds the current verdict and the PaymentAnnotated
+//! events the history. Merchants fetch the PDA before shipping and hold the order while
+//! [`holds_fulfilment`] is true; a payment never annotated has no PDA and ships as usual.
+//!
+//! The operator owner signs, or a delegate allowed to clear payments with
+//! [`operator_delegate_account`](crate::operator_delegate::operator_delegate_account) as the
+//! first remaining account.
+
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use solana_sha256_hasher::hashv;
+
+pub use crate::pdas::find_payment_annotation_pda;
+use crate::{
+    generated::{
+        accounts::PaymentAnnotation, instructions::AnnotatePaymentBuilder, types::AnnotationFlag,
+    },
+    pdas::find_payment_pda,
+};
+
+/// Hash of an off-chain note, as stored in `note_hash`.
+pub fn note_hash(note: &[u8]) -> [u8; 32] {
+    hashv(&[note]).to_bytes()
+}
+
+/// Whether the merchant should hold fulfilment of the annotated payment's order.
+pub fn holds_fulfilment(payment_annotation: &PaymentAnnotation) -> bool {
+    match payment_annotation.flag {
+        AnnotationFlag::Ok => false,
+        AnnotationFlag::ManualReview | AnnotationFlag::FlaggedFraud => true,
+    }
+}
+
+/// `AnnotatePayment` flagging the `buyer`'s payment for `order_id` in `mint` under
+/// `merchant_operator_config`. Must be signed by `payer` and `operator_authority`, the owner of
+/// `operator` or one of its delegates.
+#[allow(clippy::too_many_arguments)]
+pub fn annotate_payment(
+    payer: &Pubkey,
+    operator_authority: &Pubkey,
+    operator: &Pubkey,
+    merchant_operator_config: &Pubkey,
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    order_id: u32,
+    flag: AnnotationFlag,
+    note_hash: [u8; 32],
+) -> Instruction {
+    let (payment, _) = find_payment_pda(merchant_operator_config, buyer, mint, order_id);
+    AnnotatePaymentBuilder::new()
+        .payer(*payer)
+        .payment(payment)
+        .operator_authority(*operator_authority)
+        .buyer(*buyer)
+        .operator(*operator)
+        .merchant_operator_config(*merchant_operator_config)
+        .mint(*mint)
+        .payment_annotation(find_payment_annotation_pda(&payment).0)
+        .flag(flag)
+        .note_hash(note_hash)
+        .instruction()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{discriminator::parse_instruction, discriminator::ParsedInstruction};
+
+    fn payment_annotation(flag: AnnotationFlag) -> PaymentAnnotation {
+        PaymentAnnotation {
+            discriminator: 19,
+            payment: Pubkey::new_unique(),
+            merchant_operator_config: Pubkey::new_unique(),
+            bump: 255,
+            flag,
+            note_hash: [0; 32],
+            annotated_by: Pubkey::new_unique(),
+            updated_at: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_holds_fulfilment() {
+        assert!(!holds_fulfilment(&payment_annotation(AnnotationFlag::Ok)));
+        assert!(holds_fulfilment(&payment_annotation(
+            AnnotationFlag::ManualReview
+        )));
+        assert!(holds_fulfilment(&payment_annotation(
+            AnnotationFlag::FlaggedFraud
+        )));
+    }
+
+    #[test]
+    fn test_note_hash() {
+        assert_eq!(note_hash(b"chargeback risk"), note_hash(b"chargeback risk"));
+        assert_ne!(note_hash(b"chargeback risk"), note_hash(b"cleared"));
+    }
+
+    #[test]
+    fn test_annotate_payment() {
+        let payer = Pubkey::new_unique();
+        let operator_authority = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let merchant_operator_config = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let hash = note_hash(b"card testing pattern");
+
+        let instruction = annotate_payment(
+            &payer,
+            &operator_authority,
+            &operator,
+            &merchant_operator_config,
+            &buyer,
+            &mint,
+            7,
+            AnnotationFlag::FlaggedFraud,
+            hash,
+        );
+        let (payment, _) = find_payment_pda(&merchant_operator_config, &buyer, &mint, 7);
+        assert_eq!(instruction.accounts[1].pubkey, payment);
+        assert!(instruction.accounts[2].is_signer);
+        assert_eq!(
+            instruction.accounts[7].pubkey,
+            find_payment_annotation_pda(&payment).0
+        );
+        assert!(instruction.accounts[7].is_writable);
+
+        let ParsedInstruction::AnnotatePayment(args) =
+            parse_instruction(&instruction.data).unwrap()
+        else {
+            panic!("expected AnnotatePayment");
+        };
+        assert_eq!(args.flag, AnnotationFlag::FlaggedFraud);
+        assert_eq!(args.note_hash, hash);
+    }
+}
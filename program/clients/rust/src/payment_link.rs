@@ -0,0 +1,295 @@
+//! Signed, expiring payment links for pay-by-link commerce.
+//!
+//! A [`PaymentLink`] names everything `MakePayment` takes from the merchant side: the
+//! config, mint, amount, order id and idempotency key, plus the time the link stops being
+//! payable. The operator authority signs it with its ed25519 key, and the token carried in
+//! the link's URL is the hex encoding of the link, the signer and the signature:
+//!
+//! | Field | Size | Description |
+//! |-------|------|-------------|
+//! | `version` | 1 | [`PAYMENT_LINK_VERSION`] |
+//! | `merchant_operator_config` | 32 | Config PDA the payment is made on |
+//! | `mint` | 32 | Token mint paid in |
+//! | `amount` | 8 | Payment amount, little-endian |
+//! | `order_id` | 4 | Order id of the payment, little-endian |
+//! | `idempotency_key` | 16 | Idempotency key of the checkout |
+//! | `expires_at` | 8 | Unix timestamp the link expires at, little-endian |
+//! | `signer` | 32 | Operator authority that signed the link |
+//! | `signature` | 64 | Signature over [`PAYMENT_LINK_DOMAIN`] followed by the fields above `signer` |
+//!
+//! The service paying out links, such as `commerce-kit-api`, checks a token with
+//! [`SignedPaymentLink::verify`] against the operator authorities it trusts before building
+//! the payment from [`PaymentLink::checkout_request`] for the buyer who opened it. Anyone
+//! holding a token can pay it, but only the operator can create or change one. The domain
+//! prefix starts with `0xff`, which no transaction message does, so a link signature can
+//! never be replayed as a transaction signature.
+
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_signer::{Signer, SignerError};
+use thiserror::Error;
+
+use crate::{idempotency::IdempotencyKey, solana_pay::CheckoutRequest};
+
+/// Version byte of the current payment link layout.
+pub const PAYMENT_LINK_VERSION: u8 = 1;
+
+/// Prefix of every signed payment link message.
+pub const PAYMENT_LINK_DOMAIN: &[u8] = b"\xffcommerce-kit payment link";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PaymentLinkError {
+    #[error("payment link token is not hex")]
+    InvalidEncoding,
+    #[error("payment link token has {0} bytes")]
+    InvalidLength(usize),
+    #[error("unsupported payment link version {0}")]
+    UnsupportedVersion(u8),
+    #[error("payment link signer {0} is not trusted")]
+    UntrustedSigner(Pubkey),
+    #[error("payment link signature does not verify")]
+    InvalidSignature,
+    #[error("payment link expired at {0}")]
+    Expired(i64),
+}
+
+/// A payment the operator offers through a link.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentLink {
+    pub merchant_operator_config: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub order_id: u32,
+    pub idempotency_key: IdempotencyKey,
+    /// Unix timestamp from which the link can no longer be paid
+    pub expires_at: i64,
+}
+
+/// A payment link with the operator authority's signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedPaymentLink {
+    pub link: PaymentLink,
+    pub signer: Pubkey,
+    pub signature: Signature,
+}
+
+impl PaymentLink {
+    pub const LEN: usize = 1 + // version
+        32 + // merchant_operator_config
+        32 + // mint
+        8 + // amount
+        4 + // order_id
+        16 + // idempotency_key
+        8; // expires_at
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN);
+        data.push(PAYMENT_LINK_VERSION);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.mint.as_ref());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.idempotency_key);
+        data.extend_from_slice(&self.expires_at.to_le_bytes());
+        data
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, PaymentLinkError> {
+        if data.len() != Self::LEN {
+            return Err(PaymentLinkError::InvalidLength(data.len()));
+        }
+        if data[0] != PAYMENT_LINK_VERSION {
+            return Err(PaymentLinkError::UnsupportedVersion(data[0]));
+        }
+        let pubkey = |offset: usize| Pubkey::new_from_array(array(&data[offset..offset + 32]));
+        Ok(Self {
+            merchant_operator_config: pubkey(1),
+            mint: pubkey(33),
+            amount: u64::from_le_bytes(array(&data[65..73])),
+            order_id: u32::from_le_bytes(array(&data[73..77])),
+            idempotency_key: array(&data[77..93]),
+            expires_at: i64::from_le_bytes(array(&data[93..101])),
+        })
+    }
+
+    /// Message the operator authority signs.
+    pub fn signing_message(&self) -> Vec<u8> {
+        [PAYMENT_LINK_DOMAIN, &self.to_bytes()].concat()
+    }
+
+    /// Signs the link as `signer`, normally the config's operator authority.
+    pub fn sign(self, signer: &dyn Signer) -> Result<SignedPaymentLink, SignerError> {
+        Ok(SignedPaymentLink {
+            signer: signer.try_pubkey()?,
+            signature: signer.try_sign_message(&self.signing_message())?,
+            link: self,
+        })
+    }
+
+    /// Checkout of the link, for building its `MakePayment` with
+    /// [`CheckoutRequest::checkout_builder`].
+    pub fn checkout_request(&self) -> CheckoutRequest {
+        CheckoutRequest {
+            merchant_operator_config: self.merchant_operator_config,
+            mint: self.mint,
+            amount: self.amount,
+            order_id: self.order_id,
+            idempotency_key: self.idempotency_key,
+            references: Vec::new(),
+        }
+    }
+}
+
+impl SignedPaymentLink {
+    pub const LEN: usize = PaymentLink::LEN + 32 + 64;
+
+    /// Hex token to carry in the link's URL.
+    pub fn to_token(&self) -> String {
+        let mut data = self.link.to_bytes();
+        data.extend_from_slice(self.signer.as_ref());
+        data.extend_from_slice(self.signature.as_ref());
+        data.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Parses a token without checking its signature or expiry.
+    pub fn from_token(token: &str) -> Result<Self, PaymentLinkError> {
+        if !token.is_ascii() || token.len() % 2 != 0 {
+            return Err(PaymentLinkError::InvalidEncoding);
+        }
+        let data = (0..token.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&token[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| PaymentLinkError::InvalidEncoding)?;
+        if data.len() != Self::LEN {
+            return Err(PaymentLinkError::InvalidLength(data.len()));
+        }
+
+        let (link, rest) = data.split_at(PaymentLink::LEN);
+        let (signer, signature) = rest.split_at(32);
+        Ok(Self {
+            link: PaymentLink::from_bytes(link)?,
+            signer: Pubkey::new_from_array(array(signer)),
+            signature: Signature::from(array::<64>(signature)),
+        })
+    }
+
+    /// The link, if it was signed by one of `trusted_signers` and has not expired at `now`.
+    /// Needs the `fetch` feature.
+    #[cfg(feature = "fetch")]
+    pub fn verify(
+        &self,
+        trusted_signers: &[Pubkey],
+        now: i64,
+    ) -> Result<&PaymentLink, PaymentLinkError> {
+        if !trusted_signers.contains(&self.signer) {
+            return Err(PaymentLinkError::UntrustedSigner(self.signer));
+        }
+        if !self
+            .signature
+            .verify(self.signer.as_ref(), &self.link.signing_message())
+        {
+            return Err(PaymentLinkError::InvalidSignature);
+        }
+        if now >= self.link.expires_at {
+            return Err(PaymentLinkError::Expired(self.link.expires_at));
+        }
+        Ok(&self.link)
+    }
+}
+
+fn array<const N: usize>(data: &[u8]) -> [u8; N] {
+    data.try_into().expect("length checked by the caller")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_keypair::Keypair;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn link() -> PaymentLink {
+        PaymentLink {
+            merchant_operator_config: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount: 1_500_000,
+            order_id: 42,
+            idempotency_key: [0xa5; 16],
+            expires_at: NOW + 3_600,
+        }
+    }
+
+    #[test]
+    fn test_token_roundtrip() {
+        let operator_authority = Keypair::new();
+        let signed = link().sign(&operator_authority).unwrap();
+
+        let token = signed.to_token();
+        assert_eq!(token.len(), 2 * SignedPaymentLink::LEN);
+        assert_eq!(SignedPaymentLink::from_token(&token).unwrap(), signed);
+        assert_eq!(signed.signer, operator_authority.pubkey());
+    }
+
+    #[test]
+    fn test_from_token_errors() {
+        let token = link().sign(&Keypair::new()).unwrap().to_token();
+
+        assert_eq!(
+            SignedPaymentLink::from_token(&token.replacen('a', "z", 1)),
+            Err(PaymentLinkError::InvalidEncoding)
+        );
+        assert_eq!(
+            SignedPaymentLink::from_token(&token[2..]),
+            Err(PaymentLinkError::InvalidLength(SignedPaymentLink::LEN - 1))
+        );
+        assert_eq!(
+            SignedPaymentLink::from_token(&format!("02{}", &token[2..])),
+            Err(PaymentLinkError::UnsupportedVersion(2))
+        );
+    }
+
+    #[test]
+    fn test_checkout_request() {
+        let link = link();
+        let request = link.checkout_request();
+
+        assert_eq!(
+            request.merchant_operator_config,
+            link.merchant_operator_config
+        );
+        assert_eq!(request.mint, link.mint);
+        assert_eq!(request.amount, link.amount);
+        assert_eq!(request.order_id, link.order_id);
+        assert_eq!(request.idempotency_key, link.idempotency_key);
+        assert!(request.references.is_empty());
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn test_verify() {
+        let operator_authority = Keypair::new();
+        let trusted = [Pubkey::new_unique(), operator_authority.pubkey()];
+        let signed = link().sign(&operator_authority).unwrap();
+
+        assert_eq!(signed.verify(&trusted, NOW), Ok(&signed.link));
+        assert_eq!(
+            signed.verify(&trusted[..1], NOW),
+            Err(PaymentLinkError::UntrustedSigner(
+                operator_authority.pubkey()
+            ))
+        );
+        assert_eq!(
+            signed.verify(&trusted, signed.link.expires_at),
+            Err(PaymentLinkError::Expired(signed.link.expires_at))
+        );
+
+        // Raising the amount invalidates the signature
+        let mut tampered = signed.clone();
+        tampered.link.amount += 1;
+        assert_eq!(
+            tampered.verify(&trusted, NOW),
+            Err(PaymentLinkError::InvalidSignature)
+        );
+    }
+}
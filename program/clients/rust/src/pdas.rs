@@ -0,0 +1,298 @@
+//! Program-derived addresses.
+//!
+//! Each function mirrors the seeds the program checks for that account, so integrators do
+//! not need to re-implement seed layouts. Integer seeds are little-endian.
+
+use solana_pubkey::Pubkey;
+
+use crate::COMMERCE_PROGRAM_ID;
+
+pub const BUYER_INDEX_SEED: &[u8] = b"buyer_index";
+pub const CONFIG_REGISTRY_SEED: &[u8] = b"config_registry";
+pub const CONVERSION_RATE_SEED: &[u8] = b"conversion_rate";
+pub const ESCROW_AUTHORITY_SEED: &[u8] = b"escrow_authority";
+pub const MERCHANT_SEED: &[u8] = b"merchant";
+pub const MERCHANT_OPERATOR_CONFIG_SEED: &[u8] = b"merchant_operator_config";
+pub const MERCHANT_PROFILE_SEED: &[u8] = b"merchant_profile";
+pub const MERCHANT_STATS_SEED: &[u8] = b"merchant_stats";
+pub const OPERATOR_SEED: &[u8] = b"operator";
+pub const OPERATOR_DELEGATE_SEED: &[u8] = b"operator_delegate";
+pub const OPERATOR_FREEZE_LIST_SEED: &[u8] = b"operator_freeze_list";
+pub const ORDER_SEED: &[u8] = b"order";
+pub const PAYMENT_SEED: &[u8] = b"payment";
+pub const PAYMENT_ANNOTATION_SEED: &[u8] = b"payment_annotation";
+pub const PAYMENT_NOTE_SEED: &[u8] = b"payment_note";
+pub const PAYMENT_TREE_SEED: &[u8] = b"payment_tree";
+pub const QUOTE_SEED: &[u8] = b"quote";
+pub const REFUND_APPROVAL_SEED: &[u8] = b"refund_approval";
+pub const REFUND_DELEGATE_SEED: &[u8] = b"refund_delegate";
+pub const RISK_STATE_SEED: &[u8] = b"risk_state";
+pub const SETTLEMENT_BATCH_SEED: &[u8] = b"settlement_batch";
+pub const WEBHOOK_COMMITMENT_SEED: &[u8] = b"webhook_commitment";
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"event_authority";
+
+/// Merchant PDA and bump for the merchant's `owner`.
+pub fn find_merchant_pda(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MERCHANT_SEED, owner.as_ref()], &COMMERCE_PROGRAM_ID)
+}
+
+/// Operator PDA and bump for the operator's `owner`.
+pub fn find_operator_pda(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OPERATOR_SEED, owner.as_ref()], &COMMERCE_PROGRAM_ID)
+}
+
+/// MerchantOperatorConfig PDA and bump for `version` of a merchant-operator pair.
+pub fn find_merchant_operator_config_pda(
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    version: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            MERCHANT_OPERATOR_CONFIG_SEED,
+            merchant.as_ref(),
+            operator.as_ref(),
+            &version.to_le_bytes(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// ConfigRegistryPage PDA and bump for page `page` of `operator`'s config registry.
+pub fn find_config_registry_page_pda(operator: &Pubkey, page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CONFIG_REGISTRY_SEED, operator.as_ref(), &page.to_le_bytes()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// OperatorFreezeList PDA and bump for `operator`.
+pub fn find_operator_freeze_list_pda(operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OPERATOR_FREEZE_LIST_SEED, operator.as_ref()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// ConversionRate PDA and bump for a config's rate from `from_mint` into `to_mint`.
+pub fn find_conversion_rate_pda(
+    merchant_operator_config: &Pubkey,
+    from_mint: &Pubkey,
+    to_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CONVERSION_RATE_SEED,
+            merchant_operator_config.as_ref(),
+            from_mint.as_ref(),
+            to_mint.as_ref(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// MerchantProfile PDA and bump for `merchant`.
+pub fn find_merchant_profile_pda(merchant: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MERCHANT_PROFILE_SEED, merchant.as_ref()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// BuyerIndex PDA and bump listing `buyer`'s recent payments under a config.
+pub fn find_buyer_index_pda(merchant_operator_config: &Pubkey, buyer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            BUYER_INDEX_SEED,
+            merchant_operator_config.as_ref(),
+            buyer.as_ref(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// Payment PDA and bump for a checkout.
+pub fn find_payment_pda(
+    merchant_operator_config: &Pubkey,
+    buyer: &Pubkey,
+    mint: &Pubkey,
+    order_id: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PAYMENT_SEED,
+            merchant_operator_config.as_ref(),
+            buyer.as_ref(),
+            mint.as_ref(),
+            &order_id.to_le_bytes(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// PaymentAnnotation PDA and bump for the operator's annotation of a `payment`.
+pub fn find_payment_annotation_pda(payment: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PAYMENT_ANNOTATION_SEED, payment.as_ref()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// PaymentNote PDA and bump for the note attached to `payment`.
+pub fn find_payment_note_pda(payment: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAYMENT_NOTE_SEED, payment.as_ref()], &COMMERCE_PROGRAM_ID)
+}
+
+/// PaymentTree PDA and bump for a config's compressed payments.
+pub fn find_payment_tree_pda(merchant_operator_config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PAYMENT_TREE_SEED, merchant_operator_config.as_ref()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// MerchantStats PDA and bump for `merchant` in `mint`.
+pub fn find_merchant_stats_pda(merchant: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MERCHANT_STATS_SEED, merchant.as_ref(), mint.as_ref()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// OperatorDelegate PDA and bump for `delegate` acting for `operator`.
+pub fn find_operator_delegate_pda(operator: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OPERATOR_DELEGATE_SEED, operator.as_ref(), delegate.as_ref()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// Order PDA and bump shared by a buyer's payments for `order_id` under a config.
+pub fn find_order_pda(
+    merchant_operator_config: &Pubkey,
+    buyer: &Pubkey,
+    order_id: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ORDER_SEED,
+            merchant_operator_config.as_ref(),
+            buyer.as_ref(),
+            &order_id.to_le_bytes(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// Quote PDA and bump for a config's `order_id` quote in `mint`.
+pub fn find_quote_pda(
+    merchant_operator_config: &Pubkey,
+    mint: &Pubkey,
+    order_id: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            QUOTE_SEED,
+            merchant_operator_config.as_ref(),
+            mint.as_ref(),
+            &order_id.to_le_bytes(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// RefundApproval PDA and bump for `payment`.
+pub fn find_refund_approval_pda(payment: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFUND_APPROVAL_SEED, payment.as_ref()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// Refund delegate PDA and bump for `merchant`.
+pub fn find_refund_delegate_pda(merchant: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REFUND_DELEGATE_SEED, merchant.as_ref()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// RiskState PDA and bump counting `buyer`'s payments under a config, or every payment
+/// under it for `Pubkey::default()`.
+pub fn find_risk_state_pda(merchant_operator_config: &Pubkey, buyer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            RISK_STATE_SEED,
+            merchant_operator_config.as_ref(),
+            buyer.as_ref(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// SettlementBatch PDA and bump for a config's batch in `mint`.
+pub fn find_settlement_batch_pda(merchant_operator_config: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SETTLEMENT_BATCH_SEED,
+            merchant_operator_config.as_ref(),
+            mint.as_ref(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// WebhookCommitment PDA and bump for a config's webhook endpoint commitment.
+pub fn find_webhook_commitment_pda(merchant_operator_config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[WEBHOOK_COMMITMENT_SEED, merchant_operator_config.as_ref()],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// Escrow authority PDA and bump, the owner of a config's escrow ATA in `mint`.
+pub fn find_escrow_authority_pda(merchant_operator_config: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ESCROW_AUTHORITY_SEED,
+            merchant_operator_config.as_ref(),
+            mint.as_ref(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    )
+}
+
+/// Event authority PDA and bump, passed as `event_authority` to instructions that emit events.
+pub fn find_event_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &COMMERCE_PROGRAM_ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_pubkey::pubkey;
+
+    #[test]
+    fn test_find_event_authority_pda_matches_builder_default() {
+        assert_eq!(
+            find_event_authority_pda().0,
+            pubkey!("3VSJP7faqLk6MbCaNtMYc2Y8S8hMXRsZ5cBcwh1fjMH1")
+        );
+    }
+
+    #[test]
+    fn test_merchant_and_operator_pdas_differ_per_seed() {
+        let owner = Pubkey::new_unique();
+        assert_ne!(find_merchant_pda(&owner).0, find_operator_pda(&owner).0);
+    }
+
+    #[test]
+    fn test_find_merchant_operator_config_pda_is_per_version() {
+        let merchant = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        assert_ne!(
+            find_merchant_operator_config_pda(&merchant, &operator, 1).0,
+            find_merchant_operator_config_pda(&merchant, &operator, 2).0
+        );
+    }
+}
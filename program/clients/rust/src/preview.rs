@@ -0,0 +1,792 @@
+//! Client-side previews of `ClearPayment` and `RefundPayment`.
+//!
+//! These mirror the program's fee calculation and policy checks against fetched account
+//! state, so checkout UIs can show fees and surface policy violations before sending a
+//! transaction. The program remains the source of truth.
+
+use borsh::BorshDeserialize;
+use solana_pubkey::Pubkey;
+
+use crate::generated::{
+    accounts::{MerchantOperatorConfig, Payment},
+    errors::CommerceProgramError,
+    types::{FeeType, PlatformFeePolicy, PolicyData, Status},
+};
+
+/// Basis points denominator used for `FeeType::Bps`.
+pub const MAX_BPS: u64 = 10_000;
+
+/// Size of each policy slot stored after the config's fixed fields.
+pub const POLICY_DATA_SIZE: usize = 101;
+
+const SECONDS_PER_HOUR: u64 = 3_600;
+
+const SECONDS_PER_DAY: i128 = 86_400;
+
+/// First check a previewed instruction would fail.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// The payment is not in the `Paid` status.
+    InvalidPaymentStatus { status: Status },
+    /// The payment is below the settlement policy's minimum amount.
+    InsufficientSettlementAmount { min_settlement_amount: u64 },
+    /// The settlement policy's waiting period has not elapsed.
+    SettlementTooEarly { clearable_at: i64 },
+    /// The chargeback policy's window is still open.
+    ChargebackWindowActive { clearable_at: i64 },
+    /// The payment exceeds the refund policy's maximum amount.
+    RefundAmountExceedsPolicyLimit { max_amount: u64 },
+    /// The refund policy's window has closed.
+    RefundWindowExpired { expired_at: i64 },
+}
+
+impl PolicyViolation {
+    /// Program error the instruction would fail with.
+    pub fn program_error(&self) -> CommerceProgramError {
+        match self {
+            Self::InvalidPaymentStatus { .. } => CommerceProgramError::InvalidPaymentStatus,
+            Self::InsufficientSettlementAmount { .. } => {
+                CommerceProgramError::InsufficientSettlementAmount
+            }
+            Self::SettlementTooEarly { .. } => CommerceProgramError::SettlementTooEarly,
+            Self::ChargebackWindowActive { .. } => CommerceProgramError::ChargebackWindowActive,
+            Self::RefundAmountExceedsPolicyLimit { .. } => {
+                CommerceProgramError::RefundAmountExceedsPolicyLimit
+            }
+            Self::RefundWindowExpired { .. } => CommerceProgramError::RefundWindowExpired,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClearPaymentPreview {
+    /// Amount sent to the operator's settlement ATA.
+    pub operator_fee: u64,
+    /// Amount sent to the platform's settlement ATA under a platform fee policy.
+    pub platform_fee: u64,
+    /// Amount sent to the merchant's settlement ATA.
+    pub merchant_amount: u64,
+    /// First check the clear would fail, if any.
+    pub violation: Option<PolicyViolation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefundPaymentPreview {
+    /// Amount returned to the buyer.
+    pub refund_amount: u64,
+    /// First check the refund would fail, if any.
+    pub violation: Option<PolicyViolation>,
+}
+
+/// Splits `total_amount` into `(operator_fee, merchant_amount)` like the program does.
+///
+/// A fee tier matching `total_amount` in the first `FeeTier` policy overrides the flat
+/// `operator_fee`. Basis points are computed in u128 like the program, so any amount works;
+/// returns `None` when the fee would exceed `total_amount`, as a fee above `MAX_BPS` does.
+pub fn calculate_fees(
+    total_amount: u64,
+    operator_fee: u64,
+    fee_type: FeeType,
+    policies: &[PolicyData],
+) -> Option<(u64, u64)> {
+    let (operator_fee, fee_type) = policies
+        .iter()
+        .find_map(|policy| match policy {
+            PolicyData::FeeTier(fee_tiers) => Some(fee_tiers),
+            _ => None,
+        })
+        .and_then(|fee_tiers| {
+            let num_tiers = (fee_tiers.num_tiers as usize).min(fee_tiers.tiers.len());
+            fee_tiers.tiers[..num_tiers]
+                .iter()
+                .rev()
+                .find(|tier| tier.threshold <= total_amount)
+        })
+        .map_or((operator_fee, fee_type), |tier| (tier.fee, tier.fee_type));
+
+    let operator_fee_amount = match fee_type {
+        FeeType::Bps => {
+            let fee = total_amount as u128 * operator_fee as u128 / MAX_BPS as u128;
+            u64::try_from(fee).unwrap_or(u64::MAX)
+        }
+        // Fixed fees are capped at the payment amount
+        FeeType::Fixed => operator_fee.min(total_amount),
+    };
+
+    let merchant_amount = total_amount.checked_sub(operator_fee_amount)?;
+    Some((operator_fee_amount, merchant_amount))
+}
+
+/// The platform's fee on a payment of `amount` leaving `available` for the merchant after
+/// the operator fee, capped at `available` like the program.
+pub fn platform_fee_amount(platform_fee: &PlatformFeePolicy, amount: u64, available: u64) -> u64 {
+    let fee = match platform_fee.fee_type {
+        FeeType::Bps => {
+            let fee = amount as u128 * platform_fee.fee as u128 / MAX_BPS as u128;
+            u64::try_from(fee).unwrap_or(u64::MAX)
+        }
+        FeeType::Fixed => platform_fee.fee,
+    };
+    fee.min(available)
+}
+
+/// Decodes a `MerchantOperatorConfig` account along with its policies and accepted
+/// currencies, which are stored after the fixed fields.
+pub fn decode_merchant_operator_config(
+    data: &[u8],
+) -> Result<(MerchantOperatorConfig, Vec<PolicyData>, Vec<Pubkey>), std::io::Error> {
+    let config = MerchantOperatorConfig::from_bytes(data)?;
+    let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "account too short");
+
+    let mut offset = MerchantOperatorConfig::LEN;
+
+    let mut policies = Vec::with_capacity(config.num_policies as usize);
+    for _ in 0..config.num_policies {
+        let mut slot = data
+            .get(offset..offset + POLICY_DATA_SIZE)
+            .ok_or_else(invalid)?;
+        policies.push(PolicyData::deserialize(&mut slot)?);
+        offset += POLICY_DATA_SIZE;
+    }
+
+    let mut currencies = Vec::with_capacity(config.num_accepted_currencies as usize);
+    for _ in 0..config.num_accepted_currencies {
+        let mint = data.get(offset..offset + 32).ok_or_else(invalid)?;
+        currencies.push(Pubkey::try_from(mint).map_err(|_| invalid())?);
+        offset += 32;
+    }
+
+    Ok((config, policies, currencies))
+}
+
+/// Decodes the open-payment count of each accepted currency of a `MerchantOperatorConfig`
+/// account, in currency order: the payments holding funds in the config's escrow for it.
+/// The counts are stored after the accepted currencies.
+pub fn decode_open_payments(data: &[u8]) -> Result<Vec<u32>, std::io::Error> {
+    let config = MerchantOperatorConfig::from_bytes(data)?;
+    let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "account too short");
+
+    let mut offset = MerchantOperatorConfig::LEN
+        + config.num_policies as usize * POLICY_DATA_SIZE
+        + config.num_accepted_currencies as usize * 32;
+
+    let mut open_payments = Vec::with_capacity(config.num_accepted_currencies as usize);
+    for _ in 0..config.num_accepted_currencies {
+        let count = data.get(offset..offset + 4).ok_or_else(invalid)?;
+        open_payments.push(u32::from_le_bytes(count.try_into().map_err(|_| invalid())?));
+        offset += 4;
+    }
+
+    Ok(open_payments)
+}
+
+/// Previews `ClearPayment` for `payment` at unix timestamp `now`.
+///
+/// Returns `None` when the fee calculation overflows, which the program rejects.
+pub fn preview_clear_payment(
+    config: &MerchantOperatorConfig,
+    policies: &[PolicyData],
+    payment: &Payment,
+    now: i64,
+) -> Option<ClearPaymentPreview> {
+    // A fee override from MakePayment replaces the config's fee and fee tiers
+    let (operator_fee, merchant_amount) = if payment.has_fee_override {
+        calculate_fees(
+            payment.amount,
+            payment.fee_override.fee,
+            payment.fee_override.fee_type,
+            &[],
+        )?
+    } else {
+        calculate_fees(
+            payment.amount,
+            config.operator_fee,
+            config.fee_type,
+            policies,
+        )?
+    };
+
+    // A settlement discount moves part of the operator fee to the merchant per full day held
+    let discount = policies
+        .iter()
+        .find_map(|policy| match policy {
+            PolicyData::SettlementDiscount(settlement_discount) => Some(settlement_discount),
+            _ => None,
+        })
+        .map_or(0, |settlement_discount| {
+            let days_held = ((now as i128 - payment.created_at as i128) / SECONDS_PER_DAY).max(0);
+            let discount_bps = (days_held * settlement_discount.discount_bps_per_day as i128)
+                .min(settlement_discount.max_discount_bps as i128);
+            (operator_fee as u128 * discount_bps as u128 / MAX_BPS as u128) as u64
+        });
+    let (operator_fee, merchant_amount) = (operator_fee - discount, merchant_amount + discount);
+
+    // A platform fee comes out of the merchant amount, capped at what the operator fee leaves
+    let platform_fee = policies
+        .iter()
+        .find_map(|policy| match policy {
+            PolicyData::PlatformFee(platform_fee) => Some(platform_fee),
+            _ => None,
+        })
+        .map_or(0, |platform_fee| {
+            platform_fee_amount(platform_fee, payment.amount, merchant_amount)
+        });
+    let merchant_amount = merchant_amount - platform_fee;
+
+    // Like the program, only the first settlement and chargeback policies apply
+    let violation = status_violation(payment)
+        .or_else(|| {
+            let settlement = policies.iter().find_map(|policy| match policy {
+                PolicyData::Settlement(settlement) => Some(settlement),
+                _ => None,
+            })?;
+
+            if settlement.min_settlement_amount > 0
+                && payment.amount < settlement.min_settlement_amount
+            {
+                return Some(PolicyViolation::InsufficientSettlementAmount {
+                    min_settlement_amount: settlement.min_settlement_amount,
+                });
+            }
+
+            let clearable_at = deadline(
+                payment.created_at,
+                settlement.settlement_frequency_hours as u64 * SECONDS_PER_HOUR,
+            );
+            if settlement.settlement_frequency_hours > 0 && (now as i128) < clearable_at {
+                return Some(PolicyViolation::SettlementTooEarly {
+                    clearable_at: saturate(clearable_at),
+                });
+            }
+
+            None
+        })
+        .or_else(|| {
+            let chargeback = policies.iter().find_map(|policy| match policy {
+                PolicyData::Chargeback(chargeback) => Some(chargeback),
+                _ => None,
+            })?;
+
+            let clearable_at = deadline(payment.created_at, chargeback.window_seconds);
+            ((now as i128) < clearable_at).then_some(PolicyViolation::ChargebackWindowActive {
+                clearable_at: saturate(clearable_at),
+            })
+        });
+
+    Some(ClearPaymentPreview {
+        operator_fee,
+        platform_fee,
+        merchant_amount,
+        violation,
+    })
+}
+
+/// Previews `RefundPayment` for `payment` at unix timestamp `now`.
+pub fn preview_refund_payment(
+    policies: &[PolicyData],
+    payment: &Payment,
+    now: i64,
+) -> RefundPaymentPreview {
+    // Like the program, only the first refund policy applies
+    let violation = status_violation(payment).or_else(|| {
+        let refund = policies.iter().find_map(|policy| match policy {
+            PolicyData::Refund(refund) => Some(refund),
+            _ => None,
+        })?;
+
+        if refund.max_amount < payment.amount {
+            return Some(PolicyViolation::RefundAmountExceedsPolicyLimit {
+                max_amount: refund.max_amount,
+            });
+        }
+
+        let expired_at = deadline(payment.created_at, refund.max_time_after_purchase);
+        if refund.max_time_after_purchase > 0 && now as i128 > expired_at {
+            return Some(PolicyViolation::RefundWindowExpired {
+                expired_at: saturate(expired_at),
+            });
+        }
+
+        None
+    });
+
+    RefundPaymentPreview {
+        refund_amount: payment.amount,
+        violation,
+    }
+}
+
+/// `start` plus `seconds` in i128, so a deadline past `i64::MAX` still compares as later
+/// than any timestamp, like the program's policy checks.
+fn deadline(start: i64, seconds: u64) -> i128 {
+    start as i128 + seconds as i128
+}
+
+/// Clamps a deadline to the `i64` reported in a `PolicyViolation`.
+fn saturate(deadline: i128) -> i64 {
+    deadline.min(i64::MAX as i128) as i64
+}
+
+fn status_violation(payment: &Payment) -> Option<PolicyViolation> {
+    (payment.status != Status::Paid).then_some(PolicyViolation::InvalidPaymentStatus {
+        status: payment.status,
+    })
+}
+
+/// Fetches the config and payment and previews `ClearPayment` at the cluster's current
+/// block time.
+#[cfg(feature = "fetch")]
+pub fn fetch_preview_clear_payment(
+    rpc: &solana_client::rpc_client::RpcClient,
+    merchant_operator_config: &Pubkey,
+    payment: &Pubkey,
+) -> Result<Option<ClearPaymentPreview>, std::io::Error> {
+    let (config, policies, payment, now) =
+        fetch_preview_state(rpc, merchant_operator_config, payment)?;
+    Ok(preview_clear_payment(&config, &policies, &payment, now))
+}
+
+/// Fetches the config and payment and previews `RefundPayment` at the cluster's current
+/// block time.
+#[cfg(feature = "fetch")]
+pub fn fetch_preview_refund_payment(
+    rpc: &solana_client::rpc_client::RpcClient,
+    merchant_operator_config: &Pubkey,
+    payment: &Pubkey,
+) -> Result<RefundPaymentPreview, std::io::Error> {
+    let (_, policies, payment, now) = fetch_preview_state(rpc, merchant_operator_config, payment)?;
+    Ok(preview_refund_payment(&policies, &payment, now))
+}
+
+#[cfg(feature = "fetch")]
+fn fetch_preview_state(
+    rpc: &solana_client::rpc_client::RpcClient,
+    merchant_operator_config: &Pubkey,
+    payment: &Pubkey,
+) -> Result<(MerchantOperatorConfig, Vec<PolicyData>, Payment, i64), std::io::Error> {
+    let to_io = |e: solana_client::client_error::ClientError| {
+        std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+    };
+
+    let config_account = rpc.get_account(merchant_operator_config).map_err(to_io)?;
+    let (config, policies, _) = decode_merchant_operator_config(&config_account.data)?;
+    let payment = crate::generated::accounts::fetch_payment(rpc, payment)?.data;
+    let now = rpc
+        .get_slot()
+        .and_then(|slot| rpc.get_block_time(slot))
+        .map_err(to_io)?;
+
+    Ok((config, policies, payment, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generated::types::{
+        ChargebackPolicy, FeeOverride, FeeTier, FeeTierPolicy, PlatformFeePolicy, RefundPolicy,
+        SettlementDiscountPolicy, SettlementPolicy,
+    };
+    use borsh::BorshSerialize;
+
+    const CREATED_AT: i64 = 1_700_000_000;
+
+    fn config(operator_fee: u64, fee_type: FeeType) -> MerchantOperatorConfig {
+        MerchantOperatorConfig {
+            discriminator: 2,
+            version: 1,
+            bump: 255,
+            merchant: Pubkey::new_from_array([1; 32]),
+            operator: Pubkey::new_from_array([2; 32]),
+            operator_fee,
+            fee_type,
+            current_order_id: 0,
+            days_to_close: 30,
+            num_policies: 0,
+            num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        }
+    }
+
+    fn payment(amount: u64, status: Status) -> Payment {
+        Payment {
+            discriminator: 3,
+            order_id: 1,
+            amount,
+            created_at: CREATED_AT,
+            status,
+            bump: 255,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride {
+                fee: 0,
+                fee_type: FeeType::Bps,
+            },
+            references: [Pubkey::default(); 2],
+            rent_payer: Pubkey::default(),
+            has_order: false,
+        }
+    }
+
+    #[test]
+    fn test_calculate_fees() {
+        assert_eq!(
+            calculate_fees(10_000, 250, FeeType::Bps, &[]),
+            Some((250, 9_750))
+        );
+        assert_eq!(
+            calculate_fees(100, 500, FeeType::Fixed, &[]),
+            Some((100, 0))
+        );
+        assert_eq!(
+            calculate_fees(u64::MAX, MAX_BPS, FeeType::Bps, &[]),
+            Some((u64::MAX, 0))
+        );
+        assert_eq!(calculate_fees(100, 2 * MAX_BPS, FeeType::Bps, &[]), None);
+    }
+
+    #[test]
+    fn test_calculate_fees_fee_tiers() {
+        let tier = |threshold, fee| FeeTier {
+            threshold,
+            fee,
+            fee_type: FeeType::Bps,
+        };
+        // The unused fifth slot must be ignored
+        let policies = vec![PolicyData::FeeTier(FeeTierPolicy {
+            num_tiers: 2,
+            tiers: [
+                tier(1_000, 200),
+                tier(100_000, 100),
+                tier(0, 0),
+                tier(0, 0),
+                tier(1_000_000, 1),
+            ],
+        })];
+
+        assert_eq!(
+            calculate_fees(999, 50, FeeType::Fixed, &policies),
+            Some((50, 949))
+        );
+        assert_eq!(
+            calculate_fees(50_000, 50, FeeType::Fixed, &policies),
+            Some((1_000, 49_000))
+        );
+        assert_eq!(
+            calculate_fees(2_000_000, 50, FeeType::Fixed, &policies),
+            Some((20_000, 1_980_000))
+        );
+    }
+
+    #[test]
+    fn test_preview_clear_payment_no_policies() {
+        let preview = preview_clear_payment(
+            &config(500, FeeType::Bps),
+            &[],
+            &payment(1_000_000, Status::Paid),
+            CREATED_AT,
+        )
+        .unwrap();
+
+        assert_eq!(preview.operator_fee, 50_000);
+        assert_eq!(preview.merchant_amount, 950_000);
+        assert_eq!(preview.violation, None);
+    }
+
+    #[test]
+    fn test_preview_clear_payment_fee_override() {
+        let tier = |threshold, fee| FeeTier {
+            threshold,
+            fee,
+            fee_type: FeeType::Bps,
+        };
+        let policies = vec![PolicyData::FeeTier(FeeTierPolicy {
+            num_tiers: 1,
+            tiers: [tier(0, 100), tier(0, 0), tier(0, 0), tier(0, 0), tier(0, 0)],
+        })];
+        let mut payment = payment(1_000_000, Status::Paid);
+        payment.has_fee_override = true;
+        payment.fee_override = FeeOverride {
+            fee: 2_000,
+            fee_type: FeeType::Fixed,
+        };
+
+        // The override wins over both the config fee and the fee tiers
+        let preview =
+            preview_clear_payment(&config(500, FeeType::Bps), &policies, &payment, CREATED_AT)
+                .unwrap();
+
+        assert_eq!(preview.operator_fee, 2_000);
+        assert_eq!(preview.merchant_amount, 998_000);
+    }
+
+    #[test]
+    fn test_preview_clear_payment_platform_fee() {
+        let mut platform_fee = PlatformFeePolicy {
+            platform: Pubkey::new_unique(),
+            fee: 200,
+            fee_type: FeeType::Bps,
+        };
+        let preview = preview_clear_payment(
+            &config(500, FeeType::Bps),
+            &[PolicyData::PlatformFee(platform_fee.clone())],
+            &payment(1_000_000, Status::Paid),
+            CREATED_AT,
+        )
+        .unwrap();
+
+        assert_eq!(preview.operator_fee, 50_000);
+        assert_eq!(preview.platform_fee, 20_000);
+        assert_eq!(preview.merchant_amount, 930_000);
+
+        // Capped at what the operator fee leaves for the merchant
+        platform_fee.fee_type = FeeType::Fixed;
+        platform_fee.fee = 2_000_000;
+        let preview = preview_clear_payment(
+            &config(500, FeeType::Bps),
+            &[PolicyData::PlatformFee(platform_fee)],
+            &payment(1_000_000, Status::Paid),
+            CREATED_AT,
+        )
+        .unwrap();
+
+        assert_eq!(preview.platform_fee, 950_000);
+        assert_eq!(preview.merchant_amount, 0);
+    }
+
+    #[test]
+    fn test_preview_clear_payment_settlement_discount() {
+        let policies = [PolicyData::SettlementDiscount(SettlementDiscountPolicy {
+            discount_bps_per_day: 1_000,
+            max_discount_bps: 5_000,
+        })];
+        let preview_at = |days: i64| {
+            preview_clear_payment(
+                &config(200, FeeType::Bps),
+                &policies,
+                &payment(100_000, Status::Paid),
+                CREATED_AT + days * 86_400,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(preview_at(0).operator_fee, 2_000);
+        assert_eq!(preview_at(-5).operator_fee, 2_000);
+
+        // 10% of the operator fee goes to the merchant per full day
+        let preview = preview_at(2);
+        assert_eq!(preview.operator_fee, 1_600);
+        assert_eq!(preview.merchant_amount, 98_400);
+
+        // Up to max_discount_bps
+        let preview = preview_at(30);
+        assert_eq!(preview.operator_fee, 1_000);
+        assert_eq!(preview.merchant_amount, 99_000);
+    }
+
+    #[test]
+    fn test_preview_clear_payment_chargeback_window() {
+        let policies = vec![PolicyData::Chargeback(ChargebackPolicy {
+            window_seconds: 3_600,
+            arbiter: Pubkey::new_unique(),
+        })];
+        let config = config(10, FeeType::Fixed);
+
+        let preview = preview_clear_payment(
+            &config,
+            &policies,
+            &payment(5_000, Status::Paid),
+            CREATED_AT + 3_599,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.violation,
+            Some(PolicyViolation::ChargebackWindowActive {
+                clearable_at: CREATED_AT + 3_600
+            })
+        );
+        assert_eq!(
+            preview.violation.unwrap().program_error(),
+            CommerceProgramError::ChargebackWindowActive
+        );
+
+        let preview = preview_clear_payment(
+            &config,
+            &policies,
+            &payment(5_000, Status::Paid),
+            CREATED_AT + 3_600,
+        )
+        .unwrap();
+        assert_eq!(preview.violation, None);
+    }
+
+    #[test]
+    fn test_preview_clear_payment_settlement_violations() {
+        let policies = vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 1_000,
+            settlement_frequency_hours: 2,
+            auto_settle: false,
+        })];
+        let config = config(10, FeeType::Fixed);
+
+        let preview =
+            preview_clear_payment(&config, &policies, &payment(500, Status::Paid), CREATED_AT)
+                .unwrap();
+        assert_eq!(
+            preview.violation,
+            Some(PolicyViolation::InsufficientSettlementAmount {
+                min_settlement_amount: 1_000
+            })
+        );
+
+        let preview = preview_clear_payment(
+            &config,
+            &policies,
+            &payment(5_000, Status::Paid),
+            CREATED_AT,
+        )
+        .unwrap();
+        assert_eq!(
+            preview.violation,
+            Some(PolicyViolation::SettlementTooEarly {
+                clearable_at: CREATED_AT + 7_200
+            })
+        );
+        assert_eq!(
+            preview.violation.unwrap().program_error(),
+            CommerceProgramError::SettlementTooEarly
+        );
+
+        let preview = preview_clear_payment(
+            &config,
+            &policies,
+            &payment(5_000, Status::Paid),
+            CREATED_AT + 7_200,
+        )
+        .unwrap();
+        assert_eq!(preview.violation, None);
+    }
+
+    #[test]
+    fn test_preview_clear_payment_wrong_status() {
+        let preview = preview_clear_payment(
+            &config(0, FeeType::Bps),
+            &[],
+            &payment(1_000, Status::Refunded),
+            CREATED_AT,
+        )
+        .unwrap();
+
+        assert_eq!(
+            preview.violation,
+            Some(PolicyViolation::InvalidPaymentStatus {
+                status: Status::Refunded
+            })
+        );
+    }
+
+    #[test]
+    fn test_preview_refund_payment_violations() {
+        let policies = vec![PolicyData::Refund(RefundPolicy {
+            max_amount: 1_000,
+            max_time_after_purchase: 60,
+        })];
+
+        let preview = preview_refund_payment(&policies, &payment(2_000, Status::Paid), CREATED_AT);
+        assert_eq!(
+            preview.violation,
+            Some(PolicyViolation::RefundAmountExceedsPolicyLimit { max_amount: 1_000 })
+        );
+
+        let preview =
+            preview_refund_payment(&policies, &payment(1_000, Status::Paid), CREATED_AT + 61);
+        assert_eq!(
+            preview.violation,
+            Some(PolicyViolation::RefundWindowExpired {
+                expired_at: CREATED_AT + 60
+            })
+        );
+
+        let preview =
+            preview_refund_payment(&policies, &payment(1_000, Status::Paid), CREATED_AT + 60);
+        assert_eq!(preview.refund_amount, 1_000);
+        assert_eq!(preview.violation, None);
+    }
+
+    #[test]
+    fn test_preview_windows_beyond_i64() {
+        // Windows ending past i64::MAX must neither wrap nor close early at the clock's limit
+        let policies = vec![
+            PolicyData::Refund(RefundPolicy {
+                max_amount: u64::MAX,
+                max_time_after_purchase: u64::MAX,
+            }),
+            PolicyData::Chargeback(ChargebackPolicy {
+                window_seconds: u64::MAX,
+                arbiter: Pubkey::new_from_array([9; 32]),
+            }),
+        ];
+
+        let preview = preview_refund_payment(&policies, &payment(u64::MAX, Status::Paid), i64::MAX);
+        assert_eq!(preview.violation, None);
+
+        let preview = preview_clear_payment(
+            &config(250, FeeType::Bps),
+            &policies,
+            &payment(u64::MAX, Status::Paid),
+            i64::MAX,
+        )
+        .unwrap();
+        assert_eq!(preview.merchant_amount, u64::MAX - preview.operator_fee);
+        assert_eq!(
+            preview.violation,
+            Some(PolicyViolation::ChargebackWindowActive {
+                clearable_at: i64::MAX
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_merchant_operator_config() {
+        let policies = vec![
+            PolicyData::Refund(RefundPolicy {
+                max_amount: 1_000,
+                max_time_after_purchase: 60,
+            }),
+            PolicyData::Settlement(SettlementPolicy {
+                min_settlement_amount: 5,
+                settlement_frequency_hours: 1,
+                auto_settle: true,
+            }),
+        ];
+        let mints = vec![Pubkey::new_from_array([7; 32])];
+
+        let mut header = config(500, FeeType::Bps);
+        header.num_policies = policies.len() as u32;
+        header.num_accepted_currencies = mints.len() as u32;
+
+        let mut data = borsh::to_vec(&header).unwrap();
+        for policy in &policies {
+            let mut slot = Vec::new();
+            policy.serialize(&mut slot).unwrap();
+            slot.resize(POLICY_DATA_SIZE, 0);
+            data.extend_from_slice(&slot);
+        }
+        for mint in &mints {
+            data.extend_from_slice(mint.as_ref());
+        }
+        let currencies_end = data.len();
+        data.extend_from_slice(&3u32.to_le_bytes());
+
+        let (decoded, decoded_policies, decoded_mints) =
+            decode_merchant_operator_config(&data).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(decoded_policies, policies);
+        assert_eq!(decoded_mints, mints);
+        assert_eq!(decode_open_payments(&data).unwrap(), vec![3]);
+
+        assert!(decode_merchant_operator_config(&data[..currencies_end - 1]).is_err());
+        assert!(decode_open_payments(&data[..data.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+//! Compute budget and priority fees for commerce transactions.
+//!
+//! Without a `SetComputeUnitLimit`, a transaction is given the runtime's default of 200,000
+//! compute units per instruction and pays its priority fee on all of them, several times what
+//! commerce instructions use. [`compute_unit_limit`] instead sums each instruction's entry in
+//! [`COMPUTE_UNIT_PROFILE`], and [`PriorityFeeEstimator`] prices the units from the fees
+//! recently paid to write the transaction's accounts, as reported by
+//! `getRecentPrioritizationFees`:
+//!
+//! ```ignore
+//! let priority_fee = PriorityFeeEstimator::default().fetch_estimate(&rpc, &instructions)?;
+//! let instructions = [priority_fee.instructions(), instructions].concat();
+//! ```
+//!
+//! [`CheckoutTransactionBuilder`](crate::checkout::CheckoutTransactionBuilder) applies an
+//! estimate with `priority_fee` or `fetch_priority_fee`; its `compute_unit_limit` and
+//! `compute_unit_price` override the estimate's.
+//!
+//! The profile covers the instructions themselves. CPIs into transfer hooks, compliance
+//! programs or swap adapters run on top of it, so set the limit explicitly for those flows.
+
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    checkout::{ASSOCIATED_TOKEN_PROGRAM_ID, COMPUTE_BUDGET_PROGRAM_ID, MEMO_PROGRAM_ID},
+    discriminator::{InstructionDiscriminator, INSTRUCTION_VERSION_FLAG},
+    COMMERCE_PROGRAM_ID,
+};
+
+/// Compute units of an instruction missing from the profile: the runtime's default.
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u32 = 200_000;
+
+/// Most compute units a transaction may request.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Most accounts `getRecentPrioritizationFees` accepts.
+pub const MAX_FEE_ACCOUNTS: usize = 128;
+
+const COMPUTE_BUDGET_COMPUTE_UNITS: u32 = 150;
+
+/// ATA `CreateIdempotent` creating the account; an existing one costs far less.
+const CREATE_ATA_COMPUTE_UNITS: u32 = 30_000;
+
+/// Memo of up to a few hundred bytes.
+const MEMO_COMPUTE_UNITS: u32 = 15_000;
+
+/// Compute units per commerce instruction: the peak of each in `profiling_report.md` plus 50%,
+/// rounded up to 5,000. `MakePayment` includes creating the escrow ATA.
+pub const COMPUTE_UNIT_PROFILE: [(InstructionDiscriminator, u32); 8] = [
+    (InstructionDiscriminator::InitializeMerchant, 15_000),
+    (InstructionDiscriminator::CreateOperator, 10_000),
+    (
+        InstructionDiscriminator::InitializeMerchantOperatorConfig,
+        20_000,
+    ),
+    (InstructionDiscriminator::MakePayment, 50_000),
+    (InstructionDiscriminator::ClearPayment, 65_000),
+    (InstructionDiscriminator::RefundPayment, 50_000),
+    (
+        InstructionDiscriminator::UpdateMerchantSettlementWallet,
+        15_000,
+    ),
+    (InstructionDiscriminator::ClosePayment, 30_000),
+];
+
+/// ComputeBudget `SetComputeUnitLimit`.
+pub fn set_compute_unit_limit(compute_unit_limit: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&compute_unit_limit.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+}
+
+/// ComputeBudget `SetComputeUnitPrice`, in micro-lamports per compute unit.
+pub fn set_compute_unit_price(compute_unit_price: u64) -> Instruction {
+    let mut data = vec![3];
+    data.extend_from_slice(&compute_unit_price.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+}
+
+/// Compute units to budget for `instruction`, from the profile for commerce instructions.
+pub fn instruction_compute_units(instruction: &Instruction) -> u32 {
+    match instruction.program_id {
+        COMMERCE_PROGRAM_ID => instruction
+            .data
+            .first()
+            .and_then(|first| {
+                InstructionDiscriminator::try_from(first & !INSTRUCTION_VERSION_FLAG).ok()
+            })
+            .and_then(|discriminator| {
+                COMPUTE_UNIT_PROFILE
+                    .iter()
+                    .find(|(profiled, _)| *profiled == discriminator)
+            })
+            .map_or(DEFAULT_INSTRUCTION_COMPUTE_UNITS, |(_, compute_units)| {
+                *compute_units
+            }),
+        COMPUTE_BUDGET_PROGRAM_ID => COMPUTE_BUDGET_COMPUTE_UNITS,
+        ASSOCIATED_TOKEN_PROGRAM_ID => CREATE_ATA_COMPUTE_UNITS,
+        MEMO_PROGRAM_ID => MEMO_COMPUTE_UNITS,
+        _ => DEFAULT_INSTRUCTION_COMPUTE_UNITS,
+    }
+}
+
+/// Compute unit limit of a transaction of `instructions` with its two ComputeBudget
+/// instructions, counted once whether or not `instructions` already has them.
+pub fn compute_unit_limit(instructions: &[Instruction]) -> u32 {
+    instructions
+        .iter()
+        .filter(|instruction| instruction.program_id != COMPUTE_BUDGET_PROGRAM_ID)
+        .map(instruction_compute_units)
+        .fold(2 * COMPUTE_BUDGET_COMPUTE_UNITS, u32::saturating_add)
+        .min(MAX_COMPUTE_UNIT_LIMIT)
+}
+
+/// Accounts `instructions` write, whose recent fees a transaction of them competes with.
+/// At most [`MAX_FEE_ACCOUNTS`], in order of first use.
+pub fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = Vec::new();
+    for account in instructions
+        .iter()
+        .flat_map(|instruction| &instruction.accounts)
+        .filter(|account| account.is_writable)
+    {
+        if accounts.len() == MAX_FEE_ACCOUNTS {
+            break;
+        }
+        if !accounts.contains(&account.pubkey) {
+            accounts.push(account.pubkey);
+        }
+    }
+    accounts
+}
+
+/// Compute unit limit and price for a transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PriorityFee {
+    pub compute_unit_limit: u32,
+    /// Micro-lamports per compute unit
+    pub compute_unit_price: u64,
+}
+
+impl PriorityFee {
+    /// Lamports paid on top of the signature fees when the limit is fully used.
+    pub fn lamports(&self) -> u64 {
+        let micro_lamports = self.compute_unit_limit as u128 * self.compute_unit_price as u128;
+        micro_lamports.div_ceil(1_000_000) as u64
+    }
+
+    /// `SetComputeUnitLimit` and `SetComputeUnitPrice`, to lead the transaction.
+    pub fn instructions(&self) -> Vec<Instruction> {
+        vec![
+            set_compute_unit_limit(self.compute_unit_limit),
+            set_compute_unit_price(self.compute_unit_price),
+        ]
+    }
+}
+
+/// Prices compute units from the fees paid in recent slots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriorityFeeEstimator {
+    /// Percentile of the recent per-slot fees to pay, from 0 to 100
+    pub percentile: u8,
+    /// Lowest price paid, even when recent slots paid nothing
+    pub min_compute_unit_price: u64,
+    /// Highest price paid, bounding the fee during congestion
+    pub max_compute_unit_price: u64,
+}
+
+impl Default for PriorityFeeEstimator {
+    fn default() -> Self {
+        Self {
+            percentile: 75,
+            min_compute_unit_price: 0,
+            max_compute_unit_price: 1_000_000,
+        }
+    }
+}
+
+impl PriorityFeeEstimator {
+    pub fn percentile(mut self, percentile: u8) -> Self {
+        self.percentile = percentile.min(100);
+        self
+    }
+
+    pub fn min_compute_unit_price(mut self, min_compute_unit_price: u64) -> Self {
+        self.min_compute_unit_price = min_compute_unit_price;
+        self
+    }
+
+    pub fn max_compute_unit_price(mut self, max_compute_unit_price: u64) -> Self {
+        self.max_compute_unit_price = max_compute_unit_price;
+        self
+    }
+
+    /// Price at the percentile of `recent_fees`, one per slot, within the configured bounds.
+    pub fn compute_unit_price(&self, recent_fees: &[u64]) -> u64 {
+        let mut fees = recent_fees.to_vec();
+        fees.sort_unstable();
+        let fee = fees.len().checked_sub(1).map_or(0, |last| {
+            fees[last * self.percentile.min(100) as usize / 100]
+        });
+        fee.max(self.min_compute_unit_price)
+            .min(self.max_compute_unit_price)
+    }
+
+    /// Priority fee for `instructions` given the `recent_fees` of their writable accounts.
+    pub fn estimate(&self, instructions: &[Instruction], recent_fees: &[u64]) -> PriorityFee {
+        PriorityFee {
+            compute_unit_limit: compute_unit_limit(instructions),
+            compute_unit_price: self.compute_unit_price(recent_fees),
+        }
+    }
+
+    /// Fetches the recent fees paid to write the accounts of `instructions` and estimates
+    /// their priority fee.
+    #[cfg(feature = "fetch")]
+    pub fn fetch_estimate(
+        &self,
+        rpc: &solana_client::rpc_client::RpcClient,
+        instructions: &[Instruction],
+    ) -> Result<PriorityFee, solana_client::client_error::ClientError> {
+        let recent_fees: Vec<u64> = rpc
+            .get_recent_prioritization_fees(&writable_accounts(instructions))?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect();
+        Ok(self.estimate(instructions, &recent_fees))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discriminator::with_version_header;
+    use solana_instruction::AccountMeta;
+
+    fn key(n: u8) -> Pubkey {
+        Pubkey::new_from_array([n; 32])
+    }
+
+    fn commerce_instruction(discriminator: InstructionDiscriminator) -> Instruction {
+        Instruction::new_with_bytes(COMMERCE_PROGRAM_ID, &[discriminator as u8], vec![])
+    }
+
+    #[test]
+    fn test_instruction_compute_units() {
+        assert_eq!(
+            instruction_compute_units(&commerce_instruction(InstructionDiscriminator::MakePayment)),
+            50_000
+        );
+        let mut versioned = commerce_instruction(InstructionDiscriminator::ClearPayment);
+        versioned.data = with_version_header(&versioned.data);
+        assert_eq!(instruction_compute_units(&versioned), 65_000);
+
+        // Not profiled
+        assert_eq!(
+            instruction_compute_units(&commerce_instruction(
+                InstructionDiscriminator::MigratePayment
+            )),
+            DEFAULT_INSTRUCTION_COMPUTE_UNITS
+        );
+        assert_eq!(
+            instruction_compute_units(&Instruction::new_with_bytes(key(1), &[], vec![])),
+            DEFAULT_INSTRUCTION_COMPUTE_UNITS
+        );
+    }
+
+    #[test]
+    fn test_compute_unit_limit_counts_compute_budget_once() {
+        let checkout = vec![
+            Instruction::new_with_bytes(ASSOCIATED_TOKEN_PROGRAM_ID, &[1], vec![]),
+            commerce_instruction(InstructionDiscriminator::MakePayment),
+            Instruction::new_with_bytes(MEMO_PROGRAM_ID, b"order 42", vec![]),
+        ];
+        let limit = compute_unit_limit(&checkout);
+        assert_eq!(limit, 300 + 30_000 + 50_000 + 15_000);
+
+        let with_budget = [PriorityFee::default().instructions(), checkout].concat();
+        assert_eq!(compute_unit_limit(&with_budget), limit);
+
+        let unprofiled = vec![Instruction::new_with_bytes(key(1), &[], vec![]); 8];
+        assert_eq!(compute_unit_limit(&unprofiled), MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn test_writable_accounts() {
+        let instructions = vec![
+            Instruction::new_with_bytes(
+                key(1),
+                &[],
+                vec![
+                    AccountMeta::new(key(2), true),
+                    AccountMeta::new_readonly(key(3), false),
+                    AccountMeta::new(key(4), false),
+                ],
+            ),
+            Instruction::new_with_bytes(
+                key(1),
+                &[],
+                vec![
+                    AccountMeta::new(key(4), false),
+                    AccountMeta::new(key(5), false),
+                ],
+            ),
+        ];
+        assert_eq!(
+            writable_accounts(&instructions),
+            vec![key(2), key(4), key(5)]
+        );
+
+        let many = Instruction::new_with_bytes(
+            key(1),
+            &[],
+            (0..200)
+                .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+                .collect(),
+        );
+        assert_eq!(writable_accounts(&[many]).len(), MAX_FEE_ACCOUNTS);
+    }
+
+    #[test]
+    fn test_compute_unit_price_percentile() {
+        let recent_fees = [0, 500, 100, 0, 2_000, 1_000, 0, 300, 0, 10_000, 50];
+        let estimator = PriorityFeeEstimator::default();
+        assert_eq!(estimator.compute_unit_price(&recent_fees), 500);
+        assert_eq!(
+            estimator.percentile(50).compute_unit_price(&recent_fees),
+            100
+        );
+        assert_eq!(
+            estimator.percentile(100).compute_unit_price(&recent_fees),
+            10_000
+        );
+        assert_eq!(estimator.percentile(0).compute_unit_price(&recent_fees), 0);
+    }
+
+    #[test]
+    fn test_compute_unit_price_bounds() {
+        let estimator = PriorityFeeEstimator::default()
+            .min_compute_unit_price(1_000)
+            .max_compute_unit_price(5_000);
+        assert_eq!(estimator.compute_unit_price(&[]), 1_000);
+        assert_eq!(estimator.compute_unit_price(&[0, 0, 0]), 1_000);
+        assert_eq!(estimator.compute_unit_price(&[50_000; 4]), 5_000);
+    }
+
+    #[test]
+    fn test_estimate() {
+        let instructions = vec![commerce_instruction(
+            InstructionDiscriminator::RefundPayment,
+        )];
+        let priority_fee = PriorityFeeEstimator::default().estimate(&instructions, &[2_000, 4_000]);
+        assert_eq!(
+            priority_fee,
+            PriorityFee {
+                compute_unit_limit: 50_300,
+                compute_unit_price: 2_000,
+            }
+        );
+        // 50,300 units at 2,000 micro-lamports
+        assert_eq!(priority_fee.lamports(), 101);
+
+        let instructions = priority_fee.instructions();
+        assert_eq!(instructions[0], set_compute_unit_limit(50_300));
+        assert_eq!(instructions[1].data, [3, 0xd0, 0x07, 0, 0, 0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,113 @@
+//! Refunds for auto-settled payments.
+//!
+//! Auto-settled payments go straight to the merchant's settlement ATA, so there is nothing
+//! left in escrow for `RefundPayment`. Instead, the settlement wallet approves the
+//! merchant's refund delegate PDA on its settlement ATA with an SPL Token `Approve`, and
+//! operators call `RefundSettledPayment` within the config's refund window.
+//!
+//! The allowance shrinks with every refund and can be revoked at any time with `Revoke`.
+//! Approving again replaces the previous allowance, as the token program allows a single
+//! delegate per account. Changing the merchant's settlement wallet also stops refunds from
+//! the old wallet's ATA.
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::checkout::get_associated_token_address;
+pub use crate::pdas::find_refund_delegate_pda;
+
+const TOKEN_APPROVE_DISCRIMINATOR: u8 = 4;
+const TOKEN_REVOKE_DISCRIMINATOR: u8 = 5;
+
+/// SPL Token `Approve` letting the merchant's refund delegate move up to `amount` from the
+/// settlement wallet's ATA. Must be signed by `settlement_wallet`.
+pub fn approve_refund_delegate(
+    settlement_wallet: &Pubkey,
+    merchant: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = vec![TOKEN_APPROVE_DISCRIMINATOR];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(
+                get_associated_token_address(settlement_wallet, mint, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(find_refund_delegate_pda(merchant).0, false),
+            AccountMeta::new_readonly(*settlement_wallet, true),
+        ],
+        data,
+    }
+}
+
+/// SPL Token `Revoke` removing any delegate from the settlement wallet's ATA. Must be signed
+/// by `settlement_wallet`.
+pub fn revoke_refund_delegate(
+    settlement_wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(
+                get_associated_token_address(settlement_wallet, mint, token_program),
+                false,
+            ),
+            AccountMeta::new_readonly(*settlement_wallet, true),
+        ],
+        data: vec![TOKEN_REVOKE_DISCRIMINATOR],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkout::TOKEN_PROGRAM_ID;
+
+    #[test]
+    fn test_approve_refund_delegate() {
+        let settlement_wallet = Pubkey::new_unique();
+        let merchant = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let instruction = approve_refund_delegate(
+            &settlement_wallet,
+            &merchant,
+            &mint,
+            &TOKEN_PROGRAM_ID,
+            1_000,
+        );
+
+        assert_eq!(instruction.program_id, TOKEN_PROGRAM_ID);
+        assert_eq!(
+            instruction.accounts[0].pubkey,
+            get_associated_token_address(&settlement_wallet, &mint, &TOKEN_PROGRAM_ID)
+        );
+        assert_eq!(
+            instruction.accounts[1].pubkey,
+            find_refund_delegate_pda(&merchant).0
+        );
+        assert!(instruction.accounts[2].is_signer);
+        assert_eq!(instruction.data[0], TOKEN_APPROVE_DISCRIMINATOR);
+        assert_eq!(instruction.data[1..], 1_000u64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_revoke_refund_delegate() {
+        let settlement_wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let instruction = revoke_refund_delegate(&settlement_wallet, &mint, &TOKEN_PROGRAM_ID);
+
+        assert_eq!(instruction.accounts.len(), 2);
+        assert_eq!(instruction.accounts[1].pubkey, settlement_wallet);
+        assert!(instruction.accounts[1].is_signer);
+        assert_eq!(instruction.data, vec![TOKEN_REVOKE_DISCRIMINATOR]);
+    }
+}
@@ -0,0 +1,100 @@
+//! Off-chain helpers for risk policies.
+//!
+//! A `RiskPolicy`'s daily limits are counted in RiskState PDAs that `MakePayment` and
+//! `MakePaymentCompressed` must be passed: the config-wide one for `max_daily_volume` and
+//! the buyer's for `max_payments_per_buyer_per_day`. `CapturePayment` is passed them too when
+//! it releases part of a hold, to take the release back off the counters. [`risk_state_accounts`] lists the ones
+//! a policy needs, and [`missing_risk_state_buyers`] the `InitializeRiskState` buyers to
+//! create first.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{generated::types::RiskPolicy, pdas::find_risk_state_pda};
+
+/// Buyers whose RiskState `policy` needs for a payment by `buyer`: `Pubkey::default()` for
+/// the config-wide volume counter, then `buyer` for its payment count.
+pub fn risk_state_buyers(policy: &RiskPolicy, buyer: &Pubkey) -> Vec<Pubkey> {
+    let mut buyers = Vec::new();
+    if policy.max_daily_volume != 0 {
+        buyers.push(Pubkey::default());
+    }
+    if policy.max_payments_per_buyer_per_day != 0 {
+        buyers.push(*buyer);
+    }
+    buyers
+}
+
+/// Writable RiskState PDAs to pass to `MakePayment` after any MerchantStats, before the
+/// optional Order and references, or last to `MakePaymentCompressed`. `CapturePayment` takes
+/// them after any OperatorFreezeList, before the optional Order.
+pub fn risk_state_accounts(
+    merchant_operator_config: &Pubkey,
+    policy: &RiskPolicy,
+    buyer: &Pubkey,
+) -> Vec<AccountMeta> {
+    risk_state_buyers(policy, buyer)
+        .iter()
+        .map(|key| AccountMeta::new(find_risk_state_pda(merchant_operator_config, key).0, false))
+        .collect()
+}
+
+/// The buyers from [`risk_state_buyers`] whose RiskState `exists` reports missing, to create
+/// with `InitializeRiskState` before the payment.
+pub fn missing_risk_state_buyers(
+    merchant_operator_config: &Pubkey,
+    policy: &RiskPolicy,
+    buyer: &Pubkey,
+    exists: impl Fn(&Pubkey) -> bool,
+) -> Vec<Pubkey> {
+    risk_state_buyers(policy, buyer)
+        .into_iter()
+        .filter(|key| !exists(&find_risk_state_pda(merchant_operator_config, key).0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_daily_volume: u64, max_payments_per_buyer_per_day: u32) -> RiskPolicy {
+        RiskPolicy {
+            max_payment_amount: 0,
+            max_daily_volume,
+            max_payments_per_buyer_per_day,
+        }
+    }
+
+    #[test]
+    fn test_risk_state_accounts() {
+        let config = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+
+        assert!(risk_state_accounts(&config, &policy(0, 0), &buyer).is_empty());
+
+        let metas = risk_state_accounts(&config, &policy(1_000, 3), &buyer);
+        assert_eq!(metas.len(), 2);
+        assert_eq!(
+            metas[0].pubkey,
+            find_risk_state_pda(&config, &Pubkey::default()).0
+        );
+        assert_eq!(metas[1].pubkey, find_risk_state_pda(&config, &buyer).0);
+        assert!(metas.iter().all(|meta| meta.is_writable && !meta.is_signer));
+
+        let metas = risk_state_accounts(&config, &policy(0, 3), &buyer);
+        assert_eq!(metas.len(), 1);
+        assert_eq!(metas[0].pubkey, find_risk_state_pda(&config, &buyer).0);
+    }
+
+    #[test]
+    fn test_missing_risk_state_buyers() {
+        let config = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+        let config_wide = find_risk_state_pda(&config, &Pubkey::default()).0;
+
+        let missing = missing_risk_state_buyers(&config, &policy(1_000, 3), &buyer, |pda| {
+            *pda == config_wide
+        });
+        assert_eq!(missing, vec![buyer]);
+    }
+}
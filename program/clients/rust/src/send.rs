@@ -0,0 +1,519 @@
+//! Sending commerce transactions until they land.
+//!
+//! A failed send is only worth retrying when the failure is transient: an expired blockhash,
+//! a congested block or an unreachable RPC node. Program errors such as
+//! `InvalidPaymentStatus` fail the same way on every attempt, so [`RetryClass`] separates
+//! the two and [`send_and_confirm`] only resends the former. Operator-signed transactions
+//! prepared ahead of time can use a durable nonce instead of a recent blockhash, see
+//! [`BlockhashSource::DurableNonce`]. Sending is async and signs through
+//! [`crate::signer::CommerceSigner`], so keys may live in a KMS or HSM.
+
+use solana_hash::Hash;
+use solana_instruction::{error::InstructionError, Instruction};
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+use solana_transaction_error::TransactionError;
+
+use crate::generated::errors::CommerceProgramError;
+#[cfg(feature = "fetch")]
+use crate::signer::{try_sign_transaction, CommerceSigner};
+
+/// Size of a system program nonce account.
+pub const NONCE_ACCOUNT_LEN: usize = 80;
+
+/// Whether a failed transaction is worth sending again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetryClass {
+    /// Sending the same transaction again may succeed.
+    Transient,
+    /// The blockhash expired; the transaction must be rebuilt and signed again.
+    BlockhashExpired,
+    /// The program rejected the instruction until time passes, e.g. a settlement delay,
+    /// an open chargeback window or a daily limit.
+    RetryLater,
+    /// Every attempt will fail the same way.
+    Terminal,
+}
+
+impl RetryClass {
+    /// Whether [`send_and_confirm`] retries a failure of this class.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Transient | Self::BlockhashExpired)
+    }
+}
+
+impl CommerceProgramError {
+    /// How a transaction that failed with this error should be retried.
+    ///
+    /// `PaymentAlreadyExists` is terminal: a retried `MakePayment` should be resolved with
+    /// [`crate::idempotency`] instead.
+    pub fn retry_class(&self) -> RetryClass {
+        match self {
+            Self::SettlementTooEarly
+            | Self::PaymentCloseWindowNotReached
+            | Self::ChargebackWindowActive
+            | Self::SettlementWalletTimelockActive
+            | Self::DailyVolumeLimitExceeded
+            | Self::BuyerDailyPaymentLimitExceeded
+            | Self::DelegateDailyRefundLimitExceeded => RetryClass::RetryLater,
+            _ => RetryClass::Terminal,
+        }
+    }
+}
+
+/// The program error a transaction failed with, if it is one of ours.
+///
+/// Custom errors of programs invoked by a commerce instruction, such as the token program,
+/// are reported with the same instruction index and may be read as a program error.
+pub fn program_error(error: &TransactionError) -> Option<CommerceProgramError> {
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            CommerceProgramError::from_code(*code)
+        }
+        _ => None,
+    }
+}
+
+/// How a transaction that failed with `error` should be retried.
+pub fn classify_transaction_error(error: &TransactionError) -> RetryClass {
+    match error {
+        TransactionError::BlockhashNotFound => RetryClass::BlockhashExpired,
+        // An already processed transaction is confirmed by polling its signature
+        TransactionError::AlreadyProcessed
+        | TransactionError::AccountInUse
+        | TransactionError::ClusterMaintenance
+        | TransactionError::WouldExceedMaxBlockCostLimit
+        | TransactionError::WouldExceedMaxAccountCostLimit
+        | TransactionError::WouldExceedMaxVoteCostLimit
+        | TransactionError::WouldExceedAccountDataBlockLimit
+        | TransactionError::ProgramExecutionTemporarilyRestricted { .. }
+        | TransactionError::ProgramCacheHitMaxLimit
+        | TransactionError::CommitCancelled => RetryClass::Transient,
+        _ => program_error(error).map_or(RetryClass::Terminal, |error| error.retry_class()),
+    }
+}
+
+/// Where a transaction's `recent_blockhash` comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockhashSource {
+    /// The cluster's latest blockhash. The transaction expires after about 150 slots.
+    Latest,
+    /// The nonce stored in a system program nonce account. The transaction starts with
+    /// `AdvanceNonceAccount` and stays valid until the nonce is advanced, so an operator
+    /// can sign it ahead of time.
+    DurableNonce {
+        nonce_account: Pubkey,
+        nonce_authority: Pubkey,
+    },
+}
+
+impl BlockhashSource {
+    /// Message running `instructions` with `blockhash`, which is the stored nonce for
+    /// `DurableNonce`.
+    pub fn message(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        blockhash: Hash,
+    ) -> Message {
+        match self {
+            Self::Latest => Message::new_with_blockhash(instructions, Some(payer), &blockhash),
+            Self::DurableNonce {
+                nonce_account,
+                nonce_authority,
+            } => {
+                let mut message = Message::new_with_nonce(
+                    instructions.to_vec(),
+                    Some(payer),
+                    nonce_account,
+                    nonce_authority,
+                );
+                message.recent_blockhash = blockhash;
+                message
+            }
+        }
+    }
+}
+
+/// Initialized state of a system program nonce account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonceState {
+    /// Signer of `AdvanceNonceAccount`.
+    pub authority: Pubkey,
+    /// Value used as the `recent_blockhash` of the next nonce transaction.
+    pub nonce: Hash,
+}
+
+/// Decodes a nonce account, or `None` if it is not an initialized nonce account.
+pub fn decode_nonce_account(data: &[u8]) -> Option<NonceState> {
+    if data.len() != NONCE_ACCOUNT_LEN {
+        return None;
+    }
+    // Versions tag, then the State tag, which is 1 once initialized
+    let state = u32::from_le_bytes(data[4..8].try_into().ok()?);
+    if state != 1 {
+        return None;
+    }
+    Some(NonceState {
+        authority: Pubkey::try_from(&data[8..40]).ok()?,
+        nonce: Hash::new_from_array(data[40..72].try_into().ok()?),
+    })
+}
+
+/// Settings of [`send_and_confirm`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SendConfig {
+    /// Commitment the transaction must reach, also used for blockhashes and preflight.
+    pub commitment: solana_commitment_config::CommitmentConfig,
+    /// Transactions signed at most, each with a fresh blockhash or nonce.
+    pub max_attempts: u32,
+    /// Wait between resends of the same transaction.
+    pub poll_interval: std::time::Duration,
+}
+
+impl Default for SendConfig {
+    fn default() -> Self {
+        Self {
+            commitment: solana_commitment_config::CommitmentConfig::confirmed(),
+            max_attempts: 3,
+            poll_interval: std::time::Duration::from_secs(2),
+        }
+    }
+}
+
+#[cfg(feature = "fetch")]
+#[derive(Debug, thiserror::Error)]
+pub enum SendError {
+    /// The transaction failed with an error that retrying would not fix.
+    #[error("transaction {signature} failed: {error}")]
+    Transaction {
+        signature: solana_signature::Signature,
+        error: TransactionError,
+        class: RetryClass,
+    },
+    /// An RPC request failed with an error that retrying would not fix.
+    #[error(transparent)]
+    Rpc(solana_client::client_error::ClientError),
+    /// A signer failed, e.g. a remote signing service was unreachable.
+    #[error(transparent)]
+    Signer(#[from] solana_signer::SignerError),
+    /// The nonce account is missing or not initialized.
+    #[error("{0} is not an initialized nonce account")]
+    InvalidNonceAccount(Pubkey),
+    /// No attempt reached the requested commitment.
+    #[error("transaction not confirmed after {attempts} attempts")]
+    Unconfirmed { attempts: u32 },
+}
+
+#[cfg(feature = "fetch")]
+impl SendError {
+    /// The program error the transaction failed with, if it is one of ours.
+    pub fn program_error(&self) -> Option<CommerceProgramError> {
+        match self {
+            Self::Transaction { error, .. } => program_error(error),
+            Self::Rpc(error) => program_error(&error.get_transaction_error()?),
+            _ => None,
+        }
+    }
+}
+
+/// How a failed RPC request should be retried. Preflight failures are classified by their
+/// transaction error; network errors and unhealthy nodes are transient.
+#[cfg(feature = "fetch")]
+pub fn classify_client_error(error: &solana_client::client_error::ClientError) -> RetryClass {
+    use solana_client::{
+        client_error::ClientErrorKind,
+        rpc_request::{RpcError, RpcResponseErrorData},
+    };
+
+    if let Some(error) = error.get_transaction_error() {
+        return classify_transaction_error(&error);
+    }
+    match error.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => RetryClass::Transient,
+        ClientErrorKind::RpcError(RpcError::RpcRequestError(_))
+        | ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::NodeUnhealthy { .. },
+            ..
+        }) => RetryClass::Transient,
+        _ => RetryClass::Terminal,
+    }
+}
+
+/// Blockhash to sign with: the latest one, or the nonce stored in the nonce account.
+#[cfg(feature = "fetch")]
+pub async fn fetch_blockhash(
+    rpc: &solana_client::nonblocking::rpc_client::RpcClient,
+    source: &BlockhashSource,
+    commitment: solana_commitment_config::CommitmentConfig,
+) -> Result<Hash, SendError> {
+    match source {
+        BlockhashSource::Latest => Ok(rpc
+            .get_latest_blockhash_with_commitment(commitment)
+            .await
+            .map_err(SendError::Rpc)?
+            .0),
+        BlockhashSource::DurableNonce { nonce_account, .. } => rpc
+            .get_account_with_commitment(nonce_account, commitment)
+            .await
+            .map_err(SendError::Rpc)?
+            .value
+            .and_then(|account| decode_nonce_account(&account.data))
+            .map(|state| state.nonce)
+            .ok_or(SendError::InvalidNonceAccount(*nonce_account)),
+    }
+}
+
+/// Signs `instructions` and sends them until the transaction reaches `config.commitment`.
+///
+/// The signed transaction is resent every `config.poll_interval` while its blockhash is
+/// valid. Once it expires, or the nonce has been advanced by another transaction, it is
+/// rebuilt with a fresh blockhash and signed again, up to `config.max_attempts` times.
+/// Failures that are not [`RetryClass::is_retryable`] are returned immediately.
+///
+/// `signers` may be in any order; signers of different kinds, e.g. a local payer and a
+/// [`crate::signer::RemoteSigner`] operator authority, are passed as `&dyn CommerceSigner`.
+#[cfg(feature = "fetch")]
+pub async fn send_and_confirm<S: CommerceSigner + ?Sized>(
+    rpc: &solana_client::nonblocking::rpc_client::RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &[&S],
+    source: &BlockhashSource,
+    config: &SendConfig,
+) -> Result<solana_signature::Signature, SendError> {
+    use solana_client::rpc_config::RpcSendTransactionConfig;
+    use solana_transaction::Transaction;
+    use tokio::time::sleep;
+
+    let send_config = RpcSendTransactionConfig {
+        preflight_commitment: Some(config.commitment.commitment),
+        // Resending is done here, where the blockhash's expiry is known
+        max_retries: Some(0),
+        ..RpcSendTransactionConfig::default()
+    };
+    // Retryable RPC failures count towards the attempts like expired blockhashes do
+    let rpc_error = |error: solana_client::client_error::ClientError| {
+        if classify_client_error(&error).is_retryable() {
+            Ok(())
+        } else {
+            Err(SendError::Rpc(error))
+        }
+    };
+
+    for _ in 0..config.max_attempts {
+        let blockhash = match fetch_blockhash(rpc, source, config.commitment).await {
+            Ok(blockhash) => blockhash,
+            Err(SendError::Rpc(error)) => {
+                rpc_error(error)?;
+                sleep(config.poll_interval).await;
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+        let message = source.message(instructions, payer, blockhash);
+        let mut transaction = Transaction::new_unsigned(message);
+        try_sign_transaction(&mut transaction, signers).await?;
+        let signature = transaction.signatures[0];
+
+        loop {
+            if let Err(error) = rpc
+                .send_transaction_with_config(&transaction, send_config)
+                .await
+            {
+                if classify_client_error(&error) == RetryClass::BlockhashExpired {
+                    break;
+                }
+                rpc_error(error)?;
+            }
+
+            sleep(config.poll_interval).await;
+
+            match rpc
+                .get_signature_status_with_commitment(&signature, config.commitment)
+                .await
+            {
+                Ok(Some(Ok(()))) => return Ok(signature),
+                Ok(Some(Err(error))) => {
+                    let class = classify_transaction_error(&error);
+                    if !class.is_retryable() {
+                        return Err(SendError::Transaction {
+                            signature,
+                            error,
+                            class,
+                        });
+                    }
+                    break;
+                }
+                Ok(None) => {}
+                Err(error) => rpc_error(error)?,
+            }
+
+            // Rebuild once the transaction can no longer land
+            let expired = match source {
+                BlockhashSource::Latest => rpc
+                    .is_blockhash_valid(&blockhash, config.commitment)
+                    .await
+                    .map(|valid| !valid),
+                BlockhashSource::DurableNonce { .. } => {
+                    match fetch_blockhash(rpc, source, config.commitment).await {
+                        Ok(nonce) => Ok(nonce != blockhash),
+                        Err(SendError::Rpc(error)) => Err(error),
+                        Err(error) => return Err(error),
+                    }
+                }
+            };
+            match expired {
+                Ok(true) => break,
+                Ok(false) => {}
+                Err(error) => rpc_error(error)?,
+            }
+        }
+
+        // The transaction may have landed just before its blockhash expired
+        if let Ok(Some(result)) = rpc
+            .get_signature_status_with_commitment(&signature, config.commitment)
+            .await
+        {
+            match result {
+                Ok(()) => return Ok(signature),
+                Err(error) => {
+                    let class = classify_transaction_error(&error);
+                    if !class.is_retryable() {
+                        return Err(SendError::Transaction {
+                            signature,
+                            error,
+                            class,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Err(SendError::Unconfirmed {
+        attempts: config.max_attempts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_instruction::AccountMeta;
+
+    fn commerce_failure(error: CommerceProgramError) -> TransactionError {
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+    }
+
+    fn nonce_account_data(state: u32, authority: Pubkey, nonce: Hash) -> Vec<u8> {
+        let mut data = Vec::with_capacity(NONCE_ACCOUNT_LEN);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&state.to_le_bytes());
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(nonce.as_ref());
+        data.extend_from_slice(&5_000u64.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_classify_transaction_error() {
+        assert_eq!(
+            classify_transaction_error(&TransactionError::BlockhashNotFound),
+            RetryClass::BlockhashExpired
+        );
+        assert_eq!(
+            classify_transaction_error(&TransactionError::WouldExceedMaxBlockCostLimit),
+            RetryClass::Transient
+        );
+        assert_eq!(
+            classify_transaction_error(&TransactionError::InsufficientFundsForFee),
+            RetryClass::Terminal
+        );
+        assert_eq!(
+            classify_transaction_error(&TransactionError::InstructionError(
+                0,
+                InstructionError::MissingRequiredSignature
+            )),
+            RetryClass::Terminal
+        );
+    }
+
+    #[test]
+    fn test_classify_commerce_errors() {
+        for error in [
+            CommerceProgramError::InvalidPaymentStatus,
+            CommerceProgramError::PaymentAlreadyExists,
+            CommerceProgramError::QuoteExpired,
+        ] {
+            assert_eq!(
+                classify_transaction_error(&commerce_failure(error)),
+                RetryClass::Terminal
+            );
+        }
+        assert_eq!(
+            classify_transaction_error(&commerce_failure(CommerceProgramError::SettlementTooEarly)),
+            RetryClass::RetryLater
+        );
+        assert!(!RetryClass::RetryLater.is_retryable());
+    }
+
+    #[test]
+    fn test_program_error() {
+        assert_eq!(
+            program_error(&commerce_failure(CommerceProgramError::InvalidMint)),
+            Some(CommerceProgramError::InvalidMint)
+        );
+        assert_eq!(
+            program_error(&TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(u32::MAX)
+            )),
+            None
+        );
+        assert_eq!(program_error(&TransactionError::AccountInUse), None);
+    }
+
+    #[test]
+    fn test_decode_nonce_account() {
+        let authority = Pubkey::new_unique();
+        let nonce = Hash::new_from_array([7; 32]);
+
+        assert_eq!(
+            decode_nonce_account(&nonce_account_data(1, authority, nonce)),
+            Some(NonceState { authority, nonce })
+        );
+        assert_eq!(
+            decode_nonce_account(&nonce_account_data(0, authority, nonce)),
+            None
+        );
+        assert_eq!(decode_nonce_account(&[0; 10]), None);
+    }
+
+    #[test]
+    fn test_durable_nonce_message() {
+        let payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let nonce_authority = Pubkey::new_unique();
+        let nonce = Hash::new_from_array([7; 32]);
+        let instruction = Instruction::new_with_bytes(
+            crate::COMMERCE_PROGRAM_ID,
+            &[1],
+            vec![AccountMeta::new_readonly(Pubkey::new_unique(), false)],
+        );
+
+        let source = BlockhashSource::DurableNonce {
+            nonce_account,
+            nonce_authority,
+        };
+        let message = source.message(&[instruction.clone()], &payer, nonce);
+        assert_eq!(message.recent_blockhash, nonce);
+        assert_eq!(message.instructions.len(), 2);
+        // AdvanceNonceAccount must be the first instruction
+        assert_eq!(message.program_id(0), Some(&Pubkey::default()));
+        assert_eq!(message.program_id(1), Some(&crate::COMMERCE_PROGRAM_ID));
+
+        let message = BlockhashSource::Latest.message(&[instruction], &payer, nonce);
+        assert_eq!(message.recent_blockhash, nonce);
+        assert_eq!(message.instructions.len(), 1);
+    }
+}
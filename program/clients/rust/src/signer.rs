@@ -0,0 +1,337 @@
+//! Signers the SDK sends transactions with.
+//!
+//! Server-side operators keep their keys in a KMS or HSM rather than in keypair files, so
+//! [`crate::send::send_and_confirm`] signs through [`CommerceSigner`], an async trait
+//! implemented for every local [`Signer`] such as a `Keypair`, for [`RemoteSigner`], which
+//! asks a signing service over HTTP, and for [`ThresholdSigner`], a stub for keys split
+//! across a signing quorum. Signers of different kinds are passed together as
+//! `&dyn CommerceSigner`.
+
+use async_trait::async_trait;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_signer::{Signer, SignerError};
+use solana_transaction::Transaction;
+
+/// A key that signs commerce transactions, possibly by awaiting a remote service.
+#[async_trait]
+pub trait CommerceSigner: Send + Sync {
+    fn pubkey(&self) -> Pubkey;
+
+    /// Signature of `pubkey` over `transaction`'s message.
+    async fn sign_transaction(&self, transaction: &Transaction) -> Result<Signature, SignerError>;
+}
+
+#[async_trait]
+impl<T: Signer + Send + Sync + ?Sized> CommerceSigner for T {
+    fn pubkey(&self) -> Pubkey {
+        Signer::pubkey(self)
+    }
+
+    async fn sign_transaction(&self, transaction: &Transaction) -> Result<Signature, SignerError> {
+        self.try_sign_message(&transaction.message_data())
+    }
+}
+
+/// Signs `transaction` with `signers`, which may be in any order.
+///
+/// Fails with `KeypairPubkeyMismatch` for a signer the message does not require, and with
+/// `NotEnoughSigners` unless every required signature is set afterwards.
+pub async fn try_sign_transaction<S: CommerceSigner + ?Sized>(
+    transaction: &mut Transaction,
+    signers: &[&S],
+) -> Result<(), SignerError> {
+    let pubkeys: Vec<Pubkey> = signers.iter().map(|signer| signer.pubkey()).collect();
+    let positions = transaction.get_signing_keypair_positions(&pubkeys)?;
+
+    for (signer, position) in signers.iter().zip(positions) {
+        let position = position.ok_or(SignerError::KeypairPubkeyMismatch)?;
+        transaction.signatures[position] = signer.sign_transaction(transaction).await?;
+    }
+
+    if !transaction.is_signed() {
+        return Err(SignerError::NotEnoughSigners);
+    }
+    Ok(())
+}
+
+/// Stub for a key split across a quorum of HSMs or signing parties, of which `threshold`
+/// must take part in each signature.
+///
+/// The quorum's aggregate `pubkey` can be used to build transactions, but signing fails
+/// until a threshold scheme is implemented.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThresholdSigner {
+    pub pubkey: Pubkey,
+    pub threshold: u8,
+    /// Identifiers of the parties holding key shares.
+    pub parties: Vec<String>,
+}
+
+#[async_trait]
+impl CommerceSigner for ThresholdSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    async fn sign_transaction(&self, _transaction: &Transaction) -> Result<Signature, SignerError> {
+        Err(SignerError::Custom(format!(
+            "threshold signing with {} of {} parties is not implemented",
+            self.threshold,
+            self.parties.len()
+        )))
+    }
+}
+
+/// Signs through a remote signing service, such as a KMS or HSM proxy, over HTTP.
+///
+/// Each signature is a POST of `{"pubkey": <base58>, "message": <base64>}` to `url`, where
+/// `message` is the transaction's serialized message so the service can inspect its
+/// instructions, answered with `{"signature": <base58>}`. Signatures that do not verify
+/// against `pubkey` are rejected.
+#[cfg(feature = "fetch")]
+#[derive(Clone, Debug)]
+pub struct RemoteSigner {
+    pubkey: Pubkey,
+    url: String,
+    authorization: Option<String>,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "fetch")]
+impl RemoteSigner {
+    pub fn new(pubkey: Pubkey, url: impl Into<String>) -> Self {
+        Self {
+            pubkey,
+            url: url.into(),
+            authorization: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Sends `authorization`, e.g. `Bearer <token>`, as the `Authorization` header of each
+    /// request.
+    pub fn with_authorization(mut self, authorization: impl Into<String>) -> Self {
+        self.authorization = Some(authorization.into());
+        self
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        let body = serde_json::json!({
+            "pubkey": self.pubkey.to_string(),
+            "message": STANDARD.encode(message),
+        });
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+        if let Some(authorization) = &self.authorization {
+            request = request.header(reqwest::header::AUTHORIZATION, authorization);
+        }
+
+        let response = request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|error| SignerError::Connection(error.to_string()))?;
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|error| SignerError::Connection(error.to_string()))?;
+
+        let protocol = |reason: &str| SignerError::Protocol(format!("{}: {reason}", self.url));
+        let response: serde_json::Value =
+            serde_json::from_slice(&bytes).map_err(|_| protocol("response is not JSON"))?;
+        let signature = response["signature"]
+            .as_str()
+            .and_then(|signature| signature.parse::<Signature>().ok())
+            .ok_or_else(|| protocol("response has no base58 signature"))?;
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(protocol("signature does not verify"));
+        }
+        Ok(signature)
+    }
+}
+
+#[cfg(feature = "fetch")]
+#[async_trait]
+impl CommerceSigner for RemoteSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    async fn sign_transaction(&self, transaction: &Transaction) -> Result<Signature, SignerError> {
+        self.sign_message(&transaction.message_data()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_hash::Hash;
+    use solana_instruction::{AccountMeta, Instruction};
+    use solana_keypair::Keypair;
+    use solana_message::Message;
+
+    fn transaction(signers: &[Pubkey]) -> Transaction {
+        let accounts = signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true))
+            .collect();
+        let instruction = Instruction::new_with_bytes(crate::COMMERCE_PROGRAM_ID, &[1], accounts);
+        let message = Message::new_with_blockhash(
+            &[instruction],
+            Some(&signers[0]),
+            &Hash::new_from_array([7; 32]),
+        );
+        Transaction::new_unsigned(message)
+    }
+
+    #[tokio::test]
+    async fn test_try_sign_transaction() {
+        let payer = Keypair::new();
+        let operator_authority = Keypair::new();
+        let mut transaction =
+            transaction(&[Signer::pubkey(&payer), Signer::pubkey(&operator_authority)]);
+
+        // Any order, as trait objects
+        let signers: [&dyn CommerceSigner; 2] = [&operator_authority, &payer];
+        try_sign_transaction(&mut transaction, &signers)
+            .await
+            .unwrap();
+
+        let message = transaction.message_data();
+        assert_eq!(
+            transaction.signatures,
+            vec![
+                payer.sign_message(&message),
+                operator_authority.sign_message(&message)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_try_sign_transaction_signer_mismatch() {
+        let payer = Keypair::new();
+        let operator_authority = Keypair::new();
+        let mut transaction =
+            transaction(&[Signer::pubkey(&payer), Signer::pubkey(&operator_authority)]);
+
+        assert_eq!(
+            try_sign_transaction(&mut transaction, &[&payer]).await,
+            Err(SignerError::NotEnoughSigners)
+        );
+        assert_eq!(
+            try_sign_transaction(&mut transaction, &[&payer, &Keypair::new()]).await,
+            Err(SignerError::KeypairPubkeyMismatch)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_threshold_signer_unimplemented() {
+        let signer = ThresholdSigner {
+            pubkey: Pubkey::new_unique(),
+            threshold: 2,
+            parties: vec![
+                "hsm-a".to_string(),
+                "hsm-b".to_string(),
+                "hsm-c".to_string(),
+            ],
+        };
+        let mut transaction = transaction(&[signer.pubkey]);
+
+        assert!(matches!(
+            try_sign_transaction(&mut transaction, &[&signer]).await,
+            Err(SignerError::Custom(_))
+        ));
+    }
+
+    /// Serves one signing request with `key`, returning the request's body.
+    #[cfg(feature = "fetch")]
+    fn serve_signing_request(key: Keypair) -> (String, std::thread::JoinHandle<String>) {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/sign", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+            let body_start = loop {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                if let Some(end) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break end + 4;
+                }
+            };
+            let headers = String::from_utf8_lossy(&request[..body_start]).to_lowercase();
+            let content_length: usize = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .unwrap()
+                .trim()
+                .parse()
+                .unwrap();
+            while request.len() < body_start + content_length {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let body = String::from_utf8(request[body_start..].to_vec()).unwrap();
+
+            let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+            let message = STANDARD.decode(json["message"].as_str().unwrap()).unwrap();
+            let response = serde_json::json!({
+                "signature": key.sign_message(&message).to_string(),
+            })
+            .to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            )
+            .unwrap();
+            body
+        });
+
+        (url, handle)
+    }
+
+    #[cfg(feature = "fetch")]
+    #[tokio::test]
+    async fn test_remote_signer() {
+        let key = Keypair::new();
+        let pubkey = Signer::pubkey(&key);
+        let (url, server) = serve_signing_request(key);
+        let signer = RemoteSigner::new(pubkey, url).with_authorization("Bearer token");
+        let mut transaction = transaction(&[pubkey]);
+
+        try_sign_transaction(&mut transaction, &[&signer])
+            .await
+            .unwrap();
+
+        assert!(transaction.signatures[0].verify(pubkey.as_ref(), &transaction.message_data()));
+        let request: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+        assert_eq!(request["pubkey"], pubkey.to_string());
+    }
+
+    #[cfg(feature = "fetch")]
+    #[tokio::test]
+    async fn test_remote_signer_rejects_wrong_key() {
+        let pubkey = Pubkey::new_unique();
+        // The service signs with a key other than the one requested
+        let (url, server) = serve_signing_request(Keypair::new());
+        let signer = RemoteSigner::new(pubkey, url);
+        let mut transaction = transaction(&[pubkey]);
+
+        assert!(matches!(
+            try_sign_transaction(&mut transaction, &[&signer]).await,
+            Err(SignerError::Protocol(_))
+        ));
+        server.join().unwrap();
+    }
+}
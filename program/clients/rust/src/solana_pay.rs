@@ -0,0 +1,507 @@
+//! Solana Pay transaction requests.
+//!
+//! A point-of-sale app shows a `solana:` URL, usually as a QR code, pointing at the
+//! operator's transaction request endpoint with the checkout in the link's query:
+//! `merchant_operator_config`, `mint`, `amount`, `order_id`, `idempotency_key` (hex) and one
+//! or more `reference` keys. The wallet POSTs the buyer's account to that link; the endpoint
+//! parses the query with [`CheckoutRequest::from_link`], builds the `MakePayment` transaction
+//! for that buyer with [`CheckoutRequest::checkout_builder`], signs it as operator authority
+//! (and payer) and returns it for the buyer to sign.
+//!
+//! The references ride along read-only on `MakePayment`, which stores up to two of them on
+//! the Payment account, so the point-of-sale app can find the payment with
+//! [`find_payment_by_reference`] before it knows the buyer. Once the buyer is known,
+//! [`CheckoutRequest::payment`] is the Payment PDA to watch for the payment's status.
+
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_pubkey::Pubkey;
+use thiserror::Error;
+
+use crate::{
+    checkout::CheckoutTransactionBuilder,
+    discriminator::{parse_instruction, ParsedInstruction},
+    idempotency::IdempotencyKey,
+    pdas::find_payment_pda,
+    COMMERCE_PROGRAM_ID,
+};
+
+/// Fixed accounts of `MakePayment`; references follow them as remaining accounts.
+const MAKE_PAYMENT_ACCOUNTS: usize = 18;
+
+pub const SOLANA_PAY_SCHEME: &str = "solana:";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SolanaPayError {
+    #[error("not a solana: URL")]
+    InvalidScheme,
+    #[error("transaction request links must be https URLs")]
+    InvalidLink,
+    #[error("malformed percent-encoding")]
+    InvalidEncoding,
+    #[error("missing query parameter {0}")]
+    MissingParameter(&'static str),
+    #[error("invalid query parameter {0}")]
+    InvalidParameter(&'static str),
+}
+
+/// Operator-side accounts of a checkout, the same for every buyer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckoutAccounts {
+    pub payer: Pubkey,
+    pub operator_authority: Pubkey,
+    pub operator: Pubkey,
+    pub merchant: Pubkey,
+    pub settlement_wallet: Pubkey,
+}
+
+/// A checkout carried in a transaction request link.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheckoutRequest {
+    pub merchant_operator_config: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub order_id: u32,
+    pub idempotency_key: IdempotencyKey,
+    pub references: Vec<Pubkey>,
+}
+
+impl CheckoutRequest {
+    /// `endpoint` with the checkout appended to its query.
+    pub fn to_link(&self, endpoint: &str) -> String {
+        let mut link = String::from(endpoint);
+        link.push(if endpoint.contains('?') { '&' } else { '?' });
+        link.push_str(&format!(
+            "merchant_operator_config={}&mint={}&amount={}&order_id={}&idempotency_key={}",
+            self.merchant_operator_config,
+            self.mint,
+            self.amount,
+            self.order_id,
+            hex_encode(&self.idempotency_key),
+        ));
+        for reference in &self.references {
+            link.push_str(&format!("&reference={reference}"));
+        }
+        link
+    }
+
+    /// `solana:` transaction request URL for the checkout served at `endpoint`.
+    pub fn to_url(&self, endpoint: &str) -> String {
+        format!(
+            "{SOLANA_PAY_SCHEME}{}",
+            percent_encode(&self.to_link(endpoint))
+        )
+    }
+
+    /// Parses the checkout from a transaction request link. Unknown query parameters, such
+    /// as the endpoint's own, are ignored.
+    pub fn from_link(link: &str) -> Result<Self, SolanaPayError> {
+        let query = link.split_once('?').map_or("", |(_, query)| query);
+
+        let mut merchant_operator_config = None;
+        let mut mint = None;
+        let mut amount = None;
+        let mut order_id = None;
+        let mut idempotency_key = None;
+        let mut references = Vec::new();
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = percent_decode(value)?;
+            match name {
+                "merchant_operator_config" => {
+                    merchant_operator_config = Some(parse(&value, "merchant_operator_config")?)
+                }
+                "mint" => mint = Some(parse(&value, "mint")?),
+                "amount" => amount = Some(parse(&value, "amount")?),
+                "order_id" => order_id = Some(parse(&value, "order_id")?),
+                "idempotency_key" => {
+                    idempotency_key = Some(
+                        hex_decode(&value)
+                            .ok_or(SolanaPayError::InvalidParameter("idempotency_key"))?,
+                    )
+                }
+                "reference" => references.push(parse(&value, "reference")?),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            merchant_operator_config: merchant_operator_config
+                .ok_or(SolanaPayError::MissingParameter("merchant_operator_config"))?,
+            mint: mint.ok_or(SolanaPayError::MissingParameter("mint"))?,
+            amount: amount.ok_or(SolanaPayError::MissingParameter("amount"))?,
+            order_id: order_id.ok_or(SolanaPayError::MissingParameter("order_id"))?,
+            idempotency_key: idempotency_key
+                .ok_or(SolanaPayError::MissingParameter("idempotency_key"))?,
+            references,
+        })
+    }
+
+    /// Parses the checkout from a `solana:` transaction request URL.
+    pub fn from_url(url: &str) -> Result<Self, SolanaPayError> {
+        Self::from_link(&parse_transaction_request_url(url)?)
+    }
+
+    /// Payment PDA the checkout creates for `buyer`.
+    pub fn payment(&self, buyer: &Pubkey) -> Pubkey {
+        find_payment_pda(
+            &self.merchant_operator_config,
+            buyer,
+            &self.mint,
+            self.order_id,
+        )
+        .0
+    }
+
+    /// Checkout transaction for `buyer`, with the request's references attached. Compute
+    /// budget, ATA creation and memo options can still be added before compiling.
+    pub fn checkout_builder(
+        &self,
+        accounts: &CheckoutAccounts,
+        buyer: Pubkey,
+    ) -> CheckoutTransactionBuilder {
+        let mut builder = CheckoutTransactionBuilder::new();
+        builder
+            .payer(accounts.payer)
+            .operator_authority(accounts.operator_authority)
+            .buyer(buyer)
+            .operator(accounts.operator)
+            .merchant(accounts.merchant)
+            .merchant_operator_config(self.merchant_operator_config)
+            .settlement_wallet(accounts.settlement_wallet)
+            .mint(self.mint)
+            .order_id(self.order_id)
+            .amount(self.amount)
+            .idempotency_key(self.idempotency_key);
+        for reference in &self.references {
+            builder.reference(*reference);
+        }
+        builder
+    }
+}
+
+/// The https link of a `solana:` transaction request URL.
+pub fn parse_transaction_request_url(url: &str) -> Result<String, SolanaPayError> {
+    let link = url
+        .strip_prefix(SOLANA_PAY_SCHEME)
+        .ok_or(SolanaPayError::InvalidScheme)?;
+    let link = percent_decode(link)?;
+    if !link.starts_with("https://") {
+        return Err(SolanaPayError::InvalidLink);
+    }
+    Ok(link)
+}
+
+/// Oldest successful transaction referencing `reference`, once the wallet has sent it.
+#[cfg(feature = "fetch")]
+pub fn find_reference_signature(
+    rpc: &solana_client::rpc_client::RpcClient,
+    reference: &Pubkey,
+) -> Result<Option<String>, solana_client::client_error::ClientError> {
+    Ok(rpc
+        .get_signatures_for_address(reference)?
+        .into_iter()
+        .rev()
+        .find(|status| status.err.is_none())
+        .map(|status| status.signature))
+}
+
+/// Payment created by the oldest successful `MakePayment` carrying `reference`, once the
+/// wallet has sent it. `None` until then, or once the payment has been closed.
+#[cfg(feature = "fetch")]
+pub fn find_payment_by_reference(
+    rpc: &solana_client::rpc_client::RpcClient,
+    reference: &Pubkey,
+) -> Result<
+    Option<crate::shared::DecodedAccount<crate::accounts::Payment>>,
+    solana_client::client_error::ClientError,
+> {
+    use solana_client::{client_error::ClientErrorKind, rpc_config::RpcTransactionConfig};
+    use solana_transaction_status_client_types::{
+        option_serializer::OptionSerializer, UiTransactionEncoding,
+    };
+
+    use crate::{accounts::fetch_maybe_payment, shared::MaybeAccount};
+
+    for status in rpc
+        .get_signatures_for_address(reference)?
+        .into_iter()
+        .rev()
+        .filter(|status| status.err.is_none())
+    {
+        let signature: solana_signature::Signature = status.signature.parse().map_err(|_| {
+            ClientErrorKind::Custom(format!("invalid signature {}", status.signature))
+        })?;
+        let transaction = rpc.get_transaction_with_config(
+            &signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(rpc.commitment()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        let Some(decoded) = transaction.transaction.transaction.decode() else {
+            continue;
+        };
+
+        // v0 transactions may load accounts from lookup tables; they follow the static keys
+        let mut account_keys = decoded.message.static_account_keys().to_vec();
+        if let Some(meta) = &transaction.transaction.meta {
+            if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+                account_keys.extend(
+                    loaded
+                        .writable
+                        .iter()
+                        .chain(&loaded.readonly)
+                        .filter_map(|key| key.parse::<Pubkey>().ok()),
+                );
+            }
+        }
+
+        let Some(payment) =
+            payment_for_reference(&account_keys, decoded.message.instructions(), reference)
+        else {
+            continue;
+        };
+        if let MaybeAccount::Exists(account) = fetch_maybe_payment(rpc, &payment)? {
+            if account.data.references.contains(reference) {
+                return Ok(Some(account));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Payment PDA of the top-level `MakePayment` passing `reference` as a remaining account.
+pub fn payment_for_reference(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    reference: &Pubkey,
+) -> Option<Pubkey> {
+    instructions
+        .iter()
+        .filter(|ix| account_keys.get(ix.program_id_index as usize) == Some(&COMMERCE_PROGRAM_ID))
+        .filter(|ix| {
+            matches!(
+                parse_instruction(&ix.data),
+                Ok(ParsedInstruction::MakePayment(_))
+            )
+        })
+        .find(|ix| {
+            ix.accounts
+                .iter()
+                .skip(MAKE_PAYMENT_ACCOUNTS)
+                .any(|i| account_keys.get(*i as usize) == Some(reference))
+        })
+        .and_then(|ix| account_keys.get(*ix.accounts.get(1)? as usize))
+        .copied()
+}
+
+fn parse<T: core::str::FromStr>(value: &str, name: &'static str) -> Result<T, SolanaPayError> {
+    value
+        .parse()
+        .map_err(|_| SolanaPayError::InvalidParameter(name))
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters.
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> Result<String, SolanaPayError> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .ok_or(SolanaPayError::InvalidEncoding)?;
+            let hex = core::str::from_utf8(hex).map_err(|_| SolanaPayError::InvalidEncoding)?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| SolanaPayError::InvalidEncoding)?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| SolanaPayError::InvalidEncoding)
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn hex_decode(value: &str) -> Option<IdempotencyKey> {
+    if value.len() != 32 {
+        return None;
+    }
+    let mut key = [0u8; 16];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(value.get(2 * i..2 * i + 2)?, 16).ok()?;
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_instruction::AccountMeta;
+
+    const ENDPOINT: &str = "https://pos.example.com/api/checkout";
+
+    fn request() -> CheckoutRequest {
+        CheckoutRequest {
+            merchant_operator_config: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount: 1_500_000,
+            order_id: 42,
+            idempotency_key: [0xa5; 16],
+            references: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        }
+    }
+
+    #[test]
+    fn test_url_roundtrip() {
+        let request = request();
+        let url = request.to_url(ENDPOINT);
+
+        assert!(url.starts_with("solana:https%3A%2F%2Fpos.example.com%2Fapi%2Fcheckout%3F"));
+        assert!(!url[SOLANA_PAY_SCHEME.len()..].contains(['?', '&', '=', '/']));
+        assert_eq!(CheckoutRequest::from_url(&url).unwrap(), request);
+        assert_eq!(
+            parse_transaction_request_url(&url).unwrap(),
+            request.to_link(ENDPOINT)
+        );
+    }
+
+    #[test]
+    fn test_link_keeps_endpoint_query() {
+        let request = request();
+        let link = request.to_link("https://pos.example.com/pay?store=7");
+
+        assert!(link.starts_with("https://pos.example.com/pay?store=7&merchant_operator_config="));
+        assert_eq!(CheckoutRequest::from_link(&link).unwrap(), request);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            CheckoutRequest::from_url("https://pos.example.com"),
+            Err(SolanaPayError::InvalidScheme)
+        );
+        assert_eq!(
+            CheckoutRequest::from_url("solana:http%3A%2F%2Fpos.example.com"),
+            Err(SolanaPayError::InvalidLink)
+        );
+        assert_eq!(
+            CheckoutRequest::from_url("solana:https%3A%2F%2Fpos.example.com%ZZ"),
+            Err(SolanaPayError::InvalidEncoding)
+        );
+
+        let link = request().to_link(ENDPOINT);
+        assert_eq!(
+            CheckoutRequest::from_link(&link.replace("amount=", "amount=-")),
+            Err(SolanaPayError::InvalidParameter("amount"))
+        );
+        assert_eq!(
+            CheckoutRequest::from_link(&link.replace("idempotency_key=a5", "idempotency_key=")),
+            Err(SolanaPayError::InvalidParameter("idempotency_key"))
+        );
+        assert_eq!(
+            CheckoutRequest::from_link(&link.replace("order_id", "order")),
+            Err(SolanaPayError::MissingParameter("order_id"))
+        );
+    }
+
+    #[test]
+    fn test_checkout_builder_for_buyer() {
+        let request = request();
+        let buyer = Pubkey::new_unique();
+        let accounts = CheckoutAccounts {
+            payer: Pubkey::new_unique(),
+            operator_authority: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            merchant: Pubkey::new_unique(),
+            settlement_wallet: Pubkey::new_unique(),
+        };
+
+        let builder = request.checkout_builder(&accounts, buyer);
+        assert_eq!(builder.payment(), request.payment(&buyer));
+
+        let instructions = builder.instructions();
+        assert_eq!(instructions.len(), 1);
+        assert_eq!(instructions[0].accounts[1].pubkey, request.payment(&buyer));
+        assert_eq!(
+            instructions[0].accounts[MAKE_PAYMENT_ACCOUNTS..],
+            request
+                .references
+                .iter()
+                .map(|reference| AccountMeta::new_readonly(*reference, false))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_payment_for_reference() {
+        let request = request();
+        let buyer = Pubkey::new_unique();
+        let accounts = CheckoutAccounts {
+            payer: Pubkey::new_unique(),
+            operator_authority: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            merchant: Pubkey::new_unique(),
+            settlement_wallet: Pubkey::new_unique(),
+        };
+        let ix = request
+            .checkout_builder(&accounts, buyer)
+            .compute_unit_price(1_000)
+            .instructions();
+
+        let mut account_keys: Vec<Pubkey> = Vec::new();
+        let compiled: Vec<CompiledInstruction> = ix
+            .iter()
+            .map(|ix| {
+                let mut index = |key: &Pubkey| match account_keys.iter().position(|k| k == key) {
+                    Some(i) => i as u8,
+                    None => {
+                        account_keys.push(*key);
+                        (account_keys.len() - 1) as u8
+                    }
+                };
+                CompiledInstruction {
+                    program_id_index: index(&ix.program_id),
+                    accounts: ix.accounts.iter().map(|meta| index(&meta.pubkey)).collect(),
+                    data: ix.data.clone(),
+                }
+            })
+            .collect();
+
+        for reference in &request.references {
+            assert_eq!(
+                payment_for_reference(&account_keys, &compiled, reference),
+                Some(request.payment(&buyer))
+            );
+        }
+
+        // Not a reference, though in the instruction's accounts
+        assert_eq!(
+            payment_for_reference(&account_keys, &compiled, &buyer),
+            None
+        );
+        // Only top-level commerce instructions are searched
+        assert_eq!(
+            payment_for_reference(&account_keys, &compiled[..1], &request.references[0]),
+            None
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Off-chain helpers for split settlement.
+//!
+//! A `SplitSettlementPolicy` only stores a hash of its destination wallets, so whoever
+//! clears payments has to keep the wallets themselves. [`SplitSettlement`] holds the wallets
+//! with their shares and produces both the policy and the destination ATAs `ClearPayment`
+//! takes as remaining accounts, in the policy's order.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+use solana_sha256_hasher::hashv;
+
+use crate::{checkout::get_associated_token_address, generated::types::SplitSettlementPolicy};
+
+/// Most destinations a split settlement policy can route to.
+pub const MAX_SPLIT_DESTINATIONS: usize = 5;
+
+const MAX_BPS: u64 = 10_000;
+
+/// Hash of the destination wallets in order, matching the program's commitment.
+pub fn split_settlement_destinations_hash(wallets: &[Pubkey]) -> [u8; 32] {
+    let vals: Vec<&[u8]> = wallets.iter().map(|wallet| wallet.as_ref()).collect();
+    hashv(&vals).to_bytes()
+}
+
+/// Splits `merchant_amount` the way `ClearPayment` does: each share is rounded down and the
+/// remainder goes to the first destination.
+pub fn split_settlement_amounts(policy: &SplitSettlementPolicy, merchant_amount: u64) -> Vec<u64> {
+    let shares =
+        &policy.shares_bps[..(policy.num_destinations as usize).min(MAX_SPLIT_DESTINATIONS)];
+    let mut parts: Vec<u64> = shares
+        .iter()
+        .map(|&share| (merchant_amount as u128 * share as u128 / MAX_BPS as u128) as u64)
+        .collect();
+    let distributed = parts
+        .iter()
+        .fold(0u64, |sum, part| sum.saturating_add(*part));
+    if let Some(first) = parts.first_mut() {
+        *first = first.saturating_add(merchant_amount.saturating_sub(distributed));
+    }
+    parts
+}
+
+/// Destination wallets of a split settlement and their shares in basis points.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SplitSettlement {
+    destinations: Vec<(Pubkey, u16)>,
+}
+
+impl SplitSettlement {
+    pub fn new(destinations: impl IntoIterator<Item = (Pubkey, u16)>) -> Self {
+        Self {
+            destinations: destinations.into_iter().collect(),
+        }
+    }
+
+    pub fn destinations(&self) -> &[(Pubkey, u16)] {
+        &self.destinations
+    }
+
+    /// Policy committing to the destinations.
+    ///
+    /// Panics with more than [`MAX_SPLIT_DESTINATIONS`] destinations; shares are checked by
+    /// the program when the config is created.
+    pub fn policy(&self) -> SplitSettlementPolicy {
+        assert!(
+            self.destinations.len() <= MAX_SPLIT_DESTINATIONS,
+            "too many split settlement destinations"
+        );
+        let wallets: Vec<Pubkey> = self
+            .destinations
+            .iter()
+            .map(|(wallet, _)| *wallet)
+            .collect();
+        let mut shares_bps = [0u16; MAX_SPLIT_DESTINATIONS];
+        for (share, (_, bps)) in shares_bps.iter_mut().zip(&self.destinations) {
+            *share = *bps;
+        }
+
+        SplitSettlementPolicy {
+            destinations_hash: split_settlement_destinations_hash(&wallets),
+            num_destinations: self.destinations.len() as u8,
+            shares_bps,
+        }
+    }
+
+    /// Each destination's ATA for `mint`, in the policy's order.
+    pub fn destination_atas(&self, mint: &Pubkey, token_program: &Pubkey) -> Vec<Pubkey> {
+        self.destinations
+            .iter()
+            .map(|(wallet, _)| get_associated_token_address(wallet, mint, token_program))
+            .collect()
+    }
+
+    /// Writable destination ATAs to pass to `ClearPayment`, right before the optional Order.
+    pub fn remaining_accounts(&self, mint: &Pubkey, token_program: &Pubkey) -> Vec<AccountMeta> {
+        self.destination_atas(mint, token_program)
+            .into_iter()
+            .map(|ata| AccountMeta::new(ata, false))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn treasury_and_tax() -> SplitSettlement {
+        SplitSettlement::new([(Pubkey::new_unique(), 9_000), (Pubkey::new_unique(), 1_000)])
+    }
+
+    #[test]
+    fn test_policy() {
+        let split = treasury_and_tax();
+        let policy = split.policy();
+        let wallets: Vec<Pubkey> = split.destinations().iter().map(|(w, _)| *w).collect();
+
+        assert_eq!(policy.num_destinations, 2);
+        assert_eq!(policy.shares_bps, [9_000, 1_000, 0, 0, 0]);
+        assert_eq!(
+            policy.destinations_hash,
+            split_settlement_destinations_hash(&wallets)
+        );
+
+        // Order matters
+        let reversed: Vec<Pubkey> = wallets.iter().rev().copied().collect();
+        assert_ne!(
+            policy.destinations_hash,
+            split_settlement_destinations_hash(&reversed)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "too many split settlement destinations")]
+    fn test_policy_too_many_destinations() {
+        SplitSettlement::new((0..6).map(|_| (Pubkey::new_unique(), 1_000))).policy();
+    }
+
+    #[test]
+    fn test_split_settlement_amounts() {
+        let policy = treasury_and_tax().policy();
+
+        assert_eq!(split_settlement_amounts(&policy, 1_000), vec![900, 100]);
+        // 9 * 0.9 = 8.1 and 9 * 0.1 = 0.9 round down to 8 and 0; the first takes the rest
+        assert_eq!(split_settlement_amounts(&policy, 9), vec![9, 0]);
+        assert_eq!(split_settlement_amounts(&policy, 0), vec![0, 0]);
+
+        let parts = split_settlement_amounts(&policy, u64::MAX);
+        assert_eq!(
+            parts.iter().map(|&p| p as u128).sum::<u128>(),
+            u64::MAX as u128
+        );
+    }
+
+    #[test]
+    fn test_remaining_accounts() {
+        let split = treasury_and_tax();
+        let mint = Pubkey::new_unique();
+        let token_program = Pubkey::new_unique();
+
+        let metas = split.remaining_accounts(&mint, &token_program);
+        assert_eq!(metas.len(), 2);
+        for (meta, (wallet, _)) in metas.iter().zip(split.destinations()) {
+            assert!(meta.is_writable);
+            assert!(!meta.is_signer);
+            assert_eq!(
+                meta.pubkey,
+                get_associated_token_address(wallet, &mint, &token_program)
+            );
+        }
+    }
+}
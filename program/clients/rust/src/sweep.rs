@@ -0,0 +1,65 @@
+//! Bulk rent recovery.
+//!
+//! `SweepClosedPayments` closes up to `MAX_SWEEP_PAYMENTS` payments of one config that
+//! `ClosePayment` would accept, crediting their rent to `rent_collector`. Each payment is
+//! passed as three trailing remaining accounts, built with `sweep_remaining_accounts`, after
+//! the optional OperatorDelegate PDA and MerchantStats PDA. Split larger batches with
+//! `chunks(MAX_SWEEP_PAYMENTS)`; one ineligible payment fails its whole transaction.
+//! A payment whose rent was paid by a rent treasury (`Payment::rent_payer`) can only be
+//! swept with that treasury as `rent_collector`, so batch such payments by treasury.
+//! Buyers are passed writable so a config's `RentRebatePolicy` can pay them their share.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+/// Most payments a single `SweepClosedPayments` accepts.
+pub const MAX_SWEEP_PAYMENTS: usize = 16;
+
+/// A payment to sweep, with the buyer and mint its PDA is derived from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SweptPayment {
+    pub payment: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+}
+
+/// Remaining accounts for `payments`, to append after any delegate and stats accounts.
+/// Pass `payments.len()` as `num_payments`.
+pub fn sweep_remaining_accounts(payments: &[SweptPayment]) -> Vec<AccountMeta> {
+    payments
+        .iter()
+        .flat_map(|swept| {
+            [
+                AccountMeta::new(swept.payment, false),
+                AccountMeta::new(swept.buyer, false),
+                AccountMeta::new_readonly(swept.mint, false),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_remaining_accounts() {
+        let swept = SweptPayment {
+            payment: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+        };
+
+        let accounts = sweep_remaining_accounts(&[swept, swept]);
+        assert_eq!(accounts.len(), 6);
+        assert_eq!(
+            accounts[..3],
+            [
+                AccountMeta::new(swept.payment, false),
+                AccountMeta::new(swept.buyer, false),
+                AccountMeta::new_readonly(swept.mint, false),
+            ]
+        );
+        assert_eq!(accounts[..3], accounts[3..]);
+    }
+}
@@ -0,0 +1,310 @@
+//! Transfer hook accounts for Token-2022 mints.
+//!
+//! A Token-2022 mint can name a transfer hook program that Token-2022 invokes on every
+//! transfer, e.g. a regulated stablecoin checking both parties against an allowlist. The hook
+//! needs extra accounts, listed in its ExtraAccountMetaList PDA, so every instruction that
+//! transfers such a mint takes them as its leading remaining accounts: the hook program, the
+//! extra accounts of every transfer the instruction makes, then the ExtraAccountMetaList PDA.
+//! Mints without a hook take none.
+//!
+//! The transfers are, with the config's escrow authority PDA as authority unless noted:
+//! - `MakePayment` and `MakePaymentCompressed`: the buyer's ATA (buyer authority) to the
+//!   merchant's escrow ATA, or to the settlement ATA under an auto-settling `SettlementPolicy`
+//! - `ClearPayment`: escrow to the operator's settlement ATA for a non-zero fee, then escrow
+//!   to the settlement ATA or to each split settlement destination
+//! - `ClearPaymentCompressed`: escrow to the operator's settlement ATA for a non-zero fee,
+//!   then escrow to the settlement ATA
+//! - `ClearPaymentWithConversion`: escrow to the operator's settlement ATA for a non-zero fee
+//! - `ExecuteSettlementBatch`: escrow to the settlement ATA
+//! - `RefundPayment`, `ChargebackPayment` and `CapturePayment`: escrow to the buyer's ATA
+//! - `ClaimRefund`: escrow to the buyer's destination token account
+//! - `RefundSettledPayment`: the settlement ATA (refund delegate PDA authority) to the buyer's
+//!   ATA
+//! - `RefundPaymentWithConversion`: escrow to the operator's settlement ATA, then, with the
+//!   refund mint's hook accounts after the payment mint's, the operator authority's ATA
+//!   (operator authority) to the buyer's ATA in the refund mint
+//! - `MigrateEscrow`: the merchant PDA's legacy escrow ATA (merchant PDA authority) to escrow
+//! - `MigratePayment`: the old config's escrow (its escrow authority) to the new config's
+//!   escrow
+//!
+//! Extra accounts that differ between the transfers are all passed; Token-2022 picks each
+//! transfer's out of them.
+
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::{pubkey, Pubkey};
+pub use spl_transfer_hook_interface::offchain::{AccountDataResult, AccountFetchError};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address, offchain::add_extra_account_metas_for_execute,
+};
+
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// Token-2022 pads a mint with extensions to the size of a token account and stores its
+// account type after that, followed by the extensions as type (u16 LE), length (u16 LE)
+// and value entries
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+const TRANSFER_HOOK_EXTENSION: u16 = 14;
+
+/// One token transfer an instruction makes, which the hook resolves its extra accounts for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HookedTransfer {
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+/// Program of a Token-2022 mint's transfer hook, or `None` if `data` is not a mint with a
+/// hook set.
+pub fn transfer_hook_program(data: &[u8]) -> Option<Pubkey> {
+    if data.get(ACCOUNT_TYPE_OFFSET) != Some(&ACCOUNT_TYPE_MINT) {
+        return None;
+    }
+
+    let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+    while let Some(header) = data.get(offset..offset + 4) {
+        let extension_type = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        let value = data.get(offset + 4..offset + 4 + len)?;
+
+        // Type 0 marks the zeroed space after the last extension
+        if extension_type == 0 {
+            return None;
+        }
+        if extension_type == TRANSFER_HOOK_EXTENSION {
+            // The hook authority, then the hook program; zeroed when unset
+            let program = Pubkey::try_from(value.get(32..64)?).ok()?;
+            return (program != Pubkey::default()).then_some(program);
+        }
+        offset += 4 + len;
+    }
+    None
+}
+
+/// Address of the hook program's ExtraAccountMetaList PDA for `mint`.
+pub fn extra_account_metas_address(mint: &Pubkey, hook_program: &Pubkey) -> Pubkey {
+    get_extra_account_metas_address(mint, hook_program)
+}
+
+/// Resolves the transfer hook accounts for `transfers` of `mint`, in the order the program
+/// expects them. Pass them first among the instruction's remaining accounts.
+///
+/// `fetch_account_data_fn` returns the data of an account, or `None` if it does not exist; the
+/// hook's ExtraAccountMetaList and any account its seeds read are fetched through it.
+pub async fn resolve_transfer_hook_accounts<F, Fut>(
+    mint: &Pubkey,
+    hook_program: &Pubkey,
+    transfers: &[HookedTransfer],
+    fetch_account_data_fn: F,
+) -> Result<Vec<AccountMeta>, AccountFetchError>
+where
+    F: Fn(Pubkey) -> Fut,
+    Fut: Future<Output = AccountDataResult>,
+{
+    let extra_account_metas = extra_account_metas_address(mint, hook_program);
+
+    let mut accounts = vec![AccountMeta::new_readonly(*hook_program, false)];
+    for transfer in transfers {
+        let mut instruction = Instruction::new_with_bytes(
+            TOKEN_2022_PROGRAM_ID,
+            &[],
+            vec![
+                AccountMeta::new(transfer.source, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new(transfer.destination, false),
+                AccountMeta::new_readonly(transfer.authority, false),
+            ],
+        );
+        add_extra_account_metas_for_execute(
+            &mut instruction,
+            hook_program,
+            &transfer.source,
+            mint,
+            &transfer.destination,
+            &transfer.authority,
+            transfer.amount,
+            &fetch_account_data_fn,
+        )
+        .await?;
+
+        // The extra accounts, without the hook program and ExtraAccountMetaList appended last
+        let resolved = &instruction.accounts[4..instruction.accounts.len() - 2];
+        for meta in resolved {
+            match accounts
+                .iter_mut()
+                .find(|account| account.pubkey == meta.pubkey)
+            {
+                Some(account) => account.is_writable |= meta.is_writable,
+                None => accounts.push(AccountMeta {
+                    is_signer: false,
+                    ..meta.clone()
+                }),
+            }
+        }
+    }
+    accounts.push(AccountMeta::new_readonly(extra_account_metas, false));
+
+    Ok(accounts)
+}
+
+/// Fetches `mint` and resolves its transfer hook accounts for `transfers`, or returns none if
+/// the mint has no hook.
+#[cfg(feature = "fetch")]
+pub fn fetch_transfer_hook_accounts(
+    rpc: &solana_client::rpc_client::RpcClient,
+    mint: &Pubkey,
+    transfers: &[HookedTransfer],
+) -> Result<Vec<AccountMeta>, AccountFetchError> {
+    let mint_account = rpc.get_account(mint)?;
+    if mint_account.owner != TOKEN_2022_PROGRAM_ID {
+        return Ok(Vec::new());
+    }
+    let Some(hook_program) = transfer_hook_program(&mint_account.data) else {
+        return Ok(Vec::new());
+    };
+
+    block_on_ready(resolve_transfer_hook_accounts(
+        mint,
+        &hook_program,
+        transfers,
+        |address| {
+            std::future::ready(
+                rpc.get_account_with_commitment(&address, rpc.commitment())
+                    .map(|response| response.value.map(|account| account.data))
+                    .map_err(AccountFetchError::from),
+            )
+        },
+    ))
+}
+
+/// Runs a future whose awaits all complete immediately, such as resolving hook accounts with
+/// blocking fetches.
+#[cfg(any(feature = "fetch", test))]
+fn block_on_ready<T>(future: impl Future<Output = T>) -> T {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("every fetch completes immediately"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_tlv_account_resolution::{
+        account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+    };
+    use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+    const MINT: Pubkey = Pubkey::new_from_array([1; 32]);
+    const HOOK_PROGRAM: Pubkey = Pubkey::new_from_array([2; 32]);
+    const CONFIG: Pubkey = Pubkey::new_from_array([3; 32]);
+
+    fn mint_with_hook(program: &Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; ACCOUNT_TYPE_OFFSET];
+        data.push(ACCOUNT_TYPE_MINT);
+        // A metadata pointer ahead of the hook
+        data.extend_from_slice(&18u16.to_le_bytes());
+        data.extend_from_slice(&64u16.to_le_bytes());
+        data.extend_from_slice(&[5; 64]);
+        data.extend_from_slice(&TRANSFER_HOOK_EXTENSION.to_le_bytes());
+        data.extend_from_slice(&64u16.to_le_bytes());
+        data.extend_from_slice(&[7; 32]);
+        data.extend_from_slice(program.as_ref());
+        data
+    }
+
+    /// An allowlist-style hook: a fixed config account, plus a writable PDA per destination.
+    fn extra_account_metas_data() -> Vec<u8> {
+        let metas = [
+            ExtraAccountMeta::new_with_pubkey(&CONFIG, false, false).unwrap(),
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: b"allowed".to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 },
+                ],
+                false,
+                true,
+            )
+            .unwrap(),
+        ];
+        let mut data = vec![0u8; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas).unwrap();
+        data
+    }
+
+    fn resolve(transfers: &[HookedTransfer]) -> Vec<AccountMeta> {
+        let data = extra_account_metas_data();
+        block_on_ready(resolve_transfer_hook_accounts(
+            &MINT,
+            &HOOK_PROGRAM,
+            transfers,
+            |_| std::future::ready(Ok(Some(data.clone()))),
+        ))
+        .unwrap()
+    }
+
+    fn allowed(destination: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"allowed", destination.as_ref()], &HOOK_PROGRAM).0
+    }
+
+    #[test]
+    fn test_transfer_hook_program() {
+        assert_eq!(
+            transfer_hook_program(&mint_with_hook(&HOOK_PROGRAM)),
+            Some(HOOK_PROGRAM)
+        );
+        assert_eq!(
+            transfer_hook_program(&mint_with_hook(&Pubkey::default())),
+            None
+        );
+
+        // A mint without extensions, and a truncated extension
+        assert_eq!(transfer_hook_program(&[0u8; 82]), None);
+        let data = mint_with_hook(&HOOK_PROGRAM);
+        assert_eq!(transfer_hook_program(&data[..data.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_resolve_transfer_hook_accounts() {
+        let escrow = Pubkey::new_unique();
+        let operator_ata = Pubkey::new_unique();
+        let settlement_ata = Pubkey::new_unique();
+        let merchant = Pubkey::new_unique();
+
+        let accounts = resolve(&[
+            HookedTransfer {
+                source: escrow,
+                destination: operator_ata,
+                authority: merchant,
+                amount: 25,
+            },
+            HookedTransfer {
+                source: escrow,
+                destination: settlement_ata,
+                authority: merchant,
+                amount: 975,
+            },
+        ]);
+
+        // Hook program, the config once, each destination's PDA, then the ExtraAccountMetaList
+        assert_eq!(
+            accounts,
+            vec![
+                AccountMeta::new_readonly(HOOK_PROGRAM, false),
+                AccountMeta::new_readonly(CONFIG, false),
+                AccountMeta::new(allowed(&operator_ata), false),
+                AccountMeta::new(allowed(&settlement_ata), false),
+                AccountMeta::new_readonly(extra_account_metas_address(&MINT, &HOOK_PROGRAM), false),
+            ]
+        );
+    }
+}
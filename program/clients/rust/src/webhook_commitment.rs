@@ -0,0 +1,104 @@
+//! On-chain commitments to where a config's payment webhooks are delivered.
+//!
+//! Webhook endpoints live in the indexer's own store, where anyone with access to it could
+//! point a merchant's webhooks elsewhere. `SetWebhookCommitment`, signed by the merchant
+//! authority, stores [`webhook_commitment`] of an endpoint in the config's WebhookCommitment
+//! PDA, and an indexer that checks it delivers the config's webhooks to the endpoint only
+//! while the two match. Only the hash is on-chain, so the endpoint's URL stays private.
+//!
+//! The commitment is the SHA-256 of the endpoint id's length as a u32 LE, the id, then the
+//! URL, so no two `(id, url)` pairs share an encoding.
+
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use solana_sha256_hasher::hashv;
+
+pub use crate::pdas::find_webhook_commitment_pda;
+use crate::{generated::instructions::SetWebhookCommitmentBuilder, pdas::find_merchant_pda};
+
+/// Commitment to the endpoint `endpoint_id` delivering to `url`.
+pub fn webhook_commitment(endpoint_id: &str, url: &str) -> [u8; 32] {
+    hashv(&[
+        &(endpoint_id.len() as u32).to_le_bytes(),
+        endpoint_id.as_bytes(),
+        url.as_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// `SetWebhookCommitment` committing `merchant_operator_config` to `commitment`. Must be
+/// signed by `payer` and `merchant_authority`, the authority of the config's merchant.
+pub fn set_webhook_commitment(
+    payer: &Pubkey,
+    merchant_authority: &Pubkey,
+    merchant_operator_config: &Pubkey,
+    commitment: [u8; 32],
+) -> Instruction {
+    let (webhook_commitment, bump) = find_webhook_commitment_pda(merchant_operator_config);
+    SetWebhookCommitmentBuilder::new()
+        .payer(*payer)
+        .authority(*merchant_authority)
+        .merchant(find_merchant_pda(merchant_authority).0)
+        .merchant_operator_config(*merchant_operator_config)
+        .webhook_commitment(webhook_commitment)
+        .commitment(commitment)
+        .bump(bump)
+        .instruction()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{discriminator::parse_instruction, discriminator::ParsedInstruction};
+
+    #[test]
+    fn test_webhook_commitment() {
+        let commitment = webhook_commitment("shop", "https://shop.example/webhooks");
+        assert_eq!(
+            commitment,
+            webhook_commitment("shop", "https://shop.example/webhooks")
+        );
+        assert_ne!(
+            commitment,
+            webhook_commitment("shop", "https://attacker.example/webhooks")
+        );
+        assert_ne!(
+            commitment,
+            webhook_commitment("shop2", "https://shop.example/webhooks")
+        );
+        // The id's length keeps the boundary between id and URL
+        assert_ne!(webhook_commitment("a", "bc"), webhook_commitment("ab", "c"));
+    }
+
+    #[test]
+    fn test_set_webhook_commitment() {
+        let payer = Pubkey::new_unique();
+        let merchant_authority = Pubkey::new_unique();
+        let merchant_operator_config = Pubkey::new_unique();
+        let commitment = webhook_commitment("shop", "https://shop.example/webhooks");
+
+        let instruction = set_webhook_commitment(
+            &payer,
+            &merchant_authority,
+            &merchant_operator_config,
+            commitment,
+        );
+        let (webhook_commitment_pda, bump) = find_webhook_commitment_pda(&merchant_operator_config);
+        assert_eq!(instruction.accounts[0].pubkey, payer);
+        assert!(instruction.accounts[1].is_signer);
+        assert_eq!(
+            instruction.accounts[2].pubkey,
+            find_merchant_pda(&merchant_authority).0
+        );
+        assert_eq!(instruction.accounts[4].pubkey, webhook_commitment_pda);
+        assert!(instruction.accounts[4].is_writable);
+
+        let ParsedInstruction::SetWebhookCommitment(args) =
+            parse_instruction(&instruction.data).unwrap()
+        else {
+            panic!("expected SetWebhookCommitment");
+        };
+        assert_eq!(args.commitment, commitment);
+        assert_eq!(args.bump, bump);
+    }
+}
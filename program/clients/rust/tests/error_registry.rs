@@ -0,0 +1,80 @@
+//! Keeps `idl/commerce_program_errors.json` in sync with the program's errors.
+//!
+//! The registry must list the same codes, names and messages as the IDL. After adding an
+//! error and regenerating the IDL and client, regenerate the registry with:
+//! `UPDATE_GOLDEN=1 cargo test -p commerce-program-client --test error_registry`
+
+use std::{fs, path::PathBuf};
+
+use commerce_program_client::error_registry::error_registry;
+use serde_json::{json, Value};
+
+fn idl_path(file: &str) -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "..", "idl", file]
+        .iter()
+        .collect()
+}
+
+fn registry_json() -> Value {
+    Value::Array(
+        error_registry()
+            .into_iter()
+            .map(|info| {
+                json!({
+                    "code": info.code,
+                    "name": info.name,
+                    "message": info.message,
+                })
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_error_registry_matches_idl() {
+    let idl: Value =
+        serde_json::from_str(&fs::read_to_string(idl_path("commerce_program.json")).unwrap())
+            .unwrap();
+    let idl_errors: Vec<(u64, &str, &str)> = idl["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| {
+            (
+                error["code"].as_u64().unwrap(),
+                error["name"].as_str().unwrap(),
+                error["msg"].as_str().unwrap(),
+            )
+        })
+        .collect();
+
+    let registry = error_registry();
+    assert_eq!(registry.len(), idl_errors.len());
+    for (info, (code, name, message)) in registry.iter().zip(idl_errors) {
+        assert_eq!(
+            (info.code as u64, info.name.as_str(), info.message.as_str()),
+            (code, name, message)
+        );
+    }
+}
+
+#[test]
+fn test_error_registry_json() {
+    let path = idl_path("commerce_program_errors.json");
+    let actual = format!(
+        "{}\n",
+        serde_json::to_string_pretty(&registry_json()).unwrap()
+    );
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Missing error registry {}", path.display()));
+    assert_eq!(
+        actual, expected,
+        "error registry changed; rerun with UPDATE_GOLDEN=1 if intended"
+    );
+}
@@ -0,0 +1,1217 @@
+//! Golden fixtures for every instruction builder.
+//!
+//! Each builder is driven with fixed inputs and the resulting instruction is compared
+//! byte-for-byte against `tests/fixtures/instructions/<name>.txt`. A mismatch means the
+//! discriminator, account ordering/flags or argument encoding changed. Each instruction must
+//! also decode with `decode_instruction` and re-encode to itself.
+//!
+//! After an intentional change, regenerate the fixtures with:
+//! `UPDATE_GOLDEN=1 cargo test -p commerce-program-client --test golden_instructions`
+
+use std::{fmt::Write, fs, path::PathBuf};
+
+use commerce_program_client::{
+    decode::decode_instruction,
+    instructions::*,
+    types::{
+        AnnotationFlag, DenylistProof, EncryptedNote, FeeOverride, FeeType, PolicyData,
+        RefundPolicy, SettlementPolicy,
+    },
+};
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn render(instruction: &Instruction) -> String {
+    let mut out = String::new();
+    writeln!(out, "program_id {}", instruction.program_id).unwrap();
+    writeln!(out, "accounts {}", instruction.accounts.len()).unwrap();
+    for (index, meta) in instruction.accounts.iter().enumerate() {
+        writeln!(
+            out,
+            "{:>2} {} {} {}",
+            index,
+            meta.pubkey,
+            if meta.is_signer { "signer" } else { "-" },
+            if meta.is_writable { "writable" } else { "-" },
+        )
+        .unwrap();
+    }
+    write!(out, "data ").unwrap();
+    for byte in &instruction.data {
+        write!(out, "{:02x}", byte).unwrap();
+    }
+    writeln!(out).unwrap();
+    out
+}
+
+fn assert_golden(name: &str, instruction: Instruction) {
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "tests",
+        "fixtures",
+        "instructions",
+        &format!("{name}.txt"),
+    ]
+    .iter()
+    .collect();
+    let actual = render(&instruction);
+
+    let decoded = decode_instruction(&instruction)
+        .unwrap_or_else(|err| panic!("{name} does not decode: {err}"));
+    assert_eq!(
+        decoded.instruction(),
+        instruction,
+        "{name} does not re-encode to itself"
+    );
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Missing golden fixture {}", path.display()));
+    assert_eq!(
+        actual, expected,
+        "{name} no longer matches its golden fixture; rerun with UPDATE_GOLDEN=1 if intended"
+    );
+}
+
+#[test]
+fn test_initialize_merchant_golden() {
+    assert_golden(
+        "initialize_merchant",
+        InitializeMerchantBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .settlement_wallet(key(4))
+            .bump(254)
+            .settlement_wallet_timelock_seconds(86_400)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_create_operator_golden() {
+    assert_golden(
+        "create_operator",
+        CreateOperatorBuilder::new()
+            .payer(key(1))
+            .operator(key(2))
+            .authority(key(3))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_merchant_operator_config_golden() {
+    assert_golden(
+        "initialize_merchant_operator_config",
+        InitializeMerchantOperatorConfigBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .operator(key(4))
+            .config(key(5))
+            .version(7)
+            .bump(252)
+            .operator_fee(250)
+            .fee_type(FeeType::Bps)
+            .days_to_close(30)
+            .policies(vec![
+                PolicyData::Refund(RefundPolicy {
+                    max_amount: 5_000_000,
+                    max_time_after_purchase: 86_400,
+                }),
+                PolicyData::Settlement(SettlementPolicy {
+                    min_settlement_amount: 1_000,
+                    settlement_frequency_hours: 24,
+                    auto_settle: true,
+                }),
+            ])
+            .accepted_currencies(vec![key(6), key(7)])
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_make_payment_golden() {
+    assert_golden(
+        "make_payment",
+        MakePaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .operator(key(5))
+            .merchant(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .buyer_ata(key(9))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(10))
+            .merchant_settlement_ata(key(11))
+            .settlement_wallet(key(12))
+            .order_id(42)
+            .amount(1_000_000)
+            .idempotency_key([0x1d; 16])
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_make_payment_fee_override_golden() {
+    assert_golden(
+        "make_payment_fee_override",
+        MakePaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .operator(key(5))
+            .merchant(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .buyer_ata(key(9))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(10))
+            .merchant_settlement_ata(key(11))
+            .settlement_wallet(key(12))
+            .order_id(42)
+            .amount(1_000_000)
+            .idempotency_key([0x1d; 16])
+            .fee_override(FeeOverride {
+                fee: 150,
+                fee_type: FeeType::Bps,
+            })
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_make_payment_denylist_proof_golden() {
+    assert_golden(
+        "make_payment_denylist_proof",
+        MakePaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .operator(key(5))
+            .merchant(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .buyer_ata(key(9))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(10))
+            .merchant_settlement_ata(key(11))
+            .settlement_wallet(key(12))
+            .order_id(42)
+            .amount(1_000_000)
+            .idempotency_key([0x1d; 16])
+            .denylist_proof(DenylistProof {
+                lower: key(13),
+                upper: key(14),
+                leaf_index: 3,
+                proof: vec![[0xaa; 32], [0xbb; 32]],
+            })
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_make_payment_authorize_golden() {
+    assert_golden(
+        "make_payment_authorize",
+        MakePaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .operator(key(5))
+            .merchant(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .buyer_ata(key(9))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(10))
+            .merchant_settlement_ata(key(11))
+            .settlement_wallet(key(12))
+            .order_id(42)
+            .amount(1_000_000)
+            .idempotency_key([0x1d; 16])
+            .authorize(true)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_make_payment_note_golden() {
+    assert_golden(
+        "make_payment_note",
+        MakePaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .operator(key(5))
+            .merchant(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .buyer_ata(key(9))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(10))
+            .merchant_settlement_ata(key(11))
+            .settlement_wallet(key(12))
+            .order_id(42)
+            .amount(1_000_000)
+            .idempotency_key([0x1d; 16])
+            .note(EncryptedNote {
+                ciphertext: [0xcc; 128],
+            })
+            .add_remaining_account(AccountMeta::new(key(13), false))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_clear_payment_golden() {
+    assert_golden(
+        "clear_payment",
+        ClearPaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(9))
+            .merchant_settlement_ata(key(10))
+            .operator_settlement_ata(key(11))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_refund_payment_golden() {
+    assert_golden(
+        "refund_payment",
+        RefundPaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(9))
+            .buyer_ata(key(10))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_refund_payment_close_escrow_golden() {
+    assert_golden(
+        "refund_payment_close_escrow",
+        RefundPaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(9))
+            .buyer_ata(key(10))
+            .close_escrow(true)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_update_merchant_settlement_wallet_golden() {
+    assert_golden(
+        "update_merchant_settlement_wallet",
+        UpdateMerchantSettlementWalletBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .new_settlement_wallet(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_propose_merchant_authority_golden() {
+    assert_golden(
+        "propose_merchant_authority",
+        ProposeMerchantAuthorityBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .new_authority(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_propose_operator_authority_golden() {
+    assert_golden(
+        "propose_operator_authority",
+        ProposeOperatorAuthorityBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .operator(key(3))
+            .new_operator_authority(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_close_payment_golden() {
+    assert_golden(
+        "close_payment",
+        ClosePaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .operator(key(4))
+            .merchant(key(5))
+            .buyer(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_payment_tree_golden() {
+    assert_golden(
+        "initialize_payment_tree",
+        InitializePaymentTreeBuilder::new()
+            .payer(key(1))
+            .operator_authority(key(2))
+            .operator(key(3))
+            .merchant_operator_config(key(4))
+            .payment_tree(key(5))
+            .bump(250)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_make_payment_compressed_golden() {
+    assert_golden(
+        "make_payment_compressed",
+        MakePaymentCompressedBuilder::new()
+            .payment_tree(key(1))
+            .operator_authority(key(2))
+            .buyer(key(3))
+            .operator(key(4))
+            .merchant(key(5))
+            .merchant_operator_config(key(6))
+            .mint(key(7))
+            .buyer_ata(key(8))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(9))
+            .merchant_settlement_ata(key(10))
+            .order_id(42)
+            .amount(1_000_000)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_clear_payment_compressed_golden() {
+    assert_golden(
+        "clear_payment_compressed",
+        ClearPaymentCompressedBuilder::new()
+            .payer(key(1))
+            .payment_tree(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(9))
+            .merchant_settlement_ata(key(10))
+            .operator_settlement_ata(key(11))
+            .leaf_index(3)
+            .order_id(42)
+            .amount(1_000_000)
+            .created_at(1_700_000_000)
+            .proof(vec![[0xaa; 32], [0xbb; 32]])
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_accept_merchant_authority_golden() {
+    assert_golden(
+        "accept_merchant_authority",
+        AcceptMerchantAuthorityBuilder::new()
+            .payer(key(1))
+            .new_authority(key(2))
+            .merchant(key(3))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_accept_operator_authority_golden() {
+    assert_golden(
+        "accept_operator_authority",
+        AcceptOperatorAuthorityBuilder::new()
+            .payer(key(1))
+            .new_operator_authority(key(2))
+            .operator(key(3))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_merchant_stats_golden() {
+    assert_golden(
+        "initialize_merchant_stats",
+        InitializeMerchantStatsBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .mint(key(4))
+            .merchant_stats(key(5))
+            .bump(249)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_refund_settled_payment_golden() {
+    assert_golden(
+        "refund_settled_payment",
+        RefundSettledPaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .merchant_settlement_ata(key(9))
+            .buyer_ata(key(10))
+            .refund_delegate(key(11))
+            .refund_delegate_bump(248)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_operator_delegate_golden() {
+    assert_golden(
+        "initialize_operator_delegate",
+        InitializeOperatorDelegateBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .operator(key(3))
+            .operator_delegate(key(4))
+            .delegate(key(5))
+            .bump(247)
+            .can_make_payment(true)
+            .can_clear(true)
+            .can_refund(false)
+            .can_close(false)
+            .max_refund_amount(1_000_000)
+            .max_daily_refund_amount(5_000_000)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_update_operator_delegate_golden() {
+    assert_golden(
+        "update_operator_delegate",
+        UpdateOperatorDelegateBuilder::new()
+            .authority(key(1))
+            .operator(key(2))
+            .operator_delegate(key(3))
+            .can_make_payment(false)
+            .can_clear(true)
+            .can_refund(true)
+            .can_close(false)
+            .max_refund_amount(0)
+            .max_daily_refund_amount(25_000_000)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_revoke_operator_delegate_golden() {
+    assert_golden(
+        "revoke_operator_delegate",
+        RevokeOperatorDelegateBuilder::new()
+            .authority(key(1))
+            .operator(key(2))
+            .operator_delegate(key(3))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_clear_payment_with_conversion_golden() {
+    assert_golden(
+        "clear_payment_with_conversion",
+        ClearPaymentWithConversionBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(9))
+            .settlement_mint(key(10))
+            .conversion_rate(key(41))
+            .merchant_settlement_ata(key(11))
+            .operator_settlement_ata(key(12))
+            .swap_program(key(13))
+            .quoted_amount_out(995_000)
+            .num_swap_accounts(2)
+            .swap_data(vec![0xe5, 0x17, 0xcb, 0x97])
+            .add_remaining_accounts(&[
+                AccountMeta::new(key(14), false),
+                AccountMeta::new_readonly(key(15), false),
+            ])
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_chargeback_payment_golden() {
+    assert_golden(
+        "chargeback_payment",
+        ChargebackPaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .arbiter(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(10))
+            .merchant_operator_config(key(6))
+            .mint(key(7))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(8))
+            .buyer_ata(key(9))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_sweep_closed_payments_golden() {
+    assert_golden(
+        "sweep_closed_payments",
+        SweepClosedPaymentsBuilder::new()
+            .payer(key(1))
+            .operator_authority(key(2))
+            .operator(key(3))
+            .merchant(key(4))
+            .merchant_operator_config(key(5))
+            .rent_collector(key(6))
+            .num_payments(2)
+            .add_remaining_accounts(&[
+                AccountMeta::new(key(7), false),
+                AccountMeta::new_readonly(key(8), false),
+                AccountMeta::new_readonly(key(9), false),
+                AccountMeta::new(key(10), false),
+                AccountMeta::new_readonly(key(11), false),
+                AccountMeta::new_readonly(key(9), false),
+            ])
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_emit_event_golden() {
+    assert_golden(
+        "emit_event",
+        EmitEventBuilder::new()
+            .event_authority(key(1))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_propose_settlement_wallet_golden() {
+    assert_golden(
+        "propose_settlement_wallet",
+        ProposeSettlementWalletBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .new_settlement_wallet(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_commit_settlement_wallet_golden() {
+    assert_golden(
+        "commit_settlement_wallet",
+        CommitSettlementWalletBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_set_settlement_wallet_timelock_golden() {
+    assert_golden(
+        "set_settlement_wallet_timelock",
+        SetSettlementWalletTimelockBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .settlement_wallet_timelock_seconds(86_400)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_settlement_batch_golden() {
+    assert_golden(
+        "initialize_settlement_batch",
+        InitializeSettlementBatchBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .merchant_operator_config(key(4))
+            .mint(key(5))
+            .settlement_batch(key(6))
+            .bump(248)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_execute_settlement_batch_golden() {
+    assert_golden(
+        "execute_settlement_batch",
+        ExecuteSettlementBatchBuilder::new()
+            .settlement_batch(key(1))
+            .merchant(key(2))
+            .merchant_operator_config(key(3))
+            .mint(key(4))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(5))
+            .merchant_settlement_ata(key(6))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_create_order_golden() {
+    assert_golden(
+        "create_order",
+        CreateOrderBuilder::new()
+            .payer(key(1))
+            .operator_authority(key(2))
+            .buyer(key(3))
+            .operator(key(4))
+            .merchant(key(5))
+            .merchant_operator_config(key(6))
+            .order(key(7))
+            .order_id(42)
+            .expected_payments(2)
+            .bump(247)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_merchant_profile_golden() {
+    assert_golden(
+        "initialize_merchant_profile",
+        InitializeMerchantProfileBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .merchant_profile(key(4))
+            .name("Coffee Shop".to_string())
+            .uri("https://coffee.example".to_string())
+            .logo_hash([5; 32])
+            .contact_hash([6; 32])
+            .bump(246)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_update_merchant_profile_golden() {
+    assert_golden(
+        "update_merchant_profile",
+        UpdateMerchantProfileBuilder::new()
+            .authority(key(1))
+            .merchant(key(2))
+            .merchant_profile(key(3))
+            .name("Coffee Shop".to_string())
+            .uri("https://coffee.example".to_string())
+            .logo_hash([5; 32])
+            .contact_hash([6; 32])
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_approve_refund_golden() {
+    assert_golden(
+        "approve_refund",
+        ApproveRefundBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .authority(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .refund_approval(key(9))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_claim_refund_golden() {
+    assert_golden(
+        "claim_refund",
+        ClaimRefundBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .buyer(key(3))
+            .merchant(key(4))
+            .operator(key(5))
+            .merchant_operator_config(key(6))
+            .mint(key(7))
+            .refund_approval(key(8))
+            .rent_payer(key(11))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(9))
+            .destination(key(10))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_risk_state_golden() {
+    assert_golden(
+        "initialize_risk_state",
+        InitializeRiskStateBuilder::new()
+            .payer(key(1))
+            .merchant_operator_config(key(2))
+            .risk_state(key(3))
+            .bump(244)
+            .buyer(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_buyer_index_golden() {
+    assert_golden(
+        "initialize_buyer_index",
+        InitializeBuyerIndexBuilder::new()
+            .payer(key(1))
+            .merchant_operator_config(key(2))
+            .buyer(key(3))
+            .buyer_index(key(4))
+            .bump(243)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_merchant_with_config_golden() {
+    assert_golden(
+        "initialize_merchant_with_config",
+        InitializeMerchantWithConfigBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .operator_authority(key(3))
+            .merchant(key(4))
+            .settlement_wallet(key(5))
+            .operator(key(6))
+            .config(key(7))
+            .merchant_bump(255)
+            .settlement_wallet_timelock_seconds(86_400)
+            .version(1)
+            .config_bump(242)
+            .operator_fee(250)
+            .fee_type(FeeType::Bps)
+            .days_to_close(30)
+            .policies(vec![PolicyData::Refund(RefundPolicy {
+                max_amount: 5_000_000,
+                max_time_after_purchase: 86_400,
+            })])
+            .accepted_currencies(vec![key(8)])
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_migrate_escrow_golden() {
+    assert_golden(
+        "migrate_escrow",
+        MigrateEscrowBuilder::new()
+            .payer(key(1))
+            .merchant_authority(key(2))
+            .operator_authority(key(3))
+            .merchant(key(4))
+            .operator(key(5))
+            .merchant_operator_config(key(6))
+            .mint(key(7))
+            .legacy_escrow_ata(key(8))
+            .escrow_authority(key(9))
+            .merchant_escrow_ata(key(10))
+            .amount(1_000_000)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_create_quote_golden() {
+    assert_golden(
+        "create_quote",
+        CreateQuoteBuilder::new()
+            .payer(key(1))
+            .operator_authority(key(2))
+            .operator(key(3))
+            .merchant_operator_config(key(4))
+            .mint(key(5))
+            .quote(key(6))
+            .order_id(42)
+            .amount(1_000_000)
+            .expires_at(1_700_000_600)
+            .bump(243)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_config_registry_page_golden() {
+    assert_golden(
+        "initialize_config_registry_page",
+        InitializeConfigRegistryPageBuilder::new()
+            .payer(key(1))
+            .operator(key(2))
+            .config_registry_page(key(3))
+            .page(1)
+            .bump(242)
+            .add_remaining_account(AccountMeta::new_readonly(key(4), false))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_capture_payment_golden() {
+    assert_golden(
+        "capture_payment",
+        CapturePaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(9))
+            .buyer_ata(key(10))
+            .amount(750_000)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_initialize_operator_freeze_list_golden() {
+    assert_golden(
+        "initialize_operator_freeze_list",
+        InitializeOperatorFreezeListBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .operator(key(3))
+            .operator_freeze_list(key(4))
+            .bump(241)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_add_to_freeze_list_golden() {
+    assert_golden(
+        "add_to_freeze_list",
+        AddToFreezeListBuilder::new()
+            .authority(key(1))
+            .operator(key(2))
+            .operator_freeze_list(key(3))
+            .address(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_remove_from_freeze_list_golden() {
+    assert_golden(
+        "remove_from_freeze_list",
+        RemoveFromFreezeListBuilder::new()
+            .authority(key(1))
+            .operator(key(2))
+            .operator_freeze_list(key(3))
+            .address(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_post_conversion_rate_golden() {
+    assert_golden(
+        "post_conversion_rate",
+        PostConversionRateBuilder::new()
+            .payer(key(1))
+            .operator_authority(key(2))
+            .operator(key(3))
+            .merchant_operator_config(key(4))
+            .from_mint(key(5))
+            .to_mint(key(6))
+            .conversion_rate(key(7))
+            .rate_numerator(98)
+            .rate_denominator(100)
+            .bump(238)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_refund_payment_with_conversion_golden() {
+    assert_golden(
+        "refund_payment_with_conversion",
+        RefundPaymentWithConversionBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .merchant(key(5))
+            .operator(key(6))
+            .merchant_operator_config(key(7))
+            .mint(key(8))
+            .escrow_authority(key(40))
+            .merchant_escrow_ata(key(9))
+            .operator_settlement_ata(key(10))
+            .refund_mint(key(11))
+            .conversion_rate(key(12))
+            .operator_refund_ata(key(13))
+            .buyer_refund_ata(key(14))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_add_policy_golden() {
+    assert_golden(
+        "add_policy",
+        AddPolicyBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .merchant_operator_config(key(4))
+            .policy(PolicyData::Refund(RefundPolicy {
+                max_amount: 5_000,
+                max_time_after_purchase: 604_800,
+            }))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_add_accepted_currency_golden() {
+    assert_golden(
+        "add_accepted_currency",
+        AddAcceptedCurrencyBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .merchant_operator_config(key(4))
+            .mint(key(5))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_close_payment_note_golden() {
+    assert_golden(
+        "close_payment_note",
+        ClosePaymentNoteBuilder::new()
+            .authority(key(1))
+            .merchant(key(2))
+            .payment_note(key(3))
+            .rent_payer(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_heartbeat_golden() {
+    assert_golden(
+        "heartbeat",
+        HeartbeatBuilder::new()
+            .operator_authority(key(1))
+            .operator(key(2))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_close_escrow_ata_golden() {
+    assert_golden(
+        "close_escrow_ata",
+        CloseEscrowAtaBuilder::new()
+            .merchant_authority(key(1))
+            .operator_authority(key(2))
+            .merchant(key(3))
+            .operator(key(4))
+            .merchant_operator_config(key(5))
+            .mint(key(6))
+            .escrow_authority(key(7))
+            .merchant_escrow_ata(key(8))
+            .merchant_settlement_ata(key(9))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_migrate_payment_golden() {
+    assert_golden(
+        "migrate_payment",
+        MigratePaymentBuilder::new()
+            .payer(key(1))
+            .merchant_authority(key(2))
+            .operator_authority(key(3))
+            .merchant(key(4))
+            .operator(key(5))
+            .buyer(key(6))
+            .mint(key(7))
+            .old_merchant_operator_config(key(8))
+            .new_merchant_operator_config(key(9))
+            .old_payment(key(10))
+            .new_payment(key(11))
+            .old_escrow_authority(key(12))
+            .old_merchant_escrow_ata(key(13))
+            .new_escrow_authority(key(14))
+            .new_merchant_escrow_ata(key(15))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_get_config_summary_golden() {
+    assert_golden(
+        "get_config_summary",
+        GetConfigSummaryBuilder::new()
+            .merchant_operator_config(key(1))
+            .mint(key(2))
+            .escrow_authority(key(3))
+            .merchant_escrow_ata(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_set_webhook_commitment_golden() {
+    assert_golden(
+        "set_webhook_commitment",
+        SetWebhookCommitmentBuilder::new()
+            .payer(key(1))
+            .authority(key(2))
+            .merchant(key(3))
+            .merchant_operator_config(key(4))
+            .webhook_commitment(key(5))
+            .commitment([0xcd; 32])
+            .bump(237)
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_annotate_payment_golden() {
+    assert_golden(
+        "annotate_payment",
+        AnnotatePaymentBuilder::new()
+            .payer(key(1))
+            .payment(key(2))
+            .operator_authority(key(3))
+            .buyer(key(4))
+            .operator(key(5))
+            .merchant_operator_config(key(6))
+            .mint(key(7))
+            .payment_annotation(key(8))
+            .flag(AnnotationFlag::ManualReview)
+            .note_hash([0xcd; 32])
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_decommission_config_golden() {
+    assert_golden(
+        "decommission_config",
+        DecommissionConfigBuilder::new()
+            .authority(key(1))
+            .merchant(key(2))
+            .operator(key(3))
+            .merchant_operator_config(key(4))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_close_merchant_operator_config_golden() {
+    assert_golden(
+        "close_merchant_operator_config",
+        CloseMerchantOperatorConfigBuilder::new()
+            .merchant_authority(key(1))
+            .operator_authority(key(2))
+            .merchant(key(3))
+            .operator(key(4))
+            .merchant_operator_config(key(5))
+            .instruction(),
+    );
+}
+
+#[test]
+fn test_upgrade_account_golden() {
+    assert_golden(
+        "upgrade_account",
+        UpgradeAccountBuilder::new()
+            .payer(key(1))
+            .account(key(2))
+            .instruction(),
+    );
+}
@@ -0,0 +1,186 @@
+//! Operators the cranker runs for.
+//!
+//! The `COMMERCE_CRANKER_OPERATORS` file is a JSON array with one entry per operator
+//! authority. Each entry names the authority's keypair file, which signs and pays for every
+//! transaction sent for it, and which tasks to run:
+//!
+//! ```json
+//! [{"keypair": "operator.json", "authorization_hold_hours": 168, "max_transactions_per_minute": 30}]
+//! ```
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use solana_keypair::{read_keypair_file, Keypair};
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use thiserror::Error;
+
+const DEFAULT_POLL_SECONDS: u64 = 60;
+const DEFAULT_MAX_TRANSACTIONS_PER_MINUTE: u32 = 60;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read operators {0}: {1}")]
+    Io(PathBuf, io::Error),
+    #[error("failed to parse operators {0}: {1}")]
+    Parse(PathBuf, serde_json::Error),
+    #[error("failed to read keypair {0}: {1}")]
+    Keypair(PathBuf, String),
+    #[error("operator authority {0} is listed twice")]
+    DuplicateOperator(Pubkey),
+    #[error("operator authority {0} allows no transactions")]
+    NoTransactions(Pubkey),
+}
+
+fn default_poll_seconds() -> u64 {
+    DEFAULT_POLL_SECONDS
+}
+
+fn default_max_transactions_per_minute() -> u32 {
+    DEFAULT_MAX_TRANSACTIONS_PER_MINUTE
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// What the cranker does for one operator authority.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperatorSettings {
+    /// Keypair file of the operator authority, relative to the operators file.
+    pub keypair: PathBuf,
+    /// Seconds between scans of the operator's configs.
+    #[serde(default = "default_poll_seconds")]
+    pub poll_seconds: u64,
+    /// Transactions sent at most per minute, across all tasks.
+    #[serde(default = "default_max_transactions_per_minute")]
+    pub max_transactions_per_minute: u32,
+    /// Sweep payments whose close window has passed, returning their rent.
+    #[serde(default = "enabled")]
+    pub close_payments: bool,
+    /// Execute settlement batches their settlement policy allows paying out.
+    #[serde(default = "enabled")]
+    pub execute_settlement_batches: bool,
+    /// Hours an authorization may hold the buyer's funds before its whole hold is released
+    /// with a zero capture. Authorizations are left alone without it.
+    #[serde(default)]
+    pub authorization_hold_hours: Option<u32>,
+}
+
+/// An operator authority with its keypair.
+pub struct CrankerOperator {
+    pub keypair: Keypair,
+    pub settings: OperatorSettings,
+}
+
+impl CrankerOperator {
+    pub fn authority(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+}
+
+/// Reads the operators at `path` and their keypairs.
+pub fn load_operators(path: &Path) -> Result<Vec<CrankerOperator>, ConfigError> {
+    let data = fs::read(path).map_err(|error| ConfigError::Io(path.into(), error))?;
+    let settings: Vec<OperatorSettings> =
+        serde_json::from_slice(&data).map_err(|error| ConfigError::Parse(path.into(), error))?;
+
+    let base = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut operators: Vec<CrankerOperator> = Vec::with_capacity(settings.len());
+    for settings in settings {
+        let keypair_path = base.join(&settings.keypair);
+        let keypair = read_keypair_file(&keypair_path)
+            .map_err(|error| ConfigError::Keypair(keypair_path, error.to_string()))?;
+        let authority = keypair.pubkey();
+
+        if settings.max_transactions_per_minute == 0 {
+            return Err(ConfigError::NoTransactions(authority));
+        }
+        if operators
+            .iter()
+            .any(|operator| operator.authority() == authority)
+        {
+            return Err(ConfigError::DuplicateOperator(authority));
+        }
+        operators.push(CrankerOperator { keypair, settings });
+    }
+    Ok(operators)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_keypair(dir: &Path, name: &str, keypair: &Keypair) {
+        fs::write(
+            dir.join(name),
+            serde_json::to_vec(&keypair.to_bytes().to_vec()).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_load_operators_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = Keypair::new();
+        write_keypair(dir.path(), "operator.json", &keypair);
+        let path = dir.path().join("operators.json");
+        fs::write(
+            &path,
+            r#"[{"keypair": "operator.json", "authorization_hold_hours": 168}]"#,
+        )
+        .unwrap();
+
+        let operators = load_operators(&path).unwrap();
+        assert_eq!(operators.len(), 1);
+        assert_eq!(operators[0].authority(), keypair.pubkey());
+        assert_eq!(
+            operators[0].settings,
+            OperatorSettings {
+                keypair: "operator.json".into(),
+                poll_seconds: DEFAULT_POLL_SECONDS,
+                max_transactions_per_minute: DEFAULT_MAX_TRANSACTIONS_PER_MINUTE,
+                close_payments: true,
+                execute_settlement_batches: true,
+                authorization_hold_hours: Some(168),
+            }
+        );
+    }
+
+    #[test]
+    fn test_load_operators_rejects_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = Keypair::new();
+        write_keypair(dir.path(), "a.json", &keypair);
+        write_keypair(dir.path(), "b.json", &keypair);
+        let path = dir.path().join("operators.json");
+        fs::write(&path, r#"[{"keypair": "a.json"}, {"keypair": "b.json"}]"#).unwrap();
+
+        assert!(matches!(
+            load_operators(&path),
+            Err(ConfigError::DuplicateOperator(authority)) if authority == keypair.pubkey()
+        ));
+    }
+
+    #[test]
+    fn test_load_operators_rejects_zero_rate() {
+        let dir = tempfile::tempdir().unwrap();
+        write_keypair(dir.path(), "operator.json", &Keypair::new());
+        let path = dir.path().join("operators.json");
+        fs::write(
+            &path,
+            r#"[{"keypair": "operator.json", "max_transactions_per_minute": 0}]"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            load_operators(&path),
+            Err(ConfigError::NoTransactions(_))
+        ));
+    }
+}
@@ -0,0 +1,279 @@
+//! The crank loop of one operator authority.
+//!
+//! Every `poll_seconds`, [`Cranker`] scans the operator's configs for the work described in
+//! [`crate::tasks`], then sends one transaction per item, spaced to stay within
+//! `max_transactions_per_minute`. Payments are tracked between scans, from their
+//! `MakePayment` until their account is gone, so each scan only reads the configs' new
+//! transactions. A failed scan or transaction is logged and counted in the metrics, and
+//! retried on the next scan if the work is still there. Logs of a payment's work carry its
+//! `payment` PDA and `order_id`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use commerce_program_client::{
+    accounts::Payment,
+    pdas::find_operator_pda,
+    send::{send_and_confirm, BlockhashSource, RetryClass, SendConfig, SendError},
+};
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use tokio::time::{interval, sleep, Interval, MissedTickBehavior};
+use tracing::{field, Instrument};
+
+use crate::{
+    config::CrankerOperator,
+    metrics::{Metrics, Outcome, Stage},
+    source::{RpcSource, SourceError},
+    tasks::{
+        authorization_expired, close_eligible, execute_settlement_batch_instruction,
+        release_authorization_instruction, rent_collector, settlement_batch_ready,
+        sweep_instructions, TaskKind, TrackedPayment,
+    },
+};
+
+/// Payments of a config found so far, and the newest transaction they were read up to.
+#[derive(Default)]
+struct TrackedConfig {
+    newest: Option<Signature>,
+    payments: BTreeMap<Pubkey, TrackedPayment>,
+}
+
+/// A transaction to send, with the payment and order id it is about when it is about one.
+struct Work {
+    task: TaskKind,
+    instruction: Instruction,
+    payment: Option<(Pubkey, u32)>,
+}
+
+impl Work {
+    fn new(task: TaskKind, instruction: Instruction) -> Self {
+        Self {
+            task,
+            instruction,
+            payment: None,
+        }
+    }
+}
+
+pub struct Cranker {
+    source: Arc<RpcSource>,
+    metrics: Arc<Metrics>,
+    operator: CrankerOperator,
+    configs: HashMap<Pubkey, TrackedConfig>,
+    /// Token program of each mint seen; a mint's owner never changes.
+    token_programs: HashMap<Pubkey, Pubkey>,
+    limiter: Interval,
+}
+
+impl Cranker {
+    pub fn new(source: Arc<RpcSource>, metrics: Arc<Metrics>, operator: CrankerOperator) -> Self {
+        let mut limiter =
+            interval(Duration::from_secs(60) / operator.settings.max_transactions_per_minute);
+        limiter.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            source,
+            metrics,
+            operator,
+            configs: HashMap::new(),
+            token_programs: HashMap::new(),
+            limiter,
+        }
+    }
+
+    /// Scans and sends until the process stops.
+    #[tracing::instrument(name = "cranker", skip_all, fields(operator = %self.operator.authority()))]
+    pub async fn run(mut self) {
+        let authority = self.operator.authority();
+        loop {
+            match self.scan().await {
+                Ok(work) => {
+                    self.metrics.record_scan(&authority, true);
+                    for work in work {
+                        self.send(work).await;
+                    }
+                }
+                Err(error) => {
+                    self.metrics.record_scan(&authority, false);
+                    if matches!(error, SourceError::Rpc(_)) {
+                        self.metrics.record_rpc_error(&authority, Stage::Scan);
+                    }
+                    tracing::warn!(%error, "scan failed");
+                }
+            }
+            sleep(Duration::from_secs(self.operator.settings.poll_seconds)).await;
+        }
+    }
+
+    /// Instructions for the work found in the operator's configs.
+    async fn scan(&mut self) -> Result<Vec<Work>, SourceError> {
+        let authority = self.operator.authority();
+        let settings = self.operator.settings.clone();
+        let now = self.source.now().await?;
+        let configs = self
+            .source
+            .operator_configs(&find_operator_pda(&authority).0)
+            .await?;
+        self.configs
+            .retain(|address, _| configs.iter().any(|config| config.address == *address));
+
+        let mut work = Vec::new();
+        let mut eligible: BTreeMap<TaskKind, usize> =
+            TaskKind::ALL.iter().map(|task| (*task, 0)).collect();
+        for config in &configs {
+            let tracked = self.configs.entry(config.address).or_default();
+            let (made, newest) = self
+                .source
+                .made_payments(&config.address, tracked.newest)
+                .await?;
+            tracked.newest = newest.or(tracked.newest);
+            tracked
+                .payments
+                .extend(made.into_iter().map(|payment| (payment.payment, payment)));
+
+            let payments: Vec<TrackedPayment> = tracked.payments.values().copied().collect();
+            let addresses: Vec<Pubkey> = payments.iter().map(|payment| payment.payment).collect();
+            let accounts = self.source.accounts(&addresses).await?;
+
+            let mut closeable = Vec::new();
+            let mut expired = Vec::new();
+            for (payment, account) in payments.into_iter().zip(accounts) {
+                let Some(account) = account else {
+                    // Closed, nothing left to do
+                    tracked.payments.remove(&payment.payment);
+                    continue;
+                };
+                let state = Payment::from_bytes(&account.data)
+                    .map_err(|e| SourceError::Decode(payment.payment, e))?;
+                if settings.close_payments && close_eligible(&state, config.days_to_close, now) {
+                    tracing::debug!(
+                        payment = %payment.payment,
+                        order_id = state.order_id,
+                        "close eligible"
+                    );
+                    closeable.push((payment, rent_collector(&state, &authority)));
+                }
+                if settings
+                    .authorization_hold_hours
+                    .is_some_and(|hours| authorization_expired(&state, hours, now))
+                {
+                    expired.push((payment, state.order_id));
+                }
+            }
+
+            *eligible.entry(TaskKind::ClosePayments).or_default() += closeable.len();
+            work.extend(
+                sweep_instructions(&authority, config, &closeable)
+                    .into_iter()
+                    .map(|instruction| Work::new(TaskKind::ClosePayments, instruction)),
+            );
+
+            *eligible.entry(TaskKind::ReleaseAuthorization).or_default() += expired.len();
+            for (payment, order_id) in &expired {
+                let token_program = self.token_program(&payment.mint).await?;
+                work.push(Work {
+                    payment: Some((payment.payment, *order_id)),
+                    ..Work::new(
+                        TaskKind::ReleaseAuthorization,
+                        release_authorization_instruction(
+                            &authority,
+                            config,
+                            payment,
+                            &token_program,
+                        ),
+                    )
+                });
+            }
+
+            if settings.execute_settlement_batches {
+                for (address, batch) in self.source.settlement_batches(&config.address).await? {
+                    if !settlement_batch_ready(&batch, config.settlement.as_ref(), now) {
+                        continue;
+                    }
+                    *eligible
+                        .entry(TaskKind::ExecuteSettlementBatch)
+                        .or_default() += 1;
+                    let settlement_wallet = self.source.settlement_wallet(&config.merchant).await?;
+                    let token_program = self.token_program(&batch.mint).await?;
+                    tracing::debug!(batch = %address, "settlement batch ready");
+                    work.push(Work::new(
+                        TaskKind::ExecuteSettlementBatch,
+                        execute_settlement_batch_instruction(
+                            config,
+                            &address,
+                            &batch.mint,
+                            &settlement_wallet,
+                            &token_program,
+                        ),
+                    ));
+                }
+            }
+        }
+
+        for (task, count) in eligible {
+            self.metrics.set_eligible(&authority, task, count);
+        }
+        Ok(work)
+    }
+
+    async fn token_program(&mut self, mint: &Pubkey) -> Result<Pubkey, SourceError> {
+        if let Some(token_program) = self.token_programs.get(mint) {
+            return Ok(*token_program);
+        }
+        let token_program = self.source.token_program(mint).await?;
+        self.token_programs.insert(*mint, token_program);
+        Ok(token_program)
+    }
+
+    async fn send(&mut self, work: Work) {
+        self.limiter.tick().await;
+
+        let span = tracing::info_span!(
+            "crank",
+            task = work.task.name(),
+            payment = field::Empty,
+            order_id = field::Empty,
+        );
+        if let Some((payment, order_id)) = work.payment {
+            span.record("payment", field::display(payment));
+            span.record("order_id", order_id);
+        }
+
+        let authority = self.operator.authority();
+        let result = send_and_confirm(
+            self.source.rpc(),
+            &[work.instruction],
+            &authority,
+            &[&self.operator.keypair],
+            &BlockhashSource::Latest,
+            &SendConfig::default(),
+        )
+        .instrument(span.clone())
+        .await;
+
+        let _entered = span.enter();
+        let outcome = match result {
+            Ok(signature) => {
+                tracing::info!(%signature, "transaction confirmed");
+                Outcome::Ok
+            }
+            Err(error) => {
+                if matches!(error, SendError::Rpc(_)) {
+                    self.metrics.record_rpc_error(&authority, Stage::Send);
+                }
+                let outcome = match error.program_error().map(|error| error.retry_class()) {
+                    Some(RetryClass::RetryLater) => Outcome::RetryLater,
+                    _ => Outcome::Failed,
+                };
+                tracing::warn!(%error, result = outcome.name(), "transaction failed");
+                outcome
+            }
+        };
+        self.metrics
+            .record_transaction(&authority, work.task, outcome);
+    }
+}
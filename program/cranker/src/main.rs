@@ -0,0 +1,88 @@
+//! `commerce-kit-cranker`: sends the Commerce Program's housekeeping transactions for one or
+//! more operators, so that each operator does not have to write this bot.
+//!
+//! For each operator authority in the operators file it periodically:
+//! - sweeps `Cleared` and `Refunded` payments whose close window has passed, returning their
+//!   rent (`SweepClosedPayments`)
+//! - releases authorizations held longer than the operator's `authorization_hold_hours`
+//!   (`CapturePayment` of 0)
+//! - executes settlement batches their settlement policy allows paying out
+//!   (`ExecuteSettlementBatch`)
+//!
+//! Configuration comes from the environment:
+//! - `COMMERCE_CRANKER_OPERATORS` (required): JSON file of operators, see [`config`]
+//! - `COMMERCE_CRANKER_RPC_URL` (default `http://127.0.0.1:8899`)
+//! - `COMMERCE_CRANKER_METRICS_BIND` (default `127.0.0.1:9464`): where `GET /metrics` is
+//!   served, see [`metrics`]
+//! - `COMMERCE_CRANKER_LOG_FORMAT` (default `text`): `json` for one JSON object per log line;
+//!   `RUST_LOG` filters them (default `info`)
+
+mod config;
+mod crank;
+mod metrics;
+mod source;
+mod tasks;
+
+use std::{env, error::Error, sync::Arc};
+
+use crank::Cranker;
+use metrics::Metrics;
+use source::RpcSource;
+use tokio::task::JoinSet;
+use tracing_subscriber::EnvFilter;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+const DEFAULT_METRICS_BIND: &str = "127.0.0.1:9464";
+
+/// Logs to stdout as text, or as JSON lines when `format` is `json`.
+fn init_tracing(format: Option<&str>) -> Result<(), BoxError> {
+    let logs = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()));
+    match format {
+        None | Some("text") => logs.init(),
+        Some("json") => logs.json().init(),
+        Some(format) => return Err(format!("unknown log format: {format}").into()),
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), BoxError> {
+    init_tracing(env::var("COMMERCE_CRANKER_LOG_FORMAT").ok().as_deref())?;
+    let operators_path = env::var("COMMERCE_CRANKER_OPERATORS")
+        .map_err(|_| "COMMERCE_CRANKER_OPERATORS is not set")?;
+    let rpc_url =
+        env::var("COMMERCE_CRANKER_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+    let metrics_bind = env::var("COMMERCE_CRANKER_METRICS_BIND")
+        .unwrap_or_else(|_| DEFAULT_METRICS_BIND.to_string());
+
+    let operators = config::load_operators(operators_path.as_ref())?;
+    let source = Arc::new(RpcSource::new(rpc_url.clone()));
+    let metrics = Arc::new(Metrics::default());
+    let listener = tokio::net::TcpListener::bind(&metrics_bind).await?;
+    tracing::info!(
+        operators = operators.len(),
+        %rpc_url,
+        %metrics_bind,
+        "commerce-kit-cranker cranking"
+    );
+
+    // Crankers run until the process stops; the metrics server stops it if it fails
+    let mut services: JoinSet<Result<(), BoxError>> = JoinSet::new();
+    let server = axum::serve(listener, metrics::router(Arc::clone(&metrics)));
+    services.spawn(async { server.await.map_err(BoxError::from) });
+    for operator in operators {
+        let cranker = Cranker::new(Arc::clone(&source), Arc::clone(&metrics), operator);
+        services.spawn(async {
+            cranker.run().await;
+            Ok(())
+        });
+    }
+
+    while let Some(result) = services.join_next().await {
+        result??;
+    }
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! Prometheus metrics, served as text at `GET /metrics`.
+//!
+//! - `commerce_cranker_scans_total{operator, result}`: scans of an operator's configs, `ok`
+//!   or `failed`
+//! - `commerce_cranker_eligible{operator, task}`: payments or batches the last scan found
+//!   for each task
+//! - `commerce_cranker_transactions_total{operator, task, result}`: transactions sent, `ok`,
+//!   `retry_later` when the program asked to wait (e.g. `SettlementTooEarly`) or `failed`
+//! - `commerce_cranker_rpc_errors_total{operator, stage}`: RPC failures while scanning
+//!   (`scan`) or sending (`send`)
+
+use std::sync::Arc;
+
+use axum::{extract::State, routing::get, Router};
+use prometheus::{IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder};
+use solana_pubkey::Pubkey;
+
+use crate::tasks::TaskKind;
+
+/// How a sent transaction ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Outcome {
+    Ok,
+    RetryLater,
+    Failed,
+}
+
+impl Outcome {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::RetryLater => "retry_later",
+            Outcome::Failed => "failed",
+        }
+    }
+}
+
+/// Where an RPC failure happened.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Scan,
+    Send,
+}
+
+impl Stage {
+    fn name(&self) -> &'static str {
+        match self {
+            Stage::Scan => "scan",
+            Stage::Send => "send",
+        }
+    }
+}
+
+pub struct Metrics {
+    registry: Registry,
+    scans: IntCounterVec,
+    eligible: IntGaugeVec,
+    transactions: IntCounterVec,
+    rpc_errors: IntCounterVec,
+}
+
+fn counter(registry: &Registry, name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+    let counter = IntCounterVec::new(Opts::new(name, help), labels).expect("valid metric");
+    registry
+        .register(Box::new(counter.clone()))
+        .expect("metric registered once");
+    counter
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        let registry = Registry::new();
+        let eligible = IntGaugeVec::new(
+            Opts::new(
+                "commerce_cranker_eligible",
+                "Items the last scan found per task.",
+            ),
+            &["operator", "task"],
+        )
+        .expect("valid metric");
+        registry
+            .register(Box::new(eligible.clone()))
+            .expect("metric registered once");
+
+        Self {
+            scans: counter(
+                &registry,
+                "commerce_cranker_scans_total",
+                "Scans of an operator's configs.",
+                &["operator", "result"],
+            ),
+            eligible,
+            transactions: counter(
+                &registry,
+                "commerce_cranker_transactions_total",
+                "Transactions sent per task.",
+                &["operator", "task", "result"],
+            ),
+            rpc_errors: counter(
+                &registry,
+                "commerce_cranker_rpc_errors_total",
+                "RPC failures while scanning or sending.",
+                &["operator", "stage"],
+            ),
+            registry,
+        }
+    }
+}
+
+impl Metrics {
+    pub fn record_scan(&self, operator: &Pubkey, ok: bool) {
+        let result = if ok { "ok" } else { "failed" };
+        self.scans
+            .with_label_values(&[&operator.to_string(), result])
+            .inc();
+    }
+
+    pub fn set_eligible(&self, operator: &Pubkey, task: TaskKind, count: usize) {
+        self.eligible
+            .with_label_values(&[&operator.to_string(), task.name()])
+            .set(count as i64);
+    }
+
+    pub fn record_transaction(&self, operator: &Pubkey, task: TaskKind, outcome: Outcome) {
+        self.transactions
+            .with_label_values(&[&operator.to_string(), task.name(), outcome.name()])
+            .inc();
+    }
+
+    pub fn record_rpc_error(&self, operator: &Pubkey, stage: Stage) {
+        self.rpc_errors
+            .with_label_values(&[&operator.to_string(), stage.name()])
+            .inc();
+    }
+
+    /// Metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .expect("metrics encode as text")
+    }
+}
+
+pub fn router(metrics: Arc<Metrics>) -> Router {
+    Router::new()
+        .route("/metrics", get(render))
+        .with_state(metrics)
+}
+
+async fn render(State(metrics): State<Arc<Metrics>>) -> String {
+    metrics.render()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::default();
+        let operator = Pubkey::new_unique();
+        metrics.record_scan(&operator, true);
+        metrics.record_scan(&operator, true);
+        metrics.set_eligible(&operator, TaskKind::ClosePayments, 3);
+        metrics.set_eligible(&operator, TaskKind::ClosePayments, 2);
+        metrics.record_transaction(
+            &operator,
+            TaskKind::ExecuteSettlementBatch,
+            Outcome::RetryLater,
+        );
+        metrics.record_rpc_error(&operator, Stage::Send);
+
+        let text = metrics.render();
+        assert!(text.contains(&format!(
+            "commerce_cranker_scans_total{{operator=\"{operator}\",result=\"ok\"}} 2\n"
+        )));
+        assert!(text.contains(&format!(
+            "commerce_cranker_eligible{{operator=\"{operator}\",task=\"close_payments\"}} 2\n"
+        )));
+        assert!(text.contains(&format!(
+            "commerce_cranker_transactions_total{{operator=\"{operator}\",result=\"retry_later\",task=\"execute_settlement_batch\"}} 1\n"
+        )));
+        assert!(text.contains(&format!(
+            "commerce_cranker_rpc_errors_total{{operator=\"{operator}\",stage=\"send\"}} 1\n"
+        )));
+    }
+}
@@ -0,0 +1,369 @@
+//! Where the cranker reads on-chain state from.
+//!
+//! An operator's configs and their settlement batches are found with `getProgramAccounts`
+//! filters. Payment accounts do not store their config, buyer or mint, so payments are
+//! discovered from the `MakePayment` transactions that touched each config: the first scan
+//! pages through a config's whole history, later scans only its new transactions.
+
+use std::str::FromStr;
+
+use commerce_program_client::{
+    accounts::{Merchant, SettlementBatch},
+    discriminator::{parse_instruction, ParsedInstruction},
+    preview::decode_merchant_operator_config,
+    types::PolicyData,
+    COMMERCE_PROGRAM_ID,
+};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_pubkey::Pubkey;
+use solana_rpc_client_api::{
+    config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig},
+    filter::{Memcmp, RpcFilterType},
+};
+use solana_signature::Signature;
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+    UiTransactionEncoding,
+};
+use thiserror::Error;
+
+use crate::tasks::{CrankedConfig, TrackedPayment};
+
+const MERCHANT_OPERATOR_CONFIG_DISCRIMINATOR: u8 = 2;
+const SETTLEMENT_BATCH_DISCRIMINATOR: u8 = 7;
+
+/// Offset of `operator` in `MerchantOperatorConfig` (discriminator, version, bump, merchant).
+const CONFIG_OPERATOR_OFFSET: usize = 38;
+/// Offset of `merchant_operator_config` in `SettlementBatch` (discriminator).
+const BATCH_CONFIG_OFFSET: usize = 1;
+
+/// `getMultipleAccounts` limit.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum SourceError {
+    #[error("rpc error: {0}")]
+    Rpc(String),
+    #[error("failed to decode account {0}: {1}")]
+    Decode(Pubkey, std::io::Error),
+    #[error("account {0} not found")]
+    NotFound(Pubkey),
+}
+
+impl From<solana_client::client_error::ClientError> for SourceError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(error.to_string())
+    }
+}
+
+pub struct RpcSource {
+    rpc: RpcClient,
+}
+
+impl RpcSource {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed()),
+        }
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    /// Latest block time, which is what the program checks windows against.
+    pub async fn now(&self) -> Result<i64, SourceError> {
+        let slot = self.rpc.get_slot().await?;
+        Ok(self.rpc.get_block_time(slot).await?)
+    }
+
+    async fn program_accounts(
+        &self,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>, SourceError> {
+        let accounts = self
+            .rpc
+            .get_program_accounts_with_config(
+                &COMMERCE_PROGRAM_ID,
+                RpcProgramAccountsConfig {
+                    filters: Some(filters),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        ..RpcAccountInfoConfig::default()
+                    },
+                    ..RpcProgramAccountsConfig::default()
+                },
+            )
+            .await?;
+
+        Ok(accounts
+            .into_iter()
+            .map(|(address, account)| (address, account.data))
+            .collect())
+    }
+
+    /// Configs of the Operator PDA `operator`.
+    pub async fn operator_configs(
+        &self,
+        operator: &Pubkey,
+    ) -> Result<Vec<CrankedConfig>, SourceError> {
+        let accounts = self
+            .program_accounts(vec![
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    0,
+                    vec![MERCHANT_OPERATOR_CONFIG_DISCRIMINATOR],
+                )),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    CONFIG_OPERATOR_OFFSET,
+                    operator.to_bytes().to_vec(),
+                )),
+            ])
+            .await?;
+
+        accounts
+            .into_iter()
+            .map(|(address, data)| {
+                let (config, policies, _) = decode_merchant_operator_config(&data)
+                    .map_err(|e| SourceError::Decode(address, e))?;
+                Ok(CrankedConfig {
+                    address,
+                    merchant: config.merchant,
+                    operator: config.operator,
+                    days_to_close: config.days_to_close,
+                    settlement: policies.into_iter().find_map(|policy| match policy {
+                        PolicyData::Settlement(settlement) => Some(settlement),
+                        _ => None,
+                    }),
+                })
+            })
+            .collect()
+    }
+
+    /// Settlement batches of `config`, one per mint that has one.
+    pub async fn settlement_batches(
+        &self,
+        config: &Pubkey,
+    ) -> Result<Vec<(Pubkey, SettlementBatch)>, SourceError> {
+        let accounts = self
+            .program_accounts(vec![
+                RpcFilterType::DataSize(SettlementBatch::LEN as u64),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    0,
+                    vec![SETTLEMENT_BATCH_DISCRIMINATOR],
+                )),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    BATCH_CONFIG_OFFSET,
+                    config.to_bytes().to_vec(),
+                )),
+            ])
+            .await?;
+
+        accounts
+            .into_iter()
+            .map(|(address, data)| {
+                SettlementBatch::from_bytes(&data)
+                    .map(|batch| (address, batch))
+                    .map_err(|e| SourceError::Decode(address, e))
+            })
+            .collect()
+    }
+
+    /// Settlement wallet stored on the Merchant PDA `merchant`.
+    pub async fn settlement_wallet(&self, merchant: &Pubkey) -> Result<Pubkey, SourceError> {
+        let account = self.rpc.get_account(merchant).await?;
+        Merchant::from_bytes(&account.data)
+            .map(|merchant| merchant.settlement_wallet)
+            .map_err(|e| SourceError::Decode(*merchant, e))
+    }
+
+    /// Token program owning `mint`.
+    pub async fn token_program(&self, mint: &Pubkey) -> Result<Pubkey, SourceError> {
+        self.accounts(&[*mint])
+            .await?
+            .pop()
+            .flatten()
+            .map(|account| account.owner)
+            .ok_or(SourceError::NotFound(*mint))
+    }
+
+    pub async fn accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<solana_account::Account>>, SourceError> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            accounts.extend(self.rpc.get_multiple_accounts(chunk).await?);
+        }
+        Ok(accounts)
+    }
+
+    /// Payments made on `config` by the successful transactions after `until`, with the
+    /// newest transaction seen, to pass as `until` next time.
+    pub async fn made_payments(
+        &self,
+        config: &Pubkey,
+        until: Option<Signature>,
+    ) -> Result<(Vec<TrackedPayment>, Option<Signature>), SourceError> {
+        let mut payments = Vec::new();
+        let mut newest = None;
+        let mut before = None;
+        loop {
+            let signatures = self
+                .rpc
+                .get_signatures_for_address_with_config(
+                    config,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before,
+                        until,
+                        commitment: Some(CommitmentConfig::confirmed()),
+                        ..GetConfirmedSignaturesForAddress2Config::default()
+                    },
+                )
+                .await?;
+            let Some(last) = signatures.last() else {
+                return Ok((payments, newest));
+            };
+            before = Some(parse_signature(&last.signature)?);
+            if newest.is_none() {
+                newest = Some(parse_signature(&signatures[0].signature)?);
+            }
+
+            for status in signatures.iter().filter(|status| status.err.is_none()) {
+                let transaction = self
+                    .rpc
+                    .get_transaction_with_config(
+                        &parse_signature(&status.signature)?,
+                        RpcTransactionConfig {
+                            encoding: Some(UiTransactionEncoding::Base64),
+                            commitment: Some(CommitmentConfig::confirmed()),
+                            max_supported_transaction_version: Some(0),
+                        },
+                    )
+                    .await?;
+                payments.extend(made_payments_in_transaction(&transaction, config));
+            }
+        }
+    }
+}
+
+fn parse_signature(signature: &str) -> Result<Signature, SourceError> {
+    Signature::from_str(signature).map_err(|e| SourceError::Rpc(e.to_string()))
+}
+
+fn made_payments_in_transaction(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    config: &Pubkey,
+) -> Vec<TrackedPayment> {
+    let Some(decoded) = transaction.transaction.transaction.decode() else {
+        return Vec::new();
+    };
+
+    // v0 transactions may load accounts from lookup tables; they follow the static keys
+    let mut account_keys = decoded.message.static_account_keys().to_vec();
+    if let Some(meta) = &transaction.transaction.meta {
+        if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+            account_keys.extend(
+                loaded
+                    .writable
+                    .iter()
+                    .chain(&loaded.readonly)
+                    .filter_map(|key| Pubkey::from_str(key).ok()),
+            );
+        }
+    }
+
+    made_payments_in_instructions(&account_keys, decoded.message.instructions(), config)
+}
+
+/// Decodes the top-level `MakePayment` instructions on `config`. Payments made through CPI
+/// are not found.
+fn made_payments_in_instructions(
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    config: &Pubkey,
+) -> Vec<TrackedPayment> {
+    instructions
+        .iter()
+        .filter(|ix| account_keys.get(ix.program_id_index as usize) == Some(&COMMERCE_PROGRAM_ID))
+        .filter(|ix| {
+            matches!(
+                parse_instruction(&ix.data),
+                Ok(ParsedInstruction::MakePayment(_))
+            )
+        })
+        .filter_map(|ix| {
+            let key = |index: usize| {
+                ix.accounts
+                    .get(index)
+                    .and_then(|i| account_keys.get(*i as usize))
+                    .copied()
+            };
+            if key(6)? != *config {
+                return None;
+            }
+            Some(TrackedPayment {
+                payment: key(1)?,
+                buyer: key(3)?,
+                mint: key(7)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use commerce_program_client::instructions::MakePaymentBuilder;
+    use solana_instruction::Instruction;
+
+    use super::*;
+
+    fn compile(account_keys: &[Pubkey], ix: &Instruction) -> CompiledInstruction {
+        let index = |key: &Pubkey| account_keys.iter().position(|k| k == key).unwrap() as u8;
+        CompiledInstruction {
+            program_id_index: index(&ix.program_id),
+            accounts: ix.accounts.iter().map(|meta| index(&meta.pubkey)).collect(),
+            data: ix.data.clone(),
+        }
+    }
+
+    #[test]
+    fn test_made_payments_in_instructions() {
+        let [payment, buyer, config, mint, other] = std::array::from_fn(|_| Pubkey::new_unique());
+        let ix = MakePaymentBuilder::new()
+            .payer(other)
+            .payment(payment)
+            .operator_authority(other)
+            .buyer(buyer)
+            .operator(other)
+            .merchant(other)
+            .merchant_operator_config(config)
+            .mint(mint)
+            .buyer_ata(other)
+            .escrow_authority(other)
+            .merchant_escrow_ata(other)
+            .merchant_settlement_ata(other)
+            .settlement_wallet(other)
+            .order_id(1)
+            .amount(1)
+            .idempotency_key([0; 16])
+            .instruction();
+        let mut account_keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        account_keys.push(ix.program_id);
+        let compiled = compile(&account_keys, &ix);
+
+        assert_eq!(
+            made_payments_in_instructions(&account_keys, std::slice::from_ref(&compiled), &config),
+            vec![TrackedPayment {
+                payment,
+                buyer,
+                mint
+            }]
+        );
+        assert!(made_payments_in_instructions(&account_keys, &[compiled], &other).is_empty());
+    }
+}
@@ -0,0 +1,390 @@
+//! What the cranker sends, and when.
+//!
+//! - Payments that `ClosePayment` would accept, `Cleared` or `Refunded` once the config's
+//!   `days_to_close` have passed, are swept with `SweepClosedPayments`, up to
+//!   `MAX_SWEEP_PAYMENTS` per transaction. Payments whose rent a treasury paid are swept
+//!   separately with that treasury as the rent collector; the others return their rent to the
+//!   operator authority.
+//! - `Authorized` payments older than the operator's `authorization_hold_hours` are captured
+//!   for 0, which releases the whole hold back to the buyer.
+//! - Settlement batches with a pending amount are executed once the config's settlement
+//!   policy allows it, so auto-settled funds do not wait in escrow for the next clearing.
+//!
+//! The checks mirror the program's so that ineligible transactions are not sent; the cluster
+//! clock they are measured against is the latest block time.
+
+use commerce_program_client::{
+    accounts::{Payment, SettlementBatch},
+    checkout::get_associated_token_address,
+    instructions::{
+        CapturePaymentBuilder, ExecuteSettlementBatchBuilder, SweepClosedPaymentsBuilder,
+    },
+    pdas::find_escrow_authority_pda,
+    sweep::{sweep_remaining_accounts, SweptPayment, MAX_SWEEP_PAYMENTS},
+    types::{SettlementPolicy, Status},
+};
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+const SECONDS_PER_HOUR: i64 = 3_600;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The kinds of transaction the cranker sends, as named in its metrics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskKind {
+    ClosePayments,
+    ReleaseAuthorization,
+    ExecuteSettlementBatch,
+}
+
+impl TaskKind {
+    pub const ALL: [TaskKind; 3] = [
+        TaskKind::ClosePayments,
+        TaskKind::ReleaseAuthorization,
+        TaskKind::ExecuteSettlementBatch,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TaskKind::ClosePayments => "close_payments",
+            TaskKind::ReleaseAuthorization => "release_authorization",
+            TaskKind::ExecuteSettlementBatch => "execute_settlement_batch",
+        }
+    }
+}
+
+/// A config of the cranked operator, with what its tasks need.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrankedConfig {
+    pub address: Pubkey,
+    pub merchant: Pubkey,
+    pub operator: Pubkey,
+    pub days_to_close: u16,
+    pub settlement: Option<SettlementPolicy>,
+}
+
+/// A payment of a config, with the buyer and mint its PDA is derived from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrackedPayment {
+    pub payment: Pubkey,
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+}
+
+/// Whether `ClosePayment` accepts `payment` at `now`.
+pub fn close_eligible(payment: &Payment, days_to_close: u16, now: i64) -> bool {
+    matches!(payment.status, Status::Cleared | Status::Refunded)
+        && now.saturating_sub(payment.created_at) / SECONDS_PER_DAY >= i64::from(days_to_close)
+}
+
+/// Whether `payment` is an authorization that has held the buyer's funds for more than
+/// `hold_hours` at `now`.
+pub fn authorization_expired(payment: &Payment, hold_hours: u32, now: i64) -> bool {
+    payment.status == Status::Authorized
+        && now.saturating_sub(payment.created_at) >= i64::from(hold_hours) * SECONDS_PER_HOUR
+}
+
+/// Whether `ExecuteSettlementBatch` accepts `batch` at `now` under `settlement`.
+pub fn settlement_batch_ready(
+    batch: &SettlementBatch,
+    settlement: Option<&SettlementPolicy>,
+    now: i64,
+) -> bool {
+    if batch.pending_amount == 0 {
+        return false;
+    }
+    let Some(settlement) = settlement else {
+        return true;
+    };
+    batch.pending_amount >= settlement.min_settlement_amount
+        && now.saturating_sub(batch.last_settled_at)
+            >= i64::from(settlement.settlement_frequency_hours) * SECONDS_PER_HOUR
+}
+
+/// Account credited with `payment`'s rent when it is closed.
+pub fn rent_collector(payment: &Payment, operator_authority: &Pubkey) -> Pubkey {
+    if payment.rent_payer == Pubkey::default() {
+        *operator_authority
+    } else {
+        payment.rent_payer
+    }
+}
+
+/// `SweepClosedPayments` instructions closing `payments`, each with its rent collector.
+pub fn sweep_instructions(
+    operator_authority: &Pubkey,
+    config: &CrankedConfig,
+    payments: &[(TrackedPayment, Pubkey)],
+) -> Vec<Instruction> {
+    let mut collectors: Vec<Pubkey> = payments.iter().map(|(_, collector)| *collector).collect();
+    collectors.sort_unstable();
+    collectors.dedup();
+
+    let mut instructions = Vec::new();
+    for collector in collectors {
+        let swept: Vec<SweptPayment> = payments
+            .iter()
+            .filter(|(_, payment_collector)| *payment_collector == collector)
+            .map(|(tracked, _)| SweptPayment {
+                payment: tracked.payment,
+                buyer: tracked.buyer,
+                mint: tracked.mint,
+            })
+            .collect();
+
+        for chunk in swept.chunks(MAX_SWEEP_PAYMENTS) {
+            instructions.push(
+                SweepClosedPaymentsBuilder::new()
+                    .payer(*operator_authority)
+                    .operator_authority(*operator_authority)
+                    .operator(config.operator)
+                    .merchant(config.merchant)
+                    .merchant_operator_config(config.address)
+                    .rent_collector(collector)
+                    .num_payments(chunk.len() as u8)
+                    .add_remaining_accounts(&sweep_remaining_accounts(chunk))
+                    .instruction(),
+            );
+        }
+    }
+    instructions
+}
+
+/// `CapturePayment` of 0 for `payment`, releasing its whole hold to the buyer.
+pub fn release_authorization_instruction(
+    operator_authority: &Pubkey,
+    config: &CrankedConfig,
+    payment: &TrackedPayment,
+    token_program: &Pubkey,
+) -> Instruction {
+    let escrow_authority = find_escrow_authority_pda(&config.address, &payment.mint).0;
+    CapturePaymentBuilder::new()
+        .payer(*operator_authority)
+        .payment(payment.payment)
+        .operator_authority(*operator_authority)
+        .buyer(payment.buyer)
+        .merchant(config.merchant)
+        .operator(config.operator)
+        .merchant_operator_config(config.address)
+        .mint(payment.mint)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(get_associated_token_address(
+            &escrow_authority,
+            &payment.mint,
+            token_program,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &payment.buyer,
+            &payment.mint,
+            token_program,
+        ))
+        .token_program(*token_program)
+        .amount(0)
+        .instruction()
+}
+
+/// `ExecuteSettlementBatch` paying out `batch` to `settlement_wallet`.
+pub fn execute_settlement_batch_instruction(
+    config: &CrankedConfig,
+    batch: &Pubkey,
+    mint: &Pubkey,
+    settlement_wallet: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let escrow_authority = find_escrow_authority_pda(&config.address, mint).0;
+    ExecuteSettlementBatchBuilder::new()
+        .settlement_batch(*batch)
+        .merchant(config.merchant)
+        .merchant_operator_config(config.address)
+        .mint(*mint)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(get_associated_token_address(
+            &escrow_authority,
+            mint,
+            token_program,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            settlement_wallet,
+            mint,
+            token_program,
+        ))
+        .token_program(*token_program)
+        .instruction()
+}
+
+#[cfg(test)]
+mod tests {
+    use commerce_program_client::{
+        checkout::TOKEN_PROGRAM_ID,
+        types::{FeeOverride, FeeType},
+    };
+    use solana_instruction::AccountMeta;
+
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn payment(status: Status, created_at: i64) -> Payment {
+        Payment {
+            discriminator: 3,
+            order_id: 1,
+            amount: 1_000_000,
+            created_at,
+            status,
+            bump: 255,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride {
+                fee: 0,
+                fee_type: FeeType::Bps,
+            },
+            references: [Pubkey::default(); 2],
+            rent_payer: Pubkey::default(),
+            has_order: false,
+        }
+    }
+
+    fn config() -> CrankedConfig {
+        CrankedConfig {
+            address: Pubkey::new_unique(),
+            merchant: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            days_to_close: 7,
+            settlement: None,
+        }
+    }
+
+    fn tracked() -> TrackedPayment {
+        TrackedPayment {
+            payment: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_close_eligible() {
+        let week_ago = NOW - 7 * SECONDS_PER_DAY;
+        assert!(close_eligible(&payment(Status::Cleared, week_ago), 7, NOW));
+        assert!(close_eligible(&payment(Status::Refunded, week_ago), 7, NOW));
+        assert!(!close_eligible(
+            &payment(Status::Cleared, week_ago + 1),
+            7,
+            NOW
+        ));
+        assert!(!close_eligible(&payment(Status::Paid, week_ago), 7, NOW));
+        assert!(!close_eligible(
+            &payment(Status::Authorized, week_ago),
+            7,
+            NOW
+        ));
+    }
+
+    #[test]
+    fn test_authorization_expired() {
+        let day_ago = NOW - 24 * SECONDS_PER_HOUR;
+        assert!(authorization_expired(
+            &payment(Status::Authorized, day_ago),
+            24,
+            NOW
+        ));
+        assert!(!authorization_expired(
+            &payment(Status::Authorized, day_ago + 1),
+            24,
+            NOW
+        ));
+        assert!(!authorization_expired(
+            &payment(Status::Paid, day_ago),
+            24,
+            NOW
+        ));
+    }
+
+    #[test]
+    fn test_settlement_batch_ready() {
+        let batch = SettlementBatch {
+            discriminator: 7,
+            merchant_operator_config: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            bump: 255,
+            pending_amount: 500,
+            pending_count: 1,
+            last_settled_at: NOW - 2 * SECONDS_PER_HOUR,
+        };
+        let policy = |min_settlement_amount, settlement_frequency_hours| SettlementPolicy {
+            min_settlement_amount,
+            settlement_frequency_hours,
+            auto_settle: true,
+        };
+
+        assert!(settlement_batch_ready(&batch, None, NOW));
+        assert!(settlement_batch_ready(&batch, Some(&policy(500, 2)), NOW));
+        assert!(!settlement_batch_ready(&batch, Some(&policy(501, 0)), NOW));
+        assert!(!settlement_batch_ready(&batch, Some(&policy(0, 3)), NOW));
+
+        let empty = SettlementBatch {
+            pending_amount: 0,
+            ..batch
+        };
+        assert!(!settlement_batch_ready(&empty, None, NOW));
+    }
+
+    #[test]
+    fn test_sweep_instructions_group_by_rent_collector() {
+        let operator_authority = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let mut payments: Vec<(TrackedPayment, Pubkey)> = (0..MAX_SWEEP_PAYMENTS + 1)
+            .map(|_| (tracked(), operator_authority))
+            .collect();
+        payments.push((tracked(), treasury));
+
+        let instructions = sweep_instructions(&operator_authority, &config(), &payments);
+        assert_eq!(instructions.len(), 3);
+
+        let collectors: Vec<(Pubkey, usize)> = instructions
+            .iter()
+            .map(|instruction| {
+                (
+                    instruction.accounts[5].pubkey,
+                    (instruction.accounts.len() - 9) / 3,
+                )
+            })
+            .collect();
+        assert!(collectors.contains(&(operator_authority, MAX_SWEEP_PAYMENTS)));
+        assert!(collectors.contains(&(operator_authority, 1)));
+        assert!(collectors.contains(&(treasury, 1)));
+    }
+
+    #[test]
+    fn test_rent_collector() {
+        let operator_authority = Pubkey::new_unique();
+        let mut paid = payment(Status::Cleared, NOW);
+        assert_eq!(
+            rent_collector(&paid, &operator_authority),
+            operator_authority
+        );
+
+        paid.rent_payer = Pubkey::new_unique();
+        assert_eq!(rent_collector(&paid, &operator_authority), paid.rent_payer);
+    }
+
+    #[test]
+    fn test_release_authorization_captures_nothing() {
+        let operator_authority = Pubkey::new_unique();
+        let tracked = tracked();
+        let instruction = release_authorization_instruction(
+            &operator_authority,
+            &config(),
+            &tracked,
+            &TOKEN_PROGRAM_ID,
+        );
+
+        assert!(instruction
+            .accounts
+            .contains(&AccountMeta::new(tracked.payment, false)));
+        assert!(instruction
+            .accounts
+            .contains(&AccountMeta::new_readonly(operator_authority, true)));
+        assert_eq!(instruction.data[1..], 0u64.to_le_bytes());
+    }
+}
@@ -0,0 +1,107 @@
+//! Batch settlement: each clear pays the operator fee immediately but leaves the merchant's
+//! share in escrow on the config's SettlementBatch, and a single ExecuteSettlementBatch
+//! later pays out everything pending in one transfer.
+
+use commerce_kit_examples::{Example, ExampleResult, Scenario};
+use commerce_program_client::{
+    accounts::SettlementBatch,
+    instructions::{
+        ClearPaymentBuilder, ExecuteSettlementBatchBuilder, InitializeSettlementBatchBuilder,
+    },
+    pdas::find_settlement_batch_pda,
+    types::{PolicyData, SettlementPolicy},
+};
+use solana_sdk::{instruction::AccountMeta, signer::Signer};
+
+const NUM_PAYMENTS: u32 = 3;
+const AMOUNT: u64 = 10_000_000; // 10 USDC
+
+#[tokio::main]
+async fn main() -> ExampleResult<()> {
+    let example = Example::connect().await?;
+    let policies = vec![PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: 0,
+        settlement_frequency_hours: 0,
+        auto_settle: false,
+    })];
+    let scenario = example
+        .setup(100, policies, AMOUNT * u64::from(NUM_PAYMENTS))
+        .await?;
+    let accounts = scenario.accounts(0);
+    let merchant_operator_config = scenario.client.merchant_operator_config();
+    let (settlement_batch, bump) =
+        find_settlement_batch_pda(&merchant_operator_config, &scenario.mint);
+
+    let initialize_batch = InitializeSettlementBatchBuilder::new()
+        .payer(example.payer.pubkey())
+        .authority(scenario.merchant_authority.pubkey())
+        .merchant(accounts.merchant())
+        .merchant_operator_config(merchant_operator_config)
+        .mint(scenario.mint)
+        .settlement_batch(settlement_batch)
+        .bump(bump)
+        .instruction();
+    example
+        .send(
+            &format!("Created settlement batch {settlement_batch}"),
+            &[initialize_batch],
+            &[&scenario.merchant_authority],
+        )
+        .await?;
+
+    for order_id in 1..=NUM_PAYMENTS {
+        let checkout = scenario.client.create_checkout(
+            scenario.buyer.pubkey(),
+            scenario.mint,
+            order_id,
+            AMOUNT,
+            Scenario::idempotency_key(order_id),
+        );
+        // Passing the batch defers the merchant's share to it
+        let clear_payment = ClearPaymentBuilder::new()
+            .with_derived_accounts(&scenario.accounts(order_id))
+            .add_remaining_account(AccountMeta::new(settlement_batch, false))
+            .instruction();
+        example
+            .send(
+                &format!(
+                    "Paid and cleared payment {} into the batch",
+                    checkout.payment
+                ),
+                &[checkout.instruction, clear_payment],
+                &[&scenario.buyer],
+            )
+            .await?;
+    }
+
+    let batch =
+        SettlementBatch::from_bytes(&example.rpc.get_account_data(&settlement_batch).await?)?;
+    println!(
+        "Batch holds {} payments, {} base units pending",
+        batch.pending_count, batch.pending_amount
+    );
+
+    // Anyone may execute a batch; the payer does here
+    let execute_batch = ExecuteSettlementBatchBuilder::new()
+        .settlement_batch(settlement_batch)
+        .merchant(accounts.merchant())
+        .merchant_operator_config(merchant_operator_config)
+        .mint(scenario.mint)
+        .escrow_authority(accounts.escrow_authority())
+        .merchant_escrow_ata(accounts.merchant_escrow_ata())
+        .merchant_settlement_ata(accounts.merchant_settlement_ata().expect("wallet is known"))
+        .token_program(accounts.token_program)
+        .instruction();
+    example
+        .send("Executed settlement batch", &[execute_batch], &[])
+        .await?;
+
+    println!(
+        "Merchant settlement balance: {} USDC",
+        example
+            .token_balance(&accounts.merchant_settlement_ata().expect("wallet is known"))
+            .await?
+    );
+
+    Ok(())
+}
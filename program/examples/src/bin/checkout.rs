@@ -0,0 +1,64 @@
+//! E-commerce checkout: the operator records the buyer's order as a payment intent, the
+//! buyer pays it, and the config's auto-settle policy sends the funds straight to the
+//! merchant's settlement wallet, with no clearing step.
+
+use commerce_kit_examples::{Example, ExampleResult, Scenario};
+use commerce_program_client::{
+    accounts::Order,
+    instructions::CreateOrderBuilder,
+    types::{PolicyData, SettlementPolicy},
+};
+use solana_sdk::signer::Signer;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 25_000_000; // 25 USDC
+
+#[tokio::main]
+async fn main() -> ExampleResult<()> {
+    let example = Example::connect().await?;
+    let policies = vec![PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: 0,
+        settlement_frequency_hours: 0,
+        auto_settle: true,
+    })];
+    let scenario = example.setup(100, policies, AMOUNT).await?;
+    let client = scenario.client.track_orders(true);
+    let accounts = scenario.accounts(ORDER_ID);
+
+    // Intent: an Order expecting a single payment
+    let create_order = CreateOrderBuilder::new()
+        .with_derived_accounts(&accounts)
+        .expected_payments(1)
+        .instruction();
+    example
+        .send(&format!("Created order {ORDER_ID}"), &[create_order], &[])
+        .await?;
+
+    // Pay: signed by the buyer, recorded on the order and settled in the same instruction
+    let checkout = client.create_checkout(
+        scenario.buyer.pubkey(),
+        scenario.mint,
+        ORDER_ID,
+        AMOUNT,
+        Scenario::idempotency_key(ORDER_ID),
+    );
+    example
+        .send(
+            &format!("Paid payment {}", checkout.payment),
+            &[checkout.instruction],
+            &[&scenario.buyer],
+        )
+        .await?;
+
+    let order = checkout.order.expect("orders are tracked");
+    let order_account = Order::from_bytes(&example.rpc.get_account_data(&order).await?)?;
+    println!("Order {order} is {:?}", order_account.status);
+    println!(
+        "Merchant settlement balance: {} USDC",
+        example
+            .token_balance(&accounts.merchant_settlement_ata().expect("wallet is known"))
+            .await?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! Escrow with manual clear: the buyer's payment is held in the config's escrow until the
+//! operator clears it, e.g. once the goods have shipped, which pays the operator fee and
+//! the rest to the merchant.
+
+use commerce_kit_examples::{Example, ExampleResult, Scenario};
+use commerce_program_client::{
+    instructions::ClearPaymentBuilder,
+    types::{PolicyData, SettlementPolicy},
+};
+use solana_sdk::signer::Signer;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 40_000_000; // 40 USDC
+const OPERATOR_FEE_BPS: u64 = 250; // 2.5%
+
+#[tokio::main]
+async fn main() -> ExampleResult<()> {
+    let example = Example::connect().await?;
+    let policies = vec![PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: 0,
+        settlement_frequency_hours: 0,
+        auto_settle: false,
+    })];
+    let scenario = example.setup(OPERATOR_FEE_BPS, policies, AMOUNT).await?;
+    let accounts = scenario.accounts(ORDER_ID);
+
+    let checkout = scenario.client.create_checkout(
+        scenario.buyer.pubkey(),
+        scenario.mint,
+        ORDER_ID,
+        AMOUNT,
+        Scenario::idempotency_key(ORDER_ID),
+    );
+    example
+        .send(
+            &format!("Paid payment {} into escrow", checkout.payment),
+            &[checkout.instruction],
+            &[&scenario.buyer],
+        )
+        .await?;
+    println!(
+        "Escrow balance: {} USDC",
+        example
+            .token_balance(&accounts.merchant_escrow_ata())
+            .await?
+    );
+
+    let clear_payment = ClearPaymentBuilder::new()
+        .with_derived_accounts(&accounts)
+        .instruction();
+    example
+        .send("Cleared payment", &[clear_payment], &[])
+        .await?;
+
+    println!(
+        "Escrow balance: {} USDC",
+        example
+            .token_balance(&accounts.merchant_escrow_ata())
+            .await?
+    );
+    println!(
+        "Merchant settlement balance: {} USDC",
+        example
+            .token_balance(&accounts.merchant_settlement_ata().expect("wallet is known"))
+            .await?
+    );
+    println!(
+        "Operator fee balance: {} USDC",
+        example
+            .token_balance(&accounts.operator_settlement_ata())
+            .await?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Refund flow: the buyer pays into escrow, then the operator refunds the payment within
+//! the config's refund policy, returning the full amount to the buyer's token account.
+
+use commerce_kit_examples::{Example, ExampleResult, Scenario};
+use commerce_program_client::types::{PolicyData, RefundPolicy};
+use solana_sdk::signer::Signer;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 15_000_000; // 15 USDC
+const REFUND_WINDOW_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+#[tokio::main]
+async fn main() -> ExampleResult<()> {
+    let example = Example::connect().await?;
+    let policies = vec![PolicyData::Refund(RefundPolicy {
+        max_amount: AMOUNT,
+        max_time_after_purchase: REFUND_WINDOW_SECONDS,
+    })];
+    let scenario = example.setup(100, policies, AMOUNT).await?;
+    let accounts = scenario.accounts(ORDER_ID);
+
+    let checkout = scenario.client.create_checkout(
+        scenario.buyer.pubkey(),
+        scenario.mint,
+        ORDER_ID,
+        AMOUNT,
+        Scenario::idempotency_key(ORDER_ID),
+    );
+    example
+        .send(
+            &format!("Paid payment {} into escrow", checkout.payment),
+            &[checkout.instruction],
+            &[&scenario.buyer],
+        )
+        .await?;
+    println!(
+        "Buyer balance: {} USDC",
+        example.token_balance(&accounts.buyer_ata()).await?
+    );
+
+    let refund = scenario
+        .client
+        .refund_order(scenario.buyer.pubkey(), scenario.mint, ORDER_ID);
+    example.send("Refunded payment", &[refund], &[]).await?;
+    println!(
+        "Buyer balance: {} USDC",
+        example.token_balance(&accounts.buyer_ata()).await?
+    );
+
+    Ok(())
+}
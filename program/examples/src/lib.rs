@@ -0,0 +1,393 @@
+//! Runnable end-to-end scenarios for new integrators.
+//!
+//! Each binary runs one payment flow against a local validator with the commerce program
+//! deployed, using the Rust SDK the way an operator backend would:
+//!
+//! | Binary | Scenario |
+//! |--------|----------|
+//! | `checkout` | Order intent, payment and auto-settlement to the merchant |
+//! | `escrow` | Payment held in escrow until the operator clears it |
+//! | `refund` | Payment held in escrow, then refunded to the buyer |
+//! | `batch-settlement` | Several cleared payments settled in one transfer |
+//!
+//! ```text
+//! cargo run -p commerce-kit-examples --bin <NAME> -- [--url <RPC_URL>] [--keypair <PATH>]
+//! ```
+//!
+//! [`Example::connect`] defaults to `http://127.0.0.1:8899` and `~/.config/solana/id.json`,
+//! and [`Example::setup`] creates what every scenario starts from: an operator owned by the
+//! keypair, a fresh merchant whose config has the scenario's policies, a 6-decimal test USDC
+//! mint and a funded buyer. Every transaction is sent with `send_and_confirm` and printed
+//! with its explorer link.
+
+use std::{env, error::Error, path::PathBuf};
+
+use commerce_program_client::{
+    checkout::{create_associated_token_account_idempotent, TOKEN_PROGRAM_ID},
+    commerce_client::CommerceClient,
+    derived_accounts::DerivedAccounts,
+    idempotency::IdempotencyKey,
+    instructions::{
+        CreateOperatorBuilder, InitializeMerchantBuilder, InitializeMerchantOperatorConfigBuilder,
+    },
+    pdas::{find_merchant_operator_config_pda, find_merchant_pda, find_operator_pda},
+    send::{send_and_confirm, BlockhashSource, SendConfig},
+    types::{FeeType, PolicyData},
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_program_pack::Pack;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature},
+    signer::Signer,
+    system_instruction,
+};
+
+pub type ExampleResult<T> = Result<T, Box<dyn Error>>;
+
+pub const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+
+/// Decimals of the test USDC mint.
+pub const TEST_USDC_DECIMALS: u8 = 6;
+
+const EXPLORER_URL: &str = "https://explorer.solana.com";
+const MIN_PAYER_BALANCE: u64 = LAMPORTS_PER_SOL;
+const AIRDROP_AMOUNT: u64 = 2 * LAMPORTS_PER_SOL;
+
+/// Solana Explorer links for the cluster behind an RPC URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explorer {
+    rpc_url: String,
+}
+
+impl Explorer {
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+        }
+    }
+
+    pub fn tx(&self, signature: &Signature) -> String {
+        self.link(&format!("tx/{signature}"))
+    }
+
+    pub fn address(&self, address: &Pubkey) -> String {
+        self.link(&format!("address/{address}"))
+    }
+
+    /// Link to `path` on the public cluster the RPC URL points at, or on the explorer's
+    /// custom cluster for any other endpoint such as a local validator.
+    fn link(&self, path: &str) -> String {
+        let cluster = ["devnet", "testnet", "mainnet"]
+            .into_iter()
+            .find(|cluster| self.rpc_url.contains(cluster));
+        match cluster {
+            Some("mainnet") => format!("{EXPLORER_URL}/{path}"),
+            Some(cluster) => format!("{EXPLORER_URL}/{path}?cluster={cluster}"),
+            None => format!(
+                "{EXPLORER_URL}/{path}?cluster=custom&customUrl={}",
+                percent_encode(&self.rpc_url)
+            ),
+        }
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+struct Args {
+    url: String,
+    keypair: PathBuf,
+}
+
+fn parse_args() -> ExampleResult<Args> {
+    let mut args = Args {
+        url: DEFAULT_RPC_URL.to_string(),
+        keypair: PathBuf::from(env::var("HOME")?).join(".config/solana/id.json"),
+    };
+
+    let mut iter = env::args().skip(1);
+    while let Some(flag) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {flag}"));
+        match flag.as_str() {
+            "--url" | "-u" => args.url = value()?,
+            "--keypair" | "-k" => args.keypair = PathBuf::from(value()?),
+            _ => return Err(format!("unknown argument {flag}").into()),
+        }
+    }
+
+    Ok(args)
+}
+
+/// Connection and payer of an example run. The payer is also the operator authority.
+pub struct Example {
+    pub rpc: RpcClient,
+    pub payer: Keypair,
+    pub explorer: Explorer,
+}
+
+/// What [`Example::setup`] creates. The merchant, settlement wallet and buyer are generated
+/// on every run, so scenarios never collide with earlier ones.
+pub struct Scenario {
+    /// Client of the merchant's config with the payer's operator
+    pub client: CommerceClient,
+    pub merchant_authority: Keypair,
+    pub settlement_wallet: Keypair,
+    pub buyer: Keypair,
+    /// Test USDC mint; the payer is its mint authority
+    pub mint: Pubkey,
+}
+
+impl Scenario {
+    /// Accounts of the buyer's payment for order `order_id`.
+    pub fn accounts(&self, order_id: u32) -> DerivedAccounts {
+        self.client
+            .accounts(self.buyer.pubkey(), self.mint, order_id)
+    }
+
+    /// Idempotency key of the checkout of order `order_id`.
+    pub fn idempotency_key(order_id: u32) -> IdempotencyKey {
+        let mut idempotency_key = [0u8; 16];
+        idempotency_key[..4].copy_from_slice(&order_id.to_le_bytes());
+        idempotency_key
+    }
+}
+
+impl Example {
+    /// Connects to `--url` as `--keypair`, requesting an airdrop when the keypair has less
+    /// than 1 SOL.
+    pub async fn connect() -> ExampleResult<Self> {
+        let args = parse_args()?;
+        let payer = read_keypair_file(&args.keypair)
+            .map_err(|e| format!("failed to read keypair {}: {e}", args.keypair.display()))?;
+        let rpc = RpcClient::new_with_commitment(args.url.clone(), CommitmentConfig::confirmed());
+
+        if rpc.get_balance(&payer.pubkey()).await? < MIN_PAYER_BALANCE {
+            let signature = rpc.request_airdrop(&payer.pubkey(), AIRDROP_AMOUNT).await?;
+            rpc.poll_for_signature(&signature).await?;
+        }
+
+        Ok(Self {
+            rpc,
+            payer,
+            explorer: Explorer::new(args.url),
+        })
+    }
+
+    /// Sends `instructions`, signed by the payer and `signers`, and prints `step` with the
+    /// transaction's explorer link.
+    pub async fn send(
+        &self,
+        step: &str,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> ExampleResult<Signature> {
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+
+        let signature = send_and_confirm(
+            &self.rpc,
+            instructions,
+            &self.payer.pubkey(),
+            &all_signers,
+            &BlockhashSource::Latest,
+            &SendConfig::default(),
+        )
+        .await?;
+        println!("{step}\n  {}", self.explorer.tx(&signature));
+        Ok(signature)
+    }
+
+    /// Balance of a token account, in whole tokens.
+    pub async fn token_balance(&self, token_account: &Pubkey) -> ExampleResult<String> {
+        Ok(self
+            .rpc
+            .get_token_account_balance(token_account)
+            .await?
+            .ui_amount_string)
+    }
+
+    /// Creates the payer's operator if needed, a test USDC mint with `buyer_funding` minted
+    /// to a new buyer, and a new merchant with a config charging `operator_fee_bps` under
+    /// `policies`. The merchant's and operator's settlement ATAs are created up front.
+    pub async fn setup(
+        &self,
+        operator_fee_bps: u64,
+        policies: Vec<PolicyData>,
+        buyer_funding: u64,
+    ) -> ExampleResult<Scenario> {
+        let merchant_authority = Keypair::new();
+        let settlement_wallet = Keypair::new();
+        let buyer = Keypair::new();
+        let mint = Keypair::new();
+
+        let client = CommerceClient::new(
+            merchant_authority.pubkey(),
+            self.payer.pubkey(),
+            settlement_wallet.pubkey(),
+        );
+        let accounts = client.accounts(buyer.pubkey(), mint.pubkey(), 0);
+
+        // Operator
+        let (operator, _) = find_operator_pda(&self.payer.pubkey());
+        if self
+            .rpc
+            .get_account_with_commitment(&operator, self.rpc.commitment())
+            .await?
+            .value
+            .is_none()
+        {
+            let instruction = CreateOperatorBuilder::new()
+                .payer(self.payer.pubkey())
+                .operator(operator)
+                .authority(self.payer.pubkey())
+                .instruction();
+            self.send(&format!("Created operator {operator}"), &[instruction], &[])
+                .await?;
+        }
+
+        // Test USDC mint and funded buyer
+        let mint_rent = self
+            .rpc
+            .get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)
+            .await?;
+        self.send(
+            &format!(
+                "Created test USDC mint {} and funded buyer {}",
+                mint.pubkey(),
+                buyer.pubkey()
+            ),
+            &[
+                system_instruction::create_account(
+                    &self.payer.pubkey(),
+                    &mint.pubkey(),
+                    mint_rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &TOKEN_PROGRAM_ID,
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &TOKEN_PROGRAM_ID,
+                    &mint.pubkey(),
+                    &self.payer.pubkey(),
+                    None,
+                    TEST_USDC_DECIMALS,
+                )?,
+                create_associated_token_account_idempotent(
+                    &self.payer.pubkey(),
+                    &buyer.pubkey(),
+                    &mint.pubkey(),
+                    &TOKEN_PROGRAM_ID,
+                ),
+                spl_token::instruction::mint_to(
+                    &TOKEN_PROGRAM_ID,
+                    &mint.pubkey(),
+                    &accounts.buyer_ata(),
+                    &self.payer.pubkey(),
+                    &[],
+                    buyer_funding,
+                )?,
+            ],
+            &[&mint],
+        )
+        .await?;
+
+        // Merchant, config and settlement ATAs
+        let (merchant, merchant_bump) = find_merchant_pda(&merchant_authority.pubkey());
+        let (merchant_operator_config, config_bump) =
+            find_merchant_operator_config_pda(&merchant, &operator, client.config_version);
+
+        let initialize_merchant = InitializeMerchantBuilder::new()
+            .payer(self.payer.pubkey())
+            .authority(merchant_authority.pubkey())
+            .merchant(merchant)
+            .settlement_wallet(settlement_wallet.pubkey())
+            .bump(merchant_bump)
+            .settlement_wallet_timelock_seconds(0)
+            .instruction();
+
+        let initialize_config = InitializeMerchantOperatorConfigBuilder::new()
+            .payer(self.payer.pubkey())
+            .authority(merchant_authority.pubkey())
+            .merchant(merchant)
+            .operator(operator)
+            .config(merchant_operator_config)
+            .version(client.config_version)
+            .bump(config_bump)
+            .operator_fee(operator_fee_bps)
+            .fee_type(FeeType::Bps)
+            .days_to_close(7)
+            .policies(policies)
+            .accepted_currencies(vec![mint.pubkey()])
+            .add_remaining_account(AccountMeta::new_readonly(mint.pubkey(), false))
+            .instruction();
+
+        self.send(
+            &format!("Created merchant {merchant} with config {merchant_operator_config}"),
+            &[
+                initialize_merchant,
+                initialize_config,
+                create_associated_token_account_idempotent(
+                    &self.payer.pubkey(),
+                    &settlement_wallet.pubkey(),
+                    &mint.pubkey(),
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_associated_token_account_idempotent(
+                    &self.payer.pubkey(),
+                    &self.payer.pubkey(),
+                    &mint.pubkey(),
+                    &TOKEN_PROGRAM_ID,
+                ),
+            ],
+            &[&merchant_authority],
+        )
+        .await?;
+        println!("  {}", self.explorer.address(&merchant_operator_config));
+
+        Ok(Scenario {
+            client,
+            merchant_authority,
+            settlement_wallet,
+            buyer,
+            mint: mint.pubkey(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explorer_links() {
+        let signature = Signature::from([7; 64]);
+        let address = Pubkey::new_from_array([9; 32]);
+
+        assert_eq!(
+            Explorer::new(DEFAULT_RPC_URL).tx(&signature),
+            format!(
+                "https://explorer.solana.com/tx/{signature}?cluster=custom&customUrl=http%3A%2F%2F127.0.0.1%3A8899"
+            )
+        );
+        assert_eq!(
+            Explorer::new("https://api.devnet.solana.com").address(&address),
+            format!("https://explorer.solana.com/address/{address}?cluster=devnet")
+        );
+        assert_eq!(
+            Explorer::new("https://api.mainnet-beta.solana.com").tx(&signature),
+            format!("https://explorer.solana.com/tx/{signature}")
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Fee calculation cases shared by the program and the Rust client.
+//!
+//! `ClearPayment` splits a payment into the operator fee (from the config's fee or the
+//! matching `FeeTierPolicy` tier), the `PlatformFeePolicy` fee and the merchant amount, which
+//! a `SplitSettlementPolicy` then divides between its destinations. The program's
+//! `calculate_fees` tests and the client's `FeeCalculator` tests both run every case in
+//! [`FEE_CASES`], so the two implementations can't drift apart without one of them failing.
+//!
+//! The cases only use plain integers, so neither side's types leak into the other.
+
+#![no_std]
+
+/// A fee as configured on a config, fee tier or platform fee policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fee {
+    /// Basis points of the payment amount.
+    Bps(u64),
+    /// Fixed amount in the payment mint.
+    Fixed(u64),
+}
+
+/// One tier of a `FeeTierPolicy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeTierCase {
+    pub threshold: u64,
+    pub fee: Fee,
+}
+
+/// What `ClearPayment` pays out for a case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeOutcome {
+    pub operator_fee: u64,
+    pub platform_fee: u64,
+    pub merchant_amount: u64,
+    /// The merchant amount split by the split settlement shares, empty without them.
+    pub split_amounts: &'static [u64],
+}
+
+/// A payment amount and the fee policies it's cleared under.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeCase {
+    pub name: &'static str,
+    pub amount: u64,
+    /// The config's `operator_fee` and `fee_type`.
+    pub operator_fee: Fee,
+    /// Tiers of a `FeeTierPolicy`, in ascending threshold order; no policy when empty.
+    pub fee_tiers: &'static [FeeTierCase],
+    pub platform_fee: Option<Fee>,
+    /// Shares of a `SplitSettlementPolicy`; no policy when empty.
+    pub split_shares_bps: &'static [u16],
+    /// `None` when the fees exceed the amount and the program fails with `ArithmeticOverflow`.
+    pub expected: Option<FeeOutcome>,
+}
+
+impl FeeCase {
+    const fn new(name: &'static str, amount: u64, operator_fee: Fee) -> Self {
+        Self {
+            name,
+            amount,
+            operator_fee,
+            fee_tiers: &[],
+            platform_fee: None,
+            split_shares_bps: &[],
+            expected: None,
+        }
+    }
+
+    const fn fee_tiers(self, fee_tiers: &'static [FeeTierCase]) -> Self {
+        Self { fee_tiers, ..self }
+    }
+
+    const fn platform_fee(self, platform_fee: Fee) -> Self {
+        Self {
+            platform_fee: Some(platform_fee),
+            ..self
+        }
+    }
+
+    const fn split_shares_bps(self, split_shares_bps: &'static [u16]) -> Self {
+        Self {
+            split_shares_bps,
+            ..self
+        }
+    }
+
+    const fn pays(self, operator_fee: u64, platform_fee: u64, merchant_amount: u64) -> Self {
+        self.pays_split(operator_fee, platform_fee, merchant_amount, &[])
+    }
+
+    const fn pays_split(
+        self,
+        operator_fee: u64,
+        platform_fee: u64,
+        merchant_amount: u64,
+        split_amounts: &'static [u64],
+    ) -> Self {
+        Self {
+            expected: Some(FeeOutcome {
+                operator_fee,
+                platform_fee,
+                merchant_amount,
+                split_amounts,
+            }),
+            ..self
+        }
+    }
+}
+
+const FEE_TIERS: &[FeeTierCase] = &[
+    FeeTierCase {
+        threshold: 0,
+        fee: Fee::Bps(300),
+    },
+    FeeTierCase {
+        threshold: 10_000,
+        fee: Fee::Bps(200),
+    },
+    FeeTierCase {
+        threshold: 100_000,
+        fee: Fee::Fixed(1_000),
+    },
+];
+
+pub const FEE_CASES: &[FeeCase] = &[
+    FeeCase::new("bps", 10_000, Fee::Bps(250)).pays(250, 0, 9_750),
+    FeeCase::new("bps rounds down", 999, Fee::Bps(1)).pays(0, 0, 999),
+    FeeCase::new("bps zero fee", 1_000, Fee::Bps(0)).pays(0, 0, 1_000),
+    FeeCase::new("bps zero amount", 0, Fee::Bps(250)).pays(0, 0, 0),
+    FeeCase::new("bps whole amount", 1_000, Fee::Bps(10_000)).pays(1_000, 0, 0),
+    FeeCase::new("bps max amount", u64::MAX, Fee::Bps(250)).pays(
+        461_168_601_842_738_790,
+        0,
+        17_985_575_471_866_812_825,
+    ),
+    // A fee above MAX_BPS can't take more than the payment
+    FeeCase::new("bps above max", 1_000, Fee::Bps(20_000)),
+    FeeCase::new("fixed", 1_000, Fee::Fixed(100)).pays(100, 0, 900),
+    FeeCase::new("fixed capped", 500, Fee::Fixed(1_000)).pays(500, 0, 0),
+    FeeCase::new("first tier", 5_000, Fee::Bps(500))
+        .fee_tiers(FEE_TIERS)
+        .pays(150, 0, 4_850),
+    FeeCase::new("tier at threshold", 10_000, Fee::Bps(500))
+        .fee_tiers(FEE_TIERS)
+        .pays(200, 0, 9_800),
+    FeeCase::new("fixed tier", 1_000_000, Fee::Bps(500))
+        .fee_tiers(FEE_TIERS)
+        .pays(1_000, 0, 999_000),
+    FeeCase::new("below every tier", 500, Fee::Bps(250))
+        .fee_tiers(&[FeeTierCase {
+            threshold: 1_000,
+            fee: Fee::Bps(100),
+        }])
+        .pays(12, 0, 488),
+    FeeCase::new("platform bps", 1_000_000, Fee::Bps(500))
+        .platform_fee(Fee::Bps(200))
+        .pays(50_000, 20_000, 930_000),
+    // The platform fee is capped at what the operator fee leaves
+    FeeCase::new("platform capped", 1_000, Fee::Fixed(900))
+        .platform_fee(Fee::Fixed(500))
+        .pays(900, 100, 0),
+    // Shares round down and the remainder goes to the first destination
+    FeeCase::new("split remainder", 1_000, Fee::Bps(250))
+        .split_shares_bps(&[5_000, 3_000, 2_000])
+        .pays_split(25, 0, 975, &[488, 292, 195]),
+    FeeCase::new("tier, platform and split", 100_000, Fee::Bps(500))
+        .fee_tiers(FEE_TIERS)
+        .platform_fee(Fee::Bps(100))
+        .split_shares_bps(&[3_333, 3_333, 3_334])
+        .pays_split(1_000, 1_000, 98_000, &[32_664, 32_663, 32_673]),
+];
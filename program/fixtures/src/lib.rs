@@ -0,0 +1,481 @@
+//! Demo environment provisioning for the Commerce Program.
+//!
+//! [`provision`] creates everything an integrator needs to try the program against a
+//! localnet or devnet RPC endpoint: an operator owned by the payer, a fresh merchant and
+//! settlement wallet, a merchant operator config with refund and settlement policies, a
+//! 6-decimal test USDC mint, a funded buyer and a batch of payments in each status.
+//!
+//! `Closed` payments are not provisioned because closing waits for the config's
+//! `days_to_close` to elapse.
+
+use commerce_program_client::{
+    checkout::{
+        create_associated_token_account_idempotent, get_associated_token_address, TOKEN_PROGRAM_ID,
+    },
+    instructions::{
+        ClearPaymentBuilder, CreateOperatorBuilder, InitializeMerchantBuilder,
+        InitializeMerchantOperatorConfigBuilder, MakePaymentBuilder, RefundPaymentBuilder,
+    },
+    pdas::{
+        find_escrow_authority_pda, find_merchant_operator_config_pda, find_merchant_pda,
+        find_operator_pda, find_payment_pda,
+    },
+    types::{FeeType, PolicyData, RefundPolicy, SettlementPolicy, Status},
+};
+use serde_json::{json, Value};
+use solana_client::{client_error::ClientError, rpc_client::RpcClient};
+use solana_program_pack::Pack;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction,
+    transaction::Transaction,
+};
+use thiserror::Error;
+
+/// Decimals of the test USDC mint.
+pub const TEST_USDC_DECIMALS: u8 = 6;
+
+const CONFIG_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum FixtureError {
+    #[error("rpc error: {0}")]
+    Rpc(#[from] Box<ClientError>),
+    #[error("token instruction error: {0}")]
+    Token(#[from] solana_sdk::program_error::ProgramError),
+}
+
+impl From<ClientError> for FixtureError {
+    fn from(error: ClientError) -> Self {
+        Self::Rpc(Box::new(error))
+    }
+}
+
+/// What [`provision`] creates.
+#[derive(Clone, Debug)]
+pub struct FixtureConfig {
+    /// Payments created in each of the `Paid`, `Cleared` and `Refunded` statuses.
+    pub payments_per_status: u32,
+    /// Amount of every payment, in test USDC base units.
+    pub payment_amount: u64,
+    /// Operator fee in basis points, charged when a payment is cleared.
+    pub operator_fee_bps: u64,
+    pub days_to_close: u16,
+    /// `RefundPolicy::max_time_after_purchase`.
+    pub refund_window_seconds: u64,
+}
+
+impl Default for FixtureConfig {
+    fn default() -> Self {
+        Self {
+            payments_per_status: 3,
+            payment_amount: 10_000_000, // 10 USDC
+            operator_fee_bps: 100,      // 1%
+            days_to_close: 7,
+            refund_window_seconds: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+impl FixtureConfig {
+    /// Refund and settlement policies of the provisioned config.
+    pub fn policies(&self) -> Vec<PolicyData> {
+        vec![
+            PolicyData::Refund(RefundPolicy {
+                max_amount: self.payment_amount,
+                max_time_after_purchase: self.refund_window_seconds,
+            }),
+            PolicyData::Settlement(SettlementPolicy {
+                min_settlement_amount: 0,
+                settlement_frequency_hours: 0,
+                auto_settle: false,
+            }),
+        ]
+    }
+
+    /// Order id and final status of every provisioned payment. Order ids start at 1.
+    pub fn payment_plan(&self) -> Vec<(u32, Status)> {
+        [Status::Paid, Status::Cleared, Status::Refunded]
+            .into_iter()
+            .flat_map(|status| (0..self.payments_per_status).map(move |_| status))
+            .enumerate()
+            .map(|(i, status)| (i as u32 + 1, status))
+            .collect()
+    }
+
+    /// Test USDC minted to the buyer: enough for every planned payment.
+    pub fn buyer_funding(&self) -> u64 {
+        self.payment_amount * self.payment_plan().len() as u64
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixturePayment {
+    pub address: Pubkey,
+    pub order_id: u32,
+    pub amount: u64,
+    pub status: Status,
+}
+
+/// A provisioned environment. Keypairs are generated, so they are returned for later use.
+#[derive(Debug)]
+pub struct Fixtures {
+    pub operator_authority: Pubkey,
+    pub operator: Pubkey,
+    pub merchant_authority: Keypair,
+    pub merchant: Pubkey,
+    pub settlement_wallet: Keypair,
+    pub merchant_operator_config: Pubkey,
+    /// Test USDC mint; the payer is its mint authority.
+    pub mint: Pubkey,
+    pub buyer: Keypair,
+    pub payments: Vec<FixturePayment>,
+}
+
+impl Fixtures {
+    /// Summary with addresses and base58 secret keys of the generated keypairs.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "operator_authority": self.operator_authority.to_string(),
+            "operator": self.operator.to_string(),
+            "merchant_authority": keypair_json(&self.merchant_authority),
+            "merchant": self.merchant.to_string(),
+            "settlement_wallet": keypair_json(&self.settlement_wallet),
+            "merchant_operator_config": self.merchant_operator_config.to_string(),
+            "mint": self.mint.to_string(),
+            "buyer": keypair_json(&self.buyer),
+            "payments": self.payments.iter().map(|payment| json!({
+                "address": payment.address.to_string(),
+                "order_id": payment.order_id,
+                "amount": payment.amount,
+                "status": format!("{:?}", payment.status),
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn keypair_json(keypair: &Keypair) -> Value {
+    json!({
+        "pubkey": keypair.pubkey().to_string(),
+        "secret_key": keypair.to_base58_string(),
+    })
+}
+
+fn send(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<Signature, FixtureError> {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        rpc.get_latest_blockhash()?,
+    );
+    Ok(rpc.send_and_confirm_transaction(&transaction)?)
+}
+
+/// Provisions a demo environment, paying for every account and transaction with `payer`.
+///
+/// The operator belongs to `payer` and is reused if it already exists; everything else is
+/// created fresh on each call.
+pub fn provision(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    config: &FixtureConfig,
+) -> Result<Fixtures, FixtureError> {
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+    let mint = Keypair::new();
+
+    // Operator
+    let (operator, _) = find_operator_pda(&payer.pubkey());
+    if rpc
+        .get_account_with_commitment(&operator, rpc.commitment())?
+        .value
+        .is_none()
+    {
+        let instruction = CreateOperatorBuilder::new()
+            .payer(payer.pubkey())
+            .operator(operator)
+            .authority(payer.pubkey())
+            .instruction();
+        send(rpc, payer, &[instruction], &[])?;
+    }
+
+    // Test USDC mint and funded buyer
+    let buyer_ata =
+        get_associated_token_address(&buyer.pubkey(), &mint.pubkey(), &TOKEN_PROGRAM_ID);
+    let mint_rent = rpc.get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)?;
+    send(
+        rpc,
+        payer,
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                mint_rent,
+                spl_token::state::Mint::LEN as u64,
+                &TOKEN_PROGRAM_ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &TOKEN_PROGRAM_ID,
+                &mint.pubkey(),
+                &payer.pubkey(),
+                None,
+                TEST_USDC_DECIMALS,
+            )?,
+            create_associated_token_account_idempotent(
+                &payer.pubkey(),
+                &buyer.pubkey(),
+                &mint.pubkey(),
+                &TOKEN_PROGRAM_ID,
+            ),
+            spl_token::instruction::mint_to(
+                &TOKEN_PROGRAM_ID,
+                &mint.pubkey(),
+                &buyer_ata,
+                &payer.pubkey(),
+                &[],
+                config.buyer_funding(),
+            )?,
+        ],
+        &[&mint],
+    )?;
+
+    // Merchant, config and the settlement ATA cleared payments are paid into
+    let (merchant, merchant_bump) = find_merchant_pda(&merchant_authority.pubkey());
+    let (merchant_operator_config, config_bump) =
+        find_merchant_operator_config_pda(&merchant, &operator, CONFIG_VERSION);
+
+    let initialize_merchant = InitializeMerchantBuilder::new()
+        .payer(payer.pubkey())
+        .authority(merchant_authority.pubkey())
+        .merchant(merchant)
+        .settlement_wallet(settlement_wallet.pubkey())
+        .bump(merchant_bump)
+        .settlement_wallet_timelock_seconds(0)
+        .instruction();
+
+    let initialize_config = InitializeMerchantOperatorConfigBuilder::new()
+        .payer(payer.pubkey())
+        .authority(merchant_authority.pubkey())
+        .merchant(merchant)
+        .operator(operator)
+        .config(merchant_operator_config)
+        .version(CONFIG_VERSION)
+        .bump(config_bump)
+        .operator_fee(config.operator_fee_bps)
+        .fee_type(FeeType::Bps)
+        .days_to_close(config.days_to_close)
+        .policies(config.policies())
+        .accepted_currencies(vec![mint.pubkey()])
+        .add_remaining_account(AccountMeta::new_readonly(mint.pubkey(), false))
+        .instruction();
+
+    send(
+        rpc,
+        payer,
+        &[
+            initialize_merchant,
+            initialize_config,
+            create_associated_token_account_idempotent(
+                &payer.pubkey(),
+                &settlement_wallet.pubkey(),
+                &mint.pubkey(),
+                &TOKEN_PROGRAM_ID,
+            ),
+        ],
+        &[&merchant_authority],
+    )?;
+
+    // Payments
+    let (escrow_authority, _) =
+        find_escrow_authority_pda(&merchant_operator_config, &mint.pubkey());
+    let merchant_escrow_ata =
+        get_associated_token_address(&escrow_authority, &mint.pubkey(), &TOKEN_PROGRAM_ID);
+    let merchant_settlement_ata = get_associated_token_address(
+        &settlement_wallet.pubkey(),
+        &mint.pubkey(),
+        &TOKEN_PROGRAM_ID,
+    );
+    let operator_settlement_ata =
+        get_associated_token_address(&payer.pubkey(), &mint.pubkey(), &TOKEN_PROGRAM_ID);
+
+    let mut payments = Vec::new();
+    for (order_id, status) in config.payment_plan() {
+        let (payment, _) = find_payment_pda(
+            &merchant_operator_config,
+            &buyer.pubkey(),
+            &mint.pubkey(),
+            order_id,
+        );
+
+        let mut idempotency_key = [0u8; 16];
+        idempotency_key[..4].copy_from_slice(&order_id.to_le_bytes());
+
+        let make_payment = MakePaymentBuilder::new()
+            .payer(payer.pubkey())
+            .payment(payment)
+            .operator_authority(payer.pubkey())
+            .buyer(buyer.pubkey())
+            .operator(operator)
+            .merchant(merchant)
+            .merchant_operator_config(merchant_operator_config)
+            .mint(mint.pubkey())
+            .buyer_ata(buyer_ata)
+            .escrow_authority(escrow_authority)
+            .merchant_escrow_ata(merchant_escrow_ata)
+            .merchant_settlement_ata(merchant_settlement_ata)
+            .settlement_wallet(settlement_wallet.pubkey())
+            .order_id(order_id)
+            .amount(config.payment_amount)
+            .idempotency_key(idempotency_key)
+            .instruction();
+
+        let mut instructions = vec![make_payment];
+        match status {
+            Status::Paid => {}
+            Status::Cleared => instructions.push(
+                ClearPaymentBuilder::new()
+                    .payer(payer.pubkey())
+                    .payment(payment)
+                    .operator_authority(payer.pubkey())
+                    .buyer(buyer.pubkey())
+                    .merchant(merchant)
+                    .operator(operator)
+                    .merchant_operator_config(merchant_operator_config)
+                    .mint(mint.pubkey())
+                    .escrow_authority(escrow_authority)
+                    .merchant_escrow_ata(merchant_escrow_ata)
+                    .merchant_settlement_ata(merchant_settlement_ata)
+                    .operator_settlement_ata(operator_settlement_ata)
+                    .instruction(),
+            ),
+            Status::Refunded => instructions.push(
+                RefundPaymentBuilder::new()
+                    .payer(payer.pubkey())
+                    .payment(payment)
+                    .operator_authority(payer.pubkey())
+                    .buyer(buyer.pubkey())
+                    .merchant(merchant)
+                    .operator(operator)
+                    .merchant_operator_config(merchant_operator_config)
+                    .mint(mint.pubkey())
+                    .escrow_authority(escrow_authority)
+                    .merchant_escrow_ata(merchant_escrow_ata)
+                    .buyer_ata(buyer_ata)
+                    .instruction(),
+            ),
+            Status::RefundApproved => unreachable!("payment_plan does not approve refunds"),
+            Status::Authorized => unreachable!("payment_plan does not authorize payments"),
+        }
+
+        send(rpc, payer, &instructions, &[&buyer])?;
+
+        payments.push(FixturePayment {
+            address: payment,
+            order_id,
+            amount: config.payment_amount,
+            status,
+        });
+    }
+
+    Ok(Fixtures {
+        operator_authority: payer.pubkey(),
+        operator,
+        merchant_authority,
+        merchant,
+        settlement_wallet,
+        merchant_operator_config,
+        mint: mint.pubkey(),
+        buyer,
+        payments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_plan() {
+        let config = FixtureConfig {
+            payments_per_status: 2,
+            ..FixtureConfig::default()
+        };
+
+        assert_eq!(
+            config.payment_plan(),
+            vec![
+                (1, Status::Paid),
+                (2, Status::Paid),
+                (3, Status::Cleared),
+                (4, Status::Cleared),
+                (5, Status::Refunded),
+                (6, Status::Refunded),
+            ]
+        );
+        assert_eq!(config.buyer_funding(), 6 * config.payment_amount);
+    }
+
+    #[test]
+    fn test_policies_allow_immediate_clear_and_refund() {
+        let config = FixtureConfig::default();
+
+        for policy in config.policies() {
+            match policy {
+                PolicyData::Refund(refund) => {
+                    assert!(refund.max_amount >= config.payment_amount);
+                    assert!(refund.max_time_after_purchase > 0);
+                }
+                PolicyData::Settlement(settlement) => {
+                    assert!(!settlement.auto_settle);
+                    assert_eq!(settlement.settlement_frequency_hours, 0);
+                    assert_eq!(settlement.min_settlement_amount, 0);
+                }
+                other => panic!("unexpected policy {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_json_includes_generated_keypairs() {
+        let fixtures = Fixtures {
+            operator_authority: Pubkey::new_unique(),
+            operator: Pubkey::new_unique(),
+            merchant_authority: Keypair::new(),
+            merchant: Pubkey::new_unique(),
+            settlement_wallet: Keypair::new(),
+            merchant_operator_config: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            buyer: Keypair::new(),
+            payments: vec![FixturePayment {
+                address: Pubkey::new_unique(),
+                order_id: 1,
+                amount: 10,
+                status: Status::Cleared,
+            }],
+        };
+
+        let value = fixtures.to_json();
+
+        assert_eq!(
+            value["buyer"]["secret_key"],
+            fixtures.buyer.to_base58_string()
+        );
+        assert_eq!(
+            value["merchant_authority"]["pubkey"],
+            fixtures.merchant_authority.pubkey().to_string()
+        );
+        assert_eq!(value["payments"][0]["status"], "Cleared");
+    }
+}
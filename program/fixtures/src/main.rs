@@ -0,0 +1,71 @@
+//! `commerce-kit-fixtures`: provisions a demo Commerce Program environment.
+//!
+//! ```text
+//! commerce-kit-fixtures [--url <RPC_URL>] [--keypair <PATH>] [--payments-per-status <N>]
+//! ```
+//!
+//! Defaults to `http://127.0.0.1:8899` and `~/.config/solana/id.json`. When the payer is
+//! low on SOL an airdrop is requested, which works on localnet and devnet. The summary,
+//! including the generated keypairs, is printed as JSON.
+
+use std::{env, error::Error, path::PathBuf};
+
+use commerce_kit_fixtures::{provision, FixtureConfig};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig, native_token::LAMPORTS_PER_SOL,
+    signature::read_keypair_file, signer::Signer,
+};
+
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+const MIN_PAYER_BALANCE: u64 = LAMPORTS_PER_SOL;
+const AIRDROP_AMOUNT: u64 = 2 * LAMPORTS_PER_SOL;
+
+struct Args {
+    url: String,
+    keypair: PathBuf,
+    payments_per_status: Option<u32>,
+}
+
+fn parse_args() -> Result<Args, Box<dyn Error>> {
+    let mut args = Args {
+        url: DEFAULT_RPC_URL.to_string(),
+        keypair: PathBuf::from(env::var("HOME")?).join(".config/solana/id.json"),
+        payments_per_status: None,
+    };
+
+    let mut iter = env::args().skip(1);
+    while let Some(flag) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("missing value for {flag}"));
+        match flag.as_str() {
+            "--url" | "-u" => args.url = value()?,
+            "--keypair" | "-k" => args.keypair = PathBuf::from(value()?),
+            "--payments-per-status" => args.payments_per_status = Some(value()?.parse()?),
+            _ => return Err(format!("unknown argument {flag}").into()),
+        }
+    }
+
+    Ok(args)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = parse_args()?;
+    let payer = read_keypair_file(&args.keypair)
+        .map_err(|e| format!("failed to read keypair {}: {e}", args.keypair.display()))?;
+    let rpc = RpcClient::new_with_commitment(args.url, CommitmentConfig::confirmed());
+
+    if rpc.get_balance(&payer.pubkey())? < MIN_PAYER_BALANCE {
+        let signature = rpc.request_airdrop(&payer.pubkey(), AIRDROP_AMOUNT)?;
+        rpc.poll_for_signature(&signature)?;
+    }
+
+    let mut config = FixtureConfig::default();
+    if let Some(payments_per_status) = args.payments_per_status {
+        config.payments_per_status = payments_per_status;
+    }
+
+    let fixtures = provision(&rpc, &payer, &config)?;
+    println!("{}", serde_json::to_string_pretty(&fixtures.to_json())?);
+
+    Ok(())
+}
@@ -8,16 +8,88 @@ pub const POLICY_SIZE: usize = 100;
 pub const SECONDS_PER_HOUR: i64 = 3600;
 pub const SECONDS_PER_DAY: i64 = 86400;
 
+// Longest settlement wallet timelock a merchant can set (30 days)
+pub const MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS: u64 = 30 * SECONDS_PER_DAY as u64;
+
 // Max BPS
 pub const MAX_BPS: u64 = 10_000;
 
+// Most Payment accounts SweepClosedPayments closes in one call (3 accounts each)
+pub const MAX_SWEEP_PAYMENTS: u8 = 16;
+
+// Most payments (one per mint) that can fund a single Order
+pub const MAX_ORDER_PAYMENTS: u8 = 4;
+
+// Most Solana Pay reference keys MakePayment stores on a Payment
+pub const MAX_PAYMENT_REFERENCES: usize = 2;
+
+// MerchantProfile: longest display name and URI, in bytes
+pub const MAX_MERCHANT_NAME_LEN: usize = 32;
+pub const MAX_MERCHANT_URI_LEN: usize = 200;
+
+// MerchantOperatorConfig: most policies and accepted currencies a config can hold, whether set
+// at initialization or added later with AddPolicy and AddAcceptedCurrency
+pub const MAX_POLICIES: usize = 16;
+pub const MAX_ACCEPTED_CURRENCIES: usize = 32;
+
+// PaymentNote: size of the ciphertext MakePayment can attach to a payment
+pub const PAYMENT_NOTE_CIPHERTEXT_LEN: usize = 128;
+
+// BuyerIndex: most recent order ids kept per buyer
+pub const BUYER_INDEX_RECENT_ORDERS: usize = 8;
+
+// ConfigRegistryPage: configs listed per page; later configs go to the next page
+pub const CONFIG_REGISTRY_PAGE_CAPACITY: usize = 32;
+
+// OperatorFreezeList: most buyer addresses an operator can freeze
+pub const OPERATOR_FREEZE_LIST_CAPACITY: usize = 64;
+
+// Compressed payments: depth of the payment Merkle tree (2^20 leaves per tree)
+pub const PAYMENT_TREE_DEPTH: usize = 20;
+
+// Denylist policy: deepest denylist Merkle tree accepted (2^20 gaps between banned buyers)
+pub const MAX_DENYLIST_DEPTH: usize = 20;
+
 // Seeds and PDAs
+pub const BUYER_INDEX_SEED: &[u8] = b"buyer_index";
+pub const CONFIG_REGISTRY_SEED: &[u8] = b"config_registry";
+pub const CONVERSION_RATE_SEED: &[u8] = b"conversion_rate";
+pub const ESCROW_AUTHORITY_SEED: &[u8] = b"escrow_authority";
 pub const MERCHANT_SEED: &[u8] = b"merchant";
 pub const MERCHANT_OPERATOR_CONFIG_SEED: &[u8] = b"merchant_operator_config";
+pub const MERCHANT_PROFILE_SEED: &[u8] = b"merchant_profile";
+pub const MERCHANT_STATS_SEED: &[u8] = b"merchant_stats";
 pub const OPERATOR_SEED: &[u8] = b"operator";
+pub const OPERATOR_DELEGATE_SEED: &[u8] = b"operator_delegate";
+pub const OPERATOR_FREEZE_LIST_SEED: &[u8] = b"operator_freeze_list";
+pub const ORDER_SEED: &[u8] = b"order";
 pub const PAYMENT_SEED: &[u8] = b"payment";
+pub const PAYMENT_ANNOTATION_SEED: &[u8] = b"payment_annotation";
+pub const PAYMENT_NOTE_SEED: &[u8] = b"payment_note";
+pub const PAYMENT_TREE_SEED: &[u8] = b"payment_tree";
+pub const QUOTE_SEED: &[u8] = b"quote";
+pub const REFUND_APPROVAL_SEED: &[u8] = b"refund_approval";
+pub const REFUND_DELEGATE_SEED: &[u8] = b"refund_delegate";
+pub const RISK_STATE_SEED: &[u8] = b"risk_state";
+pub const SETTLEMENT_BATCH_SEED: &[u8] = b"settlement_batch";
+pub const WEBHOOK_COMMITMENT_SEED: &[u8] = b"webhook_commitment";
 pub const EVENT_AUTHORITY_SEED: &[u8] = b"event_authority";
 
+// Seed of a transfer hook program's ExtraAccountMetaList PDA, with the mint
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+// SPL Token-2022 program
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// SPL Memo program
+pub const MEMO_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+// Jupiter Aggregator v6, the first ConversionPolicy swap adapter
+pub const JUPITER_V6_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("JUP6LkbZbjS1jKKwapdHNy74zvZ3tGvvH7uCvy2gSfr");
+
 // Anchor Compatitable Discriminator: Sha256(anchor:event)[..8]
 pub const EVENT_IX_TAG: u64 = 0x1d9acb512ea545e4;
 pub const EVENT_IX_TAG_LE: &[u8] = EVENT_IX_TAG.to_le_bytes().as_slice();
@@ -1,16 +1,33 @@
-use pinocchio::{
-    account_info::AccountInfo, entrypoint, program_error::ProgramError, pubkey::Pubkey,
-    ProgramResult,
-};
+use pinocchio::{account_info::AccountInfo, entrypoint, pubkey::Pubkey, ProgramResult};
 
 use crate::{
     processor::{
-        process_clear_payment, process_close_payment, process_create_operator, process_emit_event,
+        process_accept_merchant_authority, process_accept_operator_authority,
+        process_add_accepted_currency, process_add_policy, process_add_to_freeze_list,
+        process_annotate_payment, process_approve_refund, process_capture_payment,
+        process_chargeback_payment, process_claim_refund, process_clear_payment,
+        process_clear_payment_compressed, process_clear_payment_with_conversion,
+        process_close_escrow_ata, process_close_merchant_operator_config, process_close_payment,
+        process_close_payment_note, process_commit_settlement_wallet, process_create_operator,
+        process_create_order, process_create_quote, process_decommission_config,
+        process_emit_event, process_execute_settlement_batch, process_get_config_summary,
+        process_heartbeat, process_initialize_buyer_index, process_initialize_config_registry_page,
         process_initialize_merchant, process_initialize_merchant_operator_config,
-        process_make_payment, process_refund_payment, process_update_merchant_authority,
-        process_update_merchant_settlement_wallet, process_update_operator_authority,
+        process_initialize_merchant_profile, process_initialize_merchant_stats,
+        process_initialize_merchant_with_config, process_initialize_operator_delegate,
+        process_initialize_operator_freeze_list, process_initialize_payment_tree,
+        process_initialize_risk_state, process_initialize_settlement_batch, process_make_payment,
+        process_make_payment_compressed, process_migrate_escrow, process_migrate_payment,
+        process_post_conversion_rate, process_propose_merchant_authority,
+        process_propose_operator_authority, process_propose_settlement_wallet,
+        process_refund_payment, process_refund_payment_with_conversion,
+        process_refund_settled_payment, process_remove_from_freeze_list,
+        process_revoke_operator_delegate, process_set_settlement_wallet_timelock,
+        process_set_webhook_commitment, process_sweep_closed_payments,
+        process_update_merchant_profile, process_update_merchant_settlement_wallet,
+        process_update_operator_delegate, process_upgrade_account,
     },
-    state::discriminator::CommerceInstructionDiscriminators,
+    state::discriminator::{parse_instruction, InstructionDiscriminator},
 };
 
 entrypoint!(process_instruction);
@@ -20,44 +37,181 @@ pub fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let (discriminator, instruction_data) = instruction_data
-        .split_first()
-        .ok_or(ProgramError::InvalidInstructionData)?;
-
-    let discriminator = CommerceInstructionDiscriminators::try_from(*discriminator)
-        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    // Every version an instruction accepts has the same layout, so processors do not take it
+    let (discriminator, _version, instruction_data) = parse_instruction(instruction_data)?;
 
     match discriminator {
-        CommerceInstructionDiscriminators::CreateMerchant => {
+        InstructionDiscriminator::InitializeMerchant => {
             process_initialize_merchant(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::CreateOperator => {
+        InstructionDiscriminator::CreateOperator => {
             process_create_operator(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::InitializeMerchantOperatorConfig => {
+        InstructionDiscriminator::InitializeMerchantOperatorConfig => {
             process_initialize_merchant_operator_config(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::MakePayment => {
+        InstructionDiscriminator::MakePayment => {
             process_make_payment(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::ClearPayment => {
+        InstructionDiscriminator::ClearPayment => {
             process_clear_payment(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::RefundPayment => {
+        InstructionDiscriminator::RefundPayment => {
             process_refund_payment(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::UpdateMerchantSettlementWallet => {
+        InstructionDiscriminator::UpdateMerchantSettlementWallet => {
             process_update_merchant_settlement_wallet(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::UpdateMerchantAuthority => {
-            process_update_merchant_authority(program_id, accounts, instruction_data)
+        InstructionDiscriminator::ProposeMerchantAuthority => {
+            process_propose_merchant_authority(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::UpdateOperatorAuthority => {
-            process_update_operator_authority(program_id, accounts, instruction_data)
+        InstructionDiscriminator::ProposeOperatorAuthority => {
+            process_propose_operator_authority(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::ClosePayment => {
+        InstructionDiscriminator::ClosePayment => {
             process_close_payment(program_id, accounts, instruction_data)
         }
-        CommerceInstructionDiscriminators::EmitEvent => process_emit_event(program_id, accounts),
+        InstructionDiscriminator::InitializePaymentTree => {
+            process_initialize_payment_tree(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::MakePaymentCompressed => {
+            process_make_payment_compressed(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::ClearPaymentCompressed => {
+            process_clear_payment_compressed(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::AcceptMerchantAuthority => {
+            process_accept_merchant_authority(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::AcceptOperatorAuthority => {
+            process_accept_operator_authority(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::InitializeMerchantStats => {
+            process_initialize_merchant_stats(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::RefundSettledPayment => {
+            process_refund_settled_payment(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::InitializeOperatorDelegate => {
+            process_initialize_operator_delegate(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::UpdateOperatorDelegate => {
+            process_update_operator_delegate(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::RevokeOperatorDelegate => {
+            process_revoke_operator_delegate(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::ClearPaymentWithConversion => {
+            process_clear_payment_with_conversion(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::ChargebackPayment => {
+            process_chargeback_payment(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::SweepClosedPayments => {
+            process_sweep_closed_payments(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::ProposeSettlementWallet => {
+            process_propose_settlement_wallet(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::CommitSettlementWallet => {
+            process_commit_settlement_wallet(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::InitializeSettlementBatch => {
+            process_initialize_settlement_batch(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::ExecuteSettlementBatch => {
+            process_execute_settlement_batch(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::CreateOrder => {
+            process_create_order(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::InitializeMerchantProfile => {
+            process_initialize_merchant_profile(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::UpdateMerchantProfile => {
+            process_update_merchant_profile(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::ApproveRefund => {
+            process_approve_refund(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::ClaimRefund => {
+            process_claim_refund(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::InitializeRiskState => {
+            process_initialize_risk_state(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::InitializeBuyerIndex => {
+            process_initialize_buyer_index(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::InitializeMerchantWithConfig => {
+            process_initialize_merchant_with_config(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::MigrateEscrow => {
+            process_migrate_escrow(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::CreateQuote => {
+            process_create_quote(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::InitializeConfigRegistryPage => {
+            process_initialize_config_registry_page(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::CapturePayment => {
+            process_capture_payment(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::InitializeOperatorFreezeList => {
+            process_initialize_operator_freeze_list(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::AddToFreezeList => {
+            process_add_to_freeze_list(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::RemoveFromFreezeList => {
+            process_remove_from_freeze_list(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::PostConversionRate => {
+            process_post_conversion_rate(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::RefundPaymentWithConversion => {
+            process_refund_payment_with_conversion(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::AddPolicy => {
+            process_add_policy(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::AddAcceptedCurrency => {
+            process_add_accepted_currency(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::ClosePaymentNote => {
+            process_close_payment_note(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::Heartbeat => {
+            process_heartbeat(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::CloseEscrowAta => {
+            process_close_escrow_ata(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::MigratePayment => {
+            process_migrate_payment(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::GetConfigSummary => {
+            process_get_config_summary(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::SetWebhookCommitment => {
+            process_set_webhook_commitment(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::AnnotatePayment => {
+            process_annotate_payment(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::DecommissionConfig => {
+            process_decommission_config(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::CloseMerchantOperatorConfig => {
+            process_close_merchant_operator_config(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::UpgradeAccount => {
+            process_upgrade_account(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::SetSettlementWalletTimelock => {
+            process_set_settlement_wallet_timelock(program_id, accounts, instruction_data)
+        }
+        InstructionDiscriminator::EmitEvent => process_emit_event(program_id, accounts),
     }
 }
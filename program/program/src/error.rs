@@ -61,6 +61,761 @@ pub enum CommerceProgramError {
     /// (18) Duplicate mint in accepted currencies
     #[error("Duplicate mint in accepted currencies")]
     DuplicateMint,
+    /// (19) PaymentTree PDA is invalid
+    #[error("PaymentTree PDA is invalid")]
+    PaymentTreeInvalidPda,
+    /// (20) PaymentTree does not match config
+    #[error("PaymentTree does not match config")]
+    PaymentTreeMismatch,
+    /// (21) PaymentTree has no remaining leaves
+    #[error("PaymentTree has no remaining leaves")]
+    PaymentTreeFull,
+    /// (22) Merkle proof does not match the PaymentTree root
+    #[error("Merkle proof does not match the PaymentTree root")]
+    InvalidMerkleProof,
+    /// (23) Merchant has no pending authority matching the signer
+    #[error("Merchant has no pending authority matching the signer")]
+    MerchantPendingAuthorityMismatch,
+    /// (24) Operator has no pending authority matching the signer
+    #[error("Operator has no pending authority matching the signer")]
+    OperatorPendingAuthorityMismatch,
+    /// (25) Settlement wallet does not match merchant settlement wallet
+    #[error("Settlement wallet does not match merchant settlement wallet")]
+    SettlementWalletMismatch,
+    /// (26) MerchantStats PDA is invalid
+    #[error("MerchantStats PDA is invalid")]
+    MerchantStatsInvalidPda,
+    /// (27) MerchantStats does not match merchant or mint
+    #[error("MerchantStats does not match merchant or mint")]
+    MerchantStatsMismatch,
+    /// (28) Payment already exists
+    #[error("Payment already exists")]
+    PaymentAlreadyExists,
+    /// (29) Fee tier policy is invalid
+    #[error("Fee tier policy is invalid")]
+    InvalidFeeTierPolicy,
+    /// (30) Payment was not auto-settled
+    #[error("Payment was not auto-settled")]
+    PaymentNotAutoSettled,
+    /// (31) Refunding a settled payment requires a refund policy with a refund window
+    #[error("Refunding a settled payment requires a refund policy with a refund window")]
+    RefundWindowRequired,
+    /// (32) Memo policy requires the memo program account
+    #[error("Memo policy requires the memo program account")]
+    MemoProgramRequired,
+    /// (33) OperatorDelegate PDA is invalid
+    #[error("OperatorDelegate PDA is invalid")]
+    OperatorDelegateInvalidPda,
+    /// (34) OperatorDelegate does not match operator or signer
+    #[error("OperatorDelegate does not match operator or signer")]
+    OperatorDelegateMismatch,
+    /// (35) OperatorDelegate lacks the permission for this instruction
+    #[error("OperatorDelegate lacks the permission for this instruction")]
+    OperatorDelegatePermissionDenied,
+    /// (36) Conversion policy is invalid
+    #[error("Conversion policy is invalid")]
+    InvalidConversionPolicy,
+    /// (37) Config has no conversion policy
+    #[error("Config has no conversion policy")]
+    ConversionPolicyRequired,
+    /// (38) Swap program or instruction is not supported by the conversion policy
+    #[error("Swap program or instruction is not supported by the conversion policy")]
+    InvalidSwap,
+    /// (39) Swap did not spend exactly the merchant amount
+    #[error("Swap did not spend exactly the merchant amount")]
+    SwapInputMismatch,
+    /// (40) Swap output is below the slippage bound
+    #[error("Swap output is below the slippage bound")]
+    SlippageExceeded,
+    /// (41) Chargeback policy is invalid
+    #[error("Chargeback policy is invalid")]
+    InvalidChargebackPolicy,
+    /// (42) Config has no chargeback policy
+    #[error("Config has no chargeback policy")]
+    ChargebackPolicyRequired,
+    /// (43) Payment is still within its chargeback window
+    #[error("Payment is still within its chargeback window")]
+    ChargebackWindowActive,
+    /// (44) Payment chargeback window has expired
+    #[error("Payment chargeback window has expired")]
+    ChargebackWindowExpired,
+    /// (45) Signer is not the chargeback arbiter
+    #[error("Signer is not the chargeback arbiter")]
+    ChargebackArbiterMismatch,
+    /// (46) Settlement wallet timelock exceeds the maximum
+    #[error("Settlement wallet timelock exceeds the maximum")]
+    InvalidSettlementWalletTimelock,
+    /// (47) Settlement wallet is timelocked and must be changed via ProposeSettlementWallet
+    #[error("Settlement wallet is timelocked and must be changed via ProposeSettlementWallet")]
+    SettlementWalletTimelocked,
+    /// (48) No settlement wallet has been proposed
+    #[error("No settlement wallet has been proposed")]
+    SettlementWalletNotProposed,
+    /// (49) Proposed settlement wallet is still timelocked
+    #[error("Proposed settlement wallet is still timelocked")]
+    SettlementWalletTimelockActive,
+    /// (50) Fee override policy is invalid
+    #[error("Fee override policy is invalid")]
+    InvalidFeeOverridePolicy,
+    /// (51) Config has no fee override policy
+    #[error("Config has no fee override policy")]
+    FeeOverridePolicyRequired,
+    /// (52) Fee override exceeds the fee override policy maximum
+    #[error("Fee override exceeds the fee override policy maximum")]
+    FeeOverrideExceedsMax,
+    /// (53) Denylist policy is invalid
+    #[error("Denylist policy is invalid")]
+    InvalidDenylistPolicy,
+    /// (54) Config has a denylist policy but no denylist proof was supplied
+    #[error("Config has a denylist policy but no denylist proof was supplied")]
+    DenylistProofRequired,
+    /// (55) Denylist proof does not show the buyer is allowed
+    #[error("Denylist proof does not show the buyer is allowed")]
+    InvalidDenylistProof,
+    /// (56) SettlementBatch PDA is invalid
+    #[error("SettlementBatch PDA is invalid")]
+    SettlementBatchInvalidPda,
+    /// (57) SettlementBatch does not match config or mint
+    #[error("SettlementBatch does not match config or mint")]
+    SettlementBatchMismatch,
+    /// (58) SettlementBatch has no pending amount
+    #[error("SettlementBatch has no pending amount")]
+    SettlementBatchEmpty,
+    /// (59) Order PDA is invalid
+    #[error("Order PDA is invalid")]
+    OrderInvalidPda,
+    /// (60) Payment does not belong to the Order
+    #[error("Payment does not belong to the Order")]
+    OrderMismatch,
+    /// (61) Order already has all its expected payments
+    #[error("Order already has all its expected payments")]
+    OrderFull,
+    /// (62) Order expected payment count is invalid
+    #[error("Order expected payment count is invalid")]
+    InvalidOrderPaymentCount,
+    /// (63) MerchantProfile PDA is invalid
+    #[error("MerchantProfile PDA is invalid")]
+    MerchantProfileInvalidPda,
+    /// (64) MerchantProfile name or URI is too long
+    #[error("MerchantProfile name or URI is too long")]
+    MerchantProfileFieldTooLong,
+    /// (65) More reference accounts than a Payment can store
+    #[error("More reference accounts than a Payment can store")]
+    TooManyPaymentReferences,
+    /// (66) Split settlement policy is invalid
+    #[error("Split settlement policy is invalid")]
+    InvalidSplitSettlementPolicy,
+    /// (67) Split settlement destinations do not match the policy
+    #[error("Split settlement destinations do not match the policy")]
+    InvalidSplitSettlementDestinations,
+    /// (68) Split settlement is not supported on this settlement path
+    #[error("Split settlement is not supported on this settlement path")]
+    SplitSettlementUnsupported,
+    /// (69) RefundApproval PDA is invalid
+    #[error("RefundApproval PDA is invalid")]
+    RefundApprovalInvalidPda,
+    /// (70) Refund approval belongs to a different payment
+    #[error("Refund approval belongs to a different payment")]
+    RefundApprovalMismatch,
+    /// (71) Refund destination is not a token account the buyer owns for the payment mint
+    #[error("Refund destination is not a token account the buyer owns for the payment mint")]
+    InvalidRefundDestination,
+    /// (72) Risk policy is invalid
+    #[error("Risk policy is invalid")]
+    InvalidRiskPolicy,
+    /// (73) Payment amount exceeds the risk policy's maximum payment amount
+    #[error("Payment amount exceeds the risk policy's maximum payment amount")]
+    PaymentAmountExceedsRiskLimit,
+    /// (74) Payment would exceed the risk policy's maximum daily volume
+    #[error("Payment would exceed the risk policy's maximum daily volume")]
+    DailyVolumeLimitExceeded,
+    /// (75) Buyer has reached the risk policy's maximum payments per day
+    #[error("Buyer has reached the risk policy's maximum payments per day")]
+    BuyerDailyPaymentLimitExceeded,
+    /// (76) RiskState PDA is invalid
+    #[error("RiskState PDA is invalid")]
+    RiskStateInvalidPda,
+    /// (77) RiskState belongs to a different config or buyer
+    #[error("RiskState belongs to a different config or buyer")]
+    RiskStateMismatch,
+    /// (78) RiskState account required by the risk policy is missing
+    #[error("RiskState account required by the risk policy is missing")]
+    RiskStateRequired,
+    /// (79) BuyerIndex PDA is invalid
+    #[error("BuyerIndex PDA is invalid")]
+    BuyerIndexInvalidPda,
+    /// (80) BuyerIndex belongs to a different config or buyer
+    #[error("BuyerIndex belongs to a different config or buyer")]
+    BuyerIndexMismatch,
+    /// (81) BuyerIndex account required by the buyer index policy is missing
+    #[error("BuyerIndex account required by the buyer index policy is missing")]
+    BuyerIndexRequired,
+    /// (82) Rent refund account does not match the payment's rent payer
+    #[error("Rent refund account does not match the payment's rent payer")]
+    RentPayerMismatch,
+    /// (83) Callback policy is invalid
+    #[error("Callback policy is invalid")]
+    InvalidCallbackPolicy,
+    /// (84) Callback program required by the callback policy is missing
+    #[error("Callback program required by the callback policy is missing")]
+    CallbackProgramRequired,
+    /// (85) Callback program does not match the callback policy
+    #[error("Callback program does not match the callback policy")]
+    CallbackProgramMismatch,
+    /// (86) Refund exceeds the delegate's refund limit
+    #[error("Refund exceeds the delegate's refund limit")]
+    DelegateRefundLimitExceeded,
+    /// (87) Refund exceeds the delegate's daily refund limit
+    #[error("Refund exceeds the delegate's daily refund limit")]
+    DelegateDailyRefundLimitExceeded,
+    /// (88) Transfer hook program or its ExtraAccountMetaList PDA not passed
+    #[error("Transfer hook program or its ExtraAccountMetaList PDA not passed")]
+    TransferHookAccountsMissing,
+    /// (89) Mint has a Token-2022 extension the program does not support
+    #[error("Mint has a Token-2022 extension the program does not support")]
+    UnsupportedMintExtension,
+    /// (90) Platform fee policy is invalid
+    #[error("Platform fee policy is invalid")]
+    InvalidPlatformFeePolicy,
+    /// (91) Platform fee is not supported on this settlement path
+    #[error("Platform fee is not supported on this settlement path")]
+    PlatformFeeUnsupported,
+    /// (92) Escrow authority PDA is invalid
+    #[error("Escrow authority PDA is invalid")]
+    EscrowAuthorityInvalidPda,
+    /// (93) Quote PDA is invalid
+    #[error("Quote PDA is invalid")]
+    QuoteInvalidPda,
+    /// (94) Payment does not match the quote
+    #[error("Payment does not match the quote")]
+    QuoteMismatch,
+    /// (95) Quote has expired
+    #[error("Quote has expired")]
+    QuoteExpired,
+    /// (96) Config registry page PDA is invalid
+    #[error("Config registry page PDA is invalid")]
+    ConfigRegistryInvalidPda,
+    /// (97) Config registry page does not belong to the operator
+    #[error("Config registry page does not belong to the operator")]
+    ConfigRegistryMismatch,
+    /// (98) Config registry page is full
+    #[error("Config registry page is full")]
+    ConfigRegistryPageFull,
+    /// (99) Previous config registry page is not full
+    #[error("Previous config registry page is not full")]
+    ConfigRegistryPreviousPageNotFull,
+    /// (100) Compliance policy is invalid
+    #[error("Compliance policy is invalid")]
+    InvalidCompliancePolicy,
+    /// (101) Compliance program required by the compliance policy is missing
+    #[error("Compliance program required by the compliance policy is missing")]
+    ComplianceProgramRequired,
+    /// (102) Compliance program does not match the compliance policy
+    #[error("Compliance program does not match the compliance policy")]
+    ComplianceProgramMismatch,
+    /// (103) Capture amount exceeds the authorized amount
+    #[error("Capture amount exceeds the authorized amount")]
+    CaptureAmountExceedsAuthorization,
+    /// (104) Authorized payments must be held in escrow
+    #[error("Authorized payments must be held in escrow")]
+    AuthorizationRequiresEscrow,
+    /// (105) Operator freeze list PDA is invalid
+    #[error("Operator freeze list PDA is invalid")]
+    OperatorFreezeListInvalidPda,
+    /// (106) Operator freeze list does not belong to the operator
+    #[error("Operator freeze list does not belong to the operator")]
+    OperatorFreezeListMismatch,
+    /// (107) Operator freeze list is full
+    #[error("Operator freeze list is full")]
+    OperatorFreezeListFull,
+    /// (108) Address is already on the freeze list
+    #[error("Address is already on the freeze list")]
+    AddressAlreadyFrozen,
+    /// (109) Address is not on the freeze list
+    #[error("Address is not on the freeze list")]
+    AddressNotFrozen,
+    /// (110) Buyer is on the operator's freeze list
+    #[error("Buyer is on the operator's freeze list")]
+    BuyerFrozen,
+    /// (111) Settlement discount policy is invalid
+    #[error("Settlement discount policy is invalid")]
+    InvalidSettlementDiscountPolicy,
+    /// (112) Refund conversion policy is invalid
+    #[error("Refund conversion policy is invalid")]
+    InvalidRefundConversionPolicy,
+    /// (113) Refunding in another mint requires a refund conversion policy
+    #[error("Refunding in another mint requires a refund conversion policy")]
+    RefundConversionPolicyRequired,
+    /// (114) Conversion rate PDA is invalid
+    #[error("Conversion rate PDA is invalid")]
+    ConversionRateInvalidPda,
+    /// (115) Conversion rate does not match the config and mints
+    #[error("Conversion rate does not match the config and mints")]
+    ConversionRateMismatch,
+    /// (116) Conversion rate is older than the refund conversion policy allows
+    #[error("Conversion rate is older than the refund conversion policy allows")]
+    ConversionRateStale,
+    /// (117) Conversion rate or converted amount is zero
+    #[error("Conversion rate or converted amount is zero")]
+    InvalidConversionRate,
+    /// (118) More policies than a config can hold
+    #[error("More policies than a config can hold")]
+    TooManyPolicies,
+    /// (119) More accepted currencies than a config can hold
+    #[error("More accepted currencies than a config can hold")]
+    TooManyAcceptedCurrencies,
+    /// (120) Config already has a policy of this type
+    #[error("Config already has a policy of this type")]
+    PolicyAlreadyExists,
+    /// (121) Payment note PDA is invalid
+    #[error("Payment note PDA is invalid")]
+    PaymentNoteInvalidPda,
+    /// (122) Operator failover policy is invalid
+    #[error("Operator failover policy is invalid")]
+    InvalidOperatorFailoverPolicy,
+    /// (123) Merchant can only clear payments with an operator failover policy
+    #[error("Merchant can only clear payments with an operator failover policy")]
+    OperatorFailoverPolicyRequired,
+    /// (124) Operator has been active within the failover threshold
+    #[error("Operator has been active within the failover threshold")]
+    OperatorStillActive,
+    /// (125) Token account is not owned by the expected wallet
+    #[error("Token account is not owned by the expected wallet")]
+    AtaOwnerMismatch,
+    /// (126) Token account holds a different mint
+    #[error("Token account holds a different mint")]
+    AtaMintMismatch,
+    /// (127) Associated token account is not initialized
+    #[error("Associated token account is not initialized")]
+    AtaNotInitialized,
+    /// (128) Escrow still holds open payments
+    #[error("Escrow still holds open payments")]
+    EscrowNotEmpty,
+    /// (129) Payment already belongs to the migration's target config
+    #[error("Payment already belongs to the migration's target config")]
+    MigrationTargetUnchanged,
+    /// (130) Instruction version is not supported
+    #[error("Instruction version is not supported")]
+    UnsupportedInstructionVersion,
+    /// (131) Rent rebate policy is invalid
+    #[error("Rent rebate policy is invalid")]
+    InvalidRentRebatePolicy,
+    /// (132) Webhook commitment PDA is invalid
+    #[error("Webhook commitment PDA is invalid")]
+    WebhookCommitmentInvalidPda,
+    /// (133) Direct settlement is not supported with this instruction or policy
+    #[error("Direct settlement is not supported with this instruction or policy")]
+    DirectSettlementUnsupported,
+    /// (134) Payment annotation PDA is invalid
+    #[error("Payment annotation PDA is invalid")]
+    PaymentAnnotationInvalidPda,
+    /// (135) Merchant operator config is decommissioned
+    #[error("Merchant operator config is decommissioned")]
+    ConfigDecommissioned,
+    /// (136) Merchant operator config is not decommissioned
+    #[error("Merchant operator config is not decommissioned")]
+    ConfigNotDecommissioned,
+    /// (137) Config still has open payments
+    #[error("Config still has open payments")]
+    ConfigHasOpenPayments,
+    /// (138) Account is not in a legacy layout
+    #[error("Account is not in a legacy layout")]
+    AccountNotUpgradable,
+    /// (139) Operator has a freeze list but it was not supplied
+    #[error("Operator has a freeze list but it was not supplied")]
+    FreezeListRequired,
+    /// (140) Config still has payment accounts
+    #[error("Config still has payment accounts")]
+    ConfigHasPaymentAccounts,
+    /// (141) Quoted amount is below the posted conversion rate
+    #[error("Quoted amount is below the posted conversion rate")]
+    QuoteBelowConversionRate,
+    /// (142) Payment belongs to an Order, which must be passed
+    #[error("Payment belongs to an Order, which must be passed")]
+    OrderRequired,
+    /// (143) Payment belongs to an Order and cannot move to another config
+    #[error("Payment belongs to an Order and cannot move to another config")]
+    PaymentInOrder,
+    /// (144) Settlement wallet timelock can only be raised
+    #[error("Settlement wallet timelock can only be raised")]
+    SettlementWalletTimelockLowered,
+}
+
+impl CommerceProgramError {
+    /// Number of errors; codes run from 0 to `COUNT - 1`.
+    pub const COUNT: u32 = 145;
+
+    /// Code carried by `ProgramError::Custom` when this error is returned.
+    pub fn code(&self) -> u32 {
+        self.clone() as u32
+    }
+
+    /// Human-readable description, the same as the `Display` text.
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::InvalidMint => "Incorrect mint provided",
+            Self::InvalidPaymentStatus => "Invalid payment status for the operation",
+            Self::InsufficientSettlementAmount => "Insufficient settlement amount",
+            Self::SettlementTooEarly => "Settlement attempted too early",
+            Self::RefundAmountExceedsPolicyLimit => "Refund amount exceeds policy limit",
+            Self::RefundWindowExpired => "Refund window expired",
+            Self::InvalidEventAuthority => "Invalid event authority",
+            Self::InvalidAta => "Invalid ATA",
+            Self::PaymentCloseWindowNotReached => "Payment close window not reached",
+            Self::MerchantOwnerMismatch => "Merchant owner does not match expected owner",
+            Self::MerchantInvalidPda => "Merchant PDA is invalid",
+            Self::OperatorOwnerMismatch => "Operator owner does not match expected owner",
+            Self::OperatorInvalidPda => "Operator PDA is invalid",
+            Self::OperatorMismatch => "Operator does not match config operator",
+            Self::MerchantMismatch => "Merchant does not match config merchant",
+            Self::OrderIdInvalid => "Order ID is invalid or already used",
+            Self::MerchantOperatorConfigInvalidPda => "MerchantOperatorConfig PDA is invalid",
+            Self::AcceptedCurrenciesEmpty => "Accepted currencies is empty",
+            Self::DuplicateMint => "Duplicate mint in accepted currencies",
+            Self::PaymentTreeInvalidPda => "PaymentTree PDA is invalid",
+            Self::PaymentTreeMismatch => "PaymentTree does not match config",
+            Self::PaymentTreeFull => "PaymentTree has no remaining leaves",
+            Self::InvalidMerkleProof => "Merkle proof does not match the PaymentTree root",
+            Self::MerchantPendingAuthorityMismatch => {
+                "Merchant has no pending authority matching the signer"
+            }
+            Self::OperatorPendingAuthorityMismatch => {
+                "Operator has no pending authority matching the signer"
+            }
+            Self::SettlementWalletMismatch => {
+                "Settlement wallet does not match merchant settlement wallet"
+            }
+            Self::MerchantStatsInvalidPda => "MerchantStats PDA is invalid",
+            Self::MerchantStatsMismatch => "MerchantStats does not match merchant or mint",
+            Self::PaymentAlreadyExists => "Payment already exists",
+            Self::InvalidFeeTierPolicy => "Fee tier policy is invalid",
+            Self::PaymentNotAutoSettled => "Payment was not auto-settled",
+            Self::RefundWindowRequired => {
+                "Refunding a settled payment requires a refund policy with a refund window"
+            }
+            Self::MemoProgramRequired => "Memo policy requires the memo program account",
+            Self::OperatorDelegateInvalidPda => "OperatorDelegate PDA is invalid",
+            Self::OperatorDelegateMismatch => "OperatorDelegate does not match operator or signer",
+            Self::OperatorDelegatePermissionDenied => {
+                "OperatorDelegate lacks the permission for this instruction"
+            }
+            Self::InvalidConversionPolicy => "Conversion policy is invalid",
+            Self::ConversionPolicyRequired => "Config has no conversion policy",
+            Self::InvalidSwap => {
+                "Swap program or instruction is not supported by the conversion policy"
+            }
+            Self::SwapInputMismatch => "Swap did not spend exactly the merchant amount",
+            Self::SlippageExceeded => "Swap output is below the slippage bound",
+            Self::InvalidChargebackPolicy => "Chargeback policy is invalid",
+            Self::ChargebackPolicyRequired => "Config has no chargeback policy",
+            Self::ChargebackWindowActive => "Payment is still within its chargeback window",
+            Self::ChargebackWindowExpired => "Payment chargeback window has expired",
+            Self::ChargebackArbiterMismatch => "Signer is not the chargeback arbiter",
+            Self::InvalidSettlementWalletTimelock => {
+                "Settlement wallet timelock exceeds the maximum"
+            }
+            Self::SettlementWalletTimelocked => {
+                "Settlement wallet is timelocked and must be changed via ProposeSettlementWallet"
+            }
+            Self::SettlementWalletNotProposed => "No settlement wallet has been proposed",
+            Self::SettlementWalletTimelockActive => {
+                "Proposed settlement wallet is still timelocked"
+            }
+            Self::InvalidFeeOverridePolicy => "Fee override policy is invalid",
+            Self::FeeOverridePolicyRequired => "Config has no fee override policy",
+            Self::FeeOverrideExceedsMax => "Fee override exceeds the fee override policy maximum",
+            Self::InvalidDenylistPolicy => "Denylist policy is invalid",
+            Self::DenylistProofRequired => {
+                "Config has a denylist policy but no denylist proof was supplied"
+            }
+            Self::InvalidDenylistProof => "Denylist proof does not show the buyer is allowed",
+            Self::SettlementBatchInvalidPda => "SettlementBatch PDA is invalid",
+            Self::SettlementBatchMismatch => "SettlementBatch does not match config or mint",
+            Self::SettlementBatchEmpty => "SettlementBatch has no pending amount",
+            Self::OrderInvalidPda => "Order PDA is invalid",
+            Self::OrderMismatch => "Payment does not belong to the Order",
+            Self::OrderFull => "Order already has all its expected payments",
+            Self::InvalidOrderPaymentCount => "Order expected payment count is invalid",
+            Self::MerchantProfileInvalidPda => "MerchantProfile PDA is invalid",
+            Self::MerchantProfileFieldTooLong => "MerchantProfile name or URI is too long",
+            Self::TooManyPaymentReferences => "More reference accounts than a Payment can store",
+            Self::InvalidSplitSettlementPolicy => "Split settlement policy is invalid",
+            Self::InvalidSplitSettlementDestinations => {
+                "Split settlement destinations do not match the policy"
+            }
+            Self::SplitSettlementUnsupported => {
+                "Split settlement is not supported on this settlement path"
+            }
+            Self::RefundApprovalInvalidPda => "RefundApproval PDA is invalid",
+            Self::RefundApprovalMismatch => "Refund approval belongs to a different payment",
+            Self::InvalidRefundDestination => {
+                "Refund destination is not a token account the buyer owns for the payment mint"
+            }
+            Self::InvalidRiskPolicy => "Risk policy is invalid",
+            Self::PaymentAmountExceedsRiskLimit => {
+                "Payment amount exceeds the risk policy's maximum payment amount"
+            }
+            Self::DailyVolumeLimitExceeded => {
+                "Payment would exceed the risk policy's maximum daily volume"
+            }
+            Self::BuyerDailyPaymentLimitExceeded => {
+                "Buyer has reached the risk policy's maximum payments per day"
+            }
+            Self::RiskStateInvalidPda => "RiskState PDA is invalid",
+            Self::RiskStateMismatch => "RiskState belongs to a different config or buyer",
+            Self::RiskStateRequired => "RiskState account required by the risk policy is missing",
+            Self::BuyerIndexInvalidPda => "BuyerIndex PDA is invalid",
+            Self::BuyerIndexMismatch => "BuyerIndex belongs to a different config or buyer",
+            Self::BuyerIndexRequired => {
+                "BuyerIndex account required by the buyer index policy is missing"
+            }
+            Self::RentPayerMismatch => {
+                "Rent refund account does not match the payment's rent payer"
+            }
+            Self::InvalidCallbackPolicy => "Callback policy is invalid",
+            Self::CallbackProgramRequired => {
+                "Callback program required by the callback policy is missing"
+            }
+            Self::CallbackProgramMismatch => "Callback program does not match the callback policy",
+            Self::DelegateRefundLimitExceeded => "Refund exceeds the delegate's refund limit",
+            Self::DelegateDailyRefundLimitExceeded => {
+                "Refund exceeds the delegate's daily refund limit"
+            }
+            Self::TransferHookAccountsMissing => {
+                "Transfer hook program or its ExtraAccountMetaList PDA not passed"
+            }
+            Self::UnsupportedMintExtension => {
+                "Mint has a Token-2022 extension the program does not support"
+            }
+            Self::InvalidPlatformFeePolicy => "Platform fee policy is invalid",
+            Self::PlatformFeeUnsupported => "Platform fee is not supported on this settlement path",
+            Self::EscrowAuthorityInvalidPda => "Escrow authority PDA is invalid",
+            Self::QuoteInvalidPda => "Quote PDA is invalid",
+            Self::QuoteMismatch => "Payment does not match the quote",
+            Self::QuoteExpired => "Quote has expired",
+            Self::ConfigRegistryInvalidPda => "Config registry page PDA is invalid",
+            Self::ConfigRegistryMismatch => "Config registry page does not belong to the operator",
+            Self::ConfigRegistryPageFull => "Config registry page is full",
+            Self::ConfigRegistryPreviousPageNotFull => "Previous config registry page is not full",
+            Self::InvalidCompliancePolicy => "Compliance policy is invalid",
+            Self::ComplianceProgramRequired => {
+                "Compliance program required by the compliance policy is missing"
+            }
+            Self::ComplianceProgramMismatch => {
+                "Compliance program does not match the compliance policy"
+            }
+            Self::CaptureAmountExceedsAuthorization => {
+                "Capture amount exceeds the authorized amount"
+            }
+            Self::AuthorizationRequiresEscrow => "Authorized payments must be held in escrow",
+            Self::OperatorFreezeListInvalidPda => "Operator freeze list PDA is invalid",
+            Self::OperatorFreezeListMismatch => {
+                "Operator freeze list does not belong to the operator"
+            }
+            Self::OperatorFreezeListFull => "Operator freeze list is full",
+            Self::AddressAlreadyFrozen => "Address is already on the freeze list",
+            Self::AddressNotFrozen => "Address is not on the freeze list",
+            Self::BuyerFrozen => "Buyer is on the operator's freeze list",
+            Self::InvalidSettlementDiscountPolicy => "Settlement discount policy is invalid",
+            Self::InvalidRefundConversionPolicy => "Refund conversion policy is invalid",
+            Self::RefundConversionPolicyRequired => {
+                "Refunding in another mint requires a refund conversion policy"
+            }
+            Self::ConversionRateInvalidPda => "Conversion rate PDA is invalid",
+            Self::ConversionRateMismatch => "Conversion rate does not match the config and mints",
+            Self::ConversionRateStale => {
+                "Conversion rate is older than the refund conversion policy allows"
+            }
+            Self::InvalidConversionRate => "Conversion rate or converted amount is zero",
+            Self::TooManyPolicies => "More policies than a config can hold",
+            Self::TooManyAcceptedCurrencies => "More accepted currencies than a config can hold",
+            Self::PolicyAlreadyExists => "Config already has a policy of this type",
+            Self::PaymentNoteInvalidPda => "Payment note PDA is invalid",
+            Self::InvalidOperatorFailoverPolicy => "Operator failover policy is invalid",
+            Self::OperatorFailoverPolicyRequired => {
+                "Merchant can only clear payments with an operator failover policy"
+            }
+            Self::OperatorStillActive => "Operator has been active within the failover threshold",
+            Self::AtaOwnerMismatch => "Token account is not owned by the expected wallet",
+            Self::AtaMintMismatch => "Token account holds a different mint",
+            Self::AtaNotInitialized => "Associated token account is not initialized",
+            Self::EscrowNotEmpty => "Escrow still holds open payments",
+            Self::MigrationTargetUnchanged => {
+                "Payment already belongs to the migration's target config"
+            }
+            Self::UnsupportedInstructionVersion => "Instruction version is not supported",
+            Self::InvalidRentRebatePolicy => "Rent rebate policy is invalid",
+            Self::WebhookCommitmentInvalidPda => "Webhook commitment PDA is invalid",
+            Self::DirectSettlementUnsupported => {
+                "Direct settlement is not supported with this instruction or policy"
+            }
+            Self::PaymentAnnotationInvalidPda => "Payment annotation PDA is invalid",
+            Self::ConfigDecommissioned => "Merchant operator config is decommissioned",
+            Self::ConfigNotDecommissioned => "Merchant operator config is not decommissioned",
+            Self::ConfigHasOpenPayments => "Config still has open payments",
+            Self::AccountNotUpgradable => "Account is not in a legacy layout",
+            Self::FreezeListRequired => "Operator has a freeze list but it was not supplied",
+            Self::ConfigHasPaymentAccounts => "Config still has payment accounts",
+            Self::QuoteBelowConversionRate => "Quoted amount is below the posted conversion rate",
+            Self::OrderRequired => "Payment belongs to an Order, which must be passed",
+            Self::PaymentInOrder => "Payment belongs to an Order and cannot move to another config",
+            Self::SettlementWalletTimelockLowered => {
+                "Settlement wallet timelock can only be raised"
+            }
+        }
+    }
+
+    /// The error returned as `ProgramError::Custom(code)`, if `code` is one of ours.
+    pub fn from_code(code: u32) -> Option<Self> {
+        Some(match code {
+            0 => Self::InvalidMint,
+            1 => Self::InvalidPaymentStatus,
+            2 => Self::InsufficientSettlementAmount,
+            3 => Self::SettlementTooEarly,
+            4 => Self::RefundAmountExceedsPolicyLimit,
+            5 => Self::RefundWindowExpired,
+            6 => Self::InvalidEventAuthority,
+            7 => Self::InvalidAta,
+            8 => Self::PaymentCloseWindowNotReached,
+            9 => Self::MerchantOwnerMismatch,
+            10 => Self::MerchantInvalidPda,
+            11 => Self::OperatorOwnerMismatch,
+            12 => Self::OperatorInvalidPda,
+            13 => Self::OperatorMismatch,
+            14 => Self::MerchantMismatch,
+            15 => Self::OrderIdInvalid,
+            16 => Self::MerchantOperatorConfigInvalidPda,
+            17 => Self::AcceptedCurrenciesEmpty,
+            18 => Self::DuplicateMint,
+            19 => Self::PaymentTreeInvalidPda,
+            20 => Self::PaymentTreeMismatch,
+            21 => Self::PaymentTreeFull,
+            22 => Self::InvalidMerkleProof,
+            23 => Self::MerchantPendingAuthorityMismatch,
+            24 => Self::OperatorPendingAuthorityMismatch,
+            25 => Self::SettlementWalletMismatch,
+            26 => Self::MerchantStatsInvalidPda,
+            27 => Self::MerchantStatsMismatch,
+            28 => Self::PaymentAlreadyExists,
+            29 => Self::InvalidFeeTierPolicy,
+            30 => Self::PaymentNotAutoSettled,
+            31 => Self::RefundWindowRequired,
+            32 => Self::MemoProgramRequired,
+            33 => Self::OperatorDelegateInvalidPda,
+            34 => Self::OperatorDelegateMismatch,
+            35 => Self::OperatorDelegatePermissionDenied,
+            36 => Self::InvalidConversionPolicy,
+            37 => Self::ConversionPolicyRequired,
+            38 => Self::InvalidSwap,
+            39 => Self::SwapInputMismatch,
+            40 => Self::SlippageExceeded,
+            41 => Self::InvalidChargebackPolicy,
+            42 => Self::ChargebackPolicyRequired,
+            43 => Self::ChargebackWindowActive,
+            44 => Self::ChargebackWindowExpired,
+            45 => Self::ChargebackArbiterMismatch,
+            46 => Self::InvalidSettlementWalletTimelock,
+            47 => Self::SettlementWalletTimelocked,
+            48 => Self::SettlementWalletNotProposed,
+            49 => Self::SettlementWalletTimelockActive,
+            50 => Self::InvalidFeeOverridePolicy,
+            51 => Self::FeeOverridePolicyRequired,
+            52 => Self::FeeOverrideExceedsMax,
+            53 => Self::InvalidDenylistPolicy,
+            54 => Self::DenylistProofRequired,
+            55 => Self::InvalidDenylistProof,
+            56 => Self::SettlementBatchInvalidPda,
+            57 => Self::SettlementBatchMismatch,
+            58 => Self::SettlementBatchEmpty,
+            59 => Self::OrderInvalidPda,
+            60 => Self::OrderMismatch,
+            61 => Self::OrderFull,
+            62 => Self::InvalidOrderPaymentCount,
+            63 => Self::MerchantProfileInvalidPda,
+            64 => Self::MerchantProfileFieldTooLong,
+            65 => Self::TooManyPaymentReferences,
+            66 => Self::InvalidSplitSettlementPolicy,
+            67 => Self::InvalidSplitSettlementDestinations,
+            68 => Self::SplitSettlementUnsupported,
+            69 => Self::RefundApprovalInvalidPda,
+            70 => Self::RefundApprovalMismatch,
+            71 => Self::InvalidRefundDestination,
+            72 => Self::InvalidRiskPolicy,
+            73 => Self::PaymentAmountExceedsRiskLimit,
+            74 => Self::DailyVolumeLimitExceeded,
+            75 => Self::BuyerDailyPaymentLimitExceeded,
+            76 => Self::RiskStateInvalidPda,
+            77 => Self::RiskStateMismatch,
+            78 => Self::RiskStateRequired,
+            79 => Self::BuyerIndexInvalidPda,
+            80 => Self::BuyerIndexMismatch,
+            81 => Self::BuyerIndexRequired,
+            82 => Self::RentPayerMismatch,
+            83 => Self::InvalidCallbackPolicy,
+            84 => Self::CallbackProgramRequired,
+            85 => Self::CallbackProgramMismatch,
+            86 => Self::DelegateRefundLimitExceeded,
+            87 => Self::DelegateDailyRefundLimitExceeded,
+            88 => Self::TransferHookAccountsMissing,
+            89 => Self::UnsupportedMintExtension,
+            90 => Self::InvalidPlatformFeePolicy,
+            91 => Self::PlatformFeeUnsupported,
+            92 => Self::EscrowAuthorityInvalidPda,
+            93 => Self::QuoteInvalidPda,
+            94 => Self::QuoteMismatch,
+            95 => Self::QuoteExpired,
+            96 => Self::ConfigRegistryInvalidPda,
+            97 => Self::ConfigRegistryMismatch,
+            98 => Self::ConfigRegistryPageFull,
+            99 => Self::ConfigRegistryPreviousPageNotFull,
+            100 => Self::InvalidCompliancePolicy,
+            101 => Self::ComplianceProgramRequired,
+            102 => Self::ComplianceProgramMismatch,
+            103 => Self::CaptureAmountExceedsAuthorization,
+            104 => Self::AuthorizationRequiresEscrow,
+            105 => Self::OperatorFreezeListInvalidPda,
+            106 => Self::OperatorFreezeListMismatch,
+            107 => Self::OperatorFreezeListFull,
+            108 => Self::AddressAlreadyFrozen,
+            109 => Self::AddressNotFrozen,
+            110 => Self::BuyerFrozen,
+            111 => Self::InvalidSettlementDiscountPolicy,
+            112 => Self::InvalidRefundConversionPolicy,
+            113 => Self::RefundConversionPolicyRequired,
+            114 => Self::ConversionRateInvalidPda,
+            115 => Self::ConversionRateMismatch,
+            116 => Self::ConversionRateStale,
+            117 => Self::InvalidConversionRate,
+            118 => Self::TooManyPolicies,
+            119 => Self::TooManyAcceptedCurrencies,
+            120 => Self::PolicyAlreadyExists,
+            121 => Self::PaymentNoteInvalidPda,
+            122 => Self::InvalidOperatorFailoverPolicy,
+            123 => Self::OperatorFailoverPolicyRequired,
+            124 => Self::OperatorStillActive,
+            125 => Self::AtaOwnerMismatch,
+            126 => Self::AtaMintMismatch,
+            127 => Self::AtaNotInitialized,
+            128 => Self::EscrowNotEmpty,
+            129 => Self::MigrationTargetUnchanged,
+            130 => Self::UnsupportedInstructionVersion,
+            131 => Self::InvalidRentRebatePolicy,
+            132 => Self::WebhookCommitmentInvalidPda,
+            133 => Self::DirectSettlementUnsupported,
+            134 => Self::PaymentAnnotationInvalidPda,
+            135 => Self::ConfigDecommissioned,
+            136 => Self::ConfigNotDecommissioned,
+            137 => Self::ConfigHasOpenPayments,
+            138 => Self::AccountNotUpgradable,
+            139 => Self::FreezeListRequired,
+            140 => Self::ConfigHasPaymentAccounts,
+            141 => Self::QuoteBelowConversionRate,
+            142 => Self::OrderRequired,
+            143 => Self::PaymentInOrder,
+            144 => Self::SettlementWalletTimelockLowered,
+            _ => return None,
+        })
+    }
 }
 
 impl From<CommerceProgramError> for ProgramError {
@@ -68,3 +823,36 @@ impl From<CommerceProgramError> for ProgramError {
         ProgramError::Custom(e as u32)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_code_round_trip() {
+        (0..CommerceProgramError::COUNT).for_each(|code| {
+            let error = CommerceProgramError::from_code(code).unwrap();
+            assert_eq!(error.code(), code);
+            assert_eq!(ProgramError::from(error), ProgramError::Custom(code));
+        });
+        assert_eq!(
+            CommerceProgramError::from_code(CommerceProgramError::COUNT),
+            None
+        );
+    }
+
+    #[test]
+    fn test_message_matches_display() {
+        (0..CommerceProgramError::COUNT).for_each(|code| {
+            let error = CommerceProgramError::from_code(code).unwrap();
+            assert_eq!(error.message(), error.to_string());
+        });
+        assert_eq!(
+            CommerceProgramError::InvalidMint.message(),
+            "Incorrect mint provided"
+        );
+    }
+}
@@ -4,13 +4,38 @@ use alloc::vec::Vec;
 use pinocchio::pubkey::Pubkey;
 use shank::ShankType;
 
-use crate::constants::EVENT_IX_TAG_LE;
+use crate::{
+    constants::EVENT_IX_TAG_LE,
+    state::{AnnotationFlag, ByteWriter, OrderStatus, Status},
+};
 
 #[repr(u8)]
 pub enum EventDiscriminators {
     PaymentCreated = 0,
     PaymentCleared = 1,
     PaymentRefunded = 2,
+    CompressedPayment = 3,
+    PaymentChargebacked = 4,
+    PaymentClosed = 5,
+    SettlementBatchExecuted = 6,
+    OrderCreated = 7,
+    OrderStatusChanged = 8,
+    RefundApproved = 9,
+    PaymentStatusChanged = 10,
+    MerchantOperatorConfigInitialized = 11,
+    PlatformFeeCollected = 12,
+    PaymentCaptured = 13,
+    MerchantInitialized = 14,
+    MerchantSettlementWalletUpdated = 15,
+    MerchantAuthorityUpdated = 16,
+    OperatorAuthorityUpdated = 17,
+    PaymentMigrated = 18,
+    WebhookCommitmentUpdated = 19,
+    PaymentAnnotated = 20,
+    ConfigDecommissioned = 21,
+    MerchantOperatorConfigClosed = 22,
+    SettlementWalletProposed = 23,
+    SettlementWalletTimelockRaised = 24,
 }
 
 #[derive(ShankType)]
@@ -27,11 +52,17 @@ pub struct PaymentCreatedEvent {
     pub amount: u64,
     /// Reference to the order_id of the payment
     pub order_id: u32,
+    /// Idempotency key supplied when the payment was made
+    pub idempotency_key: [u8; 16],
 }
 
 impl PaymentCreatedEvent {
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut data = Vec::new();
+    // tag, discriminator, buyer, merchant, operator, amount, order_id, idempotency_key
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8 + 4 + 16;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
 
         // Prepend IX Discriminator for emit_event.
         data.extend_from_slice(EVENT_IX_TAG_LE);
@@ -41,8 +72,13 @@ impl PaymentCreatedEvent {
         data.extend_from_slice(self.operator.as_ref());
         data.extend_from_slice(&self.amount.to_le_bytes());
         data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.idempotency_key);
 
-        data
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
     }
 }
 
@@ -62,28 +98,135 @@ pub struct PaymentClearedEvent {
     pub operator_fee: u64,
     /// Reference to the order_id of the payment
     pub order_id: u32,
+    /// Idempotency key supplied when the payment was made
+    pub idempotency_key: [u8; 16],
 }
 
 impl PaymentClearedEvent {
+    // tag, discriminator, buyer, merchant, operator, amount, operator_fee, order_id,
+    // idempotency_key
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8 + 8 + 4 + 16;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.operator_fee.to_le_bytes());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.idempotency_key);
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+#[derive(ShankType)]
+pub struct PaymentRefundedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Buyer this payment is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this payment is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this payment is associated with
+    pub operator: Pubkey,
+    /// Reference to the amount of the payment
+    pub amount: u64,
+    /// Reference to the order_id of the payment
+    pub order_id: u32,
+    /// Idempotency key supplied when the payment was made
+    pub idempotency_key: [u8; 16],
+}
+
+impl PaymentRefundedEvent {
+    // tag, discriminator, buyer, merchant, operator, amount, order_id, idempotency_key
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8 + 4 + 16;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.idempotency_key);
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+#[derive(ShankType)]
+pub struct CompressedPaymentEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the PaymentTree the leaf was written to
+    pub payment_tree: Pubkey,
+    /// Index of the leaf in the PaymentTree
+    pub leaf_index: u32,
+    /// Reference to the Buyer this payment is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this payment is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this payment is associated with
+    pub operator: Pubkey,
+    /// Reference to the mint of the payment
+    pub mint: Pubkey,
+    /// Reference to the amount of the payment
+    pub amount: u64,
+    /// Reference to the operator fee of the payment (0 until cleared)
+    pub operator_fee: u64,
+    /// Reference to the order_id of the payment
+    pub order_id: u32,
+    /// Reference to the creation timestamp of the payment
+    pub created_at: i64,
+    /// Status of the payment stored in the leaf
+    pub status: u8,
+}
+
+impl CompressedPaymentEvent {
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut data = Vec::new();
 
         // Prepend IX Discriminator for emit_event.
         data.extend_from_slice(EVENT_IX_TAG_LE);
         data.push(self.discriminator);
+        data.extend_from_slice(self.payment_tree.as_ref());
+        data.extend_from_slice(&self.leaf_index.to_le_bytes());
         data.extend_from_slice(self.buyer.as_ref());
         data.extend_from_slice(self.merchant.as_ref());
         data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(self.mint.as_ref());
         data.extend_from_slice(&self.amount.to_le_bytes());
         data.extend_from_slice(&self.operator_fee.to_le_bytes());
         data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.created_at.to_le_bytes());
+        data.push(self.status);
 
         data
     }
 }
 
 #[derive(ShankType)]
-pub struct PaymentRefundedEvent {
+pub struct PaymentChargebackedEvent {
     /// Unique u8 byte for event type.
     pub discriminator: u8,
     /// Reference to the Buyer this payment is associated with
@@ -96,9 +239,189 @@ pub struct PaymentRefundedEvent {
     pub amount: u64,
     /// Reference to the order_id of the payment
     pub order_id: u32,
+    /// Idempotency key supplied when the payment was made
+    pub idempotency_key: [u8; 16],
 }
 
-impl PaymentRefundedEvent {
+impl PaymentChargebackedEvent {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.idempotency_key);
+
+        data
+    }
+}
+
+#[derive(ShankType)]
+pub struct PaymentClosedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Buyer this payment is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this payment is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this payment is associated with
+    pub operator: Pubkey,
+    /// Reference to the order_id of the payment
+    pub order_id: u32,
+    /// Account the payment's rent was returned to
+    pub rent_refunded_to: Pubkey,
+}
+
+impl PaymentClosedEvent {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(self.rent_refunded_to.as_ref());
+
+        data
+    }
+}
+
+#[derive(ShankType)]
+pub struct SettlementBatchExecutedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Merchant the batch was paid out to
+    pub merchant: Pubkey,
+    /// Reference to the config the batch belongs to
+    pub merchant_operator_config: Pubkey,
+    /// Currency of the batch
+    pub mint: Pubkey,
+    /// Amount transferred to the settlement wallet
+    pub amount: u64,
+    /// Number of cleared payments the amount covers
+    pub payment_count: u32,
+}
+
+impl SettlementBatchExecutedEvent {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.mint.as_ref());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.payment_count.to_le_bytes());
+
+        data
+    }
+}
+
+#[derive(ShankType)]
+pub struct OrderCreatedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Buyer this order is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this order is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this order is associated with
+    pub operator: Pubkey,
+    /// Reference to the order_id shared by the order's payments
+    pub order_id: u32,
+    /// Number of payments funding the order
+    pub expected_payments: u8,
+}
+
+impl OrderCreatedEvent {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.push(self.expected_payments);
+
+        data
+    }
+}
+
+#[derive(ShankType)]
+pub struct OrderStatusChangedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Buyer this order is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this order is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this order is associated with
+    pub operator: Pubkey,
+    /// Reference to the order_id shared by the order's payments
+    pub order_id: u32,
+    /// Aggregate status of the order's payments after the change
+    pub status: OrderStatus,
+}
+
+impl OrderStatusChangedEvent {
+    // tag, discriminator, buyer, merchant, operator, order_id, status
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 4 + 1;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.push(self.status as u8);
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+#[derive(ShankType)]
+pub struct RefundApprovedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Buyer this payment is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this payment is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this payment is associated with
+    pub operator: Pubkey,
+    /// Operator authority, delegate or merchant owner that approved the refund
+    pub approver: Pubkey,
+    /// Reference to the amount the buyer can claim
+    pub amount: u64,
+    /// Reference to the order_id of the payment
+    pub order_id: u32,
+    /// Idempotency key supplied when the payment was made
+    pub idempotency_key: [u8; 16],
+}
+
+impl RefundApprovedEvent {
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut data = Vec::new();
 
@@ -108,9 +431,665 @@ impl PaymentRefundedEvent {
         data.extend_from_slice(self.buyer.as_ref());
         data.extend_from_slice(self.merchant.as_ref());
         data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(self.approver.as_ref());
         data.extend_from_slice(&self.amount.to_le_bytes());
         data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.idempotency_key);
 
         data
     }
 }
+
+/// Payment status recorded by PaymentStatusChanged: the Payment account's `Status` plus the
+/// states before the payment is made and after its account is closed.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ShankType)]
+pub enum PaymentLifecycleStatus {
+    Paid = 0,
+    Cleared = 1,
+    Refunded = 2,
+    RefundApproved = 3,
+    /// The payment has not been made yet
+    Uninitialized = 4,
+    /// The Payment account was closed
+    Closed = 5,
+    Authorized = 6,
+}
+
+impl From<&Status> for PaymentLifecycleStatus {
+    fn from(status: &Status) -> Self {
+        match status {
+            Status::Paid => PaymentLifecycleStatus::Paid,
+            Status::Cleared => PaymentLifecycleStatus::Cleared,
+            Status::Refunded => PaymentLifecycleStatus::Refunded,
+            Status::RefundApproved => PaymentLifecycleStatus::RefundApproved,
+            Status::Authorized => PaymentLifecycleStatus::Authorized,
+        }
+    }
+}
+
+/// Emitted on every payment status transition, alongside the transition's specific event.
+#[derive(ShankType)]
+pub struct PaymentStatusChangedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Buyer this payment is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this payment is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this payment is associated with
+    pub operator: Pubkey,
+    /// Reference to the order_id of the payment
+    pub order_id: u32,
+    /// Status before the transition
+    pub from: PaymentLifecycleStatus,
+    /// Status after the transition
+    pub to: PaymentLifecycleStatus,
+    /// Signer that made the transition
+    pub actor: Pubkey,
+}
+
+impl PaymentStatusChangedEvent {
+    // tag, discriminator, buyer, merchant, operator, order_id, from, to, actor
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 4 + 1 + 1 + 32;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.push(self.from as u8);
+        data.push(self.to as u8);
+        data.extend_from_slice(self.actor.as_ref());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted when a MerchantOperatorConfig is created, with the hash of its terms.
+#[derive(ShankType)]
+pub struct MerchantOperatorConfigInitializedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// The MerchantOperatorConfig PDA that was created
+    pub config: Pubkey,
+    /// Reference to the Merchant this config is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this config is associated with
+    pub operator: Pubkey,
+    /// Version of the config
+    pub version: u32,
+    /// SHA-256 of the config's terms, as stored on the config
+    pub config_hash: [u8; 32],
+}
+
+impl MerchantOperatorConfigInitializedEvent {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.config.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.version.to_le_bytes());
+        data.extend_from_slice(&self.config_hash);
+
+        data
+    }
+}
+
+/// Emitted by ClearPayment when a platform fee policy pays the platform its fee.
+#[derive(ShankType)]
+pub struct PlatformFeeCollectedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Buyer this payment is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this payment is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this payment is associated with
+    pub operator: Pubkey,
+    /// The platform the fee was paid to
+    pub platform: Pubkey,
+    /// Reference to the order_id of the payment
+    pub order_id: u32,
+    /// Platform fee taken from the payment
+    pub amount: u64,
+}
+
+impl PlatformFeeCollectedEvent {
+    // tag, discriminator, buyer, merchant, operator, platform, order_id, amount
+    pub const LEN: usize = 8 + 1 + 32 * 4 + 4 + 8;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(self.platform.as_ref());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by CapturePayment when an authorized payment is captured.
+#[derive(ShankType)]
+pub struct PaymentCapturedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Buyer this payment is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this payment is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this payment is associated with
+    pub operator: Pubkey,
+    /// Amount captured, now the payment's amount
+    pub amount: u64,
+    /// Rest of the authorized amount, released back to the buyer
+    pub released_amount: u64,
+    /// Reference to the order_id of the payment
+    pub order_id: u32,
+    /// Idempotency key supplied when the payment was made
+    pub idempotency_key: [u8; 16],
+}
+
+impl PaymentCapturedEvent {
+    // tag, discriminator, buyer, merchant, operator, amount, released_amount, order_id,
+    // idempotency_key
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8 + 8 + 4 + 16;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.released_amount.to_le_bytes());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.idempotency_key);
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by InitializeMerchant and InitializeMerchantWithConfig when a merchant is created.
+#[derive(ShankType)]
+pub struct MerchantInitializedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// The Merchant PDA that was created
+    pub merchant: Pubkey,
+    /// Authority of the merchant
+    pub authority: Pubkey,
+    /// Wallet the merchant is settled to
+    pub settlement_wallet: Pubkey,
+    /// Delay before a proposed settlement wallet can be committed, 0 for none
+    pub settlement_wallet_timelock_seconds: u64,
+}
+
+impl MerchantInitializedEvent {
+    // tag, discriminator, merchant, authority, settlement_wallet,
+    // settlement_wallet_timelock_seconds
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.authority.as_ref());
+        data.extend_from_slice(self.settlement_wallet.as_ref());
+        data.extend_from_slice(&self.settlement_wallet_timelock_seconds.to_le_bytes());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by UpdateMerchantSettlementWallet and CommitSettlementWallet when a merchant's
+/// settlement wallet changes.
+#[derive(ShankType)]
+pub struct MerchantSettlementWalletUpdatedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Merchant whose settlement wallet changed
+    pub merchant: Pubkey,
+    /// Settlement wallet before the update
+    pub old_settlement_wallet: Pubkey,
+    /// Settlement wallet after the update
+    pub new_settlement_wallet: Pubkey,
+}
+
+impl MerchantSettlementWalletUpdatedEvent {
+    // tag, discriminator, merchant, old_settlement_wallet, new_settlement_wallet
+    pub const LEN: usize = 8 + 1 + 32 * 3;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.old_settlement_wallet.as_ref());
+        data.extend_from_slice(self.new_settlement_wallet.as_ref());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by AcceptMerchantAuthority when a merchant's authority changes hands.
+#[derive(ShankType)]
+pub struct MerchantAuthorityUpdatedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Merchant whose authority changed
+    pub merchant: Pubkey,
+    /// Authority before the update
+    pub old_authority: Pubkey,
+    /// Authority after the update
+    pub new_authority: Pubkey,
+}
+
+impl MerchantAuthorityUpdatedEvent {
+    // tag, discriminator, merchant, old_authority, new_authority
+    pub const LEN: usize = 8 + 1 + 32 * 3;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.old_authority.as_ref());
+        data.extend_from_slice(self.new_authority.as_ref());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by AcceptOperatorAuthority when an operator's authority changes hands.
+#[derive(ShankType)]
+pub struct OperatorAuthorityUpdatedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Operator whose authority changed
+    pub operator: Pubkey,
+    /// Authority before the update
+    pub old_authority: Pubkey,
+    /// Authority after the update
+    pub new_authority: Pubkey,
+}
+
+impl OperatorAuthorityUpdatedEvent {
+    // tag, discriminator, operator, old_authority, new_authority
+    pub const LEN: usize = 8 + 1 + 32 * 3;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(self.old_authority.as_ref());
+        data.extend_from_slice(self.new_authority.as_ref());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by MigratePayment when a payment moves to another config of its merchant and
+/// operator, so indexers can follow it to its new Payment PDA.
+#[derive(ShankType)]
+pub struct PaymentMigratedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Buyer this payment is associated with
+    pub buyer: Pubkey,
+    /// Reference to the Merchant this payment is associated with
+    pub merchant: Pubkey,
+    /// Reference to the Operator this payment is associated with
+    pub operator: Pubkey,
+    /// Reference to the order_id of the payment, the same under both configs
+    pub order_id: u32,
+    /// Amount moved between the configs' escrows
+    pub amount: u64,
+    /// Payment PDA under the old config, closed
+    pub old_payment: Pubkey,
+    /// Payment PDA under the new config
+    pub new_payment: Pubkey,
+    /// Config the payment was migrated from
+    pub old_merchant_operator_config: Pubkey,
+    /// Config the payment was migrated to
+    pub new_merchant_operator_config: Pubkey,
+}
+
+impl PaymentMigratedEvent {
+    // tag, discriminator, buyer, merchant, operator, order_id, amount, old_payment,
+    // new_payment, old_merchant_operator_config, new_merchant_operator_config
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 4 + 8 + 32 * 4;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(self.old_payment.as_ref());
+        data.extend_from_slice(self.new_payment.as_ref());
+        data.extend_from_slice(self.old_merchant_operator_config.as_ref());
+        data.extend_from_slice(self.new_merchant_operator_config.as_ref());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by SetWebhookCommitment, so the history of a config's webhook commitments can be
+/// audited.
+#[derive(ShankType)]
+pub struct WebhookCommitmentUpdatedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Merchant Operator Config the commitment belongs to
+    pub merchant_operator_config: Pubkey,
+    /// Commitment before the update, zero when the account was created
+    pub old_commitment: [u8; 32],
+    /// Commitment after the update
+    pub new_commitment: [u8; 32],
+}
+
+impl WebhookCommitmentUpdatedEvent {
+    // tag, discriminator, merchant_operator_config, old_commitment, new_commitment
+    pub const LEN: usize = 8 + 1 + 32 * 3;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(&self.old_commitment);
+        data.extend_from_slice(&self.new_commitment);
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by AnnotatePayment with the payment's new annotation, so a payment's review
+/// history can be audited.
+#[derive(ShankType)]
+pub struct PaymentAnnotatedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// The annotated Payment PDA
+    pub payment: Pubkey,
+    /// Reference to the Merchant Operator Config the payment was made under
+    pub merchant_operator_config: Pubkey,
+    /// Operator owner or delegate that signed the annotation
+    pub annotated_by: Pubkey,
+    /// The payment's new review flag
+    pub flag: AnnotationFlag,
+    /// Hash of the operator's off-chain note, zero for none
+    pub note_hash: [u8; 32],
+}
+
+impl PaymentAnnotatedEvent {
+    // tag, discriminator, payment, merchant_operator_config, annotated_by, flag, note_hash
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 1 + 32;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.payment.as_ref());
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.annotated_by.as_ref());
+        data.push(self.flag as u8);
+        data.extend_from_slice(&self.note_hash);
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by DecommissionConfig when a config stops taking new payments, so indexers can stop
+/// routing checkouts to it.
+#[derive(ShankType)]
+pub struct ConfigDecommissionedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// The decommissioned Merchant Operator Config PDA
+    pub merchant_operator_config: Pubkey,
+    /// Reference to the config's Merchant PDA
+    pub merchant: Pubkey,
+    /// Reference to the config's Operator PDA
+    pub operator: Pubkey,
+    /// Merchant or operator owner that signed the decommission
+    pub decommissioned_by: Pubkey,
+    /// Unix timestamp new payments stopped at
+    pub decommissioned_at: i64,
+}
+
+impl ConfigDecommissionedEvent {
+    // tag, discriminator, merchant_operator_config, merchant, operator, decommissioned_by,
+    // decommissioned_at
+    pub const LEN: usize = 8 + 1 + 32 * 4 + 8;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(self.decommissioned_by.as_ref());
+        data.extend_from_slice(&self.decommissioned_at.to_le_bytes());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by CloseMerchantOperatorConfig once a decommissioned config is closed and its rent
+/// returned to the merchant.
+#[derive(ShankType)]
+pub struct MerchantOperatorConfigClosedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// The closed Merchant Operator Config PDA
+    pub merchant_operator_config: Pubkey,
+    /// Reference to the config's Merchant PDA
+    pub merchant: Pubkey,
+    /// Reference to the config's Operator PDA
+    pub operator: Pubkey,
+    /// Rent lamports returned to the merchant authority
+    pub rent_reclaimed: u64,
+}
+
+impl MerchantOperatorConfigClosedEvent {
+    // tag, discriminator, merchant_operator_config, merchant, operator, rent_reclaimed
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.rent_reclaimed.to_le_bytes());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by ProposeSettlementWallet, so a pending settlement wallet change is visible while
+/// its timelock runs.
+#[derive(ShankType)]
+pub struct SettlementWalletProposedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Merchant whose settlement wallet would change
+    pub merchant: Pubkey,
+    /// Settlement wallet in use until the proposal is committed
+    pub settlement_wallet: Pubkey,
+    /// Proposed settlement wallet
+    pub pending_settlement_wallet: Pubkey,
+    /// Unix timestamp from which CommitSettlementWallet can apply the proposal
+    pub unlocks_at: i64,
+}
+
+impl SettlementWalletProposedEvent {
+    // tag, discriminator, merchant, settlement_wallet, pending_settlement_wallet, unlocks_at
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.settlement_wallet.as_ref());
+        data.extend_from_slice(self.pending_settlement_wallet.as_ref());
+        data.extend_from_slice(&self.unlocks_at.to_le_bytes());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
+
+/// Emitted by SetSettlementWalletTimelock when a merchant raises its settlement wallet
+/// timelock.
+#[derive(ShankType)]
+pub struct SettlementWalletTimelockRaisedEvent {
+    /// Unique u8 byte for event type.
+    pub discriminator: u8,
+    /// Reference to the Merchant whose timelock was raised
+    pub merchant: Pubkey,
+    /// Timelock before the change, in seconds
+    pub old_timelock_seconds: u64,
+    /// Timelock after the change, in seconds
+    pub new_timelock_seconds: u64,
+    /// Unlock time of the pending settlement wallet, pushed out by the increase (0 if none)
+    pub pending_settlement_wallet_unlocks_at: i64,
+}
+
+impl SettlementWalletTimelockRaisedEvent {
+    // tag, discriminator, merchant, old_timelock_seconds, new_timelock_seconds,
+    // pending_settlement_wallet_unlocks_at
+    pub const LEN: usize = 8 + 1 + 32 + 8 * 3;
+
+    /// Serializes the event into a stack buffer, so emitting it does not allocate.
+    pub fn to_array(&self) -> [u8; Self::LEN] {
+        let mut data = ByteWriter::<{ Self::LEN }>::new();
+
+        // Prepend IX Discriminator for emit_event.
+        data.extend_from_slice(EVENT_IX_TAG_LE);
+        data.push(self.discriminator);
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(&self.old_timelock_seconds.to_le_bytes());
+        data.extend_from_slice(&self.new_timelock_seconds.to_le_bytes());
+        data.extend_from_slice(&self.pending_settlement_wallet_unlocks_at.to_le_bytes());
+
+        data.into_array()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_array().to_vec()
+    }
+}
@@ -5,7 +5,9 @@ use alloc::vec::Vec;
 use pinocchio::pubkey::Pubkey;
 use shank::ShankInstruction;
 
-use crate::state::{FeeType, PolicyData};
+use crate::state::{
+    AnnotationFlag, DenylistProof, EncryptedNote, FeeOverride, FeeType, PolicyData,
+};
 
 /// Instructions for the Solana Commerce Program. This
 /// is currently not used in the program business logic, but
@@ -13,22 +15,30 @@ use crate::state::{FeeType, PolicyData};
 #[repr(C, u8)]
 #[derive(Clone, Debug, PartialEq, ShankInstruction)]
 pub enum CommerceProgramInstruction {
-    // Initialize Merchant PDA
+    // Initialize Merchant PDA. Emits MerchantInitialized.
     #[account(0, writable, signer, name = "payer")]
     #[account(1, signer, name = "authority")]
     #[account(2, writable, name = "merchant")]
     #[account(3, name = "settlement_wallet")]
     #[account(4, name = "system_program")]
-    InitializeMerchant { bump: u8 } = 0,
+    #[account(5, name = "event_authority", desc = "Event authority PDA")]
+    #[account(6, name = "commerce_program", desc = "Commerce Program ID")]
+    InitializeMerchant {
+        bump: u8,
+        settlement_wallet_timelock_seconds: u64,
+    } = 0,
 
-    /// Creates the Operator PDA account for an Operator.
+    /// Creates the Operator PDA account for an Operator, at the canonical bump derived on-chain.
     #[account(0, writable, signer, name = "payer")]
     #[account(1, writable, name = "operator")]
     #[account(2, signer, name = "authority")]
     #[account(3, name = "system_program")]
-    CreateOperator { bump: u8 } = 1,
+    CreateOperator = 1,
 
-    // Initialize MerchantOperatorConfig PDA
+    // Initialize MerchantOperatorConfig PDA, storing the hash of its terms. Emits
+    // MerchantOperatorConfigInitialized. Mint accounts for the accepted currencies follow as
+    // remaining accounts, then optionally the operator's ConfigRegistryPage PDA, which the
+    // config is listed on.
     #[account(0, writable, signer, name = "payer")]
     #[account(1, signer, name = "authority", desc = "Authority of the merchant")]
     #[account(2, name = "merchant", desc = "Merchant PDA")]
@@ -40,6 +50,8 @@ pub enum CommerceProgramInstruction {
         desc = "The MerchantOperatorConfig PDA being initialized"
     )]
     #[account(5, name = "system_program")]
+    #[account(6, name = "event_authority", desc = "Event authority PDA")]
+    #[account(7, name = "commerce_program", desc = "Commerce Program ID")]
     InitializeMerchantOperatorConfig {
         version: u32,
         bump: u8,
@@ -50,7 +62,24 @@ pub enum CommerceProgramInstruction {
         accepted_currencies: Vec<Pubkey>,
     } = 2,
 
-    // Make Payment
+    // Make Payment. Up to two Solana Pay reference keys may be passed read-only as the last
+    // remaining accounts; they are stored on the payment. Under a RiskPolicy, pass the
+    // RiskState PDAs its daily limits need before the Order and references. A Quote PDA passed
+    // after the Order, before references, fixes the payment's amount until it expires. Under a
+    // BuyerIndexPolicy, pass the buyer's BuyerIndex PDA before any RiskState accounts. An
+    // operator rent treasury passed as a writable signer first remaining account (before the
+    // OperatorDelegate PDA) pays the payment's rent instead of the payer and is recorded on it.
+    // For a Token-2022 mint with a transfer hook, the hook program, its extra accounts and its
+    // ExtraAccountMetaList PDA lead the remaining accounts. Under a CompliancePolicy, pass the
+    // compliance program, followed by the accounts forwarded to it, after the references.
+    // With `authorize`, the payment only places a hold on `amount`, see CapturePayment. The
+    // operator's OperatorFreezeList PDA, passed before the BuyerIndex, rejects frozen buyers,
+    // and must be passed once the operator has one.
+    // With a `note`, its PaymentNote PDA leads the remaining accounts, ahead of any transfer
+    // hook accounts, and is created holding the note, funded like the payment.
+    // Under a DirectSettlementPolicy, pass the operator owner's fee ATA, then the platform's
+    // settlement ATA under a PlatformFeePolicy, right after any transfer hook accounts; payments
+    // within its cap pay the fees and the merchant at once and are created Cleared.
     #[account(0, writable, signer, name = "payer")]
     #[account(1, writable, name = "payment")]
     #[account(2, signer, name = "operator_authority")]
@@ -60,53 +89,89 @@ pub enum CommerceProgramInstruction {
     #[account(6, writable, name = "merchant_operator_config")]
     #[account(7, name = "mint")]
     #[account(8, writable, name = "buyer_ata")]
-    #[account(9, writable, name = "merchant_escrow_ata")]
-    #[account(10, writable, name = "merchant_settlement_ata")]
-    #[account(11, name = "token_program")]
-    #[account(12, name = "system_program")]
-    #[account(13, name = "event_authority", desc = "Event authority PDA")]
-    #[account(14, name = "commerce_program", desc = "Commerce Program ID")]
+    #[account(9, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(10, writable, name = "merchant_escrow_ata")]
+    #[account(11, writable, name = "merchant_settlement_ata")]
+    #[account(
+        12,
+        name = "settlement_wallet",
+        desc = "Merchant settlement wallet, owner of merchant_settlement_ata"
+    )]
+    #[account(13, name = "token_program")]
+    #[account(14, name = "associated_token_program")]
+    #[account(15, name = "system_program")]
+    #[account(16, name = "event_authority", desc = "Event authority PDA")]
+    #[account(17, name = "commerce_program", desc = "Commerce Program ID")]
     MakePayment {
         order_id: u32,
         amount: u64,
-        bump: u8,
+        /// Operator-supplied key stored on the payment so retried checkouts can be matched
+        idempotency_key: [u8; 16],
+        /// Operator fee charged when the payment is cleared instead of the config's fee,
+        /// bounded by the config's FeeOverridePolicy
+        fee_override: Option<FeeOverride>,
+        /// Hold `amount` in escrow as an Authorized payment, for CapturePayment to capture
+        authorize: bool,
+        /// Proof the buyer is not on the config's DenylistPolicy, required when it has one
+        denylist_proof: Option<DenylistProof>,
+        /// Note encrypted off-chain to the merchant's key, stored in the payment's PaymentNote
+        note: Option<EncryptedNote>,
     } = 3,
 
-    // Clear Payment
+    // Clear Payment. Under a memo policy, pass the memo program as the last remaining account.
+    // Under a callback policy, pass the callback program, followed by the accounts forwarded
+    // to it, before the memo program.
+    // Passing the config's SettlementBatch PDA before it leaves the merchant amount in escrow
+    // for ExecuteSettlementBatch instead of transferring it to the settlement wallet.
+    // Transfer hook accounts lead the remaining accounts as for MakePayment.
+    // Under a split settlement policy, pass the destination ATAs in the policy's order (before
+    // any Order PDA) to split the merchant amount across them instead; batches can't be used.
+    // Under a platform fee policy, pass the platform's settlement ATA (before any split
+    // settlement destinations) to pay the platform its fee out of the merchant amount.
+    // Under an operator failover policy, the merchant authority may sign as operator_authority
+    // once the operator has gone longer than the policy's threshold without a Heartbeat.
     #[account(0, writable, signer, name = "payer")]
     #[account(1, writable, name = "payment", desc = "New Payment PDA being created")]
     #[account(2, signer, name = "operator_authority")]
     #[account(3, name = "buyer")]
     #[account(4, name = "merchant", desc = "Merchant PDA")]
     #[account(5, name = "operator", desc = "Operator PDA")]
-    #[account(6, name = "merchant_operator_config")]
+    #[account(6, writable, name = "merchant_operator_config")]
     #[account(7, name = "mint")]
+    #[account(8, name = "escrow_authority", desc = "Escrow authority PDA")]
     #[account(
-        8,
+        9,
         writable,
         name = "merchant_escrow_ata",
-        desc = "Merchant Escrow ATA (Merchant PDA is owner)"
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
     )]
     #[account(
-        9,
+        10,
         writable,
         name = "merchant_settlement_ata",
         desc = "Merchant Settlement ATA (Merchant settlement wallet is owner)"
     )]
     #[account(
-        10,
+        11,
         writable,
         name = "operator_settlement_ata",
         desc = "Operator Settlement ATA (Operator owner is owner)"
     )]
-    #[account(11, name = "token_program")]
-    #[account(12, name = "associated_token_program")]
-    #[account(13, name = "system_program")]
-    #[account(14, name = "event_authority", desc = "Event authority PDA")]
-    #[account(15, name = "commerce_program", desc = "Commerce Program ID")]
+    #[account(12, name = "token_program")]
+    #[account(13, name = "associated_token_program")]
+    #[account(14, name = "system_program")]
+    #[account(15, name = "event_authority", desc = "Event authority PDA")]
+    #[account(16, name = "commerce_program", desc = "Commerce Program ID")]
     ClearPayment = 4,
 
-    // Refund Payment
+    // Refund Payment. Under a memo policy, pass the memo program as the last remaining account.
+    // Under a callback policy, pass the callback program, followed by the accounts forwarded
+    // to it, before the memo program. Transfer hook accounts lead the remaining accounts as
+    // for MakePayment. With `close_escrow`, a refund that leaves the escrow ATA empty with no
+    // other open payment of the config and mint also closes it, refunding its rent to the
+    // payer, or to an operator rent treasury passed as a writable signer after the transfer
+    // hook accounts (before the OperatorDelegate PDA). The operator's OperatorFreezeList PDA, passed before the Order,
+    // rejects refunds to frozen buyers, and must be passed once the operator has one.
     #[account(0, writable, signer, name = "payer")]
     #[account(1, writable, name = "payment", desc = "Payment PDA being updated")]
     #[account(2, signer, name = "operator_authority")]
@@ -115,60 +180,1233 @@ pub enum CommerceProgramInstruction {
     #[account(5, name = "operator", desc = "Operator PDA")]
     #[account(
         6,
+        writable,
         name = "merchant_operator_config",
         desc = "Merchant Operator Config PDA"
     )]
     #[account(7, name = "mint")]
+    #[account(8, name = "escrow_authority", desc = "Escrow authority PDA")]
     #[account(
-        8,
+        9,
         writable,
         name = "merchant_escrow_ata",
-        desc = "Merchant Escrow ATA (Merchant PDA is owner)"
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
     )]
-    #[account(9, writable, name = "buyer_ata")]
-    #[account(10, name = "token_program")]
-    #[account(11, name = "system_program")]
-    #[account(12, name = "event_authority", desc = "Event authority PDA")]
-    #[account(13, name = "commerce_program", desc = "Commerce Program ID")]
-    RefundPayment = 5,
+    #[account(10, writable, name = "buyer_ata")]
+    #[account(11, name = "token_program")]
+    #[account(12, name = "system_program")]
+    #[account(13, name = "event_authority", desc = "Event authority PDA")]
+    #[account(14, name = "commerce_program", desc = "Commerce Program ID")]
+    RefundPayment { close_escrow: bool } = 5,
 
-    // Update Merchant Settlement Wallet
+    // Update Merchant Settlement Wallet (merchants without a settlement wallet timelock).
+    // Emits MerchantSettlementWalletUpdated.
     #[account(0, writable, signer, name = "payer")]
     #[account(1, writable, signer, name = "authority")]
     #[account(2, writable, name = "merchant", desc = "Merchant PDA")]
     #[account(3, name = "new_settlement_wallet")]
+    #[account(4, name = "event_authority", desc = "Event authority PDA")]
+    #[account(5, name = "commerce_program", desc = "Commerce Program ID")]
     UpdateMerchantSettlementWallet = 6,
 
-    // Update Merchant Authority
+    // Propose Merchant Authority (step 1 of 2)
     #[account(0, writable, signer, name = "payer")]
     #[account(1, writable, signer, name = "authority")]
     #[account(2, writable, name = "merchant", desc = "Merchant PDA")]
-    #[account(3, name = "new_authority")]
-    UpdateMerchantAuthority = 7,
+    #[account(3, name = "new_authority", desc = "Must sign AcceptMerchantAuthority")]
+    ProposeMerchantAuthority = 7,
 
-    // Update Operator Authority
+    // Propose Operator Authority (step 1 of 2)
     #[account(0, writable, signer, name = "payer")]
     #[account(1, writable, signer, name = "authority")]
     #[account(2, writable, name = "operator", desc = "Operator PDA")]
-    #[account(3, name = "new_operator_authority")]
-    UpdateOperatorAuthority = 8,
+    #[account(
+        3,
+        name = "new_operator_authority",
+        desc = "Must sign AcceptOperatorAuthority"
+    )]
+    ProposeOperatorAuthority = 8,
 
-    // Close Payment
+    // Close Payment. Rent is refunded to the payer, or to the payment's rent_payer when it
+    // has one, passed writable as the first remaining account (before the OperatorDelegate PDA).
+    // Under a RentRebatePolicy the buyer receives its share of the rent first.
     #[account(0, writable, signer, name = "payer")]
     #[account(1, writable, name = "payment", desc = "Payment PDA to close")]
     #[account(2, signer, name = "operator_authority")]
     #[account(3, name = "operator", desc = "Operator PDA")]
     #[account(4, name = "merchant", desc = "Merchant PDA")]
-    #[account(5, name = "buyer", desc = "Buyer account")]
+    #[account(
+        5,
+        writable,
+        name = "buyer",
+        desc = "Buyer account, credited its rent rebate under a RentRebatePolicy"
+    )]
     #[account(
         6,
+        writable,
         name = "merchant_operator_config",
-        desc = "Merchant Operator Config PDA"
+        desc = "Merchant Operator Config PDA, which stops counting the payment account"
     )]
     #[account(7, name = "mint", desc = "Token mint")]
     #[account(8, name = "system_program")]
+    #[account(9, name = "event_authority", desc = "Event authority PDA")]
+    #[account(10, name = "commerce_program", desc = "Commerce Program ID")]
     ClosePayment = 9,
 
+    // Initialize PaymentTree PDA for compressed payments
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "operator_authority")]
+    #[account(2, name = "operator", desc = "Operator PDA")]
+    #[account(
+        3,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "payment_tree",
+        desc = "The PaymentTree PDA being initialized"
+    )]
+    #[account(5, name = "system_program")]
+    InitializePaymentTree { bump: u8 } = 10,
+
+    // Make Compressed Payment. Under a CompliancePolicy, pass the compliance program, followed
+    // by the accounts forwarded to it, after any RiskState PDAs. The operator's
+    // OperatorFreezeList PDA, passed before the RiskState PDAs, rejects frozen buyers as for
+    // MakePayment.
+    #[account(
+        0,
+        writable,
+        name = "payment_tree",
+        desc = "PaymentTree PDA the payment leaf is appended to"
+    )]
+    #[account(1, signer, name = "operator_authority")]
+    #[account(2, signer, name = "buyer")]
+    #[account(3, name = "operator")]
+    #[account(4, name = "merchant", desc = "Merchant PDA")]
+    #[account(5, writable, name = "merchant_operator_config")]
+    #[account(6, name = "mint")]
+    #[account(7, writable, name = "buyer_ata")]
+    #[account(8, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(9, writable, name = "merchant_escrow_ata")]
+    #[account(10, writable, name = "merchant_settlement_ata")]
+    #[account(11, name = "token_program")]
+    #[account(12, name = "event_authority", desc = "Event authority PDA")]
+    #[account(13, name = "commerce_program", desc = "Commerce Program ID")]
+    MakePaymentCompressed {
+        order_id: u32,
+        amount: u64,
+        /// Proof the buyer is not on the config's DenylistPolicy, required when it has one
+        denylist_proof: Option<DenylistProof>,
+    } = 11,
+
+    // Clear Compressed Payment
+    #[account(0, writable, signer, name = "payer")]
+    #[account(
+        1,
+        writable,
+        name = "payment_tree",
+        desc = "PaymentTree PDA holding the payment leaf"
+    )]
+    #[account(2, signer, name = "operator_authority")]
+    #[account(3, name = "buyer")]
+    #[account(4, name = "merchant", desc = "Merchant PDA")]
+    #[account(5, name = "operator", desc = "Operator PDA")]
+    #[account(6, writable, name = "merchant_operator_config")]
+    #[account(7, name = "mint")]
+    #[account(8, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        9,
+        writable,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
+    )]
+    #[account(
+        10,
+        writable,
+        name = "merchant_settlement_ata",
+        desc = "Merchant Settlement ATA (Merchant settlement wallet is owner)"
+    )]
+    #[account(
+        11,
+        writable,
+        name = "operator_settlement_ata",
+        desc = "Operator Settlement ATA (Operator owner is owner)"
+    )]
+    #[account(12, name = "token_program")]
+    #[account(13, name = "associated_token_program")]
+    #[account(14, name = "system_program")]
+    #[account(15, name = "event_authority", desc = "Event authority PDA")]
+    #[account(16, name = "commerce_program", desc = "Commerce Program ID")]
+    ClearPaymentCompressed {
+        leaf_index: u32,
+        order_id: u32,
+        amount: u64,
+        created_at: i64,
+        proof: Vec<[u8; 32]>,
+    } = 12,
+
+    // Accept Merchant Authority (step 2 of 2). Emits MerchantAuthorityUpdated.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "new_authority", desc = "Pending merchant authority")]
+    #[account(2, writable, name = "merchant", desc = "Merchant PDA")]
+    #[account(3, name = "event_authority", desc = "Event authority PDA")]
+    #[account(4, name = "commerce_program", desc = "Commerce Program ID")]
+    AcceptMerchantAuthority = 13,
+
+    // Accept Operator Authority (step 2 of 2). Emits OperatorAuthorityUpdated.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(
+        1,
+        signer,
+        name = "new_operator_authority",
+        desc = "Pending operator authority"
+    )]
+    #[account(2, writable, name = "operator", desc = "Operator PDA")]
+    #[account(3, name = "event_authority", desc = "Event authority PDA")]
+    #[account(4, name = "commerce_program", desc = "Commerce Program ID")]
+    AcceptOperatorAuthority = 14,
+
+    // Initialize MerchantStats PDA. Once created, pass it as the first remaining account
+    // to MakePayment, ClearPayment, RefundPayment and ClosePayment to keep it up to date
+    // (after the OperatorDelegate PDA when a delegate signs).
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority", desc = "Authority of the merchant")]
+    #[account(2, name = "merchant", desc = "Merchant PDA")]
+    #[account(3, name = "mint", desc = "Currency the stats are tracked in")]
+    #[account(
+        4,
+        writable,
+        name = "merchant_stats",
+        desc = "The MerchantStats PDA being initialized"
+    )]
+    #[account(5, name = "system_program")]
+    InitializeMerchantStats { bump: u8 } = 15,
+
+    // Refund an auto-settled payment from the settlement wallet. The settlement wallet must
+    // first approve the refund delegate PDA on its settlement ATA; refunds are limited to
+    // the config's refund window and the remaining allowance. The operator's
+    // OperatorFreezeList PDA rejects refunds to frozen buyers as for RefundPayment, before the
+    // Order PDA of a payment made against an order, which is then required.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, writable, name = "payment", desc = "Payment PDA being updated")]
+    #[account(2, signer, name = "operator_authority")]
+    #[account(3, name = "buyer", desc = "Refund destination owner")]
+    #[account(4, name = "merchant", desc = "Merchant PDA")]
+    #[account(5, name = "operator", desc = "Operator PDA")]
+    #[account(
+        6,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(7, name = "mint")]
+    #[account(
+        8,
+        writable,
+        name = "merchant_settlement_ata",
+        desc = "Merchant Settlement ATA (Merchant settlement wallet is owner)"
+    )]
+    #[account(9, writable, name = "buyer_ata")]
+    #[account(
+        10,
+        name = "refund_delegate",
+        desc = "Refund delegate PDA approved on the settlement ATA"
+    )]
+    #[account(11, name = "token_program")]
+    #[account(12, name = "system_program")]
+    #[account(13, name = "event_authority", desc = "Event authority PDA")]
+    #[account(14, name = "commerce_program", desc = "Commerce Program ID")]
+    RefundSettledPayment { refund_delegate_bump: u8 } = 16,
+
+    // Authorize an additional operator signing key with scoped permissions. When a delegate
+    // signs as operator_authority, pass its OperatorDelegate PDA as the first remaining account.
+    // Refunds the delegate signs are capped at max_refund_amount each and
+    // max_daily_refund_amount per day (0 for no limit); under a daily limit the
+    // OperatorDelegate PDA must be passed writable to count them.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority", desc = "Authority of the operator")]
+    #[account(2, name = "operator", desc = "Operator PDA")]
+    #[account(
+        3,
+        writable,
+        name = "operator_delegate",
+        desc = "The OperatorDelegate PDA being initialized"
+    )]
+    #[account(4, name = "delegate", desc = "The delegated signing key")]
+    #[account(5, name = "system_program")]
+    InitializeOperatorDelegate {
+        bump: u8,
+        can_make_payment: bool,
+        can_clear: bool,
+        can_refund: bool,
+        can_close: bool,
+        max_refund_amount: u64,
+        max_daily_refund_amount: u64,
+    } = 17,
+
+    // Replace a delegate's permissions and refund limits
+    #[account(0, signer, name = "authority", desc = "Authority of the operator")]
+    #[account(1, name = "operator", desc = "Operator PDA")]
+    #[account(2, writable, name = "operator_delegate", desc = "OperatorDelegate PDA")]
+    UpdateOperatorDelegate {
+        can_make_payment: bool,
+        can_clear: bool,
+        can_refund: bool,
+        can_close: bool,
+        max_refund_amount: u64,
+        max_daily_refund_amount: u64,
+    } = 18,
+
+    // Revoke a delegate, closing its OperatorDelegate PDA and returning the rent to the authority
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "authority",
+        desc = "Authority of the operator"
+    )]
+    #[account(1, name = "operator", desc = "Operator PDA")]
+    #[account(2, writable, name = "operator_delegate", desc = "OperatorDelegate PDA")]
+    RevokeOperatorDelegate = 19,
+
+    // Clear a payment into the conversion policy's settlement mint. The operator fee is paid in
+    // the payment mint and the merchant amount is swapped through the policy's swap adapter.
+    // quoted_amount_out may not be below the merchant amount at the posted ConversionRate. The
+    // last num_swap_accounts remaining accounts are forwarded to the swap program, after the
+    // optional OperatorDelegate PDA, MerchantStats PDA, Order PDA, callback program and its
+    // accounts, and memo program.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, writable, name = "payment", desc = "Payment PDA being updated")]
+    #[account(2, signer, name = "operator_authority")]
+    #[account(3, name = "buyer")]
+    #[account(4, name = "merchant", desc = "Merchant PDA")]
+    #[account(5, name = "operator", desc = "Operator PDA")]
+    #[account(6, writable, name = "merchant_operator_config")]
+    #[account(7, name = "mint", desc = "Payment mint")]
+    #[account(8, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        9,
+        writable,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner), the swap's source"
+    )]
+    #[account(
+        10,
+        name = "settlement_mint",
+        desc = "The conversion policy's settlement mint"
+    )]
+    #[account(
+        11,
+        name = "conversion_rate",
+        desc = "ConversionRate PDA from the payment mint into the settlement mint, bounding the quote"
+    )]
+    #[account(
+        12,
+        writable,
+        name = "merchant_settlement_ata",
+        desc = "Settlement wallet's ATA for the settlement mint, the swap's destination"
+    )]
+    #[account(
+        13,
+        writable,
+        name = "operator_settlement_ata",
+        desc = "Operator Settlement ATA for the payment mint (Operator owner is owner)"
+    )]
+    #[account(14, name = "token_program")]
+    #[account(15, name = "associated_token_program")]
+    #[account(16, name = "system_program")]
+    #[account(
+        17,
+        name = "swap_program",
+        desc = "Swap program of the conversion policy"
+    )]
+    #[account(18, name = "event_authority", desc = "Event authority PDA")]
+    #[account(19, name = "commerce_program", desc = "Commerce Program ID")]
+    ClearPaymentWithConversion {
+        /// Swap output quoted off-chain, bounded below by the policy's max slippage
+        quoted_amount_out: u64,
+        num_swap_accounts: u8,
+        /// Instruction data of the swap program's exact-input route
+        swap_data: Vec<u8>,
+    } = 20,
+
+    // Force a full refund of a Paid payment during its chargeback window. Signed by the
+    // config's chargeback arbiter instead of the operator. Pass the MerchantStats PDA, the
+    // operator's OperatorFreezeList PDA and the Order PDA as for RefundPayment and, under a memo
+    // policy, the memo program (last) as remaining accounts.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, writable, name = "payment", desc = "Payment PDA being updated")]
+    #[account(2, signer, name = "arbiter", desc = "The chargeback policy's arbiter")]
+    #[account(3, name = "buyer", desc = "Refund destination owner")]
+    #[account(4, name = "merchant", desc = "Merchant PDA")]
+    #[account(5, name = "operator", desc = "Operator PDA")]
+    #[account(
+        6,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(7, name = "mint")]
+    #[account(8, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        9,
+        writable,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
+    )]
+    #[account(10, writable, name = "buyer_ata")]
+    #[account(11, name = "token_program")]
+    #[account(12, name = "system_program")]
+    #[account(13, name = "event_authority", desc = "Event authority PDA")]
+    #[account(14, name = "commerce_program", desc = "Commerce Program ID")]
+    ChargebackPayment = 21,
+
+    // Close up to MAX_SWEEP_PAYMENTS payments that ClosePayment would accept, crediting their rent
+    // to rent_collector. Each payment is passed as three trailing remaining accounts (payment,
+    // buyer, mint), after the optional OperatorDelegate PDA and MerchantStats PDA. A MerchantStats
+    // PDA requires every swept payment to be in its mint. Payments with a rent_payer can only
+    // be swept with that account as rent_collector. Under a RentRebatePolicy every buyer must be
+    // writable to receive its share of the rent.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "operator_authority")]
+    #[account(2, name = "operator", desc = "Operator PDA")]
+    #[account(3, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        4,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA, which stops counting the swept payment accounts"
+    )]
+    #[account(
+        5,
+        writable,
+        name = "rent_collector",
+        desc = "Receives the closed payments' rent"
+    )]
+    #[account(6, name = "system_program")]
+    #[account(7, name = "event_authority", desc = "Event authority PDA")]
+    #[account(8, name = "commerce_program", desc = "Commerce Program ID")]
+    SweepClosedPayments { num_payments: u8 } = 22,
+
+    // Propose Settlement Wallet (step 1 of 2); replaces any pending proposal and restarts the
+    // merchant's settlement wallet timelock. Emits SettlementWalletProposed.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority")]
+    #[account(2, writable, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        3,
+        name = "new_settlement_wallet",
+        desc = "Applied by CommitSettlementWallet once the timelock elapses"
+    )]
+    #[account(4, name = "event_authority", desc = "Event authority PDA")]
+    #[account(5, name = "commerce_program", desc = "Commerce Program ID")]
+    ProposeSettlementWallet = 23,
+
+    // Commit Settlement Wallet (step 2 of 2). Emits MerchantSettlementWalletUpdated.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority")]
+    #[account(2, writable, name = "merchant", desc = "Merchant PDA")]
+    #[account(3, name = "event_authority", desc = "Event authority PDA")]
+    #[account(4, name = "commerce_program", desc = "Commerce Program ID")]
+    CommitSettlementWallet = 24,
+
+    // Initialize a config's SettlementBatch PDA for a mint. Once created, pass it to
+    // ClearPayment as the last remaining account (before the memo program) to leave merchant
+    // amounts in escrow until ExecuteSettlementBatch pays them out together.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority", desc = "Authority of the merchant")]
+    #[account(2, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        3,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(4, name = "mint", desc = "Currency the batch accumulates")]
+    #[account(
+        5,
+        writable,
+        name = "settlement_batch",
+        desc = "The SettlementBatch PDA being initialized"
+    )]
+    #[account(6, name = "system_program")]
+    InitializeSettlementBatch { bump: u8 } = 25,
+
+    // Transfer a SettlementBatch's pending amount from escrow to the merchant's settlement
+    // wallet once the config's SettlementPolicy minimum amount and frequency are met.
+    // Permissionless: funds can only move to the settlement wallet.
+    #[account(0, writable, name = "settlement_batch", desc = "SettlementBatch PDA")]
+    #[account(1, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        2,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(3, name = "mint")]
+    #[account(4, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        5,
+        writable,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
+    )]
+    #[account(
+        6,
+        writable,
+        name = "merchant_settlement_ata",
+        desc = "Merchant Settlement ATA (Merchant settlement wallet is owner)"
+    )]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "event_authority", desc = "Event authority PDA")]
+    #[account(9, name = "commerce_program", desc = "Commerce Program ID")]
+    ExecuteSettlementBatch = 26,
+
+    // Create an Order PDA funded by `expected_payments` payments in different mints, sharing
+    // the order's buyer and order_id. Pass it as the last remaining account to MakePayment
+    // (before the SettlementBatch PDA and memo program); every instruction that later changes
+    // the payment's status then requires it, keeping its aggregate status up to date.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "operator_authority")]
+    #[account(2, name = "buyer", desc = "Buyer making the order's payments")]
+    #[account(3, name = "operator", desc = "Operator PDA")]
+    #[account(4, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        5,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(6, writable, name = "order", desc = "The Order PDA being created")]
+    #[account(7, name = "system_program")]
+    #[account(8, name = "event_authority", desc = "Event authority PDA")]
+    #[account(9, name = "commerce_program", desc = "Commerce Program ID")]
+    CreateOrder {
+        order_id: u32,
+        /// Number of payments funding the order, 1 to 4
+        expected_payments: u8,
+        bump: u8,
+    } = 27,
+
+    // Initialize a merchant's MerchantProfile PDA with the display information wallets and
+    // explorers show to buyers. Not read by any payment instruction.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority", desc = "Authority of the merchant")]
+    #[account(2, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        3,
+        writable,
+        name = "merchant_profile",
+        desc = "The MerchantProfile PDA being initialized"
+    )]
+    #[account(4, name = "system_program")]
+    InitializeMerchantProfile {
+        /// Display name, at most 32 bytes
+        name: String,
+        /// Website or metadata URI, at most 200 bytes
+        uri: String,
+        logo_hash: [u8; 32],
+        contact_hash: [u8; 32],
+        bump: u8,
+    } = 28,
+
+    // Replace every field of a merchant's MerchantProfile
+    #[account(0, signer, name = "authority", desc = "Authority of the merchant")]
+    #[account(1, name = "merchant", desc = "Merchant PDA")]
+    #[account(2, writable, name = "merchant_profile", desc = "MerchantProfile PDA")]
+    UpdateMerchantProfile {
+        /// Display name, at most 32 bytes
+        name: String,
+        /// Website or metadata URI, at most 200 bytes
+        uri: String,
+        logo_hash: [u8; 32],
+        contact_hash: [u8; 32],
+    } = 29,
+
+    // Approve Refund (step 1 of 2). Marks a paid payment RefundApproved and records the
+    // approval in a RefundApproval PDA, at the canonical bump derived on-chain, funded by
+    // payer; the buyer pulls the funds with ClaimRefund.
+    // authority is the operator authority, a delegate allowed to refund (pass its
+    // OperatorDelegate PDA as the first remaining account) or the merchant owner. A payment
+    // made against an order requires its Order PDA as the last remaining account.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, writable, name = "payment", desc = "Payment PDA being updated")]
+    #[account(2, signer, name = "authority")]
+    #[account(3, name = "buyer", desc = "Buyer that can claim the refund")]
+    #[account(4, name = "merchant", desc = "Merchant PDA")]
+    #[account(5, name = "operator", desc = "Operator PDA")]
+    #[account(
+        6,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(7, name = "mint")]
+    #[account(
+        8,
+        writable,
+        name = "refund_approval",
+        desc = "The RefundApproval PDA being created"
+    )]
+    #[account(9, name = "system_program")]
+    #[account(10, name = "event_authority", desc = "Event authority PDA")]
+    #[account(11, name = "commerce_program", desc = "Commerce Program ID")]
+    ApproveRefund = 30,
+
+    // Claim Refund (step 2 of 2). The buyer sends an approved refund to any token account
+    // they own for the mint, closing the RefundApproval to its rent payer. Remaining accounts
+    // as for RefundPayment: MerchantStats, the OperatorFreezeList, Order, then the memo program
+    // under a memo policy.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, writable, name = "payment", desc = "Payment PDA being updated")]
+    #[account(2, signer, name = "buyer")]
+    #[account(3, name = "merchant", desc = "Merchant PDA")]
+    #[account(4, name = "operator", desc = "Operator PDA")]
+    #[account(
+        5,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(6, name = "mint")]
+    #[account(7, writable, name = "refund_approval", desc = "RefundApproval PDA")]
+    #[account(
+        8,
+        writable,
+        name = "rent_payer",
+        desc = "Account that paid the RefundApproval's rent"
+    )]
+    #[account(9, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        10,
+        writable,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
+    )]
+    #[account(
+        11,
+        writable,
+        name = "destination",
+        desc = "Token account owned by the buyer for the mint"
+    )]
+    #[account(12, name = "token_program")]
+    #[account(13, name = "event_authority", desc = "Event authority PDA")]
+    #[account(14, name = "commerce_program", desc = "Commerce Program ID")]
+    ClaimRefund = 31,
+
+    // Initialize a RiskState PDA counting payments for the config's RiskPolicy: the
+    // config-wide one (zeroed `buyer`) for max_daily_volume, or a buyer's for
+    // max_payments_per_buyer_per_day. Permissionless. Pass the ones the policy needs to
+    // MakePayment after any MerchantStats, before the Order and references.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(
+        1,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(
+        2,
+        writable,
+        name = "risk_state",
+        desc = "The RiskState PDA being initialized"
+    )]
+    #[account(3, name = "system_program")]
+    InitializeRiskState { bump: u8, buyer: Pubkey } = 32,
+
+    // Initialize a buyer's BuyerIndex PDA, which MakePayment keeps under the config's
+    // BuyerIndexPolicy. Permissionless; the payer funds its rent.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(
+        1,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(2, name = "buyer", desc = "The buyer the index is for")]
+    #[account(
+        3,
+        writable,
+        name = "buyer_index",
+        desc = "The BuyerIndex PDA being initialized"
+    )]
+    #[account(4, name = "system_program")]
+    InitializeBuyerIndex { bump: u8 } = 33,
+
+    // Initialize Merchant PDA and a MerchantOperatorConfig PDA in one instruction, co-signed by
+    // the operator owner. Mint accounts for the accepted currencies, then optionally the
+    // operator's ConfigRegistryPage PDA, follow as remaining accounts, as for
+    // InitializeMerchantOperatorConfig.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority", desc = "Authority of the merchant")]
+    #[account(2, signer, name = "operator_authority", desc = "Owner of the operator")]
+    #[account(
+        3,
+        writable,
+        name = "merchant",
+        desc = "The Merchant PDA being initialized"
+    )]
+    #[account(4, name = "settlement_wallet")]
+    #[account(5, name = "operator", desc = "Operator PDA")]
+    #[account(
+        6,
+        writable,
+        name = "config",
+        desc = "The MerchantOperatorConfig PDA being initialized"
+    )]
+    #[account(7, name = "system_program")]
+    #[account(8, name = "event_authority", desc = "Event authority PDA")]
+    #[account(9, name = "commerce_program", desc = "Commerce Program ID")]
+    InitializeMerchantWithConfig {
+        merchant_bump: u8,
+        settlement_wallet_timelock_seconds: u64,
+        version: u32,
+        config_bump: u8,
+        operator_fee: u64,
+        fee_type: FeeType,
+        days_to_close: u16,
+        policies: Vec<PolicyData>,
+        accepted_currencies: Vec<Pubkey>,
+    } = 34,
+
+    // Migrate Escrow. Moves `amount` of the config's escrowed funds from the merchant PDA's
+    // legacy escrow ATA to the config's escrow ATA, owned by its escrow authority PDA, creating
+    // it if missing. Co-signed by the merchant and operator owners, since the legacy escrow is
+    // shared by the merchant's configs. Transfer hook accounts are the remaining accounts.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "merchant_authority", desc = "Owner of the merchant")]
+    #[account(2, signer, name = "operator_authority", desc = "Owner of the operator")]
+    #[account(3, name = "merchant", desc = "Merchant PDA")]
+    #[account(4, name = "operator", desc = "Operator PDA")]
+    #[account(
+        5,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(6, name = "mint")]
+    #[account(
+        7,
+        writable,
+        name = "legacy_escrow_ata",
+        desc = "Legacy Escrow ATA (Merchant PDA is owner)"
+    )]
+    #[account(8, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        9,
+        writable,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
+    )]
+    #[account(10, name = "token_program")]
+    #[account(11, name = "associated_token_program")]
+    #[account(12, name = "system_program")]
+    MigrateEscrow { amount: u64 } = 35,
+
+    // Create a Quote PDA fixing the amount a payment of `order_id` in `mint` must be for, until
+    // `expires_at`. Signed by the operator owner or a delegate with the MakePayment permission,
+    // whose OperatorDelegate PDA is the first remaining account. Pass the quote to MakePayment
+    // after the Order, before any references.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "operator_authority")]
+    #[account(2, name = "operator", desc = "Operator PDA")]
+    #[account(
+        3,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(4, name = "mint")]
+    #[account(5, writable, name = "quote", desc = "The Quote PDA being created")]
+    #[account(6, name = "system_program")]
+    CreateQuote {
+        order_id: u32,
+        amount: u64,
+        expires_at: i64,
+        bump: u8,
+    } = 36,
+
+    // Initialize page `page` of an operator's ConfigRegistryPage PDAs, which list the configs
+    // created under the operator. Permissionless; the payer funds its rent. Pages after the
+    // first pass the full previous page as the first remaining account.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, name = "operator", desc = "Operator PDA")]
+    #[account(
+        2,
+        writable,
+        name = "config_registry_page",
+        desc = "The ConfigRegistryPage PDA being initialized"
+    )]
+    #[account(3, name = "system_program")]
+    InitializeConfigRegistryPage { page: u32, bump: u8 } = 37,
+
+    // Capture Payment. Captures `amount` of an Authorized payment's hold, at most the authorized
+    // amount, and releases the rest from escrow back to the buyer. The payment becomes Paid for
+    // `amount`, to be cleared or refunded as usual, or Refunded when `amount` is 0. Signed by
+    // the operator owner or a delegate with the Clear permission, whose OperatorDelegate PDA is
+    // the first remaining account. Transfer hook accounts lead the remaining accounts as for
+    // MakePayment; MerchantStats, the OperatorFreezeList, then the Order, may follow as for
+    // RefundPayment. Under a RiskPolicy, pass the RiskState PDAs its daily limits need before
+    // the Order; the released amount, and the payment when nothing is captured, come off them.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(
+        1,
+        writable,
+        name = "payment",
+        desc = "Authorized Payment PDA being captured"
+    )]
+    #[account(2, signer, name = "operator_authority")]
+    #[account(3, name = "buyer", desc = "Owner of the released funds' destination")]
+    #[account(4, name = "merchant", desc = "Merchant PDA")]
+    #[account(5, name = "operator", desc = "Operator PDA")]
+    #[account(
+        6,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(7, name = "mint")]
+    #[account(8, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        9,
+        writable,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
+    )]
+    #[account(10, writable, name = "buyer_ata")]
+    #[account(11, name = "token_program")]
+    #[account(12, name = "system_program")]
+    #[account(13, name = "event_authority", desc = "Event authority PDA")]
+    #[account(14, name = "commerce_program", desc = "Commerce Program ID")]
+    CapturePayment { amount: u64 } = 38,
+
+    // Initialize an operator's OperatorFreezeList PDA, the buyer addresses payments and refunds
+    // under the operator reject. Records the list on the operator, so every instruction that
+    // checks it then requires it. Signed by the operator owner; the payer funds its rent.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority", desc = "Authority of the operator")]
+    #[account(2, writable, name = "operator", desc = "Operator PDA")]
+    #[account(
+        3,
+        writable,
+        name = "operator_freeze_list",
+        desc = "The OperatorFreezeList PDA being initialized"
+    )]
+    #[account(4, name = "system_program")]
+    InitializeOperatorFreezeList { bump: u8 } = 39,
+
+    // Freeze an address on the operator's OperatorFreezeList
+    #[account(0, signer, name = "authority", desc = "Authority of the operator")]
+    #[account(1, name = "operator", desc = "Operator PDA")]
+    #[account(
+        2,
+        writable,
+        name = "operator_freeze_list",
+        desc = "OperatorFreezeList PDA"
+    )]
+    AddToFreezeList { address: Pubkey } = 40,
+
+    // Unfreeze an address on the operator's OperatorFreezeList
+    #[account(0, signer, name = "authority", desc = "Authority of the operator")]
+    #[account(1, name = "operator", desc = "Operator PDA")]
+    #[account(
+        2,
+        writable,
+        name = "operator_freeze_list",
+        desc = "OperatorFreezeList PDA"
+    )]
+    RemoveFromFreezeList { address: Pubkey } = 41,
+
+    // Post the rate RefundPaymentWithConversion refunds payments in `from_mint` at in `to_mint`,
+    // and ClearPaymentWithConversion bounds its quote with: `rate_numerator / rate_denominator`
+    // units of `to_mint` per unit of `from_mint`. Creates the ConversionRate PDA on the first
+    // posting for the pair and updates it after. Requires a RefundConversion policy and two
+    // distinct accepted mints, or a Conversion policy into `to_mint` and an accepted
+    // `from_mint`. Signed by the operator owner or a
+    // delegate with the Refund permission, whose OperatorDelegate PDA is the first remaining
+    // account.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "operator_authority")]
+    #[account(2, name = "operator", desc = "Operator PDA")]
+    #[account(
+        3,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(4, name = "from_mint")]
+    #[account(5, name = "to_mint")]
+    #[account(
+        6,
+        writable,
+        name = "conversion_rate",
+        desc = "The ConversionRate PDA being posted"
+    )]
+    #[account(7, name = "system_program")]
+    PostConversionRate {
+        rate_numerator: u64,
+        rate_denominator: u64,
+        bump: u8,
+    } = 42,
+
+    // Refund a Paid payment in `refund_mint`, another of the config's accepted mints, for when
+    // the payment's mint is frozen or deprecated. The operator authority's ATA in the refund
+    // mint pays the buyer the payment amount at the ConversionRate posted for the pair, which
+    // must be within the RefundConversion policy's max age; the escrowed amount goes to the
+    // operator's settlement ATA in the payment's mint. SPL Token mints only. Remaining accounts
+    // are as for RefundPayment, without transfer hook accounts or a rent payer.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, writable, name = "payment", desc = "Payment PDA being updated")]
+    #[account(2, signer, name = "operator_authority")]
+    #[account(3, name = "buyer", desc = "Refund destination owner")]
+    #[account(4, name = "merchant", desc = "Merchant PDA")]
+    #[account(5, name = "operator", desc = "Operator PDA")]
+    #[account(
+        6,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(7, name = "mint", desc = "The payment's mint")]
+    #[account(8, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        9,
+        writable,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
+    )]
+    #[account(
+        10,
+        writable,
+        name = "operator_settlement_ata",
+        desc = "Operator Settlement ATA in the payment's mint (Operator owner is owner)"
+    )]
+    #[account(11, name = "refund_mint", desc = "The mint the refund is paid in")]
+    #[account(
+        12,
+        name = "conversion_rate",
+        desc = "ConversionRate PDA from the payment's mint to the refund mint"
+    )]
+    #[account(
+        13,
+        writable,
+        name = "operator_refund_ata",
+        desc = "Operator authority's ATA in the refund mint, funding the refund"
+    )]
+    #[account(
+        14,
+        writable,
+        name = "buyer_refund_ata",
+        desc = "Buyer's ATA in the refund mint"
+    )]
+    #[account(15, name = "token_program")]
+    #[account(16, name = "associated_token_program")]
+    #[account(17, name = "system_program")]
+    #[account(18, name = "event_authority", desc = "Event authority PDA")]
+    #[account(19, name = "commerce_program", desc = "Commerce Program ID")]
+    RefundPaymentWithConversion = 43,
+
+    // Add a policy to a MerchantOperatorConfig, growing the account by one policy slot with the
+    // payer funding the extra rent. The config can have one policy per type and at most
+    // MAX_POLICIES. Recomputes the config's hash. Signed by the merchant authority.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority", desc = "Authority of the merchant")]
+    #[account(2, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        3,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(4, name = "system_program")]
+    AddPolicy { policy: PolicyData } = 44,
+
+    // Add `mint` to a MerchantOperatorConfig's accepted currencies, growing the account by one
+    // currency with the payer funding the extra rent. The config can accept at most
+    // MAX_ACCEPTED_CURRENCIES mints. Recomputes the config's hash. Signed by the merchant
+    // authority.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority", desc = "Authority of the merchant")]
+    #[account(2, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        3,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(4, name = "mint", desc = "The mint being accepted")]
+    #[account(5, name = "system_program")]
+    AddAcceptedCurrency = 45,
+
+    // Close a PaymentNote, refunding its rent to the rent payer recorded on it. Signed by the
+    // authority of the merchant the note is encrypted to.
+    #[account(0, signer, name = "authority", desc = "Authority of the merchant")]
+    #[account(1, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        2,
+        writable,
+        name = "payment_note",
+        desc = "PaymentNote PDA being closed"
+    )]
+    #[account(
+        3,
+        writable,
+        name = "rent_payer",
+        desc = "Account that paid the note's rent, refunded"
+    )]
+    ClosePaymentNote = 46,
+
+    // Record that the operator is active, for merchants' operator failover policies. Signed by
+    // the operator owner, or by a delegate allowed to clear payments with its OperatorDelegate
+    // PDA as the first remaining account.
+    #[account(0, signer, name = "operator_authority")]
+    #[account(1, writable, name = "operator", desc = "Operator PDA")]
+    Heartbeat = 47,
+
+    // Close a config's escrow ATA for a mint, returning its rent to the merchant authority.
+    // Co-signed by the merchant and operator owners; fails while the config counts open
+    // payments of the mint. Any balance left, e.g. tokens sent to the escrow directly, is swept
+    // to the merchant's settlement ATA first. Transfer hook accounts are the remaining
+    // accounts, as for MakePayment.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "merchant_authority",
+        desc = "Owner of the merchant, receives the rent"
+    )]
+    #[account(1, signer, name = "operator_authority", desc = "Owner of the operator")]
+    #[account(2, name = "merchant", desc = "Merchant PDA")]
+    #[account(3, name = "operator", desc = "Operator PDA")]
+    #[account(
+        4,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(5, name = "mint")]
+    #[account(6, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        7,
+        writable,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA being closed (Escrow authority PDA is owner)"
+    )]
+    #[account(
+        8,
+        writable,
+        name = "merchant_settlement_ata",
+        desc = "Merchant Settlement ATA (Merchant settlement wallet is owner)"
+    )]
+    #[account(9, name = "token_program")]
+    CloseEscrowAta = 48,
+
+    // Migrate a Paid payment to another config of its merchant and operator, e.g. a new
+    // config version: its escrowed amount moves to the new config's escrow ATA, creating it if
+    // missing, and the payment is re-created at its PDA under the new config with the old
+    // account's rent, keeping its order id, amount, creation time and fee override. Co-signed
+    // by the merchant and operator owners. Emits PaymentMigrated. Transfer hook accounts are
+    // the remaining accounts.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "payer",
+        desc = "Pays for the new escrow ATA, if missing"
+    )]
+    #[account(1, signer, name = "merchant_authority", desc = "Owner of the merchant")]
+    #[account(2, signer, name = "operator_authority", desc = "Owner of the operator")]
+    #[account(3, name = "merchant", desc = "Merchant PDA")]
+    #[account(4, name = "operator", desc = "Operator PDA")]
+    #[account(5, name = "buyer", desc = "Buyer of the payment")]
+    #[account(6, name = "mint")]
+    #[account(
+        7,
+        writable,
+        name = "old_merchant_operator_config",
+        desc = "Merchant Operator Config PDA the payment is migrated from"
+    )]
+    #[account(
+        8,
+        writable,
+        name = "new_merchant_operator_config",
+        desc = "Merchant Operator Config PDA the payment is migrated to"
+    )]
+    #[account(
+        9,
+        writable,
+        name = "old_payment",
+        desc = "Payment PDA under the old config, closed"
+    )]
+    #[account(
+        10,
+        writable,
+        name = "new_payment",
+        desc = "Payment PDA under the new config, created"
+    )]
+    #[account(
+        11,
+        name = "old_escrow_authority",
+        desc = "Escrow authority PDA of the old config"
+    )]
+    #[account(
+        12,
+        writable,
+        name = "old_merchant_escrow_ata",
+        desc = "Escrow ATA of the old config (old escrow authority PDA is owner)"
+    )]
+    #[account(
+        13,
+        name = "new_escrow_authority",
+        desc = "Escrow authority PDA of the new config"
+    )]
+    #[account(
+        14,
+        writable,
+        name = "new_merchant_escrow_ata",
+        desc = "Escrow ATA of the new config (new escrow authority PDA is owner)"
+    )]
+    #[account(15, name = "token_program")]
+    #[account(16, name = "associated_token_program")]
+    #[account(17, name = "system_program")]
+    #[account(18, name = "event_authority", desc = "Event authority PDA")]
+    #[account(19, name = "commerce_program", desc = "Commerce Program ID")]
+    MigratePayment = 49,
+
+    // Write a ConfigSummary of the config to return data, with its open-payment count and
+    // escrow balance in mint, so programs can read a merchant's terms by CPI. An escrow ATA
+    // that does not exist yet holds nothing.
+    #[account(
+        0,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(1, name = "mint")]
+    #[account(2, name = "escrow_authority", desc = "Escrow authority PDA")]
+    #[account(
+        3,
+        name = "merchant_escrow_ata",
+        desc = "Merchant Escrow ATA (Escrow authority PDA is owner)"
+    )]
+    #[account(4, name = "token_program")]
+    GetConfigSummary = 50,
+
+    // Set the commitment to a config's webhook endpoint that indexers check before delivering
+    // its payment webhooks. Creates the WebhookCommitment PDA on the first call and replaces
+    // the commitment after. Signed by the merchant authority. Emits WebhookCommitmentUpdated.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority", desc = "Authority of the merchant")]
+    #[account(2, name = "merchant", desc = "Merchant PDA")]
+    #[account(
+        3,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(
+        4,
+        writable,
+        name = "webhook_commitment",
+        desc = "The WebhookCommitment PDA being set"
+    )]
+    #[account(5, name = "system_program")]
+    #[account(6, name = "event_authority", desc = "Event authority PDA")]
+    #[account(7, name = "commerce_program", desc = "Commerce Program ID")]
+    SetWebhookCommitment { commitment: [u8; 32], bump: u8 } = 51,
+
+    // Annotate a payment with the operator's review verdict and the hash of an off-chain note,
+    // so merchants can see a payment flagged for review or fraud before shipping. Creates the
+    // PaymentAnnotation PDA, at the canonical bump derived on-chain, on the first call and
+    // overwrites it after. Signed by the operator owner, or by a delegate allowed to clear
+    // payments with its OperatorDelegate PDA as the first remaining account. Emits
+    // PaymentAnnotated.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, name = "payment", desc = "The Payment PDA being annotated")]
+    #[account(2, signer, name = "operator_authority")]
+    #[account(3, name = "buyer")]
+    #[account(4, name = "operator", desc = "Operator PDA")]
+    #[account(
+        5,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA"
+    )]
+    #[account(6, name = "mint")]
+    #[account(
+        7,
+        writable,
+        name = "payment_annotation",
+        desc = "The PaymentAnnotation PDA being set"
+    )]
+    #[account(8, name = "system_program")]
+    #[account(9, name = "event_authority", desc = "Event authority PDA")]
+    #[account(10, name = "commerce_program", desc = "Commerce Program ID")]
+    AnnotatePayment {
+        flag: AnnotationFlag,
+        note_hash: [u8; 32],
+    } = 52,
+
+    // Stop a config from taking new payments, the first step of closing it. Payments already
+    // made still clear and refund. Signed by the merchant or the operator owner. Emits
+    // ConfigDecommissioned.
+    #[account(
+        0,
+        signer,
+        name = "authority",
+        desc = "Owner of the merchant or of the operator"
+    )]
+    #[account(1, name = "merchant", desc = "Merchant PDA")]
+    #[account(2, name = "operator", desc = "Operator PDA")]
+    #[account(
+        3,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Merchant Operator Config PDA being decommissioned"
+    )]
+    #[account(4, name = "event_authority", desc = "Event authority PDA")]
+    #[account(5, name = "commerce_program", desc = "Commerce Program ID")]
+    DecommissionConfig = 53,
+
+    // Close a decommissioned config, returning its rent to the merchant authority. Its
+    // open-payment count must be zero in every accepted currency. Co-signed by the merchant and
+    // operator owners. Emits MerchantOperatorConfigClosed.
+    #[account(
+        0,
+        writable,
+        signer,
+        name = "merchant_authority",
+        desc = "Owner of the merchant, receives the rent"
+    )]
+    #[account(1, signer, name = "operator_authority", desc = "Owner of the operator")]
+    #[account(2, name = "merchant", desc = "Merchant PDA")]
+    #[account(3, name = "operator", desc = "Operator PDA")]
+    #[account(
+        4,
+        writable,
+        name = "merchant_operator_config",
+        desc = "Decommissioned Merchant Operator Config PDA being closed"
+    )]
+    #[account(5, name = "event_authority", desc = "Event authority PDA")]
+    #[account(6, name = "commerce_program", desc = "Commerce Program ID")]
+    CloseMerchantOperatorConfig = 54,
+
+    // Rewrite an Operator, Merchant, Payment or MerchantOperatorConfig created before its
+    // current layout, growing it with the fields it predates. Permissionless; the payer funds
+    // the extra rent. Fails with AccountNotUpgradable for an account already in its current
+    // layout.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(
+        1,
+        writable,
+        name = "account",
+        desc = "Program account in a legacy layout"
+    )]
+    #[account(2, name = "system_program")]
+    UpgradeAccount = 55,
+
+    // Raise the merchant's settlement wallet timelock, e.g. for a merchant created or upgraded
+    // without one. Signed by the merchant owner; the timelock can only go up, to at most 30
+    // days, and a pending proposal's unlock time moves out by the increase. Emits
+    // SettlementWalletTimelockRaised.
+    #[account(0, writable, signer, name = "payer")]
+    #[account(1, signer, name = "authority")]
+    #[account(2, writable, name = "merchant", desc = "Merchant PDA")]
+    #[account(3, name = "event_authority", desc = "Event authority PDA")]
+    #[account(4, name = "commerce_program", desc = "Commerce Program ID")]
+    SetSettlementWalletTimelock {
+        settlement_wallet_timelock_seconds: u64,
+    } = 56,
+
     /// Invoked via CPI from another program to log event via instruction data.
     #[account(0, signer, name = "event_authority")]
     EmitEvent {} = 228,
@@ -1,10 +1,14 @@
 #![no_std]
 
+#[cfg(test)]
+extern crate std;
+
 pub mod constants;
 pub mod error;
 pub mod events;
 #[cfg(feature = "idl")]
 pub mod instructions;
+pub mod math;
 pub mod processor;
 pub mod state;
 
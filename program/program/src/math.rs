@@ -0,0 +1,63 @@
+//! Overflow-safe arithmetic for amounts and timestamps.
+//!
+//! Amounts are u64 token units and timestamps are i64 unix seconds from the Clock sysvar,
+//! while policies express durations as unsigned seconds or hours. None of these helpers wrap,
+//! so a u64::MAX amount or a far-future clock yields a policy error instead of a bogus pass.
+
+use crate::constants::{MAX_BPS, SECONDS_PER_HOUR};
+
+/// `bps` basis points of `amount`, rounded down.
+///
+/// The product is taken in u128, so any u64 amount works; only `bps` above `MAX_BPS` can
+/// exceed `amount`, and those saturate at `u64::MAX`.
+pub fn bps_of(amount: u64, bps: u64) -> u64 {
+    let fee = amount as u128 * bps as u128 / MAX_BPS as u128;
+    u64::try_from(fee).unwrap_or(u64::MAX)
+}
+
+/// `hours` as seconds; every u32 fits without overflow.
+pub fn hours_to_seconds(hours: u32) -> u64 {
+    hours as u64 * SECONDS_PER_HOUR as u64
+}
+
+/// Seconds from `since` to `now`, negative when `now` is earlier.
+///
+/// Taken in i128 so it is exact for any pair of timestamps and compares directly against
+/// u64 policy durations, e.g. `elapsed_seconds(created_at, now) < window as i128`.
+pub fn elapsed_seconds(since: i64, now: i64) -> i128 {
+    now as i128 - since as i128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bps_of() {
+        assert_eq!(bps_of(1_000, 250), 25);
+        assert_eq!(bps_of(1_000, MAX_BPS), 1_000);
+        assert_eq!(bps_of(99, 100), 0);
+        assert_eq!(bps_of(u64::MAX, MAX_BPS), u64::MAX);
+        assert_eq!(
+            bps_of(u64::MAX, 250),
+            (u64::MAX as u128 * 250 / 10_000) as u64
+        );
+        assert_eq!(bps_of(u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_hours_to_seconds() {
+        assert_eq!(hours_to_seconds(0), 0);
+        assert_eq!(hours_to_seconds(24), 86_400);
+        assert_eq!(hours_to_seconds(u32::MAX), u32::MAX as u64 * 3_600);
+    }
+
+    #[test]
+    fn test_elapsed_seconds() {
+        assert_eq!(elapsed_seconds(100, 150), 50);
+        assert_eq!(elapsed_seconds(150, 100), -50);
+        assert_eq!(elapsed_seconds(i64::MIN, i64::MAX), u64::MAX as i128);
+        assert_eq!(elapsed_seconds(i64::MAX, i64::MIN), -(u64::MAX as i128));
+        assert!(elapsed_seconds(0, i64::MAX) < u64::MAX as i128);
+    }
+}
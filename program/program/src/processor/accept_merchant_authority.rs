@@ -0,0 +1,65 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    events::{EventDiscriminators, MerchantAuthorityUpdatedEvent},
+    processor::{emit_event, verify_current_program, verify_owner_mutability, verify_signer},
+    state::Merchant,
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_accept_merchant_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [payer_info, new_authority_info, merchant_info, event_authority_info, commerce_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: pending authority should have signed
+    verify_signer(new_authority_info, false)?;
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    let mut merchant_data = merchant_info.try_borrow_mut_data()?;
+    let mut merchant = Merchant::try_from_bytes(&merchant_data)?;
+
+    // Validate Merchant PDA
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Validate signer is the proposed authority
+    merchant.validate_pending_authority(new_authority_info.key())?;
+
+    // Hand over merchant owner and clear the proposal
+    let old_authority = merchant.owner;
+    merchant.owner = *new_authority_info.key();
+    merchant.pending_authority = Pubkey::default();
+    merchant.store(&mut merchant_data)?;
+
+    // Emit authority updated event
+    let event = MerchantAuthorityUpdatedEvent {
+        discriminator: EventDiscriminators::MerchantAuthorityUpdated as u8,
+        merchant: *merchant_info.key(),
+        old_authority,
+        new_authority: merchant.owner,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
@@ -0,0 +1,65 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    events::{EventDiscriminators, OperatorAuthorityUpdatedEvent},
+    processor::{emit_event, verify_current_program, verify_owner_mutability, verify_signer},
+    state::Operator,
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_accept_operator_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [payer_info, new_authority_info, operator_info, event_authority_info, commerce_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: pending authority should have signed
+    verify_signer(new_authority_info, false)?;
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate operator is owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    let mut operator_data = operator_info.try_borrow_mut_data()?;
+    let mut operator = Operator::try_from_bytes(&operator_data)?;
+
+    // Validate Operator PDA
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate signer is the proposed authority
+    operator.validate_pending_authority(new_authority_info.key())?;
+
+    // Hand over operator owner and clear the proposal
+    let old_authority = operator.owner;
+    operator.owner = *new_authority_info.key();
+    operator.pending_authority = Pubkey::default();
+    operator.store(&mut operator_data)?;
+
+    // Emit authority updated event
+    let event = OperatorAuthorityUpdatedEvent {
+        discriminator: EventDiscriminators::OperatorAuthorityUpdated as u8,
+        operator: *operator_info.key(),
+        old_authority,
+        new_authority: operator.owner,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
@@ -0,0 +1,92 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::{
+        realloc_pda_account, verify_mint_account, verify_owner_mutability, verify_signer,
+        verify_system_program, verify_token_interface_account,
+    },
+    state::{Merchant, MerchantOperatorConfig},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_add_accepted_currency(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [payer_info, authority_info, merchant_info, merchant_operator_config_info, mint_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_operator_config is writable and owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate mint is owned by token program, SPL Token or Token-2022, and is a valid mint
+    verify_token_interface_account(mint_info)?;
+    verify_mint_account(mint_info)?;
+
+    // Load and validate merchant
+    let merchant = {
+        let merchant_data = merchant_info.try_borrow_data()?;
+        Merchant::try_from_bytes(&merchant_data)?
+    };
+    merchant.validate_owner(authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate merchant_operator_config
+    let mut config_data: Vec<u8> = merchant_operator_config_info.try_borrow_data()?.to_vec();
+    let (mut config, policies, mut currencies) =
+        MerchantOperatorConfig::try_from_bytes(&config_data)?;
+    config.validate_pda(merchant_operator_config_info.key())?;
+    config.validate_merchant(merchant_info.key())?;
+
+    // Validate the config has room for the mint and does not accept it yet
+    MerchantOperatorConfig::validate_limits(policies.len(), currencies.len() + 1)?;
+    if currencies.contains(mint_info.key()) {
+        return Err(CommerceProgramError::DuplicateMint.into());
+    }
+
+    // Append the mint and rehash the config's terms
+    config.add_accepted_currency(*mint_info.key(), &mut config_data);
+    currencies.push(*mint_info.key());
+    config.config_hash = config.compute_config_hash(&policies, &currencies);
+    config.store(&mut config_data)?;
+
+    // Grow the account, with the payer funding the extra rent
+    realloc_pda_account(
+        payer_info,
+        &Rent::get()?,
+        config_data.len(),
+        merchant_operator_config_info,
+    )?;
+    merchant_operator_config_info
+        .try_borrow_mut_data()?
+        .copy_from_slice(&config_data);
+
+    Ok(())
+}
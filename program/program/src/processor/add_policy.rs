@@ -0,0 +1,145 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::{
+        realloc_pda_account, validate_immediate_settlement, verify_owner_mutability, verify_signer,
+        verify_system_program,
+    },
+    require_len,
+    state::{Merchant, MerchantOperatorConfig, PolicyData, PolicyType},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_add_policy(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, authority_info, merchant_info, merchant_operator_config_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_operator_config is writable and owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Load and validate merchant
+    let merchant = {
+        let merchant_data = merchant_info.try_borrow_data()?;
+        Merchant::try_from_bytes(&merchant_data)?
+    };
+    merchant.validate_owner(authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate merchant_operator_config
+    let mut config_data: Vec<u8> = merchant_operator_config_info.try_borrow_data()?.to_vec();
+    let (mut config, mut policies, currencies) =
+        MerchantOperatorConfig::try_from_bytes(&config_data)?;
+    config.validate_pda(merchant_operator_config_info.key())?;
+    config.validate_merchant(merchant_info.key())?;
+
+    // Validate the config has room for the policy and no policy of its type yet
+    MerchantOperatorConfig::validate_limits(policies.len() + 1, currencies.len())?;
+    if MerchantOperatorConfig::has_policy_type(&policies, args.policy.policy_type()) {
+        return Err(CommerceProgramError::PolicyAlreadyExists.into());
+    }
+
+    // Validate the policy's fields, and that it combines with the config's policies
+    args.policy.validate()?;
+    policies.push(args.policy.clone());
+    validate_immediate_settlement(&policies)?;
+
+    // Insert the policy and rehash the config's terms
+    config.add_policy(args.policy, &mut config_data);
+    config.config_hash = config.compute_config_hash(&policies, &currencies);
+    config.store(&mut config_data)?;
+
+    // Grow the account, with the payer funding the extra rent
+    realloc_pda_account(
+        payer_info,
+        &Rent::get()?,
+        config_data.len(),
+        merchant_operator_config_info,
+    )?;
+    merchant_operator_config_info
+        .try_borrow_mut_data()?
+        .copy_from_slice(&config_data);
+
+    Ok(())
+}
+
+struct AddPolicyArgs {
+    policy: PolicyData,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<AddPolicyArgs, ProgramError> {
+    require_len!(data, 1);
+    let policy_type =
+        PolicyType::from_u8(data[0]).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let policy_size = policy_type.get_size();
+    require_len!(data, policy_size);
+
+    let policy = PolicyData::from_bytes(&data[..policy_size])?;
+    Ok(AddPolicyArgs { policy })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::RefundPolicy;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let policy = PolicyData::Refund(RefundPolicy {
+            max_amount: 5_000,
+            max_time_after_purchase: 3_600,
+        });
+        let data = policy.to_bytes();
+
+        let args = process_instruction_data(&data[..PolicyType::Refund.get_size()]).unwrap();
+        assert_eq!(args.policy, policy);
+    }
+
+    #[test]
+    fn test_process_instruction_data_invalid() {
+        assert_eq!(
+            process_instruction_data(&[]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            process_instruction_data(&[0xff]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+
+        // Refund policy without its fields
+        assert_eq!(
+            process_instruction_data(&[PolicyType::Refund.to_u8(), 0, 0]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    processor::{verify_owner_mutability, verify_signer},
+    require_len,
+    state::{discriminator::AccountSerialize, Operator, OperatorFreezeList},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_add_to_freeze_list(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [authority_info, operator_info, operator_freeze_list_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate operator is owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate operator_freeze_list is writable and owned by this program
+    verify_owner_mutability(operator_freeze_list_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Load and validate operator
+    let operator = {
+        let operator_data = operator_info.try_borrow_data()?;
+        Operator::try_from_bytes(&operator_data)?
+    };
+    operator.validate_owner(authority_info.key())?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Load and validate operator_freeze_list
+    let mut freeze_list_data = operator_freeze_list_info.try_borrow_mut_data()?;
+    let mut freeze_list = OperatorFreezeList::try_from_bytes(&freeze_list_data)?;
+    freeze_list.validate_pda(operator_freeze_list_info.key())?;
+    freeze_list.validate_operator(operator_info.key())?;
+
+    // Freeze the address
+    freeze_list.add(args.address)?;
+    freeze_list_data.copy_from_slice(&freeze_list.to_bytes());
+
+    Ok(())
+}
+
+struct AddToFreezeListArgs {
+    address: Pubkey,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<AddToFreezeListArgs, ProgramError> {
+    require_len!(data, 32);
+    let address: Pubkey = data[0..32].try_into().unwrap();
+    Ok(AddToFreezeListArgs { address })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let args = process_instruction_data(&[7u8; 32]).unwrap();
+        assert_eq!(args.address, [7u8; 32]);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[0u8; 31]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
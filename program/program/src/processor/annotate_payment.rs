@@ -0,0 +1,204 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::PAYMENT_ANNOTATION_SEED,
+    error::CommerceProgramError,
+    events::{EventDiscriminators, PaymentAnnotatedEvent},
+    processor::{
+        create_pda_account, derive_and_validate_pda, emit_event, validate_operator_authority,
+        verify_current_program, verify_owner_mutability, verify_signer, verify_system_program,
+        verify_token_interface_account,
+    },
+    require_len,
+    state::{
+        discriminator::AccountSerialize, AnnotationFlag, MerchantOperatorConfig, Operator,
+        OperatorPermission, Payment, PaymentAnnotation,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_annotate_payment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, payment_info, operator_authority_info, buyer_info, operator_info, merchant_operator_config_info, mint_info, payment_annotation_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate payment, operator and merchant_operator_config are owned by this program
+    verify_owner_mutability(payment_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate mint is owned by token program, SPL Token or Token-2022
+    verify_token_interface_account(mint_info)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to clear payments, since
+    // the annotation is the operator's verdict on whether the payment should go through
+    validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::Clear,
+    )?;
+
+    // Load and validate merchant_operator_config belongs to the operator
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, _, _) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+
+    // Load and validate payment was made under the config
+    // No need to validate mint since it's validated via the PDA seed
+    let payment = Payment::try_from_bytes(&payment_info.try_borrow_data()?)?;
+    payment.validate_pda(
+        payment_info.key(),
+        merchant_operator_config_info.key(),
+        buyer_info.key(),
+        mint_info.key(),
+    )?;
+
+    let bump = if payment_annotation_info.data_is_empty() {
+        // First annotation of the payment: validate and create the PaymentAnnotation PDA
+        let bump = derive_and_validate_pda(
+            &[PAYMENT_ANNOTATION_SEED, payment_info.key()],
+            program_id,
+            payment_annotation_info,
+        )?;
+
+        let rent = Rent::get()?;
+        let bump_seed = [bump];
+        let signer_seeds = [
+            Seed::from(PAYMENT_ANNOTATION_SEED),
+            Seed::from(payment_info.key()),
+            Seed::from(&bump_seed),
+        ];
+        create_pda_account(
+            payer_info,
+            &rent,
+            PaymentAnnotation::LEN,
+            program_id,
+            payment_annotation_info,
+            signer_seeds,
+            None,
+        )?;
+
+        bump
+    } else {
+        // Later annotations replace the current one in place
+        verify_owner_mutability(payment_annotation_info, &COMMERCE_PROGRAM_ID, true)?;
+
+        let payment_annotation =
+            PaymentAnnotation::try_from_bytes(&payment_annotation_info.try_borrow_data()?)?;
+        if payment_annotation.payment.ne(payment_info.key()) {
+            return Err(CommerceProgramError::PaymentAnnotationInvalidPda.into());
+        }
+        payment_annotation.validate_pda(payment_annotation_info.key())?;
+
+        payment_annotation.bump
+    };
+
+    let payment_annotation = PaymentAnnotation {
+        payment: *payment_info.key(),
+        merchant_operator_config: *merchant_operator_config_info.key(),
+        bump,
+        flag: args.flag,
+        note_hash: args.note_hash,
+        annotated_by: *operator_authority_info.key(),
+        updated_at: Clock::get()?.unix_timestamp,
+    };
+
+    payment_annotation_info
+        .try_borrow_mut_data()?
+        .copy_from_slice(&payment_annotation.to_bytes());
+
+    let event = PaymentAnnotatedEvent {
+        discriminator: EventDiscriminators::PaymentAnnotated as u8,
+        payment: *payment_info.key(),
+        merchant_operator_config: *merchant_operator_config_info.key(),
+        annotated_by: *operator_authority_info.key(),
+        flag: args.flag,
+        note_hash: args.note_hash,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
+
+struct AnnotatePaymentArgs {
+    flag: AnnotationFlag,
+    note_hash: [u8; 32],
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<AnnotatePaymentArgs, ProgramError> {
+    require_len!(data, 33);
+    let flag =
+        AnnotationFlag::from_u8(data[0]).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let note_hash = data[1..33].try_into().unwrap();
+    Ok(AnnotatePaymentArgs { flag, note_hash })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = [0u8; 33];
+        data[0] = AnnotationFlag::FlaggedFraud as u8;
+        data[1..33].copy_from_slice(&[7u8; 32]);
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.flag, AnnotationFlag::FlaggedFraud);
+        assert_eq!(args.note_hash, [7u8; 32]);
+    }
+
+    #[test]
+    fn test_process_instruction_data_unknown_flag() {
+        let mut data = [0u8; 33];
+        data[0] = 3;
+        let result = process_instruction_data(&data);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[0u8; 32]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -0,0 +1,215 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::REFUND_APPROVAL_SEED,
+    events::{EventDiscriminators, PaymentLifecycleStatus, RefundApprovedEvent},
+    processor::{
+        apply_delegate_refund_limits, create_pda_account, derive_and_validate_pda, emit_event,
+        emit_payment_status_changed, split_order, update_order, validate_operator_refund_authority,
+        validate_payment_order, validate_refund_policy, verify_current_program,
+        verify_owner_mutability, verify_signer, verify_system_account, verify_system_program,
+        verify_token_interface_account,
+    },
+    state::{
+        discriminator::AccountSerialize, Merchant, MerchantOperatorConfig, Operator, Payment,
+        RefundApproval, Status,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_approve_refund(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [payer_info, payment_info, authority_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, refund_approval_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The order, if passed, trails the other remaining accounts
+    let (order_info, remaining_accounts) = split_order(remaining_accounts);
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate payment is writable and owned by this program
+    verify_owner_mutability(payment_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate merchant, operator and merchant_operator_config are owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate mint is owned by token program, SPL Token or Token-2022
+    verify_token_interface_account(mint_info)?;
+
+    // Validate refund_approval is writable
+    verify_system_account(refund_approval_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator and merchant
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Validate authority is the merchant owner, or the operator owner or a delegate allowed
+    // to refund payments
+    let operator_delegate_info = if merchant.owner.ne(authority_info.key()) {
+        validate_operator_refund_authority(
+            &operator,
+            operator_info,
+            authority_info,
+            remaining_accounts,
+        )?
+        .0
+    } else {
+        None
+    };
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, policies, _allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    // Load and validate payment
+    let mut payment_data = payment_info.try_borrow_mut_data()?;
+    let mut payment = Payment::try_from_bytes(&payment_data)?;
+
+    // Only paid payments can be refunded
+    payment.validate_status(Status::Paid)?;
+
+    // Validate Payment PDA
+    // No need to validate mint since it's validated via the PDA seed
+    payment.validate_pda(
+        payment_info.key(),
+        merchant_operator_config_info.key(),
+        buyer_info.key(),
+        mint_info.key(),
+    )?;
+
+    // An order payment records every status change on its Order
+    validate_payment_order(order_info, payment.has_order)?;
+
+    // The refund policy is checked at approval; the buyer may claim after the window closes
+    validate_refund_policy(&policies, &payment)?;
+
+    // Validate the refund is within the signing delegate's refund limits
+    apply_delegate_refund_limits(operator_delegate_info, payment.amount)?;
+
+    // Validate RefundApproval PDA, deriving its canonical bump
+    let bump = derive_and_validate_pda(
+        &[REFUND_APPROVAL_SEED, payment_info.key()],
+        &Pubkey::from(*program_id),
+        refund_approval_info,
+    )?;
+
+    let refund_approval = RefundApproval::new(
+        *payment_info.key(),
+        bump,
+        *authority_info.key(),
+        payment.amount,
+        Clock::get()?.unix_timestamp,
+        *payer_info.key(),
+    );
+
+    let rent = Rent::get()?;
+    let bump_seed = [bump];
+    let signer_seeds = [
+        Seed::from(REFUND_APPROVAL_SEED),
+        Seed::from(payment_info.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        RefundApproval::LEN,
+        program_id,
+        refund_approval_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let mut refund_approval_data = refund_approval_info.try_borrow_mut_data()?;
+    refund_approval_data.copy_from_slice(&refund_approval.to_bytes());
+
+    // Hold the payment for the buyer's claim
+    payment.status = Status::RefundApproved;
+
+    payment.store(&mut payment_data)?;
+
+    // Record the approval on the payment's order, if any. The order only counts the claim, so
+    // its status cannot change here.
+    if let Some(order_info) = order_info {
+        update_order(
+            order_info,
+            merchant_operator_config_info,
+            buyer_info,
+            payment.order_id,
+            |order| {
+                order.record_resolution(&Status::RefundApproved);
+                Ok(())
+            },
+        )?;
+    }
+
+    // Emit refund approved event
+    let event = RefundApprovedEvent {
+        discriminator: EventDiscriminators::RefundApproved as u8,
+        buyer: *buyer_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        approver: *authority_info.key(),
+        amount: payment.amount,
+        order_id: payment.order_id,
+        idempotency_key: payment.idempotency_key,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_bytes(),
+    )?;
+
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::RefundApproved,
+        authority_info.key(),
+    )?;
+
+    Ok(())
+}
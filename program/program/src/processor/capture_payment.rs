@@ -0,0 +1,312 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo, instruction::Signer, program_error::ProgramError, pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    events::{EventDiscriminators, PaymentCapturedEvent, PaymentLifecycleStatus},
+    processor::{
+        emit_event, emit_order_status_changed, emit_payment_status_changed, escrow_authority_seeds,
+        get_ata, release_open_payments, release_risk_hold, split_operator_freeze_list, split_order,
+        split_risk_states, split_transfer_hook_accounts, update_merchant_stats, update_order,
+        validate_buyer_not_frozen, validate_escrow_authority, validate_operator_authority,
+        validate_payment_order, verify_current_program, verify_owner, verify_owner_mutability,
+        verify_signer, verify_system_program, verify_token_interface_program, TokenTransfer,
+    },
+    require_len,
+    state::{Merchant, MerchantOperatorConfig, Operator, OperatorPermission, Payment, Status},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_capture_payment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [fee_payer_info, payment_info, operator_authority_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, escrow_authority_info, merchant_escrow_ata_info, buyer_ata_info, token_program_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // The order, if passed, trails the other remaining accounts
+    let (order_info, remaining_accounts) = split_order(remaining_accounts);
+
+    // RiskState accounts, if any, trail the other remaining accounts before the Order
+    let (risk_state_infos, remaining_accounts) = split_risk_states(remaining_accounts);
+
+    // The operator's OperatorFreezeList, if passed, trails the other remaining accounts before
+    // the RiskState accounts
+    let (operator_freeze_list_info, remaining_accounts) =
+        split_operator_freeze_list(remaining_accounts);
+
+    // Validate fee_payer is writable signer
+    verify_signer(fee_payer_info, true)?;
+
+    // Validate operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate payment is writable and owned by this program
+    verify_owner_mutability(payment_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate operator is owned by the program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_operator_config is owned by this program and writable for its
+    // open-payment count
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Verify system program
+    verify_system_program(system_program_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to clear payments
+    let remaining_accounts = validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::Clear,
+    )?;
+
+    // Validate merchant is a Merchant account
+    Merchant::try_from_bytes(&merchant_info.try_borrow_data()?)?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, policies, _allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+
+    // Validate merchant_operator_config PDA
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate operator and merchant match the config
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    // Load and validate payment
+    let mut payment_data = payment_info.try_borrow_mut_data()?;
+    let mut payment = Payment::try_from_bytes(&payment_data)?;
+
+    // Validate payment status is Authorized (only holds can be captured)
+    payment.validate_status(Status::Authorized)?;
+
+    // Validate Payment PDA
+    // No need to validate mint since it's validated via the PDA seed
+    payment.validate_pda(
+        payment_info.key(),
+        merchant_operator_config_info.key(),
+        buyer_info.key(),
+        mint_info.key(),
+    )?;
+
+    // An order payment records every status change on its Order
+    validate_payment_order(order_info, payment.has_order)?;
+
+    // Validate the capture is within the hold
+    let released_amount = payment
+        .amount
+        .checked_sub(args.amount)
+        .ok_or(CommerceProgramError::CaptureAmountExceedsAuthorization)?;
+
+    if released_amount > 0 {
+        // Validate the buyer the rest of the hold goes back to is not on the operator's
+        // freeze list
+        validate_buyer_not_frozen(
+            operator_freeze_list_info,
+            operator_info.key(),
+            &operator,
+            buyer_info.key(),
+        )?;
+
+        // Validate the escrow authority PDA and the escrow ATA it owns
+        let escrow_authority_bump = validate_escrow_authority(
+            escrow_authority_info,
+            merchant_operator_config_info.key(),
+            mint_info.key(),
+        )?;
+        get_ata(
+            merchant_escrow_ata_info,
+            escrow_authority_info.key(),
+            mint_info,
+            token_program_info,
+        )?;
+
+        // Validate buyer ATA (owned by buyer)
+        get_ata(
+            buyer_ata_info,
+            buyer_info.key(),
+            mint_info,
+            token_program_info,
+        )?;
+
+        // Release the rest of the hold from escrow back to the buyer
+        let bump_seed = [escrow_authority_bump];
+        let signer_seeds = escrow_authority_seeds(
+            merchant_operator_config_info.key(),
+            mint_info.key(),
+            &bump_seed,
+        );
+
+        TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+            merchant_escrow_ata_info,
+            buyer_ata_info,
+            escrow_authority_info,
+            released_amount,
+            &[Signer::from(&signer_seeds)],
+        )?;
+
+        // The released funds no longer count towards the risk policy's daily limits, nor
+        // does the payment when nothing was captured
+        release_risk_hold(
+            &policies,
+            risk_state_infos,
+            merchant_operator_config_info,
+            buyer_info,
+            released_amount,
+            args.amount == 0,
+            payment.created_at,
+        )?;
+    }
+
+    // The payment now stands for the captured amount, or was released in full
+    if args.amount > 0 {
+        payment.amount = args.amount;
+        payment.status = Status::Paid;
+    } else {
+        payment.status = Status::Refunded;
+    }
+
+    payment.store(&mut payment_data)?;
+
+    // A payment released in full no longer holds funds in escrow
+    drop(merchant_operator_config_data);
+    if payment.status == Status::Refunded {
+        release_open_payments(merchant_operator_config_info, mint_info.key(), 1)?;
+    }
+
+    // Update optional merchant stats: an authorization counts as a payment once captured
+    update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+        if args.amount > 0 {
+            stats.record_payment(args.amount);
+        }
+    })?;
+
+    // Record a full release on the payment's order, if any
+    let order_status = match order_info {
+        Some(order_info) => update_order(
+            order_info,
+            merchant_operator_config_info,
+            buyer_info,
+            payment.order_id,
+            |order| {
+                order.record_resolution(&payment.status);
+                Ok(())
+            },
+        )?,
+        None => None,
+    };
+
+    // Emit payment captured event
+    let event = PaymentCapturedEvent {
+        discriminator: EventDiscriminators::PaymentCaptured as u8,
+        buyer: *buyer_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        amount: args.amount,
+        released_amount,
+        order_id: payment.order_id,
+        idempotency_key: payment.idempotency_key,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )?;
+
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Authorized,
+        (&payment.status).into(),
+        operator_authority_info.key(),
+    )?;
+
+    emit_order_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        buyer_info.key(),
+        merchant_info.key(),
+        operator_info.key(),
+        payment.order_id,
+        order_status,
+    )?;
+
+    Ok(())
+}
+
+struct CapturePaymentArgs {
+    amount: u64,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<CapturePaymentArgs, ProgramError> {
+    require_len!(data, 8);
+
+    let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
+
+    Ok(CapturePaymentArgs { amount })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let args = process_instruction_data(&750_000u64.to_le_bytes()).unwrap();
+        assert_eq!(args.amount, 750_000);
+
+        // Capturing nothing releases the whole hold
+        let args = process_instruction_data(&0u64.to_le_bytes()).unwrap();
+        assert_eq!(args.amount, 0);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[1, 0, 0]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -0,0 +1,261 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    events::{EventDiscriminators, PaymentChargebackedEvent, PaymentLifecycleStatus},
+    processor::{
+        emit_event, emit_order_status_changed, emit_payment_status_changed, emit_transfer_memo,
+        escrow_authority_seeds, get_ata, get_chargeback_policy, get_memo_policy,
+        release_open_payments, split_memo_program, split_operator_freeze_list, split_order,
+        split_transfer_hook_accounts, update_merchant_stats, update_order,
+        validate_buyer_not_frozen, validate_escrow_authority, validate_payment_order,
+        verify_current_program, verify_owner, verify_owner_mutability, verify_signer,
+        verify_system_program, verify_token_interface_program, TokenTransfer,
+    },
+    state::{Merchant, MerchantOperatorConfig, Operator, Payment, Status},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_chargeback_payment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [fee_payer_info, payment_info, arbiter_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, escrow_authority_info, merchant_escrow_ata_info, buyer_ata_info, token_program_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // The memo program, if passed, trails the other remaining accounts
+    let (memo_program_info, remaining_accounts) = split_memo_program(remaining_accounts);
+
+    // The order, if passed, trails the other remaining accounts before the memo program
+    let (order_info, remaining_accounts) = split_order(remaining_accounts);
+
+    // The operator's OperatorFreezeList, if passed, trails the other remaining accounts before
+    // the Order
+    let (operator_freeze_list_info, remaining_accounts) =
+        split_operator_freeze_list(remaining_accounts);
+
+    // Validate fee_payer is writable signer
+    verify_signer(fee_payer_info, true)?;
+
+    // Validate arbiter should have signed
+    verify_signer(arbiter_info, false)?;
+
+    // Validate payment is writable and owned by this program
+    verify_owner_mutability(payment_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate operator is owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_operator_config is owned by this program and writable for its
+    // open-payment count
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Verify system program
+    verify_system_program(system_program_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate merchant is a Merchant account
+    Merchant::try_from_bytes(&merchant_info.try_borrow_data()?)?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, policies, _allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+
+    // Validate merchant_operator_config PDA
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate operator and merchant match the config
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    // Validate arbiter is the chargeback policy's arbiter
+    let chargeback =
+        get_chargeback_policy(&policies).ok_or(CommerceProgramError::ChargebackPolicyRequired)?;
+    if arbiter_info.key() != &chargeback.arbiter {
+        return Err(CommerceProgramError::ChargebackArbiterMismatch.into());
+    }
+
+    // Validate the refund destination is not on the operator's freeze list
+    validate_buyer_not_frozen(
+        operator_freeze_list_info,
+        operator_info.key(),
+        &operator,
+        buyer_info.key(),
+    )?;
+
+    // Load and validate payment
+    let mut payment_data = payment_info.try_borrow_mut_data()?;
+    let mut payment = Payment::try_from_bytes(&payment_data)?;
+
+    // Validate payment status is Paid (cleared payments have left escrow)
+    payment.validate_status(Status::Paid)?;
+
+    // Validate Payment PDA
+    // No need to validate mint since it's validated via the PDA seed
+    payment.validate_pda(
+        payment_info.key(),
+        merchant_operator_config_info.key(),
+        buyer_info.key(),
+        mint_info.key(),
+    )?;
+
+    // An order payment records every status change on its Order
+    validate_payment_order(order_info, payment.has_order)?;
+
+    // Validate the chargeback window is still open
+    if !chargeback.is_window_open(payment.created_at, Clock::get()?.unix_timestamp) {
+        return Err(CommerceProgramError::ChargebackWindowExpired.into());
+    }
+
+    // Validate the escrow authority PDA and the escrow ATA it owns
+    let escrow_authority_bump = validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    get_ata(
+        merchant_escrow_ata_info,
+        escrow_authority_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate buyer ATA (owned by buyer)
+    get_ata(
+        buyer_ata_info,
+        buyer_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Transfer tokens from merchant escrow back to buyer
+    // Use the escrow authority PDA as authority for the transfer
+    let bump_seed = [escrow_authority_bump];
+    let signer_seeds = escrow_authority_seeds(
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        &bump_seed,
+    );
+
+    // Attach a memo to the transfer if the memo policy asks for one
+    emit_transfer_memo(
+        get_memo_policy(&policies).is_some_and(|memo| memo.memo_on_refund),
+        memo_program_info,
+        payment.order_id,
+        payment_info.key(),
+    )?;
+
+    TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+        merchant_escrow_ata_info,
+        buyer_ata_info,
+        escrow_authority_info,
+        payment.amount,
+        &[Signer::from(&signer_seeds)],
+    )?;
+
+    // Update payment status to refunded and save
+    payment.status = Status::Refunded;
+
+    payment.store(&mut payment_data)?;
+
+    // The payment's funds have left escrow
+    drop(merchant_operator_config_data);
+    release_open_payments(merchant_operator_config_info, mint_info.key(), 1)?;
+
+    // Update optional merchant stats
+    update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+        stats.record_refund(payment.amount)
+    })?;
+
+    // Record the refund on the payment's order, if any
+    let order_status = match order_info {
+        Some(order_info) => update_order(
+            order_info,
+            merchant_operator_config_info,
+            buyer_info,
+            payment.order_id,
+            |order| {
+                order.record_resolution(&Status::Refunded);
+                Ok(())
+            },
+        )?,
+        None => None,
+    };
+
+    // Emit payment chargebacked event
+    let event = PaymentChargebackedEvent {
+        discriminator: EventDiscriminators::PaymentChargebacked as u8,
+        buyer: *buyer_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        amount: payment.amount,
+        order_id: payment.order_id,
+        idempotency_key: payment.idempotency_key,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_bytes(),
+    )?;
+
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::Refunded,
+        arbiter_info.key(),
+    )?;
+
+    emit_order_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        buyer_info.key(),
+        merchant_info.key(),
+        operator_info.key(),
+        payment.order_id,
+        order_status,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,260 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo, instruction::Signer, program_error::ProgramError, pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    events::{EventDiscriminators, PaymentLifecycleStatus, PaymentRefundedEvent},
+    processor::{
+        emit_event, emit_order_status_changed, emit_payment_status_changed, emit_transfer_memo,
+        escrow_authority_seeds, get_ata, get_memo_policy, release_open_payments,
+        split_memo_program, split_operator_freeze_list, split_order, split_transfer_hook_accounts,
+        token_account_mint, token_account_owner, update_merchant_stats, update_order,
+        validate_buyer_not_frozen, validate_escrow_authority, validate_payment_order,
+        verify_current_program, verify_owner, verify_owner_mutability, verify_signer,
+        verify_token_interface_program, verify_writable, TokenTransfer,
+    },
+    state::{Merchant, MerchantOperatorConfig, Operator, Payment, RefundApproval, Status},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_claim_refund(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [payer_info, payment_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, refund_approval_info, rent_payer_info, escrow_authority_info, merchant_escrow_ata_info, destination_info, token_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // The memo program, if passed, trails the other remaining accounts
+    let (memo_program_info, remaining_accounts) = split_memo_program(remaining_accounts);
+
+    // The order, if passed, trails the other remaining accounts before the memo program
+    let (order_info, remaining_accounts) = split_order(remaining_accounts);
+
+    // The operator's OperatorFreezeList, if passed, trails the other remaining accounts before
+    // the Order
+    let (operator_freeze_list_info, remaining_accounts) =
+        split_operator_freeze_list(remaining_accounts);
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate buyer should have signed
+    verify_signer(buyer_info, false)?;
+
+    // Validate payment and refund_approval are writable and owned by this program
+    verify_owner_mutability(payment_info, &COMMERCE_PROGRAM_ID, true)?;
+    verify_owner_mutability(refund_approval_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate rent_payer is writable
+    verify_writable(rent_payer_info)?;
+
+    // Validate merchant, operator and merchant_operator_config are owned by this program, the
+    // config writable for its open-payment count
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator and merchant
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate merchant is a Merchant account
+    Merchant::try_from_bytes(&merchant_info.try_borrow_data()?)?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&merchant_operator_config_data)?;
+    let policies = merchant_operator_config.get_policies(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    // Validate the refund destination is not on the operator's freeze list
+    validate_buyer_not_frozen(
+        operator_freeze_list_info,
+        operator_info.key(),
+        &operator,
+        buyer_info.key(),
+    )?;
+
+    // Load and validate payment
+    let mut payment_data = payment_info.try_borrow_mut_data()?;
+    let mut payment = Payment::try_from_bytes(&payment_data)?;
+
+    // Only approved refunds can be claimed, and only once
+    payment.validate_status(Status::RefundApproved)?;
+
+    // Validate Payment PDA, which also ties the payment to the signing buyer
+    payment.validate_pda(
+        payment_info.key(),
+        merchant_operator_config_info.key(),
+        buyer_info.key(),
+        mint_info.key(),
+    )?;
+
+    // An order payment records every status change on its Order
+    validate_payment_order(order_info, payment.has_order)?;
+
+    // Load and validate refund_approval
+    let refund_approval = {
+        let refund_approval_data = refund_approval_info.try_borrow_data()?;
+        RefundApproval::try_from_bytes(&refund_approval_data)?
+    };
+    refund_approval.validate_pda(refund_approval_info.key())?;
+    refund_approval.validate_payment(payment_info.key())?;
+
+    // The approval's rent goes back to whoever paid it
+    refund_approval.validate_rent_payer(rent_payer_info.key())?;
+
+    // Validate the escrow authority PDA and the escrow ATA it owns
+    let escrow_authority_bump = validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    get_ata(
+        merchant_escrow_ata_info,
+        escrow_authority_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate destination is a token account for the mint owned by the buyer
+    let destination_owner = token_account_owner(destination_info, token_program_info);
+    let destination_mint = token_account_mint(destination_info, token_program_info);
+    if destination_owner.as_ref() != Some(buyer_info.key())
+        || destination_mint.as_ref() != Some(mint_info.key())
+    {
+        return Err(CommerceProgramError::InvalidRefundDestination.into());
+    }
+
+    // Transfer tokens from merchant escrow to the buyer's destination
+    // Use the escrow authority PDA as authority for the transfer
+    let bump_seed = [escrow_authority_bump];
+    let signer_seeds = escrow_authority_seeds(
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        &bump_seed,
+    );
+
+    // Attach a memo to the transfer if the memo policy asks for one
+    emit_transfer_memo(
+        get_memo_policy(&policies).is_some_and(|memo| memo.memo_on_refund),
+        memo_program_info,
+        payment.order_id,
+        payment_info.key(),
+    )?;
+
+    TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+        merchant_escrow_ata_info,
+        destination_info,
+        escrow_authority_info,
+        refund_approval.amount,
+        &[Signer::from(&signer_seeds)],
+    )?;
+
+    // Update payment status to refunded and save
+    payment.status = Status::Refunded;
+
+    payment.store(&mut payment_data)?;
+
+    // The payment's funds have left escrow
+    drop(merchant_operator_config_data);
+    release_open_payments(merchant_operator_config_info, mint_info.key(), 1)?;
+
+    // Close the claimed approval, refunding its rent
+    let rent_payer_lamports = rent_payer_info.lamports();
+    *rent_payer_info.try_borrow_mut_lamports()? = rent_payer_lamports
+        .checked_add(refund_approval_info.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    *refund_approval_info.try_borrow_mut_lamports()? = 0;
+    refund_approval_info.close()?;
+
+    // Update optional merchant stats
+    update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+        stats.record_refund(refund_approval.amount)
+    })?;
+
+    // Record the refund on the payment's order, if any
+    let order_status = match order_info {
+        Some(order_info) => update_order(
+            order_info,
+            merchant_operator_config_info,
+            buyer_info,
+            payment.order_id,
+            |order| {
+                order.record_resolution(&Status::Refunded);
+                Ok(())
+            },
+        )?,
+        None => None,
+    };
+
+    // Emit payment refunded event
+    let event = PaymentRefundedEvent {
+        discriminator: EventDiscriminators::PaymentRefunded as u8,
+        buyer: *buyer_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        amount: refund_approval.amount,
+        order_id: payment.order_id,
+        idempotency_key: payment.idempotency_key,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )?;
+
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::RefundApproved,
+        PaymentLifecycleStatus::Refunded,
+        buyer_info.key(),
+    )?;
+
+    emit_order_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        buyer_info.key(),
+        merchant_info.key(),
+        operator_info.key(),
+        payment.order_id,
+        order_status,
+    )?;
+
+    Ok(())
+}
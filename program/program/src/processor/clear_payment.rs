@@ -1,32 +1,44 @@
 extern crate alloc;
 
-use crate::processor::{emit_event, verify_current_program};
+use crate::processor::{
+    emit_event, emit_order_status_changed, emit_payment_status_changed, emit_transfer_memo,
+    escrow_authority_seeds, get_callback_policy, get_memo_policy, get_platform_fee_policy,
+    get_settlement_policy, get_split_settlement_policy, invoke_payment_callback,
+    record_settlement_batch_clear, release_open_payments, split_callback_accounts,
+    split_memo_program, split_order, split_platform_settlement_ata, split_settlement_batch,
+    split_settlement_destinations, transfer_split_settlement, update_merchant_stats, update_order,
+    validate_chargeback_window_closed, validate_escrow_authority, validate_payment_order,
+    validate_split_settlement_destinations, verify_current_program,
+};
 use crate::{
-    constants::MAX_BPS,
-    events::{EventDiscriminators, PaymentClearedEvent},
-    processor::{get_or_create_ata, verify_ata_program},
+    events::{
+        EventDiscriminators, PaymentClearedEvent, PaymentLifecycleStatus, PlatformFeeCollectedEvent,
+    },
+    processor::{
+        get_or_create_operator_settlement_ata, split_transfer_hook_accounts, verify_ata_program,
+        verify_owner, verify_token_interface_program, TokenTransfer,
+    },
     ID as COMMERCE_PROGRAM_ID,
 };
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::{Seed, Signer},
+    instruction::Signer,
     program_error::ProgramError,
     pubkey::Pubkey,
     sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
-use pinocchio_token::instructions::Transfer;
 
 use crate::{
-    constants::{MERCHANT_SEED, SECONDS_PER_HOUR},
     error::CommerceProgramError,
+    math::{bps_of, elapsed_seconds, hours_to_seconds},
     processor::{
-        get_ata, verify_owner_mutability, verify_signer, verify_system_program,
-        verify_token_program, verify_token_program_account,
+        get_ata, validate_clear_authority, verify_owner_mutability, verify_signer,
+        verify_system_program,
     },
     state::{
-        discriminator::AccountSerialize, policy::FeeType, Merchant, MerchantOperatorConfig,
-        Operator, Payment, PolicyData, PolicyType, Status,
+        policy::FeeType, Merchant, MerchantOperatorConfig, Operator, Payment, PaymentView,
+        PolicyData, PolicyType, Status, ZeroCopy,
     },
 };
 
@@ -36,12 +48,30 @@ pub fn process_clear_payment(
     accounts: &[AccountInfo],
     _instruction_data: &[u8],
 ) -> ProgramResult {
-    let [fee_payer_info, payment_info, operator_authority_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, merchant_escrow_ata_info, merchant_settlement_ata_info, operator_settlement_ata_info, token_program_info, associated_token_program_info, system_program_info, event_authority_info, commerce_program_info] =
+    let [fee_payer_info, payment_info, operator_authority_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, escrow_authority_info, merchant_escrow_ata_info, merchant_settlement_ata_info, operator_settlement_ata_info, token_program_info, associated_token_program_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    // The mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // The memo program, if passed, trails the other remaining accounts
+    let (memo_program_info, remaining_accounts) = split_memo_program(remaining_accounts);
+
+    // The callback program and the accounts forwarded to it, if passed, trail the other
+    // remaining accounts before the memo program
+    let (callback, remaining_accounts) = split_callback_accounts(remaining_accounts);
+
+    // The settlement batch, if passed, trails the other remaining accounts before the callback
+    // program
+    let (settlement_batch_info, remaining_accounts) = split_settlement_batch(remaining_accounts);
+
+    // The order, if passed, trails the other remaining accounts before the settlement batch
+    let (order_info, remaining_accounts) = split_order(remaining_accounts);
+
     // Validate fee_payer is writable signer
     verify_signer(fee_payer_info, true)?;
 
@@ -57,14 +87,15 @@ pub fn process_clear_payment(
     // Validate operator is owned by the program
     verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
 
-    // Validate merchant_operator_config is owned by this program
-    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+    // Validate merchant_operator_config is owned by this program and writable for its
+    // open-payment count
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
 
-    // Validate mint is owned by token program
-    verify_token_program_account(mint_info)?;
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
 
-    // Validate token program
-    verify_token_program(token_program_info)?;
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
 
     // Verify system program
     verify_system_program(system_program_info)?;
@@ -79,15 +110,15 @@ pub fn process_clear_payment(
     let operator_data = operator_info.try_borrow_data()?;
     let operator = Operator::try_from_bytes(&operator_data)?;
     operator.validate_pda(operator_info.key())?;
-    operator.validate_owner(operator_authority_info.key())?;
 
     let merchant_data = merchant_info.try_borrow_data()?;
     let merchant = Merchant::try_from_bytes(&merchant_data)?;
 
     // Load and validate merchant_operator_config
     let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
-    let (merchant_operator_config, policies, allowed_mints) =
-        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    let merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&merchant_operator_config_data)?;
+    let policies = merchant_operator_config.get_policies(&merchant_operator_config_data)?;
 
     // Validate merchant_operator_config PDA
     merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
@@ -96,11 +127,52 @@ pub fn process_clear_payment(
     merchant_operator_config.validate_operator(operator_info.key())?;
     merchant_operator_config.validate_merchant(merchant_info.key())?;
 
-    // Validate mint is in the allowed_mints
-    if !allowed_mints.contains(mint_info.key()) {
+    // Validate mint is an accepted currency
+    if !merchant_operator_config
+        .accepts_currency(&merchant_operator_config_data, mint_info.key())?
+    {
         return Err(CommerceProgramError::InvalidMint.into());
     }
 
+    // Validate operator_authority is the owner or a delegate allowed to clear payments, or the
+    // merchant authority once the operator is inactive under an operator failover policy
+    let now = Clock::get()?.unix_timestamp;
+    let remaining_accounts = validate_clear_authority(
+        &operator,
+        operator_info,
+        &merchant,
+        operator_authority_info,
+        remaining_accounts,
+        &policies,
+        now,
+    )?;
+
+    // With a split settlement policy, its destination ATAs trail the other remaining accounts
+    // before the order
+    let split_settlement = get_split_settlement_policy(&policies);
+    let (split_destination_infos, remaining_accounts) = match split_settlement {
+        Some(split_settlement) => {
+            // A settlement batch pays out to the settlement wallet, bypassing the split
+            if settlement_batch_info.is_some() {
+                return Err(CommerceProgramError::SplitSettlementUnsupported.into());
+            }
+            split_settlement_destinations(remaining_accounts, split_settlement)?
+        }
+        None => (&[][..], remaining_accounts),
+    };
+
+    // With a platform fee policy, the platform's settlement ATA trails the other remaining
+    // accounts before the split settlement destinations
+    let platform_fee = get_platform_fee_policy(&policies);
+    let (platform_settlement_ata_info, remaining_accounts) = match platform_fee {
+        Some(_) => {
+            let (platform_settlement_ata_info, remaining_accounts) =
+                split_platform_settlement_ata(remaining_accounts)?;
+            (Some(platform_settlement_ata_info), remaining_accounts)
+        }
+        None => (None, remaining_accounts),
+    };
+
     // Load and validate payment
     let mut payment_data = payment_info.try_borrow_mut_data()?;
     let mut payment = Payment::try_from_bytes(&payment_data)?;
@@ -116,46 +188,95 @@ pub fn process_clear_payment(
         mint_info.key(),
     )?;
 
-    // Validate settlement policy conditions
-    validate_settlement_policy(&policies, &payment)?;
+    // An order payment records every status change on its Order
+    validate_payment_order(order_info, payment.has_order)?;
 
-    // Validate merchant escrow ATA (owned by merchant pda)
+    // Validate settlement policy conditions, which a settlement batch checks on its total instead
+    if settlement_batch_info.is_none() {
+        validate_settlement_policy(&policies, payment.amount, payment.created_at)?;
+    }
+
+    // Validate the chargeback window, if any, has passed
+    validate_chargeback_window_closed(&policies, payment.created_at, now)?;
+
+    // Validate the escrow authority PDA and the escrow ATA it owns
+    let escrow_authority_bump = validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
     get_ata(
         merchant_escrow_ata_info,
-        merchant_info.key(),
+        escrow_authority_info.key(),
         mint_info,
         token_program_info,
     )?;
 
-    // Validate merchant settlement ATA (owned by merchant pda)
-    get_ata(
-        merchant_settlement_ata_info,
-        &merchant.settlement_wallet,
-        mint_info,
-        token_program_info,
-    )?;
+    // Validate where the merchant amount goes, unless it stays in escrow
+    if let Some(split_settlement) = split_settlement {
+        validate_split_settlement_destinations(
+            split_destination_infos,
+            split_settlement,
+            mint_info,
+            token_program_info,
+        )?;
+    } else if settlement_batch_info.is_none() {
+        // Validate merchant settlement ATA (owned by merchant pda)
+        get_ata(
+            merchant_settlement_ata_info,
+            &merchant.settlement_wallet,
+            mint_info,
+            token_program_info,
+        )?;
+    }
+
+    // Validate the platform settlement ATA (owned by the policy's platform)
+    if let (Some(platform_fee), Some(platform_settlement_ata_info)) =
+        (platform_fee, platform_settlement_ata_info)
+    {
+        get_ata(
+            platform_settlement_ata_info,
+            &platform_fee.platform,
+            mint_info,
+            token_program_info,
+        )?;
+    }
 
     // Calculate operator fee and merchant amount
-    let (operator_fee_amount, merchant_amount) = calculate_fees(
-        payment.amount,
-        merchant_operator_config.operator_fee,
-        &merchant_operator_config.fee_type,
-    )?;
+    let (operator_fee_amount, merchant_amount) =
+        calculate_payment_fees(&payment, &merchant_operator_config, &policies, now)?;
+
+    // The platform's fee comes out of the merchant amount, after the operator's
+    let platform_fee_amount = platform_fee
+        .map(|platform_fee| platform_fee.fee_amount(payment.amount, merchant_amount))
+        .unwrap_or(0);
+    let merchant_amount = merchant_amount
+        .checked_sub(platform_fee_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    // Use PDA as authority for the transfers
-    let bump_seed = [merchant.bump];
-    let signer_seeds = [
-        Seed::from(MERCHANT_SEED),
-        Seed::from(merchant.owner.as_ref()),
-        Seed::from(&bump_seed),
-    ];
+    let token_transfer = TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?;
+
+    // Use the escrow authority PDA as authority for the transfers
+    let bump_seed = [escrow_authority_bump];
+    let signer_seeds = escrow_authority_seeds(
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        &bump_seed,
+    );
+
+    // Attach a memo to the transfers if the memo policy asks for one
+    emit_transfer_memo(
+        get_memo_policy(&policies).is_some_and(|memo| memo.memo_on_clear),
+        memo_program_info,
+        payment.order_id,
+        payment_info.key(),
+    )?;
 
     // Transfer operator fee if applicable
     if operator_fee_amount > 0 {
-        // Validate operator settlement ATA (owned by operator owner)
-        // Create ATA if it doesn't exist
-
-        get_or_create_ata(
+        // Validate operator settlement ATA (owned by operator owner), creating it if missing
+        get_or_create_operator_settlement_ata(
+            &operator,
             operator_settlement_ata_info,
             operator_authority_info,
             mint_info,
@@ -164,29 +285,103 @@ pub fn process_clear_payment(
             token_program_info,
         )?;
 
-        Transfer {
-            from: merchant_escrow_ata_info,
-            to: operator_settlement_ata_info,
-            authority: merchant_info,
-            amount: operator_fee_amount,
-        }
-        .invoke_signed(&[Signer::from(&signer_seeds)])?;
+        token_transfer.transfer(
+            merchant_escrow_ata_info,
+            operator_settlement_ata_info,
+            escrow_authority_info,
+            operator_fee_amount,
+            &[Signer::from(&signer_seeds)],
+        )?;
     }
 
-    // Transfer remaining amount to merchant settlement wallet
-    Transfer {
-        from: merchant_escrow_ata_info,
-        to: merchant_settlement_ata_info,
-        authority: merchant_info,
-        amount: merchant_amount,
+    // Transfer platform fee if applicable
+    if let Some(platform_settlement_ata_info) =
+        platform_settlement_ata_info.filter(|_| platform_fee_amount > 0)
+    {
+        token_transfer.transfer(
+            merchant_escrow_ata_info,
+            platform_settlement_ata_info,
+            escrow_authority_info,
+            platform_fee_amount,
+            &[Signer::from(&signer_seeds)],
+        )?;
+    }
+
+    if let Some(settlement_batch_info) = settlement_batch_info {
+        // Leave the remaining amount in escrow for ExecuteSettlementBatch
+        record_settlement_batch_clear(
+            settlement_batch_info,
+            merchant_operator_config_info,
+            mint_info,
+            merchant_amount,
+        )?;
+    } else if let Some(split_settlement) = split_settlement {
+        // Split the remaining amount across the policy's destinations
+        transfer_split_settlement(
+            &token_transfer,
+            merchant_escrow_ata_info,
+            escrow_authority_info,
+            split_destination_infos,
+            split_settlement,
+            merchant_amount,
+            &[Signer::from(&signer_seeds)],
+        )?;
+    } else {
+        // Transfer remaining amount to merchant settlement wallet
+        token_transfer.transfer(
+            merchant_escrow_ata_info,
+            merchant_settlement_ata_info,
+            escrow_authority_info,
+            merchant_amount,
+            &[Signer::from(&signer_seeds)],
+        )?;
     }
-    .invoke_signed(&[Signer::from(&signer_seeds)])?;
 
-    // Update payment status to cleared
+    // Update payment status to cleared, in place
     payment.status = Status::Cleared;
+    PaymentView::load_mut(&mut payment_data)?.set_status(Status::Cleared);
+
+    // The payment stays open while a settlement batch holds its funds in escrow, until
+    // ExecuteSettlementBatch pays them out
+    drop(merchant_operator_config_data);
+    if settlement_batch_info.is_none() {
+        release_open_payments(merchant_operator_config_info, mint_info.key(), 1)?;
+    }
 
-    // Save updated payment data
-    payment_data.copy_from_slice(&payment.to_bytes());
+    // Update optional merchant stats
+    let fees_amount = operator_fee_amount
+        .checked_add(platform_fee_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+        stats.record_clear(payment.amount, fees_amount)
+    })?;
+
+    // Let the merchant's program react, now that the payment is stored with its new status
+    drop(payment_data);
+    invoke_payment_callback(
+        get_callback_policy(&policies)
+            .filter(|callback| callback.on_clear)
+            .map(|callback| &callback.program),
+        callback,
+        payment_info,
+        &payment.status,
+        payment.amount,
+    )?;
+
+    // Record the clear on the payment's order, if any
+    let order_status = match order_info {
+        Some(order_info) => update_order(
+            order_info,
+            merchant_operator_config_info,
+            buyer_info,
+            payment.order_id,
+            |order| {
+                order.record_resolution(&Status::Cleared);
+                Ok(())
+            },
+        )?,
+        None => None,
+    };
 
     // Emit payment cleared event
     let event = PaymentClearedEvent {
@@ -197,43 +392,83 @@ pub fn process_clear_payment(
         amount: payment.amount,
         operator_fee: operator_fee_amount,
         order_id: payment.order_id,
+        idempotency_key: payment.idempotency_key,
     };
 
     emit_event(
         _program_id,
         event_authority_info,
         commerce_program_info,
-        &event.to_bytes(),
+        &event.to_array(),
+    )?;
+
+    if let Some(platform_fee) = platform_fee.filter(|_| platform_fee_amount > 0) {
+        let event = PlatformFeeCollectedEvent {
+            discriminator: EventDiscriminators::PlatformFeeCollected as u8,
+            buyer: event.buyer,
+            merchant: event.merchant,
+            operator: event.operator,
+            platform: platform_fee.platform,
+            order_id: payment.order_id,
+            amount: platform_fee_amount,
+        };
+
+        emit_event(
+            _program_id,
+            event_authority_info,
+            commerce_program_info,
+            &event.to_array(),
+        )?;
+    }
+
+    emit_payment_status_changed(
+        _program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::Cleared,
+        operator_authority_info.key(),
+    )?;
+
+    emit_order_status_changed(
+        _program_id,
+        event_authority_info,
+        commerce_program_info,
+        buyer_info.key(),
+        merchant_info.key(),
+        operator_info.key(),
+        payment.order_id,
+        order_status,
     )?;
 
     Ok(())
 }
 
-fn validate_settlement_policy(
+pub(crate) fn validate_settlement_policy(
     policies: &[PolicyData],
-    payment: &Payment,
+    amount: u64,
+    created_at: i64,
 ) -> Result<(), ProgramError> {
-    let Some(policy) = MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Settlement)
-    else {
+    let Some(settlement) = get_settlement_policy(policies) else {
         return Ok(()); // No settlement policy means no restrictions
     };
 
-    let PolicyData::Settlement(settlement) = policy else {
-        return Ok(()); // Should never happen since we found by type, but be safe
-    };
-
     // Check minimum settlement amount (0 means no limit)
-    if settlement.min_settlement_amount > 0 && payment.amount < settlement.min_settlement_amount {
+    if settlement.min_settlement_amount > 0 && amount < settlement.min_settlement_amount {
         return Err(CommerceProgramError::InsufficientSettlementAmount.into());
     }
 
     // Check settlement frequency (0 means no time restriction)
     if settlement.settlement_frequency_hours > 0 {
         let current_time = Clock::get()?.unix_timestamp;
-        let time_since_payment = current_time - payment.created_at;
-        let min_settlement_time = (settlement.settlement_frequency_hours as i64) * SECONDS_PER_HOUR;
+        let time_since_payment = elapsed_seconds(created_at, current_time);
+        let min_settlement_time = hours_to_seconds(settlement.settlement_frequency_hours);
 
-        if time_since_payment < min_settlement_time {
+        if time_since_payment < min_settlement_time as i128 {
             return Err(CommerceProgramError::SettlementTooEarly.into());
         }
     }
@@ -243,20 +478,76 @@ fn validate_settlement_policy(
     Ok(())
 }
 
-fn calculate_fees(
+/// Splits `payment` like `calculate_fees`, charging its fee override instead of the config's
+/// fee and fee tiers when MakePayment set one, less the settlement discount it earned by `now`.
+pub(crate) fn calculate_payment_fees(
+    payment: &Payment,
+    config: &MerchantOperatorConfig,
+    policies: &[PolicyData],
+    now: i64,
+) -> Result<(u64, u64), ProgramError> {
+    let fees = if payment.has_fee_override {
+        calculate_fees(
+            payment.amount,
+            payment.fee_override.fee,
+            &payment.fee_override.fee_type,
+            &[],
+        )
+    } else {
+        calculate_fees(
+            payment.amount,
+            config.operator_fee,
+            &config.fee_type,
+            policies,
+        )
+    }?;
+
+    apply_settlement_discount(fees, policies, payment.created_at, now)
+}
+
+/// Moves the discount a payment made at `created_at` earned by `now` under a
+/// SettlementDiscountPolicy from the operator fee to the merchant amount.
+pub(crate) fn apply_settlement_discount(
+    (operator_fee_amount, merchant_amount): (u64, u64),
+    policies: &[PolicyData],
+    created_at: i64,
+    now: i64,
+) -> Result<(u64, u64), ProgramError> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::SettlementDiscount) {
+        Some(PolicyData::SettlementDiscount(settlement_discount)) => {
+            let discounted_fee =
+                settlement_discount.discounted_fee(operator_fee_amount, created_at, now);
+            let discount = operator_fee_amount
+                .checked_sub(discounted_fee)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            let merchant_amount = merchant_amount
+                .checked_add(discount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            Ok((discounted_fee, merchant_amount))
+        }
+        _ => Ok((operator_fee_amount, merchant_amount)),
+    }
+}
+
+pub(crate) fn calculate_fees(
     total_amount: u64,
     operator_fee: u64,
     fee_type: &FeeType,
+    policies: &[PolicyData],
 ) -> Result<(u64, u64), ProgramError> {
+    // A matching fee tier overrides the config's flat fee
+    let (operator_fee, fee_type) =
+        match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::FeeTier) {
+            Some(PolicyData::FeeTier(fee_tiers)) => fee_tiers
+                .tier_for_amount(total_amount)
+                .map(|tier| (tier.fee, &tier.fee_type))
+                .unwrap_or((operator_fee, fee_type)),
+            _ => (operator_fee, fee_type),
+        };
+
     let operator_fee_amount = match fee_type {
-        FeeType::Bps => {
-            // Calculate basis points (1 bps = 0.01%)
-            // Safe math: (amount * bps) / 10000
-            total_amount
-                .checked_mul(operator_fee)
-                .and_then(|v| v.checked_div(MAX_BPS))
-                .ok_or(ProgramError::ArithmeticOverflow)?
-        }
+        // Calculate basis points (1 bps = 0.01%)
+        FeeType::Bps => bps_of(total_amount, operator_fee),
         FeeType::Fixed => {
             // Fixed fee in current mint - cap at total_amount to prevent trapped funds
             operator_fee.min(total_amount)
@@ -275,14 +566,20 @@ fn calculate_fees(
 mod tests {
     use super::*;
     use crate::constants::MAX_BPS;
-    use crate::state::policy::{PolicyData, SettlementPolicy};
-    use crate::state::{FeeType, Payment, Status};
-    use alloc::vec;
+    use crate::constants::SECONDS_PER_DAY;
+    use crate::state::policy::{
+        FeeTier, FeeTierPolicy, PlatformFeePolicy, PolicyData, SettlementDiscountPolicy,
+        SettlementPolicy, SplitSettlementPolicy, MAX_FEE_TIERS, MAX_SPLIT_DESTINATIONS,
+    };
+    use crate::state::{FeeOverride, FeeType, Payment, Status};
+    use alloc::{vec, vec::Vec};
+    use commerce_fee_cases::{Fee, FEE_CASES};
 
     #[test]
     fn test_calculate_fees_bps_normal() {
         // 2.5% fee (250 bps) on 10000 units = 250 units fee, 9750 merchant
-        let (operator_fee, merchant_amount) = calculate_fees(10000, 250, &FeeType::Bps).unwrap();
+        let (operator_fee, merchant_amount) =
+            calculate_fees(10000, 250, &FeeType::Bps, &[]).unwrap();
         assert_eq!(operator_fee, 250);
         assert_eq!(merchant_amount, 9750);
     }
@@ -290,14 +587,15 @@ mod tests {
     #[test]
     fn test_calculate_fees_bps_high_fee() {
         // 50% fee (5000 bps) on 1000 units = 500 units fee, 500 merchant
-        let (operator_fee, merchant_amount) = calculate_fees(1000, 5000, &FeeType::Bps).unwrap();
+        let (operator_fee, merchant_amount) =
+            calculate_fees(1000, 5000, &FeeType::Bps, &[]).unwrap();
         assert_eq!(operator_fee, 500);
         assert_eq!(merchant_amount, 500);
     }
 
     #[test]
     fn test_calculate_fees_bps_zero_fee() {
-        let (operator_fee, merchant_amount) = calculate_fees(1000, 0, &FeeType::Bps).unwrap();
+        let (operator_fee, merchant_amount) = calculate_fees(1000, 0, &FeeType::Bps, &[]).unwrap();
         assert_eq!(operator_fee, 0);
         assert_eq!(merchant_amount, 1000);
     }
@@ -305,27 +603,43 @@ mod tests {
     #[test]
     fn test_calculate_fees_bps_max_fee() {
         // 100% fee (10000 bps) = everything goes to operator
-        let (operator_fee, merchant_amount) = calculate_fees(1000, MAX_BPS, &FeeType::Bps).unwrap();
+        let (operator_fee, merchant_amount) =
+            calculate_fees(1000, MAX_BPS, &FeeType::Bps, &[]).unwrap();
         assert_eq!(operator_fee, 1000);
         assert_eq!(merchant_amount, 0);
     }
 
+    #[test]
+    fn test_calculate_fees_bps_max_amount() {
+        let (operator_fee, merchant_amount) =
+            calculate_fees(u64::MAX, 250, &FeeType::Bps, &[]).unwrap();
+        assert_eq!(operator_fee, (u64::MAX as u128 * 250 / 10_000) as u64);
+        assert_eq!(merchant_amount, u64::MAX - operator_fee);
+
+        // A fee above MAX_BPS can't take more than the payment
+        assert_eq!(
+            calculate_fees(1000, MAX_BPS * 2, &FeeType::Bps, &[]).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
     #[test]
     fn test_calculate_fees_bps_rounding() {
         // 1 bps on 999 units = 0.99 units, should round down to 0
-        let (operator_fee, merchant_amount) = calculate_fees(999, 1, &FeeType::Bps).unwrap();
+        let (operator_fee, merchant_amount) = calculate_fees(999, 1, &FeeType::Bps, &[]).unwrap();
         assert_eq!(operator_fee, 0);
         assert_eq!(merchant_amount, 999);
 
         // 1 bps on 10000 units = 1 unit exactly
-        let (operator_fee, merchant_amount) = calculate_fees(10000, 1, &FeeType::Bps).unwrap();
+        let (operator_fee, merchant_amount) = calculate_fees(10000, 1, &FeeType::Bps, &[]).unwrap();
         assert_eq!(operator_fee, 1);
         assert_eq!(merchant_amount, 9999);
     }
 
     #[test]
     fn test_calculate_fees_fixed_normal() {
-        let (operator_fee, merchant_amount) = calculate_fees(1000, 100, &FeeType::Fixed).unwrap();
+        let (operator_fee, merchant_amount) =
+            calculate_fees(1000, 100, &FeeType::Fixed, &[]).unwrap();
         assert_eq!(operator_fee, 100);
         assert_eq!(merchant_amount, 900);
     }
@@ -333,18 +647,290 @@ mod tests {
     #[test]
     fn test_calculate_fees_fixed_exceeds_total() {
         // Fixed fee larger than total amount - should be capped at total
-        let (operator_fee, merchant_amount) = calculate_fees(500, 1000, &FeeType::Fixed).unwrap();
+        let (operator_fee, merchant_amount) =
+            calculate_fees(500, 1000, &FeeType::Fixed, &[]).unwrap();
         assert_eq!(operator_fee, 500);
         assert_eq!(merchant_amount, 0);
     }
 
     #[test]
     fn test_calculate_fees_fixed_zero_fee() {
-        let (operator_fee, merchant_amount) = calculate_fees(1000, 0, &FeeType::Fixed).unwrap();
+        let (operator_fee, merchant_amount) =
+            calculate_fees(1000, 0, &FeeType::Fixed, &[]).unwrap();
         assert_eq!(operator_fee, 0);
         assert_eq!(merchant_amount, 1000);
     }
 
+    fn create_fee_tier_policies() -> Vec<PolicyData> {
+        // 2% below 100_000_000 units, 1% from 100_000_000, 5_000_000 fixed from 1_000_000_000
+        let mut tiers = [FeeTier::EMPTY; MAX_FEE_TIERS];
+        tiers[0] = FeeTier {
+            threshold: 0,
+            fee: 200,
+            fee_type: FeeType::Bps,
+        };
+        tiers[1] = FeeTier {
+            threshold: 100_000_000,
+            fee: 100,
+            fee_type: FeeType::Bps,
+        };
+        tiers[2] = FeeTier {
+            threshold: 1_000_000_000,
+            fee: 5_000_000,
+            fee_type: FeeType::Fixed,
+        };
+        vec![PolicyData::FeeTier(FeeTierPolicy {
+            num_tiers: 3,
+            tiers,
+        })]
+    }
+
+    #[test]
+    fn test_calculate_fees_fee_tiers() {
+        let policies = create_fee_tier_policies();
+
+        let (operator_fee, merchant_amount) =
+            calculate_fees(50_000_000, 250, &FeeType::Bps, &policies).unwrap();
+        assert_eq!(operator_fee, 1_000_000);
+        assert_eq!(merchant_amount, 49_000_000);
+
+        let (operator_fee, merchant_amount) =
+            calculate_fees(100_000_000, 250, &FeeType::Bps, &policies).unwrap();
+        assert_eq!(operator_fee, 1_000_000);
+        assert_eq!(merchant_amount, 99_000_000);
+
+        let (operator_fee, merchant_amount) =
+            calculate_fees(2_000_000_000, 250, &FeeType::Bps, &policies).unwrap();
+        assert_eq!(operator_fee, 5_000_000);
+        assert_eq!(merchant_amount, 1_995_000_000);
+    }
+
+    #[test]
+    fn test_calculate_fees_below_lowest_tier_uses_config_fee() {
+        let mut policies = create_fee_tier_policies();
+        if let PolicyData::FeeTier(fee_tiers) = &mut policies[0] {
+            fee_tiers.tiers[0].threshold = 1_000;
+        }
+
+        let (operator_fee, merchant_amount) =
+            calculate_fees(999, 100, &FeeType::Fixed, &policies).unwrap();
+        assert_eq!(operator_fee, 100);
+        assert_eq!(merchant_amount, 899);
+    }
+
+    #[test]
+    fn test_calculate_payment_fees_fee_override() {
+        let config = MerchantOperatorConfig {
+            version: 1,
+            bump: 255,
+            merchant: [1u8; 32],
+            operator: [2u8; 32],
+            operator_fee: 100,
+            fee_type: FeeType::Fixed,
+            current_order_id: 0,
+            days_to_close: 0,
+            num_policies: 1,
+            num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        let policies = create_fee_tier_policies();
+        let mut payment = Payment {
+            order_id: 1,
+            amount: 50_000,
+            created_at: 1000000,
+            status: Status::Paid,
+            bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
+        };
+
+        // Without an override, the config fee and tiers apply
+        assert_eq!(
+            calculate_payment_fees(&payment, &config, &policies, payment.created_at).unwrap(),
+            calculate_fees(50_000, 100, &FeeType::Fixed, &policies).unwrap()
+        );
+
+        // The override replaces both
+        payment.has_fee_override = true;
+        payment.fee_override = FeeOverride {
+            fee: 20,
+            fee_type: FeeType::Bps,
+        };
+        assert_eq!(
+            calculate_payment_fees(&payment, &config, &policies, payment.created_at).unwrap(),
+            (100, 49_900)
+        );
+    }
+
+    #[test]
+    fn test_calculate_payment_fees_settlement_discount() {
+        let config = MerchantOperatorConfig {
+            version: 1,
+            bump: 255,
+            merchant: [1u8; 32],
+            operator: [2u8; 32],
+            operator_fee: 200,
+            fee_type: FeeType::Bps,
+            current_order_id: 0,
+            days_to_close: 0,
+            num_policies: 1,
+            num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        let policies = vec![PolicyData::SettlementDiscount(SettlementDiscountPolicy {
+            discount_bps_per_day: 1_000,
+            max_discount_bps: 5_000,
+        })];
+        let mut payment = Payment {
+            order_id: 1,
+            amount: 100_000,
+            created_at: 1000000,
+            status: Status::Paid,
+            bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
+        };
+        let days_later = |days: i64| payment.created_at + days * SECONDS_PER_DAY;
+
+        // Cleared the same day, the full 2% fee applies
+        assert_eq!(
+            calculate_payment_fees(&payment, &config, &policies, days_later(0)).unwrap(),
+            (2_000, 98_000)
+        );
+
+        // 10% of the fee comes off per full day, moving to the merchant
+        assert_eq!(
+            calculate_payment_fees(&payment, &config, &policies, days_later(2)).unwrap(),
+            (1_600, 98_400)
+        );
+
+        // Up to half the fee
+        assert_eq!(
+            calculate_payment_fees(&payment, &config, &policies, days_later(30)).unwrap(),
+            (1_000, 99_000)
+        );
+
+        // A fee override is discounted too
+        payment.has_fee_override = true;
+        payment.fee_override = FeeOverride {
+            fee: 500,
+            fee_type: FeeType::Fixed,
+        };
+        assert_eq!(
+            calculate_payment_fees(&payment, &config, &policies, days_later(3)).unwrap(),
+            (350, 99_650)
+        );
+    }
+
+    #[test]
+    fn test_apply_settlement_discount_overflow() {
+        let policies = vec![PolicyData::SettlementDiscount(SettlementDiscountPolicy {
+            discount_bps_per_day: 1_000,
+            max_discount_bps: 5_000,
+        })];
+
+        // No discount earned yet, nothing moves
+        assert_eq!(
+            apply_settlement_discount((2_000, u64::MAX), &policies, 0, 0).unwrap(),
+            (2_000, u64::MAX)
+        );
+
+        // A discount the merchant amount cannot hold fails instead of wrapping
+        assert_eq!(
+            apply_settlement_discount((2_000, u64::MAX), &policies, 0, 2 * SECONDS_PER_DAY)
+                .unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+
+        // Without the policy the fees pass through
+        assert_eq!(
+            apply_settlement_discount((2_000, u64::MAX), &[], 0, 2 * SECONDS_PER_DAY).unwrap(),
+            (2_000, u64::MAX)
+        );
+    }
+
+    fn fee_of(fee: Fee) -> (u64, FeeType) {
+        match fee {
+            Fee::Bps(fee) => (fee, FeeType::Bps),
+            Fee::Fixed(fee) => (fee, FeeType::Fixed),
+        }
+    }
+
+    #[test]
+    fn test_calculate_fees_shared_cases() {
+        for case in FEE_CASES {
+            let mut policies = Vec::new();
+            if !case.fee_tiers.is_empty() {
+                let mut tiers = [FeeTier::EMPTY; MAX_FEE_TIERS];
+                for (tier, tier_case) in tiers.iter_mut().zip(case.fee_tiers) {
+                    let (fee, fee_type) = fee_of(tier_case.fee);
+                    *tier = FeeTier {
+                        threshold: tier_case.threshold,
+                        fee,
+                        fee_type,
+                    };
+                }
+                policies.push(PolicyData::FeeTier(FeeTierPolicy {
+                    num_tiers: case.fee_tiers.len() as u8,
+                    tiers,
+                }));
+            }
+
+            // Split the way ClearPayment does after the operator fee
+            let (operator_fee, fee_type) = fee_of(case.operator_fee);
+            let outcome = calculate_fees(case.amount, operator_fee, &fee_type, &policies)
+                .ok()
+                .map(|(operator_fee, merchant_amount)| {
+                    let platform_fee = case.platform_fee.map_or(0, |platform_fee| {
+                        let (fee, fee_type) = fee_of(platform_fee);
+                        PlatformFeePolicy {
+                            platform: [1; 32],
+                            fee,
+                            fee_type,
+                        }
+                        .fee_amount(case.amount, merchant_amount)
+                    });
+                    let merchant_amount = merchant_amount - platform_fee;
+
+                    let mut shares_bps = [0; MAX_SPLIT_DESTINATIONS];
+                    shares_bps[..case.split_shares_bps.len()]
+                        .copy_from_slice(case.split_shares_bps);
+                    let split_amounts = SplitSettlementPolicy {
+                        destinations_hash: [1; 32],
+                        num_destinations: case.split_shares_bps.len() as u8,
+                        shares_bps,
+                    }
+                    .split(merchant_amount)[..case.split_shares_bps.len()]
+                        .to_vec();
+
+                    (operator_fee, platform_fee, merchant_amount, split_amounts)
+                });
+
+            let expected = case.expected.map(|expected| {
+                (
+                    expected.operator_fee,
+                    expected.platform_fee,
+                    expected.merchant_amount,
+                    expected.split_amounts.to_vec(),
+                )
+            });
+
+            assert_eq!(outcome, expected, "{}", case.name);
+        }
+    }
+
     #[test]
     fn test_validate_settlement_policy_no_policy() {
         let policies = vec![];
@@ -354,10 +940,16 @@ mod tests {
             created_at: 1000000,
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         // No policy should pass validation
-        assert!(validate_settlement_policy(&policies, &payment).is_ok());
+        assert!(validate_settlement_policy(&policies, payment.amount, payment.created_at).is_ok());
     }
 
     #[test]
@@ -375,9 +967,15 @@ mod tests {
             created_at: 1000000,
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
-        assert!(validate_settlement_policy(&policies, &payment).is_ok());
+        assert!(validate_settlement_policy(&policies, payment.amount, payment.created_at).is_ok());
     }
 
     #[test]
@@ -395,9 +993,15 @@ mod tests {
             created_at: 1000000,
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
-        let result = validate_settlement_policy(&policies, &payment);
+        let result = validate_settlement_policy(&policies, payment.amount, payment.created_at);
         assert!(result.is_err());
     }
 
@@ -416,8 +1020,14 @@ mod tests {
             created_at: 1000000,
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
-        assert!(validate_settlement_policy(&policies, &payment).is_ok());
+        assert!(validate_settlement_policy(&policies, payment.amount, payment.created_at).is_ok());
     }
 }
@@ -0,0 +1,371 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use crate::processor::{emit_event, emit_payment_status_changed, verify_current_program};
+use crate::{
+    events::{CompressedPaymentEvent, EventDiscriminators, PaymentLifecycleStatus},
+    processor::{
+        clear_payment::{apply_settlement_discount, calculate_fees, validate_settlement_policy},
+        get_or_create_operator_settlement_ata, get_platform_fee_policy,
+        get_split_settlement_policy, validate_chargeback_window_closed, verify_ata_program,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::{
+        escrow_authority_seeds, get_ata, release_open_payments, split_transfer_hook_accounts,
+        validate_escrow_authority, validate_operator_authority, verify_owner,
+        verify_owner_mutability, verify_signer, verify_system_program,
+        verify_token_interface_program, TokenTransfer,
+    },
+    require_len,
+    state::{
+        discriminator::AccountSerialize, CompressedPayment, Merchant, MerchantOperatorConfig,
+        Operator, OperatorPermission, PaymentTree, Status,
+    },
+};
+
+#[inline(always)]
+pub fn process_clear_payment_compressed(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [fee_payer_info, payment_tree_info, operator_authority_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, escrow_authority_info, merchant_escrow_ata_info, merchant_settlement_ata_info, operator_settlement_ata_info, token_program_info, associated_token_program_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // Validate fee_payer is writable signer
+    verify_signer(fee_payer_info, true)?;
+
+    // Validate operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate payment_tree is writable and owned by this program
+    verify_owner_mutability(payment_tree_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate operator is owned by the program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_operator_config is owned by this program and writable for its
+    // open-payment count
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Verify system program
+    verify_system_program(system_program_info)?;
+
+    // Validate associated token program
+    verify_ata_program(associated_token_program_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator and merchant
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to clear payments
+    validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::Clear,
+    )?;
+
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, policies, allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+
+    // Validate merchant_operator_config PDA
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate operator and merchant match the config
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    // Validate mint is in the allowed_mints
+    if !allowed_mints.contains(mint_info.key()) {
+        return Err(CommerceProgramError::InvalidMint.into());
+    }
+
+    // These payouts go straight to the settlement wallet, which would bypass a split
+    if get_split_settlement_policy(&policies).is_some() {
+        return Err(CommerceProgramError::SplitSettlementUnsupported.into());
+    }
+
+    // Only ClearPayment pays the platform its fee
+    if get_platform_fee_policy(&policies).is_some() {
+        return Err(CommerceProgramError::PlatformFeeUnsupported.into());
+    }
+
+    // Load and validate payment tree
+    let mut payment_tree_data = payment_tree_info.try_borrow_mut_data()?;
+    let mut payment_tree = PaymentTree::try_from_bytes(&payment_tree_data)?;
+    payment_tree.validate_pda(payment_tree_info.key())?;
+    payment_tree.validate_merchant_operator_config(merchant_operator_config_info.key())?;
+
+    // Prove the Paid leaf is in the tree and replace it with the Cleared leaf
+    let mut payment = CompressedPayment {
+        buyer: *buyer_info.key(),
+        mint: *mint_info.key(),
+        order_id: args.order_id,
+        amount: args.amount,
+        created_at: args.created_at,
+        status: Status::Paid,
+    };
+    let paid_leaf = payment.leaf_hash();
+
+    payment.status = Status::Cleared;
+    let cleared_leaf = payment.leaf_hash();
+
+    payment_tree.replace_leaf(args.leaf_index, paid_leaf, cleared_leaf, &args.proof)?;
+
+    // Validate settlement policy conditions
+    validate_settlement_policy(&policies, payment.amount, payment.created_at)?;
+
+    // Validate the chargeback window, if any, has passed
+    let now = Clock::get()?.unix_timestamp;
+    validate_chargeback_window_closed(&policies, payment.created_at, now)?;
+
+    // Validate the escrow authority PDA and the escrow ATA it owns
+    let escrow_authority_bump = validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    get_ata(
+        merchant_escrow_ata_info,
+        escrow_authority_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate merchant settlement ATA (owned by merchant settlement wallet)
+    get_ata(
+        merchant_settlement_ata_info,
+        &merchant.settlement_wallet,
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Calculate operator fee and merchant amount
+    let fees = calculate_fees(
+        payment.amount,
+        merchant_operator_config.operator_fee,
+        &merchant_operator_config.fee_type,
+        &policies,
+    )?;
+    let (operator_fee_amount, merchant_amount) =
+        apply_settlement_discount(fees, &policies, payment.created_at, now)?;
+
+    let token_transfer = TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?;
+
+    // Use the escrow authority PDA as authority for the transfers
+    let bump_seed = [escrow_authority_bump];
+    let signer_seeds = escrow_authority_seeds(
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        &bump_seed,
+    );
+
+    // Transfer operator fee if applicable
+    if operator_fee_amount > 0 {
+        // Validate operator settlement ATA (owned by operator owner), creating it if missing
+        get_or_create_operator_settlement_ata(
+            &operator,
+            operator_settlement_ata_info,
+            operator_authority_info,
+            mint_info,
+            fee_payer_info,
+            system_program_info,
+            token_program_info,
+        )?;
+
+        token_transfer.transfer(
+            merchant_escrow_ata_info,
+            operator_settlement_ata_info,
+            escrow_authority_info,
+            operator_fee_amount,
+            &[Signer::from(&signer_seeds)],
+        )?;
+    }
+
+    // Transfer remaining amount to merchant settlement wallet
+    token_transfer.transfer(
+        merchant_escrow_ata_info,
+        merchant_settlement_ata_info,
+        escrow_authority_info,
+        merchant_amount,
+        &[Signer::from(&signer_seeds)],
+    )?;
+
+    // Save updated payment tree data
+    payment_tree_data.copy_from_slice(&payment_tree.to_bytes());
+
+    // The payment's funds have left escrow
+    drop(merchant_operator_config_data);
+    release_open_payments(merchant_operator_config_info, mint_info.key(), 1)?;
+
+    // Emit compressed payment event so indexers can rebuild the leaf
+    let event = CompressedPaymentEvent {
+        discriminator: EventDiscriminators::CompressedPayment as u8,
+        payment_tree: *payment_tree_info.key(),
+        leaf_index: args.leaf_index,
+        buyer: payment.buyer,
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        mint: payment.mint,
+        amount: payment.amount,
+        operator_fee: operator_fee_amount,
+        order_id: payment.order_id,
+        created_at: payment.created_at,
+        status: payment.status as u8,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_bytes(),
+    )?;
+
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::Cleared,
+        operator_authority_info.key(),
+    )?;
+
+    Ok(())
+}
+
+struct ClearPaymentCompressedArgs {
+    leaf_index: u32,
+    order_id: u32,
+    amount: u64,
+    created_at: i64,
+    proof: Vec<[u8; 32]>,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<ClearPaymentCompressedArgs, ProgramError> {
+    require_len!(data, 28); // 4 + 4 + 8 + 8 + 4
+    let mut offset = 0;
+
+    let leaf_index = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    offset += 4;
+
+    let order_id = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    offset += 4;
+
+    let amount = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    offset += 8;
+
+    let created_at = i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    offset += 8;
+
+    let proof_len = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+    offset += 4;
+
+    let proof_bytes = proof_len
+        .checked_mul(32)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    require_len!(data, offset + proof_bytes);
+
+    let proof = data[offset..offset + proof_bytes]
+        .chunks_exact(32)
+        .map(|node| node.try_into().unwrap())
+        .collect();
+
+    Ok(ClearPaymentCompressedArgs {
+        leaf_index,
+        order_id,
+        amount,
+        created_at,
+        proof,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = vec![];
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.extend_from_slice(&12345u32.to_le_bytes());
+        data.extend_from_slice(&1000000u64.to_le_bytes());
+        data.extend_from_slice(&1640995200i64.to_le_bytes());
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&[2u8; 32]);
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.leaf_index, 7);
+        assert_eq!(args.order_id, 12345);
+        assert_eq!(args.amount, 1000000);
+        assert_eq!(args.created_at, 1640995200);
+        assert_eq!(args.proof, vec![[1u8; 32], [2u8; 32]]);
+    }
+
+    #[test]
+    fn test_process_instruction_data_truncated_proof() {
+        let mut data = vec![];
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&0i64.to_le_bytes());
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&[1u8; 32]);
+
+        let result = process_instruction_data(&data);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_process_instruction_data_invalid_length() {
+        let data = vec![1u8; 27]; // Too short
+        let result = process_instruction_data(&data);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,448 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    events::{EventDiscriminators, PaymentClearedEvent, PaymentLifecycleStatus},
+    processor::{
+        clear_payment::{calculate_payment_fees, validate_settlement_policy},
+        emit_event, emit_order_status_changed, emit_payment_status_changed, emit_transfer_memo,
+        escrow_authority_seeds, get_ata, get_callback_policy, get_conversion_policy,
+        get_memo_policy, get_or_create_operator_settlement_ata, get_platform_fee_policy,
+        get_split_settlement_policy, invoke_payment_callback, invoke_swap, read_conversion_rate,
+        release_open_payments, split_callback_accounts, split_memo_program, split_order,
+        split_swap_accounts, split_transfer_hook_accounts, token_account_balance,
+        update_merchant_stats, update_order, validate_chargeback_window_closed,
+        validate_clear_authority, validate_escrow_authority, validate_payment_order,
+        verify_ata_program, verify_current_program, verify_owner, verify_owner_mutability,
+        verify_signer, verify_system_program, verify_token_interface_program, TokenTransfer,
+    },
+    require_len,
+    state::{Merchant, MerchantOperatorConfig, Operator, Payment, Status},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_clear_payment_with_conversion(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [fee_payer_info, payment_info, operator_authority_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, escrow_authority_info, merchant_escrow_ata_info, settlement_mint_info, conversion_rate_info, merchant_settlement_ata_info, operator_settlement_ata_info, token_program_info, associated_token_program_info, system_program_info, swap_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let args = process_instruction_data(instruction_data)?;
+
+    // The swap accounts trail everything else, preceded by the optional memo program
+    let (remaining_accounts, swap_accounts) =
+        split_swap_accounts(remaining_accounts, args.num_swap_accounts)?;
+    let (memo_program_info, remaining_accounts) = split_memo_program(remaining_accounts);
+
+    // The payment mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // The callback program and the accounts forwarded to it, if passed, trail the other
+    // remaining accounts before the memo program
+    let (callback, remaining_accounts) = split_callback_accounts(remaining_accounts);
+
+    // The order, if passed, trails the other remaining accounts before the callback program
+    let (order_info, remaining_accounts) = split_order(remaining_accounts);
+
+    // Validate fee_payer is writable signer
+    verify_signer(fee_payer_info, true)?;
+
+    // Validate operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate payment is writable and owned by this program
+    verify_owner_mutability(payment_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate operator is owned by the program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_operator_config is owned by this program and writable for its
+    // open-payment count
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate both mints are owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+    verify_owner(settlement_mint_info, token_program_info.key())?;
+
+    // Verify system program
+    verify_system_program(system_program_info)?;
+
+    // Validate associated token program
+    verify_ata_program(associated_token_program_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator and merchant
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, policies, allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+
+    // Validate merchant_operator_config PDA
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate operator and merchant match the config
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    // Validate mint is in the allowed_mints
+    if !allowed_mints.contains(mint_info.key()) {
+        return Err(CommerceProgramError::InvalidMint.into());
+    }
+
+    // Validate the config opted into conversion, into a different mint than the payment's
+    let conversion =
+        get_conversion_policy(&policies).ok_or(CommerceProgramError::ConversionPolicyRequired)?;
+    if settlement_mint_info.key() != &conversion.settlement_mint
+        || settlement_mint_info.key() == mint_info.key()
+    {
+        return Err(CommerceProgramError::InvalidMint.into());
+    }
+
+    // The converted amount goes straight to the settlement wallet, which would bypass a split
+    if get_split_settlement_policy(&policies).is_some() {
+        return Err(CommerceProgramError::SplitSettlementUnsupported.into());
+    }
+
+    // Only ClearPayment pays the platform its fee
+    if get_platform_fee_policy(&policies).is_some() {
+        return Err(CommerceProgramError::PlatformFeeUnsupported.into());
+    }
+
+    // Validate operator_authority is the owner or a delegate allowed to clear payments, or the
+    // merchant authority once the operator is inactive under an operator failover policy
+    let now = Clock::get()?.unix_timestamp;
+    let remaining_accounts = validate_clear_authority(
+        &operator,
+        operator_info,
+        &merchant,
+        operator_authority_info,
+        remaining_accounts,
+        &policies,
+        now,
+    )?;
+
+    // Load and validate payment
+    let mut payment_data = payment_info.try_borrow_mut_data()?;
+    let mut payment = Payment::try_from_bytes(&payment_data)?;
+
+    // Validate payment status is Paid
+    payment.validate_status(Status::Paid)?;
+
+    // Validate Payment PDA
+    payment.validate_pda(
+        payment_info.key(),
+        merchant_operator_config_info.key(),
+        buyer_info.key(),
+        mint_info.key(),
+    )?;
+
+    // An order payment records every status change on its Order
+    validate_payment_order(order_info, payment.has_order)?;
+
+    // Validate settlement policy conditions
+    validate_settlement_policy(&policies, payment.amount, payment.created_at)?;
+
+    // Validate the chargeback window, if any, has passed
+    validate_chargeback_window_closed(&policies, payment.created_at, now)?;
+
+    // Validate the escrow authority PDA and the escrow ATA it owns
+    let escrow_authority_bump = validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    get_ata(
+        merchant_escrow_ata_info,
+        escrow_authority_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate merchant settlement ATA in the settlement mint (owned by settlement wallet)
+    get_ata(
+        merchant_settlement_ata_info,
+        &merchant.settlement_wallet,
+        settlement_mint_info,
+        token_program_info,
+    )?;
+
+    // Calculate operator fee and merchant amount
+    let (operator_fee_amount, merchant_amount) =
+        calculate_payment_fees(&payment, &merchant_operator_config, &policies, now)?;
+
+    // The quote may not undercut the merchant amount at the rate posted for the pair
+    let conversion_rate = read_conversion_rate(
+        conversion_rate_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        settlement_mint_info.key(),
+    )?;
+    if args.quoted_amount_out < conversion_rate.convert(merchant_amount)? {
+        return Err(CommerceProgramError::QuoteBelowConversionRate.into());
+    }
+
+    // Use the escrow authority PDA as authority for the transfers and the swap
+    let bump_seed = [escrow_authority_bump];
+    let signer_seeds = escrow_authority_seeds(
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        &bump_seed,
+    );
+
+    // Attach a memo to the transfers if the memo policy asks for one
+    emit_transfer_memo(
+        get_memo_policy(&policies).is_some_and(|memo| memo.memo_on_clear),
+        memo_program_info,
+        payment.order_id,
+        payment_info.key(),
+    )?;
+
+    // Transfer operator fee in the payment mint if applicable
+    if operator_fee_amount > 0 {
+        // Validate operator settlement ATA (owned by operator owner), creating it if missing
+        get_or_create_operator_settlement_ata(
+            &operator,
+            operator_settlement_ata_info,
+            operator_authority_info,
+            mint_info,
+            fee_payer_info,
+            system_program_info,
+            token_program_info,
+        )?;
+
+        TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+            merchant_escrow_ata_info,
+            operator_settlement_ata_info,
+            escrow_authority_info,
+            operator_fee_amount,
+            &[Signer::from(&signer_seeds)],
+        )?;
+    }
+
+    // Swap the merchant amount into the settlement mint, straight into the settlement ATA
+    let escrow_before = token_account_balance(merchant_escrow_ata_info, token_program_info)?;
+    let settlement_before =
+        token_account_balance(merchant_settlement_ata_info, token_program_info)?;
+
+    invoke_swap(
+        conversion.swap_adapter,
+        swap_program_info,
+        swap_accounts,
+        &args.swap_data,
+        escrow_authority_info,
+        Signer::from(&signer_seeds),
+    )?;
+
+    // The swap must spend exactly the merchant amount from escrow
+    let escrow_spent = escrow_before.checked_sub(token_account_balance(
+        merchant_escrow_ata_info,
+        token_program_info,
+    )?);
+    if escrow_spent != Some(merchant_amount) {
+        return Err(CommerceProgramError::SwapInputMismatch.into());
+    }
+
+    // and deliver at least the quote less the policy's max slippage
+    let amount_out = token_account_balance(merchant_settlement_ata_info, token_program_info)?
+        .saturating_sub(settlement_before);
+    if amount_out < conversion.min_amount_out(args.quoted_amount_out) {
+        return Err(CommerceProgramError::SlippageExceeded.into());
+    }
+
+    // Update payment status to cleared
+    payment.status = Status::Cleared;
+
+    // Save updated payment data
+    payment.store(&mut payment_data)?;
+
+    // The payment's funds have left escrow
+    drop(merchant_operator_config_data);
+    release_open_payments(merchant_operator_config_info, mint_info.key(), 1)?;
+
+    // Update optional merchant stats, tracked in the payment mint
+    update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+        stats.record_clear(payment.amount, operator_fee_amount)
+    })?;
+
+    // Let the merchant's program react, now that the payment is stored with its new status
+    drop(payment_data);
+    invoke_payment_callback(
+        get_callback_policy(&policies)
+            .filter(|callback| callback.on_clear)
+            .map(|callback| &callback.program),
+        callback,
+        payment_info,
+        &payment.status,
+        payment.amount,
+    )?;
+
+    // Record the clear on the payment's order, if any
+    let order_status = match order_info {
+        Some(order_info) => update_order(
+            order_info,
+            merchant_operator_config_info,
+            buyer_info,
+            payment.order_id,
+            |order| {
+                order.record_resolution(&Status::Cleared);
+                Ok(())
+            },
+        )?,
+        None => None,
+    };
+
+    // Emit payment cleared event
+    let event = PaymentClearedEvent {
+        discriminator: EventDiscriminators::PaymentCleared as u8,
+        buyer: *buyer_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        amount: payment.amount,
+        operator_fee: operator_fee_amount,
+        order_id: payment.order_id,
+        idempotency_key: payment.idempotency_key,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )?;
+
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::Cleared,
+        operator_authority_info.key(),
+    )?;
+
+    emit_order_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        buyer_info.key(),
+        merchant_info.key(),
+        operator_info.key(),
+        payment.order_id,
+        order_status,
+    )?;
+
+    Ok(())
+}
+
+struct ClearPaymentWithConversionArgs {
+    quoted_amount_out: u64,
+    num_swap_accounts: u8,
+    swap_data: Vec<u8>,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<ClearPaymentWithConversionArgs, ProgramError> {
+    require_len!(data, 13); // 8 + 1 + 4
+    let mut offset = 0;
+
+    let quoted_amount_out = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    offset += 8;
+
+    // A zero quote would disable the slippage bound
+    if quoted_amount_out == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let num_swap_accounts = data[offset];
+    offset += 1;
+
+    let swap_data_len = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+    offset += 4;
+
+    require_len!(data, offset + swap_data_len);
+    let swap_data = data[offset..offset + swap_data_len].to_vec();
+
+    Ok(ClearPaymentWithConversionArgs {
+        quoted_amount_out,
+        num_swap_accounts,
+        swap_data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn instruction_data(
+        quoted_amount_out: u64,
+        num_swap_accounts: u8,
+        swap_data: &[u8],
+    ) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&quoted_amount_out.to_le_bytes());
+        data.push(num_swap_accounts);
+        data.extend_from_slice(&(swap_data.len() as u32).to_le_bytes());
+        data.extend_from_slice(swap_data);
+        data
+    }
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let args = process_instruction_data(&instruction_data(995_000, 12, &[1, 2, 3])).unwrap();
+        assert_eq!(args.quoted_amount_out, 995_000);
+        assert_eq!(args.num_swap_accounts, 12);
+        assert_eq!(args.swap_data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_process_instruction_data_zero_quote() {
+        let result = process_instruction_data(&instruction_data(0, 12, &[1, 2, 3]));
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_process_instruction_data_truncated_swap_data() {
+        let mut data = instruction_data(995_000, 12, &[1, 2, 3]);
+        data.pop();
+
+        let result = process_instruction_data(&data);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+
+        assert!(process_instruction_data(&data[..12]).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Signer, program_error::ProgramError, pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::{
+        close_token_account, escrow_authority_seeds, get_ata, split_transfer_hook_accounts,
+        token_account_balance, validate_escrow_authority, verify_ata_address, verify_owner,
+        verify_owner_mutability, verify_signer, verify_token_interface_program, TokenTransfer,
+    },
+    state::{Merchant, MerchantOperatorConfig, Operator},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Closes a config's escrow ATA for a mint once no payment of the config and mint is open,
+/// returning its rent to the merchant authority.
+///
+/// The config counts the payments holding funds in the escrow, so the close is gated on that
+/// count rather than on the balance. Whatever the escrow still holds, e.g. tokens sent to it
+/// directly, is swept to the merchant's settlement ATA first. MakePayment recreates the
+/// escrow if the mint is used again. The merchant and operator owners both sign, as for
+/// MigrateEscrow.
+#[inline(always)]
+pub fn process_close_escrow_ata(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [merchant_authority_info, operator_authority_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, escrow_authority_info, merchant_escrow_ata_info, merchant_settlement_ata_info, token_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, are the remaining accounts
+    let (transfer_hook_infos, _) = split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // Validate: merchant authority should have signed and receives the rent
+    verify_signer(merchant_authority_info, true)?;
+
+    // Validate: operator authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate merchant, operator and merchant_operator_config are owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_owner(merchant_authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_owner(operator_authority_info.key())?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Load and validate merchant_operator_config belongs to both
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+
+    // Validate the escrow authority PDA and the escrow ATA it owns
+    let escrow_authority_bump = validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    get_ata(
+        merchant_escrow_ata_info,
+        escrow_authority_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate merchant settlement ATA (owned by the settlement wallet). It only has to exist
+    // if there is a balance to sweep.
+    verify_ata_address(
+        merchant_settlement_ata_info,
+        &merchant.settlement_wallet,
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Open payments still hold funds in escrow
+    if merchant_operator_config.open_payments(&merchant_operator_config_data, mint_info.key())? != 0
+    {
+        return Err(CommerceProgramError::EscrowNotEmpty.into());
+    }
+
+    let bump_seed = [escrow_authority_bump];
+    let signer_seeds = escrow_authority_seeds(
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        &bump_seed,
+    );
+
+    // Sweep what no payment accounts for to the merchant, so the escrow can close
+    let balance = token_account_balance(merchant_escrow_ata_info, token_program_info)?;
+    if balance > 0 {
+        if merchant_settlement_ata_info.data_is_empty() {
+            return Err(CommerceProgramError::AtaNotInitialized.into());
+        }
+        TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+            merchant_escrow_ata_info,
+            merchant_settlement_ata_info,
+            escrow_authority_info,
+            balance,
+            &[Signer::from(&signer_seeds)],
+        )?;
+    }
+
+    close_token_account(
+        token_program_info,
+        merchant_escrow_ata_info,
+        merchant_authority_info,
+        escrow_authority_info,
+        &[Signer::from(&signer_seeds)],
+    )
+}
@@ -0,0 +1,107 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    events::{EventDiscriminators, MerchantOperatorConfigClosedEvent},
+    processor::{emit_event, verify_current_program, verify_owner_mutability, verify_signer},
+    state::{Merchant, MerchantOperatorConfig, Operator},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Closes a decommissioned config, returning its rent to the merchant authority.
+///
+/// The config's open-payment count must be zero in every accepted currency, so no payment is
+/// left holding funds under it, and ClosePayment must have closed every Payment account made
+/// under it, as it cannot once the config is gone. Escrow ATAs are not checked: CloseEscrowAta should close them
+/// first to reclaim their rent and sweep any balance, as it needs the config to sign for them.
+/// The merchant and operator owners both sign, as for CloseEscrowAta.
+#[inline(always)]
+pub fn process_close_merchant_operator_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [merchant_authority_info, operator_authority_info, merchant_info, operator_info, merchant_operator_config_info, event_authority_info, commerce_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: merchant authority should have signed and receives the rent
+    verify_signer(merchant_authority_info, true)?;
+
+    // Validate: operator authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate merchant and operator are owned by this program, and the config is writable
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate merchant
+    let merchant = Merchant::try_from_bytes(&merchant_info.try_borrow_data()?)?;
+    merchant.validate_owner(merchant_authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate operator
+    let operator = Operator::try_from_bytes(&operator_info.try_borrow_data()?)?;
+    operator.validate_owner(operator_authority_info.key())?;
+    operator.validate_pda(operator_info.key())?;
+
+    {
+        // Load and validate merchant_operator_config belongs to both
+        let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+        let merchant_operator_config =
+            MerchantOperatorConfig::header_from_bytes(&merchant_operator_config_data)?;
+        merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+        merchant_operator_config.validate_merchant(merchant_info.key())?;
+        merchant_operator_config.validate_operator(operator_info.key())?;
+
+        // Only a config that stopped taking payments can be closed
+        if !merchant_operator_config.is_decommissioned() {
+            return Err(CommerceProgramError::ConfigNotDecommissioned.into());
+        }
+
+        // No payment in any accepted currency may still hold funds in escrow
+        if merchant_operator_config
+            .get_open_payments(&merchant_operator_config_data)?
+            .iter()
+            .any(|&open_payments| open_payments != 0)
+        {
+            return Err(CommerceProgramError::ConfigHasOpenPayments.into());
+        }
+
+        // Nor may a Payment account be left that ClosePayment needs the config to close
+        if merchant_operator_config.payment_accounts != 0 {
+            return Err(CommerceProgramError::ConfigHasPaymentAccounts.into());
+        }
+    }
+
+    let rent_reclaimed = merchant_operator_config_info.lamports();
+    let merchant_authority_lamports = merchant_authority_info.lamports();
+    *merchant_authority_info.try_borrow_mut_lamports()? = merchant_authority_lamports
+        .checked_add(rent_reclaimed)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    *merchant_operator_config_info.try_borrow_mut_lamports()? = 0;
+    merchant_operator_config_info.close()?;
+
+    let event = MerchantOperatorConfigClosedEvent {
+        discriminator: EventDiscriminators::MerchantOperatorConfigClosed as u8,
+        merchant_operator_config: *merchant_operator_config_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        rent_reclaimed,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
@@ -6,22 +6,34 @@ use pinocchio::{
 };
 
 use crate::{
-    processor::{verify_owner_mutability, verify_signer, verify_system_program},
-    state::{Merchant, MerchantOperatorConfig, Operator, Payment},
+    events::{EventDiscriminators, PaymentClosedEvent, PaymentLifecycleStatus},
+    processor::{
+        emit_event, emit_payment_status_changed, rebate_payment_rent, rent_refund_destination,
+        split_rent_payer, update_merchant_stats, validate_operator_authority,
+        verify_current_program, verify_owner_mutability, verify_signer, verify_system_program,
+    },
+    state::{
+        Merchant, MerchantOperatorConfig, MerchantOperatorConfigView, Operator, OperatorPermission,
+        Payment, ZeroCopy,
+    },
 };
 
 #[inline(always)]
 pub fn process_close_payment(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     _instruction_data: &[u8],
 ) -> ProgramResult {
-    let [fee_payer_info, payment_info, operator_authority_info, operator_info, merchant_info, buyer_info, merchant_operator_config_info, mint_info, system_program_info] =
+    let [fee_payer_info, payment_info, operator_authority_info, operator_info, merchant_info, buyer_info, merchant_operator_config_info, mint_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    // The payment's rent payer, if it was not the fee payer, leads the remaining accounts
+    // ahead of the operator delegate
+    let (rent_payer_info, remaining_accounts) = split_rent_payer(remaining_accounts);
+
     // Validate fee_payer is writable signer
     verify_signer(fee_payer_info, true)?;
 
@@ -34,11 +46,14 @@ pub fn process_close_payment(
     // Validate system program
     verify_system_program(system_program_info)?;
 
+    // Validate commerce program
+    verify_current_program(commerce_program_info)?;
+
     // Validate merchant is owned by this program
     verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
 
-    // Validate merchant_operator_config is owned by this program
-    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+    // Validate merchant_operator_config is writable and owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
 
     // validate operator is owned by the program
     verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
@@ -50,7 +65,15 @@ pub fn process_close_payment(
     };
 
     operator.validate_pda(operator_info.key())?;
-    operator.validate_owner(operator_authority_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to close payments
+    let remaining_accounts = validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::Close,
+    )?;
 
     // Load and validate merchant
     let merchant = {
@@ -60,7 +83,7 @@ pub fn process_close_payment(
     merchant.validate_pda(merchant_info.key())?;
 
     // Load and validate merchant_operator_config
-    let (merchant_operator_config, _policies, _allowed_mints) = {
+    let (merchant_operator_config, policies, _allowed_mints) = {
         let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
         MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?
     };
@@ -89,13 +112,85 @@ pub fn process_close_payment(
         mint_info.key(),
     )?;
 
+    // Refund the rent to whoever paid it
+    let rent_destination_info =
+        rent_refund_destination(&payment.rent_payer, rent_payer_info, fee_payer_info)?;
+
+    let order_id = payment.order_id;
+    let previous_status = PaymentLifecycleStatus::from(&payment.status);
     drop(payment_data);
 
-    let payer_lamports = fee_payer_info.lamports();
-    *fee_payer_info.try_borrow_mut_lamports().unwrap() =
-        payer_lamports.checked_add(payment_info.lamports()).unwrap();
-    *payment_info.try_borrow_mut_lamports().unwrap() = 0;
-    payment_info.close()?;
+    // Update optional merchant stats
+    update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+        stats.record_close()
+    })?;
+
+    // Pay the buyer their share of the rent under a rent rebate policy
+    rebate_payment_rent(&policies, payment_info, buyer_info)?;
+
+    close_payment_account(payment_info, rent_destination_info)?;
+    release_payment_accounts(merchant_operator_config_info, 1)?;
+
+    // Emit payment closed event
+    let event = PaymentClosedEvent {
+        discriminator: EventDiscriminators::PaymentClosed as u8,
+        buyer: *buyer_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        order_id,
+        rent_refunded_to: *rent_destination_info.key(),
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_bytes(),
+    )?;
 
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        previous_status,
+        PaymentLifecycleStatus::Closed,
+        operator_authority_info.key(),
+    )?;
+
+    Ok(())
+}
+
+/// Closes `payment_info`, crediting its rent to `destination_info`.
+pub(crate) fn close_payment_account(
+    payment_info: &AccountInfo,
+    destination_info: &AccountInfo,
+) -> ProgramResult {
+    let destination_lamports = destination_info.lamports();
+    *destination_info.try_borrow_mut_lamports()? = destination_lamports
+        .checked_add(payment_info.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    *payment_info.try_borrow_mut_lamports()? = 0;
+    payment_info.close()
+}
+
+/// Stops counting `count` closed Payment accounts on a config. Stops at zero, since payments
+/// made before UpgradeAccount added the count were never counted.
+///
+/// The config must be writable and not borrowed by the caller.
+pub(crate) fn release_payment_accounts(
+    merchant_operator_config_info: &AccountInfo,
+    count: u32,
+) -> ProgramResult {
+    let mut merchant_operator_config_data = merchant_operator_config_info.try_borrow_mut_data()?;
+    let merchant_operator_config =
+        MerchantOperatorConfigView::load_mut(&mut merchant_operator_config_data)?;
+    let payment_accounts = merchant_operator_config
+        .payment_accounts()
+        .saturating_sub(count);
+    merchant_operator_config.set_payment_accounts(payment_accounts);
     Ok(())
 }
@@ -0,0 +1,63 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::{verify_owner_mutability, verify_signer, verify_writable},
+    state::{Merchant, PaymentNote},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_close_payment_note(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [authority_info, merchant_info, payment_note_info, rent_payer_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate payment_note is writable and owned by this program
+    verify_owner_mutability(payment_note_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate rent_payer is writable
+    verify_writable(rent_payer_info)?;
+
+    // Load and validate merchant
+    let merchant = {
+        let merchant_data = merchant_info.try_borrow_data()?;
+        Merchant::try_from_bytes(&merchant_data)?
+    };
+    merchant.validate_owner(authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate payment_note
+    let payment_note = {
+        let payment_note_data = payment_note_info.try_borrow_data()?;
+        PaymentNote::try_from_bytes(&payment_note_data)?
+    };
+    payment_note.validate_pda(payment_note_info.key())?;
+    payment_note.validate_merchant(merchant_info.key())?;
+
+    // Refund the rent to whoever paid it
+    if payment_note.rent_payer.ne(rent_payer_info.key()) {
+        return Err(CommerceProgramError::RentPayerMismatch.into());
+    }
+
+    let rent_payer_lamports = rent_payer_info.lamports();
+    *rent_payer_info.try_borrow_mut_lamports()? = rent_payer_lamports
+        .checked_add(payment_note_info.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    *payment_note_info.try_borrow_mut_lamports()? = 0;
+    payment_note_info.close()?;
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    events::{EventDiscriminators, MerchantSettlementWalletUpdatedEvent},
+    processor::{emit_event, verify_current_program, verify_owner_mutability, verify_signer},
+    state::Merchant,
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_commit_settlement_wallet(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [payer_info, authority_info, merchant_info, event_authority_info, commerce_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    let mut merchant_data = merchant_info.try_borrow_mut_data()?;
+    let mut merchant = Merchant::try_from_bytes(&merchant_data)?;
+
+    // Validate merchant owner
+    merchant.validate_owner(authority_info.key())?;
+
+    // Validate Merchant PDA
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Validate a wallet is pending and its timelock has elapsed
+    merchant.validate_pending_settlement_wallet(Clock::get()?.unix_timestamp)?;
+
+    // Switch settlement wallet and clear the proposal
+    let old_settlement_wallet = merchant.settlement_wallet;
+    merchant.settlement_wallet = merchant.pending_settlement_wallet;
+    merchant.pending_settlement_wallet = Pubkey::default();
+    merchant.pending_settlement_wallet_unlocks_at = 0;
+    merchant.store(&mut merchant_data)?;
+
+    // Emit settlement wallet updated event
+    let event = MerchantSettlementWalletUpdatedEvent {
+        discriminator: EventDiscriminators::MerchantSettlementWalletUpdated as u8,
+        merchant: *merchant_info.key(),
+        old_settlement_wallet,
+        new_settlement_wallet: merchant.settlement_wallet,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
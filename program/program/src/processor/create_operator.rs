@@ -5,27 +5,25 @@ use pinocchio::{
     instruction::Seed,
     program_error::ProgramError,
     pubkey::Pubkey,
-    sysvars::{rent::Rent, Sysvar},
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
     ProgramResult,
 };
 
 use crate::{
     constants::OPERATOR_SEED,
     processor::{
-        create_pda_account, validate_pda, verify_signer, verify_system_account,
+        create_pda_account, derive_and_validate_pda, verify_signer, verify_system_account,
         verify_system_program,
     },
-    require_len,
-    state::{discriminator::AccountSerialize, Operator},
+    state::Operator,
 };
 
 #[inline(always)]
 pub fn process_create_operator(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    instruction_data: &[u8],
+    _instruction_data: &[u8],
 ) -> ProgramResult {
-    let args = process_instruction_data(instruction_data)?;
     let [payer_info, operator_info, authority_info, system_program_info] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -38,17 +36,16 @@ pub fn process_create_operator(
     verify_system_account(operator_info, true)?;
     // Validate system program
     verify_system_program(system_program_info)?;
-    // Validate Operator PDA
-    validate_pda(
+    // Validate Operator PDA, deriving its canonical bump
+    let bump = derive_and_validate_pda(
         &[OPERATOR_SEED, authority_info.key()],
         &Pubkey::from(*program_id),
-        args.bump,
         operator_info,
     )?;
 
     let space = Operator::LEN;
     let rent = Rent::get()?;
-    let bump_seed = [args.bump];
+    let bump_seed = [bump];
     let signer_seeds = [
         Seed::from(OPERATOR_SEED),
         Seed::from(authority_info.key()),
@@ -65,50 +62,14 @@ pub fn process_create_operator(
     )?;
     let operator = Operator {
         owner: *authority_info.key(),
-        bump: args.bump,
+        bump,
+        pending_authority: Pubkey::default(),
+        last_active_at: Clock::get()?.unix_timestamp,
+        has_freeze_list: false,
+        original_owner: *authority_info.key(),
     };
 
     let mut operator_data = operator_info.try_borrow_mut_data()?;
-    operator_data.copy_from_slice(&operator.to_bytes());
+    operator.store(&mut operator_data)?;
     Ok(())
 }
-
-struct CreateOperatorArgs {
-    bump: u8,
-}
-
-fn process_instruction_data(data: &[u8]) -> Result<CreateOperatorArgs, ProgramError> {
-    require_len!(data, 1);
-    let bump = data[0];
-    Ok(CreateOperatorArgs { bump })
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_instruction_data_valid() {
-        let data = [128u8];
-        let args = process_instruction_data(&data).unwrap();
-        assert_eq!(args.bump, 128);
-    }
-
-    #[test]
-    fn test_process_instruction_data_edge_cases() {
-        let data = [0u8];
-        let args = process_instruction_data(&data).unwrap();
-        assert_eq!(args.bump, 0);
-
-        let data = [u8::MAX];
-        let args = process_instruction_data(&data).unwrap();
-        assert_eq!(args.bump, u8::MAX);
-    }
-
-    #[test]
-    fn test_process_instruction_data_invalid_length() {
-        let data = [];
-        let result = process_instruction_data(&data);
-        assert!(result.is_err());
-    }
-}
@@ -0,0 +1,190 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::ORDER_SEED,
+    events::{EventDiscriminators, OrderCreatedEvent},
+    processor::{
+        create_pda_account, emit_event, validate_operator_authority, validate_pda,
+        verify_current_program, verify_owner_mutability, verify_signer, verify_system_account,
+        verify_system_program,
+    },
+    require_len,
+    state::{
+        discriminator::AccountSerialize, MerchantOperatorConfig, Operator, OperatorPermission,
+        Order,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_create_order(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, operator_authority_info, buyer_info, operator_info, merchant_info, merchant_operator_config_info, order_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate order is writable
+    verify_system_account(order_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate operator, merchant and merchant_operator_config are owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to make payments
+    validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::MakePayment,
+    )?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, _, _) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+    merchant_operator_config.validate_order_id(args.order_id)?;
+
+    let order = Order::new(
+        *merchant_operator_config_info.key(),
+        *buyer_info.key(),
+        args.order_id,
+        args.bump,
+        args.expected_payments,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // Validate Order PDA
+    let order_id_seed = args.order_id.to_le_bytes();
+    validate_pda(
+        &[
+            ORDER_SEED,
+            merchant_operator_config_info.key(),
+            buyer_info.key(),
+            &order_id_seed,
+        ],
+        &Pubkey::from(*program_id),
+        args.bump,
+        order_info,
+    )?;
+
+    let space = Order::LEN;
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(ORDER_SEED),
+        Seed::from(merchant_operator_config_info.key()),
+        Seed::from(buyer_info.key()),
+        Seed::from(&order_id_seed),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        space,
+        program_id,
+        order_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let mut order_data = order_info.try_borrow_mut_data()?;
+    order_data.copy_from_slice(&order.to_bytes());
+
+    // Emit order created event
+    let event = OrderCreatedEvent {
+        discriminator: EventDiscriminators::OrderCreated as u8,
+        buyer: *buyer_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        order_id: args.order_id,
+        expected_payments: args.expected_payments,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_bytes(),
+    )?;
+
+    Ok(())
+}
+
+struct CreateOrderArgs {
+    order_id: u32,
+    expected_payments: u8,
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<CreateOrderArgs, ProgramError> {
+    require_len!(data, 6);
+    let order_id = u32::from_le_bytes(data[0..4].try_into().unwrap());
+    let expected_payments = data[4];
+    let bump = data[5];
+    Ok(CreateOrderArgs {
+        order_id,
+        expected_payments,
+        bump,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = [0u8; 6];
+        data[0..4].copy_from_slice(&42u32.to_le_bytes());
+        data[4] = 2;
+        data[5] = 253;
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.order_id, 42);
+        assert_eq!(args.expected_payments, 2);
+        assert_eq!(args.bump, 253);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[0u8; 5]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -0,0 +1,190 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::QUOTE_SEED,
+    error::CommerceProgramError,
+    processor::{
+        create_pda_account, validate_operator_authority, validate_pda, verify_owner_mutability,
+        verify_signer, verify_system_account, verify_system_program,
+    },
+    require_len,
+    state::{
+        discriminator::AccountSerialize, MerchantOperatorConfig, Operator, OperatorPermission,
+        Quote,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_create_quote(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, operator_authority_info, operator_info, merchant_operator_config_info, mint_info, quote_info, system_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate quote is writable
+    verify_system_account(quote_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate operator and merchant_operator_config are owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to make payments
+    validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::MakePayment,
+    )?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_order_id(args.order_id)?;
+
+    // Validate mint is an accepted currency
+    if !merchant_operator_config
+        .accepts_currency(&merchant_operator_config_data, mint_info.key())?
+    {
+        return Err(CommerceProgramError::InvalidMint.into());
+    }
+
+    if args.amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // A quote must be payable when created
+    let now = Clock::get()?.unix_timestamp;
+    if args.expires_at <= now {
+        return Err(CommerceProgramError::QuoteExpired.into());
+    }
+
+    // Validate Quote PDA
+    let order_id_seed = args.order_id.to_le_bytes();
+    validate_pda(
+        &[
+            QUOTE_SEED,
+            merchant_operator_config_info.key(),
+            mint_info.key(),
+            &order_id_seed,
+        ],
+        &Pubkey::from(*program_id),
+        args.bump,
+        quote_info,
+    )?;
+
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(QUOTE_SEED),
+        Seed::from(merchant_operator_config_info.key()),
+        Seed::from(mint_info.key()),
+        Seed::from(&order_id_seed),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        Quote::LEN,
+        program_id,
+        quote_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let quote = Quote {
+        merchant_operator_config: *merchant_operator_config_info.key(),
+        mint: *mint_info.key(),
+        order_id: args.order_id,
+        bump: args.bump,
+        amount: args.amount,
+        expires_at: args.expires_at,
+        created_at: now,
+        quoter: *operator_authority_info.key(),
+    };
+
+    let mut quote_data = quote_info.try_borrow_mut_data()?;
+    quote_data.copy_from_slice(&quote.to_bytes());
+
+    Ok(())
+}
+
+struct CreateQuoteArgs {
+    order_id: u32,
+    amount: u64,
+    expires_at: i64,
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<CreateQuoteArgs, ProgramError> {
+    require_len!(data, 21);
+    let order_id = u32::from_le_bytes(data[0..4].try_into().unwrap());
+    let amount = u64::from_le_bytes(data[4..12].try_into().unwrap());
+    let expires_at = i64::from_le_bytes(data[12..20].try_into().unwrap());
+    let bump = data[20];
+    Ok(CreateQuoteArgs {
+        order_id,
+        amount,
+        expires_at,
+        bump,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = [0u8; 21];
+        data[0..4].copy_from_slice(&42u32.to_le_bytes());
+        data[4..12].copy_from_slice(&1_000_000u64.to_le_bytes());
+        data[12..20].copy_from_slice(&1_700_000_600i64.to_le_bytes());
+        data[20] = 253;
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.order_id, 42);
+        assert_eq!(args.amount, 1_000_000);
+        assert_eq!(args.expires_at, 1_700_000_600);
+        assert_eq!(args.bump, 253);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[0u8; 20]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
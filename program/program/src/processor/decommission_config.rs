@@ -0,0 +1,84 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    events::{ConfigDecommissionedEvent, EventDiscriminators},
+    processor::{emit_event, verify_current_program, verify_owner_mutability, verify_signer},
+    state::{Merchant, MerchantOperatorConfig, Operator},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Stops a config from taking new payments, the first step of closing it.
+///
+/// Payments already made keep clearing and refunding as usual; once they have all settled the
+/// escrow ATAs can be closed with CloseEscrowAta and the config with
+/// CloseMerchantOperatorConfig. Either the merchant or the operator owner may sign, since both
+/// parties can walk away from the relationship. Decommissioning is final.
+#[inline(always)]
+pub fn process_decommission_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [authority_info, merchant_info, operator_info, merchant_operator_config_info, event_authority_info, commerce_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate merchant and operator are owned by this program, and the config is writable
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate merchant and operator
+    let merchant = Merchant::try_from_bytes(&merchant_info.try_borrow_data()?)?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    let operator = Operator::try_from_bytes(&operator_info.try_borrow_data()?)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate authority owns the merchant or the operator
+    if merchant.owner.ne(authority_info.key()) {
+        operator.validate_owner(authority_info.key())?;
+    }
+
+    // Load and validate merchant_operator_config belongs to both
+    let mut merchant_operator_config_data = merchant_operator_config_info.try_borrow_mut_data()?;
+    let mut merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_not_decommissioned()?;
+
+    merchant_operator_config.decommissioned_at = Clock::get()?.unix_timestamp;
+    merchant_operator_config.store(&mut merchant_operator_config_data)?;
+
+    let event = ConfigDecommissionedEvent {
+        discriminator: EventDiscriminators::ConfigDecommissioned as u8,
+        merchant_operator_config: *merchant_operator_config_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        decommissioned_by: *authority_info.key(),
+        decommissioned_at: merchant_operator_config.decommissioned_at,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
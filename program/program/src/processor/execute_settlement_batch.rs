@@ -0,0 +1,142 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    events::{EventDiscriminators, SettlementBatchExecutedEvent},
+    processor::{
+        emit_event, escrow_authority_seeds, get_ata, get_settlement_policy, release_open_payments,
+        split_transfer_hook_accounts, validate_escrow_authority, verify_current_program,
+        verify_owner, verify_owner_mutability, verify_token_interface_program, TokenTransfer,
+    },
+    state::{discriminator::AccountSerialize, Merchant, MerchantOperatorConfig, SettlementBatch},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_execute_settlement_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [settlement_batch_info, merchant_info, merchant_operator_config_info, mint_info, escrow_authority_info, merchant_escrow_ata_info, merchant_settlement_ata_info, token_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, are the remaining accounts
+    let (transfer_hook_infos, _) = split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // Validate settlement_batch is writable and owned by this program
+    verify_owner_mutability(settlement_batch_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate merchant and merchant_operator_config are owned by this program, the config
+    // writable for its open-payment count
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, policies, _) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    // Load and validate settlement batch
+    let mut settlement_batch_data = settlement_batch_info.try_borrow_mut_data()?;
+    let mut settlement_batch = SettlementBatch::try_from_bytes(&settlement_batch_data)?;
+    settlement_batch.validate_pda(settlement_batch_info.key())?;
+    settlement_batch
+        .validate_config_and_mint(merchant_operator_config_info.key(), mint_info.key())?;
+
+    // Validate the settlement policy, if any, allows paying out the pending amount
+    let now = Clock::get()?.unix_timestamp;
+    settlement_batch.validate_ready(get_settlement_policy(&policies), now)?;
+
+    // Validate the escrow authority PDA and the escrow ATA it owns
+    let escrow_authority_bump = validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    get_ata(
+        merchant_escrow_ata_info,
+        escrow_authority_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate merchant settlement ATA (owned by the settlement wallet)
+    get_ata(
+        merchant_settlement_ata_info,
+        &merchant.settlement_wallet,
+        mint_info,
+        token_program_info,
+    )?;
+
+    let (amount, payment_count) = settlement_batch.settle(now);
+    settlement_batch_data.copy_from_slice(&settlement_batch.to_bytes());
+
+    // The batched payments are open until their funds leave escrow here
+    drop(merchant_operator_config_data);
+    release_open_payments(
+        merchant_operator_config_info,
+        mint_info.key(),
+        payment_count,
+    )?;
+
+    // Use the escrow authority PDA as authority for the transfer
+    let bump_seed = [escrow_authority_bump];
+    let signer_seeds = escrow_authority_seeds(
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        &bump_seed,
+    );
+
+    TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+        merchant_escrow_ata_info,
+        merchant_settlement_ata_info,
+        escrow_authority_info,
+        amount,
+        &[Signer::from(&signer_seeds)],
+    )?;
+
+    // Emit settlement batch executed event
+    let event = SettlementBatchExecutedEvent {
+        discriminator: EventDiscriminators::SettlementBatchExecuted as u8,
+        merchant: *merchant_info.key(),
+        merchant_operator_config: *merchant_operator_config_info.key(),
+        mint: *mint_info.key(),
+        amount,
+        payment_count,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_bytes(),
+    )?;
+
+    Ok(())
+}
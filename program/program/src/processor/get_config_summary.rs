@@ -0,0 +1,81 @@
+use pinocchio::{
+    account_info::AccountInfo, cpi::set_return_data, program_error::ProgramError, pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    processor::{
+        token_account_balance, validate_escrow_authority, verify_ata_address, verify_owner,
+        verify_owner_mutability, verify_token_interface_program,
+    },
+    state::{ConfigSummary, MerchantOperatorConfig},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Writes a ConfigSummary of a config to return data, so other programs can check a merchant's
+/// fee, policies and escrowed funds by CPI without parsing the config's dynamic tail.
+///
+/// Read-only and unsigned. The summary's `open_payments` is the config's open-payment count in
+/// `mint`, and `escrow_balance` its escrow balance, which is 0 until a payment in it creates
+/// the escrow ATA.
+#[inline(always)]
+pub fn process_get_config_summary(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [merchant_operator_config_info, mint_info, escrow_authority_info, merchant_escrow_ata_info, token_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate merchant_operator_config is owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Load and validate merchant_operator_config
+    let (merchant_operator_config, policies, open_payments) = {
+        let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+        let (merchant_operator_config, policies, _allowed_mints) =
+            MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+        let open_payments = merchant_operator_config
+            .open_payments(&merchant_operator_config_data, mint_info.key())?;
+        (merchant_operator_config, policies, open_payments)
+    };
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate the escrow authority PDA and the address of the escrow ATA it owns
+    validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    verify_ata_address(
+        merchant_escrow_ata_info,
+        escrow_authority_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    let escrow_balance = if merchant_escrow_ata_info.data_is_empty() {
+        0
+    } else {
+        token_account_balance(merchant_escrow_ata_info, token_program_info)?
+    };
+
+    let summary = ConfigSummary::new(
+        &merchant_operator_config,
+        &policies,
+        escrow_balance,
+        open_payments,
+    );
+    set_return_data(&summary.to_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    processor::{validate_operator_authority, verify_owner_mutability, verify_signer},
+    state::{Operator, OperatorPermission},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_heartbeat(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [operator_authority_info, operator_info, remaining_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate operator is writable and owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    let mut operator_data = operator_info.try_borrow_mut_data()?;
+    let mut operator = Operator::try_from_bytes(&operator_data)?;
+
+    // Validate Operator PDA
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to clear payments, since
+    // the heartbeat keeps the merchants' failover from clearing in the operator's place
+    validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::Clear,
+    )?;
+
+    operator.last_active_at = Clock::get()?.unix_timestamp;
+    operator.store(&mut operator_data)
+}
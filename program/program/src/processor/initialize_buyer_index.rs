@@ -0,0 +1,120 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::BUYER_INDEX_SEED,
+    processor::{
+        create_pda_account, validate_pda, verify_owner_mutability, verify_signer,
+        verify_system_account, verify_system_program,
+    },
+    require_len,
+    state::{discriminator::AccountSerialize, BuyerIndex, MerchantOperatorConfig},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_initialize_buyer_index(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, merchant_operator_config_info, buyer_info, buyer_index_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate buyer_index is writable
+    verify_system_account(buyer_index_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate merchant_operator_config is owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, _policies, _allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate BuyerIndex PDA
+    validate_pda(
+        &[
+            BUYER_INDEX_SEED,
+            merchant_operator_config_info.key(),
+            buyer_info.key(),
+        ],
+        &Pubkey::from(*program_id),
+        args.bump,
+        buyer_index_info,
+    )?;
+
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(BUYER_INDEX_SEED),
+        Seed::from(merchant_operator_config_info.key()),
+        Seed::from(buyer_info.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        BuyerIndex::LEN,
+        program_id,
+        buyer_index_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let buyer_index = BuyerIndex::new(
+        *merchant_operator_config_info.key(),
+        *buyer_info.key(),
+        args.bump,
+    );
+
+    let mut buyer_index_data = buyer_index_info.try_borrow_mut_data()?;
+    buyer_index_data.copy_from_slice(&buyer_index.to_bytes());
+
+    Ok(())
+}
+
+struct InitializeBuyerIndexArgs {
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<InitializeBuyerIndexArgs, ProgramError> {
+    require_len!(data, 1);
+    Ok(InitializeBuyerIndexArgs { bump: data[0] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let args = process_instruction_data(&[254u8]).unwrap();
+        assert_eq!(args.bump, 254);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
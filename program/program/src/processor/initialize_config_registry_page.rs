@@ -0,0 +1,144 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::CONFIG_REGISTRY_SEED,
+    error::CommerceProgramError,
+    processor::{
+        create_pda_account, validate_pda, verify_owner_mutability, verify_signer,
+        verify_system_account, verify_system_program,
+    },
+    require_len,
+    state::{discriminator::AccountSerialize, ConfigRegistryPage, Operator},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_initialize_config_registry_page(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, operator_info, config_registry_page_info, system_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate config_registry_page is writable
+    verify_system_account(config_registry_page_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate operator is owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate operator
+    {
+        let operator_data = operator_info.try_borrow_data()?;
+        let operator = Operator::try_from_bytes(&operator_data)?;
+        operator.validate_pda(operator_info.key())?;
+    }
+
+    // Pages after the first overflow a full previous page, passed as the first remaining
+    // account
+    if args.page > 0 {
+        let previous_page_info = remaining_accounts
+            .first()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        verify_owner_mutability(previous_page_info, &COMMERCE_PROGRAM_ID, false)?;
+
+        let previous_page_data = previous_page_info.try_borrow_data()?;
+        let previous_page = ConfigRegistryPage::try_from_bytes(&previous_page_data)?;
+        previous_page.validate_pda(previous_page_info.key())?;
+        previous_page.validate_operator(operator_info.key())?;
+
+        if previous_page.page != args.page - 1 {
+            return Err(CommerceProgramError::ConfigRegistryMismatch.into());
+        }
+        if !previous_page.is_full() {
+            return Err(CommerceProgramError::ConfigRegistryPreviousPageNotFull.into());
+        }
+    }
+
+    // Validate ConfigRegistryPage PDA
+    let page_seed = args.page.to_le_bytes();
+    validate_pda(
+        &[CONFIG_REGISTRY_SEED, operator_info.key(), &page_seed],
+        &Pubkey::from(*program_id),
+        args.bump,
+        config_registry_page_info,
+    )?;
+
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(CONFIG_REGISTRY_SEED),
+        Seed::from(operator_info.key()),
+        Seed::from(&page_seed),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        ConfigRegistryPage::LEN,
+        program_id,
+        config_registry_page_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let page = ConfigRegistryPage::new(*operator_info.key(), args.page, args.bump);
+
+    let mut page_data = config_registry_page_info.try_borrow_mut_data()?;
+    page_data.copy_from_slice(&page.to_bytes());
+
+    Ok(())
+}
+
+struct InitializeConfigRegistryPageArgs {
+    page: u32,
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<InitializeConfigRegistryPageArgs, ProgramError> {
+    require_len!(data, 5);
+    let page = u32::from_le_bytes(data[0..4].try_into().unwrap());
+    let bump = data[4];
+    Ok(InitializeConfigRegistryPageArgs { page, bump })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = [0u8; 5];
+        data[0..4].copy_from_slice(&3u32.to_le_bytes());
+        data[4] = 254;
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.page, 3);
+        assert_eq!(args.bump, 254);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[0u8; 4]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -8,13 +8,15 @@ use pinocchio::{
 };
 
 use crate::{
-    constants::MERCHANT_SEED,
+    constants::{MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS, MERCHANT_SEED},
+    error::CommerceProgramError,
+    events::{EventDiscriminators, MerchantInitializedEvent},
     processor::{
-        create_pda_account, validate_pda, verify_signer, verify_system_account,
-        verify_system_program,
+        create_pda_account, emit_event, validate_pda, verify_current_program, verify_signer,
+        verify_system_account, verify_system_program,
     },
     require_len,
-    state::{discriminator::AccountSerialize, Merchant},
+    state::Merchant,
 };
 
 #[inline(always)]
@@ -23,8 +25,8 @@ pub fn process_initialize_merchant(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let args = process_instruction_data(instruction_data)?;
-    let [payer_info, authority_info, merchant_info, settlement_wallet_info, system_program_info] =
+    let args = parse_initialize_merchant_args(instruction_data)?;
+    let [payer_info, authority_info, merchant_info, settlement_wallet_info, system_program_info, event_authority_info, commerce_program_info] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -34,12 +36,44 @@ pub fn process_initialize_merchant(
     verify_signer(authority_info, false)?;
     // Validate payer is writable signer
     verify_signer(payer_info, true)?;
+    // Validate system program
+    verify_system_program(system_program_info)?;
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    create_merchant(
+        program_id,
+        payer_info,
+        authority_info,
+        merchant_info,
+        settlement_wallet_info,
+        event_authority_info,
+        commerce_program_info,
+        &args,
+    )
+}
+
+/// Creates the Merchant PDA of `authority_info`, which the caller has verified signed.
+/// Emits `MerchantInitialized`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_merchant(
+    program_id: &Pubkey,
+    payer_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    merchant_info: &AccountInfo,
+    settlement_wallet_info: &AccountInfo,
+    event_authority_info: &AccountInfo,
+    commerce_program_info: &AccountInfo,
+    args: &InitializeMerchantArgs,
+) -> ProgramResult {
     // Validate merchant is writable
     verify_system_account(merchant_info, true)?;
     // Validate settlement wallet is writable
     verify_system_account(settlement_wallet_info, false)?;
-    // Validate system program
-    verify_system_program(system_program_info)?;
+    // Validate settlement wallet timelock
+    if args.settlement_wallet_timelock_seconds > MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS {
+        return Err(CommerceProgramError::InvalidSettlementWalletTimelock.into());
+    }
     // Validate Merchant PDA
     validate_pda(
         &[MERCHANT_SEED, authority_info.key()],
@@ -71,22 +105,51 @@ pub fn process_initialize_merchant(
         owner: *authority_info.key(),
         bump: args.bump,
         settlement_wallet: *settlement_wallet_info.key(),
+        pending_authority: Pubkey::default(),
+        settlement_wallet_timelock_seconds: args.settlement_wallet_timelock_seconds,
+        pending_settlement_wallet: Pubkey::default(),
+        pending_settlement_wallet_unlocks_at: 0,
+        original_owner: *authority_info.key(),
     };
 
     let mut merchant_data = merchant_info.try_borrow_mut_data()?;
-    merchant_data.copy_from_slice(&merchant.to_bytes());
+    merchant.store(&mut merchant_data)?;
 
-    Ok(())
+    // Emit merchant initialized event
+    let event = MerchantInitializedEvent {
+        discriminator: EventDiscriminators::MerchantInitialized as u8,
+        merchant: *merchant_info.key(),
+        authority: merchant.owner,
+        settlement_wallet: merchant.settlement_wallet,
+        settlement_wallet_timelock_seconds: merchant.settlement_wallet_timelock_seconds,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
 }
 
-struct InitializeMerchantArgs {
+pub(crate) struct InitializeMerchantArgs {
     bump: u8,
+    settlement_wallet_timelock_seconds: u64,
 }
 
-fn process_instruction_data(data: &[u8]) -> Result<InitializeMerchantArgs, ProgramError> {
-    require_len!(data, 1);
+/// Bytes of InitializeMerchant instruction data.
+pub(crate) const INITIALIZE_MERCHANT_ARGS_LEN: usize = 9;
+
+pub(crate) fn parse_initialize_merchant_args(
+    data: &[u8],
+) -> Result<InitializeMerchantArgs, ProgramError> {
+    require_len!(data, INITIALIZE_MERCHANT_ARGS_LEN);
     let bump = data[0];
-    Ok(InitializeMerchantArgs { bump })
+    let settlement_wallet_timelock_seconds = u64::from_le_bytes(data[1..9].try_into().unwrap());
+    Ok(InitializeMerchantArgs {
+        bump,
+        settlement_wallet_timelock_seconds,
+    })
 }
 
 #[cfg(test)]
@@ -94,27 +157,37 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process_instruction_data_valid() {
-        let data = [255u8];
-        let args = process_instruction_data(&data).unwrap();
+    fn test_parse_initialize_merchant_args_valid() {
+        let mut data = [0u8; 9];
+        data[0] = 255;
+        data[1..9].copy_from_slice(&86_400u64.to_le_bytes());
+        let args = parse_initialize_merchant_args(&data).unwrap();
         assert_eq!(args.bump, 255);
+        assert_eq!(args.settlement_wallet_timelock_seconds, 86_400);
     }
 
     #[test]
-    fn test_process_instruction_data_edge_cases() {
-        let data = [0u8];
-        let args = process_instruction_data(&data).unwrap();
+    fn test_parse_initialize_merchant_args_edge_cases() {
+        let data = [0u8; 9];
+        let args = parse_initialize_merchant_args(&data).unwrap();
         assert_eq!(args.bump, 0);
+        assert_eq!(args.settlement_wallet_timelock_seconds, 0);
 
-        let data = [u8::MAX];
-        let args = process_instruction_data(&data).unwrap();
+        let data = [u8::MAX; 9];
+        let args = parse_initialize_merchant_args(&data).unwrap();
         assert_eq!(args.bump, u8::MAX);
+        assert_eq!(args.settlement_wallet_timelock_seconds, u64::MAX);
     }
 
     #[test]
-    fn test_process_instruction_data_invalid_length() {
+    fn test_parse_initialize_merchant_args_invalid_length() {
         let data = [];
-        let result = process_instruction_data(&data);
+        let result = parse_initialize_merchant_args(&data);
+        assert!(result.is_err());
+
+        // Bump without a timelock
+        let data = [255u8];
+        let result = parse_initialize_merchant_args(&data);
         assert!(result.is_err());
     }
 }
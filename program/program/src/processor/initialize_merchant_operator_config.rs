@@ -12,16 +12,18 @@ use alloc::vec::Vec;
 use crate::{
     constants::MERCHANT_OPERATOR_CONFIG_SEED,
     error::CommerceProgramError,
+    events::{EventDiscriminators, MerchantOperatorConfigInitializedEvent},
     processor::{
-        create_pda_account, mint_utils::validate_mints, validate_pda, verify_mint_account,
-        verify_owner_mutability, verify_signer, verify_system_account, verify_system_program,
-        verify_token_program_account,
+        create_pda_account, emit_event, mint_utils::validate_mints, register_config,
+        split_config_registry_page, validate_immediate_settlement, validate_pda,
+        verify_current_program, verify_mint_account, verify_owner_mutability, verify_signer,
+        verify_system_account, verify_system_program, verify_token_interface_account,
     },
     state::{FeeType, MerchantOperatorConfig, PolicyData, PolicyType},
     ID as COMMERCE_PROGRAM_ID,
 };
 
-const REMAINING_ACCOUNTS_OFFSET: usize = 6;
+const REMAINING_ACCOUNTS_OFFSET: usize = 8;
 
 #[inline(always)]
 pub fn process_initialize_merchant_operator_config(
@@ -29,24 +31,23 @@ pub fn process_initialize_merchant_operator_config(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let args = process_instruction_data(instruction_data)?;
+    let args = parse_initialize_merchant_operator_config_args(instruction_data)?;
 
-    if accounts.len() < REMAINING_ACCOUNTS_OFFSET + args.accepted_currencies.len() {
+    if accounts.len() < REMAINING_ACCOUNTS_OFFSET {
         return Err(ProgramError::NotEnoughAccountKeys);
     }
 
-    if args.accepted_currencies.is_empty() {
-        return Err(CommerceProgramError::AcceptedCurrenciesEmpty.into());
-    }
-
     let payer_info = &accounts[0];
     let authority_info = &accounts[1];
     let merchant_info = &accounts[2];
     let operator_info = &accounts[3];
     let config_info = &accounts[4];
     let system_program_info = &accounts[5];
+    let event_authority_info = &accounts[6];
+    let commerce_program_info = &accounts[7];
 
-    // Remaining accounts should be the mint accounts for each accepted currency
+    // Remaining accounts should be the mint accounts for each accepted currency, then the
+    // operator's ConfigRegistryPage PDA, if any
     let mint_accounts = &accounts[REMAINING_ACCOUNTS_OFFSET..];
 
     // Validate: authority should have signed
@@ -55,6 +56,54 @@ pub fn process_initialize_merchant_operator_config(
     // Validate payer is writable signer
     verify_signer(payer_info, true)?;
 
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate commerce program
+    verify_current_program(commerce_program_info)?;
+
+    create_merchant_operator_config(
+        program_id,
+        payer_info,
+        merchant_info,
+        operator_info,
+        config_info,
+        event_authority_info,
+        commerce_program_info,
+        mint_accounts,
+        &args,
+    )
+}
+
+/// Validates `args` and creates the MerchantOperatorConfig PDA of the merchant and operator,
+/// with `mint_accounts` holding one mint per accepted currency, optionally followed by the
+/// operator's ConfigRegistryPage PDA to list the config on. Emits
+/// `MerchantOperatorConfigInitialized` with the config's hash.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_merchant_operator_config(
+    program_id: &Pubkey,
+    payer_info: &AccountInfo,
+    merchant_info: &AccountInfo,
+    operator_info: &AccountInfo,
+    config_info: &AccountInfo,
+    event_authority_info: &AccountInfo,
+    commerce_program_info: &AccountInfo,
+    mint_accounts: &[AccountInfo],
+    args: &InitializeMerchantOperatorConfigArgs,
+) -> ProgramResult {
+    let (config_registry_page_info, mint_accounts) = split_config_registry_page(mint_accounts);
+
+    if mint_accounts.len() < args.accepted_currencies.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    if args.accepted_currencies.is_empty() {
+        return Err(CommerceProgramError::AcceptedCurrenciesEmpty.into());
+    }
+
+    // Validate the config fits the policy and accepted currency limits
+    MerchantOperatorConfig::validate_limits(args.policies.len(), args.accepted_currencies.len())?;
+
     // Validate config is writable
     verify_system_account(config_info, true)?;
 
@@ -64,24 +113,25 @@ pub fn process_initialize_merchant_operator_config(
     // Validate operator is owned by this program
     verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
 
-    // Validate system program
-    verify_system_program(system_program_info)?;
-
     // Validate no duplicate mints in accepted currencies
     validate_mints(&args.accepted_currencies)?;
 
+    // Validate each policy's fields, and that they combine
+    args.policies.iter().try_for_each(PolicyData::validate)?;
+    validate_immediate_settlement(&args.policies)?;
+
     // Validate mint accounts match accepted currencies and are valid mints
     mint_accounts
         .iter()
-        .enumerate()
-        .try_for_each(|(i, mint_info)| {
+        .zip(&args.accepted_currencies)
+        .try_for_each(|(mint_info, accepted_currency)| {
             // Validate mint account key matches the expected accepted currency
-            if mint_info.key() != &args.accepted_currencies[i] {
+            if mint_info.key() != accepted_currency {
                 return Err(CommerceProgramError::InvalidMint.into());
             }
 
-            // Validate mint is owned by token program
-            verify_token_program_account(mint_info)?;
+            // Validate mint is owned by token program, SPL Token or Token-2022
+            verify_token_interface_account(mint_info)?;
 
             // Validate mint is a valid mint account
             verify_mint_account(mint_info)
@@ -100,18 +150,22 @@ pub fn process_initialize_merchant_operator_config(
         config_info,
     )?;
 
-    let config = MerchantOperatorConfig {
+    let mut config = MerchantOperatorConfig {
         version: args.version,
         bump: args.bump,
         merchant: *merchant_info.key(),
         operator: *operator_info.key(),
         operator_fee: args.operator_fee,
-        fee_type: args.fee_type,
+        fee_type: args.fee_type.clone(),
         num_policies: args.policies.len() as u32,
         num_accepted_currencies: args.accepted_currencies.len() as u32,
         current_order_id: 0,
         days_to_close: args.days_to_close,
+        config_hash: [0; 32],
+        decommissioned_at: 0,
+        payment_accounts: 0,
     };
+    config.config_hash = config.compute_config_hash(&args.policies, &args.accepted_currencies);
     // Validate Merchant PDA (ensures correct authority)
     config.validate_pda(config_info.key())?;
     let space: usize = config.calculate_size();
@@ -137,13 +191,38 @@ pub fn process_initialize_merchant_operator_config(
     )?;
 
     let config_data = config.to_bytes(&args.policies, &args.accepted_currencies);
-    let mut account_data = config_info.try_borrow_mut_data()?;
-    account_data.copy_from_slice(&config_data);
+    {
+        let mut account_data = config_info.try_borrow_mut_data()?;
+        account_data.copy_from_slice(&config_data);
+    }
+
+    // List the config on the operator's registry page, if passed
+    register_config(
+        config_registry_page_info,
+        &config.operator,
+        &config.merchant,
+        config.version,
+    )?;
 
-    Ok(())
+    // Emit config initialized event
+    let event = MerchantOperatorConfigInitializedEvent {
+        discriminator: EventDiscriminators::MerchantOperatorConfigInitialized as u8,
+        config: *config_info.key(),
+        merchant: config.merchant,
+        operator: config.operator,
+        version: config.version,
+        config_hash: config.config_hash,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_bytes(),
+    )
 }
 
-struct InitializeMerchantOperatorConfigArgs {
+pub(crate) struct InitializeMerchantOperatorConfigArgs {
     version: u32,
     bump: u8,
     operator_fee: u64,
@@ -153,7 +232,7 @@ struct InitializeMerchantOperatorConfigArgs {
     accepted_currencies: Vec<Pubkey>,
 }
 
-fn process_instruction_data(
+pub(crate) fn parse_initialize_merchant_operator_config_args(
     data: &[u8],
 ) -> Result<InitializeMerchantOperatorConfigArgs, ProgramError> {
     if data.is_empty() {
@@ -288,7 +367,7 @@ mod tests {
         let pubkey_bytes = [1u8; 32];
         data.extend_from_slice(&pubkey_bytes);
 
-        let args = process_instruction_data(&data).unwrap();
+        let args = parse_initialize_merchant_operator_config_args(&data).unwrap();
         assert_eq!(args.version, 1);
         assert_eq!(args.bump, 254);
         assert_eq!(args.operator_fee, 1000);
@@ -331,7 +410,7 @@ mod tests {
         data.extend_from_slice(&[2u8; 32]); // First currency
         data.extend_from_slice(&[3u8; 32]); // Second currency
 
-        let args = process_instruction_data(&data).unwrap();
+        let args = parse_initialize_merchant_operator_config_args(&data).unwrap();
         assert_eq!(args.version, 1);
         assert_eq!(args.bump, 255);
         assert_eq!(args.operator_fee, 250);
@@ -370,7 +449,7 @@ mod tests {
         data.extend_from_slice(&1u32.to_le_bytes()); // One currency
         data.extend_from_slice(&[0u8; 32]);
 
-        let args = process_instruction_data(&data).unwrap();
+        let args = parse_initialize_merchant_operator_config_args(&data).unwrap();
         assert_eq!(args.version, 0);
         assert_eq!(args.bump, 0);
         assert_eq!(args.operator_fee, 0);
@@ -381,14 +460,14 @@ mod tests {
     #[test]
     fn test_process_instruction_data_invalid_empty() {
         let data = vec![];
-        let result = process_instruction_data(&data);
+        let result = parse_initialize_merchant_operator_config_args(&data);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_process_instruction_data_invalid_too_short() {
         let data = vec![1u8; 10]; // Too short for minimum required fields
-        let result = process_instruction_data(&data);
+        let result = parse_initialize_merchant_operator_config_args(&data);
         assert!(result.is_err());
     }
 
@@ -404,7 +483,7 @@ mod tests {
         data.extend_from_slice(&1u32.to_le_bytes());
         data.extend_from_slice(&[1u8; 32]);
 
-        let result = process_instruction_data(&data);
+        let result = parse_initialize_merchant_operator_config_args(&data);
         assert!(result.is_err());
     }
 
@@ -420,7 +499,7 @@ mod tests {
         data.push(0u8); // Policy type Settlement
                         // Missing policy data - should fail
 
-        let result = process_instruction_data(&data);
+        let result = parse_initialize_merchant_operator_config_args(&data);
         assert!(result.is_err());
     }
 
@@ -437,7 +516,7 @@ mod tests {
         data.extend_from_slice(&[1u8; 32]); // First currency
                                             // Missing second currency - should fail
 
-        let result = process_instruction_data(&data);
+        let result = parse_initialize_merchant_operator_config_args(&data);
         assert!(result.is_err());
     }
 
@@ -455,10 +534,94 @@ mod tests {
         data.extend_from_slice(&[2u8; 32]);
         data.extend_from_slice(&[3u8; 32]);
 
-        let args = process_instruction_data(&data).unwrap();
+        let args = parse_initialize_merchant_operator_config_args(&data).unwrap();
         assert_eq!(args.accepted_currencies.len(), 3);
         assert_eq!(args.accepted_currencies[0], Pubkey::from([1u8; 32]));
         assert_eq!(args.accepted_currencies[1], Pubkey::from([2u8; 32]));
         assert_eq!(args.accepted_currencies[2], Pubkey::from([3u8; 32]));
     }
 }
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use crate::state::policy::strategies::{fee_type, policy_data};
+    use proptest::{collection::vec as prop_vec, prelude::*};
+
+    /// Encodes arguments the way the clients do: policies carry only their own bytes.
+    fn encode(
+        (version, bump, operator_fee, fee_type, days_to_close): (u32, u8, u64, FeeType, u16),
+        policies: &[PolicyData],
+        currencies: &[Pubkey],
+    ) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&version.to_le_bytes());
+        data.push(bump);
+        data.extend_from_slice(&operator_fee.to_le_bytes());
+        data.push(fee_type.to_u8());
+        data.extend_from_slice(&days_to_close.to_le_bytes());
+        data.extend_from_slice(&(policies.len() as u32).to_le_bytes());
+        for policy in policies {
+            data.extend_from_slice(&policy.to_bytes()[..policy.policy_type().get_size()]);
+        }
+        data.extend_from_slice(&(currencies.len() as u32).to_le_bytes());
+        for currency in currencies {
+            data.extend_from_slice(currency);
+        }
+        data
+    }
+
+    proptest! {
+        #[test]
+        fn instruction_data_round_trips(
+            header in (any::<u32>(), any::<u8>(), any::<u64>(), fee_type(), any::<u16>()),
+            policies in prop_vec(policy_data(), 0..4),
+            currencies in prop_vec(any::<Pubkey>(), 0..4),
+        ) {
+            let data = encode(header.clone(), &policies, &currencies);
+            let Ok(args) = parse_initialize_merchant_operator_config_args(&data) else {
+                return Err(TestCaseError::fail("valid instruction data rejected"));
+            };
+
+            prop_assert_eq!(
+                (args.version, args.bump, args.operator_fee, args.fee_type, args.days_to_close),
+                header
+            );
+            prop_assert_eq!(args.policies, policies);
+            prop_assert_eq!(args.accepted_currencies, currencies);
+        }
+
+        #[test]
+        fn instruction_data_truncation_is_rejected(
+            header in (any::<u32>(), any::<u8>(), any::<u64>(), fee_type(), any::<u16>()),
+            policies in prop_vec(policy_data(), 0..4),
+            currencies in prop_vec(any::<Pubkey>(), 1..4),
+            cut in any::<prop::sample::Index>(),
+        ) {
+            let data = encode(header, &policies, &currencies);
+            let truncated = &data[..cut.index(data.len())];
+            prop_assert!(parse_initialize_merchant_operator_config_args(truncated).is_err());
+        }
+
+        #[test]
+        fn instruction_data_from_arbitrary_bytes_is_stable(
+            data in prop_vec(any::<u8>(), 0..400)
+        ) {
+            // Must never panic; anything it accepts must survive a re-encode
+            if let Ok(args) = parse_initialize_merchant_operator_config_args(&data) {
+                let header = (args.version, args.bump, args.operator_fee, args.fee_type.clone(), args.days_to_close);
+                let encoded = encode(header.clone(), &args.policies, &args.accepted_currencies);
+                let Ok(reparsed) = parse_initialize_merchant_operator_config_args(&encoded) else {
+                    return Err(TestCaseError::fail("re-encoded instruction data rejected"));
+                };
+
+                prop_assert_eq!(
+                    (reparsed.version, reparsed.bump, reparsed.operator_fee, reparsed.fee_type, reparsed.days_to_close),
+                    header
+                );
+                prop_assert_eq!(reparsed.policies, args.policies);
+                prop_assert_eq!(reparsed.accepted_currencies, args.accepted_currencies);
+            }
+        }
+    }
+}
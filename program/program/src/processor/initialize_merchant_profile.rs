@@ -0,0 +1,193 @@
+extern crate alloc;
+
+use alloc::string::String;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::{MAX_MERCHANT_NAME_LEN, MAX_MERCHANT_URI_LEN, MERCHANT_PROFILE_SEED},
+    processor::{
+        create_pda_account, validate_pda, verify_owner_mutability, verify_signer,
+        verify_system_account, verify_system_program,
+    },
+    require_len,
+    state::{read_string, Merchant, MerchantProfile},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_initialize_merchant_profile(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, authority_info, merchant_info, merchant_profile_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate merchant_profile is writable
+    verify_system_account(merchant_profile_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_owner(authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    let ProfileFields {
+        name,
+        uri,
+        logo_hash,
+        contact_hash,
+    } = args.fields;
+    let merchant_profile = MerchantProfile::new(
+        *merchant_info.key(),
+        args.bump,
+        name,
+        uri,
+        logo_hash,
+        contact_hash,
+    )?;
+
+    // Validate MerchantProfile PDA
+    validate_pda(
+        &[MERCHANT_PROFILE_SEED, merchant_info.key()],
+        &Pubkey::from(*program_id),
+        args.bump,
+        merchant_profile_info,
+    )?;
+
+    let space = MerchantProfile::LEN;
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(MERCHANT_PROFILE_SEED),
+        Seed::from(merchant_info.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        space,
+        program_id,
+        merchant_profile_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let mut merchant_profile_data = merchant_profile_info.try_borrow_mut_data()?;
+    merchant_profile.write(&mut merchant_profile_data);
+
+    Ok(())
+}
+
+pub(crate) struct ProfileFields {
+    pub name: String,
+    pub uri: String,
+    pub logo_hash: [u8; 32],
+    pub contact_hash: [u8; 32],
+}
+
+struct InitializeMerchantProfileArgs {
+    fields: ProfileFields,
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<InitializeMerchantProfileArgs, ProgramError> {
+    let (fields, offset) = parse_profile_fields(data)?;
+    require_len!(data, offset + 1);
+    let bump = data[offset];
+    Ok(InitializeMerchantProfileArgs { fields, bump })
+}
+
+/// Parses name, uri, logo_hash and contact_hash, in that order, returning the offset after
+/// them.
+pub(crate) fn parse_profile_fields(data: &[u8]) -> Result<(ProfileFields, usize), ProgramError> {
+    let (name, offset) = read_string(data, 0, MAX_MERCHANT_NAME_LEN)?;
+    let (uri, offset) = read_string(data, offset, MAX_MERCHANT_URI_LEN)?;
+
+    require_len!(data, offset + 64);
+    let logo_hash = data[offset..offset + 32].try_into().unwrap();
+    let contact_hash = data[offset + 32..offset + 64].try_into().unwrap();
+
+    Ok((
+        ProfileFields {
+            name,
+            uri,
+            logo_hash,
+            contact_hash,
+        },
+        offset + 64,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CommerceProgramError;
+    use alloc::vec::Vec;
+
+    fn profile_fields_data(name: &str, uri: &str) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(uri.as_bytes());
+        data.extend_from_slice(&[2u8; 32]);
+        data.extend_from_slice(&[3u8; 32]);
+        data
+    }
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = profile_fields_data("Coffee Shop", "https://coffee.example");
+        data.push(253);
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.fields.name, "Coffee Shop");
+        assert_eq!(args.fields.uri, "https://coffee.example");
+        assert_eq!(args.fields.logo_hash, [2u8; 32]);
+        assert_eq!(args.fields.contact_hash, [3u8; 32]);
+        assert_eq!(args.bump, 253);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let data = profile_fields_data("Coffee Shop", "https://coffee.example");
+        let result = process_instruction_data(&data);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_process_instruction_data_name_too_long() {
+        let mut data = profile_fields_data(&"n".repeat(MAX_MERCHANT_NAME_LEN + 1), "");
+        data.push(253);
+
+        let result = process_instruction_data(&data);
+        assert_eq!(
+            result.err(),
+            Some(CommerceProgramError::MerchantProfileFieldTooLong.into())
+        );
+    }
+}
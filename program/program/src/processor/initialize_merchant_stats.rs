@@ -0,0 +1,123 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::MERCHANT_STATS_SEED,
+    processor::{
+        create_pda_account, validate_pda, verify_mint_account, verify_owner_mutability,
+        verify_signer, verify_system_account, verify_system_program,
+        verify_token_interface_account,
+    },
+    require_len,
+    state::{discriminator::AccountSerialize, Merchant, MerchantStats},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_initialize_merchant_stats(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, authority_info, merchant_info, mint_info, merchant_stats_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate merchant_stats is writable
+    verify_system_account(merchant_stats_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate mint is owned by token program, SPL Token or Token-2022, and is a valid mint
+    verify_token_interface_account(mint_info)?;
+    verify_mint_account(mint_info)?;
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_owner(authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Validate MerchantStats PDA
+    validate_pda(
+        &[MERCHANT_STATS_SEED, merchant_info.key(), mint_info.key()],
+        &Pubkey::from(*program_id),
+        args.bump,
+        merchant_stats_info,
+    )?;
+
+    let space = MerchantStats::LEN;
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(MERCHANT_STATS_SEED),
+        Seed::from(merchant_info.key()),
+        Seed::from(mint_info.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        space,
+        program_id,
+        merchant_stats_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let merchant_stats = MerchantStats::new(*merchant_info.key(), *mint_info.key(), args.bump);
+
+    let mut merchant_stats_data = merchant_stats_info.try_borrow_mut_data()?;
+    merchant_stats_data.copy_from_slice(&merchant_stats.to_bytes());
+
+    Ok(())
+}
+
+struct InitializeMerchantStatsArgs {
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<InitializeMerchantStatsArgs, ProgramError> {
+    require_len!(data, 1);
+    let bump = data[0];
+    Ok(InitializeMerchantStatsArgs { bump })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let data = [254u8];
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.bump, 254);
+    }
+
+    #[test]
+    fn test_process_instruction_data_empty() {
+        let result = process_instruction_data(&[]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
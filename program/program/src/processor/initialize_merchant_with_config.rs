@@ -0,0 +1,100 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    processor::{
+        create_merchant, create_merchant_operator_config, parse_initialize_merchant_args,
+        parse_initialize_merchant_operator_config_args, verify_current_program,
+        verify_owner_mutability, verify_signer, verify_system_program,
+        INITIALIZE_MERCHANT_ARGS_LEN,
+    },
+    require_len,
+    state::Operator,
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+const REMAINING_ACCOUNTS_OFFSET: usize = 10;
+
+/// Creates a Merchant and its MerchantOperatorConfig in one instruction. The operator owner
+/// co-signs, so a merchant never exists without the config it was onboarded for.
+#[inline(always)]
+pub fn process_initialize_merchant_with_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    require_len!(instruction_data, INITIALIZE_MERCHANT_ARGS_LEN);
+    let (merchant_data, config_data) = instruction_data.split_at(INITIALIZE_MERCHANT_ARGS_LEN);
+    let merchant_args = parse_initialize_merchant_args(merchant_data)?;
+    let config_args = parse_initialize_merchant_operator_config_args(config_data)?;
+
+    if accounts.len() < REMAINING_ACCOUNTS_OFFSET {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let payer_info = &accounts[0];
+    let authority_info = &accounts[1];
+    let operator_authority_info = &accounts[2];
+    let merchant_info = &accounts[3];
+    let settlement_wallet_info = &accounts[4];
+    let operator_info = &accounts[5];
+    let config_info = &accounts[6];
+    let system_program_info = &accounts[7];
+    let event_authority_info = &accounts[8];
+    let commerce_program_info = &accounts[9];
+
+    // Remaining accounts should be the mint accounts for each accepted currency, then the
+    // operator's ConfigRegistryPage PDA, if any
+    let mint_accounts = &accounts[REMAINING_ACCOUNTS_OFFSET..];
+
+    // Validate: merchant and operator authorities should have signed
+    verify_signer(authority_info, false)?;
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate commerce program
+    verify_current_program(commerce_program_info)?;
+
+    // Validate operator is owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    {
+        let operator_data = operator_info.try_borrow_data()?;
+        let operator = Operator::try_from_bytes(&operator_data)?;
+
+        // Validate operator owner
+        operator.validate_owner(operator_authority_info.key())?;
+
+        // Validate Operator PDA
+        operator.validate_pda(operator_info.key())?;
+    }
+
+    create_merchant(
+        program_id,
+        payer_info,
+        authority_info,
+        merchant_info,
+        settlement_wallet_info,
+        event_authority_info,
+        commerce_program_info,
+        &merchant_args,
+    )?;
+
+    create_merchant_operator_config(
+        program_id,
+        payer_info,
+        merchant_info,
+        operator_info,
+        config_info,
+        event_authority_info,
+        commerce_program_info,
+        mint_accounts,
+        &config_args,
+    )
+}
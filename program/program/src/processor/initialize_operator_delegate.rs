@@ -0,0 +1,180 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::OPERATOR_DELEGATE_SEED,
+    processor::{
+        create_pda_account, validate_pda, verify_owner_mutability, verify_signer,
+        verify_system_account, verify_system_program,
+    },
+    require_len,
+    state::{discriminator::AccountSerialize, Operator, OperatorDelegate},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+pub(crate) const PERMISSIONS_LEN: usize = 4;
+pub(crate) const REFUND_LIMITS_LEN: usize = 8 + 8;
+
+#[inline(always)]
+pub fn process_initialize_operator_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, authority_info, operator_info, operator_delegate_info, delegate_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate operator_delegate is writable
+    verify_system_account(operator_delegate_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate operator is owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_owner(authority_info.key())?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate OperatorDelegate PDA
+    validate_pda(
+        &[
+            OPERATOR_DELEGATE_SEED,
+            operator_info.key(),
+            delegate_info.key(),
+        ],
+        &Pubkey::from(*program_id),
+        args.bump,
+        operator_delegate_info,
+    )?;
+
+    let space = OperatorDelegate::LEN;
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(OPERATOR_DELEGATE_SEED),
+        Seed::from(operator_info.key()),
+        Seed::from(delegate_info.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        space,
+        program_id,
+        operator_delegate_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let [can_make_payment, can_clear, can_refund, can_close] = args.permissions;
+    let (max_refund_amount, max_daily_refund_amount) = args.refund_limits;
+    let operator_delegate = OperatorDelegate {
+        operator: *operator_info.key(),
+        delegate: *delegate_info.key(),
+        bump: args.bump,
+        can_make_payment,
+        can_clear,
+        can_refund,
+        can_close,
+        max_refund_amount,
+        max_daily_refund_amount,
+        refund_day: 0,
+        daily_refund_amount: 0,
+    };
+
+    let mut operator_delegate_data = operator_delegate_info.try_borrow_mut_data()?;
+    operator_delegate_data.copy_from_slice(&operator_delegate.to_bytes());
+
+    Ok(())
+}
+
+struct InitializeOperatorDelegateArgs {
+    bump: u8,
+    permissions: [bool; PERMISSIONS_LEN],
+    refund_limits: (u64, u64),
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<InitializeOperatorDelegateArgs, ProgramError> {
+    require_len!(data, 1 + PERMISSIONS_LEN + REFUND_LIMITS_LEN);
+    let bump = data[0];
+    let permissions = parse_permissions(&data[1..])?;
+    let refund_limits = parse_refund_limits(&data[1 + PERMISSIONS_LEN..])?;
+    Ok(InitializeOperatorDelegateArgs {
+        bump,
+        permissions,
+        refund_limits,
+    })
+}
+
+/// Parses can_make_payment, can_clear, can_refund and can_close, in that order.
+pub(crate) fn parse_permissions(data: &[u8]) -> Result<[bool; PERMISSIONS_LEN], ProgramError> {
+    require_len!(data, PERMISSIONS_LEN);
+    let mut permissions = [false; PERMISSIONS_LEN];
+    for (permission, byte) in permissions.iter_mut().zip(data) {
+        *permission = match byte {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+    }
+    Ok(permissions)
+}
+
+/// Parses max_refund_amount and max_daily_refund_amount, in that order.
+pub(crate) fn parse_refund_limits(data: &[u8]) -> Result<(u64, u64), ProgramError> {
+    require_len!(data, REFUND_LIMITS_LEN);
+    let max_refund_amount = u64::from_le_bytes(data[..8].try_into().unwrap());
+    let max_daily_refund_amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    Ok((max_refund_amount, max_daily_refund_amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = vec![254u8, 1, 0, 1, 0];
+        data.extend_from_slice(&500u64.to_le_bytes());
+        data.extend_from_slice(&2_000u64.to_le_bytes());
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.bump, 254);
+        assert_eq!(args.permissions, [true, false, true, false]);
+        assert_eq!(args.refund_limits, (500, 2_000));
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[254u8, 1, 1, 1, 1, 0, 0]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_parse_permissions_invalid_bool() {
+        let result = parse_permissions(&[1, 2, 0, 0]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -0,0 +1,120 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::OPERATOR_FREEZE_LIST_SEED,
+    processor::{
+        create_pda_account, validate_pda, verify_owner_mutability, verify_signer,
+        verify_system_account, verify_system_program,
+    },
+    require_len,
+    state::{discriminator::AccountSerialize, Operator, OperatorFreezeList},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_initialize_operator_freeze_list(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, authority_info, operator_info, operator_freeze_list_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate operator_freeze_list is writable
+    verify_system_account(operator_freeze_list_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate operator is writable and owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Load and validate operator
+    let mut operator = {
+        let operator_data = operator_info.try_borrow_data()?;
+        Operator::try_from_bytes(&operator_data)?
+    };
+    operator.validate_owner(authority_info.key())?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate OperatorFreezeList PDA
+    validate_pda(
+        &[OPERATOR_FREEZE_LIST_SEED, operator_info.key()],
+        &Pubkey::from(*program_id),
+        args.bump,
+        operator_freeze_list_info,
+    )?;
+
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(OPERATOR_FREEZE_LIST_SEED),
+        Seed::from(operator_info.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        OperatorFreezeList::LEN,
+        program_id,
+        operator_freeze_list_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let freeze_list = OperatorFreezeList::new(*operator_info.key(), args.bump);
+
+    let mut freeze_list_data = operator_freeze_list_info.try_borrow_mut_data()?;
+    freeze_list_data.copy_from_slice(&freeze_list.to_bytes());
+
+    // Record the freeze list on the operator, so it must be passed wherever it is checked
+    operator.has_freeze_list = true;
+    operator.store(&mut operator_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
+
+struct InitializeOperatorFreezeListArgs {
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<InitializeOperatorFreezeListArgs, ProgramError> {
+    require_len!(data, 1);
+    Ok(InitializeOperatorFreezeListArgs { bump: data[0] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let args = process_instruction_data(&[254]).unwrap();
+        assert_eq!(args.bump, 254);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
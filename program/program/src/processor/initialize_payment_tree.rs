@@ -0,0 +1,127 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::PAYMENT_TREE_SEED,
+    processor::{
+        create_pda_account, validate_pda, verify_owner_mutability, verify_signer,
+        verify_system_account, verify_system_program,
+    },
+    require_len,
+    state::{discriminator::AccountSerialize, MerchantOperatorConfig, Operator, PaymentTree},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_initialize_payment_tree(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, operator_authority_info, operator_info, merchant_operator_config_info, payment_tree_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate payment_tree is writable
+    verify_system_account(payment_tree_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate operator is owned by the program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_operator_config is owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+    operator.validate_owner(operator_authority_info.key())?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, _, _) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+
+    // Validate PaymentTree PDA
+    validate_pda(
+        &[PAYMENT_TREE_SEED, merchant_operator_config_info.key()],
+        &Pubkey::from(*program_id),
+        args.bump,
+        payment_tree_info,
+    )?;
+
+    let space = PaymentTree::LEN;
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(PAYMENT_TREE_SEED),
+        Seed::from(merchant_operator_config_info.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        space,
+        program_id,
+        payment_tree_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let payment_tree = PaymentTree::new(*merchant_operator_config_info.key(), args.bump);
+
+    let mut payment_tree_data = payment_tree_info.try_borrow_mut_data()?;
+    payment_tree_data.copy_from_slice(&payment_tree.to_bytes());
+
+    Ok(())
+}
+
+struct InitializePaymentTreeArgs {
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<InitializePaymentTreeArgs, ProgramError> {
+    require_len!(data, 1);
+    let bump = data[0];
+    Ok(InitializePaymentTreeArgs { bump })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let data = [254u8];
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.bump, 254);
+    }
+
+    #[test]
+    fn test_process_instruction_data_empty() {
+        let result = process_instruction_data(&[]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -0,0 +1,125 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::RISK_STATE_SEED,
+    processor::{
+        create_pda_account, validate_pda, verify_owner_mutability, verify_signer,
+        verify_system_account, verify_system_program,
+    },
+    require_len,
+    state::{discriminator::AccountSerialize, MerchantOperatorConfig, RiskState},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_initialize_risk_state(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, merchant_operator_config_info, risk_state_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate risk_state is writable
+    verify_system_account(risk_state_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate merchant_operator_config is owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, _policies, _allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate RiskState PDA
+    validate_pda(
+        &[
+            RISK_STATE_SEED,
+            merchant_operator_config_info.key(),
+            &args.buyer,
+        ],
+        &Pubkey::from(*program_id),
+        args.bump,
+        risk_state_info,
+    )?;
+
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(RISK_STATE_SEED),
+        Seed::from(merchant_operator_config_info.key()),
+        Seed::from(&args.buyer),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        RiskState::LEN,
+        program_id,
+        risk_state_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let risk_state = RiskState::new(*merchant_operator_config_info.key(), args.buyer, args.bump);
+
+    let mut risk_state_data = risk_state_info.try_borrow_mut_data()?;
+    risk_state_data.copy_from_slice(&risk_state.to_bytes());
+
+    Ok(())
+}
+
+struct InitializeRiskStateArgs {
+    bump: u8,
+    buyer: Pubkey,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<InitializeRiskStateArgs, ProgramError> {
+    require_len!(data, 33);
+    Ok(InitializeRiskStateArgs {
+        bump: data[0],
+        buyer: data[1..33].try_into().unwrap(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = vec![254u8];
+        data.extend_from_slice(&[7u8; 32]);
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.bump, 254);
+        assert_eq!(args.buyer, [7u8; 32]);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[254u8; 32]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -0,0 +1,144 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::SETTLEMENT_BATCH_SEED,
+    error::CommerceProgramError,
+    processor::{
+        create_pda_account, validate_pda, verify_mint_account, verify_owner_mutability,
+        verify_signer, verify_system_account, verify_system_program,
+        verify_token_interface_account,
+    },
+    require_len,
+    state::{discriminator::AccountSerialize, Merchant, MerchantOperatorConfig, SettlementBatch},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_initialize_settlement_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, authority_info, merchant_info, merchant_operator_config_info, mint_info, settlement_batch_info, system_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate settlement_batch is writable
+    verify_system_account(settlement_batch_info, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate merchant and merchant_operator_config are owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate mint is owned by token program, SPL Token or Token-2022, and is a valid mint
+    verify_token_interface_account(mint_info)?;
+    verify_mint_account(mint_info)?;
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_owner(authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate merchant_operator_config belongs to the merchant and accepts the mint
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, _, allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+    if !allowed_mints.contains(mint_info.key()) {
+        return Err(CommerceProgramError::InvalidMint.into());
+    }
+
+    // Validate SettlementBatch PDA
+    validate_pda(
+        &[
+            SETTLEMENT_BATCH_SEED,
+            merchant_operator_config_info.key(),
+            mint_info.key(),
+        ],
+        &Pubkey::from(*program_id),
+        args.bump,
+        settlement_batch_info,
+    )?;
+
+    let space = SettlementBatch::LEN;
+    let rent = Rent::get()?;
+    let bump_seed = [args.bump];
+    let signer_seeds = [
+        Seed::from(SETTLEMENT_BATCH_SEED),
+        Seed::from(merchant_operator_config_info.key()),
+        Seed::from(mint_info.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        payer_info,
+        &rent,
+        space,
+        program_id,
+        settlement_batch_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let settlement_batch = SettlementBatch::new(
+        *merchant_operator_config_info.key(),
+        *mint_info.key(),
+        args.bump,
+        Clock::get()?.unix_timestamp,
+    );
+
+    let mut settlement_batch_data = settlement_batch_info.try_borrow_mut_data()?;
+    settlement_batch_data.copy_from_slice(&settlement_batch.to_bytes());
+
+    Ok(())
+}
+
+struct InitializeSettlementBatchArgs {
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<InitializeSettlementBatchArgs, ProgramError> {
+    require_len!(data, 1);
+    let bump = data[0];
+    Ok(InitializeSettlementBatchArgs { bump })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let data = [253u8];
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.bump, 253);
+    }
+
+    #[test]
+    fn test_process_instruction_data_empty() {
+        let result = process_instruction_data(&[]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
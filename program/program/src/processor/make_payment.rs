@@ -1,8 +1,22 @@
 extern crate alloc;
 
 use crate::{
-    events::{EventDiscriminators, PaymentCreatedEvent},
-    processor::{emit_event, verify_current_program, verify_mint_account, verify_token_program},
+    events::{
+        EventDiscriminators, PaymentClearedEvent, PaymentCreatedEvent, PaymentLifecycleStatus,
+        PlatformFeeCollectedEvent,
+    },
+    processor::{
+        apply_risk_policy, calculate_payment_fees, create_payment_note, denylist_proof_len,
+        emit_event, emit_order_status_changed, emit_payment_status_changed,
+        get_direct_settlement_policy, get_or_create_ata, get_or_create_operator_settlement_ata,
+        get_platform_fee_policy, get_split_settlement_policy, invoke_compliance_check,
+        parse_encrypted_note, split_buyer_index, split_compliance_accounts,
+        split_direct_settlement_atas, split_operator_freeze_list, split_order, split_payment_note,
+        split_quote, split_references, split_rent_payer, split_risk_states,
+        split_transfer_hook_accounts, update_buyer_index, update_order, validate_payment_order_id,
+        validate_quote, verify_ata_program, verify_current_program, verify_mint_account,
+        verify_owner, verify_token_interface_program, TokenTransfer,
+    },
     ID as COMMERCE_PROGRAM_ID,
 };
 use pinocchio::{
@@ -13,19 +27,21 @@ use pinocchio::{
     sysvars::{clock::Clock, rent::Rent, Sysvar},
     ProgramResult,
 };
-use pinocchio_token::instructions::Transfer;
 
 use crate::{
     constants::PAYMENT_SEED,
     error::CommerceProgramError,
     processor::{
-        create_pda_account, get_ata, validate_pda, verify_owner_mutability, verify_signer,
-        verify_system_account, verify_system_program, verify_token_program_account,
+        create_pda_account, derive_and_validate_pda, get_ata, parse_denylist_proof,
+        update_merchant_stats, validate_buyer_not_denylisted, validate_buyer_not_frozen,
+        validate_escrow_authority, validate_fee_override, validate_operator_authority,
+        verify_owner_mutability, verify_signer, verify_system_account, verify_system_program,
     },
     require_len,
     state::{
-        discriminator::AccountSerialize, Merchant, MerchantOperatorConfig, Operator, Payment,
-        PolicyData, PolicyType, Status,
+        DenylistProof, EncryptedNote, FeeOverride, FeeType, Merchant, MerchantOperatorConfig,
+        MerchantOperatorConfigView, Operator, OperatorPermission, Payment, PolicyData, PolicyType,
+        Status, ZeroCopy,
     },
 };
 
@@ -36,44 +52,116 @@ pub fn process_make_payment(
     instruction_data: &[u8],
 ) -> ProgramResult {
     let args = process_instruction_data(instruction_data)?;
-    let [fee_payer_info, payment_info, operator_authority_info, buyer_info, operator_info, merchant_info, merchant_operator_config_info, mint_info, buyer_ata_info, merchant_escrow_ata_info, merchant_settlement_ata_info, token_program_info, system_program_info, event_authority_info, commerce_program_info] =
+    let [fee_payer_info, payment_info, operator_authority_info, buyer_info, operator_info, merchant_info, merchant_operator_config_info, mint_info, buyer_ata_info, escrow_authority_info, merchant_escrow_ata_info, merchant_settlement_ata_info, settlement_wallet_info, token_program_info, associated_token_program_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    // The PaymentNote PDA, when a note is attached, leads the remaining accounts
+    let (payment_note_info, remaining_accounts) =
+        split_payment_note(args.note.as_ref(), remaining_accounts)?;
+
+    // The mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // Validate merchant_operator_config is owned by this program, and load it before the
+    // remaining accounts, some of which its policies call for
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+    let mut merchant_operator_config_data = merchant_operator_config_info.try_borrow_mut_data()?;
+    let merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&merchant_operator_config_data)?;
+    let policies = merchant_operator_config.get_policies(&merchant_operator_config_data)?;
+
+    // With a direct settlement policy, the operator's fee ATA and, under a platform fee
+    // policy, the platform's settlement ATA follow the transfer hook accounts
+    let platform_fee = get_platform_fee_policy(&policies);
+    let direct_settlement = get_direct_settlement_policy(&policies);
+    let (direct_settlement_atas, remaining_accounts) = match direct_settlement {
+        Some(_) => {
+            let (operator_fee_ata_info, platform_settlement_ata_info, remaining_accounts) =
+                split_direct_settlement_atas(remaining_accounts, platform_fee.is_some())?;
+            (
+                Some((operator_fee_ata_info, platform_settlement_ata_info)),
+                remaining_accounts,
+            )
+        }
+        None => (None, remaining_accounts),
+    };
+
+    // The compliance program and the accounts forwarded to it, if passed, come last, after
+    // the references
+    let (compliance, remaining_accounts) = split_compliance_accounts(remaining_accounts);
+
+    // Solana Pay references, if any, come after the optional Order and Quote
+    let (references, remaining_accounts) = split_references(remaining_accounts)?;
+
+    // The quote, if passed, trails the other remaining accounts after the Order
+    let (quote_info, remaining_accounts) = split_quote(remaining_accounts);
+
+    // The order, if passed, trails the other remaining accounts
+    let (order_info, remaining_accounts) = split_order(remaining_accounts);
+
+    // RiskState accounts, if any, trail the other remaining accounts before the Order
+    let (risk_state_infos, remaining_accounts) = split_risk_states(remaining_accounts);
+
+    // The buyer's BuyerIndex, if passed, trails the other remaining accounts before the
+    // RiskState accounts
+    let (buyer_index_info, remaining_accounts) = split_buyer_index(remaining_accounts);
+
+    // The operator's OperatorFreezeList, if passed, trails the other remaining accounts before
+    // the BuyerIndex
+    let (operator_freeze_list_info, remaining_accounts) =
+        split_operator_freeze_list(remaining_accounts);
+
+    // The operator's rent treasury, if passed, leads the remaining accounts ahead of the
+    // operator delegate and pays the Payment PDA's rent in place of the fee payer
+    let (rent_payer_info, remaining_accounts) = split_rent_payer(remaining_accounts);
+
     // Validate fee_payer is writable signer
     verify_signer(fee_payer_info, true)?;
 
+    // Validate rent_payer, if any, is writable signer
+    if let Some(rent_payer_info) = rent_payer_info {
+        verify_signer(rent_payer_info, true)?;
+    }
+
     // Validate: operator_authority should have signed
     verify_signer(operator_authority_info, false)?;
 
     // Validate: buyer should have signed
     verify_signer(buyer_info, false)?;
 
+    // Validate payment has not been created yet. A distinct error lets clients retrying with
+    // the same order id recognise the existing payment by its idempotency key.
+    if payment_info.is_owned_by(&COMMERCE_PROGRAM_ID) {
+        return Err(CommerceProgramError::PaymentAlreadyExists.into());
+    }
+
     // Validate payment is writable
     verify_system_account(payment_info, true)?;
 
     // Validate system program
     verify_system_program(system_program_info)?;
 
-    // Validate merchant_operator_config is owned by this program
-    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
-
     // validate operator is owned by the program
     verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
 
     // Validate merchant is owned by this program
     verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
 
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
     // Validate mint is owned by token program
-    verify_token_program_account(mint_info)?;
+    verify_owner(mint_info, token_program_info.key())?;
 
     // Validate mint is a valid mint
     verify_mint_account(mint_info)?;
 
-    // Validate token program
-    verify_token_program(token_program_info)?;
+    // Validate associated token program
+    verify_ata_program(associated_token_program_info)?;
 
     // Verify own program
     verify_current_program(commerce_program_info)?;
@@ -83,32 +171,90 @@ pub fn process_make_payment(
     let operator = Operator::try_from_bytes(&operator_data)?;
 
     operator.validate_pda(operator_info.key())?;
-    operator.validate_owner(operator_authority_info.key())?;
 
-    // Load and validate merchant_operator_config
-    let mut merchant_operator_config_data = merchant_operator_config_info.try_borrow_mut_data()?;
-    let (mut merchant_operator_config, policies, allowed_mints) =
-        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    // Validate operator_authority is the owner or a delegate allowed to make payments
+    let remaining_accounts = validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::MakePayment,
+    )?;
 
     // Load and validate merchant
     let merchant_data = merchant_info.try_borrow_data()?;
     let merchant = Merchant::try_from_bytes(&merchant_data)?;
 
+    merchant.validate_pda(merchant_info.key())?;
+
     // Validate merchant_operator_config PDA
     merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
 
     // Validate operator is the operator in the merchant_operator_config
     merchant_operator_config.validate_operator(operator_info.key())?;
-    merchant_operator_config.validate_order_id(args.order_id)?;
+    // Validate merchant is the merchant in the merchant_operator_config
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+    // A decommissioned config takes no new payments
+    merchant_operator_config.validate_not_decommissioned()?;
+    // Validate order id, against the order too if the payment belongs to one
+    validate_payment_order_id(
+        order_info,
+        &merchant_operator_config,
+        merchant_operator_config_info,
+        buyer_info,
+        args.order_id,
+    )?;
 
-    // Validate mint is in the allowed_mints
-    if !allowed_mints.contains(mint_info.key()) {
+    // Validate mint is an accepted currency
+    if !merchant_operator_config
+        .accepts_currency(&merchant_operator_config_data, mint_info.key())?
+    {
         return Err(CommerceProgramError::InvalidMint.into());
     }
 
-    // Validate Payment PDA
+    // Validate the fee override, if any, against the config's fee override policy
+    if let Some(fee_override) = &args.fee_override {
+        validate_fee_override(&policies, args.amount, fee_override)?;
+    }
+
+    // Validate the buyer is not on the config's denylist
+    validate_buyer_not_denylisted(&policies, buyer_info.key(), args.denylist_proof.as_ref())?;
+
+    // Validate the buyer is not on the operator's freeze list
+    validate_buyer_not_frozen(
+        operator_freeze_list_info,
+        operator_info.key(),
+        &operator,
+        buyer_info.key(),
+    )?;
+
+    // Have the config's compliance program, if any, approve the buyer and amount
+    invoke_compliance_check(&policies, compliance, buyer_info, mint_info, args.amount)?;
+
+    // Enforce the config's risk limits and count the payment towards them
+    let clock = Clock::get()?;
+    apply_risk_policy(
+        &policies,
+        risk_state_infos,
+        merchant_operator_config_info,
+        buyer_info,
+        args.amount,
+        clock.unix_timestamp,
+    )?;
+
+    // Validate the payment against its quote, if any
+    validate_quote(
+        quote_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        args.order_id,
+        args.amount,
+        clock.unix_timestamp,
+    )?;
+
+    // Validate Payment PDA, deriving its canonical bump
     let order_id_seed = args.order_id.to_le_bytes();
-    validate_pda(
+    let bump = derive_and_validate_pda(
         &[
             PAYMENT_SEED,
             merchant_operator_config_info.key().as_ref(),
@@ -117,7 +263,6 @@ pub fn process_make_payment(
             &order_id_seed,
         ],
         &Pubkey::from(*program_id),
-        args.bump,
         payment_info,
     )?;
 
@@ -140,44 +285,156 @@ pub fn process_make_payment(
         })
         .unwrap_or(false);
 
-    let (payment_status, transfer_to) = if auto_settle {
-        // Auto settlement: validate settlement ATA and transfer directly to settlement wallet
-        get_ata(
+    // Direct settlement applies to payments within the policy's cap, and pays the fees out now
+    let direct_settlement_atas = direct_settlement_atas
+        .filter(|_| direct_settlement.is_some_and(|policy| policy.applies_to(args.amount)));
+
+    // An authorization holds the funds in escrow until captured
+    if args.authorize && (auto_settle || direct_settlement_atas.is_some()) {
+        return Err(CommerceProgramError::AuthorizationRequiresEscrow.into());
+    }
+
+    // A split settlement's destinations are only paid by ClearPayment
+    if direct_settlement_atas.is_some() && get_split_settlement_policy(&policies).is_some() {
+        return Err(CommerceProgramError::DirectSettlementUnsupported.into());
+    }
+
+    let (payment_status, transfer_to) = if auto_settle || direct_settlement_atas.is_some() {
+        // Auto or direct settlement: validate settlement wallet, create its ATA if missing
+        // and transfer directly to it
+        merchant.validate_settlement_wallet(settlement_wallet_info.key())?;
+        get_or_create_ata(
             merchant_settlement_ata_info,
-            &merchant.settlement_wallet,
+            settlement_wallet_info,
             mint_info,
+            fee_payer_info,
+            system_program_info,
             token_program_info,
         )?;
 
-        // Payment is auto-settled
+        // Payment is settled already
         (Status::Cleared, merchant_settlement_ata_info)
     } else {
-        // No auto settlement: validate escrow ATA and transfer to escrow
-        get_ata(
+        // No auto settlement: validate the escrow authority PDA, create the escrow ATA it owns
+        // if missing and transfer to escrow
+        validate_escrow_authority(
+            escrow_authority_info,
+            merchant_operator_config_info.key(),
+            mint_info.key(),
+        )?;
+        get_or_create_ata(
             merchant_escrow_ata_info,
-            merchant_info.key(),
+            escrow_authority_info,
             mint_info,
+            fee_payer_info,
+            system_program_info,
             token_program_info,
         )?;
 
-        // Payment remains Paid - needs manual clearing
-        (Status::Paid, merchant_escrow_ata_info)
+        // Payment remains Paid - needs manual clearing - or Authorized until captured
+        let status = if args.authorize {
+            Status::Authorized
+        } else {
+            Status::Paid
+        };
+        (status, merchant_escrow_ata_info)
     };
 
-    // Transfer tokens to the destination ATA
-    Transfer {
-        from: buyer_ata_info,
-        to: transfer_to,
-        authority: buyer_info,
+    let payment = Payment {
+        order_id: args.order_id,
         amount: args.amount,
-    }
-    .invoke()?;
+        created_at: clock.unix_timestamp,
+        status: payment_status,
+        bump,
+        idempotency_key: args.idempotency_key,
+        has_fee_override: args.fee_override.is_some(),
+        fee_override: args.fee_override.unwrap_or(FeeOverride::EMPTY),
+        references,
+        rent_payer: rent_payer_info.map_or(Pubkey::default(), |info| *info.key()),
+        has_order: order_info.is_some(),
+    };
+
+    let token_transfer = TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?;
+
+    // Skim the fees off the payment when settling it directly, as ClearPayment would
+    let (operator_fee_amount, platform_fee_amount) = match direct_settlement_atas {
+        Some((operator_fee_ata_info, platform_settlement_ata_info)) => {
+            let (operator_fee_amount, merchant_amount) = calculate_payment_fees(
+                &payment,
+                &merchant_operator_config,
+                &policies,
+                clock.unix_timestamp,
+            )?;
+
+            // The platform's fee comes out of the merchant amount, after the operator's
+            let platform_fee_amount = platform_fee
+                .map(|platform_fee| platform_fee.fee_amount(payment.amount, merchant_amount))
+                .unwrap_or(0);
+            let merchant_amount = merchant_amount
+                .checked_sub(platform_fee_amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            if operator_fee_amount > 0 {
+                // Validate operator fee ATA (owned by operator owner), creating it if missing
+                get_or_create_operator_settlement_ata(
+                    &operator,
+                    operator_fee_ata_info,
+                    operator_authority_info,
+                    mint_info,
+                    fee_payer_info,
+                    system_program_info,
+                    token_program_info,
+                )?;
+                token_transfer.transfer(
+                    buyer_ata_info,
+                    operator_fee_ata_info,
+                    buyer_info,
+                    operator_fee_amount,
+                    &[],
+                )?;
+            }
+
+            if let (Some(platform_fee), Some(platform_settlement_ata_info)) =
+                (platform_fee, platform_settlement_ata_info)
+            {
+                // Validate the platform settlement ATA (owned by the policy's platform)
+                get_ata(
+                    platform_settlement_ata_info,
+                    &platform_fee.platform,
+                    mint_info,
+                    token_program_info,
+                )?;
+                if platform_fee_amount > 0 {
+                    token_transfer.transfer(
+                        buyer_ata_info,
+                        platform_settlement_ata_info,
+                        buyer_info,
+                        platform_fee_amount,
+                        &[],
+                    )?;
+                }
+            }
+
+            token_transfer.transfer(
+                buyer_ata_info,
+                transfer_to,
+                buyer_info,
+                merchant_amount,
+                &[],
+            )?;
+            (operator_fee_amount, platform_fee_amount)
+        }
+        None => {
+            // Transfer tokens to the destination ATA
+            token_transfer.transfer(buyer_ata_info, transfer_to, buyer_info, args.amount, &[])?;
+            (0, 0)
+        }
+    };
 
     // Create payment PDA
     let space = Payment::LEN;
     let rent = Rent::get()?;
-    let clock = Clock::get()?;
-    let bump_seed = [args.bump];
+    let bump_seed = [bump];
     let signer_seeds = [
         Seed::from(PAYMENT_SEED),
         Seed::from(merchant_operator_config_info.key()),
@@ -188,7 +445,7 @@ pub fn process_make_payment(
     ];
 
     create_pda_account(
-        fee_payer_info,
+        rent_payer_info.unwrap_or(fee_payer_info),
         &rent,
         space,
         program_id,
@@ -197,26 +454,75 @@ pub fn process_make_payment(
         None,
     )?;
 
-    let payment = Payment {
-        order_id: args.order_id,
-        amount: args.amount,
-        created_at: clock.unix_timestamp,
-        status: payment_status,
-        bump: args.bump,
-    };
-
     // Save payment data
     let mut payment_data = payment_info.try_borrow_mut_data()?;
-    payment_data.copy_from_slice(&payment.to_bytes());
+    payment.store(&mut payment_data)?;
+
+    // Attach the encrypted note, if any, in its own PDA funded like the payment
+    if let (Some(note), Some(payment_note_info)) = (&args.note, payment_note_info) {
+        create_payment_note(
+            payment_note_info,
+            payment_info,
+            merchant_info,
+            rent_payer_info.unwrap_or(fee_payer_info),
+            &rent,
+            note,
+        )?;
+    }
 
-    // Update current order id
-    merchant_operator_config.current_order_id = merchant_operator_config
+    // Update current order id and count the payment account in place
+    let next_order_id = merchant_operator_config
         .current_order_id
         .checked_add(1)
         .ok_or(ProgramError::ArithmeticOverflow)?;
+    let payment_accounts = merchant_operator_config
+        .payment_accounts
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let merchant_operator_config_view =
+        MerchantOperatorConfigView::load_mut(&mut merchant_operator_config_data)?;
+    merchant_operator_config_view.set_current_order_id(next_order_id);
+    merchant_operator_config_view.set_payment_accounts(payment_accounts);
+
+    // Count the payment as open while its funds are held in escrow
+    if payment.status != Status::Cleared {
+        merchant_operator_config
+            .increment_open_payments(&mut merchant_operator_config_data, mint_info.key())?;
+    }
 
-    merchant_operator_config_data
-        .copy_from_slice(&merchant_operator_config.to_bytes(&policies, &allowed_mints));
+    // Update optional merchant stats. An authorization is counted once captured.
+    let fees_amount = operator_fee_amount
+        .checked_add(platform_fee_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+        if !args.authorize {
+            stats.record_payment(args.amount);
+        }
+        if payment.status == Status::Cleared {
+            stats.record_clear(args.amount, fees_amount);
+        }
+    })?;
+
+    // Record the payment on the buyer's index, if the config keeps one
+    update_buyer_index(
+        &policies,
+        buyer_index_info,
+        merchant_operator_config_info,
+        buyer_info,
+        args.order_id,
+    )?;
+
+    // Record the payment on its order, if any
+    let order_status = match order_info {
+        Some(order_info) => update_order(
+            order_info,
+            merchant_operator_config_info,
+            buyer_info,
+            args.order_id,
+            |order| order.record_payment(&payment.status),
+        )?,
+        None => None,
+    };
 
     // Emit payment created event
     let event = PaymentCreatedEvent {
@@ -226,13 +532,78 @@ pub fn process_make_payment(
         operator: *operator_info.key(),
         amount: args.amount,
         order_id: args.order_id,
+        idempotency_key: args.idempotency_key,
     };
 
     emit_event(
         program_id,
         event_authority_info,
         commerce_program_info,
-        &event.to_bytes(),
+        &event.to_array(),
+    )?;
+
+    // A directly settled payment is cleared too, with the fees it paid
+    if direct_settlement_atas.is_some() {
+        let cleared = PaymentClearedEvent {
+            discriminator: EventDiscriminators::PaymentCleared as u8,
+            buyer: event.buyer,
+            merchant: event.merchant,
+            operator: event.operator,
+            amount: args.amount,
+            operator_fee: operator_fee_amount,
+            order_id: args.order_id,
+            idempotency_key: args.idempotency_key,
+        };
+
+        emit_event(
+            program_id,
+            event_authority_info,
+            commerce_program_info,
+            &cleared.to_array(),
+        )?;
+    }
+
+    if let Some(platform_fee) = platform_fee.filter(|_| platform_fee_amount > 0) {
+        let platform_fee_event = PlatformFeeCollectedEvent {
+            discriminator: EventDiscriminators::PlatformFeeCollected as u8,
+            buyer: event.buyer,
+            merchant: event.merchant,
+            operator: event.operator,
+            platform: platform_fee.platform,
+            order_id: args.order_id,
+            amount: platform_fee_amount,
+        };
+
+        emit_event(
+            program_id,
+            event_authority_info,
+            commerce_program_info,
+            &platform_fee_event.to_array(),
+        )?;
+    }
+
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Uninitialized,
+        (&payment.status).into(),
+        buyer_info.key(),
+    )?;
+
+    emit_order_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        buyer_info.key(),
+        merchant_info.key(),
+        operator_info.key(),
+        args.order_id,
+        order_status,
     )?;
 
     Ok(())
@@ -241,11 +612,17 @@ pub fn process_make_payment(
 struct MakePaymentArgs {
     order_id: u32,
     amount: u64,
-    bump: u8,
+    idempotency_key: [u8; 16],
+    fee_override: Option<FeeOverride>,
+    authorize: bool,
+    denylist_proof: Option<DenylistProof>,
+    note: Option<EncryptedNote>,
 }
 
 fn process_instruction_data(data: &[u8]) -> Result<MakePaymentArgs, ProgramError> {
-    require_len!(data, 13); // 4 + 8 + 1
+    // 4 + 8 + 16 + 1 (fee_override tag) + 1 (authorize) + 1 (denylist_proof tag)
+    // + 1 (note tag)
+    require_len!(data, 32);
     let mut offset = 0;
 
     let order_id = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
@@ -254,18 +631,55 @@ fn process_instruction_data(data: &[u8]) -> Result<MakePaymentArgs, ProgramError
     let amount = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
     offset += 8;
 
-    let bump = data[offset];
+    let idempotency_key: [u8; 16] = data[offset..offset + 16].try_into().unwrap();
+    offset += 16;
+
+    // Borsh Option: 0 for None, 1 followed by fee (u64) and fee_type (u8) for Some
+    let fee_override = match data[offset] {
+        0 => {
+            offset += 1;
+            None
+        }
+        1 => {
+            offset += 1;
+            require_len!(data, offset + 9);
+            let fee = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+            let fee_type = FeeType::from_u8(data[offset + 8])
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+            offset += 9;
+            Some(FeeOverride { fee, fee_type })
+        }
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+
+    require_len!(data, offset + 1);
+    let authorize = match data[offset] {
+        0 => false,
+        1 => true,
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+    offset += 1;
+
+    let denylist_proof = parse_denylist_proof(&data[offset..])?;
+    offset += denylist_proof_len(denylist_proof.as_ref());
+
+    let note = parse_encrypted_note(&data[offset..])?;
 
     Ok(MakePaymentArgs {
         order_id,
         amount,
-        bump,
+        idempotency_key,
+        fee_override,
+        authorize,
+        denylist_proof,
+        note,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::PAYMENT_NOTE_CIPHERTEXT_LEN;
     use alloc::vec;
 
     #[test]
@@ -273,12 +687,161 @@ mod tests {
         let mut data = vec![];
         data.extend_from_slice(&12345u32.to_le_bytes());
         data.extend_from_slice(&1000000u64.to_le_bytes());
-        data.push(254u8);
+        data.extend_from_slice(&[7u8; 16]);
+        data.push(0);
+        data.push(0);
+        data.push(0);
+        data.push(0);
 
         let args = process_instruction_data(&data).unwrap();
         assert_eq!(args.order_id, 12345);
         assert_eq!(args.amount, 1000000);
-        assert_eq!(args.bump, 254);
+        assert_eq!(args.idempotency_key, [7u8; 16]);
+        assert_eq!(args.fee_override, None);
+        assert!(!args.authorize);
+        assert_eq!(args.denylist_proof, None);
+        assert_eq!(args.note, None);
+    }
+
+    #[test]
+    fn test_process_instruction_data_fee_override() {
+        let mut data = vec![];
+        data.extend_from_slice(&12345u32.to_le_bytes());
+        data.extend_from_slice(&1000000u64.to_le_bytes());
+        data.extend_from_slice(&[7u8; 16]);
+        data.push(1);
+        data.extend_from_slice(&150u64.to_le_bytes());
+        data.push(FeeType::Fixed.to_u8());
+        data.push(0);
+        data.push(0);
+        data.push(0);
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(
+            args.fee_override,
+            Some(FeeOverride {
+                fee: 150,
+                fee_type: FeeType::Fixed,
+            })
+        );
+        assert_eq!(args.denylist_proof, None);
+
+        // Missing note tag
+        assert!(process_instruction_data(&data[..data.len() - 1]).is_err());
+
+        // Missing denylist proof tag
+        assert!(process_instruction_data(&data[..data.len() - 2]).is_err());
+
+        // Truncated override
+        assert!(process_instruction_data(&data[..data.len() - 4]).is_err());
+
+        // Unknown fee type
+        let fee_type = data.len() - 4;
+        data[fee_type] = 2;
+        assert_eq!(
+            process_instruction_data(&data).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+
+        // Unknown option tag
+        data.truncate(28);
+        data.push(2);
+        assert_eq!(
+            process_instruction_data(&data).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_process_instruction_data_denylist_proof() {
+        let mut data = vec![];
+        data.extend_from_slice(&12345u32.to_le_bytes());
+        data.extend_from_slice(&1000000u64.to_le_bytes());
+        data.extend_from_slice(&[7u8; 16]);
+        data.push(0);
+        data.push(0);
+        data.push(1);
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&[3u8; 32]);
+        data.extend_from_slice(&5u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[9u8; 32]);
+        data.push(0);
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.fee_override, None);
+        assert_eq!(
+            args.denylist_proof,
+            Some(DenylistProof {
+                lower: [1u8; 32],
+                upper: [3u8; 32],
+                leaf_index: 5,
+                proof: vec![[9u8; 32]],
+            })
+        );
+
+        assert_eq!(args.note, None);
+
+        // Truncated proof
+        assert_eq!(
+            process_instruction_data(&data[..data.len() - 2]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_process_instruction_data_note() {
+        let mut data = vec![];
+        data.extend_from_slice(&12345u32.to_le_bytes());
+        data.extend_from_slice(&1000000u64.to_le_bytes());
+        data.extend_from_slice(&[7u8; 16]);
+        data.push(0);
+        data.push(0);
+        data.push(1);
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&[3u8; 32]);
+        data.extend_from_slice(&5u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&[9u8; PAYMENT_NOTE_CIPHERTEXT_LEN]);
+
+        let args = process_instruction_data(&data).unwrap();
+        assert!(args.denylist_proof.is_some());
+        assert_eq!(
+            args.note,
+            Some(EncryptedNote {
+                ciphertext: [9u8; PAYMENT_NOTE_CIPHERTEXT_LEN],
+            })
+        );
+
+        // Truncated ciphertext
+        assert_eq!(
+            process_instruction_data(&data[..data.len() - 1]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_process_instruction_data_authorize() {
+        let mut data = vec![];
+        data.extend_from_slice(&12345u32.to_le_bytes());
+        data.extend_from_slice(&1000000u64.to_le_bytes());
+        data.extend_from_slice(&[7u8; 16]);
+        data.push(0);
+        data.push(1);
+        data.push(0);
+        data.push(0);
+
+        let args = process_instruction_data(&data).unwrap();
+        assert!(args.authorize);
+        assert_eq!(args.denylist_proof, None);
+
+        // Not a bool
+        data[29] = 2;
+        assert_eq!(
+            process_instruction_data(&data).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
     }
 
     #[test]
@@ -286,27 +849,33 @@ mod tests {
         let mut data = vec![];
         data.extend_from_slice(&0u32.to_le_bytes());
         data.extend_from_slice(&0u64.to_le_bytes());
-        data.push(0u8);
+        data.extend_from_slice(&[0u8; 16]);
+        data.push(0);
+        data.push(0);
+        data.push(0);
+        data.push(0);
 
         let args = process_instruction_data(&data).unwrap();
         assert_eq!(args.order_id, 0);
         assert_eq!(args.amount, 0);
-        assert_eq!(args.bump, 0);
 
         let mut data = vec![];
         data.extend_from_slice(&u32::MAX.to_le_bytes());
         data.extend_from_slice(&u64::MAX.to_le_bytes());
-        data.push(u8::MAX);
+        data.extend_from_slice(&[u8::MAX; 16]);
+        data.push(0);
+        data.push(0);
+        data.push(0);
+        data.push(0);
 
         let args = process_instruction_data(&data).unwrap();
         assert_eq!(args.order_id, u32::MAX);
         assert_eq!(args.amount, u64::MAX);
-        assert_eq!(args.bump, u8::MAX);
     }
 
     #[test]
     fn test_process_instruction_data_invalid_length() {
-        let data = vec![1u8; 12]; // Too short
+        let data = vec![0u8; 31]; // Too short
         let result = process_instruction_data(&data);
         assert!(result.is_err());
     }
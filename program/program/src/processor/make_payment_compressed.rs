@@ -0,0 +1,346 @@
+extern crate alloc;
+
+use crate::{
+    events::{CompressedPaymentEvent, EventDiscriminators, PaymentLifecycleStatus},
+    processor::{
+        emit_event, emit_payment_status_changed, verify_current_program, verify_mint_account,
+        verify_owner, verify_token_interface_program,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::{
+        apply_risk_policy, get_ata, get_direct_settlement_policy, invoke_compliance_check,
+        parse_denylist_proof, split_compliance_accounts, split_operator_freeze_list,
+        split_risk_states, split_transfer_hook_accounts, validate_buyer_not_denylisted,
+        validate_buyer_not_frozen, validate_escrow_authority, validate_operator_authority,
+        verify_owner_mutability, verify_signer, TokenTransfer,
+    },
+    require_len,
+    state::{
+        discriminator::AccountSerialize, CompressedPayment, DenylistProof, Merchant,
+        MerchantOperatorConfig, Operator, OperatorPermission, PaymentTree, PolicyData, PolicyType,
+        Status,
+    },
+};
+
+#[inline(always)]
+pub fn process_make_payment_compressed(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payment_tree_info, operator_authority_info, buyer_info, operator_info, merchant_info, merchant_operator_config_info, mint_info, buyer_ata_info, escrow_authority_info, merchant_escrow_ata_info, merchant_settlement_ata_info, token_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // The compliance program and the accounts forwarded to it, if passed, come last
+    let (compliance, remaining_accounts) = split_compliance_accounts(remaining_accounts);
+
+    // RiskState accounts, if any, trail the other remaining accounts
+    let (risk_state_infos, remaining_accounts) = split_risk_states(remaining_accounts);
+
+    // The operator's OperatorFreezeList, if passed, trails the other remaining accounts before
+    // the RiskState accounts
+    let (operator_freeze_list_info, remaining_accounts) =
+        split_operator_freeze_list(remaining_accounts);
+
+    // Validate: operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate: buyer should have signed
+    verify_signer(buyer_info, false)?;
+
+    // Validate payment_tree is writable and owned by this program
+    verify_owner_mutability(payment_tree_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate merchant_operator_config is owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate operator is owned by the program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Validate mint is a valid mint
+    verify_mint_account(mint_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to make payments
+    validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::MakePayment,
+    )?;
+
+    // Load and validate merchant_operator_config
+    let mut merchant_operator_config_data = merchant_operator_config_info.try_borrow_mut_data()?;
+    let (mut merchant_operator_config, policies, allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+
+    // Validate merchant_operator_config PDA
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate operator and merchant match the config
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+    merchant_operator_config.validate_not_decommissioned()?;
+    merchant_operator_config.validate_order_id(args.order_id)?;
+
+    // Validate mint is in the allowed_mints
+    if !allowed_mints.contains(mint_info.key()) {
+        return Err(CommerceProgramError::InvalidMint.into());
+    }
+
+    // Validate the buyer is not on the config's denylist
+    validate_buyer_not_denylisted(&policies, buyer_info.key(), args.denylist_proof.as_ref())?;
+
+    // Validate the buyer is not on the operator's freeze list
+    validate_buyer_not_frozen(
+        operator_freeze_list_info,
+        operator_info.key(),
+        &operator,
+        buyer_info.key(),
+    )?;
+
+    // Have the config's compliance program, if any, approve the buyer and amount
+    invoke_compliance_check(&policies, compliance, buyer_info, mint_info, args.amount)?;
+
+    // Enforce the config's risk limits and count the payment towards them
+    let created_at = Clock::get()?.unix_timestamp;
+    apply_risk_policy(
+        &policies,
+        risk_state_infos,
+        merchant_operator_config_info,
+        buyer_info,
+        args.amount,
+        created_at,
+    )?;
+
+    // Compressed payments have no fee ATAs to settle directly to, so they are only escrowed
+    if get_direct_settlement_policy(&policies).is_some_and(|policy| policy.applies_to(args.amount))
+    {
+        return Err(CommerceProgramError::DirectSettlementUnsupported.into());
+    }
+
+    // Load and validate payment tree
+    let mut payment_tree_data = payment_tree_info.try_borrow_mut_data()?;
+    let mut payment_tree = PaymentTree::try_from_bytes(&payment_tree_data)?;
+    payment_tree.validate_pda(payment_tree_info.key())?;
+    payment_tree.validate_merchant_operator_config(merchant_operator_config_info.key())?;
+
+    // Validate buyer ATA
+    get_ata(
+        buyer_ata_info,
+        buyer_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Check if auto settlement is enabled
+    let auto_settle = MerchantOperatorConfig::get_policy_by_type(&policies, PolicyType::Settlement)
+        .map(|policy| {
+            if let PolicyData::Settlement(settlement) = policy {
+                settlement.auto_settle
+            } else {
+                false
+            }
+        })
+        .unwrap_or(false);
+
+    let (payment_status, transfer_to) = if auto_settle {
+        // Auto settlement: validate settlement ATA and transfer directly to settlement wallet
+        get_ata(
+            merchant_settlement_ata_info,
+            &merchant.settlement_wallet,
+            mint_info,
+            token_program_info,
+        )?;
+
+        // Payment is auto-settled
+        (Status::Cleared, merchant_settlement_ata_info)
+    } else {
+        // No auto settlement: validate the escrow authority PDA and the escrow ATA it owns,
+        // and transfer to escrow
+        validate_escrow_authority(
+            escrow_authority_info,
+            merchant_operator_config_info.key(),
+            mint_info.key(),
+        )?;
+        get_ata(
+            merchant_escrow_ata_info,
+            escrow_authority_info.key(),
+            mint_info,
+            token_program_info,
+        )?;
+
+        // Payment remains Paid - needs manual clearing
+        (Status::Paid, merchant_escrow_ata_info)
+    };
+
+    // Transfer tokens to the destination ATA
+    TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+        buyer_ata_info,
+        transfer_to,
+        buyer_info,
+        args.amount,
+        &[],
+    )?;
+
+    // Append payment leaf to the tree
+    let payment = CompressedPayment {
+        buyer: *buyer_info.key(),
+        mint: *mint_info.key(),
+        order_id: args.order_id,
+        amount: args.amount,
+        created_at,
+        status: payment_status,
+    };
+
+    let leaf_index = payment_tree.append(payment.leaf_hash())?;
+
+    // Save payment tree data
+    payment_tree_data.copy_from_slice(&payment_tree.to_bytes());
+
+    // Update current order id
+    merchant_operator_config.current_order_id = merchant_operator_config
+        .current_order_id
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    merchant_operator_config.store(&mut merchant_operator_config_data)?;
+
+    // Count the payment as open while its funds are held in escrow
+    if payment.status != Status::Cleared {
+        merchant_operator_config
+            .increment_open_payments(&mut merchant_operator_config_data, mint_info.key())?;
+    }
+
+    let status = PaymentLifecycleStatus::from(&payment.status);
+
+    // Emit compressed payment event so indexers can rebuild the leaf
+    let event = CompressedPaymentEvent {
+        discriminator: EventDiscriminators::CompressedPayment as u8,
+        payment_tree: *payment_tree_info.key(),
+        leaf_index,
+        buyer: payment.buyer,
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        mint: payment.mint,
+        amount: payment.amount,
+        operator_fee: 0,
+        order_id: payment.order_id,
+        created_at: payment.created_at,
+        status: payment.status as u8,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_bytes(),
+    )?;
+
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Uninitialized,
+        status,
+        buyer_info.key(),
+    )?;
+
+    Ok(())
+}
+
+struct MakePaymentCompressedArgs {
+    order_id: u32,
+    amount: u64,
+    denylist_proof: Option<DenylistProof>,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<MakePaymentCompressedArgs, ProgramError> {
+    require_len!(data, 13); // 4 + 8 + 1 (denylist_proof tag)
+    let mut offset = 0;
+
+    let order_id = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    offset += 4;
+
+    let amount = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    offset += 8;
+
+    let denylist_proof = parse_denylist_proof(&data[offset..])?;
+
+    Ok(MakePaymentCompressedArgs {
+        order_id,
+        amount,
+        denylist_proof,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = vec![];
+        data.extend_from_slice(&12345u32.to_le_bytes());
+        data.extend_from_slice(&1000000u64.to_le_bytes());
+        data.push(0);
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.order_id, 12345);
+        assert_eq!(args.amount, 1000000);
+        assert_eq!(args.denylist_proof, None);
+    }
+
+    #[test]
+    fn test_process_instruction_data_invalid_length() {
+        let data = vec![0u8; 12]; // Too short
+        let result = process_instruction_data(&data);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    constants::MERCHANT_SEED,
+    error::CommerceProgramError,
+    processor::{
+        get_ata, get_or_create_ata, split_transfer_hook_accounts, validate_escrow_authority,
+        verify_ata_program, verify_owner, verify_owner_mutability, verify_signer,
+        verify_system_program, verify_token_interface_program, TokenTransfer,
+    },
+    require_len,
+    state::{Merchant, MerchantOperatorConfig, Operator},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Moves escrowed funds of a config from the merchant PDA's legacy escrow ATA to the config's
+/// escrow ATA, owned by its escrow authority PDA.
+///
+/// The legacy escrow is shared by every config of the merchant, so the merchant and operator
+/// owners both sign for the amount that belongs to this config.
+#[inline(always)]
+pub fn process_migrate_escrow(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, merchant_authority_info, operator_authority_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, legacy_escrow_ata_info, escrow_authority_info, merchant_escrow_ata_info, token_program_info, associated_token_program_info, system_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, are the remaining accounts
+    let (transfer_hook_infos, _) = split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: merchant and operator authorities should have signed
+    verify_signer(merchant_authority_info, false)?;
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate merchant, operator and merchant_operator_config are owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Verify system program
+    verify_system_program(system_program_info)?;
+
+    // Validate associated token program
+    verify_ata_program(associated_token_program_info)?;
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_owner(merchant_authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_owner(operator_authority_info.key())?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Load and validate merchant_operator_config belongs to both and accepts the mint
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    if !merchant_operator_config
+        .accepts_currency(&merchant_operator_config_data, mint_info.key())?
+    {
+        return Err(CommerceProgramError::InvalidMint.into());
+    }
+
+    // Validate the legacy escrow ATA (owned by merchant pda)
+    get_ata(
+        legacy_escrow_ata_info,
+        merchant_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate the escrow authority PDA, creating the escrow ATA it owns if missing
+    validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    get_or_create_ata(
+        merchant_escrow_ata_info,
+        escrow_authority_info,
+        mint_info,
+        payer_info,
+        system_program_info,
+        token_program_info,
+    )?;
+
+    // Use the merchant PDA, the legacy escrow's owner, as authority for the transfer
+    let bump_seed = [merchant.bump];
+    let signer_seeds = [
+        Seed::from(MERCHANT_SEED),
+        Seed::from(merchant.original_owner.as_ref()),
+        Seed::from(&bump_seed),
+    ];
+
+    TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+        legacy_escrow_ata_info,
+        merchant_escrow_ata_info,
+        merchant_info,
+        args.amount,
+        &[Signer::from(&signer_seeds)],
+    )
+}
+
+struct MigrateEscrowArgs {
+    amount: u64,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<MigrateEscrowArgs, ProgramError> {
+    require_len!(data, 8);
+
+    let amount = u64::from_le_bytes(data[..8].try_into().unwrap());
+    if amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(MigrateEscrowArgs { amount })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let args = process_instruction_data(&1_000_000u64.to_le_bytes()).unwrap();
+        assert_eq!(args.amount, 1_000_000);
+    }
+
+    #[test]
+    fn test_process_instruction_data_zero_amount() {
+        let result = process_instruction_data(&0u64.to_le_bytes());
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[1, 0, 0]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
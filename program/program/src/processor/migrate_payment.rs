@@ -0,0 +1,271 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::PAYMENT_SEED,
+    error::CommerceProgramError,
+    events::{EventDiscriminators, PaymentMigratedEvent},
+    processor::{
+        close_payment_account, create_pda_account, derive_and_validate_pda, emit_event,
+        escrow_authority_seeds, get_ata, get_or_create_ata, release_open_payments,
+        release_payment_accounts, split_transfer_hook_accounts, validate_escrow_authority,
+        verify_ata_program, verify_current_program, verify_owner, verify_owner_mutability,
+        verify_signer, verify_system_program, verify_token_interface_program, TokenTransfer,
+    },
+    state::{
+        Merchant, MerchantOperatorConfig, MerchantOperatorConfigView, Operator, Payment, Status,
+        ZeroCopy,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Moves a Paid payment from one config of its merchant and operator to another, e.g. a new
+/// config version, so upgrading a config does not strand payments that are still in flight.
+///
+/// The escrowed amount moves to the new config's escrow ATA and the payment is re-created at
+/// its PDA under the new config, funded by the old account's rent. Orders, buyer indexes and
+/// payment notes of the payment stay with the old config.
+#[inline(always)]
+pub fn process_migrate_payment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [payer_info, merchant_authority_info, operator_authority_info, merchant_info, operator_info, buyer_info, mint_info, old_merchant_operator_config_info, new_merchant_operator_config_info, old_payment_info, new_payment_info, old_escrow_authority_info, old_merchant_escrow_ata_info, new_escrow_authority_info, new_merchant_escrow_ata_info, token_program_info, associated_token_program_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, are the remaining accounts
+    let (transfer_hook_infos, _) = split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: merchant and operator authorities should have signed
+    verify_signer(merchant_authority_info, false)?;
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate merchant, operator and both configs are owned by this program, the configs
+    // writable for their open-payment counts
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(
+        old_merchant_operator_config_info,
+        &COMMERCE_PROGRAM_ID,
+        true,
+    )?;
+    verify_owner_mutability(
+        new_merchant_operator_config_info,
+        &COMMERCE_PROGRAM_ID,
+        true,
+    )?;
+
+    // Validate old payment is writable and owned by this program
+    verify_owner_mutability(old_payment_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Verify system program
+    verify_system_program(system_program_info)?;
+
+    // Validate associated token program
+    verify_ata_program(associated_token_program_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    if old_merchant_operator_config_info.key() == new_merchant_operator_config_info.key() {
+        return Err(CommerceProgramError::MigrationTargetUnchanged.into());
+    }
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_owner(merchant_authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_owner(operator_authority_info.key())?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Load and validate both configs belong to the merchant and operator
+    let old_merchant_operator_config_data = old_merchant_operator_config_info.try_borrow_data()?;
+    let old_merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&old_merchant_operator_config_data)?;
+    old_merchant_operator_config.validate_pda(old_merchant_operator_config_info.key())?;
+    old_merchant_operator_config.validate_merchant(merchant_info.key())?;
+    old_merchant_operator_config.validate_operator(operator_info.key())?;
+
+    let mut new_merchant_operator_config_data =
+        new_merchant_operator_config_info.try_borrow_mut_data()?;
+    let new_merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&new_merchant_operator_config_data)?;
+    new_merchant_operator_config.validate_pda(new_merchant_operator_config_info.key())?;
+    new_merchant_operator_config.validate_merchant(merchant_info.key())?;
+    new_merchant_operator_config.validate_operator(operator_info.key())?;
+    // Payments may move off a decommissioned config but not onto one
+    new_merchant_operator_config.validate_not_decommissioned()?;
+
+    // Validate the new config accepts the payment's mint
+    if !new_merchant_operator_config
+        .accepts_currency(&new_merchant_operator_config_data, mint_info.key())?
+    {
+        return Err(CommerceProgramError::InvalidMint.into());
+    }
+
+    // Load and validate the old payment, which must still be Paid with its amount in escrow
+    let payment = Payment::try_from_bytes(&old_payment_info.try_borrow_data()?)?;
+    payment.validate_status(Status::Paid)?;
+    payment.validate_pda(
+        old_payment_info.key(),
+        old_merchant_operator_config_info.key(),
+        buyer_info.key(),
+        mint_info.key(),
+    )?;
+
+    // The payment's Order is derived from the old config and stays with it
+    if payment.has_order {
+        return Err(CommerceProgramError::PaymentInOrder.into());
+    }
+
+    // Validate the new Payment PDA, deriving its canonical bump
+    let order_id_seed = payment.order_id.to_le_bytes();
+    let new_payment_bump = derive_and_validate_pda(
+        &[
+            PAYMENT_SEED,
+            new_merchant_operator_config_info.key().as_ref(),
+            buyer_info.key().as_ref(),
+            mint_info.key().as_ref(),
+            &order_id_seed,
+        ],
+        program_id,
+        new_payment_info,
+    )?;
+
+    // Validate the old escrow authority PDA and the escrow ATA it owns
+    let old_escrow_authority_bump = validate_escrow_authority(
+        old_escrow_authority_info,
+        old_merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    get_ata(
+        old_merchant_escrow_ata_info,
+        old_escrow_authority_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate the new escrow authority PDA, creating the escrow ATA it owns if missing
+    validate_escrow_authority(
+        new_escrow_authority_info,
+        new_merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
+    get_or_create_ata(
+        new_merchant_escrow_ata_info,
+        new_escrow_authority_info,
+        mint_info,
+        payer_info,
+        system_program_info,
+        token_program_info,
+    )?;
+
+    // Use the old escrow authority PDA as authority for the transfer
+    let bump_seed = [old_escrow_authority_bump];
+    let signer_seeds = escrow_authority_seeds(
+        old_merchant_operator_config_info.key(),
+        mint_info.key(),
+        &bump_seed,
+    );
+
+    TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+        old_merchant_escrow_ata_info,
+        new_merchant_escrow_ata_info,
+        old_escrow_authority_info,
+        payment.amount,
+        &[Signer::from(&signer_seeds)],
+    )?;
+
+    // The payment and its account are counted under the new config rather than the old one
+    drop(old_merchant_operator_config_data);
+    release_open_payments(old_merchant_operator_config_info, mint_info.key(), 1)?;
+    release_payment_accounts(old_merchant_operator_config_info, 1)?;
+    new_merchant_operator_config
+        .increment_open_payments(&mut new_merchant_operator_config_data, mint_info.key())?;
+    let payment_accounts = new_merchant_operator_config
+        .payment_accounts
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let new_merchant_operator_config_view =
+        MerchantOperatorConfigView::load_mut(&mut new_merchant_operator_config_data)?;
+    new_merchant_operator_config_view.set_payment_accounts(payment_accounts);
+    // The new config's current order id catches up with the migrated payment's order id
+    if payment.order_id > new_merchant_operator_config.current_order_id {
+        new_merchant_operator_config_view.set_current_order_id(payment.order_id);
+    }
+
+    // Close the old payment, moving its rent to the new PDA, which then only needs allocating
+    close_payment_account(old_payment_info, new_payment_info)?;
+
+    let bump_seed = [new_payment_bump];
+    let signer_seeds = [
+        Seed::from(PAYMENT_SEED),
+        Seed::from(new_merchant_operator_config_info.key()),
+        Seed::from(buyer_info.key()),
+        Seed::from(mint_info.key()),
+        Seed::from(&order_id_seed),
+        Seed::from(&bump_seed),
+    ];
+
+    create_pda_account(
+        payer_info,
+        &Rent::get()?,
+        Payment::LEN,
+        program_id,
+        new_payment_info,
+        signer_seeds,
+        None,
+    )?;
+
+    // The migrated payment keeps everything but its bump, including who funded its rent
+    let new_payment = Payment {
+        bump: new_payment_bump,
+        ..payment
+    };
+    new_payment.store(&mut new_payment_info.try_borrow_mut_data()?)?;
+
+    // Emit payment migrated event
+    let event = PaymentMigratedEvent {
+        discriminator: EventDiscriminators::PaymentMigrated as u8,
+        buyer: *buyer_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        order_id: new_payment.order_id,
+        amount: new_payment.amount,
+        old_payment: *old_payment_info.key(),
+        new_payment: *new_payment_info.key(),
+        old_merchant_operator_config: *old_merchant_operator_config_info.key(),
+        new_merchant_operator_config: *new_merchant_operator_config_info.key(),
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
@@ -1,25 +1,119 @@
+pub mod accept_merchant_authority;
+pub mod accept_operator_authority;
+pub mod add_accepted_currency;
+pub mod add_policy;
+pub mod add_to_freeze_list;
+pub mod annotate_payment;
+pub mod approve_refund;
+pub mod capture_payment;
+pub mod chargeback_payment;
+pub mod claim_refund;
 pub mod clear_payment;
+pub mod clear_payment_compressed;
+pub mod clear_payment_with_conversion;
+pub mod close_escrow_ata;
+pub mod close_merchant_operator_config;
 pub mod close_payment;
+pub mod close_payment_note;
+pub mod commit_settlement_wallet;
 pub mod create_operator;
+pub mod create_order;
+pub mod create_quote;
+pub mod decommission_config;
+pub mod execute_settlement_batch;
+pub mod get_config_summary;
+pub mod heartbeat;
+pub mod initialize_buyer_index;
+pub mod initialize_config_registry_page;
 pub mod initialize_merchant;
 pub mod initialize_merchant_operator_config;
+pub mod initialize_merchant_profile;
+pub mod initialize_merchant_stats;
+pub mod initialize_merchant_with_config;
+pub mod initialize_operator_delegate;
+pub mod initialize_operator_freeze_list;
+pub mod initialize_payment_tree;
+pub mod initialize_risk_state;
+pub mod initialize_settlement_batch;
 pub mod make_payment;
+pub mod make_payment_compressed;
+pub mod migrate_escrow;
+pub mod migrate_payment;
+pub mod post_conversion_rate;
 pub mod process_emit_event;
+pub mod propose_merchant_authority;
+pub mod propose_operator_authority;
+pub mod propose_settlement_wallet;
 pub mod refund_payment;
+pub mod refund_payment_with_conversion;
+pub mod refund_settled_payment;
+pub mod remove_from_freeze_list;
+pub mod revoke_operator_delegate;
+pub mod set_settlement_wallet_timelock;
+pub mod set_webhook_commitment;
 pub mod shared;
-pub mod update_merchant_authority;
+pub mod sweep_closed_payments;
+pub mod update_merchant_profile;
 pub mod update_merchant_settlement_wallet;
-pub mod update_operator_authority;
+pub mod update_operator_delegate;
+pub mod upgrade_account;
 
+pub use accept_merchant_authority::*;
+pub use accept_operator_authority::*;
+pub use add_accepted_currency::*;
+pub use add_policy::*;
+pub use add_to_freeze_list::*;
+pub use annotate_payment::*;
+pub use approve_refund::*;
+pub use capture_payment::*;
+pub use chargeback_payment::*;
+pub use claim_refund::*;
 pub use clear_payment::*;
+pub use clear_payment_compressed::*;
+pub use clear_payment_with_conversion::*;
+pub use close_escrow_ata::*;
+pub use close_merchant_operator_config::*;
 pub use close_payment::*;
+pub use close_payment_note::*;
+pub use commit_settlement_wallet::*;
 pub use create_operator::*;
+pub use create_order::*;
+pub use create_quote::*;
+pub use decommission_config::*;
+pub use execute_settlement_batch::*;
+pub use get_config_summary::*;
+pub use heartbeat::*;
+pub use initialize_buyer_index::*;
+pub use initialize_config_registry_page::*;
 pub use initialize_merchant::*;
 pub use initialize_merchant_operator_config::*;
+pub use initialize_merchant_profile::*;
+pub use initialize_merchant_stats::*;
+pub use initialize_merchant_with_config::*;
+pub use initialize_operator_delegate::*;
+pub use initialize_operator_freeze_list::*;
+pub use initialize_payment_tree::*;
+pub use initialize_risk_state::*;
+pub use initialize_settlement_batch::*;
 pub use make_payment::*;
+pub use make_payment_compressed::*;
+pub use migrate_escrow::*;
+pub use migrate_payment::*;
+pub use post_conversion_rate::*;
 pub use process_emit_event::*;
+pub use propose_merchant_authority::*;
+pub use propose_operator_authority::*;
+pub use propose_settlement_wallet::*;
 pub use refund_payment::*;
+pub use refund_payment_with_conversion::*;
+pub use refund_settled_payment::*;
+pub use remove_from_freeze_list::*;
+pub use revoke_operator_delegate::*;
+pub use set_settlement_wallet_timelock::*;
+pub use set_webhook_commitment::*;
 pub use shared::*;
-pub use update_merchant_authority::*;
+pub use sweep_closed_payments::*;
+pub use update_merchant_profile::*;
 pub use update_merchant_settlement_wallet::*;
-pub use update_operator_authority::*;
+pub use update_operator_delegate::*;
+pub use upgrade_account::*;
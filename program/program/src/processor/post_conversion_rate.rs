@@ -0,0 +1,203 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::CONVERSION_RATE_SEED,
+    error::CommerceProgramError,
+    processor::{
+        create_pda_account, get_conversion_policy, get_refund_conversion_policy,
+        validate_operator_authority, validate_pda, verify_owner_mutability, verify_signer,
+        verify_system_program,
+    },
+    require_len,
+    state::{
+        discriminator::AccountSerialize, ConversionRate, MerchantOperatorConfig, Operator,
+        OperatorPermission,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_post_conversion_rate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, operator_authority_info, operator_info, merchant_operator_config_info, from_mint_info, to_mint_info, conversion_rate_info, system_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate operator and merchant_operator_config are owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to refund payments
+    validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::Refund,
+    )?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, policies, allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_operator(operator_info.key())?;
+
+    // Validate the config opted into refunds in another mint, or into clearing into to_mint,
+    // whose rate bounds ClearPaymentWithConversion's quote
+    let clears_into_to_mint = get_conversion_policy(&policies)
+        .is_some_and(|conversion| &conversion.settlement_mint == to_mint_info.key());
+    if get_refund_conversion_policy(&policies).is_none() && !clears_into_to_mint {
+        return Err(CommerceProgramError::RefundConversionPolicyRequired.into());
+    }
+
+    // Validate both mints are accepted currencies, other than a conversion settlement mint, and
+    // distinct
+    if !allowed_mints.contains(from_mint_info.key())
+        || !(clears_into_to_mint || allowed_mints.contains(to_mint_info.key()))
+        || from_mint_info.key() == to_mint_info.key()
+    {
+        return Err(CommerceProgramError::InvalidMint.into());
+    }
+
+    if args.rate_numerator == 0 || args.rate_denominator == 0 {
+        return Err(CommerceProgramError::InvalidConversionRate.into());
+    }
+
+    let bump = if conversion_rate_info.data_is_empty() {
+        // First posting for the pair: validate and create the ConversionRate PDA
+        validate_pda(
+            &[
+                CONVERSION_RATE_SEED,
+                merchant_operator_config_info.key(),
+                from_mint_info.key(),
+                to_mint_info.key(),
+            ],
+            &Pubkey::from(*program_id),
+            args.bump,
+            conversion_rate_info,
+        )?;
+
+        let rent = Rent::get()?;
+        let bump_seed = [args.bump];
+        let signer_seeds = [
+            Seed::from(CONVERSION_RATE_SEED),
+            Seed::from(merchant_operator_config_info.key()),
+            Seed::from(from_mint_info.key()),
+            Seed::from(to_mint_info.key()),
+            Seed::from(&bump_seed),
+        ];
+        create_pda_account(
+            payer_info,
+            &rent,
+            ConversionRate::LEN,
+            program_id,
+            conversion_rate_info,
+            signer_seeds,
+            None,
+        )?;
+
+        args.bump
+    } else {
+        // Later postings replace the rate in place
+        verify_owner_mutability(conversion_rate_info, &COMMERCE_PROGRAM_ID, true)?;
+
+        let conversion_rate =
+            ConversionRate::try_from_bytes(&conversion_rate_info.try_borrow_data()?)?;
+        conversion_rate.validate_pda(conversion_rate_info.key())?;
+        conversion_rate.validate_pair(
+            merchant_operator_config_info.key(),
+            from_mint_info.key(),
+            to_mint_info.key(),
+        )?;
+
+        conversion_rate.bump
+    };
+
+    let conversion_rate = ConversionRate {
+        merchant_operator_config: *merchant_operator_config_info.key(),
+        from_mint: *from_mint_info.key(),
+        to_mint: *to_mint_info.key(),
+        bump,
+        rate_numerator: args.rate_numerator,
+        rate_denominator: args.rate_denominator,
+        posted_at: Clock::get()?.unix_timestamp,
+        attester: *operator_authority_info.key(),
+    };
+
+    let mut conversion_rate_data = conversion_rate_info.try_borrow_mut_data()?;
+    conversion_rate_data.copy_from_slice(&conversion_rate.to_bytes());
+
+    Ok(())
+}
+
+struct PostConversionRateArgs {
+    rate_numerator: u64,
+    rate_denominator: u64,
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<PostConversionRateArgs, ProgramError> {
+    require_len!(data, 17);
+    let rate_numerator = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    let rate_denominator = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let bump = data[16];
+    Ok(PostConversionRateArgs {
+        rate_numerator,
+        rate_denominator,
+        bump,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = [0u8; 17];
+        data[0..8].copy_from_slice(&98u64.to_le_bytes());
+        data[8..16].copy_from_slice(&100u64.to_le_bytes());
+        data[16] = 253;
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.rate_numerator, 98);
+        assert_eq!(args.rate_denominator, 100);
+        assert_eq!(args.bump, 253);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[0u8; 16]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -2,9 +2,7 @@ use pinocchio::{
     account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
 };
 
-use crate::{
-    constants::event_authority_pda, error::CommerceProgramError, processor::verify_signer,
-};
+use crate::processor::{verify_event_authority, verify_signer};
 
 #[inline(always)]
 pub fn process_emit_event(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
@@ -12,9 +10,7 @@ pub fn process_emit_event(_program_id: &Pubkey, accounts: &[AccountInfo]) -> Pro
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
-    if event_authority.key().ne(&event_authority_pda::ID) {
-        return Err(CommerceProgramError::InvalidEventAuthority.into());
-    }
+    verify_event_authority(event_authority)?;
 
     // No-op, besides checking for event authority signing.
     verify_signer(event_authority, false)?;
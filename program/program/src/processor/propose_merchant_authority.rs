@@ -4,12 +4,12 @@ use pinocchio::{
 
 use crate::{
     processor::{verify_owner_mutability, verify_signer},
-    state::{discriminator::AccountSerialize, Merchant},
+    state::Merchant,
     ID as COMMERCE_PROGRAM_ID,
 };
 
 #[inline(always)]
-pub fn process_update_merchant_authority(
+pub fn process_propose_merchant_authority(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     _instruction_data: &[u8],
@@ -36,9 +36,9 @@ pub fn process_update_merchant_authority(
     // Validate Merchant PDA
     merchant.validate_pda(merchant_info.key())?;
 
-    // Update merchant owner
-    merchant.owner = *new_authority_info.key();
-    merchant_data.copy_from_slice(&merchant.to_bytes());
+    // Record the proposed authority; it takes over once it signs AcceptMerchantAuthority
+    merchant.pending_authority = *new_authority_info.key();
+    merchant.store(&mut merchant_data)?;
 
     Ok(())
 }
@@ -4,12 +4,12 @@ use pinocchio::{
 
 use crate::{
     processor::{verify_owner_mutability, verify_signer},
-    state::{discriminator::AccountSerialize, Operator},
+    state::Operator,
     ID as COMMERCE_PROGRAM_ID,
 };
 
 #[inline(always)]
-pub fn process_update_operator_authority(
+pub fn process_propose_operator_authority(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     _instruction_data: &[u8],
@@ -36,9 +36,9 @@ pub fn process_update_operator_authority(
     // Validate Merchant PDA
     operator.validate_pda(operator_info.key())?;
 
-    // Update merchant owner
-    operator.owner = *new_authority_info.key();
-    operator_data.copy_from_slice(&operator.to_bytes());
+    // Record the proposed authority; it takes over once it signs AcceptOperatorAuthority
+    operator.pending_authority = *new_authority_info.key();
+    operator.store(&mut operator_data)?;
 
     Ok(())
 }
@@ -2,29 +2,31 @@ extern crate alloc;
 
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::{Seed, Signer},
+    instruction::Signer,
     program_error::ProgramError,
     pubkey::Pubkey,
     sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
-use pinocchio_token::instructions::Transfer;
 
 use crate::{
-    constants::MERCHANT_SEED,
     error::CommerceProgramError,
+    math::elapsed_seconds,
     processor::{
-        get_ata, verify_current_program, verify_owner_mutability, verify_signer,
-        verify_system_program, verify_token_program, verify_token_program_account,
-    },
-    state::{
-        discriminator::AccountSerialize, Merchant, MerchantOperatorConfig, Operator, Payment,
-        PolicyData, PolicyType, Status,
+        apply_delegate_refund_limits, close_token_account, emit_order_status_changed,
+        emit_transfer_memo, escrow_authority_seeds, get_ata, get_callback_policy, get_memo_policy,
+        invoke_payment_callback, release_open_payments, split_callback_accounts,
+        split_memo_program, split_operator_freeze_list, split_order, split_rent_payer,
+        split_transfer_hook_accounts, token_account_balance, update_merchant_stats, update_order,
+        validate_buyer_not_frozen, validate_escrow_authority, validate_operator_refund_authority,
+        validate_payment_order, verify_current_program, verify_owner, verify_owner_mutability,
+        verify_signer, verify_system_program, verify_token_interface_program, TokenTransfer,
     },
+    state::{Merchant, MerchantOperatorConfig, Operator, Payment, PolicyData, PolicyType, Status},
 };
 use crate::{
-    events::{EventDiscriminators, PaymentRefundedEvent},
-    processor::emit_event,
+    events::{EventDiscriminators, PaymentLifecycleStatus, PaymentRefundedEvent},
+    processor::{emit_event, emit_payment_status_changed},
     ID as COMMERCE_PROGRAM_ID,
 };
 
@@ -32,20 +34,53 @@ use crate::{
 pub fn process_refund_payment(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    _instruction_data: &[u8],
+    instruction_data: &[u8],
 ) -> ProgramResult {
-    let [fee_payer_info, payment_info, operator_authority_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, merchant_escrow_ata_info, buyer_ata_info, token_program_info, system_program_info, event_authority_info, commerce_program_info] =
+    let args = process_instruction_data(instruction_data)?;
+    let [fee_payer_info, payment_info, operator_authority_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, escrow_authority_info, merchant_escrow_ata_info, buyer_ata_info, token_program_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    // The mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // The memo program, if passed, trails the other remaining accounts
+    let (memo_program_info, remaining_accounts) = split_memo_program(remaining_accounts);
+
+    // The callback program and the accounts forwarded to it, if passed, trail the other
+    // remaining accounts before the memo program
+    let (callback, remaining_accounts) = split_callback_accounts(remaining_accounts);
+
+    // The order, if passed, trails the other remaining accounts before the callback program
+    let (order_info, remaining_accounts) = split_order(remaining_accounts);
+
+    // The operator's OperatorFreezeList, if passed, trails the other remaining accounts before
+    // the Order
+    let (operator_freeze_list_info, remaining_accounts) =
+        split_operator_freeze_list(remaining_accounts);
+
+    // When closing the escrow ATA, the operator's rent treasury, if passed, leads the other
+    // remaining accounts ahead of the operator delegate
+    let (rent_treasury_info, remaining_accounts) = if args.close_escrow {
+        split_rent_payer(remaining_accounts)
+    } else {
+        (None, remaining_accounts)
+    };
+
     // Validate fee_payer is writable signer
     verify_signer(fee_payer_info, true)?;
 
     // Validate operator_authority should have signed
     verify_signer(operator_authority_info, false)?;
 
+    // Validate rent_treasury, if any, is writable signer
+    if let Some(rent_treasury_info) = rent_treasury_info {
+        verify_signer(rent_treasury_info, true)?;
+    }
+
     // Validate payment is writable and owned by this program
     verify_owner_mutability(payment_info, &COMMERCE_PROGRAM_ID, true)?;
 
@@ -55,14 +90,15 @@ pub fn process_refund_payment(
     // Validate operator is owned by the program
     verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
 
-    // Validate merchant_operator_config is owned by this program
-    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+    // Validate merchant_operator_config is owned by this program and writable for its
+    // open-payment count
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
 
-    // Validate mint is owned by token program
-    verify_token_program_account(mint_info)?;
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
 
-    // Validate token program
-    verify_token_program(token_program_info)?;
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
 
     // Verify system program
     verify_system_program(system_program_info)?;
@@ -74,10 +110,17 @@ pub fn process_refund_payment(
     let operator_data = operator_info.try_borrow_data()?;
     let operator = Operator::try_from_bytes(&operator_data)?;
     operator.validate_pda(operator_info.key())?;
-    operator.validate_owner(operator_authority_info.key())?;
 
-    let merchant_data = merchant_info.try_borrow_data()?;
-    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    // Validate operator_authority is the owner or a delegate allowed to refund payments
+    let (operator_delegate_info, remaining_accounts) = validate_operator_refund_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+    )?;
+
+    // Validate merchant is a Merchant account
+    Merchant::try_from_bytes(&merchant_info.try_borrow_data()?)?;
 
     // Load and validate merchant_operator_config
     let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
@@ -91,6 +134,14 @@ pub fn process_refund_payment(
     merchant_operator_config.validate_operator(operator_info.key())?;
     merchant_operator_config.validate_merchant(merchant_info.key())?;
 
+    // Validate the refund destination is not on the operator's freeze list
+    validate_buyer_not_frozen(
+        operator_freeze_list_info,
+        operator_info.key(),
+        &operator,
+        buyer_info.key(),
+    )?;
+
     // Load and validate payment
     let mut payment_data = payment_info.try_borrow_mut_data()?;
     let mut payment = Payment::try_from_bytes(&payment_data)?;
@@ -107,13 +158,24 @@ pub fn process_refund_payment(
         mint_info.key(),
     )?;
 
+    // An order payment records every status change on its Order
+    validate_payment_order(order_info, payment.has_order)?;
+
     // Validate refund policy conditions
     validate_refund_policy(&policies, &payment)?;
 
-    // Validate merchant escrow ATA (owned by merchant pda)
+    // Validate the refund is within the signing delegate's refund limits
+    apply_delegate_refund_limits(operator_delegate_info, payment.amount)?;
+
+    // Validate the escrow authority PDA and the escrow ATA it owns
+    let escrow_authority_bump = validate_escrow_authority(
+        escrow_authority_info,
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+    )?;
     get_ata(
         merchant_escrow_ata_info,
-        merchant_info.key(),
+        escrow_authority_info.key(),
         mint_info,
         token_program_info,
     )?;
@@ -127,26 +189,86 @@ pub fn process_refund_payment(
     )?;
 
     // Transfer tokens from merchant escrow back to buyer
-    // Use PDA as authority for the transfer
-    let bump_seed = [merchant.bump];
-    let signer_seeds = [
-        Seed::from(MERCHANT_SEED),
-        Seed::from(merchant.owner.as_ref()),
-        Seed::from(&bump_seed),
-    ];
-
-    Transfer {
-        from: merchant_escrow_ata_info,
-        to: buyer_ata_info,
-        authority: merchant_info,
-        amount: payment.amount,
+    // Use the escrow authority PDA as authority for the transfer
+    let bump_seed = [escrow_authority_bump];
+    let signer_seeds = escrow_authority_seeds(
+        merchant_operator_config_info.key(),
+        mint_info.key(),
+        &bump_seed,
+    );
+
+    // Attach a memo to the transfer if the memo policy asks for one
+    emit_transfer_memo(
+        get_memo_policy(&policies).is_some_and(|memo| memo.memo_on_refund),
+        memo_program_info,
+        payment.order_id,
+        payment_info.key(),
+    )?;
+
+    TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+        merchant_escrow_ata_info,
+        buyer_ata_info,
+        escrow_authority_info,
+        payment.amount,
+        &[Signer::from(&signer_seeds)],
+    )?;
+
+    // The payment's funds have left escrow
+    drop(merchant_operator_config_data);
+    let open_payments = release_open_payments(merchant_operator_config_info, mint_info.key(), 1)?;
+
+    // Close the escrow ATA once the refund leaves it empty with no other open payment of the
+    // config and mint, returning its rent to the operator's rent treasury, or to the payer
+    // without one, as the escrow is not the payment's. MakePayment recreates it for the next one.
+    if args.close_escrow
+        && open_payments == 0
+        && token_account_balance(merchant_escrow_ata_info, token_program_info)? == 0
+    {
+        close_token_account(
+            token_program_info,
+            merchant_escrow_ata_info,
+            rent_treasury_info.unwrap_or(fee_payer_info),
+            escrow_authority_info,
+            &[Signer::from(&signer_seeds)],
+        )?;
     }
-    .invoke_signed(&[Signer::from(&signer_seeds)])?;
 
     // Update payment status to refunded and save
     payment.status = Status::Refunded;
 
-    payment_data.copy_from_slice(&payment.to_bytes());
+    payment.store(&mut payment_data)?;
+
+    // Update optional merchant stats
+    update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+        stats.record_refund(payment.amount)
+    })?;
+
+    // Let the merchant's program react, now that the payment is stored with its new status
+    drop(payment_data);
+    invoke_payment_callback(
+        get_callback_policy(&policies)
+            .filter(|callback| callback.on_refund)
+            .map(|callback| &callback.program),
+        callback,
+        payment_info,
+        &payment.status,
+        payment.amount,
+    )?;
+
+    // Record the refund on the payment's order, if any
+    let order_status = match order_info {
+        Some(order_info) => update_order(
+            order_info,
+            merchant_operator_config_info,
+            buyer_info,
+            payment.order_id,
+            |order| {
+                order.record_resolution(&Status::Refunded);
+                Ok(())
+            },
+        )?,
+        None => None,
+    };
 
     // Emit payment refunded event
     let event = PaymentRefundedEvent {
@@ -156,6 +278,7 @@ pub fn process_refund_payment(
         operator: *operator_info.key(),
         amount: payment.amount,
         order_id: payment.order_id,
+        idempotency_key: payment.idempotency_key,
     };
 
     emit_event(
@@ -165,10 +288,52 @@ pub fn process_refund_payment(
         &event.to_bytes(),
     )?;
 
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::Refunded,
+        operator_authority_info.key(),
+    )?;
+
+    emit_order_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        buyer_info.key(),
+        merchant_info.key(),
+        operator_info.key(),
+        payment.order_id,
+        order_status,
+    )?;
+
     Ok(())
 }
 
-fn validate_refund_policy(policies: &[PolicyData], payment: &Payment) -> Result<(), ProgramError> {
+struct RefundPaymentArgs {
+    close_escrow: bool,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<RefundPaymentArgs, ProgramError> {
+    // Clients predating `close_escrow` send no instruction data
+    let close_escrow = match data.first() {
+        None | Some(0) => false,
+        Some(1) => true,
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+
+    Ok(RefundPaymentArgs { close_escrow })
+}
+
+pub(crate) fn validate_refund_policy(
+    policies: &[PolicyData],
+    payment: &Payment,
+) -> Result<(), ProgramError> {
     let Some(policy) = MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Refund)
     else {
         return Ok(()); // No refund policy means no restrictions
@@ -186,10 +351,9 @@ fn validate_refund_policy(policies: &[PolicyData], payment: &Payment) -> Result<
     // Check refund window (0 means no time restriction)
     if refund.max_time_after_purchase > 0 {
         let current_time = Clock::get()?.unix_timestamp;
-        let time_since_payment = current_time - payment.created_at;
-        let max_refund_time = refund.max_time_after_purchase as i64;
+        let time_since_payment = elapsed_seconds(payment.created_at, current_time);
 
-        if time_since_payment > max_refund_time {
+        if time_since_payment > refund.max_time_after_purchase as i128 {
             return Err(CommerceProgramError::RefundWindowExpired.into());
         }
     }
@@ -201,9 +365,20 @@ fn validate_refund_policy(policies: &[PolicyData], payment: &Payment) -> Result<
 mod tests {
     use super::*;
     use crate::state::policy::{PolicyData, RefundPolicy};
-    use crate::state::{Payment, Status};
+    use crate::state::{FeeOverride, Payment, Status};
     use alloc::vec;
 
+    #[test]
+    fn test_process_instruction_data() {
+        assert!(!process_instruction_data(&[]).unwrap().close_escrow);
+        assert!(!process_instruction_data(&[0]).unwrap().close_escrow);
+        assert!(process_instruction_data(&[1]).unwrap().close_escrow);
+        assert_eq!(
+            process_instruction_data(&[2]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+    }
+
     #[test]
     fn test_validate_refund_policy_no_policy() {
         let policies = vec![];
@@ -213,6 +388,12 @@ mod tests {
             created_at: 1000000,
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         // No policy should pass validation
@@ -233,6 +414,12 @@ mod tests {
             created_at: 1000000,
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         assert!(validate_refund_policy(&policies, &payment).is_ok());
@@ -252,6 +439,12 @@ mod tests {
             created_at: 1000000,
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         assert!(validate_refund_policy(&policies, &payment).is_ok());
@@ -271,6 +464,12 @@ mod tests {
             created_at: 1000000,
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         let result = validate_refund_policy(&policies, &payment);
@@ -291,6 +490,12 @@ mod tests {
             created_at: 1000000,
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         let result = validate_refund_policy(&policies, &payment);
@@ -311,6 +516,12 @@ mod tests {
             created_at: 1, // Very old payment
             status: Status::Paid,
             bump: 1,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         // No time restriction means any payment age should work
@@ -0,0 +1,353 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    constants::REFUND_DELEGATE_SEED,
+    error::CommerceProgramError,
+    events::{EventDiscriminators, PaymentLifecycleStatus, PaymentRefundedEvent},
+    processor::{
+        apply_delegate_refund_limits, emit_event, emit_order_status_changed,
+        emit_payment_status_changed, get_ata, split_operator_freeze_list, split_order,
+        split_transfer_hook_accounts, update_merchant_stats, update_order,
+        validate_buyer_not_frozen, validate_operator_refund_authority, validate_payment_order,
+        validate_pda, validate_refund_policy, verify_current_program, verify_owner,
+        verify_owner_mutability, verify_signer, verify_system_program,
+        verify_token_interface_program, TokenTransfer,
+    },
+    require_len,
+    state::{Merchant, MerchantOperatorConfig, Operator, Payment, PolicyData, PolicyType, Status},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_refund_settled_payment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [fee_payer_info, payment_info, operator_authority_info, buyer_info, merchant_info, operator_info, merchant_operator_config_info, mint_info, merchant_settlement_ata_info, buyer_ata_info, refund_delegate_info, token_program_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // The mint's transfer hook accounts, if it has a hook, lead the remaining accounts
+    let (transfer_hook_infos, remaining_accounts) =
+        split_transfer_hook_accounts(mint_info, remaining_accounts)?;
+
+    // The order, if passed, trails the other remaining accounts
+    let (order_info, remaining_accounts) = split_order(remaining_accounts);
+
+    // The operator's OperatorFreezeList, if passed, trails the other remaining accounts before
+    // the Order
+    let (operator_freeze_list_info, remaining_accounts) =
+        split_operator_freeze_list(remaining_accounts);
+
+    // Validate fee_payer is writable signer
+    verify_signer(fee_payer_info, true)?;
+
+    // Validate operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate payment is writable and owned by this program
+    verify_owner_mutability(payment_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate operator is owned by the program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_operator_config is owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate token program, SPL Token or Token-2022
+    verify_token_interface_program(token_program_info)?;
+
+    // Validate mint is owned by token program
+    verify_owner(mint_info, token_program_info.key())?;
+
+    // Verify system program
+    verify_system_program(system_program_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Load and validate operator and merchant
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to refund payments
+    let (operator_delegate_info, remaining_accounts) = validate_operator_refund_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+    )?;
+
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+
+    // Load and validate merchant_operator_config
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, policies, _allowed_mints) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+
+    // Validate merchant_operator_config PDA
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate operator and merchant match the config
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    // Validate the refund destination is not on the operator's freeze list
+    validate_buyer_not_frozen(
+        operator_freeze_list_info,
+        operator_info.key(),
+        &operator,
+        buyer_info.key(),
+    )?;
+
+    // Validate the config auto-settles, so every cleared payment went straight to the
+    // settlement wallet
+    validate_auto_settle(&policies)?;
+
+    // Load and validate payment
+    let mut payment_data = payment_info.try_borrow_mut_data()?;
+    let mut payment = Payment::try_from_bytes(&payment_data)?;
+
+    // Validate payment status is Cleared (auto-settled payments are created as Cleared)
+    payment.validate_status(Status::Cleared)?;
+
+    // Validate Payment PDA
+    // No need to validate mint since it's validated via the PDA seed
+    payment.validate_pda(
+        payment_info.key(),
+        merchant_operator_config_info.key(),
+        buyer_info.key(),
+        mint_info.key(),
+    )?;
+
+    // An order payment records every status change on its Order
+    validate_payment_order(order_info, payment.has_order)?;
+
+    // Validate refund policy conditions. A refund window is required so the delegate can
+    // only move funds for recent payments.
+    validate_refund_window_required(&policies)?;
+    validate_refund_policy(&policies, &payment)?;
+
+    // Validate the refund is within the signing delegate's refund limits
+    apply_delegate_refund_limits(operator_delegate_info, payment.amount)?;
+
+    // Validate merchant settlement ATA (owned by the current settlement wallet, so approvals
+    // made by a previous settlement wallet can no longer be used)
+    get_ata(
+        merchant_settlement_ata_info,
+        &merchant.settlement_wallet,
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate buyer ATA (owned by buyer)
+    get_ata(
+        buyer_ata_info,
+        buyer_info.key(),
+        mint_info,
+        token_program_info,
+    )?;
+
+    // Validate refund delegate PDA
+    validate_pda(
+        &[REFUND_DELEGATE_SEED, merchant_info.key()],
+        &Pubkey::from(*program_id),
+        args.refund_delegate_bump,
+        refund_delegate_info,
+    )?;
+
+    // Transfer tokens from the settlement ATA back to the buyer using the delegated
+    // allowance. The token program rejects the transfer if the allowance is missing,
+    // revoked or too small.
+    let bump_seed = [args.refund_delegate_bump];
+    let signer_seeds = [
+        Seed::from(REFUND_DELEGATE_SEED),
+        Seed::from(merchant_info.key()),
+        Seed::from(&bump_seed),
+    ];
+
+    TokenTransfer::new(token_program_info, mint_info, transfer_hook_infos)?.transfer(
+        merchant_settlement_ata_info,
+        buyer_ata_info,
+        refund_delegate_info,
+        payment.amount,
+        &[Signer::from(&signer_seeds)],
+    )?;
+
+    // Update payment status to refunded and save
+    payment.status = Status::Refunded;
+
+    payment.store(&mut payment_data)?;
+
+    // Update optional merchant stats
+    update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+        stats.record_refund(payment.amount)
+    })?;
+
+    // Record the refund on the payment's order, if any
+    let order_status = match order_info {
+        Some(order_info) => update_order(
+            order_info,
+            merchant_operator_config_info,
+            buyer_info,
+            payment.order_id,
+            |order| {
+                order.record_settled_refund();
+                Ok(())
+            },
+        )?,
+        None => None,
+    };
+
+    // Emit payment refunded event
+    let event = PaymentRefundedEvent {
+        discriminator: EventDiscriminators::PaymentRefunded as u8,
+        buyer: *buyer_info.key(),
+        merchant: *merchant_info.key(),
+        operator: *operator_info.key(),
+        amount: payment.amount,
+        order_id: payment.order_id,
+        idempotency_key: payment.idempotency_key,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_bytes(),
+    )?;
+
+    emit_payment_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.buyer,
+        &event.merchant,
+        &event.operator,
+        event.order_id,
+        PaymentLifecycleStatus::Cleared,
+        PaymentLifecycleStatus::Refunded,
+        operator_authority_info.key(),
+    )?;
+
+    emit_order_status_changed(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        buyer_info.key(),
+        merchant_info.key(),
+        operator_info.key(),
+        payment.order_id,
+        order_status,
+    )?;
+
+    Ok(())
+}
+
+fn validate_auto_settle(policies: &[PolicyData]) -> Result<(), ProgramError> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Settlement) {
+        Some(PolicyData::Settlement(settlement)) if settlement.auto_settle => Ok(()),
+        _ => Err(CommerceProgramError::PaymentNotAutoSettled.into()),
+    }
+}
+
+fn validate_refund_window_required(policies: &[PolicyData]) -> Result<(), ProgramError> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Refund) {
+        Some(PolicyData::Refund(refund)) if refund.max_time_after_purchase > 0 => Ok(()),
+        _ => Err(CommerceProgramError::RefundWindowRequired.into()),
+    }
+}
+
+struct RefundSettledPaymentArgs {
+    refund_delegate_bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<RefundSettledPaymentArgs, ProgramError> {
+    require_len!(data, 1);
+    let refund_delegate_bump = data[0];
+    Ok(RefundSettledPaymentArgs {
+        refund_delegate_bump,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::policy::{RefundPolicy, SettlementPolicy};
+    use alloc::vec;
+
+    fn settlement_policy(auto_settle: bool) -> PolicyData {
+        PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle,
+        })
+    }
+
+    fn refund_policy(max_time_after_purchase: u64) -> PolicyData {
+        PolicyData::Refund(RefundPolicy {
+            max_amount: 1_000,
+            max_time_after_purchase,
+        })
+    }
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let args = process_instruction_data(&[253]).unwrap();
+        assert_eq!(args.refund_delegate_bump, 253);
+    }
+
+    #[test]
+    fn test_process_instruction_data_empty() {
+        let result = process_instruction_data(&[]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_validate_auto_settle() {
+        assert!(validate_auto_settle(&[settlement_policy(true)]).is_ok());
+        assert_eq!(
+            validate_auto_settle(&[settlement_policy(false)]).unwrap_err(),
+            CommerceProgramError::PaymentNotAutoSettled.into()
+        );
+        assert_eq!(
+            validate_auto_settle(&[]).unwrap_err(),
+            CommerceProgramError::PaymentNotAutoSettled.into()
+        );
+    }
+
+    #[test]
+    fn test_validate_refund_window_required() {
+        let policies = vec![settlement_policy(true), refund_policy(3_600)];
+        assert!(validate_refund_window_required(&policies).is_ok());
+
+        let policies = vec![settlement_policy(true), refund_policy(0)];
+        assert_eq!(
+            validate_refund_window_required(&policies).unwrap_err(),
+            CommerceProgramError::RefundWindowRequired.into()
+        );
+
+        let policies = vec![settlement_policy(true)];
+        assert_eq!(
+            validate_refund_window_required(&policies).unwrap_err(),
+            CommerceProgramError::RefundWindowRequired.into()
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    processor::{verify_owner_mutability, verify_signer},
+    state::{Operator, OperatorDelegate},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_revoke_operator_delegate(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [authority_info, operator_info, operator_delegate_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: authority should have signed and receives the rent
+    verify_signer(authority_info, true)?;
+
+    // Validate operator is owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate operator_delegate is writable and owned by this program
+    verify_owner_mutability(operator_delegate_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Load and validate operator
+    let operator = {
+        let operator_data = operator_info.try_borrow_data()?;
+        Operator::try_from_bytes(&operator_data)?
+    };
+    operator.validate_owner(authority_info.key())?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Load and validate operator_delegate
+    let operator_delegate = {
+        let operator_delegate_data = operator_delegate_info.try_borrow_data()?;
+        OperatorDelegate::try_from_bytes(&operator_delegate_data)?
+    };
+    operator_delegate.validate_pda(operator_delegate_info.key())?;
+    operator_delegate.validate_operator(operator_info.key())?;
+
+    let authority_lamports = authority_info.lamports();
+    *authority_info.try_borrow_mut_lamports()? = authority_lamports
+        .checked_add(operator_delegate_info.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    *operator_delegate_info.try_borrow_mut_lamports()? = 0;
+    operator_delegate_info.close()?;
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    events::{EventDiscriminators, SettlementWalletTimelockRaisedEvent},
+    processor::{emit_event, verify_current_program, verify_owner_mutability, verify_signer},
+    require_len,
+    state::Merchant,
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_set_settlement_wallet_timelock(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, authority_info, merchant_info, event_authority_info, commerce_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    let mut merchant_data = merchant_info.try_borrow_mut_data()?;
+    let mut merchant = Merchant::try_from_bytes(&merchant_data)?;
+
+    // Validate merchant owner
+    merchant.validate_owner(authority_info.key())?;
+
+    // Validate Merchant PDA
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Raise the timelock; lowering it would let a compromised authority skip the wait
+    let old_timelock_seconds = merchant.settlement_wallet_timelock_seconds;
+    merchant.raise_settlement_wallet_timelock(args.settlement_wallet_timelock_seconds)?;
+    merchant.store(&mut merchant_data)?;
+
+    // Emit settlement wallet timelock raised event
+    let event = SettlementWalletTimelockRaisedEvent {
+        discriminator: EventDiscriminators::SettlementWalletTimelockRaised as u8,
+        merchant: *merchant_info.key(),
+        old_timelock_seconds,
+        new_timelock_seconds: merchant.settlement_wallet_timelock_seconds,
+        pending_settlement_wallet_unlocks_at: merchant.pending_settlement_wallet_unlocks_at,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
+
+struct SetSettlementWalletTimelockArgs {
+    settlement_wallet_timelock_seconds: u64,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<SetSettlementWalletTimelockArgs, ProgramError> {
+    require_len!(data, 8);
+    let settlement_wallet_timelock_seconds = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    Ok(SetSettlementWalletTimelockArgs {
+        settlement_wallet_timelock_seconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let args = process_instruction_data(&86_400u64.to_le_bytes()).unwrap();
+        assert_eq!(args.settlement_wallet_timelock_seconds, 86_400);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[0; 7]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -0,0 +1,162 @@
+extern crate alloc;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    constants::WEBHOOK_COMMITMENT_SEED,
+    events::{EventDiscriminators, WebhookCommitmentUpdatedEvent},
+    processor::{
+        create_pda_account, emit_event, validate_pda, verify_current_program,
+        verify_owner_mutability, verify_signer, verify_system_program,
+    },
+    require_len,
+    state::{discriminator::AccountSerialize, Merchant, MerchantOperatorConfig, WebhookCommitment},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_set_webhook_commitment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let args = process_instruction_data(instruction_data)?;
+    let [payer_info, authority_info, merchant_info, merchant_operator_config_info, webhook_commitment_info, system_program_info, event_authority_info, commerce_program_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
+    // Validate merchant and merchant_operator_config are owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_owner(authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate merchant_operator_config belongs to the merchant
+    let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+    let (merchant_operator_config, _, _) =
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    let (bump, old_commitment) = if webhook_commitment_info.data_is_empty() {
+        // First commitment for the config: validate and create the WebhookCommitment PDA
+        validate_pda(
+            &[WEBHOOK_COMMITMENT_SEED, merchant_operator_config_info.key()],
+            &Pubkey::from(*program_id),
+            args.bump,
+            webhook_commitment_info,
+        )?;
+
+        let rent = Rent::get()?;
+        let bump_seed = [args.bump];
+        let signer_seeds = [
+            Seed::from(WEBHOOK_COMMITMENT_SEED),
+            Seed::from(merchant_operator_config_info.key()),
+            Seed::from(&bump_seed),
+        ];
+        create_pda_account(
+            payer_info,
+            &rent,
+            WebhookCommitment::LEN,
+            program_id,
+            webhook_commitment_info,
+            signer_seeds,
+            None,
+        )?;
+
+        (args.bump, [0u8; 32])
+    } else {
+        // Later commitments replace the current one in place
+        verify_owner_mutability(webhook_commitment_info, &COMMERCE_PROGRAM_ID, true)?;
+
+        let webhook_commitment =
+            WebhookCommitment::try_from_bytes(&webhook_commitment_info.try_borrow_data()?)?;
+        webhook_commitment.validate_pda(webhook_commitment_info.key())?;
+
+        (webhook_commitment.bump, webhook_commitment.commitment)
+    };
+
+    let webhook_commitment = WebhookCommitment {
+        merchant_operator_config: *merchant_operator_config_info.key(),
+        bump,
+        commitment: args.commitment,
+        updated_at: Clock::get()?.unix_timestamp,
+    };
+
+    webhook_commitment_info
+        .try_borrow_mut_data()?
+        .copy_from_slice(&webhook_commitment.to_bytes());
+
+    let event = WebhookCommitmentUpdatedEvent {
+        discriminator: EventDiscriminators::WebhookCommitmentUpdated as u8,
+        merchant_operator_config: *merchant_operator_config_info.key(),
+        old_commitment,
+        new_commitment: args.commitment,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
+
+struct SetWebhookCommitmentArgs {
+    commitment: [u8; 32],
+    bump: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<SetWebhookCommitmentArgs, ProgramError> {
+    require_len!(data, 33);
+    let commitment = data[0..32].try_into().unwrap();
+    let bump = data[32];
+    Ok(SetWebhookCommitmentArgs { commitment, bump })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        let mut data = [0u8; 33];
+        data[0..32].copy_from_slice(&[7u8; 32]);
+        data[32] = 253;
+
+        let args = process_instruction_data(&data).unwrap();
+        assert_eq!(args.commitment, [7u8; 32]);
+        assert_eq!(args.bump, 253);
+    }
+
+    #[test]
+    fn test_process_instruction_data_too_short() {
+        let result = process_instruction_data(&[0u8; 32]);
+        assert_eq!(result.err(), Some(ProgramError::InvalidInstructionData));
+    }
+}
@@ -1,12 +1,92 @@
-use crate::{error::CommerceProgramError, ID as COMMERCE_PROGRAM_ID};
+//! Account checks shared by every processor.
+//!
+//! The `verify_*` primitives each check one property of an account; the composite checks below
+//! them are built from those primitives so a property fails with the same error wherever it is
+//! checked:
+//!
+//! | Check | Error |
+//! |-------|-------|
+//! | Account did not sign | `ProgramError::MissingRequiredSignature` |
+//! | Account not writable | `ProgramError::InvalidAccountData` |
+//! | Account has the wrong owner | `ProgramError::InvalidAccountOwner` |
+//! | Account is not the expected program | `ProgramError::IncorrectProgramId` |
+//! | Account to create already has data | `ProgramError::AccountAlreadyInitialized` |
+//! | PDA bump differs from the canonical bump | `ProgramError::InvalidInstructionData` |
+//! | PDA address differs from the derived address | `ProgramError::InvalidSeeds` |
+//! | ATA address differs from the derived address | `CommerceProgramError::InvalidAta` |
+//! | Token account at the ATA address holds another mint | `CommerceProgramError::AtaMintMismatch` |
+//! | Token account at the ATA address belongs to another wallet | `CommerceProgramError::AtaOwnerMismatch` |
+//! | ATA must exist but has no data | `CommerceProgramError::AtaNotInitialized` |
+//! | Account is not a mint | `CommerceProgramError::InvalidMint` |
+//! | Mint charges a Token-2022 transfer fee | `CommerceProgramError::UnsupportedMintExtension` |
+//!
+//! Processors and forks should call these instead of inspecting `AccountInfo` flags directly.
+
+use crate::{
+    constants::{event_authority_pda, TOKEN_2022_PROGRAM_ID},
+    error::CommerceProgramError,
+    processor::{has_transfer_fee, is_mint, token_account_mint, token_account_owner},
+    ID as COMMERCE_PROGRAM_ID,
+};
 use pinocchio::{
     account_info::AccountInfo,
     program_error::ProgramError,
     pubkey::{find_program_address, Pubkey},
+    ProgramResult,
 };
-use pinocchio_associated_token_account::ID as ATA_PROGRAM_ID;
+use pinocchio_associated_token_account::{instructions::CreateIdempotent, ID as ATA_PROGRAM_ID};
 use pinocchio_token::{state::Mint, ID as TOKEN_PROGRAM_ID};
 
+/// Verify account is writable.
+///
+/// # Arguments
+/// * `info` - The account to verify.
+///
+/// # Returns
+/// * `Result<(), ProgramError>` - `InvalidAccountData` if the account is read-only
+#[inline(always)]
+pub fn verify_writable(info: &AccountInfo) -> Result<(), ProgramError> {
+    if !info.is_writable() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Verify account is owned by `owner`.
+///
+/// # Arguments
+/// * `info` - The account to verify.
+/// * `owner` - The expected owner of the account.
+///
+/// # Returns
+/// * `Result<(), ProgramError>` - `InvalidAccountOwner` if the owner differs
+#[inline(always)]
+pub fn verify_owner(info: &AccountInfo, owner: &Pubkey) -> Result<(), ProgramError> {
+    if !info.is_owned_by(owner) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    Ok(())
+}
+
+/// Verify account is the program `program_id`.
+///
+/// # Arguments
+/// * `info` - The account to verify.
+/// * `program_id` - The expected program address.
+///
+/// # Returns
+/// * `Result<(), ProgramError>` - `IncorrectProgramId` if the address differs
+#[inline(always)]
+pub fn verify_program(info: &AccountInfo, program_id: &Pubkey) -> Result<(), ProgramError> {
+    if info.key().ne(program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
+
 /// Verify account as a signer, returning an error if it is not or if it is not writable while
 /// expected to be.
 ///
@@ -21,8 +101,8 @@ pub fn verify_signer(info: &AccountInfo, expect_writable: bool) -> Result<(), Pr
     if !info.is_signer() {
         return Err(ProgramError::MissingRequiredSignature);
     }
-    if expect_writable && !info.is_writable() {
-        return Err(ProgramError::InvalidAccountData);
+    if expect_writable {
+        verify_writable(info)?;
     }
 
     Ok(())
@@ -39,16 +119,14 @@ pub fn verify_signer(info: &AccountInfo, expect_writable: bool) -> Result<(), Pr
 /// * `Result<(), ProgramError>` - The result of the operation
 #[inline(always)]
 pub fn verify_system_account(info: &AccountInfo, is_writable: bool) -> Result<(), ProgramError> {
-    if !info.is_owned_by(&pinocchio_system::ID) {
-        return Err(ProgramError::InvalidAccountOwner);
-    }
+    verify_owner(info, &pinocchio_system::ID)?;
 
     if !info.data_is_empty() {
         return Err(ProgramError::AccountAlreadyInitialized);
     }
 
-    if is_writable && !info.is_writable() {
-        return Err(ProgramError::InvalidAccountData);
+    if is_writable {
+        verify_writable(info)?;
     }
 
     Ok(())
@@ -63,11 +141,7 @@ pub fn verify_system_account(info: &AccountInfo, is_writable: bool) -> Result<()
 /// * `Result<(), ProgramError>` - The result of the operation
 #[inline(always)]
 pub fn verify_system_program(info: &AccountInfo) -> Result<(), ProgramError> {
-    if info.key().ne(&pinocchio_system::ID) {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    Ok(())
+    verify_program(info, &pinocchio_system::ID)
 }
 
 /// Verify account as Associated Token program, returning an error if it is not.
@@ -79,11 +153,7 @@ pub fn verify_system_program(info: &AccountInfo) -> Result<(), ProgramError> {
 /// * `Result<(), ProgramError>` - The result of the operation
 #[inline(always)]
 pub fn verify_ata_program(info: &AccountInfo) -> Result<(), ProgramError> {
-    if info.key().ne(&ATA_PROGRAM_ID) {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-
-    Ok(())
+    verify_program(info, &ATA_PROGRAM_ID)
 }
 
 /// Verify account as current program, returning an error if it is not.
@@ -95,8 +165,20 @@ pub fn verify_ata_program(info: &AccountInfo) -> Result<(), ProgramError> {
 /// * `Result<(), ProgramError>` - The result of the operation
 #[inline(always)]
 pub fn verify_current_program(info: &AccountInfo) -> Result<(), ProgramError> {
-    if info.key().ne(&COMMERCE_PROGRAM_ID) {
-        return Err(ProgramError::IncorrectProgramId);
+    verify_program(info, &COMMERCE_PROGRAM_ID)
+}
+
+/// Verify account as the program's event authority PDA.
+///
+/// # Arguments
+/// * `info` - The account to verify.
+///
+/// # Returns
+/// * `Result<(), ProgramError>` - `InvalidEventAuthority` if the address differs
+#[inline(always)]
+pub fn verify_event_authority(info: &AccountInfo) -> Result<(), ProgramError> {
+    if info.key().ne(&event_authority_pda::ID) {
+        return Err(CommerceProgramError::InvalidEventAuthority.into());
     }
 
     Ok(())
@@ -117,11 +199,9 @@ pub fn verify_owner_mutability(
     owner: &Pubkey,
     expect_writable: bool,
 ) -> Result<(), ProgramError> {
-    if !info.is_owned_by(owner) {
-        return Err(ProgramError::InvalidAccountOwner);
-    }
-    if expect_writable && !info.is_writable() {
-        return Err(ProgramError::InvalidAccountData);
+    verify_owner(info, owner)?;
+    if expect_writable {
+        verify_writable(info)?;
     }
 
     Ok(())
@@ -136,16 +216,47 @@ pub fn verify_owner_mutability(
 /// * `Result<(), ProgramError>` - The result of the operation
 #[inline(always)]
 pub fn verify_token_program(info: &AccountInfo) -> Result<(), ProgramError> {
-    if info.key().ne(&TOKEN_PROGRAM_ID) {
+    verify_program(info, &TOKEN_PROGRAM_ID)
+}
+
+/// Verify account as the Tokenkeg or Token-2022 program, returning an error if it is neither.
+///
+/// # Arguments
+/// * `info` - The account to verify.
+///
+/// # Returns
+/// * `Result<(), ProgramError>` - The result of the operation
+#[inline(always)]
+pub fn verify_token_interface_program(info: &AccountInfo) -> Result<(), ProgramError> {
+    if info.key().ne(&TOKEN_PROGRAM_ID) && info.key().ne(&TOKEN_2022_PROGRAM_ID) {
         return Err(ProgramError::IncorrectProgramId);
     }
 
     Ok(())
 }
 
+/// Verify account is owned by the Tokenkeg program.
+///
+/// # Arguments
+/// * `info` - The account to verify.
+///
+/// # Returns
+/// * `Result<(), ProgramError>` - The result of the operation
 #[inline(always)]
 pub fn verify_token_program_account(info: &AccountInfo) -> Result<(), ProgramError> {
-    if !info.is_owned_by(&TOKEN_PROGRAM_ID) {
+    verify_owner(info, &TOKEN_PROGRAM_ID)
+}
+
+/// Verify account is owned by the Tokenkeg or Token-2022 program.
+///
+/// # Arguments
+/// * `info` - The account to verify.
+///
+/// # Returns
+/// * `Result<(), ProgramError>` - The result of the operation
+#[inline(always)]
+pub fn verify_token_interface_account(info: &AccountInfo) -> Result<(), ProgramError> {
+    if !info.is_owned_by(&TOKEN_PROGRAM_ID) && !info.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
         return Err(ProgramError::InvalidAccountOwner);
     }
 
@@ -183,19 +294,51 @@ pub fn validate_pda(
     }
 
     // Validate account is owned by the program (for initialized accounts)
-    if !account_info.data_is_empty() && !account_info.is_owned_by(program_id) {
-        return Err(ProgramError::InvalidAccountOwner);
+    if !account_info.data_is_empty() {
+        verify_owner(account_info, program_id)?;
     }
 
     Ok(calculated_pda)
 }
 
+/// Derives a Program Derived Address (PDA) at its canonical bump and validates the account
+/// against it. Use this instead of [`validate_pda`] when creating a PDA, so the bump is never
+/// taken from instruction data.
+///
+/// # Arguments
+/// * `seeds` - The seeds used to derive the PDA, without the bump
+/// * `program_id` - The program ID that should own the PDA
+/// * `account_info` - The account that should match the derived PDA
+///
+/// # Returns
+/// * `Result<u8, ProgramError>` - The canonical bump on success
+#[inline(always)]
+pub fn derive_and_validate_pda(
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (calculated_pda, canonical_bump) = find_program_address(seeds, program_id);
+
+    // Validate account key matches calculated PDA
+    if account_info.key() != &calculated_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Validate account is owned by the program (for initialized accounts)
+    if !account_info.data_is_empty() {
+        verify_owner(account_info, program_id)?;
+    }
+
+    Ok(canonical_bump)
+}
+
 /// Verify account as Tokenkeg program or System program, returning an error if it is not.
 /// Use this when unsure if an ATA has been initialized by the program or not.
 ///
 /// # Arguments
 /// * `info` - The account to verify.
-/// * `expected_program_id` - The expected program ID of the account.
+/// * `expect_writable` - Whether the account is expected to be writable.
 ///
 /// # Returns
 /// * `Result<(), ProgramError>` - The result of the operation
@@ -205,19 +348,365 @@ pub fn verify_token_or_system_program(
     expect_writable: bool,
 ) -> Result<(), ProgramError> {
     if !info.is_owned_by(&TOKEN_PROGRAM_ID) && !info.is_owned_by(&pinocchio_system::ID) {
-        return Err(ProgramError::IncorrectProgramId);
+        return Err(ProgramError::InvalidAccountOwner);
     }
-    if expect_writable && !info.is_writable() {
-        return Err(ProgramError::IncorrectProgramId);
+    if expect_writable {
+        verify_writable(info)?;
     }
 
     Ok(())
 }
 
-/// Verify account as a valid Mint account
+/// Verify account as a valid Mint account. Token-2022 mints may carry extensions, except a
+/// transfer fee, which would leave escrow holding less than the payment amount.
 #[inline(always)]
 pub fn verify_mint_account(info: &AccountInfo) -> Result<(), ProgramError> {
+    if info.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
+        let data = info.try_borrow_data()?;
+        if !is_mint(&data) {
+            return Err(CommerceProgramError::InvalidMint.into());
+        }
+        if has_transfer_fee(&data) {
+            return Err(CommerceProgramError::UnsupportedMintExtension.into());
+        }
+        return Ok(());
+    }
+
     Mint::from_account_info(info).map_err(|_| CommerceProgramError::InvalidMint)?;
 
     Ok(())
 }
+
+/// Verify account is the Associated Token Account of `wallet_key` for the mint. Any other
+/// token account fails, even one of `wallet_key` for the mint.
+#[inline(always)]
+pub fn verify_ata_address(
+    ata_info: &AccountInfo,
+    wallet_key: &Pubkey,
+    mint_info: &AccountInfo,
+    token_program_info: &AccountInfo,
+) -> Result<(), ProgramError> {
+    let expected_ata = find_program_address(
+        &[
+            wallet_key.as_ref(),
+            token_program_info.key().as_ref(),
+            mint_info.key().as_ref(),
+        ],
+        &ATA_PROGRAM_ID,
+    )
+    .0;
+
+    if ata_info.key() != &expected_ata {
+        // A token account in place of the ATA reports what it disagrees on
+        let error = match (
+            token_account_mint(ata_info, token_program_info),
+            token_account_owner(ata_info, token_program_info),
+        ) {
+            (Some(mint), _) if &mint != mint_info.key() => CommerceProgramError::AtaMintMismatch,
+            (_, Some(owner)) if &owner != wallet_key => CommerceProgramError::AtaOwnerMismatch,
+            _ => CommerceProgramError::InvalidAta,
+        };
+        return Err(error.into());
+    }
+
+    Ok(())
+}
+
+/// Validates an Associated Token Account address.
+///
+/// # Arguments
+/// * `ata_info` - The ATA account to validate
+/// * `wallet_key` - The wallet that should own the ATA
+/// * `mint_info` - The token mint for the ATA
+/// * `token_program_info` - The token program account
+///
+/// # Returns
+/// * `ProgramResult` - Success if validation passes and ATA exists
+#[inline(always)]
+pub fn get_ata(
+    ata_info: &AccountInfo,
+    wallet_key: &Pubkey,
+    mint_info: &AccountInfo,
+    token_program_info: &AccountInfo,
+) -> ProgramResult {
+    verify_ata_address(ata_info, wallet_key, mint_info, token_program_info)?;
+
+    if ata_info.data_is_empty() {
+        return Err(CommerceProgramError::AtaNotInitialized.into());
+    }
+
+    Ok(())
+}
+
+/// Validates an Associated Token Account address and creates it if it doesn't exist.
+///
+/// # Arguments
+/// * `ata_info` - The ATA account to validate/create
+/// * `wallet_info` - The wallet that should own the ATA
+/// * `mint_info` - The token mint for the ATA
+/// * `payer_info` - The account paying for creation (if needed)
+/// * `system_program_info` - The system program account
+/// * `token_program_info` - The token program account
+///
+/// # Returns
+/// * `ProgramResult` - Success if validation passes and creation (if needed) succeeds
+#[inline(always)]
+pub fn get_or_create_ata(
+    ata_info: &AccountInfo,
+    wallet_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    payer_info: &AccountInfo,
+    system_program_info: &AccountInfo,
+    token_program_info: &AccountInfo,
+) -> ProgramResult {
+    verify_ata_address(ata_info, wallet_info.key(), mint_info, token_program_info)?;
+
+    // Create ATA if it doesn't exist
+    if ata_info.data_is_empty() {
+        CreateIdempotent {
+            funding_account: payer_info,
+            account: ata_info,
+            wallet: wallet_info,
+            mint: mint_info,
+            system_program: system_program_info,
+            token_program: token_program_info,
+        }
+        .invoke()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `Account` header pinocchio reads through `AccountInfo`, followed by one word of data.
+    #[repr(C)]
+    struct TestAccount {
+        borrow_state: u8,
+        is_signer: u8,
+        is_writable: u8,
+        executable: u8,
+        resize_delta: i32,
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data_len: u64,
+        data: [u8; 8],
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, owner: Pubkey) -> Self {
+            Self {
+                // Not borrowed
+                borrow_state: u8::MAX,
+                is_signer: 0,
+                is_writable: 0,
+                executable: 0,
+                resize_delta: 0,
+                key,
+                owner,
+                lamports: 1,
+                data_len: 0,
+                data: [0; 8],
+            }
+        }
+
+        fn signer(mut self) -> Self {
+            self.is_signer = 1;
+            self
+        }
+
+        fn writable(mut self) -> Self {
+            self.is_writable = 1;
+            self
+        }
+
+        fn with_data(mut self) -> Self {
+            self.data_len = self.data.len() as u64;
+            self
+        }
+
+        fn info(&mut self) -> AccountInfo {
+            // SAFETY: `AccountInfo` is a `repr(C)` wrapper around a pointer to the runtime
+            // `Account` layout, which `TestAccount` reproduces.
+            unsafe { core::mem::transmute::<*mut TestAccount, AccountInfo>(self as *mut _) }
+        }
+    }
+
+    const KEY: Pubkey = [7; 32];
+
+    #[test]
+    fn test_verify_signer() {
+        let mut account = TestAccount::new(KEY, COMMERCE_PROGRAM_ID);
+        assert_eq!(
+            verify_signer(&account.info(), false),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+
+        let mut account = TestAccount::new(KEY, COMMERCE_PROGRAM_ID).signer();
+        assert_eq!(verify_signer(&account.info(), false), Ok(()));
+        assert_eq!(
+            verify_signer(&account.info(), true),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let mut account = TestAccount::new(KEY, COMMERCE_PROGRAM_ID)
+            .signer()
+            .writable();
+        assert_eq!(verify_signer(&account.info(), true), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_owner_mutability() {
+        let mut account = TestAccount::new(KEY, COMMERCE_PROGRAM_ID);
+        assert_eq!(
+            verify_owner_mutability(&account.info(), &TOKEN_PROGRAM_ID, false),
+            Err(ProgramError::InvalidAccountOwner)
+        );
+        assert_eq!(
+            verify_owner_mutability(&account.info(), &COMMERCE_PROGRAM_ID, false),
+            Ok(())
+        );
+        assert_eq!(
+            verify_owner_mutability(&account.info(), &COMMERCE_PROGRAM_ID, true),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let mut account = TestAccount::new(KEY, COMMERCE_PROGRAM_ID).writable();
+        assert_eq!(
+            verify_owner_mutability(&account.info(), &COMMERCE_PROGRAM_ID, true),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_verify_system_account() {
+        let mut account = TestAccount::new(KEY, COMMERCE_PROGRAM_ID).writable();
+        assert_eq!(
+            verify_system_account(&account.info(), true),
+            Err(ProgramError::InvalidAccountOwner)
+        );
+
+        let mut account = TestAccount::new(KEY, pinocchio_system::ID)
+            .writable()
+            .with_data();
+        assert_eq!(
+            verify_system_account(&account.info(), true),
+            Err(ProgramError::AccountAlreadyInitialized)
+        );
+
+        let mut account = TestAccount::new(KEY, pinocchio_system::ID);
+        assert_eq!(verify_system_account(&account.info(), false), Ok(()));
+        assert_eq!(
+            verify_system_account(&account.info(), true),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_verify_programs() {
+        let mut account = TestAccount::new(KEY, pinocchio_system::ID);
+        let info = account.info();
+        for result in [
+            verify_system_program(&info),
+            verify_ata_program(&info),
+            verify_token_program(&info),
+            verify_current_program(&info),
+        ] {
+            assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+        }
+
+        let mut system = TestAccount::new(pinocchio_system::ID, KEY);
+        let mut ata = TestAccount::new(ATA_PROGRAM_ID, KEY);
+        let mut token = TestAccount::new(TOKEN_PROGRAM_ID, KEY);
+        let mut token_2022 = TestAccount::new(TOKEN_2022_PROGRAM_ID, KEY);
+        let mut current = TestAccount::new(COMMERCE_PROGRAM_ID, KEY);
+        assert_eq!(verify_system_program(&system.info()), Ok(()));
+        assert_eq!(verify_ata_program(&ata.info()), Ok(()));
+        assert_eq!(verify_token_program(&token.info()), Ok(()));
+        assert_eq!(verify_current_program(&current.info()), Ok(()));
+
+        assert_eq!(verify_token_interface_program(&token.info()), Ok(()));
+        assert_eq!(verify_token_interface_program(&token_2022.info()), Ok(()));
+        assert_eq!(
+            verify_token_program(&token_2022.info()),
+            Err(ProgramError::IncorrectProgramId)
+        );
+        assert_eq!(
+            verify_token_interface_program(&system.info()),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_verify_event_authority() {
+        let mut account = TestAccount::new(KEY, pinocchio_system::ID);
+        assert_eq!(
+            verify_event_authority(&account.info()),
+            Err(CommerceProgramError::InvalidEventAuthority.into())
+        );
+
+        let mut account = TestAccount::new(event_authority_pda::ID, pinocchio_system::ID);
+        assert_eq!(verify_event_authority(&account.info()), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_token_accounts() {
+        let mut account = TestAccount::new(KEY, COMMERCE_PROGRAM_ID).writable();
+        assert_eq!(
+            verify_token_program_account(&account.info()),
+            Err(ProgramError::InvalidAccountOwner)
+        );
+        assert_eq!(
+            verify_token_or_system_program(&account.info(), true),
+            Err(ProgramError::InvalidAccountOwner)
+        );
+
+        assert_eq!(
+            verify_token_interface_account(&account.info()),
+            Err(ProgramError::InvalidAccountOwner)
+        );
+
+        let mut account = TestAccount::new(KEY, TOKEN_2022_PROGRAM_ID);
+        assert_eq!(verify_token_interface_account(&account.info()), Ok(()));
+        assert_eq!(
+            verify_token_program_account(&account.info()),
+            Err(ProgramError::InvalidAccountOwner)
+        );
+
+        let mut account = TestAccount::new(KEY, TOKEN_PROGRAM_ID);
+        assert_eq!(verify_token_program_account(&account.info()), Ok(()));
+        assert_eq!(verify_token_interface_account(&account.info()), Ok(()));
+        assert_eq!(
+            verify_token_or_system_program(&account.info(), false),
+            Ok(())
+        );
+        assert_eq!(
+            verify_token_or_system_program(&account.info(), true),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        let mut account = TestAccount::new(KEY, pinocchio_system::ID).writable();
+        assert_eq!(
+            verify_token_or_system_program(&account.info(), true),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_verify_mint_account() {
+        let mut account = TestAccount::new(KEY, TOKEN_PROGRAM_ID).with_data();
+        assert_eq!(
+            verify_mint_account(&account.info()),
+            Err(CommerceProgramError::InvalidMint.into())
+        );
+
+        let mut account = TestAccount::new(KEY, TOKEN_2022_PROGRAM_ID).with_data();
+        assert_eq!(
+            verify_mint_account(&account.info()),
+            Err(CommerceProgramError::InvalidMint.into())
+        );
+    }
+}
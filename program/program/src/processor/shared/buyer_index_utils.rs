@@ -0,0 +1,84 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::verify_owner_mutability,
+    state::{
+        discriminator::{AccountSerialize, Discriminator},
+        BuyerIndex, BuyerIndexPolicy, MerchantOperatorConfig, PolicyData, PolicyType,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Returns the config's buyer index policy, if any.
+pub fn get_buyer_index_policy(policies: &[PolicyData]) -> Option<&BuyerIndexPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::BuyerIndex) {
+        Some(PolicyData::BuyerIndex(buyer_index)) => Some(buyer_index),
+        _ => None,
+    }
+}
+
+/// Splits the optional BuyerIndex PDA off the end of the remaining accounts (once any
+/// RiskState accounts, the Order and references have been split off), so the accounts
+/// expected first (e.g. MerchantStats) are unaffected by its presence.
+pub fn split_buyer_index(
+    remaining_accounts: &[AccountInfo],
+) -> (Option<&AccountInfo>, &[AccountInfo]) {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if is_buyer_index(last) => (Some(last), rest),
+        _ => (None, remaining_accounts),
+    }
+}
+
+fn is_buyer_index(account_info: &AccountInfo) -> bool {
+    account_info.is_owned_by(&COMMERCE_PROGRAM_ID)
+        && account_info.data_len() == BuyerIndex::LEN
+        && account_info
+            .try_borrow_data()
+            .is_ok_and(|data| data[0] == BuyerIndex::DISCRIMINATOR)
+}
+
+/// Records a payment on the buyer's BuyerIndex under the config's buyer index policy.
+///
+/// # Arguments
+/// * `policies` - The config's policies
+/// * `buyer_index_info` - The BuyerIndex PDA split off by `split_buyer_index`
+/// * `merchant_operator_config_info` - The config the payment is made under
+/// * `buyer_info` - The payment's buyer
+/// * `order_id` - The payment's order id
+///
+/// # Returns
+/// * `ProgramResult` - Success if the config has no buyer index policy, the index was
+///   validated and updated, or it is optional and was not passed
+pub fn update_buyer_index(
+    policies: &[PolicyData],
+    buyer_index_info: Option<&AccountInfo>,
+    merchant_operator_config_info: &AccountInfo,
+    buyer_info: &AccountInfo,
+    order_id: u32,
+) -> ProgramResult {
+    let Some(policy) = get_buyer_index_policy(policies) else {
+        return Ok(());
+    };
+
+    let Some(buyer_index_info) = buyer_index_info else {
+        if policy.required {
+            return Err(CommerceProgramError::BuyerIndexRequired.into());
+        }
+        return Ok(());
+    };
+
+    // Validate buyer_index is writable and owned by this program
+    verify_owner_mutability(buyer_index_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    let mut buyer_index_data = buyer_index_info.try_borrow_mut_data()?;
+    let mut buyer_index = BuyerIndex::try_from_bytes(&buyer_index_data)?;
+
+    buyer_index.validate_pda(buyer_index_info.key())?;
+    buyer_index.validate_payment(merchant_operator_config_info.key(), buyer_info.key())?;
+
+    buyer_index.record_payment(order_id)?;
+    buyer_index_data.copy_from_slice(&buyer_index.to_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    state::{CallbackPolicy, MerchantOperatorConfig, PolicyData, PolicyType, Status},
+};
+
+/// Anchor-style discriminator of the callback instruction:
+/// Sha256(global:commerce_payment_callback)[..8]
+pub const PAYMENT_CALLBACK_DISCRIMINATOR: [u8; 8] = [128, 216, 104, 223, 189, 183, 58, 192];
+
+/// Callback instruction data: discriminator, new payment status (u8) and amount (u64).
+pub const PAYMENT_CALLBACK_DATA_LEN: usize = 8 + 1 + 8;
+
+/// Returns the config's callback policy, if any.
+pub fn get_callback_policy(policies: &[PolicyData]) -> Option<&CallbackPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Callback) {
+        Some(PolicyData::Callback(callback)) => Some(callback),
+        _ => None,
+    }
+}
+
+/// Splits the optional callback program, and the accounts forwarded to it, off the end of
+/// the remaining accounts (once the memo program has been split off), so the accounts
+/// expected first (e.g. MerchantStats) are unaffected by their presence.
+///
+/// The callback program is the first executable account; none of the accounts that can
+/// precede it are.
+///
+/// # Returns
+/// * The callback program and its accounts, if passed, and the accounts before them
+pub fn split_callback_accounts(
+    remaining_accounts: &[AccountInfo],
+) -> (Option<(&AccountInfo, &[AccountInfo])>, &[AccountInfo]) {
+    match remaining_accounts
+        .iter()
+        .position(|account_info| account_info.executable())
+    {
+        Some(index) => {
+            let (remaining_accounts, callback_infos) = remaining_accounts.split_at(index);
+            let (callback_program_info, callback_account_infos) =
+                callback_infos.split_first().unwrap();
+            (
+                Some((callback_program_info, callback_account_infos)),
+                remaining_accounts,
+            )
+        }
+        None => (None, remaining_accounts),
+    }
+}
+
+/// Builds the callback instruction data for a payment moving to `status`.
+pub fn payment_callback_data(status: &Status, amount: u64) -> [u8; PAYMENT_CALLBACK_DATA_LEN] {
+    let mut data = [0u8; PAYMENT_CALLBACK_DATA_LEN];
+    data[..8].copy_from_slice(&PAYMENT_CALLBACK_DISCRIMINATOR);
+    data[8] = status.clone() as u8;
+    data[9..].copy_from_slice(&amount.to_le_bytes());
+    data
+}
+
+/// Invokes the merchant's callback program when `callback_program` is set, passing the
+/// payment PDA (read-only) followed by the forwarded accounts. The payment must already be
+/// stored with its new status, so the callback reads the same state it is told about.
+///
+/// # Arguments
+/// * `callback_program` - The callback policy's program, if it asks for this transition
+/// * `callback` - The callback program and its accounts split off by `split_callback_accounts`
+/// * `payment_info` - The payment PDA
+/// * `status` - The payment's new status
+/// * `amount` - The amount cleared or refunded
+///
+/// # Errors
+/// Returns `CommerceProgramError::CallbackProgramRequired` if a callback is required but no
+/// program was passed, and `CommerceProgramError::CallbackProgramMismatch` if the program
+/// passed is not the policy's.
+pub fn invoke_payment_callback(
+    callback_program: Option<&Pubkey>,
+    callback: Option<(&AccountInfo, &[AccountInfo])>,
+    payment_info: &AccountInfo,
+    status: &Status,
+    amount: u64,
+) -> ProgramResult {
+    let Some(callback_program) = callback_program else {
+        return Ok(());
+    };
+
+    let Some((callback_program_info, callback_account_infos)) = callback else {
+        return Err(CommerceProgramError::CallbackProgramRequired.into());
+    };
+
+    if callback_program_info.key() != callback_program {
+        return Err(CommerceProgramError::CallbackProgramMismatch.into());
+    }
+
+    let mut account_metas = Vec::with_capacity(callback_account_infos.len() + 1);
+    account_metas.push(AccountMeta::readonly(payment_info.key()));
+    account_metas.extend(callback_account_infos.iter().map(|account| AccountMeta {
+        pubkey: account.key(),
+        is_writable: account.is_writable(),
+        is_signer: account.is_signer(),
+    }));
+
+    let mut account_infos: Vec<&AccountInfo> = Vec::with_capacity(account_metas.len() + 1);
+    account_infos.push(payment_info);
+    account_infos.extend(callback_account_infos);
+    account_infos.push(callback_program_info);
+
+    slice_invoke(
+        &Instruction {
+            program_id: callback_program_info.key(),
+            accounts: &account_metas,
+            data: &payment_callback_data(status, amount),
+        },
+        &account_infos,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoPolicy;
+    use std::vec;
+
+    #[test]
+    fn test_payment_callback_data() {
+        let data = payment_callback_data(&Status::Refunded, 1_000_000);
+        assert_eq!(data[..8], PAYMENT_CALLBACK_DISCRIMINATOR);
+        assert_eq!(data[8], Status::Refunded as u8);
+        assert_eq!(data[9..], 1_000_000u64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_get_callback_policy() {
+        let callback = CallbackPolicy {
+            program: [4u8; 32],
+            on_clear: true,
+            on_refund: false,
+        };
+        let policies = vec![
+            PolicyData::Memo(MemoPolicy {
+                memo_on_clear: true,
+                memo_on_refund: false,
+            }),
+            PolicyData::Callback(callback.clone()),
+        ];
+
+        assert_eq!(get_callback_policy(&policies), Some(&callback));
+        assert_eq!(get_callback_policy(&policies[..1]), None);
+    }
+}
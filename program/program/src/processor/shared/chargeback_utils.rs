@@ -0,0 +1,69 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::{
+    error::CommerceProgramError,
+    state::{ChargebackPolicy, MerchantOperatorConfig, PolicyData, PolicyType},
+};
+
+/// Returns the config's chargeback policy, if any.
+pub fn get_chargeback_policy(policies: &[PolicyData]) -> Option<&ChargebackPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Chargeback) {
+        Some(PolicyData::Chargeback(chargeback)) => Some(chargeback),
+        _ => None,
+    }
+}
+
+/// Rejects clearing a payment created at `created_at` while its chargeback window is open.
+pub fn validate_chargeback_window_closed(
+    policies: &[PolicyData],
+    created_at: i64,
+    now: i64,
+) -> Result<(), ProgramError> {
+    match get_chargeback_policy(policies) {
+        Some(chargeback) if chargeback.is_window_open(created_at, now) => {
+            Err(CommerceProgramError::ChargebackWindowActive.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoPolicy;
+    use std::vec;
+
+    fn chargeback() -> ChargebackPolicy {
+        ChargebackPolicy {
+            window_seconds: 3600,
+            arbiter: [9u8; 32],
+        }
+    }
+
+    #[test]
+    fn test_get_chargeback_policy() {
+        let policies = vec![
+            PolicyData::Memo(MemoPolicy {
+                memo_on_clear: true,
+                memo_on_refund: true,
+            }),
+            PolicyData::Chargeback(chargeback()),
+        ];
+
+        assert_eq!(get_chargeback_policy(&policies), Some(&chargeback()));
+        assert_eq!(get_chargeback_policy(&policies[..1]), None);
+    }
+
+    #[test]
+    fn test_validate_chargeback_window_closed() {
+        let policies = vec![PolicyData::Chargeback(chargeback())];
+
+        assert_eq!(
+            validate_chargeback_window_closed(&policies, 1_000, 4_599).unwrap_err(),
+            CommerceProgramError::ChargebackWindowActive.into()
+        );
+        assert!(validate_chargeback_window_closed(&policies, 1_000, 4_600).is_ok());
+        // No chargeback policy means no hold
+        assert!(validate_chargeback_window_closed(&[], 1_000, 1_000).is_ok());
+    }
+}
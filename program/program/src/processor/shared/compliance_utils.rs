@@ -0,0 +1,152 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke,
+    instruction::{AccountMeta, Instruction},
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    state::{CompliancePolicy, MerchantOperatorConfig, PolicyData, PolicyType},
+};
+
+/// Anchor-style discriminator of the compliance check instruction:
+/// Sha256(global:commerce_compliance_check)[..8]
+pub const COMPLIANCE_CHECK_DISCRIMINATOR: [u8; 8] = [192, 147, 185, 97, 5, 110, 185, 172];
+
+/// Compliance check instruction data: discriminator and payment amount (u64).
+pub const COMPLIANCE_CHECK_DATA_LEN: usize = 8 + 8;
+
+/// Returns the config's compliance policy, if any.
+pub fn get_compliance_policy(policies: &[PolicyData]) -> Option<&CompliancePolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Compliance) {
+        Some(PolicyData::Compliance(compliance)) => Some(compliance),
+        _ => None,
+    }
+}
+
+/// Splits the optional compliance program, and the accounts forwarded to it, off the end of
+/// the remaining accounts (once any transfer hook accounts have been split off the front),
+/// so the accounts expected first (e.g. MerchantStats) are unaffected by their presence.
+///
+/// The compliance program is the first executable account; none of the accounts that can
+/// precede it are.
+///
+/// # Returns
+/// * The compliance program and its accounts, if passed, and the accounts before them
+pub fn split_compliance_accounts(
+    remaining_accounts: &[AccountInfo],
+) -> (Option<(&AccountInfo, &[AccountInfo])>, &[AccountInfo]) {
+    match remaining_accounts
+        .iter()
+        .position(|account_info| account_info.executable())
+    {
+        Some(index) => {
+            let (remaining_accounts, compliance_infos) = remaining_accounts.split_at(index);
+            let (compliance_program_info, compliance_account_infos) =
+                compliance_infos.split_first().unwrap();
+            (
+                Some((compliance_program_info, compliance_account_infos)),
+                remaining_accounts,
+            )
+        }
+        None => (None, remaining_accounts),
+    }
+}
+
+/// Builds the compliance check instruction data for a payment of `amount`.
+pub fn compliance_check_data(amount: u64) -> [u8; COMPLIANCE_CHECK_DATA_LEN] {
+    let mut data = [0u8; COMPLIANCE_CHECK_DATA_LEN];
+    data[..8].copy_from_slice(&COMPLIANCE_CHECK_DISCRIMINATOR);
+    data[8..].copy_from_slice(&amount.to_le_bytes());
+    data
+}
+
+/// Invokes the config's compliance program, if it has a compliance policy, passing the buyer
+/// and mint (read-only, without the buyer's signature) followed by the forwarded accounts.
+/// The payment fails with the program's own error if it rejects the buyer.
+///
+/// # Arguments
+/// * `policies` - The config's policies
+/// * `compliance` - The compliance program and its accounts split off by
+///   `split_compliance_accounts`
+/// * `buyer_info` - The buyer making the payment
+/// * `mint_info` - The payment mint
+/// * `amount` - The payment amount
+///
+/// # Errors
+/// Returns `CommerceProgramError::ComplianceProgramRequired` if the config has a compliance
+/// policy but no program was passed, and `CommerceProgramError::ComplianceProgramMismatch` if
+/// the program passed is not the policy's.
+pub fn invoke_compliance_check(
+    policies: &[PolicyData],
+    compliance: Option<(&AccountInfo, &[AccountInfo])>,
+    buyer_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    let Some(policy) = get_compliance_policy(policies) else {
+        return Ok(());
+    };
+
+    let Some((compliance_program_info, compliance_account_infos)) = compliance else {
+        return Err(CommerceProgramError::ComplianceProgramRequired.into());
+    };
+
+    if compliance_program_info.key() != &policy.program {
+        return Err(CommerceProgramError::ComplianceProgramMismatch.into());
+    }
+
+    let mut account_metas = Vec::with_capacity(compliance_account_infos.len() + 2);
+    account_metas.push(AccountMeta::readonly(buyer_info.key()));
+    account_metas.push(AccountMeta::readonly(mint_info.key()));
+    account_metas.extend(compliance_account_infos.iter().map(|account| AccountMeta {
+        pubkey: account.key(),
+        is_writable: account.is_writable(),
+        is_signer: account.is_signer(),
+    }));
+
+    let mut account_infos: Vec<&AccountInfo> = Vec::with_capacity(account_metas.len() + 1);
+    account_infos.push(buyer_info);
+    account_infos.push(mint_info);
+    account_infos.extend(compliance_account_infos);
+    account_infos.push(compliance_program_info);
+
+    slice_invoke(
+        &Instruction {
+            program_id: compliance_program_info.key(),
+            accounts: &account_metas,
+            data: &compliance_check_data(amount),
+        },
+        &account_infos,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::BuyerIndexPolicy;
+    use std::vec;
+
+    #[test]
+    fn test_compliance_check_data() {
+        let data = compliance_check_data(1_000_000);
+        assert_eq!(data[..8], COMPLIANCE_CHECK_DISCRIMINATOR);
+        assert_eq!(data[8..], 1_000_000u64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_get_compliance_policy() {
+        let compliance = CompliancePolicy { program: [7u8; 32] };
+        let policies = vec![
+            PolicyData::BuyerIndex(BuyerIndexPolicy { required: false }),
+            PolicyData::Compliance(compliance.clone()),
+        ];
+
+        assert_eq!(get_compliance_policy(&policies), Some(&compliance));
+        assert_eq!(get_compliance_policy(&policies[..1]), None);
+    }
+}
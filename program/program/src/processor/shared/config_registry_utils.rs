@@ -0,0 +1,61 @@
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey, ProgramResult};
+
+use crate::{
+    processor::verify_owner_mutability,
+    state::{
+        discriminator::{AccountSerialize, Discriminator},
+        ConfigRegistryPage,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Splits the optional ConfigRegistryPage PDA off the end of the remaining accounts, so the
+/// mint accounts before it are unaffected by its presence.
+pub fn split_config_registry_page(
+    remaining_accounts: &[AccountInfo],
+) -> (Option<&AccountInfo>, &[AccountInfo]) {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if is_config_registry_page(last) => (Some(last), rest),
+        _ => (None, remaining_accounts),
+    }
+}
+
+fn is_config_registry_page(account_info: &AccountInfo) -> bool {
+    account_info.is_owned_by(&COMMERCE_PROGRAM_ID)
+        && account_info.data_len() == ConfigRegistryPage::LEN
+        && account_info
+            .try_borrow_data()
+            .is_ok_and(|data| data[0] == ConfigRegistryPage::DISCRIMINATOR)
+}
+
+/// Lists a new config on the operator's ConfigRegistryPage, if one was passed.
+///
+/// # Arguments
+/// * `config_registry_page_info` - The page split off by `split_config_registry_page`
+/// * `operator` - The config's operator
+/// * `merchant` - The config's merchant
+/// * `version` - The config's version
+pub fn register_config(
+    config_registry_page_info: Option<&AccountInfo>,
+    operator: &Pubkey,
+    merchant: &Pubkey,
+    version: u32,
+) -> ProgramResult {
+    let Some(config_registry_page_info) = config_registry_page_info else {
+        return Ok(());
+    };
+
+    // Validate config_registry_page is writable and owned by this program
+    verify_owner_mutability(config_registry_page_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    let mut page_data = config_registry_page_info.try_borrow_mut_data()?;
+    let mut page = ConfigRegistryPage::try_from_bytes(&page_data)?;
+
+    page.validate_pda(config_registry_page_info.key())?;
+    page.validate_operator(operator)?;
+
+    page.push(*merchant, version)?;
+    page_data.copy_from_slice(&page.to_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::verify_owner_mutability,
+    state::{
+        ConversionRate, MerchantOperatorConfig, PolicyData, PolicyType, RefundConversionPolicy,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Returns the config's refund conversion policy, if any.
+pub fn get_refund_conversion_policy(policies: &[PolicyData]) -> Option<&RefundConversionPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::RefundConversion) {
+        Some(PolicyData::RefundConversion(refund_conversion)) => Some(refund_conversion),
+        _ => None,
+    }
+}
+
+/// Loads the ConversionRate a refund from `from_mint` into `to_mint` under
+/// `merchant_operator_config` is converted at, rejecting rates older than the policy allows.
+///
+/// # Arguments
+/// * `conversion_rate_info` - The ConversionRate PDA
+/// * `merchant_operator_config` - The config the payment was made under
+/// * `from_mint` - The payment's mint
+/// * `to_mint` - The mint the refund is paid in
+/// * `policy` - The config's refund conversion policy
+/// * `now` - Current unix timestamp
+#[inline(always)]
+pub fn load_conversion_rate(
+    conversion_rate_info: &AccountInfo,
+    merchant_operator_config: &Pubkey,
+    from_mint: &Pubkey,
+    to_mint: &Pubkey,
+    policy: &RefundConversionPolicy,
+    now: i64,
+) -> Result<ConversionRate, ProgramError> {
+    let conversion_rate = read_conversion_rate(
+        conversion_rate_info,
+        merchant_operator_config,
+        from_mint,
+        to_mint,
+    )?;
+
+    if !policy.is_fresh(conversion_rate.posted_at, now) {
+        return Err(CommerceProgramError::ConversionRateStale.into());
+    }
+
+    Ok(conversion_rate)
+}
+
+/// Loads the ConversionRate posted from `from_mint` into `to_mint` under
+/// `merchant_operator_config`, whatever its age.
+///
+/// # Arguments
+/// * `conversion_rate_info` - The ConversionRate PDA
+/// * `merchant_operator_config` - The config the rate was posted under
+/// * `from_mint` - The mint converted from
+/// * `to_mint` - The mint converted into
+#[inline(always)]
+pub fn read_conversion_rate(
+    conversion_rate_info: &AccountInfo,
+    merchant_operator_config: &Pubkey,
+    from_mint: &Pubkey,
+    to_mint: &Pubkey,
+) -> Result<ConversionRate, ProgramError> {
+    verify_owner_mutability(conversion_rate_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    let conversion_rate = ConversionRate::try_from_bytes(&conversion_rate_info.try_borrow_data()?)?;
+    conversion_rate.validate_pda(conversion_rate_info.key())?;
+    conversion_rate.validate_pair(merchant_operator_config, from_mint, to_mint)?;
+
+    Ok(conversion_rate)
+}
@@ -0,0 +1,163 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::{get_ata, get_or_create_ata, verify_owner_mutability},
+    state::{discriminator::AccountSerialize, Operator, OperatorDelegate, OperatorPermission},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Validates that `operator_authority_info` may sign for the operator.
+///
+/// The operator owner is always allowed. Any other signer must be a delegate with
+/// `permission`, whose OperatorDelegate PDA is passed as the first remaining account.
+///
+/// # Arguments
+/// * `operator` - The loaded and PDA-validated operator
+/// * `operator_info` - The operator PDA
+/// * `operator_authority_info` - The signing operator authority
+/// * `remaining_accounts` - Accounts after the instruction's fixed accounts
+/// * `permission` - Permission a delegate needs for this instruction
+///
+/// # Returns
+/// * The remaining accounts after the OperatorDelegate PDA, if one was used, so that
+///   optional accounts such as MerchantStats keep their position
+#[inline(always)]
+pub fn validate_operator_authority<'a>(
+    operator: &Operator,
+    operator_info: &AccountInfo,
+    operator_authority_info: &AccountInfo,
+    remaining_accounts: &'a [AccountInfo],
+    permission: OperatorPermission,
+) -> Result<&'a [AccountInfo], ProgramError> {
+    if operator.owner.eq(operator_authority_info.key()) {
+        return Ok(remaining_accounts);
+    }
+
+    let Some((operator_delegate_info, remaining_accounts)) = remaining_accounts.split_first()
+    else {
+        return Err(CommerceProgramError::OperatorOwnerMismatch.into());
+    };
+
+    // Validate operator_delegate is owned by this program
+    verify_owner_mutability(operator_delegate_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    let operator_delegate_data = operator_delegate_info.try_borrow_data()?;
+    let operator_delegate = OperatorDelegate::try_from_bytes(&operator_delegate_data)?;
+
+    operator_delegate.validate_pda(operator_delegate_info.key())?;
+    operator_delegate.validate_operator(operator_info.key())?;
+    operator_delegate.validate_delegate(operator_authority_info.key())?;
+
+    if !operator_delegate.has_permission(permission) {
+        return Err(CommerceProgramError::OperatorDelegatePermissionDenied.into());
+    }
+
+    Ok(remaining_accounts)
+}
+
+/// Validates that `operator_authority_info` may sign refunds for the operator, as
+/// `validate_operator_authority` does for `OperatorPermission::Refund`.
+///
+/// # Returns
+/// * The OperatorDelegate PDA if a delegate signed, for `apply_delegate_refund_limits`
+///   once the refund amount is known, and the remaining accounts after it
+#[inline(always)]
+pub fn validate_operator_refund_authority<'a>(
+    operator: &Operator,
+    operator_info: &AccountInfo,
+    operator_authority_info: &AccountInfo,
+    remaining_accounts: &'a [AccountInfo],
+) -> Result<(Option<&'a AccountInfo>, &'a [AccountInfo]), ProgramError> {
+    let accounts_after_delegate = validate_operator_authority(
+        operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::Refund,
+    )?;
+
+    let operator_delegate_info = if accounts_after_delegate.len() < remaining_accounts.len() {
+        remaining_accounts.first()
+    } else {
+        None
+    };
+    Ok((operator_delegate_info, accounts_after_delegate))
+}
+
+/// Checks a refund of `amount` against the refund limits of the delegate that signed it.
+/// Under a daily limit the refund is counted on the OperatorDelegate PDA, which must then be
+/// passed writable. Refunds signed by the operator owner have no limits.
+///
+/// # Arguments
+/// * `operator_delegate_info` - The OperatorDelegate PDA from
+///   `validate_operator_refund_authority`, if a delegate signed
+/// * `amount` - The amount refunded
+pub fn apply_delegate_refund_limits(
+    operator_delegate_info: Option<&AccountInfo>,
+    amount: u64,
+) -> ProgramResult {
+    let Some(operator_delegate_info) = operator_delegate_info else {
+        return Ok(());
+    };
+
+    let mut operator_delegate =
+        OperatorDelegate::try_from_bytes(&operator_delegate_info.try_borrow_data()?)?;
+    operator_delegate.record_refund(amount, Clock::get()?.unix_timestamp)?;
+
+    if operator_delegate.max_daily_refund_amount != 0 {
+        // Validate operator_delegate is writable to count the refund
+        verify_owner_mutability(operator_delegate_info, &COMMERCE_PROGRAM_ID, true)?;
+        operator_delegate_info
+            .try_borrow_mut_data()?
+            .copy_from_slice(&operator_delegate.to_bytes());
+    }
+
+    Ok(())
+}
+
+/// Validates the operator fee ATA, which always belongs to the operator owner, creating it
+/// if missing when the owner signs. A delegate has no owner account to create it for, so
+/// the ATA must already exist.
+///
+/// # Arguments
+/// * `operator` - The loaded and PDA-validated operator
+/// * `operator_settlement_ata_info` - The operator owner's ATA receiving the fee
+/// * `operator_authority_info` - The signing operator authority (owner or delegate)
+/// * `mint_info` - The token mint for the ATA
+/// * `payer_info` - The account paying for creation (if needed)
+/// * `system_program_info` - The system program account
+/// * `token_program_info` - The token program account
+#[inline(always)]
+pub fn get_or_create_operator_settlement_ata(
+    operator: &Operator,
+    operator_settlement_ata_info: &AccountInfo,
+    operator_authority_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    payer_info: &AccountInfo,
+    system_program_info: &AccountInfo,
+    token_program_info: &AccountInfo,
+) -> ProgramResult {
+    if operator.owner.eq(operator_authority_info.key()) {
+        return get_or_create_ata(
+            operator_settlement_ata_info,
+            operator_authority_info,
+            mint_info,
+            payer_info,
+            system_program_info,
+            token_program_info,
+        );
+    }
+
+    get_ata(
+        operator_settlement_ata_info,
+        &operator.owner,
+        mint_info,
+        token_program_info,
+    )
+}
@@ -0,0 +1,190 @@
+extern crate alloc;
+
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::CommerceProgramError,
+    require_len,
+    state::{DenylistPolicy, DenylistProof, MerchantOperatorConfig, PolicyData, PolicyType},
+};
+
+/// Returns the config's denylist policy, if any.
+pub fn get_denylist_policy(policies: &[PolicyData]) -> Option<&DenylistPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Denylist) {
+        Some(PolicyData::Denylist(denylist)) => Some(denylist),
+        _ => None,
+    }
+}
+
+/// Checks `buyer` is not on the config's denylist. Without a denylist policy every buyer is
+/// allowed and `proof` is ignored.
+pub fn validate_buyer_not_denylisted(
+    policies: &[PolicyData],
+    buyer: &Pubkey,
+    proof: Option<&DenylistProof>,
+) -> Result<(), ProgramError> {
+    let Some(policy) = get_denylist_policy(policies) else {
+        return Ok(());
+    };
+
+    let proof = proof.ok_or(CommerceProgramError::DenylistProofRequired)?;
+    if !policy.allows(buyer, proof) {
+        return Err(CommerceProgramError::InvalidDenylistProof.into());
+    }
+
+    Ok(())
+}
+
+/// Parses a borsh `Option<DenylistProof>` at the start of `data`: 0 for None, or 1
+/// followed by lower (32), upper (32), leaf_index (u32) and a u32-prefixed list of 32-byte
+/// siblings for Some.
+pub fn parse_denylist_proof(data: &[u8]) -> Result<Option<DenylistProof>, ProgramError> {
+    require_len!(data, 1);
+    match data[0] {
+        0 => return Ok(None),
+        1 => {}
+        _ => return Err(ProgramError::InvalidInstructionData),
+    }
+
+    require_len!(data, 73); // 1 + 32 + 32 + 4 + 4
+    let lower: Pubkey = data[1..33].try_into().unwrap();
+    let upper: Pubkey = data[33..65].try_into().unwrap();
+    let leaf_index = u32::from_le_bytes(data[65..69].try_into().unwrap());
+    let proof_len = u32::from_le_bytes(data[69..73].try_into().unwrap()) as usize;
+
+    let proof_bytes = proof_len
+        .checked_mul(32)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    require_len!(data, 73 + proof_bytes);
+
+    let proof = data[73..73 + proof_bytes]
+        .chunks_exact(32)
+        .map(|node| node.try_into().unwrap())
+        .collect();
+
+    Ok(Some(DenylistProof {
+        lower,
+        upper,
+        leaf_index,
+        proof,
+    }))
+}
+
+/// Number of bytes `proof` takes as a borsh `Option<DenylistProof>`, so the instruction data
+/// after it can be parsed.
+pub fn denylist_proof_len(proof: Option<&DenylistProof>) -> usize {
+    match proof {
+        Some(proof) => 73 + proof.proof.len() * 32,
+        None => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{hashv, MemoPolicy};
+    use std::{vec, vec::Vec};
+
+    const BANNED: Pubkey = [5u8; 32];
+
+    // Single banned buyer: the gaps below and above it form a depth-1 tree
+    fn denylist() -> (Vec<PolicyData>, [DenylistProof; 2]) {
+        let gaps = [([0u8; 32], BANNED), (BANNED, [0xffu8; 32])];
+        let leaves = gaps.map(|(lower, upper)| DenylistPolicy::leaf_hash(&lower, &upper));
+        let policies = vec![PolicyData::Denylist(DenylistPolicy {
+            root: hashv(&[&leaves[0], &leaves[1]]),
+            depth: 1,
+        })];
+        let proofs = [0, 1].map(|i| DenylistProof {
+            lower: gaps[i].0,
+            upper: gaps[i].1,
+            leaf_index: i as u32,
+            proof: vec![leaves[1 - i]],
+        });
+        (policies, proofs)
+    }
+
+    fn encode(proof: &DenylistProof) -> Vec<u8> {
+        let mut data = vec![1];
+        data.extend_from_slice(&proof.lower);
+        data.extend_from_slice(&proof.upper);
+        data.extend_from_slice(&proof.leaf_index.to_le_bytes());
+        data.extend_from_slice(&(proof.proof.len() as u32).to_le_bytes());
+        proof
+            .proof
+            .iter()
+            .for_each(|node| data.extend_from_slice(node));
+        data
+    }
+
+    #[test]
+    fn test_parse_denylist_proof() {
+        let (_, proofs) = denylist();
+
+        assert_eq!(parse_denylist_proof(&[0]), Ok(None));
+        assert_eq!(
+            parse_denylist_proof(&encode(&proofs[1])),
+            Ok(Some(proofs[1].clone()))
+        );
+
+        // Missing tag, unknown tag and truncated proofs
+        let data = encode(&proofs[1]);
+        assert_eq!(
+            parse_denylist_proof(&[]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            parse_denylist_proof(&[2]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            parse_denylist_proof(&data[..data.len() - 1]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            parse_denylist_proof(&data[..72]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_denylist_proof_len() {
+        let (_, proofs) = denylist();
+
+        assert_eq!(denylist_proof_len(None), 1);
+        assert_eq!(
+            denylist_proof_len(Some(&proofs[1])),
+            encode(&proofs[1]).len()
+        );
+    }
+
+    #[test]
+    fn test_validate_buyer_not_denylisted_without_policy() {
+        let policies = vec![PolicyData::Memo(MemoPolicy {
+            memo_on_clear: true,
+            memo_on_refund: false,
+        })];
+        assert!(validate_buyer_not_denylisted(&policies, &BANNED, None).is_ok());
+    }
+
+    #[test]
+    fn test_validate_buyer_not_denylisted() {
+        let (policies, proofs) = denylist();
+
+        assert!(validate_buyer_not_denylisted(&policies, &[1u8; 32], Some(&proofs[0])).is_ok());
+        assert!(validate_buyer_not_denylisted(&policies, &[9u8; 32], Some(&proofs[1])).is_ok());
+
+        assert_eq!(
+            validate_buyer_not_denylisted(&policies, &[1u8; 32], None).unwrap_err(),
+            CommerceProgramError::DenylistProofRequired.into()
+        );
+        assert_eq!(
+            validate_buyer_not_denylisted(&policies, &BANNED, Some(&proofs[0])).unwrap_err(),
+            CommerceProgramError::InvalidDenylistProof.into()
+        );
+        assert_eq!(
+            validate_buyer_not_denylisted(&policies, &[9u8; 32], Some(&proofs[0])).unwrap_err(),
+            CommerceProgramError::InvalidDenylistProof.into()
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    error::CommerceProgramError,
+    state::{DirectSettlementPolicy, MerchantOperatorConfig, PolicyData, PolicyType},
+};
+
+/// Returns the config's direct settlement policy, if any.
+pub fn get_direct_settlement_policy(policies: &[PolicyData]) -> Option<&DirectSettlementPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::DirectSettlement) {
+        Some(PolicyData::DirectSettlement(direct_settlement)) => Some(direct_settlement),
+        _ => None,
+    }
+}
+
+/// Checks a config's policies at creation or when one is added: a payment MakePayment settles
+/// itself, under a direct settlement policy or a settlement policy with `auto_settle`, is never
+/// cleared by ClearPayment, so nothing ClearPayment enforces may be configured alongside. That
+/// is a chargeback window, an `on_clear` callback, or a settlement minimum or frequency.
+///
+/// # Returns
+/// * `DirectSettlementUnsupported` if the policies combine
+pub fn validate_immediate_settlement(policies: &[PolicyData]) -> ProgramResult {
+    let mut settles_immediately = get_direct_settlement_policy(policies).is_some();
+    let mut needs_clearing = false;
+    for policy in policies {
+        match policy {
+            PolicyData::Settlement(settlement) => {
+                settles_immediately |= settlement.auto_settle;
+                needs_clearing |= settlement.min_settlement_amount > 0
+                    || settlement.settlement_frequency_hours > 0;
+            }
+            PolicyData::Chargeback(_) => needs_clearing = true,
+            PolicyData::Callback(callback) => needs_clearing |= callback.on_clear,
+            _ => {}
+        }
+    }
+
+    if settles_immediately && needs_clearing {
+        return Err(CommerceProgramError::DirectSettlementUnsupported.into());
+    }
+    Ok(())
+}
+
+/// Splits the fee ATAs a direct settlement pays off the front of the remaining accounts, once
+/// the transfer hook accounts have been split off: the operator's fee ATA, then the platform's
+/// settlement ATA when the config has a platform fee policy. Both are expected under a direct
+/// settlement policy, whether or not the payment's amount is settled directly. They precede
+/// the rent payer, which would otherwise be mistaken for them.
+///
+/// # Returns
+/// * The operator fee ATA, the platform settlement ATA if any, and the accounts after them
+pub fn split_direct_settlement_atas(
+    remaining_accounts: &[AccountInfo],
+    has_platform_fee: bool,
+) -> Result<(&AccountInfo, Option<&AccountInfo>, &[AccountInfo]), ProgramError> {
+    let (operator_fee_ata_info, remaining_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if !has_platform_fee {
+        return Ok((operator_fee_ata_info, None, remaining_accounts));
+    }
+
+    let (platform_settlement_ata_info, remaining_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    Ok((
+        operator_fee_ata_info,
+        Some(platform_settlement_ata_info),
+        remaining_accounts,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{CallbackPolicy, ChargebackPolicy, SettlementPolicy};
+
+    fn settlement(min_settlement_amount: u64, auto_settle: bool) -> PolicyData {
+        PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount,
+            settlement_frequency_hours: 0,
+            auto_settle,
+        })
+    }
+
+    fn direct_settlement() -> PolicyData {
+        PolicyData::DirectSettlement(DirectSettlementPolicy { max_amount: 1_000 })
+    }
+
+    fn callback(on_clear: bool) -> PolicyData {
+        PolicyData::Callback(CallbackPolicy {
+            program: [7; 32],
+            on_clear,
+            on_refund: true,
+        })
+    }
+
+    #[test]
+    fn test_validate_immediate_settlement() {
+        let chargeback = PolicyData::Chargeback(ChargebackPolicy {
+            window_seconds: 3_600,
+            arbiter: [9; 32],
+        });
+        let unsupported = Err(CommerceProgramError::DirectSettlementUnsupported.into());
+
+        // Escrowed payments are cleared, so anything goes
+        assert_eq!(
+            validate_immediate_settlement(&[settlement(100, false), chargeback.clone()]),
+            Ok(())
+        );
+        // Refund callbacks still fire on immediately settled payments
+        assert_eq!(
+            validate_immediate_settlement(&[direct_settlement(), callback(false)]),
+            Ok(())
+        );
+        assert_eq!(
+            validate_immediate_settlement(&[direct_settlement(), settlement(0, false)]),
+            Ok(())
+        );
+
+        assert_eq!(
+            validate_immediate_settlement(&[direct_settlement(), chargeback.clone()]),
+            unsupported
+        );
+        assert_eq!(
+            validate_immediate_settlement(&[callback(true), direct_settlement()]),
+            unsupported
+        );
+        assert_eq!(
+            validate_immediate_settlement(&[direct_settlement(), settlement(100, false)]),
+            unsupported
+        );
+        assert_eq!(
+            validate_immediate_settlement(&[settlement(0, true), chargeback]),
+            unsupported
+        );
+        assert_eq!(
+            validate_immediate_settlement(&[settlement(100, true)]),
+            unsupported
+        );
+    }
+}
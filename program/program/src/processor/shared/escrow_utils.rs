@@ -0,0 +1,77 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+
+use crate::{
+    constants::ESCROW_AUTHORITY_SEED, error::CommerceProgramError, state::MerchantOperatorConfig,
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Validates the escrow authority PDA of a config and mint, the owner of the config's escrow
+/// ATA for the mint.
+///
+/// The escrow authority holds no data and only signs for this program, so escrowed funds
+/// move through the payment flow alone rather than on the merchant's say-so.
+///
+/// # Returns
+/// * The PDA's bump, or `EscrowAuthorityInvalidPda` if the account is not the PDA
+pub fn validate_escrow_authority(
+    escrow_authority_info: &AccountInfo,
+    merchant_operator_config: &Pubkey,
+    mint: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (pda, bump) = find_program_address(
+        &[
+            ESCROW_AUTHORITY_SEED,
+            merchant_operator_config.as_ref(),
+            mint.as_ref(),
+        ],
+        &COMMERCE_PROGRAM_ID,
+    );
+
+    if pda.ne(escrow_authority_info.key()) {
+        return Err(CommerceProgramError::EscrowAuthorityInvalidPda.into());
+    }
+
+    Ok(bump)
+}
+
+/// Signer seeds of the escrow authority PDA of a config and mint.
+pub fn escrow_authority_seeds<'a>(
+    merchant_operator_config: &'a Pubkey,
+    mint: &'a Pubkey,
+    bump: &'a [u8; 1],
+) -> [Seed<'a>; 4] {
+    [
+        Seed::from(ESCROW_AUTHORITY_SEED),
+        Seed::from(merchant_operator_config.as_ref()),
+        Seed::from(mint.as_ref()),
+        Seed::from(bump),
+    ]
+}
+
+/// Stops counting `count` payments in a mint as open on a config, once their funds have left
+/// the config's escrow for the settlement wallet or the buyer.
+///
+/// The config must be writable and not borrowed by the caller.
+///
+/// # Returns
+/// * The number of payments in the mint still open on the config
+pub fn release_open_payments(
+    merchant_operator_config_info: &AccountInfo,
+    mint: &Pubkey,
+    count: u32,
+) -> Result<u32, ProgramError> {
+    let mut merchant_operator_config_data = merchant_operator_config_info.try_borrow_mut_data()?;
+    let merchant_operator_config =
+        MerchantOperatorConfig::header_from_bytes(&merchant_operator_config_data)?;
+    merchant_operator_config.decrement_open_payments(
+        &mut merchant_operator_config_data,
+        mint,
+        count,
+    )?;
+    merchant_operator_config.open_payments(&merchant_operator_config_data, mint)
+}
@@ -8,7 +8,8 @@ use pinocchio::{
 
 use crate::{
     constants::{event_authority_pda, EVENT_AUTHORITY_SEED},
-    error::CommerceProgramError,
+    events::{EventDiscriminators, PaymentLifecycleStatus, PaymentStatusChangedEvent},
+    processor::verify_event_authority,
 };
 
 /// Validates the event authority PDA and emits an event via CPI.
@@ -29,9 +30,7 @@ pub fn emit_event(
     event_data: &[u8],
 ) -> ProgramResult {
     // Check that event authority PDA is valid.
-    if event_authority_info.key().ne(&event_authority_pda::ID) {
-        return Err(CommerceProgramError::InvalidEventAuthority.into());
-    }
+    verify_event_authority(event_authority_info)?;
 
     let signer_seeds = [
         Seed::from(EVENT_AUTHORITY_SEED),
@@ -53,3 +52,37 @@ pub fn emit_event(
 
     Ok(())
 }
+
+/// Emits a PaymentStatusChanged event for a payment moving from `from` to `to`.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+pub fn emit_payment_status_changed(
+    program_id: &Pubkey,
+    event_authority_info: &AccountInfo,
+    commerce_program_info: &AccountInfo,
+    buyer: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    order_id: u32,
+    from: PaymentLifecycleStatus,
+    to: PaymentLifecycleStatus,
+    actor: &Pubkey,
+) -> ProgramResult {
+    let event = PaymentStatusChangedEvent {
+        discriminator: EventDiscriminators::PaymentStatusChanged as u8,
+        buyer: *buyer,
+        merchant: *merchant,
+        operator: *operator,
+        order_id,
+        from,
+        to,
+        actor: *actor,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
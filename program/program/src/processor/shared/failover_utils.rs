@@ -0,0 +1,113 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::validate_operator_authority,
+    state::{
+        Merchant, MerchantOperatorConfig, Operator, OperatorFailoverPolicy, OperatorPermission,
+        PolicyData, PolicyType,
+    },
+};
+
+/// Returns the config's operator failover policy, if any.
+pub fn get_operator_failover_policy(policies: &[PolicyData]) -> Option<&OperatorFailoverPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::OperatorFailover) {
+        Some(PolicyData::OperatorFailover(operator_failover)) => Some(operator_failover),
+        _ => None,
+    }
+}
+
+/// Checks the merchant may clear in place of `operator`: the config needs an operator failover
+/// policy and the operator must have been inactive beyond its threshold at `now`.
+pub fn validate_operator_failover(
+    policies: &[PolicyData],
+    operator: &Operator,
+    now: i64,
+) -> Result<(), ProgramError> {
+    let operator_failover = get_operator_failover_policy(policies)
+        .ok_or(CommerceProgramError::OperatorFailoverPolicyRequired)?;
+
+    if !operator.is_inactive(operator_failover.inactivity_threshold_seconds, now) {
+        return Err(CommerceProgramError::OperatorStillActive.into());
+    }
+
+    Ok(())
+}
+
+/// Validates that `operator_authority_info` may clear payments: the operator owner or a
+/// delegate, as `validate_operator_authority` checks, or the merchant authority under
+/// `validate_operator_failover`.
+///
+/// # Returns
+/// * The remaining accounts after the OperatorDelegate PDA, if a delegate signed
+#[inline(always)]
+pub fn validate_clear_authority<'a>(
+    operator: &Operator,
+    operator_info: &AccountInfo,
+    merchant: &Merchant,
+    operator_authority_info: &AccountInfo,
+    remaining_accounts: &'a [AccountInfo],
+    policies: &[PolicyData],
+    now: i64,
+) -> Result<&'a [AccountInfo], ProgramError> {
+    if operator.owner.ne(operator_authority_info.key())
+        && merchant.owner.eq(operator_authority_info.key())
+    {
+        validate_operator_failover(policies, operator, now)?;
+        return Ok(remaining_accounts);
+    }
+
+    validate_operator_authority(
+        operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::Clear,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoPolicy;
+    use std::vec;
+
+    const LAST_ACTIVE_AT: i64 = 1_700_000_000;
+
+    fn operator() -> Operator {
+        Operator {
+            owner: [1u8; 32],
+            bump: 255,
+            pending_authority: [0u8; 32],
+            last_active_at: LAST_ACTIVE_AT,
+            has_freeze_list: false,
+            original_owner: [1u8; 32],
+        }
+    }
+
+    #[test]
+    fn test_validate_operator_failover() {
+        let policies = vec![PolicyData::OperatorFailover(OperatorFailoverPolicy {
+            inactivity_threshold_seconds: 3_600,
+        })];
+
+        assert!(validate_operator_failover(&policies, &operator(), LAST_ACTIVE_AT + 3_601).is_ok());
+        assert_eq!(
+            validate_operator_failover(&policies, &operator(), LAST_ACTIVE_AT + 3_600).unwrap_err(),
+            CommerceProgramError::OperatorStillActive.into()
+        );
+    }
+
+    #[test]
+    fn test_validate_operator_failover_without_policy() {
+        let policies = vec![PolicyData::Memo(MemoPolicy {
+            memo_on_clear: true,
+            memo_on_refund: false,
+        })];
+
+        assert_eq!(
+            validate_operator_failover(&policies, &operator(), i64::MAX).unwrap_err(),
+            CommerceProgramError::OperatorFailoverPolicyRequired.into()
+        );
+    }
+}
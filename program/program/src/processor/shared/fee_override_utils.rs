@@ -0,0 +1,121 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::{
+    constants::MAX_BPS,
+    error::CommerceProgramError,
+    processor::calculate_fees,
+    state::{
+        FeeOverride, FeeOverridePolicy, FeeType, MerchantOperatorConfig, PolicyData, PolicyType,
+    },
+};
+
+/// Returns the config's fee override policy, if any.
+pub fn get_fee_override_policy(policies: &[PolicyData]) -> Option<&FeeOverridePolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::FeeOverride) {
+        Some(PolicyData::FeeOverride(fee_override)) => Some(fee_override),
+        _ => None,
+    }
+}
+
+/// Checks `fee_override` takes no more of a payment of `amount` than the config's fee
+/// override policy allows.
+pub fn validate_fee_override(
+    policies: &[PolicyData],
+    amount: u64,
+    fee_override: &FeeOverride,
+) -> Result<(), ProgramError> {
+    let policy =
+        get_fee_override_policy(policies).ok_or(CommerceProgramError::FeeOverridePolicyRequired)?;
+
+    // Basis points above 100% would leave the payment unclearable
+    if fee_override.fee_type == FeeType::Bps && fee_override.fee > MAX_BPS {
+        return Err(CommerceProgramError::FeeOverrideExceedsMax.into());
+    }
+
+    let (fee_amount, _) = calculate_fees(amount, fee_override.fee, &fee_override.fee_type, &[])?;
+    let (max_fee_amount, _) = calculate_fees(amount, policy.max_fee, &policy.max_fee_type, &[])?;
+    if fee_amount > max_fee_amount {
+        return Err(CommerceProgramError::FeeOverrideExceedsMax.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoPolicy;
+    use std::vec;
+
+    fn fee_override_policy(max_fee: u64, max_fee_type: FeeType) -> PolicyData {
+        PolicyData::FeeOverride(FeeOverridePolicy {
+            max_fee,
+            max_fee_type,
+        })
+    }
+
+    #[test]
+    fn test_get_fee_override_policy() {
+        let policies = vec![
+            PolicyData::Memo(MemoPolicy {
+                memo_on_clear: true,
+                memo_on_refund: true,
+            }),
+            fee_override_policy(300, FeeType::Bps),
+        ];
+
+        assert_eq!(
+            get_fee_override_policy(&policies),
+            Some(&FeeOverridePolicy {
+                max_fee: 300,
+                max_fee_type: FeeType::Bps,
+            })
+        );
+        assert_eq!(get_fee_override_policy(&policies[..1]), None);
+    }
+
+    #[test]
+    fn test_validate_fee_override() {
+        let policies = vec![fee_override_policy(300, FeeType::Bps)];
+        let fee_override = |fee, fee_type| FeeOverride { fee, fee_type };
+
+        // 3% of 10_000 is 300
+        assert!(validate_fee_override(&policies, 10_000, &fee_override(300, FeeType::Bps)).is_ok());
+        assert!(validate_fee_override(&policies, 10_000, &fee_override(0, FeeType::Bps)).is_ok());
+        assert!(
+            validate_fee_override(&policies, 10_000, &fee_override(300, FeeType::Fixed)).is_ok()
+        );
+        assert_eq!(
+            validate_fee_override(&policies, 10_000, &fee_override(301, FeeType::Fixed))
+                .unwrap_err(),
+            CommerceProgramError::FeeOverrideExceedsMax.into()
+        );
+        assert_eq!(
+            validate_fee_override(&policies, 10_000, &fee_override(301, FeeType::Bps)).unwrap_err(),
+            CommerceProgramError::FeeOverrideExceedsMax.into()
+        );
+    }
+
+    #[test]
+    fn test_validate_fee_override_bps_above_max_bps() {
+        // A fixed maximum covering the whole payment still rejects fees above 100%
+        let policies = vec![fee_override_policy(u64::MAX, FeeType::Fixed)];
+        let fee_override = FeeOverride {
+            fee: MAX_BPS + 1,
+            fee_type: FeeType::Bps,
+        };
+
+        assert_eq!(
+            validate_fee_override(&policies, 10_000, &fee_override).unwrap_err(),
+            CommerceProgramError::FeeOverrideExceedsMax.into()
+        );
+    }
+
+    #[test]
+    fn test_validate_fee_override_requires_policy() {
+        assert_eq!(
+            validate_fee_override(&[], 10_000, &FeeOverride::EMPTY).unwrap_err(),
+            CommerceProgramError::FeeOverridePolicyRequired.into()
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey, ProgramResult};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::verify_owner_mutability,
+    state::{discriminator::Discriminator, Operator, OperatorFreezeList},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Splits the operator's optional OperatorFreezeList PDA off the end of the remaining
+/// accounts (once the accounts that trail it have been split off), so the accounts expected
+/// first (e.g. MerchantStats) are unaffected by its presence.
+pub fn split_operator_freeze_list(
+    remaining_accounts: &[AccountInfo],
+) -> (Option<&AccountInfo>, &[AccountInfo]) {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if is_operator_freeze_list(last) => (Some(last), rest),
+        _ => (None, remaining_accounts),
+    }
+}
+
+fn is_operator_freeze_list(account_info: &AccountInfo) -> bool {
+    account_info.is_owned_by(&COMMERCE_PROGRAM_ID)
+        && account_info.data_len() == OperatorFreezeList::LEN
+        && account_info
+            .try_borrow_data()
+            .is_ok_and(|data| data[0] == OperatorFreezeList::DISCRIMINATOR)
+}
+
+/// Validates the buyer is not on the operator's freeze list. An operator with a freeze list
+/// must have it passed, so leaving the list out cannot skip the check.
+///
+/// # Arguments
+/// * `operator_freeze_list_info` - The list split off by `split_operator_freeze_list`
+/// * `operator_key` - The operator the payment is made under
+/// * `operator` - The loaded operator, which records whether it has a freeze list
+/// * `buyer` - The payment's buyer
+pub fn validate_buyer_not_frozen(
+    operator_freeze_list_info: Option<&AccountInfo>,
+    operator_key: &Pubkey,
+    operator: &Operator,
+    buyer: &Pubkey,
+) -> ProgramResult {
+    let Some(operator_freeze_list_info) = operator_freeze_list_info else {
+        if operator.has_freeze_list {
+            return Err(CommerceProgramError::FreezeListRequired.into());
+        }
+        return Ok(());
+    };
+
+    // Validate operator_freeze_list is owned by this program
+    verify_owner_mutability(operator_freeze_list_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    let freeze_list_data = operator_freeze_list_info.try_borrow_data()?;
+    let freeze_list = OperatorFreezeList::try_from_bytes(&freeze_list_data)?;
+
+    freeze_list.validate_pda(operator_freeze_list_info.key())?;
+    freeze_list.validate_operator(operator_key)?;
+
+    if freeze_list.is_frozen(buyer) {
+        return Err(CommerceProgramError::BuyerFrozen.into());
+    }
+
+    Ok(())
+}
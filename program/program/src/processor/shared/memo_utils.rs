@@ -0,0 +1,171 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Instruction, program::invoke,
+    program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    constants::MEMO_PROGRAM_ID,
+    error::CommerceProgramError,
+    state::{MemoPolicy, MerchantOperatorConfig, PolicyData, PolicyType},
+};
+
+const ORDER_PREFIX: &[u8] = b"order:";
+const PAYMENT_PREFIX: &[u8] = b" payment:";
+
+/// Longest memo written by `write_transfer_memo`: a 10 digit order id and a 44 character
+/// base58 payment PDA.
+pub const MAX_TRANSFER_MEMO_LEN: usize = ORDER_PREFIX.len() + 10 + PAYMENT_PREFIX.len() + 44;
+
+/// Returns the config's memo policy, if any.
+pub fn get_memo_policy(policies: &[PolicyData]) -> Option<&MemoPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Memo) {
+        Some(PolicyData::Memo(memo)) => Some(memo),
+        _ => None,
+    }
+}
+
+/// Splits the optional memo program off the end of the remaining accounts, so the
+/// accounts expected first (e.g. MerchantStats) are unaffected by its presence.
+pub fn split_memo_program(
+    remaining_accounts: &[AccountInfo],
+) -> (Option<&AccountInfo>, &[AccountInfo]) {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if last.key() == &MEMO_PROGRAM_ID => (Some(last), rest),
+        _ => (None, remaining_accounts),
+    }
+}
+
+/// Writes `order:<order_id> payment:<payment PDA>` into `buf` and returns its length.
+pub fn write_transfer_memo(
+    order_id: u32,
+    payment: &Pubkey,
+    buf: &mut [u8; MAX_TRANSFER_MEMO_LEN],
+) -> Result<usize, ProgramError> {
+    let mut len = 0;
+
+    buf[..ORDER_PREFIX.len()].copy_from_slice(ORDER_PREFIX);
+    len += ORDER_PREFIX.len();
+
+    let mut digits = [0u8; 10];
+    let mut remaining = order_id;
+    let mut start = digits.len();
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (remaining % 10) as u8;
+        remaining /= 10;
+        if remaining == 0 {
+            break;
+        }
+    }
+    buf[len..len + digits.len() - start].copy_from_slice(&digits[start..]);
+    len += digits.len() - start;
+
+    buf[len..len + PAYMENT_PREFIX.len()].copy_from_slice(PAYMENT_PREFIX);
+    len += PAYMENT_PREFIX.len();
+
+    len += bs58::encode(payment)
+        .onto(&mut buf[len..])
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    Ok(len)
+}
+
+/// Logs the transfer memo through an SPL Memo CPI when `enabled`.
+///
+/// # Arguments
+/// * `enabled` - Whether the memo policy asks for a memo on this transfer
+/// * `memo_program_info` - The memo program, passed as the last remaining account
+/// * `order_id` - The payment's order id
+/// * `payment` - The payment PDA
+///
+/// # Errors
+/// Returns `CommerceProgramError::MemoProgramRequired` if a memo is required but the memo
+/// program was not passed.
+pub fn emit_transfer_memo(
+    enabled: bool,
+    memo_program_info: Option<&AccountInfo>,
+    order_id: u32,
+    payment: &Pubkey,
+) -> ProgramResult {
+    if !enabled {
+        return Ok(());
+    }
+
+    let Some(memo_program_info) = memo_program_info else {
+        return Err(CommerceProgramError::MemoProgramRequired.into());
+    };
+
+    let mut memo = [0u8; MAX_TRANSFER_MEMO_LEN];
+    let len = write_transfer_memo(order_id, payment, &mut memo)?;
+
+    invoke(
+        &Instruction {
+            program_id: &MEMO_PROGRAM_ID,
+            accounts: &[],
+            data: &memo[..len],
+        },
+        &[memo_program_info],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::RefundPolicy;
+    use std::{string::String, vec};
+
+    fn memo_string(order_id: u32, payment: &Pubkey) -> String {
+        let mut buf = [0u8; MAX_TRANSFER_MEMO_LEN];
+        let len = write_transfer_memo(order_id, payment, &mut buf).unwrap();
+        String::from_utf8(buf[..len].to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_write_transfer_memo() {
+        let payment = [0u8; 32];
+        assert_eq!(
+            memo_string(42, &payment),
+            "order:42 payment:11111111111111111111111111111111"
+        );
+        assert!(memo_string(0, &payment).starts_with("order:0 payment:"));
+    }
+
+    #[test]
+    fn test_write_transfer_memo_fits_longest() {
+        let payment = [255u8; 32];
+        let memo = memo_string(u32::MAX, &payment);
+        assert!(memo.starts_with("order:4294967295 payment:"));
+        assert_eq!(memo.len(), MAX_TRANSFER_MEMO_LEN);
+    }
+
+    #[test]
+    fn test_get_memo_policy() {
+        let memo = MemoPolicy {
+            memo_on_clear: true,
+            memo_on_refund: false,
+        };
+        let policies = vec![
+            PolicyData::Refund(RefundPolicy {
+                max_amount: 100,
+                max_time_after_purchase: 0,
+            }),
+            PolicyData::Memo(memo.clone()),
+        ];
+
+        assert_eq!(get_memo_policy(&policies), Some(&memo));
+        assert_eq!(get_memo_policy(&policies[..1]), None);
+    }
+
+    #[test]
+    fn test_emit_transfer_memo_disabled_is_noop() {
+        assert!(emit_transfer_memo(false, None, 1, &[0u8; 32]).is_ok());
+    }
+
+    #[test]
+    fn test_emit_transfer_memo_requires_memo_program() {
+        assert_eq!(
+            emit_transfer_memo(true, None, 1, &[0u8; 32]).unwrap_err(),
+            CommerceProgramError::MemoProgramRequired.into()
+        );
+    }
+}
@@ -1,12 +1,62 @@
 pub mod account_check;
+pub mod buyer_index_utils;
+pub mod callback_utils;
+pub mod chargeback_utils;
+pub mod compliance_utils;
+pub mod config_registry_utils;
+pub mod conversion_rate_utils;
+pub mod delegate_utils;
+pub mod denylist_utils;
+pub mod direct_settlement_utils;
+pub mod escrow_utils;
 pub mod event_utils;
+pub mod failover_utils;
+pub mod fee_override_utils;
+pub mod freeze_list_utils;
+pub mod memo_utils;
 pub mod mint_utils;
+pub mod order_utils;
+pub mod payment_note_utils;
 pub mod pda_utils;
-pub mod token_utils;
+pub mod platform_fee_utils;
+pub mod quote_utils;
+pub mod reference_utils;
+pub mod rent_payer_utils;
+pub mod risk_utils;
+pub mod settlement_batch_utils;
+pub mod split_settlement_utils;
+pub mod stats_utils;
+pub mod swap_utils;
+pub mod token_transfer_utils;
 pub mod utils;
 
 pub use account_check::*;
+pub use buyer_index_utils::*;
+pub use callback_utils::*;
+pub use chargeback_utils::*;
+pub use compliance_utils::*;
+pub use config_registry_utils::*;
+pub use conversion_rate_utils::*;
+pub use delegate_utils::*;
+pub use denylist_utils::*;
+pub use direct_settlement_utils::*;
+pub use escrow_utils::*;
 pub use event_utils::*;
+pub use failover_utils::*;
+pub use fee_override_utils::*;
+pub use freeze_list_utils::*;
+pub use memo_utils::*;
+pub use order_utils::*;
+pub use payment_note_utils::*;
 pub use pda_utils::*;
+pub use platform_fee_utils::*;
+pub use quote_utils::*;
+pub use reference_utils::*;
+pub use rent_payer_utils::*;
+pub use risk_utils::*;
+pub use settlement_batch_utils::*;
+pub use split_settlement_utils::*;
+pub use stats_utils::*;
+pub use swap_utils::*;
+pub use token_transfer_utils::*;
 // pub use utils::*;
-pub use token_utils::*;
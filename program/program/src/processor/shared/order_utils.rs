@@ -0,0 +1,162 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::CommerceProgramError,
+    events::{EventDiscriminators, OrderStatusChangedEvent},
+    processor::{emit_event, verify_owner_mutability},
+    state::{
+        discriminator::{AccountSerialize, Discriminator},
+        MerchantOperatorConfig, Order, OrderStatus,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Splits the optional Order PDA off the end of the remaining accounts (once the memo program
+/// and SettlementBatch PDA have been split off), so the accounts expected first (e.g.
+/// MerchantStats) are unaffected by its presence.
+pub fn split_order(remaining_accounts: &[AccountInfo]) -> (Option<&AccountInfo>, &[AccountInfo]) {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if is_order(last) => (Some(last), rest),
+        _ => (None, remaining_accounts),
+    }
+}
+
+/// Checks the Order split off by `split_order` against the payment: a payment made against an
+/// order must pass it, so every status change is recorded on it, and one made outside an order
+/// must not.
+///
+/// # Arguments
+/// * `order_info` - The Order PDA split off by `split_order`, if any
+/// * `has_order` - Whether the payment was recorded on an Order
+#[inline(always)]
+pub fn validate_payment_order(
+    order_info: Option<&AccountInfo>,
+    has_order: bool,
+) -> Result<(), ProgramError> {
+    match (order_info, has_order) {
+        (None, true) => Err(CommerceProgramError::OrderRequired.into()),
+        (Some(_), false) => Err(CommerceProgramError::OrderMismatch.into()),
+        _ => Ok(()),
+    }
+}
+
+fn is_order(account_info: &AccountInfo) -> bool {
+    account_info.is_owned_by(&COMMERCE_PROGRAM_ID)
+        && account_info.data_len() == Order::LEN
+        && account_info
+            .try_borrow_data()
+            .is_ok_and(|data| data[0] == Order::DISCRIMINATOR)
+}
+
+/// Validates a payment's order id. A payment outside an order, and an order's first payment,
+/// must pass the config's order id check; the order's later payments share the order id its
+/// first payment took, which must be the Order's.
+///
+/// # Arguments
+/// * `order_info` - The Order PDA split off by `split_order`, if any
+/// * `merchant_operator_config` - The config the payment is made under
+/// * `merchant_operator_config_info` - The config's account
+/// * `buyer_info` - The payment's buyer
+/// * `order_id` - The payment's order id
+#[inline(always)]
+pub fn validate_payment_order_id(
+    order_info: Option<&AccountInfo>,
+    merchant_operator_config: &MerchantOperatorConfig,
+    merchant_operator_config_info: &AccountInfo,
+    buyer_info: &AccountInfo,
+    order_id: u32,
+) -> Result<(), ProgramError> {
+    let Some(order_info) = order_info else {
+        return merchant_operator_config.validate_order_id(order_id);
+    };
+
+    let order_data = order_info.try_borrow_data()?;
+    let order = Order::try_from_bytes(&order_data)?;
+
+    order.validate_pda(order_info.key())?;
+    order.validate_payment(
+        merchant_operator_config_info.key(),
+        buyer_info.key(),
+        order_id,
+    )?;
+
+    if order.payment_count == 0 {
+        merchant_operator_config.validate_order_id(order_id)?;
+    }
+
+    Ok(())
+}
+
+/// Applies `update` to the Order a payment belongs to.
+///
+/// # Arguments
+/// * `order_info` - The Order PDA split off by `split_order`
+/// * `merchant_operator_config_info` - The config the payment was made under
+/// * `buyer_info` - The payment's buyer
+/// * `order_id` - The payment's order id
+/// * `update` - Mutation to record on the order
+///
+/// # Returns
+/// * `Result<Option<OrderStatus>, ProgramError>` - The order's new status if the update
+///   changed it
+#[inline(always)]
+pub fn update_order(
+    order_info: &AccountInfo,
+    merchant_operator_config_info: &AccountInfo,
+    buyer_info: &AccountInfo,
+    order_id: u32,
+    update: impl FnOnce(&mut Order) -> Result<(), ProgramError>,
+) -> Result<Option<OrderStatus>, ProgramError> {
+    // Validate order is writable and owned by this program
+    verify_owner_mutability(order_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    let mut order_data = order_info.try_borrow_mut_data()?;
+    let mut order = Order::try_from_bytes(&order_data)?;
+
+    order.validate_pda(order_info.key())?;
+    order.validate_payment(
+        merchant_operator_config_info.key(),
+        buyer_info.key(),
+        order_id,
+    )?;
+
+    let previous_status = order.status;
+    update(&mut order)?;
+    order_data.copy_from_slice(&order.to_bytes());
+
+    Ok((order.status != previous_status).then_some(order.status))
+}
+
+/// Emits an OrderStatusChanged event for `status`, if set.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+pub fn emit_order_status_changed(
+    program_id: &Pubkey,
+    event_authority_info: &AccountInfo,
+    commerce_program_info: &AccountInfo,
+    buyer: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    order_id: u32,
+    status: Option<OrderStatus>,
+) -> Result<(), ProgramError> {
+    let Some(status) = status else {
+        return Ok(());
+    };
+
+    let event = OrderStatusChangedEvent {
+        discriminator: EventDiscriminators::OrderStatusChanged as u8,
+        buyer: *buyer,
+        merchant: *merchant,
+        operator: *operator,
+        order_id,
+        status,
+    };
+
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
+}
@@ -0,0 +1,151 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, sysvars::rent::Rent,
+    ProgramResult,
+};
+
+use crate::{
+    constants::{PAYMENT_NOTE_CIPHERTEXT_LEN, PAYMENT_NOTE_SEED},
+    error::CommerceProgramError,
+    processor::{create_pda_account, verify_system_account},
+    require_len,
+    state::{AccountSerialize, EncryptedNote, PaymentNote},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Splits the PaymentNote PDA off the front of the remaining accounts when MakePayment
+/// attaches a note, ahead of the transfer hook accounts, so the accounts after it are
+/// unaffected by its presence.
+pub fn split_payment_note<'a>(
+    note: Option<&EncryptedNote>,
+    remaining_accounts: &'a [AccountInfo],
+) -> Result<(Option<&'a AccountInfo>, &'a [AccountInfo]), ProgramError> {
+    if note.is_none() {
+        return Ok((None, remaining_accounts));
+    }
+
+    match remaining_accounts.split_first() {
+        Some((payment_note_info, rest)) => Ok((Some(payment_note_info), rest)),
+        None => Err(ProgramError::NotEnoughAccountKeys),
+    }
+}
+
+/// Creates the PaymentNote PDA of `payment_info` holding `note`.
+///
+/// # Arguments
+/// * `payment_note_info` - The PaymentNote PDA split off by `split_payment_note`
+/// * `payment_info` - The payment the note is attached to
+/// * `merchant_info` - The merchant the note is encrypted to
+/// * `rent_payer_info` - Account funding the note's rent, refunded when it is closed
+/// * `rent` - Rent sysvar
+/// * `note` - The encrypted note
+#[inline(always)]
+pub fn create_payment_note(
+    payment_note_info: &AccountInfo,
+    payment_info: &AccountInfo,
+    merchant_info: &AccountInfo,
+    rent_payer_info: &AccountInfo,
+    rent: &Rent,
+    note: &EncryptedNote,
+) -> ProgramResult {
+    verify_system_account(payment_note_info, true)?;
+
+    let (pda, bump) = PaymentNote::find_pda(payment_info.key());
+    if pda.ne(payment_note_info.key()) {
+        return Err(CommerceProgramError::PaymentNoteInvalidPda.into());
+    }
+
+    let bump_seed = [bump];
+    let signer_seeds = [
+        Seed::from(PAYMENT_NOTE_SEED),
+        Seed::from(payment_info.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(
+        rent_payer_info,
+        rent,
+        PaymentNote::LEN,
+        &COMMERCE_PROGRAM_ID,
+        payment_note_info,
+        signer_seeds,
+        None,
+    )?;
+
+    let payment_note = PaymentNote {
+        payment: *payment_info.key(),
+        merchant: *merchant_info.key(),
+        bump,
+        rent_payer: *rent_payer_info.key(),
+        ciphertext: note.ciphertext,
+    };
+    payment_note_info
+        .try_borrow_mut_data()?
+        .copy_from_slice(&payment_note.to_bytes());
+
+    Ok(())
+}
+
+/// Parses a borsh `Option<EncryptedNote>` at the start of `data`: 0 for None, or 1 followed
+/// by the ciphertext for Some.
+pub fn parse_encrypted_note(data: &[u8]) -> Result<Option<EncryptedNote>, ProgramError> {
+    require_len!(data, 1);
+    match data[0] {
+        0 => Ok(None),
+        1 => {
+            require_len!(data, 1 + PAYMENT_NOTE_CIPHERTEXT_LEN);
+            Ok(Some(EncryptedNote {
+                ciphertext: data[1..1 + PAYMENT_NOTE_CIPHERTEXT_LEN].try_into().unwrap(),
+            }))
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    #[test]
+    fn test_parse_encrypted_note() {
+        assert_eq!(parse_encrypted_note(&[0]), Ok(None));
+
+        let mut data = vec![1];
+        data.extend_from_slice(&[7u8; PAYMENT_NOTE_CIPHERTEXT_LEN]);
+        assert_eq!(
+            parse_encrypted_note(&data),
+            Ok(Some(EncryptedNote {
+                ciphertext: [7u8; PAYMENT_NOTE_CIPHERTEXT_LEN],
+            }))
+        );
+
+        // Missing tag, unknown tag and truncated ciphertext
+        assert_eq!(
+            parse_encrypted_note(&[]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            parse_encrypted_note(&[2]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            parse_encrypted_note(&data[..data.len() - 1]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_split_payment_note() {
+        let note = EncryptedNote {
+            ciphertext: [7u8; PAYMENT_NOTE_CIPHERTEXT_LEN],
+        };
+
+        let (payment_note_info, remaining_accounts) = split_payment_note(None, &[]).unwrap();
+        assert!(payment_note_info.is_none());
+        assert!(remaining_accounts.is_empty());
+
+        assert_eq!(
+            split_payment_note(Some(&note), &[]).unwrap_err(),
+            ProgramError::NotEnoughAccountKeys
+        );
+    }
+}
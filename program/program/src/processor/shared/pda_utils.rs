@@ -59,3 +59,25 @@ pub fn create_pda_account<const N: usize>(
         .invoke_signed(&signers)
     }
 }
+
+/// Resize a program-owned account to `space`, with the payer topping its lamports up to the
+/// rent-exempt minimum for the new size.
+pub fn realloc_pda_account(
+    payer: &AccountInfo,
+    rent: &Rent,
+    space: usize,
+    pda_account: &AccountInfo,
+) -> ProgramResult {
+    let required_lamports = rent
+        .minimum_balance(space)
+        .saturating_sub(pda_account.lamports());
+    if required_lamports > 0 {
+        Transfer {
+            from: payer,
+            to: pda_account,
+            lamports: required_lamports,
+        }
+        .invoke()?;
+    }
+    pda_account.resize(space)
+}
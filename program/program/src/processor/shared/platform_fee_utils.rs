@@ -0,0 +1,24 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::state::{MerchantOperatorConfig, PlatformFeePolicy, PolicyData, PolicyType};
+
+/// Returns the config's platform fee policy, if any.
+pub fn get_platform_fee_policy(policies: &[PolicyData]) -> Option<&PlatformFeePolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::PlatformFee) {
+        Some(PolicyData::PlatformFee(platform_fee)) => Some(platform_fee),
+        _ => None,
+    }
+}
+
+/// Splits the platform's settlement ATA off the end of the remaining accounts (once the
+/// split settlement destinations, if any, have been split off).
+///
+/// # Returns
+/// * The platform settlement ATA and the accounts before it
+pub fn split_platform_settlement_ata(
+    remaining_accounts: &[AccountInfo],
+) -> Result<(&AccountInfo, &[AccountInfo]), ProgramError> {
+    remaining_accounts
+        .split_last()
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
@@ -0,0 +1,55 @@
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey, ProgramResult};
+
+use crate::{
+    processor::verify_owner_mutability,
+    state::{discriminator::Discriminator, Quote},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Splits the optional Quote PDA off the end of the remaining accounts (once references have
+/// been split off), so the Order and the accounts before it are unaffected by its presence.
+pub fn split_quote(remaining_accounts: &[AccountInfo]) -> (Option<&AccountInfo>, &[AccountInfo]) {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if is_quote(last) => (Some(last), rest),
+        _ => (None, remaining_accounts),
+    }
+}
+
+fn is_quote(account_info: &AccountInfo) -> bool {
+    account_info.is_owned_by(&COMMERCE_PROGRAM_ID)
+        && account_info.data_len() == Quote::LEN
+        && account_info
+            .try_borrow_data()
+            .is_ok_and(|data| data[0] == Quote::DISCRIMINATOR)
+}
+
+/// Validates a payment against the Quote it references, if any.
+///
+/// # Arguments
+/// * `quote_info` - The Quote PDA split off by `split_quote`
+/// * `merchant_operator_config` - The config the payment is made under
+/// * `mint` - The payment's mint
+/// * `order_id` - The payment's order id
+/// * `amount` - The payment's amount
+/// * `now` - Current unix timestamp
+#[inline(always)]
+pub fn validate_quote(
+    quote_info: Option<&AccountInfo>,
+    merchant_operator_config: &Pubkey,
+    mint: &Pubkey,
+    order_id: u32,
+    amount: u64,
+    now: i64,
+) -> ProgramResult {
+    let Some(quote_info) = quote_info else {
+        return Ok(());
+    };
+
+    verify_owner_mutability(quote_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    let quote_data = quote_info.try_borrow_data()?;
+    let quote = Quote::try_from_bytes(&quote_data)?;
+
+    quote.validate_pda(quote_info.key())?;
+    quote.validate_payment(merchant_operator_config, mint, order_id, amount, now)
+}
@@ -0,0 +1,42 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    constants::MAX_PAYMENT_REFERENCES, error::CommerceProgramError, ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Splits the Solana Pay reference keys off the end of the remaining accounts, so the
+/// accounts expected first (e.g. MerchantStats) are unaffected by their presence.
+///
+/// References are the trailing read-only, non-signer accounts not owned by this program;
+/// every optional PDA that can precede them is owned by it.
+///
+/// # Returns
+/// * The references, zero-padded to `MAX_PAYMENT_REFERENCES`, and the accounts before them
+pub fn split_references(
+    remaining_accounts: &[AccountInfo],
+) -> Result<([Pubkey; MAX_PAYMENT_REFERENCES], &[AccountInfo]), ProgramError> {
+    let count = remaining_accounts
+        .iter()
+        .rev()
+        .take_while(|account_info| is_reference(account_info))
+        .count();
+    if count > MAX_PAYMENT_REFERENCES {
+        return Err(CommerceProgramError::TooManyPaymentReferences.into());
+    }
+
+    let (remaining_accounts, reference_infos) =
+        remaining_accounts.split_at(remaining_accounts.len() - count);
+
+    let mut references = [[0u8; 32]; MAX_PAYMENT_REFERENCES];
+    for (reference, reference_info) in references.iter_mut().zip(reference_infos) {
+        *reference = *reference_info.key();
+    }
+
+    Ok((references, remaining_accounts))
+}
+
+fn is_reference(account_info: &AccountInfo) -> bool {
+    !account_info.is_owned_by(&COMMERCE_PROGRAM_ID)
+        && !account_info.is_writable()
+        && !account_info.is_signer()
+}
@@ -0,0 +1,93 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::verify_writable,
+    state::{MerchantOperatorConfig, PolicyData, PolicyType, RentRebatePolicy},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Splits the optional rent payer off the front of the remaining accounts, ahead of the
+/// operator delegate, so the accounts expected after it are unaffected by its presence.
+///
+/// The rent payer is a leading writable account not owned by this program; the delegate and
+/// every optional PDA that can follow it are owned by it.
+pub fn split_rent_payer(
+    remaining_accounts: &[AccountInfo],
+) -> (Option<&AccountInfo>, &[AccountInfo]) {
+    match remaining_accounts.split_first() {
+        Some((first, rest)) if is_rent_payer(first) => (Some(first), rest),
+        _ => (None, remaining_accounts),
+    }
+}
+
+fn is_rent_payer(account_info: &AccountInfo) -> bool {
+    !account_info.is_owned_by(&COMMERCE_PROGRAM_ID) && account_info.is_writable()
+}
+
+/// Resolves the account a closed payment's rent is refunded to: the rent payer recorded by
+/// MakePayment, or the fee payer when it paid the rent itself.
+///
+/// # Arguments
+/// * `rent_payer` - The payment's recorded rent payer, zeroed when the fee payer paid
+/// * `rent_payer_info` - The rent payer split off by `split_rent_payer`, if passed
+/// * `fee_payer_info` - The closing transaction's fee payer
+///
+/// # Returns
+/// * The account to credit, or `RentPayerMismatch` if the recorded rent payer is not passed
+pub fn rent_refund_destination<'a>(
+    rent_payer: &Pubkey,
+    rent_payer_info: Option<&'a AccountInfo>,
+    fee_payer_info: &'a AccountInfo,
+) -> Result<&'a AccountInfo, ProgramError> {
+    if *rent_payer == Pubkey::default() {
+        return Ok(fee_payer_info);
+    }
+
+    match rent_payer_info {
+        Some(rent_payer_info) if rent_payer_info.key() == rent_payer => Ok(rent_payer_info),
+        _ => Err(CommerceProgramError::RentPayerMismatch.into()),
+    }
+}
+
+/// Returns the config's rent rebate policy, if any.
+pub fn get_rent_rebate_policy(policies: &[PolicyData]) -> Option<&RentRebatePolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::RentRebate) {
+        Some(PolicyData::RentRebate(rent_rebate)) => Some(rent_rebate),
+        _ => None,
+    }
+}
+
+/// Pays the buyer their share of a payment's rent under the config's rent rebate policy, just
+/// before the payment is closed, leaving the rest for its rent refund destination.
+///
+/// # Arguments
+/// * `policies` - The payment's config policies
+/// * `payment_info` - The payment about to be closed
+/// * `buyer_info` - The payment's buyer, which must be writable under the policy
+///
+/// # Returns
+/// * `InvalidAccountData` if the config has the policy and the buyer is read-only
+pub fn rebate_payment_rent(
+    policies: &[PolicyData],
+    payment_info: &AccountInfo,
+    buyer_info: &AccountInfo,
+) -> ProgramResult {
+    let Some(rent_rebate) = get_rent_rebate_policy(policies) else {
+        return Ok(());
+    };
+
+    verify_writable(buyer_info)?;
+
+    let payment_lamports = payment_info.lamports();
+    let rebate = rent_rebate.buyer_share(payment_lamports);
+    let buyer_lamports = buyer_info.lamports();
+    *buyer_info.try_borrow_mut_lamports()? = buyer_lamports
+        .checked_add(rebate)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    *payment_info.try_borrow_mut_lamports()? = payment_lamports - rebate;
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::verify_owner_mutability,
+    state::{
+        discriminator::{AccountSerialize, Discriminator},
+        MerchantOperatorConfig, PolicyData, PolicyType, RiskPolicy, RiskState,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Most RiskState accounts a payment uses: the config-wide one and the buyer's.
+const MAX_RISK_STATES: usize = 2;
+
+/// Returns the config's risk policy, if any.
+pub fn get_risk_policy(policies: &[PolicyData]) -> Option<&RiskPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Risk) {
+        Some(PolicyData::Risk(risk)) => Some(risk),
+        _ => None,
+    }
+}
+
+/// Splits the RiskState accounts off the end of the remaining accounts (once the Order and
+/// any references have been split off), so the accounts expected first (e.g. MerchantStats)
+/// are unaffected by their presence.
+///
+/// # Returns
+/// * The RiskState accounts and the accounts before them
+pub fn split_risk_states(remaining_accounts: &[AccountInfo]) -> (&[AccountInfo], &[AccountInfo]) {
+    let count = remaining_accounts
+        .iter()
+        .rev()
+        .take(MAX_RISK_STATES)
+        .take_while(|account_info| is_risk_state(account_info))
+        .count();
+
+    let (remaining_accounts, risk_state_infos) =
+        remaining_accounts.split_at(remaining_accounts.len() - count);
+    (risk_state_infos, remaining_accounts)
+}
+
+fn is_risk_state(account_info: &AccountInfo) -> bool {
+    account_info.is_owned_by(&COMMERCE_PROGRAM_ID)
+        && account_info.data_len() == RiskState::LEN
+        && account_info
+            .try_borrow_data()
+            .is_ok_and(|data| data[0] == RiskState::DISCRIMINATOR)
+}
+
+/// Checks `amount` against the config's maximum payment amount. Without a risk policy, or
+/// with the limit off, any amount is allowed.
+pub fn validate_payment_amount(policies: &[PolicyData], amount: u64) -> ProgramResult {
+    match get_risk_policy(policies) {
+        Some(risk) if risk.max_payment_amount != 0 && amount > risk.max_payment_amount => {
+            Err(CommerceProgramError::PaymentAmountExceedsRiskLimit.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Enforces the config's risk policy on a payment of `amount` by `buyer_info`, counting it on
+/// the RiskState accounts passed.
+///
+/// # Arguments
+/// * `policies` - The config's policies
+/// * `risk_state_infos` - The RiskState accounts split off by `split_risk_states`
+/// * `merchant_operator_config_info` - The config the payment is made under
+/// * `buyer_info` - The payment's buyer
+/// * `amount` - The payment amount
+/// * `now` - Current unix timestamp
+///
+/// # Returns
+/// * `ProgramResult` - Success if the payment is within every limit the policy sets, and the
+///   RiskState accounts each limit needs were passed
+pub fn apply_risk_policy(
+    policies: &[PolicyData],
+    risk_state_infos: &[AccountInfo],
+    merchant_operator_config_info: &AccountInfo,
+    buyer_info: &AccountInfo,
+    amount: u64,
+    now: i64,
+) -> ProgramResult {
+    let Some(risk) = get_risk_policy(policies) else {
+        return Ok(());
+    };
+
+    validate_payment_amount(policies, amount)?;
+
+    let mut volume_counted = false;
+    let mut buyer_counted = false;
+    for risk_state_info in risk_state_infos {
+        // Validate risk_state is writable and owned by this program
+        verify_owner_mutability(risk_state_info, &COMMERCE_PROGRAM_ID, true)?;
+
+        let mut risk_state_data = risk_state_info.try_borrow_mut_data()?;
+        let mut risk_state = RiskState::try_from_bytes(&risk_state_data)?;
+        risk_state.validate_pda(risk_state_info.key())?;
+        risk_state.validate_merchant_operator_config(merchant_operator_config_info.key())?;
+
+        risk_state.record_payment(amount, now)?;
+
+        if risk_state.is_config_wide() {
+            if risk.max_daily_volume != 0 && risk_state.daily_volume > risk.max_daily_volume {
+                return Err(CommerceProgramError::DailyVolumeLimitExceeded.into());
+            }
+            volume_counted = true;
+        } else if risk_state.buyer.eq(buyer_info.key()) {
+            if risk.max_payments_per_buyer_per_day != 0
+                && risk_state.daily_payment_count > risk.max_payments_per_buyer_per_day
+            {
+                return Err(CommerceProgramError::BuyerDailyPaymentLimitExceeded.into());
+            }
+            buyer_counted = true;
+        } else {
+            return Err(CommerceProgramError::RiskStateMismatch.into());
+        }
+
+        risk_state_data.copy_from_slice(&risk_state.to_bytes());
+    }
+
+    if (risk.max_daily_volume != 0 && !volume_counted)
+        || (risk.max_payments_per_buyer_per_day != 0 && !buyer_counted)
+    {
+        return Err(CommerceProgramError::RiskStateRequired.into());
+    }
+
+    Ok(())
+}
+
+/// Releases the uncaptured part of a hold from the RiskState accounts passed, so the config's
+/// daily limits only count what was captured.
+///
+/// # Arguments
+/// * `policies` - The config's policies
+/// * `risk_state_infos` - The RiskState accounts split off by `split_risk_states`
+/// * `merchant_operator_config_info` - The config the payment was made under
+/// * `buyer_info` - The payment's buyer
+/// * `released_amount` - Amount of the hold released back to the buyer
+/// * `released_in_full` - Whether nothing was captured, so the payment no longer counts
+/// * `created_at` - When the hold was made
+///
+/// # Returns
+/// * `ProgramResult` - Success once every limit the policy sets has been released from its
+///   RiskState account, or `RiskStateRequired` if one was not passed
+pub fn release_risk_hold(
+    policies: &[PolicyData],
+    risk_state_infos: &[AccountInfo],
+    merchant_operator_config_info: &AccountInfo,
+    buyer_info: &AccountInfo,
+    released_amount: u64,
+    released_in_full: bool,
+    created_at: i64,
+) -> ProgramResult {
+    let Some(risk) = get_risk_policy(policies) else {
+        return Ok(());
+    };
+
+    let mut volume_released = false;
+    let mut buyer_released = false;
+    for risk_state_info in risk_state_infos {
+        // Validate risk_state is writable and owned by this program
+        verify_owner_mutability(risk_state_info, &COMMERCE_PROGRAM_ID, true)?;
+
+        let mut risk_state_data = risk_state_info.try_borrow_mut_data()?;
+        let mut risk_state = RiskState::try_from_bytes(&risk_state_data)?;
+        risk_state.validate_pda(risk_state_info.key())?;
+        risk_state.validate_merchant_operator_config(merchant_operator_config_info.key())?;
+
+        if risk_state.is_config_wide() {
+            volume_released = true;
+        } else if risk_state.buyer.eq(buyer_info.key()) {
+            buyer_released = true;
+        } else {
+            return Err(CommerceProgramError::RiskStateMismatch.into());
+        }
+
+        risk_state.release_hold(released_amount, released_in_full, created_at);
+        risk_state_data.copy_from_slice(&risk_state.to_bytes());
+    }
+
+    if (risk.max_daily_volume != 0 && !volume_released)
+        || (risk.max_payments_per_buyer_per_day != 0 && released_in_full && !buyer_released)
+    {
+        return Err(CommerceProgramError::RiskStateRequired.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+    use alloc::vec;
+
+    fn risk_policies(max_payment_amount: u64) -> alloc::vec::Vec<PolicyData> {
+        vec![PolicyData::Risk(RiskPolicy {
+            max_payment_amount,
+            max_daily_volume: 0,
+            max_payments_per_buyer_per_day: 0,
+        })]
+    }
+
+    #[test]
+    fn test_validate_payment_amount_without_policy() {
+        assert!(validate_payment_amount(&[], u64::MAX).is_ok());
+        assert!(validate_payment_amount(&risk_policies(0), u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_validate_payment_amount() {
+        let policies = risk_policies(1_000);
+
+        assert!(validate_payment_amount(&policies, 1_000).is_ok());
+        assert_eq!(
+            validate_payment_amount(&policies, 1_001).unwrap_err(),
+            CommerceProgramError::PaymentAmountExceedsRiskLimit.into()
+        );
+    }
+}
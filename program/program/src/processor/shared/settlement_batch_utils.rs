@@ -0,0 +1,71 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{
+    processor::verify_owner_mutability,
+    state::{
+        discriminator::{AccountSerialize, Discriminator},
+        MerchantOperatorConfig, PolicyData, PolicyType, SettlementBatch, SettlementPolicy,
+    },
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Returns the config's settlement policy, if any.
+pub fn get_settlement_policy(policies: &[PolicyData]) -> Option<&SettlementPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Settlement) {
+        Some(PolicyData::Settlement(settlement)) => Some(settlement),
+        _ => None,
+    }
+}
+
+/// Splits the optional SettlementBatch PDA off the end of the remaining accounts (once the
+/// memo program has been split off), so the accounts expected first (e.g. MerchantStats)
+/// are unaffected by its presence.
+pub fn split_settlement_batch(
+    remaining_accounts: &[AccountInfo],
+) -> (Option<&AccountInfo>, &[AccountInfo]) {
+    match remaining_accounts.split_last() {
+        Some((last, rest)) if is_settlement_batch(last) => (Some(last), rest),
+        _ => (None, remaining_accounts),
+    }
+}
+
+fn is_settlement_batch(account_info: &AccountInfo) -> bool {
+    account_info.is_owned_by(&COMMERCE_PROGRAM_ID)
+        && account_info.data_len() == SettlementBatch::LEN
+        && account_info
+            .try_borrow_data()
+            .is_ok_and(|data| data[0] == SettlementBatch::DISCRIMINATOR)
+}
+
+/// Adds a cleared payment's merchant amount to the SettlementBatch account.
+///
+/// # Arguments
+/// * `settlement_batch_info` - The SettlementBatch PDA split off by `split_settlement_batch`
+/// * `merchant_operator_config_info` - The config the payment was made under
+/// * `mint_info` - The payment token mint
+/// * `merchant_amount` - Amount left in escrow for the merchant after the operator fee
+///
+/// # Returns
+/// * `ProgramResult` - Success if the batch was validated and updated
+#[inline(always)]
+pub fn record_settlement_batch_clear(
+    settlement_batch_info: &AccountInfo,
+    merchant_operator_config_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    merchant_amount: u64,
+) -> ProgramResult {
+    // Validate settlement_batch is writable and owned by this program
+    verify_owner_mutability(settlement_batch_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    let mut settlement_batch_data = settlement_batch_info.try_borrow_mut_data()?;
+    let mut settlement_batch = SettlementBatch::try_from_bytes(&settlement_batch_data)?;
+
+    settlement_batch.validate_pda(settlement_batch_info.key())?;
+    settlement_batch
+        .validate_config_and_mint(merchant_operator_config_info.key(), mint_info.key())?;
+
+    settlement_batch.record_clear(merchant_amount)?;
+    settlement_batch_data.copy_from_slice(&settlement_batch.to_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Signer, program_error::ProgramError, pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::{get_ata, token_account_owner, TokenTransfer},
+    state::{
+        MerchantOperatorConfig, PolicyData, PolicyType, SplitSettlementPolicy,
+        MAX_SPLIT_DESTINATIONS,
+    },
+};
+
+/// Returns the config's split settlement policy, if any.
+pub fn get_split_settlement_policy(policies: &[PolicyData]) -> Option<&SplitSettlementPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::SplitSettlement) {
+        Some(PolicyData::SplitSettlement(split_settlement)) => Some(split_settlement),
+        _ => None,
+    }
+}
+
+/// Splits the split settlement destination ATAs off the end of the remaining accounts (once
+/// the memo program, SettlementBatch and Order have been split off), one per destination in
+/// the policy's order.
+///
+/// # Returns
+/// * The destination ATAs and the accounts before them
+pub fn split_settlement_destinations<'a>(
+    remaining_accounts: &'a [AccountInfo],
+    split_settlement: &SplitSettlementPolicy,
+) -> Result<(&'a [AccountInfo], &'a [AccountInfo]), ProgramError> {
+    let split = remaining_accounts
+        .len()
+        .checked_sub(split_settlement.active_shares().len())
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let (remaining_accounts, destination_infos) = remaining_accounts.split_at(split);
+    Ok((destination_infos, remaining_accounts))
+}
+
+/// Validates that each destination is the writable `mint` ATA of the wallet the policy
+/// commits to at its position.
+///
+/// # Arguments
+/// * `destination_infos` - The destination ATAs split off by `split_settlement_destinations`
+/// * `split_settlement` - The config's split settlement policy
+/// * `mint_info` - The payment token mint
+/// * `token_program_info` - The token program account
+///
+/// # Returns
+/// * `ProgramResult` - Success if the destinations' owners hash to the policy's commitment
+pub fn validate_split_settlement_destinations(
+    destination_infos: &[AccountInfo],
+    split_settlement: &SplitSettlementPolicy,
+    mint_info: &AccountInfo,
+    token_program_info: &AccountInfo,
+) -> ProgramResult {
+    let mut wallets: [Pubkey; MAX_SPLIT_DESTINATIONS] = [[0; 32]; MAX_SPLIT_DESTINATIONS];
+
+    for (wallet, destination_info) in wallets.iter_mut().zip(destination_infos) {
+        if !destination_info.is_writable() {
+            return Err(CommerceProgramError::InvalidSplitSettlementDestinations.into());
+        }
+
+        *wallet = token_account_owner(destination_info, token_program_info)
+            .ok_or(CommerceProgramError::InvalidSplitSettlementDestinations)?;
+
+        get_ata(destination_info, wallet, mint_info, token_program_info)
+            .map_err(|_| CommerceProgramError::InvalidSplitSettlementDestinations)?;
+    }
+
+    let hash = SplitSettlementPolicy::destinations_hash(&wallets[..destination_infos.len()]);
+    if hash != split_settlement.destinations_hash {
+        return Err(CommerceProgramError::InvalidSplitSettlementDestinations.into());
+    }
+
+    Ok(())
+}
+
+/// Transfers the merchant's share of a payment from escrow to the split destinations.
+///
+/// # Arguments
+/// * `token_transfer` - The token program, mint and transfer hook accounts to transfer with
+/// * `merchant_escrow_ata_info` - The merchant's escrow ATA holding the payment
+/// * `escrow_authority_info` - The escrow authority PDA, which owns the escrow
+/// * `destination_infos` - The destination ATAs validated by
+///   `validate_split_settlement_destinations`
+/// * `split_settlement` - The config's split settlement policy
+/// * `merchant_amount` - Amount left for the merchant after the operator fee
+/// * `signers` - The escrow authority PDA's signer seeds
+///
+/// # Returns
+/// * `ProgramResult` - Success if every non-zero part was transferred
+pub fn transfer_split_settlement(
+    token_transfer: &TokenTransfer,
+    merchant_escrow_ata_info: &AccountInfo,
+    escrow_authority_info: &AccountInfo,
+    destination_infos: &[AccountInfo],
+    split_settlement: &SplitSettlementPolicy,
+    merchant_amount: u64,
+    signers: &[Signer],
+) -> ProgramResult {
+    let parts = split_settlement.split(merchant_amount);
+
+    for (destination_info, &amount) in destination_infos.iter().zip(&parts) {
+        if amount == 0 {
+            continue;
+        }
+
+        token_transfer.transfer(
+            merchant_escrow_ata_info,
+            destination_info,
+            escrow_authority_info,
+            amount,
+            signers,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{
+    processor::verify_owner_mutability,
+    state::{discriminator::AccountSerialize, MerchantStats},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Applies `update` to the optional MerchantStats account.
+///
+/// # Arguments
+/// * `remaining_accounts` - Accounts after the instruction's fixed accounts (and any
+///   OperatorDelegate PDA); the MerchantStats PDA is expected first, if passed at all
+/// * `merchant_info` - The merchant the payment belongs to
+/// * `mint_info` - The payment token mint
+/// * `update` - Mutation to record on the stats
+///
+/// # Returns
+/// * `ProgramResult` - Success if no stats account was passed, or it was validated and updated
+#[inline(always)]
+pub fn update_merchant_stats(
+    remaining_accounts: &[AccountInfo],
+    merchant_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    update: impl FnOnce(&mut MerchantStats),
+) -> ProgramResult {
+    let Some(merchant_stats_info) = remaining_accounts.first() else {
+        return Ok(());
+    };
+
+    // Validate merchant_stats is writable and owned by this program
+    verify_owner_mutability(merchant_stats_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    let mut merchant_stats_data = merchant_stats_info.try_borrow_mut_data()?;
+    let mut merchant_stats = MerchantStats::try_from_bytes(&merchant_stats_data)?;
+
+    merchant_stats.validate_pda(merchant_stats_info.key())?;
+    merchant_stats.validate_merchant_and_mint(merchant_info.key(), mint_info.key())?;
+
+    update(&mut merchant_stats);
+    merchant_stats_data.copy_from_slice(&merchant_stats.to_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke_signed,
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    constants::JUPITER_V6_PROGRAM_ID,
+    error::CommerceProgramError,
+    state::{ConversionPolicy, MerchantOperatorConfig, PolicyData, PolicyType, SwapAdapter},
+};
+
+// Anchor instruction discriminators of Jupiter v6's exact-input routes: Sha256(global:<name>)[..8]
+const JUPITER_ROUTE_DISCRIMINATOR: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+const JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+
+impl SwapAdapter {
+    pub fn program_id(&self) -> &'static Pubkey {
+        match self {
+            SwapAdapter::JupiterV6 => &JUPITER_V6_PROGRAM_ID,
+        }
+    }
+
+    /// Validates that `data` is an exact-input swap of this adapter's program. Exact-output
+    /// routes are rejected since they would leave part of the merchant amount unswapped.
+    pub fn validate_swap_data(&self, data: &[u8]) -> ProgramResult {
+        let supported = match self {
+            SwapAdapter::JupiterV6 => data.get(..8).is_some_and(|discriminator| {
+                discriminator == JUPITER_ROUTE_DISCRIMINATOR
+                    || discriminator == JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR
+            }),
+        };
+
+        if !supported {
+            return Err(CommerceProgramError::InvalidSwap.into());
+        }
+        Ok(())
+    }
+}
+
+/// Returns the config's conversion policy, if any.
+pub fn get_conversion_policy(policies: &[PolicyData]) -> Option<&ConversionPolicy> {
+    match MerchantOperatorConfig::get_policy_by_type(policies, PolicyType::Conversion) {
+        Some(PolicyData::Conversion(conversion)) => Some(conversion),
+        _ => None,
+    }
+}
+
+/// Splits the last `num_swap_accounts` accounts, which are forwarded to the swap program,
+/// off the remaining accounts.
+pub fn split_swap_accounts(
+    remaining_accounts: &[AccountInfo],
+    num_swap_accounts: u8,
+) -> Result<(&[AccountInfo], &[AccountInfo]), ProgramError> {
+    let split = remaining_accounts
+        .len()
+        .checked_sub(num_swap_accounts as usize)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    Ok(remaining_accounts.split_at(split))
+}
+
+/// Invokes the swap adapter's program with `authority_info` signing as the swap's token
+/// owner.
+///
+/// # Arguments
+/// * `adapter` - The conversion policy's swap adapter
+/// * `swap_program_info` - The swap program, must match the adapter
+/// * `swap_accounts` - Accounts forwarded to the swap program, in its expected order
+/// * `swap_data` - The swap instruction data, must be an exact-input route of the adapter
+/// * `authority_info` - The PDA that owns the swap's source token account
+/// * `signer` - Seeds of `authority_info`
+pub fn invoke_swap(
+    adapter: SwapAdapter,
+    swap_program_info: &AccountInfo,
+    swap_accounts: &[AccountInfo],
+    swap_data: &[u8],
+    authority_info: &AccountInfo,
+    signer: Signer,
+) -> ProgramResult {
+    if swap_program_info.key() != adapter.program_id() {
+        return Err(CommerceProgramError::InvalidSwap.into());
+    }
+    adapter.validate_swap_data(swap_data)?;
+
+    let account_metas: Vec<AccountMeta> = swap_accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_writable: account.is_writable(),
+            is_signer: account.is_signer() || account.key() == authority_info.key(),
+        })
+        .collect();
+
+    let mut account_infos: Vec<&AccountInfo> = swap_accounts.iter().collect();
+    account_infos.push(swap_program_info);
+
+    slice_invoke_signed(
+        &Instruction {
+            program_id: swap_program_info.key(),
+            accounts: &account_metas,
+            data: swap_data,
+        },
+        &account_infos,
+        &[signer],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MemoPolicy;
+    use std::vec;
+
+    #[test]
+    fn test_get_conversion_policy() {
+        let conversion = ConversionPolicy {
+            settlement_mint: [7u8; 32],
+            swap_adapter: SwapAdapter::JupiterV6,
+            max_slippage_bps: 50,
+        };
+        let policies = vec![
+            PolicyData::Memo(MemoPolicy {
+                memo_on_clear: true,
+                memo_on_refund: false,
+            }),
+            PolicyData::Conversion(conversion.clone()),
+        ];
+
+        assert_eq!(get_conversion_policy(&policies), Some(&conversion));
+        assert_eq!(get_conversion_policy(&policies[..1]), None);
+    }
+
+    #[test]
+    fn test_jupiter_validate_swap_data() {
+        let adapter = SwapAdapter::JupiterV6;
+        assert_eq!(adapter.program_id(), &JUPITER_V6_PROGRAM_ID);
+
+        let mut data = JUPITER_ROUTE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0u8; 24]);
+        assert!(adapter.validate_swap_data(&data).is_ok());
+        assert!(adapter
+            .validate_swap_data(&JUPITER_SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR)
+            .is_ok());
+
+        // exact_out_route
+        assert_eq!(
+            adapter
+                .validate_swap_data(&[208, 51, 239, 151, 123, 43, 237, 92])
+                .unwrap_err(),
+            CommerceProgramError::InvalidSwap.into()
+        );
+        assert!(adapter
+            .validate_swap_data(&JUPITER_ROUTE_DISCRIMINATOR[..7])
+            .is_err());
+    }
+}
@@ -0,0 +1,399 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke_signed,
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    constants::{EXTRA_ACCOUNT_METAS_SEED, TOKEN_2022_PROGRAM_ID},
+    error::CommerceProgramError,
+};
+
+/// `TransferChecked` instruction discriminator, the same in SPL Token and Token-2022
+const TRANSFER_CHECKED_DISCRIMINATOR: u8 = 12;
+
+/// `CloseAccount` instruction discriminator, the same in SPL Token and Token-2022
+const CLOSE_ACCOUNT_DISCRIMINATOR: u8 = 9;
+
+// Size of a base mint, and offsets of `decimals` and `is_initialized` in it
+const MINT_LEN: usize = 82;
+const MINT_DECIMALS_OFFSET: usize = 44;
+const MINT_IS_INITIALIZED_OFFSET: usize = 45;
+
+// Size of a base token account, and offsets of `mint`, `owner` and `amount` in it
+const TOKEN_ACCOUNT_LEN: usize = 165;
+const TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
+const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+// Token-2022 pads a mint with extensions to the size of a token account and stores its
+// account type after that, followed by the extensions as type (u16 LE), length (u16 LE)
+// and value entries
+const ACCOUNT_TYPE_OFFSET: usize = TOKEN_ACCOUNT_LEN;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+const TRANSFER_HOOK_EXTENSION: u16 = 14;
+
+/// Whether `data` is an initialized SPL Token or Token-2022 mint, with or without extensions.
+pub fn is_mint(data: &[u8]) -> bool {
+    let has_mint_layout = data.len() == MINT_LEN
+        || (data.len() > ACCOUNT_TYPE_OFFSET && data[ACCOUNT_TYPE_OFFSET] == ACCOUNT_TYPE_MINT);
+    has_mint_layout && data[MINT_IS_INITIALIZED_OFFSET] == 1
+}
+
+/// Returns the value of the mint's Token-2022 extension of `extension_type`, if it has one.
+fn mint_extension(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+    while offset + 4 <= data.len() {
+        let entry_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let len = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+        let value = data.get(offset + 4..offset + 4 + len)?;
+
+        // Type 0 marks the zeroed space after the last extension
+        if entry_type == 0 {
+            return None;
+        }
+        if entry_type == extension_type {
+            return Some(value);
+        }
+        offset += 4 + len;
+    }
+    None
+}
+
+/// Whether the mint charges a Token-2022 transfer fee, which would leave escrow holding less
+/// than the payment amount.
+pub fn has_transfer_fee(data: &[u8]) -> bool {
+    mint_extension(data, TRANSFER_FEE_CONFIG_EXTENSION).is_some()
+}
+
+/// Returns the program of the mint's Token-2022 transfer hook, if it has one set.
+pub fn transfer_hook_program(data: &[u8]) -> Option<Pubkey> {
+    // The extension holds the hook authority, then the hook program; zeroed when unset
+    let program: Pubkey = mint_extension(data, TRANSFER_HOOK_EXTENSION)?
+        .get(32..64)?
+        .try_into()
+        .ok()?;
+    (program != Pubkey::default()).then_some(program)
+}
+
+/// Returns the mint held by a token account of `token_program_info`.
+///
+/// # Returns
+/// * The token account's mint, or `None` if the account is not a token account
+pub fn token_account_mint(info: &AccountInfo, token_program_info: &AccountInfo) -> Option<Pubkey> {
+    if !info.is_owned_by(token_program_info.key()) {
+        return None;
+    }
+    let data = info.try_borrow_data().ok()?;
+    if data.len() < TOKEN_ACCOUNT_LEN {
+        return None;
+    }
+    data[TOKEN_ACCOUNT_MINT_OFFSET..TOKEN_ACCOUNT_MINT_OFFSET + 32]
+        .try_into()
+        .ok()
+}
+
+/// Returns the wallet owning a token account of `token_program_info`.
+///
+/// # Returns
+/// * The token account's owner, or `None` if the account is not a token account
+pub fn token_account_owner(info: &AccountInfo, token_program_info: &AccountInfo) -> Option<Pubkey> {
+    if !info.is_owned_by(token_program_info.key()) {
+        return None;
+    }
+    let data = info.try_borrow_data().ok()?;
+    if data.len() < TOKEN_ACCOUNT_LEN {
+        return None;
+    }
+    data[TOKEN_ACCOUNT_OWNER_OFFSET..TOKEN_ACCOUNT_OWNER_OFFSET + 32]
+        .try_into()
+        .ok()
+}
+
+/// Reads the balance of a token account of `token_program_info`, with or without extensions.
+pub fn token_account_balance(
+    info: &AccountInfo,
+    token_program_info: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    if !info.is_owned_by(token_program_info.key()) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let data = info.try_borrow_data()?;
+    if data.len() < TOKEN_ACCOUNT_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(u64::from_le_bytes(
+        data[TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8]
+            .try_into()
+            .unwrap(),
+    ))
+}
+
+/// Splits the transfer hook accounts off the front of the remaining accounts, ahead of the
+/// rent payer and the operator delegate, so the accounts expected after them are unaffected by
+/// their presence.
+///
+/// A Token-2022 mint with a transfer hook needs the hook program first, then the extra
+/// accounts its ExtraAccountMetaList PDA resolves for the instruction's transfers, then that
+/// PDA itself, which ends them. Token-2022 resolves and checks each extra account when it
+/// invokes the hook; other mints take no hook accounts.
+///
+/// # Returns
+/// * The transfer hook accounts, empty for mints without a hook, and the accounts after them
+pub fn split_transfer_hook_accounts<'a>(
+    mint_info: &AccountInfo,
+    remaining_accounts: &'a [AccountInfo],
+) -> Result<(&'a [AccountInfo], &'a [AccountInfo]), ProgramError> {
+    if !mint_info.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
+        return Ok((&[], remaining_accounts));
+    }
+
+    let Some(hook_program) = transfer_hook_program(&mint_info.try_borrow_data()?) else {
+        return Ok((&[], remaining_accounts));
+    };
+
+    match remaining_accounts.first() {
+        Some(first) if first.key() == &hook_program => {}
+        _ => return Err(CommerceProgramError::TransferHookAccountsMissing.into()),
+    }
+
+    let (extra_account_metas, _) = find_program_address(
+        &[EXTRA_ACCOUNT_METAS_SEED, mint_info.key().as_ref()],
+        &hook_program,
+    );
+    let end = remaining_accounts
+        .iter()
+        .position(|account_info| account_info.key() == &extra_account_metas)
+        .ok_or(CommerceProgramError::TransferHookAccountsMissing)?;
+
+    Ok(remaining_accounts.split_at(end + 1))
+}
+
+/// The token program, mint and transfer hook accounts the token transfers of an instruction
+/// go through. Transfers use `TransferChecked`, which both token programs support and
+/// Token-2022 requires to invoke a transfer hook.
+pub struct TokenTransfer<'a> {
+    pub token_program_info: &'a AccountInfo,
+    pub mint_info: &'a AccountInfo,
+    pub decimals: u8,
+    pub transfer_hook_infos: &'a [AccountInfo],
+}
+
+impl<'a> TokenTransfer<'a> {
+    /// Reads the mint's decimals for the transfers.
+    ///
+    /// # Arguments
+    /// * `token_program_info` - The token program owning the mint
+    /// * `mint_info` - The token mint
+    /// * `transfer_hook_infos` - The accounts split off by `split_transfer_hook_accounts`
+    pub fn new(
+        token_program_info: &'a AccountInfo,
+        mint_info: &'a AccountInfo,
+        transfer_hook_infos: &'a [AccountInfo],
+    ) -> Result<Self, ProgramError> {
+        let data = mint_info.try_borrow_data()?;
+        if !is_mint(&data) {
+            return Err(CommerceProgramError::InvalidMint.into());
+        }
+
+        Ok(Self {
+            token_program_info,
+            mint_info,
+            decimals: data[MINT_DECIMALS_OFFSET],
+            transfer_hook_infos,
+        })
+    }
+
+    /// Transfers `amount` from `from` to `to`, signed by `authority` or, for a PDA authority,
+    /// by `signers`.
+    pub fn transfer(
+        &self,
+        from: &AccountInfo,
+        to: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        signers: &[Signer],
+    ) -> ProgramResult {
+        let mut data = [0u8; 10];
+        data[0] = TRANSFER_CHECKED_DISCRIMINATOR;
+        data[1..9].copy_from_slice(&amount.to_le_bytes());
+        data[9] = self.decimals;
+
+        let transfer_metas = [
+            AccountMeta::writable(from.key()),
+            AccountMeta::readonly(self.mint_info.key()),
+            AccountMeta::writable(to.key()),
+            AccountMeta::readonly_signer(authority.key()),
+        ];
+        let transfer_infos = [from, self.mint_info, to, authority];
+
+        // Without hook accounts the transfer's accounts stay on the stack
+        if self.transfer_hook_infos.is_empty() {
+            return slice_invoke_signed(
+                &Instruction {
+                    program_id: self.token_program_info.key(),
+                    accounts: &transfer_metas,
+                    data: &data,
+                },
+                &transfer_infos,
+                signers,
+            );
+        }
+
+        let mut account_metas = Vec::with_capacity(4 + self.transfer_hook_infos.len());
+        account_metas.extend(transfer_metas);
+        account_metas.extend(
+            self.transfer_hook_infos
+                .iter()
+                .map(|account| AccountMeta::new(account.key(), account.is_writable(), false)),
+        );
+
+        let mut account_infos: Vec<&AccountInfo> = Vec::with_capacity(account_metas.len());
+        account_infos.extend(transfer_infos);
+        account_infos.extend(self.transfer_hook_infos);
+
+        slice_invoke_signed(
+            &Instruction {
+                program_id: self.token_program_info.key(),
+                accounts: &account_metas,
+                data: &data,
+            },
+            &account_infos,
+            signers,
+        )
+    }
+}
+
+/// Closes an empty token account of `token_program_info`, crediting its rent to
+/// `destination`, signed by the account's `owner` or, for a PDA owner, by `signers`.
+pub fn close_token_account(
+    token_program_info: &AccountInfo,
+    account: &AccountInfo,
+    destination: &AccountInfo,
+    owner: &AccountInfo,
+    signers: &[Signer],
+) -> ProgramResult {
+    slice_invoke_signed(
+        &Instruction {
+            program_id: token_program_info.key(),
+            accounts: &[
+                AccountMeta::writable(account.key()),
+                AccountMeta::writable(destination.key()),
+                AccountMeta::readonly_signer(owner.key()),
+            ],
+            data: &[CLOSE_ACCOUNT_DISCRIMINATOR],
+        },
+        &[account, destination, owner],
+        signers,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn mint(extensions: &[(u16, &[u8])]) -> Vec<u8> {
+        let mut data = vec![0u8; MINT_LEN];
+        data[MINT_DECIMALS_OFFSET] = 6;
+        data[MINT_IS_INITIALIZED_OFFSET] = 1;
+        if extensions.is_empty() {
+            return data;
+        }
+
+        data.resize(ACCOUNT_TYPE_OFFSET, 0);
+        data.push(ACCOUNT_TYPE_MINT);
+        for (extension_type, value) in extensions {
+            data.extend_from_slice(&extension_type.to_le_bytes());
+            data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+        data
+    }
+
+    fn transfer_hook(program: Pubkey) -> [u8; 64] {
+        let mut value = [0u8; 64];
+        value[..32].copy_from_slice(&[1; 32]);
+        value[32..].copy_from_slice(&program);
+        value
+    }
+
+    #[test]
+    fn test_is_mint() {
+        assert!(is_mint(&mint(&[])));
+        assert!(is_mint(&mint(&[(TRANSFER_HOOK_EXTENSION, &[0; 64])])));
+
+        let mut uninitialized = mint(&[]);
+        uninitialized[MINT_IS_INITIALIZED_OFFSET] = 0;
+        assert!(!is_mint(&uninitialized));
+
+        // A token account has the same padded size but another account type
+        let mut token_account = mint(&[(TRANSFER_HOOK_EXTENSION, &[0; 64])]);
+        token_account[ACCOUNT_TYPE_OFFSET] = 2;
+        assert!(!is_mint(&token_account));
+        assert!(!is_mint(&[0u8; TOKEN_ACCOUNT_LEN]));
+        assert!(!is_mint(&[]));
+    }
+
+    #[test]
+    fn test_transfer_hook_program() {
+        assert_eq!(transfer_hook_program(&mint(&[])), None);
+
+        let program = [9u8; 32];
+        let hook = transfer_hook(program);
+        assert_eq!(
+            transfer_hook_program(&mint(&[(TRANSFER_HOOK_EXTENSION, &hook)])),
+            Some(program)
+        );
+
+        // Found after other extensions
+        assert_eq!(
+            transfer_hook_program(&mint(&[(3, &[5; 34]), (TRANSFER_HOOK_EXTENSION, &hook)])),
+            Some(program)
+        );
+
+        // An unset hook program
+        assert_eq!(
+            transfer_hook_program(&mint(&[(
+                TRANSFER_HOOK_EXTENSION,
+                &transfer_hook(Pubkey::default())
+            )])),
+            None
+        );
+
+        // Truncated extension
+        assert_eq!(
+            transfer_hook_program(&mint(&[(TRANSFER_HOOK_EXTENSION, &hook[..40])])),
+            None
+        );
+        let mut truncated = mint(&[(TRANSFER_HOOK_EXTENSION, &hook)]);
+        truncated.truncate(truncated.len() - 1);
+        assert_eq!(transfer_hook_program(&truncated), None);
+
+        // Zeroed space after the last extension
+        let mut padded = mint(&[(3, &[5; 34])]);
+        padded.extend_from_slice(&[0; 72]);
+        assert_eq!(transfer_hook_program(&padded), None);
+    }
+
+    #[test]
+    fn test_has_transfer_fee() {
+        assert!(!has_transfer_fee(&mint(&[])));
+        assert!(!has_transfer_fee(&mint(&[(
+            TRANSFER_HOOK_EXTENSION,
+            &[0; 64]
+        )])));
+        assert!(has_transfer_fee(&mint(&[
+            (TRANSFER_HOOK_EXTENSION, &[0; 64]),
+            (TRANSFER_FEE_CONFIG_EXTENSION, &[0; 108]),
+        ])));
+    }
+}
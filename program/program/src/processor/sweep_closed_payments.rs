@@ -0,0 +1,206 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    constants::MAX_SWEEP_PAYMENTS,
+    events::PaymentLifecycleStatus,
+    processor::{
+        close_payment::{close_payment_account, release_payment_accounts},
+        emit_payment_status_changed, rebate_payment_rent, rent_refund_destination,
+        update_merchant_stats, validate_operator_authority, verify_current_program,
+        verify_owner_mutability, verify_signer, verify_system_program, verify_writable,
+    },
+    require_len,
+    state::{Merchant, MerchantOperatorConfig, Operator, OperatorPermission, Payment},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+/// Accounts passed per payment: the payment, its buyer and its mint.
+const ACCOUNTS_PER_PAYMENT: usize = 3;
+
+#[inline(always)]
+pub fn process_sweep_closed_payments(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [fee_payer_info, operator_authority_info, operator_info, merchant_info, merchant_operator_config_info, rent_collector_info, system_program_info, event_authority_info, commerce_program_info, remaining_accounts @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let args = process_instruction_data(instruction_data)?;
+
+    // The payment accounts trail everything else
+    let split = remaining_accounts
+        .len()
+        .checked_sub(args.num_payments as usize * ACCOUNTS_PER_PAYMENT)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let (remaining_accounts, payment_accounts) = remaining_accounts.split_at(split);
+
+    // Validate fee_payer is writable signer
+    verify_signer(fee_payer_info, true)?;
+
+    // Validate operator_authority should have signed
+    verify_signer(operator_authority_info, false)?;
+
+    // Validate rent_collector is writable
+    verify_writable(rent_collector_info)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Validate commerce program
+    verify_current_program(commerce_program_info)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_operator_config is writable and owned by this program
+    verify_owner_mutability(merchant_operator_config_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate operator is owned by the program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Load and validate operator
+    let operator = {
+        let operator_data = operator_info.try_borrow_data()?;
+        Operator::try_from_bytes(&operator_data)?
+    };
+
+    operator.validate_pda(operator_info.key())?;
+
+    // Validate operator_authority is the owner or a delegate allowed to close payments
+    let remaining_accounts = validate_operator_authority(
+        &operator,
+        operator_info,
+        operator_authority_info,
+        remaining_accounts,
+        OperatorPermission::Close,
+    )?;
+
+    // Load and validate merchant
+    let merchant = {
+        let merchant_data = merchant_info.try_borrow_data()?;
+        Merchant::try_from_bytes(&merchant_data)?
+    };
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate merchant_operator_config
+    let (merchant_operator_config, policies, _allowed_mints) = {
+        let merchant_operator_config_data = merchant_operator_config_info.try_borrow_data()?;
+        MerchantOperatorConfig::try_from_bytes(&merchant_operator_config_data)?
+    };
+
+    // Validate merchant_operator_config PDA
+    merchant_operator_config.validate_pda(merchant_operator_config_info.key())?;
+
+    // Validate operator and merchant match the config
+    merchant_operator_config.validate_operator(operator_info.key())?;
+    merchant_operator_config.validate_merchant(merchant_info.key())?;
+
+    for payment_accounts in payment_accounts.chunks_exact(ACCOUNTS_PER_PAYMENT) {
+        let [payment_info, buyer_info, mint_info] = payment_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Validate payment is writable and owned by this program
+        verify_owner_mutability(payment_info, &COMMERCE_PROGRAM_ID, true)?;
+
+        // Load and validate payment against the same rules as ClosePayment
+        let payment = {
+            let payment_data = payment_info.try_borrow_data()?;
+            Payment::try_from_bytes(&payment_data)?
+        };
+
+        payment.validate_can_close(merchant_operator_config.days_to_close)?;
+
+        payment.validate_pda(
+            payment_info.key(),
+            merchant_operator_config_info.key(),
+            buyer_info.key(),
+            mint_info.key(),
+        )?;
+
+        // Update optional merchant stats
+        update_merchant_stats(remaining_accounts, merchant_info, mint_info, |stats| {
+            stats.record_close()
+        })?;
+
+        // A payment funded by a rent treasury can only be swept back to that treasury
+        let rent_destination_info = rent_refund_destination(
+            &payment.rent_payer,
+            Some(rent_collector_info),
+            rent_collector_info,
+        )?;
+
+        // Pay the buyer their share of the rent under a rent rebate policy
+        rebate_payment_rent(&policies, payment_info, buyer_info)?;
+
+        close_payment_account(payment_info, rent_destination_info)?;
+
+        emit_payment_status_changed(
+            program_id,
+            event_authority_info,
+            commerce_program_info,
+            buyer_info.key(),
+            merchant_info.key(),
+            operator_info.key(),
+            payment.order_id,
+            PaymentLifecycleStatus::from(&payment.status),
+            PaymentLifecycleStatus::Closed,
+            operator_authority_info.key(),
+        )?;
+    }
+
+    release_payment_accounts(merchant_operator_config_info, u32::from(args.num_payments))
+}
+
+struct SweepClosedPaymentsArgs {
+    num_payments: u8,
+}
+
+fn process_instruction_data(data: &[u8]) -> Result<SweepClosedPaymentsArgs, ProgramError> {
+    require_len!(data, 1);
+
+    let num_payments = data[0];
+    if num_payments == 0 || num_payments > MAX_SWEEP_PAYMENTS {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(SweepClosedPaymentsArgs { num_payments })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_instruction_data_valid() {
+        assert_eq!(process_instruction_data(&[1]).unwrap().num_payments, 1);
+        assert_eq!(
+            process_instruction_data(&[MAX_SWEEP_PAYMENTS])
+                .unwrap()
+                .num_payments,
+            MAX_SWEEP_PAYMENTS
+        );
+    }
+
+    #[test]
+    fn test_process_instruction_data_invalid_count() {
+        assert_eq!(
+            process_instruction_data(&[0]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            process_instruction_data(&[MAX_SWEEP_PAYMENTS + 1]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            process_instruction_data(&[]).err(),
+            Some(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    processor::{parse_profile_fields, verify_owner_mutability, verify_signer, ProfileFields},
+    state::{Merchant, MerchantProfile},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_update_merchant_profile(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (
+        ProfileFields {
+            name,
+            uri,
+            logo_hash,
+            contact_hash,
+        },
+        _,
+    ) = parse_profile_fields(instruction_data)?;
+    let [authority_info, merchant_info, merchant_profile_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate merchant is owned by this program
+    verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate merchant_profile is writable and owned by this program
+    verify_owner_mutability(merchant_profile_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Load and validate merchant
+    let merchant_data = merchant_info.try_borrow_data()?;
+    let merchant = Merchant::try_from_bytes(&merchant_data)?;
+    merchant.validate_owner(authority_info.key())?;
+    merchant.validate_pda(merchant_info.key())?;
+
+    // Load and validate merchant_profile
+    let mut merchant_profile_data = merchant_profile_info.try_borrow_mut_data()?;
+    let mut merchant_profile = MerchantProfile::try_from_bytes(&merchant_profile_data)?;
+    merchant_profile.validate_pda(merchant_profile_info.key())?;
+    merchant_profile.validate_merchant(merchant_info.key())?;
+
+    merchant_profile.update(name, uri, logo_hash, contact_hash)?;
+    merchant_profile.write(&mut merchant_profile_data);
+
+    Ok(())
+}
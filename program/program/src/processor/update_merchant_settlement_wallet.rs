@@ -3,18 +3,21 @@ use pinocchio::{
 };
 
 use crate::{
-    processor::{verify_owner_mutability, verify_signer},
-    state::{discriminator::AccountSerialize, Merchant},
+    events::{EventDiscriminators, MerchantSettlementWalletUpdatedEvent},
+    processor::{emit_event, verify_current_program, verify_owner_mutability, verify_signer},
+    state::Merchant,
     ID as COMMERCE_PROGRAM_ID,
 };
 
 #[inline(always)]
 pub fn process_update_merchant_settlement_wallet(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     _instruction_data: &[u8],
 ) -> ProgramResult {
-    let [payer_info, authority_info, merchant_info, new_settlement_wallet_info] = accounts else {
+    let [payer_info, authority_info, merchant_info, new_settlement_wallet_info, event_authority_info, commerce_program_info] =
+        accounts
+    else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
@@ -27,6 +30,9 @@ pub fn process_update_merchant_settlement_wallet(
     // Validate merchant is owned by this program
     verify_owner_mutability(merchant_info, &COMMERCE_PROGRAM_ID, true)?;
 
+    // Verify own program
+    verify_current_program(commerce_program_info)?;
+
     let mut merchant_data = merchant_info.try_borrow_mut_data()?;
     let mut merchant = Merchant::try_from_bytes(&merchant_data)?;
 
@@ -36,9 +42,26 @@ pub fn process_update_merchant_settlement_wallet(
     // Validate Merchant PDA
     merchant.validate_pda(merchant_info.key())?;
 
+    // Timelocked merchants change wallets via ProposeSettlementWallet/CommitSettlementWallet
+    merchant.validate_no_settlement_wallet_timelock()?;
+
     // Update merchant settlement wallet
+    let old_settlement_wallet = merchant.settlement_wallet;
     merchant.settlement_wallet = *new_settlement_wallet_info.key();
-    merchant_data.copy_from_slice(&merchant.to_bytes());
+    merchant.store(&mut merchant_data)?;
+
+    // Emit settlement wallet updated event
+    let event = MerchantSettlementWalletUpdatedEvent {
+        discriminator: EventDiscriminators::MerchantSettlementWalletUpdated as u8,
+        merchant: *merchant_info.key(),
+        old_settlement_wallet,
+        new_settlement_wallet: merchant.settlement_wallet,
+    };
 
-    Ok(())
+    emit_event(
+        program_id,
+        event_authority_info,
+        commerce_program_info,
+        &event.to_array(),
+    )
 }
@@ -0,0 +1,58 @@
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    processor::{
+        parse_permissions, parse_refund_limits, verify_owner_mutability, verify_signer,
+        PERMISSIONS_LEN,
+    },
+    state::{discriminator::AccountSerialize, Operator, OperatorDelegate},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_update_operator_delegate(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let [can_make_payment, can_clear, can_refund, can_close] = parse_permissions(instruction_data)?;
+    let (max_refund_amount, max_daily_refund_amount) =
+        parse_refund_limits(instruction_data.get(PERMISSIONS_LEN..).unwrap_or_default())?;
+    let [authority_info, operator_info, operator_delegate_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate: authority should have signed
+    verify_signer(authority_info, false)?;
+
+    // Validate operator is owned by this program
+    verify_owner_mutability(operator_info, &COMMERCE_PROGRAM_ID, false)?;
+
+    // Validate operator_delegate is writable and owned by this program
+    verify_owner_mutability(operator_delegate_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Load and validate operator
+    let operator_data = operator_info.try_borrow_data()?;
+    let operator = Operator::try_from_bytes(&operator_data)?;
+    operator.validate_owner(authority_info.key())?;
+    operator.validate_pda(operator_info.key())?;
+
+    // Load and validate operator_delegate
+    let mut operator_delegate_data = operator_delegate_info.try_borrow_mut_data()?;
+    let mut operator_delegate = OperatorDelegate::try_from_bytes(&operator_delegate_data)?;
+    operator_delegate.validate_pda(operator_delegate_info.key())?;
+    operator_delegate.validate_operator(operator_info.key())?;
+
+    operator_delegate.can_make_payment = can_make_payment;
+    operator_delegate.can_clear = can_clear;
+    operator_delegate.can_refund = can_refund;
+    operator_delegate.can_close = can_close;
+    // Refunds already counted today still count towards a new daily limit
+    operator_delegate.max_refund_amount = max_refund_amount;
+    operator_delegate.max_daily_refund_amount = max_daily_refund_amount;
+    operator_delegate_data.copy_from_slice(&operator_delegate.to_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    error::CommerceProgramError,
+    processor::{
+        realloc_pda_account, verify_owner_mutability, verify_signer, verify_system_program,
+    },
+    state::{AccountSerialize, Discriminator, Merchant, MerchantOperatorConfig, Operator, Payment},
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+#[inline(always)]
+pub fn process_upgrade_account(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let [payer_info, account_info, system_program_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate payer is writable signer
+    verify_signer(payer_info, true)?;
+
+    // Validate the account is writable and owned by this program
+    verify_owner_mutability(account_info, &COMMERCE_PROGRAM_ID, true)?;
+
+    // Validate system program
+    verify_system_program(system_program_info)?;
+
+    // Rewrite the account in its current layout. Each legacy layout is told apart from the
+    // current one by its size, so an account is only ever upgraded once.
+    let upgraded_data: Vec<u8> = {
+        let data = account_info.try_borrow_data()?;
+        match data.first().copied() {
+            Some(Operator::DISCRIMINATOR) => {
+                Operator::from_legacy_bytes(&data, Clock::get()?.unix_timestamp)?.to_bytes()
+            }
+            Some(Merchant::DISCRIMINATOR) => Merchant::from_legacy_bytes(&data)?.to_bytes(),
+            Some(Payment::DISCRIMINATOR) => Payment::from_legacy_bytes(&data)?.to_bytes(),
+            Some(MerchantOperatorConfig::DISCRIMINATOR) => {
+                let (config, policies, currencies) =
+                    MerchantOperatorConfig::from_legacy_bytes(&data)?;
+                config.to_bytes(&policies, &currencies)
+            }
+            _ => return Err(CommerceProgramError::AccountNotUpgradable.into()),
+        }
+    };
+
+    // Grow the account, with the payer funding the extra rent
+    realloc_pda_account(payer_info, &Rent::get()?, upgraded_data.len(), account_info)?;
+    account_info
+        .try_borrow_mut_data()?
+        .copy_from_slice(&upgraded_data);
+
+    Ok(())
+}
@@ -0,0 +1,221 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{
+    constants::{BUYER_INDEX_RECENT_ORDERS, BUYER_INDEX_SEED},
+    error::CommerceProgramError,
+};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// A buyer's payment history under a config, kept by MakePayment under a BuyerIndexPolicy so
+/// wallets can list recent purchases without scanning every Payment account.
+///
+/// Seeds: [b"buyer_index", merchant_operator_config pubkey, buyer pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct BuyerIndex {
+    pub merchant_operator_config: Pubkey,
+
+    pub buyer: Pubkey,
+
+    pub bump: u8,
+
+    /// Number of payments the buyer has made under the config since the index was created
+    pub payment_count: u32,
+
+    /// Order ids of the buyer's latest payments, most recent first. Only the first
+    /// `min(payment_count, BUYER_INDEX_RECENT_ORDERS)` entries are set.
+    pub recent_order_ids: [u32; 8],
+}
+
+impl Discriminator for BuyerIndex {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::BuyerIndexDiscriminator as u8;
+}
+
+impl AccountSerialize for BuyerIndex {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.buyer.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(&self.payment_count.to_le_bytes());
+        for order_id in &self.recent_order_ids {
+            data.extend_from_slice(&order_id.to_le_bytes());
+        }
+        data
+    }
+}
+
+impl BuyerIndex {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant_operator_config
+        32 + // buyer
+        1 + // bump
+        4 + // payment_count
+        4 * BUYER_INDEX_RECENT_ORDERS; // recent_order_ids
+
+    pub fn new(merchant_operator_config: Pubkey, buyer: Pubkey, bump: u8) -> Self {
+        Self {
+            merchant_operator_config,
+            buyer,
+            bump,
+            payment_count: 0,
+            recent_order_ids: [0; BUYER_INDEX_RECENT_ORDERS],
+        }
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[
+                BUYER_INDEX_SEED,
+                self.merchant_operator_config.as_ref(),
+                self.buyer.as_ref(),
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::BuyerIndexInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_payment(
+        &self,
+        merchant_operator_config: &Pubkey,
+        buyer: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.merchant_operator_config.ne(merchant_operator_config) || self.buyer.ne(buyer) {
+            return Err(CommerceProgramError::BuyerIndexMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Counts a payment for `order_id`, dropping the oldest recent order id once the list is
+    /// full.
+    pub fn record_payment(&mut self, order_id: u32) -> Result<(), ProgramError> {
+        self.payment_count = self
+            .payment_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        self.recent_order_ids.rotate_right(1);
+        self.recent_order_ids[0] = order_id;
+
+        Ok(())
+    }
+
+    /// Order ids of the buyer's latest payments, most recent first.
+    pub fn recent_order_ids(&self) -> &[u32] {
+        let len = (self.payment_count as usize).min(BUYER_INDEX_RECENT_ORDERS);
+        &self.recent_order_ids[..len]
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        let merchant_operator_config = reader.read_pubkey()?;
+        let buyer = reader.read_pubkey()?;
+        let bump = reader.read_u8()?;
+        let payment_count = reader.read_u32()?;
+
+        let mut recent_order_ids = [0; BUYER_INDEX_RECENT_ORDERS];
+        for order_id in recent_order_ids.iter_mut() {
+            *order_id = reader.read_u32()?;
+        }
+
+        Ok(Self {
+            merchant_operator_config,
+            buyer,
+            bump,
+            payment_count,
+            recent_order_ids,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_buyer_index_serialization() {
+        let mut buyer_index = BuyerIndex::new([1u8; 32], [2u8; 32], 254);
+        buyer_index.record_payment(7).unwrap();
+        buyer_index.record_payment(9).unwrap();
+
+        let bytes = buyer_index.to_bytes();
+        assert_eq!(bytes.len(), BuyerIndex::LEN);
+
+        let deserialized = BuyerIndex::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, buyer_index);
+    }
+
+    #[test]
+    fn test_buyer_index_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; BuyerIndex::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = BuyerIndex::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_record_payment_keeps_latest_order_ids() {
+        let mut buyer_index = BuyerIndex::new([1u8; 32], [2u8; 32], 255);
+        assert!(buyer_index.recent_order_ids().is_empty());
+
+        buyer_index.record_payment(1).unwrap();
+        buyer_index.record_payment(2).unwrap();
+        assert_eq!(buyer_index.payment_count, 2);
+        assert_eq!(buyer_index.recent_order_ids(), &[2, 1]);
+
+        (3..=10).for_each(|order_id| buyer_index.record_payment(order_id).unwrap());
+        assert_eq!(buyer_index.payment_count, 10);
+        assert_eq!(buyer_index.recent_order_ids(), &[10, 9, 8, 7, 6, 5, 4, 3]);
+    }
+
+    #[test]
+    fn test_record_payment_overflow() {
+        let mut buyer_index = BuyerIndex::new([1u8; 32], [2u8; 32], 255);
+        buyer_index.payment_count = u32::MAX;
+
+        assert_eq!(
+            buyer_index.record_payment(1).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_validate_payment() {
+        let buyer_index = BuyerIndex::new([1u8; 32], [2u8; 32], 255);
+
+        assert!(buyer_index.validate_payment(&[1u8; 32], &[2u8; 32]).is_ok());
+        assert_eq!(
+            buyer_index
+                .validate_payment(&[2u8; 32], &[2u8; 32])
+                .unwrap_err(),
+            CommerceProgramError::BuyerIndexMismatch.into()
+        );
+        assert_eq!(
+            buyer_index
+                .validate_payment(&[1u8; 32], &[3u8; 32])
+                .unwrap_err(),
+            CommerceProgramError::BuyerIndexMismatch.into()
+        );
+    }
+}
@@ -0,0 +1,232 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::{ShankAccount, ShankType};
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{
+    constants::{CONFIG_REGISTRY_PAGE_CAPACITY, CONFIG_REGISTRY_SEED},
+    error::CommerceProgramError,
+};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// A MerchantOperatorConfig listed on a ConfigRegistryPage, found at the PDA of the page's
+/// operator, `merchant` and `version`.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct ConfigRegistryEntry {
+    pub merchant: Pubkey,
+    pub version: u32,
+}
+
+impl ConfigRegistryEntry {
+    pub const EMPTY: Self = Self {
+        merchant: [0; 32],
+        version: 0,
+    };
+}
+
+/// One page of the configs created under an operator, so clients can list a merchant's
+/// configs without scanning every MerchantOperatorConfig account.
+///
+/// InitializeMerchantOperatorConfig and InitializeMerchantWithConfig append the new config
+/// to the page passed to them. A page holds `CONFIG_REGISTRY_PAGE_CAPACITY` configs; the next
+/// page can only be created once the previous one is full, so pages 0 to n - 1 of n pages
+/// are always full.
+///
+/// Seeds: [b"config_registry", operator pubkey, page (u32 LE)]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct ConfigRegistryPage {
+    pub operator: Pubkey,
+
+    /// Index of the page, starting at 0
+    pub page: u32,
+
+    pub bump: u8,
+
+    /// Number of configs listed. Only the first `count` entries are set.
+    pub count: u32,
+
+    pub entries: [ConfigRegistryEntry; 32],
+}
+
+impl Discriminator for ConfigRegistryPage {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::ConfigRegistryPageDiscriminator as u8;
+}
+
+impl AccountSerialize for ConfigRegistryPage {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self.page.to_le_bytes());
+        data.push(self.bump);
+        data.extend_from_slice(&self.count.to_le_bytes());
+        for entry in &self.entries {
+            data.extend_from_slice(entry.merchant.as_ref());
+            data.extend_from_slice(&entry.version.to_le_bytes());
+        }
+        data
+    }
+}
+
+impl ConfigRegistryPage {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // operator
+        4 + // page
+        1 + // bump
+        4 + // count
+        (32 + 4) * CONFIG_REGISTRY_PAGE_CAPACITY; // entries
+
+    pub fn new(operator: Pubkey, page: u32, bump: u8) -> Self {
+        Self {
+            operator,
+            page,
+            bump,
+            count: 0,
+            entries: [ConfigRegistryEntry::EMPTY; CONFIG_REGISTRY_PAGE_CAPACITY],
+        }
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[
+                CONFIG_REGISTRY_SEED,
+                self.operator.as_ref(),
+                &self.page.to_le_bytes(),
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::ConfigRegistryInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_operator(&self, operator: &Pubkey) -> Result<(), ProgramError> {
+        if self.operator.ne(operator) {
+            return Err(CommerceProgramError::ConfigRegistryMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.count as usize >= CONFIG_REGISTRY_PAGE_CAPACITY
+    }
+
+    /// Lists the config of `merchant` and `version`.
+    pub fn push(&mut self, merchant: Pubkey, version: u32) -> Result<(), ProgramError> {
+        if self.is_full() {
+            return Err(CommerceProgramError::ConfigRegistryPageFull.into());
+        }
+
+        self.entries[self.count as usize] = ConfigRegistryEntry { merchant, version };
+        self.count += 1;
+
+        Ok(())
+    }
+
+    /// Configs listed on the page, oldest first.
+    pub fn entries(&self) -> &[ConfigRegistryEntry] {
+        let len = (self.count as usize).min(CONFIG_REGISTRY_PAGE_CAPACITY);
+        &self.entries[..len]
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        let operator = reader.read_pubkey()?;
+        let page = reader.read_u32()?;
+        let bump = reader.read_u8()?;
+        let count = reader.read_u32()?;
+
+        let mut entries = [ConfigRegistryEntry::EMPTY; CONFIG_REGISTRY_PAGE_CAPACITY];
+        for entry in entries.iter_mut() {
+            entry.merchant = reader.read_pubkey()?;
+            entry.version = reader.read_u32()?;
+        }
+
+        Ok(Self {
+            operator,
+            page,
+            bump,
+            count,
+            entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_config_registry_page_serialization() {
+        let mut page = ConfigRegistryPage::new([1u8; 32], 3, 254);
+        page.push([2u8; 32], 1).unwrap();
+        page.push([3u8; 32], 7).unwrap();
+
+        let bytes = page.to_bytes();
+        assert_eq!(bytes.len(), ConfigRegistryPage::LEN);
+
+        let deserialized = ConfigRegistryPage::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, page);
+    }
+
+    #[test]
+    fn test_config_registry_page_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; ConfigRegistryPage::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = ConfigRegistryPage::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_push_until_full() {
+        let mut page = ConfigRegistryPage::new([1u8; 32], 0, 255);
+        assert!(page.entries().is_empty());
+
+        for version in 0..CONFIG_REGISTRY_PAGE_CAPACITY as u32 {
+            assert!(!page.is_full());
+            page.push([2u8; 32], version).unwrap();
+        }
+        assert!(page.is_full());
+        assert_eq!(page.entries().len(), CONFIG_REGISTRY_PAGE_CAPACITY);
+        assert_eq!(
+            page.entries()[1],
+            ConfigRegistryEntry {
+                merchant: [2u8; 32],
+                version: 1,
+            }
+        );
+
+        assert_eq!(
+            page.push([2u8; 32], 99).unwrap_err(),
+            CommerceProgramError::ConfigRegistryPageFull.into()
+        );
+    }
+
+    #[test]
+    fn test_validate_operator() {
+        let page = ConfigRegistryPage::new([1u8; 32], 0, 255);
+
+        assert!(page.validate_operator(&[1u8; 32]).is_ok());
+        assert_eq!(
+            page.validate_operator(&[2u8; 32]).unwrap_err(),
+            CommerceProgramError::ConfigRegistryMismatch.into()
+        );
+    }
+}
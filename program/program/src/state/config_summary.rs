@@ -0,0 +1,139 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use shank::ShankType;
+
+use super::{FeeType, MerchantOperatorConfig, PolicyData};
+
+/// Fixed-size summary of a MerchantOperatorConfig that GetConfigSummary writes to return data,
+/// so programs calling it by CPI can check a merchant's terms without parsing the config's
+/// policies and accepted currencies.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct ConfigSummary {
+    pub version: u32,
+
+    pub operator_fee: u64,
+    pub fee_type: FeeType,
+
+    pub days_to_close: u16,
+
+    /// Bit `n` is set when the config has a policy of `PolicyType` `n`
+    pub policy_mask: u32,
+
+    pub num_accepted_currencies: u32,
+
+    /// Balance of the config's escrow ATA for the summarized mint, including any tokens sent
+    /// to it that no payment accounts for
+    pub escrow_balance: u64,
+
+    /// Payments in the summarized mint holding funds in escrow, from the config's open-payment
+    /// count
+    pub open_payments: u32,
+}
+
+impl ConfigSummary {
+    pub const LEN: usize = 4 + // version
+        8 + // operator_fee
+        1 + // fee_type
+        2 + // days_to_close
+        4 + // policy_mask
+        4 + // num_accepted_currencies
+        8 + // escrow_balance
+        4; // open_payments
+
+    pub fn new(
+        config: &MerchantOperatorConfig,
+        policies: &[PolicyData],
+        escrow_balance: u64,
+        open_payments: u32,
+    ) -> Self {
+        Self {
+            version: config.version,
+            operator_fee: config.operator_fee,
+            fee_type: config.fee_type.clone(),
+            days_to_close: config.days_to_close,
+            policy_mask: Self::policy_mask(policies),
+            num_accepted_currencies: config.num_accepted_currencies,
+            escrow_balance,
+            open_payments,
+        }
+    }
+
+    pub fn policy_mask(policies: &[PolicyData]) -> u32 {
+        policies
+            .iter()
+            .fold(0, |mask, policy| mask | 1 << policy.policy_type().to_u8())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN);
+        data.extend_from_slice(&self.version.to_le_bytes());
+        data.extend_from_slice(&self.operator_fee.to_le_bytes());
+        data.push(self.fee_type.to_u8());
+        data.extend_from_slice(&self.days_to_close.to_le_bytes());
+        data.extend_from_slice(&self.policy_mask.to_le_bytes());
+        data.extend_from_slice(&self.num_accepted_currencies.to_le_bytes());
+        data.extend_from_slice(&self.escrow_balance.to_le_bytes());
+        data.extend_from_slice(&self.open_payments.to_le_bytes());
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{MemoPolicy, PolicyType, RentRebatePolicy, SettlementPolicy};
+    use alloc::vec;
+
+    #[test]
+    fn test_policy_mask() {
+        assert_eq!(ConfigSummary::policy_mask(&[]), 0);
+
+        let policies = vec![
+            PolicyData::Settlement(SettlementPolicy {
+                min_settlement_amount: 0,
+                settlement_frequency_hours: 0,
+                auto_settle: false,
+            }),
+            PolicyData::Memo(MemoPolicy {
+                memo_on_clear: true,
+                memo_on_refund: false,
+            }),
+            PolicyData::RentRebate(RentRebatePolicy {
+                buyer_share_bps: 2_500,
+            }),
+        ];
+        assert_eq!(
+            ConfigSummary::policy_mask(&policies),
+            1 << PolicyType::Settlement as u32
+                | 1 << PolicyType::Memo as u32
+                | 1 << PolicyType::RentRebate as u32
+        );
+    }
+
+    #[test]
+    fn test_config_summary_to_bytes() {
+        let summary = ConfigSummary {
+            version: 3,
+            operator_fee: 250,
+            fee_type: FeeType::Fixed,
+            days_to_close: 7,
+            policy_mask: 0b101,
+            num_accepted_currencies: 2,
+            escrow_balance: 1_000_000,
+            open_payments: 4,
+        };
+
+        let bytes = summary.to_bytes();
+        assert_eq!(bytes.len(), ConfigSummary::LEN);
+        assert_eq!(&bytes[0..4], &3u32.to_le_bytes());
+        assert_eq!(&bytes[4..12], &250u64.to_le_bytes());
+        assert_eq!(bytes[12], FeeType::Fixed.to_u8());
+        assert_eq!(&bytes[13..15], &7u16.to_le_bytes());
+        assert_eq!(&bytes[15..19], &0b101u32.to_le_bytes());
+        assert_eq!(&bytes[19..23], &2u32.to_le_bytes());
+        assert_eq!(&bytes[23..31], &1_000_000u64.to_le_bytes());
+        assert_eq!(&bytes[31..35], &4u32.to_le_bytes());
+    }
+}
@@ -0,0 +1,232 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{constants::CONVERSION_RATE_SEED, error::CommerceProgramError};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// A rate between two of a config's accepted mints, posted by the operator.
+///
+/// Posted and updated by PostConversionRate. RefundPaymentWithConversion refunds a payment
+/// made in `from_mint` in `to_mint`, at `rate_numerator / rate_denominator` units of
+/// `to_mint` per unit of `from_mint`, while the rate is within the config's
+/// RefundConversionPolicy max age.
+///
+/// Seeds: [b"conversion_rate", merchant_operator_config pubkey, from_mint pubkey, to_mint pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct ConversionRate {
+    pub merchant_operator_config: Pubkey,
+
+    pub from_mint: Pubkey,
+
+    pub to_mint: Pubkey,
+
+    pub bump: u8,
+
+    pub rate_numerator: u64,
+
+    pub rate_denominator: u64,
+
+    /// Unix timestamp of the rate's last posting
+    pub posted_at: i64,
+
+    /// Operator authority or delegate that posted the rate
+    pub attester: Pubkey,
+}
+
+impl Discriminator for ConversionRate {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::ConversionRateDiscriminator as u8;
+}
+
+impl AccountSerialize for ConversionRate {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.from_mint.as_ref());
+        data.extend_from_slice(self.to_mint.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(&self.rate_numerator.to_le_bytes());
+        data.extend_from_slice(&self.rate_denominator.to_le_bytes());
+        data.extend_from_slice(&self.posted_at.to_le_bytes());
+        data.extend_from_slice(self.attester.as_ref());
+        data
+    }
+}
+
+impl ConversionRate {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant_operator_config
+        32 + // from_mint
+        32 + // to_mint
+        1 + // bump
+        8 + // rate_numerator
+        8 + // rate_denominator
+        8 + // posted_at
+        32; // attester
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[
+                CONVERSION_RATE_SEED,
+                self.merchant_operator_config.as_ref(),
+                self.from_mint.as_ref(),
+                self.to_mint.as_ref(),
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::ConversionRateInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the rate converts `from_mint` to `to_mint` under
+    /// `merchant_operator_config`.
+    pub fn validate_pair(
+        &self,
+        merchant_operator_config: &Pubkey,
+        from_mint: &Pubkey,
+        to_mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.merchant_operator_config.ne(merchant_operator_config)
+            || self.from_mint.ne(from_mint)
+            || self.to_mint.ne(to_mint)
+        {
+            return Err(CommerceProgramError::ConversionRateMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// `amount` of `from_mint` in `to_mint`, rounded down.
+    ///
+    /// Fails when the rate is zero or the result is zero or does not fit a u64.
+    pub fn convert(&self, amount: u64) -> Result<u64, ProgramError> {
+        if self.rate_numerator == 0 || self.rate_denominator == 0 {
+            return Err(CommerceProgramError::InvalidConversionRate.into());
+        }
+        let converted =
+            amount as u128 * self.rate_numerator as u128 / self.rate_denominator as u128;
+        match u64::try_from(converted) {
+            Ok(converted) if converted > 0 => Ok(converted),
+            _ => Err(CommerceProgramError::InvalidConversionRate.into()),
+        }
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            merchant_operator_config: reader.read_pubkey()?,
+            from_mint: reader.read_pubkey()?,
+            to_mint: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            rate_numerator: reader.read_u64()?,
+            rate_denominator: reader.read_u64()?,
+            posted_at: reader.read_i64()?,
+            attester: reader.read_pubkey()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn conversion_rate() -> ConversionRate {
+        ConversionRate {
+            merchant_operator_config: [1u8; 32],
+            from_mint: [2u8; 32],
+            to_mint: [3u8; 32],
+            bump: 254,
+            rate_numerator: 98,
+            rate_denominator: 100,
+            posted_at: 1_700_000_000,
+            attester: [4u8; 32],
+        }
+    }
+
+    #[test]
+    fn test_conversion_rate_serialization() {
+        let conversion_rate = conversion_rate();
+
+        let bytes = conversion_rate.to_bytes();
+        assert_eq!(bytes.len(), ConversionRate::LEN);
+
+        let deserialized = ConversionRate::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, conversion_rate);
+    }
+
+    #[test]
+    fn test_conversion_rate_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; ConversionRate::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = ConversionRate::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_validate_pair() {
+        let conversion_rate = conversion_rate();
+
+        assert!(conversion_rate
+            .validate_pair(&[1u8; 32], &[2u8; 32], &[3u8; 32])
+            .is_ok());
+
+        for (config, from_mint, to_mint) in [
+            ([9u8; 32], [2u8; 32], [3u8; 32]),
+            ([1u8; 32], [9u8; 32], [3u8; 32]),
+            ([1u8; 32], [2u8; 32], [9u8; 32]),
+            ([1u8; 32], [3u8; 32], [2u8; 32]),
+        ] {
+            assert_eq!(
+                conversion_rate
+                    .validate_pair(&config, &from_mint, &to_mint)
+                    .unwrap_err(),
+                CommerceProgramError::ConversionRateMismatch.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert() {
+        let err: ProgramError = CommerceProgramError::InvalidConversionRate.into();
+        let mut conversion_rate = conversion_rate();
+
+        assert_eq!(conversion_rate.convert(1_000_000).unwrap(), 980_000);
+        // Rounded down
+        assert_eq!(conversion_rate.convert(101).unwrap(), 98);
+        assert_eq!(conversion_rate.convert(1).unwrap_err(), err);
+
+        // Exact for any u64 amount that still fits after conversion
+        assert_eq!(
+            conversion_rate.convert(u64::MAX).unwrap(),
+            (u64::MAX as u128 * 98 / 100) as u64
+        );
+        conversion_rate.rate_numerator = 2;
+        conversion_rate.rate_denominator = 1;
+        assert_eq!(conversion_rate.convert(u64::MAX).unwrap_err(), err);
+
+        conversion_rate.rate_numerator = 0;
+        assert_eq!(conversion_rate.convert(1_000).unwrap_err(), err);
+
+        conversion_rate.rate_numerator = 1;
+        conversion_rate.rate_denominator = 0;
+        assert_eq!(conversion_rate.convert(1_000).unwrap_err(), err);
+    }
+}
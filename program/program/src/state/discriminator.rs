@@ -1,6 +1,9 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
+use pinocchio::program_error::ProgramError;
+
+use crate::error::CommerceProgramError;
 
 pub trait Discriminator {
     const DISCRIMINATOR: u8;
@@ -12,44 +15,209 @@ pub enum CommerceAccountDiscriminators {
     OperatorDiscriminator = 1,
     MerchantOperatorConfigDiscriminator = 2,
     PaymentDiscriminator = 3,
+    PaymentTreeDiscriminator = 4,
+    MerchantStatsDiscriminator = 5,
+    OperatorDelegateDiscriminator = 6,
+    SettlementBatchDiscriminator = 7,
+    OrderDiscriminator = 8,
+    MerchantProfileDiscriminator = 9,
+    RefundApprovalDiscriminator = 10,
+    RiskStateDiscriminator = 11,
+    BuyerIndexDiscriminator = 12,
+    QuoteDiscriminator = 13,
+    ConfigRegistryPageDiscriminator = 14,
+    OperatorFreezeListDiscriminator = 15,
+    ConversionRateDiscriminator = 16,
+    PaymentNoteDiscriminator = 17,
+    WebhookCommitmentDiscriminator = 18,
+    PaymentAnnotationDiscriminator = 19,
 }
 
+/// First byte of every instruction's data.
 #[repr(u8)]
-pub enum CommerceInstructionDiscriminators {
-    CreateMerchant = 0,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstructionDiscriminator {
+    InitializeMerchant = 0,
     CreateOperator = 1,
     InitializeMerchantOperatorConfig = 2,
     MakePayment = 3,
     ClearPayment = 4,
     RefundPayment = 5,
     UpdateMerchantSettlementWallet = 6,
-    UpdateMerchantAuthority = 7,
-    UpdateOperatorAuthority = 8,
+    ProposeMerchantAuthority = 7,
+    ProposeOperatorAuthority = 8,
     ClosePayment = 9,
+    InitializePaymentTree = 10,
+    MakePaymentCompressed = 11,
+    ClearPaymentCompressed = 12,
+    AcceptMerchantAuthority = 13,
+    AcceptOperatorAuthority = 14,
+    InitializeMerchantStats = 15,
+    RefundSettledPayment = 16,
+    InitializeOperatorDelegate = 17,
+    UpdateOperatorDelegate = 18,
+    RevokeOperatorDelegate = 19,
+    ClearPaymentWithConversion = 20,
+    ChargebackPayment = 21,
+    SweepClosedPayments = 22,
+    ProposeSettlementWallet = 23,
+    CommitSettlementWallet = 24,
+    InitializeSettlementBatch = 25,
+    ExecuteSettlementBatch = 26,
+    CreateOrder = 27,
+    InitializeMerchantProfile = 28,
+    UpdateMerchantProfile = 29,
+    ApproveRefund = 30,
+    ClaimRefund = 31,
+    InitializeRiskState = 32,
+    InitializeBuyerIndex = 33,
+    InitializeMerchantWithConfig = 34,
+    MigrateEscrow = 35,
+    CreateQuote = 36,
+    InitializeConfigRegistryPage = 37,
+    CapturePayment = 38,
+    InitializeOperatorFreezeList = 39,
+    AddToFreezeList = 40,
+    RemoveFromFreezeList = 41,
+    PostConversionRate = 42,
+    RefundPaymentWithConversion = 43,
+    AddPolicy = 44,
+    AddAcceptedCurrency = 45,
+    ClosePaymentNote = 46,
+    Heartbeat = 47,
+    CloseEscrowAta = 48,
+    MigratePayment = 49,
+    GetConfigSummary = 50,
+    SetWebhookCommitment = 51,
+    AnnotatePayment = 52,
+    DecommissionConfig = 53,
+    CloseMerchantOperatorConfig = 54,
+    UpgradeAccount = 55,
+    SetSettlementWalletTimelock = 56,
     EmitEvent = 228,
 }
 
-impl TryFrom<u8> for CommerceInstructionDiscriminators {
-    type Error = ();
+impl InstructionDiscriminator {
+    /// Oldest layout version of the instruction's arguments the program still reads. Version 2
+    /// dropped the bump from `CreateOperator`, `MakePayment` and `MigratePayment`, which now
+    /// derive it on-chain; every other layout is unchanged since version 1.
+    pub const fn min_version(self) -> u8 {
+        match self {
+            Self::CreateOperator | Self::MakePayment | Self::MigratePayment => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for InstructionDiscriminator {
+    type Error = ProgramError;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => Ok(CommerceInstructionDiscriminators::CreateMerchant),
-            1 => Ok(CommerceInstructionDiscriminators::CreateOperator),
-            2 => Ok(CommerceInstructionDiscriminators::InitializeMerchantOperatorConfig),
-            3 => Ok(CommerceInstructionDiscriminators::MakePayment),
-            4 => Ok(CommerceInstructionDiscriminators::ClearPayment),
-            5 => Ok(CommerceInstructionDiscriminators::RefundPayment),
-            6 => Ok(CommerceInstructionDiscriminators::UpdateMerchantSettlementWallet),
-            7 => Ok(CommerceInstructionDiscriminators::UpdateMerchantAuthority),
-            8 => Ok(CommerceInstructionDiscriminators::UpdateOperatorAuthority),
-            9 => Ok(CommerceInstructionDiscriminators::ClosePayment),
-            228 => Ok(CommerceInstructionDiscriminators::EmitEvent),
-            _ => Err(()),
+            0 => Ok(InstructionDiscriminator::InitializeMerchant),
+            1 => Ok(InstructionDiscriminator::CreateOperator),
+            2 => Ok(InstructionDiscriminator::InitializeMerchantOperatorConfig),
+            3 => Ok(InstructionDiscriminator::MakePayment),
+            4 => Ok(InstructionDiscriminator::ClearPayment),
+            5 => Ok(InstructionDiscriminator::RefundPayment),
+            6 => Ok(InstructionDiscriminator::UpdateMerchantSettlementWallet),
+            7 => Ok(InstructionDiscriminator::ProposeMerchantAuthority),
+            8 => Ok(InstructionDiscriminator::ProposeOperatorAuthority),
+            9 => Ok(InstructionDiscriminator::ClosePayment),
+            10 => Ok(InstructionDiscriminator::InitializePaymentTree),
+            11 => Ok(InstructionDiscriminator::MakePaymentCompressed),
+            12 => Ok(InstructionDiscriminator::ClearPaymentCompressed),
+            13 => Ok(InstructionDiscriminator::AcceptMerchantAuthority),
+            14 => Ok(InstructionDiscriminator::AcceptOperatorAuthority),
+            15 => Ok(InstructionDiscriminator::InitializeMerchantStats),
+            16 => Ok(InstructionDiscriminator::RefundSettledPayment),
+            17 => Ok(InstructionDiscriminator::InitializeOperatorDelegate),
+            18 => Ok(InstructionDiscriminator::UpdateOperatorDelegate),
+            19 => Ok(InstructionDiscriminator::RevokeOperatorDelegate),
+            20 => Ok(InstructionDiscriminator::ClearPaymentWithConversion),
+            21 => Ok(InstructionDiscriminator::ChargebackPayment),
+            22 => Ok(InstructionDiscriminator::SweepClosedPayments),
+            23 => Ok(InstructionDiscriminator::ProposeSettlementWallet),
+            24 => Ok(InstructionDiscriminator::CommitSettlementWallet),
+            25 => Ok(InstructionDiscriminator::InitializeSettlementBatch),
+            26 => Ok(InstructionDiscriminator::ExecuteSettlementBatch),
+            27 => Ok(InstructionDiscriminator::CreateOrder),
+            28 => Ok(InstructionDiscriminator::InitializeMerchantProfile),
+            29 => Ok(InstructionDiscriminator::UpdateMerchantProfile),
+            30 => Ok(InstructionDiscriminator::ApproveRefund),
+            31 => Ok(InstructionDiscriminator::ClaimRefund),
+            32 => Ok(InstructionDiscriminator::InitializeRiskState),
+            33 => Ok(InstructionDiscriminator::InitializeBuyerIndex),
+            34 => Ok(InstructionDiscriminator::InitializeMerchantWithConfig),
+            35 => Ok(InstructionDiscriminator::MigrateEscrow),
+            36 => Ok(InstructionDiscriminator::CreateQuote),
+            37 => Ok(InstructionDiscriminator::InitializeConfigRegistryPage),
+            38 => Ok(InstructionDiscriminator::CapturePayment),
+            39 => Ok(InstructionDiscriminator::InitializeOperatorFreezeList),
+            40 => Ok(InstructionDiscriminator::AddToFreezeList),
+            41 => Ok(InstructionDiscriminator::RemoveFromFreezeList),
+            42 => Ok(InstructionDiscriminator::PostConversionRate),
+            43 => Ok(InstructionDiscriminator::RefundPaymentWithConversion),
+            44 => Ok(InstructionDiscriminator::AddPolicy),
+            45 => Ok(InstructionDiscriminator::AddAcceptedCurrency),
+            46 => Ok(InstructionDiscriminator::ClosePaymentNote),
+            47 => Ok(InstructionDiscriminator::Heartbeat),
+            48 => Ok(InstructionDiscriminator::CloseEscrowAta),
+            49 => Ok(InstructionDiscriminator::MigratePayment),
+            50 => Ok(InstructionDiscriminator::GetConfigSummary),
+            51 => Ok(InstructionDiscriminator::SetWebhookCommitment),
+            52 => Ok(InstructionDiscriminator::AnnotatePayment),
+            53 => Ok(InstructionDiscriminator::DecommissionConfig),
+            54 => Ok(InstructionDiscriminator::CloseMerchantOperatorConfig),
+            55 => Ok(InstructionDiscriminator::UpgradeAccount),
+            56 => Ok(InstructionDiscriminator::SetSettlementWalletTimelock),
+            228 => Ok(InstructionDiscriminator::EmitEvent),
+            _ => Err(ProgramError::InvalidInstructionData),
         }
     }
 }
 
+/// Set on the discriminator of instruction data whose next byte is its version.
+pub const INSTRUCTION_VERSION_FLAG: u8 = 0x80;
+
+/// Current layout version of the instructions' arguments. Data without a version is read as
+/// this version; an instruction also reads the versions down to its
+/// [`InstructionDiscriminator::min_version`], whose layouts match the current one.
+pub const INSTRUCTION_VERSION: u8 = 2;
+
+/// Splits instruction data into its discriminator, version and the remaining argument bytes.
+///
+/// Data is either `[discriminator, args..]` or `[discriminator | INSTRUCTION_VERSION_FLAG,
+/// version, args..]`. `EmitEvent`, which only the program invokes, never carries a version.
+pub fn parse_instruction(
+    instruction_data: &[u8],
+) -> Result<(InstructionDiscriminator, u8, &[u8]), ProgramError> {
+    let (discriminator, data) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    if *discriminator == InstructionDiscriminator::EmitEvent as u8
+        || discriminator & INSTRUCTION_VERSION_FLAG == 0
+    {
+        return Ok((
+            InstructionDiscriminator::try_from(*discriminator)?,
+            INSTRUCTION_VERSION,
+            data,
+        ));
+    }
+
+    let discriminator =
+        InstructionDiscriminator::try_from(discriminator & !INSTRUCTION_VERSION_FLAG)?;
+    let (version, data) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if !(discriminator.min_version()..=INSTRUCTION_VERSION).contains(version) {
+        return Err(CommerceProgramError::UnsupportedInstructionVersion.into());
+    }
+
+    Ok((discriminator, *version, data))
+}
+
 pub trait AccountSerialize: Discriminator {
     fn to_bytes_inner(&self) -> Vec<u8>;
 
@@ -60,3 +228,110 @@ pub trait AccountSerialize: Discriminator {
         data
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instruction_discriminator_roundtrip() {
+        (0..=u8::MAX).for_each(|value| {
+            if let Ok(discriminator) = InstructionDiscriminator::try_from(value) {
+                assert_eq!(discriminator as u8, value);
+            }
+        });
+        assert_eq!(
+            InstructionDiscriminator::try_from(4).unwrap(),
+            InstructionDiscriminator::ClearPayment
+        );
+        assert_eq!(
+            InstructionDiscriminator::try_from(57).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction() {
+        let (discriminator, version, data) = parse_instruction(&[3, 1, 2, 3]).unwrap();
+        assert_eq!(discriminator, InstructionDiscriminator::MakePayment);
+        assert_eq!(version, INSTRUCTION_VERSION);
+        assert_eq!(data, &[1, 2, 3]);
+
+        let (discriminator, _, data) = parse_instruction(&[9]).unwrap();
+        assert_eq!(discriminator, InstructionDiscriminator::ClosePayment);
+        assert!(data.is_empty());
+
+        let (discriminator, _, data) = parse_instruction(&[228, 1, 2]).unwrap();
+        assert_eq!(discriminator, InstructionDiscriminator::EmitEvent);
+        assert_eq!(data, &[1, 2]);
+
+        assert_eq!(
+            parse_instruction(&[]).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+        assert_eq!(
+            parse_instruction(&[200]).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+    }
+
+    #[test]
+    fn test_parse_versioned_instruction() {
+        let (discriminator, version, data) =
+            parse_instruction(&[3 | INSTRUCTION_VERSION_FLAG, INSTRUCTION_VERSION, 1, 2, 3])
+                .unwrap();
+        assert_eq!(discriminator, InstructionDiscriminator::MakePayment);
+        assert_eq!(version, INSTRUCTION_VERSION);
+        assert_eq!(data, &[1, 2, 3]);
+
+        let (discriminator, _, data) =
+            parse_instruction(&[9 | INSTRUCTION_VERSION_FLAG, INSTRUCTION_VERSION]).unwrap();
+        assert_eq!(discriminator, InstructionDiscriminator::ClosePayment);
+        assert!(data.is_empty());
+
+        // Instructions whose layout has not changed still read version 1
+        let (discriminator, version, data) =
+            parse_instruction(&[4 | INSTRUCTION_VERSION_FLAG, 1, 1, 2]).unwrap();
+        assert_eq!(discriminator, InstructionDiscriminator::ClearPayment);
+        assert_eq!(version, 1);
+        assert_eq!(data, &[1, 2]);
+
+        // Other versions are rejected, not read with the current layout: version 1 of
+        // MakePayment, CreateOperator and MigratePayment still had the bump
+        assert_eq!(
+            parse_instruction(&[3 | INSTRUCTION_VERSION_FLAG, 1, 1, 2, 3]).unwrap_err(),
+            CommerceProgramError::UnsupportedInstructionVersion.into()
+        );
+        assert_eq!(
+            parse_instruction(&[1 | INSTRUCTION_VERSION_FLAG, 1, 255]).unwrap_err(),
+            CommerceProgramError::UnsupportedInstructionVersion.into()
+        );
+        assert_eq!(
+            parse_instruction(&[49 | INSTRUCTION_VERSION_FLAG, 1, 255]).unwrap_err(),
+            CommerceProgramError::UnsupportedInstructionVersion.into()
+        );
+        assert_eq!(
+            parse_instruction(&[
+                3 | INSTRUCTION_VERSION_FLAG,
+                INSTRUCTION_VERSION + 1,
+                1,
+                2,
+                3
+            ])
+            .unwrap_err(),
+            CommerceProgramError::UnsupportedInstructionVersion.into()
+        );
+        assert_eq!(
+            parse_instruction(&[3 | INSTRUCTION_VERSION_FLAG, 0]).unwrap_err(),
+            CommerceProgramError::UnsupportedInstructionVersion.into()
+        );
+        assert_eq!(
+            parse_instruction(&[3 | INSTRUCTION_VERSION_FLAG]).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+        assert_eq!(
+            parse_instruction(&[99 | INSTRUCTION_VERSION_FLAG, INSTRUCTION_VERSION]).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+    }
+}
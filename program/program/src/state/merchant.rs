@@ -1,6 +1,7 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
+use bytemuck::{Pod, Zeroable};
 use pinocchio::{
     program_error::ProgramError,
     pubkey::{find_program_address, Pubkey},
@@ -8,11 +9,17 @@ use pinocchio::{
 use shank::ShankAccount;
 
 use crate::ID as COMMERCE_PROGRAM_ID;
-use crate::{constants::MERCHANT_SEED, error::CommerceProgramError};
+use crate::{
+    constants::{MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS, MERCHANT_SEED},
+    error::CommerceProgramError,
+};
 
-use super::discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator};
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    zero_copy::ZeroCopy,
+};
 
-/// Seeds: [b"merchant", owner pubkey]
+/// Seeds: [b"merchant", original_owner pubkey]
 #[derive(Clone, Debug, PartialEq, ShankAccount)]
 #[repr(C)]
 pub struct Merchant {
@@ -22,6 +29,23 @@ pub struct Merchant {
 
     /// ATAs will be derived from this wallet for the merchant to receive payments
     pub settlement_wallet: Pubkey,
+
+    /// Authority proposed via ProposeMerchantAuthority, zeroed when none is pending
+    pub pending_authority: Pubkey,
+
+    /// Delay between ProposeSettlementWallet and CommitSettlementWallet; when non-zero,
+    /// UpdateMerchantSettlementWallet is disabled
+    pub settlement_wallet_timelock_seconds: u64,
+
+    /// Settlement wallet proposed via ProposeSettlementWallet, zeroed when none is pending
+    pub pending_settlement_wallet: Pubkey,
+
+    /// Unix timestamp from which the pending settlement wallet can be committed
+    pub pending_settlement_wallet_unlocks_at: i64,
+
+    /// Owner the merchant was created by and its PDA derived from, kept when
+    /// AcceptMerchantAuthority hands `owner` over
+    pub original_owner: Pubkey,
 }
 
 impl Discriminator for Merchant {
@@ -34,16 +58,122 @@ impl AccountSerialize for Merchant {
         data.extend_from_slice(self.owner.as_ref());
         data.push(self.bump);
         data.extend_from_slice(self.settlement_wallet.as_ref());
+        data.extend_from_slice(self.pending_authority.as_ref());
+        data.extend_from_slice(&self.settlement_wallet_timelock_seconds.to_le_bytes());
+        data.extend_from_slice(self.pending_settlement_wallet.as_ref());
+        data.extend_from_slice(&self.pending_settlement_wallet_unlocks_at.to_le_bytes());
+        data.extend_from_slice(self.original_owner.as_ref());
         data
     }
 }
 
+/// Zero-copy layout of a [`Merchant`] account
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct MerchantView {
+    discriminator: u8,
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub settlement_wallet: Pubkey,
+    pub pending_authority: Pubkey,
+    settlement_wallet_timelock_seconds: [u8; 8],
+    pub pending_settlement_wallet: Pubkey,
+    pending_settlement_wallet_unlocks_at: [u8; 8],
+    pub original_owner: Pubkey,
+}
+
+impl Discriminator for MerchantView {
+    const DISCRIMINATOR: u8 = Merchant::DISCRIMINATOR;
+}
+
+impl ZeroCopy for MerchantView {}
+
+impl MerchantView {
+    pub fn settlement_wallet_timelock_seconds(&self) -> u64 {
+        u64::from_le_bytes(self.settlement_wallet_timelock_seconds)
+    }
+
+    pub fn pending_settlement_wallet_unlocks_at(&self) -> i64 {
+        i64::from_le_bytes(self.pending_settlement_wallet_unlocks_at)
+    }
+}
+
+impl From<&Merchant> for MerchantView {
+    fn from(merchant: &Merchant) -> Self {
+        Self {
+            discriminator: Merchant::DISCRIMINATOR,
+            owner: merchant.owner,
+            bump: merchant.bump,
+            settlement_wallet: merchant.settlement_wallet,
+            pending_authority: merchant.pending_authority,
+            settlement_wallet_timelock_seconds: merchant
+                .settlement_wallet_timelock_seconds
+                .to_le_bytes(),
+            pending_settlement_wallet: merchant.pending_settlement_wallet,
+            pending_settlement_wallet_unlocks_at: merchant
+                .pending_settlement_wallet_unlocks_at
+                .to_le_bytes(),
+            original_owner: merchant.original_owner,
+        }
+    }
+}
+
+impl From<&MerchantView> for Merchant {
+    fn from(view: &MerchantView) -> Self {
+        Self {
+            owner: view.owner,
+            bump: view.bump,
+            settlement_wallet: view.settlement_wallet,
+            pending_authority: view.pending_authority,
+            settlement_wallet_timelock_seconds: view.settlement_wallet_timelock_seconds(),
+            pending_settlement_wallet: view.pending_settlement_wallet,
+            pending_settlement_wallet_unlocks_at: view.pending_settlement_wallet_unlocks_at(),
+            original_owner: view.original_owner,
+        }
+    }
+}
+
 impl Merchant {
     pub const LEN: usize = 1 + // discriminator
+        32 + // owner
+        1 + // bump
+        32 + // settlement_wallet
+        32 + // pending_authority
+        8 + // settlement_wallet_timelock_seconds
+        32 + // pending_settlement_wallet
+        8 + // pending_settlement_wallet_unlocks_at
+        32; // original_owner
+
+    /// Size of a merchant created before its authority handover and settlement wallet
+    /// timelock fields, which UpgradeAccount grows to `LEN`.
+    pub const LEGACY_LEN: usize = 1 + // discriminator
         32 + // owner
         1 + // bump
         32; // settlement_wallet
 
+    /// Reads a merchant in the `LEGACY_LEN` layout, with nothing pending and no settlement
+    /// wallet timelock. Its authority was never handed over, so `owner` is its original owner.
+    pub fn from_legacy_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEGACY_LEN {
+            return Err(CommerceProgramError::AccountNotUpgradable.into());
+        }
+
+        let mut reader = super::ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        let owner = reader.read_pubkey()?;
+        Ok(Self {
+            owner,
+            bump: reader.read_u8()?,
+            settlement_wallet: reader.read_pubkey()?,
+            pending_authority: Pubkey::default(),
+            settlement_wallet_timelock_seconds: 0,
+            pending_settlement_wallet: Pubkey::default(),
+            pending_settlement_wallet_unlocks_at: 0,
+            original_owner: owner,
+        })
+    }
+
     pub fn validate_owner(&self, owner: &Pubkey) -> Result<(), ProgramError> {
         if self.owner.ne(owner) {
             return Err(CommerceProgramError::MerchantOwnerMismatch.into());
@@ -51,9 +181,91 @@ impl Merchant {
         Ok(())
     }
 
+    pub fn validate_settlement_wallet(
+        &self,
+        settlement_wallet: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.settlement_wallet.ne(settlement_wallet) {
+            return Err(CommerceProgramError::SettlementWalletMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn validate_pending_authority(&self, authority: &Pubkey) -> Result<(), ProgramError> {
+        if self.pending_authority == Pubkey::default() || self.pending_authority.ne(authority) {
+            return Err(CommerceProgramError::MerchantPendingAuthorityMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Rejects instant settlement wallet updates once a timelock is set.
+    pub fn validate_no_settlement_wallet_timelock(&self) -> Result<(), ProgramError> {
+        if self.settlement_wallet_timelock_seconds != 0 {
+            return Err(CommerceProgramError::SettlementWalletTimelocked.into());
+        }
+        Ok(())
+    }
+
+    /// Raises the settlement wallet timelock to `timelock_seconds`, pushing a pending
+    /// proposal's unlock time out by the increase. Lowering it is rejected, so a compromised
+    /// authority cannot shorten the window it would have to wait out.
+    pub fn raise_settlement_wallet_timelock(
+        &mut self,
+        timelock_seconds: u64,
+    ) -> Result<(), ProgramError> {
+        if timelock_seconds > MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS {
+            return Err(CommerceProgramError::InvalidSettlementWalletTimelock.into());
+        }
+        if timelock_seconds < self.settlement_wallet_timelock_seconds {
+            return Err(CommerceProgramError::SettlementWalletTimelockLowered.into());
+        }
+
+        if self.pending_settlement_wallet != Pubkey::default() {
+            // Both timelocks are at most MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS
+            let increase = (timelock_seconds - self.settlement_wallet_timelock_seconds) as i64;
+            self.pending_settlement_wallet_unlocks_at = self
+                .pending_settlement_wallet_unlocks_at
+                .checked_add(increase)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        self.settlement_wallet_timelock_seconds = timelock_seconds;
+        Ok(())
+    }
+
+    /// Records `settlement_wallet` as pending, replacing any earlier proposal and restarting
+    /// the timelock from `now`.
+    pub fn propose_settlement_wallet(
+        &mut self,
+        settlement_wallet: &Pubkey,
+        now: i64,
+    ) -> Result<(), ProgramError> {
+        let timelock = i64::try_from(self.settlement_wallet_timelock_seconds)
+            .map_err(|_| ProgramError::ArithmeticOverflow)?;
+        self.pending_settlement_wallet_unlocks_at = now
+            .checked_add(timelock)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.pending_settlement_wallet = *settlement_wallet;
+        Ok(())
+    }
+
+    /// Checks a settlement wallet is pending and its timelock has elapsed at `now`.
+    pub fn validate_pending_settlement_wallet(&self, now: i64) -> Result<(), ProgramError> {
+        if self.pending_settlement_wallet == Pubkey::default() {
+            return Err(CommerceProgramError::SettlementWalletNotProposed.into());
+        }
+        if now < self.pending_settlement_wallet_unlocks_at {
+            return Err(CommerceProgramError::SettlementWalletTimelockActive.into());
+        }
+        Ok(())
+    }
+
+    /// Validates the merchant's PDA, derived from its original owner so that it still holds
+    /// after an authority handover.
     pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
-        let (pda, bump) =
-            find_program_address(&[MERCHANT_SEED, self.owner.as_ref()], &COMMERCE_PROGRAM_ID);
+        let (pda, bump) = find_program_address(
+            &[MERCHANT_SEED, self.original_owner.as_ref()],
+            &COMMERCE_PROGRAM_ID,
+        );
 
         if pda.ne(account_info_key) || bump != self.bump {
             return Err(CommerceProgramError::MerchantInvalidPda.into());
@@ -62,27 +274,39 @@ impl Merchant {
         Ok(())
     }
 
+    #[cfg(not(feature = "legacy-serialization"))]
     pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
-        if data[0] != Self::DISCRIMINATOR {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let mut offset: usize = 1;
-
-        let owner: Pubkey = data[offset..offset + 32].try_into().unwrap();
-        offset += 32;
+        Ok(MerchantView::load(data)?.into())
+    }
 
-        let bump = data[offset];
-        offset += 1;
+    /// Writes the merchant over `data`.
+    #[cfg(not(feature = "legacy-serialization"))]
+    pub fn store(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        MerchantView::from(self).store(data)
+    }
 
-        let settlement_wallet: Pubkey = data[offset..offset + 32].try_into().unwrap();
+    #[cfg(feature = "legacy-serialization")]
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = super::ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
 
         Ok(Self {
-            owner,
-            bump,
-            settlement_wallet,
+            owner: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            settlement_wallet: reader.read_pubkey()?,
+            pending_authority: reader.read_pubkey()?,
+            settlement_wallet_timelock_seconds: reader.read_u64()?,
+            pending_settlement_wallet: reader.read_pubkey()?,
+            pending_settlement_wallet_unlocks_at: reader.read_i64()?,
+            original_owner: reader.read_pubkey()?,
         })
     }
+
+    /// Writes the merchant over `data`.
+    #[cfg(feature = "legacy-serialization")]
+    pub fn store(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        super::zero_copy::write_prefix(&self.to_bytes(), data)
+    }
 }
 
 #[cfg(test)]
@@ -97,6 +321,11 @@ mod tests {
             owner,
             bump: 255,
             settlement_wallet: [2u8; 32],
+            pending_authority: [0u8; 32],
+            settlement_wallet_timelock_seconds: 0,
+            pending_settlement_wallet: [0u8; 32],
+            pending_settlement_wallet_unlocks_at: 0,
+            original_owner: owner,
         };
 
         assert!(merchant.validate_owner(&owner).is_ok());
@@ -110,6 +339,11 @@ mod tests {
             owner,
             bump: 255,
             settlement_wallet: [3u8; 32],
+            pending_authority: [0u8; 32],
+            settlement_wallet_timelock_seconds: 0,
+            pending_settlement_wallet: [0u8; 32],
+            pending_settlement_wallet_unlocks_at: 0,
+            original_owner: owner,
         };
 
         let result = merchant.validate_owner(&wrong_owner);
@@ -126,6 +360,11 @@ mod tests {
             owner: [1u8; 32],
             bump: 254,
             settlement_wallet: [2u8; 32],
+            pending_authority: [3u8; 32],
+            settlement_wallet_timelock_seconds: 86_400,
+            pending_settlement_wallet: [4u8; 32],
+            pending_settlement_wallet_unlocks_at: 1_700_086_400,
+            original_owner: [1u8; 32],
         };
 
         let bytes = merchant.to_bytes_inner();
@@ -138,6 +377,31 @@ mod tests {
         assert_eq!(deserialized, merchant);
     }
 
+    #[test]
+    fn test_merchant_from_legacy_bytes() {
+        let merchant = Merchant {
+            owner: [1u8; 32],
+            bump: 254,
+            settlement_wallet: [2u8; 32],
+            pending_authority: [0u8; 32],
+            settlement_wallet_timelock_seconds: 0,
+            pending_settlement_wallet: [0u8; 32],
+            pending_settlement_wallet_unlocks_at: 0,
+            original_owner: [1u8; 32],
+        };
+        let data = merchant.to_bytes();
+
+        // The legacy layout stops after the settlement wallet
+        assert_eq!(
+            Merchant::from_legacy_bytes(&data[..Merchant::LEGACY_LEN]).unwrap(),
+            merchant
+        );
+        assert_eq!(
+            Merchant::from_legacy_bytes(&data).unwrap_err(),
+            CommerceProgramError::AccountNotUpgradable.into()
+        );
+    }
+
     #[test]
     fn test_merchant_try_from_bytes_wrong_discriminator() {
         let mut data = vec![0; Merchant::LEN];
@@ -148,6 +412,31 @@ mod tests {
         assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
     }
 
+    #[test]
+    fn test_merchant_view_matches_serialization() {
+        let merchant = Merchant {
+            owner: [1u8; 32],
+            bump: 254,
+            settlement_wallet: [2u8; 32],
+            pending_authority: [3u8; 32],
+            settlement_wallet_timelock_seconds: 86_400,
+            pending_settlement_wallet: [4u8; 32],
+            pending_settlement_wallet_unlocks_at: 1_700_086_400,
+            original_owner: [1u8; 32],
+        };
+
+        assert_eq!(core::mem::size_of::<MerchantView>(), Merchant::LEN);
+        assert_eq!(
+            bytemuck::bytes_of(&MerchantView::from(&merchant)),
+            merchant.to_bytes().as_slice()
+        );
+
+        let mut data = vec![0; Merchant::LEN];
+        merchant.store(&mut data).unwrap();
+        assert_eq!(Merchant::try_from_bytes(&data).unwrap(), merchant);
+        assert_eq!(MerchantView::load(&data).unwrap().owner, [1u8; 32]);
+    }
+
     #[test]
     fn test_merchant_different_wallets() {
         let owner = [1u8; 32];
@@ -157,6 +446,11 @@ mod tests {
             owner,
             bump: 200,
             settlement_wallet,
+            pending_authority: [0u8; 32],
+            settlement_wallet_timelock_seconds: 0,
+            pending_settlement_wallet: [0u8; 32],
+            pending_settlement_wallet_unlocks_at: 0,
+            original_owner: owner,
         };
 
         // Test owner validation works
@@ -171,4 +465,158 @@ mod tests {
         assert_eq!(deserialized.owner, owner);
         assert_eq!(deserialized.settlement_wallet, settlement_wallet);
     }
+
+    #[test]
+    fn test_validate_pending_authority() {
+        let pending_authority = [5u8; 32];
+        let mut merchant = Merchant {
+            owner: [1u8; 32],
+            bump: 255,
+            settlement_wallet: [2u8; 32],
+            pending_authority: [0u8; 32],
+            settlement_wallet_timelock_seconds: 0,
+            pending_settlement_wallet: [0u8; 32],
+            pending_settlement_wallet_unlocks_at: 0,
+            original_owner: [1u8; 32],
+        };
+
+        // Nothing pending: even the default key is rejected
+        assert_eq!(
+            merchant.validate_pending_authority(&[0u8; 32]).unwrap_err(),
+            CommerceProgramError::MerchantPendingAuthorityMismatch.into()
+        );
+
+        merchant.pending_authority = pending_authority;
+        assert!(merchant
+            .validate_pending_authority(&pending_authority)
+            .is_ok());
+        assert_eq!(
+            merchant.validate_pending_authority(&[6u8; 32]).unwrap_err(),
+            CommerceProgramError::MerchantPendingAuthorityMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_validate_settlement_wallet() {
+        let merchant = Merchant {
+            owner: [1u8; 32],
+            bump: 255,
+            settlement_wallet: [2u8; 32],
+            pending_authority: [0u8; 32],
+            settlement_wallet_timelock_seconds: 0,
+            pending_settlement_wallet: [0u8; 32],
+            pending_settlement_wallet_unlocks_at: 0,
+            original_owner: [1u8; 32],
+        };
+
+        assert!(merchant.validate_settlement_wallet(&[2u8; 32]).is_ok());
+        assert_eq!(
+            merchant.validate_settlement_wallet(&[3u8; 32]).unwrap_err(),
+            CommerceProgramError::SettlementWalletMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_settlement_wallet_timelock() {
+        let mut merchant = Merchant {
+            owner: [1u8; 32],
+            bump: 255,
+            settlement_wallet: [2u8; 32],
+            pending_authority: [0u8; 32],
+            settlement_wallet_timelock_seconds: 0,
+            pending_settlement_wallet: [0u8; 32],
+            pending_settlement_wallet_unlocks_at: 0,
+            original_owner: [1u8; 32],
+        };
+        assert!(merchant.validate_no_settlement_wallet_timelock().is_ok());
+
+        merchant.settlement_wallet_timelock_seconds = 86_400;
+        assert_eq!(
+            merchant
+                .validate_no_settlement_wallet_timelock()
+                .unwrap_err(),
+            CommerceProgramError::SettlementWalletTimelocked.into()
+        );
+
+        // Nothing pending
+        assert_eq!(
+            merchant
+                .validate_pending_settlement_wallet(i64::MAX)
+                .unwrap_err(),
+            CommerceProgramError::SettlementWalletNotProposed.into()
+        );
+
+        merchant
+            .propose_settlement_wallet(&[3u8; 32], 1_000)
+            .unwrap();
+        assert_eq!(merchant.pending_settlement_wallet, [3u8; 32]);
+        assert_eq!(merchant.pending_settlement_wallet_unlocks_at, 87_400);
+        assert_eq!(
+            merchant
+                .validate_pending_settlement_wallet(87_399)
+                .unwrap_err(),
+            CommerceProgramError::SettlementWalletTimelockActive.into()
+        );
+        assert!(merchant.validate_pending_settlement_wallet(87_400).is_ok());
+
+        // A new proposal restarts the timelock
+        merchant
+            .propose_settlement_wallet(&[4u8; 32], 50_000)
+            .unwrap();
+        assert_eq!(merchant.pending_settlement_wallet, [4u8; 32]);
+        assert_eq!(
+            merchant
+                .validate_pending_settlement_wallet(87_400)
+                .unwrap_err(),
+            CommerceProgramError::SettlementWalletTimelockActive.into()
+        );
+
+        assert_eq!(
+            merchant
+                .propose_settlement_wallet(&[4u8; 32], i64::MAX)
+                .unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_raise_settlement_wallet_timelock() {
+        // A legacy merchant migrates with no timelock
+        let mut merchant = Merchant {
+            owner: [1u8; 32],
+            bump: 255,
+            settlement_wallet: [2u8; 32],
+            pending_authority: [0u8; 32],
+            settlement_wallet_timelock_seconds: 0,
+            pending_settlement_wallet: [0u8; 32],
+            pending_settlement_wallet_unlocks_at: 0,
+            original_owner: [1u8; 32],
+        };
+
+        merchant.raise_settlement_wallet_timelock(3_600).unwrap();
+        assert_eq!(merchant.settlement_wallet_timelock_seconds, 3_600);
+        assert_eq!(merchant.pending_settlement_wallet_unlocks_at, 0);
+
+        assert_eq!(
+            merchant
+                .raise_settlement_wallet_timelock(3_599)
+                .unwrap_err(),
+            CommerceProgramError::SettlementWalletTimelockLowered.into()
+        );
+        assert_eq!(
+            merchant
+                .raise_settlement_wallet_timelock(MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS + 1)
+                .unwrap_err(),
+            CommerceProgramError::InvalidSettlementWalletTimelock.into()
+        );
+
+        // A pending proposal waits out the increase
+        merchant
+            .propose_settlement_wallet(&[3u8; 32], 1_000)
+            .unwrap();
+        assert_eq!(merchant.pending_settlement_wallet_unlocks_at, 4_600);
+        merchant.raise_settlement_wallet_timelock(86_400).unwrap();
+        assert_eq!(merchant.pending_settlement_wallet_unlocks_at, 87_400);
+        assert_eq!(merchant.settlement_wallet_timelock_seconds, 86_400);
+    }
 }
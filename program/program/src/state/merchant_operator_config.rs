@@ -1,16 +1,18 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
+use bytemuck::{Pod, Zeroable};
 use pinocchio::pubkey::find_program_address;
 use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 use shank::ShankAccount;
 
-use crate::constants::MERCHANT_OPERATOR_CONFIG_SEED;
+use crate::constants::{MAX_ACCEPTED_CURRENCIES, MAX_POLICIES, MERCHANT_OPERATOR_CONFIG_SEED};
 use crate::error::CommerceProgramError;
-use crate::state::PolicyType;
+use crate::state::{hashv, PolicyType};
 
 use super::discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator};
 use super::policy::{FeeType, PolicyData};
+use super::{zero_copy::ZeroCopy, ByteReader};
 use crate::ID as COMMERCE_PROGRAM_ID;
 
 // Seeds: [b"merchant_operator_config", merchant pubkey, operator pubkey, version]
@@ -35,6 +37,15 @@ pub struct MerchantOperatorConfig {
     // Dynamic fields that follow the struct
     pub num_policies: u32,
     pub num_accepted_currencies: u32,
+
+    /// SHA-256 of the config's terms, see [`MerchantOperatorConfig::compute_config_hash`]
+    pub config_hash: [u8; 32],
+
+    /// Unix timestamp DecommissionConfig stopped new payments at, 0 while the config is active
+    pub decommissioned_at: i64,
+
+    /// Number of Payment accounts under the config that ClosePayment has yet to close
+    pub payment_accounts: u32,
 }
 
 impl Discriminator for MerchantOperatorConfig {
@@ -56,13 +67,150 @@ impl AccountSerialize for MerchantOperatorConfig {
         data.extend_from_slice(&self.days_to_close.to_le_bytes());
         data.extend_from_slice(&self.num_policies.to_le_bytes());
         data.extend_from_slice(&self.num_accepted_currencies.to_le_bytes());
+        data.extend_from_slice(&self.config_hash);
+        data.extend_from_slice(&self.decommissioned_at.to_le_bytes());
+        data.extend_from_slice(&self.payment_accounts.to_le_bytes());
 
         data
     }
 }
 
+/// Zero-copy layout of a [`MerchantOperatorConfig`] account's fixed header. Policies and
+/// accepted currencies follow it.
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct MerchantOperatorConfigView {
+    discriminator: u8,
+    version: [u8; 4],
+    pub bump: u8,
+    pub merchant: Pubkey,
+    pub operator: Pubkey,
+    operator_fee: [u8; 8],
+    fee_type: u8,
+    current_order_id: [u8; 4],
+    days_to_close: [u8; 2],
+    num_policies: [u8; 4],
+    num_accepted_currencies: [u8; 4],
+    pub config_hash: [u8; 32],
+    decommissioned_at: [u8; 8],
+    payment_accounts: [u8; 4],
+}
+
+impl Discriminator for MerchantOperatorConfigView {
+    const DISCRIMINATOR: u8 = MerchantOperatorConfig::DISCRIMINATOR;
+}
+
+impl ZeroCopy for MerchantOperatorConfigView {}
+
+impl MerchantOperatorConfigView {
+    pub fn version(&self) -> u32 {
+        u32::from_le_bytes(self.version)
+    }
+
+    pub fn operator_fee(&self) -> u64 {
+        u64::from_le_bytes(self.operator_fee)
+    }
+
+    pub fn fee_type(&self) -> Result<FeeType, ProgramError> {
+        FeeType::from_u8(self.fee_type)
+    }
+
+    pub fn current_order_id(&self) -> u32 {
+        u32::from_le_bytes(self.current_order_id)
+    }
+
+    pub fn set_current_order_id(&mut self, current_order_id: u32) {
+        self.current_order_id = current_order_id.to_le_bytes();
+    }
+
+    pub fn days_to_close(&self) -> u16 {
+        u16::from_le_bytes(self.days_to_close)
+    }
+
+    pub fn num_policies(&self) -> u32 {
+        u32::from_le_bytes(self.num_policies)
+    }
+
+    pub fn num_accepted_currencies(&self) -> u32 {
+        u32::from_le_bytes(self.num_accepted_currencies)
+    }
+
+    pub fn decommissioned_at(&self) -> i64 {
+        i64::from_le_bytes(self.decommissioned_at)
+    }
+
+    pub fn payment_accounts(&self) -> u32 {
+        u32::from_le_bytes(self.payment_accounts)
+    }
+
+    pub fn set_payment_accounts(&mut self, payment_accounts: u32) {
+        self.payment_accounts = payment_accounts.to_le_bytes();
+    }
+}
+
+impl From<&MerchantOperatorConfig> for MerchantOperatorConfigView {
+    fn from(config: &MerchantOperatorConfig) -> Self {
+        Self {
+            discriminator: MerchantOperatorConfig::DISCRIMINATOR,
+            version: config.version.to_le_bytes(),
+            bump: config.bump,
+            merchant: config.merchant,
+            operator: config.operator,
+            operator_fee: config.operator_fee.to_le_bytes(),
+            fee_type: config.fee_type.to_u8(),
+            current_order_id: config.current_order_id.to_le_bytes(),
+            days_to_close: config.days_to_close.to_le_bytes(),
+            num_policies: config.num_policies.to_le_bytes(),
+            num_accepted_currencies: config.num_accepted_currencies.to_le_bytes(),
+            config_hash: config.config_hash,
+            decommissioned_at: config.decommissioned_at.to_le_bytes(),
+            payment_accounts: config.payment_accounts.to_le_bytes(),
+        }
+    }
+}
+
+impl TryFrom<&MerchantOperatorConfigView> for MerchantOperatorConfig {
+    type Error = ProgramError;
+
+    fn try_from(view: &MerchantOperatorConfigView) -> Result<Self, Self::Error> {
+        Ok(Self {
+            version: view.version(),
+            bump: view.bump,
+            merchant: view.merchant,
+            operator: view.operator,
+            operator_fee: view.operator_fee(),
+            fee_type: view.fee_type()?,
+            current_order_id: view.current_order_id(),
+            days_to_close: view.days_to_close(),
+            num_policies: view.num_policies(),
+            num_accepted_currencies: view.num_accepted_currencies(),
+            config_hash: view.config_hash,
+            decommissioned_at: view.decommissioned_at(),
+            payment_accounts: view.payment_accounts(),
+        })
+    }
+}
+
 impl MerchantOperatorConfig {
     pub const LEN: usize = 1 + // discriminator
+        4 + // version
+        1 + // bump
+        32 + // merchant
+        32 + // operator
+        8 + // operator_fee
+        1 + // fee_type
+        4 + // current_order_id
+        2 + // days_to_close
+        4 + // num_policies
+        4 + // num_accepted_currencies
+        32 + // config_hash
+        8 + // decommissioned_at
+        4; // payment_accounts
+
+    /// Size of the header of a config created before `config_hash`, `decommissioned_at` and
+    /// `payment_accounts`, which UpgradeAccount inserts ahead of the policies and accepted
+    /// currencies.
+    pub const LEGACY_LEN: usize = 1 + // discriminator
         4 + // version
         1 + // bump
         32 + // merchant
@@ -91,6 +239,9 @@ impl MerchantOperatorConfig {
         data.extend_from_slice(&self.days_to_close.to_le_bytes());
         data.extend_from_slice(&self.num_policies.to_le_bytes());
         data.extend_from_slice(&self.num_accepted_currencies.to_le_bytes());
+        data.extend_from_slice(&self.config_hash);
+        data.extend_from_slice(&self.decommissioned_at.to_le_bytes());
+        data.extend_from_slice(&self.payment_accounts.to_le_bytes());
 
         // Add policies
         for policy in policies {
@@ -102,9 +253,50 @@ impl MerchantOperatorConfig {
             data.extend_from_slice(currency.as_ref());
         }
 
+        // Add an open-payment count for each currency, none open yet
+        data.resize(data.len() + currencies.len() * 4, 0);
+
         data
     }
 
+    /// SHA-256 of the terms merchants and operators agree to, so off-chain contracts can bind
+    /// to an exact on-chain config: version, merchant, operator, operator_fee, fee_type and
+    /// days_to_close, then num_policies and each policy as stored, then
+    /// num_accepted_currencies and each currency. Integers are little-endian.
+    /// `current_order_id`, `bump`, `payment_accounts` and the open-payment counts are not terms and are left out.
+    pub fn compute_config_hash(&self, policies: &[PolicyData], currencies: &[Pubkey]) -> [u8; 32] {
+        let mut terms = Vec::with_capacity(
+            4 + 32
+                + 32
+                + 8
+                + 1
+                + 2
+                + 4
+                + policies.len() * PolicyData::SIZE
+                + 4
+                + currencies.len() * 32,
+        );
+
+        terms.extend_from_slice(&self.version.to_le_bytes());
+        terms.extend_from_slice(self.merchant.as_ref());
+        terms.extend_from_slice(self.operator.as_ref());
+        terms.extend_from_slice(&self.operator_fee.to_le_bytes());
+        terms.push(self.fee_type.to_u8());
+        terms.extend_from_slice(&self.days_to_close.to_le_bytes());
+
+        terms.extend_from_slice(&(policies.len() as u32).to_le_bytes());
+        for policy in policies {
+            terms.extend_from_slice(&policy.to_bytes());
+        }
+
+        terms.extend_from_slice(&(currencies.len() as u32).to_le_bytes());
+        for currency in currencies {
+            terms.extend_from_slice(currency.as_ref());
+        }
+
+        hashv(&[&terms])
+    }
+
     pub fn validate_operator(&self, operator: &Pubkey) -> Result<(), ProgramError> {
         if self.operator.ne(operator) {
             return Err(CommerceProgramError::OperatorMismatch.into());
@@ -119,6 +311,19 @@ impl MerchantOperatorConfig {
         Ok(())
     }
 
+    /// Whether DecommissionConfig has stopped new payments under the config.
+    pub fn is_decommissioned(&self) -> bool {
+        self.decommissioned_at != 0
+    }
+
+    /// Fails with `ConfigDecommissioned` once the config takes no new payments.
+    pub fn validate_not_decommissioned(&self) -> Result<(), ProgramError> {
+        if self.is_decommissioned() {
+            return Err(CommerceProgramError::ConfigDecommissioned.into());
+        }
+        Ok(())
+    }
+
     pub fn validate_order_id(&self, order_id: u32) -> Result<(), ProgramError> {
         if order_id == self.current_order_id {
             return Err(CommerceProgramError::OrderIdInvalid.into());
@@ -169,18 +374,17 @@ impl MerchantOperatorConfig {
     }
 
     pub fn get_policies(&self, account_data: &[u8]) -> Result<Vec<PolicyData>, ProgramError> {
-        let mut policies = Vec::new();
-        let mut offset = Self::LEN;
+        let mut reader = ByteReader::at(account_data, Self::LEN);
 
+        // Allocated once, for no more policies than the data can hold
+        let capacity = (self.num_policies as usize)
+            .min(account_data.len().saturating_sub(Self::LEN) / PolicyData::SIZE);
+        let mut policies = Vec::with_capacity(capacity);
         for _ in 0..self.num_policies {
-            if offset + PolicyData::SIZE > account_data.len() {
-                return Err(ProgramError::InvalidAccountData);
-            }
-            let policy = PolicyData::from_bytes(&account_data[offset..offset + PolicyData::SIZE])?;
-            policies.push(policy);
-            offset += PolicyData::SIZE;
+            policies.push(PolicyData::from_bytes(
+                reader.read_bytes(PolicyData::SIZE)?,
+            )?);
         }
-
         Ok(policies)
     }
 
@@ -204,20 +408,123 @@ impl MerchantOperatorConfig {
         &self,
         account_data: &[u8],
     ) -> Result<Vec<Pubkey>, ProgramError> {
-        let mut currencies = Vec::new();
-        let policies_size = self.num_policies as usize * PolicyData::SIZE;
-        let mut offset = Self::LEN + policies_size;
+        let mut reader = ByteReader::at(account_data, self.accepted_currencies_offset()?);
 
-        for _ in 0..self.num_accepted_currencies {
-            if offset + 32 > account_data.len() {
-                return Err(ProgramError::InvalidAccountData);
-            }
-            let currency: Pubkey = account_data[offset..offset + 32].try_into().unwrap();
-            currencies.push(currency);
-            offset += 32;
-        }
+        (0..self.num_accepted_currencies)
+            .map(|_| reader.read_pubkey())
+            .collect()
+    }
+
+    /// Whether `mint` is an accepted currency, checked in place in `account_data` instead of
+    /// collecting the currencies.
+    pub fn accepts_currency(
+        &self,
+        account_data: &[u8],
+        mint: &Pubkey,
+    ) -> Result<bool, ProgramError> {
+        let currencies_size = (self.num_accepted_currencies as usize)
+            .checked_mul(32)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let currencies = ByteReader::at(account_data, self.accepted_currencies_offset()?)
+            .read_bytes(currencies_size)?;
+
+        Ok(currencies
+            .chunks_exact(32)
+            .any(|currency| currency == mint.as_ref()))
+    }
+
+    fn accepted_currencies_offset(&self) -> Result<usize, ProgramError> {
+        (self.num_policies as usize)
+            .checked_mul(PolicyData::SIZE)
+            .and_then(|policies_size| policies_size.checked_add(Self::LEN))
+            .ok_or(ProgramError::InvalidAccountData)
+    }
 
-        Ok(currencies)
+    /// The open-payment counts follow the accepted currencies, one little-endian u32 per
+    /// currency in the same order.
+    fn open_payments_offset(&self) -> Result<usize, ProgramError> {
+        (self.num_accepted_currencies as usize)
+            .checked_mul(32)
+            .and_then(|currencies_size| {
+                currencies_size.checked_add(self.accepted_currencies_offset().ok()?)
+            })
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    /// Number of payments holding funds in the config's escrow, per accepted currency in the
+    /// order of `get_accepted_currencies`.
+    pub fn get_open_payments(&self, account_data: &[u8]) -> Result<Vec<u32>, ProgramError> {
+        let mut reader = ByteReader::at(account_data, self.open_payments_offset()?);
+
+        (0..self.num_accepted_currencies)
+            .map(|_| reader.read_u32())
+            .collect()
+    }
+
+    /// Number of payments in `mint` holding funds in the config's escrow: made into escrow and
+    /// not yet paid out of it or refunded. Fails with `InvalidMint` if `mint` is not accepted.
+    pub fn open_payments(&self, account_data: &[u8], mint: &Pubkey) -> Result<u32, ProgramError> {
+        let offset = self.open_payments_offset_of(account_data, mint)?;
+        ByteReader::at(account_data, offset).read_u32()
+    }
+
+    /// Counts a payment in `mint` whose funds went into the config's escrow.
+    pub fn increment_open_payments(
+        &self,
+        account_data: &mut [u8],
+        mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let open_payments = self
+            .open_payments(account_data, mint)?
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.write_open_payments(account_data, mint, open_payments)
+    }
+
+    /// Stops counting `count` payments in `mint` whose funds left the config's escrow. Stops at
+    /// zero, since payments made before UpgradeAccount added the counts were never counted.
+    pub fn decrement_open_payments(
+        &self,
+        account_data: &mut [u8],
+        mint: &Pubkey,
+        count: u32,
+    ) -> Result<(), ProgramError> {
+        let open_payments = self
+            .open_payments(account_data, mint)?
+            .saturating_sub(count);
+        self.write_open_payments(account_data, mint, open_payments)
+    }
+
+    fn write_open_payments(
+        &self,
+        account_data: &mut [u8],
+        mint: &Pubkey,
+        open_payments: u32,
+    ) -> Result<(), ProgramError> {
+        let offset = self.open_payments_offset_of(account_data, mint)?;
+        account_data
+            .get_mut(offset..offset + 4)
+            .ok_or(ProgramError::InvalidAccountData)?
+            .copy_from_slice(&open_payments.to_le_bytes());
+        Ok(())
+    }
+
+    fn open_payments_offset_of(
+        &self,
+        account_data: &[u8],
+        mint: &Pubkey,
+    ) -> Result<usize, ProgramError> {
+        let currencies_size = (self.num_accepted_currencies as usize)
+            .checked_mul(32)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let currencies = ByteReader::at(account_data, self.accepted_currencies_offset()?)
+            .read_bytes(currencies_size)?;
+        let index = currencies
+            .chunks_exact(32)
+            .position(|currency| currency == mint.as_ref())
+            .ok_or(CommerceProgramError::InvalidMint)?;
+
+        Ok(self.open_payments_offset()? + index * 4)
     }
 
     pub fn add_policy(&mut self, policy: PolicyData, account_data: &mut Vec<u8>) {
@@ -232,71 +539,144 @@ impl MerchantOperatorConfig {
     }
 
     pub fn add_accepted_currency(&mut self, currency: Pubkey, account_data: &mut Vec<u8>) {
-        account_data.extend_from_slice(currency.as_ref());
+        let currencies_end = Self::LEN
+            + (self.num_policies as usize * PolicyData::SIZE)
+            + (self.num_accepted_currencies as usize * 32);
+
+        // Insert the currency before the open-payment counts and append its count
+        account_data.splice(
+            currencies_end..currencies_end,
+            currency.as_ref().iter().copied(),
+        );
+        account_data.extend_from_slice(&0u32.to_le_bytes());
+
         self.num_accepted_currencies += 1;
     }
 
+    /// Checks that `num_policies` and `num_accepted_currencies` are within `MAX_POLICIES` and
+    /// `MAX_ACCEPTED_CURRENCIES`.
+    pub fn validate_limits(
+        num_policies: usize,
+        num_accepted_currencies: usize,
+    ) -> Result<(), ProgramError> {
+        if num_policies > MAX_POLICIES {
+            return Err(CommerceProgramError::TooManyPolicies.into());
+        }
+        if num_accepted_currencies > MAX_ACCEPTED_CURRENCIES {
+            return Err(CommerceProgramError::TooManyAcceptedCurrencies.into());
+        }
+        Ok(())
+    }
+
     pub fn calculate_size(&self) -> usize {
         Self::LEN
             + (self.num_policies as usize * PolicyData::SIZE)
-            + (self.num_accepted_currencies as usize * 32)
+            + (self.num_accepted_currencies as usize * (32 + 4))
     }
 
-    pub fn try_from_bytes(
+    /// Reads a config in the `LEGACY_LEN` layout along with its policies and accepted
+    /// currencies, which must fill the rest of `data`. Its terms are hashed and it is active,
+    /// with no open payments counted.
+    pub fn from_legacy_bytes(
         data: &[u8],
     ) -> Result<(Self, Vec<PolicyData>, Vec<Pubkey>), ProgramError> {
-        if data[0] != Self::DISCRIMINATOR {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let mut offset: usize = 1;
-
-        let version = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
-        offset += 4;
-
-        let bump = data[offset];
-        offset += 1;
-
-        let merchant: Pubkey = data[offset..offset + 32].try_into().unwrap();
-        offset += 32;
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        let mut config = Self {
+            version: reader.read_u32()?,
+            bump: reader.read_u8()?,
+            merchant: reader.read_pubkey()?,
+            operator: reader.read_pubkey()?,
+            operator_fee: reader.read_u64()?,
+            fee_type: FeeType::from_u8(reader.read_u8()?)?,
+            current_order_id: reader.read_u32()?,
+            days_to_close: reader.read_u16()?,
+            num_policies: reader.read_u32()?,
+            num_accepted_currencies: reader.read_u32()?,
+            config_hash: [0u8; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
 
-        let operator: Pubkey = data[offset..offset + 32].try_into().unwrap();
-        offset += 32;
+        // A config in the current layout is 44 bytes, and 4 more per currency, longer than its
+        // counts account for here
+        let legacy_size = (config.num_policies as usize)
+            .checked_mul(PolicyData::SIZE)
+            .zip((config.num_accepted_currencies as usize).checked_mul(32))
+            .and_then(|(policies_size, currencies_size)| {
+                Self::LEGACY_LEN
+                    .checked_add(policies_size)?
+                    .checked_add(currencies_size)
+            });
+        if legacy_size != Some(data.len()) {
+            return Err(CommerceProgramError::AccountNotUpgradable.into());
+        }
 
-        let operator_fee = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
+        let policies = (0..config.num_policies)
+            .map(|_| PolicyData::from_bytes(reader.read_bytes(PolicyData::SIZE)?))
+            .collect::<Result<Vec<_>, _>>()?;
+        let currencies = (0..config.num_accepted_currencies)
+            .map(|_| reader.read_pubkey())
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let fee_type = FeeType::from_u8(data[offset])?;
-        offset += 1;
+        config.config_hash = config.compute_config_hash(&policies, &currencies);
 
-        let current_order_id = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
-        offset += 4;
+        Ok((config, policies, currencies))
+    }
 
-        let days_to_close = u16::from_le_bytes(data[offset..offset + 2].try_into().unwrap());
-        offset += 2;
+    pub fn try_from_bytes(
+        data: &[u8],
+    ) -> Result<(Self, Vec<PolicyData>, Vec<Pubkey>), ProgramError> {
+        let config = Self::header_from_bytes(data)?;
+        let policies = config.get_policies(data)?;
+        let currencies = config.get_accepted_currencies(data)?;
+        // The open-payment counts close the account
+        config.get_open_payments(data)?;
+        Ok((config, policies, currencies))
+    }
 
-        let num_policies = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
-        offset += 4;
+    /// Reads the fixed header, leaving the policies and accepted currencies after it to
+    /// `get_policies`, `get_accepted_currencies` and `accepts_currency`.
+    #[cfg(not(feature = "legacy-serialization"))]
+    pub fn header_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from(MerchantOperatorConfigView::load(data)?)
+    }
 
-        let num_accepted_currencies =
-            u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    /// Writes the fixed header over `data`, leaving policies and accepted currencies untouched.
+    #[cfg(not(feature = "legacy-serialization"))]
+    pub fn store(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        MerchantOperatorConfigView::from(self).store(data)
+    }
 
-        let config = Self {
-            version,
-            bump,
-            merchant,
-            operator,
-            operator_fee,
-            fee_type,
-            current_order_id,
-            days_to_close,
-            num_policies,
-            num_accepted_currencies,
-        };
+    /// Reads the fixed header, leaving the policies and accepted currencies after it to
+    /// `get_policies`, `get_accepted_currencies` and `accepts_currency`.
+    #[cfg(feature = "legacy-serialization")]
+    pub fn header_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            version: reader.read_u32()?,
+            bump: reader.read_u8()?,
+            merchant: reader.read_pubkey()?,
+            operator: reader.read_pubkey()?,
+            operator_fee: reader.read_u64()?,
+            fee_type: FeeType::from_u8(reader.read_u8()?)?,
+            current_order_id: reader.read_u32()?,
+            days_to_close: reader.read_u16()?,
+            num_policies: reader.read_u32()?,
+            num_accepted_currencies: reader.read_u32()?,
+            config_hash: reader.read_array()?,
+            decommissioned_at: reader.read_i64()?,
+            payment_accounts: reader.read_u32()?,
+        })
+    }
 
-        let policies = config.get_policies(data)?;
-        let currencies = config.get_accepted_currencies(data)?;
-        Ok((config, policies, currencies))
+    /// Writes the fixed header over `data`, leaving policies and accepted currencies untouched.
+    #[cfg(feature = "legacy-serialization")]
+    pub fn store(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        super::zero_copy::write_prefix(&AccountSerialize::to_bytes(self), data)
     }
 }
 
@@ -410,6 +790,9 @@ mod tests {
             days_to_close: 7,
             num_policies: 0,
             num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
         };
 
         assert!(config.validate_operator(&operator).is_ok());
@@ -430,6 +813,9 @@ mod tests {
             days_to_close: 7,
             num_policies: 0,
             num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
         };
 
         assert!(config.validate_operator(&wrong_operator).is_err());
@@ -449,6 +835,9 @@ mod tests {
             days_to_close: 7,
             num_policies: 0,
             num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
         };
 
         assert!(config.validate_merchant(&merchant).is_ok());
@@ -469,11 +858,42 @@ mod tests {
             days_to_close: 7,
             num_policies: 0,
             num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
         };
 
         assert!(config.validate_merchant(&wrong_merchant).is_err());
     }
 
+    #[test]
+    fn test_validate_not_decommissioned() {
+        let mut config = MerchantOperatorConfig {
+            version: 1,
+            bump: 255,
+            merchant: create_test_merchant(),
+            operator: create_test_operator(),
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 0,
+            days_to_close: 7,
+            num_policies: 0,
+            num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        assert!(!config.is_decommissioned());
+        assert!(config.validate_not_decommissioned().is_ok());
+
+        config.decommissioned_at = 1_700_000_000;
+        assert!(config.is_decommissioned());
+        assert_eq!(
+            config.validate_not_decommissioned(),
+            Err(CommerceProgramError::ConfigDecommissioned.into())
+        );
+    }
+
     #[test]
     fn test_validate_operator_and_merchant_success() {
         let merchant = create_test_merchant();
@@ -489,6 +909,9 @@ mod tests {
             days_to_close: 7,
             num_policies: 0,
             num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
         };
 
         assert!(config
@@ -512,6 +935,9 @@ mod tests {
             days_to_close: 7,
             num_policies: 0,
             num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
         };
 
         assert!(config
@@ -535,6 +961,9 @@ mod tests {
             days_to_close: 7,
             num_policies: 0,
             num_accepted_currencies: 0,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
         };
 
         assert!(config
@@ -600,4 +1029,403 @@ mod tests {
             MerchantOperatorConfig::get_policy_by_type(&empty_policies, PolicyType::Refund);
         assert_eq!(found_any, None);
     }
+
+    #[test]
+    fn test_compute_config_hash() {
+        let config = MerchantOperatorConfig {
+            version: 1,
+            bump: 255,
+            merchant: create_test_merchant(),
+            operator: create_test_operator(),
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 0,
+            days_to_close: 7,
+            num_policies: 1,
+            num_accepted_currencies: 2,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        let policies = vec![create_test_refund_policy()];
+        let currencies = vec![Pubkey::from([8; 32]), Pubkey::from([9; 32])];
+        let hash = config.compute_config_hash(&policies, &currencies);
+
+        // Terms are hashed in a fixed order, policies in their stored 101-byte slots
+        let mut terms = Vec::new();
+        terms.extend_from_slice(&1u32.to_le_bytes());
+        terms.extend_from_slice(&[1; 32]);
+        terms.extend_from_slice(&[2; 32]);
+        terms.extend_from_slice(&100u64.to_le_bytes());
+        terms.push(FeeType::Bps.to_u8());
+        terms.extend_from_slice(&7u16.to_le_bytes());
+        terms.extend_from_slice(&1u32.to_le_bytes());
+        terms.extend_from_slice(&policies[0].to_bytes());
+        terms.extend_from_slice(&2u32.to_le_bytes());
+        terms.extend_from_slice(&[8; 32]);
+        terms.extend_from_slice(&[9; 32]);
+        assert_eq!(hash, hashv(&[&terms]));
+
+        // Fields that are not terms leave the hash unchanged
+        let updated = MerchantOperatorConfig {
+            bump: 254,
+            current_order_id: 42,
+            config_hash: hash,
+            decommissioned_at: 1_700_000_000,
+            payment_accounts: 0,
+            ..config.clone()
+        };
+        assert_eq!(updated.compute_config_hash(&policies, &currencies), hash);
+
+        let other_fee = MerchantOperatorConfig {
+            operator_fee: 101,
+            ..config.clone()
+        };
+        assert_ne!(other_fee.compute_config_hash(&policies, &currencies), hash);
+        assert_ne!(config.compute_config_hash(&[], &currencies), hash);
+
+        let mut reordered = currencies.clone();
+        reordered.reverse();
+        assert_ne!(config.compute_config_hash(&policies, &reordered), hash);
+    }
+
+    #[test]
+    fn test_from_legacy_bytes() {
+        let policies = vec![create_test_refund_policy()];
+        let currencies = vec![Pubkey::from([8; 32]), Pubkey::from([9; 32])];
+        let mut config = MerchantOperatorConfig {
+            version: 1,
+            bump: 255,
+            merchant: create_test_merchant(),
+            operator: create_test_operator(),
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 7,
+            days_to_close: 7,
+            num_policies: 1,
+            num_accepted_currencies: 2,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        config.config_hash = config.compute_config_hash(&policies, &currencies);
+        let data = config.to_bytes(&policies, &currencies);
+
+        // The legacy layout has no config_hash or decommissioned_at between the header and
+        // the policies, and no open-payment counts after the currencies
+        let mut legacy = data[..MerchantOperatorConfig::LEGACY_LEN].to_vec();
+        legacy.extend_from_slice(&data[MerchantOperatorConfig::LEN..data.len() - 2 * 4]);
+        assert_eq!(
+            MerchantOperatorConfig::from_legacy_bytes(&legacy).unwrap(),
+            (config, policies, currencies)
+        );
+
+        assert_eq!(
+            MerchantOperatorConfig::from_legacy_bytes(&data).unwrap_err(),
+            CommerceProgramError::AccountNotUpgradable.into()
+        );
+        assert_eq!(
+            MerchantOperatorConfig::from_legacy_bytes(&legacy[..legacy.len() - 1]).unwrap_err(),
+            CommerceProgramError::AccountNotUpgradable.into()
+        );
+    }
+
+    #[test]
+    fn test_try_from_bytes_short_data() {
+        assert_eq!(
+            MerchantOperatorConfig::try_from_bytes(&[]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            MerchantOperatorConfig::try_from_bytes(&[MerchantOperatorConfig::DISCRIMINATOR; 10]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_view_matches_serialization() {
+        let mut config = MerchantOperatorConfig {
+            version: 3,
+            bump: 254,
+            merchant: create_test_merchant(),
+            operator: create_test_operator(),
+            operator_fee: 250,
+            fee_type: FeeType::Fixed,
+            current_order_id: 41,
+            days_to_close: 30,
+            num_policies: 1,
+            num_accepted_currencies: 1,
+            config_hash: [5; 32],
+            decommissioned_at: 1_700_000_000,
+            payment_accounts: 0,
+        };
+        let policies = vec![create_test_refund_policy()];
+        let currencies = vec![Pubkey::from([9; 32])];
+
+        assert_eq!(
+            core::mem::size_of::<MerchantOperatorConfigView>(),
+            MerchantOperatorConfig::LEN
+        );
+        let mut data = config.to_bytes(&policies, &currencies);
+        assert_eq!(
+            bytemuck::bytes_of(&MerchantOperatorConfigView::from(&config)),
+            &data[..MerchantOperatorConfig::LEN]
+        );
+
+        // Storing the header leaves the trailing policies and currencies in place
+        config.current_order_id = 42;
+        config.store(&mut data).unwrap();
+        assert_eq!(
+            MerchantOperatorConfig::try_from_bytes(&data).unwrap(),
+            (config, policies, currencies)
+        );
+
+        let view = MerchantOperatorConfigView::load_mut(&mut data).unwrap();
+        view.set_current_order_id(43);
+        assert_eq!(view.current_order_id(), 43);
+        assert_eq!(view.fee_type().unwrap(), FeeType::Fixed);
+    }
+
+    #[test]
+    fn test_accepts_currency() {
+        let config = MerchantOperatorConfig {
+            version: 1,
+            bump: 255,
+            merchant: create_test_merchant(),
+            operator: create_test_operator(),
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 0,
+            days_to_close: 7,
+            num_policies: 2,
+            num_accepted_currencies: 2,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        let policies = vec![create_test_refund_policy(), create_test_settlement_policy()];
+        let currencies = vec![Pubkey::from([8; 32]), Pubkey::from([9; 32])];
+        let data = config.to_bytes(&policies, &currencies);
+
+        assert_eq!(
+            MerchantOperatorConfig::header_from_bytes(&data),
+            Ok(config.clone())
+        );
+        assert_eq!(config.get_policies(&data), Ok(policies));
+        assert_eq!(config.accepts_currency(&data, &[9; 32]), Ok(true));
+        assert_eq!(config.accepts_currency(&data, &[7; 32]), Ok(false));
+
+        // A truncated currency list is invalid rather than not accepting the mint
+        let currencies_end = data.len() - 2 * 4;
+        assert_eq!(
+            config.accepts_currency(&data[..currencies_end - 1], &[8; 32]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_open_payments() {
+        let mut config = MerchantOperatorConfig {
+            version: 1,
+            bump: 255,
+            merchant: create_test_merchant(),
+            operator: create_test_operator(),
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 0,
+            days_to_close: 7,
+            num_policies: 1,
+            num_accepted_currencies: 2,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        let policies = vec![create_test_refund_policy()];
+        let currencies = vec![Pubkey::from([8; 32]), Pubkey::from([9; 32])];
+        let mut data = config.to_bytes(&policies, &currencies);
+        assert_eq!(config.get_open_payments(&data), Ok(vec![0, 0]));
+
+        config.increment_open_payments(&mut data, &[9; 32]).unwrap();
+        config.increment_open_payments(&mut data, &[9; 32]).unwrap();
+        config.increment_open_payments(&mut data, &[8; 32]).unwrap();
+        assert_eq!(config.open_payments(&data, &[9; 32]), Ok(2));
+        assert_eq!(config.get_open_payments(&data), Ok(vec![1, 2]));
+
+        // Counting leaves the currencies in place
+        assert_eq!(config.get_accepted_currencies(&data), Ok(currencies));
+
+        // Uncounting stops at zero, for payments made before the counts existed
+        config
+            .decrement_open_payments(&mut data, &[9; 32], 3)
+            .unwrap();
+        assert_eq!(config.get_open_payments(&data), Ok(vec![1, 0]));
+
+        // A policy goes before the currencies and a currency before the counts, which keep
+        // their values
+        config.add_policy(create_test_settlement_policy(), &mut data);
+        config.add_accepted_currency(Pubkey::from([7; 32]), &mut data);
+        assert_eq!(data.len(), config.calculate_size());
+        assert_eq!(config.get_open_payments(&data), Ok(vec![1, 0, 0]));
+        assert_eq!(config.open_payments(&data, &[8; 32]), Ok(1));
+
+        assert_eq!(
+            config.increment_open_payments(&mut data, &[6; 32]),
+            Err(CommerceProgramError::InvalidMint.into())
+        );
+        assert_eq!(
+            config.open_payments(&data[..data.len() - 1], &[7; 32]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        assert_eq!(
+            MerchantOperatorConfig::validate_limits(MAX_POLICIES, MAX_ACCEPTED_CURRENCIES),
+            Ok(())
+        );
+        assert_eq!(
+            MerchantOperatorConfig::validate_limits(MAX_POLICIES + 1, 1),
+            Err(CommerceProgramError::TooManyPolicies.into())
+        );
+        assert_eq!(
+            MerchantOperatorConfig::validate_limits(0, MAX_ACCEPTED_CURRENCIES + 1),
+            Err(CommerceProgramError::TooManyAcceptedCurrencies.into())
+        );
+    }
+
+    #[test]
+    fn test_grow_to_limits() {
+        let policies = vec![create_test_refund_policy(); MAX_POLICIES - 1];
+        let currencies: Vec<Pubkey> = (0..MAX_ACCEPTED_CURRENCIES as u8 - 1)
+            .map(|i| Pubkey::from([i; 32]))
+            .collect();
+        let mut config = MerchantOperatorConfig {
+            version: 1,
+            bump: 255,
+            merchant: create_test_merchant(),
+            operator: create_test_operator(),
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 0,
+            days_to_close: 7,
+            num_policies: policies.len() as u32,
+            num_accepted_currencies: currencies.len() as u32,
+            config_hash: [0; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        let mut data = config.to_bytes(&policies, &currencies);
+
+        // Growing a full config inserts the policy before the currencies and appends the mint
+        config.add_policy(create_test_settlement_policy(), &mut data);
+        config.add_accepted_currency(Pubkey::from([0xff; 32]), &mut data);
+        config.store(&mut data).unwrap();
+
+        assert_eq!(data.len(), config.calculate_size());
+        assert_eq!(
+            data.len(),
+            MerchantOperatorConfig::LEN
+                + MAX_POLICIES * PolicyData::SIZE
+                + MAX_ACCEPTED_CURRENCIES * (32 + 4)
+        );
+        let (decoded, decoded_policies, decoded_currencies) =
+            MerchantOperatorConfig::try_from_bytes(&data).unwrap();
+        assert_eq!(decoded, config);
+        assert_eq!(decoded_policies.len(), MAX_POLICIES);
+        assert_eq!(
+            decoded_policies.last(),
+            Some(&create_test_settlement_policy())
+        );
+        assert_eq!(decoded_currencies[..currencies.len()], currencies[..]);
+        assert_eq!(decoded_currencies.last(), Some(&Pubkey::from([0xff; 32])));
+        assert_eq!(
+            MerchantOperatorConfig::validate_limits(
+                decoded.num_policies as usize,
+                decoded.num_accepted_currencies as usize
+            ),
+            Ok(())
+        );
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use crate::state::policy::strategies::{fee_type, policy_data};
+    use proptest::{collection::vec as prop_vec, prelude::*};
+
+    fn config_parts(
+    ) -> impl Strategy<Value = (MerchantOperatorConfig, Vec<PolicyData>, Vec<Pubkey>)> {
+        (
+            (any::<u32>(), any::<u8>(), any::<Pubkey>(), any::<Pubkey>()),
+            (any::<u64>(), fee_type(), any::<u32>(), any::<u16>()),
+            prop_vec(policy_data(), 0..4),
+            prop_vec(any::<Pubkey>(), 0..4),
+        )
+            .prop_map(
+                |(
+                    (version, bump, merchant, operator),
+                    (operator_fee, fee_type, current_order_id, days_to_close),
+                    policies,
+                    currencies,
+                )| {
+                    let config = MerchantOperatorConfig {
+                        version,
+                        bump,
+                        merchant,
+                        operator,
+                        operator_fee,
+                        fee_type,
+                        current_order_id,
+                        days_to_close,
+                        num_policies: policies.len() as u32,
+                        num_accepted_currencies: currencies.len() as u32,
+                        config_hash: [0; 32],
+                        decommissioned_at: 0,
+                        payment_accounts: 0,
+                    };
+                    let config = MerchantOperatorConfig {
+                        config_hash: config.compute_config_hash(&policies, &currencies),
+                        ..config
+                    };
+                    (config, policies, currencies)
+                },
+            )
+    }
+
+    /// Arbitrary bytes, half of them carrying the config discriminator so parsing gets past
+    /// the first check.
+    fn account_data() -> impl Strategy<Value = Vec<u8>> {
+        (any::<bool>(), prop_vec(any::<u8>(), 0..600)).prop_map(|(tag, mut data)| {
+            if tag && !data.is_empty() {
+                data[0] = MerchantOperatorConfig::DISCRIMINATOR;
+            }
+            data
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn config_round_trips((config, policies, currencies) in config_parts()) {
+            let data = config.to_bytes(&policies, &currencies);
+            prop_assert_eq!(data.len(), config.calculate_size());
+            prop_assert_eq!(
+                MerchantOperatorConfig::try_from_bytes(&data),
+                Ok((config, policies, currencies))
+            );
+        }
+
+        #[test]
+        fn config_from_arbitrary_bytes_is_stable(data in account_data()) {
+            // Must never panic; anything it accepts must survive a re-encode
+            if let Ok((config, policies, currencies)) = MerchantOperatorConfig::try_from_bytes(&data) {
+                let encoded = config.to_bytes(&policies, &currencies);
+                prop_assert_eq!(
+                    MerchantOperatorConfig::try_from_bytes(&encoded),
+                    Ok((config, policies, currencies))
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,287 @@
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{
+    constants::{MAX_MERCHANT_NAME_LEN, MAX_MERCHANT_URI_LEN, MERCHANT_PROFILE_SEED},
+    error::CommerceProgramError,
+};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// Public display information for a merchant, for wallets and explorers to show who the buyer
+/// is paying. Optional and not read by any payment instruction.
+///
+/// `name` and `uri` are stored length-prefixed (u32 LE) like borsh strings; the account is
+/// allocated at `LEN` so updates never need to resize it, and the bytes after `uri` are zero.
+///
+/// Seeds: [b"merchant_profile", merchant pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct MerchantProfile {
+    pub merchant: Pubkey,
+
+    pub bump: u8,
+
+    /// SHA-256 of the merchant's logo image
+    pub logo_hash: [u8; 32],
+
+    /// SHA-256 of the merchant's support contact, e.g. an email address
+    pub contact_hash: [u8; 32],
+
+    /// Display name, at most `MAX_MERCHANT_NAME_LEN` bytes
+    pub name: String,
+
+    /// Website or metadata URI, at most `MAX_MERCHANT_URI_LEN` bytes
+    pub uri: String,
+}
+
+impl Discriminator for MerchantProfile {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::MerchantProfileDiscriminator as u8;
+}
+
+impl AccountSerialize for MerchantProfile {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(&self.logo_hash);
+        data.extend_from_slice(&self.contact_hash);
+        data.extend_from_slice(&(self.name.len() as u32).to_le_bytes());
+        data.extend_from_slice(self.name.as_bytes());
+        data.extend_from_slice(&(self.uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(self.uri.as_bytes());
+        data
+    }
+}
+
+impl MerchantProfile {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant
+        1 + // bump
+        32 + // logo_hash
+        32 + // contact_hash
+        4 + MAX_MERCHANT_NAME_LEN + // name
+        4 + MAX_MERCHANT_URI_LEN; // uri
+
+    /// Offset of the length-prefixed `name`
+    const NAME_OFFSET: usize = 98;
+
+    pub fn new(
+        merchant: Pubkey,
+        bump: u8,
+        name: String,
+        uri: String,
+        logo_hash: [u8; 32],
+        contact_hash: [u8; 32],
+    ) -> Result<Self, ProgramError> {
+        let mut profile = Self {
+            merchant,
+            bump,
+            logo_hash,
+            contact_hash,
+            name: String::new(),
+            uri: String::new(),
+        };
+        profile.update(name, uri, logo_hash, contact_hash)?;
+        Ok(profile)
+    }
+
+    /// Replaces every display field, validating the length bounds.
+    pub fn update(
+        &mut self,
+        name: String,
+        uri: String,
+        logo_hash: [u8; 32],
+        contact_hash: [u8; 32],
+    ) -> Result<(), ProgramError> {
+        if name.len() > MAX_MERCHANT_NAME_LEN || uri.len() > MAX_MERCHANT_URI_LEN {
+            return Err(CommerceProgramError::MerchantProfileFieldTooLong.into());
+        }
+        self.name = name;
+        self.uri = uri;
+        self.logo_hash = logo_hash;
+        self.contact_hash = contact_hash;
+        Ok(())
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[MERCHANT_PROFILE_SEED, self.merchant.as_ref()],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::MerchantProfileInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_merchant(&self, merchant: &Pubkey) -> Result<(), ProgramError> {
+        if self.merchant.ne(merchant) {
+            return Err(CommerceProgramError::MerchantProfileInvalidPda.into());
+        }
+        Ok(())
+    }
+
+    /// Writes the profile into `data`, zeroing the unused tail.
+    pub fn write(&self, data: &mut [u8]) {
+        let bytes = self.to_bytes();
+        data.fill(0);
+        data[..bytes.len()].copy_from_slice(&bytes);
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+        let merchant = reader.read_pubkey()?;
+        let bump = reader.read_u8()?;
+        let logo_hash = reader.read_array()?;
+        let contact_hash = reader.read_array()?;
+
+        let (name, offset) = read_string(data, Self::NAME_OFFSET, MAX_MERCHANT_NAME_LEN)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let (uri, _) = read_string(data, offset, MAX_MERCHANT_URI_LEN)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        Ok(Self {
+            merchant,
+            bump,
+            logo_hash,
+            contact_hash,
+            name,
+            uri,
+        })
+    }
+}
+
+/// Reads a u32 LE length-prefixed UTF-8 string of at most `max_len` bytes at `offset`,
+/// returning it and the offset after it.
+pub fn read_string(
+    data: &[u8],
+    offset: usize,
+    max_len: usize,
+) -> Result<(String, usize), ProgramError> {
+    let mut reader = ByteReader::at(data, offset);
+    let len = reader
+        .read_u32()
+        .map_err(|_| ProgramError::InvalidInstructionData)? as usize;
+    if len > max_len {
+        return Err(CommerceProgramError::MerchantProfileFieldTooLong.into());
+    }
+
+    let bytes = reader
+        .read_bytes(len)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let string = core::str::from_utf8(bytes).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    Ok((String::from(string), reader.offset()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn new_profile() -> MerchantProfile {
+        MerchantProfile::new(
+            [1u8; 32],
+            254,
+            String::from("Coffee Shop"),
+            String::from("https://coffee.example"),
+            [2u8; 32],
+            [3u8; 32],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merchant_profile_serialization() {
+        let profile = new_profile();
+
+        let mut data = vec![0xff; MerchantProfile::LEN];
+        profile.write(&mut data);
+        assert_eq!(data[0], MerchantProfile::DISCRIMINATOR);
+        assert_eq!(*data.last().unwrap(), 0);
+
+        let deserialized = MerchantProfile::try_from_bytes(&data).unwrap();
+        assert_eq!(deserialized, profile);
+    }
+
+    #[test]
+    fn test_merchant_profile_max_len_fits() {
+        let profile = MerchantProfile::new(
+            [1u8; 32],
+            254,
+            "n".repeat(MAX_MERCHANT_NAME_LEN),
+            "u".repeat(MAX_MERCHANT_URI_LEN),
+            [2u8; 32],
+            [3u8; 32],
+        )
+        .unwrap();
+        assert_eq!(profile.to_bytes().len(), MerchantProfile::LEN);
+    }
+
+    #[test]
+    fn test_merchant_profile_field_too_long() {
+        let mut profile = new_profile();
+        for (name, uri) in [
+            ("n".repeat(MAX_MERCHANT_NAME_LEN + 1), String::new()),
+            (String::new(), "u".repeat(MAX_MERCHANT_URI_LEN + 1)),
+        ] {
+            assert_eq!(
+                profile.update(name, uri, [0u8; 32], [0u8; 32]).unwrap_err(),
+                CommerceProgramError::MerchantProfileFieldTooLong.into()
+            );
+        }
+        assert_eq!(profile, new_profile());
+    }
+
+    #[test]
+    fn test_merchant_profile_try_from_bytes_invalid() {
+        let mut data = vec![0; MerchantProfile::LEN];
+        data[0] = 99; // Wrong discriminator
+        assert_eq!(
+            MerchantProfile::try_from_bytes(&data).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        data[0] = MerchantProfile::DISCRIMINATOR;
+        data[98..102].copy_from_slice(&1u32.to_le_bytes());
+        data[102] = 0xff; // Invalid UTF-8
+        assert_eq!(
+            MerchantProfile::try_from_bytes(&data).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_read_string() {
+        let mut data = vec![0u8; 4];
+        data[0] = 3;
+        data.extend_from_slice(b"abc");
+        assert_eq!(read_string(&data, 0, 3).unwrap(), (String::from("abc"), 7));
+        assert_eq!(
+            read_string(&data, 0, 2).unwrap_err(),
+            CommerceProgramError::MerchantProfileFieldTooLong.into()
+        );
+        assert_eq!(
+            read_string(&data[..6], 0, 3).unwrap_err(),
+            ProgramError::InvalidInstructionData
+        );
+    }
+}
@@ -0,0 +1,249 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{constants::MERCHANT_STATS_SEED, error::CommerceProgramError};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// Lifetime payment totals for a merchant in a single currency.
+///
+/// Optional: MakePayment, ClearPayment, RefundPayment and ClosePayment only update it
+/// when it is passed as the first remaining account.
+///
+/// Seeds: [b"merchant_stats", merchant pubkey, mint pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct MerchantStats {
+    pub merchant: Pubkey,
+
+    pub mint: Pubkey,
+
+    pub bump: u8,
+
+    /// Number of payments made
+    pub payment_count: u64,
+
+    /// Sum of all payment amounts
+    pub total_volume: u64,
+
+    /// Sum of payment amounts cleared (including auto-settled payments)
+    pub total_cleared: u64,
+
+    /// Sum of operator and platform fees paid on cleared payments
+    pub total_fees_paid: u64,
+
+    /// Number of payments refunded
+    pub refund_count: u64,
+
+    /// Sum of refunded amounts
+    pub total_refunded: u64,
+
+    /// Number of payment accounts closed
+    pub closed_count: u64,
+}
+
+impl Discriminator for MerchantStats {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::MerchantStatsDiscriminator as u8;
+}
+
+impl AccountSerialize for MerchantStats {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant.as_ref());
+        data.extend_from_slice(self.mint.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(&self.payment_count.to_le_bytes());
+        data.extend_from_slice(&self.total_volume.to_le_bytes());
+        data.extend_from_slice(&self.total_cleared.to_le_bytes());
+        data.extend_from_slice(&self.total_fees_paid.to_le_bytes());
+        data.extend_from_slice(&self.refund_count.to_le_bytes());
+        data.extend_from_slice(&self.total_refunded.to_le_bytes());
+        data.extend_from_slice(&self.closed_count.to_le_bytes());
+        data
+    }
+}
+
+impl MerchantStats {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant
+        32 + // mint
+        1 + // bump
+        8 + // payment_count
+        8 + // total_volume
+        8 + // total_cleared
+        8 + // total_fees_paid
+        8 + // refund_count
+        8 + // total_refunded
+        8; // closed_count
+
+    pub fn new(merchant: Pubkey, mint: Pubkey, bump: u8) -> Self {
+        Self {
+            merchant,
+            mint,
+            bump,
+            payment_count: 0,
+            total_volume: 0,
+            total_cleared: 0,
+            total_fees_paid: 0,
+            refund_count: 0,
+            total_refunded: 0,
+            closed_count: 0,
+        }
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[
+                MERCHANT_STATS_SEED,
+                self.merchant.as_ref(),
+                self.mint.as_ref(),
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::MerchantStatsInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_merchant_and_mint(
+        &self,
+        merchant: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.merchant.ne(merchant) || self.mint.ne(mint) {
+            return Err(CommerceProgramError::MerchantStatsMismatch.into());
+        }
+        Ok(())
+    }
+
+    // Totals saturate rather than fail: stats must never block a payment.
+
+    pub fn record_payment(&mut self, amount: u64) {
+        self.payment_count = self.payment_count.saturating_add(1);
+        self.total_volume = self.total_volume.saturating_add(amount);
+    }
+
+    pub fn record_clear(&mut self, amount: u64, fees: u64) {
+        self.total_cleared = self.total_cleared.saturating_add(amount);
+        self.total_fees_paid = self.total_fees_paid.saturating_add(fees);
+    }
+
+    pub fn record_refund(&mut self, amount: u64) {
+        self.refund_count = self.refund_count.saturating_add(1);
+        self.total_refunded = self.total_refunded.saturating_add(amount);
+    }
+
+    pub fn record_close(&mut self) {
+        self.closed_count = self.closed_count.saturating_add(1);
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            merchant: reader.read_pubkey()?,
+            mint: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            payment_count: reader.read_u64()?,
+            total_volume: reader.read_u64()?,
+            total_cleared: reader.read_u64()?,
+            total_fees_paid: reader.read_u64()?,
+            refund_count: reader.read_u64()?,
+            total_refunded: reader.read_u64()?,
+            closed_count: reader.read_u64()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_merchant_stats_serialization() {
+        let mut stats = MerchantStats::new([1u8; 32], [2u8; 32], 253);
+        stats.record_payment(1_000);
+        stats.record_clear(1_000, 50);
+        stats.record_refund(400);
+        stats.record_close();
+
+        let bytes = stats.to_bytes();
+        assert_eq!(bytes.len(), MerchantStats::LEN);
+
+        let deserialized = MerchantStats::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, stats);
+    }
+
+    #[test]
+    fn test_merchant_stats_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; MerchantStats::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = MerchantStats::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_merchant_stats_records() {
+        let mut stats = MerchantStats::new([1u8; 32], [2u8; 32], 255);
+
+        stats.record_payment(1_000);
+        stats.record_payment(2_000);
+        stats.record_clear(2_000, 100);
+        stats.record_refund(1_000);
+        stats.record_close();
+
+        assert_eq!(stats.payment_count, 2);
+        assert_eq!(stats.total_volume, 3_000);
+        assert_eq!(stats.total_cleared, 2_000);
+        assert_eq!(stats.total_fees_paid, 100);
+        assert_eq!(stats.refund_count, 1);
+        assert_eq!(stats.total_refunded, 1_000);
+        assert_eq!(stats.closed_count, 1);
+    }
+
+    #[test]
+    fn test_merchant_stats_saturates() {
+        let mut stats = MerchantStats::new([1u8; 32], [2u8; 32], 255);
+        stats.total_volume = u64::MAX - 1;
+
+        stats.record_payment(10);
+        assert_eq!(stats.total_volume, u64::MAX);
+    }
+
+    #[test]
+    fn test_validate_merchant_and_mint() {
+        let stats = MerchantStats::new([1u8; 32], [2u8; 32], 255);
+
+        assert!(stats
+            .validate_merchant_and_mint(&[1u8; 32], &[2u8; 32])
+            .is_ok());
+        assert_eq!(
+            stats
+                .validate_merchant_and_mint(&[1u8; 32], &[3u8; 32])
+                .unwrap_err(),
+            CommerceProgramError::MerchantStatsMismatch.into()
+        );
+        assert_eq!(
+            stats
+                .validate_merchant_and_mint(&[3u8; 32], &[2u8; 32])
+                .unwrap_err(),
+            CommerceProgramError::MerchantStatsMismatch.into()
+        );
+    }
+}
@@ -1,13 +1,53 @@
+pub mod buyer_index;
+pub mod config_registry;
+pub mod config_summary;
+pub mod conversion_rate;
 pub mod discriminator;
 pub mod merchant;
 pub mod merchant_operator_config;
+pub mod merchant_profile;
+pub mod merchant_stats;
 pub mod operator;
+pub mod operator_delegate;
+pub mod operator_freeze_list;
+pub mod order;
 pub mod payment;
+pub mod payment_annotation;
+pub mod payment_note;
+pub mod payment_tree;
 pub mod policy;
+pub mod quote;
+pub mod reader;
+pub mod refund_approval;
+pub mod risk_state;
+pub mod settlement_batch;
+pub mod webhook_commitment;
+pub mod writer;
+pub mod zero_copy;
 
+pub use buyer_index::*;
+pub use config_registry::*;
+pub use config_summary::*;
+pub use conversion_rate::*;
 pub use discriminator::*;
 pub use merchant::*;
 pub use merchant_operator_config::*;
+pub use merchant_profile::*;
+pub use merchant_stats::*;
 pub use operator::*;
+pub use operator_delegate::*;
+pub use operator_freeze_list::*;
+pub use order::*;
 pub use payment::*;
+pub use payment_annotation::*;
+pub use payment_note::*;
+pub use payment_tree::*;
 pub use policy::*;
+pub use quote::*;
+pub use reader::*;
+pub use refund_approval::*;
+pub use risk_state::*;
+pub use settlement_batch::*;
+pub use webhook_commitment::*;
+pub use writer::*;
+pub use zero_copy::*;
@@ -1,6 +1,7 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
+use bytemuck::{Pod, Zeroable};
 use pinocchio::{
     program_error::ProgramError,
     pubkey::{find_program_address, Pubkey},
@@ -8,17 +9,34 @@ use pinocchio::{
 use shank::ShankAccount;
 
 use crate::ID as COMMERCE_PROGRAM_ID;
-use crate::{constants::OPERATOR_SEED, error::CommerceProgramError};
+use crate::{constants::OPERATOR_SEED, error::CommerceProgramError, math::elapsed_seconds};
 
-use super::discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator};
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    zero_copy::ZeroCopy,
+};
 
-/// Seeds: [b"operator", owner pubkey]
+/// Seeds: [b"operator", original_owner pubkey]
 #[derive(Clone, Debug, PartialEq, ShankAccount)]
 #[repr(C)]
 pub struct Operator {
     pub owner: Pubkey,
 
     pub bump: u8,
+
+    /// Authority proposed via ProposeOperatorAuthority, zeroed when none is pending
+    pub pending_authority: Pubkey,
+
+    /// Unix timestamp of the operator's creation or last Heartbeat
+    pub last_active_at: i64,
+
+    /// Whether the operator has an OperatorFreezeList, which every payment and refund to its
+    /// buyers must then be checked against
+    pub has_freeze_list: bool,
+
+    /// Owner the operator was created by and its PDA derived from, kept when
+    /// AcceptOperatorAuthority hands `owner` over
+    pub original_owner: Pubkey,
 }
 
 impl Discriminator for Operator {
@@ -30,15 +48,107 @@ impl AccountSerialize for Operator {
         let mut data = Vec::new();
         data.extend_from_slice(self.owner.as_ref());
         data.push(self.bump);
+        data.extend_from_slice(self.pending_authority.as_ref());
+        data.extend_from_slice(&self.last_active_at.to_le_bytes());
+        data.push(self.has_freeze_list as u8);
+        data.extend_from_slice(self.original_owner.as_ref());
         data
     }
 }
 
+/// Zero-copy layout of a [`Operator`] account
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct OperatorView {
+    discriminator: u8,
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub pending_authority: Pubkey,
+    last_active_at: [u8; 8],
+    has_freeze_list: u8,
+    pub original_owner: Pubkey,
+}
+
+impl Discriminator for OperatorView {
+    const DISCRIMINATOR: u8 = Operator::DISCRIMINATOR;
+}
+
+impl ZeroCopy for OperatorView {}
+
+impl OperatorView {
+    pub fn last_active_at(&self) -> i64 {
+        i64::from_le_bytes(self.last_active_at)
+    }
+
+    pub fn has_freeze_list(&self) -> bool {
+        self.has_freeze_list == 1
+    }
+}
+
+impl From<&Operator> for OperatorView {
+    fn from(operator: &Operator) -> Self {
+        Self {
+            discriminator: Operator::DISCRIMINATOR,
+            owner: operator.owner,
+            bump: operator.bump,
+            pending_authority: operator.pending_authority,
+            last_active_at: operator.last_active_at.to_le_bytes(),
+            has_freeze_list: operator.has_freeze_list as u8,
+            original_owner: operator.original_owner,
+        }
+    }
+}
+
+impl From<&OperatorView> for Operator {
+    fn from(view: &OperatorView) -> Self {
+        Self {
+            owner: view.owner,
+            bump: view.bump,
+            pending_authority: view.pending_authority,
+            last_active_at: view.last_active_at(),
+            has_freeze_list: view.has_freeze_list(),
+            original_owner: view.original_owner,
+        }
+    }
+}
+
 impl Operator {
     pub const LEN: usize = 1 + // discriminator
+        32 + // owner
+        1 + // bump
+        32 + // pending_authority
+        8 + // last_active_at
+        1 + // has_freeze_list
+        32; // original_owner
+
+    /// Size of an operator created before `pending_authority` and `last_active_at`, which
+    /// UpgradeAccount grows to `LEN`.
+    pub const LEGACY_LEN: usize = 1 + // discriminator
         32 + // owner
         1; // bump
 
+    /// Reads an operator in the `LEGACY_LEN` layout, with no pending authority or freeze list
+    /// and active at `now`. Its authority was never handed over, so `owner` is its original
+    /// owner.
+    pub fn from_legacy_bytes(data: &[u8], now: i64) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEGACY_LEN {
+            return Err(CommerceProgramError::AccountNotUpgradable.into());
+        }
+
+        let mut reader = super::ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        let owner = reader.read_pubkey()?;
+        Ok(Self {
+            owner,
+            bump: reader.read_u8()?,
+            pending_authority: Pubkey::default(),
+            last_active_at: now,
+            has_freeze_list: false,
+            original_owner: owner,
+        })
+    }
+
     pub fn validate_owner(&self, owner: &Pubkey) -> Result<(), ProgramError> {
         if self.owner.ne(owner) {
             return Err(CommerceProgramError::OperatorOwnerMismatch.into());
@@ -46,9 +156,25 @@ impl Operator {
         Ok(())
     }
 
+    pub fn validate_pending_authority(&self, authority: &Pubkey) -> Result<(), ProgramError> {
+        if self.pending_authority == Pubkey::default() || self.pending_authority.ne(authority) {
+            return Err(CommerceProgramError::OperatorPendingAuthorityMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Whether the operator has not been active for more than `threshold_seconds` at `now`.
+    pub fn is_inactive(&self, threshold_seconds: u32, now: i64) -> bool {
+        elapsed_seconds(self.last_active_at, now) > i128::from(threshold_seconds)
+    }
+
+    /// Validates the operator's PDA, derived from its original owner so that it still holds
+    /// after an authority handover.
     pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
-        let (pda, bump) =
-            find_program_address(&[OPERATOR_SEED, self.owner.as_ref()], &COMMERCE_PROGRAM_ID);
+        let (pda, bump) = find_program_address(
+            &[OPERATOR_SEED, self.original_owner.as_ref()],
+            &COMMERCE_PROGRAM_ID,
+        );
 
         if pda.ne(account_info_key) || bump != self.bump {
             return Err(CommerceProgramError::OperatorInvalidPda.into());
@@ -57,19 +183,36 @@ impl Operator {
         Ok(())
     }
 
+    #[cfg(not(feature = "legacy-serialization"))]
     pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
-        if data[0] != Self::DISCRIMINATOR {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let mut offset: usize = 1;
+        Ok(OperatorView::load(data)?.into())
+    }
 
-        let owner: Pubkey = data[offset..offset + 32].try_into().unwrap();
-        offset += 32;
+    /// Writes the operator over `data`.
+    #[cfg(not(feature = "legacy-serialization"))]
+    pub fn store(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        OperatorView::from(self).store(data)
+    }
 
-        let bump = data[offset];
+    #[cfg(feature = "legacy-serialization")]
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = super::ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            owner: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            pending_authority: reader.read_pubkey()?,
+            last_active_at: reader.read_i64()?,
+            has_freeze_list: reader.read_bool()?,
+            original_owner: reader.read_pubkey()?,
+        })
+    }
 
-        Ok(Self { owner, bump })
+    /// Writes the operator over `data`.
+    #[cfg(feature = "legacy-serialization")]
+    pub fn store(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        super::zero_copy::write_prefix(&self.to_bytes(), data)
     }
 }
 
@@ -81,7 +224,14 @@ mod tests {
     #[test]
     fn test_validate_owner_success() {
         let owner = [1u8; 32];
-        let operator = Operator { owner, bump: 255 };
+        let operator = Operator {
+            owner,
+            bump: 255,
+            pending_authority: [0u8; 32],
+            last_active_at: 1_700_000_000,
+            has_freeze_list: false,
+            original_owner: owner,
+        };
 
         assert!(operator.validate_owner(&owner).is_ok());
     }
@@ -90,7 +240,14 @@ mod tests {
     fn test_validate_owner_failure() {
         let owner = [1u8; 32];
         let wrong_owner = [2u8; 32];
-        let operator = Operator { owner, bump: 255 };
+        let operator = Operator {
+            owner,
+            bump: 255,
+            pending_authority: [0u8; 32],
+            last_active_at: 1_700_000_000,
+            has_freeze_list: false,
+            original_owner: owner,
+        };
 
         let result = operator.validate_owner(&wrong_owner);
         assert!(result.is_err());
@@ -105,6 +262,10 @@ mod tests {
         let operator = Operator {
             owner: [1u8; 32],
             bump: 254,
+            pending_authority: [3u8; 32],
+            last_active_at: 1_700_000_000,
+            has_freeze_list: true,
+            original_owner: [1u8; 32],
         };
 
         let bytes = operator.to_bytes_inner();
@@ -117,6 +278,29 @@ mod tests {
         assert_eq!(deserialized, operator);
     }
 
+    #[test]
+    fn test_operator_from_legacy_bytes() {
+        let operator = Operator {
+            owner: [1u8; 32],
+            bump: 254,
+            pending_authority: [0u8; 32],
+            last_active_at: 1_700_000_000,
+            has_freeze_list: false,
+            original_owner: [1u8; 32],
+        };
+        let data = operator.to_bytes();
+
+        // The legacy layout stops after the bump
+        assert_eq!(
+            Operator::from_legacy_bytes(&data[..Operator::LEGACY_LEN], 1_700_000_000).unwrap(),
+            operator
+        );
+        assert_eq!(
+            Operator::from_legacy_bytes(&data, 1_700_000_000).unwrap_err(),
+            CommerceProgramError::AccountNotUpgradable.into()
+        );
+    }
+
     #[test]
     fn test_operator_try_from_bytes_wrong_discriminator() {
         let mut data = vec![0; Operator::LEN];
@@ -127,6 +311,29 @@ mod tests {
         assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
     }
 
+    #[test]
+    fn test_operator_view_matches_serialization() {
+        let operator = Operator {
+            owner: [1u8; 32],
+            bump: 254,
+            pending_authority: [3u8; 32],
+            last_active_at: 1_700_000_000,
+            has_freeze_list: true,
+            original_owner: [1u8; 32],
+        };
+
+        assert_eq!(core::mem::size_of::<OperatorView>(), Operator::LEN);
+        assert_eq!(
+            bytemuck::bytes_of(&OperatorView::from(&operator)),
+            operator.to_bytes().as_slice()
+        );
+
+        let mut data = vec![0; Operator::LEN];
+        operator.store(&mut data).unwrap();
+        assert_eq!(Operator::try_from_bytes(&data).unwrap(), operator);
+        assert_eq!(OperatorView::load(&data).unwrap().owner, [1u8; 32]);
+    }
+
     #[test]
     fn test_operator_owner_validation() {
         let owner1 = [100u8; 32];
@@ -135,6 +342,10 @@ mod tests {
         let operator = Operator {
             owner: owner1,
             bump: 128,
+            pending_authority: [0u8; 32],
+            last_active_at: 1_700_000_000,
+            has_freeze_list: false,
+            original_owner: owner1,
         };
 
         // Should succeed with correct owner
@@ -148,4 +359,51 @@ mod tests {
             CommerceProgramError::OperatorOwnerMismatch.into()
         );
     }
+
+    #[test]
+    fn test_validate_pending_authority() {
+        let pending_authority = [5u8; 32];
+        let mut operator = Operator {
+            owner: [1u8; 32],
+            bump: 255,
+            pending_authority: [0u8; 32],
+            last_active_at: 1_700_000_000,
+            has_freeze_list: false,
+            original_owner: [1u8; 32],
+        };
+
+        // Nothing pending: even the default key is rejected
+        assert_eq!(
+            operator.validate_pending_authority(&[0u8; 32]).unwrap_err(),
+            CommerceProgramError::OperatorPendingAuthorityMismatch.into()
+        );
+
+        operator.pending_authority = pending_authority;
+        assert!(operator
+            .validate_pending_authority(&pending_authority)
+            .is_ok());
+        assert_eq!(
+            operator.validate_pending_authority(&[6u8; 32]).unwrap_err(),
+            CommerceProgramError::OperatorPendingAuthorityMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_is_inactive() {
+        let last_active_at = 1_700_000_000;
+        let operator = Operator {
+            owner: [1u8; 32],
+            bump: 255,
+            pending_authority: [0u8; 32],
+            last_active_at,
+            has_freeze_list: false,
+            original_owner: [1u8; 32],
+        };
+
+        assert!(!operator.is_inactive(3_600, last_active_at));
+        assert!(!operator.is_inactive(3_600, last_active_at + 3_600));
+        assert!(operator.is_inactive(3_600, last_active_at + 3_601));
+        // A clock behind the last heartbeat does not count as inactivity
+        assert!(!operator.is_inactive(3_600, last_active_at - 10));
+    }
 }
@@ -0,0 +1,311 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{
+    constants::{OPERATOR_DELEGATE_SEED, SECONDS_PER_DAY},
+    error::CommerceProgramError,
+};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// Operator instructions a delegate can be allowed to sign.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OperatorPermission {
+    MakePayment,
+    Clear,
+    Refund,
+    Close,
+}
+
+/// An additional signing key authorized by the operator owner, scoped to a subset of
+/// the operator instructions. Lets payment servers run without the operator's root key.
+///
+/// Refunds the delegate signs can be capped per refund and per UTC day, so support staff
+/// keys can be given smaller limits than automated ones. The daily counter resets on the
+/// first refund of each day.
+///
+/// Seeds: [b"operator_delegate", operator PDA, delegate pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct OperatorDelegate {
+    pub operator: Pubkey,
+
+    pub delegate: Pubkey,
+
+    pub bump: u8,
+
+    /// MakePayment and MakePaymentCompressed
+    pub can_make_payment: bool,
+
+    /// ClearPayment, ClearPaymentCompressed, ClearPaymentWithConversion and CapturePayment
+    pub can_clear: bool,
+
+    /// RefundPayment and RefundSettledPayment
+    pub can_refund: bool,
+
+    /// ClosePayment and SweepClosedPayments
+    pub can_close: bool,
+
+    /// Largest single refund the delegate may sign, 0 for no limit
+    pub max_refund_amount: u64,
+
+    /// Largest total the delegate may refund per day, 0 for no limit
+    pub max_daily_refund_amount: u64,
+
+    /// Day `daily_refund_amount` is for, in days since the unix epoch
+    pub refund_day: i64,
+
+    /// Sum of refunds signed on `refund_day`, counted only under a daily limit
+    pub daily_refund_amount: u64,
+}
+
+impl Discriminator for OperatorDelegate {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::OperatorDelegateDiscriminator as u8;
+}
+
+impl AccountSerialize for OperatorDelegate {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(self.delegate.as_ref());
+        data.push(self.bump);
+        data.push(self.can_make_payment as u8);
+        data.push(self.can_clear as u8);
+        data.push(self.can_refund as u8);
+        data.push(self.can_close as u8);
+        data.extend_from_slice(&self.max_refund_amount.to_le_bytes());
+        data.extend_from_slice(&self.max_daily_refund_amount.to_le_bytes());
+        data.extend_from_slice(&self.refund_day.to_le_bytes());
+        data.extend_from_slice(&self.daily_refund_amount.to_le_bytes());
+        data
+    }
+}
+
+impl OperatorDelegate {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // operator
+        32 + // delegate
+        1 + // bump
+        1 + // can_make_payment
+        1 + // can_clear
+        1 + // can_refund
+        1 + // can_close
+        8 + // max_refund_amount
+        8 + // max_daily_refund_amount
+        8 + // refund_day
+        8; // daily_refund_amount
+
+    pub fn has_permission(&self, permission: OperatorPermission) -> bool {
+        match permission {
+            OperatorPermission::MakePayment => self.can_make_payment,
+            OperatorPermission::Clear => self.can_clear,
+            OperatorPermission::Refund => self.can_refund,
+            OperatorPermission::Close => self.can_close,
+        }
+    }
+
+    /// Checks a refund of `amount` made at `now` against the delegate's limits, counting it
+    /// towards the daily limit, if any, and starting the count over on a new day.
+    pub fn record_refund(&mut self, amount: u64, now: i64) -> Result<(), ProgramError> {
+        if self.max_refund_amount != 0 && amount > self.max_refund_amount {
+            return Err(CommerceProgramError::DelegateRefundLimitExceeded.into());
+        }
+
+        if self.max_daily_refund_amount == 0 {
+            return Ok(());
+        }
+
+        let day = now.div_euclid(SECONDS_PER_DAY);
+        if day != self.refund_day {
+            self.refund_day = day;
+            self.daily_refund_amount = 0;
+        }
+
+        self.daily_refund_amount = self
+            .daily_refund_amount
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if self.daily_refund_amount > self.max_daily_refund_amount {
+            return Err(CommerceProgramError::DelegateDailyRefundLimitExceeded.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[
+                OPERATOR_DELEGATE_SEED,
+                self.operator.as_ref(),
+                self.delegate.as_ref(),
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::OperatorDelegateInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_operator(&self, operator: &Pubkey) -> Result<(), ProgramError> {
+        if self.operator.ne(operator) {
+            return Err(CommerceProgramError::OperatorDelegateMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn validate_delegate(&self, delegate: &Pubkey) -> Result<(), ProgramError> {
+        if self.delegate.ne(delegate) {
+            return Err(CommerceProgramError::OperatorDelegateMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        let operator = reader.read_pubkey()?;
+        let delegate = reader.read_pubkey()?;
+        let bump = reader.read_u8()?;
+
+        Ok(Self {
+            operator,
+            delegate,
+            bump,
+            can_make_payment: reader.read_bool()?,
+            can_clear: reader.read_bool()?,
+            can_refund: reader.read_bool()?,
+            can_close: reader.read_bool()?,
+            max_refund_amount: reader.read_u64()?,
+            max_daily_refund_amount: reader.read_u64()?,
+            refund_day: reader.read_i64()?,
+            daily_refund_amount: reader.read_u64()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn create_test_delegate() -> OperatorDelegate {
+        OperatorDelegate {
+            operator: [1u8; 32],
+            delegate: [2u8; 32],
+            bump: 254,
+            can_make_payment: true,
+            can_clear: false,
+            can_refund: true,
+            can_close: false,
+            max_refund_amount: 1_000,
+            max_daily_refund_amount: 2_500,
+            refund_day: 20_000,
+            daily_refund_amount: 700,
+        }
+    }
+
+    #[test]
+    fn test_operator_delegate_serialization() {
+        let delegate = create_test_delegate();
+
+        let bytes = delegate.to_bytes();
+        assert_eq!(bytes.len(), OperatorDelegate::LEN);
+
+        let deserialized = OperatorDelegate::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, delegate);
+    }
+
+    #[test]
+    fn test_operator_delegate_try_from_bytes_invalid() {
+        let mut data = vec![0; OperatorDelegate::LEN];
+        data[0] = 99; // Wrong discriminator
+        assert_eq!(
+            OperatorDelegate::try_from_bytes(&data).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        let short = &create_test_delegate().to_bytes()[..OperatorDelegate::LEN - 1];
+        assert_eq!(
+            OperatorDelegate::try_from_bytes(short).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_has_permission() {
+        let delegate = create_test_delegate();
+
+        assert!(delegate.has_permission(OperatorPermission::MakePayment));
+        assert!(!delegate.has_permission(OperatorPermission::Clear));
+        assert!(delegate.has_permission(OperatorPermission::Refund));
+        assert!(!delegate.has_permission(OperatorPermission::Close));
+    }
+
+    #[test]
+    fn test_record_refund_limits() {
+        const DAY: i64 = SECONDS_PER_DAY;
+        let mut delegate = create_test_delegate();
+
+        assert_eq!(
+            delegate.record_refund(1_001, 20_000 * DAY).unwrap_err(),
+            CommerceProgramError::DelegateRefundLimitExceeded.into()
+        );
+
+        delegate.record_refund(1_000, 20_000 * DAY).unwrap();
+        delegate.record_refund(800, 20_000 * DAY + DAY - 1).unwrap();
+        assert_eq!(delegate.daily_refund_amount, 2_500);
+        assert_eq!(
+            delegate.record_refund(1, 20_000 * DAY).unwrap_err(),
+            CommerceProgramError::DelegateDailyRefundLimitExceeded.into()
+        );
+
+        // The daily count starts over on a new day
+        delegate.record_refund(1_000, 20_001 * DAY).unwrap();
+        assert_eq!(delegate.refund_day, 20_001);
+        assert_eq!(delegate.daily_refund_amount, 1_000);
+    }
+
+    #[test]
+    fn test_record_refund_without_limits() {
+        let mut delegate = create_test_delegate();
+        delegate.max_refund_amount = 0;
+        delegate.max_daily_refund_amount = 0;
+
+        delegate.record_refund(u64::MAX, 0).unwrap();
+        delegate.record_refund(u64::MAX, 0).unwrap();
+
+        // Nothing is counted without a daily limit
+        assert_eq!(delegate.refund_day, 20_000);
+        assert_eq!(delegate.daily_refund_amount, 700);
+    }
+
+    #[test]
+    fn test_validate_operator_and_delegate() {
+        let delegate = create_test_delegate();
+
+        assert!(delegate.validate_operator(&[1u8; 32]).is_ok());
+        assert!(delegate.validate_delegate(&[2u8; 32]).is_ok());
+        assert_eq!(
+            delegate.validate_operator(&[2u8; 32]).unwrap_err(),
+            CommerceProgramError::OperatorDelegateMismatch.into()
+        );
+        assert_eq!(
+            delegate.validate_delegate(&[1u8; 32]).unwrap_err(),
+            CommerceProgramError::OperatorDelegateMismatch.into()
+        );
+    }
+}
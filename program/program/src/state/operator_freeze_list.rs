@@ -0,0 +1,230 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{
+    constants::{OPERATOR_FREEZE_LIST_CAPACITY, OPERATOR_FREEZE_LIST_SEED},
+    error::CommerceProgramError,
+};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// Buyer addresses an operator has frozen, e.g. for a sanctions takedown. MakePayment and
+/// RefundPayment fail for a frozen buyer when the list is passed to them, without the
+/// operator's configs having to change.
+///
+/// The operator owner adds and removes addresses with AddToFreezeList and
+/// RemoveFromFreezeList. Removing an address moves the last one into its slot.
+///
+/// Seeds: [b"operator_freeze_list", operator pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct OperatorFreezeList {
+    pub operator: Pubkey,
+
+    pub bump: u8,
+
+    /// Number of frozen addresses. Only the first `count` addresses are set.
+    pub count: u32,
+
+    pub addresses: [Pubkey; 64],
+}
+
+impl Discriminator for OperatorFreezeList {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::OperatorFreezeListDiscriminator as u8;
+}
+
+impl AccountSerialize for OperatorFreezeList {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.operator.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(&self.count.to_le_bytes());
+        for address in &self.addresses {
+            data.extend_from_slice(address.as_ref());
+        }
+        data
+    }
+}
+
+impl OperatorFreezeList {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // operator
+        1 + // bump
+        4 + // count
+        32 * OPERATOR_FREEZE_LIST_CAPACITY; // addresses
+
+    pub fn new(operator: Pubkey, bump: u8) -> Self {
+        Self {
+            operator,
+            bump,
+            count: 0,
+            addresses: [[0; 32]; OPERATOR_FREEZE_LIST_CAPACITY],
+        }
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[OPERATOR_FREEZE_LIST_SEED, self.operator.as_ref()],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::OperatorFreezeListInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_operator(&self, operator: &Pubkey) -> Result<(), ProgramError> {
+        if self.operator.ne(operator) {
+            return Err(CommerceProgramError::OperatorFreezeListMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Frozen addresses, in no particular order.
+    pub fn addresses(&self) -> &[Pubkey] {
+        let len = (self.count as usize).min(OPERATOR_FREEZE_LIST_CAPACITY);
+        &self.addresses[..len]
+    }
+
+    pub fn is_frozen(&self, address: &Pubkey) -> bool {
+        self.addresses().contains(address)
+    }
+
+    pub fn add(&mut self, address: Pubkey) -> Result<(), ProgramError> {
+        if self.is_frozen(&address) {
+            return Err(CommerceProgramError::AddressAlreadyFrozen.into());
+        }
+        if self.count as usize >= OPERATOR_FREEZE_LIST_CAPACITY {
+            return Err(CommerceProgramError::OperatorFreezeListFull.into());
+        }
+
+        self.addresses[self.count as usize] = address;
+        self.count += 1;
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, address: &Pubkey) -> Result<(), ProgramError> {
+        let index = self
+            .addresses()
+            .iter()
+            .position(|frozen| frozen == address)
+            .ok_or(CommerceProgramError::AddressNotFrozen)?;
+
+        let last = self.count as usize - 1;
+        self.addresses[index] = self.addresses[last];
+        self.addresses[last] = [0; 32];
+        self.count -= 1;
+
+        Ok(())
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        let operator = reader.read_pubkey()?;
+        let bump = reader.read_u8()?;
+        let count = reader.read_u32()?;
+
+        let mut addresses = [[0; 32]; OPERATOR_FREEZE_LIST_CAPACITY];
+        for address in addresses.iter_mut() {
+            *address = reader.read_pubkey()?;
+        }
+
+        Ok(Self {
+            operator,
+            bump,
+            count,
+            addresses,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_operator_freeze_list_serialization() {
+        let mut freeze_list = OperatorFreezeList::new([1u8; 32], 254);
+        freeze_list.add([2u8; 32]).unwrap();
+        freeze_list.add([3u8; 32]).unwrap();
+
+        let bytes = freeze_list.to_bytes();
+        assert_eq!(bytes.len(), OperatorFreezeList::LEN);
+
+        let deserialized = OperatorFreezeList::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, freeze_list);
+    }
+
+    #[test]
+    fn test_operator_freeze_list_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; OperatorFreezeList::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = OperatorFreezeList::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_add_and_remove() {
+        let mut freeze_list = OperatorFreezeList::new([1u8; 32], 255);
+        freeze_list.add([2u8; 32]).unwrap();
+        freeze_list.add([3u8; 32]).unwrap();
+        freeze_list.add([4u8; 32]).unwrap();
+        assert!(freeze_list.is_frozen(&[3u8; 32]));
+
+        assert_eq!(
+            freeze_list.add([3u8; 32]).unwrap_err(),
+            CommerceProgramError::AddressAlreadyFrozen.into()
+        );
+
+        // The last address takes the removed one's slot
+        freeze_list.remove(&[2u8; 32]).unwrap();
+        assert_eq!(freeze_list.addresses(), &[[4u8; 32], [3u8; 32]]);
+        assert!(!freeze_list.is_frozen(&[2u8; 32]));
+
+        assert_eq!(
+            freeze_list.remove(&[2u8; 32]).unwrap_err(),
+            CommerceProgramError::AddressNotFrozen.into()
+        );
+    }
+
+    #[test]
+    fn test_add_until_full() {
+        let mut freeze_list = OperatorFreezeList::new([1u8; 32], 255);
+        for i in 0..OPERATOR_FREEZE_LIST_CAPACITY {
+            freeze_list.add([i as u8 + 2; 32]).unwrap();
+        }
+
+        assert_eq!(
+            freeze_list.add([255u8; 32]).unwrap_err(),
+            CommerceProgramError::OperatorFreezeListFull.into()
+        );
+    }
+
+    #[test]
+    fn test_validate_operator() {
+        let freeze_list = OperatorFreezeList::new([1u8; 32], 255);
+
+        assert!(freeze_list.validate_operator(&[1u8; 32]).is_ok());
+        assert_eq!(
+            freeze_list.validate_operator(&[2u8; 32]).unwrap_err(),
+            CommerceProgramError::OperatorFreezeListMismatch.into()
+        );
+    }
+}
@@ -0,0 +1,372 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::{ShankAccount, ShankType};
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{
+    constants::{MAX_ORDER_PAYMENTS, ORDER_SEED},
+    error::CommerceProgramError,
+    state::Status,
+};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// Aggregate status of an order's child payments
+#[derive(Clone, Copy, Debug, PartialEq, ShankType)]
+#[repr(u8)]
+pub enum OrderStatus {
+    /// Fewer than `expected_payments` payments have been made
+    Pending = 0,
+    /// Every payment has been made and at least one is still Paid
+    Paid = 1,
+    /// Every payment has been cleared
+    Cleared = 2,
+    /// Every payment has been refunded
+    Refunded = 3,
+    /// Every payment has been cleared or refunded, with at least one of each
+    PartiallyRefunded = 4,
+}
+
+impl OrderStatus {
+    pub fn from_u8(value: u8) -> Result<Self, ProgramError> {
+        match value {
+            0 => Ok(OrderStatus::Pending),
+            1 => Ok(OrderStatus::Paid),
+            2 => Ok(OrderStatus::Cleared),
+            3 => Ok(OrderStatus::Refunded),
+            4 => Ok(OrderStatus::PartiallyRefunded),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// One order funded by several payments, one per mint (e.g. 30 USDC + 20 USDT).
+///
+/// The child payments are regular Payment PDAs sharing the order's config, buyer and order id.
+/// MakePayment, ClearPayment and RefundPayment update the order when it is passed as a
+/// remaining account.
+///
+/// Seeds: [b"order", merchant_operator_config pubkey, buyer pubkey, order_id (u32 LE)]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct Order {
+    pub merchant_operator_config: Pubkey,
+
+    pub buyer: Pubkey,
+
+    pub order_id: u32,
+
+    pub bump: u8,
+
+    /// Number of payments funding the order, at most `MAX_ORDER_PAYMENTS`
+    pub expected_payments: u8,
+
+    /// Number of payments made so far
+    pub payment_count: u8,
+
+    /// Number of payments cleared, including auto-settled payments
+    pub cleared_count: u8,
+
+    /// Number of payments refunded
+    pub refunded_count: u8,
+
+    pub status: OrderStatus,
+
+    pub created_at: i64,
+}
+
+impl Discriminator for Order {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::OrderDiscriminator as u8;
+}
+
+impl AccountSerialize for Order {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.buyer.as_ref());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.push(self.bump);
+        data.push(self.expected_payments);
+        data.push(self.payment_count);
+        data.push(self.cleared_count);
+        data.push(self.refunded_count);
+        data.push(self.status as u8);
+        data.extend_from_slice(&self.created_at.to_le_bytes());
+        data
+    }
+}
+
+impl Order {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant_operator_config
+        32 + // buyer
+        4 + // order_id
+        1 + // bump
+        1 + // expected_payments
+        1 + // payment_count
+        1 + // cleared_count
+        1 + // refunded_count
+        1 + // status
+        8; // created_at
+
+    pub fn new(
+        merchant_operator_config: Pubkey,
+        buyer: Pubkey,
+        order_id: u32,
+        bump: u8,
+        expected_payments: u8,
+        created_at: i64,
+    ) -> Result<Self, ProgramError> {
+        if expected_payments == 0 || expected_payments > MAX_ORDER_PAYMENTS {
+            return Err(CommerceProgramError::InvalidOrderPaymentCount.into());
+        }
+
+        Ok(Self {
+            merchant_operator_config,
+            buyer,
+            order_id,
+            bump,
+            expected_payments,
+            payment_count: 0,
+            cleared_count: 0,
+            refunded_count: 0,
+            status: OrderStatus::Pending,
+            created_at,
+        })
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let order_id_seed = self.order_id.to_le_bytes();
+        let (pda, bump) = find_program_address(
+            &[
+                ORDER_SEED,
+                self.merchant_operator_config.as_ref(),
+                self.buyer.as_ref(),
+                &order_id_seed,
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::OrderInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    /// Validates a payment made under `merchant_operator_config` by `buyer` for `order_id`
+    /// belongs to this order.
+    pub fn validate_payment(
+        &self,
+        merchant_operator_config: &Pubkey,
+        buyer: &Pubkey,
+        order_id: u32,
+    ) -> Result<(), ProgramError> {
+        if self.merchant_operator_config.ne(merchant_operator_config)
+            || self.buyer.ne(buyer)
+            || self.order_id != order_id
+        {
+            return Err(CommerceProgramError::OrderMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Records a new child payment made with `status` (Paid, Cleared when auto-settled, or
+    /// Authorized).
+    pub fn record_payment(&mut self, status: &Status) -> Result<(), ProgramError> {
+        if self.payment_count >= self.expected_payments {
+            return Err(CommerceProgramError::OrderFull.into());
+        }
+        self.payment_count += 1;
+        self.record_resolution(status);
+        Ok(())
+    }
+
+    /// Records a child payment moving from Paid, or Authorized, to `status`.
+    pub fn record_resolution(&mut self, status: &Status) {
+        match status {
+            Status::Paid | Status::RefundApproved | Status::Authorized => {}
+            Status::Cleared => self.cleared_count += 1,
+            Status::Refunded => self.refunded_count += 1,
+        }
+        self.status = self.aggregate_status();
+    }
+
+    /// Records a cleared child payment being refunded from the settlement wallet.
+    pub fn record_settled_refund(&mut self) {
+        self.cleared_count = self.cleared_count.saturating_sub(1);
+        self.refunded_count += 1;
+        self.status = self.aggregate_status();
+    }
+
+    fn aggregate_status(&self) -> OrderStatus {
+        if self.payment_count < self.expected_payments {
+            OrderStatus::Pending
+        } else if self.cleared_count + self.refunded_count < self.payment_count {
+            OrderStatus::Paid
+        } else if self.refunded_count == 0 {
+            OrderStatus::Cleared
+        } else if self.cleared_count == 0 {
+            OrderStatus::Refunded
+        } else {
+            OrderStatus::PartiallyRefunded
+        }
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            merchant_operator_config: reader.read_pubkey()?,
+            buyer: reader.read_pubkey()?,
+            order_id: reader.read_u32()?,
+            bump: reader.read_u8()?,
+            expected_payments: reader.read_u8()?,
+            payment_count: reader.read_u8()?,
+            cleared_count: reader.read_u8()?,
+            refunded_count: reader.read_u8()?,
+            status: OrderStatus::from_u8(reader.read_u8()?)?,
+            created_at: reader.read_i64()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn new_order(expected_payments: u8) -> Order {
+        Order::new(
+            [1u8; 32],
+            [2u8; 32],
+            7,
+            254,
+            expected_payments,
+            1_700_000_000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_order_serialization() {
+        let mut order = new_order(2);
+        order.record_payment(&Status::Paid).unwrap();
+
+        let bytes = order.to_bytes();
+        assert_eq!(bytes.len(), Order::LEN);
+
+        let deserialized = Order::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, order);
+    }
+
+    #[test]
+    fn test_order_try_from_bytes_invalid() {
+        let mut data = vec![0; Order::LEN];
+        data[0] = 99; // Wrong discriminator
+        assert_eq!(
+            Order::try_from_bytes(&data).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        data[0] = Order::DISCRIMINATOR;
+        data[74] = 5; // Unknown status
+        assert_eq!(
+            Order::try_from_bytes(&data).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_order_new_validates_payment_count() {
+        for expected_payments in [0, MAX_ORDER_PAYMENTS + 1] {
+            assert_eq!(
+                Order::new([1u8; 32], [2u8; 32], 7, 254, expected_payments, 0).unwrap_err(),
+                CommerceProgramError::InvalidOrderPaymentCount.into()
+            );
+        }
+        assert!(Order::new([1u8; 32], [2u8; 32], 7, 254, MAX_ORDER_PAYMENTS, 0).is_ok());
+    }
+
+    #[test]
+    fn test_order_status_aggregation() {
+        let mut order = new_order(2);
+        assert_eq!(order.status, OrderStatus::Pending);
+
+        order.record_payment(&Status::Paid).unwrap();
+        assert_eq!(order.status, OrderStatus::Pending);
+        order.record_payment(&Status::Paid).unwrap();
+        assert_eq!(order.status, OrderStatus::Paid);
+
+        order.record_resolution(&Status::Cleared);
+        assert_eq!(order.status, OrderStatus::Paid);
+        order.record_resolution(&Status::Refunded);
+        assert_eq!(order.status, OrderStatus::PartiallyRefunded);
+
+        let mut order = new_order(2);
+        order.record_payment(&Status::Cleared).unwrap();
+        order.record_payment(&Status::Paid).unwrap();
+        order.record_resolution(&Status::Cleared);
+        assert_eq!(order.status, OrderStatus::Cleared);
+
+        let mut order = new_order(1);
+        order.record_payment(&Status::Paid).unwrap();
+        order.record_resolution(&Status::Refunded);
+        assert_eq!(order.status, OrderStatus::Refunded);
+
+        // Authorized payments are unresolved until captured
+        let mut order = new_order(1);
+        order.record_payment(&Status::Authorized).unwrap();
+        assert_eq!(order.status, OrderStatus::Paid);
+        order.record_resolution(&Status::Paid);
+        assert_eq!(order.status, OrderStatus::Paid);
+
+        // A refund after settlement moves a cleared payment to refunded
+        let mut order = new_order(2);
+        order.record_payment(&Status::Cleared).unwrap();
+        order.record_payment(&Status::Cleared).unwrap();
+        assert_eq!(order.status, OrderStatus::Cleared);
+        order.record_settled_refund();
+        assert_eq!(order.status, OrderStatus::PartiallyRefunded);
+        order.record_settled_refund();
+        assert_eq!(order.status, OrderStatus::Refunded);
+        assert_eq!((order.cleared_count, order.refunded_count), (0, 2));
+    }
+
+    #[test]
+    fn test_order_record_payment_full() {
+        let mut order = new_order(1);
+        order.record_payment(&Status::Paid).unwrap();
+        assert_eq!(
+            order.record_payment(&Status::Paid).unwrap_err(),
+            CommerceProgramError::OrderFull.into()
+        );
+    }
+
+    #[test]
+    fn test_order_validate_payment() {
+        let order = new_order(2);
+        assert!(order.validate_payment(&[1u8; 32], &[2u8; 32], 7).is_ok());
+        for (config, buyer, order_id) in [
+            ([3u8; 32], [2u8; 32], 7),
+            ([1u8; 32], [3u8; 32], 7),
+            ([1u8; 32], [2u8; 32], 8),
+        ] {
+            assert_eq!(
+                order
+                    .validate_payment(&config, &buyer, order_id)
+                    .unwrap_err(),
+                CommerceProgramError::OrderMismatch.into()
+            );
+        }
+    }
+}
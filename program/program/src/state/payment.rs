@@ -1,6 +1,7 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
+use bytemuck::{Pod, Zeroable};
 use pinocchio::{
     program_error::ProgramError,
     pubkey::{find_program_address, Pubkey},
@@ -9,12 +10,17 @@ use pinocchio::{
 use shank::{ShankAccount, ShankType};
 
 use crate::{
-    constants::{PAYMENT_SEED, SECONDS_PER_DAY},
+    constants::{MAX_PAYMENT_REFERENCES, PAYMENT_SEED, SECONDS_PER_DAY},
     error::CommerceProgramError,
+    math::elapsed_seconds,
     ID as COMMERCE_PROGRAM_ID,
 };
 
-use super::discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator};
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    policy::FeeType,
+    zero_copy::ZeroCopy,
+};
 
 #[derive(Clone, Debug, PartialEq, ShankType)]
 #[repr(u8)]
@@ -22,6 +28,11 @@ pub enum Status {
     Paid = 0,
     Cleared = 1,
     Refunded = 2,
+    /// Refund approved by the operator or merchant, waiting for the buyer to claim it
+    RefundApproved = 3,
+    /// Funds held in escrow by an authorizing MakePayment, waiting for CapturePayment to
+    /// capture some or all of them
+    Authorized = 4,
 }
 
 impl Status {
@@ -30,9 +41,36 @@ impl Status {
             0 => Ok(Status::Paid),
             1 => Ok(Status::Cleared),
             2 => Ok(Status::Refunded),
+            3 => Ok(Status::RefundApproved),
+            4 => Ok(Status::Authorized),
             _ => Err(ProgramError::InvalidAccountData),
         }
     }
+
+    /// Whether the payment's funds have left escrow for good, so the account may be closed.
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            Status::Cleared | Status::Refunded => true,
+            // RefundApproved still holds the buyer's approved refund in escrow, Authorized
+            // the buyer's hold
+            Status::Paid | Status::RefundApproved | Status::Authorized => false,
+        }
+    }
+}
+
+/// Operator fee negotiated for a single payment, bounded by the config's FeeOverridePolicy
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct FeeOverride {
+    pub fee: u64,
+    pub fee_type: FeeType,
+}
+
+impl FeeOverride {
+    pub const EMPTY: Self = Self {
+        fee: 0,
+        fee_type: FeeType::Bps,
+    };
 }
 
 // PDA seeds: [b"payment", merchant_operator_config, buyer, mint, order_id]
@@ -45,6 +83,21 @@ pub struct Payment {
     pub created_at: i64,
     pub status: Status,
     pub bump: u8,
+    /// Operator-supplied key identifying the checkout attempt that created the payment
+    pub idempotency_key: [u8; 16],
+    /// Whether ClearPayment charges `fee_override` instead of the config's fee
+    pub has_fee_override: bool,
+    /// Operator fee set by MakePayment, `FeeOverride::EMPTY` when unset
+    pub fee_override: FeeOverride,
+    /// Solana Pay reference keys passed to MakePayment (up to MAX_PAYMENT_REFERENCES),
+    /// zeroed when unused
+    pub references: [Pubkey; 2],
+    /// Rent treasury that funded the account in place of the fee payer, refunded its rent
+    /// on close; zeroed when the fee payer paid
+    pub rent_payer: Pubkey,
+    /// Whether MakePayment recorded the payment on an Order, which every later status change
+    /// must then be recorded on too
+    pub has_order: bool,
 }
 
 impl Discriminator for Payment {
@@ -59,18 +112,186 @@ impl AccountSerialize for Payment {
         data.extend_from_slice(&self.created_at.to_le_bytes());
         data.push(self.status.clone() as u8);
         data.push(self.bump);
+        data.extend_from_slice(&self.idempotency_key);
+        data.push(self.has_fee_override as u8);
+        data.extend_from_slice(&self.fee_override.fee.to_le_bytes());
+        data.push(self.fee_override.fee_type.to_u8());
+        for reference in &self.references {
+            data.extend_from_slice(reference);
+        }
+        data.extend_from_slice(&self.rent_payer);
+        data.push(self.has_order as u8);
         data
     }
 }
 
+/// Zero-copy layout of a [`Payment`] account
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PaymentView {
+    discriminator: u8,
+    order_id: [u8; 4],
+    amount: [u8; 8],
+    created_at: [u8; 8],
+    status: u8,
+    bump: u8,
+    idempotency_key: [u8; 16],
+    has_fee_override: u8,
+    fee_override_fee: [u8; 8],
+    fee_override_fee_type: u8,
+    references: [Pubkey; MAX_PAYMENT_REFERENCES],
+    rent_payer: Pubkey,
+    has_order: u8,
+}
+
+impl Discriminator for PaymentView {
+    const DISCRIMINATOR: u8 = Payment::DISCRIMINATOR;
+}
+
+impl ZeroCopy for PaymentView {}
+
+impl PaymentView {
+    pub fn order_id(&self) -> u32 {
+        u32::from_le_bytes(self.order_id)
+    }
+
+    pub fn amount(&self) -> u64 {
+        u64::from_le_bytes(self.amount)
+    }
+
+    pub fn created_at(&self) -> i64 {
+        i64::from_le_bytes(self.created_at)
+    }
+
+    pub fn status(&self) -> Result<Status, ProgramError> {
+        Status::from_u8(self.status)
+    }
+
+    pub fn set_status(&mut self, status: Status) {
+        self.status = status as u8;
+    }
+
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn idempotency_key(&self) -> &[u8; 16] {
+        &self.idempotency_key
+    }
+
+    pub fn has_fee_override(&self) -> bool {
+        self.has_fee_override == 1
+    }
+
+    pub fn fee_override(&self) -> Result<FeeOverride, ProgramError> {
+        Ok(FeeOverride {
+            fee: u64::from_le_bytes(self.fee_override_fee),
+            fee_type: FeeType::from_u8(self.fee_override_fee_type)?,
+        })
+    }
+
+    pub fn references(&self) -> &[Pubkey; MAX_PAYMENT_REFERENCES] {
+        &self.references
+    }
+
+    pub fn rent_payer(&self) -> &Pubkey {
+        &self.rent_payer
+    }
+
+    pub fn has_order(&self) -> bool {
+        self.has_order == 1
+    }
+}
+
+impl From<&Payment> for PaymentView {
+    fn from(payment: &Payment) -> Self {
+        Self {
+            discriminator: Payment::DISCRIMINATOR,
+            order_id: payment.order_id.to_le_bytes(),
+            amount: payment.amount.to_le_bytes(),
+            created_at: payment.created_at.to_le_bytes(),
+            status: payment.status.clone() as u8,
+            bump: payment.bump,
+            idempotency_key: payment.idempotency_key,
+            has_fee_override: payment.has_fee_override as u8,
+            fee_override_fee: payment.fee_override.fee.to_le_bytes(),
+            fee_override_fee_type: payment.fee_override.fee_type.to_u8(),
+            references: payment.references,
+            rent_payer: payment.rent_payer,
+            has_order: payment.has_order as u8,
+        }
+    }
+}
+
+impl TryFrom<&PaymentView> for Payment {
+    type Error = ProgramError;
+
+    fn try_from(view: &PaymentView) -> Result<Self, Self::Error> {
+        Ok(Self {
+            order_id: view.order_id(),
+            amount: view.amount(),
+            created_at: view.created_at(),
+            status: view.status()?,
+            bump: view.bump(),
+            idempotency_key: *view.idempotency_key(),
+            has_fee_override: view.has_fee_override(),
+            fee_override: view.fee_override()?,
+            references: *view.references(),
+            rent_payer: *view.rent_payer(),
+            has_order: view.has_order(),
+        })
+    }
+}
+
 impl Payment {
     pub const LEN: usize = 1 + // discriminator
+        4 + // order_id
+        8 + // amount
+        8 + // created_at
+        1 + // status
+        1 + // bump
+        16 + // idempotency_key
+        1 + // has_fee_override
+        8 + // fee_override.fee
+        1 + // fee_override.fee_type
+        32 * MAX_PAYMENT_REFERENCES + // references
+        32 + // rent_payer
+        1; // has_order
+
+    /// Size of a payment made before its idempotency key, fee override, references, rent
+    /// payer and order flag, which UpgradeAccount grows to `LEN`.
+    pub const LEGACY_LEN: usize = 1 + // discriminator
         4 + // order_id
         8 + // amount
         8 + // created_at
         1 + // status
         1; // bump
 
+    /// Reads a payment in the `LEGACY_LEN` layout, with the fields it predates zeroed as for a
+    /// payment made without them.
+    pub fn from_legacy_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEGACY_LEN {
+            return Err(CommerceProgramError::AccountNotUpgradable.into());
+        }
+
+        let mut reader = super::ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            order_id: reader.read_u32()?,
+            amount: reader.read_u64()?,
+            created_at: reader.read_i64()?,
+            status: Status::from_u8(reader.read_u8()?)?,
+            bump: reader.read_u8()?,
+            idempotency_key: [0u8; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [Pubkey::default(); MAX_PAYMENT_REFERENCES],
+            rent_payer: Pubkey::default(),
+            has_order: false,
+        })
+    }
+
     pub fn validate_status(&self, status: Status) -> Result<(), ProgramError> {
         if self.status != status {
             return Err(CommerceProgramError::InvalidPaymentStatus.into());
@@ -86,18 +307,15 @@ impl Payment {
     }
 
     pub fn validate_can_close(&self, days_to_close: u16) -> Result<(), ProgramError> {
-        self.validate_not_status(Status::Paid)?;
+        if !self.status.is_terminal() {
+            return Err(CommerceProgramError::InvalidPaymentStatus.into());
+        }
 
         let now = Clock::get()?.unix_timestamp;
 
-        let created_at = self.created_at;
-        let time_diff_in_days = now
-            .checked_sub(created_at)
-            .ok_or(ProgramError::ArithmeticOverflow)?
-            .checked_div(SECONDS_PER_DAY)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let time_diff_in_days = elapsed_seconds(self.created_at, now) / SECONDS_PER_DAY as i128;
 
-        if time_diff_in_days < days_to_close as i64 {
+        if time_diff_in_days < days_to_close as i128 {
             return Err(CommerceProgramError::PaymentCloseWindowNotReached.into());
         }
         Ok(())
@@ -129,26 +347,38 @@ impl Payment {
         Ok(())
     }
 
+    #[cfg(not(feature = "legacy-serialization"))]
     pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
-        if data[0] != Self::DISCRIMINATOR {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let mut offset: usize = 1;
-
-        let order_id = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
-        offset += 4;
-
-        let amount = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-
-        let created_at = i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
+        PaymentView::load(data)?.try_into()
+    }
 
-        let status = Status::from_u8(data[offset])?;
-        offset += 1;
+    /// Writes the payment over `data`.
+    #[cfg(not(feature = "legacy-serialization"))]
+    pub fn store(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        PaymentView::from(self).store(data)
+    }
 
-        let bump = data[offset];
+    #[cfg(feature = "legacy-serialization")]
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = super::ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        let order_id = reader.read_u32()?;
+        let amount = reader.read_u64()?;
+        let created_at = reader.read_i64()?;
+        let status = Status::from_u8(reader.read_u8()?)?;
+        let bump = reader.read_u8()?;
+        let idempotency_key = reader.read_array()?;
+        let has_fee_override = reader.read_bool()?;
+        let fee = reader.read_u64()?;
+        let fee_type = FeeType::from_u8(reader.read_u8()?)?;
+
+        let mut references = [[0u8; 32]; MAX_PAYMENT_REFERENCES];
+        for reference in references.iter_mut() {
+            *reference = reader.read_pubkey()?;
+        }
+        let rent_payer = reader.read_pubkey()?;
+        let has_order = reader.read_bool()?;
 
         Ok(Self {
             order_id,
@@ -156,8 +386,20 @@ impl Payment {
             created_at,
             status,
             bump,
+            idempotency_key,
+            has_fee_override,
+            fee_override: FeeOverride { fee, fee_type },
+            references,
+            rent_payer,
+            has_order,
         })
     }
+
+    /// Writes the payment over `data`.
+    #[cfg(feature = "legacy-serialization")]
+    pub fn store(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        super::zero_copy::write_prefix(&self.to_bytes(), data)
+    }
 }
 
 #[cfg(test)]
@@ -170,10 +412,21 @@ mod tests {
         assert_eq!(Status::from_u8(0).unwrap(), Status::Paid);
         assert_eq!(Status::from_u8(1).unwrap(), Status::Cleared);
         assert_eq!(Status::from_u8(2).unwrap(), Status::Refunded);
-        assert!(Status::from_u8(3).is_err());
+        assert_eq!(Status::from_u8(3).unwrap(), Status::RefundApproved);
+        assert_eq!(Status::from_u8(4).unwrap(), Status::Authorized);
+        assert!(Status::from_u8(5).is_err());
         assert!(Status::from_u8(255).is_err());
     }
 
+    #[test]
+    fn test_status_is_terminal() {
+        assert!(!Status::Paid.is_terminal());
+        assert!(Status::Cleared.is_terminal());
+        assert!(Status::Refunded.is_terminal());
+        assert!(!Status::RefundApproved.is_terminal());
+        assert!(!Status::Authorized.is_terminal());
+    }
+
     #[test]
     fn test_validate_status_success() {
         let payment = Payment {
@@ -182,6 +435,12 @@ mod tests {
             created_at: 1234567890,
             status: Status::Paid,
             bump: 255,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         assert!(payment.validate_status(Status::Paid).is_ok());
@@ -195,6 +454,12 @@ mod tests {
             created_at: 1234567890,
             status: Status::Paid,
             bump: 255,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         let result = payment.validate_status(Status::Cleared);
@@ -213,6 +478,12 @@ mod tests {
             created_at: 1234567890,
             status: Status::Paid,
             bump: 255,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         assert!(payment.validate_not_status(Status::Cleared).is_ok());
@@ -227,6 +498,12 @@ mod tests {
             created_at: 1234567890,
             status: Status::Cleared,
             bump: 255,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         let result = payment.validate_not_status(Status::Cleared);
@@ -245,6 +522,12 @@ mod tests {
             created_at: 1640995200,
             status: Status::Paid,
             bump: 254,
+            idempotency_key: [9; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
         };
 
         let bytes = payment.to_bytes_inner();
@@ -263,6 +546,8 @@ mod tests {
             (0, Status::Paid),
             (1, Status::Cleared),
             (2, Status::Refunded),
+            (3, Status::RefundApproved),
+            (4, Status::Authorized),
         ] {
             let payment = Payment {
                 order_id: 999,
@@ -270,6 +555,12 @@ mod tests {
                 created_at: i64::MIN,
                 status: status.clone(),
                 bump: 1,
+                idempotency_key: [0; 16],
+                has_fee_override: false,
+                fee_override: FeeOverride::EMPTY,
+                references: [[0; 32]; 2],
+                rent_payer: [0; 32],
+                has_order: false,
             };
 
             let bytes = payment.to_bytes_inner();
@@ -282,6 +573,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_payment_from_legacy_bytes() {
+        let payment = Payment {
+            order_id: 12345,
+            amount: 5000000,
+            created_at: 1640995200,
+            status: Status::Cleared,
+            bump: 254,
+            idempotency_key: [0; 16],
+            has_fee_override: false,
+            fee_override: FeeOverride::EMPTY,
+            references: [[0; 32]; 2],
+            rent_payer: [0; 32],
+            has_order: false,
+        };
+        let data = payment.to_bytes();
+
+        // The legacy layout stops after the bump
+        assert_eq!(
+            Payment::from_legacy_bytes(&data[..Payment::LEGACY_LEN]).unwrap(),
+            payment
+        );
+        assert_eq!(
+            Payment::from_legacy_bytes(&data).unwrap_err(),
+            CommerceProgramError::AccountNotUpgradable.into()
+        );
+    }
+
     #[test]
     fn test_payment_try_from_bytes_wrong_discriminator() {
         let mut data = vec![0; Payment::LEN];
@@ -292,6 +611,59 @@ mod tests {
         assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
     }
 
+    #[test]
+    fn test_payment_view_matches_serialization() {
+        let payment = Payment {
+            order_id: 12345,
+            amount: 5000000,
+            created_at: -1640995200,
+            status: Status::Refunded,
+            bump: 254,
+            idempotency_key: [9; 16],
+            has_fee_override: true,
+            fee_override: FeeOverride {
+                fee: 150,
+                fee_type: FeeType::Fixed,
+            },
+            references: [[7; 32], [0; 32]],
+            rent_payer: [3; 32],
+            has_order: true,
+        };
+
+        assert_eq!(core::mem::size_of::<PaymentView>(), Payment::LEN);
+        assert_eq!(
+            bytemuck::bytes_of(&PaymentView::from(&payment)),
+            payment.to_bytes().as_slice()
+        );
+
+        let mut data = vec![0; Payment::LEN];
+        payment.store(&mut data).unwrap();
+        assert_eq!(Payment::try_from_bytes(&data).unwrap(), payment);
+
+        let view = PaymentView::load_mut(&mut data).unwrap();
+        view.set_status(Status::Cleared);
+        assert_eq!(view.status().unwrap(), Status::Cleared);
+        assert_eq!(view.amount(), 5000000);
+        assert!(view.has_fee_override());
+        assert_eq!(view.fee_override().unwrap().fee, 150);
+        assert_eq!(view.references(), &[[7; 32], [0; 32]]);
+        assert!(view.has_order());
+        assert_eq!(
+            Payment::try_from_bytes(&data).unwrap().status,
+            Status::Cleared
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "legacy-serialization"))]
+    fn test_payment_try_from_bytes_too_short() {
+        let mut data = vec![0; Payment::LEN - 1];
+        data[0] = Payment::DISCRIMINATOR;
+
+        assert!(Payment::try_from_bytes(&data).is_err());
+        assert!(Payment::try_from_bytes(&[]).is_err());
+    }
+
     #[test]
     fn test_payment_try_from_bytes_invalid_status() {
         let mut data = vec![Payment::DISCRIMINATOR];
@@ -300,6 +672,7 @@ mod tests {
         data.extend_from_slice(&1234567890i64.to_le_bytes()); // created_at
         data.push(99); // Invalid status
         data.push(255); // bump
+        data.extend_from_slice(&[0u8; 16]); // idempotency_key
 
         let result = Payment::try_from_bytes(&data);
         assert!(result.is_err());
@@ -0,0 +1,204 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::{ShankAccount, ShankType};
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{constants::PAYMENT_ANNOTATION_SEED, error::CommerceProgramError};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// An operator's review verdict on a payment
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ShankType)]
+#[repr(u8)]
+pub enum AnnotationFlag {
+    /// Reviewed and fine to fulfil
+    Ok = 0,
+    /// Held for a person to review before the order ships
+    ManualReview = 1,
+    /// Judged fraudulent; the order should not ship
+    FlaggedFraud = 2,
+}
+
+impl AnnotationFlag {
+    pub fn from_u8(value: u8) -> Result<Self, ProgramError> {
+        match value {
+            0 => Ok(AnnotationFlag::Ok),
+            1 => Ok(AnnotationFlag::ManualReview),
+            2 => Ok(AnnotationFlag::FlaggedFraud),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Whether the merchant should hold fulfilment of the payment's order.
+    pub fn holds_fulfilment(&self) -> bool {
+        match self {
+            AnnotationFlag::Ok => false,
+            AnnotationFlag::ManualReview | AnnotationFlag::FlaggedFraud => true,
+        }
+    }
+}
+
+/// An operator's annotation of a payment, written by its risk systems or reviewers.
+///
+/// Set by AnnotatePayment, which creates it on the first call and overwrites it after, so it
+/// always holds the latest verdict; the PaymentAnnotated events keep the history. The note
+/// itself stays off-chain with the operator, only its hash is stored. Merchants read the flag
+/// before shipping, filtering by `merchant_operator_config` to find a config's annotations.
+/// Not read by any payment instruction.
+///
+/// Seeds: [b"payment_annotation", payment PDA]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct PaymentAnnotation {
+    pub payment: Pubkey,
+
+    pub merchant_operator_config: Pubkey,
+
+    pub bump: u8,
+
+    pub flag: AnnotationFlag,
+
+    /// Hash of the operator's off-chain note, zero for none
+    pub note_hash: [u8; 32],
+
+    /// Operator owner or delegate that signed the last AnnotatePayment
+    pub annotated_by: Pubkey,
+
+    /// Unix timestamp of the last AnnotatePayment
+    pub updated_at: i64,
+}
+
+impl Discriminator for PaymentAnnotation {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::PaymentAnnotationDiscriminator as u8;
+}
+
+impl AccountSerialize for PaymentAnnotation {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.payment.as_ref());
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.push(self.bump);
+        data.push(self.flag as u8);
+        data.extend_from_slice(&self.note_hash);
+        data.extend_from_slice(self.annotated_by.as_ref());
+        data.extend_from_slice(&self.updated_at.to_le_bytes());
+        data
+    }
+}
+
+impl PaymentAnnotation {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // payment
+        32 + // merchant_operator_config
+        1 + // bump
+        1 + // flag
+        32 + // note_hash
+        32 + // annotated_by
+        8; // updated_at
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[PAYMENT_ANNOTATION_SEED, self.payment.as_ref()],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::PaymentAnnotationInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            payment: reader.read_pubkey()?,
+            merchant_operator_config: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            flag: AnnotationFlag::from_u8(reader.read_u8()?)?,
+            note_hash: reader.read_array()?,
+            annotated_by: reader.read_pubkey()?,
+            updated_at: reader.read_i64()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_annotation_flag_from_u8() {
+        assert_eq!(AnnotationFlag::from_u8(0).unwrap(), AnnotationFlag::Ok);
+        assert_eq!(
+            AnnotationFlag::from_u8(1).unwrap(),
+            AnnotationFlag::ManualReview
+        );
+        assert_eq!(
+            AnnotationFlag::from_u8(2).unwrap(),
+            AnnotationFlag::FlaggedFraud
+        );
+        assert_eq!(
+            AnnotationFlag::from_u8(3).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_annotation_flag_holds_fulfilment() {
+        assert!(!AnnotationFlag::Ok.holds_fulfilment());
+        assert!(AnnotationFlag::ManualReview.holds_fulfilment());
+        assert!(AnnotationFlag::FlaggedFraud.holds_fulfilment());
+    }
+
+    #[test]
+    fn test_payment_annotation_serialization() {
+        let payment_annotation = PaymentAnnotation {
+            payment: [1u8; 32],
+            merchant_operator_config: [2u8; 32],
+            bump: 254,
+            flag: AnnotationFlag::ManualReview,
+            note_hash: [3u8; 32],
+            annotated_by: [4u8; 32],
+            updated_at: 1_700_000_000,
+        };
+
+        let bytes = payment_annotation.to_bytes();
+        assert_eq!(bytes.len(), PaymentAnnotation::LEN);
+        assert_eq!(bytes[0], PaymentAnnotation::DISCRIMINATOR);
+
+        let deserialized = PaymentAnnotation::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, payment_annotation);
+    }
+
+    #[test]
+    fn test_payment_annotation_try_from_bytes_invalid() {
+        let mut data = vec![0; PaymentAnnotation::LEN];
+        data[0] = 99; // Wrong discriminator
+        assert_eq!(
+            PaymentAnnotation::try_from_bytes(&data).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        data[0] = PaymentAnnotation::DISCRIMINATOR;
+        data[66] = 3; // Unknown flag
+        assert_eq!(
+            PaymentAnnotation::try_from_bytes(&data).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        data[66] = 0;
+        assert!(PaymentAnnotation::try_from_bytes(&data[..PaymentAnnotation::LEN - 1]).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::{ShankAccount, ShankType};
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{
+    constants::{PAYMENT_NOTE_CIPHERTEXT_LEN, PAYMENT_NOTE_SEED},
+    error::CommerceProgramError,
+};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// Note attached to a payment by MakePayment, encrypted off-chain to the merchant's key so
+/// shipping details or customer references can travel with the payment without exposing them.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+pub struct EncryptedNote {
+    pub ciphertext: [u8; 128],
+}
+
+/// Sidecar to a Payment holding its [`EncryptedNote`].
+///
+/// Created by MakePayment when a note is attached, and closed by ClosePaymentNote, which
+/// refunds the rent to `rent_payer`.
+///
+/// Seeds: [b"payment_note", payment pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct PaymentNote {
+    pub payment: Pubkey,
+
+    /// Merchant the note is encrypted to, whose authority can close it
+    pub merchant: Pubkey,
+
+    pub bump: u8,
+
+    /// Account that paid the note's rent, refunded when it is closed
+    pub rent_payer: Pubkey,
+
+    pub ciphertext: [u8; 128],
+}
+
+impl Discriminator for PaymentNote {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::PaymentNoteDiscriminator as u8;
+}
+
+impl AccountSerialize for PaymentNote {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.payment.as_ref());
+        data.extend_from_slice(self.merchant.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(self.rent_payer.as_ref());
+        data.extend_from_slice(&self.ciphertext);
+        data
+    }
+}
+
+impl PaymentNote {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // payment
+        32 + // merchant
+        1 + // bump
+        32 + // rent_payer
+        PAYMENT_NOTE_CIPHERTEXT_LEN; // ciphertext
+
+    /// PaymentNote PDA and bump for `payment`.
+    pub fn find_pda(payment: &Pubkey) -> (Pubkey, u8) {
+        find_program_address(&[PAYMENT_NOTE_SEED, payment.as_ref()], &COMMERCE_PROGRAM_ID)
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = Self::find_pda(&self.payment);
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::PaymentNoteInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_merchant(&self, merchant: &Pubkey) -> Result<(), ProgramError> {
+        if self.merchant.ne(merchant) {
+            return Err(CommerceProgramError::MerchantMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            payment: reader.read_pubkey()?,
+            merchant: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            rent_payer: reader.read_pubkey()?,
+            ciphertext: reader.read_array()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn payment_note() -> PaymentNote {
+        PaymentNote {
+            payment: [1u8; 32],
+            merchant: [2u8; 32],
+            bump: 254,
+            rent_payer: [3u8; 32],
+            ciphertext: [7u8; PAYMENT_NOTE_CIPHERTEXT_LEN],
+        }
+    }
+
+    #[test]
+    fn test_payment_note_serialization() {
+        let payment_note = payment_note();
+
+        let bytes = payment_note.to_bytes();
+        assert_eq!(bytes.len(), PaymentNote::LEN);
+
+        let deserialized = PaymentNote::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, payment_note);
+    }
+
+    #[test]
+    fn test_payment_note_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; PaymentNote::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = PaymentNote::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_validate_merchant() {
+        let payment_note = payment_note();
+
+        assert!(payment_note.validate_merchant(&[2u8; 32]).is_ok());
+        assert_eq!(
+            payment_note.validate_merchant(&[9u8; 32]).unwrap_err(),
+            CommerceProgramError::MerchantMismatch.into()
+        );
+    }
+}
@@ -0,0 +1,477 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use const_crypto::sha2::Sha256;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::{
+    constants::{PAYMENT_TREE_DEPTH, PAYMENT_TREE_SEED},
+    error::CommerceProgramError,
+    ID as COMMERCE_PROGRAM_ID,
+};
+
+use super::payment::Status;
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// Root of an empty subtree at each level: `ZERO_HASHES[0]` is an empty leaf and
+/// `ZERO_HASHES[n + 1] = H(ZERO_HASHES[n] || ZERO_HASHES[n])`.
+pub const ZERO_HASHES: [[u8; 32]; PAYMENT_TREE_DEPTH + 1] = {
+    let mut zeros = [[0u8; 32]; PAYMENT_TREE_DEPTH + 1];
+    let mut level = 0;
+    while level < PAYMENT_TREE_DEPTH {
+        zeros[level + 1] = Sha256::new()
+            .update(&zeros[level])
+            .update(&zeros[level])
+            .finalize();
+        level += 1;
+    }
+    zeros
+};
+
+/// SHA-256 over the concatenation of `vals`, using the syscall on-chain.
+pub fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    #[cfg(target_os = "solana")]
+    {
+        let mut hash_result = [0u8; 32];
+        unsafe {
+            pinocchio::syscalls::sol_sha256(
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+                hash_result.as_mut_ptr(),
+            );
+        }
+        hash_result
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let mut hasher = Sha256::new();
+        for val in vals {
+            hasher = hasher.update(val);
+        }
+        hasher.finalize()
+    }
+}
+
+/// A payment stored as a leaf of a PaymentTree instead of in its own PDA.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompressedPayment {
+    pub buyer: Pubkey,
+    pub mint: Pubkey,
+    pub order_id: u32,
+    pub amount: u64,
+    pub created_at: i64,
+    pub status: Status,
+}
+
+impl CompressedPayment {
+    /// Leaf hash: sha256(buyer || mint || order_id || amount || created_at || status)
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        hashv(&[
+            self.buyer.as_ref(),
+            self.mint.as_ref(),
+            &self.order_id.to_le_bytes(),
+            &self.amount.to_le_bytes(),
+            &self.created_at.to_le_bytes(),
+            &[self.status.clone() as u8],
+        ])
+    }
+}
+
+/// Append-only Merkle tree of compressed payments for one MerchantOperatorConfig.
+///
+/// Only the root and the right-most filled subtree at each level are stored on-chain;
+/// leaves are reconstructed off-chain from CompressedPayment events.
+///
+/// Seeds: [b"payment_tree", merchant_operator_config pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct PaymentTree {
+    pub merchant_operator_config: Pubkey,
+    pub bump: u8,
+    pub num_leaves: u32,
+    pub root: [u8; 32],
+    /// Right-most filled subtree at each level, 32 bytes per level (PAYMENT_TREE_DEPTH levels)
+    pub filled_subtrees: [u8; 640],
+}
+
+impl Discriminator for PaymentTree {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::PaymentTreeDiscriminator as u8;
+}
+
+impl AccountSerialize for PaymentTree {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(&self.num_leaves.to_le_bytes());
+        data.extend_from_slice(&self.root);
+        data.extend_from_slice(&self.filled_subtrees);
+        data
+    }
+}
+
+impl PaymentTree {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant_operator_config
+        1 + // bump
+        4 + // num_leaves
+        32 + // root
+        32 * PAYMENT_TREE_DEPTH; // filled_subtrees
+
+    pub const MAX_LEAVES: u64 = 1 << PAYMENT_TREE_DEPTH;
+
+    pub fn new(merchant_operator_config: Pubkey, bump: u8) -> Self {
+        let mut filled_subtrees = [0u8; 32 * PAYMENT_TREE_DEPTH];
+        for (subtree, zero) in filled_subtrees.chunks_exact_mut(32).zip(ZERO_HASHES.iter()) {
+            subtree.copy_from_slice(zero);
+        }
+
+        Self {
+            merchant_operator_config,
+            bump,
+            num_leaves: 0,
+            root: ZERO_HASHES[PAYMENT_TREE_DEPTH],
+            filled_subtrees,
+        }
+    }
+
+    pub fn filled_subtree(&self, level: usize) -> [u8; 32] {
+        self.filled_subtrees[level * 32..(level + 1) * 32]
+            .try_into()
+            .unwrap()
+    }
+
+    fn set_filled_subtree(&mut self, level: usize, node: [u8; 32]) {
+        self.filled_subtrees[level * 32..(level + 1) * 32].copy_from_slice(&node);
+    }
+
+    pub fn validate_merchant_operator_config(
+        &self,
+        merchant_operator_config: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.merchant_operator_config.ne(merchant_operator_config) {
+            return Err(CommerceProgramError::PaymentTreeMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[PAYMENT_TREE_SEED, self.merchant_operator_config.as_ref()],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::PaymentTreeInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    /// Appends a leaf and returns its index.
+    pub fn append(&mut self, leaf: [u8; 32]) -> Result<u32, ProgramError> {
+        if self.num_leaves as u64 >= Self::MAX_LEAVES {
+            return Err(CommerceProgramError::PaymentTreeFull.into());
+        }
+
+        let leaf_index = self.num_leaves;
+        let mut index = leaf_index;
+        let mut node = leaf;
+
+        for (level, zero) in ZERO_HASHES[..PAYMENT_TREE_DEPTH].iter().enumerate() {
+            if index & 1 == 0 {
+                self.set_filled_subtree(level, node);
+                node = hashv(&[&node, zero]);
+            } else {
+                node = hashv(&[&self.filled_subtree(level), &node]);
+            }
+            index >>= 1;
+        }
+
+        self.root = node;
+        self.num_leaves = leaf_index
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(leaf_index)
+    }
+
+    /// Replaces an existing leaf after proving `old_leaf` is at `leaf_index`.
+    pub fn replace_leaf(
+        &mut self,
+        leaf_index: u32,
+        old_leaf: [u8; 32],
+        new_leaf: [u8; 32],
+        proof: &[[u8; 32]],
+    ) -> Result<(), ProgramError> {
+        if leaf_index >= self.num_leaves || proof.len() != PAYMENT_TREE_DEPTH {
+            return Err(CommerceProgramError::InvalidMerkleProof.into());
+        }
+
+        if compute_root(leaf_index, old_leaf, proof) != self.root {
+            return Err(CommerceProgramError::InvalidMerkleProof.into());
+        }
+
+        // The right-most filled subtree at each level is cached for appends, so it must
+        // follow the replacement whenever it sits on the replaced leaf's path.
+        let last_index = self.num_leaves - 1;
+        let mut node = new_leaf;
+
+        for (level, sibling) in proof.iter().enumerate() {
+            let index = leaf_index >> level;
+            if index == (last_index >> level) & !1 {
+                self.set_filled_subtree(level, node);
+            }
+
+            node = if index & 1 == 0 {
+                hashv(&[&node, sibling])
+            } else {
+                hashv(&[sibling, &node])
+            };
+        }
+
+        self.root = node;
+
+        Ok(())
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        let merchant_operator_config = reader.read_pubkey()?;
+        let bump = reader.read_u8()?;
+        let num_leaves = reader.read_u32()?;
+        let root = reader.read_array()?;
+        let filled_subtrees = reader.read_array()?;
+
+        Ok(Self {
+            merchant_operator_config,
+            bump,
+            num_leaves,
+            root,
+            filled_subtrees,
+        })
+    }
+}
+
+/// Computes the root implied by `leaf` sitting at `leaf_index` with the given sibling path.
+pub fn compute_root(leaf_index: u32, leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    let mut node = leaf;
+    let mut index = leaf_index;
+
+    for sibling in proof {
+        node = if index & 1 == 0 {
+            hashv(&[&node, sibling])
+        } else {
+            hashv(&[sibling, &node])
+        };
+        index >>= 1;
+    }
+
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Naive full-tree reference: returns every level, leaves first, padded with zero leaves.
+    fn build_levels(leaves: &[[u8; 32]]) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![leaves.to_vec()];
+        for level in 0..PAYMENT_TREE_DEPTH {
+            let current = &levels[level];
+            let mut next = Vec::new();
+            let mut i = 0;
+            while i < current.len() {
+                let left = current[i];
+                let right = current.get(i + 1).copied().unwrap_or(ZERO_HASHES[level]);
+                next.push(hashv(&[&left, &right]));
+                i += 2;
+            }
+            if next.is_empty() {
+                next.push(ZERO_HASHES[level + 1]);
+            }
+            levels.push(next);
+        }
+        levels
+    }
+
+    fn naive_proof(leaves: &[[u8; 32]], leaf_index: usize) -> Vec<[u8; 32]> {
+        let levels = build_levels(leaves);
+        (0..PAYMENT_TREE_DEPTH)
+            .map(|level| {
+                let sibling = (leaf_index >> level) ^ 1;
+                levels[level]
+                    .get(sibling)
+                    .copied()
+                    .unwrap_or(ZERO_HASHES[level])
+            })
+            .collect()
+    }
+
+    fn leaf(i: u8) -> [u8; 32] {
+        hashv(&[&[i]])
+    }
+
+    #[test]
+    fn test_zero_hashes() {
+        assert_eq!(ZERO_HASHES[0], [0u8; 32]);
+        for level in 0..PAYMENT_TREE_DEPTH {
+            assert_eq!(
+                ZERO_HASHES[level + 1],
+                hashv(&[&ZERO_HASHES[level], &ZERO_HASHES[level]])
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_tree_is_empty() {
+        let tree = PaymentTree::new([1u8; 32], 255);
+        assert_eq!(tree.num_leaves, 0);
+        assert_eq!(tree.root, ZERO_HASHES[PAYMENT_TREE_DEPTH]);
+        assert_eq!(tree.root, build_levels(&[])[PAYMENT_TREE_DEPTH][0]);
+    }
+
+    #[test]
+    fn test_append_matches_naive_root() {
+        let mut tree = PaymentTree::new([1u8; 32], 255);
+        let mut leaves = Vec::new();
+
+        for i in 0..9u8 {
+            let index = tree.append(leaf(i)).unwrap();
+            leaves.push(leaf(i));
+
+            assert_eq!(index, i as u32);
+            assert_eq!(tree.num_leaves, i as u32 + 1);
+            assert_eq!(tree.root, build_levels(&leaves)[PAYMENT_TREE_DEPTH][0]);
+        }
+    }
+
+    #[test]
+    fn test_append_full_tree() {
+        let mut tree = PaymentTree::new([1u8; 32], 255);
+        tree.num_leaves = PaymentTree::MAX_LEAVES as u32;
+
+        let result = tree.append(leaf(0));
+        assert_eq!(
+            result.unwrap_err(),
+            CommerceProgramError::PaymentTreeFull.into()
+        );
+    }
+
+    #[test]
+    fn test_replace_leaf_then_append_matches_naive_root() {
+        let mut tree = PaymentTree::new([1u8; 32], 255);
+        let mut leaves = Vec::new();
+
+        for i in 0..7u8 {
+            tree.append(leaf(i)).unwrap();
+            leaves.push(leaf(i));
+        }
+
+        // Replace every leaf (including the right-most) and keep appending afterwards
+        // so the cached filled subtrees are exercised.
+        for i in 0..7usize {
+            let proof = naive_proof(&leaves, i);
+            let new_leaf = leaf(100 + i as u8);
+            tree.replace_leaf(i as u32, leaves[i], new_leaf, &proof)
+                .unwrap();
+            leaves[i] = new_leaf;
+            assert_eq!(tree.root, build_levels(&leaves)[PAYMENT_TREE_DEPTH][0]);
+
+            tree.append(leaf(200 + i as u8)).unwrap();
+            leaves.push(leaf(200 + i as u8));
+            assert_eq!(tree.root, build_levels(&leaves)[PAYMENT_TREE_DEPTH][0]);
+        }
+    }
+
+    #[test]
+    fn test_replace_leaf_invalid_proof() {
+        let mut tree = PaymentTree::new([1u8; 32], 255);
+        let leaves = [leaf(0), leaf(1), leaf(2)];
+        for l in leaves {
+            tree.append(l).unwrap();
+        }
+
+        let proof = naive_proof(&leaves, 1);
+
+        // Wrong old leaf
+        let result = tree.replace_leaf(1, leaf(9), leaf(10), &proof);
+        assert_eq!(
+            result.unwrap_err(),
+            CommerceProgramError::InvalidMerkleProof.into()
+        );
+
+        // Wrong index
+        let result = tree.replace_leaf(0, leaves[1], leaf(10), &proof);
+        assert_eq!(
+            result.unwrap_err(),
+            CommerceProgramError::InvalidMerkleProof.into()
+        );
+
+        // Index past the last appended leaf
+        let result = tree.replace_leaf(3, ZERO_HASHES[0], leaf(10), &naive_proof(&leaves, 3));
+        assert_eq!(
+            result.unwrap_err(),
+            CommerceProgramError::InvalidMerkleProof.into()
+        );
+
+        // Truncated proof
+        let result = tree.replace_leaf(1, leaves[1], leaf(10), &proof[..PAYMENT_TREE_DEPTH - 1]);
+        assert_eq!(
+            result.unwrap_err(),
+            CommerceProgramError::InvalidMerkleProof.into()
+        );
+    }
+
+    #[test]
+    fn test_compressed_payment_leaf_hash_depends_on_status() {
+        let payment = CompressedPayment {
+            buyer: [1u8; 32],
+            mint: [2u8; 32],
+            order_id: 1,
+            amount: 1000,
+            created_at: 1640995200,
+            status: Status::Paid,
+        };
+        let cleared = CompressedPayment {
+            status: Status::Cleared,
+            ..payment.clone()
+        };
+
+        assert_ne!(payment.leaf_hash(), cleared.leaf_hash());
+        assert_eq!(payment.leaf_hash(), payment.clone().leaf_hash());
+    }
+
+    #[test]
+    fn test_payment_tree_serialization() {
+        let mut tree = PaymentTree::new([3u8; 32], 254);
+        tree.append(leaf(1)).unwrap();
+        tree.append(leaf(2)).unwrap();
+
+        let bytes = tree.to_bytes();
+        assert_eq!(bytes.len(), PaymentTree::LEN);
+
+        let deserialized = PaymentTree::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, tree);
+    }
+
+    #[test]
+    fn test_payment_tree_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; PaymentTree::LEN];
+        data[0] = 99;
+
+        let result = PaymentTree::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+}
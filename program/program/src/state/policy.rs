@@ -1,13 +1,38 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
-use pinocchio::program_error::ProgramError;
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 use shank::ShankType;
 
-use crate::constants::POLICY_SIZE;
+use crate::constants::{MAX_BPS, MAX_DENYLIST_DEPTH, POLICY_SIZE, SECONDS_PER_DAY};
+use crate::error::CommerceProgramError;
+use crate::math::{bps_of, elapsed_seconds};
+use crate::state::payment_tree::{compute_root, hashv};
+use crate::state::ByteReader;
+use crate::ID as COMMERCE_PROGRAM_ID;
 
 pub const REFUND_POLICY_SIZE: usize = 16;
 pub const SETTLEMENT_POLICY_SIZE: usize = 13;
+pub const MAX_FEE_TIERS: usize = 5;
+pub const FEE_TIER_SIZE: usize = 17;
+pub const FEE_TIER_POLICY_SIZE: usize = 1 + MAX_FEE_TIERS * FEE_TIER_SIZE;
+pub const MEMO_POLICY_SIZE: usize = 2;
+pub const CONVERSION_POLICY_SIZE: usize = 35;
+pub const CHARGEBACK_POLICY_SIZE: usize = 40;
+pub const FEE_OVERRIDE_POLICY_SIZE: usize = 9;
+pub const DENYLIST_POLICY_SIZE: usize = 33;
+pub const MAX_SPLIT_DESTINATIONS: usize = 5;
+pub const SPLIT_SETTLEMENT_POLICY_SIZE: usize = 32 + 1 + 2 * MAX_SPLIT_DESTINATIONS;
+pub const RISK_POLICY_SIZE: usize = 20;
+pub const BUYER_INDEX_POLICY_SIZE: usize = 1;
+pub const CALLBACK_POLICY_SIZE: usize = 34;
+pub const PLATFORM_FEE_POLICY_SIZE: usize = 41;
+pub const COMPLIANCE_POLICY_SIZE: usize = 32;
+pub const SETTLEMENT_DISCOUNT_POLICY_SIZE: usize = 4;
+pub const REFUND_CONVERSION_POLICY_SIZE: usize = 4;
+pub const OPERATOR_FAILOVER_POLICY_SIZE: usize = 4;
+pub const RENT_REBATE_POLICY_SIZE: usize = 2;
+pub const DIRECT_SETTLEMENT_POLICY_SIZE: usize = 8;
 
 #[derive(Clone, Debug, PartialEq, ShankType)]
 #[repr(u8)]
@@ -30,11 +55,48 @@ impl FeeType {
     }
 }
 
+/// On-chain swap programs a ConversionPolicy can route through.
+#[derive(Clone, Copy, Debug, PartialEq, ShankType)]
+#[repr(u8)]
+pub enum SwapAdapter {
+    JupiterV6 = 0,
+}
+
+impl SwapAdapter {
+    pub fn from_u8(value: u8) -> Result<Self, ProgramError> {
+        match value {
+            0 => Ok(SwapAdapter::JupiterV6),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        *self as u8
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, ShankType)]
 #[repr(u8)]
 pub enum PolicyType {
     Refund = 0,
     Settlement = 1,
+    FeeTier = 2,
+    Memo = 3,
+    Conversion = 4,
+    Chargeback = 5,
+    FeeOverride = 6,
+    Denylist = 7,
+    SplitSettlement = 8,
+    Risk = 9,
+    BuyerIndex = 10,
+    Callback = 11,
+    PlatformFee = 12,
+    Compliance = 13,
+    SettlementDiscount = 14,
+    RefundConversion = 15,
+    OperatorFailover = 16,
+    RentRebate = 17,
+    DirectSettlement = 18,
 }
 
 impl PolicyType {
@@ -42,6 +104,23 @@ impl PolicyType {
         match value {
             0 => Ok(PolicyType::Refund),
             1 => Ok(PolicyType::Settlement),
+            2 => Ok(PolicyType::FeeTier),
+            3 => Ok(PolicyType::Memo),
+            4 => Ok(PolicyType::Conversion),
+            5 => Ok(PolicyType::Chargeback),
+            6 => Ok(PolicyType::FeeOverride),
+            7 => Ok(PolicyType::Denylist),
+            8 => Ok(PolicyType::SplitSettlement),
+            9 => Ok(PolicyType::Risk),
+            10 => Ok(PolicyType::BuyerIndex),
+            11 => Ok(PolicyType::Callback),
+            12 => Ok(PolicyType::PlatformFee),
+            13 => Ok(PolicyType::Compliance),
+            14 => Ok(PolicyType::SettlementDiscount),
+            15 => Ok(PolicyType::RefundConversion),
+            16 => Ok(PolicyType::OperatorFailover),
+            17 => Ok(PolicyType::RentRebate),
+            18 => Ok(PolicyType::DirectSettlement),
             _ => Err(ProgramError::InvalidAccountData),
         }
     }
@@ -53,6 +132,23 @@ impl PolicyType {
         1 + match self {
             PolicyType::Refund => REFUND_POLICY_SIZE,
             PolicyType::Settlement => SETTLEMENT_POLICY_SIZE,
+            PolicyType::FeeTier => FEE_TIER_POLICY_SIZE,
+            PolicyType::Memo => MEMO_POLICY_SIZE,
+            PolicyType::Conversion => CONVERSION_POLICY_SIZE,
+            PolicyType::Chargeback => CHARGEBACK_POLICY_SIZE,
+            PolicyType::FeeOverride => FEE_OVERRIDE_POLICY_SIZE,
+            PolicyType::Denylist => DENYLIST_POLICY_SIZE,
+            PolicyType::SplitSettlement => SPLIT_SETTLEMENT_POLICY_SIZE,
+            PolicyType::Risk => RISK_POLICY_SIZE,
+            PolicyType::BuyerIndex => BUYER_INDEX_POLICY_SIZE,
+            PolicyType::Callback => CALLBACK_POLICY_SIZE,
+            PolicyType::PlatformFee => PLATFORM_FEE_POLICY_SIZE,
+            PolicyType::Compliance => COMPLIANCE_POLICY_SIZE,
+            PolicyType::SettlementDiscount => SETTLEMENT_DISCOUNT_POLICY_SIZE,
+            PolicyType::RefundConversion => REFUND_CONVERSION_POLICY_SIZE,
+            PolicyType::OperatorFailover => OPERATOR_FAILOVER_POLICY_SIZE,
+            PolicyType::RentRebate => RENT_REBATE_POLICY_SIZE,
+            PolicyType::DirectSettlement => DIRECT_SETTLEMENT_POLICY_SIZE,
         }
     }
 }
@@ -82,17 +178,11 @@ impl RefundPolicy {
     }
 
     fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
-        if data.len() < REFUND_POLICY_SIZE {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let max_amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
-        let max_time_after_purchase =
-            u64::from_le_bytes(data[8..REFUND_POLICY_SIZE].try_into().unwrap());
+        let mut reader = ByteReader::new(data);
 
         Ok(Self {
-            max_amount,
-            max_time_after_purchase,
+            max_amount: reader.read_u64()?,
+            max_time_after_purchase: reader.read_u64()?,
         })
     }
 }
@@ -115,242 +205,2059 @@ impl SettlementPolicy {
     }
 
     fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
-        if data.len() < SETTLEMENT_POLICY_SIZE {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
-        let min_settlement_amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
-        let settlement_frequency_hours = u32::from_le_bytes(data[8..12].try_into().unwrap());
-        let auto_settle = data[12] == 1;
+        let mut reader = ByteReader::new(data);
 
         Ok(Self {
-            min_settlement_amount,
-            settlement_frequency_hours,
-            auto_settle,
+            min_settlement_amount: reader.read_u64()?,
+            settlement_frequency_hours: reader.read_u32()?,
+            auto_settle: reader.read_bool()?,
         })
     }
 }
 
-// Enum wrapper for concrete policy types
 #[derive(Clone, Debug, PartialEq, ShankType)]
 #[repr(C)]
-pub enum PolicyData {
-    Refund(RefundPolicy),
-    Settlement(SettlementPolicy),
+pub struct FeeTier {
+    /// Minimum payment amount for this tier to apply
+    pub threshold: u64, // 8 bytes
+    pub fee: u64,          // 8 bytes
+    pub fee_type: FeeType, // 1 byte
 }
 
-impl PolicyData {
-    pub const SIZE: usize = 1 + POLICY_SIZE; // policy_type (u8) + policy data (100 bytes)
+impl FeeTier {
+    pub const EMPTY: Self = Self {
+        threshold: 0,
+        fee: 0,
+        fee_type: FeeType::Bps,
+    };
 
-    pub fn to_bytes(&self) -> Vec<u8> {
+    fn to_bytes(&self) -> Vec<u8> {
         let mut data = Vec::new();
-        data.push(self.policy_type().to_u8());
-        match self {
-            PolicyData::Refund(policy) => data.extend_from_slice(&policy.to_bytes()),
-            PolicyData::Settlement(policy) => data.extend_from_slice(&policy.to_bytes()),
-        }
-        data.resize(Self::SIZE, 0);
+        data.extend_from_slice(&self.threshold.to_le_bytes());
+        data.extend_from_slice(&self.fee.to_le_bytes());
+        data.push(self.fee_type.to_u8());
         data
     }
 
-    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
-        if data.is_empty() {
-            return Err(ProgramError::InvalidAccountData);
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            threshold: reader.read_u64()?,
+            fee: reader.read_u64()?,
+            fee_type: FeeType::from_u8(reader.read_u8()?)?,
+        })
+    }
+}
+
+/// Operator fee schedule by payment amount. Overrides the config's flat fee
+/// for payments at or above the lowest tier threshold.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct FeeTierPolicy {
+    pub num_tiers: u8,       // 1 byte
+    pub tiers: [FeeTier; 5], // 5 * 17 bytes, only the first num_tiers are used
+}
+
+impl FeeTierPolicy {
+    pub fn active_tiers(&self) -> &[FeeTier] {
+        &self.tiers[..(self.num_tiers as usize).min(MAX_FEE_TIERS)]
+    }
+
+    /// Returns the tier with the highest threshold not above `amount`.
+    pub fn tier_for_amount(&self, amount: u64) -> Option<&FeeTier> {
+        self.active_tiers()
+            .iter()
+            .rev()
+            .find(|tier| tier.threshold <= amount)
+    }
+
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.num_tiers == 0 || self.num_tiers as usize > MAX_FEE_TIERS {
+            return Err(CommerceProgramError::InvalidFeeTierPolicy.into());
         }
 
-        let policy_type = PolicyType::from_u8(data[0])?;
-        let policy_data = &data[1..];
+        let tiers = self.active_tiers();
 
-        match policy_type {
-            PolicyType::Refund => Ok(PolicyData::Refund(RefundPolicy::from_bytes(policy_data)?)),
-            PolicyType::Settlement => Ok(PolicyData::Settlement(SettlementPolicy::from_bytes(
-                policy_data,
-            )?)),
+        // Thresholds must be strictly ascending
+        if tiers
+            .windows(2)
+            .any(|pair| pair[0].threshold >= pair[1].threshold)
+        {
+            return Err(CommerceProgramError::InvalidFeeTierPolicy.into());
+        }
+
+        if tiers
+            .iter()
+            .any(|tier| tier.fee_type == FeeType::Bps && tier.fee > MAX_BPS)
+        {
+            return Err(CommerceProgramError::InvalidFeeTierPolicy.into());
         }
+
+        Ok(())
     }
 
-    pub fn policy_type(&self) -> PolicyType {
-        match self {
-            PolicyData::Refund(_) => PolicyType::Refund,
-            PolicyData::Settlement(_) => PolicyType::Settlement,
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.push(self.num_tiers);
+        for tier in &self.tiers {
+            data.extend_from_slice(&tier.to_bytes());
         }
+        data
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use alloc::vec;
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
 
-    #[test]
-    fn test_fee_type_from_u8() {
-        assert_eq!(FeeType::from_u8(0).unwrap(), FeeType::Bps);
-        assert_eq!(FeeType::from_u8(1).unwrap(), FeeType::Fixed);
-        assert!(FeeType::from_u8(2).is_err());
-        assert!(FeeType::from_u8(255).is_err());
+        let num_tiers = reader.read_u8()?;
+        let mut tiers = [FeeTier::EMPTY; MAX_FEE_TIERS];
+        for tier in tiers.iter_mut() {
+            *tier = FeeTier::from_bytes(reader.read_bytes(FEE_TIER_SIZE)?)?;
+        }
+
+        Ok(Self { num_tiers, tiers })
     }
+}
 
-    #[test]
-    fn test_fee_type_to_u8() {
-        assert_eq!(FeeType::Bps.to_u8(), 0);
-        assert_eq!(FeeType::Fixed.to_u8(), 1);
+/// Attaches an SPL Memo (order id and payment PDA) to settlement transfers, for
+/// destination wallets that reconcile deposits by memo.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct MemoPolicy {
+    pub memo_on_clear: bool,  // 1 byte
+    pub memo_on_refund: bool, // 1 byte
+}
+
+impl MemoPolicy {
+    fn to_bytes(&self) -> Vec<u8> {
+        Vec::from([self.memo_on_clear as u8, self.memo_on_refund as u8])
     }
 
-    #[test]
-    fn test_fee_type_roundtrip() {
-        let fee_type = FeeType::Bps;
-        assert_eq!(FeeType::from_u8(fee_type.to_u8()).unwrap(), fee_type);
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
 
-        let fee_type = FeeType::Fixed;
-        assert_eq!(FeeType::from_u8(fee_type.to_u8()).unwrap(), fee_type);
+        Ok(Self {
+            memo_on_clear: reader.read_bool()?,
+            memo_on_refund: reader.read_bool()?,
+        })
     }
+}
 
-    #[test]
-    fn test_policy_type_from_u8() {
-        assert_eq!(PolicyType::from_u8(0).unwrap(), PolicyType::Refund);
-        assert_eq!(PolicyType::from_u8(1).unwrap(), PolicyType::Settlement);
-        assert!(PolicyType::from_u8(2).is_err());
-        assert!(PolicyType::from_u8(255).is_err());
+/// Lets the operator clear payments in any accepted mint into `settlement_mint` by swapping
+/// the merchant's share through `swap_adapter`, receiving at least the operator's quote
+/// less `max_slippage_bps`.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct ConversionPolicy {
+    pub settlement_mint: Pubkey,   // 32 bytes
+    pub swap_adapter: SwapAdapter, // 1 byte
+    pub max_slippage_bps: u16,     // 2 bytes
+}
+
+impl ConversionPolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.max_slippage_bps as u64 > MAX_BPS {
+            return Err(CommerceProgramError::InvalidConversionPolicy.into());
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_policy_type_to_u8() {
-        assert_eq!(PolicyType::Refund.to_u8(), 0);
-        assert_eq!(PolicyType::Settlement.to_u8(), 1);
+    /// Smallest swap output accepted for an operator quote of `quoted_amount_out`.
+    pub fn min_amount_out(&self, quoted_amount_out: u64) -> u64 {
+        bps_of(
+            quoted_amount_out,
+            MAX_BPS - (self.max_slippage_bps as u64).min(MAX_BPS),
+        )
     }
 
-    #[test]
-    fn test_policy_type_get_size() {
-        assert_eq!(PolicyType::Refund.get_size(), 1 + REFUND_POLICY_SIZE);
-        assert_eq!(
-            PolicyType::Settlement.get_size(),
-            1 + SETTLEMENT_POLICY_SIZE
-        );
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.settlement_mint);
+        data.push(self.swap_adapter.to_u8());
+        data.extend_from_slice(&self.max_slippage_bps.to_le_bytes());
+        data
     }
 
-    #[test]
-    fn test_policy_type_roundtrip() {
-        let policy_type = PolicyType::Refund;
-        assert_eq!(
-            PolicyType::from_u8(policy_type.to_u8()).unwrap(),
-            policy_type
-        );
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
 
-        let policy_type = PolicyType::Settlement;
-        assert_eq!(
-            PolicyType::from_u8(policy_type.to_u8()).unwrap(),
-            policy_type
-        );
+        Ok(Self {
+            settlement_mint: reader.read_pubkey()?,
+            swap_adapter: SwapAdapter::from_u8(reader.read_u8()?)?,
+            max_slippage_bps: reader.read_u16()?,
+        })
     }
+}
 
-    #[test]
-    fn test_refund_policy_serialization() {
-        let policy = RefundPolicy {
-            max_amount: 1000,
-            max_time_after_purchase: 3600,
-        };
-
-        let bytes = policy.to_bytes();
-        assert_eq!(bytes.len(), REFUND_POLICY_SIZE);
+/// Holds payments in escrow for `window_seconds` after purchase, during which they cannot
+/// be cleared and `arbiter` may force a full refund to the buyer.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct ChargebackPolicy {
+    /// In seconds
+    pub window_seconds: u64, // 8 bytes
+    pub arbiter: Pubkey, // 32 bytes
+}
 
-        let deserialized = RefundPolicy::from_bytes(&bytes).unwrap();
-        assert_eq!(deserialized, policy);
+impl ChargebackPolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.window_seconds == 0 || self.arbiter == Pubkey::default() {
+            return Err(CommerceProgramError::InvalidChargebackPolicy.into());
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_refund_policy_from_bytes_invalid_length() {
-        let short_data = vec![1, 2, 3];
-        assert!(RefundPolicy::from_bytes(&short_data).is_err());
+    /// Whether a payment created at `created_at` is still open to chargebacks at `now`.
+    pub fn is_window_open(&self, created_at: i64, now: i64) -> bool {
+        elapsed_seconds(created_at, now) < self.window_seconds as i128
     }
 
-    #[test]
-    fn test_settlement_policy_serialization() {
-        let policy = SettlementPolicy {
-            min_settlement_amount: 5000,
-            settlement_frequency_hours: 24,
-            auto_settle: true,
-        };
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.window_seconds.to_le_bytes());
+        data.extend_from_slice(&self.arbiter);
+        data
+    }
 
-        let bytes = policy.to_bytes();
-        assert_eq!(bytes.len(), SETTLEMENT_POLICY_SIZE);
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
 
-        let deserialized = SettlementPolicy::from_bytes(&bytes).unwrap();
-        assert_eq!(deserialized, policy);
+        Ok(Self {
+            window_seconds: reader.read_u64()?,
+            arbiter: reader.read_pubkey()?,
+        })
     }
+}
 
-    #[test]
-    fn test_settlement_policy_auto_settle_false() {
-        let policy = SettlementPolicy {
-            min_settlement_amount: 1000,
-            settlement_frequency_hours: 12,
-            auto_settle: false,
-        };
+/// Lets MakePayment set a per-payment operator fee, as long as it takes no more of the
+/// payment than `max_fee` of `max_fee_type` would.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct FeeOverridePolicy {
+    pub max_fee: u64,          // 8 bytes
+    pub max_fee_type: FeeType, // 1 byte
+}
 
-        let bytes = policy.to_bytes();
-        let deserialized = SettlementPolicy::from_bytes(&bytes).unwrap();
-        assert!(!deserialized.auto_settle);
+impl FeeOverridePolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.max_fee_type == FeeType::Bps && self.max_fee > MAX_BPS {
+            return Err(CommerceProgramError::InvalidFeeOverridePolicy.into());
+        }
+        Ok(())
     }
 
-    #[test]
-    fn test_settlement_policy_from_bytes_invalid_length() {
-        let short_data = vec![1, 2, 3];
-        assert!(SettlementPolicy::from_bytes(&short_data).is_err());
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.max_fee.to_le_bytes());
+        data.push(self.max_fee_type.to_u8());
+        data
     }
 
-    #[test]
-    fn test_policy_data_refund_serialization() {
-        let refund_policy = RefundPolicy {
-            max_amount: 2000,
-            max_time_after_purchase: 7200,
-        };
-        let policy_data = PolicyData::Refund(refund_policy.clone());
-
-        let bytes = policy_data.to_bytes();
-        assert_eq!(bytes.len(), PolicyData::SIZE);
-        assert_eq!(bytes[0], PolicyType::Refund.to_u8());
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
 
-        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
-        assert_eq!(deserialized, policy_data);
-        assert_eq!(deserialized.policy_type(), PolicyType::Refund);
+        Ok(Self {
+            max_fee: reader.read_u64()?,
+            max_fee_type: FeeType::from_u8(reader.read_u8()?)?,
+        })
     }
+}
 
-    #[test]
-    fn test_policy_data_settlement_serialization() {
-        let settlement_policy = SettlementPolicy {
-            min_settlement_amount: 3000,
-            settlement_frequency_hours: 48,
-            auto_settle: false,
-        };
-        let policy_data = PolicyData::Settlement(settlement_policy.clone());
+/// Blocks buyers on a denylist. `root` is the root of a Merkle tree of depth `depth` whose
+/// leaves are `sha256(lower || upper)` for each pair of consecutive banned buyers in
+/// ascending byte order, bounded by the all-zero and all-`0xff` keys, with unused leaves
+/// left zeroed. Buyers prove they are not banned by opening the leaf around their key.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct DenylistPolicy {
+    pub root: [u8; 32], // 32 bytes
+    pub depth: u8,      // 1 byte
+}
 
-        let bytes = policy_data.to_bytes();
-        assert_eq!(bytes.len(), PolicyData::SIZE);
-        assert_eq!(bytes[0], PolicyType::Settlement.to_u8());
+impl DenylistPolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        // A zero root cannot be opened, so it would block every buyer
+        if self.root == [0; 32] || self.depth as usize > MAX_DENYLIST_DEPTH {
+            return Err(CommerceProgramError::InvalidDenylistPolicy.into());
+        }
+        Ok(())
+    }
 
-        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
-        assert_eq!(deserialized, policy_data);
-        assert_eq!(deserialized.policy_type(), PolicyType::Settlement);
+    /// Leaf for the gap between consecutive banned buyers `lower` and `upper`.
+    pub fn leaf_hash(lower: &Pubkey, upper: &Pubkey) -> [u8; 32] {
+        hashv(&[lower, upper])
     }
 
-    #[test]
-    fn test_policy_data_from_bytes_empty() {
-        assert!(PolicyData::from_bytes(&[]).is_err());
+    /// Whether `proof` opens a gap of the denylist that strictly contains `buyer`.
+    pub fn allows(&self, buyer: &Pubkey, proof: &DenylistProof) -> bool {
+        proof.lower < *buyer
+            && *buyer < proof.upper
+            && proof.proof.len() == self.depth as usize
+            && compute_root(
+                proof.leaf_index,
+                Self::leaf_hash(&proof.lower, &proof.upper),
+                &proof.proof,
+            ) == self.root
     }
 
-    #[test]
-    fn test_policy_data_from_bytes_invalid_policy_type() {
-        let mut data = vec![0; PolicyData::SIZE];
-        data[0] = 99; // Invalid policy type
-        assert!(PolicyData::from_bytes(&data).is_err());
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.root);
+        data.push(self.depth);
+        data
     }
 
-    #[test]
-    fn test_policy_data_from_bytes_insufficient_data() {
-        let data = vec![0]; // Only policy type, no policy data
-        assert!(PolicyData::from_bytes(&data).is_err());
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            root: reader.read_array()?,
+            depth: reader.read_u8()?,
+        })
     }
+}
 
-    #[test]
+/// Merkle proof that a buyer falls strictly between two consecutive entries of a
+/// [`DenylistPolicy`], and so is not on the denylist.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+pub struct DenylistProof {
+    /// Banned buyer (or the all-zero key) just below the buyer
+    pub lower: Pubkey,
+    /// Banned buyer (or the all-`0xff` key) just above the buyer
+    pub upper: Pubkey,
+    /// Index of the `(lower, upper)` leaf
+    pub leaf_index: u32,
+    /// Sibling hashes from the leaf up to the root
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Routes the merchant's share of cleared payments to up to five wallets by `shares_bps`,
+/// e.g. 90% to a treasury and 10% to a tax escrow. The wallets don't fit in a policy slot,
+/// so `destinations_hash` commits to them in order and ClearPayment checks the owners of the
+/// destination ATAs it is passed against it.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct SplitSettlementPolicy {
+    pub destinations_hash: [u8; 32], // 32 bytes
+    pub num_destinations: u8,        // 1 byte
+    pub shares_bps: [u16; 5],        // 5 * 2 bytes, only the first num_destinations are used
+}
+
+impl SplitSettlementPolicy {
+    /// Hash of the destination wallets, in the order their shares are listed.
+    pub fn destinations_hash(wallets: &[Pubkey]) -> [u8; 32] {
+        let mut vals: [&[u8]; MAX_SPLIT_DESTINATIONS] = [&[]; MAX_SPLIT_DESTINATIONS];
+        for (val, wallet) in vals.iter_mut().zip(wallets) {
+            *val = wallet;
+        }
+        hashv(&vals[..wallets.len().min(MAX_SPLIT_DESTINATIONS)])
+    }
+
+    pub fn active_shares(&self) -> &[u16] {
+        &self.shares_bps[..(self.num_destinations as usize).min(MAX_SPLIT_DESTINATIONS)]
+    }
+
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.num_destinations == 0
+            || self.num_destinations as usize > MAX_SPLIT_DESTINATIONS
+            || self.destinations_hash == [0; 32]
+        {
+            return Err(CommerceProgramError::InvalidSplitSettlementPolicy.into());
+        }
+
+        // Every destination gets a share and together they cover the whole amount
+        let shares = self.active_shares();
+        if shares.contains(&0) || shares.iter().map(|&share| share as u64).sum::<u64>() != MAX_BPS {
+            return Err(CommerceProgramError::InvalidSplitSettlementPolicy.into());
+        }
+
+        Ok(())
+    }
+
+    /// Splits `amount` by the destination shares, rounding down and giving the remainder to
+    /// the first destination so the parts always add up to `amount`.
+    pub fn split(&self, amount: u64) -> [u64; MAX_SPLIT_DESTINATIONS] {
+        let mut parts = [0u64; MAX_SPLIT_DESTINATIONS];
+        for (part, &share) in parts.iter_mut().zip(self.active_shares()) {
+            *part = bps_of(amount, share as u64);
+        }
+        let distributed = parts
+            .iter()
+            .fold(0u64, |sum, part| sum.saturating_add(*part));
+        parts[0] = parts[0].saturating_add(amount.saturating_sub(distributed));
+        parts
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.destinations_hash);
+        data.push(self.num_destinations);
+        for share in &self.shares_bps {
+            data.extend_from_slice(&share.to_le_bytes());
+        }
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        let destinations_hash = reader.read_array()?;
+        let num_destinations = reader.read_u8()?;
+        let mut shares_bps = [0u16; MAX_SPLIT_DESTINATIONS];
+        for share in shares_bps.iter_mut() {
+            *share = reader.read_u16()?;
+        }
+
+        Ok(Self {
+            destinations_hash,
+            num_destinations,
+            shares_bps,
+        })
+    }
+}
+
+/// Caps what MakePayment accepts under the config. `max_payment_amount` bounds a single
+/// payment; `max_daily_volume` and `max_payments_per_buyer_per_day` are counted per UTC day
+/// in RiskState accounts the payment must pass. Amounts are in the mint's base units. Zero
+/// leaves a limit off.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct RiskPolicy {
+    pub max_payment_amount: u64,             // 8 bytes
+    pub max_daily_volume: u64,               // 8 bytes
+    pub max_payments_per_buyer_per_day: u32, // 4 bytes
+}
+
+impl RiskPolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        // A policy with every limit off would only make payments pass RiskState accounts
+        if self.max_payment_amount == 0
+            && self.max_daily_volume == 0
+            && self.max_payments_per_buyer_per_day == 0
+        {
+            return Err(CommerceProgramError::InvalidRiskPolicy.into());
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.max_payment_amount.to_le_bytes());
+        data.extend_from_slice(&self.max_daily_volume.to_le_bytes());
+        data.extend_from_slice(&self.max_payments_per_buyer_per_day.to_le_bytes());
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            max_payment_amount: reader.read_u64()?,
+            max_daily_volume: reader.read_u64()?,
+            max_payments_per_buyer_per_day: reader.read_u32()?,
+        })
+    }
+}
+
+/// Has MakePayment keep each buyer's BuyerIndex, passed after any MerchantStats. Indexes are
+/// opt-in because each costs rent and every payment a little more compute.
+/// With `required` set, payments without the buyer's BuyerIndex fail, so the index covers
+/// every payment the buyer makes under the config once it exists.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct BuyerIndexPolicy {
+    pub required: bool, // 1 byte
+}
+
+impl BuyerIndexPolicy {
+    fn to_bytes(&self) -> Vec<u8> {
+        Vec::from([self.required as u8])
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            required: reader.read_bool()?,
+        })
+    }
+}
+
+/// Has ClearPayment and RefundPayment invoke the merchant's `program` once the payment's
+/// funds have moved, so it can run its own logic (loyalty points, inventory) atomically
+/// with settlement. The program is passed, followed by the accounts forwarded to it, after
+/// the other remaining accounts and before the memo program.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct CallbackPolicy {
+    pub program: Pubkey, // 32 bytes
+    pub on_clear: bool,  // 1 byte
+    pub on_refund: bool, // 1 byte
+}
+
+impl CallbackPolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        // A policy calling back on nothing would only cost config space
+        if self.program == Pubkey::default()
+            || self.program == COMMERCE_PROGRAM_ID
+            || !(self.on_clear || self.on_refund)
+        {
+            return Err(CommerceProgramError::InvalidCallbackPolicy.into());
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::from(self.program);
+        data.push(self.on_clear as u8);
+        data.push(self.on_refund as u8);
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            program: reader.read_pubkey()?,
+            on_clear: reader.read_bool()?,
+            on_refund: reader.read_bool()?,
+        })
+    }
+}
+
+/// Has ClearPayment pay `platform`, a marketplace facilitating payments on top of the
+/// operator, a fee of its own out of what is left for the merchant after the operator fee.
+/// The platform's settlement ATA is passed before any split settlement destinations.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct PlatformFeePolicy {
+    pub platform: Pubkey,  // 32 bytes
+    pub fee: u64,          // 8 bytes
+    pub fee_type: FeeType, // 1 byte
+}
+
+impl PlatformFeePolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        // A zero fee would only cost config space and a settlement account per clear
+        if self.platform == Pubkey::default()
+            || self.fee == 0
+            || (self.fee_type == FeeType::Bps && self.fee > MAX_BPS)
+        {
+            return Err(CommerceProgramError::InvalidPlatformFeePolicy.into());
+        }
+        Ok(())
+    }
+
+    /// The platform's fee on a payment of `amount` leaving `available` for the merchant
+    /// after the operator fee, capped at `available` so the fees never exceed the payment.
+    pub fn fee_amount(&self, amount: u64, available: u64) -> u64 {
+        let fee = match self.fee_type {
+            FeeType::Bps => bps_of(amount, self.fee),
+            FeeType::Fixed => self.fee,
+        };
+        fee.min(available)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::from(self.platform);
+        data.extend_from_slice(&self.fee.to_le_bytes());
+        data.push(self.fee_type.to_u8());
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            platform: reader.read_pubkey()?,
+            fee: reader.read_u64()?,
+            fee_type: FeeType::from_u8(reader.read_u8()?)?,
+        })
+    }
+}
+
+/// Has MakePayment and MakePaymentCompressed invoke `program`, an identity or KYC registry,
+/// with the buyer and amount before any funds move, and fail unless it succeeds. The
+/// program is passed, followed by the accounts forwarded to it, after the other remaining
+/// accounts.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct CompliancePolicy {
+    pub program: Pubkey, // 32 bytes
+}
+
+impl CompliancePolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.program == Pubkey::default() || self.program == COMMERCE_PROGRAM_ID {
+            return Err(CommerceProgramError::InvalidCompliancePolicy.into());
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        Vec::from(self.program)
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            program: reader.read_pubkey()?,
+        })
+    }
+}
+
+/// Has ClearPayment take `discount_bps_per_day` off the operator fee for every full day the
+/// payment's funds stayed in escrow, up to `max_discount_bps`, so merchants accepting
+/// batched settlement pay less, as under acquiring contracts.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct SettlementDiscountPolicy {
+    pub discount_bps_per_day: u16, // 2 bytes
+    pub max_discount_bps: u16,     // 2 bytes
+}
+
+impl SettlementDiscountPolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.discount_bps_per_day == 0
+            || self.max_discount_bps == 0
+            || u64::from(self.max_discount_bps) > MAX_BPS
+        {
+            return Err(CommerceProgramError::InvalidSettlementDiscountPolicy.into());
+        }
+        Ok(())
+    }
+
+    /// The discount in bps earned by a payment made at `created_at` and cleared at `now`.
+    pub fn discount_bps(&self, created_at: i64, now: i64) -> u64 {
+        let days_held = (elapsed_seconds(created_at, now) / i128::from(SECONDS_PER_DAY)).max(0);
+        let discount = days_held.saturating_mul(i128::from(self.discount_bps_per_day));
+        discount.min(i128::from(self.max_discount_bps)) as u64
+    }
+
+    /// `operator_fee` less the discount earned by a payment made at `created_at` and cleared
+    /// at `now`.
+    pub fn discounted_fee(&self, operator_fee: u64, created_at: i64, now: i64) -> u64 {
+        operator_fee - bps_of(operator_fee, self.discount_bps(created_at, now))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.discount_bps_per_day.to_le_bytes());
+        data.extend_from_slice(&self.max_discount_bps.to_le_bytes());
+        data
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            discount_bps_per_day: reader.read_u16()?,
+            max_discount_bps: reader.read_u16()?,
+        })
+    }
+}
+
+/// Lets the operator refund a payment in another of the config's accepted mints, at a rate it
+/// posted in a ConversionRate account no more than `max_rate_age_seconds` before the refund,
+/// for when the payment's own mint is frozen or deprecated.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct RefundConversionPolicy {
+    pub max_rate_age_seconds: u32, // 4 bytes
+}
+
+impl RefundConversionPolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.max_rate_age_seconds == 0 {
+            return Err(CommerceProgramError::InvalidRefundConversionPolicy.into());
+        }
+        Ok(())
+    }
+
+    /// Whether a rate posted at `posted_at` may still be used at `now`.
+    pub fn is_fresh(&self, posted_at: i64, now: i64) -> bool {
+        elapsed_seconds(posted_at, now) <= i128::from(self.max_rate_age_seconds)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.max_rate_age_seconds.to_le_bytes().to_vec()
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            max_rate_age_seconds: reader.read_u32()?,
+        })
+    }
+}
+
+/// Lets the merchant authority clear escrowed payments in place of the operator once the
+/// operator has gone more than `inactivity_threshold_seconds` without a Heartbeat, so an
+/// abandoned operator cannot hold the merchant's funds in escrow.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct OperatorFailoverPolicy {
+    pub inactivity_threshold_seconds: u32, // 4 bytes
+}
+
+impl OperatorFailoverPolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.inactivity_threshold_seconds == 0 {
+            return Err(CommerceProgramError::InvalidOperatorFailoverPolicy.into());
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.inactivity_threshold_seconds.to_le_bytes().to_vec()
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            inactivity_threshold_seconds: reader.read_u32()?,
+        })
+    }
+}
+
+/// Has ClosePayment and SweepClosedPayments pay `buyer_share_bps` of a closed payment's rent
+/// to its buyer, so merchants can give back part of what the buyer's transaction cost them.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct RentRebatePolicy {
+    pub buyer_share_bps: u16, // 2 bytes
+}
+
+impl RentRebatePolicy {
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        if self.buyer_share_bps == 0 || u64::from(self.buyer_share_bps) > MAX_BPS {
+            return Err(CommerceProgramError::InvalidRentRebatePolicy.into());
+        }
+        Ok(())
+    }
+
+    /// The buyer's share of `rent` lamports reclaimed from a closed payment.
+    pub fn buyer_share(&self, rent: u64) -> u64 {
+        bps_of(rent, u64::from(self.buyer_share_bps))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.buyer_share_bps.to_le_bytes().to_vec()
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            buyer_share_bps: reader.read_u16()?,
+        })
+    }
+}
+
+/// Has MakePayment settle payments of up to `max_amount` at payment time, without escrow
+/// or a ClearPayment: the operator fee goes to the operator's fee ATA, any platform fee to
+/// the platform, and the rest to the merchant's settlement wallet, and the payment is
+/// created Cleared. Zero leaves the amount uncapped; larger payments are escrowed as usual.
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub struct DirectSettlementPolicy {
+    pub max_amount: u64, // 8 bytes
+}
+
+impl DirectSettlementPolicy {
+    /// Whether a payment of `amount` is settled at payment time.
+    pub fn applies_to(&self, amount: u64) -> bool {
+        self.max_amount == 0 || amount <= self.max_amount
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.max_amount.to_le_bytes().to_vec()
+    }
+
+    fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+
+        Ok(Self {
+            max_amount: reader.read_u64()?,
+        })
+    }
+}
+
+// Enum wrapper for concrete policy types
+#[derive(Clone, Debug, PartialEq, ShankType)]
+#[repr(C)]
+pub enum PolicyData {
+    Refund(RefundPolicy),
+    Settlement(SettlementPolicy),
+    FeeTier(FeeTierPolicy),
+    Memo(MemoPolicy),
+    Conversion(ConversionPolicy),
+    Chargeback(ChargebackPolicy),
+    FeeOverride(FeeOverridePolicy),
+    Denylist(DenylistPolicy),
+    SplitSettlement(SplitSettlementPolicy),
+    Risk(RiskPolicy),
+    BuyerIndex(BuyerIndexPolicy),
+    Callback(CallbackPolicy),
+    PlatformFee(PlatformFeePolicy),
+    Compliance(CompliancePolicy),
+    SettlementDiscount(SettlementDiscountPolicy),
+    RefundConversion(RefundConversionPolicy),
+    OperatorFailover(OperatorFailoverPolicy),
+    RentRebate(RentRebatePolicy),
+    DirectSettlement(DirectSettlementPolicy),
+}
+
+impl PolicyData {
+    pub const SIZE: usize = 1 + POLICY_SIZE; // policy_type (u8) + policy data (100 bytes)
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.push(self.policy_type().to_u8());
+        match self {
+            PolicyData::Refund(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::Settlement(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::FeeTier(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::Memo(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::Conversion(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::Chargeback(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::FeeOverride(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::Denylist(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::SplitSettlement(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::Risk(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::BuyerIndex(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::Callback(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::PlatformFee(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::Compliance(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::SettlementDiscount(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::RefundConversion(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::OperatorFailover(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::RentRebate(policy) => data.extend_from_slice(&policy.to_bytes()),
+            PolicyData::DirectSettlement(policy) => data.extend_from_slice(&policy.to_bytes()),
+        }
+        data.resize(Self::SIZE, 0);
+        data
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let (&policy_type, policy_data) =
+            data.split_first().ok_or(ProgramError::InvalidAccountData)?;
+        let policy_type = PolicyType::from_u8(policy_type)?;
+
+        match policy_type {
+            PolicyType::Refund => Ok(PolicyData::Refund(RefundPolicy::from_bytes(policy_data)?)),
+            PolicyType::Settlement => Ok(PolicyData::Settlement(SettlementPolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::FeeTier => Ok(PolicyData::FeeTier(FeeTierPolicy::from_bytes(policy_data)?)),
+            PolicyType::Memo => Ok(PolicyData::Memo(MemoPolicy::from_bytes(policy_data)?)),
+            PolicyType::Conversion => Ok(PolicyData::Conversion(ConversionPolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::Chargeback => Ok(PolicyData::Chargeback(ChargebackPolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::FeeOverride => Ok(PolicyData::FeeOverride(FeeOverridePolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::Denylist => Ok(PolicyData::Denylist(DenylistPolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::SplitSettlement => Ok(PolicyData::SplitSettlement(
+                SplitSettlementPolicy::from_bytes(policy_data)?,
+            )),
+            PolicyType::Risk => Ok(PolicyData::Risk(RiskPolicy::from_bytes(policy_data)?)),
+            PolicyType::BuyerIndex => Ok(PolicyData::BuyerIndex(BuyerIndexPolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::Callback => Ok(PolicyData::Callback(CallbackPolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::PlatformFee => Ok(PolicyData::PlatformFee(PlatformFeePolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::Compliance => Ok(PolicyData::Compliance(CompliancePolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::SettlementDiscount => Ok(PolicyData::SettlementDiscount(
+                SettlementDiscountPolicy::from_bytes(policy_data)?,
+            )),
+            PolicyType::RefundConversion => Ok(PolicyData::RefundConversion(
+                RefundConversionPolicy::from_bytes(policy_data)?,
+            )),
+            PolicyType::OperatorFailover => Ok(PolicyData::OperatorFailover(
+                OperatorFailoverPolicy::from_bytes(policy_data)?,
+            )),
+            PolicyType::RentRebate => Ok(PolicyData::RentRebate(RentRebatePolicy::from_bytes(
+                policy_data,
+            )?)),
+            PolicyType::DirectSettlement => Ok(PolicyData::DirectSettlement(
+                DirectSettlementPolicy::from_bytes(policy_data)?,
+            )),
+        }
+    }
+
+    /// Validates the policy's fields. Policies without constraints are always valid.
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        match self {
+            PolicyData::FeeTier(fee_tiers) => fee_tiers.validate(),
+            PolicyData::Conversion(conversion) => conversion.validate(),
+            PolicyData::Chargeback(chargeback) => chargeback.validate(),
+            PolicyData::FeeOverride(fee_override) => fee_override.validate(),
+            PolicyData::Denylist(denylist) => denylist.validate(),
+            PolicyData::SplitSettlement(split_settlement) => split_settlement.validate(),
+            PolicyData::Risk(risk) => risk.validate(),
+            PolicyData::Callback(callback) => callback.validate(),
+            PolicyData::PlatformFee(platform_fee) => platform_fee.validate(),
+            PolicyData::Compliance(compliance) => compliance.validate(),
+            PolicyData::SettlementDiscount(settlement_discount) => settlement_discount.validate(),
+            PolicyData::RefundConversion(refund_conversion) => refund_conversion.validate(),
+            PolicyData::OperatorFailover(operator_failover) => operator_failover.validate(),
+            PolicyData::RentRebate(rent_rebate) => rent_rebate.validate(),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn policy_type(&self) -> PolicyType {
+        match self {
+            PolicyData::Refund(_) => PolicyType::Refund,
+            PolicyData::Settlement(_) => PolicyType::Settlement,
+            PolicyData::FeeTier(_) => PolicyType::FeeTier,
+            PolicyData::Memo(_) => PolicyType::Memo,
+            PolicyData::Conversion(_) => PolicyType::Conversion,
+            PolicyData::Chargeback(_) => PolicyType::Chargeback,
+            PolicyData::FeeOverride(_) => PolicyType::FeeOverride,
+            PolicyData::Denylist(_) => PolicyType::Denylist,
+            PolicyData::SplitSettlement(_) => PolicyType::SplitSettlement,
+            PolicyData::Risk(_) => PolicyType::Risk,
+            PolicyData::BuyerIndex(_) => PolicyType::BuyerIndex,
+            PolicyData::Callback(_) => PolicyType::Callback,
+            PolicyData::PlatformFee(_) => PolicyType::PlatformFee,
+            PolicyData::Compliance(_) => PolicyType::Compliance,
+            PolicyData::SettlementDiscount(_) => PolicyType::SettlementDiscount,
+            PolicyData::RefundConversion(_) => PolicyType::RefundConversion,
+            PolicyData::OperatorFailover(_) => PolicyType::OperatorFailover,
+            PolicyData::RentRebate(_) => PolicyType::RentRebate,
+            PolicyData::DirectSettlement(_) => PolicyType::DirectSettlement,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_fee_type_from_u8() {
+        assert_eq!(FeeType::from_u8(0).unwrap(), FeeType::Bps);
+        assert_eq!(FeeType::from_u8(1).unwrap(), FeeType::Fixed);
+        assert!(FeeType::from_u8(2).is_err());
+        assert!(FeeType::from_u8(255).is_err());
+    }
+
+    #[test]
+    fn test_fee_type_to_u8() {
+        assert_eq!(FeeType::Bps.to_u8(), 0);
+        assert_eq!(FeeType::Fixed.to_u8(), 1);
+    }
+
+    #[test]
+    fn test_fee_type_roundtrip() {
+        let fee_type = FeeType::Bps;
+        assert_eq!(FeeType::from_u8(fee_type.to_u8()).unwrap(), fee_type);
+
+        let fee_type = FeeType::Fixed;
+        assert_eq!(FeeType::from_u8(fee_type.to_u8()).unwrap(), fee_type);
+    }
+
+    #[test]
+    fn test_policy_type_from_u8() {
+        assert_eq!(PolicyType::from_u8(0).unwrap(), PolicyType::Refund);
+        assert_eq!(PolicyType::from_u8(1).unwrap(), PolicyType::Settlement);
+        assert_eq!(PolicyType::from_u8(2).unwrap(), PolicyType::FeeTier);
+        assert_eq!(PolicyType::from_u8(3).unwrap(), PolicyType::Memo);
+        assert_eq!(PolicyType::from_u8(4).unwrap(), PolicyType::Conversion);
+        assert_eq!(PolicyType::from_u8(5).unwrap(), PolicyType::Chargeback);
+        assert_eq!(PolicyType::from_u8(6).unwrap(), PolicyType::FeeOverride);
+        assert_eq!(PolicyType::from_u8(7).unwrap(), PolicyType::Denylist);
+        assert_eq!(PolicyType::from_u8(8).unwrap(), PolicyType::SplitSettlement);
+        assert_eq!(PolicyType::from_u8(9).unwrap(), PolicyType::Risk);
+        assert_eq!(PolicyType::from_u8(10).unwrap(), PolicyType::BuyerIndex);
+        assert_eq!(PolicyType::from_u8(11).unwrap(), PolicyType::Callback);
+        assert_eq!(PolicyType::from_u8(12).unwrap(), PolicyType::PlatformFee);
+        assert_eq!(PolicyType::from_u8(13).unwrap(), PolicyType::Compliance);
+        assert_eq!(
+            PolicyType::from_u8(14).unwrap(),
+            PolicyType::SettlementDiscount
+        );
+        assert_eq!(
+            PolicyType::from_u8(15).unwrap(),
+            PolicyType::RefundConversion
+        );
+        assert_eq!(
+            PolicyType::from_u8(16).unwrap(),
+            PolicyType::OperatorFailover
+        );
+        assert_eq!(PolicyType::from_u8(17).unwrap(), PolicyType::RentRebate);
+        assert_eq!(
+            PolicyType::from_u8(18).unwrap(),
+            PolicyType::DirectSettlement
+        );
+        assert!(PolicyType::from_u8(19).is_err());
+        assert!(PolicyType::from_u8(255).is_err());
+    }
+
+    #[test]
+    fn test_policy_type_to_u8() {
+        assert_eq!(PolicyType::Refund.to_u8(), 0);
+        assert_eq!(PolicyType::Settlement.to_u8(), 1);
+        assert_eq!(PolicyType::FeeTier.to_u8(), 2);
+        assert_eq!(PolicyType::Memo.to_u8(), 3);
+        assert_eq!(PolicyType::Conversion.to_u8(), 4);
+        assert_eq!(PolicyType::Chargeback.to_u8(), 5);
+        assert_eq!(PolicyType::FeeOverride.to_u8(), 6);
+        assert_eq!(PolicyType::Denylist.to_u8(), 7);
+    }
+
+    #[test]
+    fn test_policy_type_get_size() {
+        assert_eq!(PolicyType::Refund.get_size(), 1 + REFUND_POLICY_SIZE);
+        assert_eq!(
+            PolicyType::Settlement.get_size(),
+            1 + SETTLEMENT_POLICY_SIZE
+        );
+        assert_eq!(PolicyType::FeeTier.get_size(), 1 + FEE_TIER_POLICY_SIZE);
+        assert!(PolicyType::FeeTier.get_size() <= PolicyData::SIZE);
+        assert_eq!(PolicyType::Memo.get_size(), 1 + MEMO_POLICY_SIZE);
+        assert_eq!(
+            PolicyType::Conversion.get_size(),
+            1 + CONVERSION_POLICY_SIZE
+        );
+        assert_eq!(
+            PolicyType::Chargeback.get_size(),
+            1 + CHARGEBACK_POLICY_SIZE
+        );
+        assert_eq!(
+            PolicyType::FeeOverride.get_size(),
+            1 + FEE_OVERRIDE_POLICY_SIZE
+        );
+        assert_eq!(PolicyType::Denylist.get_size(), 1 + DENYLIST_POLICY_SIZE);
+        assert_eq!(
+            PolicyType::SplitSettlement.get_size(),
+            1 + SPLIT_SETTLEMENT_POLICY_SIZE
+        );
+        assert_eq!(PolicyType::Risk.get_size(), 1 + RISK_POLICY_SIZE);
+        assert_eq!(
+            PolicyType::BuyerIndex.get_size(),
+            1 + BUYER_INDEX_POLICY_SIZE
+        );
+        assert_eq!(PolicyType::Callback.get_size(), 1 + CALLBACK_POLICY_SIZE);
+        assert_eq!(
+            PolicyType::PlatformFee.get_size(),
+            1 + PLATFORM_FEE_POLICY_SIZE
+        );
+        assert_eq!(
+            PolicyType::Compliance.get_size(),
+            1 + COMPLIANCE_POLICY_SIZE
+        );
+        assert_eq!(
+            PolicyType::SettlementDiscount.get_size(),
+            1 + SETTLEMENT_DISCOUNT_POLICY_SIZE
+        );
+        assert_eq!(
+            PolicyType::RefundConversion.get_size(),
+            1 + REFUND_CONVERSION_POLICY_SIZE
+        );
+        assert_eq!(
+            PolicyType::OperatorFailover.get_size(),
+            1 + OPERATOR_FAILOVER_POLICY_SIZE
+        );
+        assert_eq!(
+            PolicyType::RentRebate.get_size(),
+            1 + RENT_REBATE_POLICY_SIZE
+        );
+        assert_eq!(
+            PolicyType::DirectSettlement.get_size(),
+            1 + DIRECT_SETTLEMENT_POLICY_SIZE
+        );
+    }
+
+    #[test]
+    fn test_policy_type_roundtrip() {
+        let policy_type = PolicyType::Refund;
+        assert_eq!(
+            PolicyType::from_u8(policy_type.to_u8()).unwrap(),
+            policy_type
+        );
+
+        let policy_type = PolicyType::Settlement;
+        assert_eq!(
+            PolicyType::from_u8(policy_type.to_u8()).unwrap(),
+            policy_type
+        );
+    }
+
+    #[test]
+    fn test_refund_policy_serialization() {
+        let policy = RefundPolicy {
+            max_amount: 1000,
+            max_time_after_purchase: 3600,
+        };
+
+        let bytes = policy.to_bytes();
+        assert_eq!(bytes.len(), REFUND_POLICY_SIZE);
+
+        let deserialized = RefundPolicy::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy);
+    }
+
+    #[test]
+    fn test_refund_policy_from_bytes_invalid_length() {
+        let short_data = vec![1, 2, 3];
+        assert!(RefundPolicy::from_bytes(&short_data).is_err());
+    }
+
+    #[test]
+    fn test_settlement_policy_serialization() {
+        let policy = SettlementPolicy {
+            min_settlement_amount: 5000,
+            settlement_frequency_hours: 24,
+            auto_settle: true,
+        };
+
+        let bytes = policy.to_bytes();
+        assert_eq!(bytes.len(), SETTLEMENT_POLICY_SIZE);
+
+        let deserialized = SettlementPolicy::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy);
+    }
+
+    #[test]
+    fn test_settlement_policy_auto_settle_false() {
+        let policy = SettlementPolicy {
+            min_settlement_amount: 1000,
+            settlement_frequency_hours: 12,
+            auto_settle: false,
+        };
+
+        let bytes = policy.to_bytes();
+        let deserialized = SettlementPolicy::from_bytes(&bytes).unwrap();
+        assert!(!deserialized.auto_settle);
+    }
+
+    #[test]
+    fn test_settlement_policy_from_bytes_invalid_length() {
+        let short_data = vec![1, 2, 3];
+        assert!(SettlementPolicy::from_bytes(&short_data).is_err());
+    }
+
+    #[test]
+    fn test_policy_data_refund_serialization() {
+        let refund_policy = RefundPolicy {
+            max_amount: 2000,
+            max_time_after_purchase: 7200,
+        };
+        let policy_data = PolicyData::Refund(refund_policy.clone());
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::Refund.to_u8());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::Refund);
+    }
+
+    #[test]
+    fn test_policy_data_settlement_serialization() {
+        let settlement_policy = SettlementPolicy {
+            min_settlement_amount: 3000,
+            settlement_frequency_hours: 48,
+            auto_settle: false,
+        };
+        let policy_data = PolicyData::Settlement(settlement_policy.clone());
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::Settlement.to_u8());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::Settlement);
+    }
+
+    fn create_fee_tier_policy(tiers: &[(u64, u64, FeeType)]) -> FeeTierPolicy {
+        let mut policy = FeeTierPolicy {
+            num_tiers: tiers.len() as u8,
+            tiers: [FeeTier::EMPTY; MAX_FEE_TIERS],
+        };
+        for (slot, (threshold, fee, fee_type)) in policy.tiers.iter_mut().zip(tiers) {
+            *slot = FeeTier {
+                threshold: *threshold,
+                fee: *fee,
+                fee_type: fee_type.clone(),
+            };
+        }
+        policy
+    }
+
+    #[test]
+    fn test_fee_tier_policy_serialization() {
+        let policy = create_fee_tier_policy(&[
+            (0, 200, FeeType::Bps),
+            (100_000_000, 100, FeeType::Bps),
+            (1_000_000_000, 5_000_000, FeeType::Fixed),
+        ]);
+
+        let bytes = policy.to_bytes();
+        assert_eq!(bytes.len(), FEE_TIER_POLICY_SIZE);
+
+        let deserialized = FeeTierPolicy::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy);
+    }
+
+    #[test]
+    fn test_fee_tier_policy_from_bytes_invalid_fee_type() {
+        let mut bytes = create_fee_tier_policy(&[(0, 200, FeeType::Bps)]).to_bytes();
+        bytes[FEE_TIER_SIZE] = 9; // fee_type of the first tier
+        assert!(FeeTierPolicy::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_fee_tier_policy_tier_for_amount() {
+        let policy =
+            create_fee_tier_policy(&[(1_000, 200, FeeType::Bps), (100_000, 100, FeeType::Bps)]);
+
+        assert!(policy.tier_for_amount(999).is_none());
+        assert_eq!(policy.tier_for_amount(1_000).unwrap().fee, 200);
+        assert_eq!(policy.tier_for_amount(99_999).unwrap().fee, 200);
+        assert_eq!(policy.tier_for_amount(100_000).unwrap().fee, 100);
+        assert_eq!(policy.tier_for_amount(u64::MAX).unwrap().fee, 100);
+    }
+
+    #[test]
+    fn test_fee_tier_policy_ignores_unused_tiers() {
+        let mut policy = create_fee_tier_policy(&[(0, 200, FeeType::Bps)]);
+        policy.tiers[1] = FeeTier {
+            threshold: 10,
+            fee: 1,
+            fee_type: FeeType::Bps,
+        };
+
+        assert_eq!(policy.tier_for_amount(1_000).unwrap().fee, 200);
+        assert!(policy.validate().is_ok());
+    }
+
+    #[test]
+    fn test_fee_tier_policy_validate() {
+        let valid =
+            create_fee_tier_policy(&[(0, 200, FeeType::Bps), (100_000, 50, FeeType::Fixed)]);
+        assert!(valid.validate().is_ok());
+
+        let err: ProgramError = CommerceProgramError::InvalidFeeTierPolicy.into();
+
+        let empty = create_fee_tier_policy(&[]);
+        assert_eq!(empty.validate().unwrap_err(), err);
+
+        let mut too_many = create_fee_tier_policy(&[(0, 200, FeeType::Bps)]);
+        too_many.num_tiers = MAX_FEE_TIERS as u8 + 1;
+        assert_eq!(too_many.validate().unwrap_err(), err);
+
+        let unordered =
+            create_fee_tier_policy(&[(100_000, 200, FeeType::Bps), (0, 100, FeeType::Bps)]);
+        assert_eq!(unordered.validate().unwrap_err(), err);
+
+        let duplicate =
+            create_fee_tier_policy(&[(100_000, 200, FeeType::Bps), (100_000, 100, FeeType::Bps)]);
+        assert_eq!(duplicate.validate().unwrap_err(), err);
+
+        let bps_too_high = create_fee_tier_policy(&[(0, MAX_BPS + 1, FeeType::Bps)]);
+        assert_eq!(bps_too_high.validate().unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_policy_data_fee_tier_serialization() {
+        let policy_data = PolicyData::FeeTier(create_fee_tier_policy(&[
+            (0, 200, FeeType::Bps),
+            (100_000, 100, FeeType::Bps),
+        ]));
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::FeeTier.to_u8());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::FeeTier);
+    }
+
+    #[test]
+    fn test_policy_data_memo_serialization() {
+        let policy_data = PolicyData::Memo(MemoPolicy {
+            memo_on_clear: true,
+            memo_on_refund: false,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::Memo.to_u8());
+        assert_eq!(&bytes[1..3], &[1, 0]);
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::Memo);
+    }
+
+    #[test]
+    fn test_memo_policy_from_bytes_invalid_length() {
+        assert!(MemoPolicy::from_bytes(&[1]).is_err());
+    }
+
+    #[test]
+    fn test_policy_data_conversion_serialization() {
+        let policy_data = PolicyData::Conversion(ConversionPolicy {
+            settlement_mint: [7u8; 32],
+            swap_adapter: SwapAdapter::JupiterV6,
+            max_slippage_bps: 50,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::Conversion.to_u8());
+        assert_eq!(&bytes[1..33], &[7u8; 32]);
+        assert_eq!(bytes[33], SwapAdapter::JupiterV6.to_u8());
+        assert_eq!(&bytes[34..36], &50u16.to_le_bytes());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::Conversion);
+    }
+
+    #[test]
+    fn test_conversion_policy_from_bytes_invalid() {
+        assert!(ConversionPolicy::from_bytes(&[0; CONVERSION_POLICY_SIZE - 1]).is_err());
+
+        let mut data = [0u8; CONVERSION_POLICY_SIZE];
+        data[32] = 1; // Unknown swap adapter
+        assert!(ConversionPolicy::from_bytes(&data).is_err());
+    }
+
+    #[test]
+    fn test_conversion_policy_validate() {
+        let mut policy = ConversionPolicy {
+            settlement_mint: [7u8; 32],
+            swap_adapter: SwapAdapter::JupiterV6,
+            max_slippage_bps: MAX_BPS as u16,
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.max_slippage_bps += 1;
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            CommerceProgramError::InvalidConversionPolicy.into()
+        );
+    }
+
+    #[test]
+    fn test_conversion_policy_min_amount_out() {
+        let mut policy = ConversionPolicy {
+            settlement_mint: [7u8; 32],
+            swap_adapter: SwapAdapter::JupiterV6,
+            max_slippage_bps: 50, // 0.5%
+        };
+        assert_eq!(policy.min_amount_out(1_000_000), 995_000);
+        // No overflow on large quotes
+        assert_eq!(
+            policy.min_amount_out(u64::MAX),
+            (u64::MAX as u128 * 9_950 / 10_000) as u64
+        );
+
+        policy.max_slippage_bps = 0;
+        assert_eq!(policy.min_amount_out(1_000_000), 1_000_000);
+    }
+
+    #[test]
+    fn test_policy_data_chargeback_serialization() {
+        let policy_data = PolicyData::Chargeback(ChargebackPolicy {
+            window_seconds: 7 * 24 * 60 * 60,
+            arbiter: [9u8; 32],
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::Chargeback.to_u8());
+        assert_eq!(&bytes[1..9], &(7u64 * 24 * 60 * 60).to_le_bytes());
+        assert_eq!(&bytes[9..41], &[9u8; 32]);
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::Chargeback);
+    }
+
+    #[test]
+    fn test_chargeback_policy_from_bytes_invalid_length() {
+        assert!(ChargebackPolicy::from_bytes(&[0; CHARGEBACK_POLICY_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn test_chargeback_policy_validate() {
+        let mut policy = ChargebackPolicy {
+            window_seconds: 3600,
+            arbiter: [9u8; 32],
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.window_seconds = 0;
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            CommerceProgramError::InvalidChargebackPolicy.into()
+        );
+
+        policy.window_seconds = 3600;
+        policy.arbiter = Pubkey::default();
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            CommerceProgramError::InvalidChargebackPolicy.into()
+        );
+    }
+
+    #[test]
+    fn test_chargeback_policy_is_window_open() {
+        let policy = ChargebackPolicy {
+            window_seconds: 3600,
+            arbiter: [9u8; 32],
+        };
+        assert!(policy.is_window_open(1_000, 1_000));
+        assert!(policy.is_window_open(1_000, 4_599));
+        assert!(!policy.is_window_open(1_000, 4_600));
+        // No overflow at the extremes
+        let policy = ChargebackPolicy {
+            window_seconds: u64::MAX,
+            arbiter: [9u8; 32],
+        };
+        assert!(policy.is_window_open(i64::MAX, i64::MAX));
+    }
+
+    #[test]
+    fn test_policy_data_fee_override_serialization() {
+        let policy_data = PolicyData::FeeOverride(FeeOverridePolicy {
+            max_fee: 500,
+            max_fee_type: FeeType::Bps,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::FeeOverride.to_u8());
+        assert_eq!(&bytes[1..9], &500u64.to_le_bytes());
+        assert_eq!(bytes[9], FeeType::Bps.to_u8());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::FeeOverride);
+    }
+
+    #[test]
+    fn test_fee_override_policy_validate() {
+        let mut policy = FeeOverridePolicy {
+            max_fee: MAX_BPS,
+            max_fee_type: FeeType::Bps,
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.max_fee = MAX_BPS + 1;
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            CommerceProgramError::InvalidFeeOverridePolicy.into()
+        );
+
+        // Fixed maximums are capped at the payment amount instead
+        policy.max_fee_type = FeeType::Fixed;
+        assert!(policy.validate().is_ok());
+    }
+
+    #[test]
+    fn test_policy_data_denylist_serialization() {
+        let policy_data = PolicyData::Denylist(DenylistPolicy {
+            root: [7u8; 32],
+            depth: 3,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::Denylist.to_u8());
+        assert_eq!(&bytes[1..33], &[7u8; 32]);
+        assert_eq!(bytes[33], 3);
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::Denylist);
+    }
+
+    #[test]
+    fn test_denylist_policy_validate() {
+        let mut policy = DenylistPolicy {
+            root: [1u8; 32],
+            depth: MAX_DENYLIST_DEPTH as u8,
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.depth += 1;
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            CommerceProgramError::InvalidDenylistPolicy.into()
+        );
+
+        policy.depth = 0;
+        policy.root = [0u8; 32];
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            CommerceProgramError::InvalidDenylistPolicy.into()
+        );
+    }
+
+    #[test]
+    fn test_denylist_policy_allows() {
+        let banned = [5u8; 32];
+        let gaps = [([0u8; 32], banned), (banned, [0xffu8; 32])];
+        let leaves = gaps.map(|(lower, upper)| DenylistPolicy::leaf_hash(&lower, &upper));
+        let policy = DenylistPolicy {
+            root: hashv(&[&leaves[0], &leaves[1]]),
+            depth: 1,
+        };
+        let proof = |leaf_index: usize| DenylistProof {
+            lower: gaps[leaf_index].0,
+            upper: gaps[leaf_index].1,
+            leaf_index: leaf_index as u32,
+            proof: vec![leaves[1 - leaf_index]],
+        };
+
+        assert!(policy.allows(&[1u8; 32], &proof(0)));
+        assert!(policy.allows(&[9u8; 32], &proof(1)));
+
+        // The banned buyer is not strictly inside either gap
+        assert!(!policy.allows(&banned, &proof(0)));
+        assert!(!policy.allows(&banned, &proof(1)));
+
+        // A buyer outside the opened gap
+        assert!(!policy.allows(&[9u8; 32], &proof(0)));
+
+        // A gap that is not in the tree
+        let mut forged = proof(0);
+        forged.upper = [0xffu8; 32];
+        assert!(!policy.allows(&[9u8; 32], &forged));
+
+        // Wrong proof length
+        let mut truncated = proof(0);
+        truncated.proof.clear();
+        assert!(!policy.allows(&[1u8; 32], &truncated));
+    }
+
+    fn split_settlement_policy(shares: &[u16]) -> SplitSettlementPolicy {
+        let mut shares_bps = [0u16; MAX_SPLIT_DESTINATIONS];
+        shares_bps[..shares.len()].copy_from_slice(shares);
+        SplitSettlementPolicy {
+            destinations_hash: [4u8; 32],
+            num_destinations: shares.len() as u8,
+            shares_bps,
+        }
+    }
+
+    #[test]
+    fn test_policy_data_split_settlement_serialization() {
+        let policy_data = PolicyData::SplitSettlement(split_settlement_policy(&[9_000, 1_000]));
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::SplitSettlement.to_u8());
+        assert_eq!(&bytes[1..33], &[4u8; 32]);
+        assert_eq!(bytes[33], 2);
+        assert_eq!(&bytes[34..36], &9_000u16.to_le_bytes());
+        assert_eq!(&bytes[36..38], &1_000u16.to_le_bytes());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::SplitSettlement);
+    }
+
+    #[test]
+    fn test_split_settlement_policy_validate() {
+        assert!(split_settlement_policy(&[9_000, 1_000]).validate().is_ok());
+        assert!(split_settlement_policy(&[MAX_BPS as u16])
+            .validate()
+            .is_ok());
+        assert!(split_settlement_policy(&[2_000; 5]).validate().is_ok());
+
+        let invalid = [
+            split_settlement_policy(&[]),
+            split_settlement_policy(&[9_000, 999]),
+            split_settlement_policy(&[9_000, 1_001]),
+            split_settlement_policy(&[MAX_BPS as u16, 0]),
+            SplitSettlementPolicy {
+                destinations_hash: [0u8; 32],
+                ..split_settlement_policy(&[MAX_BPS as u16])
+            },
+            SplitSettlementPolicy {
+                num_destinations: MAX_SPLIT_DESTINATIONS as u8 + 1,
+                ..split_settlement_policy(&[2_000; 5])
+            },
+        ];
+        for policy in invalid {
+            assert_eq!(
+                policy.validate().unwrap_err(),
+                CommerceProgramError::InvalidSplitSettlementPolicy.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_settlement_policy_split() {
+        let policy = split_settlement_policy(&[9_000, 1_000]);
+        assert_eq!(policy.split(1_000_000), [900_000, 100_000, 0, 0, 0]);
+
+        // Rounding dust goes to the first destination
+        let policy = split_settlement_policy(&[3_333, 3_333, 3_334]);
+        assert_eq!(policy.split(10), [4, 3, 3, 0, 0]);
+        assert_eq!(policy.split(0), [0; MAX_SPLIT_DESTINATIONS]);
+
+        let parts = policy.split(u64::MAX);
+        assert_eq!(
+            parts.iter().map(|&part| part as u128).sum::<u128>(),
+            u64::MAX as u128
+        );
+    }
+
+    #[test]
+    fn test_split_settlement_destinations_hash() {
+        let wallets = [[1u8; 32], [2u8; 32]];
+        assert_eq!(
+            SplitSettlementPolicy::destinations_hash(&wallets),
+            hashv(&[&wallets[0], &wallets[1]])
+        );
+        assert_ne!(
+            SplitSettlementPolicy::destinations_hash(&wallets),
+            SplitSettlementPolicy::destinations_hash(&[wallets[1], wallets[0]])
+        );
+    }
+
+    #[test]
+    fn test_policy_data_risk_serialization() {
+        let policy_data = PolicyData::Risk(RiskPolicy {
+            max_payment_amount: 1_000,
+            max_daily_volume: 50_000,
+            max_payments_per_buyer_per_day: 3,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::Risk.to_u8());
+        assert_eq!(&bytes[1..9], &1_000u64.to_le_bytes());
+        assert_eq!(&bytes[9..17], &50_000u64.to_le_bytes());
+        assert_eq!(&bytes[17..21], &3u32.to_le_bytes());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::Risk);
+    }
+
+    #[test]
+    fn test_risk_policy_validate() {
+        let mut policy = RiskPolicy {
+            max_payment_amount: 0,
+            max_daily_volume: 0,
+            max_payments_per_buyer_per_day: 0,
+        };
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            CommerceProgramError::InvalidRiskPolicy.into()
+        );
+
+        // Any single limit is enough
+        policy.max_payments_per_buyer_per_day = 1;
+        assert!(policy.validate().is_ok());
+    }
+
+    #[test]
+    fn test_policy_data_buyer_index_serialization() {
+        let policy_data = PolicyData::BuyerIndex(BuyerIndexPolicy { required: true });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::BuyerIndex.to_u8());
+        assert_eq!(bytes[1], 1);
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::BuyerIndex);
+    }
+
+    #[test]
+    fn test_policy_data_callback_serialization() {
+        let policy_data = PolicyData::Callback(CallbackPolicy {
+            program: [4u8; 32],
+            on_clear: true,
+            on_refund: false,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::Callback.to_u8());
+        assert_eq!(&bytes[1..33], &[4u8; 32]);
+        assert_eq!(bytes[33..35], [1, 0]);
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::Callback);
+    }
+
+    #[test]
+    fn test_callback_policy_validate() {
+        let err: ProgramError = CommerceProgramError::InvalidCallbackPolicy.into();
+        let mut policy = CallbackPolicy {
+            program: [4u8; 32],
+            on_clear: false,
+            on_refund: true,
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.on_refund = false;
+        assert_eq!(policy.validate().unwrap_err(), err);
+
+        policy.on_clear = true;
+        policy.program = Pubkey::default();
+        assert_eq!(policy.validate().unwrap_err(), err);
+
+        policy.program = COMMERCE_PROGRAM_ID;
+        assert_eq!(policy.validate().unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_policy_data_platform_fee_serialization() {
+        let policy_data = PolicyData::PlatformFee(PlatformFeePolicy {
+            platform: [6u8; 32],
+            fee: 150,
+            fee_type: FeeType::Bps,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::PlatformFee.to_u8());
+        assert_eq!(&bytes[1..33], &[6u8; 32]);
+        assert_eq!(&bytes[33..41], &150u64.to_le_bytes());
+        assert_eq!(bytes[41], FeeType::Bps.to_u8());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::PlatformFee);
+    }
+
+    #[test]
+    fn test_platform_fee_policy_validate() {
+        let err: ProgramError = CommerceProgramError::InvalidPlatformFeePolicy.into();
+        let mut policy = PlatformFeePolicy {
+            platform: [6u8; 32],
+            fee: MAX_BPS,
+            fee_type: FeeType::Bps,
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.fee = MAX_BPS + 1;
+        assert_eq!(policy.validate().unwrap_err(), err);
+
+        policy.fee_type = FeeType::Fixed;
+        assert!(policy.validate().is_ok());
+
+        policy.fee = 0;
+        assert_eq!(policy.validate().unwrap_err(), err);
+
+        policy.fee = 1;
+        policy.platform = Pubkey::default();
+        assert_eq!(policy.validate().unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_policy_data_compliance_serialization() {
+        let policy_data = PolicyData::Compliance(CompliancePolicy { program: [7u8; 32] });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::Compliance.to_u8());
+        assert_eq!(&bytes[1..33], &[7u8; 32]);
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::Compliance);
+    }
+
+    #[test]
+    fn test_compliance_policy_validate() {
+        let err: ProgramError = CommerceProgramError::InvalidCompliancePolicy.into();
+        let mut policy = CompliancePolicy { program: [7u8; 32] };
+        assert!(policy.validate().is_ok());
+
+        policy.program = Pubkey::default();
+        assert_eq!(policy.validate().unwrap_err(), err);
+
+        policy.program = COMMERCE_PROGRAM_ID;
+        assert_eq!(policy.validate().unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_policy_data_settlement_discount_serialization() {
+        let policy_data = PolicyData::SettlementDiscount(SettlementDiscountPolicy {
+            discount_bps_per_day: 25,
+            max_discount_bps: 1_000,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::SettlementDiscount.to_u8());
+        assert_eq!(&bytes[1..3], &25u16.to_le_bytes());
+        assert_eq!(&bytes[3..5], &1_000u16.to_le_bytes());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::SettlementDiscount);
+    }
+
+    #[test]
+    fn test_settlement_discount_policy_validate() {
+        let err: ProgramError = CommerceProgramError::InvalidSettlementDiscountPolicy.into();
+        let mut policy = SettlementDiscountPolicy {
+            discount_bps_per_day: 25,
+            max_discount_bps: MAX_BPS as u16,
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.max_discount_bps = MAX_BPS as u16 + 1;
+        assert_eq!(policy.validate().unwrap_err(), err);
+
+        policy.max_discount_bps = 0;
+        assert_eq!(policy.validate().unwrap_err(), err);
+
+        policy.max_discount_bps = 1_000;
+        policy.discount_bps_per_day = 0;
+        assert_eq!(policy.validate().unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_settlement_discount_policy_discounted_fee() {
+        let policy = SettlementDiscountPolicy {
+            discount_bps_per_day: 100,
+            max_discount_bps: 500,
+        };
+        let created_at = 1_700_000_000;
+        let day = SECONDS_PER_DAY;
+
+        // Partial days earn nothing
+        assert_eq!(policy.discount_bps(created_at, created_at + day - 1), 0);
+        assert_eq!(
+            policy.discounted_fee(1_000, created_at, created_at + day - 1),
+            1_000
+        );
+
+        // 1% per full day
+        assert_eq!(policy.discount_bps(created_at, created_at + 3 * day), 300);
+        assert_eq!(
+            policy.discounted_fee(1_000, created_at, created_at + 3 * day),
+            970
+        );
+
+        // Capped at max_discount_bps
+        assert_eq!(policy.discount_bps(created_at, created_at + 30 * day), 500);
+        assert_eq!(
+            policy.discounted_fee(1_000, created_at, created_at + 30 * day),
+            950
+        );
+
+        // A clock behind created_at earns nothing
+        assert_eq!(policy.discount_bps(created_at, created_at - 10 * day), 0);
+
+        // Extreme timestamps neither overflow nor exceed the cap
+        assert_eq!(policy.discount_bps(i64::MIN, i64::MAX), 500);
+        assert_eq!(
+            policy.discounted_fee(u64::MAX, i64::MIN, i64::MAX),
+            u64::MAX - bps_of(u64::MAX, 500)
+        );
+    }
+
+    #[test]
+    fn test_policy_data_refund_conversion_serialization() {
+        let policy_data = PolicyData::RefundConversion(RefundConversionPolicy {
+            max_rate_age_seconds: 3_600,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::RefundConversion.to_u8());
+        assert_eq!(&bytes[1..5], &3_600u32.to_le_bytes());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::RefundConversion);
+    }
+
+    #[test]
+    fn test_refund_conversion_policy_validate_and_freshness() {
+        let mut policy = RefundConversionPolicy {
+            max_rate_age_seconds: 60,
+        };
+        assert!(policy.validate().is_ok());
+
+        let posted_at = 1_700_000_000;
+        assert!(policy.is_fresh(posted_at, posted_at));
+        assert!(policy.is_fresh(posted_at, posted_at + 60));
+        assert!(!policy.is_fresh(posted_at, posted_at + 61));
+        // A clock behind posted_at still counts as fresh
+        assert!(policy.is_fresh(posted_at, posted_at - 10));
+        assert!(!policy.is_fresh(i64::MIN, i64::MAX));
+
+        policy.max_rate_age_seconds = 0;
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            CommerceProgramError::InvalidRefundConversionPolicy.into()
+        );
+    }
+
+    #[test]
+    fn test_policy_data_operator_failover_serialization() {
+        let policy_data = PolicyData::OperatorFailover(OperatorFailoverPolicy {
+            inactivity_threshold_seconds: 604_800,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::OperatorFailover.to_u8());
+        assert_eq!(&bytes[1..5], &604_800u32.to_le_bytes());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::OperatorFailover);
+    }
+
+    #[test]
+    fn test_operator_failover_policy_validate() {
+        let mut policy = OperatorFailoverPolicy {
+            inactivity_threshold_seconds: 604_800,
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.inactivity_threshold_seconds = 0;
+        assert_eq!(
+            policy.validate().unwrap_err(),
+            CommerceProgramError::InvalidOperatorFailoverPolicy.into()
+        );
+    }
+
+    #[test]
+    fn test_policy_data_rent_rebate_serialization() {
+        let policy_data = PolicyData::RentRebate(RentRebatePolicy {
+            buyer_share_bps: 2_500,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::RentRebate.to_u8());
+        assert_eq!(&bytes[1..3], &2_500u16.to_le_bytes());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::RentRebate);
+    }
+
+    #[test]
+    fn test_rent_rebate_policy_validate() {
+        let err: ProgramError = CommerceProgramError::InvalidRentRebatePolicy.into();
+        let mut policy = RentRebatePolicy {
+            buyer_share_bps: MAX_BPS as u16,
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.buyer_share_bps = MAX_BPS as u16 + 1;
+        assert_eq!(policy.validate().unwrap_err(), err);
+
+        policy.buyer_share_bps = 0;
+        assert_eq!(policy.validate().unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_rent_rebate_policy_buyer_share() {
+        let mut policy = RentRebatePolicy {
+            buyer_share_bps: 2_500,
+        };
+        assert_eq!(policy.buyer_share(2_000_000), 500_000);
+        assert_eq!(policy.buyer_share(0), 0);
+
+        policy.buyer_share_bps = MAX_BPS as u16;
+        assert_eq!(policy.buyer_share(2_000_000), 2_000_000);
+        assert_eq!(policy.buyer_share(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_policy_data_direct_settlement_serialization() {
+        let policy_data = PolicyData::DirectSettlement(DirectSettlementPolicy {
+            max_amount: 50_000_000,
+        });
+
+        let bytes = policy_data.to_bytes();
+        assert_eq!(bytes.len(), PolicyData::SIZE);
+        assert_eq!(bytes[0], PolicyType::DirectSettlement.to_u8());
+        assert_eq!(&bytes[1..9], &50_000_000u64.to_le_bytes());
+
+        let deserialized = PolicyData::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, policy_data);
+        assert_eq!(deserialized.policy_type(), PolicyType::DirectSettlement);
+    }
+
+    #[test]
+    fn test_direct_settlement_policy_applies_to() {
+        let mut policy = DirectSettlementPolicy { max_amount: 1_000 };
+        assert!(policy.applies_to(0));
+        assert!(policy.applies_to(1_000));
+        assert!(!policy.applies_to(1_001));
+
+        policy.max_amount = 0;
+        assert!(policy.applies_to(u64::MAX));
+    }
+
+    #[test]
+    fn test_platform_fee_policy_fee_amount() {
+        let mut policy = PlatformFeePolicy {
+            platform: [6u8; 32],
+            fee: 200,
+            fee_type: FeeType::Bps,
+        };
+        assert_eq!(policy.fee_amount(10_000, 9_900), 200);
+        // Never more than the operator fee leaves for the merchant
+        assert_eq!(policy.fee_amount(10_000, 150), 150);
+
+        policy.fee_type = FeeType::Fixed;
+        assert_eq!(policy.fee_amount(10_000, 9_900), 200);
+        assert_eq!(policy.fee_amount(100, 50), 50);
+    }
+
+    #[test]
+    fn test_policy_data_from_bytes_empty() {
+        assert!(PolicyData::from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_policy_data_from_bytes_invalid_policy_type() {
+        let mut data = vec![0; PolicyData::SIZE];
+        data[0] = 99; // Invalid policy type
+        assert!(PolicyData::from_bytes(&data).is_err());
+    }
+
+    #[test]
+    fn test_policy_data_from_bytes_insufficient_data() {
+        let data = vec![0]; // Only policy type, no policy data
+        assert!(PolicyData::from_bytes(&data).is_err());
+    }
+
+    #[test]
     fn test_policy_data_padding() {
         let refund_policy = RefundPolicy {
             max_amount: 100,
@@ -368,3 +2275,153 @@ mod tests {
         });
     }
 }
+
+#[cfg(test)]
+pub(crate) mod strategies {
+    use super::*;
+    use alloc::vec;
+    use proptest::prelude::*;
+
+    pub fn fee_type() -> impl Strategy<Value = FeeType> {
+        prop_oneof![Just(FeeType::Bps), Just(FeeType::Fixed)]
+    }
+
+    fn fee_tier() -> impl Strategy<Value = FeeTier> {
+        (any::<u64>(), any::<u64>(), fee_type()).prop_map(|(threshold, fee, fee_type)| FeeTier {
+            threshold,
+            fee,
+            fee_type,
+        })
+    }
+
+    pub fn policy_data() -> impl Strategy<Value = PolicyData> {
+        prop_oneof![
+            (any::<u64>(), any::<u64>()).prop_map(|(max_amount, max_time_after_purchase)| {
+                PolicyData::Refund(RefundPolicy {
+                    max_amount,
+                    max_time_after_purchase,
+                })
+            }),
+            (any::<u64>(), any::<u32>(), any::<bool>()).prop_map(
+                |(min_settlement_amount, settlement_frequency_hours, auto_settle)| {
+                    PolicyData::Settlement(SettlementPolicy {
+                        min_settlement_amount,
+                        settlement_frequency_hours,
+                        auto_settle,
+                    })
+                }
+            ),
+            (any::<u8>(), proptest::array::uniform5(fee_tier())).prop_map(|(num_tiers, tiers)| {
+                PolicyData::FeeTier(FeeTierPolicy { num_tiers, tiers })
+            }),
+            (any::<bool>(), any::<bool>()).prop_map(|(memo_on_clear, memo_on_refund)| {
+                PolicyData::Memo(MemoPolicy {
+                    memo_on_clear,
+                    memo_on_refund,
+                })
+            }),
+            (any::<[u8; 32]>(), any::<u16>()).prop_map(|(settlement_mint, max_slippage_bps)| {
+                PolicyData::Conversion(ConversionPolicy {
+                    settlement_mint,
+                    swap_adapter: SwapAdapter::JupiterV6,
+                    max_slippage_bps,
+                })
+            }),
+            (any::<u64>(), any::<[u8; 32]>()).prop_map(|(window_seconds, arbiter)| {
+                PolicyData::Chargeback(ChargebackPolicy {
+                    window_seconds,
+                    arbiter,
+                })
+            }),
+            (any::<u64>(), fee_type()).prop_map(|(max_fee, max_fee_type)| {
+                PolicyData::FeeOverride(FeeOverridePolicy {
+                    max_fee,
+                    max_fee_type,
+                })
+            }),
+            (any::<[u8; 32]>(), any::<u8>())
+                .prop_map(|(root, depth)| PolicyData::Denylist(DenylistPolicy { root, depth })),
+            (any::<u64>(), any::<u64>(), any::<u32>()).prop_map(
+                |(max_payment_amount, max_daily_volume, max_payments_per_buyer_per_day)| {
+                    PolicyData::Risk(RiskPolicy {
+                        max_payment_amount,
+                        max_daily_volume,
+                        max_payments_per_buyer_per_day,
+                    })
+                }
+            ),
+            any::<bool>()
+                .prop_map(|required| PolicyData::BuyerIndex(BuyerIndexPolicy { required })),
+            (any::<[u8; 32]>(), any::<bool>(), any::<bool>()).prop_map(
+                |(program, on_clear, on_refund)| {
+                    PolicyData::Callback(CallbackPolicy {
+                        program,
+                        on_clear,
+                        on_refund,
+                    })
+                }
+            ),
+            (any::<[u8; 32]>(), any::<u64>(), fee_type()).prop_map(|(platform, fee, fee_type)| {
+                PolicyData::PlatformFee(PlatformFeePolicy {
+                    platform,
+                    fee,
+                    fee_type,
+                })
+            }),
+            any::<[u8; 32]>()
+                .prop_map(|program| PolicyData::Compliance(CompliancePolicy { program })),
+            (any::<u16>(), any::<u16>()).prop_map(|(discount_bps_per_day, max_discount_bps)| {
+                PolicyData::SettlementDiscount(SettlementDiscountPolicy {
+                    discount_bps_per_day,
+                    max_discount_bps,
+                })
+            }),
+            any::<u32>().prop_map(|max_rate_age_seconds| {
+                PolicyData::RefundConversion(RefundConversionPolicy {
+                    max_rate_age_seconds,
+                })
+            }),
+            any::<u32>().prop_map(|inactivity_threshold_seconds| {
+                PolicyData::OperatorFailover(OperatorFailoverPolicy {
+                    inactivity_threshold_seconds,
+                })
+            }),
+            any::<u16>().prop_map(|buyer_share_bps| {
+                PolicyData::RentRebate(RentRebatePolicy { buyer_share_bps })
+            }),
+            any::<u64>().prop_map(|max_amount| {
+                PolicyData::DirectSettlement(DirectSettlementPolicy { max_amount })
+            }),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use super::strategies::policy_data;
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn policy_data_round_trips(policy in policy_data()) {
+            let bytes = policy.to_bytes();
+            prop_assert_eq!(bytes.len(), PolicyData::SIZE);
+            prop_assert_eq!(PolicyData::from_bytes(&bytes), Ok(policy.clone()));
+
+            // Instruction data carries only the policy's own bytes, without the padding
+            let compact = &bytes[..policy.policy_type().get_size()];
+            prop_assert_eq!(PolicyData::from_bytes(compact), Ok(policy));
+        }
+
+        #[test]
+        fn policy_data_from_arbitrary_bytes_is_stable(
+            data in proptest::collection::vec(any::<u8>(), 0..2 * PolicyData::SIZE)
+        ) {
+            // Must never panic; anything it accepts must survive a re-encode
+            if let Ok(policy) = PolicyData::from_bytes(&data) {
+                prop_assert_eq!(PolicyData::from_bytes(&policy.to_bytes()), Ok(policy));
+            }
+        }
+    }
+}
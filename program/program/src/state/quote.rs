@@ -0,0 +1,203 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{constants::QUOTE_SEED, error::CommerceProgramError};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// A price quote for an order, signed by the operator.
+///
+/// Created by CreateQuote. MakePayment, when passed the quote, requires the payment's
+/// config, mint, order id and amount to match it and the quote not to have expired.
+///
+/// Seeds: [b"quote", merchant_operator_config pubkey, mint pubkey, order_id (u32 LE)]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct Quote {
+    pub merchant_operator_config: Pubkey,
+
+    pub mint: Pubkey,
+
+    pub order_id: u32,
+
+    pub bump: u8,
+
+    /// Amount the payment must be for
+    pub amount: u64,
+
+    /// Unix timestamp after which the quote can no longer be paid
+    pub expires_at: i64,
+
+    /// Unix timestamp of the quote's creation
+    pub created_at: i64,
+
+    /// Operator authority or delegate that signed the quote
+    pub quoter: Pubkey,
+}
+
+impl Discriminator for Quote {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::QuoteDiscriminator as u8;
+}
+
+impl AccountSerialize for Quote {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.mint.as_ref());
+        data.extend_from_slice(&self.order_id.to_le_bytes());
+        data.push(self.bump);
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.expires_at.to_le_bytes());
+        data.extend_from_slice(&self.created_at.to_le_bytes());
+        data.extend_from_slice(self.quoter.as_ref());
+        data
+    }
+}
+
+impl Quote {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant_operator_config
+        32 + // mint
+        4 + // order_id
+        1 + // bump
+        8 + // amount
+        8 + // expires_at
+        8 + // created_at
+        32; // quoter
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[
+                QUOTE_SEED,
+                self.merchant_operator_config.as_ref(),
+                self.mint.as_ref(),
+                &self.order_id.to_le_bytes(),
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::QuoteInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    /// Validates a payment of `amount` in `mint` for `order_id` under
+    /// `merchant_operator_config` against the quote at `now`.
+    pub fn validate_payment(
+        &self,
+        merchant_operator_config: &Pubkey,
+        mint: &Pubkey,
+        order_id: u32,
+        amount: u64,
+        now: i64,
+    ) -> Result<(), ProgramError> {
+        if self.merchant_operator_config.ne(merchant_operator_config)
+            || self.mint.ne(mint)
+            || self.order_id != order_id
+            || self.amount != amount
+        {
+            return Err(CommerceProgramError::QuoteMismatch.into());
+        }
+        if now > self.expires_at {
+            return Err(CommerceProgramError::QuoteExpired.into());
+        }
+        Ok(())
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            merchant_operator_config: reader.read_pubkey()?,
+            mint: reader.read_pubkey()?,
+            order_id: reader.read_u32()?,
+            bump: reader.read_u8()?,
+            amount: reader.read_u64()?,
+            expires_at: reader.read_i64()?,
+            created_at: reader.read_i64()?,
+            quoter: reader.read_pubkey()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn quote() -> Quote {
+        Quote {
+            merchant_operator_config: [1u8; 32],
+            mint: [2u8; 32],
+            order_id: 7,
+            bump: 254,
+            amount: 1_000,
+            expires_at: 1_700_000_600,
+            created_at: 1_700_000_000,
+            quoter: [3u8; 32],
+        }
+    }
+
+    #[test]
+    fn test_quote_serialization() {
+        let quote = quote();
+
+        let bytes = quote.to_bytes();
+        assert_eq!(bytes.len(), Quote::LEN);
+
+        let deserialized = Quote::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, quote);
+    }
+
+    #[test]
+    fn test_quote_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; Quote::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = Quote::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_validate_payment() {
+        let quote = quote();
+
+        assert!(quote
+            .validate_payment(&[1u8; 32], &[2u8; 32], 7, 1_000, 1_700_000_600)
+            .is_ok());
+
+        for (config, mint, order_id, amount) in [
+            ([9u8; 32], [2u8; 32], 7, 1_000),
+            ([1u8; 32], [9u8; 32], 7, 1_000),
+            ([1u8; 32], [2u8; 32], 8, 1_000),
+            ([1u8; 32], [2u8; 32], 7, 999),
+        ] {
+            assert_eq!(
+                quote
+                    .validate_payment(&config, &mint, order_id, amount, 1_700_000_000)
+                    .unwrap_err(),
+                CommerceProgramError::QuoteMismatch.into()
+            );
+        }
+
+        assert_eq!(
+            quote
+                .validate_payment(&[1u8; 32], &[2u8; 32], 7, 1_000, 1_700_000_601)
+                .unwrap_err(),
+            CommerceProgramError::QuoteExpired.into()
+        );
+    }
+}
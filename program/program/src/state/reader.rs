@@ -0,0 +1,284 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Bounds-checked cursor over account data.
+///
+/// Every read past the end of the data returns `InvalidAccountData` instead of panicking, so
+/// a truncated or garbage account fails its instruction with an error rather than aborting
+/// the program.
+pub struct ByteReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    /// Starts reading at `offset`.
+    pub fn at(data: &'a [u8], offset: usize) -> Self {
+        Self { data, offset }
+    }
+
+    /// Offset of the next byte to read.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Reads the discriminator byte, failing unless it is `expected`.
+    pub fn read_discriminator(&mut self, expected: u8) -> Result<(), ProgramError> {
+        if self.read_u8()? != expected {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], ProgramError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let bytes = self
+            .data
+            .get(self.offset..end)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ProgramError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, ProgramError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    /// Reads a byte as a bool, true only for 1.
+    pub fn read_bool(&mut self) -> Result<bool, ProgramError> {
+        Ok(self.read_u8()? == 1)
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, ProgramError> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, ProgramError> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, ProgramError> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, ProgramError> {
+        Ok(i64::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_pubkey(&mut self) -> Result<Pubkey, ProgramError> {
+        self.read_array()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_in_order() {
+        let mut data = [0u8; 48];
+        data[0] = 7;
+        data[1] = 1;
+        data[2..4].copy_from_slice(&500u16.to_le_bytes());
+        data[4..8].copy_from_slice(&70_000u32.to_le_bytes());
+        data[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        data[16..48].copy_from_slice(&[9u8; 32]);
+
+        let mut reader = ByteReader::new(&data);
+        reader.read_discriminator(7).unwrap();
+        assert!(reader.read_bool().unwrap());
+        assert_eq!(reader.read_u16().unwrap(), 500);
+        assert_eq!(reader.read_u32().unwrap(), 70_000);
+        assert_eq!(reader.read_u64().unwrap(), u64::MAX);
+        assert_eq!(reader.read_pubkey().unwrap(), [9u8; 32]);
+        assert_eq!(reader.offset(), 48);
+    }
+
+    #[test]
+    fn test_reads_past_end_fail() {
+        let data = [1u8; 3];
+
+        let mut reader = ByteReader::new(&data);
+        assert_eq!(
+            reader.read_u32().unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        // A failed read consumes nothing
+        assert_eq!(reader.offset(), 0);
+        assert_eq!(reader.read_u16().unwrap(), 257);
+        assert_eq!(
+            reader.read_pubkey().unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(reader.read_u8().unwrap(), 1);
+        assert_eq!(
+            reader.read_u8().unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        let mut reader = ByteReader::at(&data, usize::MAX);
+        assert_eq!(
+            reader.read_bytes(2).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    #[test]
+    fn test_wrong_discriminator_fails() {
+        let mut reader = ByteReader::new(&[3u8]);
+        assert_eq!(
+            reader.read_discriminator(4).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        assert_eq!(
+            ByteReader::new(&[]).read_discriminator(4).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    /// Every account must reject each strict prefix of a valid serialization and must not
+    /// panic on garbage.
+    #[test]
+    fn test_truncated_accounts_fail() {
+        use crate::state::*;
+
+        fn check<T>(bytes: &[u8], parse: fn(&[u8]) -> Result<T, ProgramError>) {
+            assert!(parse(bytes).is_ok());
+            for len in 0..bytes.len() {
+                assert!(parse(&bytes[..len]).is_err(), "prefix of length {len}");
+            }
+            let mut garbage = bytes.to_vec();
+            garbage[1..].fill(0xff);
+            let _ = parse(&garbage);
+        }
+
+        let merchant = Merchant {
+            owner: [1u8; 32],
+            bump: 254,
+            settlement_wallet: [2u8; 32],
+            pending_authority: [3u8; 32],
+            settlement_wallet_timelock_seconds: 86_400,
+            pending_settlement_wallet: [4u8; 32],
+            pending_settlement_wallet_unlocks_at: 1_700_086_400,
+            original_owner: [1u8; 32],
+        };
+        check(&merchant.to_bytes(), Merchant::try_from_bytes);
+
+        let operator = Operator {
+            owner: [1u8; 32],
+            bump: 253,
+            pending_authority: [2u8; 32],
+            last_active_at: 1_700_000_000,
+            has_freeze_list: false,
+            original_owner: [1u8; 32],
+        };
+        check(&operator.to_bytes(), Operator::try_from_bytes);
+
+        let config = MerchantOperatorConfig {
+            version: 1,
+            bump: 252,
+            merchant: [1u8; 32],
+            operator: [2u8; 32],
+            operator_fee: 100,
+            fee_type: FeeType::Bps,
+            current_order_id: 3,
+            days_to_close: 30,
+            num_policies: 2,
+            num_accepted_currencies: 1,
+            config_hash: [7; 32],
+            decommissioned_at: 0,
+            payment_accounts: 0,
+        };
+        let policies = [
+            PolicyData::Refund(RefundPolicy {
+                max_amount: 1_000,
+                max_time_after_purchase: 3_600,
+            }),
+            PolicyData::BuyerIndex(BuyerIndexPolicy { required: true }),
+        ];
+        check(
+            &config.to_bytes(&policies, &[[5u8; 32]]),
+            MerchantOperatorConfig::try_from_bytes,
+        );
+
+        let payment = Payment {
+            order_id: 1,
+            amount: 500,
+            created_at: 1_700_000_000,
+            status: Status::Paid,
+            bump: 251,
+            idempotency_key: [6u8; 16],
+            has_fee_override: true,
+            fee_override: FeeOverride {
+                fee: 10,
+                fee_type: FeeType::Fixed,
+            },
+            references: [[7u8; 32], [0u8; 32]],
+            rent_payer: [0; 32],
+            has_order: false,
+        };
+        check(&payment.to_bytes(), Payment::try_from_bytes);
+
+        let delegate = OperatorDelegate {
+            operator: [1u8; 32],
+            delegate: [2u8; 32],
+            bump: 250,
+            can_make_payment: true,
+            can_clear: false,
+            can_refund: true,
+            can_close: false,
+            max_refund_amount: 1_000,
+            max_daily_refund_amount: 5_000,
+            refund_day: 20_000,
+            daily_refund_amount: 700,
+        };
+        check(&delegate.to_bytes(), OperatorDelegate::try_from_bytes);
+
+        let profile = MerchantProfile::new(
+            [1u8; 32],
+            249,
+            "Shop".into(),
+            "https://shop.example".into(),
+            [2u8; 32],
+            [3u8; 32],
+        )
+        .unwrap();
+        let mut data = [0u8; MerchantProfile::LEN];
+        profile.write(&mut data);
+        check(&data, MerchantProfile::try_from_bytes);
+
+        let order = Order::new([1u8; 32], [2u8; 32], 4, 248, 2, 1_700_000_000).unwrap();
+        check(&order.to_bytes(), Order::try_from_bytes);
+
+        let stats = MerchantStats::new([1u8; 32], [2u8; 32], 247);
+        check(&stats.to_bytes(), MerchantStats::try_from_bytes);
+
+        let tree = PaymentTree::new([1u8; 32], 246);
+        check(&tree.to_bytes(), PaymentTree::try_from_bytes);
+
+        let approval =
+            RefundApproval::new([1u8; 32], 245, [2u8; 32], 100, 1_700_000_000, [3u8; 32]);
+        check(&approval.to_bytes(), RefundApproval::try_from_bytes);
+
+        let risk_state = RiskState::new([1u8; 32], [2u8; 32], 244);
+        check(&risk_state.to_bytes(), RiskState::try_from_bytes);
+
+        let batch = SettlementBatch::new([1u8; 32], [2u8; 32], 243, 1_700_000_000);
+        check(&batch.to_bytes(), SettlementBatch::try_from_bytes);
+
+        let buyer_index = BuyerIndex::new([1u8; 32], [2u8; 32], 242);
+        check(&buyer_index.to_bytes(), BuyerIndex::try_from_bytes);
+    }
+}
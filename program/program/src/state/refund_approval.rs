@@ -0,0 +1,185 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{constants::REFUND_APPROVAL_SEED, error::CommerceProgramError};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// A refund approved by the operator or merchant for the buyer to claim.
+///
+/// Created by ApproveRefund, which moves the payment to RefundApproved. ClaimRefund sends the
+/// amount from escrow to any token account the buyer owns for the mint and closes the
+/// account, refunding the rent to `rent_payer`.
+///
+/// Seeds: [b"refund_approval", payment pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct RefundApproval {
+    pub payment: Pubkey,
+
+    pub bump: u8,
+
+    /// Operator authority, delegate or merchant owner that approved the refund
+    pub approver: Pubkey,
+
+    /// Amount the buyer can claim
+    pub amount: u64,
+
+    /// Unix timestamp of the approval
+    pub approved_at: i64,
+
+    /// Account that paid the approval's rent, refunded when the refund is claimed
+    pub rent_payer: Pubkey,
+}
+
+impl Discriminator for RefundApproval {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::RefundApprovalDiscriminator as u8;
+}
+
+impl AccountSerialize for RefundApproval {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.payment.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(self.approver.as_ref());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.approved_at.to_le_bytes());
+        data.extend_from_slice(self.rent_payer.as_ref());
+        data
+    }
+}
+
+impl RefundApproval {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // payment
+        1 + // bump
+        32 + // approver
+        8 + // amount
+        8 + // approved_at
+        32; // rent_payer
+
+    pub fn new(
+        payment: Pubkey,
+        bump: u8,
+        approver: Pubkey,
+        amount: u64,
+        now: i64,
+        rent_payer: Pubkey,
+    ) -> Self {
+        Self {
+            payment,
+            bump,
+            approver,
+            amount,
+            approved_at: now,
+            rent_payer,
+        }
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[REFUND_APPROVAL_SEED, self.payment.as_ref()],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::RefundApprovalInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_payment(&self, payment: &Pubkey) -> Result<(), ProgramError> {
+        if self.payment.ne(payment) {
+            return Err(CommerceProgramError::RefundApprovalMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn validate_rent_payer(&self, rent_payer: &Pubkey) -> Result<(), ProgramError> {
+        if self.rent_payer.ne(rent_payer) {
+            return Err(CommerceProgramError::RentPayerMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            payment: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            approver: reader.read_pubkey()?,
+            amount: reader.read_u64()?,
+            approved_at: reader.read_i64()?,
+            rent_payer: reader.read_pubkey()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_refund_approval_serialization() {
+        let approval =
+            RefundApproval::new([1u8; 32], 254, [2u8; 32], 500, 1_700_000_000, [3u8; 32]);
+
+        let bytes = approval.to_bytes();
+        assert_eq!(bytes.len(), RefundApproval::LEN);
+
+        let deserialized = RefundApproval::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, approval);
+    }
+
+    #[test]
+    fn test_refund_approval_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; RefundApproval::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = RefundApproval::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_new() {
+        let approval = RefundApproval::new([1u8; 32], 255, [2u8; 32], 500, 100, [3u8; 32]);
+        assert_eq!(approval.approved_at, 100);
+        assert_eq!(approval.rent_payer, [3u8; 32]);
+    }
+
+    #[test]
+    fn test_validate_rent_payer() {
+        let approval = RefundApproval::new([1u8; 32], 255, [2u8; 32], 500, 0, [3u8; 32]);
+
+        assert!(approval.validate_rent_payer(&[3u8; 32]).is_ok());
+        assert_eq!(
+            approval.validate_rent_payer(&[2u8; 32]).unwrap_err(),
+            CommerceProgramError::RentPayerMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_validate_payment() {
+        let approval = RefundApproval::new([1u8; 32], 255, [2u8; 32], 500, 0, [3u8; 32]);
+
+        assert!(approval.validate_payment(&[1u8; 32]).is_ok());
+        assert_eq!(
+            approval.validate_payment(&[2u8; 32]).unwrap_err(),
+            CommerceProgramError::RefundApprovalMismatch.into()
+        );
+    }
+}
@@ -0,0 +1,263 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{
+    constants::{RISK_STATE_SEED, SECONDS_PER_DAY},
+    error::CommerceProgramError,
+};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// Daily payment counters for a config's RiskPolicy.
+///
+/// With `buyer` zeroed the account counts every payment under the config, for
+/// `max_daily_volume`; otherwise it counts one buyer's payments, for
+/// `max_payments_per_buyer_per_day`. The counters reset on the first payment of each UTC day.
+///
+/// Seeds: [b"risk_state", merchant_operator_config pubkey, buyer pubkey (zeroed for the config)]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct RiskState {
+    pub merchant_operator_config: Pubkey,
+
+    /// Buyer the counters are for, or zeroed for the whole config
+    pub buyer: Pubkey,
+
+    pub bump: u8,
+
+    /// Day the counters are for, in days since the unix epoch
+    pub day: i64,
+
+    /// Sum of payment amounts made on `day`
+    pub daily_volume: u64,
+
+    /// Number of payments made on `day`
+    pub daily_payment_count: u32,
+}
+
+impl Discriminator for RiskState {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::RiskStateDiscriminator as u8;
+}
+
+impl AccountSerialize for RiskState {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.buyer.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(&self.day.to_le_bytes());
+        data.extend_from_slice(&self.daily_volume.to_le_bytes());
+        data.extend_from_slice(&self.daily_payment_count.to_le_bytes());
+        data
+    }
+}
+
+impl RiskState {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant_operator_config
+        32 + // buyer
+        1 + // bump
+        8 + // day
+        8 + // daily_volume
+        4; // daily_payment_count
+
+    pub fn new(merchant_operator_config: Pubkey, buyer: Pubkey, bump: u8) -> Self {
+        Self {
+            merchant_operator_config,
+            buyer,
+            bump,
+            day: 0,
+            daily_volume: 0,
+            daily_payment_count: 0,
+        }
+    }
+
+    /// Whether the account counts every buyer's payments rather than a single buyer's.
+    pub fn is_config_wide(&self) -> bool {
+        self.buyer == Pubkey::default()
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[
+                RISK_STATE_SEED,
+                self.merchant_operator_config.as_ref(),
+                self.buyer.as_ref(),
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::RiskStateInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_merchant_operator_config(
+        &self,
+        merchant_operator_config: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.merchant_operator_config.ne(merchant_operator_config) {
+            return Err(CommerceProgramError::RiskStateMismatch.into());
+        }
+        Ok(())
+    }
+
+    /// Counts a payment of `amount` made at `now`, starting the counters over if it is the
+    /// first payment of a new day.
+    pub fn record_payment(&mut self, amount: u64, now: i64) -> Result<(), ProgramError> {
+        let day = now.div_euclid(SECONDS_PER_DAY);
+        if day != self.day {
+            self.day = day;
+            self.daily_volume = 0;
+            self.daily_payment_count = 0;
+        }
+
+        self.daily_volume = self
+            .daily_volume
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.daily_payment_count = self
+            .daily_payment_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Takes `amount` of a hold made at `created_at` back off the counters when it is released
+    /// rather than captured, and the payment itself too when `released_in_full`. A hold made
+    /// on an earlier day no longer counts, so it is left alone.
+    pub fn release_hold(&mut self, amount: u64, released_in_full: bool, created_at: i64) {
+        if created_at.div_euclid(SECONDS_PER_DAY) != self.day {
+            return;
+        }
+
+        self.daily_volume = self.daily_volume.saturating_sub(amount);
+        if released_in_full {
+            self.daily_payment_count = self.daily_payment_count.saturating_sub(1);
+        }
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            merchant_operator_config: reader.read_pubkey()?,
+            buyer: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            day: reader.read_i64()?,
+            daily_volume: reader.read_u64()?,
+            daily_payment_count: reader.read_u32()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const DAY: i64 = SECONDS_PER_DAY;
+
+    #[test]
+    fn test_risk_state_serialization() {
+        let mut risk_state = RiskState::new([1u8; 32], [2u8; 32], 254);
+        risk_state.record_payment(500, 20_000 * DAY).unwrap();
+
+        let bytes = risk_state.to_bytes();
+        assert_eq!(bytes.len(), RiskState::LEN);
+
+        let deserialized = RiskState::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, risk_state);
+    }
+
+    #[test]
+    fn test_risk_state_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; RiskState::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = RiskState::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_record_payment_resets_each_day() {
+        let mut risk_state = RiskState::new([1u8; 32], Pubkey::default(), 255);
+        assert!(risk_state.is_config_wide());
+
+        risk_state.record_payment(100, 20_000 * DAY).unwrap();
+        risk_state
+            .record_payment(50, 20_000 * DAY + DAY - 1)
+            .unwrap();
+        assert_eq!(risk_state.day, 20_000);
+        assert_eq!(risk_state.daily_volume, 150);
+        assert_eq!(risk_state.daily_payment_count, 2);
+
+        risk_state.record_payment(30, 20_001 * DAY).unwrap();
+        assert_eq!(risk_state.day, 20_001);
+        assert_eq!(risk_state.daily_volume, 30);
+        assert_eq!(risk_state.daily_payment_count, 1);
+    }
+
+    #[test]
+    fn test_record_payment_overflow() {
+        let mut risk_state = RiskState::new([1u8; 32], [2u8; 32], 255);
+        assert!(!risk_state.is_config_wide());
+
+        risk_state.record_payment(u64::MAX, DAY).unwrap();
+        assert_eq!(
+            risk_state.record_payment(1, DAY).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_release_hold() {
+        let mut risk_state = RiskState::new([1u8; 32], [2u8; 32], 255);
+        risk_state.record_payment(100, 20_000 * DAY).unwrap();
+        risk_state.record_payment(50, 20_000 * DAY + 10).unwrap();
+
+        // A partial capture releases the rest of the hold but still counts the payment
+        risk_state.release_hold(30, false, 20_000 * DAY);
+        assert_eq!(risk_state.daily_volume, 120);
+        assert_eq!(risk_state.daily_payment_count, 2);
+
+        // A full release takes the payment back off too
+        risk_state.release_hold(50, true, 20_000 * DAY + 10);
+        assert_eq!(risk_state.daily_volume, 70);
+        assert_eq!(risk_state.daily_payment_count, 1);
+
+        // A hold from an earlier day is no longer counted
+        risk_state.release_hold(70, true, 19_999 * DAY);
+        assert_eq!(risk_state.daily_volume, 70);
+        assert_eq!(risk_state.daily_payment_count, 1);
+    }
+
+    #[test]
+    fn test_validate_merchant_operator_config() {
+        let risk_state = RiskState::new([1u8; 32], [2u8; 32], 255);
+
+        assert!(risk_state
+            .validate_merchant_operator_config(&[1u8; 32])
+            .is_ok());
+        assert_eq!(
+            risk_state
+                .validate_merchant_operator_config(&[2u8; 32])
+                .unwrap_err(),
+            CommerceProgramError::RiskStateMismatch.into()
+        );
+    }
+}
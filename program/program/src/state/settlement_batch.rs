@@ -0,0 +1,303 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{
+    constants::SETTLEMENT_BATCH_SEED,
+    error::CommerceProgramError,
+    math::{elapsed_seconds, hours_to_seconds},
+    state::SettlementPolicy,
+};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// Merchant amounts cleared into a config's escrow but not yet paid out, in a single currency.
+///
+/// Optional: ClearPayment accumulates into it instead of transferring to the settlement
+/// wallet when it is passed as the last remaining account (before the memo program).
+/// ExecuteSettlementBatch pays out the whole pending amount once the config's
+/// SettlementPolicy is satisfied.
+///
+/// Seeds: [b"settlement_batch", merchant_operator_config pubkey, mint pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct SettlementBatch {
+    pub merchant_operator_config: Pubkey,
+
+    pub mint: Pubkey,
+
+    pub bump: u8,
+
+    /// Sum of merchant amounts cleared since the last settlement
+    pub pending_amount: u64,
+
+    /// Number of payments cleared since the last settlement
+    pub pending_count: u32,
+
+    /// Unix timestamp of the last settlement, or of initialization
+    pub last_settled_at: i64,
+}
+
+impl Discriminator for SettlementBatch {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::SettlementBatchDiscriminator as u8;
+}
+
+impl AccountSerialize for SettlementBatch {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.extend_from_slice(self.mint.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(&self.pending_amount.to_le_bytes());
+        data.extend_from_slice(&self.pending_count.to_le_bytes());
+        data.extend_from_slice(&self.last_settled_at.to_le_bytes());
+        data
+    }
+}
+
+impl SettlementBatch {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant_operator_config
+        32 + // mint
+        1 + // bump
+        8 + // pending_amount
+        4 + // pending_count
+        8; // last_settled_at
+
+    pub fn new(merchant_operator_config: Pubkey, mint: Pubkey, bump: u8, now: i64) -> Self {
+        Self {
+            merchant_operator_config,
+            mint,
+            bump,
+            pending_amount: 0,
+            pending_count: 0,
+            last_settled_at: now,
+        }
+    }
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[
+                SETTLEMENT_BATCH_SEED,
+                self.merchant_operator_config.as_ref(),
+                self.mint.as_ref(),
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::SettlementBatchInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_config_and_mint(
+        &self,
+        merchant_operator_config: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if self.merchant_operator_config.ne(merchant_operator_config) || self.mint.ne(mint) {
+            return Err(CommerceProgramError::SettlementBatchMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn record_clear(&mut self, merchant_amount: u64) -> Result<(), ProgramError> {
+        self.pending_amount = self
+            .pending_amount
+            .checked_add(merchant_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.pending_count = self.pending_count.saturating_add(1);
+        Ok(())
+    }
+
+    /// Validates the pending amount may be paid out under `settlement`, if any.
+    pub fn validate_ready(
+        &self,
+        settlement: Option<&SettlementPolicy>,
+        now: i64,
+    ) -> Result<(), ProgramError> {
+        if self.pending_amount == 0 {
+            return Err(CommerceProgramError::SettlementBatchEmpty.into());
+        }
+
+        let Some(settlement) = settlement else {
+            return Ok(());
+        };
+
+        // Check minimum settlement amount (0 means no limit)
+        if settlement.min_settlement_amount > 0
+            && self.pending_amount < settlement.min_settlement_amount
+        {
+            return Err(CommerceProgramError::InsufficientSettlementAmount.into());
+        }
+
+        // Check settlement frequency (0 means no time restriction)
+        let min_settlement_time = hours_to_seconds(settlement.settlement_frequency_hours);
+        if elapsed_seconds(self.last_settled_at, now) < min_settlement_time as i128 {
+            return Err(CommerceProgramError::SettlementTooEarly.into());
+        }
+
+        Ok(())
+    }
+
+    /// Resets the batch after paying out, returning the amount and count that were pending.
+    pub fn settle(&mut self, now: i64) -> (u64, u32) {
+        let settled = (self.pending_amount, self.pending_count);
+        self.pending_amount = 0;
+        self.pending_count = 0;
+        self.last_settled_at = now;
+        settled
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            merchant_operator_config: reader.read_pubkey()?,
+            mint: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            pending_amount: reader.read_u64()?,
+            pending_count: reader.read_u32()?,
+            last_settled_at: reader.read_i64()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SECONDS_PER_HOUR;
+    use alloc::vec;
+
+    fn settlement(min_settlement_amount: u64, settlement_frequency_hours: u32) -> SettlementPolicy {
+        SettlementPolicy {
+            min_settlement_amount,
+            settlement_frequency_hours,
+            auto_settle: false,
+        }
+    }
+
+    #[test]
+    fn test_settlement_batch_serialization() {
+        let mut batch = SettlementBatch::new([1u8; 32], [2u8; 32], 254, 1_700_000_000);
+        batch.record_clear(950).unwrap();
+
+        let bytes = batch.to_bytes();
+        assert_eq!(bytes.len(), SettlementBatch::LEN);
+
+        let deserialized = SettlementBatch::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, batch);
+    }
+
+    #[test]
+    fn test_settlement_batch_try_from_bytes_wrong_discriminator() {
+        let mut data = vec![0; SettlementBatch::LEN];
+        data[0] = 99; // Wrong discriminator
+
+        let result = SettlementBatch::try_from_bytes(&data);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_record_clear_and_settle() {
+        let mut batch = SettlementBatch::new([1u8; 32], [2u8; 32], 255, 100);
+        batch.record_clear(1_000).unwrap();
+        batch.record_clear(2_000).unwrap();
+        assert_eq!(batch.pending_amount, 3_000);
+        assert_eq!(batch.pending_count, 2);
+
+        assert_eq!(batch.settle(200), (3_000, 2));
+        assert_eq!(batch.pending_amount, 0);
+        assert_eq!(batch.pending_count, 0);
+        assert_eq!(batch.last_settled_at, 200);
+
+        batch.pending_amount = u64::MAX;
+        assert_eq!(
+            batch.record_clear(1).unwrap_err(),
+            ProgramError::ArithmeticOverflow
+        );
+    }
+
+    #[test]
+    fn test_validate_ready() {
+        let mut batch = SettlementBatch::new([1u8; 32], [2u8; 32], 255, 0);
+        assert_eq!(
+            batch.validate_ready(None, 0).unwrap_err(),
+            CommerceProgramError::SettlementBatchEmpty.into()
+        );
+
+        batch.record_clear(500).unwrap();
+        assert!(batch.validate_ready(None, 0).is_ok());
+        assert!(batch.validate_ready(Some(&settlement(0, 0)), 0).is_ok());
+
+        assert_eq!(
+            batch
+                .validate_ready(Some(&settlement(1_000, 0)), 0)
+                .unwrap_err(),
+            CommerceProgramError::InsufficientSettlementAmount.into()
+        );
+
+        let daily = settlement(500, 24);
+        assert_eq!(
+            batch
+                .validate_ready(Some(&daily), 24 * SECONDS_PER_HOUR - 1)
+                .unwrap_err(),
+            CommerceProgramError::SettlementTooEarly.into()
+        );
+        assert!(batch
+            .validate_ready(Some(&daily), 24 * SECONDS_PER_HOUR)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_validate_ready_extreme_times() {
+        let mut batch = SettlementBatch::new([1u8; 32], [2u8; 32], 255, i64::MAX - 10);
+        batch.record_clear(500).unwrap();
+
+        // A window ending past i64::MAX stays closed instead of wrapping open
+        assert_eq!(
+            batch
+                .validate_ready(Some(&settlement(0, 1)), i64::MAX)
+                .unwrap_err(),
+            CommerceProgramError::SettlementTooEarly.into()
+        );
+
+        batch.last_settled_at = i64::MIN;
+        assert!(batch
+            .validate_ready(Some(&settlement(0, u32::MAX)), 0)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_validate_config_and_mint() {
+        let batch = SettlementBatch::new([1u8; 32], [2u8; 32], 255, 0);
+
+        assert!(batch
+            .validate_config_and_mint(&[1u8; 32], &[2u8; 32])
+            .is_ok());
+        assert_eq!(
+            batch
+                .validate_config_and_mint(&[1u8; 32], &[3u8; 32])
+                .unwrap_err(),
+            CommerceProgramError::SettlementBatchMismatch.into()
+        );
+        assert_eq!(
+            batch
+                .validate_config_and_mint(&[3u8; 32], &[2u8; 32])
+                .unwrap_err(),
+            CommerceProgramError::SettlementBatchMismatch.into()
+        );
+    }
+}
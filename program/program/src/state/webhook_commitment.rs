@@ -0,0 +1,126 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use shank::ShankAccount;
+
+use crate::ID as COMMERCE_PROGRAM_ID;
+use crate::{constants::WEBHOOK_COMMITMENT_SEED, error::CommerceProgramError};
+
+use super::{
+    discriminator::{AccountSerialize, CommerceAccountDiscriminators, Discriminator},
+    ByteReader,
+};
+
+/// A merchant's commitment to where a config's payment webhooks are delivered.
+///
+/// Set by SetWebhookCommitment. Only the commitment is stored, never the endpoint itself:
+/// an indexer keeps its delivery targets in its own store and delivers a config's webhooks to
+/// an endpoint only while the endpoint's commitment matches this one, so a target changed in
+/// that store without the merchant's signature receives nothing. Not read by any payment
+/// instruction.
+///
+/// Seeds: [b"webhook_commitment", merchant_operator_config pubkey]
+#[derive(Clone, Debug, PartialEq, ShankAccount)]
+#[repr(C)]
+pub struct WebhookCommitment {
+    pub merchant_operator_config: Pubkey,
+
+    pub bump: u8,
+
+    /// SHA-256 of the endpoint, as the indexer delivering the webhooks computes it
+    pub commitment: [u8; 32],
+
+    /// Unix timestamp of the last SetWebhookCommitment
+    pub updated_at: i64,
+}
+
+impl Discriminator for WebhookCommitment {
+    const DISCRIMINATOR: u8 = CommerceAccountDiscriminators::WebhookCommitmentDiscriminator as u8;
+}
+
+impl AccountSerialize for WebhookCommitment {
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN - 1);
+        data.extend_from_slice(self.merchant_operator_config.as_ref());
+        data.push(self.bump);
+        data.extend_from_slice(&self.commitment);
+        data.extend_from_slice(&self.updated_at.to_le_bytes());
+        data
+    }
+}
+
+impl WebhookCommitment {
+    pub const LEN: usize = 1 + // discriminator
+        32 + // merchant_operator_config
+        1 + // bump
+        32 + // commitment
+        8; // updated_at
+
+    pub fn validate_pda(&self, account_info_key: &Pubkey) -> Result<(), ProgramError> {
+        let (pda, bump) = find_program_address(
+            &[
+                WEBHOOK_COMMITMENT_SEED,
+                self.merchant_operator_config.as_ref(),
+            ],
+            &COMMERCE_PROGRAM_ID,
+        );
+
+        if pda.ne(account_info_key) || bump != self.bump {
+            return Err(CommerceProgramError::WebhookCommitmentInvalidPda.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = ByteReader::new(data);
+        reader.read_discriminator(Self::DISCRIMINATOR)?;
+
+        Ok(Self {
+            merchant_operator_config: reader.read_pubkey()?,
+            bump: reader.read_u8()?,
+            commitment: reader.read_array()?,
+            updated_at: reader.read_i64()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_webhook_commitment_serialization() {
+        let webhook_commitment = WebhookCommitment {
+            merchant_operator_config: [1u8; 32],
+            bump: 254,
+            commitment: [2u8; 32],
+            updated_at: 1_700_000_000,
+        };
+
+        let bytes = webhook_commitment.to_bytes();
+        assert_eq!(bytes.len(), WebhookCommitment::LEN);
+        assert_eq!(bytes[0], WebhookCommitment::DISCRIMINATOR);
+
+        let deserialized = WebhookCommitment::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, webhook_commitment);
+    }
+
+    #[test]
+    fn test_webhook_commitment_try_from_bytes_invalid() {
+        let mut data = vec![0; WebhookCommitment::LEN];
+        data[0] = 99; // Wrong discriminator
+        assert_eq!(
+            WebhookCommitment::try_from_bytes(&data).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+
+        data[0] = WebhookCommitment::DISCRIMINATOR;
+        assert!(WebhookCommitment::try_from_bytes(&data[..WebhookCommitment::LEN - 1]).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+/// Fixed-capacity byte buffer on the stack, for serializing data of a known maximum size
+/// without a heap allocation.
+///
+/// Writing past `N` bytes is a bug in the caller's size and panics.
+pub struct ByteWriter<const N: usize> {
+    data: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> ByteWriter<N> {
+    pub fn new() -> Self {
+        Self {
+            data: [0u8; N],
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        self.data[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+    }
+
+    /// The bytes written so far.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// The buffer, with any unwritten bytes left zeroed.
+    pub fn into_array(self) -> [u8; N] {
+        self.data
+    }
+}
+
+impl<const N: usize> Default for ByteWriter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writes_in_order() {
+        let mut writer = ByteWriter::<16>::new();
+        writer.push(7);
+        writer.extend_from_slice(&70_000u32.to_le_bytes());
+        writer.extend_from_slice(&[9u8; 3]);
+
+        assert_eq!(writer.as_slice(), &[7, 0x70, 0x11, 0x01, 0x00, 9, 9, 9]);
+        assert_eq!(
+            writer.into_array(),
+            [7, 0x70, 0x11, 0x01, 0x00, 9, 9, 9, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_write_past_capacity_panics() {
+        let mut writer = ByteWriter::<4>::new();
+        writer.extend_from_slice(&[1u8; 5]);
+    }
+}
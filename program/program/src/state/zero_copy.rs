@@ -0,0 +1,46 @@
+use bytemuck::Pod;
+use pinocchio::program_error::ProgramError;
+
+use super::discriminator::Discriminator;
+
+/// In-place view of an account's fixed-size layout, discriminator included.
+///
+/// Views are `#[repr(C)]` structs of byte-aligned fields, with integers stored as
+/// little-endian byte arrays, so they are borrowed straight from account data instead of
+/// being parsed field by field.
+pub trait ZeroCopy: Pod + Discriminator {
+    const LEN: usize = core::mem::size_of::<Self>();
+
+    fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        let data = data
+            .get(..Self::LEN)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if data[0] != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        bytemuck::try_from_bytes(data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        let data = data
+            .get_mut(..Self::LEN)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if data[0] != Self::DISCRIMINATOR {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        bytemuck::try_from_bytes_mut(data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Writes the view over the start of `data`, which may be freshly allocated.
+    fn store(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        write_prefix(bytemuck::bytes_of(self), data)
+    }
+}
+
+/// Copies `bytes` over the start of `data`.
+pub fn write_prefix(bytes: &[u8], data: &mut [u8]) -> Result<(), ProgramError> {
+    data.get_mut(..bytes.len())
+        .ok_or(ProgramError::AccountDataTooSmall)?
+        .copy_from_slice(bytes);
+    Ok(())
+}
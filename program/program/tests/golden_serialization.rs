@@ -0,0 +1,853 @@
+//! Golden fixtures for every account and event layout.
+//!
+//! Each account and event type is serialized with fixed, field-distinct values and compared
+//! byte-for-byte against `tests/fixtures/<accounts|events>/<name>.bin`. Deployed accounts
+//! and indexers depend on these layouts, so a mismatch means a field was added, removed,
+//! reordered or re-encoded. Accounts must also deserialize back to the same value.
+//!
+//! After an intentional layout change, regenerate the fixtures with:
+//! `UPDATE_GOLDEN=1 cargo test -p commerce-program --test golden_serialization`
+
+use std::{fs, path::PathBuf};
+
+use commerce_program::{
+    events::*,
+    state::{
+        AccountSerialize, AnnotationFlag, BuyerIndex, BuyerIndexPolicy, CallbackPolicy,
+        ChargebackPolicy, CompliancePolicy, ConfigRegistryPage, ConversionPolicy, ConversionRate,
+        DenylistPolicy, DirectSettlementPolicy, FeeOverride, FeeOverridePolicy, FeeTier,
+        FeeTierPolicy, FeeType, MemoPolicy, Merchant, MerchantOperatorConfig, MerchantProfile,
+        MerchantStats, Operator, OperatorDelegate, OperatorFailoverPolicy, OperatorFreezeList,
+        Order, OrderStatus, Payment, PaymentAnnotation, PaymentNote, PaymentTree,
+        PlatformFeePolicy, PolicyData, Quote, RefundApproval, RefundConversionPolicy, RefundPolicy,
+        RentRebatePolicy, RiskPolicy, RiskState, SettlementBatch, SettlementDiscountPolicy,
+        SettlementPolicy, SplitSettlementPolicy, Status, SwapAdapter, WebhookCommitment,
+    },
+};
+use pinocchio::pubkey::Pubkey;
+
+fn key(n: u8) -> Pubkey {
+    [n; 32]
+}
+
+fn assert_golden(kind: &str, name: &str, actual: &[u8]) {
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "tests",
+        "fixtures",
+        kind,
+        &format!("{name}.bin"),
+    ]
+    .iter()
+    .collect();
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected =
+        fs::read(&path).unwrap_or_else(|_| panic!("Missing golden fixture {}", path.display()));
+    assert_eq!(
+        actual,
+        &expected[..],
+        "{kind}/{name} no longer matches its golden fixture; rerun with UPDATE_GOLDEN=1 if intended"
+    );
+}
+
+#[test]
+fn test_merchant_golden() {
+    let merchant = Merchant {
+        owner: key(1),
+        bump: 254,
+        settlement_wallet: key(2),
+        pending_authority: key(3),
+        settlement_wallet_timelock_seconds: 86_400,
+        pending_settlement_wallet: key(4),
+        pending_settlement_wallet_unlocks_at: 1_700_086_400,
+        original_owner: key(1),
+    };
+    let data = merchant.to_bytes();
+    assert_golden("accounts", "merchant", &data);
+    assert_eq!(Merchant::try_from_bytes(&data).unwrap(), merchant);
+}
+
+#[test]
+fn test_operator_golden() {
+    let operator = Operator {
+        owner: key(1),
+        bump: 253,
+        pending_authority: key(2),
+        last_active_at: 1_700_000_000,
+        has_freeze_list: true,
+        original_owner: key(1),
+    };
+    let data = operator.to_bytes();
+    assert_golden("accounts", "operator", &data);
+    assert_eq!(Operator::try_from_bytes(&data).unwrap(), operator);
+}
+
+#[test]
+fn test_merchant_operator_config_golden() {
+    // One policy of every type, so each policy layout is covered
+    let policies = vec![
+        PolicyData::Refund(RefundPolicy {
+            max_amount: 5_000_000,
+            max_time_after_purchase: 604_800,
+        }),
+        PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 100,
+            settlement_frequency_hours: 24,
+            auto_settle: true,
+        }),
+        PolicyData::FeeTier(FeeTierPolicy {
+            num_tiers: 2,
+            tiers: [
+                FeeTier {
+                    threshold: 0,
+                    fee: 300,
+                    fee_type: FeeType::Bps,
+                },
+                FeeTier {
+                    threshold: 1_000_000,
+                    fee: 5_000,
+                    fee_type: FeeType::Fixed,
+                },
+                FeeTier {
+                    threshold: 0,
+                    fee: 0,
+                    fee_type: FeeType::Bps,
+                },
+                FeeTier {
+                    threshold: 0,
+                    fee: 0,
+                    fee_type: FeeType::Bps,
+                },
+                FeeTier {
+                    threshold: 0,
+                    fee: 0,
+                    fee_type: FeeType::Bps,
+                },
+            ],
+        }),
+        PolicyData::Memo(MemoPolicy {
+            memo_on_clear: true,
+            memo_on_refund: false,
+        }),
+        PolicyData::Conversion(ConversionPolicy {
+            settlement_mint: key(10),
+            swap_adapter: SwapAdapter::JupiterV6,
+            max_slippage_bps: 50,
+        }),
+        PolicyData::Chargeback(ChargebackPolicy {
+            window_seconds: 172_800,
+            arbiter: key(11),
+        }),
+        PolicyData::FeeOverride(FeeOverridePolicy {
+            max_fee: 1_000,
+            max_fee_type: FeeType::Bps,
+        }),
+        PolicyData::Denylist(DenylistPolicy {
+            root: [12; 32],
+            depth: 4,
+        }),
+        PolicyData::SplitSettlement(SplitSettlementPolicy {
+            destinations_hash: [13; 32],
+            num_destinations: 2,
+            shares_bps: [9_000, 1_000, 0, 0, 0],
+        }),
+        PolicyData::Risk(RiskPolicy {
+            max_payment_amount: 10_000_000,
+            max_daily_volume: 100_000_000,
+            max_payments_per_buyer_per_day: 5,
+        }),
+        PolicyData::BuyerIndex(BuyerIndexPolicy { required: true }),
+        PolicyData::Callback(CallbackPolicy {
+            program: key(14),
+            on_clear: true,
+            on_refund: true,
+        }),
+        PolicyData::PlatformFee(PlatformFeePolicy {
+            platform: key(15),
+            fee: 150,
+            fee_type: FeeType::Bps,
+        }),
+        PolicyData::Compliance(CompliancePolicy { program: key(16) }),
+        PolicyData::SettlementDiscount(SettlementDiscountPolicy {
+            discount_bps_per_day: 25,
+            max_discount_bps: 1_000,
+        }),
+        PolicyData::RefundConversion(RefundConversionPolicy {
+            max_rate_age_seconds: 3_600,
+        }),
+        PolicyData::OperatorFailover(OperatorFailoverPolicy {
+            inactivity_threshold_seconds: 604_800,
+        }),
+        PolicyData::RentRebate(RentRebatePolicy {
+            buyer_share_bps: 2_500,
+        }),
+        PolicyData::DirectSettlement(DirectSettlementPolicy {
+            max_amount: 50_000_000,
+        }),
+    ];
+    let currencies = vec![key(20), key(21)];
+    let mut config = MerchantOperatorConfig {
+        version: 3,
+        bump: 252,
+        merchant: key(1),
+        operator: key(2),
+        operator_fee: 250,
+        fee_type: FeeType::Bps,
+        current_order_id: 41,
+        days_to_close: 30,
+        num_policies: policies.len() as u32,
+        num_accepted_currencies: currencies.len() as u32,
+        config_hash: [0; 32],
+        decommissioned_at: 1_700_000_000,
+        payment_accounts: 3,
+    };
+    config.config_hash = config.compute_config_hash(&policies, &currencies);
+
+    let data = config.to_bytes(&policies, &currencies);
+    assert_golden("accounts", "merchant_operator_config", &data);
+    assert_eq!(
+        MerchantOperatorConfig::try_from_bytes(&data).unwrap(),
+        (config, policies, currencies)
+    );
+}
+
+#[test]
+fn test_payment_golden() {
+    let payment = Payment {
+        order_id: 7,
+        amount: 1_500_000,
+        created_at: 1_700_000_000,
+        status: Status::Cleared,
+        bump: 251,
+        idempotency_key: [5; 16],
+        has_fee_override: true,
+        fee_override: FeeOverride {
+            fee: 125,
+            fee_type: FeeType::Fixed,
+        },
+        references: [key(1), key(2)],
+        rent_payer: key(3),
+        has_order: false,
+    };
+    let data = payment.to_bytes();
+    assert_golden("accounts", "payment", &data);
+    assert_eq!(Payment::try_from_bytes(&data).unwrap(), payment);
+}
+
+#[test]
+fn test_payment_tree_golden() {
+    let mut filled_subtrees = [0u8; 640];
+    for (index, byte) in filled_subtrees.iter_mut().enumerate() {
+        *byte = index as u8;
+    }
+    let tree = PaymentTree {
+        merchant_operator_config: key(1),
+        bump: 250,
+        num_leaves: 3,
+        root: [2; 32],
+        filled_subtrees,
+    };
+    let data = tree.to_bytes();
+    assert_golden("accounts", "payment_tree", &data);
+    assert_eq!(PaymentTree::try_from_bytes(&data).unwrap(), tree);
+}
+
+#[test]
+fn test_merchant_stats_golden() {
+    let stats = MerchantStats {
+        merchant: key(1),
+        mint: key(2),
+        bump: 249,
+        payment_count: 10,
+        total_volume: 20_000_000,
+        total_cleared: 15_000_000,
+        total_fees_paid: 375_000,
+        refund_count: 2,
+        total_refunded: 3_000_000,
+        closed_count: 4,
+    };
+    let data = stats.to_bytes();
+    assert_golden("accounts", "merchant_stats", &data);
+    assert_eq!(MerchantStats::try_from_bytes(&data).unwrap(), stats);
+}
+
+#[test]
+fn test_operator_delegate_golden() {
+    let delegate = OperatorDelegate {
+        operator: key(1),
+        delegate: key(2),
+        bump: 248,
+        can_make_payment: true,
+        can_clear: false,
+        can_refund: true,
+        can_close: false,
+        max_refund_amount: 1_000_000,
+        max_daily_refund_amount: 5_000_000,
+        refund_day: 19_675,
+        daily_refund_amount: 700_000,
+    };
+    let data = delegate.to_bytes();
+    assert_golden("accounts", "operator_delegate", &data);
+    assert_eq!(OperatorDelegate::try_from_bytes(&data).unwrap(), delegate);
+}
+
+#[test]
+fn test_settlement_batch_golden() {
+    let batch = SettlementBatch {
+        merchant_operator_config: key(1),
+        mint: key(2),
+        bump: 247,
+        pending_amount: 9_500_000,
+        pending_count: 6,
+        last_settled_at: 1_700_000_000,
+    };
+    let data = batch.to_bytes();
+    assert_golden("accounts", "settlement_batch", &data);
+    assert_eq!(SettlementBatch::try_from_bytes(&data).unwrap(), batch);
+}
+
+#[test]
+fn test_order_golden() {
+    let order = Order {
+        merchant_operator_config: key(1),
+        buyer: key(2),
+        order_id: 9,
+        bump: 246,
+        expected_payments: 3,
+        payment_count: 3,
+        cleared_count: 1,
+        refunded_count: 1,
+        status: OrderStatus::Paid,
+        created_at: 1_700_000_000,
+    };
+    let data = order.to_bytes();
+    assert_golden("accounts", "order", &data);
+    assert_eq!(Order::try_from_bytes(&data).unwrap(), order);
+}
+
+#[test]
+fn test_merchant_profile_golden() {
+    let profile = MerchantProfile {
+        merchant: key(1),
+        bump: 245,
+        logo_hash: [2; 32],
+        contact_hash: [3; 32],
+        name: "Coffee Shop".into(),
+        uri: "https://example.com/coffee".into(),
+    };
+    // Stored at its full length with a zeroed tail, so updates never resize it
+    let mut data = vec![0xff; MerchantProfile::LEN];
+    profile.write(&mut data);
+    assert_golden("accounts", "merchant_profile", &data);
+    assert_eq!(MerchantProfile::try_from_bytes(&data).unwrap(), profile);
+}
+
+#[test]
+fn test_refund_approval_golden() {
+    let approval = RefundApproval {
+        payment: key(1),
+        bump: 244,
+        approver: key(2),
+        amount: 1_500_000,
+        approved_at: 1_700_000_000,
+        rent_payer: key(3),
+    };
+    let data = approval.to_bytes();
+    assert_golden("accounts", "refund_approval", &data);
+    assert_eq!(RefundApproval::try_from_bytes(&data).unwrap(), approval);
+}
+
+#[test]
+fn test_risk_state_golden() {
+    let risk_state = RiskState {
+        merchant_operator_config: key(1),
+        buyer: key(2),
+        bump: 243,
+        day: 19_675,
+        daily_volume: 4_000_000,
+        daily_payment_count: 3,
+    };
+    let data = risk_state.to_bytes();
+    assert_golden("accounts", "risk_state", &data);
+    assert_eq!(RiskState::try_from_bytes(&data).unwrap(), risk_state);
+}
+
+#[test]
+fn test_buyer_index_golden() {
+    let buyer_index = BuyerIndex {
+        merchant_operator_config: key(1),
+        buyer: key(2),
+        bump: 242,
+        payment_count: 10,
+        recent_order_ids: [10, 9, 8, 7, 6, 5, 4, 3],
+    };
+    let data = buyer_index.to_bytes();
+    assert_golden("accounts", "buyer_index", &data);
+    assert_eq!(BuyerIndex::try_from_bytes(&data).unwrap(), buyer_index);
+}
+
+#[test]
+fn test_quote_golden() {
+    let quote = Quote {
+        merchant_operator_config: key(1),
+        mint: key(2),
+        order_id: 7,
+        bump: 241,
+        amount: 1_500_000,
+        expires_at: 1_700_000_600,
+        created_at: 1_700_000_000,
+        quoter: key(3),
+    };
+    let data = quote.to_bytes();
+    assert_golden("accounts", "quote", &data);
+    assert_eq!(Quote::try_from_bytes(&data).unwrap(), quote);
+}
+
+#[test]
+fn test_conversion_rate_golden() {
+    let conversion_rate = ConversionRate {
+        merchant_operator_config: key(1),
+        from_mint: key(2),
+        to_mint: key(3),
+        bump: 238,
+        rate_numerator: 98,
+        rate_denominator: 100,
+        posted_at: 1_700_000_000,
+        attester: key(4),
+    };
+    let data = conversion_rate.to_bytes();
+    assert_golden("accounts", "conversion_rate", &data);
+    assert_eq!(
+        ConversionRate::try_from_bytes(&data).unwrap(),
+        conversion_rate
+    );
+}
+
+#[test]
+fn test_payment_note_golden() {
+    let payment_note = PaymentNote {
+        payment: key(1),
+        merchant: key(2),
+        bump: 251,
+        rent_payer: key(3),
+        ciphertext: [0xab; 128],
+    };
+    let data = payment_note.to_bytes();
+    assert_golden("accounts", "payment_note", &data);
+    assert_eq!(PaymentNote::try_from_bytes(&data).unwrap(), payment_note);
+}
+
+#[test]
+fn test_webhook_commitment_golden() {
+    let webhook_commitment = WebhookCommitment {
+        merchant_operator_config: key(1),
+        bump: 237,
+        commitment: [0xcd; 32],
+        updated_at: 1_700_000_000,
+    };
+    let data = webhook_commitment.to_bytes();
+    assert_golden("accounts", "webhook_commitment", &data);
+    assert_eq!(
+        WebhookCommitment::try_from_bytes(&data).unwrap(),
+        webhook_commitment
+    );
+}
+
+#[test]
+fn test_payment_annotation_golden() {
+    let payment_annotation = PaymentAnnotation {
+        payment: key(1),
+        merchant_operator_config: key(2),
+        bump: 237,
+        flag: AnnotationFlag::FlaggedFraud,
+        note_hash: [0xcd; 32],
+        annotated_by: key(3),
+        updated_at: 1_700_000_000,
+    };
+    let data = payment_annotation.to_bytes();
+    assert_golden("accounts", "payment_annotation", &data);
+    assert_eq!(
+        PaymentAnnotation::try_from_bytes(&data).unwrap(),
+        payment_annotation
+    );
+}
+
+#[test]
+fn test_config_registry_page_golden() {
+    let mut page = ConfigRegistryPage::new(key(1), 2, 240);
+    page.push(key(2), 1).unwrap();
+    page.push(key(3), 4).unwrap();
+    let data = page.to_bytes();
+    assert_golden("accounts", "config_registry_page", &data);
+    assert_eq!(ConfigRegistryPage::try_from_bytes(&data).unwrap(), page);
+}
+
+#[test]
+fn test_operator_freeze_list_golden() {
+    let mut freeze_list = OperatorFreezeList::new(key(1), 239);
+    freeze_list.add(key(2)).unwrap();
+    freeze_list.add(key(3)).unwrap();
+    let data = freeze_list.to_bytes();
+    assert_golden("accounts", "operator_freeze_list", &data);
+    assert_eq!(
+        OperatorFreezeList::try_from_bytes(&data).unwrap(),
+        freeze_list
+    );
+}
+
+#[test]
+fn test_payment_created_event_golden() {
+    let event = PaymentCreatedEvent {
+        discriminator: EventDiscriminators::PaymentCreated as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        amount: 1_500_000,
+        order_id: 7,
+        idempotency_key: [4; 16],
+    };
+    assert_golden("events", "payment_created", &event.to_bytes());
+}
+
+#[test]
+fn test_payment_cleared_event_golden() {
+    let event = PaymentClearedEvent {
+        discriminator: EventDiscriminators::PaymentCleared as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        amount: 1_500_000,
+        operator_fee: 37_500,
+        order_id: 7,
+        idempotency_key: [4; 16],
+    };
+    assert_golden("events", "payment_cleared", &event.to_bytes());
+}
+
+#[test]
+fn test_payment_refunded_event_golden() {
+    let event = PaymentRefundedEvent {
+        discriminator: EventDiscriminators::PaymentRefunded as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        amount: 1_500_000,
+        order_id: 7,
+        idempotency_key: [4; 16],
+    };
+    assert_golden("events", "payment_refunded", &event.to_bytes());
+}
+
+#[test]
+fn test_compressed_payment_event_golden() {
+    let event = CompressedPaymentEvent {
+        discriminator: EventDiscriminators::CompressedPayment as u8,
+        payment_tree: key(1),
+        leaf_index: 12,
+        buyer: key(2),
+        merchant: key(3),
+        operator: key(4),
+        mint: key(5),
+        amount: 1_500_000,
+        operator_fee: 37_500,
+        order_id: 7,
+        created_at: 1_700_000_000,
+        status: Status::Cleared as u8,
+    };
+    assert_golden("events", "compressed_payment", &event.to_bytes());
+}
+
+#[test]
+fn test_payment_chargebacked_event_golden() {
+    let event = PaymentChargebackedEvent {
+        discriminator: EventDiscriminators::PaymentChargebacked as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        amount: 1_500_000,
+        order_id: 7,
+        idempotency_key: [4; 16],
+    };
+    assert_golden("events", "payment_chargebacked", &event.to_bytes());
+}
+
+#[test]
+fn test_payment_closed_event_golden() {
+    let event = PaymentClosedEvent {
+        discriminator: EventDiscriminators::PaymentClosed as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        order_id: 7,
+        rent_refunded_to: key(4),
+    };
+    assert_golden("events", "payment_closed", &event.to_bytes());
+}
+
+#[test]
+fn test_settlement_batch_executed_event_golden() {
+    let event = SettlementBatchExecutedEvent {
+        discriminator: EventDiscriminators::SettlementBatchExecuted as u8,
+        merchant: key(1),
+        merchant_operator_config: key(2),
+        mint: key(3),
+        amount: 9_500_000,
+        payment_count: 6,
+    };
+    assert_golden("events", "settlement_batch_executed", &event.to_bytes());
+}
+
+#[test]
+fn test_order_created_event_golden() {
+    let event = OrderCreatedEvent {
+        discriminator: EventDiscriminators::OrderCreated as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        order_id: 9,
+        expected_payments: 3,
+    };
+    assert_golden("events", "order_created", &event.to_bytes());
+}
+
+#[test]
+fn test_order_status_changed_event_golden() {
+    let event = OrderStatusChangedEvent {
+        discriminator: EventDiscriminators::OrderStatusChanged as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        order_id: 9,
+        status: OrderStatus::Cleared,
+    };
+    assert_golden("events", "order_status_changed", &event.to_bytes());
+}
+
+#[test]
+fn test_refund_approved_event_golden() {
+    let event = RefundApprovedEvent {
+        discriminator: EventDiscriminators::RefundApproved as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        approver: key(4),
+        amount: 1_500_000,
+        order_id: 7,
+        idempotency_key: [5; 16],
+    };
+    assert_golden("events", "refund_approved", &event.to_bytes());
+}
+
+#[test]
+fn test_payment_status_changed_event_golden() {
+    let event = PaymentStatusChangedEvent {
+        discriminator: EventDiscriminators::PaymentStatusChanged as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        order_id: 7,
+        from: PaymentLifecycleStatus::Paid,
+        to: PaymentLifecycleStatus::Closed,
+        actor: key(4),
+    };
+    assert_golden("events", "payment_status_changed", &event.to_bytes());
+}
+
+#[test]
+fn test_merchant_operator_config_initialized_event_golden() {
+    let event = MerchantOperatorConfigInitializedEvent {
+        discriminator: EventDiscriminators::MerchantOperatorConfigInitialized as u8,
+        config: key(1),
+        merchant: key(2),
+        operator: key(3),
+        version: 3,
+        config_hash: [4; 32],
+    };
+    assert_golden(
+        "events",
+        "merchant_operator_config_initialized",
+        &event.to_bytes(),
+    );
+}
+
+#[test]
+fn test_platform_fee_collected_event_golden() {
+    let event = PlatformFeeCollectedEvent {
+        discriminator: EventDiscriminators::PlatformFeeCollected as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        platform: key(4),
+        order_id: 7,
+        amount: 15_000,
+    };
+    assert_golden("events", "platform_fee_collected", &event.to_bytes());
+}
+
+#[test]
+fn test_payment_captured_event_golden() {
+    let event = PaymentCapturedEvent {
+        discriminator: EventDiscriminators::PaymentCaptured as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        amount: 750_000,
+        released_amount: 250_000,
+        order_id: 7,
+        idempotency_key: [8; 16],
+    };
+    assert_golden("events", "payment_captured", &event.to_bytes());
+}
+
+#[test]
+fn test_merchant_initialized_event_golden() {
+    let event = MerchantInitializedEvent {
+        discriminator: EventDiscriminators::MerchantInitialized as u8,
+        merchant: key(1),
+        authority: key(2),
+        settlement_wallet: key(3),
+        settlement_wallet_timelock_seconds: 86_400,
+    };
+    assert_golden("events", "merchant_initialized", &event.to_bytes());
+}
+
+#[test]
+fn test_merchant_settlement_wallet_updated_event_golden() {
+    let event = MerchantSettlementWalletUpdatedEvent {
+        discriminator: EventDiscriminators::MerchantSettlementWalletUpdated as u8,
+        merchant: key(1),
+        old_settlement_wallet: key(2),
+        new_settlement_wallet: key(3),
+    };
+    assert_golden(
+        "events",
+        "merchant_settlement_wallet_updated",
+        &event.to_bytes(),
+    );
+}
+
+#[test]
+fn test_settlement_wallet_proposed_event_golden() {
+    let event = SettlementWalletProposedEvent {
+        discriminator: EventDiscriminators::SettlementWalletProposed as u8,
+        merchant: key(1),
+        settlement_wallet: key(2),
+        pending_settlement_wallet: key(3),
+        unlocks_at: 1_700_086_400,
+    };
+    assert_golden("events", "settlement_wallet_proposed", &event.to_bytes());
+}
+
+#[test]
+fn test_settlement_wallet_timelock_raised_event_golden() {
+    let event = SettlementWalletTimelockRaisedEvent {
+        discriminator: EventDiscriminators::SettlementWalletTimelockRaised as u8,
+        merchant: key(1),
+        old_timelock_seconds: 0,
+        new_timelock_seconds: 86_400,
+        pending_settlement_wallet_unlocks_at: 1_700_086_400,
+    };
+    assert_golden(
+        "events",
+        "settlement_wallet_timelock_raised",
+        &event.to_bytes(),
+    );
+}
+
+#[test]
+fn test_merchant_authority_updated_event_golden() {
+    let event = MerchantAuthorityUpdatedEvent {
+        discriminator: EventDiscriminators::MerchantAuthorityUpdated as u8,
+        merchant: key(1),
+        old_authority: key(2),
+        new_authority: key(3),
+    };
+    assert_golden("events", "merchant_authority_updated", &event.to_bytes());
+}
+
+#[test]
+fn test_operator_authority_updated_event_golden() {
+    let event = OperatorAuthorityUpdatedEvent {
+        discriminator: EventDiscriminators::OperatorAuthorityUpdated as u8,
+        operator: key(1),
+        old_authority: key(2),
+        new_authority: key(3),
+    };
+    assert_golden("events", "operator_authority_updated", &event.to_bytes());
+}
+
+#[test]
+fn test_payment_migrated_event_golden() {
+    let event = PaymentMigratedEvent {
+        discriminator: EventDiscriminators::PaymentMigrated as u8,
+        buyer: key(1),
+        merchant: key(2),
+        operator: key(3),
+        order_id: 42,
+        amount: 1_000_000,
+        old_payment: key(4),
+        new_payment: key(5),
+        old_merchant_operator_config: key(6),
+        new_merchant_operator_config: key(7),
+    };
+    assert_golden("events", "payment_migrated", &event.to_bytes());
+}
+
+#[test]
+fn test_webhook_commitment_updated_event_golden() {
+    let event = WebhookCommitmentUpdatedEvent {
+        discriminator: EventDiscriminators::WebhookCommitmentUpdated as u8,
+        merchant_operator_config: key(1),
+        old_commitment: [0xab; 32],
+        new_commitment: [0xcd; 32],
+    };
+    assert_golden("events", "webhook_commitment_updated", &event.to_bytes());
+}
+
+#[test]
+fn test_payment_annotated_event_golden() {
+    let event = PaymentAnnotatedEvent {
+        discriminator: EventDiscriminators::PaymentAnnotated as u8,
+        payment: key(1),
+        merchant_operator_config: key(2),
+        annotated_by: key(3),
+        flag: AnnotationFlag::FlaggedFraud,
+        note_hash: [0xcd; 32],
+    };
+    assert_golden("events", "payment_annotated", &event.to_bytes());
+}
+
+#[test]
+fn test_config_decommissioned_event_golden() {
+    let event = ConfigDecommissionedEvent {
+        discriminator: EventDiscriminators::ConfigDecommissioned as u8,
+        merchant_operator_config: key(1),
+        merchant: key(2),
+        operator: key(3),
+        decommissioned_by: key(4),
+        decommissioned_at: 1_700_000_000,
+    };
+    assert_golden("events", "config_decommissioned", &event.to_bytes());
+}
+
+#[test]
+fn test_merchant_operator_config_closed_event_golden() {
+    let event = MerchantOperatorConfigClosedEvent {
+        discriminator: EventDiscriminators::MerchantOperatorConfigClosed as u8,
+        merchant_operator_config: key(1),
+        merchant: key(2),
+        operator: key(3),
+        rent_reclaimed: 2_500_000,
+    };
+    assert_golden(
+        "events",
+        "merchant_operator_config_closed",
+        &event.to_bytes(),
+    );
+}
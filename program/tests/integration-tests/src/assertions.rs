@@ -1,5 +1,8 @@
 use commerce_program_client::{
-    types::Status, Merchant, MerchantOperatorConfig, Operator, Payment, COMMERCE_PROGRAM_ID,
+    config_hash::verify_config_hash, types::Status, BuyerIndex, ConfigRegistryPage, ConversionRate,
+    Merchant, MerchantOperatorConfig, MerchantProfile, MerchantStats, Operator, OperatorDelegate,
+    OperatorFreezeList, Order, Payment, PaymentTree, Quote, RefundApproval, RiskState,
+    SettlementBatch, COMMERCE_PROGRAM_ID,
 };
 use solana_program_pack::Pack;
 use solana_sdk::pubkey::Pubkey;
@@ -89,7 +92,7 @@ pub fn assert_merchant_operator_config_account(
     expected_current_order_id: u32,
     _expected_num_policies: u32,
     _expected_num_accepted_currencies: u32,
-) {
+) -> MerchantOperatorConfig {
     let account = context
         .get_account(merchant_operator_config_pda)
         .expect("Merchant operator config account should exist");
@@ -106,6 +109,10 @@ pub fn assert_merchant_operator_config_account(
         expected_current_order_id
     );
     // todo validate policies and accepted currencies
+
+    verify_config_hash(&account.data).expect("config_hash should match the config's terms");
+
+    merchant_operator_config
 }
 
 pub fn assert_payment_account(
@@ -129,6 +136,306 @@ pub fn assert_payment_account(
     assert!(payment.created_at > 0, "Created timestamp should be set");
 }
 
+pub fn assert_payment_tree_account(
+    context: &mut TestContext,
+    payment_tree_pda: &Pubkey,
+    expected_merchant_operator_config: &Pubkey,
+    expected_num_leaves: u32,
+    expected_root: [u8; 32],
+) {
+    let account = context
+        .get_account(payment_tree_pda)
+        .expect("Payment tree account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let payment_tree =
+        PaymentTree::from_bytes(&account.data).expect("Should deserialize payment tree account");
+
+    assert_eq!(
+        payment_tree.merchant_operator_config,
+        *expected_merchant_operator_config
+    );
+    assert_eq!(payment_tree.num_leaves, expected_num_leaves);
+    assert_eq!(payment_tree.root, expected_root);
+}
+
+pub fn assert_merchant_stats_account(
+    context: &mut TestContext,
+    merchant_stats_pda: &Pubkey,
+    expected_merchant: &Pubkey,
+    expected_mint: &Pubkey,
+) -> MerchantStats {
+    let account = context
+        .get_account(merchant_stats_pda)
+        .expect("Merchant stats account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let merchant_stats = MerchantStats::from_bytes(&account.data)
+        .expect("Should deserialize merchant stats account");
+
+    assert_eq!(merchant_stats.merchant, *expected_merchant);
+    assert_eq!(merchant_stats.mint, *expected_mint);
+
+    merchant_stats
+}
+
+pub fn assert_settlement_batch_account(
+    context: &mut TestContext,
+    settlement_batch_pda: &Pubkey,
+    expected_merchant_operator_config: &Pubkey,
+    expected_mint: &Pubkey,
+) -> SettlementBatch {
+    let account = context
+        .get_account(settlement_batch_pda)
+        .expect("Settlement batch account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let settlement_batch = SettlementBatch::from_bytes(&account.data)
+        .expect("Should deserialize settlement batch account");
+
+    assert_eq!(
+        settlement_batch.merchant_operator_config,
+        *expected_merchant_operator_config
+    );
+    assert_eq!(settlement_batch.mint, *expected_mint);
+
+    settlement_batch
+}
+
+pub fn assert_refund_approval_account(
+    context: &mut TestContext,
+    refund_approval_pda: &Pubkey,
+    expected_payment: &Pubkey,
+    expected_approver: &Pubkey,
+    expected_amount: u64,
+) -> RefundApproval {
+    let account = context
+        .get_account(refund_approval_pda)
+        .expect("Refund approval account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let refund_approval = RefundApproval::from_bytes(&account.data)
+        .expect("Should deserialize refund approval account");
+
+    assert_eq!(refund_approval.payment, *expected_payment);
+    assert_eq!(refund_approval.approver, *expected_approver);
+    assert_eq!(refund_approval.amount, expected_amount);
+
+    refund_approval
+}
+
+pub fn assert_risk_state_account(
+    context: &mut TestContext,
+    risk_state_pda: &Pubkey,
+    expected_merchant_operator_config: &Pubkey,
+    expected_buyer: &Pubkey,
+) -> RiskState {
+    let account = context
+        .get_account(risk_state_pda)
+        .expect("Risk state account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let risk_state =
+        RiskState::from_bytes(&account.data).expect("Should deserialize risk state account");
+
+    assert_eq!(
+        risk_state.merchant_operator_config,
+        *expected_merchant_operator_config
+    );
+    assert_eq!(risk_state.buyer, *expected_buyer);
+
+    risk_state
+}
+
+pub fn assert_quote_account(
+    context: &mut TestContext,
+    quote_pda: &Pubkey,
+    expected_merchant_operator_config: &Pubkey,
+    expected_mint: &Pubkey,
+    expected_order_id: u32,
+) -> Quote {
+    let account = context
+        .get_account(quote_pda)
+        .expect("Quote account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let quote = Quote::from_bytes(&account.data).expect("Should deserialize quote account");
+
+    assert_eq!(
+        quote.merchant_operator_config,
+        *expected_merchant_operator_config
+    );
+    assert_eq!(quote.mint, *expected_mint);
+    assert_eq!(quote.order_id, expected_order_id);
+
+    quote
+}
+
+pub fn assert_conversion_rate_account(
+    context: &mut TestContext,
+    conversion_rate_pda: &Pubkey,
+    expected_merchant_operator_config: &Pubkey,
+    expected_from_mint: &Pubkey,
+    expected_to_mint: &Pubkey,
+) -> ConversionRate {
+    let account = context
+        .get_account(conversion_rate_pda)
+        .expect("Conversion rate account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let conversion_rate = ConversionRate::from_bytes(&account.data)
+        .expect("Should deserialize conversion rate account");
+
+    assert_eq!(
+        conversion_rate.merchant_operator_config,
+        *expected_merchant_operator_config
+    );
+    assert_eq!(conversion_rate.from_mint, *expected_from_mint);
+    assert_eq!(conversion_rate.to_mint, *expected_to_mint);
+
+    conversion_rate
+}
+
+pub fn assert_config_registry_page_account(
+    context: &mut TestContext,
+    config_registry_page_pda: &Pubkey,
+    expected_operator: &Pubkey,
+    expected_page: u32,
+) -> ConfigRegistryPage {
+    let account = context
+        .get_account(config_registry_page_pda)
+        .expect("ConfigRegistryPage account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let page = ConfigRegistryPage::from_bytes(&account.data)
+        .expect("Should deserialize config registry page account");
+
+    assert_eq!(page.operator, *expected_operator);
+    assert_eq!(page.page, expected_page);
+
+    page
+}
+
+pub fn assert_operator_freeze_list_account(
+    context: &mut TestContext,
+    operator_freeze_list_pda: &Pubkey,
+    expected_operator: &Pubkey,
+) -> OperatorFreezeList {
+    let account = context
+        .get_account(operator_freeze_list_pda)
+        .expect("OperatorFreezeList account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let freeze_list = OperatorFreezeList::from_bytes(&account.data)
+        .expect("Should deserialize operator freeze list account");
+
+    assert_eq!(freeze_list.operator, *expected_operator);
+
+    freeze_list
+}
+
+pub fn assert_buyer_index_account(
+    context: &mut TestContext,
+    buyer_index_pda: &Pubkey,
+    expected_merchant_operator_config: &Pubkey,
+    expected_buyer: &Pubkey,
+) -> BuyerIndex {
+    let account = context
+        .get_account(buyer_index_pda)
+        .expect("Buyer index account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let buyer_index =
+        BuyerIndex::from_bytes(&account.data).expect("Should deserialize buyer index account");
+
+    assert_eq!(
+        buyer_index.merchant_operator_config,
+        *expected_merchant_operator_config
+    );
+    assert_eq!(buyer_index.buyer, *expected_buyer);
+
+    buyer_index
+}
+
+pub fn assert_merchant_profile_account(
+    context: &mut TestContext,
+    merchant_profile_pda: &Pubkey,
+    expected_merchant: &Pubkey,
+    expected_name: &str,
+    expected_uri: &str,
+) -> MerchantProfile {
+    let account = context
+        .get_account(merchant_profile_pda)
+        .expect("Merchant profile account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let merchant_profile = MerchantProfile::from_bytes(&account.data)
+        .expect("Should deserialize merchant profile account");
+
+    assert_eq!(merchant_profile.merchant, *expected_merchant);
+    assert_eq!(merchant_profile.name, expected_name);
+    assert_eq!(merchant_profile.uri, expected_uri);
+
+    merchant_profile
+}
+
+pub fn assert_order_account(
+    context: &mut TestContext,
+    order_pda: &Pubkey,
+    expected_merchant_operator_config: &Pubkey,
+    expected_buyer: &Pubkey,
+    expected_order_id: u32,
+) -> Order {
+    let account = context
+        .get_account(order_pda)
+        .expect("Order account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let order = Order::from_bytes(&account.data).expect("Should deserialize order account");
+
+    assert_eq!(
+        order.merchant_operator_config,
+        *expected_merchant_operator_config
+    );
+    assert_eq!(order.buyer, *expected_buyer);
+    assert_eq!(order.order_id, expected_order_id);
+
+    order
+}
+
+pub fn assert_operator_delegate_account(
+    context: &mut TestContext,
+    operator_delegate_pda: &Pubkey,
+    expected_operator: &Pubkey,
+    expected_delegate: &Pubkey,
+) -> OperatorDelegate {
+    let account = context
+        .get_account(operator_delegate_pda)
+        .expect("Operator delegate account should exist");
+
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+
+    let operator_delegate = OperatorDelegate::from_bytes(&account.data)
+        .expect("Should deserialize operator delegate account");
+
+    assert_eq!(operator_delegate.operator, *expected_operator);
+    assert_eq!(operator_delegate.delegate, *expected_delegate);
+
+    operator_delegate
+}
+
 pub fn assert_token_balance_changes(
     context: &mut TestContext,
     pre_balances: [u64; 2],
@@ -165,7 +472,7 @@ pub fn assert_token_balance_changes(
 
 pub struct BalanceChange {
     pub ata: Pubkey,
-    pub expected_change: i64, // Positive for increase, negative for decrease
+    pub expected_change: i128, // Positive for increase, negative for decrease; wide enough for any u64
     pub description: String,
 }
 
@@ -189,14 +496,10 @@ pub fn assert_multiple_token_balance_changes(
             .expect("Pre-balance should exist for ATA");
 
         let post_balance = post_balances[i].1;
-        let expected_post_balance = if change.expected_change >= 0 {
-            pre_balance + (change.expected_change as u64)
-        } else {
-            pre_balance - ((-change.expected_change) as u64)
-        };
+        let expected_post_balance = pre_balance as i128 + change.expected_change;
 
         assert_eq!(
-            expected_post_balance, post_balance,
+            expected_post_balance, post_balance as i128,
             "{} (expected change: {}, pre: {}, post: {})",
             change.description, change.expected_change, pre_balance, post_balance
         );
@@ -0,0 +1,212 @@
+use crate::{
+    assertions::assert_buyer_index_account,
+    state_utils::*,
+    utils::{
+        assert_program_error, find_buyer_index_pda, find_escrow_ata, find_escrow_authority_pda,
+        find_payment_pda, set_token_balance, TestContext, TestContextBuilder,
+        BUYER_INDEX_MISMATCH_ERROR, BUYER_INDEX_REQUIRED_ERROR, DAYS_TO_CLOSE, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    buyer_index::{buyer_index_account, recent_order_ids},
+    instructions::MakePaymentBuilder,
+    types::{BuyerIndexPolicy, FeeType, PolicyData},
+};
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+
+struct BuyerIndexTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    next_order_id: u32,
+}
+
+// Helper function to set up a config with a buyer index policy, the buyer's BuyerIndex and a
+// funded buyer
+fn setup_buyer_index_test(
+    required: bool,
+) -> Result<BuyerIndexTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::BuyerIndex(BuyerIndexPolicy { required })],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    assert_initialize_buyer_index(
+        &mut context,
+        &merchant_operator_config_pda,
+        &buyer.pubkey(),
+        true,
+    )?;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &buyer_ata,
+        &USDC_MINT,
+        &buyer.pubkey(),
+        100 * PAYMENT_AMOUNT,
+    );
+
+    Ok(BuyerIndexTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        next_order_id: 1,
+    })
+}
+
+fn send_make_payment(
+    setup: &mut BuyerIndexTestSetup,
+    remaining_accounts: &[AccountMeta],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let order_id = setup.next_order_id;
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let instruction = MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(order_id)
+        .amount(PAYMENT_AMOUNT)
+        .idempotency_key([0; 16])
+        .add_remaining_accounts(remaining_accounts)
+        .instruction();
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])?;
+    setup.next_order_id += 1;
+    Ok(())
+}
+
+fn send_make_payment_with_index(
+    setup: &mut BuyerIndexTestSetup,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let buyer_index =
+        buyer_index_account(&setup.merchant_operator_config_pda, &setup.buyer.pubkey());
+    send_make_payment(setup, &[buyer_index])
+}
+
+#[tokio::test]
+async fn test_payments_are_recorded_on_buyer_index() {
+    let mut setup = setup_buyer_index_test(true).unwrap();
+
+    for _ in 0..10 {
+        send_make_payment_with_index(&mut setup).expect("Payment should succeed");
+        setup.context.svm.expire_blockhash();
+    }
+
+    let config = setup.merchant_operator_config_pda;
+    let buyer = setup.buyer.pubkey();
+    let (buyer_index_pda, _) = find_buyer_index_pda(&config, &buyer);
+    let buyer_index =
+        assert_buyer_index_account(&mut setup.context, &buyer_index_pda, &config, &buyer);
+
+    // Only the latest order ids are kept, most recent first
+    assert_eq!(buyer_index.payment_count, 10);
+    assert_eq!(recent_order_ids(&buyer_index), &[10, 9, 8, 7, 6, 5, 4, 3]);
+}
+
+#[tokio::test]
+async fn test_required_buyer_index_missing_fails() {
+    let mut setup = setup_buyer_index_test(true).unwrap();
+
+    let result = send_make_payment(&mut setup, &[]);
+    assert_program_error(result, BUYER_INDEX_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_optional_buyer_index_may_be_omitted() {
+    let mut setup = setup_buyer_index_test(false).unwrap();
+
+    send_make_payment(&mut setup, &[]).expect("Payment without the index should succeed");
+    send_make_payment_with_index(&mut setup).expect("Payment with the index should succeed");
+
+    let config = setup.merchant_operator_config_pda;
+    let buyer = setup.buyer.pubkey();
+    let (buyer_index_pda, _) = find_buyer_index_pda(&config, &buyer);
+    let buyer_index =
+        assert_buyer_index_account(&mut setup.context, &buyer_index_pda, &config, &buyer);
+    assert_eq!(buyer_index.payment_count, 1);
+    assert_eq!(recent_order_ids(&buyer_index), &[2]);
+}
+
+#[tokio::test]
+async fn test_other_buyer_index_fails() {
+    let mut setup = setup_buyer_index_test(true).unwrap();
+
+    let other_buyer = Pubkey::new_unique();
+    let config = setup.merchant_operator_config_pda;
+    assert_initialize_buyer_index(&mut setup.context, &config, &other_buyer, true).unwrap();
+
+    let result = send_make_payment(&mut setup, &[buyer_index_account(&config, &other_buyer)]);
+    assert_program_error(result, BUYER_INDEX_MISMATCH_ERROR);
+}
@@ -0,0 +1,322 @@
+use crate::{
+    assertions::assert_payment_account,
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda,
+        get_or_create_associated_token_account, get_token_balance, TestContext, ATA_PROGRAM_ID,
+        CALLBACK_PROGRAM_MISMATCH_ERROR, CALLBACK_PROGRAM_REQUIRED_ERROR, DAYS_TO_CLOSE,
+        INVALID_CALLBACK_POLICY_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    callback::callback_remaining_accounts,
+    instructions::{ClearPaymentBuilder, RefundPaymentBuilder},
+    types::{CallbackPolicy, FeeType, PolicyData, Status},
+    COMMERCE_PROGRAM_ID as PROGRAM_ID,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 1_000_000;
+
+// A program that rejects the callback instruction, standing in for a merchant program whose
+// own checks fail
+const REJECTING_CALLBACK_PROGRAM: Pubkey = ATA_PROGRAM_ID;
+
+struct CallbackTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a config with a callback policy and a single paid payment
+fn setup_callback_policy_test(
+    on_clear: bool,
+    on_refund: bool,
+) -> Result<CallbackTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let policies = vec![PolicyData::Callback(CallbackPolicy {
+        program: REJECTING_CALLBACK_PROGRAM,
+        on_clear,
+        on_refund,
+    })];
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        ORDER_ID,
+        AMOUNT,
+        true,
+        false,
+        false,
+    )?;
+
+    get_or_create_associated_token_account(&mut context, &settlement_wallet.pubkey(), &USDC_MINT);
+    get_or_create_associated_token_account(&mut context, &operator_authority.pubkey(), &USDC_MINT);
+
+    Ok(CallbackTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn clear_payment_instruction(
+    setup: &CallbackTestSetup,
+    callback_program: Option<&Pubkey>,
+) -> Instruction {
+    let mut builder = ClearPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ));
+    if let Some(callback_program) = callback_program {
+        builder.add_remaining_accounts(&callback_remaining_accounts(
+            callback_program,
+            &[AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false)],
+        ));
+    }
+    builder.instruction()
+}
+
+fn refund_payment_instruction(
+    setup: &CallbackTestSetup,
+    callback_program: Option<&Pubkey>,
+) -> Instruction {
+    let mut builder = RefundPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ));
+    if let Some(callback_program) = callback_program {
+        builder.add_remaining_accounts(&callback_remaining_accounts(callback_program, &[]));
+    }
+    builder.instruction()
+}
+
+fn send(
+    setup: &mut CallbackTestSetup,
+    instruction: Instruction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+}
+
+#[tokio::test]
+async fn test_clear_payment_without_callback_program_fails() {
+    let mut setup = setup_callback_policy_test(true, false).unwrap();
+
+    let instruction = clear_payment_instruction(&setup, None);
+    let result = send(&mut setup, instruction);
+    assert_program_error(result, CALLBACK_PROGRAM_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_wrong_callback_program_fails() {
+    let mut setup = setup_callback_policy_test(true, false).unwrap();
+
+    let instruction = clear_payment_instruction(&setup, Some(&TOKEN_PROGRAM_ID));
+    let result = send(&mut setup, instruction);
+    assert_program_error(result, CALLBACK_PROGRAM_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_failing_callback_rolls_back_clear() {
+    let mut setup = setup_callback_policy_test(true, false).unwrap();
+    let escrow_ata = get_associated_token_address(&setup.merchant_pda, &USDC_MINT);
+    let escrow_balance = get_token_balance(&mut setup.context, &escrow_ata);
+
+    let instruction = clear_payment_instruction(&setup, Some(&REJECTING_CALLBACK_PROGRAM));
+    let result = send(&mut setup, instruction);
+    assert!(result.is_err(), "A failing callback should fail the clear");
+
+    // The payment and its funds are left as they were
+    assert_payment_account(
+        &mut setup.context,
+        &setup.payment_pda,
+        ORDER_ID,
+        AMOUNT,
+        Status::Paid,
+    );
+    assert_eq!(
+        get_token_balance(&mut setup.context, &escrow_ata),
+        escrow_balance
+    );
+}
+
+#[tokio::test]
+async fn test_refund_payment_callback_disabled_skips_callback() {
+    let mut setup = setup_callback_policy_test(true, false).unwrap();
+
+    // The policy only calls back on clear, so the refund needs no callback program
+    let instruction = refund_payment_instruction(&setup, None);
+    send(&mut setup, instruction).expect("Refund should succeed");
+
+    assert_payment_account(
+        &mut setup.context,
+        &setup.payment_pda,
+        ORDER_ID,
+        AMOUNT,
+        Status::Refunded,
+    );
+}
+
+#[tokio::test]
+async fn test_refund_payment_without_callback_program_fails() {
+    let mut setup = setup_callback_policy_test(false, true).unwrap();
+
+    let instruction = refund_payment_instruction(&setup, None);
+    let result = send(&mut setup, instruction);
+    assert_program_error(result, CALLBACK_PROGRAM_REQUIRED_ERROR);
+
+    let instruction = refund_payment_instruction(&setup, Some(&REJECTING_CALLBACK_PROGRAM));
+    let result = send(&mut setup, instruction);
+    assert!(result.is_err(), "A failing callback should fail the refund");
+
+    assert_payment_account(
+        &mut setup.context,
+        &setup.payment_pda,
+        ORDER_ID,
+        AMOUNT,
+        Status::Paid,
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_callback_policy_fails() {
+    for (program, on_clear, on_refund) in [
+        (REJECTING_CALLBACK_PROGRAM, false, false),
+        (Pubkey::default(), true, true),
+        (PROGRAM_ID, true, false),
+    ] {
+        let mut context = TestContext::new();
+        let operator_authority = context.payer.insecure_clone();
+        let merchant_authority = Keypair::new();
+        let settlement_wallet = Keypair::new();
+
+        let (operator_pda, _) =
+            assert_get_or_create_operator(&mut context, &operator_authority, true, false).unwrap();
+        let (merchant_pda, _) = assert_get_or_create_merchant(
+            &mut context,
+            &merchant_authority,
+            &settlement_wallet,
+            true,
+            false,
+        )
+        .unwrap();
+
+        let result = assert_get_or_create_merchant_operator_config(
+            &mut context,
+            &merchant_authority,
+            &merchant_pda,
+            &operator_pda,
+            1u32,
+            500u64,
+            FeeType::Bps,
+            0u32,
+            DAYS_TO_CLOSE,
+            vec![PolicyData::Callback(CallbackPolicy {
+                program,
+                on_clear,
+                on_refund,
+            })],
+            vec![USDC_MINT],
+            true,
+            false,
+        )
+        .map(|_| ());
+        assert_program_error(result, INVALID_CALLBACK_POLICY_ERROR);
+    }
+}
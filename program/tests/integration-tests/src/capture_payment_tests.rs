@@ -0,0 +1,310 @@
+use crate::{
+    assertions::assert_payment_account,
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        get_token_balance, set_token_balance, TestContext, TestContextBuilder,
+        AUTHORIZATION_REQUIRES_ESCROW_ERROR, CAPTURE_AMOUNT_EXCEEDS_AUTHORIZATION_ERROR,
+        DAYS_TO_CLOSE, INVALID_PAYMENT_STATUS_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{CapturePaymentBuilder, MakePaymentBuilder},
+    types::{FeeType, PolicyData, SettlementPolicy, Status},
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const ORDER_ID: u32 = 1;
+const AUTHORIZED_AMOUNT: u64 = 1_000_000;
+
+struct CaptureTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a config and a funded buyer, with auto-settle if `auto_settle`
+fn setup_capture_test(auto_settle: bool) -> Result<CaptureTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let policies = if auto_settle {
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: true,
+        })]
+    } else {
+        vec![]
+    };
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &buyer_ata,
+        &USDC_MINT,
+        &buyer.pubkey(),
+        AUTHORIZED_AMOUNT,
+    );
+
+    let (payment_pda, _) = find_payment_pda(
+        &merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+
+    Ok(CaptureTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn authorize_payment_instruction(setup: &CaptureTestSetup) -> Instruction {
+    MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(ORDER_ID)
+        .amount(AUTHORIZED_AMOUNT)
+        .idempotency_key([0; 16])
+        .authorize(true)
+        .instruction()
+}
+
+fn capture_payment_instruction(setup: &CaptureTestSetup, amount: u64) -> Instruction {
+    CapturePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .amount(amount)
+        .instruction()
+}
+
+fn authorize_payment(setup: &mut CaptureTestSetup) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction = authorize_payment_instruction(setup);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+}
+
+fn capture_payment(
+    setup: &mut CaptureTestSetup,
+    amount: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction = capture_payment_instruction(setup, amount);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+}
+
+#[tokio::test]
+async fn test_capture_payment_partial() {
+    let mut setup = setup_capture_test(false).unwrap();
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+    let escrow_ata = find_escrow_ata(&setup.merchant_operator_config_pda, &USDC_MINT);
+
+    authorize_payment(&mut setup).unwrap();
+    let payment_pda = setup.payment_pda;
+    assert_payment_account(
+        &mut setup.context,
+        &payment_pda,
+        ORDER_ID,
+        AUTHORIZED_AMOUNT,
+        Status::Authorized,
+    );
+    assert_eq!(get_token_balance(&mut setup.context, &buyer_ata), 0);
+    assert_eq!(
+        get_token_balance(&mut setup.context, &escrow_ata),
+        AUTHORIZED_AMOUNT
+    );
+
+    let captured = 600_000;
+    capture_payment(&mut setup, captured).unwrap();
+
+    // The capture stays in escrow as a regular payment and the rest goes back to the buyer
+    assert_payment_account(
+        &mut setup.context,
+        &payment_pda,
+        ORDER_ID,
+        captured,
+        Status::Paid,
+    );
+    assert_eq!(
+        get_token_balance(&mut setup.context, &buyer_ata),
+        AUTHORIZED_AMOUNT - captured
+    );
+    assert_eq!(get_token_balance(&mut setup.context, &escrow_ata), captured);
+
+    // The captured payment clears like any other
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    let config_pda = setup.merchant_operator_config_pda;
+    assert_clear_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &config_pda,
+        false,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_capture_payment_zero_releases_hold() {
+    let mut setup = setup_capture_test(false).unwrap();
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+
+    authorize_payment(&mut setup).unwrap();
+    capture_payment(&mut setup, 0).unwrap();
+
+    let payment_pda = setup.payment_pda;
+    assert_payment_account(
+        &mut setup.context,
+        &payment_pda,
+        ORDER_ID,
+        AUTHORIZED_AMOUNT,
+        Status::Refunded,
+    );
+    assert_eq!(
+        get_token_balance(&mut setup.context, &buyer_ata),
+        AUTHORIZED_AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn test_capture_payment_exceeding_authorization_fails() {
+    let mut setup = setup_capture_test(false).unwrap();
+
+    authorize_payment(&mut setup).unwrap();
+    let result = capture_payment(&mut setup, AUTHORIZED_AMOUNT + 1);
+    assert_program_error(result, CAPTURE_AMOUNT_EXCEEDS_AUTHORIZATION_ERROR);
+}
+
+#[tokio::test]
+async fn test_capture_payment_twice_fails() {
+    let mut setup = setup_capture_test(false).unwrap();
+
+    authorize_payment(&mut setup).unwrap();
+    capture_payment(&mut setup, AUTHORIZED_AMOUNT).unwrap();
+
+    setup.context.svm.expire_blockhash();
+    let result = capture_payment(&mut setup, AUTHORIZED_AMOUNT);
+    assert_program_error(result, INVALID_PAYMENT_STATUS_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_authorized_payment_fails() {
+    let mut setup = setup_capture_test(false).unwrap();
+
+    authorize_payment(&mut setup).unwrap();
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    let payment_pda = setup.payment_pda;
+    let config_pda = setup.merchant_operator_config_pda;
+    let result = assert_clear_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &config_pda,
+        false,
+    );
+    assert_program_error(result, INVALID_PAYMENT_STATUS_ERROR);
+}
+
+#[tokio::test]
+async fn test_authorize_payment_with_auto_settle_fails() {
+    let mut setup = setup_capture_test(true).unwrap();
+
+    let result = authorize_payment(&mut setup);
+    assert_program_error(result, AUTHORIZATION_REQUIRES_ESCROW_ERROR);
+}
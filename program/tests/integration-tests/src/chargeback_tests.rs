@@ -0,0 +1,357 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda,
+        get_or_create_associated_token_account, TestContext, BUYER_FROZEN_ERROR,
+        CHARGEBACK_ARBITER_MISMATCH_ERROR, CHARGEBACK_POLICY_REQUIRED_ERROR,
+        CHARGEBACK_WINDOW_ACTIVE_ERROR, CHARGEBACK_WINDOW_EXPIRED_ERROR, DAYS_TO_CLOSE,
+        FREEZE_LIST_REQUIRED_ERROR, INVALID_CHARGEBACK_POLICY_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{AddToFreezeListBuilder, ChargebackPaymentBuilder, ClearPaymentBuilder},
+    types::{ChargebackPolicy, FeeType, PolicyData},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const CHARGEBACK_WINDOW_SECONDS: u64 = 24 * 60 * 60;
+
+struct ChargebackTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Pubkey,
+    arbiter: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a USDC config with the given policies and a single paid payment
+fn setup_chargeback_test(
+    policies: Vec<PolicyData>,
+    arbiter: Keypair,
+) -> Result<ChargebackTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        1_000_000,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+
+    get_or_create_associated_token_account(&mut context, &settlement_wallet.pubkey(), &USDC_MINT);
+    get_or_create_associated_token_account(&mut context, &operator_authority.pubkey(), &USDC_MINT);
+
+    Ok(ChargebackTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        arbiter,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn setup_with_chargeback_policy() -> ChargebackTestSetup {
+    let arbiter = Keypair::new();
+    let policy = chargeback_policy(CHARGEBACK_WINDOW_SECONDS, arbiter.pubkey());
+    setup_chargeback_test(vec![policy], arbiter).unwrap()
+}
+
+fn chargeback_policy(window_seconds: u64, arbiter: Pubkey) -> PolicyData {
+    PolicyData::Chargeback(ChargebackPolicy {
+        window_seconds,
+        arbiter,
+    })
+}
+
+fn chargeback_payment_instruction(setup: &ChargebackTestSetup, arbiter: &Pubkey) -> Instruction {
+    ChargebackPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .arbiter(*arbiter)
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .instruction()
+}
+
+fn clear_payment_instruction(setup: &ChargebackTestSetup) -> Instruction {
+    ClearPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .instruction()
+}
+
+#[tokio::test]
+async fn test_chargeback_payment_within_window_success() {
+    let mut setup = setup_with_chargeback_policy();
+    let arbiter = setup.arbiter.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    setup
+        .context
+        .advance_clock(CHARGEBACK_WINDOW_SECONDS as i64 - 1);
+
+    assert_chargeback_payment(
+        &mut setup.context,
+        &arbiter,
+        &buyer,
+        &setup.payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+    .expect("Should charge back payment within the window");
+}
+
+#[tokio::test]
+async fn test_chargeback_payment_wrong_arbiter_fails() {
+    let mut setup = setup_with_chargeback_policy();
+
+    // The operator is not the arbiter
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let instruction = chargeback_payment_instruction(&setup, &operator_authority.pubkey());
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, CHARGEBACK_ARBITER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_chargeback_payment_without_policy_fails() {
+    let mut setup = setup_chargeback_test(vec![], Keypair::new()).unwrap();
+    let arbiter = setup.arbiter.insecure_clone();
+    setup
+        .context
+        .airdrop_if_required(&arbiter.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    let instruction = chargeback_payment_instruction(&setup, &arbiter.pubkey());
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&arbiter]);
+
+    assert_program_error(result, CHARGEBACK_POLICY_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_chargeback_payment_after_window_fails() {
+    let mut setup = setup_with_chargeback_policy();
+    let arbiter = setup.arbiter.insecure_clone();
+    setup
+        .context
+        .airdrop_if_required(&arbiter.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    setup
+        .context
+        .advance_clock(CHARGEBACK_WINDOW_SECONDS as i64);
+
+    let instruction = chargeback_payment_instruction(&setup, &arbiter.pubkey());
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&arbiter]);
+
+    assert_program_error(result, CHARGEBACK_WINDOW_EXPIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_chargeback_payment_to_frozen_buyer_fails() {
+    let mut setup = setup_with_chargeback_policy();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let arbiter = setup.arbiter.insecure_clone();
+    let buyer = setup.buyer.pubkey();
+    setup
+        .context
+        .airdrop_if_required(&arbiter.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    let (freeze_list_pda, _) = assert_initialize_operator_freeze_list(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+    )
+    .unwrap();
+    let instruction = AddToFreezeListBuilder::new()
+        .authority(operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .operator_freeze_list(freeze_list_pda)
+        .address(buyer)
+        .instruction();
+    setup.context.send_transaction(instruction).unwrap();
+
+    // Leaving the freeze list out does not skip the check
+    let instruction = chargeback_payment_instruction(&setup, &arbiter.pubkey());
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&arbiter]);
+    assert_program_error(result, FREEZE_LIST_REQUIRED_ERROR);
+
+    let mut instruction = chargeback_payment_instruction(&setup, &arbiter.pubkey());
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(freeze_list_pda, false));
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&arbiter]);
+    assert_program_error(result, BUYER_FROZEN_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_within_chargeback_window_fails() {
+    let mut setup = setup_with_chargeback_policy();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    setup
+        .context
+        .advance_clock(CHARGEBACK_WINDOW_SECONDS as i64 - 1);
+
+    let instruction = clear_payment_instruction(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, CHARGEBACK_WINDOW_ACTIVE_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_after_chargeback_window_success() {
+    let mut setup = setup_with_chargeback_policy();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    setup
+        .context
+        .advance_clock(CHARGEBACK_WINDOW_SECONDS as i64);
+
+    assert_clear_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &setup.payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+    .expect("Should clear payment once the chargeback window has passed");
+}
+
+#[tokio::test]
+async fn test_initialize_config_with_invalid_chargeback_policy_fails() {
+    let mut setup = setup_chargeback_test(vec![], Keypair::new()).unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    for policy in [
+        chargeback_policy(0, Pubkey::new_unique()),
+        chargeback_policy(CHARGEBACK_WINDOW_SECONDS, Pubkey::default()),
+    ] {
+        let result = assert_get_or_create_merchant_operator_config(
+            &mut setup.context,
+            &merchant_authority,
+            &setup.merchant_pda,
+            &setup.operator_pda,
+            2u32,
+            500u64,
+            FeeType::Bps,
+            0u32,
+            DAYS_TO_CLOSE,
+            vec![policy],
+            vec![USDC_MINT],
+            true,
+            false,
+        )
+        .map(|_| ());
+
+        assert_program_error(result, INVALID_CHARGEBACK_POLICY_ERROR);
+    }
+}
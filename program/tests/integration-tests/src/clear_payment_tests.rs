@@ -1,16 +1,17 @@
 use crate::{
     state_utils::*,
     utils::{
-        assert_program_error, get_or_create_associated_token_account, set_mint, TestContext,
-        DAYS_TO_CLOSE, INSUFFICIENT_SETTLEMENT_AMOUNT_ERROR, INVALID_ACCOUNT_OWNER_ERROR,
-        INVALID_INSTRUCTION_DATA_ERROR, INVALID_MINT_ERROR, INVALID_PAYMENT_STATUS_ERROR,
-        NOT_ENOUGH_ACCOUNT_KEYS_ERROR, OPERATOR_OWNER_MISMATCH_ERROR, SETTLEMENT_TOO_EARLY_ERROR,
-        USDC_MINT, USDT_MINT,
+        assert_program_error, find_escrow_authority_pda, get_or_create_associated_token_account,
+        get_token_balance, set_mint, TestContext, DAYS_TO_CLOSE,
+        INSUFFICIENT_SETTLEMENT_AMOUNT_ERROR, INVALID_ACCOUNT_OWNER_ERROR, INVALID_ATA_ERROR,
+        INVALID_MINT_ERROR, INVALID_PAYMENT_STATUS_ERROR, NOT_ENOUGH_ACCOUNT_KEYS_ERROR,
+        OPERATOR_OWNER_MISMATCH_ERROR, SETTLEMENT_TOO_EARLY_ERROR, USDC_MINT, USDT_MINT,
     },
 };
 use commerce_program_client::{
+    discriminator::InstructionDiscriminator,
     instructions::ClearPaymentBuilder,
-    types::{FeeType, PolicyData, SettlementPolicy},
+    types::{FeeTier, FeeTierPolicy, FeeType, PolicyData, SettlementPolicy},
 };
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -621,6 +622,118 @@ async fn test_clear_payment_fee_exceeds_amount() {
     .expect("Should clear payment successfully even when fee exceeds amount");
 }
 
+#[tokio::test]
+async fn test_clear_payment_with_fee_tiers() {
+    let mut context = TestContext::new();
+
+    // Create keypairs
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    // Create buyer ATA
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    // Step 1: Create operator
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)
+            .expect("Should create operator");
+
+    // Step 2: Create merchant
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )
+    .expect("Should create merchant");
+
+    // Step 3: Create merchant operator config with 2% under 100 USDC and 1% above
+    let tier = |threshold, fee| FeeTier {
+        threshold,
+        fee,
+        fee_type: FeeType::Bps,
+    };
+    let policies = vec![PolicyData::FeeTier(FeeTierPolicy {
+        num_tiers: 2,
+        tiers: [
+            tier(0, 200),
+            tier(100_000_000, 100),
+            tier(0, 0),
+            tier(0, 0),
+            tier(0, 0),
+        ],
+    })];
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500, // Flat 5% fee, overridden by the tiers
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )
+    .expect("Should create merchant operator config");
+
+    let operator_settlement_ata =
+        get_associated_token_address(&operator_authority.pubkey(), &USDC_MINT);
+
+    // Step 4: Make and clear a payment in each tier
+    for (order_id, amount, expected_operator_fee) in [
+        (1u32, 50_000_000u64, 1_000_000u64),  // 50 USDC at 2%
+        (2u32, 200_000_000u64, 2_000_000u64), // 200 USDC at 1%
+    ] {
+        let (payment_pda, _) = assert_make_payment(
+            &mut context,
+            &operator_authority,
+            &operator_authority,
+            &buyer,
+            &merchant_operator_config_pda,
+            &operator_pda,
+            &USDC_MINT,
+            order_id,
+            amount,
+            true,  // fail_if_exists
+            false, // is_auto_settle
+            false,
+        )
+        .expect("Should make payment successfully");
+
+        get_or_create_associated_token_account(
+            &mut context,
+            &operator_authority.pubkey(),
+            &USDC_MINT,
+        );
+        let operator_balance_before = get_token_balance(&mut context, &operator_settlement_ata);
+
+        assert_clear_payment(
+            &mut context,
+            &operator_authority,
+            &operator_authority,
+            &buyer,
+            &payment_pda,
+            &USDC_MINT,
+            &merchant_operator_config_pda,
+            false,
+        )
+        .expect("Should clear payment successfully");
+
+        assert_eq!(
+            get_token_balance(&mut context, &operator_settlement_ata) - operator_balance_before,
+            expected_operator_fee
+        );
+    }
+}
+
 /*
 SAD PATH TESTS
 */
@@ -659,7 +772,8 @@ async fn test_clear_payment_unsigned_operator_authority_fails() {
         .expect("Should deserialize operator");
     let operator_owner = operator_data.owner;
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, &USDC_MINT);
     let operator_settlement_ata = get_associated_token_address(&operator_owner, &USDC_MINT);
 
@@ -672,6 +786,7 @@ async fn test_clear_payment_unsigned_operator_authority_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -733,7 +848,8 @@ async fn test_clear_payment_invalid_payment_status_fails() {
         .expect("Should deserialize operator");
     let operator_owner = operator_data.owner;
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, &USDC_MINT);
     let operator_settlement_ata = get_associated_token_address(&operator_owner, &USDC_MINT);
 
@@ -746,6 +862,7 @@ async fn test_clear_payment_invalid_payment_status_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -782,7 +899,8 @@ async fn test_clear_payment_invalid_operator_fails() {
         .expect("Should deserialize merchant");
     let settlement_wallet = merchant_data.settlement_wallet;
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, &USDC_MINT);
     let operator_settlement_ata = get_associated_token_address(&fake_operator.pubkey(), &USDC_MINT);
 
@@ -795,6 +913,7 @@ async fn test_clear_payment_invalid_operator_fails() {
         .operator(fake_operator.pubkey()) // Wrong operator
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -833,7 +952,8 @@ async fn test_clear_payment_invalid_merchant_fails() {
         .expect("Should deserialize operator");
     let operator_owner = operator_data.owner;
 
-    let merchant_escrow_ata = get_associated_token_address(&fake_merchant.pubkey(), &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let merchant_settlement_ata = get_associated_token_address(&fake_merchant.pubkey(), &USDC_MINT);
     let operator_settlement_ata = get_associated_token_address(&operator_owner, &USDC_MINT);
 
@@ -846,6 +966,7 @@ async fn test_clear_payment_invalid_merchant_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -889,7 +1010,8 @@ async fn test_clear_payment_invalid_merchant_operator_config_fails() {
         .expect("Should deserialize operator");
     let operator_owner = operator_data.owner;
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&fake_config.pubkey(), &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, &USDC_MINT);
     let operator_settlement_ata = get_associated_token_address(&operator_owner, &USDC_MINT);
 
@@ -902,6 +1024,7 @@ async fn test_clear_payment_invalid_merchant_operator_config_fails() {
         .operator(operator_pda)
         .merchant_operator_config(fake_config.pubkey()) // Wrong merchant operator config
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -947,7 +1070,9 @@ async fn test_clear_payment_invalid_mint_not_in_allowed_list_fails() {
         .expect("Should deserialize operator");
     let operator_owner = operator_data.owner;
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &invalid_mint);
+    let escrow_authority =
+        find_escrow_authority_pda(&merchant_operator_config_pda, &invalid_mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &invalid_mint);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, &invalid_mint);
     let operator_settlement_ata = get_associated_token_address(&operator_owner, &invalid_mint);
 
@@ -960,6 +1085,7 @@ async fn test_clear_payment_invalid_mint_not_in_allowed_list_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(invalid_mint) // This mint is not in allowed_currencies
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -1003,7 +1129,8 @@ async fn test_clear_payment_invalid_payment_pda_fails() {
         .expect("Should deserialize operator");
     let operator_owner = operator_data.owner;
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, &USDC_MINT);
     let operator_settlement_ata = get_associated_token_address(&operator_owner, &USDC_MINT);
 
@@ -1016,6 +1143,7 @@ async fn test_clear_payment_invalid_payment_pda_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -1072,6 +1200,7 @@ async fn test_clear_payment_invalid_merchant_escrow_ata_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0)
         .merchant_escrow_ata(wrong_merchant_escrow_ata) // Wrong escrow ATA
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -1080,7 +1209,7 @@ async fn test_clear_payment_invalid_merchant_escrow_ata_fails() {
         .instruction();
 
     let result = context.send_transaction_with_signers(instruction, &[&operator_authority]);
-    assert_program_error(result, INVALID_INSTRUCTION_DATA_ERROR);
+    assert_program_error(result, INVALID_ATA_ERROR);
 }
 
 #[tokio::test]
@@ -1120,7 +1249,7 @@ async fn test_clear_payment_not_enough_account_keys_fails() {
     let instruction = Instruction {
         program_id: commerce_program_client::COMMERCE_PROGRAM_ID,
         accounts,
-        data: vec![4], // Clear payment instruction discriminator
+        data: vec![InstructionDiscriminator::ClearPayment.into()],
     };
 
     let result = context.send_transaction_with_signers(instruction, &[&operator_authority]);
@@ -1163,7 +1292,8 @@ async fn test_clear_payment_insufficient_settlement_amount_fails() {
         .expect("Should deserialize operator");
     let operator_owner = operator_data.owner;
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, &USDC_MINT);
     let operator_settlement_ata = get_associated_token_address(&operator_owner, &USDC_MINT);
 
@@ -1176,6 +1306,7 @@ async fn test_clear_payment_insufficient_settlement_amount_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -1222,7 +1353,8 @@ async fn test_clear_payment_settlement_too_early_fails() {
         .expect("Should deserialize operator");
     let operator_owner = operator_data.owner;
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, &USDC_MINT);
     let operator_settlement_ata = get_associated_token_address(&operator_owner, &USDC_MINT);
 
@@ -1235,6 +1367,7 @@ async fn test_clear_payment_settlement_too_early_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
@@ -1281,7 +1414,8 @@ async fn test_clear_payment_both_settlement_policies_fails() {
         .expect("Should deserialize operator");
     let operator_owner = operator_data.owner;
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, &USDC_MINT);
     let operator_settlement_ata = get_associated_token_address(&operator_owner, &USDC_MINT);
 
@@ -1294,6 +1428,7 @@ async fn test_clear_payment_both_settlement_policies_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
         .operator_settlement_ata(operator_settlement_ata)
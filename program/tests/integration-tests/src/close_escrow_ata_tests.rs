@@ -0,0 +1,272 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda,
+        get_or_create_associated_token_account, get_token_balance, set_token_balance, TestContext,
+        DAYS_TO_CLOSE, ESCROW_NOT_EMPTY_ERROR, OPERATOR_OWNER_MISMATCH_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::CloseEscrowAtaBuilder,
+    preview::decode_open_payments,
+    types::{FeeType, PolicyData, SettlementPolicy},
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const AMOUNT: u64 = 1_000_000;
+
+struct CloseEscrowAtaTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a config without auto settlement and a paid payment in USDC
+fn setup_close_escrow_ata_test() -> Result<CloseEscrowAtaTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        AMOUNT,
+        true,
+        false,
+        false,
+    )?;
+
+    Ok(CloseEscrowAtaTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn close_escrow_ata_instruction(
+    setup: &CloseEscrowAtaTestSetup,
+    operator_authority: &Pubkey,
+) -> Instruction {
+    CloseEscrowAtaBuilder::new()
+        .merchant_authority(setup.merchant_authority.pubkey())
+        .operator_authority(*operator_authority)
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet.pubkey(),
+            &USDC_MINT,
+        ))
+        .instruction()
+}
+
+fn close_escrow_ata(setup: &mut CloseEscrowAtaTestSetup) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction = close_escrow_ata_instruction(setup, &setup.operator_authority.pubkey());
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority, &operator_authority])
+}
+
+fn clear_payment(setup: &mut CloseEscrowAtaTestSetup) {
+    let payer = setup.context.payer.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_clear_payment(
+        &mut setup.context,
+        &payer,
+        &operator_authority,
+        &buyer,
+        &setup.payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_close_escrow_ata_returns_rent() {
+    let mut setup = setup_close_escrow_ata_test().unwrap();
+    clear_payment(&mut setup);
+
+    let escrow_ata = find_escrow_ata(&setup.merchant_operator_config_pda, &USDC_MINT);
+    let rent = setup.context.get_account(&escrow_ata).unwrap().lamports;
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let merchant_authority_before = setup
+        .context
+        .get_account(&merchant_authority.pubkey())
+        .map_or(0, |account| account.lamports);
+
+    close_escrow_ata(&mut setup).expect("Close escrow ATA should succeed");
+
+    assert!(setup.context.get_account(&escrow_ata).is_none());
+    let merchant_authority_after = setup
+        .context
+        .get_account(&merchant_authority.pubkey())
+        .map_or(0, |account| account.lamports);
+    assert_eq!(merchant_authority_after, merchant_authority_before + rent);
+
+    // The next payment in the mint recreates the escrow
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_make_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &setup.merchant_operator_config_pda,
+        &setup.operator_pda,
+        &USDC_MINT,
+        2,
+        AMOUNT,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_close_escrow_ata_with_open_payment_fails() {
+    let mut setup = setup_close_escrow_ata_test().unwrap();
+
+    let result = close_escrow_ata(&mut setup);
+    assert_program_error(result, ESCROW_NOT_EMPTY_ERROR);
+}
+
+#[tokio::test]
+async fn test_close_escrow_ata_with_drained_open_payment_fails() {
+    let mut setup = setup_close_escrow_ata_test().unwrap();
+
+    // The payment is still open even once the escrow holds none of its funds
+    let config_data = setup
+        .context
+        .get_account_data(&setup.merchant_operator_config_pda)
+        .unwrap();
+    assert_eq!(decode_open_payments(&config_data).unwrap(), vec![1]);
+    let escrow_authority =
+        find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0;
+    let escrow_ata = find_escrow_ata(&setup.merchant_operator_config_pda, &USDC_MINT);
+    set_token_balance(
+        &mut setup.context,
+        &escrow_ata,
+        &USDC_MINT,
+        &escrow_authority,
+        0,
+    );
+
+    let result = close_escrow_ata(&mut setup);
+    assert_program_error(result, ESCROW_NOT_EMPTY_ERROR);
+}
+
+#[tokio::test]
+async fn test_close_escrow_ata_sweeps_dust_to_settlement_wallet() {
+    let mut setup = setup_close_escrow_ata_test().unwrap();
+    clear_payment(&mut setup);
+    let config_data = setup
+        .context
+        .get_account_data(&setup.merchant_operator_config_pda)
+        .unwrap();
+    assert_eq!(decode_open_payments(&config_data).unwrap(), vec![0]);
+
+    // Tokens sent straight to the escrow belong to no payment
+    let escrow_authority =
+        find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0;
+    let escrow_ata = find_escrow_ata(&setup.merchant_operator_config_pda, &USDC_MINT);
+    set_token_balance(
+        &mut setup.context,
+        &escrow_ata,
+        &USDC_MINT,
+        &escrow_authority,
+        7,
+    );
+    let settlement_ata =
+        get_associated_token_address(&setup.settlement_wallet.pubkey(), &USDC_MINT);
+    let settlement_before = get_token_balance(&mut setup.context, &settlement_ata);
+
+    close_escrow_ata(&mut setup).expect("Close escrow ATA should succeed");
+
+    assert!(setup.context.get_account(&escrow_ata).is_none());
+    assert_eq!(
+        get_token_balance(&mut setup.context, &settlement_ata),
+        settlement_before + 7
+    );
+}
+
+#[tokio::test]
+async fn test_close_escrow_ata_wrong_operator_authority_fails() {
+    let mut setup = setup_close_escrow_ata_test().unwrap();
+    clear_payment(&mut setup);
+    let other_authority = Keypair::new();
+
+    let instruction = close_escrow_ata_instruction(&setup, &other_authority.pubkey());
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority, &other_authority]);
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
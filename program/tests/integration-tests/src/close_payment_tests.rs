@@ -2,12 +2,13 @@ use crate::{
     state_utils::*,
     utils::{
         assert_program_error, get_or_create_associated_token_account, TestContext, DAYS_TO_CLOSE,
-        INVALID_ACCOUNT_DATA_ERROR, INVALID_ACCOUNT_OWNER_ERROR, INVALID_PAYMENT_STATUS_ERROR,
-        MISSING_REQUIRED_SIGNATURE_ERROR, OPERATOR_OWNER_MISMATCH_ERROR,
-        PAYMENT_CANNOT_BE_CLOSED_ERROR, USDC_MINT, USDT_MINT,
+        INCORRECT_PROGRAM_ID_ERROR, INVALID_ACCOUNT_DATA_ERROR, INVALID_ACCOUNT_OWNER_ERROR,
+        INVALID_PAYMENT_STATUS_ERROR, MISSING_REQUIRED_SIGNATURE_ERROR,
+        OPERATOR_OWNER_MISMATCH_ERROR, PAYMENT_CANNOT_BE_CLOSED_ERROR, USDC_MINT, USDT_MINT,
     },
 };
 use commerce_program_client::{
+    discriminator::InstructionDiscriminator,
     instructions::ClosePaymentBuilder,
     types::{FeeType, PolicyData, SettlementPolicy},
 };
@@ -19,22 +20,42 @@ use solana_sdk::{
     system_program::ID as SYSTEM_PROGRAM_ID,
 };
 
+type ClosePaymentTestSetup = (
+    TestContext,
+    Keypair,
+    Keypair,
+    Keypair,
+    Keypair,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    u8,
+);
+
 // Helper function to set up test context for close_payment tests
-async fn setup_close_payment_test() -> Result<
-    (
-        TestContext,
-        Keypair,
-        Keypair,
-        Keypair,
-        Keypair,
-        Pubkey,
-        Pubkey,
-        Pubkey,
-        Pubkey,
-        u8,
-    ),
-    Box<dyn std::error::Error>,
-> {
+async fn setup_close_payment_test() -> Result<ClosePaymentTestSetup, Box<dyn std::error::Error>> {
+    let mut setup = setup_paid_payment_test().await?;
+    let (context, operator_authority, buyer, config_pda, payment_pda) =
+        (&mut setup.0, &setup.1, &setup.4, &setup.7, &setup.8);
+
+    // Clear the payment so it can be closed
+    assert_clear_payment(
+        context,
+        operator_authority,
+        operator_authority,
+        buyer,
+        payment_pda,
+        &USDC_MINT,
+        config_pda,
+        false,
+    )?;
+
+    Ok(setup)
+}
+
+// Helper function to set up a payment still in Paid status
+async fn setup_paid_payment_test() -> Result<ClosePaymentTestSetup, Box<dyn std::error::Error>> {
     let mut context = TestContext::new();
     let operator_authority = context.payer.insecure_clone();
     let merchant_authority = Keypair::new();
@@ -103,18 +124,6 @@ async fn setup_close_payment_test() -> Result<
         false,
     )?;
 
-    // Clear the payment so it can be closed
-    assert_clear_payment(
-        &mut context,
-        &operator_authority,
-        &operator_authority,
-        &buyer,
-        &payment_pda,
-        &USDC_MINT,
-        &merchant_operator_config_pda,
-        false,
-    )?;
-
     Ok((
         context,
         operator_authority,
@@ -222,7 +231,7 @@ async fn test_close_payment_unsigned_operator_authority_fails() {
     let instruction = Instruction {
         program_id: commerce_program_client::COMMERCE_PROGRAM_ID,
         accounts,
-        data: vec![9], // ClosePayment discriminator only, no additional data
+        data: vec![InstructionDiscriminator::ClosePayment.into()], // No additional data
     };
 
     let result = context.send_transaction_with_signers(instruction, &[]);
@@ -262,6 +271,40 @@ async fn test_close_payment_invalid_operator_fails() {
     assert_program_error(result, INVALID_ACCOUNT_OWNER_ERROR);
 }
 
+#[tokio::test]
+async fn test_close_payment_wrong_commerce_program_fails() {
+    let (
+        mut context,
+        operator_authority,
+        _merchant_authority,
+        _settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+        _bump,
+    ) = setup_close_payment_test().await.unwrap();
+
+    context.advance_clock(8 * 24 * 60 * 60);
+
+    let instruction = ClosePaymentBuilder::new()
+        .payer(context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .operator(operator_pda)
+        .merchant(merchant_pda)
+        .buyer(buyer.pubkey())
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .commerce_program(Pubkey::new_unique()) // Not the commerce program
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, INCORRECT_PROGRAM_ID_ERROR);
+}
+
 #[tokio::test]
 async fn test_close_payment_invalid_merchant_fails() {
     let (
@@ -698,3 +741,144 @@ async fn test_close_payment_wrong_operator_authority_fails() {
     let result = context.send_transaction_with_signers(instruction, &[&wrong_authority]);
     assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
 }
+
+#[tokio::test]
+async fn test_close_refunded_payment_success() {
+    let (
+        mut context,
+        operator_authority,
+        _merchant_authority,
+        _settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+        _bump,
+    ) = setup_paid_payment_test().await.unwrap();
+
+    assert_refund_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &merchant_operator_config_pda,
+        false,
+    )
+    .expect("Should refund payment");
+
+    context.advance_clock(8 * 24 * 60 * 60); // 8 days in seconds
+
+    let instruction = ClosePaymentBuilder::new()
+        .payer(context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .operator(operator_pda)
+        .merchant(merchant_pda)
+        .buyer(buyer.pubkey())
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Should close refunded payment after days_to_close elapsed");
+
+    let final_balance = context
+        .get_account(&payment_pda)
+        .map(|a| a.lamports)
+        .unwrap_or(0);
+    assert_eq!(final_balance, 0, "Payment account should be closed");
+}
+
+#[tokio::test]
+async fn test_close_refunded_payment_too_early_fails() {
+    let (
+        mut context,
+        operator_authority,
+        _merchant_authority,
+        _settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+        _bump,
+    ) = setup_paid_payment_test().await.unwrap();
+
+    assert_refund_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &merchant_operator_config_pda,
+        false,
+    )
+    .expect("Should refund payment");
+
+    // Refunded payments wait out the same days_to_close window as cleared ones
+    let instruction = ClosePaymentBuilder::new()
+        .payer(context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .operator(operator_pda)
+        .merchant(merchant_pda)
+        .buyer(buyer.pubkey())
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, PAYMENT_CANNOT_BE_CLOSED_ERROR);
+}
+
+#[tokio::test]
+async fn test_close_refund_approved_payment_fails() {
+    let (
+        mut context,
+        operator_authority,
+        _merchant_authority,
+        _settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+        _bump,
+    ) = setup_paid_payment_test().await.unwrap();
+
+    assert_approve_refund(
+        &mut context,
+        &operator_authority,
+        &buyer.pubkey(),
+        &payment_pda,
+        &USDC_MINT,
+        &merchant_operator_config_pda,
+        false,
+    )
+    .expect("Should approve refund");
+
+    context.advance_clock(8 * 24 * 60 * 60); // 8 days in seconds
+
+    // The approved refund is still in escrow, so the payment is not terminal
+    let instruction = ClosePaymentBuilder::new()
+        .payer(context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .operator(operator_pda)
+        .merchant(merchant_pda)
+        .buyer(buyer.pubkey())
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, INVALID_PAYMENT_STATUS_ERROR);
+}
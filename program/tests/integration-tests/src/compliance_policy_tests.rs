@@ -0,0 +1,235 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        get_token_balance, set_token_balance, TestContext, TestContextBuilder, ATA_PROGRAM_ID,
+        COMPLIANCE_PROGRAM_MISMATCH_ERROR, COMPLIANCE_PROGRAM_REQUIRED_ERROR, DAYS_TO_CLOSE,
+        INVALID_COMPLIANCE_POLICY_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    compliance::compliance_remaining_accounts,
+    instructions::MakePaymentBuilder,
+    types::{CompliancePolicy, FeeType, PolicyData},
+    COMMERCE_PROGRAM_ID as PROGRAM_ID,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 1_000_000;
+
+// A program that rejects the compliance check, standing in for a registry the buyer is not
+// verified with
+const REJECTING_COMPLIANCE_PROGRAM: Pubkey = ATA_PROGRAM_ID;
+
+struct ComplianceTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a config with a compliance policy and a funded buyer
+fn setup_compliance_policy_test() -> Result<ComplianceTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Compliance(CompliancePolicy {
+            program: REJECTING_COMPLIANCE_PROGRAM,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &buyer_ata,
+        &USDC_MINT,
+        &buyer.pubkey(),
+        AMOUNT,
+    );
+
+    Ok(ComplianceTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn make_payment_instruction(
+    setup: &ComplianceTestSetup,
+    compliance_program: Option<&Pubkey>,
+) -> Instruction {
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+
+    let mut builder = MakePaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(ORDER_ID)
+        .amount(AMOUNT)
+        .idempotency_key([0; 16]);
+    if let Some(compliance_program) = compliance_program {
+        builder.add_remaining_accounts(&compliance_remaining_accounts(
+            compliance_program,
+            &[AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false)],
+        ));
+    }
+    builder.instruction()
+}
+
+fn send(
+    setup: &mut ComplianceTestSetup,
+    instruction: Instruction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+}
+
+#[tokio::test]
+async fn test_make_payment_without_compliance_program_fails() {
+    let mut setup = setup_compliance_policy_test().unwrap();
+
+    let instruction = make_payment_instruction(&setup, None);
+    let result = send(&mut setup, instruction);
+    assert_program_error(result, COMPLIANCE_PROGRAM_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_wrong_compliance_program_fails() {
+    let mut setup = setup_compliance_policy_test().unwrap();
+
+    let instruction = make_payment_instruction(&setup, Some(&TOKEN_PROGRAM_ID));
+    let result = send(&mut setup, instruction);
+    assert_program_error(result, COMPLIANCE_PROGRAM_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_rejected_by_compliance_program_fails() {
+    let mut setup = setup_compliance_policy_test().unwrap();
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+
+    let instruction = make_payment_instruction(&setup, Some(&REJECTING_COMPLIANCE_PROGRAM));
+    let result = send(&mut setup, instruction);
+    assert!(result.is_err(), "A rejected check should fail the payment");
+
+    // No payment is created and the buyer keeps their funds
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+    assert!(setup.context.get_account(&payment_pda).is_none());
+    assert_eq!(get_token_balance(&mut setup.context, &buyer_ata), AMOUNT);
+}
+
+#[tokio::test]
+async fn test_invalid_compliance_policy_fails() {
+    for program in [Pubkey::default(), PROGRAM_ID] {
+        let mut context = TestContext::new();
+        let operator_authority = context.payer.insecure_clone();
+        let merchant_authority = Keypair::new();
+        let settlement_wallet = Keypair::new();
+
+        let (operator_pda, _) =
+            assert_get_or_create_operator(&mut context, &operator_authority, true, false).unwrap();
+        let (merchant_pda, _) = assert_get_or_create_merchant(
+            &mut context,
+            &merchant_authority,
+            &settlement_wallet,
+            true,
+            false,
+        )
+        .unwrap();
+
+        let result = assert_get_or_create_merchant_operator_config(
+            &mut context,
+            &merchant_authority,
+            &merchant_pda,
+            &operator_pda,
+            1u32,
+            500u64,
+            FeeType::Bps,
+            0u32,
+            DAYS_TO_CLOSE,
+            vec![PolicyData::Compliance(CompliancePolicy { program })],
+            vec![USDC_MINT],
+            true,
+            false,
+        )
+        .map(|_| ());
+        assert_program_error(result, INVALID_COMPLIANCE_POLICY_ERROR);
+    }
+}
@@ -0,0 +1,475 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_tree_pda,
+        get_or_create_associated_token_account, TestContext, DAYS_TO_CLOSE,
+        INVALID_MERKLE_PROOF_ERROR, OPERATOR_OWNER_MISMATCH_ERROR, PAYMENT_TREE_MISMATCH_ERROR,
+        USDC_MINT, USDT_MINT,
+    },
+};
+use commerce_program_client::{
+    compression::PaymentMerkleTree,
+    instructions::{
+        ClearPaymentCompressedBuilder, InitializePaymentTreeBuilder, MakePaymentCompressedBuilder,
+    },
+    types::{FeeType, PolicyData, SettlementPolicy},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+// Helper function to set up a config with an initialized payment tree
+async fn setup_compressed_payment_test(
+    auto_settle: bool,
+) -> Result<(TestContext, Keypair, Keypair, Pubkey, Pubkey, Pubkey), Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    // Create buyer ATA
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    // Create operator
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    // Create merchant
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    // Create merchant operator config
+    let policies = vec![PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: 0,
+        settlement_frequency_hours: 0,
+        auto_settle,
+    })];
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT, USDT_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    // Create payment tree
+    let (payment_tree_pda, _) = assert_initialize_payment_tree(
+        &mut context,
+        &operator_authority,
+        &operator_pda,
+        &merchant_operator_config_pda,
+        true, // fail_if_exists
+        false,
+    )?;
+
+    Ok((
+        context,
+        operator_authority,
+        buyer,
+        operator_pda,
+        merchant_operator_config_pda,
+        payment_tree_pda,
+    ))
+}
+
+// Helper function to create another config for the operator with a fresh merchant
+fn create_other_merchant_operator_config(
+    context: &mut TestContext,
+    operator_pda: &Pubkey,
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        context,
+        &merchant_authority,
+        &merchant_pda,
+        operator_pda,
+        1,
+        0,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    Ok(merchant_operator_config_pda)
+}
+
+/*
+HAPPY PATH TESTS
+*/
+#[tokio::test]
+async fn test_make_and_clear_payment_compressed_success() {
+    let (mut context, operator_authority, buyer, operator_pda, merchant_operator_config_pda, _) =
+        setup_compressed_payment_test(false)
+            .await
+            .expect("Should set up compressed payment test");
+
+    let mut tree = PaymentMerkleTree::new();
+    let amount = 1_000_000u64;
+
+    // Append several payments so proofs span more than one leaf
+    let mut payments = Vec::new();
+    for order_id in 1..=3u32 {
+        let (leaf_index, created_at) = assert_make_payment_compressed(
+            &mut context,
+            &operator_authority,
+            &buyer,
+            &merchant_operator_config_pda,
+            &operator_pda,
+            &USDC_MINT,
+            order_id,
+            amount,
+            false, // is_auto_settle
+            &mut tree,
+            true, // with_profiling
+        )
+        .expect("Should make compressed payment");
+        payments.push((leaf_index, order_id, created_at));
+    }
+
+    // Clear out of order, including the right-most leaf
+    for &(leaf_index, order_id, created_at) in [payments[1], payments[2], payments[0]].iter() {
+        assert_clear_payment_compressed(
+            &mut context,
+            &operator_authority,
+            &operator_authority,
+            &buyer.pubkey(),
+            &USDC_MINT,
+            &merchant_operator_config_pda,
+            leaf_index,
+            order_id,
+            amount,
+            created_at,
+            &mut tree,
+            true, // with_profiling
+        )
+        .expect("Should clear compressed payment");
+    }
+
+    // Tree keeps accepting payments after leaves were replaced
+    assert_make_payment_compressed(
+        &mut context,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        4,
+        amount,
+        false,
+        &mut tree,
+        false,
+    )
+    .expect("Should make compressed payment after clears");
+}
+
+#[tokio::test]
+async fn test_make_payment_compressed_auto_settle_success() {
+    let (mut context, operator_authority, buyer, operator_pda, merchant_operator_config_pda, _) =
+        setup_compressed_payment_test(true)
+            .await
+            .expect("Should set up compressed payment test");
+
+    let mut tree = PaymentMerkleTree::new();
+
+    assert_make_payment_compressed(
+        &mut context,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        1_000_000,
+        true, // is_auto_settle
+        &mut tree,
+        false,
+    )
+    .expect("Should make auto-settled compressed payment");
+}
+
+/*
+FAILURE TESTS
+*/
+#[tokio::test]
+async fn test_initialize_payment_tree_wrong_operator_authority_fails() {
+    let (mut context, _operator_authority, _buyer, operator_pda, _, _) =
+        setup_compressed_payment_test(false)
+            .await
+            .expect("Should set up compressed payment test");
+
+    // Use another config so its tree PDA is still uninitialized
+    let merchant_operator_config_pda =
+        create_other_merchant_operator_config(&mut context, &operator_pda)
+            .expect("Should create merchant operator config");
+
+    let wrong_authority = Keypair::new();
+    context
+        .airdrop_if_required(&wrong_authority.pubkey(), 1_000_000_000)
+        .unwrap();
+    let (payment_tree_pda, bump) = find_payment_tree_pda(&merchant_operator_config_pda);
+
+    let instruction = InitializePaymentTreeBuilder::new()
+        .payer(context.payer.pubkey())
+        .operator_authority(wrong_authority.pubkey())
+        .operator(operator_pda)
+        .merchant_operator_config(merchant_operator_config_pda)
+        .payment_tree(payment_tree_pda)
+        .bump(bump)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&wrong_authority]);
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_compressed_invalid_proof_fails() {
+    let (
+        mut context,
+        operator_authority,
+        buyer,
+        operator_pda,
+        merchant_operator_config_pda,
+        payment_tree_pda,
+    ) = setup_compressed_payment_test(false)
+        .await
+        .expect("Should set up compressed payment test");
+
+    let mut tree = PaymentMerkleTree::new();
+    let amount = 1_000_000u64;
+
+    let (leaf_index, created_at) = assert_make_payment_compressed(
+        &mut context,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        amount,
+        false,
+        &mut tree,
+        false,
+    )
+    .expect("Should make compressed payment");
+
+    let merchant_operator_config = commerce_program_client::MerchantOperatorConfig::from_bytes(
+        &context
+            .get_account(&merchant_operator_config_pda)
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    let merchant = commerce_program_client::Merchant::from_bytes(
+        &context
+            .get_account(&merchant_operator_config.merchant)
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+
+    // Claim a larger amount than was paid: the leaf no longer matches the root
+    let instruction = ClearPaymentCompressedBuilder::new()
+        .payer(operator_authority.pubkey())
+        .payment_tree(payment_tree_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .merchant(merchant_operator_config.merchant)
+        .operator(operator_pda)
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0)
+        .merchant_escrow_ata(find_escrow_ata(&merchant_operator_config_pda, &USDC_MINT))
+        .merchant_settlement_ata(get_associated_token_address(
+            &merchant.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .leaf_index(leaf_index)
+        .order_id(1)
+        .amount(amount * 2)
+        .created_at(created_at)
+        .proof(tree.proof(leaf_index).unwrap())
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, INVALID_MERKLE_PROOF_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_compressed_twice_fails() {
+    let (mut context, operator_authority, buyer, operator_pda, merchant_operator_config_pda, _) =
+        setup_compressed_payment_test(false)
+            .await
+            .expect("Should set up compressed payment test");
+
+    let mut tree = PaymentMerkleTree::new();
+    let amount = 1_000_000u64;
+
+    let (leaf_index, created_at) = assert_make_payment_compressed(
+        &mut context,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        amount,
+        false,
+        &mut tree,
+        false,
+    )
+    .expect("Should make compressed payment");
+
+    // Keep the pre-clear tree to replay the original Paid proof
+    let stale_tree = tree.clone();
+
+    assert_clear_payment_compressed(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        &merchant_operator_config_pda,
+        leaf_index,
+        1,
+        amount,
+        created_at,
+        &mut tree,
+        false,
+    )
+    .expect("Should clear compressed payment");
+
+    let (payment_tree_pda, _) = find_payment_tree_pda(&merchant_operator_config_pda);
+    let merchant_operator_config = commerce_program_client::MerchantOperatorConfig::from_bytes(
+        &context
+            .get_account(&merchant_operator_config_pda)
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    let merchant = commerce_program_client::Merchant::from_bytes(
+        &context
+            .get_account(&merchant_operator_config.merchant)
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+
+    let instruction = ClearPaymentCompressedBuilder::new()
+        .payer(operator_authority.pubkey())
+        .payment_tree(payment_tree_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .merchant(merchant_operator_config.merchant)
+        .operator(operator_pda)
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0)
+        .merchant_escrow_ata(find_escrow_ata(&merchant_operator_config_pda, &USDC_MINT))
+        .merchant_settlement_ata(get_associated_token_address(
+            &merchant.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .leaf_index(leaf_index)
+        .order_id(1)
+        .amount(amount)
+        .created_at(created_at)
+        .proof(stale_tree.proof(leaf_index).unwrap())
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, INVALID_MERKLE_PROOF_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_compressed_tree_from_other_config_fails() {
+    let (mut context, operator_authority, buyer, operator_pda, merchant_operator_config_pda, _) =
+        setup_compressed_payment_test(false)
+            .await
+            .expect("Should set up compressed payment test");
+
+    // Initialize a tree for another config of the same operator
+    let other_config_pda = create_other_merchant_operator_config(&mut context, &operator_pda)
+        .expect("Should create merchant operator config");
+    let (other_tree_pda, _) = assert_initialize_payment_tree(
+        &mut context,
+        &operator_authority,
+        &operator_pda,
+        &other_config_pda,
+        true,
+        false,
+    )
+    .expect("Should initialize other payment tree");
+
+    let merchant_operator_config = commerce_program_client::MerchantOperatorConfig::from_bytes(
+        &context
+            .get_account(&merchant_operator_config_pda)
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+    let merchant = commerce_program_client::Merchant::from_bytes(
+        &context
+            .get_account(&merchant_operator_config.merchant)
+            .unwrap()
+            .data,
+    )
+    .unwrap();
+
+    let instruction = MakePaymentCompressedBuilder::new()
+        .payment_tree(other_tree_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .operator(operator_pda)
+        .merchant(merchant_operator_config.merchant)
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(&buyer.pubkey(), &USDC_MINT))
+        .escrow_authority(find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0)
+        .merchant_escrow_ata(find_escrow_ata(&merchant_operator_config_pda, &USDC_MINT))
+        .merchant_settlement_ata(get_associated_token_address(
+            &merchant.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .order_id(1)
+        .amount(1_000_000)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+    assert_program_error(result, PAYMENT_TREE_MISMATCH_ERROR);
+}
@@ -0,0 +1,184 @@
+use crate::{
+    state_utils::{
+        assert_get_or_create_merchant, assert_get_or_create_merchant_with_timelock,
+        assert_get_or_create_operator, assert_propose_merchant_authority,
+        assert_propose_operator_authority, assert_propose_settlement_wallet,
+    },
+    utils::{
+        assert_merchant_authority_updated_event_present, assert_merchant_initialized_event_present,
+        assert_merchant_settlement_wallet_updated_event_present,
+        assert_operator_authority_updated_event_present, find_merchant_pda, find_operator_pda,
+        TestContext,
+    },
+};
+use commerce_program_client::instructions::{
+    AcceptMerchantAuthorityBuilder, AcceptOperatorAuthorityBuilder, CommitSettlementWalletBuilder,
+    InitializeMerchantBuilder, UpdateMerchantSettlementWalletBuilder,
+};
+use solana_sdk::signature::{Keypair, Signer};
+
+const SETTLEMENT_WALLET_TIMELOCK_SECONDS: u64 = 24 * 60 * 60;
+
+#[tokio::test]
+async fn test_initialize_merchant_emits_event() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (merchant_pda, bump) = find_merchant_pda(&authority.pubkey());
+    let instruction = InitializeMerchantBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .settlement_wallet(settlement_wallet.pubkey())
+        .bump(bump)
+        .settlement_wallet_timelock_seconds(SETTLEMENT_WALLET_TIMELOCK_SECONDS)
+        .instruction();
+
+    let metadata = context
+        .send_transaction_with_signers_with_transaction_result(instruction, &[&authority], false)
+        .unwrap();
+    assert_merchant_initialized_event_present(
+        &metadata,
+        &merchant_pda,
+        &authority.pubkey(),
+        &settlement_wallet.pubkey(),
+        SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+    );
+}
+
+#[tokio::test]
+async fn test_update_merchant_settlement_wallet_emits_event() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let new_settlement_wallet = Keypair::new();
+
+    let (merchant_pda, _) =
+        assert_get_or_create_merchant(&mut context, &authority, &settlement_wallet, true, false)
+            .unwrap();
+
+    let instruction = UpdateMerchantSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .new_settlement_wallet(new_settlement_wallet.pubkey())
+        .instruction();
+
+    let metadata = context
+        .send_transaction_with_signers_with_transaction_result(instruction, &[&authority], false)
+        .unwrap();
+    assert_merchant_settlement_wallet_updated_event_present(
+        &metadata,
+        &merchant_pda,
+        &settlement_wallet.pubkey(),
+        &new_settlement_wallet.pubkey(),
+    );
+}
+
+#[tokio::test]
+async fn test_commit_settlement_wallet_emits_event() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let new_settlement_wallet = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant_with_timelock(
+        &mut context,
+        &authority,
+        &settlement_wallet,
+        SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+        true,
+        false,
+    )
+    .unwrap();
+
+    assert_propose_settlement_wallet(
+        &mut context,
+        &authority,
+        &new_settlement_wallet.pubkey(),
+        false,
+    )
+    .unwrap();
+
+    context.advance_clock(SETTLEMENT_WALLET_TIMELOCK_SECONDS as i64);
+
+    let instruction = CommitSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .instruction();
+
+    let metadata = context
+        .send_transaction_with_signers_with_transaction_result(instruction, &[&authority], false)
+        .unwrap();
+    assert_merchant_settlement_wallet_updated_event_present(
+        &metadata,
+        &merchant_pda,
+        &settlement_wallet.pubkey(),
+        &new_settlement_wallet.pubkey(),
+    );
+}
+
+#[tokio::test]
+async fn test_accept_merchant_authority_emits_event() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let new_authority = Keypair::new();
+
+    let (merchant_pda, _) =
+        assert_get_or_create_merchant(&mut context, &authority, &settlement_wallet, true, false)
+            .unwrap();
+
+    assert_propose_merchant_authority(&mut context, &authority, &new_authority.pubkey(), false)
+        .unwrap();
+
+    let instruction = AcceptMerchantAuthorityBuilder::new()
+        .payer(context.payer.pubkey())
+        .new_authority(new_authority.pubkey())
+        .merchant(merchant_pda)
+        .instruction();
+
+    let metadata = context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&new_authority],
+            false,
+        )
+        .unwrap();
+    assert_merchant_authority_updated_event_present(
+        &metadata,
+        &merchant_pda,
+        &authority.pubkey(),
+        &new_authority.pubkey(),
+    );
+}
+
+#[tokio::test]
+async fn test_accept_operator_authority_emits_event() {
+    let mut context = TestContext::new();
+    let owner = Keypair::new();
+    let new_owner = Keypair::new();
+
+    assert_get_or_create_operator(&mut context, &owner, true, false).unwrap();
+
+    assert_propose_operator_authority(&mut context, &owner, &new_owner.pubkey(), false).unwrap();
+
+    let (operator_pda, _) = find_operator_pda(&owner.pubkey());
+    let instruction = AcceptOperatorAuthorityBuilder::new()
+        .payer(context.payer.pubkey())
+        .new_operator_authority(new_owner.pubkey())
+        .operator(operator_pda)
+        .instruction();
+
+    let metadata = context
+        .send_transaction_with_signers_with_transaction_result(instruction, &[&new_owner], false)
+        .unwrap();
+    assert_operator_authority_updated_event_present(
+        &metadata,
+        &operator_pda,
+        &owner.pubkey(),
+        &new_owner.pubkey(),
+    );
+}
@@ -0,0 +1,262 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, set_mint, TestContext, TestContextBuilder, DAYS_TO_CLOSE,
+        DUPLICATE_MINT_ERROR, MERCHANT_OWNER_MISMATCH_ERROR, POLICY_ALREADY_EXISTS_ERROR,
+        TOO_MANY_ACCEPTED_CURRENCIES_ERROR, TOO_MANY_POLICIES_ERROR, USDC_MINT, USDT_MINT,
+    },
+};
+use commerce_program_client::{
+    config_hash::verify_config_hash,
+    config_size::{merchant_operator_config_size, MAX_ACCEPTED_CURRENCIES, MAX_POLICIES},
+    instructions::{AddAcceptedCurrencyBuilder, AddPolicyBuilder},
+    preview::decode_merchant_operator_config,
+    types::{FeeType, MemoPolicy, PolicyData, RefundPolicy, SettlementPolicy},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+struct ConfigGrowthTestSetup {
+    context: TestContext,
+    merchant_authority: Keypair,
+    merchant_pda: Pubkey,
+    operator_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+fn refund_policy() -> PolicyData {
+    PolicyData::Refund(RefundPolicy {
+        max_amount: 5_000_000,
+        max_time_after_purchase: 604_800,
+    })
+}
+
+fn settlement_policy() -> PolicyData {
+    PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: 100,
+        settlement_frequency_hours: 24,
+        auto_settle: false,
+    })
+}
+
+// Helper function to set up a config accepting USDC with `policies`
+fn setup_config_growth_test(
+    policies: Vec<PolicyData>,
+) -> Result<ConfigGrowthTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_usdt()
+        .with_operator()
+        .build();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        0u64,
+        FeeType::Fixed,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    Ok(ConfigGrowthTestSetup {
+        context,
+        merchant_authority,
+        merchant_pda,
+        operator_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn add_policy(
+    setup: &mut ConfigGrowthTestSetup,
+    authority: &Keypair,
+    policy: PolicyData,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction = AddPolicyBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .policy(policy)
+        .instruction();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[authority])
+}
+
+fn add_accepted_currency(
+    setup: &mut ConfigGrowthTestSetup,
+    mint: &Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let instruction = AddAcceptedCurrencyBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .authority(merchant_authority.pubkey())
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(*mint)
+        .instruction();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority])
+}
+
+// Decodes the config, checking it is sized for its policies and currencies, rent exempt and
+// hashed over its current terms
+fn assert_config_grown(
+    setup: &mut ConfigGrowthTestSetup,
+    num_policies: usize,
+    num_accepted_currencies: usize,
+) -> (Vec<PolicyData>, Vec<Pubkey>) {
+    let account = setup
+        .context
+        .get_account(&setup.merchant_operator_config_pda)
+        .unwrap();
+    assert_eq!(
+        account.data.len(),
+        merchant_operator_config_size(num_policies, num_accepted_currencies)
+    );
+    assert!(
+        account.lamports
+            >= setup
+                .context
+                .svm
+                .minimum_balance_for_rent_exemption(account.data.len())
+    );
+    verify_config_hash(&account.data).unwrap();
+
+    let (_, policies, currencies) = decode_merchant_operator_config(&account.data).unwrap();
+    assert_eq!(policies.len(), num_policies);
+    assert_eq!(currencies.len(), num_accepted_currencies);
+    (policies, currencies)
+}
+
+#[tokio::test]
+async fn test_add_policy_grows_config() {
+    let mut setup = setup_config_growth_test(vec![settlement_policy()]).unwrap();
+
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    add_policy(&mut setup, &merchant_authority, refund_policy()).unwrap();
+
+    let (policies, currencies) = assert_config_grown(&mut setup, 2, 1);
+    assert_eq!(policies, vec![settlement_policy(), refund_policy()]);
+    assert_eq!(currencies, vec![USDC_MINT]);
+}
+
+#[tokio::test]
+async fn test_add_accepted_currency_grows_config() {
+    let mut setup = setup_config_growth_test(vec![refund_policy()]).unwrap();
+
+    add_accepted_currency(&mut setup, &USDT_MINT).unwrap();
+
+    let (policies, currencies) = assert_config_grown(&mut setup, 1, 2);
+    assert_eq!(policies, vec![refund_policy()]);
+    assert_eq!(currencies, vec![USDC_MINT, USDT_MINT]);
+
+    // The added mint can be paid in
+    let operator_authority = setup.context.payer.insecure_clone();
+    let buyer = Keypair::new();
+    let operator_pda = setup.operator_pda;
+    let merchant_operator_config_pda = setup.merchant_operator_config_pda;
+    assert_make_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDT_MINT,
+        1,
+        1_000_000,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_add_policy_rejects_policy_type_already_set() {
+    let mut setup = setup_config_growth_test(vec![refund_policy()]).unwrap();
+
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let result = add_policy(&mut setup, &merchant_authority, refund_policy());
+
+    assert_program_error(result, POLICY_ALREADY_EXISTS_ERROR);
+}
+
+#[tokio::test]
+async fn test_add_policy_rejects_non_merchant_authority() {
+    let mut setup = setup_config_growth_test(vec![]).unwrap();
+
+    let result = add_policy(&mut setup, &Keypair::new(), refund_policy());
+
+    assert_program_error(result, MERCHANT_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_add_accepted_currency_rejects_duplicate_mint() {
+    let mut setup = setup_config_growth_test(vec![]).unwrap();
+
+    let result = add_accepted_currency(&mut setup, &USDC_MINT);
+
+    assert_program_error(result, DUPLICATE_MINT_ERROR);
+}
+
+#[tokio::test]
+async fn test_add_policy_up_to_max_policies() {
+    let mut setup = setup_config_growth_test(vec![refund_policy(); MAX_POLICIES - 1]).unwrap();
+
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    add_policy(&mut setup, &merchant_authority, settlement_policy()).unwrap();
+    assert_config_grown(&mut setup, MAX_POLICIES, 1);
+
+    let result = add_policy(
+        &mut setup,
+        &merchant_authority,
+        PolicyData::Memo(MemoPolicy {
+            memo_on_clear: true,
+            memo_on_refund: false,
+        }),
+    );
+    assert_program_error(result, TOO_MANY_POLICIES_ERROR);
+}
+
+#[tokio::test]
+async fn test_add_accepted_currency_up_to_max_accepted_currencies() {
+    let mut setup = setup_config_growth_test(vec![]).unwrap();
+
+    for _ in 1..MAX_ACCEPTED_CURRENCIES {
+        let mint = Pubkey::new_unique();
+        set_mint(&mut setup.context, &mint);
+        add_accepted_currency(&mut setup, &mint).unwrap();
+    }
+    assert_config_grown(&mut setup, 0, MAX_ACCEPTED_CURRENCIES);
+
+    let result = add_accepted_currency(&mut setup, &USDT_MINT);
+    assert_program_error(result, TOO_MANY_ACCEPTED_CURRENCIES_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_config_rejects_too_many_policies() {
+    let result = setup_config_growth_test(vec![refund_policy(); MAX_POLICIES + 1]);
+
+    assert_program_error(result.map(|_| ()), TOO_MANY_POLICIES_ERROR);
+}
@@ -0,0 +1,184 @@
+use crate::{
+    assertions::assert_config_registry_page_account,
+    state_utils::*,
+    utils::{
+        assert_program_error, find_config_registry_page_pda, find_merchant_operator_config_pda,
+        TestContext, CONFIG_REGISTRY_MISMATCH_ERROR, CONFIG_REGISTRY_PAGE_FULL_ERROR,
+        CONFIG_REGISTRY_PREVIOUS_PAGE_NOT_FULL_ERROR, DAYS_TO_CLOSE, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    config_registry::CONFIG_REGISTRY_PAGE_CAPACITY,
+    instructions::{InitializeConfigRegistryPageBuilder, InitializeMerchantOperatorConfigBuilder},
+    types::{ConfigRegistryEntry, FeeType},
+};
+use solana_sdk::{
+    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+
+struct ConfigRegistryTestSetup {
+    context: TestContext,
+    merchant_authority: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+}
+
+// Helper function to set up an operator and a merchant without configs
+fn setup_config_registry_test() -> Result<ConfigRegistryTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    Ok(ConfigRegistryTestSetup {
+        context,
+        merchant_authority,
+        operator_pda,
+        merchant_pda,
+    })
+}
+
+// Creates config `version` of the setup's merchant, passing `config_registry_page` after the
+// mint accounts
+fn create_registered_config(
+    setup: &mut ConfigRegistryTestSetup,
+    version: u32,
+    config_registry_page: &Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (config_pda, bump) =
+        find_merchant_operator_config_pda(&setup.merchant_pda, &setup.operator_pda, version);
+
+    let mut builder = InitializeMerchantOperatorConfigBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .authority(setup.merchant_authority.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .config(config_pda)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .version(version)
+        .bump(bump)
+        .operator_fee(0)
+        .fee_type(FeeType::Fixed)
+        .days_to_close(DAYS_TO_CLOSE)
+        .policies(vec![])
+        .accepted_currencies(vec![USDC_MINT])
+        .add_remaining_account(AccountMeta::new_readonly(USDC_MINT, false))
+        .add_remaining_account(AccountMeta::new(*config_registry_page, false));
+
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(builder.instruction(), &[&merchant_authority])
+}
+
+#[tokio::test]
+async fn test_register_config_success() {
+    let mut setup = setup_config_registry_test().unwrap();
+    let operator_pda = setup.operator_pda;
+    let (page_pda, _) =
+        assert_initialize_config_registry_page(&mut setup.context, &operator_pda, 0, None).unwrap();
+
+    create_registered_config(&mut setup, 1, &page_pda).expect("Registered config should succeed");
+    create_registered_config(&mut setup, 2, &page_pda).expect("Registered config should succeed");
+
+    let page = assert_config_registry_page_account(&mut setup.context, &page_pda, &operator_pda, 0);
+    assert_eq!(page.count, 2);
+    assert_eq!(
+        page.listed(),
+        &[
+            ConfigRegistryEntry {
+                merchant: setup.merchant_pda,
+                version: 1,
+            },
+            ConfigRegistryEntry {
+                merchant: setup.merchant_pda,
+                version: 2,
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_register_config_other_operator_page_fails() {
+    let mut setup = setup_config_registry_test().unwrap();
+
+    let other_operator_authority = Keypair::new();
+    setup
+        .context
+        .airdrop_if_required(&other_operator_authority.pubkey(), 1_000_000_000)
+        .unwrap();
+    let (other_operator_pda, _) =
+        assert_get_or_create_operator(&mut setup.context, &other_operator_authority, true, false)
+            .unwrap();
+    let (other_page_pda, _) =
+        assert_initialize_config_registry_page(&mut setup.context, &other_operator_pda, 0, None)
+            .unwrap();
+
+    let result = create_registered_config(&mut setup, 1, &other_page_pda);
+    assert_program_error(result, CONFIG_REGISTRY_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_second_page_requires_full_previous_page() {
+    let mut setup = setup_config_registry_test().unwrap();
+    let operator_pda = setup.operator_pda;
+    let (first_page_pda, _) =
+        assert_initialize_config_registry_page(&mut setup.context, &operator_pda, 0, None).unwrap();
+    let (second_page_pda, bump) = find_config_registry_page_pda(&operator_pda, 1);
+
+    let instruction = InitializeConfigRegistryPageBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .operator(operator_pda)
+        .config_registry_page(second_page_pda)
+        .page(1)
+        .bump(bump)
+        .add_remaining_account(AccountMeta::new_readonly(first_page_pda, false))
+        .instruction();
+
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, CONFIG_REGISTRY_PREVIOUS_PAGE_NOT_FULL_ERROR);
+}
+
+#[tokio::test]
+async fn test_full_page_overflows_to_next_page() {
+    let mut setup = setup_config_registry_test().unwrap();
+    let operator_pda = setup.operator_pda;
+    let (first_page_pda, _) =
+        assert_initialize_config_registry_page(&mut setup.context, &operator_pda, 0, None).unwrap();
+
+    let capacity = CONFIG_REGISTRY_PAGE_CAPACITY as u32;
+    for version in 1..=capacity {
+        create_registered_config(&mut setup, version, &first_page_pda).unwrap();
+    }
+
+    let result = create_registered_config(&mut setup, capacity + 1, &first_page_pda);
+    assert_program_error(result, CONFIG_REGISTRY_PAGE_FULL_ERROR);
+
+    let (second_page_pda, _) = assert_initialize_config_registry_page(
+        &mut setup.context,
+        &operator_pda,
+        1,
+        Some(&first_page_pda),
+    )
+    .unwrap();
+    create_registered_config(&mut setup, capacity + 1, &second_page_pda)
+        .expect("Config should register on the next page");
+
+    let page =
+        assert_config_registry_page_account(&mut setup.context, &second_page_pda, &operator_pda, 1);
+    assert_eq!(page.count, 1);
+    assert_eq!(page.entries[0].version, capacity + 1);
+}
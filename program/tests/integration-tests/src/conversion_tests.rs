@@ -0,0 +1,310 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_conversion_rate_pda, find_escrow_ata, find_escrow_authority_pda,
+        get_or_create_associated_token_account, TestContext, CONVERSION_POLICY_REQUIRED_ERROR,
+        DAYS_TO_CLOSE, INVALID_CONVERSION_POLICY_ERROR, INVALID_MINT_ERROR, INVALID_SWAP_ERROR,
+        QUOTE_BELOW_CONVERSION_RATE_ERROR, USDC_MINT, USDT_MINT,
+    },
+};
+use commerce_program_client::{
+    conversion::JUPITER_V6_PROGRAM_ID,
+    instructions::ClearPaymentWithConversionBuilder,
+    types::{ConversionPolicy, FeeType, PolicyData, SwapAdapter},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+// Anchor discriminators of Jupiter v6 `route` and `exact_out_route`
+const JUPITER_ROUTE: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+const JUPITER_EXACT_OUT_ROUTE: [u8; 8] = [208, 51, 239, 151, 123, 43, 237, 92];
+
+// The merchant amount of the 1 USDT payment after the 5% operator fee, in USDC at 1:1
+const QUOTED_AMOUNT_OUT: u64 = 950_000;
+
+struct ConversionTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    conversion_rate_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a USDT config with the given policies and a single paid payment,
+// posting a 1:1 USDT to USDC rate under a conversion policy
+fn setup_conversion_test(
+    policies: Vec<PolicyData>,
+) -> Result<ConversionTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let converts = policies
+        .iter()
+        .any(|policy| matches!(policy, PolicyData::Conversion(_)));
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDT_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    // USDC is only the settlement mint, not an accepted currency
+    let (conversion_rate_pda, _) = if converts {
+        assert_post_conversion_rate(
+            &mut context,
+            &operator_authority,
+            &operator_pda,
+            &merchant_operator_config_pda,
+            &USDT_MINT,
+            &USDC_MINT,
+            1,
+            1,
+        )?
+    } else {
+        find_conversion_rate_pda(&merchant_operator_config_pda, &USDT_MINT, &USDC_MINT)
+    };
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDT_MINT,
+        1,
+        1_000_000,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+
+    get_or_create_associated_token_account(&mut context, &settlement_wallet.pubkey(), &USDC_MINT);
+    get_or_create_associated_token_account(&mut context, &operator_authority.pubkey(), &USDT_MINT);
+
+    Ok(ConversionTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        conversion_rate_pda,
+        payment_pda,
+    })
+}
+
+fn conversion_policy(max_slippage_bps: u16) -> PolicyData {
+    PolicyData::Conversion(ConversionPolicy {
+        settlement_mint: USDC_MINT,
+        swap_adapter: SwapAdapter::JupiterV6,
+        max_slippage_bps,
+    })
+}
+
+fn clear_payment_with_conversion_instruction(
+    setup: &ConversionTestSetup,
+    settlement_mint: &Pubkey,
+    swap_program: &Pubkey,
+    swap_data: &[u8],
+    quoted_amount_out: u64,
+) -> Instruction {
+    // The escrow ATA as a stand-in route; the program rejects the swap before invoking it
+    let swap_accounts = [
+        AccountMeta::new_readonly(setup.merchant_pda, false),
+        AccountMeta::new(
+            get_associated_token_address(&setup.merchant_pda, &USDT_MINT),
+            false,
+        ),
+    ];
+
+    ClearPaymentWithConversionBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDT_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDT_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDT_MINT,
+        ))
+        .settlement_mint(*settlement_mint)
+        .conversion_rate(setup.conversion_rate_pda)
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            settlement_mint,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDT_MINT,
+        ))
+        .swap_program(*swap_program)
+        .quoted_amount_out(quoted_amount_out)
+        .num_swap_accounts(swap_accounts.len() as u8)
+        .swap_data(swap_data.to_vec())
+        .add_remaining_accounts(&swap_accounts)
+        .instruction()
+}
+
+#[tokio::test]
+async fn test_clear_payment_with_conversion_without_policy_fails() {
+    let mut setup = setup_conversion_test(vec![]).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    let instruction = clear_payment_with_conversion_instruction(
+        &setup,
+        &USDC_MINT,
+        &JUPITER_V6_PROGRAM_ID,
+        &JUPITER_ROUTE,
+        QUOTED_AMOUNT_OUT,
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, CONVERSION_POLICY_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_with_conversion_wrong_settlement_mint_fails() {
+    let mut setup = setup_conversion_test(vec![conversion_policy(100)]).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    // Settling into the payment mint itself is not a conversion
+    let instruction = clear_payment_with_conversion_instruction(
+        &setup,
+        &USDT_MINT,
+        &JUPITER_V6_PROGRAM_ID,
+        &JUPITER_ROUTE,
+        QUOTED_AMOUNT_OUT,
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, INVALID_MINT_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_with_conversion_unsupported_swap_program_fails() {
+    let mut setup = setup_conversion_test(vec![conversion_policy(100)]).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    let instruction = clear_payment_with_conversion_instruction(
+        &setup,
+        &USDC_MINT,
+        &spl_token::ID,
+        &JUPITER_ROUTE,
+        QUOTED_AMOUNT_OUT,
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, INVALID_SWAP_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_with_conversion_exact_out_route_fails() {
+    let mut setup = setup_conversion_test(vec![conversion_policy(100)]).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    let instruction = clear_payment_with_conversion_instruction(
+        &setup,
+        &USDC_MINT,
+        &JUPITER_V6_PROGRAM_ID,
+        &JUPITER_EXACT_OUT_ROUTE,
+        QUOTED_AMOUNT_OUT,
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, INVALID_SWAP_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_with_conversion_quote_below_rate_fails() {
+    let mut setup = setup_conversion_test(vec![conversion_policy(100)]).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    // A quote below the merchant amount at the posted rate would let the swap undersell it
+    let instruction = clear_payment_with_conversion_instruction(
+        &setup,
+        &USDC_MINT,
+        &JUPITER_V6_PROGRAM_ID,
+        &JUPITER_ROUTE,
+        QUOTED_AMOUNT_OUT - 1,
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, QUOTE_BELOW_CONVERSION_RATE_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_config_with_invalid_conversion_policy_fails() {
+    let mut setup = setup_conversion_test(vec![]).unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    let result = assert_get_or_create_merchant_operator_config(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        2u32,
+        500u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![conversion_policy(10_001)],
+        vec![USDT_MINT],
+        true,
+        false,
+    )
+    .map(|_| ());
+
+    assert_program_error(result, INVALID_CONVERSION_POLICY_ERROR);
+}
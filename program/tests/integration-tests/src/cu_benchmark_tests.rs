@@ -0,0 +1,471 @@
+//! Compute unit and binary size benchmarks, run with `cargo test --features cu-benchmarks`.
+//!
+//! Each scenario profiles one instruction in a representative setup and compares its compute
+//! units against `budget_baseline.txt`, which also records the size of the compiled program.
+//! The run fails when a scenario uses more than `CU_REGRESSION_THRESHOLD_PERCENT` above its
+//! baseline, when the program grows more than `SIZE_REGRESSION_THRESHOLD_PERCENT`, or when any
+//! benchmarked instruction exceeds `MAX_INSTRUCTION_CU`; entries without a baseline are only
+//! reported.
+//!
+//! After an intentional change, rerecord the baseline with `cargo xtask update-baselines`, or:
+//! `UPDATE_BUDGET_BASELINE=1 cargo test --features cu-benchmarks cu_benchmark`
+
+use std::{collections::BTreeMap, fmt::Write, fs, path::PathBuf};
+
+use crate::{
+    state_utils::*,
+    utils::{
+        find_escrow_ata, find_escrow_authority_pda, get_or_create_associated_token_account,
+        program_so, ComputeUnitSample, TestContext, TestContextBuilder, DAYS_TO_CLOSE, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::ClearPaymentBuilder,
+    split_settlement::{SplitSettlement, MAX_SPLIT_DESTINATIONS},
+    types::{FeeTier, FeeTierPolicy, FeeType, PolicyData, RefundPolicy},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+/// Largest increase over the baseline, in percent, before the run fails.
+const CU_REGRESSION_THRESHOLD_PERCENT: u64 = 5;
+
+/// Largest growth of the compiled program over the baseline, in percent, before the run fails.
+const SIZE_REGRESSION_THRESHOLD_PERCENT: u64 = 2;
+
+/// Compute units any one benchmarked instruction may use: the runtime's default budget for an
+/// instruction, so no flow needs a `SetComputeUnitLimit` on its happy path.
+const MAX_INSTRUCTION_CU: u64 = 200_000;
+
+/// Baseline entry for the size of `commerce_program.so`, in bytes.
+const PROGRAM_SIZE_ENTRY: &str = "program_size_bytes";
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+
+struct BenchmarkSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up an operator and merchant, and a config with `policies` unless
+// `policies` is `None`
+fn setup_benchmark(
+    policies: Option<Vec<PolicyData>>,
+) -> Result<BenchmarkSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+    get_or_create_associated_token_account(&mut context, &operator_authority.pubkey(), &USDC_MINT);
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let mut setup = BenchmarkSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda: Pubkey::default(),
+    };
+    if let Some(policies) = policies {
+        setup.merchant_operator_config_pda = create_config(&mut setup, policies)?;
+    }
+
+    Ok(setup)
+}
+
+fn create_config(
+    setup: &mut BenchmarkSetup,
+    policies: Vec<PolicyData>,
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        1u32,
+        250u64, // 2.5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+    Ok(merchant_operator_config_pda)
+}
+
+fn make_payment(setup: &mut BenchmarkSetup) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let (payment_pda, _) = assert_make_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &setup.buyer,
+        &setup.merchant_operator_config_pda,
+        &setup.operator_pda,
+        &USDC_MINT,
+        1,
+        PAYMENT_AMOUNT,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+    Ok(payment_pda)
+}
+
+fn clear_payment(
+    setup: &mut BenchmarkSetup,
+    payment_pda: &Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    assert_clear_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &setup.buyer,
+        payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+}
+
+fn fee_tier_policy() -> PolicyData {
+    let tiers = std::array::from_fn(|i| FeeTier {
+        threshold: i as u64 * 100_000,
+        fee: 300 - i as u64 * 50,
+        fee_type: FeeType::Bps,
+    });
+    PolicyData::FeeTier(FeeTierPolicy {
+        num_tiers: tiers.len() as u8,
+        tiers,
+    })
+}
+
+fn split_settlement() -> SplitSettlement {
+    SplitSettlement::new((0..MAX_SPLIT_DESTINATIONS).map(|_| (Pubkey::new_unique(), 2_000)))
+}
+
+fn profile(
+    setup: &mut BenchmarkSetup,
+    f: impl FnOnce(&mut BenchmarkSetup) -> Result<(), Box<dyn std::error::Error>>,
+) -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>> {
+    setup.context.start_profiling();
+    let result = f(setup);
+    let samples = setup.context.stop_profiling();
+    result.map(|_| samples)
+}
+
+fn bench_initialize_merchant_operator_config(
+) -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>> {
+    let mut setup = setup_benchmark(None)?;
+    profile(&mut setup, |setup| {
+        create_config(setup, vec![fee_tier_policy()]).map(|_| ())
+    })
+}
+
+fn bench_make_payment() -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>> {
+    let mut setup = setup_benchmark(Some(vec![]))?;
+    profile(&mut setup, |setup| make_payment(setup).map(|_| ()))
+}
+
+fn bench_clear_payment() -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>> {
+    let mut setup = setup_benchmark(Some(vec![]))?;
+    let payment_pda = make_payment(&mut setup)?;
+    profile(&mut setup, |setup| clear_payment(setup, &payment_pda))
+}
+
+fn bench_clear_payment_fee_tier() -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>> {
+    let mut setup = setup_benchmark(Some(vec![fee_tier_policy()]))?;
+    let payment_pda = make_payment(&mut setup)?;
+    profile(&mut setup, |setup| clear_payment(setup, &payment_pda))
+}
+
+fn bench_clear_payment_split_settlement(
+) -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>> {
+    let split = split_settlement();
+    let mut setup = setup_benchmark(Some(vec![PolicyData::SplitSettlement(split.policy())]))?;
+    let payment_pda = make_payment(&mut setup)?;
+    for (wallet, _) in split.destinations() {
+        get_or_create_associated_token_account(&mut setup.context, wallet, &USDC_MINT);
+    }
+
+    let merchant = commerce_program_client::Merchant::from_bytes(
+        &setup
+            .context
+            .get_account_data(&setup.merchant_pda)
+            .expect("Merchant should exist"),
+    )?;
+    let instruction = ClearPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &merchant.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .add_remaining_accounts(&split.remaining_accounts(&USDC_MINT, &spl_token::ID))
+        .instruction();
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    profile(&mut setup, |setup| {
+        setup
+            .context
+            .send_transaction_with_signers(instruction, &[&operator_authority])
+    })
+}
+
+fn bench_refund_payment() -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>> {
+    let mut setup = setup_benchmark(Some(vec![PolicyData::Refund(RefundPolicy {
+        max_amount: PAYMENT_AMOUNT,
+        max_time_after_purchase: 86_400,
+    })]))?;
+    let payment_pda = make_payment(&mut setup)?;
+    let operator_authority = setup.operator_authority.insecure_clone();
+    profile(&mut setup, |setup| {
+        assert_refund_payment(
+            &mut setup.context,
+            &operator_authority,
+            &operator_authority,
+            &setup.buyer,
+            &payment_pda,
+            &USDC_MINT,
+            &setup.merchant_operator_config_pda,
+            false,
+        )
+    })
+}
+
+fn bench_close_payment() -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>> {
+    let mut setup = setup_benchmark(Some(vec![]))?;
+    let payment_pda = make_payment(&mut setup)?;
+    clear_payment(&mut setup, &payment_pda)?;
+    setup
+        .context
+        .advance_clock(DAYS_TO_CLOSE as i64 * 86_400 + 1);
+
+    let payer = setup.context.payer.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    profile(&mut setup, |setup| {
+        assert_close_payment(
+            &mut setup.context,
+            &payer,
+            &payment_pda,
+            &setup.buyer.pubkey(),
+            &setup.merchant_pda,
+            &setup.operator_pda,
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+            &operator_authority,
+            false,
+        )
+    })
+}
+
+type Scenario = fn() -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>>;
+
+const SCENARIOS: &[(&str, Scenario)] = &[
+    (
+        "initialize_merchant_operator_config_fee_tier",
+        bench_initialize_merchant_operator_config,
+    ),
+    ("make_payment", bench_make_payment),
+    ("clear_payment", bench_clear_payment),
+    ("clear_payment_fee_tier", bench_clear_payment_fee_tier),
+    (
+        "clear_payment_split_settlement",
+        bench_clear_payment_split_settlement,
+    ),
+    ("refund_payment", bench_refund_payment),
+    ("close_payment", bench_close_payment),
+];
+
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("budget_baseline.txt")
+}
+
+/// Entry values from `<entry> <value>` lines, skipping blank lines and `#` comments.
+fn parse_baseline(baseline: &str) -> BTreeMap<String, u64> {
+    baseline
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (entry, value) = line
+                .split_once(char::is_whitespace)
+                .unwrap_or_else(|| panic!("Malformed baseline line: {line}"));
+            let value = value
+                .trim()
+                .parse()
+                .unwrap_or_else(|_| panic!("Malformed baseline line: {line}"));
+            (entry.to_string(), value)
+        })
+        .collect()
+}
+
+fn render_baseline(results: &[(&str, u64)]) -> String {
+    let mut baseline = String::from(
+        "# Program size in bytes and compute units per benchmark scenario, see\n\
+         # src/cu_benchmark_tests.rs. Rerecord with: cargo xtask update-baselines\n",
+    );
+    for (entry, value) in results {
+        writeln!(baseline, "{entry} {value}").unwrap();
+    }
+    baseline
+}
+
+/// Whether `value` exceeds `baseline` by more than `threshold_percent`.
+fn is_regression(baseline: u64, value: u64, threshold_percent: u64) -> bool {
+    value as u128 * 100 > baseline as u128 * (100 + threshold_percent) as u128
+}
+
+fn threshold_percent(entry: &str) -> u64 {
+    if entry == PROGRAM_SIZE_ENTRY {
+        SIZE_REGRESSION_THRESHOLD_PERCENT
+    } else {
+        CU_REGRESSION_THRESHOLD_PERCENT
+    }
+}
+
+#[tokio::test]
+async fn test_cu_benchmark() {
+    let mut over_budget = Vec::new();
+    let mut results: Vec<(&str, u64)> = vec![(PROGRAM_SIZE_ENTRY, program_so().len() as u64)];
+    results.extend(SCENARIOS.iter().map(|(scenario, bench)| {
+        let samples = bench().unwrap_or_else(|e| panic!("{scenario} failed: {e}"));
+        assert!(
+            !samples.is_empty(),
+            "{scenario} sent no program instruction"
+        );
+        over_budget.extend(
+            samples
+                .iter()
+                .filter(|sample| sample.cu_consumed > MAX_INSTRUCTION_CU)
+                .map(|sample| format!("{scenario}/{}", sample.operation)),
+        );
+        (
+            *scenario,
+            samples.iter().map(|sample| sample.cu_consumed).sum(),
+        )
+    }));
+
+    assert!(
+        over_budget.is_empty(),
+        "Instructions used more than {MAX_INSTRUCTION_CU} compute units: {over_budget:?}"
+    );
+
+    if std::env::var_os("UPDATE_BUDGET_BASELINE").is_some() {
+        fs::write(baseline_path(), render_baseline(&results)).unwrap();
+        return;
+    }
+
+    let baseline = parse_baseline(&fs::read_to_string(baseline_path()).unwrap_or_default());
+
+    let mut report = String::new();
+    let mut regressions = Vec::new();
+    for (entry, value) in &results {
+        let unit = if *entry == PROGRAM_SIZE_ENTRY {
+            "B"
+        } else {
+            "CU"
+        };
+        match baseline.get(*entry) {
+            Some(&expected) => {
+                let delta = *value as i128 - expected as i128;
+                writeln!(
+                    report,
+                    "{entry:<48} {value:>8} {unit} ({delta:+} vs {expected})"
+                )
+                .unwrap();
+                if is_regression(expected, *value, threshold_percent(entry)) {
+                    regressions.push(*entry);
+                }
+            }
+            None => writeln!(report, "{entry:<48} {value:>8} {unit} (no baseline)").unwrap(),
+        }
+    }
+    println!("{report}");
+
+    assert!(
+        regressions.is_empty(),
+        "Regressed past the baseline in {regressions:?} (compute units allow \
+         {CU_REGRESSION_THRESHOLD_PERCENT}%, program size {SIZE_REGRESSION_THRESHOLD_PERCENT}%); \
+         run `cargo xtask update-baselines` if intended"
+    );
+}
+
+#[test]
+fn test_parse_baseline() {
+    let results = [
+        (PROGRAM_SIZE_ENTRY, 123_456),
+        ("make_payment", 12_345),
+        ("clear_payment", 23_456),
+    ];
+    let baseline = parse_baseline(&render_baseline(&results));
+
+    assert_eq!(baseline.len(), 3);
+    assert_eq!(baseline[PROGRAM_SIZE_ENTRY], 123_456);
+    assert_eq!(baseline["make_payment"], 12_345);
+    assert_eq!(baseline["clear_payment"], 23_456);
+}
+
+#[test]
+fn test_is_regression() {
+    assert!(!is_regression(10_000, 10_000, 5));
+    assert!(!is_regression(10_000, 10_500, 5));
+    assert!(is_regression(10_000, 10_501, 5));
+    assert!(!is_regression(10_000, 9_000, 5));
+    assert!(is_regression(0, 1, 5));
+    assert!(is_regression(10_000, 10_201, 2));
+}
+
+#[test]
+fn test_threshold_percent() {
+    assert_eq!(
+        threshold_percent(PROGRAM_SIZE_ENTRY),
+        SIZE_REGRESSION_THRESHOLD_PERCENT
+    );
+    assert_eq!(
+        threshold_percent("make_payment"),
+        CU_REGRESSION_THRESHOLD_PERCENT
+    );
+}
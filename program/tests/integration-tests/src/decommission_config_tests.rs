@@ -0,0 +1,447 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_config_decommissioned_event_present,
+        assert_merchant_operator_config_closed_event_present, assert_program_error,
+        find_escrow_ata, find_escrow_authority_pda, get_or_create_associated_token_account,
+        set_token_balance, TestContext, CONFIG_DECOMMISSIONED_ERROR,
+        CONFIG_HAS_OPEN_PAYMENTS_ERROR, CONFIG_HAS_PAYMENT_ACCOUNTS_ERROR,
+        CONFIG_NOT_DECOMMISSIONED_ERROR, DAYS_TO_CLOSE, OPERATOR_OWNER_MISMATCH_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    accounts::MerchantOperatorConfig,
+    checkout::TOKEN_PROGRAM_ID,
+    decommission::{Decommission, DecommissionStep, EscrowAta, PaymentAccount},
+    escrow_audit::token_account_amount,
+    preview::decode_open_payments,
+    types::{FeeType, PolicyData, SettlementPolicy},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const AMOUNT: u64 = 1_000_000;
+
+struct DecommissionTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+    decommission: Decommission,
+}
+
+impl DecommissionTestSetup {
+    fn config(&mut self) -> Option<MerchantOperatorConfig> {
+        self.context
+            .get_account(&self.merchant_operator_config_pda)
+            .map(|account| MerchantOperatorConfig::from_bytes(&account.data).unwrap())
+    }
+
+    fn escrows(&mut self) -> Vec<EscrowAta> {
+        let escrow_ata = find_escrow_ata(&self.merchant_operator_config_pda, &USDC_MINT);
+        let balance = self
+            .context
+            .get_account(&escrow_ata)
+            .and_then(|account| token_account_amount(&account.data));
+        let open_payments = self
+            .context
+            .get_account(&self.merchant_operator_config_pda)
+            .map_or(vec![0], |account| {
+                decode_open_payments(&account.data).unwrap()
+            });
+        vec![EscrowAta::new(
+            &self.merchant_operator_config_pda,
+            USDC_MINT,
+            TOKEN_PROGRAM_ID,
+            balance,
+            open_payments[0],
+        )]
+    }
+
+    fn payments(&mut self) -> Vec<PaymentAccount> {
+        self.context
+            .get_account(&self.payment_pda)
+            .map(|_| PaymentAccount {
+                payment: self.payment_pda,
+                buyer: self.buyer.pubkey(),
+                mint: USDC_MINT,
+                rent_payer: None,
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn next_step(&mut self) -> DecommissionStep {
+        let config = self.config();
+        let escrows = self.escrows();
+        let payments = self.payments();
+        self.decommission
+            .next_step(config.as_ref(), &escrows, &payments)
+    }
+
+    fn make_payment(&mut self, order_id: u32) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+        let operator_authority = self.operator_authority.insecure_clone();
+        let buyer = self.buyer.insecure_clone();
+        assert_make_payment(
+            &mut self.context,
+            &operator_authority,
+            &operator_authority,
+            &buyer,
+            &self.merchant_operator_config_pda,
+            &self.operator_pda,
+            &USDC_MINT,
+            order_id,
+            AMOUNT,
+            true,
+            false,
+            false,
+        )
+    }
+
+    fn clear_payment(&mut self) {
+        let payer = self.context.payer.insecure_clone();
+        let operator_authority = self.operator_authority.insecure_clone();
+        let buyer = self.buyer.insecure_clone();
+        assert_clear_payment(
+            &mut self.context,
+            &payer,
+            &operator_authority,
+            &buyer,
+            &self.payment_pda,
+            &USDC_MINT,
+            &self.merchant_operator_config_pda,
+            false,
+        )
+        .unwrap();
+    }
+
+    fn close_payment(&mut self) {
+        let operator_authority = self.operator_authority.insecure_clone();
+        self.context
+            .advance_clock(DAYS_TO_CLOSE as i64 * 24 * 60 * 60);
+        assert_close_payment(
+            &mut self.context,
+            &operator_authority,
+            &self.payment_pda,
+            &self.buyer.pubkey(),
+            &self.merchant_pda,
+            &self.operator_pda,
+            &self.merchant_operator_config_pda,
+            &USDC_MINT,
+            &operator_authority,
+            false,
+        )
+        .unwrap();
+    }
+
+    fn decommission_config(&mut self) {
+        let operator_authority = self.operator_authority.insecure_clone();
+        self.context
+            .send_transaction_with_signers(
+                self.decommission.decommission_config(),
+                &[&operator_authority],
+            )
+            .expect("Decommission should succeed");
+    }
+}
+
+// Helper function to set up a config without auto settlement and a paid payment in USDC
+fn setup_decommission_test() -> Result<DecommissionTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let decommission = Decommission::new(merchant_authority.pubkey(), operator_authority.pubkey())
+        .settlement_wallet(settlement_wallet.pubkey());
+    assert_eq!(
+        decommission.merchant_operator_config(),
+        merchant_operator_config_pda
+    );
+
+    let mut setup = DecommissionTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda: Pubkey::default(),
+        decommission,
+    };
+    setup.payment_pda = setup.make_payment(1)?.0;
+
+    Ok(setup)
+}
+
+#[tokio::test]
+async fn test_decommission_and_close_config() {
+    let mut setup = setup_decommission_test().unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let config = setup.merchant_operator_config_pda;
+
+    // Stop new payments
+    let DecommissionStep::Decommission(instruction) = setup.next_step() else {
+        panic!("an active config should be decommissioned first");
+    };
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&operator_authority],
+            false,
+        )
+        .unwrap();
+    let decommissioned_at = setup.config().unwrap().decommissioned_at;
+    assert!(decommissioned_at > 0);
+    assert_config_decommissioned_event_present(
+        &metadata,
+        &config,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        &operator_authority.pubkey(),
+        decommissioned_at,
+    );
+
+    let result = setup.make_payment(2).map(|_| ());
+    assert_program_error(result, CONFIG_DECOMMISSIONED_ERROR);
+
+    // The open payment holds the escrow until it clears
+    assert_eq!(
+        setup.next_step(),
+        DecommissionStep::AwaitingSettlement {
+            mints: vec![USDC_MINT],
+            close_escrows: vec![],
+        }
+    );
+    let instruction = setup.decommission.close_merchant_operator_config();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority, &operator_authority]);
+    assert_program_error(result, CONFIG_HAS_OPEN_PAYMENTS_ERROR);
+
+    setup.clear_payment();
+
+    // Close the drained escrow, then the config
+    let DecommissionStep::CloseEscrows(instructions) = setup.next_step() else {
+        panic!("a drained escrow should be closed next");
+    };
+    for instruction in instructions {
+        setup
+            .context
+            .send_transaction_with_signers(instruction, &[&merchant_authority, &operator_authority])
+            .expect("Close escrow ATA should succeed");
+    }
+
+    // The cleared payment's account must close before the config, which ClosePayment needs
+    assert_eq!(config_payment_accounts(&mut setup.context, &config), 1);
+    let instruction = setup.decommission.close_merchant_operator_config();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority, &operator_authority]);
+    assert_program_error(result, CONFIG_HAS_PAYMENT_ACCOUNTS_ERROR);
+
+    let DecommissionStep::ClosePayments {
+        payment_accounts: 1,
+        close_payments,
+    } = setup.next_step()
+    else {
+        panic!("the payment account should be closed once the escrows are");
+    };
+    setup
+        .context
+        .advance_clock(DAYS_TO_CLOSE as i64 * 24 * 60 * 60);
+    for instruction in close_payments {
+        setup
+            .context
+            .send_transaction_with_signers(instruction, &[&operator_authority])
+            .expect("Close payment should succeed");
+    }
+    assert!(setup.context.get_account(&setup.payment_pda).is_none());
+
+    let DecommissionStep::CloseConfig(instruction) = setup.next_step() else {
+        panic!("the config should close once its escrows and payments are closed");
+    };
+    let rent = setup.context.get_account(&config).unwrap().lamports;
+    let merchant_authority_before = setup
+        .context
+        .get_account(&merchant_authority.pubkey())
+        .map_or(0, |account| account.lamports);
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&merchant_authority, &operator_authority],
+            false,
+        )
+        .unwrap();
+    assert_merchant_operator_config_closed_event_present(
+        &metadata,
+        &config,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        rent,
+    );
+
+    assert!(setup.context.get_account(&config).is_none());
+    let merchant_authority_after = setup
+        .context
+        .get_account(&merchant_authority.pubkey())
+        .map_or(0, |account| account.lamports);
+    assert_eq!(merchant_authority_after, merchant_authority_before + rent);
+    assert_eq!(setup.next_step(), DecommissionStep::Closed);
+}
+
+#[tokio::test]
+async fn test_refund_after_decommission() {
+    let mut setup = setup_decommission_test().unwrap();
+    setup.decommission_config();
+
+    let payer = setup.context.payer.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_refund_payment(
+        &mut setup.context,
+        &payer,
+        &operator_authority,
+        &buyer,
+        &setup.payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+    .expect("Open payments still refund after decommissioning");
+
+    assert!(matches!(
+        setup.next_step(),
+        DecommissionStep::CloseEscrows(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_decommission_by_merchant() {
+    let mut setup = setup_decommission_test().unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let decommission = setup.decommission.decommissioned_by_merchant();
+
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            decommission.decommission_config(),
+            &[&merchant_authority],
+            false,
+        )
+        .unwrap();
+    let decommissioned_at = setup.config().unwrap().decommissioned_at;
+    assert_config_decommissioned_event_present(
+        &metadata,
+        &setup.merchant_operator_config_pda,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        &merchant_authority.pubkey(),
+        decommissioned_at,
+    );
+
+    // Decommissioning is final
+    setup.context.advance_clock(60);
+    let result = setup
+        .context
+        .send_transaction_with_signers(decommission.decommission_config(), &[&merchant_authority]);
+    assert_program_error(result, CONFIG_DECOMMISSIONED_ERROR);
+    assert_eq!(setup.config().unwrap().decommissioned_at, decommissioned_at);
+}
+
+#[tokio::test]
+async fn test_decommission_wrong_authority_fails() {
+    let mut setup = setup_decommission_test().unwrap();
+    let imposter = Keypair::new();
+    let decommission = Decommission {
+        decommission_authority: imposter.pubkey(),
+        ..setup.decommission
+    };
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(decommission.decommission_config(), &[&imposter]);
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_close_active_config_fails() {
+    let mut setup = setup_decommission_test().unwrap();
+    setup.clear_payment();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    let instruction = setup.decommission.close_merchant_operator_config();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority, &operator_authority]);
+    assert_program_error(result, CONFIG_NOT_DECOMMISSIONED_ERROR);
+}
+
+#[tokio::test]
+async fn test_close_config_with_dust_in_escrow() {
+    let mut setup = setup_decommission_test().unwrap();
+    setup.clear_payment();
+    setup.close_payment();
+    setup.decommission_config();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    // Tokens sent straight to the escrow open no payment, so they do not hold up the close
+    let config = setup.merchant_operator_config_pda;
+    set_token_balance(
+        &mut setup.context,
+        &find_escrow_ata(&config, &USDC_MINT),
+        &USDC_MINT,
+        &find_escrow_authority_pda(&config, &USDC_MINT).0,
+        5,
+    );
+
+    let instruction = setup.decommission.close_merchant_operator_config();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority, &operator_authority])
+        .expect("Close config should succeed");
+    assert!(setup.context.get_account(&config).is_none());
+}
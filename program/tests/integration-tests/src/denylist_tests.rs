@@ -0,0 +1,234 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        set_token_balance, TestContext, TestContextBuilder, DAYS_TO_CLOSE,
+        DENYLIST_PROOF_REQUIRED_ERROR, INVALID_DENYLIST_POLICY_ERROR, INVALID_DENYLIST_PROOF_ERROR,
+        USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    denylist::DenylistTree,
+    instructions::MakePaymentBuilder,
+    types::{DenylistPolicy, DenylistProof, FeeType, PolicyData},
+    Payment,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+
+struct DenylistTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a config with the given policies and a funded buyer
+fn setup_denylist_test(
+    buyer: Keypair,
+    policies: Vec<PolicyData>,
+) -> Result<DenylistTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &buyer_ata,
+        &USDC_MINT,
+        &buyer.pubkey(),
+        PAYMENT_AMOUNT,
+    );
+
+    Ok(DenylistTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn denylist_policy(tree: &DenylistTree) -> Vec<PolicyData> {
+    vec![PolicyData::Denylist(tree.policy())]
+}
+
+fn make_payment_instruction(
+    setup: &DenylistTestSetup,
+    denylist_proof: Option<DenylistProof>,
+) -> (Instruction, Pubkey) {
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        1,
+    );
+
+    let mut builder = MakePaymentBuilder::new();
+    if let Some(denylist_proof) = denylist_proof {
+        builder.denylist_proof(denylist_proof);
+    }
+    let instruction = builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(1)
+        .amount(PAYMENT_AMOUNT)
+        .idempotency_key([0; 16])
+        .instruction();
+
+    (instruction, payment_pda)
+}
+
+fn send_make_payment(
+    setup: &mut DenylistTestSetup,
+    denylist_proof: Option<DenylistProof>,
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let (instruction, payment_pda) = make_payment_instruction(setup, denylist_proof);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])?;
+    Ok(payment_pda)
+}
+
+#[tokio::test]
+async fn test_make_payment_allowed_buyer_with_proof() {
+    let buyer = Keypair::new();
+    let tree = DenylistTree::new((0..3).map(|_| Pubkey::new_unique()));
+    let proof = tree.proof(&buyer.pubkey()).unwrap();
+    let mut setup = setup_denylist_test(buyer, denylist_policy(&tree)).unwrap();
+
+    let payment_pda = send_make_payment(&mut setup, Some(proof)).unwrap();
+
+    let payment_account = setup.context.get_account(&payment_pda).unwrap();
+    let payment = Payment::from_bytes(&payment_account.data).unwrap();
+    assert_eq!(payment.amount, PAYMENT_AMOUNT);
+}
+
+#[tokio::test]
+async fn test_make_payment_banned_buyer_fails() {
+    let buyer = Keypair::new();
+    let allowed = Pubkey::new_unique();
+    let tree = DenylistTree::new([buyer.pubkey(), Pubkey::new_unique()]);
+    // A banned buyer has no gap of their own, so borrow another buyer's proof
+    let proof = tree.proof(&allowed).unwrap();
+    let mut setup = setup_denylist_test(buyer, denylist_policy(&tree)).unwrap();
+
+    let result = send_make_payment(&mut setup, Some(proof));
+
+    assert_program_error(result.map(|_| ()), INVALID_DENYLIST_PROOF_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_denylist_without_proof_fails() {
+    let buyer = Keypair::new();
+    let tree = DenylistTree::new([Pubkey::new_unique()]);
+    let mut setup = setup_denylist_test(buyer, denylist_policy(&tree)).unwrap();
+
+    let result = send_make_payment(&mut setup, None);
+
+    assert_program_error(result.map(|_| ()), DENYLIST_PROOF_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_stale_proof_fails() {
+    let buyer = Keypair::new();
+    let mut tree = DenylistTree::new([Pubkey::new_unique()]);
+    let stale_proof = tree.proof(&buyer.pubkey()).unwrap();
+    tree.insert(Pubkey::new_unique());
+    let mut setup = setup_denylist_test(buyer, denylist_policy(&tree)).unwrap();
+
+    let result = send_make_payment(&mut setup, Some(stale_proof));
+
+    assert_program_error(result.map(|_| ()), INVALID_DENYLIST_PROOF_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_config_invalid_denylist_policy_fails() {
+    let mut setup = setup_denylist_test(Keypair::new(), vec![]).unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    let result = assert_get_or_create_merchant_operator_config(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        2u32,
+        500u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Denylist(DenylistPolicy {
+            root: [0; 32],
+            depth: 0,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )
+    .map(|_| ());
+
+    assert_program_error(result, INVALID_DENYLIST_POLICY_ERROR);
+}
@@ -0,0 +1,308 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        get_or_create_associated_token_account, get_token_balance, idempotency_key,
+        set_token_balance, TestContext, TestContextBuilder, AUTHORIZATION_REQUIRES_ESCROW_ERROR,
+        DAYS_TO_CLOSE, DIRECT_SETTLEMENT_UNSUPPORTED_ERROR, NOT_ENOUGH_ACCOUNT_KEYS_ERROR,
+        USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::MakePaymentBuilder,
+    split_settlement::SplitSettlement,
+    types::{
+        CallbackPolicy, ChargebackPolicy, DirectSettlementPolicy, FeeType, PlatformFeePolicy,
+        PolicyData, Status,
+    },
+    Payment,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const MAX_DIRECT_AMOUNT: u64 = 1_000_000;
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+// 5% operator fee
+const OPERATOR_FEE: u64 = PAYMENT_AMOUNT / 20;
+// 2% platform fee
+const PLATFORM_FEE: u64 = PAYMENT_AMOUNT / 50;
+const MERCHANT_AMOUNT: u64 = PAYMENT_AMOUNT - OPERATOR_FEE - PLATFORM_FEE;
+
+struct DirectSettlementTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    platform: Pubkey,
+}
+
+impl DirectSettlementTestSetup {
+    // The operator's fee ATA, then the platform's settlement ATA
+    fn fee_atas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(
+                get_associated_token_address(&self.operator_authority.pubkey(), &USDC_MINT),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address(&self.platform, &USDC_MINT),
+                false,
+            ),
+        ]
+    }
+
+    fn balance(&mut self, wallet: &Pubkey) -> u64 {
+        let ata = get_associated_token_address(wallet, &USDC_MINT);
+        get_token_balance(&mut self.context, &ata)
+    }
+
+    fn escrow_balance(&mut self) -> u64 {
+        let escrow_ata = find_escrow_ata(&self.merchant_operator_config_pda, &USDC_MINT);
+        get_token_balance(&mut self.context, &escrow_ata)
+    }
+
+    fn payment_status(&mut self, order_id: u32) -> Status {
+        let (payment_pda, _) = find_payment_pda(
+            &self.merchant_operator_config_pda,
+            &self.buyer.pubkey(),
+            &USDC_MINT,
+            order_id,
+        );
+        let payment_account = self
+            .context
+            .get_account(&payment_pda)
+            .expect("Payment should exist");
+        Payment::from_bytes(&payment_account.data)
+            .expect("Should deserialize payment")
+            .status
+    }
+}
+
+// Helper function to set up a config with a 5% operator fee, a 2% platform fee and a direct
+// settlement policy capped at MAX_DIRECT_AMOUNT, plus `extra_policies`
+fn setup_direct_settlement_test(
+    extra_policies: Vec<PolicyData>,
+) -> Result<DirectSettlementTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+    let platform = Pubkey::new_unique();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let mut policies = vec![
+        PolicyData::PlatformFee(PlatformFeePolicy {
+            platform,
+            fee: 200, // 2%
+            fee_type: FeeType::Bps,
+        }),
+        PolicyData::DirectSettlement(DirectSettlementPolicy {
+            max_amount: MAX_DIRECT_AMOUNT,
+        }),
+    ];
+    policies.extend(extra_policies);
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    get_or_create_associated_token_account(&mut context, &platform, &USDC_MINT);
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &buyer_ata,
+        &USDC_MINT,
+        &buyer.pubkey(),
+        PAYMENT_AMOUNT * 10,
+    );
+
+    Ok(DirectSettlementTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        platform,
+    })
+}
+
+fn make_payment_instruction(
+    setup: &DirectSettlementTestSetup,
+    order_id: u32,
+    amount: u64,
+    authorize: bool,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+    let mut builder = MakePaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(amount)
+        .authorize(authorize)
+        .add_remaining_accounts(&remaining_accounts);
+    builder.instruction()
+}
+
+fn make_payment(
+    setup: &mut DirectSettlementTestSetup,
+    order_id: u32,
+    amount: u64,
+    authorize: bool,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction =
+        make_payment_instruction(setup, order_id, amount, authorize, remaining_accounts);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_make_payment_direct_settlement() {
+    let mut setup = setup_direct_settlement_test(vec![]).unwrap();
+    let fee_atas = setup.fee_atas();
+
+    make_payment(&mut setup, 1, PAYMENT_AMOUNT, false, fee_atas)
+        .expect("Should settle the payment directly");
+
+    let platform = setup.platform;
+    let settlement_wallet = setup.settlement_wallet;
+    let operator_authority = setup.operator_authority.pubkey();
+
+    assert_eq!(setup.payment_status(1), Status::Cleared);
+    assert_eq!(setup.balance(&operator_authority), OPERATOR_FEE);
+    assert_eq!(setup.balance(&platform), PLATFORM_FEE);
+    assert_eq!(setup.balance(&settlement_wallet), MERCHANT_AMOUNT);
+    assert_eq!(setup.escrow_balance(), 0);
+}
+
+#[tokio::test]
+async fn test_make_payment_direct_settlement_above_max_amount_escrows() {
+    let mut setup = setup_direct_settlement_test(vec![]).unwrap();
+    let fee_atas = setup.fee_atas();
+
+    make_payment(&mut setup, 1, MAX_DIRECT_AMOUNT + 1, false, fee_atas)
+        .expect("Should escrow the payment");
+
+    let platform = setup.platform;
+    assert_eq!(setup.payment_status(1), Status::Paid);
+    assert_eq!(setup.escrow_balance(), MAX_DIRECT_AMOUNT + 1);
+    assert_eq!(setup.balance(&platform), 0);
+}
+
+#[tokio::test]
+async fn test_make_payment_direct_settlement_missing_ata_fails() {
+    let mut setup = setup_direct_settlement_test(vec![]).unwrap();
+    // The operator's fee ATA without the platform's settlement ATA
+    let fee_atas = setup.fee_atas()[..1].to_vec();
+
+    let result = make_payment(&mut setup, 1, PAYMENT_AMOUNT, false, fee_atas);
+    assert_program_error(result, NOT_ENOUGH_ACCOUNT_KEYS_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_direct_settlement_authorize_fails() {
+    let mut setup = setup_direct_settlement_test(vec![]).unwrap();
+    let fee_atas = setup.fee_atas();
+
+    let result = make_payment(&mut setup, 1, PAYMENT_AMOUNT, true, fee_atas);
+    assert_program_error(result, AUTHORIZATION_REQUIRES_ESCROW_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_direct_settlement_with_split_settlement_fails() {
+    let split =
+        SplitSettlement::new([(Pubkey::new_unique(), 9_000), (Pubkey::new_unique(), 1_000)]);
+    let mut setup =
+        setup_direct_settlement_test(vec![PolicyData::SplitSettlement(split.policy())]).unwrap();
+    let fee_atas = setup.fee_atas();
+
+    let result = make_payment(&mut setup, 1, PAYMENT_AMOUNT, false, fee_atas);
+    assert_program_error(result, DIRECT_SETTLEMENT_UNSUPPORTED_ERROR);
+}
+
+#[tokio::test]
+async fn test_direct_settlement_with_clearing_policies_fails() {
+    // Directly settled payments are never cleared, so a chargeback window or an on_clear
+    // callback would never apply to them
+    let chargeback = PolicyData::Chargeback(ChargebackPolicy {
+        window_seconds: 24 * 60 * 60,
+        arbiter: Pubkey::new_unique(),
+    });
+    let result = setup_direct_settlement_test(vec![chargeback]).map(|_| ());
+    assert_program_error(result, DIRECT_SETTLEMENT_UNSUPPORTED_ERROR);
+
+    let callback = PolicyData::Callback(CallbackPolicy {
+        program: Pubkey::new_unique(),
+        on_clear: true,
+        on_refund: false,
+    });
+    let result = setup_direct_settlement_test(vec![callback]).map(|_| ());
+    assert_program_error(result, DIRECT_SETTLEMENT_UNSUPPORTED_ERROR);
+}
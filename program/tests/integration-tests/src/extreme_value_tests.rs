@@ -0,0 +1,214 @@
+//! Extreme-value tests, run with `cargo test --features extreme-values`.
+//!
+//! These push u64::MAX amounts and far-future clocks through the processors to check that
+//! fee and policy-window arithmetic neither overflows nor wraps into the wrong answer.
+
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, get_or_create_associated_token_account, TestContext,
+        CHARGEBACK_WINDOW_ACTIVE_ERROR, DAYS_TO_CLOSE, SETTLEMENT_TOO_EARLY_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::types::{
+    ChargebackPolicy, FeeType, PolicyData, RefundPolicy, SettlementPolicy,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+struct ExtremeValueSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up an operator, merchant and config ready for payments
+fn setup_extreme_value_test(
+    operator_fee: u64,
+    fee_type: FeeType,
+    policies: Vec<PolicyData>,
+) -> Result<ExtremeValueSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        operator_fee,
+        fee_type,
+        0,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    Ok(ExtremeValueSetup {
+        context,
+        operator_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn make_payment(setup: &mut ExtremeValueSetup, amount: u64) -> Pubkey {
+    let (payment_pda, _) = assert_make_payment(
+        &mut setup.context,
+        &setup.operator_authority,
+        &setup.operator_authority,
+        &setup.buyer,
+        &setup.merchant_operator_config_pda,
+        &setup.operator_pda,
+        &USDC_MINT,
+        1,
+        amount,
+        true,
+        false,
+        false,
+    )
+    .expect("Should make payment");
+    payment_pda
+}
+
+fn clear_payment(
+    setup: &mut ExtremeValueSetup,
+    payment_pda: &Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    assert_clear_payment(
+        &mut setup.context,
+        &setup.operator_authority,
+        &setup.operator_authority,
+        &setup.buyer,
+        payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+}
+
+#[tokio::test]
+async fn test_clear_max_amount_payment_with_bps_fee() {
+    let mut setup = setup_extreme_value_test(250, FeeType::Bps, vec![]).unwrap();
+    let payment_pda = make_payment(&mut setup, u64::MAX);
+
+    // The fee is 2.5% of u64::MAX rather than an overflow error
+    clear_payment(&mut setup, &payment_pda).expect("Should clear a u64::MAX payment");
+}
+
+#[tokio::test]
+async fn test_refund_max_amount_payment_with_unbounded_window_far_future() {
+    let policies = vec![PolicyData::Refund(RefundPolicy {
+        max_amount: u64::MAX,
+        max_time_after_purchase: u64::MAX,
+    })];
+    let mut setup = setup_extreme_value_test(250, FeeType::Bps, policies).unwrap();
+    let payment_pda = make_payment(&mut setup, u64::MAX);
+
+    // A window longer than i64::MAX seconds must not wrap into an expired one
+    setup.context.advance_clock(i64::MAX);
+
+    assert_refund_payment(
+        &mut setup.context,
+        &setup.operator_authority,
+        &setup.operator_authority,
+        &setup.buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+    .expect("Should refund within an unbounded window");
+}
+
+#[tokio::test]
+async fn test_clear_payment_at_max_timestamp_settlement_too_early_fails() {
+    let policies = vec![PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: 0,
+        settlement_frequency_hours: u32::MAX,
+        auto_settle: false,
+    })];
+    let mut setup = setup_extreme_value_test(250, FeeType::Bps, policies).unwrap();
+
+    // The settlement window ends past i64::MAX, so it is still open at the clock's limit
+    setup.context.advance_clock(i64::MAX);
+    let payment_pda = make_payment(&mut setup, 1_000_000);
+
+    let result = clear_payment(&mut setup, &payment_pda);
+    assert_program_error(result, SETTLEMENT_TOO_EARLY_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_far_future_settlement_frequency_success() {
+    let policies = vec![PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: 0,
+        settlement_frequency_hours: u32::MAX,
+        auto_settle: false,
+    })];
+    let mut setup = setup_extreme_value_test(250, FeeType::Bps, policies).unwrap();
+    let payment_pda = make_payment(&mut setup, u64::MAX);
+
+    setup.context.advance_clock(i64::MAX);
+
+    clear_payment(&mut setup, &payment_pda).expect("Should clear once u32::MAX hours have elapsed");
+}
+
+#[tokio::test]
+async fn test_clear_payment_unbounded_chargeback_window_fails() {
+    let policies = vec![PolicyData::Chargeback(ChargebackPolicy {
+        window_seconds: u64::MAX,
+        arbiter: Keypair::new().pubkey(),
+    })];
+    let mut setup = setup_extreme_value_test(250, FeeType::Bps, policies).unwrap();
+    let payment_pda = make_payment(&mut setup, u64::MAX);
+
+    setup.context.advance_clock(i64::MAX);
+
+    let result = clear_payment(&mut setup, &payment_pda);
+    assert_program_error(result, CHARGEBACK_WINDOW_ACTIVE_ERROR);
+}
+
+#[tokio::test]
+async fn test_close_max_amount_payment_far_future() {
+    let mut setup = setup_extreme_value_test(250, FeeType::Bps, vec![]).unwrap();
+    let payment_pda = make_payment(&mut setup, u64::MAX);
+    clear_payment(&mut setup, &payment_pda).expect("Should clear a u64::MAX payment");
+
+    setup.context.advance_clock(i64::MAX);
+
+    let payer = setup.context.payer.insecure_clone();
+    assert_close_payment(
+        &mut setup.context,
+        &payer,
+        &payment_pda,
+        &setup.buyer.pubkey(),
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        &setup.merchant_operator_config_pda,
+        &USDC_MINT,
+        &setup.operator_authority,
+        false,
+    )
+    .expect("Should close payment at the clock's limit");
+}
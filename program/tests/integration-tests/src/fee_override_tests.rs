@@ -0,0 +1,264 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        get_token_balance, set_token_balance, TestContext, TestContextBuilder, DAYS_TO_CLOSE,
+        FEE_OVERRIDE_EXCEEDS_MAX_ERROR, FEE_OVERRIDE_POLICY_REQUIRED_ERROR,
+        INVALID_FEE_OVERRIDE_POLICY_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::MakePaymentBuilder,
+    types::{FeeOverride, FeeOverridePolicy, FeeType, PolicyData},
+    Payment,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+
+struct FeeOverrideTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a 5% config with the given policies and a funded buyer
+fn setup_fee_override_test(
+    policies: Vec<PolicyData>,
+) -> Result<FeeOverrideTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &buyer_ata,
+        &USDC_MINT,
+        &buyer.pubkey(),
+        PAYMENT_AMOUNT,
+    );
+
+    Ok(FeeOverrideTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn max_fee_policy(max_fee: u64, max_fee_type: FeeType) -> Vec<PolicyData> {
+    vec![PolicyData::FeeOverride(FeeOverridePolicy {
+        max_fee,
+        max_fee_type,
+    })]
+}
+
+fn make_payment_instruction(
+    setup: &FeeOverrideTestSetup,
+    order_id: u32,
+    fee_override: FeeOverride,
+) -> (Instruction, Pubkey) {
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let instruction = MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(order_id)
+        .amount(PAYMENT_AMOUNT)
+        .idempotency_key([0; 16])
+        .fee_override(fee_override)
+        .instruction();
+
+    (instruction, payment_pda)
+}
+
+fn send_make_payment(
+    setup: &mut FeeOverrideTestSetup,
+    fee_override: FeeOverride,
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let (instruction, payment_pda) = make_payment_instruction(setup, 1, fee_override);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])?;
+    Ok(payment_pda)
+}
+
+#[tokio::test]
+async fn test_make_payment_fee_override_used_at_clear() {
+    let mut setup = setup_fee_override_test(max_fee_policy(200, FeeType::Bps)).unwrap();
+
+    let fee_override = FeeOverride {
+        fee: 100,
+        fee_type: FeeType::Bps,
+    };
+    let payment_pda = send_make_payment(&mut setup, fee_override.clone()).unwrap();
+
+    let payment_account = setup.context.get_account(&payment_pda).unwrap();
+    let payment = Payment::from_bytes(&payment_account.data).unwrap();
+    assert!(payment.has_fee_override);
+    assert_eq!(payment.fee_override, fee_override);
+
+    let operator_settlement_ata =
+        get_associated_token_address(&setup.operator_authority.pubkey(), &USDC_MINT);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    let config_pda = setup.merchant_operator_config_pda;
+    assert_clear_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &config_pda,
+        false,
+    )
+    .unwrap();
+
+    // 1% from the override rather than the config's 5%
+    assert_eq!(
+        get_token_balance(&mut setup.context, &operator_settlement_ata),
+        PAYMENT_AMOUNT / 100
+    );
+}
+
+#[tokio::test]
+async fn test_make_payment_fee_override_exceeds_max_fails() {
+    let mut setup = setup_fee_override_test(max_fee_policy(200, FeeType::Bps)).unwrap();
+
+    let result = send_make_payment(
+        &mut setup,
+        FeeOverride {
+            fee: 201,
+            fee_type: FeeType::Bps,
+        },
+    );
+
+    assert_program_error(result.map(|_| ()), FEE_OVERRIDE_EXCEEDS_MAX_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_fixed_fee_override_bounded_by_bps_max() {
+    let mut setup = setup_fee_override_test(max_fee_policy(200, FeeType::Bps)).unwrap();
+
+    // 2% of the payment is the most a fixed override may charge
+    let result = send_make_payment(
+        &mut setup,
+        FeeOverride {
+            fee: PAYMENT_AMOUNT / 50 + 1,
+            fee_type: FeeType::Fixed,
+        },
+    );
+
+    assert_program_error(result.map(|_| ()), FEE_OVERRIDE_EXCEEDS_MAX_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_fee_override_without_policy_fails() {
+    let mut setup = setup_fee_override_test(vec![]).unwrap();
+
+    let result = send_make_payment(
+        &mut setup,
+        FeeOverride {
+            fee: 100,
+            fee_type: FeeType::Bps,
+        },
+    );
+
+    assert_program_error(result.map(|_| ()), FEE_OVERRIDE_POLICY_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_config_invalid_fee_override_policy_fails() {
+    let mut setup = setup_fee_override_test(vec![]).unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    let result = assert_get_or_create_merchant_operator_config(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        2u32,
+        500u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        max_fee_policy(10_001, FeeType::Bps),
+        vec![USDC_MINT],
+        true,
+        false,
+    )
+    .map(|_| ());
+
+    assert_program_error(result, INVALID_FEE_OVERRIDE_POLICY_ERROR);
+}
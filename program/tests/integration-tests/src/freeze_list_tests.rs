@@ -0,0 +1,395 @@
+use crate::{
+    assertions::assert_operator_freeze_list_account,
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        get_or_create_associated_token_account, idempotency_key, set_token_balance, TestContext,
+        ADDRESS_ALREADY_FROZEN_ERROR, ADDRESS_NOT_FROZEN_ERROR, BUYER_FROZEN_ERROR, DAYS_TO_CLOSE,
+        FREEZE_LIST_REQUIRED_ERROR, OPERATOR_FREEZE_LIST_MISMATCH_ERROR,
+        OPERATOR_OWNER_MISMATCH_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{
+        AddToFreezeListBuilder, InitializeOperatorFreezeListBuilder, MakePaymentBuilder,
+        RefundPaymentBuilder, RemoveFromFreezeListBuilder,
+    },
+    pdas::find_operator_freeze_list_pda,
+    types::{FeeType, PolicyData, SettlementPolicy},
+    Merchant,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const AMOUNT: u64 = 1_000_000;
+
+struct FreezeListTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    freeze_list_pda: Pubkey,
+}
+
+// Helper function to set up a config holding payments in escrow and an empty freeze list
+fn setup_freeze_list_test() -> Result<FreezeListTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let (freeze_list_pda, _) =
+        assert_initialize_operator_freeze_list(&mut context, &operator_authority, &operator_pda)?;
+
+    Ok(FreezeListTestSetup {
+        context,
+        operator_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        freeze_list_pda,
+    })
+}
+
+fn add_to_freeze_list_instruction(setup: &FreezeListTestSetup, address: &Pubkey) -> Instruction {
+    AddToFreezeListBuilder::new()
+        .authority(setup.operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .operator_freeze_list(setup.freeze_list_pda)
+        .address(*address)
+        .instruction()
+}
+
+fn remove_from_freeze_list_instruction(
+    setup: &FreezeListTestSetup,
+    address: &Pubkey,
+) -> Instruction {
+    RemoveFromFreezeListBuilder::new()
+        .authority(setup.operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .operator_freeze_list(setup.freeze_list_pda)
+        .address(*address)
+        .instruction()
+}
+
+fn make_payment_instruction(
+    setup: &mut FreezeListTestSetup,
+    order_id: u32,
+    freeze_list: Option<&Pubkey>,
+) -> Instruction {
+    let merchant_account = setup.context.get_account(&setup.merchant_pda).unwrap();
+    let settlement_wallet = Merchant::from_bytes(&merchant_account.data)
+        .unwrap()
+        .settlement_wallet;
+
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut setup.context,
+        &buyer_ata,
+        &USDC_MINT,
+        &setup.buyer.pubkey(),
+        AMOUNT,
+    );
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let mut builder = MakePaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(&settlement_wallet, &USDC_MINT))
+        .settlement_wallet(settlement_wallet)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(AMOUNT);
+
+    if let Some(freeze_list) = freeze_list {
+        builder.add_remaining_account(AccountMeta::new_readonly(*freeze_list, false));
+    }
+
+    builder.instruction()
+}
+
+fn make_payment(setup: &mut FreezeListTestSetup, order_id: u32) -> Pubkey {
+    let buyer = setup.buyer.insecure_clone();
+    let freeze_list_pda = setup.freeze_list_pda;
+    let instruction = make_payment_instruction(setup, order_id, Some(&freeze_list_pda));
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer])
+        .expect("Make payment should succeed");
+
+    find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    )
+    .0
+}
+
+fn refund_payment_instruction(
+    setup: &FreezeListTestSetup,
+    payment_pda: &Pubkey,
+    freeze_list: Option<&Pubkey>,
+) -> Instruction {
+    let mut builder = RefundPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(*payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .close_escrow(false);
+
+    if let Some(freeze_list) = freeze_list {
+        builder.add_remaining_account(AccountMeta::new_readonly(*freeze_list, false));
+    }
+
+    builder.instruction()
+}
+
+#[tokio::test]
+async fn test_add_and_remove_from_freeze_list() {
+    let mut setup = setup_freeze_list_test().unwrap();
+    let frozen = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+
+    for address in [&frozen, &other] {
+        let instruction = add_to_freeze_list_instruction(&setup, address);
+        setup
+            .context
+            .send_transaction(instruction)
+            .expect("Add to freeze list should succeed");
+    }
+
+    let freeze_list = assert_operator_freeze_list_account(
+        &mut setup.context,
+        &setup.freeze_list_pda,
+        &setup.operator_pda,
+    );
+    assert_eq!(freeze_list.count, 2);
+    assert_eq!(&freeze_list.addresses[..2], &[frozen, other]);
+
+    let instruction = add_to_freeze_list_instruction(&setup, &frozen);
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, ADDRESS_ALREADY_FROZEN_ERROR);
+
+    let instruction = remove_from_freeze_list_instruction(&setup, &frozen);
+    setup
+        .context
+        .send_transaction(instruction)
+        .expect("Remove from freeze list should succeed");
+
+    let freeze_list = assert_operator_freeze_list_account(
+        &mut setup.context,
+        &setup.freeze_list_pda,
+        &setup.operator_pda,
+    );
+    assert_eq!(freeze_list.count, 1);
+    assert_eq!(freeze_list.addresses[0], other);
+
+    let instruction = remove_from_freeze_list_instruction(&setup, &frozen);
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, ADDRESS_NOT_FROZEN_ERROR);
+}
+
+#[tokio::test]
+async fn test_add_to_freeze_list_requires_operator_owner() {
+    let mut setup = setup_freeze_list_test().unwrap();
+    let attacker = Keypair::new();
+
+    let instruction = AddToFreezeListBuilder::new()
+        .authority(attacker.pubkey())
+        .operator(setup.operator_pda)
+        .operator_freeze_list(setup.freeze_list_pda)
+        .address(Pubkey::new_unique())
+        .instruction();
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&attacker]);
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_rejects_frozen_buyer() {
+    let mut setup = setup_freeze_list_test().unwrap();
+    let buyer = setup.buyer.insecure_clone();
+    let freeze_list_pda = setup.freeze_list_pda;
+
+    // An empty freeze list lets the payment through
+    let instruction = make_payment_instruction(&mut setup, 1, Some(&freeze_list_pda));
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer])
+        .expect("Make payment with freeze list should succeed");
+
+    let instruction = add_to_freeze_list_instruction(&setup, &buyer.pubkey());
+    setup.context.send_transaction(instruction).unwrap();
+
+    let instruction = make_payment_instruction(&mut setup, 2, Some(&freeze_list_pda));
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer]);
+    assert_program_error(result, BUYER_FROZEN_ERROR);
+
+    // Leaving the list out does not skip the check
+    let instruction = make_payment_instruction(&mut setup, 2, None);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer]);
+    assert_program_error(result, FREEZE_LIST_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_refund_payment_rejects_frozen_buyer() {
+    let mut setup = setup_freeze_list_test().unwrap();
+    let payment_pda = make_payment(&mut setup, 1);
+    let buyer = setup.buyer.pubkey();
+
+    let instruction = add_to_freeze_list_instruction(&setup, &buyer);
+    setup.context.send_transaction(instruction).unwrap();
+
+    let instruction =
+        refund_payment_instruction(&setup, &payment_pda, Some(&setup.freeze_list_pda));
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, BUYER_FROZEN_ERROR);
+
+    let instruction = refund_payment_instruction(&setup, &payment_pda, None);
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, FREEZE_LIST_REQUIRED_ERROR);
+
+    let instruction = remove_from_freeze_list_instruction(&setup, &buyer);
+    setup.context.send_transaction(instruction).unwrap();
+
+    let instruction =
+        refund_payment_instruction(&setup, &payment_pda, Some(&setup.freeze_list_pda));
+    setup
+        .context
+        .send_transaction(instruction)
+        .expect("Refund after unfreezing should succeed");
+}
+
+#[tokio::test]
+async fn test_refund_payment_rejects_other_operators_freeze_list() {
+    let mut setup = setup_freeze_list_test().unwrap();
+    let payment_pda = make_payment(&mut setup, 1);
+
+    let other_owner = Keypair::new();
+    let (other_operator_pda, _) =
+        assert_get_or_create_operator(&mut setup.context, &other_owner, true, false).unwrap();
+    let (other_freeze_list_pda, _) = assert_initialize_operator_freeze_list(
+        &mut setup.context,
+        &other_owner,
+        &other_operator_pda,
+    )
+    .unwrap();
+
+    let instruction =
+        refund_payment_instruction(&setup, &payment_pda, Some(&other_freeze_list_pda));
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, OPERATOR_FREEZE_LIST_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_operator_freeze_list_requires_operator_owner() {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let attacker = Keypair::new();
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false).unwrap();
+    let (freeze_list_pda, bump) = find_operator_freeze_list_pda(&operator_pda);
+
+    // Anyone else setting up the list would make it required for all of the operator's payments
+    let instruction = InitializeOperatorFreezeListBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(attacker.pubkey())
+        .operator(operator_pda)
+        .operator_freeze_list(freeze_list_pda)
+        .bump(bump)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&attacker]);
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
@@ -0,0 +1,198 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, set_token_balance,
+        TestContext, TestContextBuilder, DAYS_TO_CLOSE, ESCROW_AUTHORITY_INVALID_PDA_ERROR,
+        USDC_MINT,
+    },
+};
+use borsh::BorshDeserialize;
+use commerce_program_client::{
+    instructions::GetConfigSummaryBuilder,
+    types::{
+        ConfigSummary, FeeType, MemoPolicy, PolicyData, PolicyType, RentRebatePolicy,
+        SettlementPolicy,
+    },
+    COMMERCE_PROGRAM_ID,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair};
+
+const AMOUNT: u64 = 1_000_000;
+
+struct GetConfigSummaryTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a config with a 2.5% fee, three policies and one accepted mint
+fn setup_get_config_summary_test() -> Result<GetConfigSummaryTestSetup, Box<dyn std::error::Error>>
+{
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        3u32,
+        250u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![
+            PolicyData::Settlement(SettlementPolicy {
+                min_settlement_amount: 0,
+                settlement_frequency_hours: 0,
+                auto_settle: false,
+            }),
+            PolicyData::Memo(MemoPolicy {
+                memo_on_clear: true,
+                memo_on_refund: false,
+            }),
+            PolicyData::RentRebate(RentRebatePolicy {
+                buyer_share_bps: 2_500,
+            }),
+        ],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    Ok(GetConfigSummaryTestSetup {
+        context,
+        operator_authority,
+        buyer,
+        operator_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn get_config_summary_instruction(config: &Pubkey, escrow_authority: &Pubkey) -> Instruction {
+    GetConfigSummaryBuilder::new()
+        .merchant_operator_config(*config)
+        .mint(USDC_MINT)
+        .escrow_authority(*escrow_authority)
+        .merchant_escrow_ata(find_escrow_ata(config, &USDC_MINT))
+        .instruction()
+}
+
+fn get_config_summary(setup: &mut GetConfigSummaryTestSetup) -> ConfigSummary {
+    let config = setup.merchant_operator_config_pda;
+    let escrow_authority = find_escrow_authority_pda(&config, &USDC_MINT).0;
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            get_config_summary_instruction(&config, &escrow_authority),
+            &[],
+            false,
+        )
+        .expect("Get config summary should succeed");
+
+    assert_eq!(metadata.return_data.program_id, COMMERCE_PROGRAM_ID);
+    ConfigSummary::try_from_slice(&metadata.return_data.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_get_config_summary_without_escrow() {
+    let mut setup = setup_get_config_summary_test().unwrap();
+
+    assert_eq!(
+        get_config_summary(&mut setup),
+        ConfigSummary {
+            version: 3,
+            operator_fee: 250,
+            fee_type: FeeType::Bps,
+            days_to_close: DAYS_TO_CLOSE,
+            policy_mask: 1 << PolicyType::Settlement as u32
+                | 1 << PolicyType::Memo as u32
+                | 1 << PolicyType::RentRebate as u32,
+            num_accepted_currencies: 1,
+            escrow_balance: 0,
+            open_payments: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_get_config_summary_reports_open_payments() {
+    let mut setup = setup_get_config_summary_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &setup.merchant_operator_config_pda,
+        &setup.operator_pda,
+        &USDC_MINT,
+        1,
+        AMOUNT,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+    let summary = get_config_summary(&mut setup);
+    assert_eq!(summary.open_payments, 1);
+    assert_eq!(summary.escrow_balance, AMOUNT);
+
+    // Clearing takes the payment's funds out of escrow
+    assert_clear_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+    .unwrap();
+    let summary = get_config_summary(&mut setup);
+    assert_eq!(summary.open_payments, 0);
+    assert_eq!(summary.escrow_balance, 0);
+
+    // Tokens sent straight to the escrow open no payment
+    let config = setup.merchant_operator_config_pda;
+    set_token_balance(
+        &mut setup.context,
+        &find_escrow_ata(&config, &USDC_MINT),
+        &USDC_MINT,
+        &find_escrow_authority_pda(&config, &USDC_MINT).0,
+        5,
+    );
+    let summary = get_config_summary(&mut setup);
+    assert_eq!(summary.open_payments, 0);
+    assert_eq!(summary.escrow_balance, 5);
+}
+
+#[tokio::test]
+async fn test_get_config_summary_wrong_escrow_authority_fails() {
+    let mut setup = setup_get_config_summary_test().unwrap();
+
+    let instruction =
+        get_config_summary_instruction(&setup.merchant_operator_config_pda, &Pubkey::new_unique());
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, ESCROW_AUTHORITY_INVALID_PDA_ERROR);
+}
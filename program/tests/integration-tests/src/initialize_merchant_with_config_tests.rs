@@ -0,0 +1,208 @@
+use crate::{
+    assertions::assert_account_not_exists,
+    state_utils::{
+        assert_get_or_create_merchant, assert_get_or_create_operator,
+        assert_initialize_merchant_with_config,
+    },
+    utils::{
+        assert_program_error, find_merchant_operator_config_pda, find_merchant_pda, TestContext,
+        ACCEPTED_CURRENCIES_EMPTY_ERROR, DAYS_TO_CLOSE, INVALID_ACCOUNT_OWNER_ERROR,
+        MISSING_REQUIRED_SIGNATURE_ERROR, OPERATOR_OWNER_MISMATCH_ERROR, USDC_MINT, USDT_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::InitializeMerchantWithConfigBuilder,
+    types::{FeeType, PolicyData, RefundPolicy},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+
+const VERSION: u32 = 1;
+const OPERATOR_FEE: u64 = 100;
+
+fn initialize_merchant_with_config_instruction(
+    context: &TestContext,
+    authority: &Pubkey,
+    settlement_wallet: &Pubkey,
+    operator_authority: &Pubkey,
+    operator_pda: &Pubkey,
+    accepted_currencies: Vec<Pubkey>,
+) -> Instruction {
+    let (merchant_pda, merchant_bump) = find_merchant_pda(authority);
+    let (config_pda, config_bump) =
+        find_merchant_operator_config_pda(&merchant_pda, operator_pda, VERSION);
+
+    let mut builder = InitializeMerchantWithConfigBuilder::new();
+    builder
+        .payer(context.payer.pubkey())
+        .authority(*authority)
+        .operator_authority(*operator_authority)
+        .merchant(merchant_pda)
+        .settlement_wallet(*settlement_wallet)
+        .operator(*operator_pda)
+        .config(config_pda)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .merchant_bump(merchant_bump)
+        .settlement_wallet_timelock_seconds(0)
+        .version(VERSION)
+        .config_bump(config_bump)
+        .operator_fee(OPERATOR_FEE)
+        .fee_type(FeeType::Bps)
+        .days_to_close(DAYS_TO_CLOSE)
+        .policies(vec![])
+        .accepted_currencies(accepted_currencies.clone());
+
+    for currency in &accepted_currencies {
+        builder.add_remaining_account(AccountMeta::new_readonly(*currency, false));
+    }
+
+    builder.instruction()
+}
+
+#[tokio::test]
+async fn test_initialize_merchant_with_config_success() {
+    let mut context = TestContext::new();
+
+    let operator_authority = Keypair::new();
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, false, false).unwrap();
+
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    assert_initialize_merchant_with_config(
+        &mut context,
+        &authority,
+        &settlement_wallet,
+        &operator_authority,
+        &operator_pda,
+        VERSION,
+        OPERATOR_FEE,
+        FeeType::Bps,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Refund(RefundPolicy {
+            max_amount: 1000,
+            max_time_after_purchase: 3600,
+        })],
+        vec![USDC_MINT, USDT_MINT],
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_initialize_merchant_with_config_missing_operator_signature_fails() {
+    let mut context = TestContext::new();
+
+    let operator_authority = Keypair::new();
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, false, false).unwrap();
+
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let mut instruction = initialize_merchant_with_config_instruction(
+        &context,
+        &authority.pubkey(),
+        &settlement_wallet.pubkey(),
+        &operator_authority.pubkey(),
+        &operator_pda,
+        vec![USDC_MINT],
+    );
+    // Mark operator authority as non-signer
+    instruction.accounts[2].is_signer = false;
+
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, MISSING_REQUIRED_SIGNATURE_ERROR);
+
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    assert_account_not_exists(&mut context, &merchant_pda);
+}
+
+#[tokio::test]
+async fn test_initialize_merchant_with_config_wrong_operator_authority_fails() {
+    let mut context = TestContext::new();
+
+    let operator_authority = Keypair::new();
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, false, false).unwrap();
+
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let wrong_operator_authority = Keypair::new();
+
+    let instruction = initialize_merchant_with_config_instruction(
+        &context,
+        &authority.pubkey(),
+        &settlement_wallet.pubkey(),
+        &wrong_operator_authority.pubkey(),
+        &operator_pda,
+        vec![USDC_MINT],
+    );
+
+    let result = context
+        .send_transaction_with_signers(instruction, &[&authority, &wrong_operator_authority]);
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_merchant_with_config_existing_merchant_fails() {
+    let mut context = TestContext::new();
+
+    let operator_authority = Keypair::new();
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, false, false).unwrap();
+
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    assert_get_or_create_merchant(&mut context, &authority, &settlement_wallet, true, false)
+        .unwrap();
+
+    let instruction = initialize_merchant_with_config_instruction(
+        &context,
+        &authority.pubkey(),
+        &settlement_wallet.pubkey(),
+        &operator_authority.pubkey(),
+        &operator_pda,
+        vec![USDC_MINT],
+    );
+
+    let result =
+        context.send_transaction_with_signers(instruction, &[&authority, &operator_authority]);
+    assert_program_error(result, INVALID_ACCOUNT_OWNER_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_merchant_with_config_invalid_config_creates_nothing() {
+    let mut context = TestContext::new();
+
+    let operator_authority = Keypair::new();
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, false, false).unwrap();
+
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let instruction = initialize_merchant_with_config_instruction(
+        &context,
+        &authority.pubkey(),
+        &settlement_wallet.pubkey(),
+        &operator_authority.pubkey(),
+        &operator_pda,
+        vec![],
+    );
+
+    let result =
+        context.send_transaction_with_signers(instruction, &[&authority, &operator_authority]);
+    assert_program_error(result, ACCEPTED_CURRENCIES_EMPTY_ERROR);
+
+    // The merchant is rolled back along with the config
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let (config_pda, _) = find_merchant_operator_config_pda(&merchant_pda, &operator_pda, VERSION);
+    assert_account_not_exists(&mut context, &merchant_pda);
+    assert_account_not_exists(&mut context, &config_pda);
+}
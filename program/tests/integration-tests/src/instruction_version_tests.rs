@@ -0,0 +1,76 @@
+use crate::{
+    assertions::assert_operator_account,
+    utils::{
+        assert_program_error, find_operator_pda, TestContext, UNSUPPORTED_INSTRUCTION_VERSION_ERROR,
+    },
+};
+use commerce_program_client::{
+    discriminator::{with_version_header, INSTRUCTION_VERSION, INSTRUCTION_VERSION_FLAG},
+    instructions::CreateOperatorBuilder,
+};
+use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+
+fn create_operator_instruction(context: &TestContext, owner: &Keypair) -> (Instruction, u8) {
+    let (operator_pda, bump) = find_operator_pda(&owner.pubkey());
+    let instruction = CreateOperatorBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(owner.pubkey())
+        .operator(operator_pda)
+        .instruction();
+    (instruction, bump)
+}
+
+#[tokio::test]
+async fn test_versioned_instruction_succeeds() {
+    let mut context = TestContext::new();
+    let owner = Keypair::new();
+    let (mut instruction, bump) = create_operator_instruction(&context, &owner);
+    instruction.data = with_version_header(&instruction.data);
+    assert_eq!(instruction.data[1], INSTRUCTION_VERSION);
+
+    context
+        .send_transaction_with_signers(instruction, &[&owner])
+        .expect("Versioned create operator should succeed");
+
+    assert_operator_account(
+        &mut context,
+        &find_operator_pda(&owner.pubkey()).0,
+        &owner.pubkey(),
+        bump,
+    );
+}
+
+#[tokio::test]
+async fn test_unsupported_instruction_version_fails() {
+    let mut context = TestContext::new();
+    let owner = Keypair::new();
+    let (mut instruction, _) = create_operator_instruction(&context, &owner);
+    instruction.data = [
+        &[
+            instruction.data[0] | INSTRUCTION_VERSION_FLAG,
+            INSTRUCTION_VERSION + 1,
+        ],
+        &instruction.data[1..],
+    ]
+    .concat();
+
+    let result = context.send_transaction_with_signers(instruction, &[&owner]);
+    assert_program_error(result, UNSUPPORTED_INSTRUCTION_VERSION_ERROR);
+}
+
+#[tokio::test]
+async fn test_create_operator_version_1_fails() {
+    let mut context = TestContext::new();
+    let owner = Keypair::new();
+    let (mut instruction, bump) = create_operator_instruction(&context, &owner);
+    // Version 1 of CreateOperator carried the bump, which the program now derives
+    instruction.data = [
+        &[instruction.data[0] | INSTRUCTION_VERSION_FLAG, 1],
+        &instruction.data[1..],
+        &[bump],
+    ]
+    .concat();
+
+    let result = context.send_transaction_with_signers(instruction, &[&owner]);
+    assert_program_error(result, UNSUPPORTED_INSTRUCTION_VERSION_ERROR);
+}
@@ -22,4 +22,151 @@ pub mod close_payment_tests;
 #[cfg(test)]
 pub mod merchant_operator_config_tests;
 
+#[cfg(test)]
+pub mod compressed_payment_tests;
+
+#[cfg(test)]
+pub mod merchant_stats_tests;
+
+#[cfg(test)]
+pub mod refund_settled_payment_tests;
+
+#[cfg(test)]
+pub mod memo_policy_tests;
+
+#[cfg(test)]
+pub mod operator_delegate_tests;
+
+#[cfg(test)]
+pub mod conversion_tests;
+
+#[cfg(test)]
+pub mod chargeback_tests;
+
+#[cfg(test)]
+pub mod sweep_closed_payments_tests;
+
+#[cfg(test)]
+pub mod fee_override_tests;
+
+#[cfg(test)]
+pub mod denylist_tests;
+
+#[cfg(test)]
+pub mod settlement_batch_tests;
+
+#[cfg(test)]
+pub mod order_tests;
+
+#[cfg(test)]
+pub mod merchant_profile_tests;
+
+#[cfg(test)]
+pub mod split_settlement_tests;
+
+#[cfg(test)]
+pub mod refund_approval_tests;
+
+#[cfg(test)]
+pub mod risk_policy_tests;
+
+#[cfg(test)]
+pub mod buyer_index_tests;
+
+#[cfg(test)]
+pub mod malformed_account_tests;
+
+#[cfg(test)]
+pub mod initialize_merchant_with_config_tests;
+
+#[cfg(test)]
+pub mod upgrade_account_tests;
+
+#[cfg(test)]
+pub mod rent_payer_tests;
+
+#[cfg(test)]
+pub mod callback_policy_tests;
+
+#[cfg(test)]
+pub mod transfer_hook_tests;
+
+#[cfg(test)]
+pub mod platform_fee_tests;
+
+#[cfg(test)]
+pub mod migrate_escrow_tests;
+
+#[cfg(test)]
+pub mod quote_tests;
+
+#[cfg(test)]
+pub mod config_registry_tests;
+
+#[cfg(test)]
+pub mod compliance_policy_tests;
+
+#[cfg(test)]
+pub mod capture_payment_tests;
+
+#[cfg(test)]
+pub mod refund_close_escrow_tests;
+
+#[cfg(test)]
+pub mod config_event_tests;
+
+#[cfg(test)]
+pub mod freeze_list_tests;
+
+#[cfg(test)]
+pub mod settlement_discount_tests;
+
+#[cfg(test)]
+pub mod nonce_tests;
+
+#[cfg(test)]
+pub mod refund_conversion_tests;
+
+#[cfg(test)]
+pub mod config_growth_tests;
+
+#[cfg(test)]
+pub mod payment_note_tests;
+
+#[cfg(test)]
+pub mod operator_failover_tests;
+
+#[cfg(test)]
+pub mod close_escrow_ata_tests;
+
+#[cfg(test)]
+pub mod non_canonical_escrow_tests;
+
+#[cfg(test)]
+pub mod non_canonical_bump_tests;
+
+#[cfg(test)]
+pub mod migrate_payment_tests;
+
+#[cfg(test)]
+pub mod decommission_config_tests;
+#[cfg(test)]
+pub mod direct_settlement_tests;
+#[cfg(test)]
+pub mod get_config_summary_tests;
+#[cfg(test)]
+pub mod instruction_version_tests;
+#[cfg(test)]
+pub mod payment_annotation_tests;
+#[cfg(test)]
+pub mod rent_rebate_tests;
+#[cfg(test)]
+pub mod webhook_commitment_tests;
+
+#[cfg(all(test, feature = "extreme-values"))]
+pub mod extreme_value_tests;
+
+#[cfg(all(test, feature = "cu-benchmarks"))]
+pub mod cu_benchmark_tests;
+
 pub mod utils;
@@ -1,14 +1,19 @@
 use crate::{
     state_utils::*,
     utils::{
-        assert_program_error, find_event_authority_pda, find_merchant_pda, find_payment_pda,
-        get_or_create_associated_token_account, set_mint, TestContext, DAYS_TO_CLOSE,
-        INVALID_ACCOUNT_OWNER_ERROR, INVALID_INSTRUCTION_DATA_ERROR, INVALID_MINT_ERROR,
-        MISSING_REQUIRED_SIGNATURE_ERROR, OPERATOR_OWNER_MISMATCH_ERROR,
-        TOKEN_INSUFFICIENT_FUNDS_ERROR, USDC_MINT, USDT_MINT,
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_event_authority_pda,
+        find_merchant_pda, find_payment_pda, get_or_create_associated_token_account,
+        idempotency_key, set_mint, TestContext, ATA_PROGRAM_ID, DAYS_TO_CLOSE,
+        INVALID_ACCOUNT_OWNER_ERROR, INVALID_ATA_ERROR, INVALID_INSTRUCTION_DATA_ERROR,
+        INVALID_MINT_ERROR, MERCHANT_MISMATCH_ERROR, MISSING_REQUIRED_SIGNATURE_ERROR,
+        OPERATOR_OWNER_MISMATCH_ERROR, PAYMENT_ALREADY_EXISTS_ERROR,
+        SETTLEMENT_WALLET_MISMATCH_ERROR, TOKEN_INSUFFICIENT_FUNDS_ERROR,
+        TOO_MANY_PAYMENT_REFERENCES_ERROR, USDC_MINT, USDT_MINT,
     },
 };
 use commerce_program_client::{
+    discriminator::InstructionDiscriminator,
+    idempotency::{resolve_make_payment, MakePaymentOutcome},
     instructions::MakePaymentBuilder,
     types::{FeeType, PolicyData, SettlementPolicy},
     COMMERCE_PROGRAM_ID,
@@ -65,10 +70,19 @@ async fn setup_make_payment_test(
     let operator_fee = 500u64; // 5%
     let version = 1u32;
     let current_order_id = 0u32;
-    let policies = vec![PolicyData::Settlement(SettlementPolicy {
-        min_settlement_amount: 1_000_000u64,
-        settlement_frequency_hours: 30u32,
-        auto_settle,
+    // MakePayment settles auto-settled payments itself, so they take no minimum or frequency
+    let policies = vec![PolicyData::Settlement(if auto_settle {
+        SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle,
+        }
+    } else {
+        SettlementPolicy {
+            min_settlement_amount: 1_000_000u64,
+            settlement_frequency_hours: 30u32,
+            auto_settle,
+        }
     })];
     let accepted_currencies = vec![USDC_MINT, USDT_MINT];
 
@@ -239,7 +253,7 @@ async fn test_make_payment_unsigned_fee_payer_fails() {
         .airdrop_if_required(&wrong_payer.pubkey(), 1_000_000_000)
         .unwrap();
 
-    let (payment_pda, bump) = find_payment_pda(
+    let (payment_pda, _) = find_payment_pda(
         &merchant_operator_config_pda,
         &buyer.pubkey(),
         &USDC_MINT,
@@ -248,15 +262,16 @@ async fn test_make_payment_unsigned_fee_payer_fails() {
 
     let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
 
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
     let merchant_escrow_ata =
-        get_or_create_associated_token_account(&mut context, &merchant_pda, &USDC_MINT);
+        get_or_create_associated_token_account(&mut context, &escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
     let settlement_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = MakePaymentBuilder::new()
         .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
         .amount(amount)
-        .bump(bump)
         .payer(wrong_payer.pubkey()) // Wrong payer
         .payment(payment_pda)
         .operator_authority(operator_authority.pubkey())
@@ -266,8 +281,10 @@ async fn test_make_payment_unsigned_fee_payer_fails() {
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
         .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(buyer.pubkey())
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .instruction();
@@ -300,7 +317,7 @@ async fn test_make_payment_unsigned_operator_authority_fails() {
         .airdrop_if_required(&non_signer.pubkey(), 1_000_000_000)
         .unwrap();
 
-    let (payment_pda, bump) = find_payment_pda(
+    let (payment_pda, _) = find_payment_pda(
         &merchant_operator_config_pda,
         &buyer.pubkey(),
         &USDC_MINT,
@@ -309,14 +326,15 @@ async fn test_make_payment_unsigned_operator_authority_fails() {
 
     let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
     let settlement_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = MakePaymentBuilder::new()
         .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
         .amount(amount)
-        .bump(bump)
         .payer(context.payer.pubkey())
         .payment(payment_pda)
         .operator_authority(non_signer.pubkey()) // Wrong operator authority
@@ -326,8 +344,10 @@ async fn test_make_payment_unsigned_operator_authority_fails() {
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
         .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(buyer.pubkey())
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .instruction();
@@ -352,7 +372,7 @@ async fn test_make_payment_unsigned_buyer_fails() {
     let order_id = 1u32;
     let amount = 1_000_000u64;
 
-    let (payment_pda, bump) = find_payment_pda(
+    let (payment_pda, _) = find_payment_pda(
         &merchant_operator_config_pda,
         &buyer.pubkey(),
         &USDC_MINT,
@@ -368,10 +388,9 @@ async fn test_make_payment_unsigned_buyer_fails() {
     // Get event authority PDA for manual instruction construction
     let (event_authority, _) = find_event_authority_pda();
 
-    let mut instruction_data = vec![3]; // Make payment discriminator
+    let mut instruction_data = vec![InstructionDiscriminator::MakePayment.into()];
     instruction_data.extend_from_slice(&order_id.to_le_bytes());
     instruction_data.extend_from_slice(&amount.to_le_bytes());
-    instruction_data.push(bump);
 
     let accounts = vec![
         AccountMeta::new(context.payer.pubkey(), true),
@@ -385,7 +404,9 @@ async fn test_make_payment_unsigned_buyer_fails() {
         AccountMeta::new(buyer_ata, false),
         AccountMeta::new(merchant_escrow_ata, false),
         AccountMeta::new(settlement_ata, false),
+        AccountMeta::new_readonly(buyer.pubkey(), false),
         AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
         AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
         AccountMeta::new_readonly(event_authority, false),
         AccountMeta::new_readonly(COMMERCE_PROGRAM_ID, false),
@@ -420,7 +441,7 @@ async fn test_make_payment_invalid_operator_fails() {
 
     let fake_operator = Keypair::new();
 
-    let (payment_pda, bump) = find_payment_pda(
+    let (payment_pda, _) = find_payment_pda(
         &merchant_operator_config_pda,
         &buyer.pubkey(),
         &USDC_MINT,
@@ -429,14 +450,15 @@ async fn test_make_payment_invalid_operator_fails() {
 
     let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
     let settlement_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = MakePaymentBuilder::new()
         .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
         .amount(amount)
-        .bump(bump)
         .payer(context.payer.pubkey())
         .payment(payment_pda)
         .operator_authority(operator_authority.pubkey())
@@ -446,8 +468,10 @@ async fn test_make_payment_invalid_operator_fails() {
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
         .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(buyer.pubkey())
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .instruction();
@@ -477,19 +501,20 @@ async fn test_make_payment_invalid_merchant_operator_config_pda_fails() {
 
     let fake_config = Keypair::new();
 
-    let (payment_pda, bump) =
+    let (payment_pda, _) =
         find_payment_pda(&fake_config.pubkey(), &buyer.pubkey(), &USDC_MINT, order_id);
 
     let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&fake_config.pubkey(), &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
     let settlement_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = MakePaymentBuilder::new()
         .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
         .amount(amount)
-        .bump(bump)
         .payer(context.payer.pubkey())
         .payment(payment_pda)
         .operator_authority(operator_authority.pubkey())
@@ -499,8 +524,10 @@ async fn test_make_payment_invalid_merchant_operator_config_pda_fails() {
         .merchant_operator_config(fake_config.pubkey()) // Wrong merchant operator config
         .mint(USDC_MINT)
         .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(buyer.pubkey())
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .instruction();
@@ -525,7 +552,7 @@ async fn test_make_payment_wrong_order_id_fails() {
     let wrong_order_id = 5u32; // Should be 1 (current_order_id + 1)
     let amount = 1_000_000u64;
 
-    let (payment_pda, bump) = find_payment_pda(
+    let (payment_pda, _) = find_payment_pda(
         &merchant_operator_config_pda,
         &buyer.pubkey(),
         &USDC_MINT,
@@ -534,15 +561,16 @@ async fn test_make_payment_wrong_order_id_fails() {
 
     let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
 
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
     let merchant_escrow_ata =
-        get_or_create_associated_token_account(&mut context, &merchant_pda, &USDC_MINT);
+        get_or_create_associated_token_account(&mut context, &escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
     let settlement_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = MakePaymentBuilder::new()
         .order_id(wrong_order_id)
+        .idempotency_key(idempotency_key(wrong_order_id))
         .amount(amount)
-        .bump(bump)
         .payer(context.payer.pubkey())
         .payment(payment_pda)
         .operator_authority(operator_authority.pubkey())
@@ -552,8 +580,10 @@ async fn test_make_payment_wrong_order_id_fails() {
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
         .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(buyer.pubkey())
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .instruction();
@@ -584,7 +614,7 @@ async fn test_make_payment_invalid_mint_not_in_allowed_list_fails() {
     let invalid_mint = Pubkey::new_unique();
     set_mint(&mut context, &invalid_mint);
 
-    let (payment_pda, bump) = find_payment_pda(
+    let (payment_pda, _) = find_payment_pda(
         &merchant_operator_config_pda,
         &buyer.pubkey(),
         &invalid_mint,
@@ -593,14 +623,16 @@ async fn test_make_payment_invalid_mint_not_in_allowed_list_fails() {
 
     let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &invalid_mint);
+    let escrow_authority =
+        find_escrow_authority_pda(&merchant_operator_config_pda, &invalid_mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &invalid_mint);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &invalid_mint);
     let settlement_ata = get_associated_token_address(&buyer.pubkey(), &invalid_mint);
 
     let instruction = MakePaymentBuilder::new()
         .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
         .amount(amount)
-        .bump(bump)
         .payer(context.payer.pubkey())
         .payment(payment_pda)
         .operator_authority(operator_authority.pubkey())
@@ -610,8 +642,10 @@ async fn test_make_payment_invalid_mint_not_in_allowed_list_fails() {
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(invalid_mint) // This mint is not in allowed_currencies
         .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(buyer.pubkey())
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .instruction();
@@ -622,65 +656,6 @@ async fn test_make_payment_invalid_mint_not_in_allowed_list_fails() {
     assert_program_error(result, INVALID_MINT_ERROR);
 }
 
-#[tokio::test]
-async fn test_make_payment_invalid_payment_pda_wrong_bump_fails() {
-    let (
-        mut context,
-        operator_authority,
-        _merchant_authority,
-        _settlement_wallet,
-        buyer,
-        operator_pda,
-        _merchant_pda,
-        merchant_operator_config_pda,
-    ) = setup_make_payment_test(false).await.unwrap();
-
-    let order_id = 1u32;
-    let amount = 1_000_000u64;
-
-    let (payment_pda, correct_bump) = find_payment_pda(
-        &merchant_operator_config_pda,
-        &buyer.pubkey(),
-        &USDC_MINT,
-        order_id,
-    );
-
-    let wrong_bump = if correct_bump == 255 {
-        254
-    } else {
-        correct_bump + 1
-    };
-
-    let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
-
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
-    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
-    let settlement_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
-
-    let instruction = MakePaymentBuilder::new()
-        .order_id(order_id)
-        .amount(amount)
-        .bump(wrong_bump) // Wrong bump
-        .payer(context.payer.pubkey())
-        .payment(payment_pda)
-        .operator_authority(operator_authority.pubkey())
-        .buyer(buyer.pubkey())
-        .operator(operator_pda)
-        .merchant(merchant_pda)
-        .merchant_operator_config(merchant_operator_config_pda)
-        .mint(USDC_MINT)
-        .buyer_ata(buyer_ata)
-        .merchant_escrow_ata(merchant_escrow_ata)
-        .merchant_settlement_ata(settlement_ata)
-        .token_program(TOKEN_PROGRAM_ID)
-        .system_program(SYSTEM_PROGRAM_ID)
-        .instruction();
-
-    let result = context.send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
-
-    assert_program_error(result, INVALID_INSTRUCTION_DATA_ERROR);
-}
-
 #[tokio::test]
 async fn test_make_payment_invalid_buyer_ata_fails() {
     let (
@@ -702,7 +677,7 @@ async fn test_make_payment_invalid_buyer_ata_fails() {
         .airdrop_if_required(&different_buyer.pubkey(), 1_000_000_000)
         .unwrap();
 
-    let (payment_pda, bump) = find_payment_pda(
+    let (payment_pda, _) = find_payment_pda(
         &merchant_operator_config_pda,
         &buyer.pubkey(),
         &USDC_MINT,
@@ -711,15 +686,16 @@ async fn test_make_payment_invalid_buyer_ata_fails() {
 
     let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let _buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
     let different_buyer_ata = get_associated_token_address(&different_buyer.pubkey(), &USDC_MINT); // This ATA doesn't exist
     let settlement_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = MakePaymentBuilder::new()
         .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
         .amount(amount)
-        .bump(bump)
         .payer(context.payer.pubkey())
         .payment(payment_pda)
         .operator_authority(operator_authority.pubkey())
@@ -729,15 +705,17 @@ async fn test_make_payment_invalid_buyer_ata_fails() {
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
         .buyer_ata(different_buyer_ata) // Wrong ATA that doesn't exist
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(buyer.pubkey())
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .instruction();
 
     let result = context.send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
 
-    assert_program_error(result, INVALID_INSTRUCTION_DATA_ERROR);
+    assert_program_error(result, INVALID_ATA_ERROR);
 }
 
 #[tokio::test]
@@ -754,9 +732,9 @@ async fn test_make_payment_invalid_instruction_data_length_fails() {
     ) = setup_make_payment_test(false).await.unwrap();
 
     // Create properly formatted instruction data but with wrong length
-    // MakePayment instruction discriminator (3) + partial data
-    let mut invalid_data = vec![3]; // Correct instruction discriminator
-    invalid_data.extend_from_slice(&[0; 10]); // Insufficient data for order_id, amount, bump
+    // MakePayment instruction discriminator + partial data
+    let mut invalid_data: Vec<u8> = vec![InstructionDiscriminator::MakePayment.into()];
+    invalid_data.extend_from_slice(&[0; 10]); // Insufficient data for order_id, amount, idempotency_key
 
     let order_id = 1u32;
     let (payment_pda, _) = find_payment_pda(
@@ -824,7 +802,7 @@ async fn test_make_payment_auto_settle_unsigned_fee_payer_fails() {
         .airdrop_if_required(&non_signer.pubkey(), 1_000_000_000)
         .unwrap();
 
-    let (payment_pda, bump) = find_payment_pda(
+    let (payment_pda, _) = find_payment_pda(
         &merchant_operator_config_pda,
         &buyer.pubkey(),
         &USDC_MINT,
@@ -833,14 +811,15 @@ async fn test_make_payment_auto_settle_unsigned_fee_payer_fails() {
 
     let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
     let settlement_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = MakePaymentBuilder::new()
         .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
         .amount(amount)
-        .bump(bump)
         .payer(context.payer.pubkey())
         .payment(payment_pda)
         .operator_authority(non_signer.pubkey()) // Wrong operator authority
@@ -850,8 +829,10 @@ async fn test_make_payment_auto_settle_unsigned_fee_payer_fails() {
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
         .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(buyer.pubkey())
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .instruction();
@@ -881,7 +862,7 @@ async fn test_make_payment_auto_settle_invalid_mint_fails() {
     let invalid_mint = Pubkey::new_unique();
     set_mint(&mut context, &invalid_mint);
 
-    let (payment_pda, bump) = find_payment_pda(
+    let (payment_pda, _) = find_payment_pda(
         &merchant_operator_config_pda,
         &buyer.pubkey(),
         &invalid_mint,
@@ -890,14 +871,16 @@ async fn test_make_payment_auto_settle_invalid_mint_fails() {
 
     let (merchant_pda, _) = find_merchant_pda(&_merchant_authority.pubkey());
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &invalid_mint);
+    let escrow_authority =
+        find_escrow_authority_pda(&merchant_operator_config_pda, &invalid_mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &invalid_mint);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &invalid_mint);
     let settlement_ata = get_associated_token_address(&buyer.pubkey(), &invalid_mint);
 
     let instruction = MakePaymentBuilder::new()
         .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
         .amount(amount)
-        .bump(bump)
         .payer(context.payer.pubkey())
         .payment(payment_pda)
         .operator_authority(operator_authority.pubkey())
@@ -907,8 +890,10 @@ async fn test_make_payment_auto_settle_invalid_mint_fails() {
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(invalid_mint) // This mint is not in allowed_currencies
         .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(buyer.pubkey())
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .instruction();
@@ -918,3 +903,372 @@ async fn test_make_payment_auto_settle_invalid_mint_fails() {
     // Check for specific InvalidMint error
     assert_program_error(result, INVALID_MINT_ERROR);
 }
+
+#[tokio::test]
+async fn test_make_payment_auto_settle_wrong_settlement_wallet_fails() {
+    let (
+        mut context,
+        operator_authority,
+        _merchant_authority,
+        _settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    ) = setup_make_payment_test(true).await.unwrap(); // auto_settle = true
+
+    let order_id = 1u32;
+    let amount = 1_000_000u64;
+
+    let (payment_pda, _) = find_payment_pda(
+        &merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    // Settlement ATA is consistent with the wallet passed, but the wallet is not the merchant's
+    let wrong_settlement_wallet = Pubkey::new_unique();
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    let settlement_ata = get_associated_token_address(&wrong_settlement_wallet, &USDC_MINT);
+
+    let instruction = MakePaymentBuilder::new()
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(amount)
+        .payer(context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .operator(operator_pda)
+        .merchant(merchant_pda)
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(wrong_settlement_wallet)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+
+    assert_program_error(result, SETTLEMENT_WALLET_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_auto_settle_wrong_merchant_fails() {
+    let (
+        mut context,
+        operator_authority,
+        _merchant_authority,
+        _settlement_wallet,
+        buyer,
+        operator_pda,
+        _merchant_pda,
+        merchant_operator_config_pda,
+    ) = setup_make_payment_test(true).await.unwrap(); // auto_settle = true
+
+    // A merchant outside the config, settling to its own wallet
+    let other_merchant_authority = Keypair::new();
+    let other_settlement_wallet = Keypair::new();
+    let (other_merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &other_merchant_authority,
+        &other_settlement_wallet,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let order_id = 1u32;
+    let amount = 1_000_000u64;
+
+    let (payment_pda, _) = find_payment_pda(
+        &merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    let settlement_ata =
+        get_associated_token_address(&other_settlement_wallet.pubkey(), &USDC_MINT);
+
+    let instruction = MakePaymentBuilder::new()
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(amount)
+        .payer(context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .operator(operator_pda)
+        .merchant(other_merchant_pda) // Not the config's merchant
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .merchant_settlement_ata(settlement_ata)
+        .settlement_wallet(other_settlement_wallet.pubkey())
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+
+    assert_program_error(result, MERCHANT_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_retry_returns_existing_payment() {
+    let (
+        mut context,
+        operator_authority,
+        _merchant_authority,
+        settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    ) = setup_make_payment_test(false).await.unwrap();
+
+    let order_id = 1u32;
+    let amount = 1_000_000u64;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        order_id,
+        amount,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+
+    // Retry the same checkout: the payment PDA already exists
+    let payer = context.payer.pubkey();
+    let retry_instruction = |key: [u8; 16]| {
+        MakePaymentBuilder::new()
+            .order_id(order_id)
+            .idempotency_key(key)
+            .amount(amount)
+            .payer(payer)
+            .payment(payment_pda)
+            .operator_authority(operator_authority.pubkey())
+            .buyer(buyer.pubkey())
+            .operator(operator_pda)
+            .merchant(merchant_pda)
+            .merchant_operator_config(merchant_operator_config_pda)
+            .mint(USDC_MINT)
+            .buyer_ata(get_associated_token_address(&buyer.pubkey(), &USDC_MINT))
+            .escrow_authority(
+                find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0,
+            )
+            .merchant_escrow_ata(find_escrow_ata(&merchant_operator_config_pda, &USDC_MINT))
+            .merchant_settlement_ata(get_associated_token_address(
+                &settlement_wallet.pubkey(),
+                &USDC_MINT,
+            ))
+            .settlement_wallet(settlement_wallet.pubkey())
+            .instruction()
+    };
+
+    context.svm.expire_blockhash();
+    let result = context.send_transaction_with_signers(
+        retry_instruction(idempotency_key(order_id)),
+        &[&operator_authority, &buyer],
+    );
+    assert_program_error(result, PAYMENT_ALREADY_EXISTS_ERROR);
+
+    // The client helper resolves the retry to the payment created by the first attempt
+    let existing = context
+        .get_account(&payment_pda)
+        .map(|account| commerce_program_client::Payment::from_bytes(&account.data).unwrap());
+    context.svm.expire_blockhash();
+    let result = context.send_transaction_with_signers(
+        retry_instruction(idempotency_key(order_id)),
+        &[&operator_authority, &buyer],
+    );
+    let outcome =
+        resolve_make_payment(result, &idempotency_key(order_id), || existing.clone()).unwrap();
+    match outcome {
+        MakePaymentOutcome::Existing(payment) => {
+            assert_eq!(payment.order_id, order_id);
+            assert_eq!(payment.amount, amount);
+            assert_eq!(payment.idempotency_key, idempotency_key(order_id));
+        }
+        MakePaymentOutcome::Created(_) => panic!("Retry should not create a payment"),
+    }
+
+    // A different checkout reusing the order id is not treated as a retry
+    context.svm.expire_blockhash();
+    let result = context.send_transaction_with_signers(
+        retry_instruction([0xff; 16]),
+        &[&operator_authority, &buyer],
+    );
+    assert!(resolve_make_payment(result, &[0xff; 16], || existing.clone()).is_err());
+}
+
+#[allow(clippy::too_many_arguments)]
+fn make_payment_with_references_instruction(
+    context: &TestContext,
+    operator_authority: &Keypair,
+    buyer: &Keypair,
+    operator_pda: &Pubkey,
+    merchant_pda: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    settlement_wallet: &Pubkey,
+    order_id: u32,
+    references: &[Pubkey],
+) -> Instruction {
+    let (payment_pda, _) = find_payment_pda(
+        merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let mut builder = MakePaymentBuilder::new();
+    builder
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(1_000_000)
+        .payer(context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .operator(*operator_pda)
+        .merchant(*merchant_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(&buyer.pubkey(), &USDC_MINT))
+        .escrow_authority(find_escrow_authority_pda(merchant_operator_config_pda, &USDC_MINT).0)
+        .merchant_escrow_ata(find_escrow_ata(merchant_operator_config_pda, &USDC_MINT))
+        .merchant_settlement_ata(get_associated_token_address(settlement_wallet, &USDC_MINT))
+        .settlement_wallet(*settlement_wallet);
+    for reference in references {
+        builder.add_remaining_account(AccountMeta::new_readonly(*reference, false));
+    }
+    builder.instruction()
+}
+
+#[tokio::test]
+async fn test_make_payment_stores_references() {
+    let (
+        mut context,
+        operator_authority,
+        _merchant_authority,
+        settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    ) = setup_make_payment_test(false).await.unwrap();
+    context
+        .airdrop_if_required(&buyer.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    let references = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let order_id = 1u32;
+    let instruction = make_payment_with_references_instruction(
+        &context,
+        &operator_authority,
+        &buyer,
+        &operator_pda,
+        &merchant_pda,
+        &merchant_operator_config_pda,
+        &settlement_wallet.pubkey(),
+        order_id,
+        &references,
+    );
+    context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+        .unwrap();
+
+    let (payment_pda, _) = find_payment_pda(
+        &merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+    let payment_account = context.get_account(&payment_pda).unwrap();
+    let payment = commerce_program_client::Payment::from_bytes(&payment_account.data).unwrap();
+    assert_eq!(payment.references, references);
+
+    // A single reference leaves the second slot zeroed
+    let order_id = 2u32;
+    let instruction = make_payment_with_references_instruction(
+        &context,
+        &operator_authority,
+        &buyer,
+        &operator_pda,
+        &merchant_pda,
+        &merchant_operator_config_pda,
+        &settlement_wallet.pubkey(),
+        order_id,
+        &references[..1],
+    );
+    context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+        .unwrap();
+
+    let (payment_pda, _) = find_payment_pda(
+        &merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+    let payment_account = context.get_account(&payment_pda).unwrap();
+    let payment = commerce_program_client::Payment::from_bytes(&payment_account.data).unwrap();
+    assert_eq!(payment.references, [references[0], Pubkey::default()]);
+}
+
+#[tokio::test]
+async fn test_make_payment_too_many_references_fails() {
+    let (
+        mut context,
+        operator_authority,
+        _merchant_authority,
+        settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    ) = setup_make_payment_test(false).await.unwrap();
+    context
+        .airdrop_if_required(&buyer.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    let instruction = make_payment_with_references_instruction(
+        &context,
+        &operator_authority,
+        &buyer,
+        &operator_pda,
+        &merchant_pda,
+        &merchant_operator_config_pda,
+        &settlement_wallet.pubkey(),
+        1,
+        &[
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ],
+    );
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+    assert_program_error(result, TOO_MANY_PAYMENT_REFERENCES_ERROR);
+}
@@ -0,0 +1,451 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        set_token_balance, TestContext, TestContextBuilder, DAYS_TO_CLOSE,
+        INVALID_ACCOUNT_DATA_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{
+        ClearPaymentBuilder, ClosePaymentBuilder, MakePaymentBuilder,
+        ProposeMerchantAuthorityBuilder, ProposeOperatorAuthorityBuilder, RefundPaymentBuilder,
+        UpdateMerchantSettlementWalletBuilder,
+    },
+    types::{FeeType, PolicyData, RefundPolicy},
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+
+/// Offset of `status` in a Payment account
+const PAYMENT_STATUS_OFFSET: usize = 21;
+/// Offset of `num_policies` in a MerchantOperatorConfig account
+const CONFIG_NUM_POLICIES_OFFSET: usize = 85;
+
+struct MalformedAccountTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a merchant, operator, config and one paid payment
+fn setup_malformed_account_test() -> Result<MalformedAccountTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Refund(RefundPolicy {
+            max_amount: PAYMENT_AMOUNT,
+            max_time_after_purchase: 3600,
+        })],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &buyer_ata,
+        &USDC_MINT,
+        &buyer.pubkey(),
+        10 * PAYMENT_AMOUNT,
+    );
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        PAYMENT_AMOUNT,
+        true,
+        false,
+        false,
+    )?;
+
+    Ok(MalformedAccountTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+/// Truncations and corruptions applied to a program account's data
+fn truncations(data: &[u8]) -> Vec<Vec<u8>> {
+    vec![
+        Vec::new(),
+        data[..1].to_vec(),
+        data[..data.len() / 2].to_vec(),
+        data[..data.len() - 1].to_vec(),
+    ]
+}
+
+/// Sends `instruction` once per malformed version of `account`, expecting each to fail with
+/// InvalidAccountData, then restores the account.
+fn assert_rejects_malformed(
+    setup: &mut MalformedAccountTestSetup,
+    account: &Pubkey,
+    malformed: Vec<Vec<u8>>,
+    instruction: &Instruction,
+    signers: &[&Keypair],
+) {
+    let original = setup
+        .context
+        .get_account(account)
+        .expect("Account should exist");
+
+    for data in malformed {
+        setup
+            .context
+            .create_account(account, &original.owner, data, original.lamports);
+
+        let result = setup
+            .context
+            .send_transaction_with_signers(instruction.clone(), signers);
+        assert_program_error(result, INVALID_ACCOUNT_DATA_ERROR);
+
+        // Resending the same instruction needs a fresh blockhash
+        setup.context.svm.expire_blockhash();
+    }
+
+    setup
+        .context
+        .create_account(account, &original.owner, original.data, original.lamports);
+}
+
+fn make_payment_instruction(setup: &MalformedAccountTestSetup) -> Instruction {
+    let order_id = 2;
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(order_id)
+        .amount(PAYMENT_AMOUNT)
+        .idempotency_key([0; 16])
+        .instruction()
+}
+
+fn clear_payment_instruction(setup: &MalformedAccountTestSetup) -> Instruction {
+    ClearPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction()
+}
+
+fn refund_payment_instruction(setup: &MalformedAccountTestSetup) -> Instruction {
+    RefundPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction()
+}
+
+fn close_payment_instruction(setup: &MalformedAccountTestSetup) -> Instruction {
+    ClosePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .buyer(setup.buyer.pubkey())
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction()
+}
+
+#[tokio::test]
+async fn test_truncated_merchant_is_rejected() {
+    let mut setup = setup_malformed_account_test().unwrap();
+    let merchant_pda = setup.merchant_pda;
+    let merchant_data = setup.context.get_account_data(&merchant_pda).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    let make_payment = make_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &merchant_pda,
+        truncations(&merchant_data),
+        &make_payment,
+        &[&operator_authority, &buyer],
+    );
+
+    let clear_payment = clear_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &merchant_pda,
+        truncations(&merchant_data),
+        &clear_payment,
+        &[&operator_authority],
+    );
+
+    let update_settlement_wallet = UpdateMerchantSettlementWalletBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .authority(merchant_authority.pubkey())
+        .merchant(merchant_pda)
+        .new_settlement_wallet(Pubkey::new_unique())
+        .instruction();
+    assert_rejects_malformed(
+        &mut setup,
+        &merchant_pda,
+        truncations(&merchant_data),
+        &update_settlement_wallet,
+        &[&merchant_authority],
+    );
+
+    let propose_authority = ProposeMerchantAuthorityBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .authority(merchant_authority.pubkey())
+        .merchant(merchant_pda)
+        .new_authority(Pubkey::new_unique())
+        .instruction();
+    assert_rejects_malformed(
+        &mut setup,
+        &merchant_pda,
+        truncations(&merchant_data),
+        &propose_authority,
+        &[&merchant_authority],
+    );
+}
+
+#[tokio::test]
+async fn test_truncated_operator_is_rejected() {
+    let mut setup = setup_malformed_account_test().unwrap();
+    let operator_pda = setup.operator_pda;
+    let operator_data = setup.context.get_account_data(&operator_pda).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    let make_payment = make_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &operator_pda,
+        truncations(&operator_data),
+        &make_payment,
+        &[&operator_authority, &buyer],
+    );
+
+    let refund_payment = refund_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &operator_pda,
+        truncations(&operator_data),
+        &refund_payment,
+        &[&operator_authority],
+    );
+
+    let propose_authority = ProposeOperatorAuthorityBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .authority(operator_authority.pubkey())
+        .operator(operator_pda)
+        .new_operator_authority(Pubkey::new_unique())
+        .instruction();
+    assert_rejects_malformed(
+        &mut setup,
+        &operator_pda,
+        truncations(&operator_data),
+        &propose_authority,
+        &[&operator_authority],
+    );
+}
+
+#[tokio::test]
+async fn test_malformed_merchant_operator_config_is_rejected() {
+    let mut setup = setup_malformed_account_test().unwrap();
+    let config_pda = setup.merchant_operator_config_pda;
+    let config_data = setup.context.get_account_data(&config_pda).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    // Claims far more policies than the account holds
+    let mut overflowing_policies = config_data.clone();
+    overflowing_policies[CONFIG_NUM_POLICIES_OFFSET..CONFIG_NUM_POLICIES_OFFSET + 4]
+        .copy_from_slice(&u32::MAX.to_le_bytes());
+    let mut malformed = truncations(&config_data);
+    malformed.push(overflowing_policies);
+
+    let make_payment = make_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &config_pda,
+        malformed.clone(),
+        &make_payment,
+        &[&operator_authority, &buyer],
+    );
+
+    let clear_payment = clear_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &config_pda,
+        malformed.clone(),
+        &clear_payment,
+        &[&operator_authority],
+    );
+
+    let refund_payment = refund_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &config_pda,
+        malformed,
+        &refund_payment,
+        &[&operator_authority],
+    );
+}
+
+#[tokio::test]
+async fn test_malformed_payment_is_rejected() {
+    let mut setup = setup_malformed_account_test().unwrap();
+    let payment_pda = setup.payment_pda;
+    let payment_data = setup.context.get_account_data(&payment_pda).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    let mut unknown_status = payment_data.clone();
+    unknown_status[PAYMENT_STATUS_OFFSET] = 0xff;
+    let mut malformed = truncations(&payment_data);
+    malformed.push(unknown_status);
+
+    let clear_payment = clear_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &payment_pda,
+        malformed.clone(),
+        &clear_payment,
+        &[&operator_authority],
+    );
+
+    let refund_payment = refund_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &payment_pda,
+        malformed.clone(),
+        &refund_payment,
+        &[&operator_authority],
+    );
+
+    setup.context.advance_clock(8 * 24 * 60 * 60); // Past days_to_close
+    let close_payment = close_payment_instruction(&setup);
+    assert_rejects_malformed(
+        &mut setup,
+        &payment_pda,
+        malformed,
+        &close_payment,
+        &[&operator_authority],
+    );
+}
@@ -0,0 +1,250 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda,
+        get_or_create_associated_token_account, TestContext, DAYS_TO_CLOSE,
+        MEMO_PROGRAM_REQUIRED_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{ClearPaymentBuilder, RefundPaymentBuilder},
+    memo::{memo_program_account, transfer_memo},
+    types::{FeeType, MemoPolicy, PolicyData},
+    Payment,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+struct MemoTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a config with a memo policy and a single paid payment
+fn setup_memo_policy_test(
+    memo_on_clear: bool,
+    memo_on_refund: bool,
+) -> Result<MemoTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let policies = vec![PolicyData::Memo(MemoPolicy {
+        memo_on_clear,
+        memo_on_refund,
+    })];
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        1_000_000,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+
+    get_or_create_associated_token_account(&mut context, &settlement_wallet.pubkey(), &USDC_MINT);
+    get_or_create_associated_token_account(&mut context, &operator_authority.pubkey(), &USDC_MINT);
+
+    Ok(MemoTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn clear_payment_instruction(setup: &MemoTestSetup, with_memo_program: bool) -> Instruction {
+    let mut builder = ClearPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ));
+    if with_memo_program {
+        builder.add_remaining_account(memo_program_account());
+    }
+    builder.instruction()
+}
+
+fn refund_payment_instruction(setup: &MemoTestSetup, with_memo_program: bool) -> Instruction {
+    let mut builder = RefundPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ));
+    if with_memo_program {
+        builder.add_remaining_account(memo_program_account());
+    }
+    builder.instruction()
+}
+
+fn expected_memo(setup: &mut MemoTestSetup) -> String {
+    let payment_account = setup.context.get_account(&setup.payment_pda).unwrap();
+    let payment = Payment::from_bytes(&payment_account.data).unwrap();
+    transfer_memo(payment.order_id, &setup.payment_pda)
+}
+
+#[tokio::test]
+async fn test_clear_payment_with_memo_policy_logs_memo() {
+    let mut setup = setup_memo_policy_test(true, false).unwrap();
+    let memo = expected_memo(&mut setup);
+
+    let instruction = clear_payment_instruction(&setup, true);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&operator_authority],
+            false,
+        )
+        .expect("Clear payment should succeed");
+
+    assert!(metadata.logs.iter().any(|log| log.contains(&memo)));
+}
+
+#[tokio::test]
+async fn test_clear_payment_with_memo_policy_without_memo_program_fails() {
+    let mut setup = setup_memo_policy_test(true, false).unwrap();
+
+    let instruction = clear_payment_instruction(&setup, false);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, MEMO_PROGRAM_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_memo_disabled_skips_memo() {
+    let mut setup = setup_memo_policy_test(false, true).unwrap();
+    let memo = expected_memo(&mut setup);
+
+    // The memo program is ignored when the policy doesn't ask for a memo on clear
+    let instruction = clear_payment_instruction(&setup, true);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&operator_authority],
+            false,
+        )
+        .expect("Clear payment should succeed");
+
+    assert!(!metadata.logs.iter().any(|log| log.contains(&memo)));
+}
+
+#[tokio::test]
+async fn test_refund_payment_with_memo_policy_logs_memo() {
+    let mut setup = setup_memo_policy_test(false, true).unwrap();
+    let memo = expected_memo(&mut setup);
+
+    let instruction = refund_payment_instruction(&setup, true);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&operator_authority],
+            false,
+        )
+        .expect("Refund payment should succeed");
+
+    assert!(metadata.logs.iter().any(|log| log.contains(&memo)));
+}
+
+#[tokio::test]
+async fn test_refund_payment_with_memo_policy_without_memo_program_fails() {
+    let mut setup = setup_memo_policy_test(false, true).unwrap();
+
+    let instruction = refund_payment_instruction(&setup, false);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, MEMO_PROGRAM_REQUIRED_ERROR);
+}
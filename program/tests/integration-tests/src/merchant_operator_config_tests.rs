@@ -5,13 +5,14 @@ use crate::{
     },
     utils::{
         assert_program_error, set_mint, TestContext, ACCEPTED_CURRENCIES_EMPTY_ERROR,
-        DAYS_TO_CLOSE, DUPLICATE_MINT_ERROR, INVALID_ACCOUNT_OWNER_ERROR, INVALID_MINT_ERROR,
-        NOT_ENOUGH_ACCOUNT_KEYS_ERROR, USDC_MINT, USDT_MINT,
+        DAYS_TO_CLOSE, DUPLICATE_MINT_ERROR, INVALID_ACCOUNT_OWNER_ERROR,
+        INVALID_FEE_TIER_POLICY_ERROR, INVALID_MINT_ERROR, NOT_ENOUGH_ACCOUNT_KEYS_ERROR,
+        USDC_MINT, USDT_MINT,
     },
 };
 use commerce_program_client::{
     instructions::InitializeMerchantOperatorConfigBuilder,
-    types::{FeeType, PolicyData, RefundPolicy},
+    types::{FeeTier, FeeTierPolicy, FeeType, PolicyData, RefundPolicy},
 };
 use solana_sdk::{
     instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer,
@@ -452,3 +453,62 @@ async fn test_initialize_merchant_operator_config_duplicate_mint_fails() {
     // Should fail due to duplicate mint in accepted_currencies
     assert_program_error(result, DUPLICATE_MINT_ERROR);
 }
+
+#[tokio::test]
+async fn test_initialize_merchant_operator_config_unordered_fee_tiers_fails() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let owner = Keypair::new();
+
+    // Setup Merchant
+    let (merchant_pda, _) =
+        assert_get_or_create_merchant(&mut context, &authority, &settlement_wallet, false, false)
+            .unwrap();
+
+    // Setup Operator
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &owner, false, false).unwrap();
+
+    // Thresholds must be strictly ascending
+    let tier = |threshold, fee| FeeTier {
+        threshold,
+        fee,
+        fee_type: FeeType::Bps,
+    };
+    let policies = vec![PolicyData::FeeTier(FeeTierPolicy {
+        num_tiers: 2,
+        tiers: [
+            tier(100_000_000, 100),
+            tier(0, 200),
+            tier(0, 0),
+            tier(0, 0),
+            tier(0, 0),
+        ],
+    })];
+
+    let version = 1;
+    let (config_pda, bump) =
+        crate::utils::find_merchant_operator_config_pda(&merchant_pda, &operator_pda, version);
+
+    let instruction = InitializeMerchantOperatorConfigBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .operator(operator_pda)
+        .config(config_pda)
+        .version(version)
+        .bump(bump)
+        .operator_fee(100)
+        .fee_type(FeeType::Bps)
+        .days_to_close(DAYS_TO_CLOSE)
+        .policies(policies)
+        .accepted_currencies(vec![USDC_MINT])
+        .system_program(SYSTEM_PROGRAM_ID)
+        .add_remaining_account(AccountMeta::new_readonly(USDC_MINT, false))
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+
+    assert_program_error(result, INVALID_FEE_TIER_POLICY_ERROR);
+}
@@ -0,0 +1,180 @@
+use crate::{
+    assertions::assert_merchant_profile_account,
+    state_utils::*,
+    utils::{
+        assert_program_error, find_merchant_profile_pda, TestContext,
+        MERCHANT_OWNER_MISMATCH_ERROR, MERCHANT_PROFILE_FIELD_TOO_LONG_ERROR,
+        MERCHANT_PROFILE_INVALID_PDA_ERROR,
+    },
+};
+use commerce_program_client::instructions::{
+    InitializeMerchantProfileBuilder, UpdateMerchantProfileBuilder,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const NAME: &str = "Coffee Shop";
+const URI: &str = "https://coffee.example";
+const LOGO_HASH: [u8; 32] = [1; 32];
+const CONTACT_HASH: [u8; 32] = [2; 32];
+
+// Helper function to set up a merchant without a profile
+fn setup_merchant_profile_test(
+) -> Result<(TestContext, Keypair, Pubkey), Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    Ok((context, merchant_authority, merchant_pda))
+}
+
+fn update_merchant_profile_instruction(
+    authority: &Pubkey,
+    merchant_pda: &Pubkey,
+    merchant_profile_pda: &Pubkey,
+    name: &str,
+    uri: &str,
+) -> Instruction {
+    UpdateMerchantProfileBuilder::new()
+        .authority(*authority)
+        .merchant(*merchant_pda)
+        .merchant_profile(*merchant_profile_pda)
+        .name(name.to_string())
+        .uri(uri.to_string())
+        .logo_hash(LOGO_HASH)
+        .contact_hash([3; 32])
+        .instruction()
+}
+
+#[tokio::test]
+async fn test_initialize_and_update_merchant_profile() {
+    let (mut context, merchant_authority, merchant_pda) = setup_merchant_profile_test().unwrap();
+
+    let (merchant_profile_pda, _) = assert_initialize_merchant_profile(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        NAME,
+        URI,
+        LOGO_HASH,
+        CONTACT_HASH,
+        true, // fail_if_exists
+        false,
+    )
+    .unwrap();
+
+    // A shorter name and URI leave no trace of the previous ones
+    let instruction = update_merchant_profile_instruction(
+        &merchant_authority.pubkey(),
+        &merchant_pda,
+        &merchant_profile_pda,
+        "Tea",
+        "",
+    );
+    context
+        .send_transaction_with_signers(instruction, &[&merchant_authority])
+        .unwrap();
+
+    let merchant_profile = assert_merchant_profile_account(
+        &mut context,
+        &merchant_profile_pda,
+        &merchant_pda,
+        "Tea",
+        "",
+    );
+    assert_eq!(merchant_profile.contact_hash, [3; 32]);
+
+    let account = context.get_account(&merchant_profile_pda).unwrap();
+    let end_of_uri = 1 + 32 + 1 + 32 + 32 + 4 + 3 + 4;
+    assert!(account.data[end_of_uri..].iter().all(|byte| *byte == 0));
+}
+
+#[tokio::test]
+async fn test_initialize_merchant_profile_field_too_long_fails() {
+    let (mut context, merchant_authority, merchant_pda) = setup_merchant_profile_test().unwrap();
+    let (merchant_profile_pda, bump) = find_merchant_profile_pda(&merchant_pda);
+
+    let instruction = InitializeMerchantProfileBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(merchant_authority.pubkey())
+        .merchant(merchant_pda)
+        .merchant_profile(merchant_profile_pda)
+        .name("n".repeat(33))
+        .uri(URI.to_string())
+        .logo_hash(LOGO_HASH)
+        .contact_hash(CONTACT_HASH)
+        .bump(bump)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&merchant_authority]);
+    assert_program_error(result, MERCHANT_PROFILE_FIELD_TOO_LONG_ERROR);
+}
+
+#[tokio::test]
+async fn test_update_merchant_profile_wrong_authority_fails() {
+    let (mut context, merchant_authority, merchant_pda) = setup_merchant_profile_test().unwrap();
+    let (merchant_profile_pda, _) = assert_initialize_merchant_profile(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        NAME,
+        URI,
+        LOGO_HASH,
+        CONTACT_HASH,
+        true, // fail_if_exists
+        false,
+    )
+    .unwrap();
+
+    let imposter = Keypair::new();
+    let instruction = update_merchant_profile_instruction(
+        &imposter.pubkey(),
+        &merchant_pda,
+        &merchant_profile_pda,
+        "Imposter",
+        URI,
+    );
+
+    let result = context.send_transaction_with_signers(instruction, &[&imposter]);
+    assert_program_error(result, MERCHANT_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_update_other_merchants_profile_fails() {
+    let (mut context, merchant_authority, merchant_pda) = setup_merchant_profile_test().unwrap();
+    let (merchant_profile_pda, _) = assert_initialize_merchant_profile(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        NAME,
+        URI,
+        LOGO_HASH,
+        CONTACT_HASH,
+        true, // fail_if_exists
+        false,
+    )
+    .unwrap();
+
+    let other_authority = Keypair::new();
+    let (other_merchant_pda, _) =
+        assert_get_or_create_merchant(&mut context, &other_authority, &Keypair::new(), true, false)
+            .unwrap();
+
+    let instruction = update_merchant_profile_instruction(
+        &other_authority.pubkey(),
+        &other_merchant_pda,
+        &merchant_profile_pda,
+        "Imposter",
+        URI,
+    );
+
+    let result = context.send_transaction_with_signers(instruction, &[&other_authority]);
+    assert_program_error(result, MERCHANT_PROFILE_INVALID_PDA_ERROR);
+}
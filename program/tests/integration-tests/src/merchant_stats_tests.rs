@@ -0,0 +1,438 @@
+use crate::{
+    assertions::assert_merchant_stats_account,
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_merchant_stats_pda,
+        find_payment_pda, get_or_create_associated_token_account, idempotency_key,
+        set_token_balance, TestContext, DAYS_TO_CLOSE, MERCHANT_MISMATCH_ERROR,
+        MERCHANT_OWNER_MISMATCH_ERROR, MERCHANT_STATS_MISMATCH_ERROR, USDC_MINT, USDT_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{
+        ClearPaymentBuilder, InitializeMerchantStatsBuilder, MakePaymentBuilder,
+        RefundPaymentBuilder,
+    },
+    types::{FeeType, PolicyData, SettlementPolicy},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+struct StatsTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a merchant and operator config accepting USDC and USDT
+fn setup_merchant_stats_test(
+    auto_settle: bool,
+) -> Result<StatsTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let policies = vec![PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: 0,
+        settlement_frequency_hours: 0,
+        auto_settle,
+    })];
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT, USDT_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    Ok(StatsTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn make_payment_with_stats_instruction(
+    setup: &mut StatsTestSetup,
+    merchant_stats_pda: &Pubkey,
+    order_id: u32,
+    amount: u64,
+) -> (Instruction, Pubkey) {
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut setup.context,
+        &buyer_ata,
+        &USDC_MINT,
+        &setup.buyer.pubkey(),
+        amount * 2,
+    );
+
+    let instruction = MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(amount)
+        .add_remaining_account(AccountMeta::new(*merchant_stats_pda, false))
+        .instruction();
+
+    (instruction, payment_pda)
+}
+
+#[tokio::test]
+async fn test_initialize_merchant_stats_success() {
+    let mut setup = setup_merchant_stats_test(false).unwrap();
+
+    assert_initialize_merchant_stats(
+        &mut setup.context,
+        &setup.merchant_authority,
+        &setup.merchant_pda,
+        &USDC_MINT,
+        true, // fail_if_exists
+        true,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_initialize_merchant_stats_wrong_authority_fails() {
+    let mut setup = setup_merchant_stats_test(false).unwrap();
+    let wrong_authority = Keypair::new();
+    setup
+        .context
+        .airdrop_if_required(&wrong_authority.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    let (merchant_stats_pda, bump) = find_merchant_stats_pda(&setup.merchant_pda, &USDC_MINT);
+
+    let instruction = InitializeMerchantStatsBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .authority(wrong_authority.pubkey())
+        .merchant(setup.merchant_pda)
+        .mint(USDC_MINT)
+        .merchant_stats(merchant_stats_pda)
+        .bump(bump)
+        .instruction();
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&wrong_authority]);
+
+    assert_program_error(result, MERCHANT_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_and_clear_payment_update_merchant_stats() {
+    let mut setup = setup_merchant_stats_test(false).unwrap();
+    let amount = 1_000_000u64;
+
+    let (merchant_stats_pda, _) = assert_initialize_merchant_stats(
+        &mut setup.context,
+        &setup.merchant_authority,
+        &setup.merchant_pda,
+        &USDC_MINT,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let (instruction, payment_pda) =
+        make_payment_with_stats_instruction(&mut setup, &merchant_stats_pda, 1, amount);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+        .expect("Make payment should succeed");
+
+    let stats = assert_merchant_stats_account(
+        &mut setup.context,
+        &merchant_stats_pda,
+        &setup.merchant_pda,
+        &USDC_MINT,
+    );
+    assert_eq!(stats.payment_count, 1);
+    assert_eq!(stats.total_volume, amount);
+    assert_eq!(stats.total_cleared, 0);
+
+    let escrow_authority =
+        find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
+    let merchant_settlement_ata =
+        get_associated_token_address(&setup.settlement_wallet, &USDC_MINT);
+    let operator_settlement_ata =
+        get_associated_token_address(&operator_authority.pubkey(), &USDC_MINT);
+    get_or_create_associated_token_account(
+        &mut setup.context,
+        &setup.settlement_wallet,
+        &USDC_MINT,
+    );
+    get_or_create_associated_token_account(
+        &mut setup.context,
+        &operator_authority.pubkey(),
+        &USDC_MINT,
+    );
+
+    let instruction = ClearPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .merchant_settlement_ata(merchant_settlement_ata)
+        .operator_settlement_ata(operator_settlement_ata)
+        .add_remaining_account(AccountMeta::new(merchant_stats_pda, false))
+        .instruction();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Clear payment should succeed");
+
+    let stats = assert_merchant_stats_account(
+        &mut setup.context,
+        &merchant_stats_pda,
+        &setup.merchant_pda,
+        &USDC_MINT,
+    );
+    assert_eq!(stats.payment_count, 1);
+    assert_eq!(stats.total_cleared, amount);
+    assert_eq!(stats.total_fees_paid, amount * 500 / 10_000);
+    assert_eq!(stats.refund_count, 0);
+}
+
+#[tokio::test]
+async fn test_auto_settle_payment_updates_merchant_stats() {
+    let mut setup = setup_merchant_stats_test(true).unwrap();
+    let amount = 250_000u64;
+
+    let (merchant_stats_pda, _) = assert_initialize_merchant_stats(
+        &mut setup.context,
+        &setup.merchant_authority,
+        &setup.merchant_pda,
+        &USDC_MINT,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let (instruction, _) =
+        make_payment_with_stats_instruction(&mut setup, &merchant_stats_pda, 1, amount);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+        .expect("Make payment should succeed");
+
+    let stats = assert_merchant_stats_account(
+        &mut setup.context,
+        &merchant_stats_pda,
+        &setup.merchant_pda,
+        &USDC_MINT,
+    );
+    assert_eq!(stats.payment_count, 1);
+    assert_eq!(stats.total_volume, amount);
+    assert_eq!(stats.total_cleared, amount);
+    assert_eq!(stats.total_fees_paid, 0);
+}
+
+#[tokio::test]
+async fn test_refund_payment_updates_merchant_stats() {
+    let mut setup = setup_merchant_stats_test(false).unwrap();
+    let amount = 400_000u64;
+
+    let (merchant_stats_pda, _) = assert_initialize_merchant_stats(
+        &mut setup.context,
+        &setup.merchant_authority,
+        &setup.merchant_pda,
+        &USDC_MINT,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let (instruction, payment_pda) =
+        make_payment_with_stats_instruction(&mut setup, &merchant_stats_pda, 1, amount);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+        .expect("Make payment should succeed");
+
+    let instruction = RefundPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(&buyer.pubkey(), &USDC_MINT))
+        .add_remaining_account(AccountMeta::new(merchant_stats_pda, false))
+        .instruction();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Refund payment should succeed");
+
+    let stats = assert_merchant_stats_account(
+        &mut setup.context,
+        &merchant_stats_pda,
+        &setup.merchant_pda,
+        &USDC_MINT,
+    );
+    assert_eq!(stats.payment_count, 1);
+    assert_eq!(stats.refund_count, 1);
+    assert_eq!(stats.total_refunded, amount);
+    assert_eq!(stats.total_cleared, 0);
+}
+
+#[tokio::test]
+async fn test_make_payment_with_other_mint_stats_fails() {
+    let mut setup = setup_merchant_stats_test(false).unwrap();
+
+    // Stats account tracks USDT, payment is made in USDC
+    let (usdt_stats_pda, _) = assert_initialize_merchant_stats(
+        &mut setup.context,
+        &setup.merchant_authority,
+        &setup.merchant_pda,
+        &USDT_MINT,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let (instruction, _) =
+        make_payment_with_stats_instruction(&mut setup, &usdt_stats_pda, 1, 100_000);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+
+    assert_program_error(result, MERCHANT_STATS_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_with_other_merchant_stats_fails() {
+    let mut setup = setup_merchant_stats_test(false).unwrap();
+
+    // Stats for a merchant outside the config, passed as the payment's merchant
+    let other_merchant_authority = Keypair::new();
+    let other_settlement_wallet = Keypair::new();
+    let (other_merchant_pda, _) = assert_get_or_create_merchant(
+        &mut setup.context,
+        &other_merchant_authority,
+        &other_settlement_wallet,
+        true,
+        false,
+    )
+    .unwrap();
+    let (other_stats_pda, _) = assert_initialize_merchant_stats(
+        &mut setup.context,
+        &other_merchant_authority,
+        &other_merchant_pda,
+        &USDC_MINT,
+        true,
+        false,
+    )
+    .unwrap();
+    setup.merchant_pda = other_merchant_pda;
+    setup.settlement_wallet = other_settlement_wallet.pubkey();
+
+    let (instruction, _) =
+        make_payment_with_stats_instruction(&mut setup, &other_stats_pda, 1, 100_000);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+
+    assert_program_error(result, MERCHANT_MISMATCH_ERROR);
+    let stats = assert_merchant_stats_account(
+        &mut setup.context,
+        &other_stats_pda,
+        &other_merchant_pda,
+        &USDC_MINT,
+    );
+    assert_eq!(stats.payment_count, 0);
+}
@@ -1,12 +1,26 @@
 use crate::{
     state_utils::{
-        assert_get_or_create_merchant, assert_update_merchant_authority,
-        assert_update_merchant_settlement_wallet,
+        assert_accept_merchant_authority, assert_commit_settlement_wallet,
+        assert_get_or_create_merchant, assert_get_or_create_merchant_with_timelock,
+        assert_propose_merchant_authority, assert_propose_settlement_wallet,
+        assert_set_settlement_wallet_timelock, assert_update_merchant_settlement_wallet,
     },
-    utils::TestContext,
+    utils::{
+        assert_program_error, find_merchant_pda, TestContext,
+        INVALID_SETTLEMENT_WALLET_TIMELOCK_ERROR, MERCHANT_PENDING_AUTHORITY_MISMATCH_ERROR,
+        SETTLEMENT_WALLET_NOT_PROPOSED_ERROR, SETTLEMENT_WALLET_TIMELOCKED_ERROR,
+        SETTLEMENT_WALLET_TIMELOCK_ACTIVE_ERROR, SETTLEMENT_WALLET_TIMELOCK_LOWERED_ERROR,
+    },
+};
+use commerce_program_client::instructions::{
+    AcceptMerchantAuthorityBuilder, CommitSettlementWalletBuilder, InitializeMerchantBuilder,
+    SetSettlementWalletTimelockBuilder, UpdateMerchantSettlementWalletBuilder,
 };
 use solana_sdk::signature::{Keypair, Signer};
 
+const SETTLEMENT_WALLET_TIMELOCK_SECONDS: u64 = 24 * 60 * 60;
+const MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS: u64 = 30 * 24 * 60 * 60;
+
 #[tokio::test]
 async fn test_create_merchant_success() {
     let mut context = TestContext::new();
@@ -38,14 +52,365 @@ pub async fn test_update_merchant_settlement_wallet_success() {
 }
 
 #[tokio::test]
-pub async fn test_update_merchant_authority_success() {
+pub async fn test_transfer_merchant_authority_success() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let new_authority = Keypair::new();
+
+    assert_get_or_create_merchant(&mut context, &authority, &settlement_wallet, true, false)
+        .unwrap();
+
+    assert_propose_merchant_authority(&mut context, &authority, &new_authority.pubkey(), true)
+        .unwrap();
+
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    assert_accept_merchant_authority(
+        &mut context,
+        &merchant_pda,
+        &new_authority,
+        &settlement_wallet.pubkey(),
+        true,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_accept_merchant_authority_wrong_signer_fails() {
     let mut context = TestContext::new();
     let authority = Keypair::new();
     let settlement_wallet = Keypair::new();
+    let new_authority = Keypair::new();
+    let wrong_authority = Keypair::new();
 
     assert_get_or_create_merchant(&mut context, &authority, &settlement_wallet, true, false)
         .unwrap();
 
-    assert_update_merchant_authority(&mut context, &authority, &settlement_wallet.pubkey(), true)
+    assert_propose_merchant_authority(&mut context, &authority, &new_authority.pubkey(), false)
         .unwrap();
+
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let instruction = AcceptMerchantAuthorityBuilder::new()
+        .payer(context.payer.pubkey())
+        .new_authority(wrong_authority.pubkey())
+        .merchant(merchant_pda)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&wrong_authority]);
+    assert_program_error(result, MERCHANT_PENDING_AUTHORITY_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+pub async fn test_accept_merchant_authority_without_proposal_fails() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    assert_get_or_create_merchant(&mut context, &authority, &settlement_wallet, true, false)
+        .unwrap();
+
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let instruction = AcceptMerchantAuthorityBuilder::new()
+        .payer(context.payer.pubkey())
+        .new_authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, MERCHANT_PENDING_AUTHORITY_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+pub async fn test_initialize_merchant_timelock_too_long_fails() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (merchant_pda, bump) = find_merchant_pda(&authority.pubkey());
+    let instruction = InitializeMerchantBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .settlement_wallet(settlement_wallet.pubkey())
+        .bump(bump)
+        .settlement_wallet_timelock_seconds(MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS + 1)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, INVALID_SETTLEMENT_WALLET_TIMELOCK_ERROR);
+}
+
+#[tokio::test]
+pub async fn test_update_timelocked_settlement_wallet_fails() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    assert_get_or_create_merchant_with_timelock(
+        &mut context,
+        &authority,
+        &settlement_wallet,
+        SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let instruction = UpdateMerchantSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .new_settlement_wallet(Keypair::new().pubkey())
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, SETTLEMENT_WALLET_TIMELOCKED_ERROR);
+}
+
+#[tokio::test]
+pub async fn test_timelocked_settlement_wallet_change_success() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let new_settlement_wallet = Keypair::new();
+
+    assert_get_or_create_merchant_with_timelock(
+        &mut context,
+        &authority,
+        &settlement_wallet,
+        SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+        true,
+        false,
+    )
+    .unwrap();
+
+    assert_propose_settlement_wallet(
+        &mut context,
+        &authority,
+        &new_settlement_wallet.pubkey(),
+        true,
+    )
+    .unwrap();
+
+    // Committing before the timelock elapses fails
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let instruction = CommitSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .instruction();
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, SETTLEMENT_WALLET_TIMELOCK_ACTIVE_ERROR);
+
+    context.advance_clock(SETTLEMENT_WALLET_TIMELOCK_SECONDS as i64);
+
+    assert_commit_settlement_wallet(
+        &mut context,
+        &authority,
+        &new_settlement_wallet.pubkey(),
+        true,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_settlement_wallet_reproposal_restarts_timelock() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    assert_get_or_create_merchant_with_timelock(
+        &mut context,
+        &authority,
+        &settlement_wallet,
+        SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+        true,
+        false,
+    )
+    .unwrap();
+
+    assert_propose_settlement_wallet(&mut context, &authority, &Keypair::new().pubkey(), false)
+        .unwrap();
+
+    context.advance_clock(SETTLEMENT_WALLET_TIMELOCK_SECONDS as i64 - 1);
+
+    let new_settlement_wallet = Keypair::new();
+    assert_propose_settlement_wallet(
+        &mut context,
+        &authority,
+        &new_settlement_wallet.pubkey(),
+        false,
+    )
+    .unwrap();
+
+    context.advance_clock(1);
+
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let instruction = CommitSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .instruction();
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, SETTLEMENT_WALLET_TIMELOCK_ACTIVE_ERROR);
+
+    context.advance_clock(SETTLEMENT_WALLET_TIMELOCK_SECONDS as i64);
+
+    assert_commit_settlement_wallet(
+        &mut context,
+        &authority,
+        &new_settlement_wallet.pubkey(),
+        false,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+pub async fn test_commit_settlement_wallet_without_proposal_fails() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    assert_get_or_create_merchant_with_timelock(
+        &mut context,
+        &authority,
+        &settlement_wallet,
+        SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let instruction = CommitSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, SETTLEMENT_WALLET_NOT_PROPOSED_ERROR);
+}
+
+#[tokio::test]
+pub async fn test_raise_settlement_wallet_timelock_success() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    // Legacy merchants start without a timelock
+    assert_get_or_create_merchant(&mut context, &authority, &settlement_wallet, true, false)
+        .unwrap();
+
+    assert_set_settlement_wallet_timelock(
+        &mut context,
+        &authority,
+        SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+        true,
+    )
+    .unwrap();
+
+    // Direct updates are now timelocked
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let instruction = UpdateMerchantSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .new_settlement_wallet(Keypair::new().pubkey())
+        .instruction();
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, SETTLEMENT_WALLET_TIMELOCKED_ERROR);
+}
+
+#[tokio::test]
+pub async fn test_lower_settlement_wallet_timelock_fails() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    assert_get_or_create_merchant_with_timelock(
+        &mut context,
+        &authority,
+        &settlement_wallet,
+        SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let instruction = SetSettlementWalletTimelockBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .settlement_wallet_timelock_seconds(SETTLEMENT_WALLET_TIMELOCK_SECONDS - 1)
+        .instruction();
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, SETTLEMENT_WALLET_TIMELOCK_LOWERED_ERROR);
+
+    let instruction = SetSettlementWalletTimelockBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .settlement_wallet_timelock_seconds(MAX_SETTLEMENT_WALLET_TIMELOCK_SECONDS + 1)
+        .instruction();
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, INVALID_SETTLEMENT_WALLET_TIMELOCK_ERROR);
+}
+
+#[tokio::test]
+pub async fn test_raise_settlement_wallet_timelock_extends_pending_proposal() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let new_settlement_wallet = Keypair::new();
+
+    assert_get_or_create_merchant_with_timelock(
+        &mut context,
+        &authority,
+        &settlement_wallet,
+        SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+        true,
+        false,
+    )
+    .unwrap();
+
+    assert_propose_settlement_wallet(
+        &mut context,
+        &authority,
+        &new_settlement_wallet.pubkey(),
+        false,
+    )
+    .unwrap();
+
+    assert_set_settlement_wallet_timelock(
+        &mut context,
+        &authority,
+        2 * SETTLEMENT_WALLET_TIMELOCK_SECONDS,
+        false,
+    )
+    .unwrap();
+
+    // The original unlock time no longer releases the proposal
+    context.advance_clock(SETTLEMENT_WALLET_TIMELOCK_SECONDS as i64);
+
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let instruction = CommitSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .instruction();
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, SETTLEMENT_WALLET_TIMELOCK_ACTIVE_ERROR);
+
+    context.advance_clock(SETTLEMENT_WALLET_TIMELOCK_SECONDS as i64);
+
+    assert_commit_settlement_wallet(
+        &mut context,
+        &authority,
+        &new_settlement_wallet.pubkey(),
+        false,
+    )
+    .unwrap();
 }
@@ -0,0 +1,215 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda,
+        get_or_create_associated_token_account, get_token_balance, set_token_balance, TestContext,
+        DAYS_TO_CLOSE, ESCROW_AUTHORITY_INVALID_PDA_ERROR, MISSING_REQUIRED_SIGNATURE_ERROR,
+        TOKEN_INSUFFICIENT_FUNDS_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::MigrateEscrowBuilder,
+    types::{FeeType, PolicyData, SettlementPolicy},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const ORDER_ID: u32 = 1;
+const LEGACY_ESCROW_AMOUNT: u64 = 5_000_000;
+const MIGRATE_AMOUNT: u64 = 3_000_000;
+
+struct MigrateEscrowTestSetup {
+    context: TestContext,
+    merchant_authority: Keypair,
+    operator_authority: Keypair,
+    merchant_pda: Pubkey,
+    operator_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    legacy_escrow_ata: Pubkey,
+}
+
+// Helper function to set up a config whose funds sit in the merchant PDA's legacy escrow ATA
+fn setup_migrate_escrow_test() -> Result<MigrateEscrowTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let legacy_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &legacy_escrow_ata,
+        &USDC_MINT,
+        &merchant_pda,
+        LEGACY_ESCROW_AMOUNT,
+    );
+
+    Ok(MigrateEscrowTestSetup {
+        context,
+        merchant_authority,
+        operator_authority,
+        merchant_pda,
+        operator_pda,
+        merchant_operator_config_pda,
+        legacy_escrow_ata,
+    })
+}
+
+fn migrate_escrow_instruction(
+    setup: &MigrateEscrowTestSetup,
+    escrow_authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    MigrateEscrowBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .merchant_authority(setup.merchant_authority.pubkey())
+        .operator_authority(setup.operator_authority.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .legacy_escrow_ata(setup.legacy_escrow_ata)
+        .escrow_authority(*escrow_authority)
+        .merchant_escrow_ata(get_associated_token_address(escrow_authority, &USDC_MINT))
+        .amount(amount)
+        .instruction()
+}
+
+#[tokio::test]
+async fn test_migrate_escrow_success() {
+    let mut setup = setup_migrate_escrow_test().unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let (escrow_authority, _) =
+        find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT);
+    let escrow_ata = find_escrow_ata(&setup.merchant_operator_config_pda, &USDC_MINT);
+
+    let instruction = migrate_escrow_instruction(&setup, &escrow_authority, MIGRATE_AMOUNT);
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority])
+        .expect("Migrate escrow should succeed");
+
+    assert_eq!(
+        get_token_balance(&mut setup.context, &setup.legacy_escrow_ata),
+        LEGACY_ESCROW_AMOUNT - MIGRATE_AMOUNT
+    );
+    assert_eq!(
+        get_token_balance(&mut setup.context, &escrow_ata),
+        MIGRATE_AMOUNT
+    );
+
+    // The migrated escrow clears like any payment made after the migration
+    let buyer = Keypair::new();
+    get_or_create_associated_token_account(&mut setup.context, &buyer.pubkey(), &USDC_MINT);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let (payment_pda, _) = assert_make_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &setup.merchant_operator_config_pda,
+        &setup.operator_pda,
+        &USDC_MINT,
+        ORDER_ID,
+        MIGRATE_AMOUNT,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+    assert_clear_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        get_token_balance(&mut setup.context, &escrow_ata),
+        MIGRATE_AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_escrow_requires_merchant_signature() {
+    let mut setup = setup_migrate_escrow_test().unwrap();
+    let (escrow_authority, _) =
+        find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT);
+
+    let mut instruction = migrate_escrow_instruction(&setup, &escrow_authority, MIGRATE_AMOUNT);
+    instruction.accounts[1] = AccountMeta::new_readonly(setup.merchant_authority.pubkey(), false);
+
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, MISSING_REQUIRED_SIGNATURE_ERROR);
+}
+
+#[tokio::test]
+async fn test_migrate_escrow_rejects_merchant_owned_destination() {
+    let mut setup = setup_migrate_escrow_test().unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    // The merchant PDA is not the config's escrow authority
+    let merchant_pda = setup.merchant_pda;
+    let instruction = migrate_escrow_instruction(&setup, &merchant_pda, MIGRATE_AMOUNT);
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority]);
+    assert_program_error(result, ESCROW_AUTHORITY_INVALID_PDA_ERROR);
+}
+
+#[tokio::test]
+async fn test_migrate_escrow_amount_exceeds_legacy_balance() {
+    let mut setup = setup_migrate_escrow_test().unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let (escrow_authority, _) =
+        find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT);
+
+    let instruction =
+        migrate_escrow_instruction(&setup, &escrow_authority, LEGACY_ESCROW_AMOUNT + 1);
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority]);
+    assert_program_error(result, TOKEN_INSUFFICIENT_FUNDS_ERROR);
+}
@@ -0,0 +1,331 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_payment_migrated_event_present, assert_program_error, find_escrow_ata,
+        find_escrow_authority_pda, find_payment_pda, get_or_create_associated_token_account,
+        get_token_balance, TestContext, DAYS_TO_CLOSE, INVALID_PAYMENT_STATUS_ERROR,
+        MERCHANT_OWNER_MISMATCH_ERROR, MIGRATION_TARGET_UNCHANGED_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::MigratePaymentBuilder,
+    types::{FeeType, PolicyData, SettlementPolicy, Status},
+    MerchantOperatorConfig, Payment,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 1_000_000;
+
+struct MigratePaymentTestSetup {
+    context: TestContext,
+    merchant_authority: Keypair,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    merchant_pda: Pubkey,
+    operator_pda: Pubkey,
+    old_config_pda: Pubkey,
+    new_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up configs version 1 and 2 and a paid payment under version 1
+fn setup_migrate_payment_test() -> Result<MigratePaymentTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let mut configs = Vec::new();
+    for version in [1, 2] {
+        let (config_pda, _) = assert_get_or_create_merchant_operator_config(
+            &mut context,
+            &merchant_authority,
+            &merchant_pda,
+            &operator_pda,
+            version,
+            500,
+            FeeType::Bps,
+            0,
+            DAYS_TO_CLOSE,
+            vec![PolicyData::Settlement(SettlementPolicy {
+                min_settlement_amount: 0,
+                settlement_frequency_hours: 0,
+                auto_settle: false,
+            })],
+            vec![USDC_MINT],
+            true,
+            false,
+        )?;
+        configs.push(config_pda);
+    }
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &configs[0],
+        &operator_pda,
+        &USDC_MINT,
+        ORDER_ID,
+        AMOUNT,
+        true,
+        false,
+        false,
+    )?;
+
+    Ok(MigratePaymentTestSetup {
+        context,
+        merchant_authority,
+        operator_authority,
+        buyer,
+        merchant_pda,
+        operator_pda,
+        old_config_pda: configs[0],
+        new_config_pda: configs[1],
+        payment_pda,
+    })
+}
+
+fn current_order_id(context: &mut TestContext, config_pda: &Pubkey) -> u32 {
+    let config_account = context.get_account(config_pda).unwrap();
+    MerchantOperatorConfig::from_bytes(&config_account.data)
+        .unwrap()
+        .current_order_id
+}
+
+fn migrate_payment_instruction(
+    setup: &MigratePaymentTestSetup,
+    merchant_authority: &Pubkey,
+    new_config_pda: &Pubkey,
+) -> Instruction {
+    let (new_payment_pda, _) =
+        find_payment_pda(new_config_pda, &setup.buyer.pubkey(), &USDC_MINT, ORDER_ID);
+
+    MigratePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .merchant_authority(*merchant_authority)
+        .operator_authority(setup.operator_authority.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .buyer(setup.buyer.pubkey())
+        .mint(USDC_MINT)
+        .old_merchant_operator_config(setup.old_config_pda)
+        .new_merchant_operator_config(*new_config_pda)
+        .old_payment(setup.payment_pda)
+        .new_payment(new_payment_pda)
+        .old_escrow_authority(find_escrow_authority_pda(&setup.old_config_pda, &USDC_MINT).0)
+        .old_merchant_escrow_ata(find_escrow_ata(&setup.old_config_pda, &USDC_MINT))
+        .new_escrow_authority(find_escrow_authority_pda(new_config_pda, &USDC_MINT).0)
+        .new_merchant_escrow_ata(find_escrow_ata(new_config_pda, &USDC_MINT))
+        .instruction()
+}
+
+#[tokio::test]
+async fn test_migrate_payment_success() {
+    let mut setup = setup_migrate_payment_test().unwrap();
+    let old_payment_account = setup.context.get_account(&setup.payment_pda).unwrap();
+    let old_payment = Payment::from_bytes(&old_payment_account.data).unwrap();
+    let old_escrow_ata = find_escrow_ata(&setup.old_config_pda, &USDC_MINT);
+    let new_escrow_ata = find_escrow_ata(&setup.new_config_pda, &USDC_MINT);
+    let (new_payment_pda, new_payment_bump) = find_payment_pda(
+        &setup.new_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+
+    let instruction = migrate_payment_instruction(
+        &setup,
+        &setup.merchant_authority.pubkey(),
+        &setup.new_config_pda,
+    );
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let transaction_metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&merchant_authority, &operator_authority],
+            false,
+        )
+        .expect("Migrate payment should succeed");
+
+    // The escrowed amount moved to the new config's escrow
+    assert_eq!(get_token_balance(&mut setup.context, &old_escrow_ata), 0);
+    assert_eq!(
+        get_token_balance(&mut setup.context, &new_escrow_ata),
+        AMOUNT
+    );
+
+    // The payment moved to its PDA under the new config with the old account's rent
+    assert!(setup.context.get_account(&setup.payment_pda).is_none());
+    let new_payment_account = setup.context.get_account(&new_payment_pda).unwrap();
+    assert_eq!(new_payment_account.lamports, old_payment_account.lamports);
+    let new_payment = Payment::from_bytes(&new_payment_account.data).unwrap();
+    assert_eq!(new_payment.bump, new_payment_bump);
+    assert_eq!(new_payment.status, Status::Paid);
+    assert_eq!(
+        Payment {
+            bump: old_payment.bump,
+            ..new_payment
+        },
+        old_payment
+    );
+
+    // The new config's current order id caught up with the migrated payment's
+    assert_eq!(
+        current_order_id(&mut setup.context, &setup.new_config_pda),
+        ORDER_ID
+    );
+
+    assert_payment_migrated_event_present(
+        &transaction_metadata,
+        &setup.buyer.pubkey(),
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        ORDER_ID,
+        AMOUNT,
+        &setup.payment_pda,
+        &new_payment_pda,
+        &setup.old_config_pda,
+        &setup.new_config_pda,
+    );
+
+    // The migrated payment clears under the new config
+    let payer = setup.context.payer.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_clear_payment(
+        &mut setup.context,
+        &payer,
+        &operator_authority,
+        &buyer,
+        &new_payment_pda,
+        &USDC_MINT,
+        &setup.new_config_pda,
+        false,
+    )
+    .unwrap();
+    setup.context.assert_escrow_invariant();
+}
+
+#[tokio::test]
+async fn test_migrate_payment_keeps_later_current_order_id() {
+    let mut setup = setup_migrate_payment_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let other_buyer = Keypair::new();
+    get_or_create_associated_token_account(&mut setup.context, &other_buyer.pubkey(), &USDC_MINT);
+
+    // The new config is already past the migrated payment's order id
+    for order_id in [ORDER_ID + 1, ORDER_ID + 2] {
+        assert_make_payment(
+            &mut setup.context,
+            &operator_authority,
+            &operator_authority,
+            &other_buyer,
+            &setup.new_config_pda,
+            &setup.operator_pda,
+            &USDC_MINT,
+            order_id,
+            AMOUNT,
+            true,
+            false,
+            false,
+        )
+        .unwrap();
+    }
+    let new_config_order_id = current_order_id(&mut setup.context, &setup.new_config_pda);
+    assert!(new_config_order_id > ORDER_ID);
+
+    let instruction = migrate_payment_instruction(
+        &setup,
+        &setup.merchant_authority.pubkey(),
+        &setup.new_config_pda,
+    );
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority, &operator_authority])
+        .expect("Migrate payment should succeed");
+
+    assert_eq!(
+        current_order_id(&mut setup.context, &setup.new_config_pda),
+        new_config_order_id
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_payment_to_same_config_fails() {
+    let mut setup = setup_migrate_payment_test().unwrap();
+
+    let instruction = migrate_payment_instruction(
+        &setup,
+        &setup.merchant_authority.pubkey(),
+        &setup.old_config_pda,
+    );
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority, &operator_authority]);
+    assert_program_error(result, MIGRATION_TARGET_UNCHANGED_ERROR);
+}
+
+#[tokio::test]
+async fn test_migrate_cleared_payment_fails() {
+    let mut setup = setup_migrate_payment_test().unwrap();
+    let payer = setup.context.payer.insecure_clone();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_clear_payment(
+        &mut setup.context,
+        &payer,
+        &operator_authority,
+        &buyer,
+        &setup.payment_pda,
+        &USDC_MINT,
+        &setup.old_config_pda,
+        false,
+    )
+    .unwrap();
+
+    let instruction = migrate_payment_instruction(
+        &setup,
+        &setup.merchant_authority.pubkey(),
+        &setup.new_config_pda,
+    );
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority, &operator_authority]);
+    assert_program_error(result, INVALID_PAYMENT_STATUS_ERROR);
+}
+
+#[tokio::test]
+async fn test_migrate_payment_wrong_merchant_authority_fails() {
+    let mut setup = setup_migrate_payment_test().unwrap();
+    let other_authority = Keypair::new();
+
+    let instruction =
+        migrate_payment_instruction(&setup, &other_authority.pubkey(), &setup.new_config_pda);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&other_authority, &operator_authority]);
+    assert_program_error(result, MERCHANT_OWNER_MISMATCH_ERROR);
+}
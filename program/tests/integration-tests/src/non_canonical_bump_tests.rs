@@ -0,0 +1,145 @@
+use crate::{
+    assertions::assert_account_not_exists,
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_authority_pda, find_non_canonical_pda,
+        get_or_create_associated_token_account, idempotency_key, TestContext, DAYS_TO_CLOSE,
+        INVALID_INSTRUCTION_DATA_ERROR, INVALID_SEEDS_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{CreateOperatorBuilder, InitializeMerchantBuilder, MakePaymentBuilder},
+    pdas::{MERCHANT_SEED, OPERATOR_SEED, PAYMENT_SEED},
+    types::{FeeType, PolicyData, SettlementPolicy},
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 1_000_000;
+
+#[tokio::test]
+async fn test_create_operator_non_canonical_pda_fails() {
+    let mut context = TestContext::new();
+    let owner = Keypair::new();
+    let (non_canonical_operator, _) =
+        find_non_canonical_pda(&[OPERATOR_SEED, owner.pubkey().as_ref()]);
+
+    let instruction = CreateOperatorBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(owner.pubkey())
+        .operator(non_canonical_operator)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&owner]);
+    assert_program_error(result, INVALID_SEEDS_ERROR);
+    assert_account_not_exists(&mut context, &non_canonical_operator);
+}
+
+#[tokio::test]
+async fn test_initialize_merchant_non_canonical_bump_fails() {
+    let mut context = TestContext::new();
+    let authority = Keypair::new();
+    context
+        .airdrop_if_required(&authority.pubkey(), 1_000_000_000)
+        .unwrap();
+    let (non_canonical_merchant, non_canonical_bump) =
+        find_non_canonical_pda(&[MERCHANT_SEED, authority.pubkey().as_ref()]);
+
+    // InitializeMerchant still takes its bump, which must be the canonical one
+    let instruction = InitializeMerchantBuilder::new()
+        .bump(non_canonical_bump)
+        .settlement_wallet_timelock_seconds(0)
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(non_canonical_merchant)
+        .settlement_wallet(Pubkey::new_unique())
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&authority]);
+    assert_program_error(result, INVALID_INSTRUCTION_DATA_ERROR);
+    assert_account_not_exists(&mut context, &non_canonical_merchant);
+}
+
+#[tokio::test]
+async fn test_make_payment_non_canonical_payment_pda_fails() {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false).unwrap();
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )
+    .unwrap();
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )
+    .unwrap();
+
+    let (non_canonical_payment, _) = find_non_canonical_pda(&[
+        PAYMENT_SEED,
+        merchant_operator_config_pda.as_ref(),
+        buyer.pubkey().as_ref(),
+        USDC_MINT.as_ref(),
+        &ORDER_ID.to_le_bytes(),
+    ]);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+
+    let instruction = MakePaymentBuilder::new()
+        .order_id(ORDER_ID)
+        .idempotency_key(idempotency_key(ORDER_ID))
+        .amount(AMOUNT)
+        .payer(context.payer.pubkey())
+        .payment(non_canonical_payment)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .operator(operator_pda)
+        .merchant(merchant_pda)
+        .merchant_operator_config(merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(&buyer.pubkey(), &USDC_MINT))
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(get_associated_token_address(&escrow_authority, &USDC_MINT))
+        .merchant_settlement_ata(get_associated_token_address(
+            &settlement_wallet.pubkey(),
+            &USDC_MINT,
+        ))
+        .settlement_wallet(settlement_wallet.pubkey())
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+    assert_program_error(result, INVALID_SEEDS_ERROR);
+    assert_account_not_exists(&mut context, &non_canonical_payment);
+}
@@ -0,0 +1,257 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        get_or_create_associated_token_account, get_token_balance, idempotency_key,
+        set_token_balance, TestContext, DAYS_TO_CLOSE, INVALID_ATA_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{ClearPaymentBuilder, MakePaymentBuilder, RefundPaymentBuilder},
+    types::{FeeType, PolicyData, SettlementPolicy},
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 1_000_000;
+
+struct NonCanonicalEscrowTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    escrow_authority: Pubkey,
+}
+
+// Helper function to set up a config without auto settlement, so payments go to escrow
+fn setup_non_canonical_escrow_test(
+) -> Result<NonCanonicalEscrowTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+
+    Ok(NonCanonicalEscrowTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        escrow_authority,
+    })
+}
+
+// A token account of the escrow authority and USDC that is not its ATA
+fn create_non_canonical_escrow(setup: &mut NonCanonicalEscrowTestSetup, amount: u64) -> Pubkey {
+    let token_account = Pubkey::new_unique();
+    set_token_balance(
+        &mut setup.context,
+        &token_account,
+        &USDC_MINT,
+        &setup.escrow_authority,
+        amount,
+    );
+    token_account
+}
+
+fn make_escrowed_payment(setup: &mut NonCanonicalEscrowTestSetup) -> Pubkey {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_make_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &setup.merchant_operator_config_pda,
+        &setup.operator_pda,
+        &USDC_MINT,
+        ORDER_ID,
+        AMOUNT,
+        true,
+        false,
+        false,
+    )
+    .unwrap()
+    .0
+}
+
+#[tokio::test]
+async fn test_make_payment_non_canonical_escrow_fails() {
+    let mut setup = setup_non_canonical_escrow_test().unwrap();
+    let non_canonical_escrow = create_non_canonical_escrow(&mut setup, 0);
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+
+    let instruction = MakePaymentBuilder::new()
+        .order_id(ORDER_ID)
+        .idempotency_key(idempotency_key(ORDER_ID))
+        .amount(AMOUNT)
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .escrow_authority(setup.escrow_authority)
+        .merchant_escrow_ata(non_canonical_escrow)
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet.pubkey(),
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet.pubkey())
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+    assert_program_error(result, INVALID_ATA_ERROR);
+
+    // The rejected payment never left the buyer
+    assert_eq!(
+        get_token_balance(&mut setup.context, &non_canonical_escrow),
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_clear_payment_non_canonical_escrow_fails() {
+    let mut setup = setup_non_canonical_escrow_test().unwrap();
+    let payment_pda = make_escrowed_payment(&mut setup);
+    let non_canonical_escrow = create_non_canonical_escrow(&mut setup, AMOUNT);
+
+    let instruction = ClearPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(setup.escrow_authority)
+        .merchant_escrow_ata(non_canonical_escrow)
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet.pubkey(),
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, INVALID_ATA_ERROR);
+
+    // The payment is still held by the canonical escrow ATA
+    let escrow_ata = find_escrow_ata(&setup.merchant_operator_config_pda, &USDC_MINT);
+    assert_eq!(get_token_balance(&mut setup.context, &escrow_ata), AMOUNT);
+    assert_eq!(
+        get_token_balance(&mut setup.context, &non_canonical_escrow),
+        AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn test_refund_payment_non_canonical_escrow_fails() {
+    let mut setup = setup_non_canonical_escrow_test().unwrap();
+    let payment_pda = make_escrowed_payment(&mut setup);
+    let non_canonical_escrow = create_non_canonical_escrow(&mut setup, AMOUNT);
+
+    let instruction = RefundPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(setup.escrow_authority)
+        .merchant_escrow_ata(non_canonical_escrow)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, INVALID_ATA_ERROR);
+
+    let escrow_ata = find_escrow_ata(&setup.merchant_operator_config_pda, &USDC_MINT);
+    assert_eq!(get_token_balance(&mut setup.context, &escrow_ata), AMOUNT);
+    assert_eq!(
+        get_token_balance(&mut setup.context, &non_canonical_escrow),
+        AMOUNT
+    );
+}
@@ -0,0 +1,209 @@
+use crate::{
+    assertions::{assert_account_not_exists, assert_payment_account},
+    state_utils::*,
+    utils::{
+        find_escrow_ata, find_escrow_authority_pda, find_payment_pda, get_token_balance,
+        idempotency_key, set_token_balance, TestContext, DAYS_TO_CLOSE, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::MakePaymentBuilder,
+    types::{FeeType, PolicyData, SettlementPolicy, Status},
+    Merchant,
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID, transaction::Transaction,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const AMOUNT: u64 = 1_000_000;
+
+struct NonceTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    nonce_account: Pubkey,
+}
+
+// Helper function to set up a config without auto settlement and a durable nonce account
+// authorized by the operator, as an operator backend queuing payments would use
+fn setup_nonce_test() -> Result<NonceTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let nonce_account = context.create_nonce_account(&operator_authority.pubkey());
+
+    Ok(NonceTestSetup {
+        context,
+        operator_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        nonce_account,
+    })
+}
+
+fn make_payment_instruction(setup: &mut NonceTestSetup, order_id: u32) -> (Instruction, Pubkey) {
+    let merchant_account = setup.context.get_account(&setup.merchant_pda).unwrap();
+    let settlement_wallet = Merchant::from_bytes(&merchant_account.data)
+        .unwrap()
+        .settlement_wallet;
+
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut setup.context,
+        &buyer_ata,
+        &USDC_MINT,
+        &setup.buyer.pubkey(),
+        AMOUNT,
+    );
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let instruction = MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(&settlement_wallet, &USDC_MINT))
+        .settlement_wallet(settlement_wallet)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(AMOUNT)
+        .instruction();
+
+    (instruction, payment_pda)
+}
+
+// Signs a MakePayment against the setup's nonce, as a backend queuing it would
+fn queued_make_payment(setup: &mut NonceTestSetup, order_id: u32) -> (Transaction, Pubkey) {
+    let (instruction, payment_pda) = make_payment_instruction(setup, order_id);
+    let buyer = setup.buyer.insecure_clone();
+    let transaction = setup.context.nonce_transaction(
+        instruction,
+        &setup.nonce_account,
+        &setup.operator_authority,
+        &[&buyer],
+    );
+    (transaction, payment_pda)
+}
+
+#[tokio::test]
+async fn test_make_payment_with_durable_nonce() {
+    let mut setup = setup_nonce_test().unwrap();
+    let nonce = setup.context.get_nonce(&setup.nonce_account);
+    let (transaction, payment_pda) = queued_make_payment(&mut setup, 1);
+
+    // The queue outlives the blockhash the transaction would otherwise have used
+    for _ in 0..3 {
+        setup.context.svm.expire_blockhash();
+    }
+
+    setup.context.send_signed_transaction(transaction).unwrap();
+
+    assert_payment_account(&mut setup.context, &payment_pda, 1, AMOUNT, Status::Paid);
+    let escrow_ata = find_escrow_ata(&setup.merchant_operator_config_pda, &USDC_MINT);
+    assert_eq!(get_token_balance(&mut setup.context, &escrow_ata), AMOUNT);
+    assert_ne!(setup.context.get_nonce(&setup.nonce_account), nonce);
+}
+
+#[tokio::test]
+async fn test_make_payment_with_used_nonce_fails() {
+    let mut setup = setup_nonce_test().unwrap();
+
+    // Both signed against the same nonce, which the first advances
+    let (first, first_payment_pda) = queued_make_payment(&mut setup, 1);
+    let (second, second_payment_pda) = queued_make_payment(&mut setup, 2);
+
+    setup.context.svm.expire_blockhash();
+    setup.context.send_signed_transaction(first).unwrap();
+
+    setup.context.svm.expire_blockhash();
+    assert!(setup.context.send_signed_transaction(second).is_err());
+
+    assert_payment_account(
+        &mut setup.context,
+        &first_payment_pda,
+        1,
+        AMOUNT,
+        Status::Paid,
+    );
+    assert_account_not_exists(&mut setup.context, &second_payment_pda);
+}
+
+#[tokio::test]
+async fn test_make_payment_with_expired_blockhash_fails() {
+    let mut setup = setup_nonce_test().unwrap();
+    let (instruction, payment_pda) = make_payment_instruction(&mut setup, 1);
+
+    // The same payment queued with a recent blockhash instead of the nonce
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&setup.context.payer.pubkey()),
+        &[&setup.context.payer, &setup.buyer],
+        setup.context.svm.latest_blockhash(),
+    );
+    setup.context.svm.expire_blockhash();
+
+    assert!(setup.context.send_signed_transaction(transaction).is_err());
+    assert_account_not_exists(&mut setup.context, &payment_pda);
+}
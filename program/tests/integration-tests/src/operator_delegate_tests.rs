@@ -0,0 +1,684 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda,
+        find_operator_delegate_pda, find_payment_pda, get_or_create_associated_token_account,
+        idempotency_key, set_token_balance, TestContext, DAYS_TO_CLOSE,
+        DELEGATE_DAILY_REFUND_LIMIT_EXCEEDED_ERROR, DELEGATE_REFUND_LIMIT_EXCEEDED_ERROR,
+        INVALID_ACCOUNT_DATA_ERROR, OPERATOR_DELEGATE_MISMATCH_ERROR,
+        OPERATOR_DELEGATE_PERMISSION_DENIED_ERROR, OPERATOR_OWNER_MISMATCH_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{
+        ClearPaymentBuilder, InitializeOperatorDelegateBuilder, MakePaymentBuilder,
+        RefundPaymentBuilder, RevokeOperatorDelegateBuilder, UpdateOperatorDelegateBuilder,
+    },
+    operator_delegate::{operator_delegate_account, operator_delegate_refund_account},
+    types::{FeeType, Status},
+    OperatorDelegate, Payment,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+struct DelegateTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    delegate: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up an operator, merchant and config plus an unauthorized delegate key
+fn setup_operator_delegate_test() -> Result<DelegateTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+    let delegate = Keypair::new();
+
+    context.airdrop_if_required(&delegate.pubkey(), 1_000_000_000)?;
+    context.airdrop_if_required(&buyer.pubkey(), 1_000_000_000)?;
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    get_or_create_associated_token_account(&mut context, &settlement_wallet.pubkey(), &USDC_MINT);
+    get_or_create_associated_token_account(&mut context, &operator_authority.pubkey(), &USDC_MINT);
+
+    Ok(DelegateTestSetup {
+        context,
+        operator_authority,
+        delegate,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn make_payment_instruction(
+    setup: &mut DelegateTestSetup,
+    signer: &Pubkey,
+    order_id: u32,
+    remaining_accounts: &[AccountMeta],
+) -> (Instruction, Pubkey) {
+    let amount = 1_000_000u64;
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut setup.context,
+        &buyer_ata,
+        &USDC_MINT,
+        &setup.buyer.pubkey(),
+        amount * 2,
+    );
+
+    let instruction = MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(*signer)
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(amount)
+        .add_remaining_accounts(remaining_accounts)
+        .instruction();
+
+    (instruction, payment_pda)
+}
+
+fn clear_payment_instruction(
+    setup: &DelegateTestSetup,
+    signer: &Pubkey,
+    payment_pda: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    ClearPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(*payment_pda)
+        .operator_authority(*signer)
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .add_remaining_accounts(remaining_accounts)
+        .instruction()
+}
+
+fn refund_payment_instruction(
+    setup: &DelegateTestSetup,
+    signer: &Pubkey,
+    payment_pda: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    RefundPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(*payment_pda)
+        .operator_authority(*signer)
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .add_remaining_accounts(remaining_accounts)
+        .instruction()
+}
+
+fn payment_status(setup: &mut DelegateTestSetup, payment_pda: &Pubkey) -> Status {
+    let account = setup.context.get_account(payment_pda).unwrap();
+    Payment::from_bytes(&account.data).unwrap().status
+}
+
+#[tokio::test]
+async fn test_initialize_operator_delegate_success() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    assert_initialize_operator_delegate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &setup.delegate.pubkey(),
+        true,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_initialize_operator_delegate_wrong_authority_fails() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let wrong_authority = setup.delegate.insecure_clone();
+
+    let (operator_delegate_pda, bump) =
+        find_operator_delegate_pda(&setup.operator_pda, &wrong_authority.pubkey());
+
+    let instruction = InitializeOperatorDelegateBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .authority(wrong_authority.pubkey())
+        .operator(setup.operator_pda)
+        .operator_delegate(operator_delegate_pda)
+        .delegate(wrong_authority.pubkey())
+        .bump(bump)
+        .can_make_payment(true)
+        .can_clear(true)
+        .can_refund(true)
+        .can_close(true)
+        .max_refund_amount(0)
+        .max_daily_refund_amount(0)
+        .instruction();
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&wrong_authority]);
+
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_delegate_make_and_clear_payment_success() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = setup.delegate.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    assert_initialize_operator_delegate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &delegate.pubkey(),
+        true,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let delegate_account = operator_delegate_account(&setup.operator_pda, &delegate.pubkey());
+
+    let (instruction, payment_pda) = make_payment_instruction(
+        &mut setup,
+        &delegate.pubkey(),
+        1,
+        std::slice::from_ref(&delegate_account),
+    );
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate, &buyer])
+        .expect("Delegate should make payment");
+    assert_eq!(payment_status(&mut setup, &payment_pda), Status::Paid);
+
+    let instruction = clear_payment_instruction(
+        &setup,
+        &delegate.pubkey(),
+        &payment_pda,
+        std::slice::from_ref(&delegate_account),
+    );
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate])
+        .expect("Delegate should clear payment");
+    assert_eq!(payment_status(&mut setup, &payment_pda), Status::Cleared);
+}
+
+#[tokio::test]
+async fn test_delegate_without_permission_fails() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = setup.delegate.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    // Make-payment only
+    assert_initialize_operator_delegate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &delegate.pubkey(),
+        true,
+        false,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let delegate_account = operator_delegate_account(&setup.operator_pda, &delegate.pubkey());
+
+    let (instruction, payment_pda) = make_payment_instruction(
+        &mut setup,
+        &delegate.pubkey(),
+        1,
+        std::slice::from_ref(&delegate_account),
+    );
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate, &buyer])
+        .expect("Delegate should make payment");
+
+    let instruction = refund_payment_instruction(
+        &setup,
+        &delegate.pubkey(),
+        &payment_pda,
+        std::slice::from_ref(&delegate_account),
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate]);
+
+    assert_program_error(result, OPERATOR_DELEGATE_PERMISSION_DENIED_ERROR);
+}
+
+#[tokio::test]
+async fn test_delegate_without_delegate_account_fails() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = setup.delegate.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    assert_initialize_operator_delegate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &delegate.pubkey(),
+        true,
+        true,
+        true,
+        true,
+    )
+    .unwrap();
+
+    let (instruction, _) = make_payment_instruction(&mut setup, &delegate.pubkey(), 1, &[]);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate, &buyer]);
+
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_delegate_with_another_delegates_account_fails() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = setup.delegate.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    let impostor = Keypair::new();
+    setup
+        .context
+        .airdrop_if_required(&impostor.pubkey(), 1_000_000_000)
+        .unwrap();
+
+    assert_initialize_operator_delegate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &delegate.pubkey(),
+        true,
+        true,
+        true,
+        true,
+    )
+    .unwrap();
+
+    let delegate_account = operator_delegate_account(&setup.operator_pda, &delegate.pubkey());
+    let (instruction, _) = make_payment_instruction(
+        &mut setup,
+        &impostor.pubkey(),
+        1,
+        std::slice::from_ref(&delegate_account),
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&impostor, &buyer]);
+
+    assert_program_error(result, OPERATOR_DELEGATE_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_update_operator_delegate_grants_refund() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = setup.delegate.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    let (operator_delegate_pda, _) = assert_initialize_operator_delegate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &delegate.pubkey(),
+        true,
+        false,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let instruction = UpdateOperatorDelegateBuilder::new()
+        .authority(operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .operator_delegate(operator_delegate_pda)
+        .can_make_payment(true)
+        .can_clear(false)
+        .can_refund(true)
+        .can_close(false)
+        .max_refund_amount(0)
+        .max_daily_refund_amount(0)
+        .instruction();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Update operator delegate should succeed");
+
+    let account = setup.context.get_account(&operator_delegate_pda).unwrap();
+    let operator_delegate = OperatorDelegate::from_bytes(&account.data).unwrap();
+    assert!(operator_delegate.can_refund);
+
+    let delegate_account = operator_delegate_account(&setup.operator_pda, &delegate.pubkey());
+    let (instruction, payment_pda) = make_payment_instruction(
+        &mut setup,
+        &delegate.pubkey(),
+        1,
+        std::slice::from_ref(&delegate_account),
+    );
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate, &buyer])
+        .expect("Delegate should make payment");
+
+    let instruction = refund_payment_instruction(
+        &setup,
+        &delegate.pubkey(),
+        &payment_pda,
+        std::slice::from_ref(&delegate_account),
+    );
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate])
+        .expect("Delegate should refund payment");
+    assert_eq!(payment_status(&mut setup, &payment_pda), Status::Refunded);
+}
+
+#[tokio::test]
+async fn test_revoke_operator_delegate() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = setup.delegate.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    let (operator_delegate_pda, _) = assert_initialize_operator_delegate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &delegate.pubkey(),
+        true,
+        true,
+        true,
+        true,
+    )
+    .unwrap();
+
+    // A delegate cannot revoke itself
+    let instruction = RevokeOperatorDelegateBuilder::new()
+        .authority(delegate.pubkey())
+        .operator(setup.operator_pda)
+        .operator_delegate(operator_delegate_pda)
+        .instruction();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate]);
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+
+    let instruction = RevokeOperatorDelegateBuilder::new()
+        .authority(operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .operator_delegate(operator_delegate_pda)
+        .instruction();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Revoke operator delegate should succeed");
+
+    assert!(setup
+        .context
+        .get_account(&operator_delegate_pda)
+        .is_none_or(|account| account.lamports == 0));
+
+    let delegate_account = operator_delegate_account(&setup.operator_pda, &delegate.pubkey());
+    let (instruction, _) = make_payment_instruction(
+        &mut setup,
+        &delegate.pubkey(),
+        1,
+        std::slice::from_ref(&delegate_account),
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate, &buyer]);
+    assert!(result.is_err());
+}
+
+// Helper function to authorize the setup's delegate to make payments and refund them within
+// the given limits, and to make `count` payments signed by it
+fn setup_delegate_refund_limits(
+    setup: &mut DelegateTestSetup,
+    max_refund_amount: u64,
+    max_daily_refund_amount: u64,
+    count: u32,
+) -> (Pubkey, Vec<Pubkey>) {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = setup.delegate.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    let (operator_delegate_pda, _) = assert_initialize_operator_delegate_with_refund_limits(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &delegate.pubkey(),
+        true,
+        false,
+        true,
+        false,
+        max_refund_amount,
+        max_daily_refund_amount,
+    )
+    .unwrap();
+
+    let delegate_account = operator_delegate_account(&setup.operator_pda, &delegate.pubkey());
+    let payment_pdas = (1..=count)
+        .map(|order_id| {
+            let (instruction, payment_pda) = make_payment_instruction(
+                setup,
+                &delegate.pubkey(),
+                order_id,
+                std::slice::from_ref(&delegate_account),
+            );
+            setup
+                .context
+                .send_transaction_with_signers(instruction, &[&delegate, &buyer])
+                .expect("Delegate should make payment");
+            payment_pda
+        })
+        .collect();
+
+    (operator_delegate_pda, payment_pdas)
+}
+
+#[tokio::test]
+async fn test_delegate_refund_over_limit_fails() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = setup.delegate.insecure_clone();
+
+    // Payments are 1_000_000, above the delegate's cap
+    let (_, payment_pdas) = setup_delegate_refund_limits(&mut setup, 999_999, 0, 1);
+
+    let delegate_account = operator_delegate_account(&setup.operator_pda, &delegate.pubkey());
+    let instruction = refund_payment_instruction(
+        &setup,
+        &delegate.pubkey(),
+        &payment_pdas[0],
+        std::slice::from_ref(&delegate_account),
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate]);
+    assert_program_error(result, DELEGATE_REFUND_LIMIT_EXCEEDED_ERROR);
+
+    // The operator owner is not bound by the delegate's limits
+    let instruction =
+        refund_payment_instruction(&setup, &operator_authority.pubkey(), &payment_pdas[0], &[]);
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Operator owner should refund payment");
+    assert_eq!(
+        payment_status(&mut setup, &payment_pdas[0]),
+        Status::Refunded
+    );
+}
+
+#[tokio::test]
+async fn test_delegate_daily_refund_limit() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let delegate = setup.delegate.insecure_clone();
+
+    // Two 1_000_000 refunds fit the daily limit, a third does not
+    let (operator_delegate_pda, payment_pdas) =
+        setup_delegate_refund_limits(&mut setup, 0, 2_500_000, 3);
+
+    let delegate_account =
+        operator_delegate_refund_account(&setup.operator_pda, &delegate.pubkey());
+    for payment_pda in &payment_pdas[..2] {
+        let instruction = refund_payment_instruction(
+            &setup,
+            &delegate.pubkey(),
+            payment_pda,
+            std::slice::from_ref(&delegate_account),
+        );
+        setup
+            .context
+            .send_transaction_with_signers(instruction, &[&delegate])
+            .expect("Delegate should refund payment");
+    }
+
+    let account = setup.context.get_account(&operator_delegate_pda).unwrap();
+    let operator_delegate = OperatorDelegate::from_bytes(&account.data).unwrap();
+    assert_eq!(operator_delegate.daily_refund_amount, 2_000_000);
+
+    let instruction = refund_payment_instruction(
+        &setup,
+        &delegate.pubkey(),
+        &payment_pdas[2],
+        std::slice::from_ref(&delegate_account),
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate]);
+    assert_program_error(result, DELEGATE_DAILY_REFUND_LIMIT_EXCEEDED_ERROR);
+    assert_eq!(payment_status(&mut setup, &payment_pdas[2]), Status::Paid);
+}
+
+#[tokio::test]
+async fn test_delegate_daily_refund_limit_readonly_account_fails() {
+    let mut setup = setup_operator_delegate_test().unwrap();
+    let delegate = setup.delegate.insecure_clone();
+
+    let (_, payment_pdas) = setup_delegate_refund_limits(&mut setup, 0, 2_500_000, 1);
+
+    // The refund can't be counted without a writable OperatorDelegate PDA
+    let delegate_account = operator_delegate_account(&setup.operator_pda, &delegate.pubkey());
+    let instruction = refund_payment_instruction(
+        &setup,
+        &delegate.pubkey(),
+        &payment_pdas[0],
+        std::slice::from_ref(&delegate_account),
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate]);
+    assert_program_error(result, INVALID_ACCOUNT_DATA_ERROR);
+}
@@ -0,0 +1,232 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_authority_pda, get_or_create_associated_token_account,
+        TestContext, DAYS_TO_CLOSE, OPERATOR_FAILOVER_POLICY_REQUIRED_ERROR,
+        OPERATOR_OWNER_MISMATCH_ERROR, OPERATOR_STILL_ACTIVE_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{ClearPaymentBuilder, HeartbeatBuilder},
+    types::{FeeType, OperatorFailoverPolicy, PolicyData, SettlementPolicy},
+    Merchant, Operator,
+};
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 1_000_000;
+const INACTIVITY_THRESHOLD_SECONDS: u32 = 7 * 24 * 60 * 60;
+
+struct OperatorFailoverTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a config, with or without a failover policy, and a paid payment
+fn setup_operator_failover_test(
+    with_failover_policy: bool,
+) -> Result<OperatorFailoverTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let mut policies = vec![PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: 0,
+        settlement_frequency_hours: 0,
+        auto_settle: false,
+    })];
+    if with_failover_policy {
+        policies.push(PolicyData::OperatorFailover(OperatorFailoverPolicy {
+            inactivity_threshold_seconds: INACTIVITY_THRESHOLD_SECONDS,
+        }));
+    }
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        ORDER_ID,
+        AMOUNT,
+        true,
+        false,
+        false,
+    )?;
+
+    Ok(OperatorFailoverTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn merchant_clear_payment_instruction(setup: &mut OperatorFailoverTestSetup) -> Instruction {
+    let merchant_account = setup.context.get_account(&setup.merchant_pda).unwrap();
+    let settlement_wallet = Merchant::from_bytes(&merchant_account.data)
+        .unwrap()
+        .settlement_wallet;
+    let escrow_authority =
+        find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0;
+
+    ClearPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.merchant_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(get_associated_token_address(&escrow_authority, &USDC_MINT))
+        .merchant_settlement_ata(get_associated_token_address(&settlement_wallet, &USDC_MINT))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction()
+}
+
+fn last_active_at(setup: &mut OperatorFailoverTestSetup) -> i64 {
+    let operator_account = setup.context.get_account(&setup.operator_pda).unwrap();
+    Operator::from_bytes(&operator_account.data)
+        .unwrap()
+        .last_active_at
+}
+
+#[tokio::test]
+async fn test_heartbeat_updates_last_active_at() {
+    let mut setup = setup_operator_failover_test(true).unwrap();
+
+    // Created operators start out active
+    let now = setup.context.svm.get_sysvar::<Clock>().unix_timestamp;
+    assert_eq!(last_active_at(&mut setup), now);
+
+    setup.context.advance_clock(3_600);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    assert_heartbeat(&mut setup.context, &operator_authority, &setup.operator_pda).unwrap();
+}
+
+#[tokio::test]
+async fn test_heartbeat_wrong_authority_fails() {
+    let mut setup = setup_operator_failover_test(true).unwrap();
+    let other_authority = Keypair::new();
+
+    let instruction = HeartbeatBuilder::new()
+        .operator_authority(other_authority.pubkey())
+        .operator(setup.operator_pda)
+        .instruction();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&other_authority]);
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_merchant_clear_payment_after_operator_inactive() {
+    let mut setup = setup_operator_failover_test(true).unwrap();
+    setup
+        .context
+        .advance_clock(INACTIVITY_THRESHOLD_SECONDS as i64 + 1);
+
+    let payer = setup.context.payer.insecure_clone();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_clear_payment(
+        &mut setup.context,
+        &payer,
+        &merchant_authority,
+        &buyer,
+        &setup.payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_merchant_clear_payment_operator_active_fails() {
+    let mut setup = setup_operator_failover_test(true).unwrap();
+    setup
+        .context
+        .advance_clock(INACTIVITY_THRESHOLD_SECONDS as i64 + 1);
+
+    // A heartbeat restarts the inactivity threshold
+    let operator_authority = setup.operator_authority.insecure_clone();
+    assert_heartbeat(&mut setup.context, &operator_authority, &setup.operator_pda).unwrap();
+
+    let instruction = merchant_clear_payment_instruction(&mut setup);
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority]);
+    assert_program_error(result, OPERATOR_STILL_ACTIVE_ERROR);
+}
+
+#[tokio::test]
+async fn test_merchant_clear_payment_without_policy_fails() {
+    let mut setup = setup_operator_failover_test(false).unwrap();
+    setup
+        .context
+        .advance_clock(INACTIVITY_THRESHOLD_SECONDS as i64 + 1);
+
+    let instruction = merchant_clear_payment_instruction(&mut setup);
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority]);
+    assert_program_error(result, OPERATOR_FAILOVER_POLICY_REQUIRED_ERROR);
+}
@@ -1,6 +1,21 @@
-use crate::{state_utils::assert_get_or_create_operator, utils::TestContext};
+use crate::{
+    state_utils::{
+        assert_accept_merchant_authority, assert_accept_operator_authority, assert_clear_payment,
+        assert_get_or_create_merchant, assert_get_or_create_merchant_operator_config,
+        assert_get_or_create_operator, assert_make_payment, assert_propose_merchant_authority,
+        assert_propose_operator_authority,
+    },
+    utils::{
+        assert_program_error, find_operator_pda, get_or_create_associated_token_account,
+        TestContext, DAYS_TO_CLOSE, OPERATOR_PENDING_AUTHORITY_MISMATCH_ERROR, USDC_MINT,
+    },
+};
 
-use solana_sdk::signature::Keypair;
+use commerce_program_client::{
+    instructions::AcceptOperatorAuthorityBuilder,
+    types::{FeeType, PolicyData, SettlementPolicy},
+};
+use solana_sdk::signature::{Keypair, Signer};
 
 #[tokio::test]
 async fn test_create_operator_success() {
@@ -11,9 +26,136 @@ async fn test_create_operator_success() {
 }
 
 #[tokio::test]
-async fn test_update_operator_authority_success() {
+async fn test_transfer_operator_authority_success() {
     let mut context = TestContext::new();
     let owner = Keypair::new();
+    let new_owner = Keypair::new();
 
     assert_get_or_create_operator(&mut context, &owner, true, true).unwrap();
+
+    assert_propose_operator_authority(&mut context, &owner, &new_owner.pubkey(), true).unwrap();
+
+    let (operator_pda, _) = find_operator_pda(&owner.pubkey());
+    assert_accept_operator_authority(&mut context, &operator_pda, &new_owner, true).unwrap();
+}
+
+#[tokio::test]
+async fn test_accept_operator_authority_wrong_signer_fails() {
+    let mut context = TestContext::new();
+    let owner = Keypair::new();
+    let new_owner = Keypair::new();
+    let wrong_owner = Keypair::new();
+
+    assert_get_or_create_operator(&mut context, &owner, true, false).unwrap();
+
+    assert_propose_operator_authority(&mut context, &owner, &new_owner.pubkey(), false).unwrap();
+
+    let (operator_pda, _) = find_operator_pda(&owner.pubkey());
+    let instruction = AcceptOperatorAuthorityBuilder::new()
+        .payer(context.payer.pubkey())
+        .new_operator_authority(wrong_owner.pubkey())
+        .operator(operator_pda)
+        .instruction();
+
+    let result = context.send_transaction_with_signers(instruction, &[&wrong_owner]);
+    assert_program_error(result, OPERATOR_PENDING_AUTHORITY_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_payment_after_authority_handover_success() {
+    let mut context = TestContext::new();
+    let operator_owner = Keypair::new();
+    let new_operator_owner = Keypair::new();
+    let merchant_owner = Keypair::new();
+    let new_merchant_owner = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_owner, true, false).unwrap();
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_owner,
+        &settlement_wallet,
+        true,
+        false,
+    )
+    .unwrap();
+
+    // Hand both accounts over; their PDAs stay derived from the original owners
+    assert_propose_operator_authority(
+        &mut context,
+        &operator_owner,
+        &new_operator_owner.pubkey(),
+        false,
+    )
+    .unwrap();
+    assert_accept_operator_authority(&mut context, &operator_pda, &new_operator_owner, false)
+        .unwrap();
+    assert_propose_merchant_authority(
+        &mut context,
+        &merchant_owner,
+        &new_merchant_owner.pubkey(),
+        false,
+    )
+    .unwrap();
+    assert_accept_merchant_authority(
+        &mut context,
+        &merchant_pda,
+        &new_merchant_owner,
+        &settlement_wallet.pubkey(),
+        false,
+    )
+    .unwrap();
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &new_merchant_owner,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )
+    .unwrap();
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &new_operator_owner,
+        &new_operator_owner,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        1_000_000,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+
+    assert_clear_payment(
+        &mut context,
+        &new_operator_owner,
+        &new_operator_owner,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &merchant_operator_config_pda,
+        false,
+    )
+    .unwrap();
 }
@@ -0,0 +1,669 @@
+use crate::{
+    assertions::assert_order_account,
+    state_utils::*,
+    utils::{
+        assert_order_status_changed_event_present, assert_program_error, find_escrow_ata,
+        find_escrow_authority_pda, find_order_pda, find_payment_pda,
+        get_or_create_associated_token_account, idempotency_key, set_token_balance, TestContext,
+        TestContextBuilder, DAYS_TO_CLOSE, INVALID_ORDER_PAYMENT_COUNT_ERROR, ORDER_FULL_ERROR,
+        ORDER_ID_INVALID_ERROR, ORDER_MISMATCH_ERROR, ORDER_REQUIRED_ERROR, USDC_MINT, USDT_MINT,
+    },
+};
+use commerce_program_client::{
+    accounts::Payment,
+    instructions::{
+        ChargebackPaymentBuilder, ClearPaymentBuilder, CreateOrderBuilder, MakePaymentBuilder,
+        RefundPaymentBuilder, RefundSettledPaymentBuilder,
+    },
+    refund_delegate::{approve_refund_delegate, find_refund_delegate_pda},
+    types::{ChargebackPolicy, FeeType, OrderStatus, PolicyData, RefundPolicy, SettlementPolicy},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const ORDER_ID: u32 = 1;
+// 30 USDC + 20 USDT
+const USDC_AMOUNT: u64 = 30_000_000;
+const USDT_AMOUNT: u64 = 20_000_000;
+
+struct OrderTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a config accepting USDC and USDT
+fn setup_order_test() -> Result<OrderTestSetup, Box<dyn std::error::Error>> {
+    setup_order_test_with_policies(vec![])
+}
+
+// Helper function to set up a config accepting USDC and USDT under `policies`
+fn setup_order_test_with_policies(
+    policies: Vec<PolicyData>,
+) -> Result<OrderTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_usdt()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        0u64,
+        FeeType::Fixed,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT, USDT_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    for mint in [USDC_MINT, USDT_MINT] {
+        get_or_create_associated_token_account(&mut context, &settlement_wallet.pubkey(), &mint);
+        get_or_create_associated_token_account(&mut context, &operator_authority.pubkey(), &mint);
+    }
+
+    Ok(OrderTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn create_order(
+    setup: &mut OrderTestSetup,
+    expected_payments: u8,
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let (order_pda, _) = assert_create_order(
+        &mut setup.context,
+        &operator_authority,
+        &setup.buyer.pubkey(),
+        &setup.operator_pda,
+        &setup.merchant_pda,
+        &setup.merchant_operator_config_pda,
+        ORDER_ID,
+        expected_payments,
+        true, // fail_if_exists
+        false,
+    )?;
+    Ok(order_pda)
+}
+
+fn make_payment_instruction(
+    setup: &mut OrderTestSetup,
+    order_pda: Pubkey,
+    mint: Pubkey,
+    order_id: u32,
+    amount: u64,
+) -> Instruction {
+    let buyer = setup.buyer.pubkey();
+    let buyer_ata = get_associated_token_address(&buyer, &mint);
+    set_token_balance(&mut setup.context, &buyer_ata, &mint, &buyer, amount);
+
+    let (payment_pda, _) =
+        find_payment_pda(&setup.merchant_operator_config_pda, &buyer, &mint, order_id);
+
+    let mut builder = MakePaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(buyer)
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(mint)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(find_escrow_authority_pda(&setup.merchant_operator_config_pda, &mint).0)
+        .merchant_escrow_ata(find_escrow_ata(&setup.merchant_operator_config_pda, &mint))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet.pubkey(),
+            &mint,
+        ))
+        .settlement_wallet(setup.settlement_wallet.pubkey())
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(amount)
+        .add_remaining_account(AccountMeta::new(order_pda, false));
+    builder.instruction()
+}
+
+fn clear_payment_instruction(
+    setup: &OrderTestSetup,
+    order_pda: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    let buyer = setup.buyer.pubkey();
+    let (payment_pda, _) =
+        find_payment_pda(&setup.merchant_operator_config_pda, &buyer, &mint, ORDER_ID);
+
+    let mut builder = ClearPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(buyer)
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(mint)
+        .escrow_authority(find_escrow_authority_pda(&setup.merchant_operator_config_pda, &mint).0)
+        .merchant_escrow_ata(find_escrow_ata(&setup.merchant_operator_config_pda, &mint))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet.pubkey(),
+            &mint,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &mint,
+        ))
+        .add_remaining_account(AccountMeta::new(order_pda, false));
+    builder.instruction()
+}
+
+fn refund_payment_instruction(
+    setup: &OrderTestSetup,
+    order_pda: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    let buyer = setup.buyer.pubkey();
+    let (payment_pda, _) =
+        find_payment_pda(&setup.merchant_operator_config_pda, &buyer, &mint, ORDER_ID);
+
+    let mut builder = RefundPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(buyer)
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(mint)
+        .escrow_authority(find_escrow_authority_pda(&setup.merchant_operator_config_pda, &mint).0)
+        .merchant_escrow_ata(find_escrow_ata(&setup.merchant_operator_config_pda, &mint))
+        .buyer_ata(get_associated_token_address(&buyer, &mint))
+        .add_remaining_account(AccountMeta::new(order_pda, false));
+    builder.instruction()
+}
+
+fn chargeback_payment_instruction(
+    setup: &OrderTestSetup,
+    order_pda: Pubkey,
+    arbiter: &Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    let buyer = setup.buyer.pubkey();
+    let (payment_pda, _) =
+        find_payment_pda(&setup.merchant_operator_config_pda, &buyer, &mint, ORDER_ID);
+
+    let mut builder = ChargebackPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .arbiter(*arbiter)
+        .buyer(buyer)
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(mint)
+        .escrow_authority(find_escrow_authority_pda(&setup.merchant_operator_config_pda, &mint).0)
+        .merchant_escrow_ata(find_escrow_ata(&setup.merchant_operator_config_pda, &mint))
+        .buyer_ata(get_associated_token_address(&buyer, &mint))
+        .add_remaining_account(AccountMeta::new(order_pda, false));
+    builder.instruction()
+}
+
+fn refund_settled_payment_instruction(
+    setup: &OrderTestSetup,
+    order_pda: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    let buyer = setup.buyer.pubkey();
+    let (payment_pda, _) =
+        find_payment_pda(&setup.merchant_operator_config_pda, &buyer, &mint, ORDER_ID);
+    let (refund_delegate, refund_delegate_bump) = find_refund_delegate_pda(&setup.merchant_pda);
+
+    let mut builder = RefundSettledPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(buyer)
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(mint)
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet.pubkey(),
+            &mint,
+        ))
+        .buyer_ata(get_associated_token_address(&buyer, &mint))
+        .refund_delegate(refund_delegate)
+        .refund_delegate_bump(refund_delegate_bump)
+        .add_remaining_account(AccountMeta::new(order_pda, false));
+    builder.instruction()
+}
+
+// Drops the Order PDA, the last remaining account, from `instruction`
+fn without_order(mut instruction: Instruction) -> Instruction {
+    instruction.accounts.pop();
+    instruction
+}
+
+fn send(
+    setup: &mut OrderTestSetup,
+    instruction: Instruction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+}
+
+fn send_payment(
+    setup: &mut OrderTestSetup,
+    instruction: Instruction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+}
+
+fn order_status(setup: &mut OrderTestSetup, order_pda: &Pubkey) -> OrderStatus {
+    let buyer = setup.buyer.pubkey();
+    assert_order_account(
+        &mut setup.context,
+        order_pda,
+        &setup.merchant_operator_config_pda,
+        &buyer,
+        ORDER_ID,
+    )
+    .status
+}
+
+#[tokio::test]
+async fn test_order_paid_across_mints() {
+    let mut setup = setup_order_test().unwrap();
+    let order_pda = create_order(&mut setup, 2).unwrap();
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDC_MINT, ORDER_ID, USDC_AMOUNT);
+    send_payment(&mut setup, instruction).unwrap();
+    assert_eq!(order_status(&mut setup, &order_pda), OrderStatus::Pending);
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDT_MINT, ORDER_ID, USDT_AMOUNT);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    let transaction_metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&operator_authority, &buyer],
+            false,
+        )
+        .unwrap();
+
+    let order = assert_order_account(
+        &mut setup.context,
+        &order_pda,
+        &setup.merchant_operator_config_pda,
+        &buyer.pubkey(),
+        ORDER_ID,
+    );
+    assert_eq!(order.payment_count, 2);
+    assert_eq!(order.status, OrderStatus::Paid);
+    assert_order_status_changed_event_present(
+        &transaction_metadata,
+        &buyer.pubkey(),
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        ORDER_ID,
+        OrderStatus::Paid,
+    );
+}
+
+#[tokio::test]
+async fn test_order_partially_refunded() {
+    let mut setup = setup_order_test().unwrap();
+    let order_pda = create_order(&mut setup, 2).unwrap();
+
+    for (mint, amount) in [(USDC_MINT, USDC_AMOUNT), (USDT_MINT, USDT_AMOUNT)] {
+        let instruction = make_payment_instruction(&mut setup, order_pda, mint, ORDER_ID, amount);
+        send_payment(&mut setup, instruction).unwrap();
+    }
+
+    let instruction = clear_payment_instruction(&setup, order_pda, USDC_MINT);
+    send(&mut setup, instruction).unwrap();
+    assert_eq!(order_status(&mut setup, &order_pda), OrderStatus::Paid);
+
+    let instruction = refund_payment_instruction(&setup, order_pda, USDT_MINT);
+    send(&mut setup, instruction).unwrap();
+
+    let buyer = setup.buyer.pubkey();
+    let order = assert_order_account(
+        &mut setup.context,
+        &order_pda,
+        &setup.merchant_operator_config_pda,
+        &buyer,
+        ORDER_ID,
+    );
+    assert_eq!(order.cleared_count, 1);
+    assert_eq!(order.refunded_count, 1);
+    assert_eq!(order.status, OrderStatus::PartiallyRefunded);
+}
+
+#[tokio::test]
+async fn test_order_cleared() {
+    let mut setup = setup_order_test().unwrap();
+    let order_pda = create_order(&mut setup, 2).unwrap();
+
+    for (mint, amount) in [(USDC_MINT, USDC_AMOUNT), (USDT_MINT, USDT_AMOUNT)] {
+        let instruction = make_payment_instruction(&mut setup, order_pda, mint, ORDER_ID, amount);
+        send_payment(&mut setup, instruction).unwrap();
+    }
+
+    for mint in [USDC_MINT, USDT_MINT] {
+        let instruction = clear_payment_instruction(&setup, order_pda, mint);
+        send(&mut setup, instruction).unwrap();
+    }
+
+    assert_eq!(order_status(&mut setup, &order_pda), OrderStatus::Cleared);
+}
+
+#[tokio::test]
+async fn test_order_full_fails() {
+    let mut setup = setup_order_test().unwrap();
+    let order_pda = create_order(&mut setup, 1).unwrap();
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDC_MINT, ORDER_ID, USDC_AMOUNT);
+    send_payment(&mut setup, instruction).unwrap();
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDT_MINT, ORDER_ID, USDT_AMOUNT);
+    let result = send_payment(&mut setup, instruction);
+    assert_program_error(result, ORDER_FULL_ERROR);
+}
+
+#[tokio::test]
+async fn test_order_mismatched_order_id_fails() {
+    let mut setup = setup_order_test().unwrap();
+    let order_pda = create_order(&mut setup, 2).unwrap();
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDC_MINT, ORDER_ID + 1, USDC_AMOUNT);
+    let result = send_payment(&mut setup, instruction);
+    assert_program_error(result, ORDER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_order_first_payment_with_current_order_id_fails() {
+    let mut setup = setup_order_test().unwrap();
+    let order_pda = create_order(&mut setup, 2).unwrap();
+
+    // A payment outside the order moves the config's current order id onto the order's
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_make_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &setup.merchant_operator_config_pda,
+        &setup.operator_pda,
+        &USDC_MINT,
+        ORDER_ID + 1,
+        USDC_AMOUNT,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDC_MINT, ORDER_ID, USDC_AMOUNT);
+    let result = send_payment(&mut setup, instruction);
+    assert_program_error(result, ORDER_ID_INVALID_ERROR);
+}
+
+#[tokio::test]
+async fn test_create_order_invalid_payment_count_fails() {
+    let mut setup = setup_order_test().unwrap();
+    let buyer = setup.buyer.pubkey();
+    let (order_pda, bump) = find_order_pda(&setup.merchant_operator_config_pda, &buyer, ORDER_ID);
+
+    for expected_payments in [0, 5] {
+        let instruction = CreateOrderBuilder::new()
+            .payer(setup.context.payer.pubkey())
+            .operator_authority(setup.operator_authority.pubkey())
+            .buyer(buyer)
+            .operator(setup.operator_pda)
+            .merchant(setup.merchant_pda)
+            .merchant_operator_config(setup.merchant_operator_config_pda)
+            .order(order_pda)
+            .order_id(ORDER_ID)
+            .expected_payments(expected_payments)
+            .bump(bump)
+            .instruction();
+
+        let operator_authority = setup.operator_authority.insecure_clone();
+        let result = setup
+            .context
+            .send_transaction_with_signers(instruction, &[&operator_authority]);
+        assert_program_error(result, INVALID_ORDER_PAYMENT_COUNT_ERROR);
+    }
+}
+
+#[tokio::test]
+async fn test_order_payment_records_order() {
+    let mut setup = setup_order_test().unwrap();
+    let order_pda = create_order(&mut setup, 2).unwrap();
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDC_MINT, ORDER_ID, USDC_AMOUNT);
+    send_payment(&mut setup, instruction).unwrap();
+
+    let buyer = setup.buyer.pubkey();
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &buyer,
+        &USDC_MINT,
+        ORDER_ID,
+    );
+    let payment_account = setup.context.get_account(&payment_pda).unwrap();
+    assert!(
+        Payment::from_bytes(&payment_account.data)
+            .unwrap()
+            .has_order
+    );
+}
+
+#[tokio::test]
+async fn test_order_clear_without_order_fails() {
+    let mut setup = setup_order_test().unwrap();
+    let order_pda = create_order(&mut setup, 2).unwrap();
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDC_MINT, ORDER_ID, USDC_AMOUNT);
+    send_payment(&mut setup, instruction).unwrap();
+
+    // Leaving the order out would let its counts drift from its payments
+    let instruction = without_order(clear_payment_instruction(&setup, order_pda, USDC_MINT));
+    let result = send(&mut setup, instruction);
+    assert_program_error(result, ORDER_REQUIRED_ERROR);
+
+    let instruction = without_order(refund_payment_instruction(&setup, order_pda, USDC_MINT));
+    let result = send(&mut setup, instruction);
+    assert_program_error(result, ORDER_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_order_passed_for_payment_outside_order_fails() {
+    let mut setup = setup_order_test().unwrap();
+    let order_pda = create_order(&mut setup, 2).unwrap();
+
+    let instruction = without_order(make_payment_instruction(
+        &mut setup,
+        order_pda,
+        USDC_MINT,
+        ORDER_ID,
+        USDC_AMOUNT,
+    ));
+    send_payment(&mut setup, instruction).unwrap();
+
+    let instruction = clear_payment_instruction(&setup, order_pda, USDC_MINT);
+    let result = send(&mut setup, instruction);
+    assert_program_error(result, ORDER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_order_chargeback_records_refund() {
+    let arbiter = Keypair::new();
+    let mut setup =
+        setup_order_test_with_policies(vec![PolicyData::Chargeback(ChargebackPolicy {
+            window_seconds: 86_400,
+            arbiter: arbiter.pubkey(),
+        })])
+        .unwrap();
+    setup
+        .context
+        .airdrop_if_required(&arbiter.pubkey(), 1_000_000_000)
+        .unwrap();
+    let order_pda = create_order(&mut setup, 1).unwrap();
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDC_MINT, ORDER_ID, USDC_AMOUNT);
+    send_payment(&mut setup, instruction).unwrap();
+
+    let instruction = without_order(chargeback_payment_instruction(
+        &setup,
+        order_pda,
+        &arbiter.pubkey(),
+        USDC_MINT,
+    ));
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&arbiter]);
+    assert_program_error(result, ORDER_REQUIRED_ERROR);
+
+    let instruction =
+        chargeback_payment_instruction(&setup, order_pda, &arbiter.pubkey(), USDC_MINT);
+    let transaction_metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(instruction, &[&arbiter], false)
+        .unwrap();
+
+    let buyer = setup.buyer.pubkey();
+    let order = assert_order_account(
+        &mut setup.context,
+        &order_pda,
+        &setup.merchant_operator_config_pda,
+        &buyer,
+        ORDER_ID,
+    );
+    assert_eq!(order.refunded_count, 1);
+    assert_eq!(order.status, OrderStatus::Refunded);
+    assert_order_status_changed_event_present(
+        &transaction_metadata,
+        &buyer,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        ORDER_ID,
+        OrderStatus::Refunded,
+    );
+}
+
+#[tokio::test]
+async fn test_order_refund_settled_payment_records_refund() {
+    let mut setup = setup_order_test_with_policies(vec![
+        PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: true,
+        }),
+        PolicyData::Refund(RefundPolicy {
+            max_amount: USDC_AMOUNT,
+            max_time_after_purchase: 86_400,
+        }),
+    ])
+    .unwrap();
+    let order_pda = create_order(&mut setup, 1).unwrap();
+
+    let instruction =
+        make_payment_instruction(&mut setup, order_pda, USDC_MINT, ORDER_ID, USDC_AMOUNT);
+    send_payment(&mut setup, instruction).unwrap();
+    assert_eq!(order_status(&mut setup, &order_pda), OrderStatus::Cleared);
+
+    let settlement_wallet = setup.settlement_wallet.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(
+            approve_refund_delegate(
+                &settlement_wallet.pubkey(),
+                &setup.merchant_pda,
+                &USDC_MINT,
+                &TOKEN_PROGRAM_ID,
+                USDC_AMOUNT,
+            ),
+            &[&settlement_wallet],
+        )
+        .unwrap();
+
+    let instruction = without_order(refund_settled_payment_instruction(
+        &setup, order_pda, USDC_MINT,
+    ));
+    let result = send(&mut setup, instruction);
+    assert_program_error(result, ORDER_REQUIRED_ERROR);
+
+    let instruction = refund_settled_payment_instruction(&setup, order_pda, USDC_MINT);
+    send(&mut setup, instruction).unwrap();
+
+    let buyer = setup.buyer.pubkey();
+    let order = assert_order_account(
+        &mut setup.context,
+        &order_pda,
+        &setup.merchant_operator_config_pda,
+        &buyer,
+        ORDER_ID,
+    );
+    assert_eq!(order.cleared_count, 0);
+    assert_eq!(order.refunded_count, 1);
+    assert_eq!(order.status, OrderStatus::Refunded);
+}
@@ -0,0 +1,331 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_payment_annotated_event_present, assert_program_error, find_payment_annotation_pda,
+        find_payment_pda, get_or_create_associated_token_account, TestContext, DAYS_TO_CLOSE,
+        INVALID_SEEDS_ERROR, OPERATOR_DELEGATE_PERMISSION_DENIED_ERROR,
+        OPERATOR_OWNER_MISMATCH_ERROR, PAYMENT_ANNOTATION_INVALID_PDA_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    accounts::PaymentAnnotation,
+    instructions::AnnotatePaymentBuilder,
+    operator_delegate::operator_delegate_account,
+    payment_annotation::{annotate_payment, holds_fulfilment, note_hash},
+    types::{AnnotationFlag, FeeType},
+    COMMERCE_PROGRAM_ID,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const AMOUNT: u64 = 1_000_000;
+
+struct PaymentAnnotationTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+impl PaymentAnnotationTestSetup {
+    fn annotate_instruction(
+        &self,
+        operator_authority: &Pubkey,
+        order_id: u32,
+        flag: AnnotationFlag,
+        note_hash: [u8; 32],
+    ) -> Instruction {
+        annotate_payment(
+            &self.context.payer.pubkey(),
+            operator_authority,
+            &self.operator_pda,
+            &self.merchant_operator_config_pda,
+            &self.buyer.pubkey(),
+            &USDC_MINT,
+            order_id,
+            flag,
+            note_hash,
+        )
+    }
+
+    fn payment_pda(&self, order_id: u32) -> Pubkey {
+        find_payment_pda(
+            &self.merchant_operator_config_pda,
+            &self.buyer.pubkey(),
+            &USDC_MINT,
+            order_id,
+        )
+        .0
+    }
+
+    fn payment_annotation(&mut self, order_id: u32) -> PaymentAnnotation {
+        let (payment_annotation_pda, _) = find_payment_annotation_pda(&self.payment_pda(order_id));
+        let account = self.context.get_account(&payment_annotation_pda).unwrap();
+        assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+        PaymentAnnotation::from_bytes(&account.data).unwrap()
+    }
+}
+
+// Helper function to set up an operator, merchant and config with paid payments for orders 1 and 2
+fn setup_payment_annotation_test() -> Result<PaymentAnnotationTestSetup, Box<dyn std::error::Error>>
+{
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    for order_id in [1, 2] {
+        assert_make_payment(
+            &mut context,
+            &operator_authority,
+            &operator_authority,
+            &buyer,
+            &merchant_operator_config_pda,
+            &operator_pda,
+            &USDC_MINT,
+            order_id,
+            AMOUNT,
+            true,
+            false,
+            false,
+        )?;
+    }
+
+    Ok(PaymentAnnotationTestSetup {
+        context,
+        operator_authority,
+        buyer,
+        operator_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+#[tokio::test]
+async fn test_annotate_and_reannotate_payment() {
+    let mut setup = setup_payment_annotation_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let payment_pda = setup.payment_pda(1);
+    let config = setup.merchant_operator_config_pda;
+    let review_note = note_hash(b"billing and shipping countries differ");
+
+    let instruction = setup.annotate_instruction(
+        &operator_authority.pubkey(),
+        1,
+        AnnotationFlag::ManualReview,
+        review_note,
+    );
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&operator_authority],
+            false,
+        )
+        .unwrap();
+    assert_payment_annotated_event_present(
+        &metadata,
+        &payment_pda,
+        &config,
+        &operator_authority.pubkey(),
+        AnnotationFlag::ManualReview,
+        &review_note,
+    );
+
+    let annotation = setup.payment_annotation(1);
+    assert_eq!(annotation.payment, payment_pda);
+    assert_eq!(annotation.merchant_operator_config, config);
+    assert_eq!(annotation.bump, find_payment_annotation_pda(&payment_pda).1);
+    assert_eq!(annotation.flag, AnnotationFlag::ManualReview);
+    assert_eq!(annotation.note_hash, review_note);
+    assert_eq!(annotation.annotated_by, operator_authority.pubkey());
+    assert!(holds_fulfilment(&annotation));
+
+    // A reviewer clearing the payment replaces the verdict in place
+    let cleared_note = note_hash(b"buyer confirmed by phone");
+    let instruction = setup.annotate_instruction(
+        &operator_authority.pubkey(),
+        1,
+        AnnotationFlag::Ok,
+        cleared_note,
+    );
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&operator_authority],
+            false,
+        )
+        .unwrap();
+    assert_payment_annotated_event_present(
+        &metadata,
+        &payment_pda,
+        &config,
+        &operator_authority.pubkey(),
+        AnnotationFlag::Ok,
+        &cleared_note,
+    );
+
+    let annotation = setup.payment_annotation(1);
+    assert_eq!(annotation.flag, AnnotationFlag::Ok);
+    assert_eq!(annotation.note_hash, cleared_note);
+    assert!(!holds_fulfilment(&annotation));
+}
+
+#[tokio::test]
+async fn test_annotate_payment_by_clear_delegate() {
+    let mut setup = setup_payment_annotation_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = Keypair::new();
+
+    assert_initialize_operator_delegate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &delegate.pubkey(),
+        false,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let mut instruction = setup.annotate_instruction(
+        &delegate.pubkey(),
+        1,
+        AnnotationFlag::FlaggedFraud,
+        note_hash(b"card testing pattern"),
+    );
+    instruction.accounts.push(operator_delegate_account(
+        &setup.operator_pda,
+        &delegate.pubkey(),
+    ));
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate])
+        .expect("Clear delegate should annotate payment");
+
+    let annotation = setup.payment_annotation(1);
+    assert_eq!(annotation.flag, AnnotationFlag::FlaggedFraud);
+    assert_eq!(annotation.annotated_by, delegate.pubkey());
+}
+
+#[tokio::test]
+async fn test_annotate_payment_delegate_without_clear_fails() {
+    let mut setup = setup_payment_annotation_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let delegate = Keypair::new();
+
+    // Make-payment only
+    assert_initialize_operator_delegate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &delegate.pubkey(),
+        true,
+        false,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let mut instruction =
+        setup.annotate_instruction(&delegate.pubkey(), 1, AnnotationFlag::Ok, [0; 32]);
+    instruction.accounts.push(operator_delegate_account(
+        &setup.operator_pda,
+        &delegate.pubkey(),
+    ));
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&delegate]);
+    assert_program_error(result, OPERATOR_DELEGATE_PERMISSION_DENIED_ERROR);
+}
+
+#[tokio::test]
+async fn test_annotate_payment_wrong_authority_fails() {
+    let mut setup = setup_payment_annotation_test().unwrap();
+    let imposter = Keypair::new();
+
+    let instruction = setup.annotate_instruction(
+        &imposter.pubkey(),
+        1,
+        AnnotationFlag::Ok,
+        note_hash(b"looks fine"),
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&imposter]);
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_annotate_payment_wrong_annotation_pda_fails() {
+    let mut setup = setup_payment_annotation_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let (first_annotation_pda, _) = find_payment_annotation_pda(&setup.payment_pda(1));
+
+    let annotate_second_payment = |setup: &PaymentAnnotationTestSetup| {
+        AnnotatePaymentBuilder::new()
+            .payer(setup.context.payer.pubkey())
+            .payment(setup.payment_pda(2))
+            .operator_authority(operator_authority.pubkey())
+            .buyer(setup.buyer.pubkey())
+            .operator(setup.operator_pda)
+            .merchant_operator_config(setup.merchant_operator_config_pda)
+            .mint(USDC_MINT)
+            .payment_annotation(first_annotation_pda)
+            .flag(AnnotationFlag::ManualReview)
+            .note_hash([0; 32])
+            .instruction()
+    };
+
+    // Not yet created: rejected by the derived address
+    let instruction = annotate_second_payment(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, INVALID_SEEDS_ERROR);
+
+    // Created for order 1: rejected as another payment's annotation
+    let instruction =
+        setup.annotate_instruction(&operator_authority.pubkey(), 1, AnnotationFlag::Ok, [0; 32]);
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .unwrap();
+
+    let instruction = annotate_second_payment(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, PAYMENT_ANNOTATION_INVALID_PDA_ERROR);
+}
@@ -0,0 +1,335 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_note_pda,
+        find_payment_pda, get_or_create_associated_token_account, idempotency_key,
+        set_token_balance, TestContext, DAYS_TO_CLOSE, MERCHANT_MISMATCH_ERROR,
+        MERCHANT_OWNER_MISMATCH_ERROR, NOT_ENOUGH_ACCOUNT_KEYS_ERROR,
+        PAYMENT_NOTE_INVALID_PDA_ERROR, RENT_PAYER_MISMATCH_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{ClosePaymentNoteBuilder, MakePaymentBuilder},
+    types::{EncryptedNote, FeeType, PolicyData, SettlementPolicy},
+    Merchant, PaymentNote,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 1_000_000;
+const CIPHERTEXT: [u8; 128] = [7u8; 128];
+
+struct PaymentNoteTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a merchant, operator and config accepting USDC
+fn setup_payment_note_test() -> Result<PaymentNoteTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    Ok(PaymentNoteTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn make_payment_instruction(
+    setup: &mut PaymentNoteTestSetup,
+    payment_note: Option<&Pubkey>,
+) -> Instruction {
+    let merchant_account = setup.context.get_account(&setup.merchant_pda).unwrap();
+    let settlement_wallet = Merchant::from_bytes(&merchant_account.data)
+        .unwrap()
+        .settlement_wallet;
+
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut setup.context,
+        &buyer_ata,
+        &USDC_MINT,
+        &setup.buyer.pubkey(),
+        AMOUNT,
+    );
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+
+    let mut builder = MakePaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(&settlement_wallet, &USDC_MINT))
+        .settlement_wallet(settlement_wallet)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .order_id(ORDER_ID)
+        .idempotency_key(idempotency_key(ORDER_ID))
+        .amount(AMOUNT)
+        .note(EncryptedNote {
+            ciphertext: CIPHERTEXT,
+        });
+
+    if let Some(payment_note) = payment_note {
+        builder.add_remaining_account(AccountMeta::new(*payment_note, false));
+    }
+
+    builder.instruction()
+}
+
+fn close_payment_note_instruction(
+    authority: &Pubkey,
+    merchant: &Pubkey,
+    payment_note: &Pubkey,
+    rent_payer: &Pubkey,
+) -> Instruction {
+    ClosePaymentNoteBuilder::new()
+        .authority(*authority)
+        .merchant(*merchant)
+        .payment_note(*payment_note)
+        .rent_payer(*rent_payer)
+        .instruction()
+}
+
+fn lamports(context: &mut TestContext, pubkey: &Pubkey) -> u64 {
+    context.get_account(pubkey).map_or(0, |a| a.lamports)
+}
+
+// Makes a payment with a note and returns the note's PDA
+fn make_payment_with_note(setup: &mut PaymentNoteTestSetup) -> Pubkey {
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+    let (payment_note_pda, _) = find_payment_note_pda(&payment_pda);
+
+    let instruction = make_payment_instruction(setup, Some(&payment_note_pda));
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer])
+        .expect("Make payment with a note should succeed");
+
+    payment_note_pda
+}
+
+#[tokio::test]
+async fn test_make_payment_with_note() {
+    let mut setup = setup_payment_note_test().unwrap();
+    let payment_note_pda = make_payment_with_note(&mut setup);
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+    let (_, bump) = find_payment_note_pda(&payment_pda);
+
+    let payment_note_account = setup.context.get_account(&payment_note_pda).unwrap();
+    let payment_note = PaymentNote::from_bytes(&payment_note_account.data).unwrap();
+    assert_eq!(payment_note.payment, payment_pda);
+    assert_eq!(payment_note.merchant, setup.merchant_pda);
+    assert_eq!(payment_note.bump, bump);
+    assert_eq!(payment_note.rent_payer, setup.context.payer.pubkey());
+    assert_eq!(payment_note.ciphertext, CIPHERTEXT);
+}
+
+#[tokio::test]
+async fn test_make_payment_with_note_missing_account_fails() {
+    let mut setup = setup_payment_note_test().unwrap();
+
+    let instruction = make_payment_instruction(&mut setup, None);
+    let buyer = setup.buyer.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer]);
+    assert_program_error(result, NOT_ENOUGH_ACCOUNT_KEYS_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_with_note_wrong_pda_fails() {
+    let mut setup = setup_payment_note_test().unwrap();
+
+    let instruction = make_payment_instruction(&mut setup, Some(&Pubkey::new_unique()));
+    let buyer = setup.buyer.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer]);
+    assert_program_error(result, PAYMENT_NOTE_INVALID_PDA_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_with_note_wrong_merchant_fails() {
+    let mut setup = setup_payment_note_test().unwrap();
+
+    // A merchant outside the config would be recorded as the note's reader
+    let other_merchant_authority = Keypair::new();
+    let other_settlement_wallet = Keypair::new();
+    let (other_merchant_pda, _) = assert_get_or_create_merchant(
+        &mut setup.context,
+        &other_merchant_authority,
+        &other_settlement_wallet,
+        true,
+        false,
+    )
+    .unwrap();
+    setup.merchant_pda = other_merchant_pda;
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+    let (payment_note_pda, _) = find_payment_note_pda(&payment_pda);
+
+    let instruction = make_payment_instruction(&mut setup, Some(&payment_note_pda));
+    let buyer = setup.buyer.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer]);
+    assert_program_error(result, MERCHANT_MISMATCH_ERROR);
+    assert!(setup.context.get_account(&payment_note_pda).is_none());
+}
+
+#[tokio::test]
+async fn test_close_payment_note_refunds_rent() {
+    let mut setup = setup_payment_note_test().unwrap();
+    let payment_note_pda = make_payment_with_note(&mut setup);
+    let rent_payer = setup.context.payer.pubkey();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    let rent = lamports(&mut setup.context, &payment_note_pda);
+    let rent_payer_before = lamports(&mut setup.context, &rent_payer);
+
+    let instruction = close_payment_note_instruction(
+        &merchant_authority.pubkey(),
+        &setup.merchant_pda,
+        &payment_note_pda,
+        &rent_payer,
+    );
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority])
+        .expect("Close payment note should succeed");
+
+    assert!(setup.context.get_account(&payment_note_pda).is_none());
+
+    // The payer got the rent back, less the transaction fee it paid
+    let rent_payer_after = lamports(&mut setup.context, &rent_payer);
+    assert!(rent_payer_after > rent_payer_before);
+    assert!(rent_payer_after <= rent_payer_before + rent);
+}
+
+#[tokio::test]
+async fn test_close_payment_note_wrong_authority_fails() {
+    let mut setup = setup_payment_note_test().unwrap();
+    let payment_note_pda = make_payment_with_note(&mut setup);
+    let rent_payer = setup.context.payer.pubkey();
+    let other_authority = Keypair::new();
+
+    let instruction = close_payment_note_instruction(
+        &other_authority.pubkey(),
+        &setup.merchant_pda,
+        &payment_note_pda,
+        &rent_payer,
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&other_authority]);
+    assert_program_error(result, MERCHANT_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_close_payment_note_wrong_rent_payer_fails() {
+    let mut setup = setup_payment_note_test().unwrap();
+    let payment_note_pda = make_payment_with_note(&mut setup);
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    let instruction = close_payment_note_instruction(
+        &merchant_authority.pubkey(),
+        &setup.merchant_pda,
+        &payment_note_pda,
+        &Pubkey::new_unique(),
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&merchant_authority]);
+    assert_program_error(result, RENT_PAYER_MISMATCH_ERROR);
+}
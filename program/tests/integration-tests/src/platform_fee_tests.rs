@@ -0,0 +1,317 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda,
+        get_or_create_associated_token_account, get_token_balance, set_token_balance, TestContext,
+        TestContextBuilder, ATA_MINT_MISMATCH_ERROR, ATA_NOT_INITIALIZED_ERROR,
+        ATA_OWNER_MISMATCH_ERROR, DAYS_TO_CLOSE, INVALID_PLATFORM_FEE_POLICY_ERROR,
+        NOT_ENOUGH_ACCOUNT_KEYS_ERROR, USDC_MINT, USDT_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::ClearPaymentBuilder,
+    split_settlement::SplitSettlement,
+    types::{FeeType, PlatformFeePolicy, PolicyData},
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+// 5% operator fee
+const OPERATOR_FEE: u64 = PAYMENT_AMOUNT / 20;
+// 2% platform fee
+const PLATFORM_FEE: u64 = PAYMENT_AMOUNT / 50;
+const MERCHANT_AMOUNT: u64 = PAYMENT_AMOUNT - OPERATOR_FEE - PLATFORM_FEE;
+
+struct PlatformFeeTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    platform: Pubkey,
+    payment_pda: Pubkey,
+}
+
+impl PlatformFeeTestSetup {
+    fn platform_settlement_ata(&self) -> AccountMeta {
+        AccountMeta::new(
+            get_associated_token_address(&self.platform, &USDC_MINT),
+            false,
+        )
+    }
+}
+
+fn platform_fee_policy(platform: Pubkey) -> PolicyData {
+    PolicyData::PlatformFee(PlatformFeePolicy {
+        platform,
+        fee: 200, // 2%
+        fee_type: FeeType::Bps,
+    })
+}
+
+// Helper function to set up a config with a 5% operator fee and a 2% platform fee, plus
+// `extra_policies`, with one paid payment
+fn setup_platform_fee_test(
+    extra_policies: Vec<PolicyData>,
+) -> Result<PlatformFeeTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+    let platform = Pubkey::new_unique();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let mut policies = vec![platform_fee_policy(platform)];
+    policies.extend(extra_policies);
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        PAYMENT_AMOUNT,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+
+    for wallet in [
+        settlement_wallet.pubkey(),
+        operator_authority.pubkey(),
+        platform,
+    ] {
+        get_or_create_associated_token_account(&mut context, &wallet, &USDC_MINT);
+    }
+
+    Ok(PlatformFeeTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        platform,
+        payment_pda,
+    })
+}
+
+fn clear_payment_instruction(
+    setup: &PlatformFeeTestSetup,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut builder = ClearPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .add_remaining_accounts(&remaining_accounts);
+    builder.instruction()
+}
+
+fn clear_payment(
+    setup: &mut PlatformFeeTestSetup,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction = clear_payment_instruction(setup, remaining_accounts);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])?;
+    Ok(())
+}
+
+fn balance(setup: &mut PlatformFeeTestSetup, wallet: &Pubkey) -> u64 {
+    let ata = get_associated_token_address(wallet, &USDC_MINT);
+    get_token_balance(&mut setup.context, &ata)
+}
+
+#[tokio::test]
+async fn test_clear_payment_platform_fee() {
+    let mut setup = setup_platform_fee_test(vec![]).unwrap();
+    let remaining_accounts = vec![setup.platform_settlement_ata()];
+
+    clear_payment(&mut setup, remaining_accounts).expect("Should clear with a platform fee");
+
+    let platform = setup.platform;
+    let settlement_wallet = setup.settlement_wallet;
+    let operator_authority = setup.operator_authority.pubkey();
+    let merchant_pda = setup.merchant_pda;
+
+    assert_eq!(balance(&mut setup, &platform), PLATFORM_FEE);
+    assert_eq!(balance(&mut setup, &operator_authority), OPERATOR_FEE);
+    assert_eq!(balance(&mut setup, &settlement_wallet), MERCHANT_AMOUNT);
+    assert_eq!(balance(&mut setup, &merchant_pda), 0);
+}
+
+#[tokio::test]
+async fn test_clear_payment_platform_fee_with_split_settlement() {
+    let treasury = Pubkey::new_unique();
+    let tax_escrow = Pubkey::new_unique();
+    let split = SplitSettlement::new([(treasury, 9_000), (tax_escrow, 1_000)]);
+    let mut setup =
+        setup_platform_fee_test(vec![PolicyData::SplitSettlement(split.policy())]).unwrap();
+    for wallet in [treasury, tax_escrow] {
+        get_or_create_associated_token_account(&mut setup.context, &wallet, &USDC_MINT);
+    }
+
+    // The platform's settlement ATA comes before the split settlement destinations
+    let mut remaining_accounts = vec![setup.platform_settlement_ata()];
+    remaining_accounts.extend(split.remaining_accounts(&USDC_MINT, &spl_token::ID));
+
+    clear_payment(&mut setup, remaining_accounts)
+        .expect("Should clear with a platform fee and split settlement");
+
+    let platform = setup.platform;
+    assert_eq!(balance(&mut setup, &platform), PLATFORM_FEE);
+    assert_eq!(balance(&mut setup, &treasury), MERCHANT_AMOUNT * 9 / 10);
+    assert_eq!(balance(&mut setup, &tax_escrow), MERCHANT_AMOUNT / 10);
+}
+
+#[tokio::test]
+async fn test_clear_payment_platform_fee_missing_ata_fails() {
+    let mut setup = setup_platform_fee_test(vec![]).unwrap();
+
+    let result = clear_payment(&mut setup, vec![]);
+    assert_program_error(result, NOT_ENOUGH_ACCOUNT_KEYS_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_platform_fee_wrong_ata_fails() {
+    let mut setup = setup_platform_fee_test(vec![]).unwrap();
+    // The operator's ATA instead of the platform's
+    let remaining_accounts = vec![AccountMeta::new(
+        get_associated_token_address(&setup.operator_authority.pubkey(), &USDC_MINT),
+        false,
+    )];
+
+    let result = clear_payment(&mut setup, remaining_accounts);
+    assert_program_error(result, ATA_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_platform_fee_wrong_mint_ata_fails() {
+    let mut setup = setup_platform_fee_test(vec![]).unwrap();
+    // A token account of the platform's, but for another mint
+    let token_account = Pubkey::new_unique();
+    let platform = setup.platform;
+    set_token_balance(&mut setup.context, &token_account, &USDT_MINT, &platform, 0);
+
+    let result = clear_payment(&mut setup, vec![AccountMeta::new(token_account, false)]);
+    assert_program_error(result, ATA_MINT_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_platform_fee_uninitialized_ata_fails() {
+    let mut setup = setup_platform_fee_test(vec![]).unwrap();
+    let platform_ata = get_associated_token_address(&setup.platform, &USDC_MINT);
+    setup
+        .context
+        .svm
+        .set_account(
+            platform_ata,
+            Account {
+                lamports: 1_000_000,
+                ..Account::default()
+            },
+        )
+        .unwrap();
+
+    let remaining_accounts = vec![setup.platform_settlement_ata()];
+    let result = clear_payment(&mut setup, remaining_accounts);
+    assert_program_error(result, ATA_NOT_INITIALIZED_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_config_invalid_platform_fee_policy_fails() {
+    let mut setup = setup_platform_fee_test(vec![]).unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    // Over 100%
+    let policy = PolicyData::PlatformFee(PlatformFeePolicy {
+        platform: setup.platform,
+        fee: 10_001,
+        fee_type: FeeType::Bps,
+    });
+
+    let result = assert_get_or_create_merchant_operator_config(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        2u32,
+        500u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![policy],
+        vec![USDC_MINT],
+        true,
+        false,
+    )
+    .map(|_| ());
+
+    assert_program_error(result, INVALID_PLATFORM_FEE_POLICY_ERROR);
+}
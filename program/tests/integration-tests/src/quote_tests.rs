@@ -0,0 +1,221 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        find_quote_pda, idempotency_key, set_token_balance, TestContext, DAYS_TO_CLOSE,
+        QUOTE_EXPIRED_ERROR, QUOTE_MISMATCH_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{CreateQuoteBuilder, MakePaymentBuilder},
+    types::FeeType,
+};
+use solana_program::clock::Clock;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const ORDER_ID: u32 = 1;
+const QUOTED_AMOUNT: u64 = 25_000_000;
+const QUOTE_TTL_SECONDS: i64 = 600;
+
+struct QuoteTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    settlement_wallet: Pubkey,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a config accepting USDC
+fn setup_quote_test() -> Result<QuoteTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        0,
+        FeeType::Fixed,
+        0,
+        DAYS_TO_CLOSE,
+        vec![],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    Ok(QuoteTestSetup {
+        context,
+        operator_authority,
+        buyer: Keypair::new(),
+        settlement_wallet: settlement_wallet.pubkey(),
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn now(context: &TestContext) -> i64 {
+    context.svm.get_sysvar::<Clock>().unix_timestamp
+}
+
+fn create_quote(setup: &mut QuoteTestSetup) -> Pubkey {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let expires_at = now(&setup.context) + QUOTE_TTL_SECONDS;
+    let (quote_pda, _) = assert_create_quote(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &setup.merchant_operator_config_pda,
+        &USDC_MINT,
+        ORDER_ID,
+        QUOTED_AMOUNT,
+        expires_at,
+        true,
+    )
+    .unwrap();
+    quote_pda
+}
+
+fn make_payment_instruction(
+    setup: &mut QuoteTestSetup,
+    quote_pda: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let buyer = setup.buyer.pubkey();
+    let buyer_ata = get_associated_token_address(&buyer, &USDC_MINT);
+    set_token_balance(&mut setup.context, &buyer_ata, &USDC_MINT, &buyer, amount);
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &buyer,
+        &USDC_MINT,
+        ORDER_ID,
+    );
+
+    let mut builder = MakePaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(buyer)
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(ORDER_ID)
+        .idempotency_key(idempotency_key(ORDER_ID))
+        .amount(amount)
+        .add_remaining_account(AccountMeta::new_readonly(quote_pda, false));
+    builder.instruction()
+}
+
+fn send_payment(
+    setup: &mut QuoteTestSetup,
+    instruction: Instruction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+}
+
+#[tokio::test]
+async fn test_make_payment_with_quote_success() {
+    let mut setup = setup_quote_test().unwrap();
+    let quote_pda = create_quote(&mut setup);
+
+    let instruction = make_payment_instruction(&mut setup, quote_pda, QUOTED_AMOUNT);
+    send_payment(&mut setup, instruction).expect("Quoted payment should succeed");
+
+    let buyer = setup.buyer.pubkey();
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &buyer,
+        &USDC_MINT,
+        ORDER_ID,
+    );
+    assert!(setup.context.get_account(&payment_pda).is_some());
+}
+
+#[tokio::test]
+async fn test_make_payment_quote_amount_mismatch_fails() {
+    let mut setup = setup_quote_test().unwrap();
+    let quote_pda = create_quote(&mut setup);
+
+    let instruction = make_payment_instruction(&mut setup, quote_pda, QUOTED_AMOUNT - 1);
+    let result = send_payment(&mut setup, instruction);
+    assert_program_error(result, QUOTE_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_expired_quote_fails() {
+    let mut setup = setup_quote_test().unwrap();
+    let quote_pda = create_quote(&mut setup);
+
+    setup.context.advance_clock(QUOTE_TTL_SECONDS + 1);
+
+    let instruction = make_payment_instruction(&mut setup, quote_pda, QUOTED_AMOUNT);
+    let result = send_payment(&mut setup, instruction);
+    assert_program_error(result, QUOTE_EXPIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_create_quote_already_expired_fails() {
+    let mut setup = setup_quote_test().unwrap();
+    let (quote_pda, bump) =
+        find_quote_pda(&setup.merchant_operator_config_pda, &USDC_MINT, ORDER_ID);
+
+    let instruction = CreateQuoteBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .operator_authority(setup.operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .quote(quote_pda)
+        .order_id(ORDER_ID)
+        .amount(QUOTED_AMOUNT)
+        .expires_at(now(&setup.context))
+        .bump(bump)
+        .instruction();
+
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, QUOTE_EXPIRED_ERROR);
+}
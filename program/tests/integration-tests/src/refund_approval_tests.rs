@@ -0,0 +1,322 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_refund_approval_pda,
+        get_or_create_associated_token_account, set_token_balance, TestContext, TestContextBuilder,
+        DAYS_TO_CLOSE, INVALID_ACCOUNT_OWNER_ERROR, INVALID_PAYMENT_STATUS_ERROR,
+        INVALID_REFUND_DESTINATION_ERROR, OPERATOR_OWNER_MISMATCH_ERROR,
+        REFUND_AMOUNT_EXCEEDS_POLICY_LIMIT_ERROR, RENT_PAYER_MISMATCH_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{ApproveRefundBuilder, ClaimRefundBuilder, RefundPaymentBuilder},
+    types::{FeeType, PolicyData, RefundPolicy},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+
+struct RefundApprovalTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a config with a refund policy and one paid payment
+fn setup_refund_approval_test(
+    max_refund_amount: u64,
+) -> Result<RefundApprovalTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Refund(RefundPolicy {
+            max_amount: max_refund_amount,
+            max_time_after_purchase: 0,
+        })],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        PAYMENT_AMOUNT,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+
+    Ok(RefundApprovalTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn approve_refund(
+    setup: &mut RefundApprovalTestSetup,
+    authority: &Keypair,
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let buyer = setup.buyer.pubkey();
+    let payment_pda = setup.payment_pda;
+    let merchant_operator_config_pda = setup.merchant_operator_config_pda;
+    assert_approve_refund(
+        &mut setup.context,
+        authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &merchant_operator_config_pda,
+        false,
+    )
+}
+
+fn claim_refund(
+    setup: &mut RefundApprovalTestSetup,
+    destination: &Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let buyer = setup.buyer.insecure_clone();
+    let payment_pda = setup.payment_pda;
+    let merchant_operator_config_pda = setup.merchant_operator_config_pda;
+    assert_claim_refund(
+        &mut setup.context,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &merchant_operator_config_pda,
+        destination,
+        false,
+    )
+}
+
+#[tokio::test]
+async fn test_approve_and_claim_refund_success() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    approve_refund(&mut setup, &operator_authority).expect("Operator should approve the refund");
+
+    let buyer = setup.buyer.pubkey();
+    let buyer_ata = get_or_create_associated_token_account(&mut setup.context, &buyer, &USDC_MINT);
+    claim_refund(&mut setup, &buyer_ata).expect("Buyer should claim the refund");
+}
+
+#[tokio::test]
+async fn test_merchant_owner_approves_refund() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    approve_refund(&mut setup, &merchant_authority).expect("Merchant should approve the refund");
+}
+
+#[tokio::test]
+async fn test_claim_refund_to_other_buyer_token_account() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    approve_refund(&mut setup, &operator_authority).unwrap();
+
+    // A token account that is not the buyer's ATA, as when the original ATA was closed
+    let buyer = setup.buyer.pubkey();
+    let destination = Pubkey::new_unique();
+    set_token_balance(&mut setup.context, &destination, &USDC_MINT, &buyer, 0);
+
+    claim_refund(&mut setup, &destination).expect("Buyer should claim to any account they own");
+}
+
+#[tokio::test]
+async fn test_claim_refund_destination_not_owned_by_buyer_fails() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    approve_refund(&mut setup, &operator_authority).unwrap();
+
+    let destination = get_associated_token_address(&operator_authority.pubkey(), &USDC_MINT);
+    get_or_create_associated_token_account(
+        &mut setup.context,
+        &operator_authority.pubkey(),
+        &USDC_MINT,
+    );
+
+    let result = claim_refund(&mut setup, &destination);
+    assert_program_error(result, INVALID_REFUND_DESTINATION_ERROR);
+}
+
+#[tokio::test]
+async fn test_claim_refund_twice_fails() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    approve_refund(&mut setup, &operator_authority).unwrap();
+
+    let buyer = setup.buyer.pubkey();
+    let buyer_ata = get_or_create_associated_token_account(&mut setup.context, &buyer, &USDC_MINT);
+    claim_refund(&mut setup, &buyer_ata).unwrap();
+
+    // The claim closed the approval
+    setup.context.svm.expire_blockhash();
+    let result = claim_refund(&mut setup, &buyer_ata);
+    assert_program_error(result, INVALID_ACCOUNT_OWNER_ERROR);
+}
+
+#[tokio::test]
+async fn test_claim_refund_wrong_rent_payer_fails() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    approve_refund(&mut setup, &operator_authority).unwrap();
+
+    let buyer = setup.buyer.insecure_clone();
+    let buyer_ata =
+        get_or_create_associated_token_account(&mut setup.context, &buyer.pubkey(), &USDC_MINT);
+    let instruction = ClaimRefundBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .buyer(buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .refund_approval(find_refund_approval_pda(&setup.payment_pda).0)
+        .rent_payer(buyer.pubkey())
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .destination(buyer_ata)
+        .instruction();
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer]);
+    assert_program_error(result, RENT_PAYER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_claim_refund_without_approval_fails() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+
+    let buyer = setup.buyer.pubkey();
+    let buyer_ata = get_or_create_associated_token_account(&mut setup.context, &buyer, &USDC_MINT);
+    let result = claim_refund(&mut setup, &buyer_ata);
+    assert_program_error(result, INVALID_ACCOUNT_OWNER_ERROR);
+}
+
+#[tokio::test]
+async fn test_approve_refund_unauthorized_fails() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+    let stranger = Keypair::new();
+
+    let result = approve_refund(&mut setup, &stranger).map(|_| ());
+    assert_program_error(result, OPERATOR_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_approve_refund_exceeds_policy_fails() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT - 1).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    let result = approve_refund(&mut setup, &operator_authority).map(|_| ());
+    assert_program_error(result, REFUND_AMOUNT_EXCEEDS_POLICY_LIMIT_ERROR);
+}
+
+#[tokio::test]
+async fn test_approve_refund_twice_fails() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    approve_refund(&mut setup, &operator_authority).unwrap();
+
+    let (refund_approval_pda, _) = find_refund_approval_pda(&setup.payment_pda);
+    let instruction = ApproveRefundBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .authority(operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .refund_approval(refund_approval_pda)
+        .instruction();
+
+    setup.context.svm.expire_blockhash();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_refund_payment_after_approval_fails() {
+    let mut setup = setup_refund_approval_test(PAYMENT_AMOUNT).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    approve_refund(&mut setup, &operator_authority).unwrap();
+
+    let buyer = setup.buyer.pubkey();
+    let buyer_ata = get_or_create_associated_token_account(&mut setup.context, &buyer, &USDC_MINT);
+    let instruction = RefundPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer)
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(buyer_ata)
+        .instruction();
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, INVALID_PAYMENT_STATUS_ERROR);
+}
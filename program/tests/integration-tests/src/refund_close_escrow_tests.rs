@@ -0,0 +1,333 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        get_or_create_associated_token_account, get_token_balance, idempotency_key,
+        set_token_balance, TestContext, DAYS_TO_CLOSE, MISSING_REQUIRED_SIGNATURE_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{MakePaymentBuilder, RefundPaymentBuilder},
+    types::{FeeType, PolicyData, SettlementPolicy},
+    Merchant,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const AMOUNT: u64 = 1_000_000;
+const TREASURY_LAMPORTS: u64 = 1_000_000_000;
+// LiteSVM's default fee for the payer's signature
+const TRANSACTION_FEE: u64 = 5_000;
+
+struct CloseEscrowTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    treasury: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    escrow_ata: Pubkey,
+}
+
+// Helper function to set up a config without auto settlement and a funded rent treasury
+fn setup_close_escrow_test() -> Result<CloseEscrowTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+    let treasury = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+    context.create_account(
+        &treasury.pubkey(),
+        &SYSTEM_PROGRAM_ID,
+        vec![],
+        TREASURY_LAMPORTS,
+    );
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    Ok(CloseEscrowTestSetup {
+        context,
+        operator_authority,
+        buyer,
+        treasury,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        escrow_ata: find_escrow_ata(&merchant_operator_config_pda, &USDC_MINT),
+    })
+}
+
+fn make_payment(setup: &mut CloseEscrowTestSetup, order_id: u32) -> Pubkey {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_make_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &setup.merchant_operator_config_pda,
+        &setup.operator_pda,
+        &USDC_MINT,
+        order_id,
+        AMOUNT,
+        true,
+        false,
+        false,
+    )
+    .unwrap()
+    .0
+}
+
+// Makes a payment whose rent the treasury pays
+fn make_treasury_payment(setup: &mut CloseEscrowTestSetup, order_id: u32) -> Pubkey {
+    let merchant_account = setup.context.get_account(&setup.merchant_pda).unwrap();
+    let settlement_wallet = Merchant::from_bytes(&merchant_account.data)
+        .unwrap()
+        .settlement_wallet;
+
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut setup.context,
+        &buyer_ata,
+        &USDC_MINT,
+        &setup.buyer.pubkey(),
+        AMOUNT,
+    );
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let instruction = MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(setup.escrow_ata)
+        .merchant_settlement_ata(get_associated_token_address(&settlement_wallet, &USDC_MINT))
+        .settlement_wallet(settlement_wallet)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(AMOUNT)
+        .add_remaining_account(AccountMeta::new(setup.treasury.pubkey(), true))
+        .instruction();
+
+    let treasury = setup.treasury.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer, &treasury])
+        .expect("Make payment with rent treasury should succeed");
+
+    payment_pda
+}
+
+fn refund_payment_instruction(
+    setup: &CloseEscrowTestSetup,
+    payment_pda: &Pubkey,
+    close_escrow: bool,
+    rent_treasury: Option<AccountMeta>,
+) -> Instruction {
+    let mut builder = RefundPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(*payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(setup.escrow_ata)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .close_escrow(close_escrow);
+
+    if let Some(rent_treasury) = rent_treasury {
+        builder.add_remaining_account(rent_treasury);
+    }
+
+    builder.instruction()
+}
+
+fn lamports(context: &mut TestContext, pubkey: &Pubkey) -> u64 {
+    context.get_account(pubkey).map_or(0, |a| a.lamports)
+}
+
+#[tokio::test]
+async fn test_refund_close_escrow_closes_empty_escrow() {
+    let mut setup = setup_close_escrow_test().unwrap();
+    let payment_pda = make_payment(&mut setup, 1);
+    assert!(setup.context.get_account(&setup.escrow_ata).is_some());
+
+    let instruction = refund_payment_instruction(&setup, &payment_pda, true, None);
+    setup
+        .context
+        .send_transaction(instruction)
+        .expect("Refund closing the escrow should succeed");
+
+    assert_eq!(lamports(&mut setup.context, &setup.escrow_ata), 0);
+
+    // The next payment recreates the escrow
+    make_payment(&mut setup, 2);
+    assert_eq!(
+        get_token_balance(&mut setup.context, &setup.escrow_ata),
+        AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn test_refund_without_close_escrow_keeps_empty_escrow() {
+    let mut setup = setup_close_escrow_test().unwrap();
+    let payment_pda = make_payment(&mut setup, 1);
+
+    let instruction = refund_payment_instruction(&setup, &payment_pda, false, None);
+    setup
+        .context
+        .send_transaction(instruction)
+        .expect("Refund should succeed");
+
+    assert_eq!(get_token_balance(&mut setup.context, &setup.escrow_ata), 0);
+}
+
+#[tokio::test]
+async fn test_refund_close_escrow_keeps_escrow_of_open_payments() {
+    let mut setup = setup_close_escrow_test().unwrap();
+    let payment_pda = make_payment(&mut setup, 1);
+    make_payment(&mut setup, 2);
+
+    let instruction = refund_payment_instruction(&setup, &payment_pda, true, None);
+    setup
+        .context
+        .send_transaction(instruction)
+        .expect("Refund should succeed");
+
+    // The other payment's funds stay in escrow
+    assert_eq!(
+        get_token_balance(&mut setup.context, &setup.escrow_ata),
+        AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn test_refund_close_escrow_refunds_rent_to_treasury() {
+    let mut setup = setup_close_escrow_test().unwrap();
+    let payment_pda = make_payment(&mut setup, 1);
+    let treasury = setup.treasury.insecure_clone();
+
+    let rent = lamports(&mut setup.context, &setup.escrow_ata);
+    let treasury_before = lamports(&mut setup.context, &treasury.pubkey());
+
+    // The rent treasury must sign
+    let instruction = refund_payment_instruction(
+        &setup,
+        &payment_pda,
+        true,
+        Some(AccountMeta::new(treasury.pubkey(), false)),
+    );
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, MISSING_REQUIRED_SIGNATURE_ERROR);
+
+    let instruction = refund_payment_instruction(
+        &setup,
+        &payment_pda,
+        true,
+        Some(AccountMeta::new(treasury.pubkey(), true)),
+    );
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&treasury])
+        .expect("Refund closing the escrow should succeed");
+
+    // The escrow's rent went to the operator's rent treasury, though the payer paid the
+    // payment's rent
+    assert_eq!(lamports(&mut setup.context, &setup.escrow_ata), 0);
+    assert_eq!(
+        lamports(&mut setup.context, &treasury.pubkey()),
+        treasury_before + rent
+    );
+}
+
+#[tokio::test]
+async fn test_refund_close_escrow_without_treasury_refunds_rent_to_payer() {
+    let mut setup = setup_close_escrow_test().unwrap();
+    let payment_pda = make_treasury_payment(&mut setup, 1);
+    let treasury = setup.treasury.pubkey();
+    let payer = setup.context.payer.pubkey();
+
+    let rent = lamports(&mut setup.context, &setup.escrow_ata);
+    let treasury_before = lamports(&mut setup.context, &treasury);
+    let payer_before = lamports(&mut setup.context, &payer);
+
+    // The payment's rent payer is not owed the escrow's rent
+    let instruction = refund_payment_instruction(&setup, &payment_pda, true, None);
+    setup
+        .context
+        .send_transaction(instruction)
+        .expect("Refund closing the escrow should succeed");
+
+    assert_eq!(lamports(&mut setup.context, &setup.escrow_ata), 0);
+    assert_eq!(lamports(&mut setup.context, &treasury), treasury_before);
+    assert_eq!(
+        lamports(&mut setup.context, &payer),
+        payer_before + rent - TRANSACTION_FEE
+    );
+}
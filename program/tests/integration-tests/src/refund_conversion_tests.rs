@@ -0,0 +1,377 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_conversion_rate_pda, find_escrow_ata, find_escrow_authority_pda,
+        get_token_balance, set_token_balance, TestContext, TestContextBuilder,
+        CONVERSION_RATE_MISMATCH_ERROR, CONVERSION_RATE_STALE_ERROR, DAYS_TO_CLOSE,
+        INVALID_CONVERSION_RATE_ERROR, INVALID_MINT_ERROR, INVALID_REFUND_CONVERSION_POLICY_ERROR,
+        REFUND_CONVERSION_POLICY_REQUIRED_ERROR, USDC_MINT, USDT_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{PostConversionRateBuilder, RefundPaymentWithConversionBuilder},
+    types::{FeeType, PolicyData, RefundConversionPolicy, Status},
+    Payment,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+const MAX_RATE_AGE_SECONDS: u32 = 3_600;
+// 0.98 USDT per USDC
+const RATE_NUMERATOR: u64 = 98;
+const RATE_DENOMINATOR: u64 = 100;
+const OPERATOR_USDT_BALANCE: u64 = 10_000_000;
+
+struct RefundConversionTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+fn refund_conversion_policy() -> PolicyData {
+    PolicyData::RefundConversion(RefundConversionPolicy {
+        max_rate_age_seconds: MAX_RATE_AGE_SECONDS,
+    })
+}
+
+// Helper function to set up a config accepting USDC and USDT with `policies`, one paid USDC
+// payment, and USDT in the operator authority's ATA to refund from
+fn setup_refund_conversion_test(
+    policies: Vec<PolicyData>,
+) -> Result<RefundConversionTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_usdt()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        0u64,
+        FeeType::Fixed,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT, USDT_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        PAYMENT_AMOUNT,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+
+    let operator_usdt_ata = get_associated_token_address(&operator_authority.pubkey(), &USDT_MINT);
+    set_token_balance(
+        &mut context,
+        &operator_usdt_ata,
+        &USDT_MINT,
+        &operator_authority.pubkey(),
+        OPERATOR_USDT_BALANCE,
+    );
+
+    Ok(RefundConversionTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn post_rate(
+    setup: &mut RefundConversionTestSetup,
+    from_mint: &Pubkey,
+    to_mint: &Pubkey,
+) -> Pubkey {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let (conversion_rate_pda, _) = assert_post_conversion_rate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &setup.merchant_operator_config_pda,
+        from_mint,
+        to_mint,
+        RATE_NUMERATOR,
+        RATE_DENOMINATOR,
+    )
+    .unwrap();
+    conversion_rate_pda
+}
+
+fn post_rate_instruction(
+    setup: &RefundConversionTestSetup,
+    from_mint: &Pubkey,
+    to_mint: &Pubkey,
+    rate_numerator: u64,
+    rate_denominator: u64,
+) -> Instruction {
+    let (conversion_rate_pda, bump) =
+        find_conversion_rate_pda(&setup.merchant_operator_config_pda, from_mint, to_mint);
+    PostConversionRateBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .operator_authority(setup.operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .from_mint(*from_mint)
+        .to_mint(*to_mint)
+        .conversion_rate(conversion_rate_pda)
+        .rate_numerator(rate_numerator)
+        .rate_denominator(rate_denominator)
+        .bump(bump)
+        .instruction()
+}
+
+fn refund_instruction(
+    setup: &RefundConversionTestSetup,
+    refund_mint: &Pubkey,
+    conversion_rate_pda: Pubkey,
+) -> Instruction {
+    let operator_authority = setup.operator_authority.pubkey();
+    let buyer = setup.buyer.pubkey();
+    RefundPaymentWithConversionBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(operator_authority)
+        .buyer(buyer)
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &operator_authority,
+            &USDC_MINT,
+        ))
+        .refund_mint(*refund_mint)
+        .conversion_rate(conversion_rate_pda)
+        .operator_refund_ata(get_associated_token_address(
+            &operator_authority,
+            refund_mint,
+        ))
+        .buyer_refund_ata(get_associated_token_address(&buyer, refund_mint))
+        .instruction()
+}
+
+fn payment_status(setup: &mut RefundConversionTestSetup) -> Status {
+    let account = setup.context.get_account(&setup.payment_pda).unwrap();
+    Payment::from_bytes(&account.data).unwrap().status
+}
+
+#[tokio::test]
+async fn test_post_conversion_rate_updates_in_place() {
+    let mut setup = setup_refund_conversion_test(vec![refund_conversion_policy()]).unwrap();
+
+    let first = post_rate(&mut setup, &USDC_MINT, &USDT_MINT);
+
+    setup.context.advance_clock(60);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let (second, _) = assert_post_conversion_rate(
+        &mut setup.context,
+        &operator_authority,
+        &setup.operator_pda,
+        &setup.merchant_operator_config_pda,
+        &USDC_MINT,
+        &USDT_MINT,
+        99,
+        100,
+    )
+    .unwrap();
+
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn test_refund_payment_with_conversion() {
+    let mut setup = setup_refund_conversion_test(vec![refund_conversion_policy()]).unwrap();
+    let conversion_rate_pda = post_rate(&mut setup, &USDC_MINT, &USDT_MINT);
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let instruction = refund_instruction(&setup, &USDT_MINT, conversion_rate_pda);
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .unwrap();
+
+    assert_eq!(payment_status(&mut setup), Status::Refunded);
+
+    // The buyer is refunded 0.98 USDT per USDC paid, from the operator's USDT
+    let refund_amount = PAYMENT_AMOUNT * RATE_NUMERATOR / RATE_DENOMINATOR;
+    let buyer_usdt_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDT_MINT);
+    let operator_usdt_ata = get_associated_token_address(&operator_authority.pubkey(), &USDT_MINT);
+    assert_eq!(
+        get_token_balance(&mut setup.context, &buyer_usdt_ata),
+        refund_amount
+    );
+    assert_eq!(
+        get_token_balance(&mut setup.context, &operator_usdt_ata),
+        OPERATOR_USDT_BALANCE - refund_amount
+    );
+
+    // The escrowed USDC goes to the operator
+    let escrow_ata = find_escrow_ata(&setup.merchant_operator_config_pda, &USDC_MINT);
+    let operator_usdc_ata = get_associated_token_address(&operator_authority.pubkey(), &USDC_MINT);
+    assert_eq!(get_token_balance(&mut setup.context, &escrow_ata), 0);
+    assert_eq!(
+        get_token_balance(&mut setup.context, &operator_usdc_ata),
+        PAYMENT_AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn test_refund_payment_with_stale_conversion_rate_fails() {
+    let mut setup = setup_refund_conversion_test(vec![refund_conversion_policy()]).unwrap();
+    let conversion_rate_pda = post_rate(&mut setup, &USDC_MINT, &USDT_MINT);
+
+    setup.context.advance_clock(MAX_RATE_AGE_SECONDS as i64 + 1);
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let instruction = refund_instruction(&setup, &USDT_MINT, conversion_rate_pda);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, CONVERSION_RATE_STALE_ERROR);
+    assert_eq!(payment_status(&mut setup), Status::Paid);
+
+    // Reposting the rate makes it fresh again
+    post_rate(&mut setup, &USDC_MINT, &USDT_MINT);
+    let instruction = refund_instruction(&setup, &USDT_MINT, conversion_rate_pda);
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .unwrap();
+    assert_eq!(payment_status(&mut setup), Status::Refunded);
+}
+
+#[tokio::test]
+async fn test_refund_payment_with_reverse_conversion_rate_fails() {
+    let mut setup = setup_refund_conversion_test(vec![refund_conversion_policy()]).unwrap();
+    let reverse_rate_pda = post_rate(&mut setup, &USDT_MINT, &USDC_MINT);
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let instruction = refund_instruction(&setup, &USDT_MINT, reverse_rate_pda);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, CONVERSION_RATE_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_refund_payment_with_conversion_into_payment_mint_fails() {
+    let mut setup = setup_refund_conversion_test(vec![refund_conversion_policy()]).unwrap();
+    let conversion_rate_pda = post_rate(&mut setup, &USDC_MINT, &USDT_MINT);
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let instruction = refund_instruction(&setup, &USDC_MINT, conversion_rate_pda);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, INVALID_MINT_ERROR);
+}
+
+#[tokio::test]
+async fn test_post_conversion_rate_without_policy_fails() {
+    let mut setup = setup_refund_conversion_test(vec![]).unwrap();
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let instruction = post_rate_instruction(
+        &setup,
+        &USDC_MINT,
+        &USDT_MINT,
+        RATE_NUMERATOR,
+        RATE_DENOMINATOR,
+    );
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, REFUND_CONVERSION_POLICY_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_post_zero_conversion_rate_fails() {
+    let mut setup = setup_refund_conversion_test(vec![refund_conversion_policy()]).unwrap();
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let instruction = post_rate_instruction(&setup, &USDC_MINT, &USDT_MINT, 0, RATE_DENOMINATOR);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, INVALID_CONVERSION_RATE_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_config_invalid_refund_conversion_policy_fails() {
+    let mut setup = setup_refund_conversion_test(vec![]).unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    let policy = PolicyData::RefundConversion(RefundConversionPolicy {
+        max_rate_age_seconds: 0,
+    });
+
+    let result = assert_get_or_create_merchant_operator_config(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        2u32,
+        0u64,
+        FeeType::Fixed,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![policy],
+        vec![USDC_MINT, USDT_MINT],
+        true,
+        false,
+    )
+    .map(|_| ());
+
+    assert_program_error(result, INVALID_REFUND_CONVERSION_POLICY_ERROR);
+}
@@ -1,14 +1,15 @@
 use crate::{
     state_utils::*,
     utils::{
-        assert_program_error, get_or_create_associated_token_account, TestContext, DAYS_TO_CLOSE,
-        INVALID_ACCOUNT_DATA_ERROR, INVALID_ACCOUNT_OWNER_ERROR, INVALID_INSTRUCTION_DATA_ERROR,
-        INVALID_PAYMENT_STATUS_ERROR, NOT_ENOUGH_ACCOUNT_KEYS_ERROR, OPERATOR_OWNER_MISMATCH_ERROR,
-        REFUND_AMOUNT_EXCEEDS_POLICY_LIMIT_ERROR, REFUND_WINDOW_EXPIRED_ERROR, USDC_MINT,
-        USDT_MINT,
+        assert_program_error, find_escrow_authority_pda, get_or_create_associated_token_account,
+        TestContext, DAYS_TO_CLOSE, INVALID_ACCOUNT_DATA_ERROR, INVALID_ACCOUNT_OWNER_ERROR,
+        INVALID_ATA_ERROR, INVALID_PAYMENT_STATUS_ERROR, NOT_ENOUGH_ACCOUNT_KEYS_ERROR,
+        OPERATOR_OWNER_MISMATCH_ERROR, REFUND_AMOUNT_EXCEEDS_POLICY_LIMIT_ERROR,
+        REFUND_WINDOW_EXPIRED_ERROR, USDC_MINT, USDT_MINT,
     },
 };
 use commerce_program_client::{
+    discriminator::InstructionDiscriminator,
     instructions::RefundPaymentBuilder,
     types::{FeeType, PolicyData, RefundPolicy, SettlementPolicy},
 };
@@ -314,7 +315,8 @@ async fn test_refund_payment_invalid_signer_verification_fails() {
         .airdrop_if_required(&wrong_signer.pubkey(), 1_000_000_000)
         .unwrap();
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -326,6 +328,7 @@ async fn test_refund_payment_invalid_signer_verification_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -355,7 +358,8 @@ async fn test_refund_payment_unsigned_operator_authority_fails() {
         .airdrop_if_required(&non_signer.pubkey(), 1_000_000_000)
         .unwrap();
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -367,6 +371,7 @@ async fn test_refund_payment_unsigned_operator_authority_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -405,7 +410,8 @@ async fn test_refund_payment_invalid_payment_status_fails() {
     .expect("Should clear payment successfully first time");
 
     // Try to refund - should fail because payment is already cleared
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -417,6 +423,7 @@ async fn test_refund_payment_invalid_payment_status_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -443,7 +450,8 @@ async fn test_refund_payment_invalid_operator_fails() {
 
     let fake_operator = Keypair::new();
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -455,6 +463,7 @@ async fn test_refund_payment_invalid_operator_fails() {
         .operator(fake_operator.pubkey()) // Wrong operator
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -484,7 +493,8 @@ async fn test_refund_payment_invalid_merchant_fails() {
 
     let fake_merchant = Keypair::new();
 
-    let merchant_escrow_ata = get_associated_token_address(&fake_merchant.pubkey(), &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -496,6 +506,7 @@ async fn test_refund_payment_invalid_merchant_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -522,7 +533,8 @@ async fn test_refund_payment_invalid_merchant_operator_config_fails() {
 
     let fake_config = Keypair::new();
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&fake_config.pubkey(), &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -534,6 +546,7 @@ async fn test_refund_payment_invalid_merchant_operator_config_fails() {
         .operator(operator_pda)
         .merchant_operator_config(fake_config.pubkey()) // Wrong merchant operator config
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -564,7 +577,9 @@ async fn test_refund_payment_invalid_mint_pda_validation_fails() {
     // This will fail the payment PDA validation since mint is part of the PDA seeds
     let different_mint = USDT_MINT; // Valid mint, but different from the USDC_MINT used in payment creation
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &different_mint);
+    let escrow_authority =
+        find_escrow_authority_pda(&merchant_operator_config_pda, &different_mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &different_mint);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &different_mint);
 
     let instruction = RefundPaymentBuilder::new()
@@ -576,6 +591,7 @@ async fn test_refund_payment_invalid_mint_pda_validation_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(different_mint) // Different mint from payment creation
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -603,7 +619,8 @@ async fn test_refund_payment_invalid_payment_pda_fails() {
 
     let fake_payment = Keypair::new();
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -615,6 +632,7 @@ async fn test_refund_payment_invalid_payment_pda_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -655,6 +673,7 @@ async fn test_refund_payment_invalid_merchant_escrow_ata_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0)
         .merchant_escrow_ata(wrong_merchant_escrow_ata) // Wrong escrow ATA
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -662,7 +681,7 @@ async fn test_refund_payment_invalid_merchant_escrow_ata_fails() {
         .instruction();
 
     let result = context.send_transaction_with_signers(instruction, &[&operator_authority]);
-    assert_program_error(result, INVALID_INSTRUCTION_DATA_ERROR);
+    assert_program_error(result, INVALID_ATA_ERROR);
 }
 
 #[tokio::test]
@@ -704,7 +723,7 @@ async fn test_refund_payment_not_enough_account_keys_fails() {
     let instruction = Instruction {
         program_id: commerce_program_client::COMMERCE_PROGRAM_ID,
         accounts,
-        data: vec![5], // Refund payment instruction discriminator
+        data: vec![InstructionDiscriminator::RefundPayment.into()],
     };
 
     let result = context.send_transaction_with_signers(instruction, &[&operator_authority]);
@@ -731,7 +750,8 @@ async fn test_refund_payment_exceeds_max_amount_fails() {
         .await
         .unwrap();
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -743,6 +763,7 @@ async fn test_refund_payment_exceeds_max_amount_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -772,7 +793,8 @@ async fn test_refund_payment_window_expired_fails() {
     // Advance time by 1 hour (beyond the 30 minute window)
     context.advance_clock(3600); // 1 hour
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -784,6 +806,7 @@ async fn test_refund_payment_window_expired_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -813,7 +836,8 @@ async fn test_refund_payment_both_refund_policies_fails() {
     // Payment amount is 1 USDC, which fails the max_amount check
     // Don't advance time, so it would also fail if we got that far
 
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, &USDC_MINT);
+    let escrow_authority = find_escrow_authority_pda(&merchant_operator_config_pda, &USDC_MINT).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, &USDC_MINT);
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
 
     let instruction = RefundPaymentBuilder::new()
@@ -825,6 +849,7 @@ async fn test_refund_payment_both_refund_policies_fails() {
         .operator(operator_pda)
         .merchant_operator_config(merchant_operator_config_pda)
         .mint(USDC_MINT)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -0,0 +1,281 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_operator_pda, get_or_create_associated_token_account,
+        TestContext, DAYS_TO_CLOSE, INVALID_PAYMENT_STATUS_ERROR, PAYMENT_NOT_AUTO_SETTLED_ERROR,
+        REFUND_WINDOW_EXPIRED_ERROR, REFUND_WINDOW_REQUIRED_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::RefundSettledPaymentBuilder,
+    refund_delegate::{approve_refund_delegate, find_refund_delegate_pda, revoke_refund_delegate},
+    types::{FeeType, PolicyData, RefundPolicy, SettlementPolicy},
+};
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const REFUND_WINDOW_SECONDS: u64 = 86_400;
+
+struct RefundSettledTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Keypair,
+    buyer: Keypair,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a config, make one payment and optionally approve the refund
+// delegate for `allowance`
+fn setup_refund_settled_test(
+    auto_settle: bool,
+    refund_window_seconds: u64,
+    allowance: Option<u64>,
+) -> Result<RefundSettledTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let policies = vec![
+        PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle,
+        }),
+        PolicyData::Refund(RefundPolicy {
+            max_amount: 10_000_000,
+            max_time_after_purchase: refund_window_seconds,
+        }),
+    ];
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        0u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        1_000_000, // 1 USDC
+        true,      // fail_if_exists
+        auto_settle,
+        false,
+    )?;
+
+    if let Some(allowance) = allowance {
+        get_or_create_associated_token_account(
+            &mut context,
+            &settlement_wallet.pubkey(),
+            &USDC_MINT,
+        );
+        context.send_transaction_with_signers(
+            approve_refund_delegate(
+                &settlement_wallet.pubkey(),
+                &merchant_pda,
+                &USDC_MINT,
+                &TOKEN_PROGRAM_ID,
+                allowance,
+            ),
+            &[&settlement_wallet],
+        )?;
+    }
+
+    Ok(RefundSettledTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet,
+        buyer,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn refund_settled_payment_instruction(setup: &RefundSettledTestSetup) -> Instruction {
+    let (refund_delegate, refund_delegate_bump) = find_refund_delegate_pda(&setup.merchant_pda);
+
+    RefundSettledPaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(find_operator_pda(&setup.operator_authority.pubkey()).0)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet.pubkey(),
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .refund_delegate(refund_delegate)
+        .refund_delegate_bump(refund_delegate_bump)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction()
+}
+
+/*
+HAPPY PATH TESTS
+*/
+#[tokio::test]
+async fn test_refund_settled_payment_success() {
+    let mut setup =
+        setup_refund_settled_test(true, REFUND_WINDOW_SECONDS, Some(5_000_000)).unwrap();
+
+    assert_refund_settled_payment(
+        &mut setup.context,
+        &setup.operator_authority,
+        &setup.operator_authority,
+        &setup.buyer,
+        &setup.payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        true,
+    )
+    .expect("Should refund settled payment");
+
+    // Refunding the same payment again fails: it is no longer Cleared
+    setup.context.svm.expire_blockhash();
+    let instruction = refund_settled_payment_instruction(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&setup.operator_authority]);
+    assert_program_error(result, INVALID_PAYMENT_STATUS_ERROR);
+}
+
+/*
+SAD PATH TESTS
+*/
+#[tokio::test]
+async fn test_refund_settled_payment_not_auto_settled_fails() {
+    let mut setup = setup_refund_settled_test(false, REFUND_WINDOW_SECONDS, None).unwrap();
+
+    let instruction = refund_settled_payment_instruction(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&setup.operator_authority]);
+
+    assert_program_error(result, PAYMENT_NOT_AUTO_SETTLED_ERROR);
+}
+
+#[tokio::test]
+async fn test_refund_settled_payment_without_refund_window_fails() {
+    let mut setup = setup_refund_settled_test(true, 0, Some(5_000_000)).unwrap();
+
+    let instruction = refund_settled_payment_instruction(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&setup.operator_authority]);
+
+    assert_program_error(result, REFUND_WINDOW_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_refund_settled_payment_after_window_fails() {
+    let mut setup =
+        setup_refund_settled_test(true, REFUND_WINDOW_SECONDS, Some(5_000_000)).unwrap();
+
+    setup
+        .context
+        .advance_clock(REFUND_WINDOW_SECONDS as i64 + 1);
+
+    let instruction = refund_settled_payment_instruction(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&setup.operator_authority]);
+
+    assert_program_error(result, REFUND_WINDOW_EXPIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_refund_settled_payment_without_allowance_fails() {
+    let mut setup = setup_refund_settled_test(true, REFUND_WINDOW_SECONDS, None).unwrap();
+
+    let instruction = refund_settled_payment_instruction(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&setup.operator_authority]);
+
+    assert!(
+        result.is_err(),
+        "Refund without an approved delegate should fail"
+    );
+}
+
+#[tokio::test]
+async fn test_refund_settled_payment_insufficient_allowance_fails() {
+    let mut setup = setup_refund_settled_test(true, REFUND_WINDOW_SECONDS, Some(999_999)).unwrap();
+
+    let instruction = refund_settled_payment_instruction(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&setup.operator_authority]);
+
+    assert!(result.is_err(), "Refund above the allowance should fail");
+}
+
+#[tokio::test]
+async fn test_refund_settled_payment_after_revoke_fails() {
+    let mut setup =
+        setup_refund_settled_test(true, REFUND_WINDOW_SECONDS, Some(5_000_000)).unwrap();
+
+    let settlement_wallet = setup.settlement_wallet.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(
+            revoke_refund_delegate(&settlement_wallet.pubkey(), &USDC_MINT, &TOKEN_PROGRAM_ID),
+            &[&settlement_wallet],
+        )
+        .expect("Should revoke refund delegate");
+
+    let instruction = refund_settled_payment_instruction(&setup);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&setup.operator_authority]);
+
+    assert!(
+        result.is_err(),
+        "Refund after revoking the delegate should fail"
+    );
+}
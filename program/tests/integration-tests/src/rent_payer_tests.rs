@@ -0,0 +1,364 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_payment_closed_event_present, assert_program_error, find_escrow_ata,
+        find_escrow_authority_pda, find_payment_pda, get_or_create_associated_token_account,
+        idempotency_key, set_token_balance, TestContext, DAYS_TO_CLOSE,
+        MISSING_REQUIRED_SIGNATURE_ERROR, RENT_PAYER_MISMATCH_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{ClosePaymentBuilder, MakePaymentBuilder, SweepClosedPaymentsBuilder},
+    sweep::{sweep_remaining_accounts, SweptPayment},
+    types::{FeeType, PolicyData, SettlementPolicy},
+    Merchant, Payment,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+const ORDER_ID: u32 = 1;
+const AMOUNT: u64 = 1_000_000;
+const TREASURY_LAMPORTS: u64 = 1_000_000_000;
+const CLOSE_DELAY_SECONDS: i64 = (DAYS_TO_CLOSE as i64 + 1) * 24 * 60 * 60;
+
+struct RentPayerTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    treasury: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a config and a funded operator rent treasury
+fn setup_rent_payer_test() -> Result<RentPayerTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+    let treasury = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+    context.create_account(
+        &treasury.pubkey(),
+        &SYSTEM_PROGRAM_ID,
+        vec![],
+        TREASURY_LAMPORTS,
+    );
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        500,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Settlement(SettlementPolicy {
+            min_settlement_amount: 0,
+            settlement_frequency_hours: 0,
+            auto_settle: false,
+        })],
+        vec![USDC_MINT],
+        true,
+        false,
+    )?;
+
+    Ok(RentPayerTestSetup {
+        context,
+        operator_authority,
+        buyer,
+        treasury,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn make_payment_instruction(setup: &mut RentPayerTestSetup, treasury_signs: bool) -> Instruction {
+    let merchant_account = setup.context.get_account(&setup.merchant_pda).unwrap();
+    let settlement_wallet = Merchant::from_bytes(&merchant_account.data)
+        .unwrap()
+        .settlement_wallet;
+
+    let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut setup.context,
+        &buyer_ata,
+        &USDC_MINT,
+        &setup.buyer.pubkey(),
+        AMOUNT,
+    );
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+
+    MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(&settlement_wallet, &USDC_MINT))
+        .settlement_wallet(settlement_wallet)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .order_id(ORDER_ID)
+        .idempotency_key(idempotency_key(ORDER_ID))
+        .amount(AMOUNT)
+        .add_remaining_account(AccountMeta::new(setup.treasury.pubkey(), treasury_signs))
+        .instruction()
+}
+
+fn close_payment_instruction(
+    setup: &RentPayerTestSetup,
+    payment_pda: &Pubkey,
+    rent_payer: Option<&Pubkey>,
+) -> Instruction {
+    let mut builder = ClosePaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(*payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .buyer(setup.buyer.pubkey())
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .system_program(SYSTEM_PROGRAM_ID);
+
+    if let Some(rent_payer) = rent_payer {
+        builder.add_remaining_account(AccountMeta::new(*rent_payer, false));
+    }
+
+    builder.instruction()
+}
+
+fn lamports(context: &mut TestContext, pubkey: &Pubkey) -> u64 {
+    context.get_account(pubkey).map_or(0, |a| a.lamports)
+}
+
+// Makes a payment funded by the treasury, clears it and waits out the close window
+fn make_cleared_treasury_payment(setup: &mut RentPayerTestSetup) -> Pubkey {
+    let instruction = make_payment_instruction(setup, true);
+    let treasury = setup.treasury.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer, &treasury])
+        .expect("Make payment with rent treasury should succeed");
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    assert_clear_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )
+    .unwrap();
+
+    setup.context.advance_clock(CLOSE_DELAY_SECONDS);
+
+    payment_pda
+}
+
+#[tokio::test]
+async fn test_make_payment_rent_paid_by_treasury() {
+    let mut setup = setup_rent_payer_test().unwrap();
+
+    let instruction = make_payment_instruction(&mut setup, true);
+    let treasury = setup.treasury.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer, &treasury])
+        .expect("Make payment with rent treasury should succeed");
+
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &USDC_MINT,
+        ORDER_ID,
+    );
+    let payment_account = setup.context.get_account(&payment_pda).unwrap();
+    let payment = Payment::from_bytes(&payment_account.data).unwrap();
+    assert_eq!(payment.rent_payer, treasury.pubkey());
+
+    // The treasury paid exactly the payment's rent
+    assert_eq!(
+        lamports(&mut setup.context, &treasury.pubkey()),
+        TREASURY_LAMPORTS - payment_account.lamports
+    );
+}
+
+#[tokio::test]
+async fn test_make_payment_without_treasury_records_no_rent_payer() {
+    let mut setup = setup_rent_payer_test().unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &setup.merchant_operator_config_pda,
+        &setup.operator_pda,
+        &USDC_MINT,
+        ORDER_ID,
+        AMOUNT,
+        true,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let payment_account = setup.context.get_account(&payment_pda).unwrap();
+    let payment = Payment::from_bytes(&payment_account.data).unwrap();
+    assert_eq!(payment.rent_payer, Pubkey::default());
+}
+
+#[tokio::test]
+async fn test_make_payment_unsigned_treasury_fails() {
+    let mut setup = setup_rent_payer_test().unwrap();
+
+    let instruction = make_payment_instruction(&mut setup, false);
+    let buyer = setup.buyer.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&buyer]);
+    assert_program_error(result, MISSING_REQUIRED_SIGNATURE_ERROR);
+}
+
+#[tokio::test]
+async fn test_close_payment_refunds_rent_to_treasury() {
+    let mut setup = setup_rent_payer_test().unwrap();
+    let payment_pda = make_cleared_treasury_payment(&mut setup);
+    let treasury = setup.treasury.pubkey();
+
+    let rent = lamports(&mut setup.context, &payment_pda);
+    let treasury_before = lamports(&mut setup.context, &treasury);
+
+    let instruction = close_payment_instruction(&setup, &payment_pda, Some(&treasury));
+    let transaction_metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(instruction, &[], false)
+        .expect("Close payment should succeed");
+
+    assert_eq!(lamports(&mut setup.context, &payment_pda), 0);
+    assert_eq!(
+        lamports(&mut setup.context, &treasury),
+        treasury_before + rent
+    );
+
+    assert_payment_closed_event_present(
+        &transaction_metadata,
+        &setup.buyer.pubkey(),
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        ORDER_ID,
+        &treasury,
+    );
+}
+
+#[tokio::test]
+async fn test_close_payment_without_treasury_fails() {
+    let mut setup = setup_rent_payer_test().unwrap();
+    let payment_pda = make_cleared_treasury_payment(&mut setup);
+
+    let instruction = close_payment_instruction(&setup, &payment_pda, None);
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, RENT_PAYER_MISMATCH_ERROR);
+
+    let other = Pubkey::new_unique();
+    let instruction = close_payment_instruction(&setup, &payment_pda, Some(&other));
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, RENT_PAYER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_sweep_treasury_payment_to_other_collector_fails() {
+    let mut setup = setup_rent_payer_test().unwrap();
+    let payment_pda = make_cleared_treasury_payment(&mut setup);
+
+    let swept = [SweptPayment {
+        payment: payment_pda,
+        buyer: setup.buyer.pubkey(),
+        mint: USDC_MINT,
+    }];
+    let sweep = |rent_collector: Pubkey| {
+        SweepClosedPaymentsBuilder::new()
+            .payer(setup.context.payer.pubkey())
+            .operator_authority(setup.operator_authority.pubkey())
+            .operator(setup.operator_pda)
+            .merchant(setup.merchant_pda)
+            .merchant_operator_config(setup.merchant_operator_config_pda)
+            .rent_collector(rent_collector)
+            .num_payments(1)
+            .add_remaining_accounts(&sweep_remaining_accounts(&swept))
+            .instruction()
+    };
+
+    let treasury = setup.treasury.pubkey();
+    let wrong_collector_instruction = sweep(Pubkey::new_unique());
+    let treasury_instruction = sweep(treasury);
+
+    let result = setup.context.send_transaction(wrong_collector_instruction);
+    assert_program_error(result, RENT_PAYER_MISMATCH_ERROR);
+
+    setup
+        .context
+        .send_transaction(treasury_instruction)
+        .expect("Sweep to the rent treasury should succeed");
+
+    // The treasury got back the rent it paid
+    assert_eq!(lamports(&mut setup.context, &treasury), TREASURY_LAMPORTS);
+}
@@ -0,0 +1,232 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, TestContext, TestContextBuilder, DAYS_TO_CLOSE,
+        INVALID_ACCOUNT_DATA_ERROR, INVALID_RENT_REBATE_POLICY_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{ClosePaymentBuilder, SweepClosedPaymentsBuilder},
+    sweep::{sweep_remaining_accounts, SweptPayment},
+    types::{FeeType, PolicyData, RentRebatePolicy},
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const CLOSE_DELAY_SECONDS: i64 = (DAYS_TO_CLOSE as i64 + 1) * 24 * 60 * 60;
+// A quarter of the rent goes to the buyer
+const BUYER_SHARE_BPS: u16 = 2_500;
+
+struct RentRebateTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a config with a rent rebate policy and one cleared payment that
+// can be closed
+fn setup_rent_rebate_test() -> Result<RentRebateTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        100u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::RentRebate(RentRebatePolicy {
+            buyer_share_bps: BUYER_SHARE_BPS,
+        })],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        1_000_000,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+
+    assert_clear_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &payment_pda,
+        &USDC_MINT,
+        &merchant_operator_config_pda,
+        false,
+    )?;
+
+    context.advance_clock(CLOSE_DELAY_SECONDS);
+
+    Ok(RentRebateTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+fn close_payment_instruction(setup: &RentRebateTestSetup) -> Instruction {
+    ClosePaymentBuilder::new()
+        .payer(setup.operator_authority.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .buyer(setup.buyer.pubkey())
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .instruction()
+}
+
+fn lamports(context: &mut TestContext, address: &Pubkey) -> u64 {
+    context.get_account(address).map_or(0, |a| a.lamports)
+}
+
+#[tokio::test]
+async fn test_close_payment_rebates_rent_to_buyer() {
+    let mut setup = setup_rent_rebate_test().unwrap();
+    let rent = lamports(&mut setup.context, &setup.payment_pda);
+    let buyer_before = lamports(&mut setup.context, &setup.buyer.pubkey());
+
+    let instruction = close_payment_instruction(&setup);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Close payment should succeed");
+
+    assert_eq!(lamports(&mut setup.context, &setup.payment_pda), 0);
+    assert_eq!(
+        lamports(&mut setup.context, &setup.buyer.pubkey()),
+        buyer_before + rent * u64::from(BUYER_SHARE_BPS) / 10_000
+    );
+}
+
+#[tokio::test]
+async fn test_close_payment_readonly_buyer_fails() {
+    let mut setup = setup_rent_rebate_test().unwrap();
+
+    let mut instruction = close_payment_instruction(&setup);
+    instruction.accounts[5].is_writable = false;
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+    assert_program_error(result, INVALID_ACCOUNT_DATA_ERROR);
+}
+
+#[tokio::test]
+async fn test_sweep_closed_payments_rebates_rent_to_buyer() {
+    let mut setup = setup_rent_rebate_test().unwrap();
+    let rent = lamports(&mut setup.context, &setup.payment_pda);
+    let buyer_before = lamports(&mut setup.context, &setup.buyer.pubkey());
+    let rent_collector = Pubkey::new_unique();
+    setup
+        .context
+        .airdrop_if_required(&rent_collector, 1_000_000_000)
+        .unwrap();
+    let collector_before = lamports(&mut setup.context, &rent_collector);
+
+    let swept = SweptPayment {
+        payment: setup.payment_pda,
+        buyer: setup.buyer.pubkey(),
+        mint: USDC_MINT,
+    };
+    let instruction = SweepClosedPaymentsBuilder::new()
+        .payer(setup.operator_authority.pubkey())
+        .operator_authority(setup.operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .rent_collector(rent_collector)
+        .num_payments(1)
+        .add_remaining_accounts(&sweep_remaining_accounts(&[swept]))
+        .instruction();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Sweep should succeed");
+
+    let rebate = rent * u64::from(BUYER_SHARE_BPS) / 10_000;
+    assert_eq!(lamports(&mut setup.context, &setup.payment_pda), 0);
+    assert_eq!(
+        lamports(&mut setup.context, &setup.buyer.pubkey()),
+        buyer_before + rebate
+    );
+    assert_eq!(
+        lamports(&mut setup.context, &rent_collector),
+        collector_before + rent - rebate
+    );
+}
+
+#[tokio::test]
+async fn test_initialize_config_invalid_rent_rebate_policy_fails() {
+    let mut setup = setup_rent_rebate_test().unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    // Over 100%
+    let policy = PolicyData::RentRebate(RentRebatePolicy {
+        buyer_share_bps: 10_001,
+    });
+
+    let result = assert_get_or_create_merchant_operator_config(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        2u32,
+        100u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![policy],
+        vec![USDC_MINT],
+        true,
+        false,
+    )
+    .map(|_| ());
+
+    assert_program_error(result, INVALID_RENT_REBATE_POLICY_ERROR);
+}
@@ -0,0 +1,442 @@
+use crate::{
+    assertions::assert_risk_state_account,
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda, find_payment_pda,
+        find_risk_state_pda, set_token_balance, TestContext, TestContextBuilder,
+        BUYER_DAILY_PAYMENT_LIMIT_EXCEEDED_ERROR, DAILY_VOLUME_LIMIT_EXCEEDED_ERROR, DAYS_TO_CLOSE,
+        INVALID_RISK_POLICY_ERROR, PAYMENT_AMOUNT_EXCEEDS_RISK_LIMIT_ERROR,
+        RISK_STATE_MISMATCH_ERROR, RISK_STATE_REQUIRED_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    accounts::RiskState,
+    instructions::{CapturePaymentBuilder, MakePaymentBuilder},
+    risk::risk_state_accounts,
+    types::{FeeType, PolicyData, RiskPolicy},
+};
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+struct RiskTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    policy: RiskPolicy,
+    next_order_id: u32,
+}
+
+// Helper function to set up a config with the given risk policy, its RiskState accounts and
+// a funded buyer
+fn setup_risk_test(policy: RiskPolicy) -> Result<RiskTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Risk(policy.clone())],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    assert_initialize_risk_state(
+        &mut context,
+        &merchant_operator_config_pda,
+        &Pubkey::default(),
+        true,
+    )?;
+    assert_initialize_risk_state(
+        &mut context,
+        &merchant_operator_config_pda,
+        &buyer.pubkey(),
+        true,
+    )?;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &buyer_ata,
+        &USDC_MINT,
+        &buyer.pubkey(),
+        100 * PAYMENT_AMOUNT,
+    );
+
+    Ok(RiskTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        policy,
+        next_order_id: 1,
+    })
+}
+
+fn risk_policy(
+    max_payment_amount: u64,
+    max_daily_volume: u64,
+    max_payments_per_buyer_per_day: u32,
+) -> RiskPolicy {
+    RiskPolicy {
+        max_payment_amount,
+        max_daily_volume,
+        max_payments_per_buyer_per_day,
+    }
+}
+
+fn send_make_payment_with_accounts(
+    setup: &mut RiskTestSetup,
+    amount: u64,
+    risk_states: &[AccountMeta],
+) -> Result<(), Box<dyn std::error::Error>> {
+    send_payment(setup, amount, false, risk_states).map(|_| ())
+}
+
+// Makes a payment, or only places a hold if `authorize`, returning its PDA
+fn send_payment(
+    setup: &mut RiskTestSetup,
+    amount: u64,
+    authorize: bool,
+    risk_states: &[AccountMeta],
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let order_id = setup.next_order_id;
+    let (payment_pda, _) = find_payment_pda(
+        &setup.merchant_operator_config_pda,
+        &setup.buyer.pubkey(),
+        &USDC_MINT,
+        order_id,
+    );
+
+    let instruction = MakePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .settlement_wallet(setup.settlement_wallet)
+        .order_id(order_id)
+        .amount(amount)
+        .idempotency_key([0; 16])
+        .authorize(authorize)
+        .add_remaining_accounts(risk_states)
+        .instruction();
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])?;
+    setup.next_order_id += 1;
+    Ok(payment_pda)
+}
+
+fn send_capture(
+    setup: &mut RiskTestSetup,
+    payment_pda: &Pubkey,
+    amount: u64,
+    risk_states: &[AccountMeta],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction = CapturePaymentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .payment(*payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .buyer_ata(get_associated_token_address(
+            &setup.buyer.pubkey(),
+            &USDC_MINT,
+        ))
+        .amount(amount)
+        .add_remaining_accounts(risk_states)
+        .instruction();
+
+    setup.context.send_transaction(instruction)
+}
+
+// Returns the config-wide and the buyer's RiskState accounts
+fn risk_states(setup: &mut RiskTestSetup) -> (RiskState, RiskState) {
+    let config = setup.merchant_operator_config_pda;
+    let buyer = setup.buyer.pubkey();
+    let (config_wide_pda, _) = find_risk_state_pda(&config, &Pubkey::default());
+    let (buyer_pda, _) = find_risk_state_pda(&config, &buyer);
+    (
+        assert_risk_state_account(
+            &mut setup.context,
+            &config_wide_pda,
+            &config,
+            &Pubkey::default(),
+        ),
+        assert_risk_state_account(&mut setup.context, &buyer_pda, &config, &buyer),
+    )
+}
+
+fn send_make_payment(
+    setup: &mut RiskTestSetup,
+    amount: u64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let risk_states = risk_state_accounts(
+        &setup.merchant_operator_config_pda,
+        &setup.policy,
+        &setup.buyer.pubkey(),
+    );
+    send_make_payment_with_accounts(setup, amount, &risk_states)
+}
+
+#[tokio::test]
+async fn test_payments_within_risk_limits_are_counted() {
+    let mut setup = setup_risk_test(risk_policy(PAYMENT_AMOUNT, 3 * PAYMENT_AMOUNT, 3)).unwrap();
+
+    send_make_payment(&mut setup, PAYMENT_AMOUNT).expect("First payment should succeed");
+    send_make_payment(&mut setup, PAYMENT_AMOUNT / 2).expect("Second payment should succeed");
+
+    let config = setup.merchant_operator_config_pda;
+    let (config_wide_pda, _) = find_risk_state_pda(&config, &Pubkey::default());
+    let risk_state = assert_risk_state_account(
+        &mut setup.context,
+        &config_wide_pda,
+        &config,
+        &Pubkey::default(),
+    );
+    assert_eq!(risk_state.daily_volume, PAYMENT_AMOUNT + PAYMENT_AMOUNT / 2);
+    assert_eq!(risk_state.daily_payment_count, 2);
+
+    let buyer = setup.buyer.pubkey();
+    let (buyer_pda, _) = find_risk_state_pda(&config, &buyer);
+    let risk_state = assert_risk_state_account(&mut setup.context, &buyer_pda, &config, &buyer);
+    assert_eq!(risk_state.daily_payment_count, 2);
+}
+
+#[tokio::test]
+async fn test_partial_capture_releases_risk_volume() {
+    let mut setup = setup_risk_test(risk_policy(0, 2 * PAYMENT_AMOUNT, 3)).unwrap();
+    let accounts = risk_state_accounts(
+        &setup.merchant_operator_config_pda,
+        &setup.policy,
+        &setup.buyer.pubkey(),
+    );
+
+    let payment_pda = send_payment(&mut setup, PAYMENT_AMOUNT, true, &accounts).unwrap();
+    send_capture(&mut setup, &payment_pda, PAYMENT_AMOUNT / 4, &accounts)
+        .expect("Partial capture should succeed");
+
+    // Only the captured amount counts, and the payment still does
+    let (config_wide, buyer) = risk_states(&mut setup);
+    assert_eq!(config_wide.daily_volume, PAYMENT_AMOUNT / 4);
+    assert_eq!(config_wide.daily_payment_count, 1);
+    assert_eq!(buyer.daily_payment_count, 1);
+
+    // The released volume is available again
+    send_make_payment(&mut setup, 2 * PAYMENT_AMOUNT - PAYMENT_AMOUNT / 4)
+        .expect("Payment up to the daily volume should succeed");
+}
+
+#[tokio::test]
+async fn test_void_releases_risk_hold() {
+    let mut setup = setup_risk_test(risk_policy(0, 2 * PAYMENT_AMOUNT, 1)).unwrap();
+    let accounts = risk_state_accounts(
+        &setup.merchant_operator_config_pda,
+        &setup.policy,
+        &setup.buyer.pubkey(),
+    );
+
+    let payment_pda = send_payment(&mut setup, PAYMENT_AMOUNT, true, &accounts).unwrap();
+    send_capture(&mut setup, &payment_pda, 0, &accounts).expect("Void should succeed");
+
+    // Neither the amount nor the payment counts any more
+    let (config_wide, buyer) = risk_states(&mut setup);
+    assert_eq!(config_wide.daily_volume, 0);
+    assert_eq!(config_wide.daily_payment_count, 0);
+    assert_eq!(buyer.daily_volume, 0);
+    assert_eq!(buyer.daily_payment_count, 0);
+
+    // The buyer's one payment of the day is available again
+    send_make_payment(&mut setup, PAYMENT_AMOUNT).expect("Payment after a void should succeed");
+}
+
+#[tokio::test]
+async fn test_capture_without_risk_states_fails() {
+    let mut setup = setup_risk_test(risk_policy(0, 2 * PAYMENT_AMOUNT, 0)).unwrap();
+    let accounts = risk_state_accounts(
+        &setup.merchant_operator_config_pda,
+        &setup.policy,
+        &setup.buyer.pubkey(),
+    );
+
+    let payment_pda = send_payment(&mut setup, PAYMENT_AMOUNT, true, &accounts).unwrap();
+    let result = send_capture(&mut setup, &payment_pda, PAYMENT_AMOUNT / 2, &[]);
+    assert_program_error(result, RISK_STATE_REQUIRED_ERROR);
+
+    // Capturing the whole hold releases nothing and needs no RiskState
+    send_capture(&mut setup, &payment_pda, PAYMENT_AMOUNT, &[])
+        .expect("Full capture should succeed");
+}
+
+#[tokio::test]
+async fn test_payment_above_max_amount_fails() {
+    let mut setup = setup_risk_test(risk_policy(PAYMENT_AMOUNT, 0, 0)).unwrap();
+
+    let result = send_make_payment(&mut setup, PAYMENT_AMOUNT + 1);
+    assert_program_error(result, PAYMENT_AMOUNT_EXCEEDS_RISK_LIMIT_ERROR);
+
+    send_make_payment(&mut setup, PAYMENT_AMOUNT).expect("Payment at the limit should succeed");
+}
+
+#[tokio::test]
+async fn test_daily_volume_limit() {
+    let mut setup = setup_risk_test(risk_policy(0, 2 * PAYMENT_AMOUNT, 0)).unwrap();
+
+    send_make_payment(&mut setup, PAYMENT_AMOUNT).unwrap();
+    send_make_payment(&mut setup, PAYMENT_AMOUNT).unwrap();
+
+    let result = send_make_payment(&mut setup, 1);
+    assert_program_error(result, DAILY_VOLUME_LIMIT_EXCEEDED_ERROR);
+
+    // The counters start over the next day
+    setup.context.advance_clock(SECONDS_PER_DAY);
+    send_make_payment(&mut setup, PAYMENT_AMOUNT).expect("Volume should reset the next day");
+}
+
+#[tokio::test]
+async fn test_buyer_daily_payment_limit() {
+    let mut setup = setup_risk_test(risk_policy(0, 0, 2)).unwrap();
+
+    send_make_payment(&mut setup, PAYMENT_AMOUNT).unwrap();
+    send_make_payment(&mut setup, PAYMENT_AMOUNT).unwrap();
+
+    let result = send_make_payment(&mut setup, PAYMENT_AMOUNT);
+    assert_program_error(result, BUYER_DAILY_PAYMENT_LIMIT_EXCEEDED_ERROR);
+
+    setup.context.advance_clock(SECONDS_PER_DAY);
+    send_make_payment(&mut setup, PAYMENT_AMOUNT).expect("Count should reset the next day");
+}
+
+#[tokio::test]
+async fn test_missing_risk_state_fails() {
+    let mut setup = setup_risk_test(risk_policy(0, 2 * PAYMENT_AMOUNT, 2)).unwrap();
+
+    // Only the config-wide account, without the buyer's
+    let (config_wide_pda, _) =
+        find_risk_state_pda(&setup.merchant_operator_config_pda, &Pubkey::default());
+    let result = send_make_payment_with_accounts(
+        &mut setup,
+        PAYMENT_AMOUNT,
+        &[AccountMeta::new(config_wide_pda, false)],
+    );
+    assert_program_error(result, RISK_STATE_REQUIRED_ERROR);
+}
+
+#[tokio::test]
+async fn test_other_buyer_risk_state_fails() {
+    let mut setup = setup_risk_test(risk_policy(0, 0, 2)).unwrap();
+
+    let other_buyer = Pubkey::new_unique();
+    let config = setup.merchant_operator_config_pda;
+    assert_initialize_risk_state(&mut setup.context, &config, &other_buyer, true).unwrap();
+
+    let (other_pda, _) = find_risk_state_pda(&config, &other_buyer);
+    let result = send_make_payment_with_accounts(
+        &mut setup,
+        PAYMENT_AMOUNT,
+        &[AccountMeta::new(other_pda, false)],
+    );
+    assert_program_error(result, RISK_STATE_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_risk_policy_without_limits_fails() {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let result = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Risk(risk_policy(0, 0, 0))],
+        vec![USDC_MINT],
+        true,
+        false,
+    )
+    .map(|_| ());
+    assert_program_error(result, INVALID_RISK_POLICY_ERROR);
+}
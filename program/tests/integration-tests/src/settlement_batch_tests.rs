@@ -0,0 +1,313 @@
+use crate::{
+    assertions::assert_settlement_batch_account,
+    state_utils::*,
+    utils::{
+        assert_program_error, assert_settlement_batch_executed_event_present, find_escrow_ata,
+        find_escrow_authority_pda, get_or_create_associated_token_account, get_token_balance,
+        TestContext, TestContextBuilder, DAYS_TO_CLOSE, INSUFFICIENT_SETTLEMENT_AMOUNT_ERROR,
+        SETTLEMENT_BATCH_EMPTY_ERROR, SETTLEMENT_TOO_EARLY_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::{ClearPaymentBuilder, ExecuteSettlementBatchBuilder},
+    types::{FeeType, PolicyData, SettlementPolicy},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+// 5% operator fee
+const MERCHANT_AMOUNT: u64 = PAYMENT_AMOUNT - PAYMENT_AMOUNT / 20;
+const SETTLEMENT_FREQUENCY_HOURS: u32 = 24;
+
+struct SettlementBatchTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    settlement_batch_pda: Pubkey,
+    payment_pdas: Vec<Pubkey>,
+}
+
+// Helper function to set up a config that settles at most daily and at least 1.5 payments'
+// worth at a time, its settlement batch and `num_payments` paid payments
+fn setup_settlement_batch_test(
+    num_payments: u32,
+) -> Result<SettlementBatchTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let policies = vec![PolicyData::Settlement(SettlementPolicy {
+        min_settlement_amount: PAYMENT_AMOUNT * 3 / 2,
+        settlement_frequency_hours: SETTLEMENT_FREQUENCY_HOURS,
+        auto_settle: false,
+    })];
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        policies,
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (settlement_batch_pda, _) = assert_initialize_settlement_batch(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &merchant_operator_config_pda,
+        &USDC_MINT,
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let mut payment_pdas = Vec::new();
+    for order_id in 1..=num_payments {
+        let (payment_pda, _) = assert_make_payment(
+            &mut context,
+            &operator_authority,
+            &operator_authority,
+            &buyer,
+            &merchant_operator_config_pda,
+            &operator_pda,
+            &USDC_MINT,
+            order_id,
+            PAYMENT_AMOUNT,
+            true,  // fail_if_exists
+            false, // is_auto_settle
+            false,
+        )?;
+        payment_pdas.push(payment_pda);
+    }
+
+    get_or_create_associated_token_account(&mut context, &settlement_wallet.pubkey(), &USDC_MINT);
+    get_or_create_associated_token_account(&mut context, &operator_authority.pubkey(), &USDC_MINT);
+
+    Ok(SettlementBatchTestSetup {
+        context,
+        operator_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        settlement_batch_pda,
+        payment_pdas,
+    })
+}
+
+fn clear_payment_instruction(
+    setup: &SettlementBatchTestSetup,
+    payment_pda: Pubkey,
+    with_settlement_batch: bool,
+) -> Instruction {
+    let mut builder = ClearPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ));
+    if with_settlement_batch {
+        builder.add_remaining_account(AccountMeta::new(setup.settlement_batch_pda, false));
+    }
+    builder.instruction()
+}
+
+fn clear_into_batch(
+    setup: &mut SettlementBatchTestSetup,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let operator_authority = setup.operator_authority.insecure_clone();
+    for payment_pda in setup.payment_pdas.clone() {
+        let instruction = clear_payment_instruction(setup, payment_pda, true);
+        setup
+            .context
+            .send_transaction_with_signers(instruction, &[&operator_authority])?;
+    }
+    Ok(())
+}
+
+fn execute_settlement_batch_instruction(setup: &SettlementBatchTestSetup) -> Instruction {
+    ExecuteSettlementBatchBuilder::new()
+        .settlement_batch(setup.settlement_batch_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .instruction()
+}
+
+fn settlement_balance(setup: &mut SettlementBatchTestSetup) -> u64 {
+    let settlement_ata = get_associated_token_address(&setup.settlement_wallet, &USDC_MINT);
+    get_token_balance(&mut setup.context, &settlement_ata)
+}
+
+#[tokio::test]
+async fn test_clear_payment_into_settlement_batch() {
+    let mut setup = setup_settlement_batch_test(2).unwrap();
+    let escrow_ata = get_associated_token_address(&setup.merchant_pda, &USDC_MINT);
+
+    clear_into_batch(&mut setup).unwrap();
+
+    // Merchant amounts stay in escrow; the operator fees are paid out immediately
+    assert_eq!(settlement_balance(&mut setup), 0);
+    assert_eq!(
+        get_token_balance(&mut setup.context, &escrow_ata),
+        2 * MERCHANT_AMOUNT
+    );
+    let operator_ata = get_associated_token_address(&setup.operator_authority.pubkey(), &USDC_MINT);
+    assert_eq!(
+        get_token_balance(&mut setup.context, &operator_ata),
+        2 * (PAYMENT_AMOUNT - MERCHANT_AMOUNT)
+    );
+
+    let config_pda = setup.merchant_operator_config_pda;
+    let settlement_batch = assert_settlement_batch_account(
+        &mut setup.context,
+        &setup.settlement_batch_pda,
+        &config_pda,
+        &USDC_MINT,
+    );
+    assert_eq!(settlement_batch.pending_amount, 2 * MERCHANT_AMOUNT);
+    assert_eq!(settlement_batch.pending_count, 2);
+}
+
+#[tokio::test]
+async fn test_execute_settlement_batch_after_frequency() {
+    let mut setup = setup_settlement_batch_test(2).unwrap();
+    clear_into_batch(&mut setup).unwrap();
+
+    let instruction = execute_settlement_batch_instruction(&setup);
+    let result = setup.context.send_transaction(instruction);
+    assert_program_error(result, SETTLEMENT_TOO_EARLY_ERROR);
+
+    setup
+        .context
+        .advance_clock(SETTLEMENT_FREQUENCY_HOURS as i64 * 3600);
+
+    // Anyone may execute the batch, so only the fee payer signs
+    let instruction = execute_settlement_batch_instruction(&setup);
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(instruction, &[], false)
+        .unwrap();
+    assert_settlement_batch_executed_event_present(
+        &metadata,
+        &setup.merchant_pda,
+        &setup.merchant_operator_config_pda,
+        &USDC_MINT,
+        2 * MERCHANT_AMOUNT,
+        2,
+    );
+
+    assert_eq!(settlement_balance(&mut setup), 2 * MERCHANT_AMOUNT);
+
+    let config_pda = setup.merchant_operator_config_pda;
+    let settlement_batch = assert_settlement_batch_account(
+        &mut setup.context,
+        &setup.settlement_batch_pda,
+        &config_pda,
+        &USDC_MINT,
+    );
+    assert_eq!(settlement_batch.pending_amount, 0);
+    assert_eq!(settlement_batch.pending_count, 0);
+}
+
+#[tokio::test]
+async fn test_execute_settlement_batch_below_min_amount_fails() {
+    let mut setup = setup_settlement_batch_test(1).unwrap();
+    clear_into_batch(&mut setup).unwrap();
+    setup
+        .context
+        .advance_clock(SETTLEMENT_FREQUENCY_HOURS as i64 * 3600);
+
+    let instruction = execute_settlement_batch_instruction(&setup);
+    let result = setup.context.send_transaction(instruction);
+
+    assert_program_error(result, INSUFFICIENT_SETTLEMENT_AMOUNT_ERROR);
+}
+
+#[tokio::test]
+async fn test_execute_empty_settlement_batch_fails() {
+    let mut setup = setup_settlement_batch_test(0).unwrap();
+    setup
+        .context
+        .advance_clock(SETTLEMENT_FREQUENCY_HOURS as i64 * 3600);
+
+    let instruction = execute_settlement_batch_instruction(&setup);
+    let result = setup.context.send_transaction(instruction);
+
+    assert_program_error(result, SETTLEMENT_BATCH_EMPTY_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_without_batch_is_policy_gated() {
+    let mut setup = setup_settlement_batch_test(1).unwrap();
+
+    let instruction = clear_payment_instruction(&setup, setup.payment_pdas[0], false);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, INSUFFICIENT_SETTLEMENT_AMOUNT_ERROR);
+}
@@ -0,0 +1,191 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, get_token_balance, TestContext, TestContextBuilder, DAYS_TO_CLOSE,
+        INVALID_SETTLEMENT_DISCOUNT_POLICY_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::types::{FeeType, PolicyData, SettlementDiscountPolicy};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+// 2% operator fee
+const OPERATOR_FEE: u64 = PAYMENT_AMOUNT / 50;
+
+struct SettlementDiscountTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// 10% of the operator fee off per full day in escrow, up to half the fee
+fn settlement_discount_policy() -> PolicyData {
+    PolicyData::SettlementDiscount(SettlementDiscountPolicy {
+        discount_bps_per_day: 1_000,
+        max_discount_bps: 5_000,
+    })
+}
+
+// Helper function to set up a config with a 2% operator fee and a settlement discount, with
+// one paid payment
+fn setup_settlement_discount_test(
+) -> Result<SettlementDiscountTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        200u64, // 2%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![settlement_discount_policy()],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        PAYMENT_AMOUNT,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+
+    Ok(SettlementDiscountTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+// Clears the payment after `days` in escrow, returning the operator fee and merchant amount
+fn clear_payment_after(
+    setup: &mut SettlementDiscountTestSetup,
+    days: i64,
+) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    setup.context.advance_clock(days * SECONDS_PER_DAY);
+
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let buyer = setup.buyer.insecure_clone();
+    assert_clear_payment(
+        &mut setup.context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &setup.payment_pda,
+        &USDC_MINT,
+        &setup.merchant_operator_config_pda,
+        false,
+    )?;
+
+    let operator_ata = get_associated_token_address(&operator_authority.pubkey(), &USDC_MINT);
+    let merchant_ata = get_associated_token_address(&setup.settlement_wallet, &USDC_MINT);
+    Ok((
+        get_token_balance(&mut setup.context, &operator_ata),
+        get_token_balance(&mut setup.context, &merchant_ata),
+    ))
+}
+
+#[tokio::test]
+async fn test_clear_payment_same_day_no_discount() {
+    let mut setup = setup_settlement_discount_test().unwrap();
+
+    let (operator_fee, merchant_amount) = clear_payment_after(&mut setup, 0).unwrap();
+
+    assert_eq!(operator_fee, OPERATOR_FEE);
+    assert_eq!(merchant_amount, PAYMENT_AMOUNT - OPERATOR_FEE);
+}
+
+#[tokio::test]
+async fn test_clear_payment_discounted_per_day_held() {
+    let mut setup = setup_settlement_discount_test().unwrap();
+
+    // Two full days take 20% off the operator fee, which goes to the merchant
+    let (operator_fee, merchant_amount) = clear_payment_after(&mut setup, 2).unwrap();
+
+    assert_eq!(operator_fee, OPERATOR_FEE * 8 / 10);
+    assert_eq!(merchant_amount, PAYMENT_AMOUNT - OPERATOR_FEE * 8 / 10);
+}
+
+#[tokio::test]
+async fn test_clear_payment_discount_capped() {
+    let mut setup = setup_settlement_discount_test().unwrap();
+
+    let (operator_fee, merchant_amount) = clear_payment_after(&mut setup, 30).unwrap();
+
+    assert_eq!(operator_fee, OPERATOR_FEE / 2);
+    assert_eq!(merchant_amount, PAYMENT_AMOUNT - OPERATOR_FEE / 2);
+}
+
+#[tokio::test]
+async fn test_initialize_config_invalid_settlement_discount_policy_fails() {
+    let mut setup = setup_settlement_discount_test().unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    // Over 100%
+    let policy = PolicyData::SettlementDiscount(SettlementDiscountPolicy {
+        discount_bps_per_day: 100,
+        max_discount_bps: 10_001,
+    });
+
+    let result = assert_get_or_create_merchant_operator_config(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        2u32,
+        200u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![policy],
+        vec![USDC_MINT],
+        true,
+        false,
+    )
+    .map(|_| ());
+
+    assert_program_error(result, INVALID_SETTLEMENT_DISCOUNT_POLICY_ERROR);
+}
@@ -0,0 +1,293 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_ata, find_escrow_authority_pda,
+        get_or_create_associated_token_account, get_token_balance, TestContext, TestContextBuilder,
+        DAYS_TO_CLOSE, INVALID_SPLIT_SETTLEMENT_DESTINATIONS_ERROR,
+        INVALID_SPLIT_SETTLEMENT_POLICY_ERROR, NOT_ENOUGH_ACCOUNT_KEYS_ERROR,
+        SPLIT_SETTLEMENT_UNSUPPORTED_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::ClearPaymentBuilder,
+    split_settlement::SplitSettlement,
+    types::{FeeType, PolicyData},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+// 5% operator fee
+const MERCHANT_AMOUNT: u64 = PAYMENT_AMOUNT - PAYMENT_AMOUNT / 20;
+
+struct SplitSettlementTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    merchant_authority: Keypair,
+    settlement_wallet: Pubkey,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    treasury: Pubkey,
+    tax_escrow: Pubkey,
+    payment_pda: Pubkey,
+}
+
+impl SplitSettlementTestSetup {
+    // 90% to the treasury, 10% to the tax escrow
+    fn split(&self) -> SplitSettlement {
+        SplitSettlement::new([(self.treasury, 9_000), (self.tax_escrow, 1_000)])
+    }
+}
+
+// Helper function to set up a config splitting settlement 90/10 between a treasury and a tax
+// escrow, with one paid payment
+fn setup_split_settlement_test() -> Result<SplitSettlementTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+    let treasury = Pubkey::new_unique();
+    let tax_escrow = Pubkey::new_unique();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let split = SplitSettlement::new([(treasury, 9_000), (tax_escrow, 1_000)]);
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::SplitSettlement(split.policy())],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        PAYMENT_AMOUNT,
+        true,  // fail_if_exists
+        false, // is_auto_settle
+        false,
+    )?;
+
+    for wallet in [
+        settlement_wallet.pubkey(),
+        operator_authority.pubkey(),
+        treasury,
+        tax_escrow,
+    ] {
+        get_or_create_associated_token_account(&mut context, &wallet, &USDC_MINT);
+    }
+
+    Ok(SplitSettlementTestSetup {
+        context,
+        operator_authority,
+        merchant_authority,
+        settlement_wallet: settlement_wallet.pubkey(),
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        treasury,
+        tax_escrow,
+        payment_pda,
+    })
+}
+
+fn clear_payment_instruction(
+    setup: &SplitSettlementTestSetup,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut builder = ClearPaymentBuilder::new();
+    builder
+        .payer(setup.context.payer.pubkey())
+        .payment(setup.payment_pda)
+        .operator_authority(setup.operator_authority.pubkey())
+        .buyer(setup.buyer.pubkey())
+        .merchant(setup.merchant_pda)
+        .operator(setup.operator_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .mint(USDC_MINT)
+        .escrow_authority(
+            find_escrow_authority_pda(&setup.merchant_operator_config_pda, &USDC_MINT).0,
+        )
+        .merchant_escrow_ata(find_escrow_ata(
+            &setup.merchant_operator_config_pda,
+            &USDC_MINT,
+        ))
+        .merchant_settlement_ata(get_associated_token_address(
+            &setup.settlement_wallet,
+            &USDC_MINT,
+        ))
+        .operator_settlement_ata(get_associated_token_address(
+            &setup.operator_authority.pubkey(),
+            &USDC_MINT,
+        ))
+        .add_remaining_accounts(&remaining_accounts);
+    builder.instruction()
+}
+
+fn clear_payment(
+    setup: &mut SplitSettlementTestSetup,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction = clear_payment_instruction(setup, remaining_accounts);
+    let operator_authority = setup.operator_authority.insecure_clone();
+    setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority])?;
+    Ok(())
+}
+
+fn balance(setup: &mut SplitSettlementTestSetup, wallet: &Pubkey) -> u64 {
+    let ata = get_associated_token_address(wallet, &USDC_MINT);
+    get_token_balance(&mut setup.context, &ata)
+}
+
+#[tokio::test]
+async fn test_clear_payment_split_settlement() {
+    let mut setup = setup_split_settlement_test().unwrap();
+    let remaining_accounts = setup.split().remaining_accounts(&USDC_MINT, &spl_token::ID);
+
+    clear_payment(&mut setup, remaining_accounts).expect("Should clear with split settlement");
+
+    let treasury = setup.treasury;
+    let tax_escrow = setup.tax_escrow;
+    let settlement_wallet = setup.settlement_wallet;
+    let operator_authority = setup.operator_authority.pubkey();
+    let merchant_pda = setup.merchant_pda;
+
+    assert_eq!(balance(&mut setup, &treasury), MERCHANT_AMOUNT * 9 / 10);
+    assert_eq!(balance(&mut setup, &tax_escrow), MERCHANT_AMOUNT / 10);
+    assert_eq!(balance(&mut setup, &settlement_wallet), 0);
+    assert_eq!(
+        balance(&mut setup, &operator_authority),
+        PAYMENT_AMOUNT - MERCHANT_AMOUNT
+    );
+    assert_eq!(balance(&mut setup, &merchant_pda), 0);
+}
+
+#[tokio::test]
+async fn test_clear_payment_split_settlement_wrong_order_fails() {
+    let mut setup = setup_split_settlement_test().unwrap();
+    let mut remaining_accounts = setup.split().remaining_accounts(&USDC_MINT, &spl_token::ID);
+    remaining_accounts.reverse();
+
+    let result = clear_payment(&mut setup, remaining_accounts);
+    assert_program_error(result, INVALID_SPLIT_SETTLEMENT_DESTINATIONS_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_split_settlement_wrong_destination_fails() {
+    let mut setup = setup_split_settlement_test().unwrap();
+    let settlement_wallet = setup.settlement_wallet;
+    let mut remaining_accounts = setup.split().remaining_accounts(&USDC_MINT, &spl_token::ID);
+    remaining_accounts[1] = AccountMeta::new(
+        get_associated_token_address(&settlement_wallet, &USDC_MINT),
+        false,
+    );
+
+    let result = clear_payment(&mut setup, remaining_accounts);
+    assert_program_error(result, INVALID_SPLIT_SETTLEMENT_DESTINATIONS_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_split_settlement_readonly_destination_fails() {
+    let mut setup = setup_split_settlement_test().unwrap();
+    let mut remaining_accounts = setup.split().remaining_accounts(&USDC_MINT, &spl_token::ID);
+    remaining_accounts[0].is_writable = false;
+
+    let result = clear_payment(&mut setup, remaining_accounts);
+    assert_program_error(result, INVALID_SPLIT_SETTLEMENT_DESTINATIONS_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_split_settlement_missing_destinations_fails() {
+    let mut setup = setup_split_settlement_test().unwrap();
+    let mut remaining_accounts = setup.split().remaining_accounts(&USDC_MINT, &spl_token::ID);
+    remaining_accounts.pop();
+
+    let result = clear_payment(&mut setup, remaining_accounts);
+    assert_program_error(result, NOT_ENOUGH_ACCOUNT_KEYS_ERROR);
+}
+
+#[tokio::test]
+async fn test_clear_payment_split_settlement_with_settlement_batch_fails() {
+    let mut setup = setup_split_settlement_test().unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+    let (settlement_batch_pda, _) = assert_initialize_settlement_batch(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.merchant_operator_config_pda,
+        &USDC_MINT,
+        true, // fail_if_exists
+        false,
+    )
+    .unwrap();
+
+    let mut remaining_accounts = setup.split().remaining_accounts(&USDC_MINT, &spl_token::ID);
+    remaining_accounts.push(AccountMeta::new(settlement_batch_pda, false));
+
+    let result = clear_payment(&mut setup, remaining_accounts);
+    assert_program_error(result, SPLIT_SETTLEMENT_UNSUPPORTED_ERROR);
+}
+
+#[tokio::test]
+async fn test_initialize_config_invalid_split_settlement_policy_fails() {
+    let mut setup = setup_split_settlement_test().unwrap();
+    let merchant_authority = setup.merchant_authority.insecure_clone();
+
+    // Shares must add up to 100%
+    let split = SplitSettlement::new([(setup.treasury, 9_000), (setup.tax_escrow, 500)]);
+
+    let result = assert_get_or_create_merchant_operator_config(
+        &mut setup.context,
+        &merchant_authority,
+        &setup.merchant_pda,
+        &setup.operator_pda,
+        2u32,
+        500u64,
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::SplitSettlement(split.policy())],
+        vec![USDC_MINT],
+        true,
+        false,
+    )
+    .map(|_| ());
+
+    assert_program_error(result, INVALID_SPLIT_SETTLEMENT_POLICY_ERROR);
+}
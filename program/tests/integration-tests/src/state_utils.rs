@@ -1,26 +1,54 @@
 use crate::{
     assertions::{
-        assert_account_not_exists, assert_merchant_account,
-        assert_merchant_operator_config_account, assert_multiple_token_balance_changes,
-        assert_operator_account, assert_payment_account, assert_token_balance_changes,
+        assert_account_not_exists, assert_buyer_index_account, assert_config_registry_page_account,
+        assert_conversion_rate_account, assert_merchant_account,
+        assert_merchant_operator_config_account, assert_merchant_profile_account,
+        assert_merchant_stats_account, assert_multiple_token_balance_changes,
+        assert_operator_account, assert_operator_delegate_account,
+        assert_operator_freeze_list_account, assert_order_account, assert_payment_account,
+        assert_payment_tree_account, assert_quote_account, assert_refund_approval_account,
+        assert_risk_state_account, assert_settlement_batch_account, assert_token_balance_changes,
         BalanceChange,
     },
     utils::{
-        assert_event_present, find_merchant_operator_config_pda, find_merchant_pda,
-        find_operator_pda, find_payment_pda, get_or_create_associated_token_account,
-        get_token_balance, set_token_balance, TestContext, MAX_BPS,
+        assert_event_present, assert_merchant_operator_config_initialized_event_present,
+        assert_payment_closed_event_present, assert_payment_status_changed_event_present,
+        assert_refund_approved_event_present, assert_settlement_wallet_proposed_event_present,
+        assert_settlement_wallet_timelock_raised_event_present, find_buyer_index_pda,
+        find_config_registry_page_pda, find_conversion_rate_pda, find_escrow_authority_pda,
+        find_merchant_operator_config_pda, find_merchant_pda, find_merchant_profile_pda,
+        find_merchant_stats_pda, find_operator_delegate_pda, find_operator_freeze_list_pda,
+        find_operator_pda, find_order_pda, find_payment_pda, find_payment_tree_pda, find_quote_pda,
+        find_refund_approval_pda, find_risk_state_pda, find_settlement_batch_pda,
+        get_or_create_associated_token_account, get_token_balance, idempotency_key,
+        lifecycle_status, set_token_balance, TestContext,
     },
 };
 use commerce_program_client::{
+    compression::{payment_leaf_hash, PaymentMerkleTree},
     instructions::{
-        ClearPaymentBuilder, ClosePaymentBuilder, CreateOperatorBuilder, InitializeMerchantBuilder,
-        InitializeMerchantOperatorConfigBuilder, MakePaymentBuilder, RefundPaymentBuilder,
-        UpdateMerchantAuthorityBuilder, UpdateMerchantSettlementWalletBuilder,
-        UpdateOperatorAuthorityBuilder,
+        AcceptMerchantAuthorityBuilder, AcceptOperatorAuthorityBuilder, ApproveRefundBuilder,
+        ChargebackPaymentBuilder, ClaimRefundBuilder, ClearPaymentBuilder,
+        ClearPaymentCompressedBuilder, ClosePaymentBuilder, CommitSettlementWalletBuilder,
+        CreateOperatorBuilder, CreateOrderBuilder, CreateQuoteBuilder, HeartbeatBuilder,
+        InitializeBuyerIndexBuilder, InitializeConfigRegistryPageBuilder,
+        InitializeMerchantBuilder, InitializeMerchantOperatorConfigBuilder,
+        InitializeMerchantProfileBuilder, InitializeMerchantStatsBuilder,
+        InitializeMerchantWithConfigBuilder, InitializeOperatorDelegateBuilder,
+        InitializeOperatorFreezeListBuilder, InitializePaymentTreeBuilder,
+        InitializeRiskStateBuilder, InitializeSettlementBatchBuilder, MakePaymentBuilder,
+        MakePaymentCompressedBuilder, PostConversionRateBuilder, ProposeMerchantAuthorityBuilder,
+        ProposeOperatorAuthorityBuilder, ProposeSettlementWalletBuilder, RefundPaymentBuilder,
+        RefundSettledPaymentBuilder, SetSettlementWalletTimelockBuilder,
+        UpdateMerchantSettlementWalletBuilder,
     },
-    types::{FeeType, PolicyData, Status},
+    preview::{calculate_fees, decode_merchant_operator_config, preview_clear_payment},
+    refund_delegate::find_refund_delegate_pda,
+    types::{FeeType, OrderStatus, PaymentLifecycleStatus, PolicyData, Status},
+    Merchant, Operator,
 };
 use solana_sdk::{
+    clock::Clock,
     instruction::AccountMeta,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
@@ -45,7 +73,6 @@ pub fn assert_get_or_create_operator(
 
     // Create operator instruction
     let instruction = CreateOperatorBuilder::new()
-        .bump(bump)
         .payer(context.payer.pubkey())
         .authority(owner.pubkey())
         .operator(operator_pda)
@@ -66,12 +93,53 @@ pub fn assert_get_or_create_operator(
     Ok((operator_pda, bump))
 }
 
+pub fn assert_heartbeat(
+    context: &mut TestContext,
+    operator_authority: &Keypair,
+    operator_pda: &Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction = HeartbeatBuilder::new()
+        .operator_authority(operator_authority.pubkey())
+        .operator(*operator_pda)
+        .instruction();
+
+    context.send_transaction_with_signers(instruction, &[operator_authority])?;
+
+    let operator_account = context
+        .get_account(operator_pda)
+        .expect("Operator should exist");
+    let operator =
+        Operator::from_bytes(&operator_account.data).expect("Should deserialize operator");
+    let now = context.svm.get_sysvar::<Clock>().unix_timestamp;
+    assert_eq!(operator.last_active_at, now);
+
+    Ok(())
+}
+
 pub fn assert_get_or_create_merchant(
     context: &mut TestContext,
     authority: &Keypair,
     settlement_wallet: &Keypair,
     fail_if_exists: bool,
     with_profiling: bool,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    assert_get_or_create_merchant_with_timelock(
+        context,
+        authority,
+        settlement_wallet,
+        0,
+        fail_if_exists,
+        with_profiling,
+    )
+}
+
+pub fn assert_get_or_create_merchant_with_timelock(
+    context: &mut TestContext,
+    authority: &Keypair,
+    settlement_wallet: &Keypair,
+    settlement_wallet_timelock_seconds: u64,
+    fail_if_exists: bool,
+    with_profiling: bool,
 ) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
     context.airdrop_if_required(&authority.pubkey(), 1_000_000_000)?;
 
@@ -84,6 +152,7 @@ pub fn assert_get_or_create_merchant(
     // Initialize merchant instruction
     let instruction = InitializeMerchantBuilder::new()
         .bump(bump)
+        .settlement_wallet_timelock_seconds(settlement_wallet_timelock_seconds)
         .payer(context.payer.pubkey())
         .authority(authority.pubkey())
         .merchant(merchant_pda)
@@ -108,6 +177,13 @@ pub fn assert_get_or_create_merchant(
         &settlement_wallet.pubkey(),
     );
 
+    let merchant_account = context.get_account(&merchant_pda).unwrap();
+    let merchant = Merchant::from_bytes(&merchant_account.data).unwrap();
+    assert_eq!(
+        merchant.settlement_wallet_timelock_seconds,
+        settlement_wallet_timelock_seconds
+    );
+
     Ok((merchant_pda, bump))
 }
 
@@ -159,15 +235,13 @@ pub fn assert_get_or_create_merchant_operator_config(
     let instruction = builder.instruction();
 
     // Send transaction with authority as additional signer
-    context
-        .send_transaction_with_signers_with_transaction_result(
-            instruction,
-            &[authority],
-            with_profiling,
-        )
-        .expect("Create merchant operator config should succeed");
+    let transaction_metadata = context.send_transaction_with_signers_with_transaction_result(
+        instruction,
+        &[authority],
+        with_profiling,
+    )?;
 
-    assert_merchant_operator_config_account(
+    let config = assert_merchant_operator_config_account(
         context,
         &merchant_operator_config_pda,
         merchant_operator_config_bump,
@@ -180,9 +254,109 @@ pub fn assert_get_or_create_merchant_operator_config(
         accepted_currencies.len() as u32,
     );
 
+    assert_merchant_operator_config_initialized_event_present(
+        &transaction_metadata,
+        &merchant_operator_config_pda,
+        merchant_pda,
+        operator_pda,
+        version,
+        &config.config_hash,
+    );
+
     Ok((merchant_operator_config_pda, merchant_operator_config_bump))
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn assert_initialize_merchant_with_config(
+    context: &mut TestContext,
+    authority: &Keypair,
+    settlement_wallet: &Keypair,
+    operator_authority: &Keypair,
+    operator_pda: &Pubkey,
+    version: u32,
+    operator_fee: u64,
+    fee_type: FeeType,
+    days_to_close: u16,
+    policies: Vec<PolicyData>,
+    accepted_currencies: Vec<Pubkey>,
+) -> Result<(Pubkey, Pubkey), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&authority.pubkey(), 1_000_000_000)?;
+
+    let (merchant_pda, merchant_bump) = find_merchant_pda(&authority.pubkey());
+    let (merchant_operator_config_pda, merchant_operator_config_bump) =
+        find_merchant_operator_config_pda(&merchant_pda, operator_pda, version);
+
+    assert_account_not_exists(context, &merchant_pda);
+    assert_account_not_exists(context, &merchant_operator_config_pda);
+
+    // Initialize Merchant and MerchantOperatorConfig in one instruction
+    let mut builder = InitializeMerchantWithConfigBuilder::new();
+    builder
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .operator_authority(operator_authority.pubkey())
+        .merchant(merchant_pda)
+        .settlement_wallet(settlement_wallet.pubkey())
+        .operator(*operator_pda)
+        .config(merchant_operator_config_pda)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .merchant_bump(merchant_bump)
+        .settlement_wallet_timelock_seconds(0)
+        .version(version)
+        .config_bump(merchant_operator_config_bump)
+        .operator_fee(operator_fee)
+        .fee_type(fee_type)
+        .days_to_close(days_to_close)
+        .policies(policies.clone())
+        .accepted_currencies(accepted_currencies.clone());
+
+    // Add mint accounts as remaining accounts for each accepted currency
+    for currency in &accepted_currencies {
+        builder.add_remaining_account(AccountMeta::new_readonly(*currency, false));
+    }
+
+    let instruction = builder.instruction();
+
+    // Both the merchant and operator authorities sign
+    let transaction_metadata = context.send_transaction_with_signers_with_transaction_result(
+        instruction,
+        &[authority, operator_authority],
+        false,
+    )?;
+
+    assert_merchant_account(
+        context,
+        &merchant_pda,
+        &authority.pubkey(),
+        merchant_bump,
+        &settlement_wallet.pubkey(),
+    );
+
+    let config = assert_merchant_operator_config_account(
+        context,
+        &merchant_operator_config_pda,
+        merchant_operator_config_bump,
+        version,
+        &merchant_pda,
+        operator_pda,
+        operator_fee,
+        0,
+        policies.len() as u32,
+        accepted_currencies.len() as u32,
+    );
+
+    assert_merchant_operator_config_initialized_event_present(
+        &transaction_metadata,
+        &merchant_operator_config_pda,
+        &merchant_pda,
+        operator_pda,
+        version,
+        &config.config_hash,
+    );
+
+    Ok((merchant_pda, merchant_operator_config_pda))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn assert_make_payment(
     context: &mut TestContext,
@@ -233,18 +407,19 @@ pub fn assert_make_payment(
 
     // Calculate ATAs
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), mint);
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, mint);
+    let escrow_authority = find_escrow_authority_pda(merchant_operator_config_pda, mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, mint);
     let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, mint);
 
-    set_token_balance(context, &buyer_ata, mint, &buyer.pubkey(), amount * 2);
-
-    // Create merchant escrow ATA if it doesn't exist
-    get_or_create_associated_token_account(context, &merchant_pda, mint);
+    set_token_balance(
+        context,
+        &buyer_ata,
+        mint,
+        &buyer.pubkey(),
+        amount.saturating_mul(2),
+    );
 
-    // Create merchant settlement ATA if it doesn't exist and auto_settle is true
-    if is_auto_settle {
-        get_or_create_associated_token_account(context, &settlement_wallet, mint);
-    }
+    // Merchant escrow / settlement ATAs are created by the program if missing
 
     // Get pre-balances for token transfer assertion (buyer to escrow)
     let pre_balances = [
@@ -263,13 +438,15 @@ pub fn assert_make_payment(
         .merchant_operator_config(*merchant_operator_config_pda)
         .mint(*mint)
         .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .merchant_settlement_ata(merchant_settlement_ata)
+        .settlement_wallet(settlement_wallet)
         .token_program(TOKEN_PROGRAM_ID)
         .system_program(SYSTEM_PROGRAM_ID)
         .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
         .amount(amount)
-        .bump(bump)
         .instruction();
 
     // Send transaction with required signers (payer, operator_authority, buyer)
@@ -320,6 +497,18 @@ pub fn assert_make_payment(
         amount,
         order_id,
         None,
+        &idempotency_key(order_id),
+    );
+
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        &buyer.pubkey(),
+        &merchant_pda,
+        operator_pda,
+        order_id,
+        PaymentLifecycleStatus::Uninitialized,
+        lifecycle_status(expected_payment_status),
+        &buyer.pubkey(),
     );
 
     Ok((payment_pda, bump))
@@ -360,7 +549,8 @@ pub fn assert_refund_payment(
 
     // Calculate ATAs
     let buyer_ata = get_associated_token_address(&buyer.pubkey(), mint);
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, mint);
+    let escrow_authority = find_escrow_authority_pda(merchant_operator_config_pda, mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, mint);
 
     // Get pre-balances for token transfer assertion (escrow to buyer)
     let pre_balances = [
@@ -378,6 +568,7 @@ pub fn assert_refund_payment(
         .operator(operator_pda)
         .merchant_operator_config(*merchant_operator_config_pda)
         .mint(*mint)
+        .escrow_authority(escrow_authority)
         .merchant_escrow_ata(merchant_escrow_ata)
         .buyer_ata(buyer_ata)
         .token_program(TOKEN_PROGRAM_ID)
@@ -420,34 +611,41 @@ pub fn assert_refund_payment(
         payment.amount,
         payment.order_id,
         None,
+        &payment.idempotency_key,
+    );
+
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        &buyer.pubkey(),
+        &merchant_pda,
+        &operator_pda,
+        payment.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::Refunded,
+        &operator_authority.pubkey(),
     );
 
     Ok(())
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn assert_clear_payment(
+pub fn assert_approve_refund(
     context: &mut TestContext,
-    payer: &Keypair,
-    operator_authority: &Keypair,
-    buyer: &Keypair,
+    authority: &Keypair,
+    buyer: &Pubkey,
     payment_pda: &Pubkey,
     mint: &Pubkey,
     merchant_operator_config_pda: &Pubkey,
     with_profiling: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    context.airdrop_if_required(&payer.pubkey(), 1_000_000_000)?;
-    context.airdrop_if_required(&operator_authority.pubkey(), 1_000_000_000)?;
-    context.airdrop_if_required(&buyer.pubkey(), 1_000_000_000)?;
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&authority.pubkey(), 1_000_000_000)?;
 
-    // Get payment account to extract required information
     let payment_account = context
         .get_account(payment_pda)
         .expect("Payment should exist");
     let payment = commerce_program_client::Payment::from_bytes(&payment_account.data)
         .expect("Should deserialize payment");
 
-    // Get the merchant from the merchant_operator_config
     let merchant_operator_config_account = context
         .get_account(merchant_operator_config_pda)
         .expect("Merchant operator config should exist");
@@ -457,292 +655,1795 @@ pub fn assert_clear_payment(
     .expect("Should deserialize merchant operator config");
     let merchant_pda = merchant_operator_config.merchant;
     let operator_pda = merchant_operator_config.operator;
-    let operator_fee = merchant_operator_config.operator_fee;
-    let fee_type = merchant_operator_config.fee_type;
-
-    // Get the merchant account to get settlement wallet
-    let merchant_account = context
-        .get_account(&merchant_pda)
-        .expect("Merchant should exist");
-    let merchant = commerce_program_client::Merchant::from_bytes(&merchant_account.data)
-        .expect("Should deserialize merchant");
-    let settlement_wallet = merchant.settlement_wallet;
-
-    // Get the operator account to get operator owner
-    let operator_account = context
-        .get_account(&operator_pda)
-        .expect("Operator should exist");
-    let operator = commerce_program_client::Operator::from_bytes(&operator_account.data)
-        .expect("Should deserialize operator");
-    let operator_owner = operator.owner;
-
-    // Calculate ATAs
-    let merchant_escrow_ata = get_associated_token_address(&merchant_pda, mint);
-    let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, mint);
-    let operator_settlement_ata = get_associated_token_address(&operator_owner, mint);
-
-    // Create settlement ATAs if they don't exist
-    get_or_create_associated_token_account(context, &settlement_wallet, mint);
-    get_or_create_associated_token_account(context, &operator_owner, mint);
 
-    // Get pre-balances for all ATAs
-    let pre_balances = vec![
-        (
-            merchant_escrow_ata,
-            get_token_balance(context, &merchant_escrow_ata),
-        ),
-        (
-            merchant_settlement_ata,
-            get_token_balance(context, &merchant_settlement_ata),
-        ),
-        (
-            operator_settlement_ata,
-            get_token_balance(context, &operator_settlement_ata),
-        ),
-    ];
+    let (refund_approval_pda, bump) = find_refund_approval_pda(payment_pda);
+    assert_account_not_exists(context, &refund_approval_pda);
 
-    // Create clear payment instruction
-    let instruction = ClearPaymentBuilder::new()
-        .payer(payer.pubkey())
+    let instruction = ApproveRefundBuilder::new()
+        .payer(context.payer.pubkey())
         .payment(*payment_pda)
-        .operator_authority(operator_authority.pubkey())
-        .buyer(buyer.pubkey())
+        .authority(authority.pubkey())
+        .buyer(*buyer)
         .merchant(merchant_pda)
         .operator(operator_pda)
         .merchant_operator_config(*merchant_operator_config_pda)
         .mint(*mint)
-        .merchant_escrow_ata(merchant_escrow_ata)
-        .merchant_settlement_ata(merchant_settlement_ata)
-        .operator_settlement_ata(operator_settlement_ata)
-        .token_program(TOKEN_PROGRAM_ID)
-        .system_program(SYSTEM_PROGRAM_ID)
+        .refund_approval(refund_approval_pda)
         .instruction();
 
-    // Send transaction with required signers (payer, operator_authority)
-    let transaction_metadata = context
-        .send_transaction_with_signers_with_transaction_result(
-            instruction,
-            &[operator_authority],
-            with_profiling,
-        )
-        .expect("Clear payment should succeed");
+    let transaction_metadata = context.send_transaction_with_signers_with_transaction_result(
+        instruction,
+        &[authority],
+        with_profiling,
+    )?;
 
     assert_payment_account(
         context,
         payment_pda,
         payment.order_id,
         payment.amount,
-        Status::Cleared,
+        Status::RefundApproved,
     );
 
-    // Calculate expected amounts
-    let (expected_operator_fee, expected_merchant_amount) = match fee_type {
-        FeeType::Bps => {
-            let fee = (payment.amount * operator_fee) / MAX_BPS;
-            (fee, payment.amount - fee)
-        }
-        FeeType::Fixed => {
-            // Cap operator fee at payment amount to prevent trapped funds
-            let capped_fee = operator_fee.min(payment.amount);
-            (capped_fee, payment.amount - capped_fee)
-        }
-    };
-
-    // Assert balance changes using the generic function
-    let balance_changes = vec![
-        BalanceChange {
-            ata: merchant_escrow_ata,
-            expected_change: -(payment.amount as i64),
-            description: "Escrow balance should decrease by payment amount".to_string(),
-        },
-        BalanceChange {
-            ata: merchant_settlement_ata,
-            expected_change: expected_merchant_amount as i64,
-            description: "Merchant balance should increase by payment amount minus operator fee"
-                .to_string(),
-        },
-        BalanceChange {
-            ata: operator_settlement_ata,
-            expected_change: expected_operator_fee as i64,
-            description: "Operator balance should increase by operator fee".to_string(),
-        },
-    ];
-
-    assert_multiple_token_balance_changes(context, &pre_balances, &balance_changes);
+    let refund_approval = assert_refund_approval_account(
+        context,
+        &refund_approval_pda,
+        payment_pda,
+        &authority.pubkey(),
+        payment.amount,
+    );
+    assert_eq!(refund_approval.bump, bump);
+    assert_eq!(refund_approval.rent_payer, context.payer.pubkey());
 
-    // Assert PaymentCleared event was emitted
-    assert_event_present(
+    assert_refund_approved_event_present(
         &transaction_metadata,
-        1, // PaymentCleared discriminator
-        &buyer.pubkey(),
+        buyer,
         &merchant_pda,
         &operator_pda,
+        &authority.pubkey(),
         payment.amount,
         payment.order_id,
-        Some(expected_operator_fee),
+        &payment.idempotency_key,
     );
 
-    Ok(())
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        buyer,
+        &merchant_pda,
+        &operator_pda,
+        payment.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::RefundApproved,
+        &authority.pubkey(),
+    );
+
+    Ok(refund_approval_pda)
 }
 
-pub fn assert_update_merchant_settlement_wallet(
+pub fn assert_claim_refund(
     context: &mut TestContext,
-    authority: &Keypair,
-    with_profiling: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let new_settlement_wallet = Keypair::new();
+    buyer: &Keypair,
+    payment_pda: &Pubkey,
+    mint: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    destination: &Pubkey,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&buyer.pubkey(), 1_000_000_000)?;
+
+    let payment_account = context
+        .get_account(payment_pda)
+        .expect("Payment should exist");
+    let payment = commerce_program_client::Payment::from_bytes(&payment_account.data)
+        .expect("Should deserialize payment");
+
+    let merchant_operator_config_account = context
+        .get_account(merchant_operator_config_pda)
+        .expect("Merchant operator config should exist");
+    let merchant_operator_config = commerce_program_client::MerchantOperatorConfig::from_bytes(
+        &merchant_operator_config_account.data,
+    )
+    .expect("Should deserialize merchant operator config");
+    let merchant_pda = merchant_operator_config.merchant;
+    let operator_pda = merchant_operator_config.operator;
+
+    let (refund_approval_pda, _) = find_refund_approval_pda(payment_pda);
+    let escrow_authority = find_escrow_authority_pda(merchant_operator_config_pda, mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, mint);
+
+    let pre_balances = [
+        get_token_balance(context, &merchant_escrow_ata),
+        get_token_balance(context, destination),
+    ];
+
+    let instruction = ClaimRefundBuilder::new()
+        .payer(context.payer.pubkey())
+        .payment(*payment_pda)
+        .buyer(buyer.pubkey())
+        .merchant(merchant_pda)
+        .operator(operator_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .mint(*mint)
+        .refund_approval(refund_approval_pda)
+        .rent_payer(context.payer.pubkey())
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .destination(*destination)
+        .token_program(TOKEN_PROGRAM_ID)
+        .instruction();
+
+    let transaction_metadata = context.send_transaction_with_signers_with_transaction_result(
+        instruction,
+        &[buyer],
+        with_profiling,
+    )?;
+
+    assert_payment_account(
+        context,
+        payment_pda,
+        payment.order_id,
+        payment.amount,
+        Status::Refunded,
+    );
+
+    assert_account_not_exists(context, &refund_approval_pda);
+
+    assert_token_balance_changes(
+        context,
+        pre_balances,
+        &merchant_escrow_ata,
+        destination,
+        payment.amount,
+    );
+
+    assert_event_present(
+        &transaction_metadata,
+        2, // PaymentRefunded discriminator
+        &buyer.pubkey(),
+        &merchant_pda,
+        &operator_pda,
+        payment.amount,
+        payment.order_id,
+        None,
+        &payment.idempotency_key,
+    );
+
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        &buyer.pubkey(),
+        &merchant_pda,
+        &operator_pda,
+        payment.order_id,
+        PaymentLifecycleStatus::RefundApproved,
+        PaymentLifecycleStatus::Refunded,
+        &buyer.pubkey(),
+    );
+
+    Ok(())
+}
+
+pub fn assert_chargeback_payment(
+    context: &mut TestContext,
+    arbiter: &Keypair,
+    buyer: &Keypair,
+    payment_pda: &Pubkey,
+    mint: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&arbiter.pubkey(), 1_000_000_000)?;
+
+    let payment_account = context
+        .get_account(payment_pda)
+        .expect("Payment should exist");
+    let payment = commerce_program_client::Payment::from_bytes(&payment_account.data)
+        .expect("Should deserialize payment");
+
+    let merchant_operator_config_account = context
+        .get_account(merchant_operator_config_pda)
+        .expect("Merchant operator config should exist");
+    let merchant_operator_config = commerce_program_client::MerchantOperatorConfig::from_bytes(
+        &merchant_operator_config_account.data,
+    )
+    .expect("Should deserialize merchant operator config");
+    let merchant_pda = merchant_operator_config.merchant;
+    let operator_pda = merchant_operator_config.operator;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), mint);
+    let escrow_authority = find_escrow_authority_pda(merchant_operator_config_pda, mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, mint);
+
+    let pre_balances = [
+        get_token_balance(context, &merchant_escrow_ata),
+        get_token_balance(context, &buyer_ata),
+    ];
+
+    let instruction = ChargebackPaymentBuilder::new()
+        .payer(context.payer.pubkey())
+        .payment(*payment_pda)
+        .arbiter(arbiter.pubkey())
+        .buyer(buyer.pubkey())
+        .merchant(merchant_pda)
+        .operator(operator_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .mint(*mint)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .buyer_ata(buyer_ata)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let transaction_metadata = context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[arbiter],
+            with_profiling,
+        )
+        .expect("Chargeback payment should succeed");
+
+    assert_payment_account(
+        context,
+        payment_pda,
+        payment.order_id,
+        payment.amount,
+        Status::Refunded,
+    );
+
+    // Assert token transfer from merchant escrow back to buyer
+    assert_token_balance_changes(
+        context,
+        pre_balances,
+        &merchant_escrow_ata,
+        &buyer_ata,
+        payment.amount,
+    );
+
+    // Assert PaymentChargebacked event was emitted
+    assert_event_present(
+        &transaction_metadata,
+        4, // PaymentChargebacked discriminator
+        &buyer.pubkey(),
+        &merchant_pda,
+        &operator_pda,
+        payment.amount,
+        payment.order_id,
+        None,
+        &payment.idempotency_key,
+    );
+
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        &buyer.pubkey(),
+        &merchant_pda,
+        &operator_pda,
+        payment.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::Refunded,
+        &arbiter.pubkey(),
+    );
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_clear_payment(
+    context: &mut TestContext,
+    payer: &Keypair,
+    operator_authority: &Keypair,
+    buyer: &Keypair,
+    payment_pda: &Pubkey,
+    mint: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&payer.pubkey(), 1_000_000_000)?;
+    context.airdrop_if_required(&operator_authority.pubkey(), 1_000_000_000)?;
+    context.airdrop_if_required(&buyer.pubkey(), 1_000_000_000)?;
+
+    // Get payment account to extract required information
+    let payment_account = context
+        .get_account(payment_pda)
+        .expect("Payment should exist");
+    let payment = commerce_program_client::Payment::from_bytes(&payment_account.data)
+        .expect("Should deserialize payment");
+
+    // Get the merchant from the merchant_operator_config
+    let merchant_operator_config_account = context
+        .get_account(merchant_operator_config_pda)
+        .expect("Merchant operator config should exist");
+    let (merchant_operator_config, policies, _) =
+        decode_merchant_operator_config(&merchant_operator_config_account.data)
+            .expect("Should deserialize merchant operator config");
+    let merchant_pda = merchant_operator_config.merchant;
+    let operator_pda = merchant_operator_config.operator;
+
+    // Get the merchant account to get settlement wallet
+    let merchant_account = context
+        .get_account(&merchant_pda)
+        .expect("Merchant should exist");
+    let merchant = commerce_program_client::Merchant::from_bytes(&merchant_account.data)
+        .expect("Should deserialize merchant");
+    let settlement_wallet = merchant.settlement_wallet;
+
+    // Get the operator account to get operator owner
+    let operator_account = context
+        .get_account(&operator_pda)
+        .expect("Operator should exist");
+    let operator = commerce_program_client::Operator::from_bytes(&operator_account.data)
+        .expect("Should deserialize operator");
+    let operator_owner = operator.owner;
+
+    // Calculate ATAs
+    let escrow_authority = find_escrow_authority_pda(merchant_operator_config_pda, mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, mint);
+    let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, mint);
+    let operator_settlement_ata = get_associated_token_address(&operator_owner, mint);
+
+    // Create settlement ATAs if they don't exist
+    get_or_create_associated_token_account(context, &settlement_wallet, mint);
+    get_or_create_associated_token_account(context, &operator_owner, mint);
+
+    // Get pre-balances for all ATAs
+    let pre_balances = vec![
+        (
+            merchant_escrow_ata,
+            get_token_balance(context, &merchant_escrow_ata),
+        ),
+        (
+            merchant_settlement_ata,
+            get_token_balance(context, &merchant_settlement_ata),
+        ),
+        (
+            operator_settlement_ata,
+            get_token_balance(context, &operator_settlement_ata),
+        ),
+    ];
+
+    // Create clear payment instruction
+    let instruction = ClearPaymentBuilder::new()
+        .payer(payer.pubkey())
+        .payment(*payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .merchant(merchant_pda)
+        .operator(operator_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .mint(*mint)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .merchant_settlement_ata(merchant_settlement_ata)
+        .operator_settlement_ata(operator_settlement_ata)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    // Send transaction with required signers (payer, operator_authority)
+    let transaction_metadata = context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[operator_authority],
+            with_profiling,
+        )
+        .expect("Clear payment should succeed");
+
+    assert_payment_account(
+        context,
+        payment_pda,
+        payment.order_id,
+        payment.amount,
+        Status::Cleared,
+    );
+
+    // Calculate expected amounts, charging the payment's fee override when it has one and
+    // applying any settlement discount
+    let now = context.svm.get_sysvar::<Clock>().unix_timestamp;
+    let preview = preview_clear_payment(&merchant_operator_config, &policies, &payment, now)
+        .expect("Fee calculation should not overflow");
+    let (expected_operator_fee, expected_merchant_amount) =
+        (preview.operator_fee, preview.merchant_amount);
+
+    // Assert balance changes using the generic function
+    let balance_changes = vec![
+        BalanceChange {
+            ata: merchant_escrow_ata,
+            expected_change: -(payment.amount as i128),
+            description: "Escrow balance should decrease by payment amount".to_string(),
+        },
+        BalanceChange {
+            ata: merchant_settlement_ata,
+            expected_change: expected_merchant_amount as i128,
+            description: "Merchant balance should increase by payment amount minus operator fee"
+                .to_string(),
+        },
+        BalanceChange {
+            ata: operator_settlement_ata,
+            expected_change: expected_operator_fee as i128,
+            description: "Operator balance should increase by operator fee".to_string(),
+        },
+    ];
+
+    assert_multiple_token_balance_changes(context, &pre_balances, &balance_changes);
+
+    // Assert PaymentCleared event was emitted
+    assert_event_present(
+        &transaction_metadata,
+        1, // PaymentCleared discriminator
+        &buyer.pubkey(),
+        &merchant_pda,
+        &operator_pda,
+        payment.amount,
+        payment.order_id,
+        Some(expected_operator_fee),
+        &payment.idempotency_key,
+    );
+
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        &buyer.pubkey(),
+        &merchant_pda,
+        &operator_pda,
+        payment.order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::Cleared,
+        &operator_authority.pubkey(),
+    );
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_refund_settled_payment(
+    context: &mut TestContext,
+    payer: &Keypair,
+    operator_authority: &Keypair,
+    buyer: &Keypair,
+    payment_pda: &Pubkey,
+    mint: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&payer.pubkey(), 1_000_000_000)?;
+    context.airdrop_if_required(&operator_authority.pubkey(), 1_000_000_000)?;
+
+    let payment_account = context
+        .get_account(payment_pda)
+        .expect("Payment should exist");
+    let payment = commerce_program_client::Payment::from_bytes(&payment_account.data)
+        .expect("Should deserialize payment");
+
+    let merchant_operator_config_account = context
+        .get_account(merchant_operator_config_pda)
+        .expect("Merchant operator config should exist");
+    let merchant_operator_config = commerce_program_client::MerchantOperatorConfig::from_bytes(
+        &merchant_operator_config_account.data,
+    )
+    .expect("Should deserialize merchant operator config");
+    let merchant_pda = merchant_operator_config.merchant;
+    let operator_pda = merchant_operator_config.operator;
+
+    let merchant_account = context
+        .get_account(&merchant_pda)
+        .expect("Merchant should exist");
+    let merchant =
+        Merchant::from_bytes(&merchant_account.data).expect("Should deserialize merchant");
+
+    // Calculate ATAs
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), mint);
+    let merchant_settlement_ata = get_associated_token_address(&merchant.settlement_wallet, mint);
+
+    // Get pre-balances for token transfer assertion (settlement wallet to buyer)
+    let pre_balances = [
+        get_token_balance(context, &merchant_settlement_ata),
+        get_token_balance(context, &buyer_ata),
+    ];
+
+    let (refund_delegate, refund_delegate_bump) = find_refund_delegate_pda(&merchant_pda);
+
+    let instruction = RefundSettledPaymentBuilder::new()
+        .payer(payer.pubkey())
+        .payment(*payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .merchant(merchant_pda)
+        .operator(operator_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .mint(*mint)
+        .merchant_settlement_ata(merchant_settlement_ata)
+        .buyer_ata(buyer_ata)
+        .refund_delegate(refund_delegate)
+        .refund_delegate_bump(refund_delegate_bump)
+        .token_program(TOKEN_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    let transaction_metadata = context.send_transaction_with_signers_with_transaction_result(
+        instruction,
+        &[operator_authority],
+        with_profiling,
+    )?;
+
+    assert_payment_account(
+        context,
+        payment_pda,
+        payment.order_id,
+        payment.amount,
+        Status::Refunded,
+    );
+
+    // Assert token transfer from the settlement wallet back to buyer
+    assert_token_balance_changes(
+        context,
+        pre_balances,
+        &merchant_settlement_ata,
+        &buyer_ata,
+        payment.amount,
+    );
+
+    // Assert PaymentRefunded event was emitted
+    assert_event_present(
+        &transaction_metadata,
+        2, // PaymentRefunded discriminator
+        &buyer.pubkey(),
+        &merchant_pda,
+        &operator_pda,
+        payment.amount,
+        payment.order_id,
+        None,
+        &payment.idempotency_key,
+    );
+
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        &buyer.pubkey(),
+        &merchant_pda,
+        &operator_pda,
+        payment.order_id,
+        PaymentLifecycleStatus::Cleared,
+        PaymentLifecycleStatus::Refunded,
+        &operator_authority.pubkey(),
+    );
+
+    Ok(())
+}
+
+pub fn assert_update_merchant_settlement_wallet(
+    context: &mut TestContext,
+    authority: &Keypair,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let new_settlement_wallet = Keypair::new();
+
+    let (merchant_pda, bump) = find_merchant_pda(&authority.pubkey());
+
+    let instruction = UpdateMerchantSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .new_settlement_wallet(new_settlement_wallet.pubkey())
+        .instruction();
+
+    context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[authority],
+            with_profiling,
+        )
+        .expect("Update merchant settlement wallet should succeed");
+
+    assert_merchant_account(
+        context,
+        &merchant_pda,
+        &authority.pubkey(),
+        bump,
+        &new_settlement_wallet.pubkey(),
+    );
+
+    Ok(())
+}
+
+pub fn assert_propose_settlement_wallet(
+    context: &mut TestContext,
+    authority: &Keypair,
+    new_settlement_wallet: &Pubkey,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let now = context.svm.get_sysvar::<Clock>().unix_timestamp;
+    let merchant_account = context.get_account(&merchant_pda).unwrap();
+    let settlement_wallet = Merchant::from_bytes(&merchant_account.data)
+        .unwrap()
+        .settlement_wallet;
+
+    let instruction = ProposeSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .new_settlement_wallet(*new_settlement_wallet)
+        .instruction();
+
+    let transaction_metadata = context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[authority],
+            with_profiling,
+        )
+        .expect("Propose settlement wallet should succeed");
+
+    // Settlement wallet is unchanged until the proposal is committed
+    let merchant_account = context.get_account(&merchant_pda).unwrap();
+    let merchant = Merchant::from_bytes(&merchant_account.data).unwrap();
+    assert_ne!(merchant.settlement_wallet, *new_settlement_wallet);
+    assert_eq!(merchant.pending_settlement_wallet, *new_settlement_wallet);
+    assert_eq!(
+        merchant.pending_settlement_wallet_unlocks_at,
+        now + merchant.settlement_wallet_timelock_seconds as i64
+    );
+
+    assert_settlement_wallet_proposed_event_present(
+        &transaction_metadata,
+        &merchant_pda,
+        &settlement_wallet,
+        new_settlement_wallet,
+        merchant.pending_settlement_wallet_unlocks_at,
+    );
+
+    Ok(())
+}
+
+pub fn assert_commit_settlement_wallet(
+    context: &mut TestContext,
+    authority: &Keypair,
+    new_settlement_wallet: &Pubkey,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (merchant_pda, bump) = find_merchant_pda(&authority.pubkey());
+
+    let instruction = CommitSettlementWalletBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .instruction();
+
+    context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[authority],
+            with_profiling,
+        )
+        .expect("Commit settlement wallet should succeed");
+
+    assert_merchant_account(
+        context,
+        &merchant_pda,
+        &authority.pubkey(),
+        bump,
+        new_settlement_wallet,
+    );
+
+    let merchant_account = context.get_account(&merchant_pda).unwrap();
+    let merchant = Merchant::from_bytes(&merchant_account.data).unwrap();
+    assert_eq!(merchant.pending_settlement_wallet, Pubkey::default());
+    assert_eq!(merchant.pending_settlement_wallet_unlocks_at, 0);
+
+    Ok(())
+}
+
+pub fn assert_set_settlement_wallet_timelock(
+    context: &mut TestContext,
+    authority: &Keypair,
+    settlement_wallet_timelock_seconds: u64,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+    let merchant_account = context.get_account(&merchant_pda).unwrap();
+    let merchant = Merchant::from_bytes(&merchant_account.data).unwrap();
+    let old_timelock_seconds = merchant.settlement_wallet_timelock_seconds;
+    let old_unlocks_at = merchant.pending_settlement_wallet_unlocks_at;
+    let has_pending_settlement_wallet = merchant.pending_settlement_wallet != Pubkey::default();
+
+    let instruction = SetSettlementWalletTimelockBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .settlement_wallet_timelock_seconds(settlement_wallet_timelock_seconds)
+        .instruction();
+
+    let transaction_metadata = context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[authority],
+            with_profiling,
+        )
+        .expect("Set settlement wallet timelock should succeed");
+
+    // A pending proposal's unlock moves out by the increase
+    let merchant_account = context.get_account(&merchant_pda).unwrap();
+    let merchant = Merchant::from_bytes(&merchant_account.data).unwrap();
+    assert_eq!(
+        merchant.settlement_wallet_timelock_seconds,
+        settlement_wallet_timelock_seconds
+    );
+    let expected_unlocks_at = if has_pending_settlement_wallet {
+        old_unlocks_at + (settlement_wallet_timelock_seconds - old_timelock_seconds) as i64
+    } else {
+        old_unlocks_at
+    };
+    assert_eq!(
+        merchant.pending_settlement_wallet_unlocks_at,
+        expected_unlocks_at
+    );
+
+    assert_settlement_wallet_timelock_raised_event_present(
+        &transaction_metadata,
+        &merchant_pda,
+        old_timelock_seconds,
+        settlement_wallet_timelock_seconds,
+        expected_unlocks_at,
+    );
+
+    Ok(())
+}
+
+pub fn assert_propose_merchant_authority(
+    context: &mut TestContext,
+    authority: &Keypair,
+    new_authority: &Pubkey,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (merchant_pda, _) = find_merchant_pda(&authority.pubkey());
+
+    let instruction = ProposeMerchantAuthorityBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .merchant(merchant_pda)
+        .new_authority(*new_authority)
+        .instruction();
+
+    context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[authority],
+            with_profiling,
+        )
+        .expect("Propose merchant authority should succeed");
+
+    // Owner is unchanged until the proposal is accepted
+    let merchant_account = context.get_account(&merchant_pda).unwrap();
+    let merchant = Merchant::from_bytes(&merchant_account.data).unwrap();
+    assert_eq!(merchant.owner, authority.pubkey());
+    assert_eq!(merchant.pending_authority, *new_authority);
+
+    Ok(())
+}
+
+pub fn assert_accept_merchant_authority(
+    context: &mut TestContext,
+    merchant_pda: &Pubkey,
+    new_authority: &Keypair,
+    settlement_wallet: &Pubkey,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let merchant_account = context.get_account(merchant_pda).unwrap();
+    let bump = Merchant::from_bytes(&merchant_account.data).unwrap().bump;
+
+    let instruction = AcceptMerchantAuthorityBuilder::new()
+        .payer(context.payer.pubkey())
+        .new_authority(new_authority.pubkey())
+        .merchant(*merchant_pda)
+        .instruction();
+
+    context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[new_authority],
+            with_profiling,
+        )
+        .expect("Accept merchant authority should succeed");
+
+    assert_merchant_account(
+        context,
+        merchant_pda,
+        &new_authority.pubkey(),
+        bump,
+        settlement_wallet,
+    );
+
+    let merchant_account = context.get_account(merchant_pda).unwrap();
+    let merchant = Merchant::from_bytes(&merchant_account.data).unwrap();
+    assert_eq!(merchant.pending_authority, Pubkey::default());
+
+    Ok(())
+}
+
+pub fn assert_propose_operator_authority(
+    context: &mut TestContext,
+    authority: &Keypair,
+    new_authority: &Pubkey,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (operator_pda, _) = find_operator_pda(&authority.pubkey());
+
+    let instruction = ProposeOperatorAuthorityBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .operator(operator_pda)
+        .new_operator_authority(*new_authority)
+        .instruction();
+
+    context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[authority],
+            with_profiling,
+        )
+        .expect("Propose operator authority should succeed");
+
+    // Owner is unchanged until the proposal is accepted
+    let operator_account = context.get_account(&operator_pda).unwrap();
+    let operator = Operator::from_bytes(&operator_account.data).unwrap();
+    assert_eq!(operator.owner, authority.pubkey());
+    assert_eq!(operator.pending_authority, *new_authority);
+
+    Ok(())
+}
+
+pub fn assert_accept_operator_authority(
+    context: &mut TestContext,
+    operator_pda: &Pubkey,
+    new_authority: &Keypair,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let operator_account = context.get_account(operator_pda).unwrap();
+    let bump = Operator::from_bytes(&operator_account.data).unwrap().bump;
+
+    let instruction = AcceptOperatorAuthorityBuilder::new()
+        .payer(context.payer.pubkey())
+        .new_operator_authority(new_authority.pubkey())
+        .operator(*operator_pda)
+        .instruction();
+
+    context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[new_authority],
+            with_profiling,
+        )
+        .expect("Accept operator authority should succeed");
+
+    assert_operator_account(context, operator_pda, &new_authority.pubkey(), bump);
+
+    let operator_account = context.get_account(operator_pda).unwrap();
+    let operator = Operator::from_bytes(&operator_account.data).unwrap();
+    assert_eq!(operator.pending_authority, Pubkey::default());
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_close_payment(
+    context: &mut TestContext,
+    payer: &Keypair,
+    payment_pda: &Pubkey,
+    buyer: &Pubkey,
+    merchant_pda: &Pubkey,
+    operator_pda: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    mint: &Pubkey,
+    operator_authority: &Keypair,
+    with_profiling: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&payer.pubkey(), 1_000_000_000)?;
+
+    // Get initial balance of fee payer to verify lamport transfer
+    let initial_payer_balance = context
+        .get_account(&payer.pubkey())
+        .map(|a| a.lamports)
+        .unwrap_or(0);
+
+    let payment_account = context
+        .get_account(payment_pda)
+        .expect("Payment should exist");
+    let payment = commerce_program_client::Payment::from_bytes(&payment_account.data)
+        .expect("Should deserialize payment");
+    let payment_accounts = config_payment_accounts(context, merchant_operator_config_pda);
+
+    // Create close payment instruction
+    let instruction = ClosePaymentBuilder::new()
+        .payer(payer.pubkey())
+        .payment(*payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .operator(*operator_pda)
+        .merchant(*merchant_pda)
+        .buyer(*buyer)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .mint(*mint)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+
+    // Send transaction with required signers
+    let transaction_metadata = context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[payer, operator_authority],
+            with_profiling,
+        )
+        .expect("Close payment should succeed");
+
+    // Verify payment account is closed (balance should be 0)
+    let final_payment_balance = context
+        .get_account(payment_pda)
+        .map(|a| a.lamports)
+        .unwrap_or(0);
+    assert_eq!(final_payment_balance, 0, "Payment account should be closed");
+    assert_eq!(
+        config_payment_accounts(context, merchant_operator_config_pda),
+        payment_accounts.saturating_sub(1)
+    );
+
+    // Verify lamports were transferred to fee payer
+    let final_payer_balance = context
+        .get_account(&payer.pubkey())
+        .map(|a| a.lamports)
+        .unwrap_or(0);
+    assert!(
+        final_payer_balance > initial_payer_balance,
+        "Fee payer should receive lamports from closed account"
+    );
+
+    // Assert PaymentClosed event was emitted
+    assert_payment_closed_event_present(
+        &transaction_metadata,
+        buyer,
+        merchant_pda,
+        operator_pda,
+        payment.order_id,
+        &payer.pubkey(),
+    );
+
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        buyer,
+        merchant_pda,
+        operator_pda,
+        payment.order_id,
+        lifecycle_status(payment.status),
+        PaymentLifecycleStatus::Closed,
+        &operator_authority.pubkey(),
+    );
+
+    Ok(())
+}
+
+/// Payment accounts the config counts as not yet closed.
+pub fn config_payment_accounts(
+    context: &mut TestContext,
+    merchant_operator_config_pda: &Pubkey,
+) -> u32 {
+    let merchant_operator_config_account = context
+        .get_account(merchant_operator_config_pda)
+        .expect("Merchant operator config should exist");
+    commerce_program_client::MerchantOperatorConfig::from_bytes(
+        &merchant_operator_config_account.data,
+    )
+    .expect("Should deserialize merchant operator config")
+    .payment_accounts
+}
+
+pub fn assert_initialize_payment_tree(
+    context: &mut TestContext,
+    operator_authority: &Keypair,
+    operator_pda: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    fail_if_exists: bool,
+    with_profiling: bool,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&operator_authority.pubkey(), 1_000_000_000)?;
+
+    let (payment_tree_pda, bump) = find_payment_tree_pda(merchant_operator_config_pda);
+
+    if fail_if_exists {
+        assert_account_not_exists(context, &payment_tree_pda);
+    }
+
+    let instruction = InitializePaymentTreeBuilder::new()
+        .payer(context.payer.pubkey())
+        .operator_authority(operator_authority.pubkey())
+        .operator(*operator_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .payment_tree(payment_tree_pda)
+        .bump(bump)
+        .instruction();
+
+    context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[operator_authority],
+            with_profiling,
+        )
+        .expect("Initialize payment tree should succeed");
+
+    assert_payment_tree_account(
+        context,
+        &payment_tree_pda,
+        merchant_operator_config_pda,
+        0,
+        PaymentMerkleTree::new().root(),
+    );
+
+    Ok((payment_tree_pda, bump))
+}
+
+pub fn assert_initialize_merchant_stats(
+    context: &mut TestContext,
+    merchant_authority: &Keypair,
+    merchant_pda: &Pubkey,
+    mint: &Pubkey,
+    fail_if_exists: bool,
+    with_profiling: bool,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&merchant_authority.pubkey(), 1_000_000_000)?;
+
+    let (merchant_stats_pda, bump) = find_merchant_stats_pda(merchant_pda, mint);
+
+    if fail_if_exists {
+        assert_account_not_exists(context, &merchant_stats_pda);
+    }
+
+    let instruction = InitializeMerchantStatsBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(merchant_authority.pubkey())
+        .merchant(*merchant_pda)
+        .mint(*mint)
+        .merchant_stats(merchant_stats_pda)
+        .bump(bump)
+        .instruction();
+
+    context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[merchant_authority],
+            with_profiling,
+        )
+        .expect("Initialize merchant stats should succeed");
+
+    let merchant_stats =
+        assert_merchant_stats_account(context, &merchant_stats_pda, merchant_pda, mint);
+    assert_eq!(merchant_stats.bump, bump);
+    assert_eq!(merchant_stats.payment_count, 0);
+    assert_eq!(merchant_stats.total_volume, 0);
+
+    Ok((merchant_stats_pda, bump))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_initialize_merchant_profile(
+    context: &mut TestContext,
+    merchant_authority: &Keypair,
+    merchant_pda: &Pubkey,
+    name: &str,
+    uri: &str,
+    logo_hash: [u8; 32],
+    contact_hash: [u8; 32],
+    fail_if_exists: bool,
+    with_profiling: bool,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&merchant_authority.pubkey(), 1_000_000_000)?;
+
+    let (merchant_profile_pda, bump) = find_merchant_profile_pda(merchant_pda);
+
+    if fail_if_exists {
+        assert_account_not_exists(context, &merchant_profile_pda);
+    }
+
+    let instruction = InitializeMerchantProfileBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(merchant_authority.pubkey())
+        .merchant(*merchant_pda)
+        .merchant_profile(merchant_profile_pda)
+        .name(name.to_string())
+        .uri(uri.to_string())
+        .logo_hash(logo_hash)
+        .contact_hash(contact_hash)
+        .bump(bump)
+        .instruction();
+
+    context.send_transaction_with_signers_with_transaction_result(
+        instruction,
+        &[merchant_authority],
+        with_profiling,
+    )?;
+
+    let merchant_profile =
+        assert_merchant_profile_account(context, &merchant_profile_pda, merchant_pda, name, uri);
+    assert_eq!(merchant_profile.bump, bump);
+    assert_eq!(merchant_profile.logo_hash, logo_hash);
+    assert_eq!(merchant_profile.contact_hash, contact_hash);
+
+    Ok((merchant_profile_pda, bump))
+}
+
+pub fn assert_initialize_settlement_batch(
+    context: &mut TestContext,
+    merchant_authority: &Keypair,
+    merchant_pda: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    mint: &Pubkey,
+    fail_if_exists: bool,
+    with_profiling: bool,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&merchant_authority.pubkey(), 1_000_000_000)?;
+
+    let (settlement_batch_pda, bump) =
+        find_settlement_batch_pda(merchant_operator_config_pda, mint);
+
+    if fail_if_exists {
+        assert_account_not_exists(context, &settlement_batch_pda);
+    }
+
+    let instruction = InitializeSettlementBatchBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(merchant_authority.pubkey())
+        .merchant(*merchant_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .mint(*mint)
+        .settlement_batch(settlement_batch_pda)
+        .bump(bump)
+        .instruction();
+
+    context.send_transaction_with_signers_with_transaction_result(
+        instruction,
+        &[merchant_authority],
+        with_profiling,
+    )?;
+
+    let settlement_batch = assert_settlement_batch_account(
+        context,
+        &settlement_batch_pda,
+        merchant_operator_config_pda,
+        mint,
+    );
+    assert_eq!(settlement_batch.bump, bump);
+    assert_eq!(settlement_batch.pending_amount, 0);
+    assert_eq!(settlement_batch.pending_count, 0);
+
+    Ok((settlement_batch_pda, bump))
+}
+
+pub fn assert_initialize_risk_state(
+    context: &mut TestContext,
+    merchant_operator_config_pda: &Pubkey,
+    buyer: &Pubkey,
+    fail_if_exists: bool,
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let (risk_state_pda, bump) = find_risk_state_pda(merchant_operator_config_pda, buyer);
+
+    if fail_if_exists {
+        assert_account_not_exists(context, &risk_state_pda);
+    }
+
+    let instruction = InitializeRiskStateBuilder::new()
+        .payer(context.payer.pubkey())
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .risk_state(risk_state_pda)
+        .bump(bump)
+        .buyer(*buyer)
+        .instruction();
+
+    context.send_transaction(instruction)?;
+
+    let risk_state = assert_risk_state_account(
+        context,
+        &risk_state_pda,
+        merchant_operator_config_pda,
+        buyer,
+    );
+    assert_eq!(risk_state.bump, bump);
+    assert_eq!(risk_state.daily_volume, 0);
+    assert_eq!(risk_state.daily_payment_count, 0);
+
+    Ok(risk_state_pda)
+}
+
+pub fn assert_initialize_buyer_index(
+    context: &mut TestContext,
+    merchant_operator_config_pda: &Pubkey,
+    buyer: &Pubkey,
+    fail_if_exists: bool,
+) -> Result<Pubkey, Box<dyn std::error::Error>> {
+    let (buyer_index_pda, bump) = find_buyer_index_pda(merchant_operator_config_pda, buyer);
+
+    if fail_if_exists {
+        assert_account_not_exists(context, &buyer_index_pda);
+    }
+
+    let instruction = InitializeBuyerIndexBuilder::new()
+        .payer(context.payer.pubkey())
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .buyer(*buyer)
+        .buyer_index(buyer_index_pda)
+        .bump(bump)
+        .instruction();
+
+    context.send_transaction(instruction)?;
+
+    let buyer_index = assert_buyer_index_account(
+        context,
+        &buyer_index_pda,
+        merchant_operator_config_pda,
+        buyer,
+    );
+    assert_eq!(buyer_index.bump, bump);
+    assert_eq!(buyer_index.payment_count, 0);
+
+    Ok(buyer_index_pda)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_create_order(
+    context: &mut TestContext,
+    operator_authority: &Keypair,
+    buyer: &Pubkey,
+    operator_pda: &Pubkey,
+    merchant_pda: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    order_id: u32,
+    expected_payments: u8,
+    fail_if_exists: bool,
+    with_profiling: bool,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&operator_authority.pubkey(), 1_000_000_000)?;
+
+    let (order_pda, bump) = find_order_pda(merchant_operator_config_pda, buyer, order_id);
+
+    if fail_if_exists {
+        assert_account_not_exists(context, &order_pda);
+    }
+
+    let instruction = CreateOrderBuilder::new()
+        .payer(context.payer.pubkey())
+        .operator_authority(operator_authority.pubkey())
+        .buyer(*buyer)
+        .operator(*operator_pda)
+        .merchant(*merchant_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .order(order_pda)
+        .order_id(order_id)
+        .expected_payments(expected_payments)
+        .bump(bump)
+        .instruction();
+
+    context.send_transaction_with_signers_with_transaction_result(
+        instruction,
+        &[operator_authority],
+        with_profiling,
+    )?;
+
+    let order = assert_order_account(
+        context,
+        &order_pda,
+        merchant_operator_config_pda,
+        buyer,
+        order_id,
+    );
+    assert_eq!(order.bump, bump);
+    assert_eq!(order.expected_payments, expected_payments);
+    assert_eq!(order.payment_count, 0);
+    assert_eq!(order.status, OrderStatus::Pending);
+
+    Ok((order_pda, bump))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_create_quote(
+    context: &mut TestContext,
+    operator_authority: &Keypair,
+    operator_pda: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    mint: &Pubkey,
+    order_id: u32,
+    amount: u64,
+    expires_at: i64,
+    fail_if_exists: bool,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    let (quote_pda, bump) = find_quote_pda(merchant_operator_config_pda, mint, order_id);
+
+    if fail_if_exists {
+        assert_account_not_exists(context, &quote_pda);
+    }
+
+    let instruction = CreateQuoteBuilder::new()
+        .payer(context.payer.pubkey())
+        .operator_authority(operator_authority.pubkey())
+        .operator(*operator_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .mint(*mint)
+        .quote(quote_pda)
+        .order_id(order_id)
+        .amount(amount)
+        .expires_at(expires_at)
+        .bump(bump)
+        .instruction();
+
+    context.send_transaction_with_signers(instruction, &[operator_authority])?;
+
+    let quote = assert_quote_account(
+        context,
+        &quote_pda,
+        merchant_operator_config_pda,
+        mint,
+        order_id,
+    );
+    assert_eq!(quote.bump, bump);
+    assert_eq!(quote.amount, amount);
+    assert_eq!(quote.expires_at, expires_at);
+    assert_eq!(quote.quoter, operator_authority.pubkey());
+
+    Ok((quote_pda, bump))
+}
+
+pub fn assert_initialize_config_registry_page(
+    context: &mut TestContext,
+    operator_pda: &Pubkey,
+    page: u32,
+    previous_page_pda: Option<&Pubkey>,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    let (config_registry_page_pda, bump) = find_config_registry_page_pda(operator_pda, page);
+
+    assert_account_not_exists(context, &config_registry_page_pda);
+
+    let mut builder = InitializeConfigRegistryPageBuilder::new();
+    builder
+        .payer(context.payer.pubkey())
+        .operator(*operator_pda)
+        .config_registry_page(config_registry_page_pda)
+        .page(page)
+        .bump(bump);
+    if let Some(previous_page_pda) = previous_page_pda {
+        builder.add_remaining_account(AccountMeta::new_readonly(*previous_page_pda, false));
+    }
+
+    context.send_transaction(builder.instruction())?;
+
+    let registry_page =
+        assert_config_registry_page_account(context, &config_registry_page_pda, operator_pda, page);
+    assert_eq!(registry_page.bump, bump);
+    assert_eq!(registry_page.count, 0);
+
+    Ok((config_registry_page_pda, bump))
+}
+
+pub fn assert_initialize_operator_freeze_list(
+    context: &mut TestContext,
+    authority: &Keypair,
+    operator_pda: &Pubkey,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    let (operator_freeze_list_pda, bump) = find_operator_freeze_list_pda(operator_pda);
+
+    assert_account_not_exists(context, &operator_freeze_list_pda);
+
+    let instruction = InitializeOperatorFreezeListBuilder::new()
+        .payer(context.payer.pubkey())
+        .authority(authority.pubkey())
+        .operator(*operator_pda)
+        .operator_freeze_list(operator_freeze_list_pda)
+        .bump(bump)
+        .instruction();
+
+    context.send_transaction_with_signers(instruction, &[authority])?;
+
+    let freeze_list =
+        assert_operator_freeze_list_account(context, &operator_freeze_list_pda, operator_pda);
+    assert_eq!(freeze_list.bump, bump);
+    assert_eq!(freeze_list.count, 0);
+
+    // The operator now requires its freeze list wherever it is checked
+    let operator = Operator::from_bytes(&context.get_account(operator_pda).unwrap().data)?;
+    assert!(operator.has_freeze_list);
+
+    Ok((operator_freeze_list_pda, bump))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_post_conversion_rate(
+    context: &mut TestContext,
+    operator_authority: &Keypair,
+    operator_pda: &Pubkey,
+    merchant_operator_config_pda: &Pubkey,
+    from_mint: &Pubkey,
+    to_mint: &Pubkey,
+    rate_numerator: u64,
+    rate_denominator: u64,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    let (conversion_rate_pda, bump) =
+        find_conversion_rate_pda(merchant_operator_config_pda, from_mint, to_mint);
+
+    let instruction = PostConversionRateBuilder::new()
+        .payer(context.payer.pubkey())
+        .operator_authority(operator_authority.pubkey())
+        .operator(*operator_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .from_mint(*from_mint)
+        .to_mint(*to_mint)
+        .conversion_rate(conversion_rate_pda)
+        .rate_numerator(rate_numerator)
+        .rate_denominator(rate_denominator)
+        .bump(bump)
+        .instruction();
+
+    context.send_transaction_with_signers(instruction, &[operator_authority])?;
+
+    let conversion_rate = assert_conversion_rate_account(
+        context,
+        &conversion_rate_pda,
+        merchant_operator_config_pda,
+        from_mint,
+        to_mint,
+    );
+    let now = context.svm.get_sysvar::<Clock>().unix_timestamp;
+    assert_eq!(conversion_rate.bump, bump);
+    assert_eq!(conversion_rate.rate_numerator, rate_numerator);
+    assert_eq!(conversion_rate.rate_denominator, rate_denominator);
+    assert_eq!(conversion_rate.posted_at, now);
+    assert_eq!(conversion_rate.attester, operator_authority.pubkey());
+
+    Ok((conversion_rate_pda, bump))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_initialize_operator_delegate(
+    context: &mut TestContext,
+    operator_authority: &Keypair,
+    operator_pda: &Pubkey,
+    delegate: &Pubkey,
+    can_make_payment: bool,
+    can_clear: bool,
+    can_refund: bool,
+    can_close: bool,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    assert_initialize_operator_delegate_with_refund_limits(
+        context,
+        operator_authority,
+        operator_pda,
+        delegate,
+        can_make_payment,
+        can_clear,
+        can_refund,
+        can_close,
+        0,
+        0,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_initialize_operator_delegate_with_refund_limits(
+    context: &mut TestContext,
+    operator_authority: &Keypair,
+    operator_pda: &Pubkey,
+    delegate: &Pubkey,
+    can_make_payment: bool,
+    can_clear: bool,
+    can_refund: bool,
+    can_close: bool,
+    max_refund_amount: u64,
+    max_daily_refund_amount: u64,
+) -> Result<(Pubkey, u8), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&operator_authority.pubkey(), 1_000_000_000)?;
 
-    let (merchant_pda, bump) = find_merchant_pda(&authority.pubkey());
+    let (operator_delegate_pda, bump) = find_operator_delegate_pda(operator_pda, delegate);
+    assert_account_not_exists(context, &operator_delegate_pda);
 
-    let instruction = UpdateMerchantSettlementWalletBuilder::new()
+    let instruction = InitializeOperatorDelegateBuilder::new()
         .payer(context.payer.pubkey())
-        .authority(authority.pubkey())
-        .merchant(merchant_pda)
-        .new_settlement_wallet(new_settlement_wallet.pubkey())
+        .authority(operator_authority.pubkey())
+        .operator(*operator_pda)
+        .operator_delegate(operator_delegate_pda)
+        .delegate(*delegate)
+        .bump(bump)
+        .can_make_payment(can_make_payment)
+        .can_clear(can_clear)
+        .can_refund(can_refund)
+        .can_close(can_close)
+        .max_refund_amount(max_refund_amount)
+        .max_daily_refund_amount(max_daily_refund_amount)
         .instruction();
 
-    context
-        .send_transaction_with_signers_with_transaction_result(
-            instruction,
-            &[authority],
-            with_profiling,
-        )
-        .expect("Update merchant settlement wallet should succeed");
-
-    assert_merchant_account(
-        context,
-        &merchant_pda,
-        &authority.pubkey(),
-        bump,
-        &new_settlement_wallet.pubkey(),
+    context.send_transaction_with_signers(instruction, &[operator_authority])?;
+
+    let operator_delegate =
+        assert_operator_delegate_account(context, &operator_delegate_pda, operator_pda, delegate);
+    assert_eq!(operator_delegate.bump, bump);
+    assert_eq!(operator_delegate.can_make_payment, can_make_payment);
+    assert_eq!(operator_delegate.can_clear, can_clear);
+    assert_eq!(operator_delegate.can_refund, can_refund);
+    assert_eq!(operator_delegate.can_close, can_close);
+    assert_eq!(operator_delegate.max_refund_amount, max_refund_amount);
+    assert_eq!(
+        operator_delegate.max_daily_refund_amount,
+        max_daily_refund_amount
     );
+    assert_eq!(operator_delegate.daily_refund_amount, 0);
 
-    Ok(())
+    Ok((operator_delegate_pda, bump))
 }
 
-pub fn assert_update_merchant_authority(
+/// Makes a compressed payment and mirrors the new leaf in `tree`.
+/// Returns the leaf index and the payment's created_at timestamp.
+#[allow(clippy::too_many_arguments)]
+pub fn assert_make_payment_compressed(
     context: &mut TestContext,
-    authority: &Keypair,
-    settlement_wallet: &Pubkey,
+    operator_authority: &Keypair,
+    buyer: &Keypair,
+    merchant_operator_config_pda: &Pubkey,
+    operator_pda: &Pubkey,
+    mint: &Pubkey,
+    order_id: u32,
+    amount: u64,
+    is_auto_settle: bool,
+    tree: &mut PaymentMerkleTree,
     with_profiling: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let new_authority = Keypair::new();
-    let (merchant_pda, bump) = find_merchant_pda(&authority.pubkey());
+) -> Result<(u32, i64), Box<dyn std::error::Error>> {
+    context.airdrop_if_required(&operator_authority.pubkey(), 1_000_000_000)?;
+    context.airdrop_if_required(&buyer.pubkey(), 1_000_000_000)?;
 
-    let instruction = UpdateMerchantAuthorityBuilder::new()
-        .payer(context.payer.pubkey())
-        .authority(authority.pubkey())
-        .merchant(merchant_pda)
-        .new_authority(new_authority.pubkey())
-        .instruction();
+    let (payment_tree_pda, _) = find_payment_tree_pda(merchant_operator_config_pda);
 
-    context
-        .send_transaction_with_signers_with_transaction_result(
-            instruction,
-            &[authority],
-            with_profiling,
-        )
-        .expect("Update merchant settlement wallet should succeed");
+    let merchant_operator_config_account = context
+        .get_account(merchant_operator_config_pda)
+        .expect("Merchant operator config should exist");
+    let merchant_operator_config = commerce_program_client::MerchantOperatorConfig::from_bytes(
+        &merchant_operator_config_account.data,
+    )
+    .expect("Should deserialize merchant operator config");
+    let merchant_pda = merchant_operator_config.merchant;
 
-    assert_merchant_account(
+    let merchant_account = context
+        .get_account(&merchant_pda)
+        .expect("Merchant should exist");
+    let merchant = commerce_program_client::Merchant::from_bytes(&merchant_account.data)
+        .expect("Should deserialize merchant");
+    let settlement_wallet = merchant.settlement_wallet;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), mint);
+    let escrow_authority = find_escrow_authority_pda(merchant_operator_config_pda, mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, mint);
+    let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, mint);
+
+    set_token_balance(
         context,
-        &merchant_pda,
-        &new_authority.pubkey(),
-        bump,
-        settlement_wallet,
+        &buyer_ata,
+        mint,
+        &buyer.pubkey(),
+        amount.saturating_mul(2),
     );
 
-    Ok(())
-}
+    get_or_create_associated_token_account(context, &merchant_pda, mint);
 
-pub fn assert_update_operator_authority(
-    context: &mut TestContext,
-    authority: &Keypair,
-    with_profiling: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let (operator_pda, bump) = find_operator_pda(&authority.pubkey());
+    if is_auto_settle {
+        get_or_create_associated_token_account(context, &settlement_wallet, mint);
+    }
 
-    let new_authority = Keypair::new();
+    let expected_receiver = if is_auto_settle {
+        merchant_settlement_ata
+    } else {
+        merchant_escrow_ata
+    };
 
-    let instruction = UpdateOperatorAuthorityBuilder::new()
-        .payer(context.payer.pubkey())
-        .authority(authority.pubkey())
-        .operator(operator_pda)
-        .new_operator_authority(new_authority.pubkey())
+    let pre_balances = [
+        get_token_balance(context, &buyer_ata),
+        get_token_balance(context, &expected_receiver),
+    ];
+
+    let instruction = MakePaymentCompressedBuilder::new()
+        .payment_tree(payment_tree_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .operator(*operator_pda)
+        .merchant(merchant_pda)
+        .merchant_operator_config(*merchant_operator_config_pda)
+        .mint(*mint)
+        .buyer_ata(buyer_ata)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .merchant_settlement_ata(merchant_settlement_ata)
+        .order_id(order_id)
+        .amount(amount)
         .instruction();
 
-    context
+    let transaction_metadata = context
         .send_transaction_with_signers_with_transaction_result(
             instruction,
-            &[authority],
+            &[operator_authority, buyer],
             with_profiling,
         )
-        .expect("Update operator authority should succeed");
+        .expect("Make compressed payment should succeed");
 
-    assert_operator_account(context, &operator_pda, &new_authority.pubkey(), bump);
+    let created_at = context.svm.get_sysvar::<Clock>().unix_timestamp;
+    let expected_status = if is_auto_settle {
+        Status::Cleared
+    } else {
+        Status::Paid
+    };
 
-    Ok(())
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        &buyer.pubkey(),
+        &merchant_pda,
+        operator_pda,
+        order_id,
+        PaymentLifecycleStatus::Uninitialized,
+        lifecycle_status(expected_status),
+        &buyer.pubkey(),
+    );
+
+    let leaf_index = tree.append(payment_leaf_hash(
+        &buyer.pubkey(),
+        mint,
+        order_id,
+        amount,
+        created_at,
+        expected_status,
+    ));
+
+    assert_payment_tree_account(
+        context,
+        &payment_tree_pda,
+        merchant_operator_config_pda,
+        leaf_index + 1,
+        tree.root(),
+    );
+
+    assert_token_balance_changes(
+        context,
+        pre_balances,
+        &buyer_ata,
+        &expected_receiver,
+        amount,
+    );
+
+    Ok((leaf_index, created_at))
 }
 
+/// Clears a compressed payment using a proof from `tree` and mirrors the updated leaf.
 #[allow(clippy::too_many_arguments)]
-pub fn assert_close_payment(
+pub fn assert_clear_payment_compressed(
     context: &mut TestContext,
     payer: &Keypair,
-    payment_pda: &Pubkey,
+    operator_authority: &Keypair,
     buyer: &Pubkey,
-    merchant_pda: &Pubkey,
-    operator_pda: &Pubkey,
-    merchant_operator_config_pda: &Pubkey,
     mint: &Pubkey,
-    operator_authority: &Keypair,
+    merchant_operator_config_pda: &Pubkey,
+    leaf_index: u32,
+    order_id: u32,
+    amount: u64,
+    created_at: i64,
+    tree: &mut PaymentMerkleTree,
     with_profiling: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     context.airdrop_if_required(&payer.pubkey(), 1_000_000_000)?;
+    context.airdrop_if_required(&operator_authority.pubkey(), 1_000_000_000)?;
 
-    // Get initial balance of fee payer to verify lamport transfer
-    let initial_payer_balance = context
-        .get_account(&payer.pubkey())
-        .map(|a| a.lamports)
-        .unwrap_or(0);
+    let (payment_tree_pda, _) = find_payment_tree_pda(merchant_operator_config_pda);
 
-    // Create close payment instruction
-    let instruction = ClosePaymentBuilder::new()
+    let merchant_operator_config_account = context
+        .get_account(merchant_operator_config_pda)
+        .expect("Merchant operator config should exist");
+    let (merchant_operator_config, policies, _) =
+        decode_merchant_operator_config(&merchant_operator_config_account.data)
+            .expect("Should deserialize merchant operator config");
+    let merchant_pda = merchant_operator_config.merchant;
+    let operator_pda = merchant_operator_config.operator;
+    let operator_fee = merchant_operator_config.operator_fee;
+    let fee_type = merchant_operator_config.fee_type;
+
+    let merchant_account = context
+        .get_account(&merchant_pda)
+        .expect("Merchant should exist");
+    let merchant = commerce_program_client::Merchant::from_bytes(&merchant_account.data)
+        .expect("Should deserialize merchant");
+    let settlement_wallet = merchant.settlement_wallet;
+
+    let escrow_authority = find_escrow_authority_pda(merchant_operator_config_pda, mint).0;
+    let merchant_escrow_ata = get_associated_token_address(&escrow_authority, mint);
+    let merchant_settlement_ata = get_associated_token_address(&settlement_wallet, mint);
+    let operator_settlement_ata = get_associated_token_address(&operator_authority.pubkey(), mint);
+
+    get_or_create_associated_token_account(context, &settlement_wallet, mint);
+    get_or_create_associated_token_account(context, &operator_authority.pubkey(), mint);
+
+    let pre_balances = vec![
+        (
+            merchant_escrow_ata,
+            get_token_balance(context, &merchant_escrow_ata),
+        ),
+        (
+            merchant_settlement_ata,
+            get_token_balance(context, &merchant_settlement_ata),
+        ),
+        (
+            operator_settlement_ata,
+            get_token_balance(context, &operator_settlement_ata),
+        ),
+    ];
+
+    let proof = tree.proof(leaf_index).expect("Leaf should exist in tree");
+
+    let instruction = ClearPaymentCompressedBuilder::new()
         .payer(payer.pubkey())
-        .payment(*payment_pda)
+        .payment_tree(payment_tree_pda)
         .operator_authority(operator_authority.pubkey())
-        .operator(*operator_pda)
-        .merchant(*merchant_pda)
         .buyer(*buyer)
+        .merchant(merchant_pda)
+        .operator(operator_pda)
         .merchant_operator_config(*merchant_operator_config_pda)
         .mint(*mint)
-        .system_program(SYSTEM_PROGRAM_ID)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .merchant_settlement_ata(merchant_settlement_ata)
+        .operator_settlement_ata(operator_settlement_ata)
+        .leaf_index(leaf_index)
+        .order_id(order_id)
+        .amount(amount)
+        .created_at(created_at)
+        .proof(proof)
         .instruction();
 
-    // Send transaction with required signers
-    context
+    let transaction_metadata = context
         .send_transaction_with_signers_with_transaction_result(
             instruction,
-            &[payer, operator_authority],
+            &[operator_authority],
             with_profiling,
         )
-        .expect("Close payment should succeed");
+        .expect("Clear compressed payment should succeed");
 
-    // Verify payment account is closed (balance should be 0)
-    let final_payment_balance = context
-        .get_account(payment_pda)
-        .map(|a| a.lamports)
-        .unwrap_or(0);
-    assert_eq!(final_payment_balance, 0, "Payment account should be closed");
+    assert_payment_status_changed_event_present(
+        &transaction_metadata,
+        buyer,
+        &merchant_pda,
+        &operator_pda,
+        order_id,
+        PaymentLifecycleStatus::Paid,
+        PaymentLifecycleStatus::Cleared,
+        &operator_authority.pubkey(),
+    );
 
-    // Verify lamports were transferred to fee payer
-    let final_payer_balance = context
-        .get_account(&payer.pubkey())
-        .map(|a| a.lamports)
-        .unwrap_or(0);
-    assert!(
-        final_payer_balance > initial_payer_balance,
-        "Fee payer should receive lamports from closed account"
+    tree.replace(
+        leaf_index,
+        payment_leaf_hash(buyer, mint, order_id, amount, created_at, Status::Cleared),
+    );
+
+    assert_payment_tree_account(
+        context,
+        &payment_tree_pda,
+        merchant_operator_config_pda,
+        tree.leaves().len() as u32,
+        tree.root(),
     );
 
+    let (expected_operator_fee, expected_merchant_amount) =
+        calculate_fees(amount, operator_fee, fee_type, &policies)
+            .expect("Fee calculation should not overflow");
+
+    let balance_changes = vec![
+        BalanceChange {
+            ata: merchant_escrow_ata,
+            expected_change: -(amount as i128),
+            description: "Escrow balance should decrease by payment amount".to_string(),
+        },
+        BalanceChange {
+            ata: merchant_settlement_ata,
+            expected_change: expected_merchant_amount as i128,
+            description: "Merchant balance should increase by payment amount minus operator fee"
+                .to_string(),
+        },
+        BalanceChange {
+            ata: operator_settlement_ata,
+            expected_change: expected_operator_fee as i128,
+            description: "Operator balance should increase by operator fee".to_string(),
+        },
+    ];
+
+    assert_multiple_token_balance_changes(context, &pre_balances, &balance_changes);
+
     Ok(())
 }
@@ -0,0 +1,257 @@
+use crate::{
+    assertions::assert_account_not_exists,
+    state_utils::*,
+    utils::{
+        assert_payment_status_changed_event_present, assert_program_error,
+        get_or_create_associated_token_account, TestContext, DAYS_TO_CLOSE,
+        INVALID_ACCOUNT_DATA_ERROR, INVALID_INSTRUCTION_DATA_ERROR, INVALID_PAYMENT_STATUS_ERROR,
+        PAYMENT_CANNOT_BE_CLOSED_ERROR, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    instructions::SweepClosedPaymentsBuilder,
+    sweep::{sweep_remaining_accounts, SweptPayment},
+    types::{FeeType, PaymentLifecycleStatus},
+    Payment,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const CLOSE_DELAY_SECONDS: i64 = (DAYS_TO_CLOSE as i64 + 1) * 24 * 60 * 60;
+
+struct SweepTestSetup {
+    context: TestContext,
+    operator_authority: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payments: Vec<Pubkey>,
+}
+
+// Helper function to set up a config with `num_payments` payments, the first `num_cleared` of
+// them cleared
+fn setup_sweep_test(
+    num_payments: u32,
+    num_cleared: u32,
+) -> Result<SweepTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    get_or_create_associated_token_account(&mut context, &buyer.pubkey(), &USDC_MINT);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false)?;
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let mut payments = vec![];
+    for order_id in 1..=num_payments {
+        let (payment_pda, _) = assert_make_payment(
+            &mut context,
+            &operator_authority,
+            &operator_authority,
+            &buyer,
+            &merchant_operator_config_pda,
+            &operator_pda,
+            &USDC_MINT,
+            order_id,
+            1_000_000,
+            true,  // fail_if_exists
+            false, // is_auto_settle
+            false,
+        )?;
+
+        if order_id <= num_cleared {
+            assert_clear_payment(
+                &mut context,
+                &operator_authority,
+                &operator_authority,
+                &buyer,
+                &payment_pda,
+                &USDC_MINT,
+                &merchant_operator_config_pda,
+                false,
+            )?;
+        }
+
+        payments.push(payment_pda);
+    }
+
+    Ok(SweepTestSetup {
+        context,
+        operator_authority,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payments,
+    })
+}
+
+fn sweep_instruction(
+    setup: &SweepTestSetup,
+    rent_collector: &Pubkey,
+    payments: &[SweptPayment],
+) -> Instruction {
+    SweepClosedPaymentsBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .operator_authority(setup.operator_authority.pubkey())
+        .operator(setup.operator_pda)
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .rent_collector(*rent_collector)
+        .num_payments(payments.len() as u8)
+        .add_remaining_accounts(&sweep_remaining_accounts(payments))
+        .instruction()
+}
+
+fn swept_payments(setup: &SweepTestSetup) -> Vec<SweptPayment> {
+    setup
+        .payments
+        .iter()
+        .map(|payment| SweptPayment {
+            payment: *payment,
+            buyer: setup.buyer.pubkey(),
+            mint: USDC_MINT,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_sweep_closed_payments_success() {
+    let mut setup = setup_sweep_test(3, 3).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+    let rent_collector = Pubkey::new_unique();
+
+    let payments = setup.payments.clone();
+    let total_rent: u64 = payments
+        .iter()
+        .map(|payment| setup.context.get_account(payment).unwrap().lamports)
+        .sum();
+    let order_ids: Vec<u32> = payments
+        .iter()
+        .map(|payment| {
+            let account = setup.context.get_account(payment).unwrap();
+            Payment::from_bytes(&account.data).unwrap().order_id
+        })
+        .collect();
+
+    setup.context.advance_clock(CLOSE_DELAY_SECONDS);
+
+    let instruction = sweep_instruction(&setup, &rent_collector, &swept_payments(&setup));
+    let transaction_metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&operator_authority],
+            false,
+        )
+        .expect("Should sweep closable payments");
+
+    for payment in &payments {
+        assert_account_not_exists(&mut setup.context, payment);
+    }
+    for order_id in order_ids {
+        assert_payment_status_changed_event_present(
+            &transaction_metadata,
+            &setup.buyer.pubkey(),
+            &setup.merchant_pda,
+            &setup.operator_pda,
+            order_id,
+            PaymentLifecycleStatus::Cleared,
+            PaymentLifecycleStatus::Closed,
+            &operator_authority.pubkey(),
+        );
+    }
+    assert_eq!(
+        setup.context.get_account(&rent_collector).unwrap().lamports,
+        total_rent
+    );
+}
+
+#[tokio::test]
+async fn test_sweep_closed_payments_too_early_fails() {
+    let mut setup = setup_sweep_test(2, 2).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    let instruction = sweep_instruction(&setup, &Pubkey::new_unique(), &swept_payments(&setup));
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, PAYMENT_CANNOT_BE_CLOSED_ERROR);
+}
+
+#[tokio::test]
+async fn test_sweep_closed_payments_with_paid_payment_fails() {
+    // The last payment is still in escrow, which fails the whole sweep
+    let mut setup = setup_sweep_test(2, 1).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    setup.context.advance_clock(CLOSE_DELAY_SECONDS);
+
+    let instruction = sweep_instruction(&setup, &Pubkey::new_unique(), &swept_payments(&setup));
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, INVALID_PAYMENT_STATUS_ERROR);
+}
+
+#[tokio::test]
+async fn test_sweep_closed_payments_wrong_buyer_fails() {
+    let mut setup = setup_sweep_test(1, 1).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    setup.context.advance_clock(CLOSE_DELAY_SECONDS);
+
+    let payments = [SweptPayment {
+        payment: setup.payments[0],
+        buyer: Keypair::new().pubkey(),
+        mint: USDC_MINT,
+    }];
+    let instruction = sweep_instruction(&setup, &Pubkey::new_unique(), &payments);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, INVALID_ACCOUNT_DATA_ERROR);
+}
+
+#[tokio::test]
+async fn test_sweep_closed_payments_without_payments_fails() {
+    let mut setup = setup_sweep_test(1, 1).unwrap();
+    let operator_authority = setup.operator_authority.insecure_clone();
+
+    let instruction = sweep_instruction(&setup, &Pubkey::new_unique(), &[]);
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority]);
+
+    assert_program_error(result, INVALID_INSTRUCTION_DATA_ERROR);
+}
@@ -0,0 +1,471 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, find_escrow_authority_pda, find_payment_pda,
+        find_refund_approval_pda, idempotency_key, TestContext, DAYS_TO_CLOSE,
+        INVALID_ACCOUNT_OWNER_ERROR, TRANSFER_HOOK_ACCOUNTS_MISSING_ERROR,
+        UNSUPPORTED_MINT_EXTENSION_ERROR,
+    },
+};
+use commerce_program_client::{
+    instructions::{
+        ApproveRefundBuilder, ClaimRefundBuilder, MakePaymentBuilder, RefundPaymentBuilder,
+    },
+    transfer_hook::TOKEN_2022_PROGRAM_ID,
+    types::FeeType,
+};
+use solana_program_pack::Pack;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_option::COption,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token::{
+    state::{Account as TokenAccount, AccountState, Mint},
+    ID as TOKEN_PROGRAM_ID,
+};
+
+const HOOK_PROGRAM: Pubkey = Pubkey::new_from_array([42; 32]);
+
+const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+const TRANSFER_HOOK_EXTENSION: u16 = 14;
+
+/// TransferHook extension value: its authority, then its program.
+fn transfer_hook(program: &Pubkey) -> Vec<u8> {
+    let mut value = vec![0u8; 32];
+    value.extend_from_slice(program.as_ref());
+    value
+}
+
+/// Sets a Token-2022 mint with `extensions`, as (type, value) pairs.
+fn set_token_2022_mint(context: &mut TestContext, mint: &Pubkey, extensions: &[(u16, Vec<u8>)]) {
+    let mut data = vec![0u8; Mint::LEN];
+    Mint::pack(
+        Mint {
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+            mint_authority: COption::None,
+            supply: 1_000_000_000,
+        },
+        &mut data,
+    )
+    .expect("Failed to pack mint account");
+
+    if !extensions.is_empty() {
+        // Padded to the size of a token account, then the Mint account type and the
+        // extensions as type, length and value entries
+        data.resize(TokenAccount::LEN, 0);
+        data.push(1);
+        for (extension_type, value) in extensions {
+            data.extend_from_slice(&extension_type.to_le_bytes());
+            data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+    }
+
+    context
+        .svm
+        .set_account(
+            *mint,
+            Account {
+                lamports: 1_000_000_000,
+                data,
+                owner: TOKEN_2022_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .expect("Failed to set mint account");
+}
+
+fn set_token_2022_balance(
+    context: &mut TestContext,
+    ata: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) {
+    let mut data = vec![0u8; TokenAccount::LEN];
+    TokenAccount::pack(
+        TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        },
+        &mut data,
+    )
+    .expect("Failed to pack token account");
+
+    context
+        .svm
+        .set_account(
+            *ata,
+            Account {
+                lamports: 2039280,
+                data,
+                owner: TOKEN_2022_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .expect("Failed to set token account");
+}
+
+/// Balance of a Token-2022 account, whose extensions follow the base account.
+fn get_token_2022_balance(context: &mut TestContext, ata: &Pubkey) -> u64 {
+    let account = context
+        .get_account(ata)
+        .expect("Token account should exist");
+    TokenAccount::unpack_from_slice(&account.data[..TokenAccount::LEN])
+        .expect("Should deserialize token account")
+        .amount
+}
+
+fn ata(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(wallet, mint, &TOKEN_2022_PROGRAM_ID)
+}
+
+struct Token2022Test {
+    context: TestContext,
+    operator_authority: Keypair,
+    settlement_wallet: Keypair,
+    buyer: Keypair,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    mint: Pubkey,
+}
+
+async fn setup_token_2022_test(extensions: &[(u16, Vec<u8>)]) -> Token2022Test {
+    let mut context = TestContext::new();
+    let operator_authority = context.payer.insecure_clone();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+    let mint = Pubkey::new_unique();
+
+    set_token_2022_mint(&mut context, &mint, extensions);
+
+    let (operator_pda, _) =
+        assert_get_or_create_operator(&mut context, &operator_authority, true, false).unwrap();
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )
+    .unwrap();
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1,
+        0,
+        FeeType::Bps,
+        0,
+        DAYS_TO_CLOSE,
+        vec![],
+        vec![mint],
+        true,
+        false,
+    )
+    .unwrap();
+
+    Token2022Test {
+        context,
+        operator_authority,
+        settlement_wallet,
+        buyer,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        mint,
+    }
+}
+
+fn make_payment_instruction(
+    test: &Token2022Test,
+    order_id: u32,
+    amount: u64,
+    token_program: Pubkey,
+) -> Instruction {
+    let (payment_pda, _) = find_payment_pda(
+        &test.merchant_operator_config_pda,
+        &test.buyer.pubkey(),
+        &test.mint,
+        order_id,
+    );
+    let (escrow_authority, _) =
+        find_escrow_authority_pda(&test.merchant_operator_config_pda, &test.mint);
+
+    MakePaymentBuilder::new()
+        .payer(test.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(test.operator_authority.pubkey())
+        .buyer(test.buyer.pubkey())
+        .operator(test.operator_pda)
+        .merchant(test.merchant_pda)
+        .merchant_operator_config(test.merchant_operator_config_pda)
+        .mint(test.mint)
+        .buyer_ata(ata(&test.buyer.pubkey(), &test.mint))
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(ata(&escrow_authority, &test.mint))
+        .merchant_settlement_ata(ata(&test.settlement_wallet.pubkey(), &test.mint))
+        .settlement_wallet(test.settlement_wallet.pubkey())
+        .token_program(token_program)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .order_id(order_id)
+        .idempotency_key(idempotency_key(order_id))
+        .amount(amount)
+        .instruction()
+}
+
+#[tokio::test]
+async fn test_make_and_refund_payment_token_2022_mint() {
+    let mut test = setup_token_2022_test(&[]).await;
+    let order_id = 1u32;
+    let amount = 1_000_000u64;
+
+    let buyer_ata = ata(&test.buyer.pubkey(), &test.mint);
+    let (escrow_authority, _) =
+        find_escrow_authority_pda(&test.merchant_operator_config_pda, &test.mint);
+    let merchant_escrow_ata = ata(&escrow_authority, &test.mint);
+    set_token_2022_balance(
+        &mut test.context,
+        &buyer_ata,
+        &test.mint,
+        &test.buyer.pubkey(),
+        amount,
+    );
+
+    let instruction = make_payment_instruction(&test, order_id, amount, TOKEN_2022_PROGRAM_ID);
+    let operator_authority = test.operator_authority.insecure_clone();
+    let buyer = test.buyer.insecure_clone();
+    test.context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+        .expect("Make payment with a Token-2022 mint should succeed");
+
+    assert_eq!(get_token_2022_balance(&mut test.context, &buyer_ata), 0);
+    assert_eq!(
+        get_token_2022_balance(&mut test.context, &merchant_escrow_ata),
+        amount
+    );
+
+    let (payment_pda, _) = find_payment_pda(
+        &test.merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &test.mint,
+        order_id,
+    );
+    let instruction = RefundPaymentBuilder::new()
+        .payer(test.context.payer.pubkey())
+        .payment(payment_pda)
+        .operator_authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .merchant(test.merchant_pda)
+        .operator(test.operator_pda)
+        .merchant_operator_config(test.merchant_operator_config_pda)
+        .mint(test.mint)
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .buyer_ata(buyer_ata)
+        .token_program(TOKEN_2022_PROGRAM_ID)
+        .system_program(SYSTEM_PROGRAM_ID)
+        .instruction();
+    test.context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Refund payment with a Token-2022 mint should succeed");
+
+    assert_eq!(
+        get_token_2022_balance(&mut test.context, &buyer_ata),
+        amount
+    );
+    assert_eq!(
+        get_token_2022_balance(&mut test.context, &merchant_escrow_ata),
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_approve_and_claim_refund_token_2022_mint() {
+    let mut test = setup_token_2022_test(&[]).await;
+    let order_id = 1u32;
+    let amount = 1_000_000u64;
+
+    let buyer_ata = ata(&test.buyer.pubkey(), &test.mint);
+    let (escrow_authority, _) =
+        find_escrow_authority_pda(&test.merchant_operator_config_pda, &test.mint);
+    let merchant_escrow_ata = ata(&escrow_authority, &test.mint);
+    set_token_2022_balance(
+        &mut test.context,
+        &buyer_ata,
+        &test.mint,
+        &test.buyer.pubkey(),
+        amount,
+    );
+
+    let instruction = make_payment_instruction(&test, order_id, amount, TOKEN_2022_PROGRAM_ID);
+    let operator_authority = test.operator_authority.insecure_clone();
+    let buyer = test.buyer.insecure_clone();
+    test.context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer])
+        .expect("Make payment with a Token-2022 mint should succeed");
+
+    let (payment_pda, _) = find_payment_pda(
+        &test.merchant_operator_config_pda,
+        &buyer.pubkey(),
+        &test.mint,
+        order_id,
+    );
+    let (refund_approval_pda, _) = find_refund_approval_pda(&payment_pda);
+    let instruction = ApproveRefundBuilder::new()
+        .payer(test.context.payer.pubkey())
+        .payment(payment_pda)
+        .authority(operator_authority.pubkey())
+        .buyer(buyer.pubkey())
+        .merchant(test.merchant_pda)
+        .operator(test.operator_pda)
+        .merchant_operator_config(test.merchant_operator_config_pda)
+        .mint(test.mint)
+        .refund_approval(refund_approval_pda)
+        .instruction();
+    test.context
+        .send_transaction_with_signers(instruction, &[&operator_authority])
+        .expect("Approve refund with a Token-2022 mint should succeed");
+
+    // Claimed into a Token-2022 account of the buyer other than its ATA
+    let destination = Pubkey::new_unique();
+    set_token_2022_balance(
+        &mut test.context,
+        &destination,
+        &test.mint,
+        &buyer.pubkey(),
+        0,
+    );
+    let instruction = ClaimRefundBuilder::new()
+        .payer(test.context.payer.pubkey())
+        .payment(payment_pda)
+        .buyer(buyer.pubkey())
+        .merchant(test.merchant_pda)
+        .operator(test.operator_pda)
+        .merchant_operator_config(test.merchant_operator_config_pda)
+        .mint(test.mint)
+        .refund_approval(refund_approval_pda)
+        .rent_payer(test.context.payer.pubkey())
+        .escrow_authority(escrow_authority)
+        .merchant_escrow_ata(merchant_escrow_ata)
+        .destination(destination)
+        .token_program(TOKEN_2022_PROGRAM_ID)
+        .instruction();
+    test.context
+        .send_transaction_with_signers(instruction, &[&buyer])
+        .expect("Claim refund with a Token-2022 mint should succeed");
+
+    assert_eq!(
+        get_token_2022_balance(&mut test.context, &destination),
+        amount
+    );
+    assert_eq!(
+        get_token_2022_balance(&mut test.context, &merchant_escrow_ata),
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_make_payment_token_2022_mint_with_token_program_fails() {
+    let mut test = setup_token_2022_test(&[]).await;
+    let amount = 1_000_000u64;
+
+    let buyer_ata = ata(&test.buyer.pubkey(), &test.mint);
+    set_token_2022_balance(
+        &mut test.context,
+        &buyer_ata,
+        &test.mint,
+        &test.buyer.pubkey(),
+        amount,
+    );
+
+    // The mint is owned by Token-2022, not the token program passed
+    let instruction = make_payment_instruction(&test, 1, amount, TOKEN_PROGRAM_ID);
+    let operator_authority = test.operator_authority.insecure_clone();
+    let buyer = test.buyer.insecure_clone();
+    let result = test
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+
+    assert_program_error(result, INVALID_ACCOUNT_OWNER_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_transfer_hook_accounts_missing_fails() {
+    let mut test =
+        setup_token_2022_test(&[(TRANSFER_HOOK_EXTENSION, transfer_hook(&HOOK_PROGRAM))]).await;
+    let amount = 1_000_000u64;
+
+    let buyer_ata = ata(&test.buyer.pubkey(), &test.mint);
+    set_token_2022_balance(
+        &mut test.context,
+        &buyer_ata,
+        &test.mint,
+        &test.buyer.pubkey(),
+        amount,
+    );
+
+    let operator_authority = test.operator_authority.insecure_clone();
+    let buyer = test.buyer.insecure_clone();
+
+    // No hook accounts
+    let instruction = make_payment_instruction(&test, 1, amount, TOKEN_2022_PROGRAM_ID);
+    let result = test
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+    assert_program_error(result, TRANSFER_HOOK_ACCOUNTS_MISSING_ERROR);
+
+    // The hook program without its ExtraAccountMetaList PDA
+    let mut instruction = make_payment_instruction(&test, 1, amount, TOKEN_2022_PROGRAM_ID);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(HOOK_PROGRAM, false));
+    let result = test
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+    assert_program_error(result, TRANSFER_HOOK_ACCOUNTS_MISSING_ERROR);
+}
+
+#[tokio::test]
+async fn test_make_payment_transfer_fee_mint_fails() {
+    let mut test = setup_token_2022_test(&[(TRANSFER_FEE_CONFIG_EXTENSION, vec![0; 108])]).await;
+    let amount = 1_000_000u64;
+
+    let buyer_ata = ata(&test.buyer.pubkey(), &test.mint);
+    set_token_2022_balance(
+        &mut test.context,
+        &buyer_ata,
+        &test.mint,
+        &test.buyer.pubkey(),
+        amount,
+    );
+
+    // Escrow would receive less than the payment amount
+    let instruction = make_payment_instruction(&test, 1, amount, TOKEN_2022_PROGRAM_ID);
+    let operator_authority = test.operator_authority.insecure_clone();
+    let buyer = test.buyer.insecure_clone();
+    let result = test
+        .context
+        .send_transaction_with_signers(instruction, &[&operator_authority, &buyer]);
+
+    assert_program_error(result, UNSUPPORTED_MINT_EXTENSION_ERROR);
+}
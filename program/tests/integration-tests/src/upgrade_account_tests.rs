@@ -0,0 +1,251 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, set_token_balance, TestContext, TestContextBuilder,
+        ACCOUNT_NOT_UPGRADABLE_ERROR, DAYS_TO_CLOSE, USDC_MINT,
+    },
+};
+use commerce_program_client::{
+    config_hash::verify_config_hash,
+    instructions::UpgradeAccountBuilder,
+    types::{FeeType, PolicyData, RefundPolicy},
+    Merchant, MerchantOperatorConfig, Operator, Payment, COMMERCE_PROGRAM_ID,
+};
+use solana_sdk::{clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+
+const PAYMENT_AMOUNT: u64 = 1_000_000;
+
+/// Size of an Operator account before `pending_authority` and `last_active_at`
+const LEGACY_OPERATOR_LEN: usize = 34;
+/// Size of a Merchant account before its authority handover and settlement wallet timelock
+const LEGACY_MERCHANT_LEN: usize = 66;
+/// Size of a Payment account before its idempotency key, fee override, references and rent
+/// payer
+const LEGACY_PAYMENT_LEN: usize = 23;
+/// Size of a MerchantOperatorConfig header before `config_hash`, `decommissioned_at` and
+/// `payment_accounts`
+const LEGACY_CONFIG_HEADER_LEN: usize = 93;
+
+struct UpgradeAccountTestSetup {
+    context: TestContext,
+    operator_pda: Pubkey,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+    payment_pda: Pubkey,
+}
+
+// Helper function to set up a merchant, operator, config and one paid payment
+fn setup_upgrade_account_test() -> Result<UpgradeAccountTestSetup, Box<dyn std::error::Error>> {
+    let mut context = TestContextBuilder::new()
+        .with_usdc()
+        .with_operator()
+        .build();
+    let operator_authority = context.payer.insecure_clone();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+    let buyer = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        500u64, // 5%
+        FeeType::Bps,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![PolicyData::Refund(RefundPolicy {
+            max_amount: PAYMENT_AMOUNT,
+            max_time_after_purchase: 3600,
+        })],
+        vec![USDC_MINT],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    let buyer_ata = get_associated_token_address(&buyer.pubkey(), &USDC_MINT);
+    set_token_balance(
+        &mut context,
+        &buyer_ata,
+        &USDC_MINT,
+        &buyer.pubkey(),
+        10 * PAYMENT_AMOUNT,
+    );
+
+    let (payment_pda, _) = assert_make_payment(
+        &mut context,
+        &operator_authority,
+        &operator_authority,
+        &buyer,
+        &merchant_operator_config_pda,
+        &operator_pda,
+        &USDC_MINT,
+        1,
+        PAYMENT_AMOUNT,
+        true,
+        false,
+        false,
+    )?;
+
+    Ok(UpgradeAccountTestSetup {
+        context,
+        operator_pda,
+        merchant_pda,
+        merchant_operator_config_pda,
+        payment_pda,
+    })
+}
+
+/// Rewrites `account` with `data` in a legacy layout, funded only for its legacy size.
+fn set_legacy_account(context: &mut TestContext, account: &Pubkey, data: Vec<u8>) {
+    let lamports = context.svm.minimum_balance_for_rent_exemption(data.len());
+    context.create_account(account, &COMMERCE_PROGRAM_ID, data, lamports);
+}
+
+fn upgrade_account(
+    context: &mut TestContext,
+    account: &Pubkey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let instruction = UpgradeAccountBuilder::new()
+        .payer(context.payer.pubkey())
+        .account(*account)
+        .instruction();
+    context.send_transaction(instruction)
+}
+
+/// Upgrades `account`, whose data was set to a legacy layout, and checks it ends up as
+/// `expected`, rent-exempt and no longer upgradable.
+fn assert_upgrade_account(context: &mut TestContext, account: &Pubkey, expected: &[u8]) {
+    upgrade_account(context, account).expect("Upgrade account should succeed");
+
+    let upgraded = context.get_account(account).unwrap();
+    assert_eq!(upgraded.data, expected);
+    assert_eq!(
+        upgraded.lamports,
+        context
+            .svm
+            .minimum_balance_for_rent_exemption(upgraded.data.len())
+    );
+
+    let result = upgrade_account(context, account);
+    assert_program_error(result, ACCOUNT_NOT_UPGRADABLE_ERROR);
+}
+
+#[tokio::test]
+async fn test_upgrade_legacy_operator() {
+    let mut setup = setup_upgrade_account_test().unwrap();
+    let operator_pda = setup.operator_pda;
+    let data = setup.context.get_account_data(&operator_pda).unwrap();
+    set_legacy_account(
+        &mut setup.context,
+        &operator_pda,
+        data[..LEGACY_OPERATOR_LEN].to_vec(),
+    );
+
+    // A legacy operator is active from its upgrade, with no pending authority or freeze list,
+    // and its owner is the one its PDA was derived from
+    let now = setup.context.svm.get_sysvar::<Clock>().unix_timestamp;
+    let mut expected = data[..LEGACY_OPERATOR_LEN].to_vec();
+    expected.extend_from_slice(&[0; 32]);
+    expected.extend_from_slice(&now.to_le_bytes());
+    expected.push(0);
+    expected.extend_from_slice(&data[1..33]);
+    assert_upgrade_account(&mut setup.context, &operator_pda, &expected);
+
+    let operator = Operator::from_bytes(&expected).unwrap();
+    assert_eq!(operator.last_active_at, now);
+    assert_eq!(operator.original_owner, operator.owner);
+}
+
+#[tokio::test]
+async fn test_upgrade_legacy_merchant() {
+    let mut setup = setup_upgrade_account_test().unwrap();
+    let merchant_pda = setup.merchant_pda;
+    let data = setup.context.get_account_data(&merchant_pda).unwrap();
+    set_legacy_account(
+        &mut setup.context,
+        &merchant_pda,
+        data[..LEGACY_MERCHANT_LEN].to_vec(),
+    );
+
+    let mut expected = data[..LEGACY_MERCHANT_LEN].to_vec();
+    expected.resize(Merchant::LEN - 32, 0);
+    expected.extend_from_slice(&data[1..33]);
+    assert_upgrade_account(&mut setup.context, &merchant_pda, &expected);
+
+    let merchant = Merchant::from_bytes(&expected).unwrap();
+    assert_eq!(merchant.original_owner, merchant.owner);
+}
+
+#[tokio::test]
+async fn test_upgrade_legacy_payment() {
+    let mut setup = setup_upgrade_account_test().unwrap();
+    let payment_pda = setup.payment_pda;
+    let data = setup.context.get_account_data(&payment_pda).unwrap();
+    set_legacy_account(
+        &mut setup.context,
+        &payment_pda,
+        data[..LEGACY_PAYMENT_LEN].to_vec(),
+    );
+
+    let mut expected = data[..LEGACY_PAYMENT_LEN].to_vec();
+    expected.resize(Payment::LEN, 0);
+    assert_upgrade_account(&mut setup.context, &payment_pda, &expected);
+
+    let payment = Payment::from_bytes(&expected).unwrap();
+    assert_eq!(payment.amount, PAYMENT_AMOUNT);
+}
+
+#[tokio::test]
+async fn test_upgrade_legacy_merchant_operator_config() {
+    let mut setup = setup_upgrade_account_test().unwrap();
+    let config_pda = setup.merchant_operator_config_pda;
+    let data = setup.context.get_account_data(&config_pda).unwrap();
+
+    // The legacy header runs straight into the policies and accepted currencies, with no
+    // open-payment count after them
+    let num_currencies = MerchantOperatorConfig::from_bytes(&data)
+        .unwrap()
+        .num_accepted_currencies as usize;
+    let currencies_end = data.len() - num_currencies * 4;
+    let mut legacy = data[..LEGACY_CONFIG_HEADER_LEN].to_vec();
+    legacy.extend_from_slice(&data[MerchantOperatorConfig::LEN..currencies_end]);
+    set_legacy_account(&mut setup.context, &config_pda, legacy);
+
+    // The upgrade restores the hash of the config's terms and leaves it active. Payments
+    // made before it were never counted, so every count starts at zero.
+    let mut expected = data[..currencies_end].to_vec();
+    expected[MerchantOperatorConfig::LEN - 4..MerchantOperatorConfig::LEN].fill(0);
+    expected.resize(data.len(), 0);
+    assert_upgrade_account(&mut setup.context, &config_pda, &expected);
+    verify_config_hash(&expected).unwrap();
+    let config = MerchantOperatorConfig::from_bytes(&expected).unwrap();
+    assert_eq!(config.decommissioned_at, 0);
+    assert_eq!(config.payment_accounts, 0);
+}
+
+#[tokio::test]
+async fn test_upgrade_current_account_fails() {
+    let mut setup = setup_upgrade_account_test().unwrap();
+
+    for account in [
+        setup.operator_pda,
+        setup.merchant_pda,
+        setup.merchant_operator_config_pda,
+        setup.payment_pda,
+    ] {
+        let result = upgrade_account(&mut setup.context, &account);
+        assert_program_error(result, ACCOUNT_NOT_UPGRADABLE_ERROR);
+    }
+}
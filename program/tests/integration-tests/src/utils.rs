@@ -1,10 +1,29 @@
-use commerce_program_client::{CommerceProgramError, COMMERCE_PROGRAM_ID as PROGRAM_ID};
+pub use commerce_program_client::pdas::{
+    find_buyer_index_pda, find_config_registry_page_pda, find_conversion_rate_pda,
+    find_escrow_authority_pda, find_event_authority_pda, find_merchant_operator_config_pda,
+    find_merchant_pda, find_merchant_profile_pda, find_merchant_stats_pda,
+    find_operator_delegate_pda, find_operator_freeze_list_pda, find_operator_pda, find_order_pda,
+    find_payment_annotation_pda, find_payment_note_pda, find_payment_pda, find_payment_tree_pda,
+    find_quote_pda, find_refund_approval_pda, find_risk_state_pda, find_settlement_batch_pda,
+    find_webhook_commitment_pda,
+};
+use commerce_program_client::{
+    accounts::{Payment, SettlementBatch},
+    discriminator::InstructionDiscriminator,
+    escrow_audit::{audit_escrow, token_account_amount, AuditedPayment, EscrowBalance},
+    types::{AnnotationFlag, OrderStatus, PaymentLifecycleStatus, Status},
+    CommerceProgramError, COMMERCE_PROGRAM_ID as PROGRAM_ID,
+};
 use litesvm::{types::TransactionMetadata, LiteSVM};
 use solana_program::pubkey;
 use solana_program_pack::Pack;
 use solana_sdk::{
     account::Account,
+    account_utils::StateMut,
+    hash::Hash,
     instruction::Instruction,
+    message::Message,
+    nonce::state::{State as NonceState, Versions as NonceVersions},
     program_option::COption,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
@@ -17,9 +36,14 @@ use spl_token::{
 
 use solana_program::clock::Clock;
 use spl_associated_token_account::{
-    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+    get_associated_token_address, get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
 };
 
+use crate::state_utils::assert_get_or_create_operator;
+use solana_system_interface::instruction::{advance_nonce_account, create_nonce_account};
+use std::{collections::BTreeMap, sync::OnceLock};
+
 const MIN_LAMPORTS: u64 = 500_000_000;
 pub const MAX_BPS: u64 = 10_000;
 pub const DAYS_TO_CLOSE: u16 = 7;
@@ -53,12 +77,191 @@ pub const MERCHANT_OPERATOR_CONFIG_INVALID_PDA_ERROR: u32 =
 pub const ACCEPTED_CURRENCIES_EMPTY_ERROR: u32 =
     CommerceProgramError::AcceptedCurrenciesEmpty as u32;
 pub const DUPLICATE_MINT_ERROR: u32 = CommerceProgramError::DuplicateMint as u32;
+pub const PAYMENT_TREE_INVALID_PDA_ERROR: u32 = CommerceProgramError::PaymentTreeInvalidPda as u32;
+pub const PAYMENT_TREE_MISMATCH_ERROR: u32 = CommerceProgramError::PaymentTreeMismatch as u32;
+pub const INVALID_MERKLE_PROOF_ERROR: u32 = CommerceProgramError::InvalidMerkleProof as u32;
+pub const MERCHANT_PENDING_AUTHORITY_MISMATCH_ERROR: u32 =
+    CommerceProgramError::MerchantPendingAuthorityMismatch as u32;
+pub const OPERATOR_PENDING_AUTHORITY_MISMATCH_ERROR: u32 =
+    CommerceProgramError::OperatorPendingAuthorityMismatch as u32;
+pub const SETTLEMENT_WALLET_MISMATCH_ERROR: u32 =
+    CommerceProgramError::SettlementWalletMismatch as u32;
+pub const MERCHANT_STATS_INVALID_PDA_ERROR: u32 =
+    CommerceProgramError::MerchantStatsInvalidPda as u32;
+pub const MERCHANT_STATS_MISMATCH_ERROR: u32 = CommerceProgramError::MerchantStatsMismatch as u32;
+pub const PAYMENT_ALREADY_EXISTS_ERROR: u32 = CommerceProgramError::PaymentAlreadyExists as u32;
+pub const INVALID_FEE_TIER_POLICY_ERROR: u32 = CommerceProgramError::InvalidFeeTierPolicy as u32;
+pub const PAYMENT_NOT_AUTO_SETTLED_ERROR: u32 = CommerceProgramError::PaymentNotAutoSettled as u32;
+pub const REFUND_WINDOW_REQUIRED_ERROR: u32 = CommerceProgramError::RefundWindowRequired as u32;
+pub const MEMO_PROGRAM_REQUIRED_ERROR: u32 = CommerceProgramError::MemoProgramRequired as u32;
+pub const OPERATOR_DELEGATE_MISMATCH_ERROR: u32 =
+    CommerceProgramError::OperatorDelegateMismatch as u32;
+pub const OPERATOR_DELEGATE_PERMISSION_DENIED_ERROR: u32 =
+    CommerceProgramError::OperatorDelegatePermissionDenied as u32;
+pub const INVALID_CONVERSION_POLICY_ERROR: u32 =
+    CommerceProgramError::InvalidConversionPolicy as u32;
+pub const CONVERSION_POLICY_REQUIRED_ERROR: u32 =
+    CommerceProgramError::ConversionPolicyRequired as u32;
+pub const INVALID_SWAP_ERROR: u32 = CommerceProgramError::InvalidSwap as u32;
+pub const INVALID_CHARGEBACK_POLICY_ERROR: u32 =
+    CommerceProgramError::InvalidChargebackPolicy as u32;
+pub const CHARGEBACK_POLICY_REQUIRED_ERROR: u32 =
+    CommerceProgramError::ChargebackPolicyRequired as u32;
+pub const CHARGEBACK_WINDOW_ACTIVE_ERROR: u32 = CommerceProgramError::ChargebackWindowActive as u32;
+pub const CHARGEBACK_WINDOW_EXPIRED_ERROR: u32 =
+    CommerceProgramError::ChargebackWindowExpired as u32;
+pub const CHARGEBACK_ARBITER_MISMATCH_ERROR: u32 =
+    CommerceProgramError::ChargebackArbiterMismatch as u32;
+pub const INVALID_SETTLEMENT_WALLET_TIMELOCK_ERROR: u32 =
+    CommerceProgramError::InvalidSettlementWalletTimelock as u32;
+pub const SETTLEMENT_WALLET_TIMELOCKED_ERROR: u32 =
+    CommerceProgramError::SettlementWalletTimelocked as u32;
+pub const SETTLEMENT_WALLET_NOT_PROPOSED_ERROR: u32 =
+    CommerceProgramError::SettlementWalletNotProposed as u32;
+pub const SETTLEMENT_WALLET_TIMELOCK_ACTIVE_ERROR: u32 =
+    CommerceProgramError::SettlementWalletTimelockActive as u32;
+pub const INVALID_FEE_OVERRIDE_POLICY_ERROR: u32 =
+    CommerceProgramError::InvalidFeeOverridePolicy as u32;
+pub const FEE_OVERRIDE_POLICY_REQUIRED_ERROR: u32 =
+    CommerceProgramError::FeeOverridePolicyRequired as u32;
+pub const FEE_OVERRIDE_EXCEEDS_MAX_ERROR: u32 = CommerceProgramError::FeeOverrideExceedsMax as u32;
+pub const INVALID_DENYLIST_POLICY_ERROR: u32 = CommerceProgramError::InvalidDenylistPolicy as u32;
+pub const DENYLIST_PROOF_REQUIRED_ERROR: u32 = CommerceProgramError::DenylistProofRequired as u32;
+pub const INVALID_DENYLIST_PROOF_ERROR: u32 = CommerceProgramError::InvalidDenylistProof as u32;
+pub const SETTLEMENT_BATCH_INVALID_PDA_ERROR: u32 =
+    CommerceProgramError::SettlementBatchInvalidPda as u32;
+pub const SETTLEMENT_BATCH_MISMATCH_ERROR: u32 =
+    CommerceProgramError::SettlementBatchMismatch as u32;
+pub const SETTLEMENT_BATCH_EMPTY_ERROR: u32 = CommerceProgramError::SettlementBatchEmpty as u32;
+pub const ORDER_INVALID_PDA_ERROR: u32 = CommerceProgramError::OrderInvalidPda as u32;
+pub const ORDER_MISMATCH_ERROR: u32 = CommerceProgramError::OrderMismatch as u32;
+pub const ORDER_FULL_ERROR: u32 = CommerceProgramError::OrderFull as u32;
+pub const INVALID_ORDER_PAYMENT_COUNT_ERROR: u32 =
+    CommerceProgramError::InvalidOrderPaymentCount as u32;
+pub const MERCHANT_PROFILE_INVALID_PDA_ERROR: u32 =
+    CommerceProgramError::MerchantProfileInvalidPda as u32;
+pub const MERCHANT_PROFILE_FIELD_TOO_LONG_ERROR: u32 =
+    CommerceProgramError::MerchantProfileFieldTooLong as u32;
+pub const TOO_MANY_PAYMENT_REFERENCES_ERROR: u32 =
+    CommerceProgramError::TooManyPaymentReferences as u32;
+pub const INVALID_SPLIT_SETTLEMENT_POLICY_ERROR: u32 =
+    CommerceProgramError::InvalidSplitSettlementPolicy as u32;
+pub const INVALID_SPLIT_SETTLEMENT_DESTINATIONS_ERROR: u32 =
+    CommerceProgramError::InvalidSplitSettlementDestinations as u32;
+pub const SPLIT_SETTLEMENT_UNSUPPORTED_ERROR: u32 =
+    CommerceProgramError::SplitSettlementUnsupported as u32;
+pub const REFUND_APPROVAL_INVALID_PDA_ERROR: u32 =
+    CommerceProgramError::RefundApprovalInvalidPda as u32;
+pub const REFUND_APPROVAL_MISMATCH_ERROR: u32 = CommerceProgramError::RefundApprovalMismatch as u32;
+pub const INVALID_REFUND_DESTINATION_ERROR: u32 =
+    CommerceProgramError::InvalidRefundDestination as u32;
+pub const INVALID_RISK_POLICY_ERROR: u32 = CommerceProgramError::InvalidRiskPolicy as u32;
+pub const PAYMENT_AMOUNT_EXCEEDS_RISK_LIMIT_ERROR: u32 =
+    CommerceProgramError::PaymentAmountExceedsRiskLimit as u32;
+pub const DAILY_VOLUME_LIMIT_EXCEEDED_ERROR: u32 =
+    CommerceProgramError::DailyVolumeLimitExceeded as u32;
+pub const BUYER_DAILY_PAYMENT_LIMIT_EXCEEDED_ERROR: u32 =
+    CommerceProgramError::BuyerDailyPaymentLimitExceeded as u32;
+pub const RISK_STATE_MISMATCH_ERROR: u32 = CommerceProgramError::RiskStateMismatch as u32;
+pub const RISK_STATE_REQUIRED_ERROR: u32 = CommerceProgramError::RiskStateRequired as u32;
+pub const BUYER_INDEX_MISMATCH_ERROR: u32 = CommerceProgramError::BuyerIndexMismatch as u32;
+pub const BUYER_INDEX_REQUIRED_ERROR: u32 = CommerceProgramError::BuyerIndexRequired as u32;
+pub const RENT_PAYER_MISMATCH_ERROR: u32 = CommerceProgramError::RentPayerMismatch as u32;
+pub const INVALID_CALLBACK_POLICY_ERROR: u32 = CommerceProgramError::InvalidCallbackPolicy as u32;
+pub const CALLBACK_PROGRAM_REQUIRED_ERROR: u32 =
+    CommerceProgramError::CallbackProgramRequired as u32;
+pub const CALLBACK_PROGRAM_MISMATCH_ERROR: u32 =
+    CommerceProgramError::CallbackProgramMismatch as u32;
+pub const DELEGATE_REFUND_LIMIT_EXCEEDED_ERROR: u32 =
+    CommerceProgramError::DelegateRefundLimitExceeded as u32;
+pub const DELEGATE_DAILY_REFUND_LIMIT_EXCEEDED_ERROR: u32 =
+    CommerceProgramError::DelegateDailyRefundLimitExceeded as u32;
+pub const TRANSFER_HOOK_ACCOUNTS_MISSING_ERROR: u32 =
+    CommerceProgramError::TransferHookAccountsMissing as u32;
+pub const UNSUPPORTED_MINT_EXTENSION_ERROR: u32 =
+    CommerceProgramError::UnsupportedMintExtension as u32;
+pub const INVALID_PLATFORM_FEE_POLICY_ERROR: u32 =
+    CommerceProgramError::InvalidPlatformFeePolicy as u32;
+pub const ESCROW_AUTHORITY_INVALID_PDA_ERROR: u32 =
+    CommerceProgramError::EscrowAuthorityInvalidPda as u32;
+pub const QUOTE_MISMATCH_ERROR: u32 = CommerceProgramError::QuoteMismatch as u32;
+pub const QUOTE_EXPIRED_ERROR: u32 = CommerceProgramError::QuoteExpired as u32;
+pub const CONFIG_REGISTRY_MISMATCH_ERROR: u32 = CommerceProgramError::ConfigRegistryMismatch as u32;
+pub const CONFIG_REGISTRY_PAGE_FULL_ERROR: u32 =
+    CommerceProgramError::ConfigRegistryPageFull as u32;
+pub const CONFIG_REGISTRY_PREVIOUS_PAGE_NOT_FULL_ERROR: u32 =
+    CommerceProgramError::ConfigRegistryPreviousPageNotFull as u32;
+pub const INVALID_COMPLIANCE_POLICY_ERROR: u32 =
+    CommerceProgramError::InvalidCompliancePolicy as u32;
+pub const COMPLIANCE_PROGRAM_REQUIRED_ERROR: u32 =
+    CommerceProgramError::ComplianceProgramRequired as u32;
+pub const COMPLIANCE_PROGRAM_MISMATCH_ERROR: u32 =
+    CommerceProgramError::ComplianceProgramMismatch as u32;
+pub const CAPTURE_AMOUNT_EXCEEDS_AUTHORIZATION_ERROR: u32 =
+    CommerceProgramError::CaptureAmountExceedsAuthorization as u32;
+pub const AUTHORIZATION_REQUIRES_ESCROW_ERROR: u32 =
+    CommerceProgramError::AuthorizationRequiresEscrow as u32;
+pub const OPERATOR_FREEZE_LIST_MISMATCH_ERROR: u32 =
+    CommerceProgramError::OperatorFreezeListMismatch as u32;
+pub const OPERATOR_FREEZE_LIST_FULL_ERROR: u32 =
+    CommerceProgramError::OperatorFreezeListFull as u32;
+pub const ADDRESS_ALREADY_FROZEN_ERROR: u32 = CommerceProgramError::AddressAlreadyFrozen as u32;
+pub const ADDRESS_NOT_FROZEN_ERROR: u32 = CommerceProgramError::AddressNotFrozen as u32;
+pub const BUYER_FROZEN_ERROR: u32 = CommerceProgramError::BuyerFrozen as u32;
+pub const INVALID_SETTLEMENT_DISCOUNT_POLICY_ERROR: u32 =
+    CommerceProgramError::InvalidSettlementDiscountPolicy as u32;
+pub const INVALID_REFUND_CONVERSION_POLICY_ERROR: u32 =
+    CommerceProgramError::InvalidRefundConversionPolicy as u32;
+pub const REFUND_CONVERSION_POLICY_REQUIRED_ERROR: u32 =
+    CommerceProgramError::RefundConversionPolicyRequired as u32;
+pub const CONVERSION_RATE_MISMATCH_ERROR: u32 = CommerceProgramError::ConversionRateMismatch as u32;
+pub const CONVERSION_RATE_STALE_ERROR: u32 = CommerceProgramError::ConversionRateStale as u32;
+pub const INVALID_CONVERSION_RATE_ERROR: u32 = CommerceProgramError::InvalidConversionRate as u32;
+pub const TOO_MANY_POLICIES_ERROR: u32 = CommerceProgramError::TooManyPolicies as u32;
+pub const TOO_MANY_ACCEPTED_CURRENCIES_ERROR: u32 =
+    CommerceProgramError::TooManyAcceptedCurrencies as u32;
+pub const POLICY_ALREADY_EXISTS_ERROR: u32 = CommerceProgramError::PolicyAlreadyExists as u32;
+pub const PAYMENT_NOTE_INVALID_PDA_ERROR: u32 = CommerceProgramError::PaymentNoteInvalidPda as u32;
+pub const OPERATOR_FAILOVER_POLICY_REQUIRED_ERROR: u32 =
+    CommerceProgramError::OperatorFailoverPolicyRequired as u32;
+pub const OPERATOR_STILL_ACTIVE_ERROR: u32 = CommerceProgramError::OperatorStillActive as u32;
+pub const ATA_OWNER_MISMATCH_ERROR: u32 = CommerceProgramError::AtaOwnerMismatch as u32;
+pub const ATA_MINT_MISMATCH_ERROR: u32 = CommerceProgramError::AtaMintMismatch as u32;
+pub const ATA_NOT_INITIALIZED_ERROR: u32 = CommerceProgramError::AtaNotInitialized as u32;
+pub const ESCROW_NOT_EMPTY_ERROR: u32 = CommerceProgramError::EscrowNotEmpty as u32;
+pub const MIGRATION_TARGET_UNCHANGED_ERROR: u32 =
+    CommerceProgramError::MigrationTargetUnchanged as u32;
+pub const UNSUPPORTED_INSTRUCTION_VERSION_ERROR: u32 =
+    CommerceProgramError::UnsupportedInstructionVersion as u32;
+pub const INVALID_RENT_REBATE_POLICY_ERROR: u32 =
+    CommerceProgramError::InvalidRentRebatePolicy as u32;
+pub const WEBHOOK_COMMITMENT_INVALID_PDA_ERROR: u32 =
+    CommerceProgramError::WebhookCommitmentInvalidPda as u32;
+pub const DIRECT_SETTLEMENT_UNSUPPORTED_ERROR: u32 =
+    CommerceProgramError::DirectSettlementUnsupported as u32;
+pub const PAYMENT_ANNOTATION_INVALID_PDA_ERROR: u32 =
+    CommerceProgramError::PaymentAnnotationInvalidPda as u32;
+pub const CONFIG_DECOMMISSIONED_ERROR: u32 = CommerceProgramError::ConfigDecommissioned as u32;
+pub const CONFIG_NOT_DECOMMISSIONED_ERROR: u32 =
+    CommerceProgramError::ConfigNotDecommissioned as u32;
+pub const CONFIG_HAS_OPEN_PAYMENTS_ERROR: u32 = CommerceProgramError::ConfigHasOpenPayments as u32;
+pub const ACCOUNT_NOT_UPGRADABLE_ERROR: u32 = CommerceProgramError::AccountNotUpgradable as u32;
+pub const FREEZE_LIST_REQUIRED_ERROR: u32 = CommerceProgramError::FreezeListRequired as u32;
+pub const CONFIG_HAS_PAYMENT_ACCOUNTS_ERROR: u32 =
+    CommerceProgramError::ConfigHasPaymentAccounts as u32;
+pub const QUOTE_BELOW_CONVERSION_RATE_ERROR: u32 =
+    CommerceProgramError::QuoteBelowConversionRate as u32;
+pub const ORDER_REQUIRED_ERROR: u32 = CommerceProgramError::OrderRequired as u32;
+pub const PAYMENT_IN_ORDER_ERROR: u32 = CommerceProgramError::PaymentInOrder as u32;
+pub const SETTLEMENT_WALLET_TIMELOCK_LOWERED_ERROR: u32 =
+    CommerceProgramError::SettlementWalletTimelockLowered as u32;
 
 // Standard Solana Program Error Codes
 pub const INVALID_ARGUMENT_ERROR: u32 = 5; // ProgramError::InvalidArgument
 pub const INVALID_ACCOUNT_DATA_ERROR: u32 = 6; // ProgramError::InvalidAccountData
 pub const NOT_ENOUGH_ACCOUNT_KEYS_ERROR: u32 = 2; // ProgramError::NotEnoughAccountKeys
 pub const INVALID_INSTRUCTION_DATA_ERROR: u32 = 3; // ProgramError::InvalidInstructionData
+pub const INCORRECT_PROGRAM_ID_ERROR: u32 = 7; // ProgramError::IncorrectProgramId
 pub const INVALID_ACCOUNT_OWNER_ERROR: u32 = 23; // ProgramError::InvalidAccountOwner
 pub const INVALID_SEEDS_ERROR: u32 = 14; // ProgramError::InvalidSeeds
 pub const MISSING_REQUIRED_SIGNATURE_ERROR: u32 = 0; // ProgramError::MissingRequiredSignature
@@ -85,11 +288,97 @@ const USDT_MINT_DATA: &[u8] = &[
 pub struct TestContext {
     pub svm: LiteSVM,
     pub payer: Keypair,
+    /// Samples recorded while `profile_compute_units` runs.
+    compute_units: Option<Vec<ComputeUnitSample>>,
+    /// (payment, mint) of each successful `MakePayment`, by config, audited on drop.
+    payments: BTreeMap<Pubkey, Vec<(Pubkey, Pubkey)>>,
 }
 
-impl TestContext {
+/// Compute units one commerce program instruction consumed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComputeUnitSample {
+    pub operation: &'static str,
+    pub cu_consumed: u64,
+}
+
+/// Where `cargo-build-sbf` writes the compiled commerce program, relative to this crate.
+const PROGRAM_SO_PATH: &str = "../../target/deploy/commerce_program.so";
+
+/// The compiled commerce program, read once per test binary so the budget benchmarks measure
+/// the same binary the tests run. It is read when the tests run rather than embedded, so the
+/// crate builds and lints without a prior `cargo-build-sbf`.
+pub fn program_so() -> &'static [u8] {
+    static PROGRAM_SO: OnceLock<Vec<u8>> = OnceLock::new();
+    PROGRAM_SO.get_or_init(|| {
+        std::fs::read(PROGRAM_SO_PATH).unwrap_or_else(|err| {
+            panic!(
+                "Failed to read the commerce program at {PROGRAM_SO_PATH} ({err}); \
+                 build it with `cargo-build-sbf` first"
+            )
+        })
+    })
+}
+
+/// Programs loaded once per test binary and cloned into each `TestContext`, so tests do not
+/// re-read and re-verify the `.so` files.
+static PROGRAM_SNAPSHOT: OnceLock<LiteSVM> = OnceLock::new();
+
+fn program_snapshot() -> LiteSVM {
+    PROGRAM_SNAPSHOT
+        .get_or_init(|| {
+            let mut svm = LiteSVM::new().with_sysvars();
+
+            svm.add_program(PROGRAM_ID, program_so());
+
+            let token_program_data =
+                std::fs::read("deps/spl_token.so").expect("Failed to read token program");
+            svm.add_program(TOKEN_PROGRAM_ID, &token_program_data);
+
+            let ata_program_data = std::fs::read("deps/spl_associated_token_account.so")
+                .expect("Failed to read associated token program");
+            svm.add_program(ATA_PROGRAM_ID, &ata_program_data);
+
+            svm
+        })
+        .clone()
+}
+
+/// Builds a `TestContext` from the shared program snapshot.
+///
+/// ```ignore
+/// let context = TestContextBuilder::new().with_usdc().with_operator().build();
+/// let operator_pda = context.operator_pda();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TestContextBuilder {
+    usdc: bool,
+    usdt: bool,
+    operator: bool,
+}
+
+impl TestContextBuilder {
     pub fn new() -> Self {
-        let mut svm = LiteSVM::new().with_sysvars();
+        Self::default()
+    }
+
+    pub fn with_usdc(mut self) -> Self {
+        self.usdc = true;
+        self
+    }
+
+    pub fn with_usdt(mut self) -> Self {
+        self.usdt = true;
+        self
+    }
+
+    /// Creates an operator owned by the payer.
+    pub fn with_operator(mut self) -> Self {
+        self.operator = true;
+        self
+    }
+
+    pub fn build(self) -> TestContext {
+        let mut svm = program_snapshot();
 
         // Override clock to start at current time instead of Unix epoch 0
         let current_time = std::time::SystemTime::now()
@@ -105,48 +394,57 @@ impl TestContext {
             unix_timestamp: current_time,
         });
 
-        let program_data = include_bytes!("../../../target/deploy/commerce_program.so");
-        svm.add_program(PROGRAM_ID, program_data);
+        if self.usdc {
+            set_preloaded_mint(&mut svm, USDC_MINT, USDC_MINT_DATA);
+        }
+        if self.usdt {
+            set_preloaded_mint(&mut svm, USDT_MINT, USDT_MINT_DATA);
+        }
 
-        let token_program_data =
-            std::fs::read("deps/spl_token.so").expect("Failed to read token program");
-        svm.add_program(TOKEN_PROGRAM_ID, &token_program_data);
+        let payer = Keypair::new();
 
-        let ata_program_data = std::fs::read("deps/spl_associated_token_account.so")
-            .expect("Failed to read associated token program");
-        svm.add_program(ATA_PROGRAM_ID, &ata_program_data);
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
 
-        // set usdc
-        svm.set_account(
-            USDC_MINT,
-            Account {
-                lamports: 407591838630,
-                data: USDC_MINT_DATA.to_vec(),
-                owner: TOKEN_PROGRAM_ID,
-                executable: false,
-                rent_epoch: 0,
-            },
-        )
-        .unwrap();
+        let mut context = TestContext {
+            svm,
+            payer,
+            compute_units: None,
+            payments: BTreeMap::new(),
+        };
 
-        // set usdt
-        svm.set_account(
-            USDT_MINT,
-            Account {
-                lamports: 407591838630,
-                data: USDT_MINT_DATA.to_vec(),
-                owner: TOKEN_PROGRAM_ID,
-                executable: false,
-                rent_epoch: 0,
-            },
-        )
-        .unwrap();
+        if self.operator {
+            let operator_authority = context.payer.insecure_clone();
+            assert_get_or_create_operator(&mut context, &operator_authority, true, false)
+                .expect("Create operator should succeed");
+        }
 
-        let payer = Keypair::new();
+        context
+    }
+}
 
-        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+fn set_preloaded_mint(svm: &mut LiteSVM, mint: Pubkey, data: &[u8]) {
+    svm.set_account(
+        mint,
+        Account {
+            lamports: 407591838630,
+            data: data.to_vec(),
+            owner: TOKEN_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+}
+
+impl TestContext {
+    /// Context with the USDC and USDT mints.
+    pub fn new() -> Self {
+        TestContextBuilder::new().with_usdc().with_usdt().build()
+    }
 
-        Self { svm, payer }
+    /// Operator PDA owned by the payer, as created by `TestContextBuilder::with_operator`.
+    pub fn operator_pda(&self) -> Pubkey {
+        find_operator_pda(&self.payer.pubkey()).0
     }
 
     pub fn airdrop_if_required(
@@ -187,18 +485,7 @@ impl TestContext {
         &mut self,
         instruction: Instruction,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let transaction = Transaction::new_signed_with_payer(
-            &[instruction],
-            Some(&self.payer.pubkey()),
-            &[&self.payer],
-            self.svm.latest_blockhash(),
-        );
-
-        let result = self.svm.send_transaction(transaction);
-        match result {
-            Ok(_) => Ok(()),
-            Err(e) => Err(format!("Transaction failed: {:?}", e).into()),
-        }
+        self.send_transaction_with_signers(instruction, &[])
     }
 
     pub fn send_transaction_with_signers(
@@ -226,24 +513,201 @@ impl TestContext {
             self.svm.latest_blockhash(),
         );
 
-        // Simulate first to get CU consumption for profiling (only if enabled)
-        if enable_profiling && instruction.program_id == PROGRAM_ID {
-            let simulation_result = self.svm.simulate_transaction(transaction.clone());
-            if let Ok(sim_metadata) = simulation_result {
-                let cu_consumed = sim_metadata.meta.compute_units_consumed;
-                let operation = get_operation_name(&instruction);
+        let metadata = self.send_signed_transaction(transaction)?;
+
+        if instruction.program_id == PROGRAM_ID {
+            let sample = ComputeUnitSample {
+                operation: get_operation_name(&instruction),
+                cu_consumed: metadata.compute_units_consumed,
+            };
+            if enable_profiling {
                 eprintln!(
                     r#"{{"type":"profiling","operation":"{}","cu_consumed":{}}}"#,
-                    operation, cu_consumed
+                    sample.operation, sample.cu_consumed
                 );
             }
+            if let Some(samples) = &mut self.compute_units {
+                samples.push(sample);
+            }
         }
 
-        let result = self.svm.send_transaction(transaction);
-        match result {
-            Ok(logs) => Ok(logs),
-            Err(e) => Err(format!("Transaction failed: {:?}", e).into()),
+        Ok(metadata)
+    }
+
+    /// Sends a transaction signed earlier, e.g. a queued `nonce_transaction`.
+    pub fn send_signed_transaction(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<TransactionMetadata, Box<dyn std::error::Error>> {
+        let payments = made_payments(&transaction.message);
+        let metadata = self
+            .svm
+            .send_transaction(transaction)
+            .map_err(|e| format!("Transaction failed: {:?}", e))?;
+
+        for (config, payment, mint) in payments {
+            self.payments
+                .entry(config)
+                .or_default()
+                .push((payment, mint));
         }
+
+        Ok(metadata)
+    }
+
+    /// Creates a durable nonce account authorized by `authority`. The blockhash its nonce was
+    /// derived from is expired, so the nonce can be used right away.
+    pub fn create_nonce_account(&mut self, authority: &Pubkey) -> Pubkey {
+        let nonce_account = Keypair::new();
+        let lamports = self
+            .svm
+            .minimum_balance_for_rent_exemption(NonceState::size());
+        let instructions = create_nonce_account(
+            &self.payer.pubkey(),
+            &nonce_account.pubkey(),
+            authority,
+            lamports,
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer, &nonce_account],
+            self.svm.latest_blockhash(),
+        );
+        self.send_signed_transaction(transaction)
+            .expect("Failed to create nonce account");
+        self.svm.expire_blockhash();
+
+        nonce_account.pubkey()
+    }
+
+    /// Durable nonce currently stored in `nonce_account`.
+    pub fn get_nonce(&self, nonce_account: &Pubkey) -> Hash {
+        let account = self
+            .svm
+            .get_account(nonce_account)
+            .expect("Nonce account should exist");
+        let versions: NonceVersions = account.state().expect("Should deserialize nonce account");
+        match versions.state() {
+            NonceState::Initialized(data) => data.blockhash(),
+            NonceState::Uninitialized => panic!("Nonce account {nonce_account} is uninitialized"),
+        }
+    }
+
+    /// Signs `instruction` against the durable nonce of `nonce_account`, after the
+    /// `AdvanceNonceAccount` every nonce transaction starts with. Unlike transactions using a
+    /// recent blockhash, it can be queued and sent however much later, until the nonce is
+    /// advanced.
+    pub fn nonce_transaction(
+        &self,
+        instruction: Instruction,
+        nonce_account: &Pubkey,
+        nonce_authority: &Keypair,
+        signers: &[&Keypair],
+    ) -> Transaction {
+        let mut all_signers = vec![&self.payer, nonce_authority];
+        all_signers.extend(signers);
+
+        Transaction::new_signed_with_payer(
+            &[
+                advance_nonce_account(nonce_account, &nonce_authority.pubkey()),
+                instruction,
+            ],
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            self.get_nonce(nonce_account),
+        )
+    }
+
+    /// Asserts that the escrow of every config paid through this context holds at least its
+    /// open payments and pending settlement batch.
+    pub fn assert_escrow_invariant(&self) {
+        for (config, payments) in &self.payments {
+            let audited: Vec<AuditedPayment> = payments
+                .iter()
+                .filter_map(|(payment, mint)| {
+                    let account = self.svm.get_account(payment)?;
+                    Some(AuditedPayment {
+                        payment: *payment,
+                        mint: *mint,
+                        account: Payment::from_bytes(&account.data).ok()?,
+                    })
+                })
+                .collect();
+
+            let mut mints: Vec<Pubkey> = payments.iter().map(|(_, mint)| *mint).collect();
+            mints.sort_unstable();
+            mints.dedup();
+            let escrows: Vec<EscrowBalance> = mints
+                .into_iter()
+                .map(|mint| {
+                    let token_program = self
+                        .svm
+                        .get_account(&mint)
+                        .map_or(TOKEN_PROGRAM_ID, |account| account.owner);
+                    let escrow_authority = find_escrow_authority_pda(config, &mint).0;
+                    let escrow_ata = get_associated_token_address_with_program_id(
+                        &escrow_authority,
+                        &mint,
+                        &token_program,
+                    );
+                    EscrowBalance {
+                        mint,
+                        balance: self
+                            .svm
+                            .get_account(&escrow_ata)
+                            .and_then(|account| token_account_amount(&account.data))
+                            .unwrap_or(0),
+                        batch_pending: self
+                            .svm
+                            .get_account(&find_settlement_batch_pda(config, &mint).0)
+                            .and_then(|account| SettlementBatch::from_bytes(&account.data).ok())
+                            .map_or(0, |batch| batch.pending_amount),
+                    }
+                })
+                .collect();
+
+            let audit = audit_escrow(config, &audited, &escrows);
+            let shortfall = audit.shortfalls().next();
+            if let Some(mint) = shortfall {
+                panic!(
+                    "escrow of config {config} for mint {} is short by {}: unbacked payments {:?}",
+                    mint.mint,
+                    mint.discrepancy().unsigned_abs(),
+                    mint.unbacked_payments
+                );
+            }
+        }
+    }
+
+    /// Starts recording the compute units of each commerce program instruction sent, dropping
+    /// anything recorded before.
+    pub fn start_profiling(&mut self) {
+        self.compute_units = Some(Vec::new());
+    }
+
+    /// Stops recording and returns the samples since `start_profiling`, in order.
+    pub fn stop_profiling(&mut self) -> Vec<ComputeUnitSample> {
+        self.compute_units.take().unwrap_or_default()
+    }
+
+    /// Runs `f` and returns the compute units of each commerce program instruction it sent,
+    /// in order. Setup done before the call is not counted.
+    ///
+    /// ```ignore
+    /// let samples = context.profile_compute_units(|context| {
+    ///     assert_clear_payment(context, /* ... */ false)
+    /// })?;
+    /// ```
+    pub fn profile_compute_units(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<(), Box<dyn std::error::Error>>,
+    ) -> Result<Vec<ComputeUnitSample>, Box<dyn std::error::Error>> {
+        self.start_profiling();
+        let result = f(self);
+        let samples = self.stop_profiling();
+        result.map(|_| samples)
     }
 
     pub fn get_account(&mut self, pubkey: &Pubkey) -> Option<Account> {
@@ -254,14 +718,15 @@ impl TestContext {
         self.get_account(pubkey).map(|account| account.data)
     }
 
+    /// Moves the clock forward `seconds`, saturating so tests can jump to the far future.
     pub fn advance_clock(&mut self, seconds: i64) {
         let current_clock = self.svm.get_sysvar::<Clock>();
         self.svm.set_sysvar(&Clock {
-            slot: current_clock.slot + seconds as u64,
+            slot: current_clock.slot.saturating_add_signed(seconds),
             epoch_start_timestamp: current_clock.epoch_start_timestamp,
             epoch: current_clock.epoch,
             leader_schedule_epoch: current_clock.leader_schedule_epoch,
-            unix_timestamp: current_clock.unix_timestamp + seconds,
+            unix_timestamp: current_clock.unix_timestamp.saturating_add(seconds),
         });
     }
 }
@@ -272,65 +737,68 @@ impl Default for TestContext {
     }
 }
 
-pub fn find_operator_pda(authority: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"operator", authority.as_ref()], &PROGRAM_ID)
+/// Every test ends with the escrow invariant checked, unless it already failed.
+impl Drop for TestContext {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            self.assert_escrow_invariant();
+        }
+    }
 }
 
-pub fn find_merchant_pda(authority: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"merchant", authority.as_ref()], &PROGRAM_ID)
+/// (config, payment, mint) of each commerce program `MakePayment` in `message`.
+fn made_payments(message: &Message) -> Vec<(Pubkey, Pubkey, Pubkey)> {
+    message
+        .instructions
+        .iter()
+        .filter(|ix| message.account_keys[ix.program_id_index as usize] == PROGRAM_ID)
+        .filter(|ix| ix.data.first() == Some(&(InstructionDiscriminator::MakePayment as u8)))
+        .map(|ix| {
+            let key = |index: usize| message.account_keys[ix.accounts[index] as usize];
+            (key(6), key(1), key(7))
+        })
+        .collect()
 }
 
-pub fn find_merchant_operator_config_pda(
-    merchant: &Pubkey,
-    operator: &Pubkey,
-    version: u32,
-) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            b"merchant_operator_config",
-            merchant.as_ref(),
-            operator.as_ref(),
-            &version.to_le_bytes(),
-        ],
-        &PROGRAM_ID,
-    )
+/// Deterministic idempotency key used by tests for a given order id.
+pub fn idempotency_key(order_id: u32) -> [u8; 16] {
+    let mut key = [0u8; 16];
+    key[..4].copy_from_slice(&order_id.to_le_bytes());
+    key
 }
 
-pub fn find_payment_pda(
-    merchant_operator_config: &Pubkey,
-    buyer: &Pubkey,
-    mint: &Pubkey,
-    order_id: u32,
-) -> (Pubkey, u8) {
-    Pubkey::find_program_address(
-        &[
-            b"payment",
-            merchant_operator_config.as_ref(),
-            buyer.as_ref(),
-            mint.as_ref(),
-            &order_id.to_le_bytes(),
-        ],
-        &PROGRAM_ID,
-    )
+/// Escrow ATA of `merchant_operator_config` for `mint`, owned by the config's escrow authority PDA.
+pub fn find_escrow_ata(merchant_operator_config: &Pubkey, mint: &Pubkey) -> Pubkey {
+    let (escrow_authority, _) = find_escrow_authority_pda(merchant_operator_config, mint);
+    get_associated_token_address(&escrow_authority, mint)
 }
 
-pub fn find_event_authority_pda() -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"event_authority"], &PROGRAM_ID)
+/// Program address of `seeds` at the highest bump below the canonical one, with that bump.
+/// The program must reject it wherever it expects the PDA of `seeds`.
+pub fn find_non_canonical_pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    let (_, canonical_bump) = Pubkey::find_program_address(seeds, &PROGRAM_ID);
+    (0..canonical_bump)
+        .rev()
+        .find_map(|bump| {
+            let bump_seed = [bump];
+            let mut bumped_seeds = seeds.to_vec();
+            bumped_seeds.push(&bump_seed);
+            Pubkey::create_program_address(&bumped_seeds, &PROGRAM_ID)
+                .ok()
+                .map(|pda| (pda, bump))
+        })
+        .expect("Seeds should have a non-canonical bump")
 }
 
 pub fn get_token_balance(context: &mut TestContext, ata: &Pubkey) -> u64 {
     let account = context.get_account(ata);
     match account {
-        Some(account) => {
-            if account.owner == TOKEN_PROGRAM_ID {
-                let token_account =
-                    TokenAccount::unpack(&account.data).expect("Should deserialize token account");
-                token_account.amount
-            } else {
-                0
-            }
+        Some(account) if account.owner == TOKEN_PROGRAM_ID => {
+            let token_account =
+                TokenAccount::unpack(&account.data).expect("Should deserialize token account");
+            token_account.amount
         }
-        None => 0,
+        _ => 0,
     }
 }
 
@@ -436,6 +904,7 @@ pub fn assert_program_error(
                 3 => vec!["InvalidInstructionData"],
                 5 => vec!["InvalidArgument"],
                 6 => vec!["InvalidAccountData"],
+                7 => vec!["IncorrectProgramId"],
                 14 => vec!["InvalidSeeds"],
                 23 => vec!["InvalidAccountOwner"],
                 _ => vec![],
@@ -479,6 +948,7 @@ pub fn assert_event_present(
     amount: u64,
     order_id: u32,
     operator_fee: Option<u64>,
+    idempotency_key: &[u8; 16],
 ) {
     // Build expected event data using same format as events.rs
     // EVENT_IX_TAG_LE = 0x1d9acb512ea545e4.to_le_bytes() = [228, 69, 165, 46, 81, 203, 154, 29]
@@ -497,6 +967,7 @@ pub fn assert_event_present(
     }
 
     expected_data.extend_from_slice(&order_id.to_le_bytes());
+    expected_data.extend_from_slice(idempotency_key);
 
     let mut event_found = false;
 
@@ -520,24 +991,527 @@ pub fn assert_event_present(
     );
 }
 
-/// Map instruction discriminator to operation name for profiling
-fn get_operation_name(instruction: &Instruction) -> &'static str {
-    if instruction.data.is_empty() {
-        return "Unknown";
-    }
+pub fn assert_payment_closed_event_present(
+    transaction_metadata: &TransactionMetadata,
+    buyer: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    order_id: u32,
+    rent_refunded_to: &Pubkey,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(5); // PaymentClosed discriminator
+    expected_data.extend_from_slice(buyer.as_ref());
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(operator.as_ref());
+    expected_data.extend_from_slice(&order_id.to_le_bytes());
+    expected_data.extend_from_slice(rent_refunded_to.as_ref());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected PaymentClosed event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_settlement_batch_executed_event_present(
+    transaction_metadata: &TransactionMetadata,
+    merchant: &Pubkey,
+    merchant_operator_config: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    payment_count: u32,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(6); // SettlementBatchExecuted discriminator
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(merchant_operator_config.as_ref());
+    expected_data.extend_from_slice(mint.as_ref());
+    expected_data.extend_from_slice(&amount.to_le_bytes());
+    expected_data.extend_from_slice(&payment_count.to_le_bytes());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected SettlementBatchExecuted event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_refund_approved_event_present(
+    transaction_metadata: &TransactionMetadata,
+    buyer: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    approver: &Pubkey,
+    amount: u64,
+    order_id: u32,
+    idempotency_key: &[u8; 16],
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(9); // RefundApproved discriminator
+    expected_data.extend_from_slice(buyer.as_ref());
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(operator.as_ref());
+    expected_data.extend_from_slice(approver.as_ref());
+    expected_data.extend_from_slice(&amount.to_le_bytes());
+    expected_data.extend_from_slice(&order_id.to_le_bytes());
+    expected_data.extend_from_slice(idempotency_key);
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected RefundApproved event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_merchant_operator_config_initialized_event_present(
+    transaction_metadata: &TransactionMetadata,
+    config: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    version: u32,
+    config_hash: &[u8; 32],
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(11); // MerchantOperatorConfigInitialized discriminator
+    expected_data.extend_from_slice(config.as_ref());
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(operator.as_ref());
+    expected_data.extend_from_slice(&version.to_le_bytes());
+    expected_data.extend_from_slice(config_hash);
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected MerchantOperatorConfigInitialized event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_merchant_initialized_event_present(
+    transaction_metadata: &TransactionMetadata,
+    merchant: &Pubkey,
+    authority: &Pubkey,
+    settlement_wallet: &Pubkey,
+    settlement_wallet_timelock_seconds: u64,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(14); // MerchantInitialized discriminator
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(authority.as_ref());
+    expected_data.extend_from_slice(settlement_wallet.as_ref());
+    expected_data.extend_from_slice(&settlement_wallet_timelock_seconds.to_le_bytes());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected MerchantInitialized event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_merchant_settlement_wallet_updated_event_present(
+    transaction_metadata: &TransactionMetadata,
+    merchant: &Pubkey,
+    old_settlement_wallet: &Pubkey,
+    new_settlement_wallet: &Pubkey,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(15); // MerchantSettlementWalletUpdated discriminator
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(old_settlement_wallet.as_ref());
+    expected_data.extend_from_slice(new_settlement_wallet.as_ref());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected MerchantSettlementWalletUpdated event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_settlement_wallet_proposed_event_present(
+    transaction_metadata: &TransactionMetadata,
+    merchant: &Pubkey,
+    settlement_wallet: &Pubkey,
+    pending_settlement_wallet: &Pubkey,
+    unlocks_at: i64,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(23); // SettlementWalletProposed discriminator
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(settlement_wallet.as_ref());
+    expected_data.extend_from_slice(pending_settlement_wallet.as_ref());
+    expected_data.extend_from_slice(&unlocks_at.to_le_bytes());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected SettlementWalletProposed event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_settlement_wallet_timelock_raised_event_present(
+    transaction_metadata: &TransactionMetadata,
+    merchant: &Pubkey,
+    old_timelock_seconds: u64,
+    new_timelock_seconds: u64,
+    pending_settlement_wallet_unlocks_at: i64,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(24); // SettlementWalletTimelockRaised discriminator
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(&old_timelock_seconds.to_le_bytes());
+    expected_data.extend_from_slice(&new_timelock_seconds.to_le_bytes());
+    expected_data.extend_from_slice(&pending_settlement_wallet_unlocks_at.to_le_bytes());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected SettlementWalletTimelockRaised event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
 
-    match instruction.data[0] {
-        0 => "InitializeMerchant",
-        1 => "CreateOperator",
-        2 => "InitializeMerchantOperatorConfig",
-        3 => "MakePayment",
-        4 => "ClearPayment",
-        5 => "RefundPayment",
-        6 => "UpdateMerchantSettlementWallet",
-        7 => "UpdateMerchantAuthority",
-        8 => "UpdateOperatorAuthority",
-        9 => "ClosePayment",
-        228 => "EmitEvent",
-        _ => "Unknown",
+pub fn assert_merchant_authority_updated_event_present(
+    transaction_metadata: &TransactionMetadata,
+    merchant: &Pubkey,
+    old_authority: &Pubkey,
+    new_authority: &Pubkey,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(16); // MerchantAuthorityUpdated discriminator
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(old_authority.as_ref());
+    expected_data.extend_from_slice(new_authority.as_ref());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected MerchantAuthorityUpdated event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_operator_authority_updated_event_present(
+    transaction_metadata: &TransactionMetadata,
+    operator: &Pubkey,
+    old_authority: &Pubkey,
+    new_authority: &Pubkey,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(17); // OperatorAuthorityUpdated discriminator
+    expected_data.extend_from_slice(operator.as_ref());
+    expected_data.extend_from_slice(old_authority.as_ref());
+    expected_data.extend_from_slice(new_authority.as_ref());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected OperatorAuthorityUpdated event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_payment_migrated_event_present(
+    transaction_metadata: &TransactionMetadata,
+    buyer: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    order_id: u32,
+    amount: u64,
+    old_payment: &Pubkey,
+    new_payment: &Pubkey,
+    old_merchant_operator_config: &Pubkey,
+    new_merchant_operator_config: &Pubkey,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(18); // PaymentMigrated discriminator
+    expected_data.extend_from_slice(buyer.as_ref());
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(operator.as_ref());
+    expected_data.extend_from_slice(&order_id.to_le_bytes());
+    expected_data.extend_from_slice(&amount.to_le_bytes());
+    expected_data.extend_from_slice(old_payment.as_ref());
+    expected_data.extend_from_slice(new_payment.as_ref());
+    expected_data.extend_from_slice(old_merchant_operator_config.as_ref());
+    expected_data.extend_from_slice(new_merchant_operator_config.as_ref());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected PaymentMigrated event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_order_status_changed_event_present(
+    transaction_metadata: &TransactionMetadata,
+    buyer: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    order_id: u32,
+    status: OrderStatus,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(8); // OrderStatusChanged discriminator
+    expected_data.extend_from_slice(buyer.as_ref());
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(operator.as_ref());
+    expected_data.extend_from_slice(&order_id.to_le_bytes());
+    expected_data.push(status as u8);
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected OrderStatusChanged event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+/// Lifecycle status matching a Payment account's `status`.
+pub fn lifecycle_status(status: Status) -> PaymentLifecycleStatus {
+    match status {
+        Status::Paid => PaymentLifecycleStatus::Paid,
+        Status::Cleared => PaymentLifecycleStatus::Cleared,
+        Status::Refunded => PaymentLifecycleStatus::Refunded,
+        Status::RefundApproved => PaymentLifecycleStatus::RefundApproved,
+        Status::Authorized => PaymentLifecycleStatus::Authorized,
     }
 }
+
+pub fn assert_webhook_commitment_updated_event_present(
+    transaction_metadata: &TransactionMetadata,
+    merchant_operator_config: &Pubkey,
+    old_commitment: &[u8; 32],
+    new_commitment: &[u8; 32],
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(19); // WebhookCommitmentUpdated discriminator
+    expected_data.extend_from_slice(merchant_operator_config.as_ref());
+    expected_data.extend_from_slice(old_commitment);
+    expected_data.extend_from_slice(new_commitment);
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected WebhookCommitmentUpdated event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_payment_annotated_event_present(
+    transaction_metadata: &TransactionMetadata,
+    payment: &Pubkey,
+    merchant_operator_config: &Pubkey,
+    annotated_by: &Pubkey,
+    flag: AnnotationFlag,
+    note_hash: &[u8; 32],
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(20); // PaymentAnnotated discriminator
+    expected_data.extend_from_slice(payment.as_ref());
+    expected_data.extend_from_slice(merchant_operator_config.as_ref());
+    expected_data.extend_from_slice(annotated_by.as_ref());
+    expected_data.push(flag as u8);
+    expected_data.extend_from_slice(note_hash);
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected PaymentAnnotated event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_config_decommissioned_event_present(
+    transaction_metadata: &TransactionMetadata,
+    merchant_operator_config: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    decommissioned_by: &Pubkey,
+    decommissioned_at: i64,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(21); // ConfigDecommissioned discriminator
+    expected_data.extend_from_slice(merchant_operator_config.as_ref());
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(operator.as_ref());
+    expected_data.extend_from_slice(decommissioned_by.as_ref());
+    expected_data.extend_from_slice(&decommissioned_at.to_le_bytes());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected ConfigDecommissioned event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+pub fn assert_merchant_operator_config_closed_event_present(
+    transaction_metadata: &TransactionMetadata,
+    merchant_operator_config: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    rent_reclaimed: u64,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(22); // MerchantOperatorConfigClosed discriminator
+    expected_data.extend_from_slice(merchant_operator_config.as_ref());
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(operator.as_ref());
+    expected_data.extend_from_slice(&rent_reclaimed.to_le_bytes());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected MerchantOperatorConfigClosed event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn assert_payment_status_changed_event_present(
+    transaction_metadata: &TransactionMetadata,
+    buyer: &Pubkey,
+    merchant: &Pubkey,
+    operator: &Pubkey,
+    order_id: u32,
+    from: PaymentLifecycleStatus,
+    to: PaymentLifecycleStatus,
+    actor: &Pubkey,
+) {
+    let mut expected_data = Vec::new();
+    expected_data.extend_from_slice(&[228, 69, 165, 46, 81, 203, 154, 29]); // EVENT_IX_TAG_LE
+    expected_data.push(10); // PaymentStatusChanged discriminator
+    expected_data.extend_from_slice(buyer.as_ref());
+    expected_data.extend_from_slice(merchant.as_ref());
+    expected_data.extend_from_slice(operator.as_ref());
+    expected_data.extend_from_slice(&order_id.to_le_bytes());
+    expected_data.push(from as u8);
+    expected_data.push(to as u8);
+    expected_data.extend_from_slice(actor.as_ref());
+
+    let event_found = transaction_metadata
+        .inner_instructions
+        .iter()
+        .flatten()
+        .any(|inner_instruction| inner_instruction.instruction.data == expected_data);
+
+    assert!(
+        event_found,
+        "Expected PaymentStatusChanged event not found in transaction. Expected data: {:?}",
+        expected_data
+    );
+}
+
+/// Map instruction discriminator to operation name for profiling
+fn get_operation_name(instruction: &Instruction) -> &'static str {
+    instruction
+        .data
+        .first()
+        .and_then(|discriminator| InstructionDiscriminator::try_from(*discriminator).ok())
+        .map_or("Unknown", InstructionDiscriminator::name)
+}
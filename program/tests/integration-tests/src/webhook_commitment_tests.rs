@@ -0,0 +1,207 @@
+use crate::{
+    state_utils::*,
+    utils::{
+        assert_program_error, assert_webhook_commitment_updated_event_present,
+        find_webhook_commitment_pda, TestContext, TestContextBuilder, DAYS_TO_CLOSE,
+        MERCHANT_MISMATCH_ERROR, MERCHANT_OWNER_MISMATCH_ERROR,
+        WEBHOOK_COMMITMENT_INVALID_PDA_ERROR,
+    },
+};
+use commerce_program_client::{
+    accounts::WebhookCommitment,
+    instructions::SetWebhookCommitmentBuilder,
+    types::FeeType,
+    webhook_commitment::{set_webhook_commitment, webhook_commitment},
+    COMMERCE_PROGRAM_ID,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const URL: &str = "https://shop.example/webhooks";
+
+struct WebhookCommitmentTestSetup {
+    context: TestContext,
+    merchant_authority: Keypair,
+    merchant_pda: Pubkey,
+    merchant_operator_config_pda: Pubkey,
+}
+
+// Helper function to set up a merchant with one config
+fn setup_webhook_commitment_test() -> Result<WebhookCommitmentTestSetup, Box<dyn std::error::Error>>
+{
+    let mut context = TestContextBuilder::new().with_operator().build();
+    let operator_pda = context.operator_pda();
+    let merchant_authority = Keypair::new();
+    let settlement_wallet = Keypair::new();
+
+    let (merchant_pda, _) = assert_get_or_create_merchant(
+        &mut context,
+        &merchant_authority,
+        &settlement_wallet,
+        true,
+        false,
+    )?;
+
+    let (merchant_operator_config_pda, _) = assert_get_or_create_merchant_operator_config(
+        &mut context,
+        &merchant_authority,
+        &merchant_pda,
+        &operator_pda,
+        1u32,
+        0u64,
+        FeeType::Fixed,
+        0u32,
+        DAYS_TO_CLOSE,
+        vec![],
+        vec![],
+        true, // fail_if_exists
+        false,
+    )?;
+
+    Ok(WebhookCommitmentTestSetup {
+        context,
+        merchant_authority,
+        merchant_pda,
+        merchant_operator_config_pda,
+    })
+}
+
+fn get_webhook_commitment(context: &mut TestContext, config: &Pubkey) -> WebhookCommitment {
+    let (webhook_commitment_pda, _) = find_webhook_commitment_pda(config);
+    let account = context.get_account(&webhook_commitment_pda).unwrap();
+    assert_eq!(account.owner, COMMERCE_PROGRAM_ID);
+    WebhookCommitment::from_bytes(&account.data).unwrap()
+}
+
+#[tokio::test]
+async fn test_set_and_update_webhook_commitment() {
+    let mut setup = setup_webhook_commitment_test().unwrap();
+    let config = setup.merchant_operator_config_pda;
+    let first = webhook_commitment("shop", URL);
+
+    let instruction = set_webhook_commitment(
+        &setup.context.payer.pubkey(),
+        &setup.merchant_authority.pubkey(),
+        &config,
+        first,
+    );
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&setup.merchant_authority],
+            false,
+        )
+        .unwrap();
+    assert_webhook_commitment_updated_event_present(&metadata, &config, &[0; 32], &first);
+
+    let account = get_webhook_commitment(&mut setup.context, &config);
+    assert_eq!(account.merchant_operator_config, config);
+    assert_eq!(account.bump, find_webhook_commitment_pda(&config).1);
+    assert_eq!(account.commitment, first);
+
+    // Moving the endpoint replaces the commitment in place
+    let second = webhook_commitment("shop", "https://shop.example/v2/webhooks");
+    let instruction = set_webhook_commitment(
+        &setup.context.payer.pubkey(),
+        &setup.merchant_authority.pubkey(),
+        &config,
+        second,
+    );
+    let metadata = setup
+        .context
+        .send_transaction_with_signers_with_transaction_result(
+            instruction,
+            &[&setup.merchant_authority],
+            false,
+        )
+        .unwrap();
+    assert_webhook_commitment_updated_event_present(&metadata, &config, &first, &second);
+    assert_eq!(
+        get_webhook_commitment(&mut setup.context, &config).commitment,
+        second
+    );
+}
+
+#[tokio::test]
+async fn test_set_webhook_commitment_wrong_authority_fails() {
+    let mut setup = setup_webhook_commitment_test().unwrap();
+    let imposter = Keypair::new();
+    let (webhook_commitment_pda, bump) =
+        find_webhook_commitment_pda(&setup.merchant_operator_config_pda);
+
+    let instruction = SetWebhookCommitmentBuilder::new()
+        .payer(setup.context.payer.pubkey())
+        .authority(imposter.pubkey())
+        .merchant(setup.merchant_pda)
+        .merchant_operator_config(setup.merchant_operator_config_pda)
+        .webhook_commitment(webhook_commitment_pda)
+        .commitment(webhook_commitment(
+            "shop",
+            "https://attacker.example/webhooks",
+        ))
+        .bump(bump)
+        .instruction();
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&imposter]);
+    assert_program_error(result, MERCHANT_OWNER_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_set_webhook_commitment_on_other_merchants_config_fails() {
+    let mut setup = setup_webhook_commitment_test().unwrap();
+    let other_authority = Keypair::new();
+    assert_get_or_create_merchant(
+        &mut setup.context,
+        &other_authority,
+        &Keypair::new(),
+        true,
+        false,
+    )
+    .unwrap();
+
+    let instruction = set_webhook_commitment(
+        &setup.context.payer.pubkey(),
+        &other_authority.pubkey(),
+        &setup.merchant_operator_config_pda,
+        webhook_commitment("shop", "https://attacker.example/webhooks"),
+    );
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&other_authority]);
+    assert_program_error(result, MERCHANT_MISMATCH_ERROR);
+}
+
+#[tokio::test]
+async fn test_set_webhook_commitment_tampered_account_fails() {
+    let mut setup = setup_webhook_commitment_test().unwrap();
+    let config = setup.merchant_operator_config_pda;
+    let (webhook_commitment_pda, bump) = find_webhook_commitment_pda(&config);
+
+    // A program-owned commitment at the PDA recording another config
+    let mut data = vec![18];
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.push(bump);
+    data.extend_from_slice(&[0; 32]);
+    data.extend_from_slice(&0i64.to_le_bytes());
+    setup.context.create_account(
+        &webhook_commitment_pda,
+        &COMMERCE_PROGRAM_ID,
+        data,
+        1_000_000_000,
+    );
+
+    let instruction = set_webhook_commitment(
+        &setup.context.payer.pubkey(),
+        &setup.merchant_authority.pubkey(),
+        &config,
+        webhook_commitment("shop", URL),
+    );
+
+    let result = setup
+        .context
+        .send_transaction_with_signers(instruction, &[&setup.merchant_authority]);
+    assert_program_error(result, WEBHOOK_COMMITMENT_INVALID_PDA_ERROR);
+}
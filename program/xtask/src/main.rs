@@ -0,0 +1,89 @@
+//! `cargo xtask`: development tasks for the Commerce Program workspace.
+//!
+//! ```text
+//! cargo xtask check-budgets
+//! cargo xtask update-baselines
+//! ```
+//!
+//! `check-budgets` builds the program with `cargo-build-sbf` and runs the compute unit and
+//! binary size benchmarks against `tests/integration-tests/budget_baseline.txt`, failing on a
+//! regression past their thresholds.
+//!
+//! `update-baselines` builds the program and rerecords that baseline, for changes that move
+//! compute units or the program size on purpose. Commit the baseline alongside the change.
+//!
+//! Both expect the SPL programs from `make setup-deps` in `tests/integration-tests/deps`.
+
+use std::{
+    env,
+    error::Error,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const BASELINE: &str = "tests/integration-tests/budget_baseline.txt";
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives in the workspace")
+        .to_path_buf()
+}
+
+fn run(command: &mut Command) -> Result<(), Box<dyn Error>> {
+    let status = command.status().map_err(|e| format!("{command:?}: {e}"))?;
+    if !status.success() {
+        return Err(format!("{command:?} failed with {status}").into());
+    }
+    Ok(())
+}
+
+fn build_program(root: &Path) -> Result<(), Box<dyn Error>> {
+    run(Command::new("cargo-build-sbf").current_dir(root))
+}
+
+fn run_benchmarks(root: &Path, update: bool) -> Result<(), Box<dyn Error>> {
+    let tests = root.join("tests/integration-tests");
+    if !tests.join("deps/spl_token.so").exists() {
+        return Err(
+            "missing SPL programs in tests/integration-tests/deps; run make setup-deps".into(),
+        );
+    }
+
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    command.current_dir(&tests).args([
+        "test",
+        "--features",
+        "cu-benchmarks",
+        "cu_benchmark",
+        "--",
+        "--nocapture",
+    ]);
+    if update {
+        command.env("UPDATE_BUDGET_BASELINE", "1");
+    }
+    run(&mut command)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let root = workspace_root();
+    match env::args().nth(1).as_deref() {
+        Some("check-budgets") => {
+            build_program(&root)?;
+            run_benchmarks(&root, false)?;
+        }
+        Some("update-baselines") => {
+            build_program(&root)?;
+            run_benchmarks(&root, true)?;
+            println!("Recorded {BASELINE}");
+        }
+        Some(command) => return Err(format!("unknown task {command}").into()),
+        None => {
+            return Err(
+                "usage: cargo xtask check-budgets\n       cargo xtask update-baselines".into(),
+            )
+        }
+    }
+
+    Ok(())
+}